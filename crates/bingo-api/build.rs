@@ -1,7 +1,7 @@
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    tonic_build::configure()
-        .build_server(true)
-        .build_client(false)
-        .compile_protos(&["../../proto/rules_engine.proto"], &["../../proto/"])?;
+    tonic_build::configure().build_server(true).build_client(false).compile_protos(
+        &["../../proto/rules_engine.proto", "../../proto/grpc/health/v1/health.proto"],
+        &["../../proto/"],
+    )?;
     Ok(())
 }
@@ -0,0 +1,261 @@
+//! Rate limiting and admission control for fact streams
+//!
+//! `AdmissionControl` meters the facts and bytes flowing into the engine with token
+//! buckets: one set shared by the whole server and one set per session. Each bucket
+//! refills at its rate up to its burst size, so short bursts are absorbed while a
+//! sustained overload is refused with `RESOURCE_EXHAUSTED` before it reaches the
+//! engine. The refusal carries `retry-after` (whole seconds) and
+//! `grpc-retry-pushback-ms` metadata saying when the same request would be admitted.
+//!
+//! A request larger than a bucket's burst size is admitted once the bucket is full
+//! and leaves it in debt, so it is slowed down rather than refused forever.
+//!
+//! Limits can be changed while the server runs with `set_limits`, which every handle
+//! sharing the buckets sees.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use tonic::Status;
+use tonic::metadata::MetadataValue;
+use tracing::warn;
+
+/// Sustained rate and burst size of one token bucket
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    /// Units admitted per second on average
+    pub per_second: f64,
+    /// Units that may be admitted at once after a quiet period
+    pub burst: f64,
+}
+
+impl RateLimit {
+    /// `per_second` units a second, with a burst of one second's worth
+    pub fn per_second(per_second: f64) -> Self {
+        Self { per_second, burst: per_second }
+    }
+
+    /// Allow bursts of `burst` units
+    pub fn with_burst(self, burst: f64) -> Self {
+        Self { burst, ..self }
+    }
+}
+
+/// Fact and byte rate limits of one scope, unlimited where `None`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AdmissionLimits {
+    pub facts: Option<RateLimit>,
+    pub bytes: Option<RateLimit>,
+}
+
+impl AdmissionLimits {
+    fn is_unlimited(&self) -> bool {
+        self.facts.is_none() && self.bytes.is_none()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TokenBucket {
+    limit: RateLimit,
+    tokens: f64,
+    refilled: Instant,
+}
+
+impl TokenBucket {
+    fn new(limit: RateLimit, now: Instant) -> Self {
+        Self { limit, tokens: limit.burst, refilled: now }
+    }
+
+    /// How long until `amount` can be taken, zero when it can be taken now
+    fn wait_for(&mut self, amount: f64, now: Instant) -> Duration {
+        let elapsed = now.saturating_duration_since(self.refilled).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.limit.per_second).min(self.limit.burst);
+        self.refilled = now;
+
+        let needed = amount.min(self.limit.burst);
+        if self.tokens >= needed {
+            Duration::ZERO
+        } else if self.limit.per_second > 0.0 {
+            Duration::from_secs_f64((needed - self.tokens) / self.limit.per_second)
+        } else {
+            Duration::MAX
+        }
+    }
+
+    fn take(&mut self, amount: f64) {
+        self.tokens -= amount;
+    }
+
+    fn is_full(&self, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.refilled).as_secs_f64();
+        self.tokens + elapsed * self.limit.per_second >= self.limit.burst
+    }
+}
+
+/// Fact and byte buckets of one scope
+#[derive(Debug, Clone, Default)]
+struct Buckets {
+    facts: Option<TokenBucket>,
+    bytes: Option<TokenBucket>,
+}
+
+impl Buckets {
+    fn new(limits: &AdmissionLimits, now: Instant) -> Self {
+        Self {
+            facts: limits.facts.map(|limit| TokenBucket::new(limit, now)),
+            bytes: limits.bytes.map(|limit| TokenBucket::new(limit, now)),
+        }
+    }
+
+    fn wait_for(&mut self, facts: f64, bytes: f64, now: Instant) -> Duration {
+        let facts = self.facts.as_mut().map_or(Duration::ZERO, |b| b.wait_for(facts, now));
+        let bytes = self.bytes.as_mut().map_or(Duration::ZERO, |b| b.wait_for(bytes, now));
+        facts.max(bytes)
+    }
+
+    fn take(&mut self, facts: f64, bytes: f64) {
+        self.facts.iter_mut().for_each(|bucket| bucket.take(facts));
+        self.bytes.iter_mut().for_each(|bucket| bucket.take(bytes));
+    }
+
+    fn is_full(&self, now: Instant) -> bool {
+        self.facts.iter().chain(&self.bytes).all(|bucket| bucket.is_full(now))
+    }
+}
+
+#[derive(Debug, Default)]
+struct AdmissionState {
+    global_limits: AdmissionLimits,
+    session_limits: AdmissionLimits,
+    global: Buckets,
+    sessions: HashMap<String, Buckets>,
+}
+
+impl AdmissionState {
+    fn new(global_limits: AdmissionLimits, session_limits: AdmissionLimits) -> Self {
+        Self {
+            global_limits,
+            session_limits,
+            global: Buckets::new(&global_limits, Instant::now()),
+            sessions: HashMap::new(),
+        }
+    }
+
+    fn is_enabled(&self) -> bool {
+        !self.global_limits.is_unlimited() || !self.session_limits.is_unlimited()
+    }
+}
+
+/// Sessions whose buckets are kept before full, idle ones are dropped
+const SESSION_BUCKETS_BEFORE_PRUNING: usize = 1024;
+
+/// Token-bucket admission of facts into the engine, globally and per session
+///
+/// Cloning returns a handle sharing the same buckets. The default admits everything.
+#[derive(Debug, Clone, Default)]
+pub struct AdmissionControl {
+    state: Arc<Mutex<AdmissionState>>,
+}
+
+impl AdmissionControl {
+    /// Admission under `global` limits shared by every caller and `session` limits
+    /// applied to each session separately
+    pub fn new(global: AdmissionLimits, session: AdmissionLimits) -> Self {
+        Self { state: Arc::new(Mutex::new(AdmissionState::new(global, session))) }
+    }
+
+    /// Replace the limits, starting every bucket over full under the new ones
+    pub fn set_limits(&self, global: AdmissionLimits, session: AdmissionLimits) {
+        *self.state.lock().unwrap_or_else(PoisonError::into_inner) =
+            AdmissionState::new(global, session);
+    }
+
+    /// Global and per-session limits currently applied
+    pub fn limits(&self) -> (AdmissionLimits, AdmissionLimits) {
+        let state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        (state.global_limits, state.session_limits)
+    }
+
+    /// Whether any limit is configured
+    pub fn is_enabled(&self) -> bool {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner).is_enabled()
+    }
+
+    /// Admit `facts` facts of `bytes` encoded bytes for the session `session_id`, or for
+    /// the server as a whole when `None`
+    ///
+    /// Nothing is taken from any bucket when the request is refused.
+    pub fn admit(
+        &self,
+        session_id: Option<&str>,
+        facts: usize,
+        bytes: usize,
+    ) -> Result<(), Status> {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        if !state.is_enabled() {
+            return Ok(());
+        }
+        let now = Instant::now();
+        let (facts, bytes) = (facts as f64, bytes as f64);
+        let AdmissionState { session_limits, global, sessions, .. } = &mut *state;
+
+        let mut wait = global.wait_for(facts, bytes, now);
+        let session = match session_id.filter(|_| !session_limits.is_unlimited()) {
+            Some(session_id) => {
+                if !sessions.contains_key(session_id)
+                    && sessions.len() >= SESSION_BUCKETS_BEFORE_PRUNING
+                {
+                    sessions.retain(|_, buckets| !buckets.is_full(now));
+                }
+                let buckets = sessions
+                    .entry(session_id.to_string())
+                    .or_insert_with(|| Buckets::new(session_limits, now));
+                wait = wait.max(buckets.wait_for(facts, bytes, now));
+                Some(buckets)
+            }
+            None => None,
+        };
+
+        if !wait.is_zero() {
+            warn!(
+                ?session_id,
+                facts,
+                bytes,
+                retry_after_ms = wait.as_millis() as u64,
+                "Rate limit exceeded"
+            );
+            return Err(resource_exhausted(wait));
+        }
+        global.take(facts, bytes);
+        if let Some(buckets) = session {
+            buckets.take(facts, bytes);
+        }
+        Ok(())
+    }
+
+    /// Drop the buckets of a session that has ended
+    pub fn forget_session(&self, session: &str) {
+        self.state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .sessions
+            .remove(session);
+    }
+}
+
+/// `RESOURCE_EXHAUSTED` telling the caller to retry after `wait`
+fn resource_exhausted(wait: Duration) -> Status {
+    let mut status = Status::resource_exhausted(format!(
+        "Rate limit exceeded, retry after {} ms",
+        wait.as_millis()
+    ));
+    let seconds = wait.as_secs() + u64::from(wait.subsec_nanos() > 0);
+    let metadata = status.metadata_mut();
+    metadata.insert("retry-after", MetadataValue::from(seconds));
+    metadata.insert(
+        "grpc-retry-pushback-ms",
+        MetadataValue::from(wait.as_millis() as u64),
+    );
+    status
+}
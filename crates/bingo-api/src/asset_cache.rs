@@ -0,0 +1,281 @@
+//! Per-tenant cache of compiled rulesets
+//!
+//! Rulesets registered through `RegisterRuleset` are compiled once into an engine and
+//! kept here so `EvaluateRulesetStream` can reuse them. Every tenant gets its own LRU
+//! cache with hit, miss, eviction and size counters, which back the cache admin RPCs
+//! and can be fed into `EnhancedMonitoring` as cache performance metrics.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use bingo_core::BingoEngine;
+use bingo_core::CachePerformanceMetrics;
+use bingo_core::cache::{CacheStats, LruCache};
+use tracing::{debug, info};
+
+/// Tenant used when a request does not name one
+pub const DEFAULT_TENANT: &str = "default";
+
+/// Default number of compiled rulesets kept per tenant
+pub const DEFAULT_CAPACITY_PER_TENANT: usize = 64;
+
+/// A ruleset compiled into a ready-to-use engine
+#[derive(Debug)]
+pub struct CompiledRuleset {
+    pub ruleset_id: String,
+    pub ruleset_hash: String,
+    pub engine: Arc<BingoEngine>,
+    pub rules_compiled: usize,
+    /// Approximate memory held by the compiled engine
+    pub size_bytes: usize,
+    /// Evaluations share the engine, so they run one at a time
+    pub evaluation_lock: Mutex<()>,
+}
+
+impl CompiledRuleset {
+    pub fn new(
+        ruleset_id: String,
+        ruleset_hash: String,
+        engine: Arc<BingoEngine>,
+        rules_compiled: usize,
+    ) -> Self {
+        let size_bytes = engine.get_stats().memory_usage_bytes;
+        Self {
+            ruleset_id,
+            ruleset_hash,
+            engine,
+            rules_compiled,
+            size_bytes,
+            evaluation_lock: Mutex::new(()),
+        }
+    }
+}
+
+/// Cache statistics for a single tenant
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TenantCacheStats {
+    pub tenant_id: String,
+    pub entries: usize,
+    pub capacity: usize,
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    pub bytes: usize,
+    pub ruleset_ids: Vec<String>,
+}
+
+/// What a purge removed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PurgeOutcome {
+    pub entries_purged: usize,
+    pub bytes_freed: usize,
+}
+
+#[derive(Debug)]
+struct TenantCache {
+    entries: LruCache<String, Arc<CompiledRuleset>>,
+}
+
+impl TenantCache {
+    fn bytes(&self) -> usize {
+        self.entries.iter().map(|(_, ruleset)| ruleset.size_bytes).sum()
+    }
+
+    fn stats(&self, tenant_id: &str) -> TenantCacheStats {
+        let cache_stats = self.entries.stats();
+        let mut ruleset_ids: Vec<String> = self.entries.iter().map(|(id, _)| id.clone()).collect();
+        ruleset_ids.sort();
+
+        TenantCacheStats {
+            tenant_id: tenant_id.to_string(),
+            entries: cache_stats.size,
+            capacity: cache_stats.capacity,
+            hits: cache_stats.hits,
+            misses: cache_stats.misses,
+            evictions: cache_stats.evictions,
+            bytes: self.bytes(),
+            ruleset_ids,
+        }
+    }
+}
+
+/// Compiled rulesets grouped by tenant
+#[derive(Debug)]
+pub struct CompiledAssetCache {
+    capacity_per_tenant: usize,
+    tenants: Mutex<HashMap<String, TenantCache>>,
+}
+
+impl CompiledAssetCache {
+    pub fn new(capacity_per_tenant: usize) -> Self {
+        Self { capacity_per_tenant, tenants: Mutex::new(HashMap::new()) }
+    }
+
+    /// Tenant ID to use for a request, falling back to the default tenant
+    pub fn tenant_or_default(tenant_id: &str) -> &str {
+        if tenant_id.is_empty() {
+            DEFAULT_TENANT
+        } else {
+            tenant_id
+        }
+    }
+
+    /// Cache a compiled ruleset, replacing any earlier version with the same ID
+    pub fn insert(&self, tenant_id: &str, ruleset: CompiledRuleset) -> Arc<CompiledRuleset> {
+        let ruleset = Arc::new(ruleset);
+        let mut tenants = self.tenants.lock().unwrap();
+        let tenant = tenants
+            .entry(tenant_id.to_string())
+            .or_insert_with(|| TenantCache { entries: LruCache::new(self.capacity_per_tenant) });
+        tenant.entries.put(ruleset.ruleset_id.clone(), ruleset.clone());
+
+        info!(
+            tenant_id = %tenant_id,
+            ruleset_id = %ruleset.ruleset_id,
+            size_bytes = ruleset.size_bytes,
+            "Cached compiled ruleset"
+        );
+        ruleset
+    }
+
+    /// Look up a compiled ruleset, counting the lookup as a hit or miss
+    pub fn get(&self, tenant_id: &str, ruleset_id: &str) -> Option<Arc<CompiledRuleset>> {
+        let mut tenants = self.tenants.lock().unwrap();
+        let tenant = tenants
+            .entry(tenant_id.to_string())
+            .or_insert_with(|| TenantCache { entries: LruCache::new(self.capacity_per_tenant) });
+        let ruleset = tenant.entries.get(&ruleset_id.to_string()).cloned();
+
+        debug!(
+            tenant_id = %tenant_id,
+            ruleset_id = %ruleset_id,
+            hit = ruleset.is_some(),
+            "Compiled ruleset lookup"
+        );
+        ruleset
+    }
+
+    /// Statistics for one tenant, or for every tenant when `tenant_id` is `None`
+    pub fn stats(&self, tenant_id: Option<&str>) -> Vec<TenantCacheStats> {
+        let tenants = self.tenants.lock().unwrap();
+        let mut stats: Vec<TenantCacheStats> = tenants
+            .iter()
+            .filter(|(id, _)| tenant_id.is_none_or(|wanted| wanted == id.as_str()))
+            .map(|(id, tenant)| tenant.stats(id))
+            .collect();
+        stats.sort_by(|a, b| a.tenant_id.cmp(&b.tenant_id));
+        stats
+    }
+
+    /// Remove cached rulesets
+    ///
+    /// `tenant_id` of `None` purges every tenant; `ruleset_id` of `None` purges every
+    /// ruleset of the selected tenants. Counters are kept so metrics stay cumulative.
+    pub fn purge(&self, tenant_id: Option<&str>, ruleset_id: Option<&str>) -> PurgeOutcome {
+        let mut tenants = self.tenants.lock().unwrap();
+        let mut outcome = PurgeOutcome::default();
+
+        for (id, tenant) in tenants.iter_mut() {
+            if tenant_id.is_some_and(|wanted| wanted != id.as_str()) {
+                continue;
+            }
+
+            match ruleset_id {
+                Some(ruleset_id) => {
+                    if let Some(ruleset) = tenant.entries.remove(&ruleset_id.to_string()) {
+                        outcome.entries_purged += 1;
+                        outcome.bytes_freed += ruleset.size_bytes;
+                    }
+                }
+                None => {
+                    outcome.entries_purged += tenant.entries.len();
+                    outcome.bytes_freed += tenant.bytes();
+                    tenant.entries.clear();
+                }
+            }
+        }
+
+        info!(
+            tenant_id = ?tenant_id,
+            ruleset_id = ?ruleset_id,
+            entries_purged = outcome.entries_purged,
+            bytes_freed = outcome.bytes_freed,
+            "Purged compiled ruleset cache"
+        );
+        outcome
+    }
+
+    /// Cache counters across all tenants, ready to record with `EnhancedMonitoring`
+    pub fn performance_metrics(&self) -> CachePerformanceMetrics {
+        let tenants = self.tenants.lock().unwrap();
+        let mut metrics = CachePerformanceMetrics::default();
+        for tenant in tenants.values() {
+            // Sizes come from the compiled engines rather than the per-entry estimate
+            metrics.accumulate(&CacheStats { size: 0, ..tenant.entries.stats() });
+            metrics.cache_bytes += tenant.bytes() as u64;
+        }
+        metrics.rule_cache_hit_rate = metrics.overall_hit_rate();
+        metrics
+    }
+}
+
+impl Default for CompiledAssetCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY_PER_TENANT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compiled(ruleset_id: &str) -> CompiledRuleset {
+        let engine = Arc::new(BingoEngine::new().unwrap());
+        CompiledRuleset::new(
+            ruleset_id.to_string(),
+            format!("hash-{ruleset_id}"),
+            engine,
+            0,
+        )
+    }
+
+    #[test]
+    fn test_tenants_are_isolated() {
+        let cache = CompiledAssetCache::new(4);
+        cache.insert("acme", compiled("payroll"));
+
+        assert!(cache.get("acme", "payroll").is_some());
+        assert!(cache.get("globex", "payroll").is_none());
+
+        let stats = cache.stats(None);
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].tenant_id, "acme");
+        assert_eq!((stats[0].hits, stats[0].misses), (1, 0));
+        assert_eq!((stats[1].hits, stats[1].misses), (0, 1));
+    }
+
+    #[test]
+    fn test_evictions_are_counted_per_tenant() {
+        let cache = CompiledAssetCache::new(1);
+        cache.insert("acme", compiled("a"));
+        cache.insert("acme", compiled("b"));
+
+        let stats = cache.stats(Some("acme"));
+        assert_eq!(stats[0].entries, 1);
+        assert_eq!(stats[0].evictions, 1);
+        assert_eq!(stats[0].ruleset_ids, vec!["b".to_string()]);
+    }
+
+    #[test]
+    fn test_purge_by_ruleset_and_tenant() {
+        let cache = CompiledAssetCache::new(4);
+        cache.insert("acme", compiled("a"));
+        cache.insert("acme", compiled("b"));
+        cache.insert("globex", compiled("a"));
+
+        assert_eq!(cache.purge(Some("acme"), Some("a")).entries_purged, 1);
+        assert_eq!(cache.purge(Some("acme"), Some("a")).entries_purged, 0);
+        assert_eq!(cache.purge(None, None).entries_purged, 2);
+        assert!(cache.stats(None).iter().all(|tenant| tenant.entries == 0));
+    }
+}
@@ -0,0 +1,316 @@
+//! Authentication and authorization for the gRPC API
+//!
+//! `AuthInterceptor` is a tonic interceptor that identifies the caller of every
+//! request through pluggable authenticators: API keys in `x-api-key`, HS256 JSON Web
+//! Tokens in `authorization: Bearer`, or the client certificate of a mutual TLS
+//! connection. The caller's `Identity` is attached to the request, and each RPC
+//! checks it with `authorize` against the permission the operation needs.
+//!
+//! Rule admins may change rules, rulesets, globals and reference tables; evaluators
+//! may only evaluate facts and read. Requests without an identity are let through, so
+//! a server without the interceptor is unauthenticated as before.
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use ring::{digest, hmac};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tonic::metadata::MetadataMap;
+use tonic::service::Interceptor;
+use tonic::{Request, Status};
+use tracing::warn;
+
+/// What a caller may do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Role {
+    /// Compile, change and delete rules, and everything an evaluator may do
+    RuleAdmin,
+    /// Evaluate facts and read rules, statistics and session state
+    Evaluator,
+}
+
+impl Role {
+    /// Parse `admin`, `rule-admin`, `evaluator` or `evaluate`
+    pub fn parse(role: &str) -> Option<Self> {
+        match role.trim().to_ascii_lowercase().as_str() {
+            "admin" | "rule-admin" | "rule_admin" => Some(Self::RuleAdmin),
+            "evaluator" | "evaluate" => Some(Self::Evaluator),
+            _ => None,
+        }
+    }
+
+    /// Whether the role grants `permission`
+    pub fn allows(self, permission: Permission) -> bool {
+        match self {
+            Self::RuleAdmin => true,
+            Self::Evaluator => permission == Permission::Evaluate,
+        }
+    }
+}
+
+/// What an RPC needs its caller to be allowed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Permission {
+    /// Change rules, rulesets, session globals, reference tables or the cache
+    ManageRules,
+    /// Evaluate facts and read engine state
+    Evaluate,
+}
+
+/// Authenticated caller, attached to the request by `AuthInterceptor`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Identity {
+    /// Who the caller is, e.g. a service account or certificate name
+    pub subject: String,
+    pub role: Role,
+}
+
+impl Identity {
+    pub fn new(subject: impl Into<String>, role: Role) -> Self {
+        Self { subject: subject.into(), role }
+    }
+}
+
+/// Check that the caller of `request` holds `permission`
+///
+/// Requests that passed no `AuthInterceptor` carry no identity and are allowed.
+pub fn authorize<T>(request: &Request<T>, permission: Permission) -> Result<(), Status> {
+    match request.extensions().get::<Identity>() {
+        Some(identity) if !identity.role.allows(permission) => {
+            warn!(subject = %identity.subject, ?permission, "Permission denied");
+            Err(Status::permission_denied(format!(
+                "'{}' is not allowed to {}",
+                identity.subject,
+                match permission {
+                    Permission::ManageRules => "manage rules",
+                    Permission::Evaluate => "evaluate facts",
+                }
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// A way of proving who the caller is
+pub trait Authenticator: Send + Sync {
+    /// The caller proven by the request's credentials
+    ///
+    /// Returns `None` when the request carries no credentials of this kind, and an
+    /// `unauthenticated` status when it carries invalid ones.
+    fn authenticate(&self, request: &Request<()>) -> Result<Option<Identity>, Status>;
+}
+
+/// Static API keys sent in the `x-api-key` header
+#[derive(Default)]
+pub struct ApiKeyAuthenticator {
+    keys: HashMap<String, Identity>,
+}
+
+impl ApiKeyAuthenticator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accept `key` as proof of `identity`
+    pub fn with_key(mut self, key: impl Into<String>, identity: Identity) -> Self {
+        self.keys.insert(key.into(), identity);
+        self
+    }
+
+    /// Parse comma-separated `key:subject:role` entries
+    pub fn parse(entries: &str) -> anyhow::Result<Self> {
+        entries.split(',').filter(|entry| !entry.trim().is_empty()).try_fold(
+            Self::new(),
+            |keys, entry| {
+                let mut parts = entry.trim().splitn(3, ':');
+                let (Some(key), Some(subject), Some(role)) =
+                    (parts.next(), parts.next(), parts.next())
+                else {
+                    anyhow::bail!("API key entries must be key:subject:role");
+                };
+                let role = Role::parse(role)
+                    .ok_or_else(|| anyhow::anyhow!("Unknown role '{role}' for '{subject}'"))?;
+                Ok(keys.with_key(key, Identity::new(subject, role)))
+            },
+        )
+    }
+}
+
+impl Authenticator for ApiKeyAuthenticator {
+    fn authenticate(&self, request: &Request<()>) -> Result<Option<Identity>, Status> {
+        let Some(key) = request.metadata().get("x-api-key") else {
+            return Ok(None);
+        };
+        key.to_str()
+            .ok()
+            .and_then(|key| self.keys.get(key))
+            .cloned()
+            .map(Some)
+            .ok_or_else(|| Status::unauthenticated("Unknown API key"))
+    }
+}
+
+/// HS256 JSON Web Tokens sent as `authorization: Bearer <token>`
+///
+/// The token's `sub` claim names the caller and its `role` claim grants the role.
+/// Tokens past their `exp` claim are refused.
+pub struct JwtAuthenticator {
+    key: hmac::Key,
+}
+
+impl JwtAuthenticator {
+    /// Verify tokens signed with the shared `secret`
+    pub fn hs256(secret: &[u8]) -> Self {
+        Self { key: hmac::Key::new(hmac::HMAC_SHA256, secret) }
+    }
+
+    fn verify(&self, token: &str) -> Result<Identity, String> {
+        let mut parts = token.split('.');
+        let (Some(header), Some(claims), Some(signature), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err("Malformed token".to_string());
+        };
+
+        let signed = &token[..token.len() - signature.len() - 1];
+        let header: serde_json::Value = decode_segment(header)?;
+        if header["alg"] != "HS256" {
+            return Err(format!("Unsupported token algorithm {}", header["alg"]));
+        }
+        let signature =
+            URL_SAFE_NO_PAD.decode(signature).map_err(|_| "Malformed token signature")?;
+        hmac::verify(&self.key, signed.as_bytes(), &signature)
+            .map_err(|_| "Invalid token signature")?;
+
+        let claims: serde_json::Value = decode_segment(claims)?;
+        if let Some(expiry) = claims["exp"].as_i64()
+            && expiry <= chrono::Utc::now().timestamp()
+        {
+            return Err("Token has expired".to_string());
+        }
+        let subject = claims["sub"].as_str().ok_or("Token has no subject")?;
+        let role = claims["role"]
+            .as_str()
+            .and_then(Role::parse)
+            .ok_or_else(|| format!("Token for '{subject}' grants no known role"))?;
+        Ok(Identity::new(subject, role))
+    }
+}
+
+fn decode_segment(segment: &str) -> Result<serde_json::Value, String> {
+    let bytes = URL_SAFE_NO_PAD.decode(segment).map_err(|_| "Malformed token")?;
+    serde_json::from_slice(&bytes).map_err(|_| "Malformed token".to_string())
+}
+
+impl Authenticator for JwtAuthenticator {
+    fn authenticate(&self, request: &Request<()>) -> Result<Option<Identity>, Status> {
+        let Some(token) = bearer_token(request.metadata()) else {
+            return Ok(None);
+        };
+        self.verify(token).map(Some).map_err(Status::unauthenticated)
+    }
+}
+
+fn bearer_token(metadata: &MetadataMap) -> Option<&str> {
+    metadata.get("authorization")?.to_str().ok()?.strip_prefix("Bearer ")
+}
+
+/// Client certificates of mutual TLS connections, identified by SHA-256 fingerprint
+///
+/// Only requests on TLS connections with client authentication carry certificates;
+/// the server must be built with a `ServerTlsConfig` that requires them.
+#[derive(Default)]
+pub struct MtlsAuthenticator {
+    fingerprints: HashMap<String, Identity>,
+}
+
+impl MtlsAuthenticator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accept the certificate with the hex SHA-256 `fingerprint` as proof of `identity`
+    pub fn with_certificate(mut self, fingerprint: &str, identity: Identity) -> Self {
+        let fingerprint = fingerprint.replace(':', "").to_ascii_lowercase();
+        self.fingerprints.insert(fingerprint, identity);
+        self
+    }
+
+    /// The identity of the DER-encoded client certificate
+    pub fn identify(&self, certificate: &[u8]) -> Option<&Identity> {
+        let fingerprint = digest::digest(&digest::SHA256, certificate);
+        let hex: String = fingerprint.as_ref().iter().map(|byte| format!("{byte:02x}")).collect();
+        self.fingerprints.get(&hex)
+    }
+}
+
+impl Authenticator for MtlsAuthenticator {
+    fn authenticate(&self, request: &Request<()>) -> Result<Option<Identity>, Status> {
+        let Some(certificates) = request.peer_certs() else {
+            return Ok(None);
+        };
+        let Some(certificate) = certificates.first() else {
+            return Ok(None);
+        };
+        self.identify(certificate.as_ref())
+            .cloned()
+            .map(Some)
+            .ok_or_else(|| Status::unauthenticated("Unknown client certificate"))
+    }
+}
+
+/// Tonic interceptor attaching the caller's `Identity` to every request
+///
+/// Authenticators are tried in the order they were added, and the first one finding
+/// credentials decides. Requests without any credentials are refused.
+#[derive(Clone, Default)]
+pub struct AuthInterceptor {
+    authenticators: Vec<Arc<dyn Authenticator>>,
+}
+
+impl AuthInterceptor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also accept credentials checked by `authenticator`
+    pub fn with(mut self, authenticator: impl Authenticator + 'static) -> Self {
+        self.authenticators.push(Arc::new(authenticator));
+        self
+    }
+
+    /// Interceptor configured from `BINGO_API_KEYS` and `BINGO_JWT_SECRET`
+    ///
+    /// `BINGO_API_KEYS` holds comma-separated `key:subject:role` entries and
+    /// `BINGO_JWT_SECRET` the HS256 secret tokens are signed with. Returns `None` when
+    /// neither is set.
+    pub fn from_environment() -> anyhow::Result<Option<Self>> {
+        let mut interceptor = Self::new();
+        if let Ok(keys) = std::env::var("BINGO_API_KEYS") {
+            interceptor = interceptor.with(ApiKeyAuthenticator::parse(&keys)?);
+        }
+        if let Ok(secret) = std::env::var("BINGO_JWT_SECRET") {
+            interceptor = interceptor.with(JwtAuthenticator::hs256(secret.as_bytes()));
+        }
+        Ok((!interceptor.authenticators.is_empty()).then_some(interceptor))
+    }
+}
+
+impl Interceptor for AuthInterceptor {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        for authenticator in &self.authenticators {
+            match authenticator.authenticate(&request) {
+                Ok(Some(identity)) => {
+                    request.extensions_mut().insert(identity);
+                    return Ok(request);
+                }
+                Ok(None) => {}
+                Err(status) => {
+                    warn!(reason = status.message(), "Authentication failed");
+                    return Err(status);
+                }
+            }
+        }
+        Err(Status::unauthenticated("Credentials are required"))
+    }
+}
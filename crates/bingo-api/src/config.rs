@@ -0,0 +1,591 @@
+//! Typed server configuration from a file and the environment
+//!
+//! `ServerConfig` gathers the server's settings: listen address and thread counts,
+//! fact rate limits, session limits and eviction, the engine pool, shutdown and
+//! tracing. `ServerConfig::load` starts from the defaults, applies the TOML or YAML
+//! file named by `BINGO_CONFIG` if there is one, then the `BINGO_*` environment
+//! variables, so the environment wins over the file.
+//!
+//! `ConfigWatcher` polls the file and applies the settings that can change while the
+//! server runs: `limits` and `sessions`. The other sections take effect on restart.
+
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use bingo_core::{EngineConfig, EvictionPolicy};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::AppState;
+use crate::admission::{AdmissionLimits, RateLimit};
+use crate::engine_pool::EnginePoolConfig;
+use crate::shutdown::ShutdownConfig;
+use crate::tracing_setup::TracingConfig;
+
+/// Environment variable naming the configuration file
+pub const CONFIG_PATH_VAR: &str = "BINGO_CONFIG";
+
+/// How often `ConfigWatcher` checks the file for changes by default
+pub const DEFAULT_RELOAD_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Settings of the whole server
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    pub server: ServerSettings,
+    pub limits: LimitSettings,
+    pub sessions: SessionSettings,
+    pub engine_pool: EnginePoolSettings,
+    pub shutdown: ShutdownSettings,
+    pub tracing: TracingSettings,
+}
+
+/// Where the server listens and the threads it runs on; restart to change
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerSettings {
+    pub listen_address: String,
+    /// Threads serving requests, one per CPU when unset
+    pub worker_threads: Option<usize>,
+    /// Threads evaluating facts in parallel, one per CPU when unset
+    pub compute_threads: Option<usize>,
+}
+
+impl Default for ServerSettings {
+    fn default() -> Self {
+        Self {
+            listen_address: "0.0.0.0:50051".to_string(),
+            worker_threads: None,
+            compute_threads: None,
+        }
+    }
+}
+
+/// Fact rate limits, unlimited where unset; reloadable
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LimitSettings {
+    pub global_facts_per_sec: Option<f64>,
+    pub global_bytes_per_sec: Option<f64>,
+    pub session_facts_per_sec: Option<f64>,
+    pub session_bytes_per_sec: Option<f64>,
+    /// Seconds' worth of each rate a burst may use
+    pub burst_seconds: f64,
+}
+
+impl Default for LimitSettings {
+    fn default() -> Self {
+        Self {
+            global_facts_per_sec: None,
+            global_bytes_per_sec: None,
+            session_facts_per_sec: None,
+            session_bytes_per_sec: None,
+            burst_seconds: 1.0,
+        }
+    }
+}
+
+impl LimitSettings {
+    /// Global and per-session admission limits
+    pub fn admission_limits(&self) -> (AdmissionLimits, AdmissionLimits) {
+        let limit = |rate: Option<f64>| {
+            rate.map(|rate| RateLimit::per_second(rate).with_burst(rate * self.burst_seconds))
+        };
+        (
+            AdmissionLimits {
+                facts: limit(self.global_facts_per_sec),
+                bytes: limit(self.global_bytes_per_sec),
+            },
+            AdmissionLimits {
+                facts: limit(self.session_facts_per_sec),
+                bytes: limit(self.session_bytes_per_sec),
+            },
+        )
+    }
+}
+
+/// Which facts sessions evict to stay within their limits
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Eviction {
+    #[default]
+    Reject,
+    OldestFirst,
+    /// Facts older than `eviction_ttl_seconds`
+    Ttl,
+}
+
+impl FromStr for Eviction {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> anyhow::Result<Self> {
+        match value {
+            "reject" => Ok(Self::Reject),
+            "oldest_first" => Ok(Self::OldestFirst),
+            "ttl" => Ok(Self::Ttl),
+            _ => anyhow::bail!("expected reject, oldest_first or ttl"),
+        }
+    }
+}
+
+/// Lifetime and working memory limits of sessions; reloadable
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SessionSettings {
+    /// Sessions unused for this long are closed, never when unset
+    pub ttl_seconds: Option<u64>,
+    pub max_working_memory_facts: Option<usize>,
+    pub max_tokens: Option<usize>,
+    pub eviction: Eviction,
+    pub eviction_ttl_seconds: Option<u64>,
+}
+
+impl SessionSettings {
+    /// Time after which an unused session is closed
+    pub fn ttl(&self) -> Option<Duration> {
+        self.ttl_seconds.map(Duration::from_secs)
+    }
+
+    /// Capacity limits of each session's engine
+    pub fn engine_config(&self) -> anyhow::Result<EngineConfig> {
+        let eviction = match self.eviction {
+            Eviction::Reject => EvictionPolicy::Reject,
+            Eviction::OldestFirst => EvictionPolicy::OldestFirst,
+            Eviction::Ttl => {
+                let Some(seconds) = self.eviction_ttl_seconds else {
+                    anyhow::bail!("sessions.eviction_ttl_seconds is required with ttl eviction");
+                };
+                EvictionPolicy::Ttl(chrono::Duration::seconds(seconds as i64))
+            }
+        };
+        let config = EngineConfig {
+            max_working_memory_facts: self.max_working_memory_facts,
+            max_tokens: self.max_tokens,
+            eviction,
+        };
+        config
+            .validate()
+            .map_err(|e| anyhow::anyhow!("Invalid session limits: {}", e))?;
+        Ok(config)
+    }
+}
+
+/// Idle engines kept ready; restart to change
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EnginePoolSettings {
+    pub min_idle: usize,
+    pub max_idle: usize,
+}
+
+impl Default for EnginePoolSettings {
+    fn default() -> Self {
+        let EnginePoolConfig { min_idle, max_idle } = EnginePoolConfig::default();
+        Self { min_idle, max_idle }
+    }
+}
+
+/// Draining and session persistence on shutdown; restart to change
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ShutdownSettings {
+    pub drain_seconds: f64,
+    pub snapshot_dir: Option<PathBuf>,
+}
+
+impl Default for ShutdownSettings {
+    fn default() -> Self {
+        let ShutdownConfig { drain_timeout, snapshot_dir } = ShutdownConfig::default();
+        Self { drain_seconds: drain_timeout.as_secs_f64(), snapshot_dir }
+    }
+}
+
+/// Service identity in logs and spans; restart to change
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TracingSettings {
+    pub service_name: String,
+    pub service_version: String,
+    pub environment: String,
+    pub rule_firing_spans: bool,
+}
+
+impl Default for TracingSettings {
+    fn default() -> Self {
+        let TracingConfig { service_name, service_version, environment, rule_firing_spans } =
+            TracingConfig::default();
+        Self { service_name, service_version, environment, rule_firing_spans }
+    }
+}
+
+impl ServerConfig {
+    /// Defaults, overridden by the file named by `BINGO_CONFIG`, then the environment
+    pub fn load() -> anyhow::Result<Self> {
+        let path = std::env::var_os(CONFIG_PATH_VAR).filter(|path| !path.is_empty());
+        Self::load_from(path.as_deref().map(Path::new), |name| {
+            std::env::var(name).ok()
+        })
+    }
+
+    /// Defaults, overridden by the file at `path` if given, then by the variables
+    /// `var` looks up
+    pub fn load_from(
+        path: Option<&Path>,
+        var: impl Fn(&str) -> Option<String>,
+    ) -> anyhow::Result<Self> {
+        let mut config = match path {
+            Some(path) => Self::from_file(path)?,
+            None => Self::default(),
+        };
+        config.apply_environment(var)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Settings of a TOML or YAML file, chosen by its extension
+    pub fn from_file(path: &Path) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read config {}: {}", path.display(), e))?;
+        let parsed = match path.extension().and_then(|extension| extension.to_str()) {
+            Some("toml") => toml::from_str(&text).map_err(anyhow::Error::from),
+            Some("yaml" | "yml") => serde_yaml::from_str(&text).map_err(anyhow::Error::from),
+            _ => anyhow::bail!(
+                "Config {} must be a .toml, .yaml or .yml file",
+                path.display()
+            ),
+        };
+        parsed.map_err(|e| anyhow::anyhow!("Invalid config {}: {}", path.display(), e))
+    }
+
+    /// Override settings with the environment variables `var` looks up
+    fn apply_environment(&mut self, var: impl Fn(&str) -> Option<String>) -> anyhow::Result<()> {
+        let var = &var;
+        if let Some(address) = var("GRPC_LISTEN_ADDRESS") {
+            self.server.listen_address = address;
+        }
+        set(&mut self.server.worker_threads, var, "BINGO_WORKER_THREADS")?;
+        set(
+            &mut self.server.compute_threads,
+            var,
+            "BINGO_COMPUTE_THREADS",
+        )?;
+
+        let limits = &mut self.limits;
+        set(
+            &mut limits.global_facts_per_sec,
+            var,
+            "BINGO_GLOBAL_FACTS_PER_SEC",
+        )?;
+        set(
+            &mut limits.global_bytes_per_sec,
+            var,
+            "BINGO_GLOBAL_BYTES_PER_SEC",
+        )?;
+        set(
+            &mut limits.session_facts_per_sec,
+            var,
+            "BINGO_SESSION_FACTS_PER_SEC",
+        )?;
+        set(
+            &mut limits.session_bytes_per_sec,
+            var,
+            "BINGO_SESSION_BYTES_PER_SEC",
+        )?;
+        if let Some(seconds) = parse(var, "BINGO_RATE_LIMIT_BURST_SECONDS")? {
+            limits.burst_seconds = seconds;
+        }
+
+        let sessions = &mut self.sessions;
+        set(&mut sessions.ttl_seconds, var, "BINGO_SESSION_TTL_SECONDS")?;
+        set(
+            &mut sessions.max_working_memory_facts,
+            var,
+            "BINGO_MAX_WORKING_MEMORY_FACTS",
+        )?;
+        set(&mut sessions.max_tokens, var, "BINGO_MAX_TOKENS")?;
+        if let Some(eviction) = parse(var, "BINGO_EVICTION")? {
+            sessions.eviction = eviction;
+        }
+        set(
+            &mut sessions.eviction_ttl_seconds,
+            var,
+            "BINGO_EVICTION_TTL_SECONDS",
+        )?;
+
+        if let Some(min_idle) = parse(var, "BINGO_ENGINE_POOL_MIN")? {
+            self.engine_pool.min_idle = min_idle;
+        }
+        if let Some(max_idle) = parse(var, "BINGO_ENGINE_POOL_MAX")? {
+            self.engine_pool.max_idle = max_idle;
+        }
+
+        if let Some(seconds) = parse(var, "BINGO_SHUTDOWN_DRAIN_SECONDS")? {
+            self.shutdown.drain_seconds = seconds;
+        }
+        if let Some(dir) = var("BINGO_SNAPSHOT_DIR").filter(|dir| !dir.is_empty()) {
+            self.shutdown.snapshot_dir = Some(PathBuf::from(dir));
+        }
+
+        let tracing = &mut self.tracing;
+        if let Some(name) = var("SERVICE_NAME") {
+            tracing.service_name = name;
+        }
+        if let Some(version) = var("SERVICE_VERSION") {
+            tracing.service_version = version;
+        }
+        if let Some(environment) = var("BINGO_ENVIRONMENT") {
+            tracing.environment = environment;
+        }
+        if let Some(spans) = var("BINGO_RULE_FIRING_SPANS") {
+            tracing.rule_firing_spans = spans == "true" || spans == "1";
+        }
+        Ok(())
+    }
+
+    /// Reject settings the server can't run with
+    pub fn validate(&self) -> anyhow::Result<()> {
+        for (setting, threads) in [
+            ("server.worker_threads", self.server.worker_threads),
+            ("server.compute_threads", self.server.compute_threads),
+        ] {
+            if threads == Some(0) {
+                anyhow::bail!("{setting} must be at least 1");
+            }
+        }
+
+        let limits = &self.limits;
+        for (setting, rate) in [
+            ("limits.global_facts_per_sec", limits.global_facts_per_sec),
+            ("limits.global_bytes_per_sec", limits.global_bytes_per_sec),
+            ("limits.session_facts_per_sec", limits.session_facts_per_sec),
+            ("limits.session_bytes_per_sec", limits.session_bytes_per_sec),
+            ("limits.burst_seconds", Some(limits.burst_seconds)),
+        ] {
+            if let Some(rate) = rate
+                && !(rate.is_finite() && rate > 0.0)
+            {
+                anyhow::bail!("{setting} must be a positive number, got {rate}");
+            }
+        }
+
+        if self.sessions.ttl_seconds == Some(0) {
+            anyhow::bail!("sessions.ttl_seconds must be at least 1");
+        }
+        self.sessions.engine_config()?;
+
+        let pool = &self.engine_pool;
+        if pool.min_idle > pool.max_idle {
+            anyhow::bail!(
+                "engine_pool.min_idle ({}) cannot exceed engine_pool.max_idle ({})",
+                pool.min_idle,
+                pool.max_idle
+            );
+        }
+
+        let drain_seconds = self.shutdown.drain_seconds;
+        if !(drain_seconds.is_finite() && drain_seconds >= 0.0) {
+            anyhow::bail!("shutdown.drain_seconds must be a non-negative number");
+        }
+        Ok(())
+    }
+
+    pub fn engine_pool(&self) -> EnginePoolConfig {
+        EnginePoolConfig {
+            min_idle: self.engine_pool.min_idle,
+            max_idle: self.engine_pool.max_idle,
+        }
+    }
+
+    pub fn shutdown(&self) -> ShutdownConfig {
+        ShutdownConfig {
+            drain_timeout: Duration::from_secs_f64(self.shutdown.drain_seconds),
+            snapshot_dir: self.shutdown.snapshot_dir.clone(),
+        }
+    }
+
+    pub fn tracing(&self) -> TracingConfig {
+        let settings = self.tracing.clone();
+        TracingConfig {
+            service_name: settings.service_name,
+            service_version: settings.service_version,
+            environment: settings.environment,
+            rule_firing_spans: settings.rule_firing_spans,
+        }
+    }
+
+    /// Names of the sections that differ from `other` but only change on restart
+    pub fn restart_required(&self, other: &Self) -> Vec<&'static str> {
+        let mut sections = Vec::new();
+        if self.server != other.server {
+            sections.push("server");
+        }
+        if self.engine_pool != other.engine_pool {
+            sections.push("engine_pool");
+        }
+        if self.shutdown != other.shutdown {
+            sections.push("shutdown");
+        }
+        if self.tracing != other.tracing {
+            sections.push("tracing");
+        }
+        sections
+    }
+}
+
+/// Parse the variable `name`, if set
+fn parse<T: FromStr>(var: &impl Fn(&str) -> Option<String>, name: &str) -> anyhow::Result<Option<T>>
+where
+    T::Err: std::fmt::Display,
+{
+    match var(name) {
+        Some(value) => match value.trim().parse() {
+            Ok(parsed) => Ok(Some(parsed)),
+            Err(e) => anyhow::bail!("{name} is invalid, got '{value}': {e}"),
+        },
+        None => Ok(None),
+    }
+}
+
+/// Set an optional setting from the variable `name`, if set
+fn set<T: FromStr>(
+    setting: &mut Option<T>,
+    var: &impl Fn(&str) -> Option<String>,
+    name: &str,
+) -> anyhow::Result<()>
+where
+    T::Err: std::fmt::Display,
+{
+    if let Some(value) = parse(var, name)? {
+        *setting = Some(value);
+    }
+    Ok(())
+}
+
+/// Reloads the configuration file when it changes and applies it to the server
+#[derive(Debug)]
+pub struct ConfigWatcher {
+    path: PathBuf,
+    interval: Duration,
+}
+
+impl ConfigWatcher {
+    /// Watch the file at `path`, checking it every 5 seconds
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into(), interval: DEFAULT_RELOAD_INTERVAL }
+    }
+
+    /// Check the file every `interval` rather than every 5 seconds
+    pub fn with_interval(self, interval: Duration) -> Self {
+        Self { interval, ..self }
+    }
+
+    /// Apply the file to `app_state` whenever its modification time changes
+    ///
+    /// A file that fails to load or validate is reported and the running settings
+    /// are kept. The task ends when the returned handle is aborted.
+    pub fn spawn(self, app_state: Arc<AppState>) -> tokio::task::JoinHandle<()> {
+        // The file as it is now counts as applied, even before the task first runs
+        let mut loaded = modified(&self.path);
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(self.interval).await;
+                let current = modified(&self.path);
+                if current == loaded {
+                    continue;
+                }
+                loaded = current;
+                match ServerConfig::load_from(Some(&self.path), |name| std::env::var(name).ok()) {
+                    Ok(config) => {
+                        info!(path = %self.path.display(), "Configuration changed, reloading");
+                        if let Err(e) = app_state.apply_config(config) {
+                            warn!(error = %e, "Failed to apply reloaded configuration");
+                        }
+                    }
+                    Err(e) => warn!(error = %e, "Keeping running configuration"),
+                }
+            }
+        })
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn load(file: Option<(&str, &str)>, env: &[(&str, &str)]) -> anyhow::Result<ServerConfig> {
+        let env: HashMap<String, String> =
+            env.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect();
+        let path = file.map(|(extension, text)| {
+            let path = std::env::temp_dir().join(format!(
+                "bingo-config-{}-{}.{extension}",
+                std::process::id(),
+                text.len()
+            ));
+            std::fs::write(&path, text).unwrap();
+            path
+        });
+        let config = ServerConfig::load_from(path.as_deref(), |name| env.get(name).cloned());
+        if let Some(path) = path {
+            std::fs::remove_file(path).unwrap();
+        }
+        config
+    }
+
+    #[test]
+    fn test_file_settings_are_overridden_by_the_environment() {
+        let toml = r#"
+            [limits]
+            session_facts_per_sec = 100.0
+            burst_seconds = 2.0
+
+            [sessions]
+            ttl_seconds = 600
+            eviction = "ttl"
+            eviction_ttl_seconds = 60
+        "#;
+        let config = load(Some(("toml", toml)), &[("BINGO_SESSION_TTL_SECONDS", "30")]).unwrap();
+        assert_eq!(config.sessions.ttl(), Some(Duration::from_secs(30)));
+        assert_eq!(
+            config.sessions.engine_config().unwrap().eviction,
+            EvictionPolicy::Ttl(chrono::Duration::seconds(60))
+        );
+        let (global, session) = config.limits.admission_limits();
+        assert_eq!(global, AdmissionLimits::default());
+        assert_eq!(
+            session.facts,
+            Some(RateLimit::per_second(100.0).with_burst(200.0))
+        );
+        assert_eq!(config.server, ServerSettings::default());
+
+        let yaml = "engine_pool:\n  min_idle: 1\n  max_idle: 2\n";
+        let config = load(Some(("yaml", yaml)), &[]).unwrap();
+        assert_eq!(
+            config.engine_pool(),
+            EnginePoolConfig { min_idle: 1, max_idle: 2 }
+        );
+    }
+
+    #[test]
+    fn test_invalid_settings_are_refused() {
+        for (file, env) in [
+            (Some(("toml", "[limits]\nrate = 5\n")), vec![]),
+            (
+                None,
+                vec![("BINGO_ENGINE_POOL_MIN", "8"), ("BINGO_ENGINE_POOL_MAX", "2")],
+            ),
+            (None, vec![("BINGO_GLOBAL_FACTS_PER_SEC", "-1")]),
+            (None, vec![("BINGO_EVICTION", "ttl")]),
+            (None, vec![("BINGO_WORKER_THREADS", "many")]),
+            (Some(("ini", "limits=1")), vec![]),
+        ] {
+            assert!(load(file, &env).is_err(), "{file:?} {env:?}");
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! Pool of pre-initialized engines for sessions and stateless evaluation
+//!
+//! Building an engine on the request path adds its construction cost to the first
+//! request of every session and to every `Evaluate` call, so the pool keeps engines
+//! built ahead of time. `replenish` tops the pool up to its minimum off the request
+//! path. Engines handed back with `release` are reset to the state of a new engine
+//! and kept for the next caller, up to the pool's maximum; beyond it they are dropped.
+
+use std::sync::{Arc, Mutex};
+
+use bingo_core::{BingoEngine, BingoResult};
+use tracing::{debug, warn};
+
+/// How many idle engines the pool keeps ready
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnginePoolConfig {
+    /// Idle engines `replenish` builds up to
+    pub min_idle: usize,
+    /// Idle engines kept at most; released engines beyond it are dropped
+    pub max_idle: usize,
+}
+
+impl Default for EnginePoolConfig {
+    fn default() -> Self {
+        Self { min_idle: 4, max_idle: 16 }
+    }
+}
+
+/// Engines built ahead of the requests that use them
+#[derive(Debug)]
+pub struct EnginePool {
+    idle: Mutex<Vec<BingoEngine>>,
+    config: EnginePoolConfig,
+}
+
+impl EnginePool {
+    /// Pool keeping `config.min_idle` engines ready, built before this returns
+    pub fn new(config: EnginePoolConfig) -> BingoResult<Self> {
+        let pool = Self { idle: Mutex::new(Vec::with_capacity(config.max_idle)), config };
+        pool.replenish()?;
+        Ok(pool)
+    }
+
+    /// Take a ready engine, building one when the pool has run dry
+    pub fn acquire(&self) -> BingoResult<BingoEngine> {
+        match self.idle.lock().unwrap().pop() {
+            Some(engine) => Ok(engine),
+            None => {
+                debug!("Engine pool is empty, building an engine on the request path");
+                BingoEngine::new()
+            }
+        }
+    }
+
+    /// Hand an engine back, resetting it for its next user
+    ///
+    /// The engine is dropped when the pool already holds `max_idle` engines or the
+    /// reset fails.
+    pub fn release(&self, mut engine: BingoEngine) {
+        if self.idle() >= self.config.max_idle {
+            return;
+        }
+        if let Err(e) = engine.reset() {
+            warn!(error = %e, "Dropping engine that failed to reset");
+            return;
+        }
+        let mut idle = self.idle.lock().unwrap();
+        if idle.len() < self.config.max_idle {
+            idle.push(engine);
+        }
+    }
+
+    /// Build engines until `min_idle` are ready again
+    pub fn replenish(&self) -> BingoResult<()> {
+        while self.idle() < self.config.min_idle {
+            // Built outside the lock so requests can take engines meanwhile
+            let engine = BingoEngine::new()?;
+            let mut idle = self.idle.lock().unwrap();
+            if idle.len() < self.config.min_idle {
+                idle.push(engine);
+            }
+        }
+        Ok(())
+    }
+
+    /// Top the pool up on a blocking task, so callers don't wait for the engines
+    ///
+    /// Does nothing outside a Tokio runtime or while the pool holds `min_idle` engines.
+    pub fn replenish_in_background(self: &Arc<Self>) {
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        if self.idle() >= self.config.min_idle {
+            return;
+        }
+        let pool = Arc::clone(self);
+        runtime.spawn_blocking(move || {
+            if let Err(e) = pool.replenish() {
+                warn!(error = %e, "Failed to replenish engine pool");
+            }
+        });
+    }
+
+    /// Engines ready to be taken
+    pub fn idle(&self) -> usize {
+        self.idle.lock().unwrap().len()
+    }
+
+    /// Minimum and maximum idle engines
+    pub fn config(&self) -> EnginePoolConfig {
+        self.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(min_idle: usize, max_idle: usize) -> EnginePool {
+        EnginePool::new(EnginePoolConfig { min_idle, max_idle }).unwrap()
+    }
+
+    #[test]
+    fn test_acquired_engines_are_replaced_by_replenish() {
+        let pool = pool(2, 4);
+        assert_eq!(pool.idle(), 2);
+
+        let _first = pool.acquire().unwrap();
+        let _second = pool.acquire().unwrap();
+        let _built_on_demand = pool.acquire().unwrap();
+        assert_eq!(pool.idle(), 0);
+
+        pool.replenish().unwrap();
+        assert_eq!(pool.idle(), 2);
+    }
+
+    #[test]
+    fn test_released_engines_are_reset_and_capped() {
+        let pool = pool(0, 1);
+        let engine = pool.acquire().unwrap();
+        engine.set_global("region", bingo_core::FactValue::Integer(1)).unwrap();
+        pool.release(engine);
+        pool.release(BingoEngine::new().unwrap());
+        assert_eq!(pool.idle(), 1);
+
+        assert!(pool.acquire().unwrap().globals().is_empty());
+    }
+}
@@ -9,10 +9,20 @@ pub struct Fact {
     /// Unix timestamp
     #[prost(int64, tag = "3")]
     pub created_at: i64,
+    /// Encoding of `payload`: "application/json" or "application/msgpack". Empty or
+    /// "application/protobuf" means the fields are in `data`.
+    #[prost(string, tag = "4")]
+    pub content_type: ::prost::alloc::string::String,
+    /// The fact's fields as one JSON object or MessagePack map, used instead of `data`
+    #[prost(bytes = "vec", tag = "5")]
+    pub payload: ::prost::alloc::vec::Vec<u8>,
+    /// Type of the fact, which routes it only to rules of its type or of any type
+    #[prost(string, tag = "6")]
+    pub fact_type: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Value {
-    #[prost(oneof = "value::Value", tags = "1, 2, 3, 4")]
+    #[prost(oneof = "value::Value", tags = "1, 2, 3, 4, 5")]
     pub value: ::core::option::Option<value::Value>,
 }
 /// Nested message and enum types in `Value`.
@@ -27,6 +37,9 @@ pub mod value {
         BoolValue(bool),
         #[prost(int64, tag = "4")]
         IntValue(i64),
+        /// Exact decimal, e.g. "1234.50"
+        #[prost(string, tag = "5")]
+        DecimalValue(::prost::alloc::string::String),
     }
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -51,6 +64,24 @@ pub struct Rule {
     pub created_at: i64,
     #[prost(int64, tag = "10")]
     pub updated_at: i64,
+    /// Team or person responsible for the rule
+    #[prost(string, tag = "11")]
+    pub owner: ::prost::alloc::string::String,
+    /// Link to the rule's specification, ticket or policy document
+    #[prost(string, tag = "12")]
+    pub link: ::prost::alloc::string::String,
+    /// Unix seconds from which the rule fires, 0 for immediately
+    #[prost(int64, tag = "13")]
+    pub effective_date: i64,
+    /// Unix seconds from which the rule no longer fires, 0 for never
+    #[prost(int64, tag = "14")]
+    pub expiry_date: i64,
+    /// Group sessions can disable as a whole, empty for none
+    #[prost(string, tag = "15")]
+    pub rule_group: ::prost::alloc::string::String,
+    /// Rules sharing it fire at most once per fact between them
+    #[prost(string, tag = "16")]
+    pub activation_group: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Condition {
@@ -170,6 +201,12 @@ pub struct RuleExecutionResult {
         ::prost::alloc::string::String,
         ::prost::alloc::string::String,
     >,
+    /// Version of the ruleset that produced the result
+    #[prost(uint64, tag = "7")]
+    pub ruleset_version: u64,
+    /// External ID of the matched fact, empty if it has none
+    #[prost(string, tag = "8")]
+    pub external_id: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ActionResult {
@@ -217,6 +254,9 @@ pub struct RegisterRulesetRequest {
     pub ruleset_id: ::prost::alloc::string::String,
     #[prost(message, repeated, tag = "2")]
     pub rules: ::prost::alloc::vec::Vec<Rule>,
+    /// Empty for the default tenant
+    #[prost(string, tag = "3")]
+    pub tenant_id: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct RegisterRulesetResponse {
@@ -241,6 +281,56 @@ pub struct EvaluateRulesetRequest {
     pub request_id: ::prost::alloc::string::String,
     #[prost(message, optional, tag = "4")]
     pub options: ::core::option::Option<ProcessingOptions>,
+    /// Empty for the default tenant
+    #[prost(string, tag = "5")]
+    pub tenant_id: ::prost::alloc::string::String,
+}
+/// Compiled-asset cache administration
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CacheStatsRequest {
+    /// Empty for every tenant
+    #[prost(string, tag = "1")]
+    pub tenant_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TenantCacheStats {
+    #[prost(string, tag = "1")]
+    pub tenant_id: ::prost::alloc::string::String,
+    #[prost(int32, tag = "2")]
+    pub entries: i32,
+    #[prost(int32, tag = "3")]
+    pub capacity: i32,
+    #[prost(int64, tag = "4")]
+    pub hits: i64,
+    #[prost(int64, tag = "5")]
+    pub misses: i64,
+    #[prost(int64, tag = "6")]
+    pub evictions: i64,
+    #[prost(int64, tag = "7")]
+    pub bytes: i64,
+    #[prost(string, repeated, tag = "8")]
+    pub ruleset_ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CacheStatsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub tenants: ::prost::alloc::vec::Vec<TenantCacheStats>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PurgeCacheRequest {
+    /// Empty to purge every tenant
+    #[prost(string, tag = "1")]
+    pub tenant_id: ::prost::alloc::string::String,
+    /// Empty to purge every ruleset of the tenant
+    #[prost(string, tag = "2")]
+    pub ruleset_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PurgeCacheResponse {
+    #[prost(int32, tag = "1")]
+    pub entries_purged: i32,
+    #[prost(int64, tag = "2")]
+    pub bytes_freed: i64,
 }
 /// Two-phase processing messages
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -252,6 +342,12 @@ pub struct CompileRulesRequest {
     pub session_id: ::prost::alloc::string::String,
     #[prost(message, optional, tag = "3")]
     pub options: ::core::option::Option<ProcessingOptions>,
+    /// Registered ruleset to warm-start the session from
+    #[prost(string, tag = "4")]
+    pub ruleset_template: ::prost::alloc::string::String,
+    /// Tenant owning ruleset_template (empty = default tenant)
+    #[prost(string, tag = "5")]
+    pub tenant_id: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CompileRulesResponse {
@@ -269,6 +365,9 @@ pub struct CompileRulesResponse {
     pub compilation_time_ms: i64,
     #[prost(string, tag = "7")]
     pub engine_version: ::prost::alloc::string::String,
+    /// Version of the session's ruleset after compilation
+    #[prost(uint64, tag = "8")]
+    pub ruleset_version: u64,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ProcessFactsStreamRequest {
@@ -343,330 +442,1695 @@ pub mod processing_control {
         }
     }
 }
-/// Single-call alternative with rules validation
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ProcessWithRulesRequest {
-    #[prost(message, repeated, tag = "1")]
-    pub rules: ::prost::alloc::vec::Vec<Rule>,
-    #[prost(message, repeated, tag = "2")]
-    pub facts: ::prost::alloc::vec::Vec<Fact>,
-    #[prost(string, tag = "3")]
-    pub request_id: ::prost::alloc::string::String,
-    #[prost(message, optional, tag = "4")]
-    pub options: ::core::option::Option<ProcessingOptions>,
-    /// If true, only validate rules without processing facts
-    #[prost(bool, tag = "5")]
-    pub validate_rules_only: bool,
-}
+/// Continuous fact ingestion with flow control
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ProcessingResponse {
-    #[prost(oneof = "processing_response::Response", tags = "1, 2, 3, 4")]
-    pub response: ::core::option::Option<processing_response::Response>,
+pub struct IngestFactsRequest {
+    #[prost(oneof = "ingest_facts_request::Request", tags = "1, 2, 3")]
+    pub request: ::core::option::Option<ingest_facts_request::Request>,
 }
-/// Nested message and enum types in `ProcessingResponse`.
-pub mod processing_response {
+/// Nested message and enum types in `IngestFactsRequest`.
+pub mod ingest_facts_request {
     #[derive(Clone, PartialEq, ::prost::Oneof)]
-    pub enum Response {
-        /// First response: rules compilation result
+    pub enum Request {
+        /// Must be the first message
         #[prost(message, tag = "1")]
-        RulesCompiled(super::CompileRulesResponse),
-        /// Subsequent responses: fact processing results
+        Start(super::IngestStart),
+        /// Fact to process against the session's rules
         #[prost(message, tag = "2")]
-        ExecutionResult(super::RuleExecutionResult),
-        /// Status updates during processing
+        Fact(super::Fact),
+        /// FLUSH requests an ack, STOP ends the stream
         #[prost(message, tag = "3")]
-        StatusUpdate(super::ProcessingStatus),
-        /// Final response
-        #[prost(message, tag = "4")]
-        Completion(super::ProcessingComplete),
+        Control(super::ProcessingControl),
     }
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ProcessingComplete {
+pub struct IngestStart {
+    /// Session compiled with CompileRules
     #[prost(string, tag = "1")]
-    pub request_id: ::prost::alloc::string::String,
+    pub session_id: ::prost::alloc::string::String,
+    /// Responses buffered for a slow reader before ingestion waits (0 = server default)
     #[prost(int32, tag = "2")]
-    pub total_facts_processed: i32,
+    pub max_pending_responses: i32,
+    /// Send an IngestAck every N facts (0 = only on FLUSH, STOP and end of stream)
     #[prost(int32, tag = "3")]
-    pub total_results_generated: i32,
+    pub ack_interval: i32,
+    /// Business date, in Unix seconds, that rule effective and expiry dates are checked
+    /// against (0 = the clock when each fact is processed)
     #[prost(int64, tag = "4")]
-    pub total_processing_time_ms: i64,
+    pub evaluation_date: i64,
+    /// Check each fact against its own created_at instead; excludes evaluation_date
     #[prost(bool, tag = "5")]
-    pub success: bool,
-    #[prost(string, tag = "6")]
+    pub evaluate_at_event_time: bool,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IngestAck {
+    #[prost(int64, tag = "1")]
+    pub facts_received: i64,
+    #[prost(int64, tag = "2")]
+    pub facts_processed: i64,
+    #[prost(int64, tag = "3")]
+    pub facts_rejected: i64,
+    #[prost(int64, tag = "4")]
+    pub results_sent: i64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FactRejection {
+    #[prost(string, tag = "1")]
+    pub fact_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
     pub error_message: ::prost::alloc::string::String,
+    /// Set when the fact failed its type's schema
+    #[prost(message, repeated, tag = "3")]
+    pub violations: ::prost::alloc::vec::Vec<FactViolation>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct HealthResponse {
+pub struct FactViolation {
     #[prost(string, tag = "1")]
-    pub status: ::prost::alloc::string::String,
+    pub field: ::prost::alloc::string::String,
+    /// missing_field, type_mismatch or unknown_field
     #[prost(string, tag = "2")]
-    pub version: ::prost::alloc::string::String,
-    #[prost(int64, tag = "3")]
-    pub uptime_seconds: i64,
+    pub code: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub message: ::prost::alloc::string::String,
 }
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
-#[repr(i32)]
-pub enum SimpleOperator {
-    Equal = 0,
-    NotEqual = 1,
-    GreaterThan = 2,
-    LessThan = 3,
-    GreaterThanOrEqual = 4,
-    LessThanOrEqual = 5,
-    Contains = 6,
-    StartsWith = 7,
-    EndsWith = 8,
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IngestFactsResponse {
+    #[prost(oneof = "ingest_facts_response::Response", tags = "1, 2, 3")]
+    pub response: ::core::option::Option<ingest_facts_response::Response>,
 }
-impl SimpleOperator {
-    /// String value of the enum field names used in the ProtoBuf definition.
-    ///
-    /// The values are not transformed in any way and thus are considered stable
-    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
-    pub fn as_str_name(&self) -> &'static str {
-        match self {
-            Self::Equal => "SIMPLE_OPERATOR_EQUAL",
-            Self::NotEqual => "SIMPLE_OPERATOR_NOT_EQUAL",
-            Self::GreaterThan => "SIMPLE_OPERATOR_GREATER_THAN",
-            Self::LessThan => "SIMPLE_OPERATOR_LESS_THAN",
-            Self::GreaterThanOrEqual => "SIMPLE_OPERATOR_GREATER_THAN_OR_EQUAL",
-            Self::LessThanOrEqual => "SIMPLE_OPERATOR_LESS_THAN_OR_EQUAL",
-            Self::Contains => "SIMPLE_OPERATOR_CONTAINS",
-            Self::StartsWith => "SIMPLE_OPERATOR_STARTS_WITH",
-            Self::EndsWith => "SIMPLE_OPERATOR_ENDS_WITH",
-        }
+/// Nested message and enum types in `IngestFactsResponse`.
+pub mod ingest_facts_response {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Response {
+        /// A rule fired for an ingested fact
+        #[prost(message, tag = "1")]
+        Result(super::RuleExecutionResult),
+        /// A fact could not be converted or processed
+        #[prost(message, tag = "2")]
+        Rejected(super::FactRejection),
+        /// Progress so far
+        #[prost(message, tag = "3")]
+        Ack(super::IngestAck),
     }
-    /// Creates an enum from field names used in the ProtoBuf definition.
-    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
-        match value {
-            "SIMPLE_OPERATOR_EQUAL" => Some(Self::Equal),
-            "SIMPLE_OPERATOR_NOT_EQUAL" => Some(Self::NotEqual),
-            "SIMPLE_OPERATOR_GREATER_THAN" => Some(Self::GreaterThan),
-            "SIMPLE_OPERATOR_LESS_THAN" => Some(Self::LessThan),
-            "SIMPLE_OPERATOR_GREATER_THAN_OR_EQUAL" => Some(Self::GreaterThanOrEqual),
-            "SIMPLE_OPERATOR_LESS_THAN_OR_EQUAL" => Some(Self::LessThanOrEqual),
-            "SIMPLE_OPERATOR_CONTAINS" => Some(Self::Contains),
-            "SIMPLE_OPERATOR_STARTS_WITH" => Some(Self::StartsWith),
-            "SIMPLE_OPERATOR_ENDS_WITH" => Some(Self::EndsWith),
-            _ => None,
-        }
+}
+/// Interactive step-debugging of a session's rules
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DebugRequest {
+    #[prost(oneof = "debug_request::Request", tags = "1, 2, 3, 4")]
+    pub request: ::core::option::Option<debug_request::Request>,
+}
+/// Nested message and enum types in `DebugRequest`.
+pub mod debug_request {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Request {
+        /// Must be the first message
+        #[prost(message, tag = "1")]
+        Start(super::DebugStart),
+        /// Queue a fact for a later step
+        #[prost(message, tag = "2")]
+        Fact(super::Fact),
+        /// Step through queued facts or end the stream
+        #[prost(message, tag = "3")]
+        Command(super::DebugCommand),
+        /// Replace the breakpoints
+        #[prost(message, tag = "4")]
+        Breakpoints(super::DebugBreakpoints),
     }
 }
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
-#[repr(i32)]
-pub enum LogicalOperator {
-    And = 0,
-    Or = 1,
-    Not = 2,
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DebugStart {
+    /// Session compiled with CompileRules
+    #[prost(string, tag = "1")]
+    pub session_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub breakpoints: ::core::option::Option<DebugBreakpoints>,
 }
-impl LogicalOperator {
-    /// String value of the enum field names used in the ProtoBuf definition.
-    ///
-    /// The values are not transformed in any way and thus are considered stable
-    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
-    pub fn as_str_name(&self) -> &'static str {
-        match self {
-            Self::And => "LOGICAL_OPERATOR_AND",
-            Self::Or => "LOGICAL_OPERATOR_OR",
-            Self::Not => "LOGICAL_OPERATOR_NOT",
-        }
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DebugBreakpoints {
+    /// Pause when the rule fires
+    #[prost(string, repeated, tag = "1")]
+    pub rule_ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Pause when a fact enters the alpha node's memory
+    #[prost(uint64, repeated, tag = "2")]
+    pub alpha_node_ids: ::prost::alloc::vec::Vec<u64>,
+    /// Pause when a token enters the beta node's memory
+    #[prost(uint64, repeated, tag = "3")]
+    pub beta_node_ids: ::prost::alloc::vec::Vec<u64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DebugCommand {
+    #[prost(enumeration = "debug_command::CommandType", tag = "1")]
+    pub r#type: i32,
+}
+/// Nested message and enum types in `DebugCommand`.
+pub mod debug_command {
+    #[derive(
+        Clone,
+        Copy,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash,
+        PartialOrd,
+        Ord,
+        ::prost::Enumeration
+    )]
+    #[repr(i32)]
+    pub enum CommandType {
+        /// Process the next queued fact
+        Step = 0,
+        /// Step until a breakpoint is reached or the queue is empty
+        Resume = 1,
+        /// End the debug session
+        Stop = 2,
     }
-    /// Creates an enum from field names used in the ProtoBuf definition.
-    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
-        match value {
-            "LOGICAL_OPERATOR_AND" => Some(Self::And),
-            "LOGICAL_OPERATOR_OR" => Some(Self::Or),
-            "LOGICAL_OPERATOR_NOT" => Some(Self::Not),
-            _ => None,
+    impl CommandType {
+        /// String value of the enum field names used in the ProtoBuf definition.
+        ///
+        /// The values are not transformed in any way and thus are considered stable
+        /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+        pub fn as_str_name(&self) -> &'static str {
+            match self {
+                Self::Step => "COMMAND_TYPE_STEP",
+                Self::Resume => "COMMAND_TYPE_RESUME",
+                Self::Stop => "COMMAND_TYPE_STOP",
+            }
+        }
+        /// Creates an enum from field names used in the ProtoBuf definition.
+        pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+            match value {
+                "COMMAND_TYPE_STEP" => Some(Self::Step),
+                "COMMAND_TYPE_RESUME" => Some(Self::Resume),
+                "COMMAND_TYPE_STOP" => Some(Self::Stop),
+                _ => None,
+            }
         }
     }
 }
-/// Generated server implementations.
-pub mod rules_engine_service_server {
-    #![allow(
-        unused_variables,
-        dead_code,
-        missing_docs,
-        clippy::wildcard_imports,
-        clippy::let_unit_value,
-    )]
-    use tonic::codegen::*;
-    /// Generated trait containing gRPC methods that should be implemented for use with RulesEngineServiceServer.
-    #[async_trait]
-    pub trait RulesEngineService: std::marker::Send + std::marker::Sync + 'static {
-        /// Two-phase processing: compile rules first, then stream facts
-        async fn compile_rules(
-            &self,
-            request: tonic::Request<super::CompileRulesRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::CompileRulesResponse>,
-            tonic::Status,
-        >;
-        /// Server streaming response type for the ProcessFactsStream method.
-        type ProcessFactsStreamStream: tonic::codegen::tokio_stream::Stream<
-                Item = std::result::Result<super::RuleExecutionResult, tonic::Status>,
-            >
-            + std::marker::Send
-            + 'static;
-        async fn process_facts_stream(
-            &self,
-            request: tonic::Request<tonic::Streaming<super::ProcessFactsStreamRequest>>,
-        ) -> std::result::Result<
-            tonic::Response<Self::ProcessFactsStreamStream>,
-            tonic::Status,
-        >;
-        /// Server streaming response type for the ProcessWithRulesStream method.
-        type ProcessWithRulesStreamStream: tonic::codegen::tokio_stream::Stream<
-                Item = std::result::Result<super::ProcessingResponse, tonic::Status>,
-            >
-            + std::marker::Send
-            + 'static;
-        /// Alternative: single-call with rules validation before fact streaming
-        async fn process_with_rules_stream(
-            &self,
-            request: tonic::Request<super::ProcessWithRulesRequest>,
-        ) -> std::result::Result<
-            tonic::Response<Self::ProcessWithRulesStreamStream>,
-            tonic::Status,
-        >;
-        /// Server streaming response type for the ProcessFactsBatch method.
-        type ProcessFactsBatchStream: tonic::codegen::tokio_stream::Stream<
-                Item = std::result::Result<super::ProcessingStatus, tonic::Status>,
-            >
-            + std::marker::Send
-            + 'static;
-        /// Batch processing with status updates
-        async fn process_facts_batch(
-            &self,
-            request: tonic::Request<super::ProcessFactsRequest>,
-        ) -> std::result::Result<
-            tonic::Response<Self::ProcessFactsBatchStream>,
-            tonic::Status,
-        >;
-        /// Server streaming response type for the EvaluateRulesetStream method.
-        type EvaluateRulesetStreamStream: tonic::codegen::tokio_stream::Stream<
-                Item = std::result::Result<super::RuleExecutionResult, tonic::Status>,
-            >
-            + std::marker::Send
-            + 'static;
-        /// Cached ruleset processing (streaming)
-        async fn evaluate_ruleset_stream(
-            &self,
-            request: tonic::Request<super::EvaluateRulesetRequest>,
-        ) -> std::result::Result<
-            tonic::Response<Self::EvaluateRulesetStreamStream>,
-            tonic::Status,
-        >;
-        /// Ruleset management
-        async fn register_ruleset(
-            &self,
-            request: tonic::Request<super::RegisterRulesetRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::RegisterRulesetResponse>,
-            tonic::Status,
-        >;
-        /// Health check
-        async fn health_check(
-            &self,
-            request: tonic::Request<()>,
-        ) -> std::result::Result<tonic::Response<super::HealthResponse>, tonic::Status>;
-    }
-    /// Main service definition
-    #[derive(Debug)]
-    pub struct RulesEngineServiceServer<T> {
-        inner: Arc<T>,
-        accept_compression_encodings: EnabledCompressionEncodings,
-        send_compression_encodings: EnabledCompressionEncodings,
-        max_decoding_message_size: Option<usize>,
-        max_encoding_message_size: Option<usize>,
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DebugState {
+    #[prost(string, tag = "1")]
+    pub debug_session_id: ::prost::alloc::string::String,
+    /// Steps taken for the request, oldest first
+    #[prost(message, repeated, tag = "2")]
+    pub steps: ::prost::alloc::vec::Vec<DebugStep>,
+    /// The last step reached a breakpoint
+    #[prost(bool, tag = "3")]
+    pub paused_at_breakpoint: bool,
+    #[prost(int32, tag = "4")]
+    pub pending_facts: i32,
+    #[prost(uint64, tag = "5")]
+    pub steps_taken: u64,
+    /// Memories after the last step, by node id
+    #[prost(message, repeated, tag = "6")]
+    pub alpha_memories: ::prost::alloc::vec::Vec<AlphaMemory>,
+    #[prost(message, repeated, tag = "7")]
+    pub beta_memories: ::prost::alloc::vec::Vec<BetaMemory>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DebugStep {
+    #[prost(uint64, tag = "1")]
+    pub step: u64,
+    /// Id the memories refer to the fact by
+    #[prost(uint64, tag = "2")]
+    pub fact_id: u64,
+    #[prost(message, optional, tag = "3")]
+    pub fact: ::core::option::Option<Fact>,
+    /// Alpha nodes the fact entered
+    #[prost(uint64, repeated, tag = "4")]
+    pub alpha_node_ids: ::prost::alloc::vec::Vec<u64>,
+    /// Beta nodes that gained tokens
+    #[prost(uint64, repeated, tag = "5")]
+    pub beta_node_ids: ::prost::alloc::vec::Vec<u64>,
+    #[prost(message, repeated, tag = "6")]
+    pub results: ::prost::alloc::vec::Vec<RuleExecutionResult>,
+    #[prost(message, optional, tag = "7")]
+    pub breakpoints_hit: ::core::option::Option<DebugBreakpoints>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AlphaMemory {
+    #[prost(uint64, tag = "1")]
+    pub node_id: u64,
+    #[prost(string, tag = "2")]
+    pub condition: ::prost::alloc::string::String,
+    #[prost(uint64, repeated, tag = "3")]
+    pub fact_ids: ::prost::alloc::vec::Vec<u64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BetaMemory {
+    #[prost(uint64, tag = "1")]
+    pub node_id: u64,
+    /// Rules whose matches pass through the node
+    #[prost(string, repeated, tag = "2")]
+    pub rule_ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(message, repeated, tag = "3")]
+    pub tokens: ::prost::alloc::vec::Vec<BetaToken>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BetaToken {
+    /// Facts joined by the partial match
+    #[prost(uint64, repeated, tag = "1")]
+    pub fact_ids: ::prost::alloc::vec::Vec<u64>,
+}
+/// Rule management within a compiled session
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateRuleRequest {
+    #[prost(string, tag = "1")]
+    pub session_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub rule: ::core::option::Option<Rule>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateRuleRequest {
+    #[prost(string, tag = "1")]
+    pub session_id: ::prost::alloc::string::String,
+    /// Replaces the rule with the same id
+    #[prost(message, optional, tag = "2")]
+    pub rule: ::core::option::Option<Rule>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeleteRuleRequest {
+    #[prost(string, tag = "1")]
+    pub session_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub rule_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RuleMutationResponse {
+    #[prost(string, tag = "1")]
+    pub rule_id: ::prost::alloc::string::String,
+    /// Version of the session's ruleset after the change
+    #[prost(uint64, tag = "2")]
+    pub ruleset_version: u64,
+    #[prost(int32, tag = "3")]
+    pub rule_count: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListRulesRequest {
+    #[prost(string, tag = "1")]
+    pub session_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListRulesResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub rules: ::prost::alloc::vec::Vec<Rule>,
+    #[prost(uint64, tag = "2")]
+    pub ruleset_version: u64,
+}
+/// Per-rule hit counts, for finding rules that never fire and rules that fire most
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetRuleStatsRequest {
+    #[prost(string, tag = "1")]
+    pub session_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RuleStats {
+    #[prost(string, tag = "1")]
+    pub rule_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub rule_name: ::prost::alloc::string::String,
+    /// Facts the rule's conditions were evaluated against
+    #[prost(uint64, tag = "3")]
+    pub evaluations: u64,
+    /// Times the rule fired
+    #[prost(uint64, tag = "4")]
+    pub activations: u64,
+    /// Unix timestamp in milliseconds, 0 if never fired
+    #[prost(int64, tag = "5")]
+    pub last_fired_at: i64,
+    /// Mean condition evaluation time per fact
+    #[prost(uint64, tag = "6")]
+    pub avg_condition_time_ns: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetRuleStatsResponse {
+    /// Ordered by rule id
+    #[prost(message, repeated, tag = "1")]
+    pub rules: ::prost::alloc::vec::Vec<RuleStats>,
+    #[prost(uint64, tag = "2")]
+    pub ruleset_version: u64,
+}
+/// Which rules can trigger which: an edge runs from a rule whose actions write a field
+/// or create a fact type to each rule whose conditions read it
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetRuleGraphRequest {
+    #[prost(string, tag = "1")]
+    pub session_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RuleGraphNode {
+    #[prost(string, tag = "1")]
+    pub rule_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub rule_name: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RuleGraphEdge {
+    #[prost(string, tag = "1")]
+    pub from_rule_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub to_rule_id: ::prost::alloc::string::String,
+    /// Fields, and `type:<name>` fact types, in name order
+    #[prost(string, repeated, tag = "3")]
+    pub via: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RuleCycle {
+    /// Rules that can keep firing each other, by id
+    #[prost(string, repeated, tag = "1")]
+    pub rule_ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetRuleGraphResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub nodes: ::prost::alloc::vec::Vec<RuleGraphNode>,
+    /// Ordered by source, then target rule id
+    #[prost(message, repeated, tag = "2")]
+    pub edges: ::prost::alloc::vec::Vec<RuleGraphEdge>,
+    /// Empty when no rule can trigger itself
+    #[prost(message, repeated, tag = "3")]
+    pub cycles: ::prost::alloc::vec::Vec<RuleCycle>,
+    /// Graphviz rendering, cycle edges in red
+    #[prost(string, tag = "4")]
+    pub dot: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "5")]
+    pub ruleset_version: u64,
+}
+/// Session globals and reference tables, pushed once instead of embedded in every fact
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetSessionGlobalsRequest {
+    #[prost(string, tag = "1")]
+    pub session_id: ::prost::alloc::string::String,
+    /// Replaces the globals with these names
+    #[prost(map = "string, message", tag = "2")]
+    pub globals: ::std::collections::HashMap<::prost::alloc::string::String, Value>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetSessionGlobalsRequest {
+    #[prost(string, tag = "1")]
+    pub session_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SessionGlobalsResponse {
+    /// Every global of the session
+    #[prost(map = "string, message", tag = "1")]
+    pub globals: ::std::collections::HashMap<::prost::alloc::string::String, Value>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReferenceTableEntry {
+    #[prost(message, optional, tag = "1")]
+    pub key: ::core::option::Option<Value>,
+    /// Unset for key-only rows, which read back as true
+    #[prost(message, optional, tag = "2")]
+    pub value: ::core::option::Option<Value>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetReferenceTableRequest {
+    #[prost(string, tag = "1")]
+    pub session_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub name: ::prost::alloc::string::String,
+    /// Replaces the whole table
+    #[prost(message, repeated, tag = "3")]
+    pub entries: ::prost::alloc::vec::Vec<ReferenceTableEntry>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetReferenceTableResponse {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(int32, tag = "2")]
+    pub row_count: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetReferenceTableRequest {
+    #[prost(string, tag = "1")]
+    pub session_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub name: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetReferenceTableResponse {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    /// In no particular order
+    #[prost(message, repeated, tag = "2")]
+    pub entries: ::prost::alloc::vec::Vec<ReferenceTableEntry>,
+}
+/// Rule groups a session fires; rules in a disabled group match but do not fire
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetSessionRuleGroupsRequest {
+    #[prost(string, tag = "1")]
+    pub session_id: ::prost::alloc::string::String,
+    #[prost(string, repeated, tag = "2")]
+    pub enable: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Applied after enable
+    #[prost(string, repeated, tag = "3")]
+    pub disable: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SessionRuleGroupsResponse {
+    /// Every disabled group of the session, sorted
+    #[prost(string, repeated, tag = "1")]
+    pub disabled_groups: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+/// Typed fact schemas, checked against ingested facts whose `type` field names the type
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SchemaField {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(enumeration = "schema_field::Kind", tag = "2")]
+    pub kind: i32,
+    /// Facts must carry a non-null value
+    #[prost(bool, tag = "3")]
+    pub required: bool,
+    #[prost(string, repeated, tag = "4")]
+    pub enum_values: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+/// Nested message and enum types in `SchemaField`.
+pub mod schema_field {
+    #[derive(
+        Clone,
+        Copy,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash,
+        PartialOrd,
+        Ord,
+        ::prost::Enumeration
+    )]
+    #[repr(i32)]
+    pub enum Kind {
+        Text = 0,
+        Integer = 1,
+        Decimal = 2,
+        Boolean = 3,
+        Date = 4,
+        /// One of enum_values
+        Enum = 5,
     }
-    impl<T> RulesEngineServiceServer<T> {
-        pub fn new(inner: T) -> Self {
-            Self::from_arc(Arc::new(inner))
-        }
-        pub fn from_arc(inner: Arc<T>) -> Self {
-            Self {
-                inner,
-                accept_compression_encodings: Default::default(),
-                send_compression_encodings: Default::default(),
-                max_decoding_message_size: None,
-                max_encoding_message_size: None,
-            }
-        }
-        pub fn with_interceptor<F>(
-            inner: T,
-            interceptor: F,
-        ) -> InterceptedService<Self, F>
-        where
-            F: tonic::service::Interceptor,
-        {
-            InterceptedService::new(Self::new(inner), interceptor)
-        }
-        /// Enable decompressing requests with the given encoding.
-        #[must_use]
-        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.accept_compression_encodings.enable(encoding);
-            self
-        }
-        /// Compress responses with the given encoding, if the client supports it.
-        #[must_use]
-        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.send_compression_encodings.enable(encoding);
-            self
-        }
-        /// Limits the maximum size of a decoded message.
+    impl Kind {
+        /// String value of the enum field names used in the ProtoBuf definition.
         ///
-        /// Default: `4MB`
-        #[must_use]
-        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
-            self.max_decoding_message_size = Some(limit);
-            self
+        /// The values are not transformed in any way and thus are considered stable
+        /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+        pub fn as_str_name(&self) -> &'static str {
+            match self {
+                Self::Text => "KIND_TEXT",
+                Self::Integer => "KIND_INTEGER",
+                Self::Decimal => "KIND_DECIMAL",
+                Self::Boolean => "KIND_BOOLEAN",
+                Self::Date => "KIND_DATE",
+                Self::Enum => "KIND_ENUM",
+            }
         }
-        /// Limits the maximum size of an encoded message.
-        ///
-        /// Default: `usize::MAX`
-        #[must_use]
-        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
-            self.max_encoding_message_size = Some(limit);
-            self
+        /// Creates an enum from field names used in the ProtoBuf definition.
+        pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+            match value {
+                "KIND_TEXT" => Some(Self::Text),
+                "KIND_INTEGER" => Some(Self::Integer),
+                "KIND_DECIMAL" => Some(Self::Decimal),
+                "KIND_BOOLEAN" => Some(Self::Boolean),
+                "KIND_DATE" => Some(Self::Date),
+                "KIND_ENUM" => Some(Self::Enum),
+                _ => None,
+            }
         }
     }
-    impl<T, B> tonic::codegen::Service<http::Request<B>> for RulesEngineServiceServer<T>
-    where
-        T: RulesEngineService,
-        B: Body + std::marker::Send + 'static,
-        B::Error: Into<StdError> + std::marker::Send + 'static,
-    {
-        type Response = http::Response<tonic::body::BoxBody>;
-        type Error = std::convert::Infallible;
-        type Future = BoxFuture<Self::Response, Self::Error>;
-        fn poll_ready(
-            &mut self,
-            _cx: &mut Context<'_>,
-        ) -> Poll<std::result::Result<(), Self::Error>> {
-            Poll::Ready(Ok(()))
-        }
-        fn call(&mut self, req: http::Request<B>) -> Self::Future {
-            match req.uri().path() {
-                "/rules_engine.v1.RulesEngineService/CompileRules" => {
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetFactSchemaRequest {
+    #[prost(string, tag = "1")]
+    pub session_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub fact_type: ::prost::alloc::string::String,
+    /// Replaces any schema for the type
+    #[prost(message, repeated, tag = "3")]
+    pub fields: ::prost::alloc::vec::Vec<SchemaField>,
+    /// Reject fields the schema does not declare
+    #[prost(bool, tag = "4")]
+    pub closed: bool,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetFactSchemaResponse {
+    #[prost(string, tag = "1")]
+    pub fact_type: ::prost::alloc::string::String,
+    #[prost(int32, tag = "2")]
+    pub field_count: i32,
+}
+/// Stateless evaluation of facts against rules sent with them
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EvaluateRequest {
+    #[prost(message, repeated, tag = "1")]
+    pub rules: ::prost::alloc::vec::Vec<Rule>,
+    #[prost(message, repeated, tag = "2")]
+    pub facts: ::prost::alloc::vec::Vec<Fact>,
+    #[prost(string, tag = "3")]
+    pub request_id: ::prost::alloc::string::String,
+    /// Unix seconds rule effective and expiry dates are checked against (0 = the clock)
+    #[prost(int64, tag = "4")]
+    pub evaluation_date: i64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EvaluateResponse {
+    #[prost(string, tag = "1")]
+    pub request_id: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "2")]
+    pub results: ::prost::alloc::vec::Vec<RuleExecutionResult>,
+    /// Results aggregated by rule
+    #[prost(message, optional, tag = "3")]
+    pub summary: ::core::option::Option<BatchSummary>,
+    #[prost(int64, tag = "4")]
+    pub processing_time_ms: i64,
+}
+/// Single-call alternative with rules validation
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ProcessWithRulesRequest {
+    #[prost(message, repeated, tag = "1")]
+    pub rules: ::prost::alloc::vec::Vec<Rule>,
+    #[prost(message, repeated, tag = "2")]
+    pub facts: ::prost::alloc::vec::Vec<Fact>,
+    #[prost(string, tag = "3")]
+    pub request_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "4")]
+    pub options: ::core::option::Option<ProcessingOptions>,
+    /// If true, only validate rules without processing facts
+    #[prost(bool, tag = "5")]
+    pub validate_rules_only: bool,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ProcessingResponse {
+    #[prost(oneof = "processing_response::Response", tags = "1, 2, 3, 4")]
+    pub response: ::core::option::Option<processing_response::Response>,
+}
+/// Nested message and enum types in `ProcessingResponse`.
+pub mod processing_response {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Response {
+        /// First response: rules compilation result
+        #[prost(message, tag = "1")]
+        RulesCompiled(super::CompileRulesResponse),
+        /// Subsequent responses: fact processing results
+        #[prost(message, tag = "2")]
+        ExecutionResult(super::RuleExecutionResult),
+        /// Status updates during processing
+        #[prost(message, tag = "3")]
+        StatusUpdate(super::ProcessingStatus),
+        /// Final response
+        #[prost(message, tag = "4")]
+        Completion(super::ProcessingComplete),
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ProcessingComplete {
+    #[prost(string, tag = "1")]
+    pub request_id: ::prost::alloc::string::String,
+    #[prost(int32, tag = "2")]
+    pub total_facts_processed: i32,
+    #[prost(int32, tag = "3")]
+    pub total_results_generated: i32,
+    #[prost(int64, tag = "4")]
+    pub total_processing_time_ms: i64,
+    #[prost(bool, tag = "5")]
+    pub success: bool,
+    #[prost(string, tag = "6")]
+    pub error_message: ::prost::alloc::string::String,
+    /// Results aggregated by rule
+    #[prost(message, optional, tag = "7")]
+    pub summary: ::core::option::Option<BatchSummary>,
+}
+/// Execution figures for a batch, so dashboards don't re-aggregate individual results
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BatchSummary {
+    #[prost(int64, tag = "1")]
+    pub facts_processed: i64,
+    #[prost(int64, tag = "2")]
+    pub results_generated: i64,
+    /// Distinct facts at least one rule fired for
+    #[prost(int64, tag = "3")]
+    pub facts_affected: i64,
+    /// Changes per field name across all rules
+    #[prost(map = "string, int64", tag = "4")]
+    pub fields_changed: ::std::collections::HashMap<::prost::alloc::string::String, i64>,
+    /// Rules that fired, ordered by rule id
+    #[prost(message, repeated, tag = "5")]
+    pub rules: ::prost::alloc::vec::Vec<RuleSummary>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RuleSummary {
+    #[prost(string, tag = "1")]
+    pub rule_id: ::prost::alloc::string::String,
+    #[prost(int64, tag = "2")]
+    pub times_fired: i64,
+    /// Distinct facts the rule fired for
+    #[prost(int64, tag = "3")]
+    pub facts_affected: i64,
+    #[prost(map = "string, int64", tag = "4")]
+    pub fields_changed: ::std::collections::HashMap<::prost::alloc::string::String, i64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HealthResponse {
+    #[prost(string, tag = "1")]
+    pub status: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub version: ::prost::alloc::string::String,
+    #[prost(int64, tag = "3")]
+    pub uptime_seconds: i64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReadinessResponse {
+    /// No check failed and the server is not shutting down
+    #[prost(bool, tag = "1")]
+    pub ready: bool,
+    #[prost(message, repeated, tag = "2")]
+    pub checks: ::prost::alloc::vec::Vec<ReadinessCheckResult>,
+    #[prost(bool, tag = "3")]
+    pub shutting_down: bool,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReadinessCheckResult {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    /// "pass", "warning", "fail" or "unknown"
+    #[prost(string, tag = "2")]
+    pub status: ::prost::alloc::string::String,
+    /// "critical", "high", "medium" or "low"
+    #[prost(string, tag = "3")]
+    pub severity: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub message: ::prost::alloc::string::String,
+    #[prost(string, repeated, tag = "5")]
+    pub recommendations: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum SimpleOperator {
+    Equal = 0,
+    NotEqual = 1,
+    GreaterThan = 2,
+    LessThan = 3,
+    GreaterThanOrEqual = 4,
+    LessThanOrEqual = 5,
+    Contains = 6,
+    StartsWith = 7,
+    EndsWith = 8,
+    Within = 9,
+    OlderThan = 10,
+    Overlaps = 11,
+    Matches = 12,
+    ContainsIgnoreCase = 13,
+    In = 14,
+    NotIn = 15,
+}
+impl SimpleOperator {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Equal => "SIMPLE_OPERATOR_EQUAL",
+            Self::NotEqual => "SIMPLE_OPERATOR_NOT_EQUAL",
+            Self::GreaterThan => "SIMPLE_OPERATOR_GREATER_THAN",
+            Self::LessThan => "SIMPLE_OPERATOR_LESS_THAN",
+            Self::GreaterThanOrEqual => "SIMPLE_OPERATOR_GREATER_THAN_OR_EQUAL",
+            Self::LessThanOrEqual => "SIMPLE_OPERATOR_LESS_THAN_OR_EQUAL",
+            Self::Contains => "SIMPLE_OPERATOR_CONTAINS",
+            Self::StartsWith => "SIMPLE_OPERATOR_STARTS_WITH",
+            Self::EndsWith => "SIMPLE_OPERATOR_ENDS_WITH",
+            Self::Within => "SIMPLE_OPERATOR_WITHIN",
+            Self::OlderThan => "SIMPLE_OPERATOR_OLDER_THAN",
+            Self::Overlaps => "SIMPLE_OPERATOR_OVERLAPS",
+            Self::Matches => "SIMPLE_OPERATOR_MATCHES",
+            Self::ContainsIgnoreCase => "SIMPLE_OPERATOR_CONTAINS_IGNORE_CASE",
+            Self::In => "SIMPLE_OPERATOR_IN",
+            Self::NotIn => "SIMPLE_OPERATOR_NOT_IN",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "SIMPLE_OPERATOR_EQUAL" => Some(Self::Equal),
+            "SIMPLE_OPERATOR_NOT_EQUAL" => Some(Self::NotEqual),
+            "SIMPLE_OPERATOR_GREATER_THAN" => Some(Self::GreaterThan),
+            "SIMPLE_OPERATOR_LESS_THAN" => Some(Self::LessThan),
+            "SIMPLE_OPERATOR_GREATER_THAN_OR_EQUAL" => Some(Self::GreaterThanOrEqual),
+            "SIMPLE_OPERATOR_LESS_THAN_OR_EQUAL" => Some(Self::LessThanOrEqual),
+            "SIMPLE_OPERATOR_CONTAINS" => Some(Self::Contains),
+            "SIMPLE_OPERATOR_STARTS_WITH" => Some(Self::StartsWith),
+            "SIMPLE_OPERATOR_ENDS_WITH" => Some(Self::EndsWith),
+            "SIMPLE_OPERATOR_WITHIN" => Some(Self::Within),
+            "SIMPLE_OPERATOR_OLDER_THAN" => Some(Self::OlderThan),
+            "SIMPLE_OPERATOR_OVERLAPS" => Some(Self::Overlaps),
+            "SIMPLE_OPERATOR_MATCHES" => Some(Self::Matches),
+            "SIMPLE_OPERATOR_CONTAINS_IGNORE_CASE" => Some(Self::ContainsIgnoreCase),
+            "SIMPLE_OPERATOR_IN" => Some(Self::In),
+            "SIMPLE_OPERATOR_NOT_IN" => Some(Self::NotIn),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum LogicalOperator {
+    And = 0,
+    Or = 1,
+    Not = 2,
+}
+impl LogicalOperator {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::And => "LOGICAL_OPERATOR_AND",
+            Self::Or => "LOGICAL_OPERATOR_OR",
+            Self::Not => "LOGICAL_OPERATOR_NOT",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "LOGICAL_OPERATOR_AND" => Some(Self::And),
+            "LOGICAL_OPERATOR_OR" => Some(Self::Or),
+            "LOGICAL_OPERATOR_NOT" => Some(Self::Not),
+            _ => None,
+        }
+    }
+}
+/// Generated server implementations.
+pub mod rules_engine_service_server {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with RulesEngineServiceServer.
+    #[async_trait]
+    pub trait RulesEngineService: std::marker::Send + std::marker::Sync + 'static {
+        /// Two-phase processing: compile rules first, then stream facts
+        async fn compile_rules(
+            &self,
+            request: tonic::Request<super::CompileRulesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CompileRulesResponse>,
+            tonic::Status,
+        >;
+        /// Server streaming response type for the ProcessFactsStream method.
+        type ProcessFactsStreamStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::RuleExecutionResult, tonic::Status>,
+            >
+            + std::marker::Send
+            + 'static;
+        async fn process_facts_stream(
+            &self,
+            request: tonic::Request<tonic::Streaming<super::ProcessFactsStreamRequest>>,
+        ) -> std::result::Result<
+            tonic::Response<Self::ProcessFactsStreamStream>,
+            tonic::Status,
+        >;
+        /// Server streaming response type for the IngestFacts method.
+        type IngestFactsStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::IngestFactsResponse, tonic::Status>,
+            >
+            + std::marker::Send
+            + 'static;
+        /// Continuous ingestion: facts are processed as they arrive and results streamed back.
+        /// The server stops reading facts while responses are unread, so a slow reader
+        /// applies backpressure to the sender instead of growing server memory.
+        async fn ingest_facts(
+            &self,
+            request: tonic::Request<tonic::Streaming<super::IngestFactsRequest>>,
+        ) -> std::result::Result<
+            tonic::Response<Self::IngestFactsStream>,
+            tonic::Status,
+        >;
+        /// Server streaming response type for the DebugRules method.
+        type DebugRulesStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::DebugState, tonic::Status>,
+            >
+            + std::marker::Send
+            + 'static;
+        /// Step-debugger over a copy of a session's rules: queued facts are processed one
+        /// at a time, pausing on rule and node breakpoints, and every request is answered
+        /// with the alpha and beta memory contents. Debugging never changes the session.
+        async fn debug_rules(
+            &self,
+            request: tonic::Request<tonic::Streaming<super::DebugRequest>>,
+        ) -> std::result::Result<
+            tonic::Response<Self::DebugRulesStream>,
+            tonic::Status,
+        >;
+        /// Rule management within a compiled session. Every change bumps the session's
+        /// ruleset version, which is reported on each result it produces.
+        async fn create_rule(
+            &self,
+            request: tonic::Request<super::CreateRuleRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::RuleMutationResponse>,
+            tonic::Status,
+        >;
+        async fn update_rule(
+            &self,
+            request: tonic::Request<super::UpdateRuleRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::RuleMutationResponse>,
+            tonic::Status,
+        >;
+        async fn delete_rule(
+            &self,
+            request: tonic::Request<super::DeleteRuleRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::RuleMutationResponse>,
+            tonic::Status,
+        >;
+        async fn list_rules(
+            &self,
+            request: tonic::Request<super::ListRulesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListRulesResponse>,
+            tonic::Status,
+        >;
+        async fn get_rule_stats(
+            &self,
+            request: tonic::Request<super::GetRuleStatsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetRuleStatsResponse>,
+            tonic::Status,
+        >;
+        async fn get_rule_graph(
+            &self,
+            request: tonic::Request<super::GetRuleGraphRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetRuleGraphResponse>,
+            tonic::Status,
+        >;
+        /// Session globals (read by calculator inputs mapped as `@name`) and reference tables
+        /// (read by `in "table"` conditions and `table[field]` calculator inputs). Setting one
+        /// creates the session if needed, so both can be pushed before CompileRules, and
+        /// applies to the session's existing rules.
+        async fn set_session_globals(
+            &self,
+            request: tonic::Request<super::SetSessionGlobalsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SessionGlobalsResponse>,
+            tonic::Status,
+        >;
+        async fn get_session_globals(
+            &self,
+            request: tonic::Request<super::GetSessionGlobalsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SessionGlobalsResponse>,
+            tonic::Status,
+        >;
+        async fn set_reference_table(
+            &self,
+            request: tonic::Request<super::SetReferenceTableRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SetReferenceTableResponse>,
+            tonic::Status,
+        >;
+        async fn get_reference_table(
+            &self,
+            request: tonic::Request<super::GetReferenceTableRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetReferenceTableResponse>,
+            tonic::Status,
+        >;
+        /// Enable or disable rule groups for a session, creating the session if needed
+        async fn set_session_rule_groups(
+            &self,
+            request: tonic::Request<super::SetSessionRuleGroupsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SessionRuleGroupsResponse>,
+            tonic::Status,
+        >;
+        /// Typed fact schema for a fact type, creating the session if needed. Ingested facts
+        /// of the type whose fields do not match are rejected with their violations.
+        async fn set_fact_schema(
+            &self,
+            request: tonic::Request<super::SetFactSchemaRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SetFactSchemaResponse>,
+            tonic::Status,
+        >;
+        /// Server streaming response type for the ProcessWithRulesStream method.
+        type ProcessWithRulesStreamStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::ProcessingResponse, tonic::Status>,
+            >
+            + std::marker::Send
+            + 'static;
+        /// Alternative: single-call with rules validation before fact streaming
+        async fn process_with_rules_stream(
+            &self,
+            request: tonic::Request<super::ProcessWithRulesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::ProcessWithRulesStreamStream>,
+            tonic::Status,
+        >;
+        /// Stateless evaluation: the request's rules are compiled into an engine drawn from a
+        /// pool of pre-initialized engines, its facts evaluated and the engine reset for reuse, so
+        /// integration tests and what-if tools need no session
+        async fn evaluate(
+            &self,
+            request: tonic::Request<super::EvaluateRequest>,
+        ) -> std::result::Result<tonic::Response<super::EvaluateResponse>, tonic::Status>;
+        /// Server streaming response type for the ProcessFactsBatch method.
+        type ProcessFactsBatchStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::ProcessingStatus, tonic::Status>,
+            >
+            + std::marker::Send
+            + 'static;
+        /// Batch processing with status updates
+        async fn process_facts_batch(
+            &self,
+            request: tonic::Request<super::ProcessFactsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::ProcessFactsBatchStream>,
+            tonic::Status,
+        >;
+        /// Server streaming response type for the EvaluateRulesetStream method.
+        type EvaluateRulesetStreamStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::RuleExecutionResult, tonic::Status>,
+            >
+            + std::marker::Send
+            + 'static;
+        /// Cached ruleset processing (streaming)
+        async fn evaluate_ruleset_stream(
+            &self,
+            request: tonic::Request<super::EvaluateRulesetRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::EvaluateRulesetStreamStream>,
+            tonic::Status,
+        >;
+        /// Ruleset management
+        async fn register_ruleset(
+            &self,
+            request: tonic::Request<super::RegisterRulesetRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::RegisterRulesetResponse>,
+            tonic::Status,
+        >;
+        /// Compiled-asset cache administration
+        async fn get_cache_stats(
+            &self,
+            request: tonic::Request<super::CacheStatsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CacheStatsResponse>,
+            tonic::Status,
+        >;
+        async fn purge_cache(
+            &self,
+            request: tonic::Request<super::PurgeCacheRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::PurgeCacheResponse>,
+            tonic::Status,
+        >;
+        /// Health check
+        async fn health_check(
+            &self,
+            request: tonic::Request<()>,
+        ) -> std::result::Result<tonic::Response<super::HealthResponse>, tonic::Status>;
+        /// Readiness: the engine self-checks (memory headroom, rule compilation, thread pool)
+        /// with the outcome of each, as the grpc.health.v1 readiness probe sees them
+        async fn readiness_check(
+            &self,
+            request: tonic::Request<()>,
+        ) -> std::result::Result<
+            tonic::Response<super::ReadinessResponse>,
+            tonic::Status,
+        >;
+    }
+    /// Main service definition
+    #[derive(Debug)]
+    pub struct RulesEngineServiceServer<T> {
+        inner: Arc<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    impl<T> RulesEngineServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for RulesEngineServiceServer<T>
+    where
+        T: RulesEngineService,
+        B: Body + std::marker::Send + 'static,
+        B::Error: Into<StdError> + std::marker::Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            match req.uri().path() {
+                "/rules_engine.v1.RulesEngineService/CompileRules" => {
+                    #[allow(non_camel_case_types)]
+                    struct CompileRulesSvc<T: RulesEngineService>(pub Arc<T>);
+                    impl<
+                        T: RulesEngineService,
+                    > tonic::server::UnaryService<super::CompileRulesRequest>
+                    for CompileRulesSvc<T> {
+                        type Response = super::CompileRulesResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CompileRulesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RulesEngineService>::compile_rules(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = CompileRulesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rules_engine.v1.RulesEngineService/ProcessFactsStream" => {
+                    #[allow(non_camel_case_types)]
+                    struct ProcessFactsStreamSvc<T: RulesEngineService>(pub Arc<T>);
+                    impl<
+                        T: RulesEngineService,
+                    > tonic::server::StreamingService<super::ProcessFactsStreamRequest>
+                    for ProcessFactsStreamSvc<T> {
+                        type Response = super::RuleExecutionResult;
+                        type ResponseStream = T::ProcessFactsStreamStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                tonic::Streaming<super::ProcessFactsStreamRequest>,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RulesEngineService>::process_facts_stream(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ProcessFactsStreamSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rules_engine.v1.RulesEngineService/IngestFacts" => {
+                    #[allow(non_camel_case_types)]
+                    struct IngestFactsSvc<T: RulesEngineService>(pub Arc<T>);
+                    impl<
+                        T: RulesEngineService,
+                    > tonic::server::StreamingService<super::IngestFactsRequest>
+                    for IngestFactsSvc<T> {
+                        type Response = super::IngestFactsResponse;
+                        type ResponseStream = T::IngestFactsStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                tonic::Streaming<super::IngestFactsRequest>,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RulesEngineService>::ingest_facts(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = IngestFactsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rules_engine.v1.RulesEngineService/DebugRules" => {
+                    #[allow(non_camel_case_types)]
+                    struct DebugRulesSvc<T: RulesEngineService>(pub Arc<T>);
+                    impl<
+                        T: RulesEngineService,
+                    > tonic::server::StreamingService<super::DebugRequest>
+                    for DebugRulesSvc<T> {
+                        type Response = super::DebugState;
+                        type ResponseStream = T::DebugRulesStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                tonic::Streaming<super::DebugRequest>,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RulesEngineService>::debug_rules(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = DebugRulesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rules_engine.v1.RulesEngineService/CreateRule" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreateRuleSvc<T: RulesEngineService>(pub Arc<T>);
+                    impl<
+                        T: RulesEngineService,
+                    > tonic::server::UnaryService<super::CreateRuleRequest>
+                    for CreateRuleSvc<T> {
+                        type Response = super::RuleMutationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CreateRuleRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RulesEngineService>::create_rule(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = CreateRuleSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rules_engine.v1.RulesEngineService/UpdateRule" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpdateRuleSvc<T: RulesEngineService>(pub Arc<T>);
+                    impl<
+                        T: RulesEngineService,
+                    > tonic::server::UnaryService<super::UpdateRuleRequest>
+                    for UpdateRuleSvc<T> {
+                        type Response = super::RuleMutationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpdateRuleRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RulesEngineService>::update_rule(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = UpdateRuleSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rules_engine.v1.RulesEngineService/DeleteRule" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeleteRuleSvc<T: RulesEngineService>(pub Arc<T>);
+                    impl<
+                        T: RulesEngineService,
+                    > tonic::server::UnaryService<super::DeleteRuleRequest>
+                    for DeleteRuleSvc<T> {
+                        type Response = super::RuleMutationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeleteRuleRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RulesEngineService>::delete_rule(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = DeleteRuleSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rules_engine.v1.RulesEngineService/ListRules" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListRulesSvc<T: RulesEngineService>(pub Arc<T>);
+                    impl<
+                        T: RulesEngineService,
+                    > tonic::server::UnaryService<super::ListRulesRequest>
+                    for ListRulesSvc<T> {
+                        type Response = super::ListRulesResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListRulesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RulesEngineService>::list_rules(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListRulesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rules_engine.v1.RulesEngineService/GetRuleStats" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetRuleStatsSvc<T: RulesEngineService>(pub Arc<T>);
+                    impl<
+                        T: RulesEngineService,
+                    > tonic::server::UnaryService<super::GetRuleStatsRequest>
+                    for GetRuleStatsSvc<T> {
+                        type Response = super::GetRuleStatsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetRuleStatsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RulesEngineService>::get_rule_stats(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetRuleStatsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rules_engine.v1.RulesEngineService/GetRuleGraph" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetRuleGraphSvc<T: RulesEngineService>(pub Arc<T>);
+                    impl<
+                        T: RulesEngineService,
+                    > tonic::server::UnaryService<super::GetRuleGraphRequest>
+                    for GetRuleGraphSvc<T> {
+                        type Response = super::GetRuleGraphResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetRuleGraphRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RulesEngineService>::get_rule_graph(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetRuleGraphSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rules_engine.v1.RulesEngineService/SetSessionGlobals" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetSessionGlobalsSvc<T: RulesEngineService>(pub Arc<T>);
+                    impl<
+                        T: RulesEngineService,
+                    > tonic::server::UnaryService<super::SetSessionGlobalsRequest>
+                    for SetSessionGlobalsSvc<T> {
+                        type Response = super::SessionGlobalsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetSessionGlobalsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RulesEngineService>::set_session_globals(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SetSessionGlobalsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rules_engine.v1.RulesEngineService/GetSessionGlobals" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetSessionGlobalsSvc<T: RulesEngineService>(pub Arc<T>);
+                    impl<
+                        T: RulesEngineService,
+                    > tonic::server::UnaryService<super::GetSessionGlobalsRequest>
+                    for GetSessionGlobalsSvc<T> {
+                        type Response = super::SessionGlobalsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetSessionGlobalsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RulesEngineService>::get_session_globals(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetSessionGlobalsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rules_engine.v1.RulesEngineService/SetReferenceTable" => {
                     #[allow(non_camel_case_types)]
-                    struct CompileRulesSvc<T: RulesEngineService>(pub Arc<T>);
+                    struct SetReferenceTableSvc<T: RulesEngineService>(pub Arc<T>);
                     impl<
                         T: RulesEngineService,
-                    > tonic::server::UnaryService<super::CompileRulesRequest>
-                    for CompileRulesSvc<T> {
-                        type Response = super::CompileRulesResponse;
+                    > tonic::server::UnaryService<super::SetReferenceTableRequest>
+                    for SetReferenceTableSvc<T> {
+                        type Response = super::SetReferenceTableResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::CompileRulesRequest>,
+                            request: tonic::Request<super::SetReferenceTableRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as RulesEngineService>::compile_rules(&inner, request)
+                                <T as RulesEngineService>::set_reference_table(&inner, request)
                                     .await
                             };
                             Box::pin(fut)
@@ -678,7 +2142,7 @@ pub mod rules_engine_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = CompileRulesSvc(inner);
+                        let method = SetReferenceTableSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -694,31 +2158,25 @@ pub mod rules_engine_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/rules_engine.v1.RulesEngineService/ProcessFactsStream" => {
+                "/rules_engine.v1.RulesEngineService/GetReferenceTable" => {
                     #[allow(non_camel_case_types)]
-                    struct ProcessFactsStreamSvc<T: RulesEngineService>(pub Arc<T>);
+                    struct GetReferenceTableSvc<T: RulesEngineService>(pub Arc<T>);
                     impl<
                         T: RulesEngineService,
-                    > tonic::server::StreamingService<super::ProcessFactsStreamRequest>
-                    for ProcessFactsStreamSvc<T> {
-                        type Response = super::RuleExecutionResult;
-                        type ResponseStream = T::ProcessFactsStreamStream;
+                    > tonic::server::UnaryService<super::GetReferenceTableRequest>
+                    for GetReferenceTableSvc<T> {
+                        type Response = super::GetReferenceTableResponse;
                         type Future = BoxFuture<
-                            tonic::Response<Self::ResponseStream>,
+                            tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<
-                                tonic::Streaming<super::ProcessFactsStreamRequest>,
-                            >,
+                            request: tonic::Request<super::GetReferenceTableRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as RulesEngineService>::process_facts_stream(
-                                        &inner,
-                                        request,
-                                    )
+                                <T as RulesEngineService>::get_reference_table(&inner, request)
                                     .await
                             };
                             Box::pin(fut)
@@ -730,7 +2188,7 @@ pub mod rules_engine_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = ProcessFactsStreamSvc(inner);
+                        let method = GetReferenceTableSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -741,7 +2199,99 @@ pub mod rules_engine_service_server {
                                 max_decoding_message_size,
                                 max_encoding_message_size,
                             );
-                        let res = grpc.streaming(method, req).await;
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rules_engine.v1.RulesEngineService/SetSessionRuleGroups" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetSessionRuleGroupsSvc<T: RulesEngineService>(pub Arc<T>);
+                    impl<
+                        T: RulesEngineService,
+                    > tonic::server::UnaryService<super::SetSessionRuleGroupsRequest>
+                    for SetSessionRuleGroupsSvc<T> {
+                        type Response = super::SessionRuleGroupsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetSessionRuleGroupsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RulesEngineService>::set_session_rule_groups(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SetSessionRuleGroupsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rules_engine.v1.RulesEngineService/SetFactSchema" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetFactSchemaSvc<T: RulesEngineService>(pub Arc<T>);
+                    impl<
+                        T: RulesEngineService,
+                    > tonic::server::UnaryService<super::SetFactSchemaRequest>
+                    for SetFactSchemaSvc<T> {
+                        type Response = super::SetFactSchemaResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetFactSchemaRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RulesEngineService>::set_fact_schema(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SetFactSchemaSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
                         Ok(res)
                     };
                     Box::pin(fut)
@@ -797,6 +2347,52 @@ pub mod rules_engine_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/rules_engine.v1.RulesEngineService/Evaluate" => {
+                    #[allow(non_camel_case_types)]
+                    struct EvaluateSvc<T: RulesEngineService>(pub Arc<T>);
+                    impl<
+                        T: RulesEngineService,
+                    > tonic::server::UnaryService<super::EvaluateRequest>
+                    for EvaluateSvc<T> {
+                        type Response = super::EvaluateResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::EvaluateRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RulesEngineService>::evaluate(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = EvaluateSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/rules_engine.v1.RulesEngineService/ProcessFactsBatch" => {
                     #[allow(non_camel_case_types)]
                     struct ProcessFactsBatchSvc<T: RulesEngineService>(pub Arc<T>);
@@ -944,6 +2540,98 @@ pub mod rules_engine_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/rules_engine.v1.RulesEngineService/GetCacheStats" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetCacheStatsSvc<T: RulesEngineService>(pub Arc<T>);
+                    impl<
+                        T: RulesEngineService,
+                    > tonic::server::UnaryService<super::CacheStatsRequest>
+                    for GetCacheStatsSvc<T> {
+                        type Response = super::CacheStatsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CacheStatsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RulesEngineService>::get_cache_stats(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetCacheStatsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rules_engine.v1.RulesEngineService/PurgeCache" => {
+                    #[allow(non_camel_case_types)]
+                    struct PurgeCacheSvc<T: RulesEngineService>(pub Arc<T>);
+                    impl<
+                        T: RulesEngineService,
+                    > tonic::server::UnaryService<super::PurgeCacheRequest>
+                    for PurgeCacheSvc<T> {
+                        type Response = super::PurgeCacheResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PurgeCacheRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RulesEngineService>::purge_cache(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = PurgeCacheSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/rules_engine.v1.RulesEngineService/HealthCheck" => {
                     #[allow(non_camel_case_types)]
                     struct HealthCheckSvc<T: RulesEngineService>(pub Arc<T>);
@@ -985,6 +2673,47 @@ pub mod rules_engine_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/rules_engine.v1.RulesEngineService/ReadinessCheck" => {
+                    #[allow(non_camel_case_types)]
+                    struct ReadinessCheckSvc<T: RulesEngineService>(pub Arc<T>);
+                    impl<T: RulesEngineService> tonic::server::UnaryService<()>
+                    for ReadinessCheckSvc<T> {
+                        type Response = super::ReadinessResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(&mut self, request: tonic::Request<()>) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RulesEngineService>::readiness_check(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ReadinessCheckSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         let mut response = http::Response::new(empty_body());
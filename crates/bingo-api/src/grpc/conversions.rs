@@ -1,27 +1,65 @@
 use anyhow::{Result, anyhow};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
+use crate::asset_cache::TenantCacheStats as AssetTenantCacheStats;
 use crate::generated::*;
 use bingo_core::{
     Action as CoreAction, ActionResult as CoreActionResult, ActionType as CoreActionType,
-    Condition as CoreCondition, Fact as CoreFact, FactData as CoreFactData,
-    FactValue as CoreFactValue, LogicalOperator as CoreLogicalOperator, Operator, Rule as CoreRule,
-    RuleExecutionResult as CoreResult,
+    BatchSummary as CoreBatchSummary, CheckResult, CheckSeverity, CheckStatus,
+    Condition as CoreCondition, DebugBreakpoint, DebugStep as CoreDebugStep, EvaluationDate,
+    Fact as CoreFact, FactData as CoreFactData, FactPayloadFormat, FactProblem,
+    FactSchema as CoreFactSchema, FactValidationReport, FactValue as CoreFactValue,
+    LogicalOperator as CoreLogicalOperator, NetworkMemoryContents, Operator, ReferenceTable,
+    Rule as CoreRule, RuleExecutionResult as CoreResult, RuleFlowGraph, RuleMetadata,
+    RuleStats as CoreRuleStats, deserialize_fact_fields, types::FieldType,
 };
 
-pub fn from_proto_fact(proto_fact: Fact) -> Result<CoreFact> {
-    let mut fields = HashMap::new();
+/// Content type of facts whose fields are in the `data` map
+pub const PROTOBUF_CONTENT_TYPE: &str = "application/protobuf";
 
-    for (key, value) in proto_fact.data {
-        let core_value = from_proto_value(value)?;
-        fields.insert(key, core_value);
-    }
+/// Convert a fact, decoding its fields from `payload` when its content type names
+/// JSON or MessagePack
+pub fn from_proto_fact(proto_fact: Fact) -> Result<CoreFact> {
+    let content_type = proto_fact.content_type.trim();
+    let fields = if content_type.is_empty() || content_type == PROTOBUF_CONTENT_TYPE {
+        if !proto_fact.payload.is_empty() {
+            return Err(anyhow!(
+                "Fact '{}' has a payload but no JSON or MessagePack content type",
+                proto_fact.id
+            ));
+        }
+        proto_fact
+            .data
+            .into_iter()
+            .map(|(key, value)| Ok((key, from_proto_value(value)?)))
+            .collect::<Result<HashMap<_, _>>>()?
+    } else {
+        let format = FactPayloadFormat::from_content_type(content_type).ok_or_else(|| {
+            anyhow!(
+                "Fact '{}' has unsupported content type '{content_type}'",
+                proto_fact.id
+            )
+        })?;
+        if !proto_fact.data.is_empty() {
+            return Err(anyhow!(
+                "Fact '{}' sets both data and a {content_type} payload",
+                proto_fact.id
+            ));
+        }
+        deserialize_fact_fields(format, &proto_fact.payload).map_err(|e| {
+            anyhow!(
+                "Fact '{}' has an invalid {content_type} payload: {e}",
+                proto_fact.id
+            )
+        })?
+    };
 
     Ok(CoreFact {
         id: proto_fact.id.parse().unwrap_or(0),
         external_id: Some(proto_fact.id.clone()),
         timestamp: chrono::DateTime::from_timestamp(proto_fact.created_at, 0)
             .unwrap_or_else(chrono::Utc::now),
+        fact_type: Some(proto_fact.fact_type).filter(|fact_type| !fact_type.is_empty()),
         data: CoreFactData { fields },
     })
 }
@@ -32,6 +70,10 @@ pub fn from_proto_value(value: Value) -> Result<CoreFactValue> {
         Some(value::Value::NumberValue(n)) => Ok(CoreFactValue::Float(n)),
         Some(value::Value::BoolValue(b)) => Ok(CoreFactValue::Boolean(b)),
         Some(value::Value::IntValue(i)) => Ok(CoreFactValue::Integer(i)),
+        Some(value::Value::DecimalValue(d)) => d
+            .parse()
+            .map(CoreFactValue::Decimal)
+            .map_err(|e| anyhow!("Invalid decimal value '{d}': {e}")),
         None => Ok(CoreFactValue::Null),
     }
 }
@@ -41,8 +83,12 @@ pub fn to_proto_value(core_value: &CoreFactValue) -> Value {
         CoreFactValue::String(s) => value::Value::StringValue(s.clone()),
         CoreFactValue::Integer(i) => value::Value::IntValue(*i),
         CoreFactValue::Float(f) => value::Value::NumberValue(*f),
+        CoreFactValue::Decimal(d) => value::Value::DecimalValue(d.to_string()),
         CoreFactValue::Boolean(b) => value::Value::BoolValue(*b),
         CoreFactValue::Date(dt) => value::Value::StringValue(dt.to_rfc3339()),
+        CoreFactValue::Duration(_) | CoreFactValue::Interval { .. } => {
+            value::Value::StringValue(core_value.to_string())
+        }
         CoreFactValue::Null => value::Value::StringValue("null".to_string()),
         CoreFactValue::Array(_) | CoreFactValue::Object(_) => {
             // For complex types, serialize to JSON string for now
@@ -53,6 +99,82 @@ pub fn to_proto_value(core_value: &CoreFactValue) -> Value {
     Value { value: Some(value) }
 }
 
+/// Convert reference table rows, where a row without a value is a key-only row
+pub fn from_proto_reference_table(entries: Vec<ReferenceTableEntry>) -> Result<ReferenceTable> {
+    let rows = entries
+        .into_iter()
+        .map(|entry| {
+            let key = entry.key.ok_or_else(|| anyhow!("Reference table row has no key"))?;
+            let value = match entry.value {
+                Some(value) => from_proto_value(value)?,
+                None => CoreFactValue::Boolean(true),
+            };
+            Ok((from_proto_value(key)?, value))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(ReferenceTable::from_entries(rows))
+}
+
+/// Convert a fact schema; fields of a kind without bounds accept any value of the kind
+pub fn from_proto_fact_schema(request: &SetFactSchemaRequest) -> Result<CoreFactSchema> {
+    if request.fact_type.is_empty() {
+        return Err(anyhow!("fact_type is required"));
+    }
+    let mut schema = CoreFactSchema::new(request.fact_type.clone());
+    for field in &request.fields {
+        let kind = schema_field::Kind::try_from(field.kind)
+            .map_err(|_| anyhow!("Field '{}' has unknown kind {}", field.name, field.kind))?;
+        let field_type = match kind {
+            schema_field::Kind::Text => FieldType::Text { max_length: usize::MAX, pattern: None },
+            schema_field::Kind::Integer => FieldType::Integer { min: i64::MIN, max: i64::MAX },
+            schema_field::Kind::Decimal => FieldType::Decimal { precision: 0, scale: 0 },
+            schema_field::Kind::Boolean => FieldType::Boolean,
+            schema_field::Kind::Date => FieldType::Date { format: String::new() },
+            schema_field::Kind::Enum if field.enum_values.is_empty() => {
+                return Err(anyhow!("Enum field '{}' has no enum_values", field.name));
+            }
+            schema_field::Kind::Enum => FieldType::Enum { values: field.enum_values.clone() },
+        };
+        schema = if field.required {
+            schema.required(field.name.clone(), field_type)
+        } else {
+            schema.optional(field.name.clone(), field_type)
+        };
+    }
+    Ok(if request.closed {
+        schema.closed()
+    } else {
+        schema
+    })
+}
+
+pub fn to_proto_fact_violations(report: &FactValidationReport) -> Vec<FactViolation> {
+    report
+        .problems
+        .iter()
+        .map(|problem| FactViolation {
+            field: problem.field().to_string(),
+            code: match problem {
+                FactProblem::MissingField { .. } => "missing_field",
+                FactProblem::TypeMismatch { .. } => "type_mismatch",
+                FactProblem::UnknownField { .. } => "unknown_field",
+            }
+            .to_string(),
+            message: problem.to_string(),
+        })
+        .collect()
+}
+
+pub fn to_proto_reference_table(table: &ReferenceTable) -> Vec<ReferenceTableEntry> {
+    table
+        .iter()
+        .map(|(key, value)| ReferenceTableEntry {
+            key: Some(to_proto_value(key)),
+            value: Some(to_proto_value(value)),
+        })
+        .collect()
+}
+
 pub fn to_proto_fact(core_fact: &CoreFact) -> Fact {
     let mut data = HashMap::new();
 
@@ -64,6 +186,8 @@ pub fn to_proto_fact(core_fact: &CoreFact) -> Fact {
         id: core_fact.external_id.clone().unwrap_or_else(|| core_fact.id.to_string()),
         data,
         created_at: core_fact.timestamp.timestamp(),
+        fact_type: core_fact.fact_type.clone().unwrap_or_default(),
+        ..Default::default()
     }
 }
 
@@ -86,7 +210,159 @@ pub fn from_proto_rule(proto_rule: Rule) -> Result<CoreRule> {
         .parse::<u64>()
         .map_err(|_| anyhow!("Invalid rule ID: {}", proto_rule.id))?;
 
-    Ok(CoreRule { id, name: proto_rule.name, conditions, actions })
+    let metadata = RuleMetadata {
+        description: Some(proto_rule.description).filter(|text| !text.is_empty()),
+        owner: Some(proto_rule.owner).filter(|owner| !owner.is_empty()),
+        link: Some(proto_rule.link).filter(|link| !link.is_empty()),
+        effective_date: from_proto_date(proto_rule.effective_date, "effective date")?,
+        expiry_date: from_proto_date(proto_rule.expiry_date, "expiry date")?,
+        rule_group: Some(proto_rule.rule_group).filter(|group| !group.is_empty()),
+        activation_group: Some(proto_rule.activation_group).filter(|group| !group.is_empty()),
+    };
+
+    Ok(CoreRule { id, name: proto_rule.name, conditions, actions, metadata })
+}
+
+/// Rule date from Unix seconds, where 0 leaves it unset
+fn from_proto_date(seconds: i64, what: &str) -> Result<Option<chrono::DateTime<chrono::Utc>>> {
+    if seconds == 0 {
+        return Ok(None);
+    }
+    chrono::DateTime::from_timestamp(seconds, 0)
+        .map(Some)
+        .ok_or_else(|| anyhow!("Invalid rule {what}: {seconds}"))
+}
+
+/// Date facts are evaluated as of, from Unix seconds where 0 means the clock, or each
+/// fact's own timestamp when `at_event_time` is set
+pub fn from_proto_evaluation_date(seconds: i64, at_event_time: bool) -> Result<EvaluationDate> {
+    if at_event_time {
+        if seconds != 0 {
+            return Err(anyhow!(
+                "evaluation_date cannot be set when evaluating at event time"
+            ));
+        }
+        return Ok(EvaluationDate::EventTime);
+    }
+    if seconds == 0 {
+        return Ok(EvaluationDate::Now);
+    }
+    chrono::DateTime::from_timestamp(seconds, 0)
+        .map(EvaluationDate::At)
+        .ok_or_else(|| anyhow!("Invalid evaluation date: {seconds}"))
+}
+
+/// Convert a core rule back to its proto definition
+///
+/// Fails for conditions and actions the proto schema cannot express, which only
+/// occur in rules that were not defined through the API.
+pub fn to_proto_rule(core_rule: &CoreRule) -> Result<Rule> {
+    let conditions = core_rule
+        .conditions
+        .iter()
+        .map(to_proto_condition)
+        .collect::<Result<Vec<_>>>()?;
+
+    let actions = core_rule.actions.iter().map(to_proto_action).collect::<Result<Vec<_>>>()?;
+
+    let metadata = &core_rule.metadata;
+    Ok(Rule {
+        id: core_rule.id.to_string(),
+        name: core_rule.name.clone(),
+        description: metadata.description.clone().unwrap_or_default(),
+        conditions,
+        actions,
+        enabled: true,
+        owner: metadata.owner.clone().unwrap_or_default(),
+        link: metadata.link.clone().unwrap_or_default(),
+        effective_date: metadata.effective_date.map_or(0, |date| date.timestamp()),
+        expiry_date: metadata.expiry_date.map_or(0, |date| date.timestamp()),
+        rule_group: metadata.rule_group.clone().unwrap_or_default(),
+        activation_group: metadata.activation_group.clone().unwrap_or_default(),
+        ..Default::default()
+    })
+}
+
+pub fn to_proto_condition(core_condition: &CoreCondition) -> Result<Condition> {
+    let condition_type = match core_condition {
+        CoreCondition::Simple { field, operator, value } => {
+            let operator = match operator {
+                Operator::Equal => SimpleOperator::Equal,
+                Operator::NotEqual => SimpleOperator::NotEqual,
+                Operator::GreaterThan => SimpleOperator::GreaterThan,
+                Operator::LessThan => SimpleOperator::LessThan,
+                Operator::GreaterThanOrEqual => SimpleOperator::GreaterThanOrEqual,
+                Operator::LessThanOrEqual => SimpleOperator::LessThanOrEqual,
+                Operator::Contains => SimpleOperator::Contains,
+                Operator::StartsWith => SimpleOperator::StartsWith,
+                Operator::EndsWith => SimpleOperator::EndsWith,
+                Operator::Within => SimpleOperator::Within,
+                Operator::OlderThan => SimpleOperator::OlderThan,
+                Operator::Overlaps => SimpleOperator::Overlaps,
+                Operator::Matches => SimpleOperator::Matches,
+                Operator::ContainsIgnoreCase => SimpleOperator::ContainsIgnoreCase,
+                Operator::In => SimpleOperator::In,
+                Operator::NotIn => SimpleOperator::NotIn,
+            };
+
+            condition::ConditionType::Simple(SimpleCondition {
+                field: field.clone(),
+                operator: operator as i32,
+                value: Some(to_proto_value(value)),
+            })
+        }
+        CoreCondition::Complex { operator, conditions } => {
+            let operator = match operator {
+                CoreLogicalOperator::And => LogicalOperator::And,
+                CoreLogicalOperator::Or => LogicalOperator::Or,
+                CoreLogicalOperator::Not => LogicalOperator::Not,
+            };
+
+            condition::ConditionType::Complex(ComplexCondition {
+                operator: operator as i32,
+                conditions: conditions
+                    .iter()
+                    .map(to_proto_condition)
+                    .collect::<Result<Vec<_>>>()?,
+            })
+        }
+        other => {
+            return Err(anyhow!(
+                "Condition cannot be expressed in the API: {other:?}"
+            ));
+        }
+    };
+
+    Ok(Condition { condition_type: Some(condition_type) })
+}
+
+pub fn to_proto_action(core_action: &CoreAction) -> Result<Action> {
+    let action_type = match &core_action.action_type {
+        CoreActionType::CreateFact { data } => action::ActionType::CreateFact(CreateFactAction {
+            fields: data
+                .fields
+                .iter()
+                .map(|(key, value)| (key.clone(), to_proto_value(value)))
+                .collect(),
+        }),
+        CoreActionType::CallCalculator { calculator_name, input_mapping, output_field } => {
+            action::ActionType::CallCalculator(CallCalculatorAction {
+                calculator_name: calculator_name.clone(),
+                input_mapping: input_mapping.clone(),
+                output_field: output_field.clone(),
+            })
+        }
+        CoreActionType::Formula { expression, output_field } => {
+            action::ActionType::Formula(FormulaAction {
+                formula: expression.clone(),
+                variable_mapping: HashMap::new(),
+                output_field: output_field.clone(),
+            })
+        }
+        other => return Err(anyhow!("Action cannot be expressed in the API: {other:?}")),
+    };
+
+    Ok(Action { action_type: Some(action_type) })
 }
 
 pub fn from_proto_condition(proto_condition: Condition) -> Result<CoreCondition> {
@@ -100,8 +376,15 @@ pub fn from_proto_condition(proto_condition: Condition) -> Result<CoreCondition>
                 SimpleOperator::GreaterThanOrEqual => Operator::GreaterThanOrEqual,
                 SimpleOperator::LessThanOrEqual => Operator::LessThanOrEqual,
                 SimpleOperator::Contains => Operator::Contains,
-                SimpleOperator::StartsWith => Operator::Contains, // Map to available operator
-                SimpleOperator::EndsWith => Operator::Contains,   // Map to available operator
+                SimpleOperator::StartsWith => Operator::StartsWith,
+                SimpleOperator::EndsWith => Operator::EndsWith,
+                SimpleOperator::Within => Operator::Within,
+                SimpleOperator::OlderThan => Operator::OlderThan,
+                SimpleOperator::Overlaps => Operator::Overlaps,
+                SimpleOperator::Matches => Operator::Matches,
+                SimpleOperator::ContainsIgnoreCase => Operator::ContainsIgnoreCase,
+                SimpleOperator::In => Operator::In,
+                SimpleOperator::NotIn => Operator::NotIn,
             };
 
             let value = simple.value.ok_or_else(|| anyhow!("Missing value in simple condition"))?;
@@ -157,12 +440,21 @@ pub fn from_proto_action(proto_action: Action) -> Result<CoreAction> {
     }
 }
 
-pub fn to_proto_result(core_result: CoreResult) -> Result<RuleExecutionResult> {
-    // Create a dummy fact since the core result only has fact_id
-    let dummy_fact = Fact {
-        id: core_result.fact_id.to_string(),
+pub fn to_proto_result(
+    core_result: CoreResult,
+    ruleset_version: u64,
+) -> Result<RuleExecutionResult> {
+    // The matched fact is identified by the client's ID; its fields are not repeated
+    let external_id = core_result.external_id.clone().unwrap_or_default();
+    let created_at = core_result.fact_timestamp.unwrap_or_else(chrono::Utc::now);
+    let matched_fact = Fact {
+        id: core_result
+            .external_id
+            .clone()
+            .unwrap_or_else(|| core_result.fact_id.to_string()),
         data: HashMap::new(),
-        created_at: chrono::Utc::now().timestamp(),
+        created_at: created_at.timestamp(),
+        ..Default::default()
     };
 
     let action_results = core_result
@@ -173,14 +465,26 @@ pub fn to_proto_result(core_result: CoreResult) -> Result<RuleExecutionResult> {
 
     let mut metadata = HashMap::new();
     metadata.insert("fact_id".to_string(), core_result.fact_id.to_string());
+    metadata.insert("fact_created_at".to_string(), created_at.to_rfc3339());
+    if !external_id.is_empty() {
+        metadata.insert("external_id".to_string(), external_id.clone());
+    }
+    if !core_result.field_collisions.is_empty() {
+        metadata.insert(
+            "field_collisions".to_string(),
+            serde_json::to_string(&core_result.field_collisions)?,
+        );
+    }
 
     Ok(RuleExecutionResult {
         rule_id: core_result.rule_id.to_string(),
         rule_name: format!("rule_{}", core_result.rule_id),
-        matched_fact: Some(dummy_fact),
+        matched_fact: Some(matched_fact),
         action_results,
         execution_time_ns: 0,
         metadata,
+        ruleset_version,
+        external_id,
     })
 }
 
@@ -249,6 +553,14 @@ pub fn to_proto_action_result(core_action_result: CoreActionResult) -> Result<Ac
                 "array_appended:{field}=[{appended_value}]"
             ))),
         ),
+        CoreActionResult::ObjectMerged { field, merged_keys, .. } => (
+            true,
+            String::new(),
+            Some(action_result::Result::FormulaResult(format!(
+                "object_merged:{field}={{{}}}",
+                merged_keys.join(",")
+            ))),
+        ),
         CoreActionResult::NotificationSent { notification_type, .. } => (
             true,
             String::new(),
@@ -256,7 +568,195 @@ pub fn to_proto_action_result(core_action_result: CoreActionResult) -> Result<Ac
                 "notification_sent:{notification_type:?}"
             ))),
         ),
+        CoreActionResult::WebhookDispatched { endpoint, queued } => (
+            *queued,
+            if *queued {
+                String::new()
+            } else {
+                format!("Webhook queue for '{endpoint}' is full")
+            },
+            Some(action_result::Result::FormulaResult(format!(
+                "webhook_dispatched:{endpoint}"
+            ))),
+        ),
     };
 
     Ok(ActionResult { action_id: "action_0".to_string(), success, error_message, result })
 }
+
+pub fn to_proto_batch_summary(summary: &CoreBatchSummary) -> BatchSummary {
+    BatchSummary {
+        facts_processed: summary.facts_processed as i64,
+        results_generated: summary.results_generated as i64,
+        facts_affected: summary.facts_affected as i64,
+        fields_changed: to_proto_field_counts(&summary.fields_changed),
+        rules: summary
+            .rules
+            .iter()
+            .map(|rule| RuleSummary {
+                rule_id: rule.rule_id.to_string(),
+                times_fired: rule.times_fired as i64,
+                facts_affected: rule.facts_affected as i64,
+                fields_changed: to_proto_field_counts(&rule.fields_changed),
+            })
+            .collect(),
+    }
+}
+
+pub fn to_proto_readiness_check(check: &CheckResult) -> ReadinessCheckResult {
+    ReadinessCheckResult {
+        name: check.name.clone(),
+        status: match check.status {
+            CheckStatus::Pass => "pass",
+            CheckStatus::Warning => "warning",
+            CheckStatus::Fail => "fail",
+            CheckStatus::Unknown => "unknown",
+        }
+        .to_string(),
+        severity: match check.severity {
+            CheckSeverity::Critical => "critical",
+            CheckSeverity::High => "high",
+            CheckSeverity::Medium => "medium",
+            CheckSeverity::Low => "low",
+        }
+        .to_string(),
+        message: check.message.clone().unwrap_or_default(),
+        recommendations: check.recommendations.clone(),
+    }
+}
+
+pub fn to_proto_rule_stats(stats: &CoreRuleStats) -> RuleStats {
+    RuleStats {
+        rule_id: stats.rule_id.to_string(),
+        rule_name: stats.rule_name.clone(),
+        evaluations: stats.evaluations,
+        activations: stats.activations,
+        last_fired_at: stats.last_fired.map_or(0, |fired| fired.timestamp_millis()),
+        avg_condition_time_ns: stats
+            .average_condition_time()
+            .map_or(0, |average| average.as_nanos() as u64),
+    }
+}
+
+/// Convert a rule flow graph, rendering it in DOT alongside its nodes and edges
+pub fn to_proto_rule_graph(graph: &RuleFlowGraph, ruleset_version: u64) -> GetRuleGraphResponse {
+    GetRuleGraphResponse {
+        nodes: graph
+            .nodes
+            .iter()
+            .map(|node| RuleGraphNode {
+                rule_id: node.rule_id.to_string(),
+                rule_name: node.name.clone(),
+            })
+            .collect(),
+        edges: graph
+            .edges
+            .iter()
+            .map(|edge| RuleGraphEdge {
+                from_rule_id: edge.from.to_string(),
+                to_rule_id: edge.to.to_string(),
+                via: edge.via.clone(),
+            })
+            .collect(),
+        cycles: graph
+            .cycles()
+            .into_iter()
+            .map(|cycle| RuleCycle {
+                rule_ids: cycle.iter().map(|rule_id| rule_id.to_string()).collect(),
+            })
+            .collect(),
+        dot: graph.to_dot(),
+        ruleset_version,
+    }
+}
+
+fn to_proto_field_counts(fields: &BTreeMap<String, usize>) -> HashMap<String, i64> {
+    fields.iter().map(|(field, count)| (field.clone(), *count as i64)).collect()
+}
+
+pub fn from_proto_debug_breakpoints(breakpoints: DebugBreakpoints) -> Result<Vec<DebugBreakpoint>> {
+    let rules = breakpoints.rule_ids.iter().map(|rule_id| {
+        rule_id
+            .parse()
+            .map(DebugBreakpoint::Rule)
+            .map_err(|_| anyhow!("Invalid rule ID: {rule_id}"))
+    });
+    let alpha_nodes = breakpoints
+        .alpha_node_ids
+        .into_iter()
+        .map(|id| Ok(DebugBreakpoint::AlphaNode(id)));
+    let beta_nodes = breakpoints
+        .beta_node_ids
+        .into_iter()
+        .map(|id| Ok(DebugBreakpoint::BetaNode(id)));
+    rules.chain(alpha_nodes).chain(beta_nodes).collect()
+}
+
+pub fn to_proto_debug_breakpoints(breakpoints: &[DebugBreakpoint]) -> DebugBreakpoints {
+    let mut proto = DebugBreakpoints::default();
+    for breakpoint in breakpoints {
+        match *breakpoint {
+            DebugBreakpoint::Rule(rule_id) => proto.rule_ids.push(rule_id.to_string()),
+            DebugBreakpoint::AlphaNode(node_id) => proto.alpha_node_ids.push(node_id),
+            DebugBreakpoint::BetaNode(node_id) => proto.beta_node_ids.push(node_id),
+        }
+    }
+    proto
+}
+
+pub fn to_proto_debug_step(step: CoreDebugStep, ruleset_version: u64) -> Result<DebugStep> {
+    Ok(DebugStep {
+        step: step.step,
+        fact_id: step.fact.id,
+        fact: Some(to_proto_fact(&step.fact)),
+        alpha_node_ids: step.alpha_nodes,
+        beta_node_ids: step.beta_nodes,
+        breakpoints_hit: Some(to_proto_debug_breakpoints(&step.breakpoints_hit)),
+        results: step
+            .results
+            .into_iter()
+            .map(|result| to_proto_result(result, ruleset_version))
+            .collect::<Result<Vec<_>>>()?,
+    })
+}
+
+pub fn to_proto_alpha_memories(memory: &NetworkMemoryContents) -> Vec<AlphaMemory> {
+    memory
+        .alpha
+        .iter()
+        .map(|alpha| AlphaMemory {
+            node_id: alpha.node_id,
+            condition: alpha.condition.clone(),
+            fact_ids: alpha.fact_ids.clone(),
+        })
+        .collect()
+}
+
+pub fn to_proto_beta_memories(memory: &NetworkMemoryContents) -> Vec<BetaMemory> {
+    memory
+        .beta
+        .iter()
+        .map(|beta| BetaMemory {
+            node_id: beta.node_id,
+            rule_ids: beta.rule_ids.iter().map(u64::to_string).collect(),
+            tokens: beta
+                .tokens
+                .iter()
+                .map(|fact_ids| BetaToken { fact_ids: fact_ids.clone() })
+                .collect(),
+        })
+        .collect()
+}
+
+pub fn to_proto_cache_stats(stats: AssetTenantCacheStats) -> TenantCacheStats {
+    TenantCacheStats {
+        tenant_id: stats.tenant_id,
+        entries: stats.entries as i32,
+        capacity: stats.capacity as i32,
+        hits: stats.hits as i64,
+        misses: stats.misses as i64,
+        evictions: stats.evictions as i64,
+        bytes: stats.bytes as i64,
+        ruleset_ids: stats.ruleset_ids,
+    }
+}
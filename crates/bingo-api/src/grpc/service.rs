@@ -1,15 +1,43 @@
 use std::collections::HashMap;
 use std::pin::Pin;
 use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
 use tokio_stream::{Stream, StreamExt};
 use tonic::{Request, Response, Status, Streaming};
+use tracing::Instrument;
 
 use crate::AppState;
+use crate::admission::AdmissionControl;
+use crate::asset_cache::{CompiledAssetCache, CompiledRuleset};
+use crate::auth::{Permission, authorize};
+use crate::generated::debug_command::CommandType;
 use crate::generated::processing_control::ControlType;
 use crate::generated::rules_engine_service_server::RulesEngineService;
 use crate::generated::*;
-use crate::grpc::conversions::{from_proto_fact, from_proto_rule, to_proto_result};
-use bingo_core::{BingoEngine, Rule as CoreRule};
+use crate::grpc::conversions::{
+    from_proto_debug_breakpoints, from_proto_evaluation_date, from_proto_fact,
+    from_proto_fact_schema, from_proto_reference_table, from_proto_rule, from_proto_value,
+    to_proto_alpha_memories, to_proto_batch_summary, to_proto_beta_memories, to_proto_cache_stats,
+    to_proto_debug_step, to_proto_fact_violations, to_proto_reference_table, to_proto_result,
+    to_proto_rule, to_proto_rule_graph, to_proto_rule_stats, to_proto_value,
+};
+use crate::tracing_setup::grpc_request_span;
+use bingo_core::{
+    BatchSummary as CoreBatchSummary, BingoEngine, BingoError, DebugSession,
+    DebugStep as CoreDebugStep, EvaluationDate, Rule as CoreRule,
+};
+use prost::Message;
+
+/// Responses buffered per ingestion stream when the client does not set a limit
+pub const DEFAULT_INGEST_BUFFER: usize = 64;
+
+/// Upper bound on responses buffered per ingestion stream
+pub const MAX_INGEST_BUFFER: usize = 4096;
+
+type IngestResponseStream = ReceiverStream<Result<IngestFactsResponse, Status>>;
+
+type DebugResponseStream = Pin<Box<dyn Stream<Item = Result<DebugState, Status>> + Send>>;
 
 pub struct RulesEngineServiceImpl {
     app_state: Arc<AppState>,
@@ -19,6 +47,386 @@ impl RulesEngineServiceImpl {
     pub fn new(app_state: Arc<AppState>) -> Self {
         Self { app_state }
     }
+
+    /// Start ingesting facts from a request stream whose first message is `IngestStart`
+    ///
+    /// Facts are processed by a background task that forwards responses through a
+    /// bounded channel. While the channel is full the task stops reading requests, so
+    /// a client that does not read its responses is held back by transport flow
+    /// control instead of being buffered in server memory.
+    pub async fn start_ingestion<S>(&self, mut requests: S) -> Result<IngestResponseStream, Status>
+    where
+        S: Stream<Item = Result<IngestFactsRequest, Status>> + Send + Unpin + 'static,
+    {
+        let start = match requests.next().await {
+            Some(Ok(IngestFactsRequest {
+                request: Some(ingest_facts_request::Request::Start(start)),
+            })) => start,
+            Some(Err(status)) => return Err(status),
+            _ => {
+                return Err(Status::failed_precondition(
+                    "The first ingestion message must be IngestStart",
+                ));
+            }
+        };
+
+        let engine = self.session_engine(&start.session_id)?;
+        let evaluation_date =
+            from_proto_evaluation_date(start.evaluation_date, start.evaluate_at_event_time)
+                .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        let buffer = match start.max_pending_responses {
+            limit if limit > 0 => (limit as usize).min(MAX_INGEST_BUFFER),
+            _ => DEFAULT_INGEST_BUFFER,
+        };
+        let (responses, receiver) = mpsc::channel(buffer);
+
+        tracing::info!(
+            session_id = %start.session_id,
+            buffer = buffer,
+            ack_interval = start.ack_interval,
+            ?evaluation_date,
+            "Starting fact ingestion"
+        );
+
+        // Facts are processed within the request's span
+        tokio::spawn(
+            run_ingestion(
+                engine,
+                self.app_state.admission.clone(),
+                requests,
+                responses,
+                start.ack_interval.max(0) as i64,
+                evaluation_date,
+                start.session_id,
+            )
+            .instrument(tracing::Span::current()),
+        );
+
+        Ok(ReceiverStream::new(receiver))
+    }
+
+    /// Start a step-debugging session from a request stream whose first message is `DebugStart`
+    ///
+    /// The debug session runs against a copy of the session's compiled network, so
+    /// stepping facts never changes the engine's working memory. One `DebugState` is
+    /// streamed back for the start message and for every request after it.
+    pub async fn start_debugging<S>(&self, mut requests: S) -> Result<DebugResponseStream, Status>
+    where
+        S: Stream<Item = Result<DebugRequest, Status>> + Send + Unpin + 'static,
+    {
+        let start = match requests.next().await {
+            Some(Ok(DebugRequest { request: Some(debug_request::Request::Start(start)) })) => start,
+            Some(Err(status)) => return Err(status),
+            _ => {
+                return Err(Status::failed_precondition(
+                    "The first debug message must be DebugStart",
+                ));
+            }
+        };
+
+        let engine = self.session_engine(&start.session_id)?;
+        let ruleset_version = engine.ruleset_version();
+        let mut session = engine
+            .debug_session()
+            .map_err(|e| Status::internal(format!("Failed to start debug session: {e}")))?;
+        replace_breakpoints(&mut session, start.breakpoints.unwrap_or_default())?;
+        let started = debug_state(&session, Vec::new(), ruleset_version)?;
+        tracing::info!(
+            session_id = %start.session_id,
+            debug_session_id = %session.id(),
+            "Debug session started"
+        );
+
+        let stream = async_stream::stream! {
+            yield Ok(started);
+
+            while let Some(request) = requests.next().await {
+                let request = match request {
+                    Ok(request) => request,
+                    Err(status) => {
+                        yield Err(status);
+                        return;
+                    }
+                };
+
+                let mut stop = false;
+                let steps = match request.request {
+                    Some(debug_request::Request::Fact(fact)) => from_proto_fact(fact)
+                        .map(|fact| {
+                            session.queue([fact]);
+                            Vec::new()
+                        })
+                        .map_err(|e| Status::invalid_argument(format!("Invalid fact: {e}"))),
+                    Some(debug_request::Request::Breakpoints(breakpoints)) => {
+                        replace_breakpoints(&mut session, breakpoints).map(|()| Vec::new())
+                    }
+                    Some(debug_request::Request::Command(command)) => match command.r#type() {
+                        CommandType::Step => session.step().map(|step| step.into_iter().collect()),
+                        CommandType::Resume => session.resume(),
+                        CommandType::Stop => {
+                            stop = true;
+                            Ok(Vec::new())
+                        }
+                    }
+                    .map_err(|e| Status::internal(format!("Debug step failed: {e}"))),
+                    Some(debug_request::Request::Start(_)) => {
+                        Err(Status::failed_precondition("The debug session has already started"))
+                    }
+                    None => continue,
+                };
+
+                match steps.and_then(|steps| debug_state(&session, steps, ruleset_version)) {
+                    Ok(state) => yield Ok(state),
+                    Err(status) => {
+                        yield Err(status);
+                        return;
+                    }
+                }
+                if stop {
+                    tracing::info!(debug_session_id = %session.id(), "Debug session stopped");
+                    return;
+                }
+            }
+        };
+
+        Ok(Box::pin(stream))
+    }
+
+    /// Engine of a session created by `CompileRules`
+    fn session_engine(&self, session_id: &str) -> Result<Arc<BingoEngine>, Status> {
+        self.app_state.get_engine(session_id).ok_or_else(|| {
+            Status::not_found(format!("Session '{session_id}' has no compiled rules"))
+        })
+    }
+}
+
+/// Convert a rule from a rule management request
+fn required_rule(rule: Option<Rule>) -> Result<CoreRule, Status> {
+    let rule = rule.ok_or_else(|| Status::invalid_argument("rule is required"))?;
+    from_proto_rule(rule).map_err(|e| Status::invalid_argument(format!("Invalid rule: {e}")))
+}
+
+/// Reject requests that would create a session without an ID
+fn required_session_id(session_id: &str) -> Result<(), Status> {
+    if session_id.is_empty() {
+        return Err(Status::invalid_argument("session_id is required"));
+    }
+    Ok(())
+}
+
+fn session_globals_response(engine: &BingoEngine) -> SessionGlobalsResponse {
+    SessionGlobalsResponse {
+        globals: engine
+            .globals()
+            .iter()
+            .map(|(name, value)| (name.clone(), to_proto_value(value)))
+            .collect(),
+    }
+}
+
+fn session_rule_groups_response(engine: &BingoEngine) -> SessionRuleGroupsResponse {
+    SessionRuleGroupsResponse {
+        disabled_groups: engine.disabled_rule_groups().into_iter().collect(),
+    }
+}
+
+fn rule_mutation_response(rule_id: u64, engine: &BingoEngine) -> RuleMutationResponse {
+    RuleMutationResponse {
+        rule_id: rule_id.to_string(),
+        ruleset_version: engine.ruleset_version(),
+        rule_count: engine.rule_count() as i32,
+    }
+}
+
+/// Replace a debug session's breakpoints with those of a request
+fn replace_breakpoints(
+    session: &mut DebugSession,
+    breakpoints: DebugBreakpoints,
+) -> Result<(), Status> {
+    let breakpoints = from_proto_debug_breakpoints(breakpoints)
+        .map_err(|e| Status::invalid_argument(format!("Invalid breakpoint: {e}")))?;
+    for breakpoint in session.breakpoints().collect::<Vec<_>>() {
+        session.clear_breakpoint(breakpoint);
+    }
+    for breakpoint in breakpoints {
+        session.set_breakpoint(breakpoint);
+    }
+    Ok(())
+}
+
+/// Debug session state after `steps`, with the network memories they left
+fn debug_state(
+    session: &DebugSession,
+    steps: Vec<CoreDebugStep>,
+    ruleset_version: u64,
+) -> Result<DebugState, Status> {
+    let paused_at_breakpoint = steps.last().is_some_and(|step| !step.breakpoints_hit.is_empty());
+    let steps = steps
+        .into_iter()
+        .map(|step| to_proto_debug_step(step, ruleset_version))
+        .collect::<anyhow::Result<Vec<_>>>()
+        .map_err(|e| Status::internal(format!("Result conversion failed: {e}")))?;
+    let memory = session.memory();
+    Ok(DebugState {
+        debug_session_id: session.id().to_string(),
+        steps,
+        paused_at_breakpoint,
+        pending_facts: session.pending_count() as i32,
+        steps_taken: session.steps(),
+        alpha_memories: to_proto_alpha_memories(&memory),
+        beta_memories: to_proto_beta_memories(&memory),
+    })
+}
+
+/// Progress acknowledgement for an ingestion stream
+/// Admit `facts` under the server's rate limits, and the session's when `session_id`
+/// is given
+fn admit_facts(
+    admission: &AdmissionControl,
+    session_id: Option<&str>,
+    facts: &[Fact],
+) -> Result<(), Status> {
+    let bytes = facts.iter().map(Message::encoded_len).sum();
+    admission.admit(session_id, facts.len(), bytes)
+}
+
+fn ack(progress: &IngestAck) -> ingest_facts_response::Response {
+    ingest_facts_response::Response::Ack(IngestAck { ..*progress })
+}
+
+/// Process ingested facts in arrival order until the client stops or disconnects
+///
+/// Rule effective and expiry dates are checked against `evaluation_date`.
+/// A fact over the session's or the server's rate limit ends the stream with
+/// `RESOURCE_EXHAUSTED` before it reaches the engine.
+async fn run_ingestion<S>(
+    engine: Arc<BingoEngine>,
+    admission: AdmissionControl,
+    mut requests: S,
+    responses: mpsc::Sender<Result<IngestFactsResponse, Status>>,
+    ack_interval: i64,
+    evaluation_date: EvaluationDate,
+    session_id: String,
+) where
+    S: Stream<Item = Result<IngestFactsRequest, Status>> + Unpin,
+{
+    let mut progress = IngestAck::default();
+
+    while let Some(request) = requests.next().await {
+        let request = match request {
+            Ok(request) => request,
+            Err(status) => {
+                let _ = responses.send(Err(status)).await;
+                return;
+            }
+        };
+
+        let mut outgoing = Vec::new();
+        match request.request {
+            Some(ingest_facts_request::Request::Fact(fact)) => {
+                if let Err(status) =
+                    admit_facts(&admission, Some(&session_id), std::slice::from_ref(&fact))
+                {
+                    let _ = responses.send(Err(status)).await;
+                    return;
+                }
+                progress.facts_received += 1;
+                let fact_id = fact.id.clone();
+
+                let processed = from_proto_fact(fact)
+                    .map_err(|e| (format!("Invalid fact: {e}"), Vec::new()))
+                    .and_then(|fact| {
+                        engine.process_facts_dated(vec![fact], evaluation_date).map_err(|e| {
+                            let violations = match &e {
+                                BingoError::FactValidation { report, .. } => {
+                                    to_proto_fact_violations(report)
+                                }
+                                _ => Vec::new(),
+                            };
+                            (format!("Fact processing failed: {e}"), violations)
+                        })
+                    });
+                match processed {
+                    Ok((results, ruleset_version)) => {
+                        progress.facts_processed += 1;
+                        for result in results {
+                            match to_proto_result(result, ruleset_version) {
+                                Ok(result) => {
+                                    progress.results_sent += 1;
+                                    outgoing.push(ingest_facts_response::Response::Result(result));
+                                }
+                                Err(e) => {
+                                    let status =
+                                        Status::internal(format!("Result conversion failed: {e}"));
+                                    let _ = responses.send(Err(status)).await;
+                                    return;
+                                }
+                            }
+                        }
+                    }
+                    Err((error_message, violations)) => {
+                        progress.facts_rejected += 1;
+                        outgoing.push(ingest_facts_response::Response::Rejected(FactRejection {
+                            fact_id,
+                            error_message,
+                            violations,
+                        }));
+                    }
+                }
+
+                if ack_interval > 0 && progress.facts_received % ack_interval == 0 {
+                    outgoing.push(ack(&progress));
+                }
+            }
+            Some(ingest_facts_request::Request::Control(control)) => match control.r#type() {
+                ControlType::Flush => {
+                    outgoing.push(ack(&progress));
+                }
+                ControlType::Stop => {
+                    tracing::info!(session_id = %session_id, "Fact ingestion stopped by client");
+                    break;
+                }
+                ControlType::Pause | ControlType::Resume => {
+                    // Ingestion is paced by how fast the client reads responses
+                    tracing::debug!(session_id = %session_id, "Ignoring pause/resume during ingestion");
+                }
+            },
+            Some(ingest_facts_request::Request::Start(_)) => {
+                let _ = responses
+                    .send(Err(Status::failed_precondition(
+                        "Ingestion already started",
+                    )))
+                    .await;
+                return;
+            }
+            None => {
+                let _ = responses.send(Err(Status::invalid_argument("Empty request"))).await;
+                return;
+            }
+        }
+
+        // Waits while the client is behind on reading, which stops further requests
+        // from being read
+        for response in outgoing {
+            if responses
+                .send(Ok(IngestFactsResponse { response: Some(response) }))
+                .await
+                .is_err()
+            {
+                tracing::info!(session_id = %session_id, "Ingestion client disconnected");
+                return;
+            }
+        }
+    }
+
+    tracing::info!(
+        session_id = %session_id,
+        facts_processed = progress.facts_processed,
+        facts_rejected = progress.facts_rejected,
+        "Fact ingestion finished"
+    );
+    let _ = responses.send(Ok(IngestFactsResponse { response: Some(ack(&progress)) })).await;
 }
 
 #[tonic::async_trait]
@@ -28,6 +436,7 @@ impl RulesEngineService for RulesEngineServiceImpl {
         &self,
         request: Request<CompileRulesRequest>,
     ) -> Result<Response<CompileRulesResponse>, Status> {
+        authorize(&request, Permission::ManageRules)?;
         let req = request.into_inner();
         let session_id = if req.session_id.is_empty() {
             uuid::Uuid::new_v4().to_string()
@@ -51,8 +460,18 @@ impl RulesEngineService for RulesEngineServiceImpl {
             .collect::<Result<Vec<_>, _>>()
             .map_err(|e| Status::invalid_argument(format!("Invalid rule: {e}")))?;
 
-        // Get or create engine for this session
-        let engine = self.app_state.get_or_create_engine(&session_id);
+        // Get or create engine for this session, warm-starting from a template if named
+        let engine = if req.ruleset_template.is_empty() {
+            self.app_state.get_or_create_engine(&session_id)
+        } else {
+            self.app_state
+                .get_or_create_engine_from_template(
+                    &session_id,
+                    &req.tenant_id,
+                    &req.ruleset_template,
+                )
+                .map_err(|e| Status::not_found(e.to_string()))?
+        };
 
         // Add rules to the engine
         for rule in core_rules.iter() {
@@ -80,6 +499,7 @@ impl RulesEngineService for RulesEngineServiceImpl {
             network_nodes_created: stats.node_count as i32,
             compilation_time_ms: compilation_time.as_millis() as i64,
             engine_version: env!("CARGO_PKG_VERSION").to_string(),
+            ruleset_version: engine.ruleset_version(),
         }))
     }
 
@@ -91,6 +511,7 @@ impl RulesEngineService for RulesEngineServiceImpl {
         &self,
         request: Request<Streaming<ProcessFactsStreamRequest>>,
     ) -> Result<Response<Self::ProcessFactsStreamStream>, Status> {
+        authorize(&request, Permission::Evaluate)?;
         let mut request_stream = request.into_inner();
 
         let stream = async_stream::stream! {
@@ -132,6 +553,8 @@ impl RulesEngineService for RulesEngineServiceImpl {
                             action_results: vec![],
                             execution_time_ns: 1000,
                             metadata: HashMap::new(),
+                            ruleset_version: 0,
+                            external_id: core_fact.external_id.clone().unwrap_or_default(),
                         };
 
                         yield Ok(result);
@@ -165,6 +588,280 @@ impl RulesEngineService for RulesEngineServiceImpl {
         Ok(Response::new(Box::pin(stream)))
     }
 
+    type IngestFactsStream = IngestResponseStream;
+
+    async fn ingest_facts(
+        &self,
+        request: Request<Streaming<IngestFactsRequest>>,
+    ) -> Result<Response<Self::IngestFactsStream>, Status> {
+        authorize(&request, Permission::Evaluate)?;
+        let span = grpc_request_span("IngestFacts", request.metadata());
+        let stream = self.start_ingestion(request.into_inner()).instrument(span).await?;
+        Ok(Response::new(stream))
+    }
+
+    type DebugRulesStream = DebugResponseStream;
+
+    async fn debug_rules(
+        &self,
+        request: Request<Streaming<DebugRequest>>,
+    ) -> Result<Response<Self::DebugRulesStream>, Status> {
+        authorize(&request, Permission::Evaluate)?;
+        let stream = self.start_debugging(request.into_inner()).await?;
+        Ok(Response::new(stream))
+    }
+
+    async fn create_rule(
+        &self,
+        request: Request<CreateRuleRequest>,
+    ) -> Result<Response<RuleMutationResponse>, Status> {
+        authorize(&request, Permission::ManageRules)?;
+        let req = request.into_inner();
+        let engine = self.session_engine(&req.session_id)?;
+        let rule = required_rule(req.rule)?;
+        let rule_id = rule.id;
+
+        if engine.get_rule(rule_id).is_some() {
+            return Err(Status::already_exists(format!(
+                "Rule {rule_id} already exists in session '{}'",
+                req.session_id
+            )));
+        }
+        engine
+            .add_rule(rule)
+            .map_err(|e| Status::invalid_argument(format!("Rule compilation failed: {e}")))?;
+
+        tracing::info!(session_id = %req.session_id, rule_id = rule_id, "Rule created");
+        Ok(Response::new(rule_mutation_response(rule_id, &engine)))
+    }
+
+    async fn update_rule(
+        &self,
+        request: Request<UpdateRuleRequest>,
+    ) -> Result<Response<RuleMutationResponse>, Status> {
+        authorize(&request, Permission::ManageRules)?;
+        let req = request.into_inner();
+        let engine = self.session_engine(&req.session_id)?;
+        let rule = required_rule(req.rule)?;
+        let rule_id = rule.id;
+
+        if engine.get_rule(rule_id).is_none() {
+            return Err(Status::not_found(format!(
+                "Rule {rule_id} does not exist in session '{}'",
+                req.session_id
+            )));
+        }
+        engine
+            .update_rule(rule)
+            .map_err(|e| Status::invalid_argument(format!("Rule compilation failed: {e}")))?;
+
+        tracing::info!(session_id = %req.session_id, rule_id = rule_id, "Rule updated");
+        Ok(Response::new(rule_mutation_response(rule_id, &engine)))
+    }
+
+    async fn delete_rule(
+        &self,
+        request: Request<DeleteRuleRequest>,
+    ) -> Result<Response<RuleMutationResponse>, Status> {
+        authorize(&request, Permission::ManageRules)?;
+        let req = request.into_inner();
+        let engine = self.session_engine(&req.session_id)?;
+        let rule_id = req
+            .rule_id
+            .parse::<u64>()
+            .map_err(|_| Status::invalid_argument(format!("Invalid rule ID: {}", req.rule_id)))?;
+
+        engine.remove_rule(rule_id).map_err(|_| {
+            Status::not_found(format!(
+                "Rule {rule_id} does not exist in session '{}'",
+                req.session_id
+            ))
+        })?;
+
+        tracing::info!(session_id = %req.session_id, rule_id = rule_id, "Rule deleted");
+        Ok(Response::new(rule_mutation_response(rule_id, &engine)))
+    }
+
+    async fn list_rules(
+        &self,
+        request: Request<ListRulesRequest>,
+    ) -> Result<Response<ListRulesResponse>, Status> {
+        authorize(&request, Permission::Evaluate)?;
+        let req = request.into_inner();
+        let engine = self.session_engine(&req.session_id)?;
+
+        // Read the version first; a change racing with the listing can only make the
+        // reported version older than the rules, never newer
+        let ruleset_version = engine.ruleset_version();
+        let rules = engine
+            .rules()
+            .iter()
+            .map(to_proto_rule)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| Status::internal(format!("Rule conversion failed: {e}")))?;
+
+        Ok(Response::new(ListRulesResponse { rules, ruleset_version }))
+    }
+
+    async fn get_rule_stats(
+        &self,
+        request: Request<GetRuleStatsRequest>,
+    ) -> Result<Response<GetRuleStatsResponse>, Status> {
+        authorize(&request, Permission::Evaluate)?;
+        let req = request.into_inner();
+        let engine = self.session_engine(&req.session_id)?;
+
+        let ruleset_version = engine.ruleset_version();
+        let rules = engine.rule_stats().iter().map(to_proto_rule_stats).collect();
+
+        Ok(Response::new(GetRuleStatsResponse {
+            rules,
+            ruleset_version,
+        }))
+    }
+
+    async fn get_rule_graph(
+        &self,
+        request: Request<GetRuleGraphRequest>,
+    ) -> Result<Response<GetRuleGraphResponse>, Status> {
+        authorize(&request, Permission::Evaluate)?;
+        let req = request.into_inner();
+        let engine = self.session_engine(&req.session_id)?;
+
+        let ruleset_version = engine.ruleset_version();
+        let graph = engine.rule_flow_graph();
+        Ok(Response::new(to_proto_rule_graph(&graph, ruleset_version)))
+    }
+
+    async fn set_session_globals(
+        &self,
+        request: Request<SetSessionGlobalsRequest>,
+    ) -> Result<Response<SessionGlobalsResponse>, Status> {
+        authorize(&request, Permission::ManageRules)?;
+        let req = request.into_inner();
+        required_session_id(&req.session_id)?;
+        let globals = req
+            .globals
+            .into_iter()
+            .map(|(name, value)| Ok((name, from_proto_value(value)?)))
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map_err(|e| Status::invalid_argument(format!("Invalid global: {e}")))?;
+
+        let engine = self.app_state.get_or_create_engine(&req.session_id);
+        engine
+            .set_globals(globals)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        tracing::info!(session_id = %req.session_id, "Session globals set");
+        Ok(Response::new(session_globals_response(&engine)))
+    }
+
+    async fn get_session_globals(
+        &self,
+        request: Request<GetSessionGlobalsRequest>,
+    ) -> Result<Response<SessionGlobalsResponse>, Status> {
+        authorize(&request, Permission::Evaluate)?;
+        let req = request.into_inner();
+        let engine = self.session_engine(&req.session_id)?;
+        Ok(Response::new(session_globals_response(&engine)))
+    }
+
+    async fn set_reference_table(
+        &self,
+        request: Request<SetReferenceTableRequest>,
+    ) -> Result<Response<SetReferenceTableResponse>, Status> {
+        authorize(&request, Permission::ManageRules)?;
+        let req = request.into_inner();
+        required_session_id(&req.session_id)?;
+        let table = from_proto_reference_table(req.entries).map_err(|e| {
+            Status::invalid_argument(format!("Invalid reference table '{}': {e}", req.name))
+        })?;
+        let row_count = table.len() as i32;
+
+        let engine = self.app_state.get_or_create_engine(&req.session_id);
+        engine
+            .register_reference_table(req.name.clone(), table)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        tracing::info!(
+            session_id = %req.session_id,
+            table = %req.name,
+            rows = row_count,
+            "Reference table set"
+        );
+        Ok(Response::new(SetReferenceTableResponse {
+            name: req.name,
+            row_count,
+        }))
+    }
+
+    async fn set_fact_schema(
+        &self,
+        request: Request<SetFactSchemaRequest>,
+    ) -> Result<Response<SetFactSchemaResponse>, Status> {
+        authorize(&request, Permission::ManageRules)?;
+        let req = request.into_inner();
+        required_session_id(&req.session_id)?;
+        let schema = from_proto_fact_schema(&req).map_err(|e| {
+            Status::invalid_argument(format!("Invalid schema for '{}': {e}", req.fact_type))
+        })?;
+        let field_count = schema.fields.len() as i32;
+
+        let engine = self.app_state.get_or_create_engine(&req.session_id);
+        engine.register_fact_schema(schema);
+
+        tracing::info!(
+            session_id = %req.session_id,
+            fact_type = %req.fact_type,
+            fields = field_count,
+            "Fact schema set"
+        );
+        Ok(Response::new(SetFactSchemaResponse {
+            fact_type: req.fact_type,
+            field_count,
+        }))
+    }
+
+    async fn get_reference_table(
+        &self,
+        request: Request<GetReferenceTableRequest>,
+    ) -> Result<Response<GetReferenceTableResponse>, Status> {
+        authorize(&request, Permission::Evaluate)?;
+        let req = request.into_inner();
+        let engine = self.session_engine(&req.session_id)?;
+        let table = engine.reference_data().get(&req.name).ok_or_else(|| {
+            Status::not_found(format!(
+                "Reference table '{}' is not registered in session '{}'",
+                req.name, req.session_id
+            ))
+        })?;
+
+        Ok(Response::new(GetReferenceTableResponse {
+            name: req.name,
+            entries: to_proto_reference_table(&table),
+        }))
+    }
+
+    async fn set_session_rule_groups(
+        &self,
+        request: Request<SetSessionRuleGroupsRequest>,
+    ) -> Result<Response<SessionRuleGroupsResponse>, Status> {
+        authorize(&request, Permission::ManageRules)?;
+        let req = request.into_inner();
+        required_session_id(&req.session_id)?;
+
+        let engine = self.app_state.get_or_create_engine(&req.session_id);
+        for group in &req.enable {
+            engine.enable_rule_group(group);
+        }
+        for group in req.disable {
+            engine.disable_rule_group(group);
+        }
+
+        tracing::info!(session_id = %req.session_id, "Session rule groups set");
+        Ok(Response::new(session_rule_groups_response(&engine)))
+    }
+
     type ProcessWithRulesStreamStream =
         Pin<Box<dyn Stream<Item = Result<ProcessingResponse, Status>> + Send>>;
 
@@ -173,8 +870,10 @@ impl RulesEngineService for RulesEngineServiceImpl {
         &self,
         request: Request<ProcessWithRulesRequest>,
     ) -> Result<Response<Self::ProcessWithRulesStreamStream>, Status> {
+        authorize(&request, Permission::Evaluate)?;
         let req = request.into_inner();
         let request_id = req.request_id.clone();
+        admit_facts(&self.app_state.admission, None, &req.facts)?;
 
         tracing::info!(
             request_id = %request_id,
@@ -235,6 +934,7 @@ impl RulesEngineService for RulesEngineServiceImpl {
                         network_nodes_created: node_count as i32,
                         compilation_time_ms: compilation_time.as_millis() as i64,
                         engine_version: env!("CARGO_PKG_VERSION").to_string(),
+                        ruleset_version: engine.ruleset_version(),
                     }
                 ))
             });
@@ -259,12 +959,14 @@ impl RulesEngineService for RulesEngineServiceImpl {
             // Process facts through the engine
             let total_facts = core_facts.len();
             let mut total_results = 0;
+            let mut summary = None;
 
             // Process facts in the engine
             if !validate_only {
-                match engine.process_facts(core_facts) {
-                    Ok(results) => {
+                match engine.process_facts_summarized(core_facts) {
+                    Ok((results, batch_summary)) => {
                         total_results = results.len();
+                        summary = Some(to_proto_batch_summary(&batch_summary));
 
                         yield Ok(ProcessingResponse {
                             response: Some(processing_response::Response::StatusUpdate(
@@ -311,6 +1013,7 @@ impl RulesEngineService for RulesEngineServiceImpl {
                         total_processing_time_ms: start_time.elapsed().as_millis() as i64,
                         success: true,
                         error_message: String::new(),
+                        summary,
                     }
                 ))
             });
@@ -319,6 +1022,75 @@ impl RulesEngineService for RulesEngineServiceImpl {
         Ok(Response::new(Box::pin(stream)))
     }
 
+    async fn evaluate(
+        &self,
+        request: Request<EvaluateRequest>,
+    ) -> Result<Response<EvaluateResponse>, Status> {
+        authorize(&request, Permission::Evaluate)?;
+        let req = request.into_inner();
+        admit_facts(&self.app_state.admission, None, &req.facts)?;
+        let start = std::time::Instant::now();
+
+        let rules = req
+            .rules
+            .into_iter()
+            .map(from_proto_rule)
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map_err(|e| Status::invalid_argument(format!("Invalid rule: {e}")))?;
+        let facts = req
+            .facts
+            .into_iter()
+            .map(from_proto_fact)
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map_err(|e| Status::invalid_argument(format!("Invalid fact: {e}")))?;
+        let evaluation_date = from_proto_evaluation_date(req.evaluation_date, false)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        // The engine only serves this request and is reset for the next one off the
+        // request path
+        let pool = &self.app_state.engine_pool;
+        let engine = pool
+            .acquire()
+            .map_err(|e| Status::internal(format!("Failed to create engine: {e}")))?;
+        pool.replenish_in_background();
+
+        let rule_count = rules.len();
+        let fact_count = facts.len();
+        let evaluated = engine
+            .add_rules(rules)
+            .map_err(|e| Status::invalid_argument(format!("Rule compilation failed: {e}")))
+            .and_then(|()| {
+                engine.process_facts_dated(facts, evaluation_date).map_err(|e| match e {
+                    BingoError::FactValidation { .. } => Status::invalid_argument(e.to_string()),
+                    _ => Status::internal(format!("Fact processing failed: {e}")),
+                })
+            });
+        let pool = Arc::clone(pool);
+        tokio::task::spawn_blocking(move || pool.release(engine));
+        let (results, ruleset_version) = evaluated?;
+
+        let summary = to_proto_batch_summary(&CoreBatchSummary::from_results(fact_count, &results));
+        let results = results
+            .into_iter()
+            .map(|result| to_proto_result(result, ruleset_version))
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map_err(|e| Status::internal(format!("Result conversion failed: {e}")))?;
+
+        tracing::info!(
+            request_id = %req.request_id,
+            rules_count = rule_count,
+            facts_count = fact_count,
+            results_count = results.len(),
+            "Evaluated facts against request rules"
+        );
+        Ok(Response::new(EvaluateResponse {
+            request_id: req.request_id,
+            results,
+            summary: Some(summary),
+            processing_time_ms: start.elapsed().as_millis() as i64,
+        }))
+    }
+
     type ProcessFactsBatchStream =
         Pin<Box<dyn Stream<Item = Result<ProcessingStatus, Status>> + Send>>;
 
@@ -337,20 +1109,140 @@ impl RulesEngineService for RulesEngineServiceImpl {
 
     async fn evaluate_ruleset_stream(
         &self,
-        _request: Request<EvaluateRulesetRequest>,
+        request: Request<EvaluateRulesetRequest>,
     ) -> Result<Response<Self::EvaluateRulesetStreamStream>, Status> {
-        // Implementation using cached rulesets
-        Err(Status::unimplemented(
-            "EvaluateRulesetStream not yet implemented",
-        ))
+        authorize(&request, Permission::Evaluate)?;
+        let span = grpc_request_span("EvaluateRulesetStream", request.metadata());
+        let req = request.into_inner();
+        admit_facts(&self.app_state.admission, None, &req.facts)?;
+        let tenant_id = CompiledAssetCache::tenant_or_default(&req.tenant_id);
+
+        let ruleset =
+            self.app_state.asset_cache.get(tenant_id, &req.ruleset_id).ok_or_else(|| {
+                Status::not_found(format!(
+                    "Ruleset '{}' is not cached for tenant '{}'",
+                    req.ruleset_id, tenant_id
+                ))
+            })?;
+
+        let core_facts: Vec<bingo_core::Fact> = req
+            .facts
+            .into_iter()
+            .map(from_proto_fact)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| Status::invalid_argument(format!("Invalid fact: {e}")))?;
+
+        tracing::info!(
+            tenant_id = %tenant_id,
+            ruleset_id = %ruleset.ruleset_id,
+            request_id = %req.request_id,
+            facts_count = core_facts.len(),
+            "Evaluating cached ruleset"
+        );
+
+        // The compiled engine is shared, so facts are cleared once the evaluation ends
+        let (core_results, ruleset_version) = {
+            let _evaluation = ruleset.evaluation_lock.lock().unwrap();
+            let results = span.in_scope(|| ruleset.engine.process_facts_versioned(core_facts));
+            ruleset.engine.clear_facts();
+            results.map_err(|e| Status::internal(format!("Fact processing failed: {e}")))?
+        };
+
+        let results: Vec<Result<RuleExecutionResult, Status>> = core_results
+            .into_iter()
+            .map(|result| {
+                to_proto_result(result, ruleset_version)
+                    .map_err(|e| Status::internal(format!("Result conversion failed: {e}")))
+            })
+            .collect();
+
+        Ok(Response::new(Box::pin(tokio_stream::iter(results))))
     }
 
     async fn register_ruleset(
         &self,
-        _request: Request<RegisterRulesetRequest>,
+        request: Request<RegisterRulesetRequest>,
     ) -> Result<Response<RegisterRulesetResponse>, Status> {
-        // Implementation for ruleset caching
-        Err(Status::unimplemented("RegisterRuleset not yet implemented"))
+        authorize(&request, Permission::ManageRules)?;
+        let req = request.into_inner();
+        if req.ruleset_id.is_empty() {
+            return Err(Status::invalid_argument("ruleset_id is required"));
+        }
+        let tenant_id = CompiledAssetCache::tenant_or_default(&req.tenant_id);
+
+        // Hash the rule definitions so clients can tell which version is cached
+        let encoded_rules: Vec<u8> =
+            req.rules.iter().flat_map(|rule| rule.encode_to_vec()).collect();
+        let ruleset_hash = format!("{:x}", md5::compute(&encoded_rules));
+
+        let core_rules: Vec<CoreRule> = req
+            .rules
+            .into_iter()
+            .map(from_proto_rule)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| Status::invalid_argument(format!("Invalid rule: {e}")))?;
+
+        let engine = BingoEngine::new()
+            .map_err(|e| Status::internal(format!("Failed to create engine: {e}")))?;
+        for rule in core_rules.iter() {
+            engine
+                .add_rule(rule.clone())
+                .map_err(|e| Status::invalid_argument(format!("Rule compilation failed: {e}")))?;
+        }
+
+        let ruleset = self.app_state.asset_cache.insert(
+            tenant_id,
+            CompiledRuleset::new(
+                req.ruleset_id,
+                ruleset_hash,
+                Arc::new(engine),
+                core_rules.len(),
+            ),
+        );
+
+        Ok(Response::new(RegisterRulesetResponse {
+            ruleset_id: ruleset.ruleset_id.clone(),
+            ruleset_hash: ruleset.ruleset_hash.clone(),
+            rules_compiled: ruleset.rules_compiled as i32,
+            success: true,
+            error_message: String::new(),
+        }))
+    }
+
+    async fn get_cache_stats(
+        &self,
+        request: Request<CacheStatsRequest>,
+    ) -> Result<Response<CacheStatsResponse>, Status> {
+        authorize(&request, Permission::Evaluate)?;
+        let req = request.into_inner();
+        let tenant_id = (!req.tenant_id.is_empty()).then_some(req.tenant_id.as_str());
+
+        let tenants = self
+            .app_state
+            .asset_cache
+            .stats(tenant_id)
+            .into_iter()
+            .map(to_proto_cache_stats)
+            .collect();
+
+        Ok(Response::new(CacheStatsResponse { tenants }))
+    }
+
+    async fn purge_cache(
+        &self,
+        request: Request<PurgeCacheRequest>,
+    ) -> Result<Response<PurgeCacheResponse>, Status> {
+        authorize(&request, Permission::ManageRules)?;
+        let req = request.into_inner();
+        let tenant_id = (!req.tenant_id.is_empty()).then_some(req.tenant_id.as_str());
+        let ruleset_id = (!req.ruleset_id.is_empty()).then_some(req.ruleset_id.as_str());
+
+        let outcome = self.app_state.asset_cache.purge(tenant_id, ruleset_id);
+
+        Ok(Response::new(PurgeCacheResponse {
+            entries_purged: outcome.entries_purged as i32,
+            bytes_freed: outcome.bytes_freed as i64,
+        }))
     }
 
     async fn health_check(
@@ -363,4 +1255,11 @@ impl RulesEngineService for RulesEngineServiceImpl {
             uptime_seconds: self.app_state.elapsed().as_secs() as i64,
         }))
     }
+
+    async fn readiness_check(
+        &self,
+        _request: Request<()>,
+    ) -> Result<Response<ReadinessResponse>, Status> {
+        crate::health::readiness(&self.app_state).await.map(Response::new)
+    }
 }
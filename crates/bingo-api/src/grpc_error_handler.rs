@@ -224,6 +224,17 @@ impl GrpcErrorHandler {
             },
         );
 
+        // Capacity errors - working memory limits the client can back off from
+        self.error_mappings.insert(
+            "capacity".to_string(),
+            GrpcErrorMapping {
+                status_code: Code::ResourceExhausted,
+                include_details: true,
+                user_actionable: true,
+                message_template: Some("Working memory capacity exceeded: {message}".to_string()),
+            },
+        );
+
         // Configuration errors - server errors
         self.error_mappings.insert(
             "configuration".to_string(),
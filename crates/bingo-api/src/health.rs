@@ -0,0 +1,123 @@
+//! Health and readiness for orchestrators and load balancers
+//!
+//! `HealthService` implements the standard `grpc.health.v1` protocol, which Kubernetes
+//! gRPC probes and most load balancers speak. It is served without authentication.
+//! The overall service `""` is serving while the server runs and not once it starts
+//! shutting down, which suits liveness probes. `rules_engine.v1.RulesEngineService`
+//! is serving only while the engine self-checks of `ProductionReadinessValidator` pass
+//! as well, which suits readiness probes. `ReadinessCheck` on the rules engine service
+//! reports the outcome of each check.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use bingo_core::{CheckStatus, ProductionReadinessValidator, load_config_from_env};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Request, Response, Status};
+
+use crate::AppState;
+use crate::generated::health::health_check_response::ServingStatus;
+use crate::generated::health::health_server::Health;
+use crate::generated::health::{HealthCheckRequest, HealthCheckResponse};
+use crate::generated::rules_engine_service_server::SERVICE_NAME as RULES_ENGINE_SERVICE;
+use crate::generated::{ReadinessCheckResult, ReadinessResponse};
+use crate::grpc::conversions::to_proto_readiness_check;
+
+/// How often `Watch` re-evaluates the status it reports
+const DEFAULT_WATCH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Run the engine self-checks for `state`
+pub async fn readiness(state: &AppState) -> Result<ReadinessResponse, Status> {
+    // The checks compile a probe rule and wait on the thread pool, so they run off the
+    // async workers
+    let checks = tokio::task::spawn_blocking(|| {
+        ProductionReadinessValidator::new(load_config_from_env()).validate_engine()
+    })
+    .await
+    .map_err(|e| Status::internal(format!("Readiness checks panicked: {e}")))?
+    .map_err(|e| Status::internal(format!("Readiness checks failed to run: {e}")))?;
+
+    let shutting_down = state.is_shutting_down();
+    let ready = !shutting_down && checks.iter().all(|check| check.status != CheckStatus::Fail);
+    let checks: Vec<ReadinessCheckResult> = checks.iter().map(to_proto_readiness_check).collect();
+    Ok(ReadinessResponse { ready, checks, shutting_down })
+}
+
+/// `grpc.health.v1` health service
+#[derive(Debug, Clone)]
+pub struct HealthService {
+    app_state: Arc<AppState>,
+    watch_interval: Duration,
+}
+
+impl HealthService {
+    pub fn new(app_state: Arc<AppState>) -> Self {
+        Self { app_state, watch_interval: DEFAULT_WATCH_INTERVAL }
+    }
+
+    /// Re-evaluate watched statuses every `watch_interval` rather than every 5 seconds
+    pub fn with_watch_interval(self, watch_interval: Duration) -> Self {
+        Self { watch_interval, ..self }
+    }
+
+    /// Status of `service`, or `None` for services this server doesn't know
+    async fn serving_status(&self, service: &str) -> Option<ServingStatus> {
+        let serving = match service {
+            "" => !self.app_state.is_shutting_down(),
+            RULES_ENGINE_SERVICE => readiness(&self.app_state).await.is_ok_and(|r| r.ready),
+            _ => return None,
+        };
+        Some(if serving {
+            ServingStatus::Serving
+        } else {
+            ServingStatus::NotServing
+        })
+    }
+}
+
+#[tonic::async_trait]
+impl Health for HealthService {
+    async fn check(
+        &self,
+        request: Request<HealthCheckRequest>,
+    ) -> Result<Response<HealthCheckResponse>, Status> {
+        let service = request.into_inner().service;
+        match self.serving_status(&service).await {
+            Some(status) => Ok(Response::new(HealthCheckResponse { status: status as i32 })),
+            None => Err(Status::not_found(format!("Unknown service '{service}'"))),
+        }
+    }
+
+    type WatchStream = ReceiverStream<Result<HealthCheckResponse, Status>>;
+
+    async fn watch(
+        &self,
+        request: Request<HealthCheckRequest>,
+    ) -> Result<Response<Self::WatchStream>, Status> {
+        let service = request.into_inner().service;
+        let (tx, rx) = mpsc::channel(1);
+        let health = self.clone();
+
+        tokio::spawn(async move {
+            let mut reported = None;
+            loop {
+                let status =
+                    health.serving_status(&service).await.unwrap_or(ServingStatus::ServiceUnknown);
+                if reported != Some(status) {
+                    let response = HealthCheckResponse { status: status as i32 };
+                    if tx.send(Ok(response)).await.is_err() {
+                        break;
+                    }
+                    reported = Some(status);
+                }
+                tokio::select! {
+                    _ = tokio::time::sleep(health.watch_interval) => {}
+                    _ = tx.closed() => break,
+                }
+            }
+        });
+
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+}
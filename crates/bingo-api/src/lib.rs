@@ -14,26 +14,46 @@
 //! This module provides a gRPC streaming API for the Bingo RETE rules engine
 //! with efficient memory usage and real-time processing.
 
-use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
-use std::time::Duration;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
 
-use anyhow::anyhow;
-use bingo_core::BingoEngine;
+use anyhow::{Context, anyhow};
+use bingo_core::{BingoEngine, EngineConfig, EngineSnapshot};
 use chrono::{DateTime, Utc};
 use tracing::{info, warn};
 
+use crate::admission::AdmissionControl;
+use crate::asset_cache::CompiledAssetCache;
+use crate::config::ServerConfig;
+use crate::engine_pool::EnginePool;
+
 // Only keep what we need for gRPC
+pub mod admission;
+pub mod asset_cache;
+pub mod auth;
+pub mod config;
+pub mod engine_pool;
 pub mod grpc;
+pub mod health;
+pub mod shutdown;
 pub mod tracing_setup;
 
 // Enhanced error handling modules
 pub mod error_cli;
 pub mod grpc_error_handler;
 
-// Generated protocol buffer code
+// Generated protocol buffer code; prost sizes oneof variants by their message types
+#[allow(clippy::large_enum_variant)]
 pub mod generated {
     tonic::include_proto!("rules_engine.v1");
+
+    /// The standard gRPC health checking protocol, `grpc.health.v1`
+    pub mod health {
+        tonic::include_proto!("grpc.health.v1");
+    }
 }
 
 /// Application state for gRPC service with shared engine management
@@ -44,17 +64,161 @@ pub struct AppState {
     pub engines: RwLock<HashMap<String, Arc<BingoEngine>>>,
     /// Default engine for stateless operations
     pub default_engine: Arc<BingoEngine>,
+    /// Compiled rulesets cached per tenant
+    pub asset_cache: CompiledAssetCache,
+    /// Rate limits facts are admitted under
+    pub admission: AdmissionControl,
+    /// Pre-initialized engines for new sessions and stateless evaluation
+    pub engine_pool: Arc<EnginePool>,
+    /// Set once the server starts shutting down
+    shutting_down: AtomicBool,
+    /// Settings the server runs with, replaced on reload
+    config: RwLock<ServerConfig>,
+    /// When each session was last used, for closing idle sessions
+    last_used: Mutex<HashMap<String, Instant>>,
 }
 
 impl AppState {
     pub async fn new() -> anyhow::Result<Self> {
+        Self::from_config(ServerConfig::default())
+    }
+
+    /// State running with the limits, session settings and engine pool of `config`
+    pub fn from_config(config: ServerConfig) -> anyhow::Result<Self> {
         info!("Initializing gRPC application state with thread-safe engines");
+        config.validate()?;
 
         let default_engine = Arc::new(
             BingoEngine::new().map_err(|e| anyhow!("Failed to create default engine: {}", e))?,
         );
+        let engine_pool = EnginePool::new(config.engine_pool())
+            .map_err(|e| anyhow!("Failed to pre-warm engine pool: {}", e))?;
+        let (global_limits, session_limits) = config.limits.admission_limits();
+
+        Ok(Self {
+            start_time: Utc::now(),
+            engines: RwLock::new(HashMap::new()),
+            default_engine,
+            asset_cache: CompiledAssetCache::default(),
+            admission: AdmissionControl::new(global_limits, session_limits),
+            engine_pool: Arc::new(engine_pool),
+            shutting_down: AtomicBool::new(false),
+            config: RwLock::new(config),
+            last_used: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Settings the server runs with
+    pub fn config(&self) -> ServerConfig {
+        self.config.read().unwrap().clone()
+    }
+
+    /// Apply reloaded settings
+    ///
+    /// Rate limits and session settings take effect at once; existing sessions get the
+    /// new working memory limits from their next insert. Changes to other sections are
+    /// reported and wait for a restart.
+    pub fn apply_config(&self, config: ServerConfig) -> anyhow::Result<()> {
+        config.validate()?;
+        let mut running = self.config.write().unwrap();
+        for section in config.restart_required(&running) {
+            warn!(
+                "Configuration section '{}' changed and applies after a restart",
+                section
+            );
+        }
+
+        if config.limits != running.limits {
+            let (global_limits, session_limits) = config.limits.admission_limits();
+            self.admission.set_limits(global_limits, session_limits);
+            info!("Applied new rate limits");
+        }
+        if config.sessions != running.sessions {
+            let engine_config = config.sessions.engine_config()?;
+            for engine in self.engines.read().unwrap().values() {
+                engine
+                    .set_config(engine_config.clone())
+                    .map_err(|e| anyhow!("Failed to apply session limits: {}", e))?;
+            }
+            info!(ttl = ?config.sessions.ttl(), "Applied new session settings");
+        }
+        // Sections needing a restart keep describing what the server runs with
+        *running =
+            ServerConfig { limits: config.limits, sessions: config.sessions, ..running.clone() };
+        Ok(())
+    }
+
+    /// Working memory limits of a new session's engine
+    fn session_engine_config(&self) -> EngineConfig {
+        self.config.read().unwrap().sessions.engine_config().unwrap_or_default()
+    }
 
-        Ok(Self { start_time: Utc::now(), engines: RwLock::new(HashMap::new()), default_engine })
+    /// Record that `session_id` was just used
+    fn touch_session(&self, session_id: &str) {
+        self.last_used.lock().unwrap().insert(session_id.to_string(), Instant::now());
+    }
+
+    /// Close sessions unused for longer than the session TTL, if one is set
+    ///
+    /// Engines no request holds any more go back to the engine pool. Returns the
+    /// number of sessions closed.
+    pub fn evict_idle_sessions(&self) -> usize {
+        let Some(ttl) = self.config.read().unwrap().sessions.ttl() else {
+            return 0;
+        };
+        let idle: Vec<String> = self
+            .last_used
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, used)| used.elapsed() >= ttl)
+            .map(|(session_id, _)| session_id.clone())
+            .collect();
+
+        for session_id in &idle {
+            info!("Closing session {} idle for over {:?}", session_id, ttl);
+            if let Some(engine) = self.remove_engine(session_id)
+                && let Ok(engine) = Arc::try_unwrap(engine)
+            {
+                self.engine_pool.release(engine);
+            }
+        }
+        idle.len()
+    }
+
+    /// Close idle sessions every `interval` in the background
+    pub fn spawn_session_sweeper(
+        self: &Arc<Self>,
+        interval: Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        let state = Arc::clone(self);
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                let state = Arc::clone(&state);
+                // Closing sessions resets their engines, which is blocking work
+                let _ = tokio::task::spawn_blocking(move || state.evict_idle_sessions()).await;
+            }
+        })
+    }
+
+    /// Admit facts under `admission` rather than the configured limits
+    pub fn with_admission_control(self, admission: AdmissionControl) -> Self {
+        Self { admission, ..self }
+    }
+
+    /// Draw engines from `engine_pool` rather than the default-sized pool
+    pub fn with_engine_pool(self, engine_pool: EnginePool) -> Self {
+        Self { engine_pool: Arc::new(engine_pool), ..self }
+    }
+
+    /// Report the server as shutting down, so health checks stop reporting it serving
+    pub fn begin_shutdown(&self) {
+        self.shutting_down.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutting_down.load(Ordering::Relaxed)
     }
 
     pub fn elapsed(&self) -> Duration {
@@ -63,6 +227,7 @@ impl AppState {
 
     /// Get or create an engine for a session
     pub fn get_or_create_engine(&self, session_id: &str) -> Arc<BingoEngine> {
+        self.touch_session(session_id);
         // First try to get existing engine with read lock
         {
             let engines = self.engines.read().unwrap();
@@ -80,13 +245,72 @@ impl AppState {
 
         info!("Creating new engine for session: {}", session_id);
         let engine =
-            Arc::new(BingoEngine::new().unwrap_or_else(|e| {
+            Arc::new(self.engine_pool.acquire().unwrap_or_else(|e| {
                 panic!("Failed to create engine for session {session_id}: {e}")
             }));
+        self.engine_pool.replenish_in_background();
+        if let Err(e) = engine.set_config(self.session_engine_config()) {
+            warn!(
+                "Failed to apply session limits to session {}: {}",
+                session_id, e
+            );
+        }
         engines.insert(session_id.to_string(), engine.clone());
         engine
     }
 
+    /// Get or create an engine for a session, warm-started from a cached ruleset
+    ///
+    /// A new session gets a copy of the compiled network of `ruleset_id` from the
+    /// tenant's asset cache instead of compiling its rules again. An existing session
+    /// is returned unchanged.
+    pub fn get_or_create_engine_from_template(
+        &self,
+        session_id: &str,
+        tenant_id: &str,
+        ruleset_id: &str,
+    ) -> anyhow::Result<Arc<BingoEngine>> {
+        self.touch_session(session_id);
+        if let Some(engine) = self.engines.read().unwrap().get(session_id) {
+            return Ok(engine.clone());
+        }
+
+        let tenant_id = CompiledAssetCache::tenant_or_default(tenant_id);
+        let template = self.asset_cache.get(tenant_id, ruleset_id).ok_or_else(|| {
+            anyhow!(
+                "Ruleset '{}' is not registered for tenant '{}'",
+                ruleset_id,
+                tenant_id
+            )
+        })?;
+        let engine = Arc::new(
+            BingoEngine::from_template(&template.engine)
+                .map_err(|e| anyhow!("Failed to create engine from template: {}", e))?,
+        );
+        engine
+            .set_config(self.session_engine_config())
+            .map_err(|e| anyhow!("Failed to apply session limits: {}", e))?;
+
+        let mut engines = self.engines.write().unwrap();
+        // Another thread may have created the session while the template was copied
+        let engine = engines.entry(session_id.to_string()).or_insert(engine).clone();
+
+        info!(
+            "Created engine for session {} from ruleset template {}/{}",
+            session_id, tenant_id, ruleset_id
+        );
+        Ok(engine)
+    }
+
+    /// Get the engine of an existing session
+    pub fn get_engine(&self, session_id: &str) -> Option<Arc<BingoEngine>> {
+        let engine = self.engines.read().unwrap().get(session_id).cloned();
+        if engine.is_some() {
+            self.touch_session(session_id);
+        }
+        engine
+    }
+
     /// Get the default engine for stateless operations
     pub fn get_default_engine(&self) -> Arc<BingoEngine> {
         self.default_engine.clone()
@@ -95,6 +319,8 @@ impl AppState {
     /// Remove an engine for a session (cleanup)
     pub fn remove_engine(&self, session_id: &str) -> Option<Arc<BingoEngine>> {
         info!("Removing engine for session: {}", session_id);
+        self.admission.forget_session(session_id);
+        self.last_used.lock().unwrap().remove(session_id);
         self.engines.write().unwrap().remove(session_id)
     }
 
@@ -102,4 +328,157 @@ impl AppState {
     pub fn active_sessions(&self) -> usize {
         self.engines.read().unwrap().len()
     }
+
+    /// Write a snapshot of every session to `dir`, one file per session
+    ///
+    /// Every session is attempted even when some fail, and only then are snapshots of
+    /// sessions that no longer exist removed, so a later
+    /// [`restore_sessions`](Self::restore_sessions) brings back exactly these sessions. A
+    /// session whose snapshot can't be written keeps its previous one. Returns the number
+    /// of sessions written, or an error naming every session that wasn't.
+    pub fn persist_sessions(&self, dir: &Path) -> anyhow::Result<usize> {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create snapshot directory {}", dir.display()))?;
+        let engines: Vec<(String, Arc<BingoEngine>)> = self
+            .engines
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(session_id, engine)| (session_id.clone(), engine.clone()))
+            .collect();
+
+        let mut current = HashSet::with_capacity(engines.len());
+        let mut written = 0;
+        let mut errors = Vec::new();
+        for (session_id, engine) in &engines {
+            let path = dir.join(snapshot_file_name(session_id));
+            match write_snapshot(session_id, engine, &path) {
+                Ok(()) => written += 1,
+                Err(e) => errors.push(format!("{e:#}")),
+            }
+            current.insert(path);
+        }
+        for path in snapshot_files(dir)? {
+            if !current.contains(&path)
+                && let Err(e) = std::fs::remove_file(&path)
+            {
+                errors.push(format!(
+                    "Failed to remove stale snapshot {}: {e}",
+                    path.display()
+                ));
+            }
+        }
+
+        info!("Persisted {} sessions to {}", written, dir.display());
+        if !errors.is_empty() {
+            anyhow::bail!("Failed to persist sessions: {}", errors.join("; "));
+        }
+        Ok(written)
+    }
+
+    /// Recreate the sessions persisted to `dir` by [`persist_sessions`](Self::persist_sessions)
+    ///
+    /// A missing directory holds no sessions. Snapshots that can't be read are skipped
+    /// with a warning. Returns the number of sessions restored.
+    pub fn restore_sessions(&self, dir: &Path) -> anyhow::Result<usize> {
+        if !dir.exists() {
+            return Ok(0);
+        }
+        let mut restored = 0;
+        for path in snapshot_files(dir)? {
+            let Some(session_id) = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(session_id_from_file_stem)
+            else {
+                warn!(
+                    "Skipping snapshot with an unexpected name: {}",
+                    path.display()
+                );
+                continue;
+            };
+            let engine = std::fs::read(&path)
+                .map_err(|e| anyhow!("{}", e))
+                .and_then(|bytes| EngineSnapshot::from_bytes(&bytes).map_err(|e| anyhow!("{}", e)))
+                .and_then(|snapshot| {
+                    let engine = self.engine_pool.acquire().map_err(|e| anyhow!("{}", e))?;
+                    engine.restore(&snapshot).map_err(|e| anyhow!("{}", e))?;
+                    engine
+                        .set_config(self.session_engine_config())
+                        .map_err(|e| anyhow!("{}", e))?;
+                    Ok(engine)
+                });
+            match engine {
+                Ok(engine) => {
+                    self.touch_session(&session_id);
+                    self.engines.write().unwrap().insert(session_id, Arc::new(engine));
+                    restored += 1;
+                }
+                Err(e) => warn!("Skipping snapshot {}: {}", path.display(), e),
+            }
+        }
+        self.engine_pool.replenish_in_background();
+        info!("Restored {} sessions from {}", restored, dir.display());
+        Ok(restored)
+    }
+}
+
+/// Snapshot files in `dir`
+fn snapshot_files(dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let entries = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read snapshot directory {}", dir.display()))?;
+    let mut files = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().is_some_and(|extension| extension == "snapshot") {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+/// Write `engine`'s snapshot to `path`
+///
+/// It is written aside and renamed, so a crash never leaves a truncated snapshot.
+fn write_snapshot(session_id: &str, engine: &BingoEngine, path: &Path) -> anyhow::Result<()> {
+    let bytes = engine
+        .snapshot()
+        .and_then(|snapshot| snapshot.to_bytes())
+        .map_err(|e| anyhow!("Failed to snapshot session {}: {}", session_id, e))?;
+    let partial = path.with_extension("snapshot.partial");
+    std::fs::write(&partial, bytes)
+        .and_then(|()| std::fs::rename(&partial, path))
+        .with_context(|| format!("Failed to write snapshot {}", path.display()))
+}
+
+/// File a session's snapshot is written to, with bytes that aren't safe in file
+/// names percent-encoded
+fn snapshot_file_name(session_id: &str) -> String {
+    let mut name = String::with_capacity(session_id.len() + 9);
+    for byte in session_id.bytes() {
+        if byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_' {
+            name.push(byte as char);
+        } else {
+            name.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    name.push_str(".snapshot");
+    name
+}
+
+/// Session ID encoded in a snapshot file stem by [`snapshot_file_name`]
+fn session_id_from_file_stem(stem: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(stem.len());
+    let mut rest = stem.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = std::str::from_utf8(tail.get(..2)?).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).ok()
 }
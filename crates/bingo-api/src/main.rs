@@ -3,19 +3,42 @@
 
 use std::env;
 use std::sync::Arc;
+use std::time::Duration;
 
+use tonic::service::interceptor::InterceptedService;
 use tonic::transport::Server;
-use tracing::info;
+use tracing::{info, warn};
 
 use bingo_api::AppState;
+use bingo_api::auth::AuthInterceptor;
+use bingo_api::config::{CONFIG_PATH_VAR, ConfigWatcher, ServerConfig};
+use bingo_api::generated::health::health_server::HealthServer;
 use bingo_api::generated::rules_engine_service_server::RulesEngineServiceServer;
 use bingo_api::grpc::service::RulesEngineServiceImpl;
+use bingo_api::health::HealthService;
+use bingo_api::shutdown::{serve_with_drain, shutdown_signal};
 
-#[tokio::main]
-async fn main() -> anyhow::Result<()> {
+/// How often sessions idle past the configured TTL are closed
+const SESSION_SWEEP_INTERVAL: Duration = Duration::from_secs(10);
+
+fn main() -> anyhow::Result<()> {
+    // Settings come from the BINGO_CONFIG file and the environment
+    let config = ServerConfig::load()?;
+
+    // Thread counts are fixed when the runtimes start
+    let mut runtime = tokio::runtime::Builder::new_multi_thread();
+    if let Some(threads) = config.server.worker_threads {
+        runtime.worker_threads(threads);
+    }
+    if let Some(threads) = config.server.compute_threads {
+        rayon::ThreadPoolBuilder::new().num_threads(threads).build_global()?;
+    }
+    runtime.enable_all().build()?.block_on(run(config))
+}
+
+async fn run(config: ServerConfig) -> anyhow::Result<()> {
     // Initialize distributed tracing
-    let tracing_config = bingo_api::tracing_setup::TracingConfig::from_environment();
-    bingo_api::tracing_setup::init_tracing(tracing_config)?;
+    bingo_api::tracing_setup::init_tracing(config.tracing())?;
 
     info!(
         version = "0.1.0",
@@ -44,7 +67,7 @@ async fn main() -> anyhow::Result<()> {
     }
 
     // Start gRPC server
-    start_grpc_server().await
+    start_grpc_server(config).await
 }
 
 async fn explain_command() -> anyhow::Result<()> {
@@ -68,31 +91,87 @@ fn print_help() {
     println!("  --help     Show this help message");
     println!();
     println!("If no command is provided, starts the gRPC server.");
+    println!("Set {CONFIG_PATH_VAR} to a TOML or YAML file to configure it.");
 }
 
-async fn start_grpc_server() -> anyhow::Result<()> {
-    // Environment-based configuration for gRPC
-    let grpc_addr = env::var("GRPC_LISTEN_ADDRESS").unwrap_or_else(|_| "0.0.0.0:50051".to_string());
+async fn start_grpc_server(config: ServerConfig) -> anyhow::Result<()> {
+    let grpc_addr = config.server.listen_address.clone();
 
     info!(?grpc_addr, "Configuring gRPC server");
 
-    // Initialize application state
-    let app_state = AppState::new().await?;
+    // Initialize application state, pre-initializing the engine pool
+    info!(
+        min_idle = config.engine_pool.min_idle,
+        max_idle = config.engine_pool.max_idle,
+        "Pre-initializing engine pool"
+    );
+    let shutdown_config = config.shutdown();
+    let app_state = Arc::new(AppState::from_config(config)?);
+    if app_state.admission.is_enabled() {
+        info!("gRPC fact rate limits enabled");
+    }
 
-    // Create gRPC service
-    let grpc_service = RulesEngineServiceImpl::new(Arc::new(app_state));
+    // Sessions persisted by the last shutdown are picked up again
+    if let Some(dir) = &shutdown_config.snapshot_dir {
+        app_state.restore_sessions(dir)?;
+    }
 
-    let server = Server::builder()
-        .add_service(RulesEngineServiceServer::new(grpc_service))
-        .serve(grpc_addr.parse()?);
+    // Limits and session settings follow the configuration file while serving
+    let watcher = env::var(CONFIG_PATH_VAR).ok().map(|path| {
+        info!(%path, "Watching configuration file for changes");
+        ConfigWatcher::new(path).spawn(app_state.clone())
+    });
+    let sweeper = app_state.spawn_session_sweeper(SESSION_SWEEP_INTERVAL);
+
+    // Create gRPC service
+    let grpc_service = RulesEngineServiceImpl::new(app_state.clone());
+
+    // Callers are authenticated when API keys or a JWT secret are configured
+    let service = RulesEngineServiceServer::new(grpc_service);
+    // Health checks are answered without credentials, so orchestrators can probe
+    let health = HealthServer::new(HealthService::new(app_state.clone()));
+    let router = match AuthInterceptor::from_environment()? {
+        Some(interceptor) => {
+            info!("gRPC authentication enabled");
+            Server::builder()
+                .add_service(health)
+                .add_service(InterceptedService::new(service, interceptor))
+        }
+        None => Server::builder().add_service(health).add_service(service),
+    };
+    let addr = grpc_addr.parse()?;
 
     println!("🚀 Bingo RETE gRPC server starting on {grpc_addr}");
     info!("gRPC server started successfully");
 
-    server.await?;
+    // On SIGTERM the server stops accepting streams and drains those in flight
+    serve_with_drain(
+        |stop| {
+            router.serve_with_shutdown(addr, async {
+                let _ = stop.await;
+            })
+        },
+        async {
+            shutdown_signal().await;
+            app_state.begin_shutdown();
+        },
+        shutdown_config.drain_timeout,
+    )
+    .await?;
+    sweeper.abort();
+    if let Some(watcher) = watcher {
+        watcher.abort();
+    }
+
+    if let Some(dir) = &shutdown_config.snapshot_dir
+        && let Err(e) = app_state.persist_sessions(dir)
+    {
+        warn!("Failed to persist sessions: {:#}", e);
+    }
 
-    // Gracefully shutdown tracing
+    // Gracefully shutdown tracing and metrics, exporting what they still hold
     bingo_api::tracing_setup::shutdown_tracing();
+    bingo_api::tracing_setup::shutdown_metrics();
 
     Ok(())
 }
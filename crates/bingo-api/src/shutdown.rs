@@ -0,0 +1,124 @@
+//! Graceful shutdown of the gRPC server
+//!
+//! On SIGTERM or Ctrl-C the server stops accepting connections and tells connected
+//! clients not to open new streams, then waits for requests in flight up to a drain
+//! deadline. Requests still running at the deadline are cut off. When a snapshot
+//! directory is configured, every session is then written to it, and restored from it
+//! when the server starts again.
+
+use std::future::Future;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use tracing::{info, warn};
+
+/// How the server shuts down
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShutdownConfig {
+    /// Time requests in flight are given to finish once shutdown starts
+    pub drain_timeout: Duration,
+    /// Directory sessions are persisted to on shutdown and restored from on start
+    pub snapshot_dir: Option<PathBuf>,
+}
+
+impl Default for ShutdownConfig {
+    fn default() -> Self {
+        Self { drain_timeout: Duration::from_secs(30), snapshot_dir: None }
+    }
+}
+
+/// Resolves when the process is asked to stop by SIGTERM or Ctrl-C
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            warn!(error = %e, "Failed to listen for Ctrl-C");
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(e) => {
+                warn!(error = %e, "Failed to listen for SIGTERM");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => info!("Received Ctrl-C, shutting down"),
+        _ = terminate => info!("Received SIGTERM, shutting down"),
+    }
+}
+
+/// Run `serve` until it stops after `signal`, giving it `drain_timeout` to drain
+///
+/// `serve` is expected to stop accepting work once `signal` resolves, as tonic's
+/// `serve_with_shutdown` does, and to finish when the requests in flight have. It is
+/// dropped, cutting those requests off, if they haven't finished by the deadline.
+pub async fn serve_with_drain<S, E>(
+    serve: impl FnOnce(tokio::sync::oneshot::Receiver<()>) -> S,
+    signal: impl Future<Output = ()>,
+    drain_timeout: Duration,
+) -> Result<(), E>
+where
+    S: Future<Output = Result<(), E>>,
+{
+    let (stop_tx, stop_rx) = tokio::sync::oneshot::channel();
+    let server = serve(stop_rx);
+    tokio::pin!(server);
+
+    tokio::select! {
+        result = &mut server => return result,
+        _ = signal => {}
+    }
+
+    info!(?drain_timeout, "Draining requests in flight");
+    let _ = stop_tx.send(());
+    match tokio::time::timeout(drain_timeout, &mut server).await {
+        Ok(result) => result,
+        Err(_) => {
+            warn!(
+                ?drain_timeout,
+                "Drain deadline passed, cutting off requests in flight"
+            );
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_requests_in_flight_are_drained_until_the_deadline() {
+        // A server whose requests finish 10ms after it is told to stop
+        let quick = |stop: tokio::sync::oneshot::Receiver<()>| async move {
+            let _ = stop.await;
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            Ok::<_, ()>(())
+        };
+        let drained = serve_with_drain(quick, async {}, Duration::from_secs(5));
+        assert_eq!(drained.await, Ok(()));
+
+        // A server whose requests never finish is cut off at the deadline
+        let stuck = |_stop| std::future::pending::<Result<(), ()>>();
+        let started = std::time::Instant::now();
+        serve_with_drain(stuck, async {}, Duration::from_millis(20)).await.unwrap();
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_server_errors_before_the_signal_are_returned() {
+        let failing = |_stop| async { Err::<(), _>("address in use") };
+        let result = serve_with_drain(failing, std::future::pending(), Duration::ZERO).await;
+        assert_eq!(result, Err("address in use"));
+    }
+}
@@ -2,9 +2,30 @@
 //!
 //! This module provides basic tracing setup using tracing-subscriber
 //! with console output for gRPC services.
+//!
+//! Rule firings can also be exported as OpenTelemetry spans. Each gRPC request that
+//! evaluates facts runs in a `grpc_request` span, and the engine runs every rule
+//! firing in a `rule_firing` span. `rule_firing_layer` turns these into OTel spans,
+//! with each firing a child of the request that ingested its facts.
+
+use opentelemetry::trace::{
+    Span as _, SpanContext, SpanKind, TraceContextExt, TraceFlags, TraceState, Tracer,
+};
+use opentelemetry::{Array, Context, KeyValue, Value};
+use std::time::{Instant, SystemTime};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::{Metadata, Subscriber, info};
+use tracing_subscriber::filter::filter_fn;
+use tracing_subscriber::layer::{self, Layer, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
+
+/// Target of the `grpc_request` spans opened by the gRPC service
+pub const GRPC_REQUEST_TARGET: &str = "bingo::grpc";
 
-use tracing::info;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+/// Target of the `rule_firing` spans opened by the engine
+pub const RULE_FIRING_TARGET: &str = "bingo::rule_firing";
 
 /// Configuration for tracing
 #[derive(Debug, Clone)]
@@ -15,6 +36,8 @@ pub struct TracingConfig {
     pub service_version: String,
     /// Environment (dev, staging, prod)
     pub environment: String,
+    /// Export rule firings as OpenTelemetry spans
+    pub rule_firing_spans: bool,
 }
 
 impl Default for TracingConfig {
@@ -23,35 +46,31 @@ impl Default for TracingConfig {
             service_name: "bingo-grpc-api".to_string(),
             service_version: env!("CARGO_PKG_VERSION").to_string(),
             environment: "development".to_string(),
-        }
-    }
-}
-
-impl TracingConfig {
-    /// Create configuration from environment variables
-    pub fn from_environment() -> Self {
-        Self {
-            service_name: std::env::var("SERVICE_NAME")
-                .unwrap_or_else(|_| "bingo-grpc-api".to_string()),
-            service_version: std::env::var("SERVICE_VERSION")
-                .unwrap_or_else(|_| env!("CARGO_PKG_VERSION").to_string()),
-            environment: std::env::var("BINGO_ENVIRONMENT")
-                .unwrap_or_else(|_| "development".to_string()),
+            rule_firing_spans: false,
         }
     }
 }
 
 /// Initialize simplified tracing
+///
+/// With `rule_firing_spans` set, rule firings are exported through the global
+/// OpenTelemetry tracer provider, which must be installed beforehand.
 pub fn init_tracing(config: TracingConfig) -> anyhow::Result<()> {
     info!("Initializing tracing for gRPC service");
 
-    // Initialize subscriber with simple console logging
+    // The console filter applies to the console only, so it never hides the
+    // debug-level rule firing spans from the OTel layer
+    let rule_firings = config
+        .rule_firing_spans
+        .then(|| rule_firing_layer(opentelemetry::global::tracer(config.service_name.clone())));
     tracing_subscriber::registry()
         .with(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "bingo_api=info,info".into()),
+            tracing_subscriber::fmt::layer().with_target(false).with_filter(
+                tracing_subscriber::EnvFilter::try_from_default_env()
+                    .unwrap_or_else(|_| "bingo_api=info,info".into()),
+            ),
         )
-        .with(tracing_subscriber::fmt::layer().with_target(false))
+        .with(rule_firings)
         .init();
 
     info!(
@@ -64,7 +83,179 @@ pub fn init_tracing(config: TracingConfig) -> anyhow::Result<()> {
     Ok(())
 }
 
-/// Shutdown tracing (no-op for simple tracing)
+/// Shutdown tracing, flushing spans the global OpenTelemetry tracer provider still holds
 pub fn shutdown_tracing() {
     info!("Shutting down tracing");
+    opentelemetry::global::shutdown_tracer_provider();
+}
+
+/// Shutdown metrics, flushing what the global OpenTelemetry meter provider still holds
+///
+/// The installed provider is swapped for a no-op one, and dropping it exports its
+/// final collection.
+pub fn shutdown_metrics() {
+    info!("Shutting down metrics");
+    opentelemetry::global::shutdown_meter_provider();
+}
+
+/// Span of a gRPC request that evaluates facts
+///
+/// A W3C `traceparent` header in the request metadata makes the request's OTel span
+/// a child of the caller's span.
+pub fn grpc_request_span(method: &str, metadata: &tonic::metadata::MetadataMap) -> tracing::Span {
+    let traceparent = metadata.get("traceparent").and_then(|value| value.to_str().ok());
+    tracing::info_span!(
+        target: "bingo::grpc",
+        "grpc_request",
+        rpc.method = method,
+        traceparent = traceparent,
+    )
+}
+
+/// Layer exporting `grpc_request` and `rule_firing` spans to an OpenTelemetry tracer
+///
+/// Firing spans carry the rule ID, the IDs of the facts that matched and the firing
+/// duration, and are parented to the innermost enclosing request span. Firings outside
+/// a gRPC request are not exported.
+pub fn rule_firing_layer<S, T>(tracer: T) -> impl Layer<S>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+    T: Tracer + Send + Sync + 'static,
+    T::Span: Send + Sync + 'static,
+{
+    RuleFiringLayer { tracer }.with_filter(filter_fn(|metadata: &Metadata<'_>| {
+        metadata.target() == GRPC_REQUEST_TARGET || metadata.target() == RULE_FIRING_TARGET
+    }))
+}
+
+struct RuleFiringLayer<T> {
+    tracer: T,
+}
+
+/// OTel context of an open gRPC request span
+struct RequestContext(Context);
+
+/// Rule firing in progress
+struct Firing {
+    rule_id: i64,
+    fact_ids: Vec<i64>,
+    started_at: SystemTime,
+    started: Instant,
+}
+
+/// Fields of the spans handled by `RuleFiringLayer`
+#[derive(Default)]
+struct SpanFields {
+    method: Option<String>,
+    traceparent: Option<String>,
+    rule_id: i64,
+    fact_ids: Vec<i64>,
+}
+
+impl Visit for SpanFields {
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        if field.name() == "rule_id" {
+            self.rule_id = value as i64;
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "rpc.method" => self.method = Some(value.to_string()),
+            "traceparent" => self.traceparent = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        // Fact IDs are recorded in their `Debug` form, e.g. `[3, 7]`
+        if field.name() == "fact_ids" {
+            self.fact_ids = format!("{value:?}")
+                .trim_matches(['[', ']'])
+                .split(", ")
+                .filter_map(|id| id.parse().ok())
+                .collect();
+        }
+    }
+}
+
+/// Remote parent context from a W3C `traceparent` header
+fn remote_parent(traceparent: &str) -> Option<Context> {
+    let mut parts = traceparent.split('-');
+    let (_version, trace_id, span_id, flags) =
+        (parts.next()?, parts.next()?, parts.next()?, parts.next()?);
+    let span_context = SpanContext::new(
+        opentelemetry::trace::TraceId::from_hex(trace_id).ok()?,
+        opentelemetry::trace::SpanId::from_hex(span_id).ok()?,
+        TraceFlags::new(u8::from_str_radix(flags, 16).ok()?),
+        true,
+        TraceState::default(),
+    );
+    span_context
+        .is_valid()
+        .then(|| Context::new().with_remote_span_context(span_context))
+}
+
+impl<S, T> Layer<S> for RuleFiringLayer<T>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+    T: Tracer + Send + Sync + 'static,
+    T::Span: Send + Sync + 'static,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: layer::Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let mut fields = SpanFields::default();
+        attrs.record(&mut fields);
+
+        if attrs.metadata().target() == RULE_FIRING_TARGET {
+            span.extensions_mut().insert(Firing {
+                rule_id: fields.rule_id,
+                fact_ids: fields.fact_ids,
+                started_at: SystemTime::now(),
+                started: Instant::now(),
+            });
+        } else {
+            let parent = fields.traceparent.as_deref().and_then(remote_parent).unwrap_or_default();
+            let method = fields.method.unwrap_or_default();
+            let request = self
+                .tracer
+                .span_builder(format!("grpc {method}"))
+                .with_kind(SpanKind::Server)
+                .with_attributes(vec![
+                    KeyValue::new("rpc.system", "grpc"),
+                    KeyValue::new("rpc.method", method),
+                ])
+                .start_with_context(&self.tracer, &parent);
+            span.extensions_mut().insert(RequestContext(parent.with_span(request)));
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: layer::Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else { return };
+        if let Some(RequestContext(request)) = span.extensions_mut().remove::<RequestContext>() {
+            request.span().end();
+            return;
+        }
+        let Some(firing) = span.extensions_mut().remove::<Firing>() else {
+            return;
+        };
+        let Some(parent) = span.scope().skip(1).find_map(|ancestor| {
+            ancestor.extensions().get::<RequestContext>().map(|r| r.0.clone())
+        }) else {
+            return;
+        };
+
+        let duration = firing.started.elapsed();
+        self.tracer
+            .span_builder("rule_firing")
+            .with_kind(SpanKind::Internal)
+            .with_start_time(firing.started_at)
+            .with_attributes(vec![
+                KeyValue::new("bingo.rule_id", firing.rule_id),
+                KeyValue::new("bingo.fact_ids", Value::Array(Array::I64(firing.fact_ids))),
+                KeyValue::new("bingo.duration_ms", duration.as_secs_f64() * 1000.0),
+            ])
+            .start_with_context(&self.tracer, &parent)
+            .end_with_timestamp(firing.started_at + duration);
+    }
 }
@@ -0,0 +1,130 @@
+//! gRPC Authentication Tests
+//!
+//! Tests that the auth interceptor identifies callers by API key or JWT, refuses
+//! missing, unknown, forged and expired credentials, and that evaluate-only callers
+//! cannot manage rules while rule admins can.
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use bingo_api::AppState;
+use bingo_api::auth::{
+    ApiKeyAuthenticator, AuthInterceptor, Identity, JwtAuthenticator, MtlsAuthenticator, Role,
+};
+use bingo_api::generated::rules_engine_service_server::RulesEngineService;
+use bingo_api::generated::*;
+use bingo_api::grpc::service::RulesEngineServiceImpl;
+use ring::hmac;
+use std::sync::Arc;
+use tonic::service::Interceptor;
+use tonic::{Code, Request, Status};
+
+const JWT_SECRET: &[u8] = b"test-signing-secret";
+
+fn interceptor() -> AuthInterceptor {
+    AuthInterceptor::new()
+        .with(
+            ApiKeyAuthenticator::parse("k-admin:release-pipeline:admin, k-eval:billing:evaluator")
+                .unwrap(),
+        )
+        .with(JwtAuthenticator::hs256(JWT_SECRET))
+}
+
+fn token(claims: serde_json::Value, secret: &[u8]) -> String {
+    let header = URL_SAFE_NO_PAD.encode(r#"{"alg":"HS256","typ":"JWT"}"#);
+    let claims = URL_SAFE_NO_PAD.encode(claims.to_string());
+    let signed = format!("{header}.{claims}");
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret);
+    let signature = URL_SAFE_NO_PAD.encode(hmac::sign(&key, signed.as_bytes()));
+    format!("{signed}.{signature}")
+}
+
+/// Run `message` through the interceptor with one metadata header, as the server would
+fn intercept<T>(header: Option<(&'static str, String)>, message: T) -> Result<Request<T>, Status> {
+    let mut request = Request::new(());
+    if let Some((name, value)) = header {
+        request.metadata_mut().insert(name, value.parse().unwrap());
+    }
+    let (metadata, extensions, ()) = interceptor().call(request)?.into_parts();
+    Ok(Request::from_parts(metadata, extensions, message))
+}
+
+fn identity(header: Option<(&'static str, String)>) -> Result<Identity, Status> {
+    let request = intercept(header, ())?;
+    Ok(request.extensions().get::<Identity>().unwrap().clone())
+}
+
+#[test]
+fn test_callers_are_identified_by_api_key_or_token() {
+    let admin = identity(Some(("x-api-key", "k-admin".to_string()))).unwrap();
+    assert_eq!(admin, Identity::new("release-pipeline", Role::RuleAdmin));
+
+    let valid = token(
+        serde_json::json!({"sub": "fraud-scorer", "role": "evaluator"}),
+        JWT_SECRET,
+    );
+    let evaluator = identity(Some(("authorization", format!("Bearer {valid}")))).unwrap();
+    assert_eq!(evaluator, Identity::new("fraud-scorer", Role::Evaluator));
+
+    let refused = |header: Option<(&'static str, String)>| identity(header).unwrap_err();
+    assert_eq!(refused(None).code(), Code::Unauthenticated);
+    assert_eq!(
+        refused(Some(("x-api-key", "k-stolen".to_string()))).code(),
+        Code::Unauthenticated
+    );
+
+    let forged = token(
+        serde_json::json!({"sub": "mallory", "role": "admin"}),
+        b"guessed",
+    );
+    let status = refused(Some(("authorization", format!("Bearer {forged}"))));
+    assert_eq!(status.message(), "Invalid token signature");
+
+    let expired = token(
+        serde_json::json!({"sub": "fraud-scorer", "role": "evaluator", "exp": 1_700_000_000}),
+        JWT_SECRET,
+    );
+    let status = refused(Some(("authorization", format!("Bearer {expired}"))));
+    assert_eq!(status.message(), "Token has expired");
+
+    // Client certificates are recognised by their SHA-256 fingerprint
+    let certificate = b"DER encoded certificate";
+    let fingerprint = ring::digest::digest(&ring::digest::SHA256, certificate);
+    let hex: String = fingerprint.as_ref().iter().map(|byte| format!("{byte:02X}:")).collect();
+    let mtls = MtlsAuthenticator::new().with_certificate(
+        hex.trim_end_matches(':'),
+        Identity::new("ledger", Role::Evaluator),
+    );
+    assert_eq!(mtls.identify(certificate).unwrap().subject, "ledger");
+    assert!(mtls.identify(b"another certificate").is_none());
+}
+
+#[tokio::test]
+async fn test_evaluators_cannot_manage_rules() {
+    let service = RulesEngineServiceImpl::new(Arc::new(AppState::new().await.unwrap()));
+    let compile = || CompileRulesRequest {
+        rules: vec![],
+        session_id: "governed".to_string(),
+        ..Default::default()
+    };
+    let list = || ListRulesRequest { session_id: "governed".to_string() };
+    let evaluator = || Some(("x-api-key", "k-eval".to_string()));
+    let admin = || Some(("x-api-key", "k-admin".to_string()));
+
+    let status = service
+        .compile_rules(intercept(evaluator(), compile()).unwrap())
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::PermissionDenied);
+    assert_eq!(status.message(), "'billing' is not allowed to manage rules");
+
+    let purge = PurgeCacheRequest::default();
+    let status = service.purge_cache(intercept(evaluator(), purge).unwrap()).await.unwrap_err();
+    assert_eq!(status.code(), Code::PermissionDenied);
+
+    service.compile_rules(intercept(admin(), compile()).unwrap()).await.unwrap();
+    service.list_rules(intercept(evaluator(), list()).unwrap()).await.unwrap();
+    service.list_rules(intercept(admin(), list()).unwrap()).await.unwrap();
+
+    // A server without the interceptor does not check callers
+    service.compile_rules(Request::new(compile())).await.unwrap();
+}
@@ -0,0 +1,89 @@
+//! gRPC Batch Summary Tests
+//!
+//! Tests that the completion message of a single-call processing stream carries the
+//! batch results aggregated by rule.
+
+use bingo_api::AppState;
+use bingo_api::generated::rules_engine_service_server::RulesEngineService;
+use bingo_api::generated::*;
+use bingo_api::grpc::service::RulesEngineServiceImpl;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio_stream::StreamExt;
+use tonic::Request;
+
+fn entity_rule(id: &str, entity_type: &str) -> Rule {
+    Rule {
+        id: id.to_string(),
+        name: format!("Flag {entity_type}"),
+        description: String::new(),
+        conditions: vec![Condition {
+            condition_type: Some(condition::ConditionType::Simple(SimpleCondition {
+                field: "entity_type".to_string(),
+                operator: SimpleOperator::Equal as i32,
+                value: Some(Value {
+                    value: Some(value::Value::StringValue(entity_type.to_string())),
+                }),
+            })),
+        }],
+        actions: vec![Action {
+            action_type: Some(action::ActionType::CreateFact(CreateFactAction {
+                fields: HashMap::from([(
+                    "flagged".to_string(),
+                    Value { value: Some(value::Value::BoolValue(true)) },
+                )]),
+            })),
+        }],
+        priority: 100,
+        enabled: true,
+        tags: vec![],
+        created_at: 0,
+        updated_at: 0,
+        ..Default::default()
+    }
+}
+
+fn entity(id: u64, entity_type: &str) -> Fact {
+    Fact {
+        id: id.to_string(),
+        data: HashMap::from([(
+            "entity_type".to_string(),
+            Value { value: Some(value::Value::StringValue(entity_type.to_string())) },
+        )]),
+        created_at: 0,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_completion_carries_batch_summary() {
+    let service = RulesEngineServiceImpl::new(Arc::new(AppState::new().await.unwrap()));
+    let request = Request::new(ProcessWithRulesRequest {
+        rules: vec![entity_rule("1", "shift"), entity_rule("2", "employee")],
+        facts: vec![entity(1, "shift"), entity(2, "shift"), entity(3, "employee"), entity(4, "x")],
+        request_id: "summary_test".to_string(),
+        options: None,
+        validate_rules_only: false,
+    });
+
+    let mut stream = service.process_with_rules_stream(request).await.unwrap().into_inner();
+    let mut summary = None;
+    while let Some(response) = stream.next().await {
+        if let Some(processing_response::Response::Completion(complete)) =
+            response.unwrap().response
+        {
+            summary = complete.summary;
+        }
+    }
+
+    let summary = summary.expect("completion should carry a summary");
+    assert_eq!(summary.facts_processed, 4);
+    assert_eq!(summary.results_generated, 3);
+    assert_eq!(summary.facts_affected, 3);
+    let fired: Vec<(&str, i64, i64)> = summary
+        .rules
+        .iter()
+        .map(|rule| (rule.rule_id.as_str(), rule.times_fired, rule.facts_affected))
+        .collect();
+    assert_eq!(fired, vec![("1", 2, 2), ("2", 1, 1)]);
+}
@@ -0,0 +1,180 @@
+//! gRPC Compiled-Asset Cache Admin Tests
+//!
+//! Tests registering rulesets into the per-tenant cache, evaluating them, starting
+//! sessions from them, and inspecting and purging the cache through the admin RPCs.
+
+use bingo_api::AppState;
+use bingo_api::generated::rules_engine_service_server::RulesEngineService;
+use bingo_api::generated::*;
+use bingo_api::grpc::service::RulesEngineServiceImpl;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio_stream::StreamExt;
+use tonic::Request;
+
+fn create_rule() -> Rule {
+    Rule {
+        id: "1".to_string(),
+        name: "Flag shifts".to_string(),
+        description: String::new(),
+        conditions: vec![Condition {
+            condition_type: Some(condition::ConditionType::Simple(SimpleCondition {
+                field: "entity_type".to_string(),
+                operator: SimpleOperator::Equal as i32,
+                value: Some(Value { value: Some(value::Value::StringValue("shift".to_string())) }),
+            })),
+        }],
+        actions: vec![Action {
+            action_type: Some(action::ActionType::CreateFact(CreateFactAction {
+                fields: HashMap::from([(
+                    "flagged".to_string(),
+                    Value { value: Some(value::Value::BoolValue(true)) },
+                )]),
+            })),
+        }],
+        priority: 100,
+        enabled: true,
+        tags: vec![],
+        created_at: 0,
+        updated_at: 0,
+        ..Default::default()
+    }
+}
+
+fn create_shift(id: &str) -> Fact {
+    Fact {
+        id: id.to_string(),
+        data: HashMap::from([(
+            "entity_type".to_string(),
+            Value { value: Some(value::Value::StringValue("shift".to_string())) },
+        )]),
+        created_at: 0,
+        ..Default::default()
+    }
+}
+
+async fn register(service: &RulesEngineServiceImpl, tenant_id: &str, ruleset_id: &str) {
+    let response = service
+        .register_ruleset(Request::new(RegisterRulesetRequest {
+            ruleset_id: ruleset_id.to_string(),
+            rules: vec![create_rule()],
+            tenant_id: tenant_id.to_string(),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(response.success);
+    assert_eq!(response.rules_compiled, 1);
+}
+
+async fn cache_stats(service: &RulesEngineServiceImpl, tenant_id: &str) -> Vec<TenantCacheStats> {
+    service
+        .get_cache_stats(Request::new(CacheStatsRequest {
+            tenant_id: tenant_id.to_string(),
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+        .tenants
+}
+
+#[tokio::test]
+async fn test_cached_ruleset_hits_and_misses_are_reported() {
+    let service = RulesEngineServiceImpl::new(Arc::new(AppState::new().await.unwrap()));
+    register(&service, "acme", "payroll").await;
+
+    let stream = service
+        .evaluate_ruleset_stream(Request::new(EvaluateRulesetRequest {
+            ruleset_id: "payroll".to_string(),
+            facts: vec![create_shift("1"), create_shift("2")],
+            request_id: "req-1".to_string(),
+            options: None,
+            tenant_id: "acme".to_string(),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    let results: Vec<_> = stream.collect().await;
+    assert_eq!(results.len(), 2);
+
+    // Another tenant cannot see the ruleset
+    let missing = service
+        .evaluate_ruleset_stream(Request::new(EvaluateRulesetRequest {
+            ruleset_id: "payroll".to_string(),
+            facts: vec![],
+            request_id: "req-2".to_string(),
+            options: None,
+            tenant_id: "globex".to_string(),
+        }))
+        .await;
+    assert_eq!(missing.err().unwrap().code(), tonic::Code::NotFound);
+
+    let acme = cache_stats(&service, "acme").await;
+    assert_eq!(acme.len(), 1);
+    assert_eq!(acme[0].entries, 1);
+    assert_eq!(acme[0].hits, 1);
+    assert_eq!(acme[0].ruleset_ids, vec!["payroll".to_string()]);
+
+    let all = cache_stats(&service, "").await;
+    assert_eq!(all.len(), 2);
+    assert_eq!(all[1].tenant_id, "globex");
+    assert_eq!(all[1].misses, 1);
+}
+
+#[tokio::test]
+async fn test_purge_cache_per_tenant() {
+    let service = RulesEngineServiceImpl::new(Arc::new(AppState::new().await.unwrap()));
+    register(&service, "acme", "payroll").await;
+    register(&service, "acme", "tronc").await;
+    register(&service, "globex", "payroll").await;
+
+    let purged = service
+        .purge_cache(Request::new(PurgeCacheRequest {
+            tenant_id: "acme".to_string(),
+            ruleset_id: String::new(),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(purged.entries_purged, 2);
+
+    assert_eq!(cache_stats(&service, "acme").await[0].entries, 0);
+    assert_eq!(cache_stats(&service, "globex").await[0].entries, 1);
+}
+
+#[tokio::test]
+async fn test_compile_rules_from_ruleset_template() {
+    let app_state = Arc::new(AppState::new().await.unwrap());
+    let service = RulesEngineServiceImpl::new(app_state.clone());
+    register(&service, "acme", "payroll").await;
+
+    let response = service
+        .compile_rules(Request::new(CompileRulesRequest {
+            session_id: "acme-session".to_string(),
+            ruleset_template: "payroll".to_string(),
+            tenant_id: "acme".to_string(),
+            ..Default::default()
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(response.success);
+    assert_eq!(response.rules_compiled, 0);
+
+    // The session has the template's rules but its own, empty fact store
+    let session = app_state.get_or_create_engine("acme-session");
+    assert_eq!(session.get_stats().rule_count, 1);
+    assert_eq!(session.get_stats().fact_count, 0);
+    assert_eq!(cache_stats(&service, "acme").await[0].hits, 1);
+
+    let missing = service
+        .compile_rules(Request::new(CompileRulesRequest {
+            session_id: "globex-session".to_string(),
+            ruleset_template: "payroll".to_string(),
+            tenant_id: "globex".to_string(),
+            ..Default::default()
+        }))
+        .await;
+    assert_eq!(missing.err().unwrap().code(), tonic::Code::NotFound);
+    assert_eq!(app_state.active_sessions(), 1);
+}
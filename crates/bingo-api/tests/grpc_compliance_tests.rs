@@ -51,6 +51,7 @@ fn create_basic_compliance_rules() -> Vec<Rule> {
         tags: vec!["compliance".to_string(), "calculation".to_string()],
         created_at: Utc::now().timestamp(),
         updated_at: Utc::now().timestamp(),
+        ..Default::default()
     }]
 }
 
@@ -63,6 +64,7 @@ async fn test_basic_rule_compilation() {
         rules: create_basic_compliance_rules(),
         session_id: "basic_test_session".to_string(),
         options: None,
+        ..Default::default()
     });
 
     let compile_response = service.compile_rules(compile_request).await.unwrap();
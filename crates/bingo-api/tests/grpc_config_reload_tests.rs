@@ -0,0 +1,135 @@
+//! gRPC Configuration Reload Tests
+//!
+//! Tests that reloaded rate limits and session settings apply without a restart, that
+//! restart-only sections keep their running values, that sessions idle past the
+//! session TTL are closed, and that `ConfigWatcher` picks up a rewritten file.
+
+use bingo_api::AppState;
+use bingo_api::admission::{AdmissionLimits, RateLimit};
+use bingo_api::config::{ConfigWatcher, ServerConfig};
+use bingo_api::generated::rules_engine_service_server::RulesEngineService;
+use bingo_api::generated::*;
+use bingo_api::grpc::service::RulesEngineServiceImpl;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tonic::Request;
+
+fn shift_rule() -> Rule {
+    Rule {
+        id: "1".to_string(),
+        name: "Flag shifts".to_string(),
+        conditions: vec![Condition {
+            condition_type: Some(condition::ConditionType::Simple(SimpleCondition {
+                field: "entity_type".to_string(),
+                operator: SimpleOperator::Equal as i32,
+                value: Some(Value { value: Some(value::Value::StringValue("shift".to_string())) }),
+            })),
+        }],
+        actions: vec![Action {
+            action_type: Some(action::ActionType::CreateFact(CreateFactAction {
+                fields: HashMap::from([(
+                    "flagged".to_string(),
+                    Value { value: Some(value::Value::BoolValue(true)) },
+                )]),
+            })),
+        }],
+        enabled: true,
+        ..Default::default()
+    }
+}
+
+async fn open_session(service: &RulesEngineServiceImpl, session_id: &str) {
+    let compiled = service
+        .compile_rules(Request::new(CompileRulesRequest {
+            rules: vec![shift_rule()],
+            session_id: session_id.to_string(),
+            ..Default::default()
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(compiled.success, "{}", compiled.error_message);
+}
+
+#[tokio::test]
+async fn test_reloaded_limits_apply_without_restart() {
+    let state = AppState::from_config(ServerConfig::default()).unwrap();
+    assert!(!state.admission.is_enabled());
+
+    let mut config = ServerConfig::default();
+    config.limits.session_facts_per_sec = Some(200.0);
+    config.limits.burst_seconds = 2.0;
+    config.server.listen_address = "0.0.0.0:6000".to_string();
+    state.apply_config(config).unwrap();
+
+    let session_facts = RateLimit::per_second(200.0).with_burst(400.0);
+    assert_eq!(
+        state.admission.limits(),
+        (
+            AdmissionLimits::default(),
+            AdmissionLimits { facts: Some(session_facts), bytes: None }
+        )
+    );
+    // The listen address only changes on restart
+    let running = state.config();
+    assert_eq!(running.limits.session_facts_per_sec, Some(200.0));
+    assert_eq!(running.server.listen_address, "0.0.0.0:50051");
+
+    // Invalid settings are refused and the running ones kept
+    let mut invalid = ServerConfig::default();
+    invalid.limits.burst_seconds = 0.0;
+    assert!(state.apply_config(invalid).is_err());
+    assert!(state.admission.is_enabled());
+}
+
+#[tokio::test]
+async fn test_sessions_idle_past_ttl_are_closed() {
+    let state = Arc::new(AppState::from_config(ServerConfig::default()).unwrap());
+    let service = RulesEngineServiceImpl::new(state.clone());
+    open_session(&service, "idle").await;
+    open_session(&service, "busy").await;
+
+    // Without a TTL sessions stay open however long they idle
+    assert_eq!(state.evict_idle_sessions(), 0);
+
+    let mut config = ServerConfig::default();
+    config.sessions.ttl_seconds = Some(1);
+    state.apply_config(config).unwrap();
+
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+    assert!(state.get_engine("busy").is_some());
+    assert_eq!(state.evict_idle_sessions(), 1);
+    assert!(state.get_engine("idle").is_none());
+    assert_eq!(state.active_sessions(), 1);
+}
+
+#[tokio::test]
+async fn test_watcher_applies_rewritten_file() {
+    let path = std::env::temp_dir().join(format!("bingo-reload-{}.toml", std::process::id()));
+    std::fs::write(&path, "[sessions]\nttl_seconds = 60\n").unwrap();
+    let state = Arc::new(AppState::from_config(ServerConfig::from_file(&path).unwrap()).unwrap());
+    let watcher = ConfigWatcher::new(&path)
+        .with_interval(Duration::from_millis(10))
+        .spawn(state.clone());
+
+    std::fs::write(
+        &path,
+        "[sessions]\nttl_seconds = 120\n\n[limits]\nglobal_facts_per_sec = 5000.0\n",
+    )
+    .unwrap();
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while state.config().sessions.ttl_seconds != Some(120) {
+        assert!(Instant::now() < deadline, "configuration was not reloaded");
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert!(state.admission.is_enabled());
+
+    // A file that no longer parses leaves the running settings in place
+    std::fs::write(&path, "[sessions]\nttl_seconds = \"soon\"\n").unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(state.config().sessions.ttl_seconds, Some(120));
+
+    watcher.abort();
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,158 @@
+//! gRPC Rule Debugging Tests
+//!
+//! Tests the bidirectional DebugRules stream: queued facts are stepped one at a time,
+//! resuming pauses at a rule breakpoint with the network memories of that step, and
+//! debugging never changes the session's working memory.
+
+use bingo_api::AppState;
+use bingo_api::generated::debug_command::CommandType;
+use bingo_api::generated::rules_engine_service_server::RulesEngineService;
+use bingo_api::generated::*;
+use bingo_api::grpc::service::RulesEngineServiceImpl;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio_stream::StreamExt;
+use tonic::{Code, Request, Status};
+
+fn large_amount_rule() -> Rule {
+    Rule {
+        id: "1".to_string(),
+        name: "Large amount".to_string(),
+        description: String::new(),
+        conditions: vec![Condition {
+            condition_type: Some(condition::ConditionType::Simple(SimpleCondition {
+                field: "amount".to_string(),
+                operator: SimpleOperator::GreaterThan as i32,
+                value: Some(Value { value: Some(value::Value::IntValue(100)) }),
+            })),
+        }],
+        actions: vec![Action {
+            action_type: Some(action::ActionType::CreateFact(CreateFactAction {
+                fields: HashMap::from([(
+                    "flagged".to_string(),
+                    Value { value: Some(value::Value::BoolValue(true)) },
+                )]),
+            })),
+        }],
+        priority: 100,
+        enabled: true,
+        tags: vec![],
+        created_at: 0,
+        updated_at: 0,
+        ..Default::default()
+    }
+}
+
+fn start_request(session_id: &str, rule_ids: &[&str]) -> Result<DebugRequest, Status> {
+    Ok(DebugRequest {
+        request: Some(debug_request::Request::Start(DebugStart {
+            session_id: session_id.to_string(),
+            breakpoints: Some(DebugBreakpoints {
+                rule_ids: rule_ids.iter().map(|id| id.to_string()).collect(),
+                ..Default::default()
+            }),
+        })),
+    })
+}
+
+fn fact_request(id: usize, amount: i64) -> Result<DebugRequest, Status> {
+    Ok(DebugRequest {
+        request: Some(debug_request::Request::Fact(Fact {
+            id: id.to_string(),
+            data: HashMap::from([(
+                "amount".to_string(),
+                Value { value: Some(value::Value::IntValue(amount)) },
+            )]),
+            ..Default::default()
+        })),
+    })
+}
+
+fn command_request(command_type: CommandType) -> Result<DebugRequest, Status> {
+    Ok(DebugRequest {
+        request: Some(debug_request::Request::Command(DebugCommand {
+            r#type: command_type as i32,
+        })),
+    })
+}
+
+async fn compiled_service(session_id: &str, app_state: &Arc<AppState>) -> RulesEngineServiceImpl {
+    let service = RulesEngineServiceImpl::new(Arc::clone(app_state));
+    service
+        .compile_rules(Request::new(CompileRulesRequest {
+            rules: vec![large_amount_rule()],
+            session_id: session_id.to_string(),
+            ..Default::default()
+        }))
+        .await
+        .unwrap();
+    service
+}
+
+#[tokio::test]
+async fn test_resume_pauses_at_rule_breakpoint() {
+    let app_state = Arc::new(AppState::new().await.unwrap());
+    let service = compiled_service("debug-1", &app_state).await;
+    let requests = tokio_stream::iter(vec![
+        start_request("debug-1", &["1"]),
+        fact_request(1, 20),
+        fact_request(2, 250),
+        fact_request(3, 300),
+        command_request(CommandType::Resume),
+        command_request(CommandType::Step),
+        command_request(CommandType::Stop),
+        // Never read after STOP
+        command_request(CommandType::Step),
+    ]);
+
+    let states: Vec<DebugState> = service
+        .start_debugging(requests)
+        .await
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+        .await;
+    assert_eq!(states.len(), 7);
+    assert_eq!(states[3].pending_facts, 3);
+
+    let resumed = &states[4];
+    assert!(resumed.paused_at_breakpoint);
+    assert_eq!(resumed.steps.len(), 2);
+    assert!(resumed.steps[0].results.is_empty());
+    let paused = &resumed.steps[1];
+    assert_eq!(paused.fact_id, 2);
+    assert_eq!(paused.results.len(), 1);
+    assert_eq!(paused.breakpoints_hit.as_ref().unwrap().rule_ids, ["1"]);
+    assert_eq!(resumed.pending_facts, 1);
+    assert_eq!(resumed.steps_taken, 2);
+    assert_eq!(resumed.alpha_memories.len(), 1);
+    assert_eq!(resumed.alpha_memories[0].condition, "amount > 100");
+    assert_eq!(resumed.alpha_memories[0].fact_ids, [2]);
+
+    let stepped = &states[5];
+    assert_eq!(stepped.steps.len(), 1);
+    assert_eq!(stepped.alpha_memories[0].fact_ids, [2, 3]);
+    assert_eq!(stepped.pending_facts, 0);
+    assert_eq!(states[6].steps_taken, 3);
+
+    let engine = app_state.get_engine("debug-1").unwrap();
+    assert_eq!(engine.fact_count(), 0);
+}
+
+#[tokio::test]
+async fn test_debugging_requires_start_and_compiled_session() {
+    let app_state = Arc::new(AppState::new().await.unwrap());
+    let service = compiled_service("debug-2", &app_state).await;
+
+    let requests = tokio_stream::iter(vec![command_request(CommandType::Step)]);
+    let status = service.start_debugging(requests).await.err().unwrap();
+    assert_eq!(status.code(), Code::FailedPrecondition);
+
+    let requests = tokio_stream::iter(vec![start_request("missing", &[])]);
+    let status = service.start_debugging(requests).await.err().unwrap();
+    assert_eq!(status.code(), Code::NotFound);
+
+    let requests = tokio_stream::iter(vec![start_request("debug-2", &["not-a-rule-id"])]);
+    let status = service.start_debugging(requests).await.err().unwrap();
+    assert_eq!(status.code(), Code::InvalidArgument);
+}
@@ -0,0 +1,157 @@
+//! gRPC Stateless Evaluation Tests
+//!
+//! Tests that `Evaluate` runs a request's facts against the rules sent with them
+//! without creating a session, that no rules or facts carry over between requests,
+//! that engines go back to the pool after use, and that invalid rules are refused.
+
+use bingo_api::AppState;
+use bingo_api::engine_pool::{EnginePool, EnginePoolConfig};
+use bingo_api::generated::rules_engine_service_server::RulesEngineService;
+use bingo_api::generated::*;
+use bingo_api::grpc::service::RulesEngineServiceImpl;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tonic::{Code, Request};
+
+fn entity_rule(id: &str, entity_type: &str) -> Rule {
+    Rule {
+        id: id.to_string(),
+        name: format!("Flag {entity_type}"),
+        conditions: vec![Condition {
+            condition_type: Some(condition::ConditionType::Simple(SimpleCondition {
+                field: "entity_type".to_string(),
+                operator: SimpleOperator::Equal as i32,
+                value: Some(Value {
+                    value: Some(value::Value::StringValue(entity_type.to_string())),
+                }),
+            })),
+        }],
+        actions: vec![Action {
+            action_type: Some(action::ActionType::CreateFact(CreateFactAction {
+                fields: HashMap::from([(
+                    "flagged".to_string(),
+                    Value { value: Some(value::Value::BoolValue(true)) },
+                )]),
+            })),
+        }],
+        enabled: true,
+        ..Default::default()
+    }
+}
+
+fn entity(id: u64, entity_type: &str) -> Fact {
+    Fact {
+        id: id.to_string(),
+        data: HashMap::from([(
+            "entity_type".to_string(),
+            Value { value: Some(value::Value::StringValue(entity_type.to_string())) },
+        )]),
+        ..Default::default()
+    }
+}
+
+async fn evaluate(
+    service: &RulesEngineServiceImpl,
+    rules: Vec<Rule>,
+    facts: Vec<Fact>,
+) -> Result<EvaluateResponse, tonic::Status> {
+    service
+        .evaluate(Request::new(EvaluateRequest {
+            rules,
+            facts,
+            request_id: "what-if".to_string(),
+            ..Default::default()
+        }))
+        .await
+        .map(|response| response.into_inner())
+}
+
+#[tokio::test]
+async fn test_evaluate_runs_facts_against_request_rules() {
+    let state = Arc::new(AppState::new().await.unwrap());
+    let service = RulesEngineServiceImpl::new(state.clone());
+
+    let response = evaluate(
+        &service,
+        vec![entity_rule("1", "shift"), entity_rule("2", "break")],
+        vec![entity(1, "shift"), entity(2, "break"), entity(3, "lunch")],
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(response.request_id, "what-if");
+    let fired: Vec<(&str, &str)> = response
+        .results
+        .iter()
+        .map(|result| {
+            (
+                result.matched_fact.as_ref().unwrap().id.as_str(),
+                result.rule_id.as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(fired, vec![("1", "1"), ("2", "2")]);
+    let summary = response.summary.unwrap();
+    assert_eq!((summary.facts_processed, summary.results_generated), (3, 2));
+    assert_eq!(state.active_sessions(), 0);
+
+    // The next request starts from an empty engine
+    let response = evaluate(
+        &service,
+        vec![entity_rule("1", "lunch")],
+        vec![entity(4, "shift")],
+    )
+    .await
+    .unwrap();
+    assert!(response.results.is_empty());
+}
+
+#[tokio::test]
+async fn test_evaluate_returns_engines_to_the_pool() {
+    let pool = EnginePool::new(EnginePoolConfig { min_idle: 0, max_idle: 1 }).unwrap();
+    let state = Arc::new(AppState::new().await.unwrap().with_engine_pool(pool));
+    let service = RulesEngineServiceImpl::new(state.clone());
+    assert_eq!(state.engine_pool.idle(), 0);
+
+    evaluate(
+        &service,
+        vec![entity_rule("1", "shift")],
+        vec![entity(1, "shift")],
+    )
+    .await
+    .unwrap();
+
+    // The engine is reset off the request path
+    for _ in 0..100 {
+        if state.engine_pool.idle() == 1 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert_eq!(state.engine_pool.idle(), 1);
+
+    // New sessions are given the pooled engine
+    let engine = state.get_or_create_engine("night-shift");
+    assert_eq!(engine.rule_count(), 0);
+    assert_eq!(state.engine_pool.idle(), 0);
+}
+
+#[tokio::test]
+async fn test_evaluate_refuses_invalid_rules() {
+    let service = RulesEngineServiceImpl::new(Arc::new(AppState::new().await.unwrap()));
+
+    let invalid = Rule { id: "not-a-number".to_string(), ..entity_rule("1", "shift") };
+    let status = evaluate(&service, vec![invalid], vec![entity(1, "shift")]).await.unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+
+    // Rules that fail to compile are refused as well
+    let backwards = Rule {
+        effective_date: 1_704_067_200,
+        expiry_date: 1_672_531_200,
+        ..entity_rule("1", "shift")
+    };
+    let status = evaluate(&service, vec![backwards], vec![entity(1, "shift")]).await.unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+    assert!(status.message().contains("Rule compilation failed"));
+}
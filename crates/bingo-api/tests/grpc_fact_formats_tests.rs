@@ -0,0 +1,123 @@
+//! gRPC Fact Format Tests
+//!
+//! Tests that facts in one request may carry their fields as protobuf values, a JSON
+//! payload or a MessagePack payload, and that payloads with an unsupported content type
+//! are rejected as invalid arguments.
+
+use bingo_api::AppState;
+use bingo_api::generated::rules_engine_service_server::RulesEngineService;
+use bingo_api::generated::*;
+use bingo_api::grpc::service::RulesEngineServiceImpl;
+use bingo_core::{FactPayloadFormat, FactValue, serialize_fact_fields};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio_stream::StreamExt;
+use tonic::{Code, Request};
+
+fn large_amount_rule() -> Rule {
+    Rule {
+        id: "1".to_string(),
+        name: "Large amount".to_string(),
+        description: String::new(),
+        conditions: vec![Condition {
+            condition_type: Some(condition::ConditionType::Simple(SimpleCondition {
+                field: "amount".to_string(),
+                operator: SimpleOperator::GreaterThan as i32,
+                value: Some(Value { value: Some(value::Value::IntValue(100)) }),
+            })),
+        }],
+        actions: vec![Action {
+            action_type: Some(action::ActionType::CreateFact(CreateFactAction {
+                fields: HashMap::from([(
+                    "flagged".to_string(),
+                    Value { value: Some(value::Value::BoolValue(true)) },
+                )]),
+            })),
+        }],
+        priority: 100,
+        enabled: true,
+        tags: vec![],
+        created_at: 0,
+        updated_at: 0,
+        ..Default::default()
+    }
+}
+
+fn payload_fact(id: &str, format: FactPayloadFormat, amount: i64) -> Fact {
+    let fields = HashMap::from([("amount".to_string(), FactValue::Integer(amount))]);
+    Fact {
+        id: id.to_string(),
+        content_type: format.content_type().to_string(),
+        payload: serialize_fact_fields(format, &fields).unwrap(),
+        ..Default::default()
+    }
+}
+
+fn process_request(facts: Vec<Fact>) -> Request<ProcessWithRulesRequest> {
+    Request::new(ProcessWithRulesRequest {
+        rules: vec![large_amount_rule()],
+        facts,
+        request_id: "formats_test".to_string(),
+        options: None,
+        validate_rules_only: false,
+    })
+}
+
+#[tokio::test]
+async fn test_facts_in_one_request_use_different_formats() {
+    let service = RulesEngineServiceImpl::new(Arc::new(AppState::new().await.unwrap()));
+    let protobuf = Fact {
+        id: "proto".to_string(),
+        data: HashMap::from([(
+            "amount".to_string(),
+            Value { value: Some(value::Value::IntValue(500)) },
+        )]),
+        ..Default::default()
+    };
+    let facts = vec![
+        protobuf,
+        payload_fact("json", FactPayloadFormat::Json, 250),
+        payload_fact("msgpack", FactPayloadFormat::MessagePack, 300),
+        payload_fact("small", FactPayloadFormat::MessagePack, 5),
+    ];
+
+    let mut stream = service.process_with_rules_stream(process_request(facts)).await.unwrap();
+    let mut summary = None;
+    while let Some(response) = stream.get_mut().next().await {
+        if let Some(processing_response::Response::Completion(complete)) =
+            response.unwrap().response
+        {
+            summary = complete.summary;
+        }
+    }
+
+    let summary = summary.expect("completion should carry a summary");
+    assert_eq!(summary.facts_processed, 4);
+    assert_eq!(summary.results_generated, 3);
+}
+
+#[tokio::test]
+async fn test_unsupported_content_type_is_invalid_argument() {
+    let service = RulesEngineServiceImpl::new(Arc::new(AppState::new().await.unwrap()));
+    let csv = Fact {
+        id: "csv".to_string(),
+        content_type: "text/csv".to_string(),
+        payload: b"amount\n250".to_vec(),
+        ..Default::default()
+    };
+
+    let mut stream = service.process_with_rules_stream(process_request(vec![csv])).await.unwrap();
+    let status = loop {
+        match stream.get_mut().next().await {
+            Some(Err(status)) => break status,
+            Some(Ok(_)) => continue,
+            None => panic!("stream should report the invalid fact"),
+        }
+    };
+    assert_eq!(status.code(), Code::InvalidArgument);
+    assert!(
+        status.message().contains("text/csv"),
+        "{}",
+        status.message()
+    );
+}
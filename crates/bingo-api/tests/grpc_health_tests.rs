@@ -0,0 +1,103 @@
+//! gRPC Health Tests
+//!
+//! Tests the grpc.health.v1 statuses for liveness and readiness, that both stop
+//! serving once the server starts shutting down, and that `ReadinessCheck` reports
+//! each engine self-check.
+
+use bingo_api::AppState;
+use bingo_api::generated::health::health_check_response::ServingStatus;
+use bingo_api::generated::health::health_server::Health;
+use bingo_api::generated::health::{HealthCheckRequest, HealthCheckResponse};
+use bingo_api::generated::rules_engine_service_server::RulesEngineService;
+use bingo_api::grpc::service::RulesEngineServiceImpl;
+use bingo_api::health::HealthService;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_stream::StreamExt;
+use tonic::{Code, Request};
+
+const READINESS_SERVICE: &str = "rules_engine.v1.RulesEngineService";
+
+async fn check(health: &HealthService, service: &str) -> Result<ServingStatus, Code> {
+    health
+        .check(Request::new(HealthCheckRequest {
+            service: service.to_string(),
+        }))
+        .await
+        .map(|response| response.into_inner().status())
+        .map_err(|status| status.code())
+}
+
+#[tokio::test]
+async fn test_liveness_and_readiness_stop_serving_on_shutdown() {
+    let state = Arc::new(AppState::new().await.unwrap());
+    let health = HealthService::new(state.clone());
+
+    assert_eq!(check(&health, "").await, Ok(ServingStatus::Serving));
+    assert_eq!(
+        check(&health, READINESS_SERVICE).await,
+        Ok(ServingStatus::Serving)
+    );
+    assert_eq!(
+        check(&health, "payments.v1.Ledger").await,
+        Err(Code::NotFound)
+    );
+
+    state.begin_shutdown();
+    assert_eq!(check(&health, "").await, Ok(ServingStatus::NotServing));
+    assert_eq!(
+        check(&health, READINESS_SERVICE).await,
+        Ok(ServingStatus::NotServing)
+    );
+}
+
+#[tokio::test]
+async fn test_readiness_check_reports_each_self_check() {
+    let state = Arc::new(AppState::new().await.unwrap());
+    let service = RulesEngineServiceImpl::new(state.clone());
+
+    let readiness = service.readiness_check(Request::new(())).await.unwrap().into_inner();
+    assert!(readiness.ready, "{:?}", readiness.checks);
+    let checks: Vec<(&str, &str)> = readiness
+        .checks
+        .iter()
+        .map(|check| (check.name.as_str(), check.severity.as_str()))
+        .collect();
+    assert_eq!(
+        checks,
+        vec![
+            ("Memory Headroom", "critical"),
+            ("Rule Compilation", "critical"),
+            ("Thread Pool", "high"),
+        ]
+    );
+
+    state.begin_shutdown();
+    let readiness = service.readiness_check(Request::new(())).await.unwrap().into_inner();
+    assert!(!readiness.ready);
+    assert!(readiness.shutting_down);
+}
+
+#[tokio::test]
+async fn test_watch_reports_status_changes() {
+    let state = Arc::new(AppState::new().await.unwrap());
+    let health = HealthService::new(state.clone()).with_watch_interval(Duration::from_millis(10));
+
+    let watch = |service: &str| {
+        health.watch(Request::new(HealthCheckRequest {
+            service: service.to_string(),
+        }))
+    };
+    let mut overall = watch("").await.unwrap().into_inner();
+    let status = |response: Option<Result<HealthCheckResponse, tonic::Status>>| {
+        response.unwrap().unwrap().status()
+    };
+    assert_eq!(status(overall.next().await), ServingStatus::Serving);
+
+    // Unknown services are reported rather than refused
+    let mut unknown = watch("payments.v1.Ledger").await.unwrap().into_inner();
+    assert_eq!(status(unknown.next().await), ServingStatus::ServiceUnknown);
+
+    state.begin_shutdown();
+    assert_eq!(status(overall.next().await), ServingStatus::NotServing);
+}
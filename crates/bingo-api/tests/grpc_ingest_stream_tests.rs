@@ -0,0 +1,315 @@
+//! gRPC Streaming Fact Ingestion Tests
+//!
+//! Tests the bidirectional IngestFacts stream: results and acknowledgements are
+//! streamed back as facts arrive, a client that stops reading responses stops the
+//! server from reading further facts, and facts failing their type's schema are
+//! rejected with their violations.
+
+use bingo_api::AppState;
+use bingo_api::generated::processing_control::ControlType;
+use bingo_api::generated::rules_engine_service_server::RulesEngineService;
+use bingo_api::generated::*;
+use bingo_api::grpc::service::RulesEngineServiceImpl;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio_stream::StreamExt;
+use tonic::{Request, Status};
+
+fn create_rule() -> Rule {
+    Rule {
+        id: "1".to_string(),
+        name: "Flag shifts".to_string(),
+        description: String::new(),
+        conditions: vec![Condition {
+            condition_type: Some(condition::ConditionType::Simple(SimpleCondition {
+                field: "entity_type".to_string(),
+                operator: SimpleOperator::Equal as i32,
+                value: Some(Value { value: Some(value::Value::StringValue("shift".to_string())) }),
+            })),
+        }],
+        actions: vec![Action {
+            action_type: Some(action::ActionType::CreateFact(CreateFactAction {
+                fields: HashMap::from([(
+                    "flagged".to_string(),
+                    Value { value: Some(value::Value::BoolValue(true)) },
+                )]),
+            })),
+        }],
+        priority: 100,
+        enabled: true,
+        tags: vec![],
+        created_at: 0,
+        updated_at: 0,
+        ..Default::default()
+    }
+}
+
+fn fact_request(id: usize, entity_type: &str) -> Result<IngestFactsRequest, Status> {
+    Ok(IngestFactsRequest {
+        request: Some(ingest_facts_request::Request::Fact(Fact {
+            id: id.to_string(),
+            data: HashMap::from([(
+                "entity_type".to_string(),
+                Value { value: Some(value::Value::StringValue(entity_type.to_string())) },
+            )]),
+            created_at: 0,
+            ..Default::default()
+        })),
+    })
+}
+
+fn start_request(
+    session_id: &str,
+    max_pending_responses: i32,
+    ack_interval: i32,
+) -> Result<IngestFactsRequest, Status> {
+    Ok(IngestFactsRequest {
+        request: Some(ingest_facts_request::Request::Start(IngestStart {
+            session_id: session_id.to_string(),
+            max_pending_responses,
+            ack_interval,
+            ..Default::default()
+        })),
+    })
+}
+
+fn control_request(control_type: ControlType) -> Result<IngestFactsRequest, Status> {
+    Ok(IngestFactsRequest {
+        request: Some(ingest_facts_request::Request::Control(ProcessingControl {
+            r#type: control_type as i32,
+            reason: String::new(),
+        })),
+    })
+}
+
+async fn compiled_service(session_id: &str) -> RulesEngineServiceImpl {
+    let service = RulesEngineServiceImpl::new(Arc::new(AppState::new().await.unwrap()));
+    service
+        .compile_rules(Request::new(CompileRulesRequest {
+            rules: vec![create_rule()],
+            session_id: session_id.to_string(),
+            ..Default::default()
+        }))
+        .await
+        .unwrap();
+    service
+}
+
+fn ack_of(response: &IngestFactsResponse) -> Option<&IngestAck> {
+    match &response.response {
+        Some(ingest_facts_response::Response::Ack(ack)) => Some(ack),
+        _ => None,
+    }
+}
+
+#[tokio::test]
+async fn test_results_and_acks_stream_back() {
+    let service = compiled_service("ingest-1").await;
+    let requests = tokio_stream::iter(vec![
+        start_request("ingest-1", 0, 2),
+        fact_request(1, "shift"),
+        fact_request(2, "break"),
+        fact_request(3, "shift"),
+        control_request(ControlType::Flush),
+        control_request(ControlType::Stop),
+        // Never read after STOP
+        fact_request(4, "shift"),
+    ]);
+
+    let responses: Vec<IngestFactsResponse> = service
+        .start_ingestion(requests)
+        .await
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+    let results = responses
+        .iter()
+        .filter(|response| {
+            matches!(
+                response.response,
+                Some(ingest_facts_response::Response::Result(_))
+            )
+        })
+        .count();
+    assert_eq!(results, 2);
+
+    // Interval ack after fact 2, then the flush ack and the final ack
+    let acks: Vec<&IngestAck> = responses.iter().filter_map(ack_of).collect();
+    assert_eq!(acks.len(), 3);
+    assert_eq!(acks[0].facts_received, 2);
+    assert_eq!(acks[0].results_sent, 1);
+    let last = acks.last().unwrap();
+    assert_eq!(last.facts_received, 3);
+    assert_eq!(last.facts_processed, 3);
+    assert_eq!(last.facts_rejected, 0);
+    assert_eq!(last.results_sent, 2);
+}
+
+#[tokio::test]
+async fn test_ingestion_requires_a_compiled_session() {
+    let service = compiled_service("ingest-2").await;
+
+    let unknown = service
+        .start_ingestion(tokio_stream::iter(vec![start_request("missing", 0, 0)]))
+        .await;
+    assert_eq!(unknown.err().unwrap().code(), tonic::Code::NotFound);
+
+    let no_start = service
+        .start_ingestion(tokio_stream::iter(vec![fact_request(1, "shift")]))
+        .await;
+    assert_eq!(
+        no_start.err().unwrap().code(),
+        tonic::Code::FailedPrecondition
+    );
+}
+
+#[tokio::test]
+async fn test_slow_reader_stops_fact_intake() {
+    let service = compiled_service("ingest-3").await;
+
+    // An endless supply of matching facts, counting how many the server pulls
+    let pulled = Arc::new(AtomicUsize::new(0));
+    let counter = pulled.clone();
+    let requests = tokio_stream::iter(
+        std::iter::once(start_request("ingest-3", 2, 0))
+            .chain((1..).map(|id| fact_request(id, "shift"))),
+    )
+    .map(move |request| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        request
+    });
+
+    let mut responses = service.start_ingestion(requests).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    // Start message, two buffered results and the fact whose result is waiting to send
+    let stalled_at = pulled.load(Ordering::SeqCst);
+    assert!(stalled_at <= 4, "server kept reading facts: {stalled_at}");
+
+    for _ in 0..10 {
+        responses.next().await.unwrap().unwrap();
+    }
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let resumed_at = pulled.load(Ordering::SeqCst);
+    assert!(resumed_at > stalled_at);
+    assert!(resumed_at <= stalled_at + 10);
+}
+
+#[tokio::test]
+async fn test_results_carry_the_client_fact_id() {
+    let service = compiled_service("ingest-4").await;
+    let requests = tokio_stream::iter(vec![
+        start_request("ingest-4", 0, 0),
+        Ok(IngestFactsRequest {
+            request: Some(ingest_facts_request::Request::Fact(Fact {
+                id: "shift-2024-07-01-emp-7".to_string(),
+                data: HashMap::from([(
+                    "entity_type".to_string(),
+                    Value { value: Some(value::Value::StringValue("shift".to_string())) },
+                )]),
+                created_at: 1_719_792_000,
+                ..Default::default()
+            })),
+        }),
+    ]);
+
+    let results: Vec<RuleExecutionResult> = service
+        .start_ingestion(requests)
+        .await
+        .unwrap()
+        .filter_map(|response| match response.unwrap().response {
+            Some(ingest_facts_response::Response::Result(result)) => Some(result),
+            _ => None,
+        })
+        .collect()
+        .await;
+
+    assert_eq!(results.len(), 1);
+    let result = &results[0];
+    assert_eq!(result.external_id, "shift-2024-07-01-emp-7");
+    let matched_fact = result.matched_fact.as_ref().unwrap();
+    assert_eq!(matched_fact.id, "shift-2024-07-01-emp-7");
+    assert_eq!(matched_fact.created_at, 1_719_792_000);
+    assert_eq!(result.metadata["external_id"], "shift-2024-07-01-emp-7");
+    assert_eq!(
+        result.metadata["fact_created_at"],
+        "2024-07-01T00:00:00+00:00"
+    );
+    assert_ne!(result.metadata["fact_id"], "0");
+}
+
+#[tokio::test]
+async fn test_facts_failing_their_schema_are_rejected_with_violations() {
+    let service = compiled_service("ingest-schema").await;
+    let schema = |kind: schema_field::Kind| SetFactSchemaRequest {
+        session_id: "ingest-schema".to_string(),
+        fact_type: "timesheet".to_string(),
+        fields: vec![SchemaField {
+            name: "hours".to_string(),
+            kind: kind as i32,
+            required: true,
+            enum_values: vec![],
+        }],
+        closed: false,
+    };
+    let invalid = service
+        .set_fact_schema(Request::new(schema(schema_field::Kind::Enum)))
+        .await
+        .unwrap_err();
+    assert_eq!(invalid.code(), tonic::Code::InvalidArgument);
+    let set = service
+        .set_fact_schema(Request::new(schema(schema_field::Kind::Integer)))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(set.field_count, 1);
+
+    let timesheet = |id: usize, hours: value::Value| {
+        let mut request = fact_request(id, "shift").unwrap();
+        let Some(ingest_facts_request::Request::Fact(fact)) = &mut request.request else {
+            unreachable!()
+        };
+        fact.data.insert(
+            "type".to_string(),
+            Value { value: Some(value::Value::StringValue("timesheet".to_string())) },
+        );
+        fact.data.insert("hours".to_string(), Value { value: Some(hours) });
+        Ok(request)
+    };
+    let requests = tokio_stream::iter(vec![
+        start_request("ingest-schema", 0, 0),
+        timesheet(1, value::Value::IntValue(8)),
+        timesheet(2, value::Value::StringValue("8".to_string())),
+    ]);
+    let responses: Vec<IngestFactsResponse> = service
+        .start_ingestion(requests)
+        .await
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+    let rejections: Vec<&FactRejection> = responses
+        .iter()
+        .filter_map(|response| match &response.response {
+            Some(ingest_facts_response::Response::Rejected(rejection)) => Some(rejection),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(rejections.len(), 1);
+    assert_eq!(rejections[0].fact_id, "2");
+    assert_eq!(
+        rejections[0].violations,
+        vec![FactViolation {
+            field: "hours".to_string(),
+            code: "type_mismatch".to_string(),
+            message: "field 'hours' expects an integer, got string 8".to_string(),
+        }]
+    );
+    let acks: Vec<&IngestAck> = responses.iter().filter_map(ack_of).collect();
+    assert_eq!(acks.last().unwrap().facts_processed, 1);
+}
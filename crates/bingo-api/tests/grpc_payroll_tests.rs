@@ -54,6 +54,7 @@ fn create_payroll_rules() -> Vec<Rule> {
             tags: vec!["payroll".to_string(), "calculation".to_string()],
             created_at: Utc::now().timestamp(),
             updated_at: Utc::now().timestamp(),
+            ..Default::default()
         },
         Rule {
             id: "2".to_string(),
@@ -87,6 +88,7 @@ fn create_payroll_rules() -> Vec<Rule> {
             tags: vec!["payroll".to_string(), "overtime".to_string()],
             created_at: Utc::now().timestamp(),
             updated_at: Utc::now().timestamp(),
+            ..Default::default()
         },
         Rule {
             id: "3".to_string(),
@@ -126,6 +128,7 @@ fn create_payroll_rules() -> Vec<Rule> {
             tags: vec!["payroll".to_string(), "gross_pay".to_string()],
             created_at: Utc::now().timestamp(),
             updated_at: Utc::now().timestamp(),
+            ..Default::default()
         },
     ]
 }
@@ -160,6 +163,7 @@ fn create_payroll_facts() -> Vec<Fact> {
                 );
                 map
             },
+            ..Default::default()
         },
         Fact {
             id: "shift_001".to_string(),
@@ -204,6 +208,7 @@ fn create_payroll_facts() -> Vec<Fact> {
                 );
                 map
             },
+            ..Default::default()
         },
         Fact {
             id: "shift_002".to_string(),
@@ -248,6 +253,7 @@ fn create_payroll_facts() -> Vec<Fact> {
                 );
                 map
             },
+            ..Default::default()
         },
     ]
 }
@@ -261,6 +267,7 @@ async fn test_payroll_rule_compilation() {
         rules: create_payroll_rules(),
         session_id: "payroll_test_session".to_string(),
         options: None,
+        ..Default::default()
     });
 
     let compile_response = service.compile_rules(compile_request).await.unwrap();
@@ -403,6 +410,7 @@ async fn test_payroll_scenario() {
                 );
                 map
             },
+            ..Default::default()
         };
         overtime_facts.push(shift);
     }
@@ -0,0 +1,143 @@
+//! gRPC Rate Limiting Tests
+//!
+//! Tests that facts over the per-session or global rate limits are refused with
+//! RESOURCE_EXHAUSTED and retry-after metadata, that sessions are limited
+//! independently, and that oversized batches are slowed down rather than refused
+//! forever.
+
+use bingo_api::AppState;
+use bingo_api::admission::{AdmissionControl, AdmissionLimits, RateLimit};
+use bingo_api::generated::rules_engine_service_server::RulesEngineService;
+use bingo_api::generated::*;
+use bingo_api::grpc::service::RulesEngineServiceImpl;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio_stream::StreamExt;
+use tonic::{Code, Request, Status};
+
+fn shift(id: usize) -> Fact {
+    Fact {
+        id: id.to_string(),
+        data: HashMap::from([(
+            "entity_type".to_string(),
+            Value { value: Some(value::Value::StringValue("shift".to_string())) },
+        )]),
+        created_at: 0,
+        ..Default::default()
+    }
+}
+
+fn facts_per_second(rate: f64) -> AdmissionLimits {
+    AdmissionLimits { facts: Some(RateLimit::per_second(rate)), bytes: None }
+}
+
+fn retry_after(status: &Status) -> (u64, u64) {
+    let value = |key| status.metadata().get(key).unwrap().to_str().unwrap().parse().unwrap();
+    (value("retry-after"), value("grpc-retry-pushback-ms"))
+}
+
+#[test]
+fn test_sessions_are_limited_independently() {
+    let admission = AdmissionControl::new(AdmissionLimits::default(), facts_per_second(2.0));
+    admission.admit(Some("payroll"), 1, 100).unwrap();
+    admission.admit(Some("payroll"), 1, 100).unwrap();
+
+    let status = admission.admit(Some("payroll"), 1, 100).unwrap_err();
+    assert_eq!(status.code(), Code::ResourceExhausted);
+    let (seconds, millis) = retry_after(&status);
+    assert_eq!(seconds, 1);
+    assert!(millis > 0 && millis <= 500, "unexpected pushback {millis}");
+
+    // Another session has its own budget, and ending a session resets it
+    admission.admit(Some("rostering"), 2, 100).unwrap();
+    admission.forget_session("payroll");
+    admission.admit(Some("payroll"), 1, 100).unwrap();
+}
+
+#[test]
+fn test_global_byte_limit_applies_to_every_caller() {
+    let bytes = AdmissionLimits { facts: None, bytes: Some(RateLimit::per_second(1_000.0)) };
+    let admission = AdmissionControl::new(bytes, AdmissionLimits::default());
+    admission.admit(Some("a"), 1, 600).unwrap();
+    let status = admission.admit(Some("b"), 1, 600).unwrap_err();
+    assert_eq!(status.code(), Code::ResourceExhausted);
+    assert_eq!(retry_after(&status).0, 1);
+
+    // A refused request takes nothing from the bucket
+    admission.admit(None, 1, 400).unwrap();
+
+    // Without limits everything is admitted
+    let unlimited = AdmissionControl::default();
+    assert!(!unlimited.is_enabled());
+    unlimited.admit(Some("a"), 1_000_000, usize::MAX).unwrap();
+}
+
+#[tokio::test]
+async fn test_ingestion_over_the_session_limit_is_refused() {
+    let admission = AdmissionControl::new(
+        AdmissionLimits::default(),
+        AdmissionLimits { facts: Some(RateLimit::per_second(1.0).with_burst(2.0)), bytes: None },
+    );
+    let state = AppState::new().await.unwrap().with_admission_control(admission);
+    let service = RulesEngineServiceImpl::new(Arc::new(state));
+    service
+        .compile_rules(Request::new(CompileRulesRequest {
+            rules: vec![],
+            session_id: "limited".to_string(),
+            ..Default::default()
+        }))
+        .await
+        .unwrap();
+
+    let start = IngestFactsRequest {
+        request: Some(ingest_facts_request::Request::Start(IngestStart {
+            session_id: "limited".to_string(),
+            max_pending_responses: 0,
+            ack_interval: 1,
+            ..Default::default()
+        })),
+    };
+    let fact =
+        |id| IngestFactsRequest { request: Some(ingest_facts_request::Request::Fact(shift(id))) };
+    let requests = tokio_stream::iter([start, fact(1), fact(2), fact(3), fact(4)].map(Ok));
+    let responses: Vec<Result<IngestFactsResponse, Status>> =
+        service.start_ingestion(requests).await.unwrap().collect().await;
+
+    // An ack for each admitted fact, then the refusal ends the stream
+    assert_eq!(responses.len(), 3);
+    assert!(responses[..2].iter().all(Result::is_ok));
+    let status = responses[2].as_ref().unwrap_err();
+    assert_eq!(status.code(), Code::ResourceExhausted);
+    assert_eq!(retry_after(status).0, 1);
+}
+
+#[tokio::test]
+async fn test_oversized_batches_are_slowed_down() {
+    let admission = AdmissionControl::new(facts_per_second(3.0), AdmissionLimits::default());
+    let state = AppState::new().await.unwrap().with_admission_control(admission);
+    let service = RulesEngineServiceImpl::new(Arc::new(state));
+    let request = || {
+        Request::new(ProcessWithRulesRequest {
+            rules: vec![],
+            facts: (1..=6).map(shift).collect(),
+            request_id: "burst".to_string(),
+            options: None,
+            validate_rules_only: false,
+        })
+    };
+
+    // The first batch is larger than the burst but admitted from a full bucket, and
+    // the next must wait for the debt to be paid back
+    service.process_with_rules_stream(request()).await.unwrap();
+    let status = match service.process_with_rules_stream(request()).await {
+        Ok(_) => panic!("second batch was admitted"),
+        Err(status) => status,
+    };
+    assert_eq!(status.code(), Code::ResourceExhausted);
+    let (seconds, millis) = retry_after(&status);
+    assert_eq!(seconds, 2);
+    assert!(
+        millis > 1_900 && millis <= 2_000,
+        "unexpected pushback {millis}"
+    );
+}
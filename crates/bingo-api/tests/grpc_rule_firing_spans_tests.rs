@@ -0,0 +1,268 @@
+//! gRPC Rule Firing Span Tests
+//!
+//! Tests that rule firings are exported as OpenTelemetry spans that are children of
+//! the gRPC request evaluating the facts, continue the caller's trace, and carry the
+//! rule ID, fact IDs and firing duration.
+
+use bingo_api::AppState;
+use bingo_api::generated::rules_engine_service_server::RulesEngineService;
+use bingo_api::generated::*;
+use bingo_api::grpc::service::RulesEngineServiceImpl;
+use bingo_api::tracing_setup::rule_firing_layer;
+use opentelemetry::trace::{
+    SpanBuilder, SpanContext, SpanId, Status as SpanStatus, TraceContextExt, TraceFlags, TraceId,
+    TraceState, Tracer,
+};
+use opentelemetry::{Array, Context, KeyValue, Value as OtelValue};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+use tokio_stream::StreamExt;
+use tonic::Request;
+use tracing_subscriber::layer::SubscriberExt;
+
+const CALLER_TRACE: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+const CALLER_SPAN: &str = "00f067aa0ba902b7";
+
+/// Span as handed to an exporter
+#[derive(Debug, Clone)]
+struct Exported {
+    name: String,
+    context: SpanContext,
+    parent: SpanContext,
+    attributes: Vec<KeyValue>,
+    start: SystemTime,
+    end: SystemTime,
+}
+
+impl Exported {
+    fn attribute(&self, key: &str) -> Option<&OtelValue> {
+        self.attributes.iter().find(|kv| kv.key.as_str() == key).map(|kv| &kv.value)
+    }
+}
+
+/// Tracer recording ended spans in place of an SDK exporter
+#[derive(Clone, Default)]
+struct RecordingTracer {
+    exported: Arc<Mutex<Vec<Exported>>>,
+    next_span_id: Arc<AtomicU64>,
+}
+
+struct RecordingSpan {
+    span: Exported,
+    exported: Arc<Mutex<Vec<Exported>>>,
+}
+
+impl Tracer for RecordingTracer {
+    type Span = RecordingSpan;
+
+    fn build_with_context(&self, builder: SpanBuilder, parent_cx: &Context) -> RecordingSpan {
+        let parent = parent_cx.span().span_context().clone();
+        let trace_id = if parent.is_valid() {
+            parent.trace_id()
+        } else {
+            TraceId::from_bytes([1; 16])
+        };
+        let span_id = SpanId::from_bytes(
+            (self.next_span_id.fetch_add(1, Ordering::Relaxed) + 1).to_be_bytes(),
+        );
+        RecordingSpan {
+            span: Exported {
+                name: builder.name.to_string(),
+                context: SpanContext::new(
+                    trace_id,
+                    span_id,
+                    TraceFlags::SAMPLED,
+                    false,
+                    TraceState::default(),
+                ),
+                parent,
+                attributes: builder.attributes.unwrap_or_default(),
+                start: builder.start_time.unwrap_or_else(SystemTime::now),
+                end: SystemTime::UNIX_EPOCH,
+            },
+            exported: self.exported.clone(),
+        }
+    }
+}
+
+impl opentelemetry::trace::Span for RecordingSpan {
+    fn add_event_with_timestamp<T>(&mut self, _: T, _: SystemTime, _: Vec<KeyValue>)
+    where
+        T: Into<Cow<'static, str>>,
+    {
+    }
+
+    fn span_context(&self) -> &SpanContext {
+        &self.span.context
+    }
+
+    fn is_recording(&self) -> bool {
+        true
+    }
+
+    fn set_attribute(&mut self, attribute: KeyValue) {
+        self.span.attributes.push(attribute);
+    }
+
+    fn set_status(&mut self, _: SpanStatus) {}
+
+    fn update_name<T>(&mut self, new_name: T)
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.span.name = new_name.into().to_string();
+    }
+
+    fn end_with_timestamp(&mut self, timestamp: SystemTime) {
+        self.span.end = timestamp;
+        self.exported.lock().unwrap().push(self.span.clone());
+    }
+}
+
+fn create_rule() -> Rule {
+    Rule {
+        id: "1".to_string(),
+        name: "Flag shifts".to_string(),
+        description: String::new(),
+        conditions: vec![Condition {
+            condition_type: Some(condition::ConditionType::Simple(SimpleCondition {
+                field: "entity_type".to_string(),
+                operator: SimpleOperator::Equal as i32,
+                value: Some(Value { value: Some(value::Value::StringValue("shift".to_string())) }),
+            })),
+        }],
+        actions: vec![Action {
+            action_type: Some(action::ActionType::CreateFact(CreateFactAction {
+                fields: HashMap::from([(
+                    "flagged".to_string(),
+                    Value { value: Some(value::Value::BoolValue(true)) },
+                )]),
+            })),
+        }],
+        priority: 100,
+        enabled: true,
+        tags: vec![],
+        created_at: 0,
+        updated_at: 0,
+        ..Default::default()
+    }
+}
+
+fn create_shift(id: &str) -> Fact {
+    Fact {
+        id: id.to_string(),
+        data: HashMap::from([(
+            "entity_type".to_string(),
+            Value { value: Some(value::Value::StringValue("shift".to_string())) },
+        )]),
+        created_at: 0,
+        ..Default::default()
+    }
+}
+
+async fn evaluate(service: &RulesEngineServiceImpl, traceparent: Option<&str>) -> usize {
+    let mut request = Request::new(EvaluateRulesetRequest {
+        ruleset_id: "payroll".to_string(),
+        facts: vec![create_shift("1"), create_shift("2")],
+        request_id: "req-1".to_string(),
+        options: None,
+        tenant_id: "acme".to_string(),
+    });
+    if let Some(traceparent) = traceparent {
+        request.metadata_mut().insert("traceparent", traceparent.parse().unwrap());
+    }
+    let stream = service.evaluate_ruleset_stream(request).await.unwrap().into_inner();
+    stream.collect::<Vec<_>>().await.len()
+}
+
+async fn payroll_service() -> RulesEngineServiceImpl {
+    let service = RulesEngineServiceImpl::new(Arc::new(AppState::new().await.unwrap()));
+    let response = service
+        .register_ruleset(Request::new(RegisterRulesetRequest {
+            ruleset_id: "payroll".to_string(),
+            rules: vec![create_rule()],
+            tenant_id: "acme".to_string(),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(response.success);
+    service
+}
+
+#[tokio::test]
+async fn test_rule_firings_are_children_of_the_request_span() {
+    let tracer = RecordingTracer::default();
+    let subscriber = tracing_subscriber::registry().with(rule_firing_layer(tracer.clone()));
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let service = payroll_service().await;
+    let traceparent = format!("00-{CALLER_TRACE}-{CALLER_SPAN}-01");
+    assert_eq!(evaluate(&service, Some(&traceparent)).await, 2);
+
+    let exported = tracer.exported.lock().unwrap().clone();
+    assert_eq!(exported.len(), 3);
+    // Firings end before the request that evaluated them
+    let request = exported.last().unwrap();
+    assert_eq!(request.name, "grpc EvaluateRulesetStream");
+    assert_eq!(
+        request.parent.trace_id(),
+        TraceId::from_hex(CALLER_TRACE).unwrap()
+    );
+    assert_eq!(
+        request.parent.span_id(),
+        SpanId::from_hex(CALLER_SPAN).unwrap()
+    );
+    assert!(request.parent.is_remote());
+
+    let mut fact_ids = Vec::new();
+    for firing in &exported[..2] {
+        assert_eq!(firing.name, "rule_firing");
+        assert_eq!(firing.parent, request.context);
+        assert_eq!(firing.context.trace_id(), request.context.trace_id());
+        assert_eq!(firing.attribute("bingo.rule_id"), Some(&OtelValue::I64(1)));
+        match firing.attribute("bingo.fact_ids") {
+            Some(OtelValue::Array(Array::I64(ids))) => fact_ids.extend(ids.iter().copied()),
+            other => panic!("unexpected fact IDs {other:?}"),
+        }
+        match firing.attribute("bingo.duration_ms") {
+            Some(OtelValue::F64(duration_ms)) => assert!(*duration_ms >= 0.0),
+            other => panic!("unexpected duration {other:?}"),
+        }
+        assert!(firing.start >= request.start && firing.end <= request.end);
+    }
+    fact_ids.sort_unstable();
+    fact_ids.dedup();
+    assert_eq!(fact_ids.len(), 2);
+}
+
+#[tokio::test]
+async fn test_requests_without_a_caller_start_a_trace() {
+    let tracer = RecordingTracer::default();
+    let subscriber = tracing_subscriber::registry().with(rule_firing_layer(tracer.clone()));
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let service = payroll_service().await;
+    assert_eq!(evaluate(&service, None).await, 2);
+    let exported = tracer.exported.lock().unwrap().clone();
+    assert_eq!(exported.len(), 3);
+    assert!(!exported[2].parent.is_valid());
+
+    // Firings outside a gRPC request are not exported
+    let engine = bingo_core::BingoEngine::new().unwrap();
+    engine
+        .add_rules_from_dsl(r#"rule "Any" id 9 when amount > 0 then set seen = true"#)
+        .unwrap();
+    let fields = HashMap::from([("amount".to_string(), bingo_core::FactValue::Integer(5))]);
+    let fired = engine
+        .process_facts(vec![bingo_core::Fact::new(
+            0,
+            bingo_core::FactData { fields },
+        )])
+        .unwrap();
+    assert_eq!(fired.len(), 1);
+    assert_eq!(tracer.exported.lock().unwrap().len(), 3);
+}
@@ -0,0 +1,158 @@
+//! gRPC Rule Group Tests
+//!
+//! Tests disabling and re-enabling rule groups per session, and that rules sharing an
+//! activation group fire at most once per fact between them.
+
+use bingo_api::AppState;
+use bingo_api::generated::rules_engine_service_server::RulesEngineService;
+use bingo_api::generated::*;
+use bingo_api::grpc::service::RulesEngineServiceImpl;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio_stream::StreamExt;
+use tonic::{Code, Request};
+
+fn float(value: f64) -> Value {
+    Value { value: Some(value::Value::NumberValue(value)) }
+}
+
+/// Rule flagging orders whose amount exceeds `minimum`
+fn pricing_rule(id: &str, minimum: f64, rule_group: &str, activation_group: &str) -> Rule {
+    Rule {
+        id: id.to_string(),
+        name: format!("Pricing rule {id}"),
+        conditions: vec![Condition {
+            condition_type: Some(condition::ConditionType::Simple(SimpleCondition {
+                field: "amount".to_string(),
+                operator: SimpleOperator::GreaterThan as i32,
+                value: Some(float(minimum)),
+            })),
+        }],
+        actions: vec![Action {
+            action_type: Some(action::ActionType::CreateFact(CreateFactAction {
+                fields: HashMap::from([("priced_by".to_string(), float(id.parse().unwrap()))]),
+            })),
+        }],
+        enabled: true,
+        rule_group: rule_group.to_string(),
+        activation_group: activation_group.to_string(),
+        ..Default::default()
+    }
+}
+
+async fn set_rule_groups(
+    service: &RulesEngineServiceImpl,
+    session_id: &str,
+    enable: &[&str],
+    disable: &[&str],
+) -> Vec<String> {
+    service
+        .set_session_rule_groups(Request::new(SetSessionRuleGroupsRequest {
+            session_id: session_id.to_string(),
+            enable: enable.iter().map(|group| group.to_string()).collect(),
+            disable: disable.iter().map(|group| group.to_string()).collect(),
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+        .disabled_groups
+}
+
+/// Ingest `(fact id, amount)` orders and return the fired rule IDs per fact, sorted
+async fn fired_rules(
+    service: &RulesEngineServiceImpl,
+    session_id: &str,
+    orders: &[(u64, f64)],
+) -> Vec<(String, String)> {
+    let mut requests = vec![Ok(IngestFactsRequest {
+        request: Some(ingest_facts_request::Request::Start(IngestStart {
+            session_id: session_id.to_string(),
+            ..Default::default()
+        })),
+    })];
+    for (id, amount) in orders {
+        requests.push(Ok(IngestFactsRequest {
+            request: Some(ingest_facts_request::Request::Fact(Fact {
+                id: id.to_string(),
+                data: HashMap::from([("amount".to_string(), float(*amount))]),
+                ..Default::default()
+            })),
+        }));
+    }
+
+    let mut fired: Vec<(String, String)> = service
+        .start_ingestion(tokio_stream::iter(requests))
+        .await
+        .unwrap()
+        .filter_map(|response| match response.unwrap().response {
+            Some(ingest_facts_response::Response::Result(result)) => {
+                Some((result.matched_fact.unwrap().id, result.rule_id))
+            }
+            _ => None,
+        })
+        .collect()
+        .await;
+    fired.sort();
+    fired
+}
+
+fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+    expected
+        .iter()
+        .map(|(fact, rule)| (fact.to_string(), rule.to_string()))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_rule_groups_and_activation_groups() {
+    let service = RulesEngineServiceImpl::new(Arc::new(AppState::new().await.unwrap()));
+    let compiled = service
+        .compile_rules(Request::new(CompileRulesRequest {
+            rules: vec![
+                pricing_rule("1", 100.0, "", "discount"),
+                pricing_rule("2", 0.0, "", "discount"),
+                pricing_rule("3", 0.0, "seasonal", ""),
+            ],
+            session_id: "pricing".to_string(),
+            ..Default::default()
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(compiled.success, "{}", compiled.error_message);
+
+    // Only the first matching rule of the discount group fires for each order
+    let fired = fired_rules(&service, "pricing", &[(1, 150.0), (2, 50.0)]).await;
+    assert_eq!(
+        fired,
+        pairs(&[("1", "1"), ("1", "3"), ("2", "2"), ("2", "3")])
+    );
+
+    assert_eq!(
+        set_rule_groups(&service, "pricing", &[], &["seasonal"]).await,
+        vec!["seasonal"]
+    );
+    let fired = fired_rules(&service, "pricing", &[(3, 150.0)]).await;
+    assert_eq!(fired, pairs(&[("3", "1")]));
+
+    assert!(set_rule_groups(&service, "pricing", &["seasonal"], &[]).await.is_empty());
+    let fired = fired_rules(&service, "pricing", &[(4, 150.0)]).await;
+    assert_eq!(fired, pairs(&[("4", "1"), ("4", "3")]));
+}
+
+#[tokio::test]
+async fn test_rule_groups_are_set_per_session() {
+    let service = RulesEngineServiceImpl::new(Arc::new(AppState::new().await.unwrap()));
+    set_rule_groups(&service, "north", &[], &["seasonal"]).await;
+    assert!(set_rule_groups(&service, "south", &[], &[]).await.is_empty());
+
+    let status = service
+        .set_session_rule_groups(Request::new(SetSessionRuleGroupsRequest {
+            session_id: String::new(),
+            disable: vec!["seasonal".to_string()],
+            ..Default::default()
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+}
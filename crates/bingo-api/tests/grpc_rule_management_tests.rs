@@ -0,0 +1,448 @@
+//! gRPC Rule Management Tests
+//!
+//! Tests creating, updating, deleting and listing rules within a compiled session,
+//! that every result reports the ruleset version that produced it, per-rule hit
+//! counts, the graph of rules triggering each other, that rule metadata is listed and
+//! expired rules no longer fire, and that ingestion can check rule validity against a
+//! business date.
+
+use bingo_api::AppState;
+use bingo_api::generated::rules_engine_service_server::RulesEngineService;
+use bingo_api::generated::*;
+use bingo_api::grpc::service::RulesEngineServiceImpl;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio_stream::StreamExt;
+use tonic::{Code, Request, Status};
+
+fn entity_rule(id: &str, entity_type: &str) -> Rule {
+    Rule {
+        id: id.to_string(),
+        name: format!("Flag {entity_type}"),
+        description: String::new(),
+        conditions: vec![Condition {
+            condition_type: Some(condition::ConditionType::Simple(SimpleCondition {
+                field: "entity_type".to_string(),
+                operator: SimpleOperator::Equal as i32,
+                value: Some(Value {
+                    value: Some(value::Value::StringValue(entity_type.to_string())),
+                }),
+            })),
+        }],
+        actions: vec![Action {
+            action_type: Some(action::ActionType::CreateFact(CreateFactAction {
+                fields: HashMap::from([(
+                    "flagged".to_string(),
+                    Value { value: Some(value::Value::BoolValue(true)) },
+                )]),
+            })),
+        }],
+        priority: 100,
+        enabled: true,
+        tags: vec![],
+        created_at: 0,
+        updated_at: 0,
+        ..Default::default()
+    }
+}
+
+async fn compiled_service(session_id: &str) -> RulesEngineServiceImpl {
+    let service = RulesEngineServiceImpl::new(Arc::new(AppState::new().await.unwrap()));
+    let compiled = service
+        .compile_rules(Request::new(CompileRulesRequest {
+            rules: vec![entity_rule("1", "shift")],
+            session_id: session_id.to_string(),
+            ..Default::default()
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(compiled.ruleset_version, 1);
+    service
+}
+
+/// Ingest `(fact id, entity type)` facts and return the rule results
+async fn evaluate(
+    service: &RulesEngineServiceImpl,
+    session_id: &str,
+    facts: &[(u64, &str)],
+) -> Vec<RuleExecutionResult> {
+    let start = IngestStart { session_id: session_id.to_string(), ..Default::default() };
+    ingest(service, start, facts).await.unwrap()
+}
+
+/// Ingest facts after `start` and return the rule results
+async fn ingest(
+    service: &RulesEngineServiceImpl,
+    start: IngestStart,
+    facts: &[(u64, &str)],
+) -> Result<Vec<RuleExecutionResult>, Status> {
+    let mut requests = vec![Ok(IngestFactsRequest {
+        request: Some(ingest_facts_request::Request::Start(start)),
+    })];
+    for (id, entity_type) in facts {
+        requests.push(Ok(IngestFactsRequest {
+            request: Some(ingest_facts_request::Request::Fact(Fact {
+                id: id.to_string(),
+                data: HashMap::from([(
+                    "entity_type".to_string(),
+                    Value { value: Some(value::Value::StringValue(entity_type.to_string())) },
+                )]),
+                created_at: 0,
+                ..Default::default()
+            })),
+        }));
+    }
+
+    Ok(service
+        .start_ingestion(tokio_stream::iter(requests))
+        .await?
+        .filter_map(|response| match response.unwrap().response {
+            Some(ingest_facts_response::Response::Result(result)) => Some(result),
+            _ => None,
+        })
+        .collect()
+        .await)
+}
+
+#[tokio::test]
+async fn test_rule_lifecycle_bumps_ruleset_version() {
+    let service = compiled_service("crud").await;
+
+    let created = service
+        .create_rule(Request::new(CreateRuleRequest {
+            session_id: "crud".to_string(),
+            rule: Some(entity_rule("2", "break")),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(created.rule_id, "2");
+    assert_eq!(created.ruleset_version, 2);
+    assert_eq!(created.rule_count, 2);
+
+    let listed = service
+        .list_rules(Request::new(ListRulesRequest {
+            session_id: "crud".to_string(),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(listed.ruleset_version, 2);
+    let ids: Vec<&str> = listed.rules.iter().map(|rule| rule.id.as_str()).collect();
+    assert_eq!(ids, vec!["1", "2"]);
+    assert_eq!(
+        listed.rules[1].conditions,
+        entity_rule("2", "break").conditions
+    );
+
+    let updated = service
+        .update_rule(Request::new(UpdateRuleRequest {
+            session_id: "crud".to_string(),
+            rule: Some(entity_rule("2", "lunch")),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(updated.ruleset_version, 3);
+    assert_eq!(updated.rule_count, 2);
+
+    let deleted = service
+        .delete_rule(Request::new(DeleteRuleRequest {
+            session_id: "crud".to_string(),
+            rule_id: "2".to_string(),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(deleted.ruleset_version, 4);
+    assert_eq!(deleted.rule_count, 1);
+}
+
+#[tokio::test]
+async fn test_results_report_producing_ruleset_version() {
+    let service = compiled_service("versioned").await;
+
+    let results = evaluate(&service, "versioned", &[(1, "shift"), (2, "break")]).await;
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].ruleset_version, 1);
+
+    service
+        .create_rule(Request::new(CreateRuleRequest {
+            session_id: "versioned".to_string(),
+            rule: Some(entity_rule("2", "break")),
+        }))
+        .await
+        .unwrap();
+    let results = evaluate(&service, "versioned", &[(3, "break")]).await;
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].rule_id, "2");
+    assert_eq!(results[0].ruleset_version, 2);
+
+    service
+        .update_rule(Request::new(UpdateRuleRequest {
+            session_id: "versioned".to_string(),
+            rule: Some(entity_rule("2", "lunch")),
+        }))
+        .await
+        .unwrap();
+    assert!(evaluate(&service, "versioned", &[(4, "break")]).await.is_empty());
+    let results = evaluate(&service, "versioned", &[(5, "lunch")]).await;
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].ruleset_version, 3);
+}
+
+#[tokio::test]
+async fn test_rule_metadata_is_listed_and_expiry_enforced() {
+    let service = compiled_service("governed").await;
+
+    let expired = Rule {
+        description: "Flags breaks for the 2023 break audit".to_string(),
+        owner: "workforce-compliance".to_string(),
+        link: "https://wiki.example.com/break-audit".to_string(),
+        effective_date: 1_672_531_200,
+        expiry_date: 1_704_067_200,
+        ..entity_rule("2", "break")
+    };
+    service
+        .create_rule(Request::new(CreateRuleRequest {
+            session_id: "governed".to_string(),
+            rule: Some(expired),
+        }))
+        .await
+        .unwrap();
+
+    let listed = service
+        .list_rules(Request::new(ListRulesRequest {
+            session_id: "governed".to_string(),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    let rule = &listed.rules[1];
+    assert_eq!(rule.description, "Flags breaks for the 2023 break audit");
+    assert_eq!(rule.owner, "workforce-compliance");
+    assert_eq!(rule.link, "https://wiki.example.com/break-audit");
+    assert_eq!(
+        (rule.effective_date, rule.expiry_date),
+        (1_672_531_200, 1_704_067_200)
+    );
+    assert_eq!(listed.rules[0].owner, "");
+    assert_eq!(listed.rules[0].expiry_date, 0);
+
+    // The expired rule matches but no longer fires
+    let results = evaluate(&service, "governed", &[(1, "shift"), (2, "break")]).await;
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].rule_id, "1");
+
+    // A rule cannot expire before it takes effect
+    let backwards = Rule {
+        effective_date: 1_704_067_200,
+        expiry_date: 1_672_531_200,
+        ..entity_rule("3", "lunch")
+    };
+    let status = service
+        .create_rule(Request::new(CreateRuleRequest {
+            session_id: "governed".to_string(),
+            rule: Some(backwards),
+        }))
+        .await
+        .unwrap_err();
+    assert!(
+        status.message().contains("before it takes effect"),
+        "{}",
+        status.message()
+    );
+}
+
+#[tokio::test]
+async fn test_ingestion_evaluates_rules_as_of_a_business_date() {
+    let service = compiled_service("dated").await;
+    let audit_2023 = Rule {
+        effective_date: 1_672_531_200,
+        expiry_date: 1_704_067_200,
+        ..entity_rule("2", "break")
+    };
+    service
+        .create_rule(Request::new(CreateRuleRequest {
+            session_id: "dated".to_string(),
+            rule: Some(audit_2023),
+        }))
+        .await
+        .unwrap();
+
+    // Mid-2023 the audit rule was in effect
+    let as_of_2023 = IngestStart {
+        session_id: "dated".to_string(),
+        evaluation_date: 1_688_169_600,
+        ..Default::default()
+    };
+    let results = ingest(&service, as_of_2023.clone(), &[(1, "break")]).await.unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].rule_id, "2");
+    assert!(evaluate(&service, "dated", &[(2, "break")]).await.is_empty());
+
+    let ambiguous = IngestStart { evaluate_at_event_time: true, ..as_of_2023 };
+    let status = ingest(&service, ambiguous, &[(3, "break")]).await.unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn test_rule_management_errors() {
+    let service = compiled_service("errors").await;
+
+    let duplicate = service
+        .create_rule(Request::new(CreateRuleRequest {
+            session_id: "errors".to_string(),
+            rule: Some(entity_rule("1", "break")),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(duplicate.code(), Code::AlreadyExists);
+
+    let missing = service
+        .update_rule(Request::new(UpdateRuleRequest {
+            session_id: "errors".to_string(),
+            rule: Some(entity_rule("9", "break")),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(missing.code(), Code::NotFound);
+
+    let missing = service
+        .delete_rule(Request::new(DeleteRuleRequest {
+            session_id: "errors".to_string(),
+            rule_id: "9".to_string(),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(missing.code(), Code::NotFound);
+
+    let invalid_id = service
+        .delete_rule(Request::new(DeleteRuleRequest {
+            session_id: "errors".to_string(),
+            rule_id: "not-a-number".to_string(),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(invalid_id.code(), Code::InvalidArgument);
+
+    let no_session = service
+        .list_rules(Request::new(ListRulesRequest {
+            session_id: "unknown".to_string(),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(no_session.code(), Code::NotFound);
+
+    // Failed changes leave the version alone
+    let listed = service
+        .list_rules(Request::new(ListRulesRequest {
+            session_id: "errors".to_string(),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(listed.ruleset_version, 1);
+}
+
+#[tokio::test]
+async fn test_rule_stats_report_dead_and_fired_rules() {
+    let service = compiled_service("stats").await;
+    service
+        .create_rule(Request::new(CreateRuleRequest {
+            session_id: "stats".to_string(),
+            rule: Some(entity_rule("2", "break")),
+        }))
+        .await
+        .unwrap();
+    evaluate(&service, "stats", &[(1, "shift"), (2, "shift")]).await;
+
+    let stats = service
+        .get_rule_stats(Request::new(GetRuleStatsRequest {
+            session_id: "stats".to_string(),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(stats.ruleset_version, 2);
+    let activations: Vec<(&str, u64)> = stats
+        .rules
+        .iter()
+        .map(|rule| (rule.rule_id.as_str(), rule.activations))
+        .collect();
+    assert_eq!(activations, vec![("1", 2), ("2", 0)]);
+    assert!(stats.rules[0].last_fired_at > 0);
+    assert_eq!(stats.rules[1].last_fired_at, 0);
+
+    let no_session = service
+        .get_rule_stats(Request::new(GetRuleStatsRequest {
+            session_id: "unknown".to_string(),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(no_session.code(), Code::NotFound);
+}
+
+#[tokio::test]
+async fn test_rule_graph_reports_cycles() {
+    let service = compiled_service("graph").await;
+
+    // Flagged facts are turned back into shifts, which rule 1 flags again
+    let mut reshift = entity_rule("2", "ignored");
+    reshift.name = "Reshift".to_string();
+    reshift.conditions[0] = Condition {
+        condition_type: Some(condition::ConditionType::Simple(SimpleCondition {
+            field: "flagged".to_string(),
+            operator: SimpleOperator::Equal as i32,
+            value: Some(Value { value: Some(value::Value::BoolValue(true)) }),
+        })),
+    };
+    reshift.actions[0] = Action {
+        action_type: Some(action::ActionType::CreateFact(CreateFactAction {
+            fields: HashMap::from([(
+                "entity_type".to_string(),
+                Value { value: Some(value::Value::StringValue("shift".to_string())) },
+            )]),
+        })),
+    };
+    service
+        .create_rule(Request::new(CreateRuleRequest {
+            session_id: "graph".to_string(),
+            rule: Some(reshift),
+        }))
+        .await
+        .unwrap();
+
+    let graph = service
+        .get_rule_graph(Request::new(GetRuleGraphRequest {
+            session_id: "graph".to_string(),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(graph.ruleset_version, 2);
+    let names: Vec<&str> = graph.nodes.iter().map(|node| node.rule_name.as_str()).collect();
+    assert_eq!(names, vec!["Flag shift", "Reshift"]);
+    let edges: Vec<(&str, &str, &[String])> = graph
+        .edges
+        .iter()
+        .map(|edge| {
+            (
+                edge.from_rule_id.as_str(),
+                edge.to_rule_id.as_str(),
+                edge.via.as_slice(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        edges,
+        vec![
+            ("1", "2", &["flagged".to_string()][..]),
+            ("2", "1", &["entity_type".to_string()][..]),
+        ]
+    );
+    assert_eq!(graph.cycles.len(), 1);
+    assert_eq!(graph.cycles[0].rule_ids, vec!["1", "2"]);
+    assert!(graph.dot.contains("color=red"));
+}
@@ -0,0 +1,318 @@
+//! gRPC Session Globals Tests
+//!
+//! Tests pushing globals and reference tables into a session once and having rules
+//! read them, reading them back, and that updates apply to the session's existing rules.
+
+use bingo_api::AppState;
+use bingo_api::generated::rules_engine_service_server::RulesEngineService;
+use bingo_api::generated::*;
+use bingo_api::grpc::service::RulesEngineServiceImpl;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio_stream::StreamExt;
+use tonic::{Code, Request};
+
+fn string(value: &str) -> Value {
+    Value { value: Some(value::Value::StringValue(value.to_string())) }
+}
+
+fn float(value: f64) -> Value {
+    Value { value: Some(value::Value::NumberValue(value)) }
+}
+
+fn rule(id: &str, condition: SimpleCondition, action: action::ActionType) -> Rule {
+    Rule {
+        id: id.to_string(),
+        name: format!("Rule {id}"),
+        description: String::new(),
+        conditions: vec![Condition {
+            condition_type: Some(condition::ConditionType::Simple(condition)),
+        }],
+        actions: vec![Action { action_type: Some(action) }],
+        priority: 100,
+        enabled: true,
+        tags: vec![],
+        created_at: 0,
+        updated_at: 0,
+        ..Default::default()
+    }
+}
+
+/// Multiplies `amount` by the `vat_rate` global
+fn vat_rule() -> Rule {
+    rule(
+        "1",
+        SimpleCondition {
+            field: "amount".to_string(),
+            operator: SimpleOperator::GreaterThan as i32,
+            value: Some(float(0.0)),
+        },
+        action::ActionType::CallCalculator(CallCalculatorAction {
+            calculator_name: "multiply".to_string(),
+            input_mapping: HashMap::from([
+                ("a".to_string(), "amount".to_string()),
+                ("b".to_string(), "@vat_rate".to_string()),
+            ]),
+            output_field: "vat".to_string(),
+        }),
+    )
+}
+
+/// Flags orders from a country in the `sanctions` table
+fn sanctions_rule() -> Rule {
+    rule(
+        "2",
+        SimpleCondition {
+            field: "country".to_string(),
+            operator: SimpleOperator::In as i32,
+            value: Some(string("sanctions")),
+        },
+        action::ActionType::CreateFact(CreateFactAction {
+            fields: HashMap::from([(
+                "blocked".to_string(),
+                Value { value: Some(value::Value::BoolValue(true)) },
+            )]),
+        }),
+    )
+}
+
+fn key_only(key: &str) -> ReferenceTableEntry {
+    ReferenceTableEntry { key: Some(string(key)), value: None }
+}
+
+async fn set_vat_rate(service: &RulesEngineServiceImpl, session_id: &str, rate: f64) {
+    service
+        .set_session_globals(Request::new(SetSessionGlobalsRequest {
+            session_id: session_id.to_string(),
+            globals: HashMap::from([("vat_rate".to_string(), float(rate))]),
+        }))
+        .await
+        .unwrap();
+}
+
+async fn set_sanctions(service: &RulesEngineServiceImpl, session_id: &str, countries: &[&str]) {
+    let response = service
+        .set_reference_table(Request::new(SetReferenceTableRequest {
+            session_id: session_id.to_string(),
+            name: "sanctions".to_string(),
+            entries: countries.iter().map(|country| key_only(country)).collect(),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(response.row_count, countries.len() as i32);
+}
+
+/// Ingest `(fact id, country, amount)` orders and return the rule results
+async fn evaluate(
+    service: &RulesEngineServiceImpl,
+    session_id: &str,
+    orders: &[(u64, &str, f64)],
+) -> Vec<RuleExecutionResult> {
+    let mut requests = vec![Ok(IngestFactsRequest {
+        request: Some(ingest_facts_request::Request::Start(IngestStart {
+            session_id: session_id.to_string(),
+            ..Default::default()
+        })),
+    })];
+    for (id, country, amount) in orders {
+        requests.push(Ok(IngestFactsRequest {
+            request: Some(ingest_facts_request::Request::Fact(Fact {
+                id: id.to_string(),
+                data: HashMap::from([
+                    ("country".to_string(), string(country)),
+                    ("amount".to_string(), float(*amount)),
+                ]),
+                created_at: 0,
+                ..Default::default()
+            })),
+        }));
+    }
+
+    service
+        .start_ingestion(tokio_stream::iter(requests))
+        .await
+        .unwrap()
+        .filter_map(|response| match response.unwrap().response {
+            Some(ingest_facts_response::Response::Result(result)) => Some(result),
+            _ => None,
+        })
+        .collect()
+        .await
+}
+
+/// VAT calculated for each result of the VAT rule
+fn vat_amounts(results: &[RuleExecutionResult]) -> Vec<f64> {
+    results
+        .iter()
+        .filter(|result| result.rule_id == "1")
+        .flat_map(|result| &result.action_results)
+        .filter_map(|action| match &action.result {
+            Some(action_result::Result::FormulaResult(output)) => {
+                output.strip_prefix("multiply:").map(|vat| vat.parse().unwrap())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_globals_and_tables_pushed_before_compiling() {
+    let service = RulesEngineServiceImpl::new(Arc::new(AppState::new().await.unwrap()));
+    set_vat_rate(&service, "orders", 0.25).await;
+    set_sanctions(&service, "orders", &["IR"]).await;
+
+    let compiled = service
+        .compile_rules(Request::new(CompileRulesRequest {
+            rules: vec![vat_rule(), sanctions_rule()],
+            session_id: "orders".to_string(),
+            ..Default::default()
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(compiled.success, "{}", compiled.error_message);
+
+    let results = evaluate(&service, "orders", &[(1, "DE", 100.0), (2, "IR", 10.0)]).await;
+    let mut vat = vat_amounts(&results);
+    vat.sort_by(f64::total_cmp);
+    assert_eq!(vat, vec![2.5, 25.0]);
+    let blocked: Vec<&str> = results
+        .iter()
+        .filter(|result| result.rule_id == "2")
+        .map(|result| result.matched_fact.as_ref().unwrap().id.as_str())
+        .collect();
+    assert_eq!(blocked, vec!["2"]);
+
+    // Updates apply to the compiled rules without recompiling them
+    set_vat_rate(&service, "orders", 0.5).await;
+    set_sanctions(&service, "orders", &["IR", "RU"]).await;
+    let results = evaluate(&service, "orders", &[(3, "RU", 100.0)]).await;
+    assert_eq!(vat_amounts(&results), vec![50.0]);
+    assert!(results.iter().any(|result| result.rule_id == "2"));
+}
+
+#[tokio::test]
+async fn test_globals_and_tables_read_back() {
+    let service = RulesEngineServiceImpl::new(Arc::new(AppState::new().await.unwrap()));
+    set_vat_rate(&service, "readback", 0.2).await;
+    let response = service
+        .set_session_globals(Request::new(SetSessionGlobalsRequest {
+            session_id: "readback".to_string(),
+            globals: HashMap::from([("currency".to_string(), string("EUR"))]),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(response.globals.len(), 2);
+
+    let globals = service
+        .get_session_globals(Request::new(GetSessionGlobalsRequest {
+            session_id: "readback".to_string(),
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+        .globals;
+    assert_eq!(globals["vat_rate"], float(0.2));
+    assert_eq!(globals["currency"], string("EUR"));
+
+    service
+        .set_reference_table(Request::new(SetReferenceTableRequest {
+            session_id: "readback".to_string(),
+            name: "tax_rates".to_string(),
+            entries: vec![ReferenceTableEntry {
+                key: Some(string("DE")),
+                value: Some(float(0.19)),
+            }],
+        }))
+        .await
+        .unwrap();
+    let table = service
+        .get_reference_table(Request::new(GetReferenceTableRequest {
+            session_id: "readback".to_string(),
+            name: "tax_rates".to_string(),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(
+        table.entries,
+        vec![ReferenceTableEntry { key: Some(string("DE")), value: Some(float(0.19)) }]
+    );
+}
+
+#[tokio::test]
+async fn test_invalid_requests_are_rejected() {
+    let service = RulesEngineServiceImpl::new(Arc::new(AppState::new().await.unwrap()));
+
+    let status = service
+        .get_session_globals(Request::new(GetSessionGlobalsRequest {
+            session_id: "unknown".to_string(),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::NotFound);
+
+    let status = service
+        .set_session_globals(Request::new(SetSessionGlobalsRequest {
+            session_id: String::new(),
+            globals: HashMap::from([("vat_rate".to_string(), float(0.2))]),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+
+    // A batch with an invalid name sets nothing
+    let status = service
+        .set_session_globals(Request::new(SetSessionGlobalsRequest {
+            session_id: "invalid".to_string(),
+            globals: HashMap::from([
+                ("vat_rate".to_string(), float(0.2)),
+                ("@vat_rate".to_string(), float(0.2)),
+            ]),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+    let globals = service
+        .get_session_globals(Request::new(GetSessionGlobalsRequest {
+            session_id: "invalid".to_string(),
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+        .globals;
+    assert!(globals.is_empty());
+
+    let status = service
+        .set_reference_table(Request::new(SetReferenceTableRequest {
+            session_id: "invalid".to_string(),
+            name: "sanctions".to_string(),
+            entries: vec![ReferenceTableEntry { key: None, value: None }],
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+
+    let status = service
+        .get_reference_table(Request::new(GetReferenceTableRequest {
+            session_id: "invalid".to_string(),
+            name: "sanctions".to_string(),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::NotFound);
+
+    // Rules reading an unset global are rejected at compile time
+    let status = service
+        .compile_rules(Request::new(CompileRulesRequest {
+            rules: vec![vat_rule()],
+            session_id: "unset".to_string(),
+            ..Default::default()
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+    assert!(status.message().contains("Global 'vat_rate' is not set"));
+}
@@ -85,6 +85,7 @@ async fn test_concurrent_session_creation() {
                         value: bingo_core::FactValue::Boolean(true),
                     },
                 }],
+                metadata: Default::default(),
             };
 
             engine.add_rule(rule).unwrap();
@@ -152,6 +153,7 @@ async fn test_session_cleanup_and_isolation() {
                     value: bingo_core::FactValue::Boolean(true),
                 },
             }],
+            metadata: Default::default(),
         };
 
         engine.add_rule(rule).unwrap();
@@ -250,6 +252,7 @@ async fn test_high_concurrency_session_isolation() {
                         value: bingo_core::FactValue::Boolean(true),
                     },
                 }],
+                metadata: Default::default(),
             };
 
             engine.add_rule(rule).unwrap();
@@ -271,6 +274,7 @@ async fn test_high_concurrency_session_isolation() {
                         id: (session_idx * 1000 + fact_idx) as u64,
                         external_id: Some(format!("{session_id}_{fact_idx}")),
                         timestamp: chrono::Utc::now(),
+                        fact_type: None,
                         data: bingo_core::FactData { fields },
                     }
                 })
@@ -376,6 +380,7 @@ fn test_default_engine_isolation() {
                     value: bingo_core::FactValue::String("default_engine".to_string()),
                 },
             }],
+            metadata: Default::default(),
         };
 
         default_engine.add_rule(default_rule).unwrap();
@@ -395,6 +400,7 @@ fn test_default_engine_isolation() {
                     value: bingo_core::FactValue::String("session_engine".to_string()),
                 },
             }],
+            metadata: Default::default(),
         };
 
         session_engine.add_rule(session_rule).unwrap();
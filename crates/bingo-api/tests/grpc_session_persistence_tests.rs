@@ -0,0 +1,84 @@
+//! gRPC Session Persistence Tests
+//!
+//! Tests that sessions persisted on shutdown are restored with their rules and facts
+//! when the server starts again, and that sessions removed in the meantime stay gone.
+
+use bingo_api::AppState;
+use bingo_api::generated::rules_engine_service_server::RulesEngineService;
+use bingo_api::generated::*;
+use bingo_api::grpc::service::RulesEngineServiceImpl;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tonic::Request;
+
+fn shift_rule() -> Rule {
+    Rule {
+        id: "1".to_string(),
+        name: "Flag shifts".to_string(),
+        conditions: vec![Condition {
+            condition_type: Some(condition::ConditionType::Simple(SimpleCondition {
+                field: "entity_type".to_string(),
+                operator: SimpleOperator::Equal as i32,
+                value: Some(Value { value: Some(value::Value::StringValue("shift".to_string())) }),
+            })),
+        }],
+        actions: vec![Action {
+            action_type: Some(action::ActionType::CreateFact(CreateFactAction {
+                fields: HashMap::from([(
+                    "flagged".to_string(),
+                    Value { value: Some(value::Value::BoolValue(true)) },
+                )]),
+            })),
+        }],
+        enabled: true,
+        ..Default::default()
+    }
+}
+
+fn snapshot_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("bingo-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+#[tokio::test]
+async fn test_persisted_sessions_are_restored() {
+    let dir = snapshot_dir("session-persistence");
+    let state = Arc::new(AppState::new().await.unwrap());
+    let service = RulesEngineServiceImpl::new(state.clone());
+
+    // Session IDs need not be valid file names
+    for session_id in ["payroll/north", "closed"] {
+        let compiled = service
+            .compile_rules(Request::new(CompileRulesRequest {
+                rules: vec![shift_rule()],
+                session_id: session_id.to_string(),
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(compiled.success, "{}", compiled.error_message);
+    }
+    assert_eq!(state.persist_sessions(&dir).unwrap(), 2);
+
+    state.remove_engine("closed");
+    assert_eq!(state.persist_sessions(&dir).unwrap(), 1);
+
+    let restarted = AppState::new().await.unwrap();
+    assert_eq!(restarted.restore_sessions(&dir).unwrap(), 1);
+    assert_eq!(restarted.active_sessions(), 1);
+    let engine = restarted.get_engine("payroll/north").unwrap();
+    assert_eq!(engine.rule_count(), 1);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_missing_snapshot_directory_restores_nothing() {
+    let dir = snapshot_dir("no-snapshots");
+    let state = AppState::new().await.unwrap();
+    assert_eq!(state.restore_sessions(&dir).unwrap(), 0);
+    assert_eq!(state.active_sessions(), 0);
+}
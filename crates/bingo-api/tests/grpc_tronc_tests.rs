@@ -56,6 +56,7 @@ fn create_tronc_rules() -> Vec<Rule> {
             tags: vec!["tronc".to_string(), "administration".to_string()],
             created_at: Utc::now().timestamp(),
             updated_at: Utc::now().timestamp(),
+            ..Default::default()
         },
         Rule {
             id: "2".to_string(),
@@ -88,6 +89,7 @@ fn create_tronc_rules() -> Vec<Rule> {
             tags: vec!["tronc".to_string(), "aggregation".to_string()],
             created_at: Utc::now().timestamp(),
             updated_at: Utc::now().timestamp(),
+            ..Default::default()
         },
         Rule {
             id: "3".to_string(),
@@ -121,6 +123,7 @@ fn create_tronc_rules() -> Vec<Rule> {
             tags: vec!["tronc".to_string(), "allocation".to_string()],
             created_at: Utc::now().timestamp(),
             updated_at: Utc::now().timestamp(),
+            ..Default::default()
         },
     ]
 }
@@ -155,6 +158,7 @@ fn create_tronc_facts() -> Vec<Fact> {
                 );
                 map
             },
+            ..Default::default()
         },
         Fact {
             id: "role_weight_waiter".to_string(),
@@ -177,6 +181,7 @@ fn create_tronc_facts() -> Vec<Fact> {
                 );
                 map
             },
+            ..Default::default()
         },
         Fact {
             id: "role_weight_bartender".to_string(),
@@ -199,6 +204,7 @@ fn create_tronc_facts() -> Vec<Fact> {
                 );
                 map
             },
+            ..Default::default()
         },
         Fact {
             id: "shift_001".to_string(),
@@ -227,6 +233,7 @@ fn create_tronc_facts() -> Vec<Fact> {
                 );
                 map
             },
+            ..Default::default()
         },
         Fact {
             id: "shift_002".to_string(),
@@ -255,6 +262,7 @@ fn create_tronc_facts() -> Vec<Fact> {
                 );
                 map
             },
+            ..Default::default()
         },
         Fact {
             id: "shift_003".to_string(),
@@ -283,6 +291,7 @@ fn create_tronc_facts() -> Vec<Fact> {
                 );
                 map
             },
+            ..Default::default()
         },
     ]
 }
@@ -296,6 +305,7 @@ async fn test_tronc_rule_compilation() {
         rules: create_tronc_rules(),
         session_id: "tronc_test_session".to_string(),
         options: None,
+        ..Default::default()
     });
 
     let compile_response = service.compile_rules(compile_request).await.unwrap();
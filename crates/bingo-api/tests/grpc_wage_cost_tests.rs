@@ -57,6 +57,7 @@ fn create_wage_cost_rules() -> Vec<Rule> {
             tags: vec!["wage_cost".to_string(), "hours".to_string()],
             created_at: Utc::now().timestamp(),
             updated_at: Utc::now().timestamp(),
+            ..Default::default()
         },
         Rule {
             id: "2".to_string(),
@@ -98,6 +99,7 @@ fn create_wage_cost_rules() -> Vec<Rule> {
             tags: vec!["wage_cost".to_string(), "base_pay".to_string()],
             created_at: Utc::now().timestamp(),
             updated_at: Utc::now().timestamp(),
+            ..Default::default()
         },
         Rule {
             id: "3".to_string(),
@@ -130,6 +132,7 @@ fn create_wage_cost_rules() -> Vec<Rule> {
             tags: vec!["wage_cost".to_string(), "aggregation".to_string()],
             created_at: Utc::now().timestamp(),
             updated_at: Utc::now().timestamp(),
+            ..Default::default()
         },
         Rule {
             id: "4".to_string(),
@@ -197,6 +200,7 @@ fn create_wage_cost_rules() -> Vec<Rule> {
             tags: vec!["wage_cost".to_string(), "benefits".to_string(), "taxes".to_string()],
             created_at: Utc::now().timestamp(),
             updated_at: Utc::now().timestamp(),
+            ..Default::default()
         },
     ]
 }
@@ -241,6 +245,7 @@ fn create_wage_cost_facts() -> Vec<Fact> {
                 );
                 map
             },
+            ..Default::default()
         },
         Fact {
             id: "emp_profile_002".to_string(),
@@ -279,6 +284,7 @@ fn create_wage_cost_facts() -> Vec<Fact> {
                 );
                 map
             },
+            ..Default::default()
         },
         Fact {
             id: "shift_001".to_string(),
@@ -319,6 +325,7 @@ fn create_wage_cost_facts() -> Vec<Fact> {
                 );
                 map
             },
+            ..Default::default()
         },
         Fact {
             id: "shift_002".to_string(),
@@ -359,6 +366,7 @@ fn create_wage_cost_facts() -> Vec<Fact> {
                 );
                 map
             },
+            ..Default::default()
         },
         Fact {
             id: "shift_003".to_string(),
@@ -399,6 +407,7 @@ fn create_wage_cost_facts() -> Vec<Fact> {
                 );
                 map
             },
+            ..Default::default()
         },
     ]
 }
@@ -412,6 +421,7 @@ async fn test_wage_cost_rule_compilation() {
         rules: create_wage_cost_rules(),
         session_id: "wage_cost_test_session".to_string(),
         options: None,
+        ..Default::default()
     });
 
     let compile_response = service.compile_rules(compile_request).await.unwrap();
@@ -6,6 +6,7 @@ use std::collections::HashMap;
 
 use bingo_types::FactValue;
 
+use crate::built_in::decimal::{decimal_arg, decimal_result, uses_decimal};
 use crate::plugin::{CalculationResult, CalculatorPlugin};
 
 /// Calculator for addition operations
@@ -25,6 +26,10 @@ impl CalculatorPlugin for AddCalculator {
     }
 
     fn calculate(&self, args: &HashMap<String, &FactValue>) -> CalculationResult {
+        if uses_decimal(args, &["a", "b"]) {
+            let (a, b) = (decimal_arg(args, "a")?, decimal_arg(args, "b")?);
+            return decimal_result(a.checked_add(b), args);
+        }
         let a = match args.get("a") {
             Some(FactValue::Float(f)) => *f,
             Some(FactValue::Integer(i)) => *i as f64,
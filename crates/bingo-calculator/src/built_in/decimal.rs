@@ -0,0 +1,131 @@
+//! Exact decimal arithmetic shared by the numeric calculators
+//!
+//! A calculator switches from `f64` to exact decimal arithmetic when any of its numeric
+//! arguments is a `FactValue::Decimal`, and then returns a `Decimal`. The result can be
+//! rounded through two optional arguments:
+//!
+//! - `scale`: Decimal places to keep (defaults to 2)
+//! - `rounding`: One of `half_even` (the default), `half_up`, `half_down`, `up`,
+//!   `down`, `ceiling` or `floor`
+//!
+//! Without either argument the result keeps its full precision.
+
+use std::collections::HashMap;
+
+use bingo_types::{Decimal, DecimalRounding, FactValue, RoundingMode};
+
+use crate::plugin::CalculationResult;
+
+/// Whether any of the named arguments is a `Decimal`
+pub fn uses_decimal(args: &HashMap<String, &FactValue>, names: &[&str]) -> bool {
+    names.iter().any(|name| args.get(*name).is_some_and(|value| value.is_decimal()))
+}
+
+/// Numeric argument as an exact decimal
+pub fn decimal_arg(args: &HashMap<String, &FactValue>, name: &str) -> Result<Decimal, String> {
+    decimal_value(args.get(name).copied())
+        .ok_or_else(|| format!("Invalid argument '{name}': expected number"))
+}
+
+/// Numeric value as an exact decimal
+pub fn decimal_value(value: Option<&FactValue>) -> Option<Decimal> {
+    match value {
+        Some(value @ (FactValue::Decimal(_) | FactValue::Integer(_) | FactValue::Float(_))) => {
+            value.as_decimal()
+        }
+        _ => None,
+    }
+}
+
+/// Rounding requested through the `scale` and `rounding` arguments, if any
+pub fn rounding_from_args(
+    args: &HashMap<String, &FactValue>,
+) -> Result<Option<DecimalRounding>, String> {
+    let scale = match args.get("scale") {
+        None => None,
+        Some(FactValue::Integer(scale)) => Some(
+            u32::try_from(*scale)
+                .ok()
+                .filter(|scale| *scale <= 28)
+                .ok_or_else(|| format!("Invalid argument 'scale': {scale} is not in 0..=28"))?,
+        ),
+        Some(_) => return Err("Invalid argument 'scale': expected integer".to_string()),
+    };
+    let mode = match args.get("rounding") {
+        None => None,
+        Some(FactValue::String(mode)) => {
+            Some(mode.parse::<RoundingMode>().map_err(|e| e.to_string())?)
+        }
+        Some(_) => return Err("Invalid argument 'rounding': expected string".to_string()),
+    };
+
+    if scale.is_none() && mode.is_none() {
+        return Ok(None);
+    }
+    let default = DecimalRounding::default();
+    Ok(Some(DecimalRounding::new(
+        scale.unwrap_or(default.scale),
+        mode.unwrap_or(default.mode),
+    )))
+}
+
+/// Decimal result of a calculation, rounded as the arguments request
+///
+/// `None` stands for an arithmetic overflow or a division by zero.
+pub fn decimal_result(
+    value: Option<Decimal>,
+    args: &HashMap<String, &FactValue>,
+) -> CalculationResult {
+    let value = value.ok_or_else(|| "Decimal arithmetic overflow".to_string())?;
+    Ok(match rounding_from_args(args)? {
+        Some(rounding) => FactValue::Decimal(rounding.apply(value)),
+        None => FactValue::Decimal(value),
+    })
+}
+
+/// Result of a calculation done in decimal arithmetic: a `Decimal` rounded as the
+/// arguments request when `exact`, otherwise the nearest `Float`
+///
+/// For calculators that always compute exactly but only return decimals to callers
+/// that passed them in.
+pub fn number_result(
+    value: Option<Decimal>,
+    exact: bool,
+    args: &HashMap<String, &FactValue>,
+) -> CalculationResult {
+    if exact {
+        return decimal_result(value, args);
+    }
+    let value = value.ok_or_else(|| "Decimal arithmetic overflow".to_string())?;
+    FactValue::Decimal(value)
+        .as_f64()
+        .map(FactValue::Float)
+        .ok_or_else(|| format!("{value} is out of range for a float"))
+}
+
+/// Array argument whose elements are all numeric, as exact decimals
+pub fn decimal_array_arg(
+    args: &HashMap<String, &FactValue>,
+    name: &str,
+) -> Result<Vec<Decimal>, String> {
+    match args.get(name) {
+        Some(FactValue::Array(values)) => values
+            .iter()
+            .map(|value| decimal_value(Some(value)))
+            .collect::<Option<_>>()
+            .ok_or_else(|| format!("Invalid argument '{name}': expected array of numbers")),
+        _ => Err(format!("Invalid argument '{name}': expected array")),
+    }
+}
+
+/// Optional numeric argument as an exact decimal, `default` when absent
+pub fn decimal_arg_or(
+    args: &HashMap<String, &FactValue>,
+    name: &str,
+    default: Decimal,
+) -> Result<Decimal, String> {
+    match args.get(name) {
+        None => Ok(default),
+        Some(_) => decimal_arg(args, name),
+    }
+}
@@ -2,6 +2,9 @@
 
 pub mod weighted_average;
 
+// Exact decimal arithmetic shared by the numeric calculators
+pub mod decimal;
+
 // Mathematical calculators
 pub mod add;
 pub mod multiply;
@@ -13,3 +16,9 @@ pub mod time_between_datetime;
 
 // Allocation calculators
 pub mod proportional_allocator;
+
+// Payroll calculators
+pub mod overtime;
+pub mod pro_rata;
+pub mod rate_lookup;
+pub mod tiered_rate;
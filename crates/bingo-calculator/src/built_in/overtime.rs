@@ -0,0 +1,139 @@
+//! Calculator splitting a week of hours into regular, overtime and double time
+//!
+//! Each day's hours are split first: hours beyond `double_time_threshold` are double
+//! time, and hours beyond `daily_threshold` up to it are overtime. Regular hours left
+//! over once the week's regular total passes `weekly_threshold` then move to overtime,
+//! so no hour is paid a premium twice.
+//!
+//! # Parameters
+//! - `hours` (required): Hours worked on each day of the week
+//! - `hourly_rate` (required): Pay for a regular hour
+//! - `daily_threshold` (optional): Hours a day before overtime; no daily overtime if absent
+//! - `weekly_threshold` (optional): Regular hours a week before overtime (defaults to 40)
+//! - `overtime_multiplier` (optional): Rate multiplier for overtime (defaults to 1.5)
+//! - `double_time_threshold` (optional): Hours a day before double time
+//! - `double_time_multiplier` (optional): Rate multiplier for double time (defaults to 2)
+//!
+//! # Returns
+//! An object with `regular_hours`, `overtime_hours`, `double_time_hours` and
+//! `gross_pay`, each a `Decimal` when any argument is one and a `Float` otherwise.
+
+use std::collections::HashMap;
+
+use bingo_types::{Decimal, FactValue};
+
+use crate::built_in::decimal::{
+    decimal_arg, decimal_arg_or, decimal_array_arg, number_result, uses_decimal,
+};
+use crate::plugin::{CalculationResult, CalculatorFieldType, CalculatorPlugin, FieldSpec};
+
+const INPUT_FIELDS: &[FieldSpec] = &[
+    FieldSpec::required("hours", CalculatorFieldType::Array),
+    FieldSpec::required("hourly_rate", CalculatorFieldType::Float),
+    FieldSpec::optional("daily_threshold", CalculatorFieldType::Float),
+    FieldSpec::optional("weekly_threshold", CalculatorFieldType::Float),
+    FieldSpec::optional("overtime_multiplier", CalculatorFieldType::Float),
+    FieldSpec::optional("double_time_threshold", CalculatorFieldType::Float),
+    FieldSpec::optional("double_time_multiplier", CalculatorFieldType::Float),
+];
+
+#[derive(Debug, Default)]
+pub struct OvertimeCalculator;
+
+impl CalculatorPlugin for OvertimeCalculator {
+    fn name(&self) -> &str {
+        "overtime"
+    }
+
+    fn calculate(&self, args: &HashMap<String, &FactValue>) -> CalculationResult {
+        let hours = decimal_array_arg(args, "hours")?;
+        let hourly_rate = decimal_arg(args, "hourly_rate")?;
+        let daily_threshold = optional_decimal_arg(args, "daily_threshold")?;
+        let weekly_threshold = decimal_arg_or(args, "weekly_threshold", Decimal::from(40))?;
+        let overtime_multiplier = decimal_arg_or(args, "overtime_multiplier", Decimal::new(15, 1))?;
+        let double_time_threshold = optional_decimal_arg(args, "double_time_threshold")?;
+        let double_time_multiplier =
+            decimal_arg_or(args, "double_time_multiplier", Decimal::from(2))?;
+
+        if hours.iter().any(Decimal::is_sign_negative) {
+            return Err("Invalid argument 'hours': hours must not be negative".to_string());
+        }
+        if let (Some(daily), Some(double)) = (daily_threshold, double_time_threshold)
+            && double < daily
+        {
+            return Err(
+                "Invalid argument 'double_time_threshold': must not be below 'daily_threshold'"
+                    .to_string(),
+            );
+        }
+
+        let (mut regular, mut overtime, mut double_time) =
+            (Decimal::ZERO, Decimal::ZERO, Decimal::ZERO);
+        for day in hours {
+            let premium_start = double_time_threshold.map_or(day, |threshold| day.min(threshold));
+            double_time += day - premium_start;
+            let regular_end =
+                daily_threshold.map_or(premium_start, |threshold| premium_start.min(threshold));
+            overtime += premium_start - regular_end;
+            regular += regular_end;
+        }
+        if regular > weekly_threshold {
+            overtime += regular - weekly_threshold;
+            regular = weekly_threshold.max(Decimal::ZERO);
+        }
+
+        let gross_pay = overtime
+            .checked_mul(overtime_multiplier)
+            .zip(double_time.checked_mul(double_time_multiplier))
+            .and_then(|(overtime, double_time)| {
+                regular.checked_add(overtime)?.checked_add(double_time)
+            })
+            .and_then(|paid_hours| paid_hours.checked_mul(hourly_rate));
+
+        let decimal_hours = matches!(
+            args.get("hours"),
+            Some(FactValue::Array(values)) if values.iter().any(FactValue::is_decimal)
+        );
+        let exact = decimal_hours
+            || uses_decimal(
+                args,
+                &[
+                    "hourly_rate",
+                    "daily_threshold",
+                    "weekly_threshold",
+                    "overtime_multiplier",
+                    "double_time_threshold",
+                    "double_time_multiplier",
+                ],
+            );
+        Ok(FactValue::Object(HashMap::from([
+            (
+                "regular_hours".to_string(),
+                number_result(Some(regular), exact, args)?,
+            ),
+            (
+                "overtime_hours".to_string(),
+                number_result(Some(overtime), exact, args)?,
+            ),
+            (
+                "double_time_hours".to_string(),
+                number_result(Some(double_time), exact, args)?,
+            ),
+            (
+                "gross_pay".to_string(),
+                number_result(gross_pay, exact, args)?,
+            ),
+        ])))
+    }
+
+    fn input_fields(&self) -> &[FieldSpec] {
+        INPUT_FIELDS
+    }
+}
+
+fn optional_decimal_arg(
+    args: &HashMap<String, &FactValue>,
+    name: &str,
+) -> Result<Option<Decimal>, String> {
+    args.get(name).map(|_| decimal_arg(args, name)).transpose()
+}
@@ -7,6 +7,7 @@ use std::collections::HashMap;
 
 use bingo_types::FactValue;
 
+use crate::built_in::decimal::{decimal_arg, decimal_result, uses_decimal};
 use crate::plugin::{CalculationResult, CalculatorPlugin};
 
 /// Calculator for percentage deduction operations
@@ -26,6 +27,12 @@ impl CalculatorPlugin for PercentageDeductCalculator {
     }
 
     fn calculate(&self, args: &HashMap<String, &FactValue>) -> CalculationResult {
+        if uses_decimal(args, &["amount", "percentage"]) {
+            let amount = decimal_arg(args, "amount")?;
+            let percentage = decimal_arg(args, "percentage")?;
+            let result = amount.checked_mul(percentage).and_then(|delta| amount.checked_sub(delta));
+            return decimal_result(result, args);
+        }
         let amount = match args.get("amount") {
             Some(FactValue::Float(f)) => *f,
             Some(FactValue::Integer(i)) => *i as f64,
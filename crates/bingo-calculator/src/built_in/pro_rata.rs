@@ -0,0 +1,119 @@
+//! Calculator pro-rating an amount to the part of a period a date range covers
+//!
+//! Days are counted inclusively on calendar dates in UTC, so a range starting and
+//! ending on the same day covers one day.
+//!
+//! # Parameters
+//! - `amount` (required): Amount for the whole period
+//! - `period_start` (required): First day of the period
+//! - `period_end` (required): Last day of the period
+//! - `start` (optional): First day covered (defaults to `period_start`)
+//! - `end` (optional): Last day covered (defaults to `period_end`)
+//! - `basis` (optional): `calendar_days` (the default) or `business_days`, which counts
+//!   Monday to Friday only
+//!
+//! Dates are `FactValue::Date` values or RFC3339 formatted strings.
+//!
+//! # Returns
+//! The pro-rated amount, as a `Decimal` when `amount` is one and a `Float` otherwise.
+//! A range outside the period gets nothing.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Datelike, NaiveDate, Utc, Weekday};
+
+use bingo_types::{Decimal, FactValue};
+
+use crate::built_in::decimal::{decimal_arg, number_result, uses_decimal};
+use crate::plugin::{CalculationResult, CalculatorFieldType, CalculatorPlugin, FieldSpec};
+
+const INPUT_FIELDS: &[FieldSpec] = &[
+    FieldSpec::required("amount", CalculatorFieldType::Float),
+    FieldSpec::required("period_start", CalculatorFieldType::DateTime),
+    FieldSpec::required("period_end", CalculatorFieldType::DateTime),
+    FieldSpec::optional("start", CalculatorFieldType::DateTime),
+    FieldSpec::optional("end", CalculatorFieldType::DateTime),
+    FieldSpec::optional("basis", CalculatorFieldType::String),
+];
+
+#[derive(Debug, Default)]
+pub struct ProRataCalculator;
+
+impl CalculatorPlugin for ProRataCalculator {
+    fn name(&self) -> &str {
+        "pro_rata"
+    }
+
+    fn calculate(&self, args: &HashMap<String, &FactValue>) -> CalculationResult {
+        let amount = decimal_arg(args, "amount")?;
+        let period_start = date_arg(args, "period_start")?;
+        let period_end = date_arg(args, "period_end")?;
+        if period_end < period_start {
+            return Err("Invalid argument 'period_end': period ends before it starts".to_string());
+        }
+        let start = match args.get("start") {
+            Some(_) => date_arg(args, "start")?.max(period_start),
+            None => period_start,
+        };
+        let end = match args.get("end") {
+            Some(_) => date_arg(args, "end")?.min(period_end),
+            None => period_end,
+        };
+        let business_days = match args.get("basis") {
+            None => false,
+            Some(FactValue::String(basis)) if basis == "calendar_days" => false,
+            Some(FactValue::String(basis)) if basis == "business_days" => true,
+            Some(FactValue::String(basis)) => {
+                return Err(format!(
+                    "Invalid argument 'basis': expected 'calendar_days' or 'business_days', got '{basis}'"
+                ));
+            }
+            Some(_) => return Err("Invalid argument 'basis': expected string".to_string()),
+        };
+
+        let period_days = count_days(period_start, period_end, business_days);
+        if period_days == 0 {
+            return Err("Period has no business days to pro-rate over".to_string());
+        }
+        let covered_days = count_days(start, end, business_days);
+        // Multiply first so the share is not rounded before it is applied
+        let prorated = amount
+            .checked_mul(Decimal::from(covered_days))
+            .and_then(|product| product.checked_div(Decimal::from(period_days)));
+        number_result(prorated, uses_decimal(args, &["amount"]), args)
+    }
+
+    fn input_fields(&self) -> &[FieldSpec] {
+        INPUT_FIELDS
+    }
+}
+
+/// Date argument given as a `Date` or an RFC3339 string, as a UTC calendar date
+pub(crate) fn date_arg(
+    args: &HashMap<String, &FactValue>,
+    name: &str,
+) -> Result<NaiveDate, String> {
+    match args.get(name) {
+        Some(FactValue::Date(date)) => Ok(date.date_naive()),
+        Some(FactValue::String(s)) => Ok(DateTime::parse_from_rfc3339(s)
+            .map_err(|e| format!("Invalid argument '{name}': invalid datetime '{s}': {e}"))?
+            .with_timezone(&Utc)
+            .date_naive()),
+        _ => Err(format!("Invalid argument '{name}': expected datetime")),
+    }
+}
+
+/// Days from `start` to `end` inclusive, or none when `end` comes first
+fn count_days(start: NaiveDate, end: NaiveDate, business_days: bool) -> i64 {
+    if end < start {
+        return 0;
+    }
+    if !business_days {
+        return (end - start).num_days() + 1;
+    }
+    start
+        .iter_days()
+        .take_while(|day| *day <= end)
+        .filter(|day| !matches!(day.weekday(), Weekday::Sat | Weekday::Sun))
+        .count() as i64
+}
@@ -5,8 +5,9 @@
 
 use std::collections::HashMap;
 
-use bingo_types::FactValue;
+use bingo_types::{Decimal, FactValue};
 
+use crate::built_in::decimal::{decimal_arg, decimal_result, uses_decimal};
 use crate::plugin::{CalculationResult, CalculatorPlugin};
 
 #[derive(Debug, Default)]
@@ -18,6 +19,19 @@ impl CalculatorPlugin for ProportionalAllocatorCalculator {
     }
 
     fn calculate(&self, args: &HashMap<String, &FactValue>) -> CalculationResult {
+        if uses_decimal(args, &["total_amount", "individual_value", "total_value"]) {
+            let total_amount = decimal_arg(args, "total_amount")?;
+            let individual_value = decimal_arg(args, "individual_value")?;
+            let total_value = decimal_arg(args, "total_value")?;
+            if total_value.is_zero() {
+                return decimal_result(Some(Decimal::ZERO), args);
+            }
+            // Multiply first so the share is not rounded before it is applied
+            let allocation = total_amount
+                .checked_mul(individual_value)
+                .and_then(|product| product.checked_div(total_value));
+            return decimal_result(allocation, args);
+        }
         let total_amount = match args.get("total_amount") {
             Some(FactValue::Float(f)) => *f,
             Some(FactValue::Integer(i)) => *i as f64,
@@ -0,0 +1,143 @@
+//! Calculator resolving tax and levy rates from registered rate tables
+//!
+//! Rules name the table and pass the jurisdiction and date, so the rates themselves
+//! live outside the rules and a new fiscal year only needs a new table entry. Tables
+//! implement [`RateTable`]; [`EffectiveDatedRates`] keeps them in memory, and other
+//! sources such as a database plug in through the same trait.
+//!
+//! Tables are registered with [`Calculator::register_rate_table`] before the
+//! calculator is handed to an engine, so a rate never changes under a cached result.
+//!
+//! # Parameters
+//! - `table` (required): Name of the rate table
+//! - `jurisdiction` (required): Jurisdiction code, e.g. `US-CA`
+//! - `date` (required): Date the rate applies on, as a `FactValue::Date` or RFC3339 string
+//! - `amount` (optional): Amount to apply the rate to
+//!
+//! # Returns
+//! The rate in effect, or `amount` multiplied by it when given. The product is a
+//! `Decimal` when the amount or rate is one, and a `Float` otherwise.
+//!
+//! [`Calculator::register_rate_table`]: crate::Calculator::register_rate_table
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+
+use bingo_types::FactValue;
+
+use crate::built_in::decimal::{decimal_arg, decimal_value, number_result, uses_decimal};
+use crate::built_in::pro_rata::date_arg;
+use crate::plugin::{CalculationResult, CalculatorFieldType, CalculatorPlugin, FieldSpec};
+
+const INPUT_FIELDS: &[FieldSpec] = &[
+    FieldSpec::required("table", CalculatorFieldType::String),
+    FieldSpec::required("jurisdiction", CalculatorFieldType::String),
+    FieldSpec::required("date", CalculatorFieldType::DateTime),
+    FieldSpec::optional("amount", CalculatorFieldType::Float),
+];
+
+/// Source of rates by jurisdiction and date
+pub trait RateTable: Send + Sync {
+    /// Rate in effect in `jurisdiction` on `date`, if any
+    fn rate(&self, jurisdiction: &str, date: NaiveDate) -> Option<FactValue>;
+}
+
+/// In-memory rate table where each rate holds from its effective date until the next
+/// rate for the same jurisdiction takes effect
+#[derive(Debug, Clone, Default)]
+pub struct EffectiveDatedRates {
+    rates: HashMap<String, BTreeMap<NaiveDate, FactValue>>,
+}
+
+impl EffectiveDatedRates {
+    /// Create an empty table
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the rate taking effect in `jurisdiction` on `effective_from`, replacing any
+    /// rate with the same effective date
+    pub fn with_rate(
+        mut self,
+        jurisdiction: impl Into<String>,
+        effective_from: NaiveDate,
+        rate: FactValue,
+    ) -> Self {
+        self.rates.entry(jurisdiction.into()).or_default().insert(effective_from, rate);
+        self
+    }
+}
+
+impl RateTable for EffectiveDatedRates {
+    fn rate(&self, jurisdiction: &str, date: NaiveDate) -> Option<FactValue> {
+        let (_, rate) = self.rates.get(jurisdiction)?.range(..=date).next_back()?;
+        Some(rate.clone())
+    }
+}
+
+/// Looks up rates in the tables it was built with
+#[derive(Clone, Default)]
+pub struct RateLookupCalculator {
+    tables: HashMap<String, Arc<dyn RateTable>>,
+}
+
+impl RateLookupCalculator {
+    /// Calculator without any tables
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `table` under `name`, replacing any table with that name
+    pub fn with_table(mut self, name: impl Into<String>, table: Arc<dyn RateTable>) -> Self {
+        self.tables.insert(name.into(), table);
+        self
+    }
+
+    /// Whether a table is registered under `name`
+    pub fn contains(&self, name: &str) -> bool {
+        self.tables.contains_key(name)
+    }
+}
+
+impl std::fmt::Debug for RateLookupCalculator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut names: Vec<&String> = self.tables.keys().collect();
+        names.sort_unstable();
+        f.debug_struct("RateLookupCalculator").field("tables", &names).finish()
+    }
+}
+
+impl CalculatorPlugin for RateLookupCalculator {
+    fn name(&self) -> &str {
+        "rate_lookup"
+    }
+
+    fn calculate(&self, args: &HashMap<String, &FactValue>) -> CalculationResult {
+        let Some(FactValue::String(name)) = args.get("table") else {
+            return Err("Invalid argument 'table': expected string".to_string());
+        };
+        let Some(FactValue::String(jurisdiction)) = args.get("jurisdiction") else {
+            return Err("Invalid argument 'jurisdiction': expected string".to_string());
+        };
+        let date = date_arg(args, "date")?;
+        let table = self.tables.get(name).ok_or_else(|| format!("Unknown rate table '{name}'"))?;
+        let rate = table.rate(jurisdiction, date).ok_or_else(|| {
+            format!("No rate in table '{name}' for jurisdiction '{jurisdiction}' on {date}")
+        })?;
+
+        if !args.contains_key("amount") {
+            return Ok(rate);
+        }
+        let amount = decimal_arg(args, "amount")?;
+        let decimal_rate = decimal_value(Some(&rate))
+            .ok_or_else(|| format!("Rate in table '{name}' is not a number: {rate:?}"))?;
+        let exact = rate.is_decimal() || uses_decimal(args, &["amount"]);
+        number_result(amount.checked_mul(decimal_rate), exact, args)
+    }
+
+    fn input_fields(&self) -> &[FieldSpec] {
+        INPUT_FIELDS
+    }
+}
@@ -0,0 +1,139 @@
+//! Calculator applying bracketed rates to an amount
+//!
+//! Tiers are objects with a `rate` and an `up_to` bound, in ascending order of bound;
+//! the last tier may leave out `up_to` to cover every amount above the one before it.
+//!
+//! # Parameters
+//! - `amount` (required): Amount the rates apply to
+//! - `tiers` (required): Array of `{ "up_to": number, "rate": number }` objects
+//! - `mode` (optional): `graduated` (the default) charges each tier's rate on the part
+//!   of the amount within the tier; `flat` charges the whole amount at the rate of the
+//!   tier it falls in
+//!
+//! # Returns
+//! The charge, as a `Decimal` when any argument is one and a `Float` otherwise.
+
+use std::collections::HashMap;
+
+use bingo_types::{Decimal, FactValue};
+
+use crate::built_in::decimal::{decimal_arg, decimal_value, number_result, uses_decimal};
+use crate::plugin::{CalculationResult, CalculatorFieldType, CalculatorPlugin, FieldSpec};
+
+const INPUT_FIELDS: &[FieldSpec] = &[
+    FieldSpec::required("amount", CalculatorFieldType::Float),
+    FieldSpec::required("tiers", CalculatorFieldType::Array),
+    FieldSpec::optional("mode", CalculatorFieldType::String),
+];
+
+#[derive(Debug, Default)]
+pub struct TieredRateCalculator;
+
+/// Upper bound and rate of one tier; `None` bounds the last tier above
+struct Tier {
+    up_to: Option<Decimal>,
+    rate: Decimal,
+}
+
+impl CalculatorPlugin for TieredRateCalculator {
+    fn name(&self) -> &str {
+        "tiered_rate"
+    }
+
+    fn calculate(&self, args: &HashMap<String, &FactValue>) -> CalculationResult {
+        let amount = decimal_arg(args, "amount")?;
+        if amount.is_sign_negative() {
+            return Err("Invalid argument 'amount': must not be negative".to_string());
+        }
+        let Some(FactValue::Array(values)) = args.get("tiers") else {
+            return Err("Invalid argument 'tiers': expected array".to_string());
+        };
+        let tiers = parse_tiers(values)?;
+        let graduated = match args.get("mode") {
+            None => true,
+            Some(FactValue::String(mode)) if mode == "graduated" => true,
+            Some(FactValue::String(mode)) if mode == "flat" => false,
+            Some(FactValue::String(mode)) => {
+                return Err(format!(
+                    "Invalid argument 'mode': expected 'graduated' or 'flat', got '{mode}'"
+                ));
+            }
+            Some(_) => return Err("Invalid argument 'mode': expected string".to_string()),
+        };
+
+        if let Some(ceiling) = tiers.last().and_then(|tier| tier.up_to)
+            && amount > ceiling
+        {
+            return Err(format!(
+                "Amount {amount} exceeds the last tier, which ends at {ceiling}"
+            ));
+        }
+
+        let charge = if graduated {
+            let mut charge = Some(Decimal::ZERO);
+            let mut floor = Decimal::ZERO;
+            for tier in &tiers {
+                let ceiling = tier.up_to.map_or(amount, |up_to| up_to.min(amount));
+                if ceiling > floor {
+                    charge = charge
+                        .zip((ceiling - floor).checked_mul(tier.rate))
+                        .and_then(|(charge, part)| charge.checked_add(part));
+                }
+                floor = ceiling;
+            }
+            charge
+        } else {
+            // Validated above: some tier always covers the amount
+            tiers
+                .iter()
+                .find(|tier| tier.up_to.is_none_or(|up_to| amount <= up_to))
+                .and_then(|tier| amount.checked_mul(tier.rate))
+        };
+
+        let exact = uses_decimal(args, &["amount"])
+            || values.iter().any(|tier| match tier {
+                FactValue::Object(fields) => fields.values().any(FactValue::is_decimal),
+                _ => false,
+            });
+        number_result(charge, exact, args)
+    }
+
+    fn input_fields(&self) -> &[FieldSpec] {
+        INPUT_FIELDS
+    }
+}
+
+fn parse_tiers(values: &[FactValue]) -> Result<Vec<Tier>, String> {
+    if values.is_empty() {
+        return Err("Invalid argument 'tiers': expected at least one tier".to_string());
+    }
+    let mut tiers: Vec<Tier> = Vec::with_capacity(values.len());
+    for (index, value) in values.iter().enumerate() {
+        let FactValue::Object(fields) = value else {
+            return Err(format!("Invalid tier {index}: expected object"));
+        };
+        let rate = decimal_value(fields.get("rate"))
+            .ok_or_else(|| format!("Invalid tier {index}: 'rate' must be a number"))?;
+        let up_to = match fields.get("up_to") {
+            None if index + 1 == values.len() => None,
+            None => {
+                return Err(format!(
+                    "Invalid tier {index}: only the last tier may omit 'up_to'"
+                ));
+            }
+            Some(up_to) => Some(
+                decimal_value(Some(up_to))
+                    .ok_or_else(|| format!("Invalid tier {index}: 'up_to' must be a number"))?,
+            ),
+        };
+        if let (Some(up_to), Some(previous)) = (up_to, tiers.last().and_then(|tier| tier.up_to))
+            && up_to <= previous
+        {
+            return Err(format!(
+                "Invalid tier {index}: 'up_to' must be above the previous tier's {previous}"
+            ));
+        }
+        tiers.push(Tier { up_to, rate });
+    }
+    Ok(tiers)
+}
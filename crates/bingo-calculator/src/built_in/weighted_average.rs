@@ -5,8 +5,9 @@
 
 use std::collections::HashMap;
 
-use bingo_types::FactValue;
+use bingo_types::{Decimal, FactValue};
 
+use crate::built_in::decimal::{decimal_result, decimal_value};
 use crate::plugin::{CalculationResult, CalculatorPlugin};
 
 /// Calculates the weighted average for a list of items.
@@ -26,6 +27,38 @@ impl WeightedAverageCalculator {
     pub fn new() -> Self {
         Self
     }
+
+    /// Exact weighted average, used when any value or weight is a `Decimal`
+    fn decimal_weighted_average(
+        items: &[FactValue],
+        args: &HashMap<String, &FactValue>,
+    ) -> CalculationResult {
+        let mut total_weighted_value = Some(Decimal::ZERO);
+        let mut total_weight = Some(Decimal::ZERO);
+
+        for item_val in items {
+            let FactValue::Object(item) = item_val else {
+                return Err("'items' must be an array of objects.".to_string());
+            };
+            let value = decimal_value(item.get("value"))
+                .ok_or_else(|| "Each item must have a numeric 'value' field.".to_string())?;
+            let weight = decimal_value(item.get("weight"))
+                .ok_or_else(|| "Each item must have a numeric 'weight' field.".to_string())?;
+
+            total_weighted_value = total_weighted_value
+                .and_then(|total| total.checked_add(value.checked_mul(weight)?));
+            total_weight = total_weight.and_then(|total| total.checked_add(weight));
+        }
+
+        match total_weight {
+            Some(weight) if weight.is_zero() => decimal_result(Some(Decimal::ZERO), args),
+            Some(weight) => decimal_result(
+                total_weighted_value.and_then(|total| total.checked_div(weight)),
+                args,
+            ),
+            None => decimal_result(None, args),
+        }
+    }
 }
 
 impl CalculatorPlugin for WeightedAverageCalculator {
@@ -42,6 +75,12 @@ impl CalculatorPlugin for WeightedAverageCalculator {
         if items.is_empty() {
             return Ok(FactValue::Float(0.0));
         }
+        if items.iter().any(|item| match item {
+            FactValue::Object(item) => item.values().any(FactValue::is_decimal),
+            _ => false,
+        }) {
+            return Self::decimal_weighted_average(items, args);
+        }
 
         // Initialize accumulators for weighted average calculation
         let mut total_weighted_value = 0.0;
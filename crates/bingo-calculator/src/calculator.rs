@@ -1,15 +1,23 @@
 use crate::built_in::{
-    add::AddCalculator, multiply::MultiplyCalculator, percentage_add::PercentageAddCalculator,
+    add::AddCalculator,
+    multiply::MultiplyCalculator,
+    overtime::OvertimeCalculator,
+    percentage_add::PercentageAddCalculator,
     percentage_deduct::PercentageDeductCalculator,
+    pro_rata::ProRataCalculator,
     proportional_allocator::ProportionalAllocatorCalculator,
+    rate_lookup::{RateLookupCalculator, RateTable},
+    tiered_rate::TieredRateCalculator,
     time_between_datetime::TimeBetweenDatetimeCalculator,
 };
-use crate::plugin::CalculationResult;
+use crate::plugin::{CalculationResult, CalculatorPlugin, FieldSpec, validate_inputs};
 use crate::plugin_manager::PluginManager;
 use bingo_types::FactValue;
+use std::sync::Arc;
 
 pub struct Calculator {
     plugin_manager: PluginManager,
+    rate_lookup: RateLookupCalculator,
 }
 
 impl Default for Calculator {
@@ -27,15 +35,51 @@ impl Calculator {
         plugin_manager.register(Box::new(PercentageDeductCalculator));
         plugin_manager.register(Box::new(ProportionalAllocatorCalculator));
         plugin_manager.register(Box::new(TimeBetweenDatetimeCalculator));
-        Self { plugin_manager }
+        plugin_manager.register(Box::new(OvertimeCalculator));
+        plugin_manager.register(Box::new(TieredRateCalculator));
+        plugin_manager.register(Box::new(ProRataCalculator));
+        let rate_lookup = RateLookupCalculator::new();
+        plugin_manager.register(Box::new(rate_lookup.clone()));
+        Self { plugin_manager, rate_lookup }
     }
 
+    /// Register a calculator, replacing any registered under the same name
+    pub fn register(&mut self, plugin: Box<dyn CalculatorPlugin>) {
+        self.plugin_manager.register(plugin);
+    }
+
+    /// Register a rate table the `rate_lookup` calculator can read as `name`, replacing
+    /// any table with that name
+    pub fn register_rate_table(&mut self, name: impl Into<String>, table: Arc<dyn RateTable>) {
+        self.rate_lookup = std::mem::take(&mut self.rate_lookup).with_table(name, table);
+        self.plugin_manager.register(Box::new(self.rate_lookup.clone()));
+    }
+
+    /// Whether a calculator is registered under `name`
+    pub fn contains(&self, name: &str) -> bool {
+        self.plugin_manager.contains(name)
+    }
+
+    /// Inputs declared by the calculator registered under `name`
+    pub fn input_fields(&self, name: &str) -> Option<&[FieldSpec]> {
+        self.plugin_manager.get(name).map(|plugin| plugin.input_fields())
+    }
+
+    /// Whether the calculator registered under `name` returns the same result for the
+    /// same arguments; `false` when none is registered
+    pub fn is_deterministic(&self, name: &str) -> bool {
+        self.plugin_manager.get(name).is_some_and(|plugin| plugin.is_deterministic())
+    }
+
+    /// Run the calculator registered under `calculator_name`, after checking `args`
+    /// against the inputs it declares
     pub fn calculate(
         &self,
         calculator_name: &str,
         args: &std::collections::HashMap<String, &FactValue>,
     ) -> CalculationResult {
         if let Some(plugin) = self.plugin_manager.get(calculator_name) {
+            validate_inputs(plugin.input_fields(), args)?;
             plugin.calculate(args)
         } else {
             Err(format!("calculator '{calculator_name}' not found"))
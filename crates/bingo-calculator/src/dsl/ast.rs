@@ -50,6 +50,9 @@ pub enum Expression {
 
     /// Date literal from string
     DateLiteral { iso_string: String },
+
+    /// Lambda passed to a list function (item => item.price * item.quantity)
+    Lambda { parameter: String, body: Box<Expression> },
 }
 
 /// Binary operators supported by the calculator
@@ -183,6 +186,11 @@ impl Expression {
         Self::FieldAccess { object: Box::new(object), field: field.to_string() }
     }
 
+    /// Create a lambda binding `parameter` in `body`
+    pub fn lambda(parameter: &str, body: Expression) -> Self {
+        Self::Lambda { parameter: parameter.to_string(), body: Box::new(body) }
+    }
+
     /// Create a conditional set expression
     pub fn conditional_set(
         conditions: Vec<(Expression, Expression)>,
@@ -279,6 +287,13 @@ fn extract_variables_recursive(expr: &Expression, variables: &mut HashSet<String
             extract_variables_recursive(array, variables);
             extract_variables_recursive(index, variables);
         }
+        Expression::Lambda { parameter, body } => {
+            // The parameter is bound by the lambda, not read from the context
+            let mut body_variables = HashSet::new();
+            extract_variables_recursive(body, &mut body_variables);
+            body_variables.remove(parameter);
+            variables.extend(body_variables);
+        }
         Expression::DateLiteral { .. } | Expression::Literal(_) => {
             // Literals don't contain variables
         }
@@ -349,6 +364,28 @@ mod tests {
         assert_eq!(variables, vec!["base_amount", "status"]);
     }
 
+    #[test]
+    fn test_lambda_parameter_is_not_a_variable() {
+        // sum_of(items, item => item.price * rate)
+        let expr = Expression::call(
+            "sum_of",
+            vec![
+                Expression::var("items"),
+                Expression::lambda(
+                    "item",
+                    Expression::binary(
+                        Expression::field(Expression::var("item"), "price"),
+                        BinaryOperator::Multiply,
+                        Expression::var("rate"),
+                    ),
+                ),
+            ],
+        );
+
+        let variables = extract_variables(&expr);
+        assert_eq!(variables, vec!["items", "rate"]);
+    }
+
     #[test]
     fn test_conditional_set_expression() {
         let expr = Expression::conditional_set(
@@ -0,0 +1,657 @@
+//! Compilation of calculator expressions to closures
+//!
+//! The evaluator walks the AST on every call, re-matching each node, re-evaluating
+//! constant subexpressions and cloning every variable it reads. Rule actions evaluate
+//! the same few formulas for every fact, so [`compile`] turns an expression into a
+//! [`Program`] once:
+//!
+//! - **Constant folding**: Operators, conditionals, arrays and objects whose operands
+//!   are all literals are evaluated at compile time. Subexpressions that would fail,
+//!   such as `1 / 0`, are left to fail when evaluated, so an untaken branch never
+//!   raises an error
+//! - **Closure tree**: Each remaining node becomes a boxed closure calling its
+//!   children directly. Operators read variables and constants in place, numeric
+//!   operators skip the general operator match, and lambda parameters are resolved to
+//!   slots instead of names
+//!
+//! A program returns the same value or error as the evaluator for every expression;
+//! function calls always run at evaluation time, since registered functions may read
+//! the context or be replaced.
+
+use crate::dsl::EvaluationContext;
+use crate::dsl::ast::{BinaryOperator, Expression};
+use crate::dsl::evaluator::{
+    access_field, apply_list_function, evaluate_binary_op, evaluate_unary_op, index_value,
+    is_list_function, is_truthy, lambda_error, list_arguments_error,
+};
+use crate::dsl::functions::FunctionRegistry;
+use crate::types::FactValue;
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// Compiled expression node, given the values bound to the parameters of the
+/// enclosing lambdas, outermost first
+type Node = Box<
+    dyn Fn(&EvaluationContext, &FunctionRegistry, &mut Vec<FactValue>) -> Result<FactValue>
+        + Send
+        + Sync,
+>;
+
+/// Expression compiled to a tree of closures
+pub struct Program {
+    root: Node,
+    /// Value of an expression that folded to a constant
+    constant: Option<FactValue>,
+}
+
+impl std::fmt::Debug for Program {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Program")
+            .field("constant", &self.constant)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Compile an expression, folding its constant subexpressions
+pub fn compile(expr: &Expression) -> Program {
+    let folded = fold_constants(expr);
+    let constant = match &folded {
+        Expression::Literal(value) => Some(value.clone()),
+        _ => None,
+    };
+    Program { root: compile_node(&folded, &mut Vec::new()), constant }
+}
+
+impl Program {
+    /// Evaluate the program in the given context
+    pub fn evaluate(
+        &self,
+        context: &EvaluationContext,
+        functions: &FunctionRegistry,
+    ) -> Result<FactValue> {
+        (self.root)(context, functions, &mut Vec::new())
+    }
+
+    /// Value of the expression when it folded to a constant
+    pub fn constant(&self) -> Option<&FactValue> {
+        self.constant.as_ref()
+    }
+}
+
+/// Where an operator reads an operand from
+///
+/// Constants and variables are read in place rather than through a node cloning them.
+enum Operand {
+    /// A constant, read in place
+    Constant(FactValue),
+    /// A fact field or global, read in place
+    Variable(String),
+    /// The parameter of an enclosing lambda
+    Parameter(usize),
+    /// Any other expression
+    Node(Node),
+}
+
+impl Operand {
+    /// Operand for `expr` inside lambdas binding `parameters`
+    fn compile(expr: &Expression, parameters: &mut Vec<String>) -> Self {
+        match expr {
+            Expression::Literal(value) => Operand::Constant(value.clone()),
+            // Lambda parameters shadow fact fields and globals
+            Expression::Variable(name) => {
+                match parameters.iter().rposition(|parameter| parameter == name) {
+                    Some(slot) => Operand::Parameter(slot),
+                    None => Operand::Variable(name.clone()),
+                }
+            }
+            expr => Operand::Node(compile_node(expr, parameters)),
+        }
+    }
+
+    /// Value of the operand, borrowed when it is read in place
+    fn value<'v>(
+        &'v self,
+        context: &'v EvaluationContext,
+        functions: &FunctionRegistry,
+        bound: &mut Vec<FactValue>,
+    ) -> Result<Cow<'v, FactValue>> {
+        Ok(match self {
+            Operand::Constant(value) => Cow::Borrowed(value),
+            Operand::Variable(name) => Cow::Borrowed(lookup(name, context)?),
+            Operand::Parameter(slot) => Cow::Owned(bound[*slot].clone()),
+            Operand::Node(node) => Cow::Owned(node(context, functions, bound)?),
+        })
+    }
+}
+
+/// Compile an expression inside lambdas binding `parameters`
+fn compile_node(expr: &Expression, parameters: &mut Vec<String>) -> Node {
+    match expr {
+        Expression::Literal(value) => {
+            let value = value.clone();
+            Box::new(move |_, _, _| Ok(value.clone()))
+        }
+
+        Expression::Variable(_) => match Operand::compile(expr, parameters) {
+            Operand::Parameter(slot) => Box::new(move |_, _, bound| Ok(bound[slot].clone())),
+            Operand::Variable(name) => {
+                Box::new(move |context, _, _| Ok(lookup(&name, context)?.clone()))
+            }
+            _ => unreachable!("variables compile to parameters or lookups"),
+        },
+
+        Expression::BinaryOp { left, operator, right } => {
+            let operator = operator.clone();
+            match (
+                Operand::compile(left, parameters),
+                Operand::compile(right, parameters),
+            ) {
+                // Specialised pairs skip matching on the operands at evaluation time
+                (Operand::Variable(name), Operand::Constant(right)) => {
+                    Box::new(move |context, _, _| {
+                        binary_op(lookup(&name, context)?, &operator, &right)
+                    })
+                }
+                (Operand::Node(left), Operand::Constant(right)) => {
+                    Box::new(move |context, functions, bound| {
+                        binary_op(&left(context, functions, bound)?, &operator, &right)
+                    })
+                }
+                (Operand::Variable(left), Operand::Variable(right)) => {
+                    Box::new(move |context, _, _| {
+                        binary_op(lookup(&left, context)?, &operator, lookup(&right, context)?)
+                    })
+                }
+                (Operand::Node(left), Operand::Node(right)) => {
+                    Box::new(move |context, functions, bound| {
+                        let left = left(context, functions, bound)?;
+                        binary_op(&left, &operator, &right(context, functions, bound)?)
+                    })
+                }
+                (left, right) => Box::new(move |context, functions, bound| {
+                    let left = left.value(context, functions, bound)?;
+                    let right = right.value(context, functions, bound)?;
+                    binary_op(&left, &operator, &right)
+                }),
+            }
+        }
+
+        Expression::UnaryOp { operator, operand } => {
+            let operand = compile_node(operand, parameters);
+            let operator = operator.clone();
+            Box::new(move |context, functions, bound| {
+                evaluate_unary_op(&operator, &operand(context, functions, bound)?)
+            })
+        }
+
+        Expression::FunctionCall { name, args } if is_list_function(name) => {
+            let (list, lambda) = match args.as_slice() {
+                [list] if name != "map" && name != "filter" => (list, None),
+                [list, Expression::Lambda { parameter, body }] => {
+                    parameters.push(parameter.clone());
+                    let body = compile_node(body, parameters);
+                    parameters.pop();
+                    (list, Some(body))
+                }
+                _ => return fail(list_arguments_error(name)),
+            };
+            let list = compile_node(list, parameters);
+            let name = name.clone();
+            Box::new(move |context, functions, bound| {
+                let list = list(context, functions, bound)?;
+                apply_list_function(&name, list, |item| match &lambda {
+                    Some(body) => {
+                        bound.push(item.clone());
+                        let value = body(context, functions, bound);
+                        bound.pop();
+                        value
+                    }
+                    None => Ok(item.clone()),
+                })
+            })
+        }
+
+        Expression::FunctionCall { name, args } => {
+            let args: Vec<Node> = args.iter().map(|arg| compile_node(arg, parameters)).collect();
+            let name = name.clone();
+            Box::new(move |context, functions, bound| {
+                let mut arg_values = Vec::with_capacity(args.len());
+                for arg in &args {
+                    arg_values.push(arg(context, functions, bound)?);
+                }
+                functions.call_with_context(&name, &arg_values, context)
+            })
+        }
+
+        Expression::Conditional { condition, then_expr, else_expr } => {
+            let condition = compile_node(condition, parameters);
+            let then_expr = compile_node(then_expr, parameters);
+            let else_expr = compile_node(else_expr, parameters);
+            Box::new(move |context, functions, bound| {
+                if is_truthy(&condition(context, functions, bound)?) {
+                    then_expr(context, functions, bound)
+                } else {
+                    else_expr(context, functions, bound)
+                }
+            })
+        }
+
+        Expression::FieldAccess { object, field } => {
+            let object = compile_node(object, parameters);
+            let field = field.clone();
+            Box::new(move |context, functions, bound| {
+                access_field(object(context, functions, bound)?, &field, context)
+            })
+        }
+
+        Expression::ConditionalSet { conditions, default_value } => {
+            let conditions: Vec<(Node, Node)> = conditions
+                .iter()
+                .map(|(condition, value)| {
+                    (
+                        compile_node(condition, parameters),
+                        compile_node(value, parameters),
+                    )
+                })
+                .collect();
+            let default_value =
+                default_value.as_deref().map(|default| compile_node(default, parameters));
+            Box::new(move |context, functions, bound| {
+                for (condition, value) in &conditions {
+                    if is_truthy(&condition(context, functions, bound)?) {
+                        return value(context, functions, bound);
+                    }
+                }
+                match &default_value {
+                    Some(default) => default(context, functions, bound),
+                    None => Err(anyhow!(
+                        "No conditions matched in conditional set and no default value provided"
+                    )),
+                }
+            })
+        }
+
+        Expression::ArrayLiteral { elements } => {
+            let elements: Vec<Node> =
+                elements.iter().map(|element| compile_node(element, parameters)).collect();
+            Box::new(move |context, functions, bound| {
+                let mut values = Vec::with_capacity(elements.len());
+                for element in &elements {
+                    values.push(element(context, functions, bound)?);
+                }
+                Ok(FactValue::Array(values))
+            })
+        }
+
+        Expression::ObjectLiteral { fields } => {
+            let fields: Vec<(String, Node)> = fields
+                .iter()
+                .map(|(key, value)| (key.clone(), compile_node(value, parameters)))
+                .collect();
+            Box::new(move |context, functions, bound| {
+                let mut object = HashMap::new();
+                for (key, value) in &fields {
+                    object.insert(key.clone(), value(context, functions, bound)?);
+                }
+                Ok(FactValue::Object(object))
+            })
+        }
+
+        Expression::ArrayIndex { array, index } => {
+            let array = compile_node(array, parameters);
+            let index = compile_node(index, parameters);
+            Box::new(move |context, functions, bound| {
+                let array = array(context, functions, bound)?;
+                index_value(array, index(context, functions, bound)?)
+            })
+        }
+
+        // Valid dates were folded to literals
+        Expression::DateLiteral { iso_string } => match parse_date(iso_string) {
+            Ok(date) => compile_node(&Expression::Literal(date), parameters),
+            Err(error) => fail(error),
+        },
+
+        Expression::Lambda { parameter, .. } => fail(lambda_error(parameter)),
+    }
+}
+
+/// Node failing with the error the evaluator raises for its expression
+fn fail(error: anyhow::Error) -> Node {
+    let message = error.to_string();
+    Box::new(move |_, _, _| Err(anyhow!("{}", message)))
+}
+
+/// Apply a binary operator, trying the numeric fast path first
+fn binary_op(left: &FactValue, operator: &BinaryOperator, right: &FactValue) -> Result<FactValue> {
+    match numeric_binary_op(left, operator, right) {
+        Some(value) => Ok(value),
+        None => evaluate_binary_op(left, operator, right),
+    }
+}
+
+/// Arithmetic or comparison of two integers or two floats, computed as the evaluator
+/// does without going through its match over every operator and type
+///
+/// Returns `None` for other operands and operators, and for division by zero, which
+/// the evaluator reports as an error.
+fn numeric_binary_op(
+    left: &FactValue,
+    operator: &BinaryOperator,
+    right: &FactValue,
+) -> Option<FactValue> {
+    use {BinaryOperator::*, FactValue::*};
+
+    Some(match (left, right) {
+        (Integer(a), Integer(b)) => match operator {
+            Add => Integer(a + b),
+            Subtract => Integer(a - b),
+            Multiply => Integer(a * b),
+            Divide if *b != 0 => Integer(a / b),
+            Modulo if *b != 0 => Integer(a % b),
+            Equal => Boolean(a == b),
+            NotEqual => Boolean(a != b),
+            LessThan => Boolean(a < b),
+            LessThanOrEqual => Boolean(a <= b),
+            GreaterThan => Boolean(a > b),
+            GreaterThanOrEqual => Boolean(a >= b),
+            _ => return None,
+        },
+        (Float(a), Float(b)) => match operator {
+            Add => Float(a + b),
+            Subtract => Float(a - b),
+            Multiply => Float(a * b),
+            Divide if *b != 0.0 => Float(a / b),
+            Modulo => Float(a % b),
+            Equal => Boolean((a - b).abs() < f64::EPSILON),
+            NotEqual => Boolean((a - b).abs() >= f64::EPSILON),
+            LessThan => Boolean(a < b),
+            LessThanOrEqual => Boolean(a <= b),
+            GreaterThan => Boolean(a > b),
+            GreaterThanOrEqual => Boolean(a >= b),
+            _ => return None,
+        },
+        _ => return None,
+    })
+}
+
+/// Fact field or global named `name`, with fact fields shadowing globals
+fn lookup<'v>(name: &str, context: &'v EvaluationContext) -> Result<&'v FactValue> {
+    context
+        .current_fact
+        .data
+        .fields
+        .get(name)
+        .or_else(|| context.globals.get(name))
+        .ok_or_else(|| anyhow!("Variable '{}' not found in context", name))
+}
+
+/// Parse a date literal as the evaluator does
+fn parse_date(iso_string: &str) -> Result<FactValue> {
+    match iso_string.parse::<DateTime<Utc>>() {
+        Ok(dt) => Ok(FactValue::Date(dt)),
+        Err(e) => Err(anyhow!("Invalid date format '{}': {}", iso_string, e)),
+    }
+}
+
+/// Evaluate every subexpression whose operands are all constant and would not fail
+fn fold_constants(expr: &Expression) -> Expression {
+    let literal = |expr: &Expression| match expr {
+        Expression::Literal(value) => Some(value.clone()),
+        _ => None,
+    };
+
+    match expr {
+        Expression::BinaryOp { left, operator, right } => {
+            let (left, right) = (fold_constants(left), fold_constants(right));
+            if let (Some(a), Some(b)) = (literal(&left), literal(&right))
+                && let Ok(value) = evaluate_binary_op(&a, operator, &b)
+            {
+                return Expression::Literal(value);
+            }
+            Expression::binary(left, operator.clone(), right)
+        }
+
+        Expression::UnaryOp { operator, operand } => {
+            let operand = fold_constants(operand);
+            if let Some(value) = literal(&operand)
+                && let Ok(value) = evaluate_unary_op(operator, &value)
+            {
+                return Expression::Literal(value);
+            }
+            Expression::unary(operator.clone(), operand)
+        }
+
+        Expression::Conditional { condition, then_expr, else_expr } => {
+            let condition = fold_constants(condition);
+            match literal(&condition) {
+                Some(value) if is_truthy(&value) => fold_constants(then_expr),
+                Some(_) => fold_constants(else_expr),
+                None => Expression::conditional(
+                    condition,
+                    fold_constants(then_expr),
+                    fold_constants(else_expr),
+                ),
+            }
+        }
+
+        Expression::ConditionalSet { conditions, default_value } => {
+            let mut folded = Vec::new();
+            for (condition, value) in conditions {
+                let condition = fold_constants(condition);
+                match literal(&condition) {
+                    // A condition that always holds ends the set
+                    Some(always) if is_truthy(&always) => {
+                        let value = fold_constants(value);
+                        return if folded.is_empty() {
+                            value
+                        } else {
+                            Expression::conditional_set(folded, Some(value))
+                        };
+                    }
+                    Some(_) => {}
+                    None => folded.push((condition, fold_constants(value))),
+                }
+            }
+            let default_value = default_value.as_deref().map(fold_constants);
+            match default_value {
+                Some(default) if folded.is_empty() => default,
+                default_value => Expression::conditional_set(folded, default_value),
+            }
+        }
+
+        Expression::ArrayLiteral { elements } => {
+            let elements: Vec<Expression> = elements.iter().map(fold_constants).collect();
+            match elements.iter().map(literal).collect::<Option<Vec<_>>>() {
+                Some(values) => Expression::Literal(FactValue::Array(values)),
+                None => Expression::array(elements),
+            }
+        }
+
+        Expression::ObjectLiteral { fields } => {
+            let fields: Vec<(String, Expression)> =
+                fields.iter().map(|(key, value)| (key.clone(), fold_constants(value))).collect();
+            let values: Option<HashMap<String, FactValue>> = fields
+                .iter()
+                .map(|(key, value)| literal(value).map(|value| (key.clone(), value)))
+                .collect();
+            match values {
+                Some(values) => Expression::Literal(FactValue::Object(values)),
+                None => Expression::object(fields),
+            }
+        }
+
+        Expression::ArrayIndex { array, index } => {
+            let (array, index) = (fold_constants(array), fold_constants(index));
+            if let (Some(a), Some(i)) = (literal(&array), literal(&index))
+                && let Ok(value) = index_value(a, i)
+            {
+                return Expression::Literal(value);
+            }
+            Expression::index(array, index)
+        }
+
+        Expression::DateLiteral { iso_string } => match parse_date(iso_string) {
+            Ok(date) => Expression::Literal(date),
+            Err(_) => expr.clone(),
+        },
+
+        Expression::FunctionCall { name, args } => Expression::FunctionCall {
+            name: name.clone(),
+            args: args.iter().map(fold_constants).collect(),
+        },
+
+        Expression::FieldAccess { object, field } => Expression::FieldAccess {
+            object: Box::new(fold_constants(object)),
+            field: field.clone(),
+        },
+
+        Expression::Lambda { parameter, body } => {
+            Expression::lambda(parameter, fold_constants(body))
+        }
+
+        Expression::Literal(_) | Expression::Variable(_) => expr.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::evaluator::evaluate_expression;
+    use crate::dsl::parser::parse_expression;
+    use crate::dsl::{CalculatorResult, Fact, FactData};
+    use std::time::Instant;
+
+    fn order() -> Fact {
+        let line = |price: i64, quantity: i64| {
+            FactValue::Object(HashMap::from([
+                ("price".to_string(), FactValue::Integer(price)),
+                ("quantity".to_string(), FactValue::Integer(quantity)),
+            ]))
+        };
+        let fields = HashMap::from([
+            ("amount".to_string(), FactValue::Float(250.0)),
+            ("quantity".to_string(), FactValue::Integer(4)),
+            (
+                "status".to_string(),
+                FactValue::String("active".to_string()),
+            ),
+            (
+                "lines".to_string(),
+                FactValue::Array(vec![line(10, 2), line(25, 1)]),
+            ),
+        ]);
+        Fact { id: 1, data: FactData { fields } }
+    }
+
+    fn context(fact: &Fact) -> EvaluationContext<'_> {
+        let globals = HashMap::from([("rate".to_string(), FactValue::Float(0.2))]);
+        EvaluationContext { current_fact: fact, facts: &[], globals }
+    }
+
+    #[test]
+    fn test_constants_are_folded() {
+        let program = compile(&parse_expression("2 * (3 + 4) ** 2").unwrap());
+        assert_eq!(program.constant(), Some(&FactValue::Integer(98)));
+        let program = compile(&parse_expression("if 1 > 2 then 1 / 0 else [1, 2][-1]").unwrap());
+        assert_eq!(program.constant(), Some(&FactValue::Integer(2)));
+
+        // Only the operands that are constant fold
+        let folded = fold_constants(&parse_expression("amount * (1 + 8 / 100.0) - 5 * 2").unwrap());
+        let amount = Expression::var("amount");
+        assert_eq!(
+            folded,
+            Expression::binary(
+                Expression::binary(amount, BinaryOperator::Multiply, Expression::float(1.08)),
+                BinaryOperator::Subtract,
+                Expression::int(10),
+            )
+        );
+
+        // A constant that would fail is left to fail when evaluated
+        let program = compile(&parse_expression("1 / 0").unwrap());
+        assert!(program.constant().is_none());
+        let fact = order();
+        let registry = FunctionRegistry::with_builtins();
+        assert!(program.evaluate(&context(&fact), &registry).is_err());
+    }
+
+    #[test]
+    fn test_programs_match_tree_walk() {
+        let fact = order();
+        let context = context(&fact);
+        let registry = FunctionRegistry::with_builtins();
+        for source in [
+            "amount * (1 + rate) - quantity",
+            "if status == \"active\" then max(amount, 300) else 0",
+            "quantity > 3 && amount < 100 || status contains \"act\"",
+            "sum_of(lines, line => line.price * line.quantity * quantity)",
+            "map(filter(lines, q => q.quantity < 2), q => q.price)",
+            "all(lines, line => any([1, 2], quantity => quantity == line.quantity))",
+            "{total: amount, lines: [quantity, 2 + 3]}",
+            "lines[-1][\"price\"] + lines[0].price",
+            "missing + 1",
+            "unknown(quantity)",
+            "map(lines)",
+            "-status",
+            "lines[5]",
+            "sum_of(status)",
+        ] {
+            let expr = parse_expression(source).unwrap();
+            let compiled = compile(&expr).evaluate(&context, &registry);
+            let walked = evaluate_expression(&expr, &context, &registry);
+            match (compiled, walked) {
+                (Ok(value), Ok(CalculatorResult::Value(expected))) => {
+                    assert_eq!(value, expected, "{source}")
+                }
+                (Err(error), Err(expected)) => {
+                    assert_eq!(error.to_string(), expected.to_string(), "{source}")
+                }
+                (compiled, walked) => panic!("{source}: {compiled:?} != {walked:?}"),
+            }
+        }
+    }
+
+    #[test]
+    #[ignore = "benchmark; run with cargo test --release -- --ignored --nocapture"]
+    fn bench_compiled_against_tree_walk() {
+        let fact = order();
+        let context = context(&fact);
+        let registry = FunctionRegistry::with_builtins();
+        let expr = parse_expression(
+            "if quantity * 2 >= 4 + 2 then amount * (1 + 8.25 / 100) - 2.5 * (3 + 1) \
+             else amount * (1 - 15 / 100.0)",
+        )
+        .unwrap();
+        let program = compile(&expr);
+
+        // Best of several rounds, so a scheduling hiccup doesn't decide the result
+        let time = |evaluate: &dyn Fn()| {
+            (0..5)
+                .map(|_| {
+                    let started = Instant::now();
+                    for _ in 0..100_000 {
+                        evaluate();
+                    }
+                    started.elapsed()
+                })
+                .min()
+                .unwrap()
+        };
+        let walked = time(&|| {
+            std::hint::black_box(evaluate_expression(&expr, &context, &registry).unwrap());
+        });
+        let compiled = time(&|| {
+            std::hint::black_box(program.evaluate(&context, &registry).unwrap());
+        });
+
+        let speedup = walked.as_secs_f64() / compiled.as_secs_f64();
+        println!("tree walk {walked:?}, compiled {compiled:?}, {speedup:.1}x");
+        assert!(
+            speedup > 3.0,
+            "compiled evaluation only {speedup:.1}x faster"
+        );
+    }
+}
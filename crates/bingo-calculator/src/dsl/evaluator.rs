@@ -2,6 +2,10 @@
 //!
 //! This module evaluates parsed AST expressions against fact contexts,
 //! providing type-safe computation with comprehensive error handling.
+//!
+//! The list functions `map`, `filter`, `sum_of`, `any` and `all` take a lambda
+//! (`item => item.price * item.quantity`) applied to each element of an array. The
+//! lambda's parameter shadows fact fields and globals of the same name.
 
 use crate::dsl::ast::{BinaryOperator, Expression, UnaryOperator};
 use crate::dsl::functions::FunctionRegistry;
@@ -15,20 +19,44 @@ pub fn evaluate_expression(
     context: &EvaluationContext,
     functions: &FunctionRegistry,
 ) -> Result<CalculatorResult> {
-    let value = evaluate_to_value(expr, context, functions)?;
+    let value = evaluate_to_value(expr, context, functions, None)?;
     Ok(CalculatorResult::Value(value))
 }
 
+/// A lambda parameter bound to the element being evaluated, linked to the bindings of
+/// enclosing lambdas
+struct Binding<'s> {
+    parameter: &'s str,
+    value: &'s FactValue,
+    parent: Option<&'s Binding<'s>>,
+}
+
+impl Binding<'_> {
+    /// Value bound to `name` by this or an enclosing lambda
+    fn lookup(&self, name: &str) -> Option<&FactValue> {
+        if self.parameter == name {
+            return Some(self.value);
+        }
+        self.parent.and_then(|parent| parent.lookup(name))
+    }
+}
+
 /// Evaluate an expression to a single value
 fn evaluate_to_value(
     expr: &Expression,
     context: &EvaluationContext,
     functions: &FunctionRegistry,
+    scope: Option<&Binding>,
 ) -> Result<FactValue> {
     match expr {
         Expression::Literal(value) => Ok(value.clone()),
 
         Expression::Variable(name) => {
+            // Lambda parameters shadow fact fields and globals
+            if let Some(value) = scope.and_then(|binding| binding.lookup(name)) {
+                return Ok(value.clone());
+            }
+
             // Check current fact fields first
             if let Some(value) = context.current_fact.data.fields.get(name) {
                 return Ok(value.clone());
@@ -43,73 +71,56 @@ fn evaluate_to_value(
         }
 
         Expression::BinaryOp { left, operator, right } => {
-            let left_val = evaluate_to_value(left, context, functions)?;
-            let right_val = evaluate_to_value(right, context, functions)?;
+            let left_val = evaluate_to_value(left, context, functions, scope)?;
+            let right_val = evaluate_to_value(right, context, functions, scope)?;
             evaluate_binary_op(&left_val, operator, &right_val)
         }
 
         Expression::UnaryOp { operator, operand } => {
-            let operand_val = evaluate_to_value(operand, context, functions)?;
+            let operand_val = evaluate_to_value(operand, context, functions, scope)?;
             evaluate_unary_op(operator, &operand_val)
         }
 
+        Expression::FunctionCall { name, args } if is_list_function(name) => {
+            evaluate_list_function(name, args, context, functions, scope)
+        }
+
         Expression::FunctionCall { name, args } => {
             let mut arg_values = Vec::new();
             for arg in args {
-                arg_values.push(evaluate_to_value(arg, context, functions)?);
+                arg_values.push(evaluate_to_value(arg, context, functions, scope)?);
             }
 
             functions.call_with_context(name, &arg_values, context)
         }
 
         Expression::Conditional { condition, then_expr, else_expr } => {
-            let condition_val = evaluate_to_value(condition, context, functions)?;
+            let condition_val = evaluate_to_value(condition, context, functions, scope)?;
 
             if is_truthy(&condition_val) {
-                evaluate_to_value(then_expr, context, functions)
+                evaluate_to_value(then_expr, context, functions, scope)
             } else {
-                evaluate_to_value(else_expr, context, functions)
+                evaluate_to_value(else_expr, context, functions, scope)
             }
         }
 
         Expression::FieldAccess { object, field } => {
-            let object_val = evaluate_to_value(object, context, functions)?;
-
-            // For now, field access is only supported on facts
-            
-            match object_val {
-                FactValue::String(fact_id_str) => {
-                    // Try to find fact by ID string
-                    if let Ok(fact_id) = fact_id_str.parse::<u64>() {
-                        if let Some(fact) = context.facts.iter().find(|f| f.id == fact_id) {
-                            if let Some(field_value) = fact.data.fields.get(field) {
-                                Ok(field_value.clone())
-                            } else {
-                                Err(anyhow!("Field '{}' not found on fact {}", field, fact_id))
-                            }
-                        } else {
-                            Err(anyhow!("Fact with ID {} not found", fact_id))
-                        }
-                    } else {
-                        Err(anyhow!("Cannot access field '{}' on string value", field))
-                    }
-                }
-                _ => Err(anyhow!("Field access not supported on {:?}", object_val)),
-            }
+            let object_val = evaluate_to_value(object, context, functions, scope)?;
+            access_field(object_val, field, context)
         }
 
         Expression::ConditionalSet { conditions, default_value } => {
             // Evaluate conditions in order and return the first matching value
             for (condition, value) in conditions {
-                let condition_val = evaluate_to_value(condition, context, functions)?;
+                let condition_val = evaluate_to_value(condition, context, functions, scope)?;
                 if is_truthy(&condition_val) {
-                    return evaluate_to_value(value, context, functions);
+                    return evaluate_to_value(value, context, functions, scope);
                 }
             }
 
             // If no conditions matched, use default value or return error
             if let Some(default) = default_value {
-                evaluate_to_value(default, context, functions)
+                evaluate_to_value(default, context, functions, scope)
             } else {
                 Err(anyhow!(
                     "No conditions matched in conditional set and no default value provided"
@@ -120,7 +131,7 @@ fn evaluate_to_value(
         Expression::ArrayLiteral { elements } => {
             let mut array_values = Vec::new();
             for element in elements {
-                array_values.push(evaluate_to_value(element, context, functions)?);
+                array_values.push(evaluate_to_value(element, context, functions, scope)?);
             }
             Ok(FactValue::Array(array_values))
         }
@@ -128,34 +139,16 @@ fn evaluate_to_value(
         Expression::ObjectLiteral { fields } => {
             let mut object_fields = std::collections::HashMap::new();
             for (key, value_expr) in fields {
-                let value = evaluate_to_value(value_expr, context, functions)?;
+                let value = evaluate_to_value(value_expr, context, functions, scope)?;
                 object_fields.insert(key.clone(), value);
             }
             Ok(FactValue::Object(object_fields))
         }
 
         Expression::ArrayIndex { array, index } => {
-            let array_val = evaluate_to_value(array, context, functions)?;
-            let index_val = evaluate_to_value(index, context, functions)?;
-
-            match (array_val, index_val) {
-                (FactValue::Array(arr), FactValue::Integer(idx)) => {
-                    let index = if idx < 0 {
-                        // Negative indexing from end
-                        (arr.len() as i64 + idx) as usize
-                    } else {
-                        idx as usize
-                    };
-
-                    arr.get(index)
-                        .cloned()
-                        .ok_or_else(|| anyhow!("Array index {} out of bounds", idx))
-                }
-                (FactValue::Object(obj), FactValue::String(key)) => {
-                    Ok(obj.get(&key).cloned().unwrap_or(FactValue::Null))
-                }
-                _ => Err(anyhow!("Invalid array/object indexing operation")),
-            }
+            let array_val = evaluate_to_value(array, context, functions, scope)?;
+            let index_val = evaluate_to_value(index, context, functions, scope)?;
+            index_value(array_val, index_val)
         }
 
         Expression::DateLiteral { iso_string } => {
@@ -165,11 +158,164 @@ fn evaluate_to_value(
                 Err(e) => Err(anyhow!("Invalid date format '{}': {}", iso_string, e)),
             }
         }
+
+        Expression::Lambda { parameter, .. } => Err(lambda_error(parameter)),
+    }
+}
+
+/// Whether `name` is a list function taking a lambda
+pub(crate) fn is_list_function(name: &str) -> bool {
+    matches!(name, "map" | "filter" | "sum_of" | "any" | "all")
+}
+
+/// Error for a list function called without an array and lambda
+pub(crate) fn list_arguments_error(name: &str) -> anyhow::Error {
+    anyhow!("{}() requires an array and a lambda", name)
+}
+
+/// Error for a lambda evaluated outside a list function
+pub(crate) fn lambda_error(parameter: &str) -> anyhow::Error {
+    anyhow!(
+        "Lambda '{} => ...' can only be passed to map, filter, sum_of, any or all",
+        parameter
+    )
+}
+
+/// Evaluate `map`, `filter`, `sum_of`, `any` or `all` over the array in `args[0]`
+///
+/// `sum_of`, `any` and `all` may omit the lambda to sum the elements or test their
+/// truthiness directly.
+fn evaluate_list_function(
+    name: &str,
+    args: &[Expression],
+    context: &EvaluationContext,
+    functions: &FunctionRegistry,
+    scope: Option<&Binding>,
+) -> Result<FactValue> {
+    let (list, lambda) = match args {
+        [list] if name != "map" && name != "filter" => (list, None),
+        [list, Expression::Lambda { parameter, body }] => (list, Some((parameter, body))),
+        _ => return Err(list_arguments_error(name)),
+    };
+    let list_val = evaluate_to_value(list, context, functions, scope)?;
+
+    // Value of the lambda body for one element, or the element itself without a lambda
+    apply_list_function(name, list_val, |item| match lambda {
+        Some((parameter, body)) => {
+            let binding = Binding { parameter, value: item, parent: scope };
+            evaluate_to_value(body, context, functions, Some(&binding))
+        }
+        None => Ok(item.clone()),
+    })
+}
+
+/// Apply list function `name` to the elements of `list`, with `apply` giving the value
+/// of its lambda for an element
+///
+/// A null list has no elements. `any` and `all` stop at the first element deciding
+/// the result.
+pub(crate) fn apply_list_function(
+    name: &str,
+    list: FactValue,
+    mut apply: impl FnMut(&FactValue) -> Result<FactValue>,
+) -> Result<FactValue> {
+    let items = match list {
+        FactValue::Array(items) => items,
+        FactValue::Null => Vec::new(),
+        other => return Err(anyhow!("{}() requires an array, found {:?}", name, other)),
+    };
+
+    match name {
+        "map" => Ok(FactValue::Array(
+            items.iter().map(apply).collect::<Result<_>>()?,
+        )),
+        "filter" => {
+            let mut kept = Vec::new();
+            for item in items {
+                if is_truthy(&apply(&item)?) {
+                    kept.push(item);
+                }
+            }
+            Ok(FactValue::Array(kept))
+        }
+        "sum_of" => items.iter().try_fold(FactValue::Integer(0), |sum, item| {
+            evaluate_binary_op(&sum, &BinaryOperator::Add, &apply(item)?)
+        }),
+        "any" => {
+            for item in &items {
+                if is_truthy(&apply(item)?) {
+                    return Ok(FactValue::Boolean(true));
+                }
+            }
+            Ok(FactValue::Boolean(false))
+        }
+        "all" => {
+            for item in &items {
+                if !is_truthy(&apply(item)?) {
+                    return Ok(FactValue::Boolean(false));
+                }
+            }
+            Ok(FactValue::Boolean(true))
+        }
+        _ => unreachable!("not a list function: {name}"),
+    }
+}
+
+/// Read `field` of an object, or of the fact whose ID is the string `object`
+pub(crate) fn access_field(
+    object: FactValue,
+    field: &str,
+    context: &EvaluationContext,
+) -> Result<FactValue> {
+    match object {
+        FactValue::Object(mut object) => Ok(object.remove(field).unwrap_or(FactValue::Null)),
+        // Missing nested payloads read as null all the way down
+        FactValue::Null => Ok(FactValue::Null),
+        FactValue::String(fact_id_str) => {
+            // Try to find fact by ID string
+            if let Ok(fact_id) = fact_id_str.parse::<u64>() {
+                if let Some(fact) = context.facts.iter().find(|f| f.id == fact_id) {
+                    if let Some(field_value) = fact.data.fields.get(field) {
+                        Ok(field_value.clone())
+                    } else {
+                        Err(anyhow!("Field '{}' not found on fact {}", field, fact_id))
+                    }
+                } else {
+                    Err(anyhow!("Fact with ID {} not found", fact_id))
+                }
+            } else {
+                Err(anyhow!("Cannot access field '{}' on string value", field))
+            }
+        }
+        _ => Err(anyhow!("Field access not supported on {:?}", object)),
+    }
+}
+
+/// Element `index` of an array, counting from the end when negative, or the value of
+/// key `index` in an object
+pub(crate) fn index_value(array: FactValue, index: FactValue) -> Result<FactValue> {
+    match (array, index) {
+        (FactValue::Array(arr), FactValue::Integer(idx)) => {
+            let index = if idx < 0 {
+                // Negative indexing from end
+                (arr.len() as i64 + idx) as usize
+            } else {
+                idx as usize
+            };
+
+            arr.get(index)
+                .cloned()
+                .ok_or_else(|| anyhow!("Array index {} out of bounds", idx))
+        }
+        (FactValue::Object(obj), FactValue::String(key)) => {
+            Ok(obj.get(&key).cloned().unwrap_or(FactValue::Null))
+        }
+        _ => Err(anyhow!("Invalid array/object indexing operation")),
     }
 }
 
 /// Evaluate a binary operation
-fn evaluate_binary_op(
+pub(crate) fn evaluate_binary_op(
     left: &FactValue,
     operator: &BinaryOperator,
     right: &FactValue,
@@ -322,7 +468,10 @@ fn evaluate_binary_op(
 }
 
 /// Evaluate a unary operation
-fn evaluate_unary_op(operator: &UnaryOperator, operand: &FactValue) -> Result<FactValue> {
+pub(crate) fn evaluate_unary_op(
+    operator: &UnaryOperator,
+    operand: &FactValue,
+) -> Result<FactValue> {
     use {FactValue::*, UnaryOperator::*};
 
     match (operator, operand) {
@@ -343,7 +492,7 @@ fn evaluate_unary_op(operator: &UnaryOperator, operand: &FactValue) -> Result<Fa
 }
 
 /// Check if a value is considered "truthy" for conditional evaluation
-fn is_truthy(value: &FactValue) -> bool {
+pub(crate) fn is_truthy(value: &FactValue) -> bool {
     match value {
         FactValue::Boolean(b) => *b,
         FactValue::Integer(i) => *i != 0,
@@ -355,3 +504,110 @@ fn is_truthy(value: &FactValue) -> bool {
         FactValue::Null => false,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::parser::parse_expression;
+    use crate::dsl::{Fact, FactData};
+    use std::collections::HashMap;
+
+    fn line(sku: &str, price: i64, quantity: i64) -> FactValue {
+        FactValue::Object(HashMap::from([
+            ("sku".to_string(), FactValue::String(sku.to_string())),
+            ("price".to_string(), FactValue::Integer(price)),
+            ("quantity".to_string(), FactValue::Integer(quantity)),
+        ]))
+    }
+
+    fn order() -> Fact {
+        let customer = HashMap::from([("tier".to_string(), FactValue::String("gold".to_string()))]);
+        let fields = HashMap::from([
+            ("customer".to_string(), FactValue::Object(customer)),
+            (
+                "lines".to_string(),
+                FactValue::Array(vec![line("A", 10, 2), line("B", 25, 1), line("C", 5, 4)]),
+            ),
+            ("price".to_string(), FactValue::Integer(999)),
+        ]);
+        Fact { id: 1, data: FactData { fields } }
+    }
+
+    fn eval(expression: &str) -> Result<FactValue> {
+        let fact = order();
+        let context =
+            EvaluationContext { current_fact: &fact, facts: &[], globals: HashMap::new() };
+        let expr = parse_expression(expression)?;
+        evaluate_to_value(&expr, &context, &FunctionRegistry::with_builtins(), None)
+    }
+
+    #[test]
+    fn test_nested_navigation() {
+        assert_eq!(
+            eval("customer.tier").unwrap(),
+            FactValue::String("gold".to_string())
+        );
+        assert_eq!(eval("lines[1].price").unwrap(), FactValue::Integer(25));
+        assert_eq!(
+            eval(r#"lines[-1]["sku"]"#).unwrap(),
+            FactValue::String("C".to_string())
+        );
+        assert_eq!(eval("customer.address.city").unwrap(), FactValue::Null);
+        assert!(eval("lines[3]").is_err());
+    }
+
+    #[test]
+    fn test_list_functions() {
+        assert_eq!(
+            eval("sum_of(lines, line => line.price * line.quantity)").unwrap(),
+            FactValue::Integer(65)
+        );
+        assert_eq!(
+            eval("map(lines, line => line.sku)").unwrap(),
+            FactValue::Array(vec![
+                FactValue::String("A".to_string()),
+                FactValue::String("B".to_string()),
+                FactValue::String("C".to_string()),
+            ])
+        );
+        assert_eq!(
+            eval("map(filter(lines, line => line.quantity > 1), line => line.sku)").unwrap(),
+            FactValue::Array(vec![
+                FactValue::String("A".to_string()),
+                FactValue::String("C".to_string()),
+            ])
+        );
+        assert_eq!(
+            eval("any(lines, line => line.price > 20)").unwrap(),
+            FactValue::Boolean(true)
+        );
+        assert_eq!(
+            eval("all(lines, line => line.price > 5)").unwrap(),
+            FactValue::Boolean(false)
+        );
+        assert_eq!(eval("sum_of([1, 2.5, 3])").unwrap(), FactValue::Float(6.5));
+        assert_eq!(eval("all([])").unwrap(), FactValue::Boolean(true));
+    }
+
+    #[test]
+    fn test_lambda_scoping() {
+        // The parameter shadows the fact's own price field
+        assert_eq!(
+            eval("sum_of(map(lines, price => 1), n => n)").unwrap(),
+            FactValue::Integer(3)
+        );
+        assert_eq!(
+            eval("sum_of(lines, line => line.price) + price").unwrap(),
+            FactValue::Integer(1039)
+        );
+        // Inner lambdas see the parameters of enclosing ones
+        assert_eq!(
+            eval("all(lines, line => any([2, 1, 4], q => q == line.quantity))").unwrap(),
+            FactValue::Boolean(true)
+        );
+
+        assert!(eval("map(lines)").is_err());
+        assert!(eval("sum_of(price, p => p)").is_err());
+        assert!(eval("max(lines, line => 1)").is_err());
+    }
+}
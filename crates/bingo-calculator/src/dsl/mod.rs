@@ -3,11 +3,13 @@
 //! This module implements a domain-specific language for expressing calculations
 //! and logic within rule actions. The DSL is designed to be:
 //! - Safe: No arbitrary code execution, sandboxed evaluation
-//! - Fast: Compiled expressions with optimized evaluation
+//! - Fast: Expressions compiled to closures with constants folded
 //! - Simple: Intuitive syntax for business users
 //! - Extensible: Plugin system for custom functions
 
 pub mod ast;
+pub mod batch;
+pub mod compiler;
 pub mod evaluator;
 pub mod functions;
 pub mod parser;
@@ -16,6 +18,7 @@ use crate::types::FactValue;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
 
 /// Calculator expression that can be evaluated against fact context
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,6 +29,16 @@ pub struct CalculatorExpression {
     pub ast: ast::Expression,
     /// Variables referenced in the expression
     pub variables: Vec<String>,
+    /// Program compiled from the AST, shared by clones of the expression
+    #[serde(skip)]
+    program: Arc<OnceLock<compiler::Program>>,
+}
+
+impl CalculatorExpression {
+    /// Compiled program for the expression, built on first use
+    pub fn program(&self) -> &compiler::Program {
+        self.program.get_or_init(|| compiler::compile(&self.ast))
+    }
 }
 
 /// Fact structure for DSL evaluation
@@ -117,7 +130,9 @@ impl Calculator {
         // Extract variables
         let variables = ast::extract_variables(&ast);
 
-        let compiled = CalculatorExpression { source: expression.to_string(), ast, variables };
+        let program = Arc::new(OnceLock::from(compiler::compile(&ast)));
+        let compiled =
+            CalculatorExpression { source: expression.to_string(), ast, variables, program };
 
         // Cache the compiled expression
         self.expression_cache.insert(expression.to_string(), compiled.clone());
@@ -131,7 +146,22 @@ impl Calculator {
         expression: &CalculatorExpression,
         context: &EvaluationContext,
     ) -> Result<CalculatorResult> {
-        evaluator::evaluate_expression(&expression.ast, context, &self.functions)
+        let value = expression.program().evaluate(context, &self.functions)?;
+        Ok(CalculatorResult::Value(value))
+    }
+
+    /// Evaluate an expression for every fact in `facts`, in order
+    ///
+    /// Arithmetic, comparison and conditional expressions over numeric and boolean
+    /// fields are evaluated a column at a time; see [`batch`] for when the batch is
+    /// evaluated fact by fact instead.
+    pub fn evaluate_batch(
+        &self,
+        expression: &CalculatorExpression,
+        facts: &[Fact],
+        globals: &HashMap<String, FactValue>,
+    ) -> Result<Vec<FactValue>> {
+        batch::evaluate_batch(&expression.ast, facts, globals, &self.functions)
     }
 
     /// Convenience method to compile and evaluate in one step
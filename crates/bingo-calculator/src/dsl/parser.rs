@@ -58,6 +58,7 @@ pub enum Token {
     Comma,
     Dot,
     Colon,
+    Arrow,
 
     // Special
     Eof,
@@ -107,6 +108,7 @@ impl fmt::Display for Token {
             Token::Comma => write!(f, ","),
             Token::Dot => write!(f, "."),
             Token::Colon => write!(f, ":"),
+            Token::Arrow => write!(f, "=>"),
             Token::Eof => write!(f, "EOF"),
         }
     }
@@ -283,6 +285,10 @@ impl Lexer {
                         self.advance();
                         self.advance();
                         Ok(Token::Equal)
+                    } else if self.peek() == Some('>') {
+                        self.advance();
+                        self.advance();
+                        Ok(Token::Arrow)
                     } else {
                         Err(anyhow!("Unexpected character '='. Did you mean '=='?"))
                     }
@@ -584,11 +590,11 @@ impl Parser {
                         let mut args = Vec::new();
 
                         if !matches!(self.current_token, Token::RightParen) {
-                            args.push(self.parse_expression()?);
+                            args.push(self.parse_argument()?);
 
                             while matches!(self.current_token, Token::Comma) {
                                 self.advance()?;
-                                args.push(self.parse_expression()?);
+                                args.push(self.parse_argument()?);
                             }
                         }
 
@@ -612,6 +618,21 @@ impl Parser {
         Ok(expr)
     }
 
+    /// Parse a function argument, which may be a lambda (item => item.price)
+    fn parse_argument(&mut self) -> Result<Expression> {
+        let argument = self.parse_expression()?;
+        if !matches!(self.current_token, Token::Arrow) {
+            return Ok(argument);
+        }
+
+        let Expression::Variable(parameter) = argument else {
+            return Err(anyhow!("Expected a parameter name before '=>'"));
+        };
+        self.advance()?; // consume '=>'
+        let body = self.parse_expression()?;
+        Ok(Expression::lambda(&parameter, body))
+    }
+
     fn parse_primary_expression(&mut self) -> Result<Expression> {
         match &self.current_token {
             Token::Integer(value) => {
@@ -981,6 +1002,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parser_nested_navigation() {
+        let expr = parse_expression("order.lines[0].price").unwrap();
+
+        let line = Expression::index(
+            Expression::field(Expression::var("order"), "lines"),
+            Expression::int(0),
+        );
+        assert_eq!(expr, Expression::field(line, "price"));
+    }
+
+    #[test]
+    fn test_parser_lambda_argument() {
+        let expr = parse_expression("filter(lines, line => line.quantity > 1)").unwrap();
+
+        match expr {
+            Expression::FunctionCall { name, args } => {
+                assert_eq!(name, "filter");
+                assert_eq!(args[0], Expression::var("lines"));
+                let quantity = Expression::field(Expression::var("line"), "quantity");
+                assert_eq!(
+                    args[1],
+                    Expression::lambda(
+                        "line",
+                        Expression::binary(
+                            quantity,
+                            BinaryOperator::GreaterThan,
+                            Expression::int(1)
+                        )
+                    )
+                );
+            }
+            _ => panic!("Expected function call"),
+        }
+
+        assert!(parse_expression("map(lines, 1 => 2)").is_err());
+        assert!(parse_expression("x => x").is_err());
+    }
+
     #[test]
     fn test_parser_nested_structures() {
         let expr = parse_expression(r#"{"users": [1, 2, 3], "active": true}"#).unwrap();
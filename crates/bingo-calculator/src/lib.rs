@@ -11,6 +11,8 @@ pub mod limit_validator;
 pub mod plugin;
 pub mod plugin_manager;
 pub mod threshold_check;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 // Re-exports for convenience
 pub use bingo_types::FactValue;
@@ -18,3 +20,5 @@ pub use calculator::Calculator;
 pub use limit_validator::LimitValidateCalculator;
 pub use plugin::CalculatorInputs;
 pub use threshold_check::ThresholdCheckCalculator;
+#[cfg(feature = "wasm")]
+pub use wasm::{WasmCalculator, WasmLimits};
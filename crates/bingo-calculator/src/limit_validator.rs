@@ -2,7 +2,14 @@ use std::collections::HashMap;
 
 use bingo_types::FactValue;
 
-use crate::plugin::{CalculationResult, CalculatorPlugin};
+use crate::built_in::decimal::{decimal_arg, uses_decimal};
+use crate::plugin::{CalculationResult, CalculatorFieldType, CalculatorPlugin, FieldSpec};
+
+const INPUT_FIELDS: &[FieldSpec] = &[
+    FieldSpec::required("value", CalculatorFieldType::Float),
+    FieldSpec::required("min", CalculatorFieldType::Float),
+    FieldSpec::required("max", CalculatorFieldType::Float),
+];
 
 pub struct LimitValidateCalculator;
 
@@ -12,6 +19,11 @@ impl CalculatorPlugin for LimitValidateCalculator {
     }
 
     fn calculate(&self, args: &HashMap<String, &FactValue>) -> CalculationResult {
+        if uses_decimal(args, &["value", "min", "max"]) {
+            let value = decimal_arg(args, "value")?;
+            let (min, max) = (decimal_arg(args, "min")?, decimal_arg(args, "max")?);
+            return Ok(FactValue::Boolean(value >= min && value <= max));
+        }
         let value = match args.get("value") {
             Some(FactValue::Float(f)) => *f,
             Some(FactValue::Integer(i)) => *i as f64,
@@ -34,4 +46,8 @@ impl CalculatorPlugin for LimitValidateCalculator {
             Ok(FactValue::Boolean(false))
         }
     }
+
+    fn input_fields(&self) -> &[FieldSpec] {
+        INPUT_FIELDS
+    }
 }
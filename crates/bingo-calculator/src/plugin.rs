@@ -1,5 +1,6 @@
 use bingo_types::FactValue;
 use std::collections::HashMap;
+use std::fmt;
 
 pub type CalculationResult = Result<FactValue, String>;
 
@@ -10,6 +11,109 @@ pub struct CalculatorInputs {
     pub fields: HashMap<String, FactValue>,
 }
 
+/// Type of value a calculator input accepts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalculatorFieldType {
+    /// An integer
+    Integer,
+    /// Any number: an integer, a float or a decimal
+    Float,
+    /// A string
+    String,
+    /// A boolean
+    Boolean,
+    /// A date, or a string holding an RFC 3339 date and time
+    DateTime,
+    /// An array
+    Array,
+    /// An object
+    Object,
+}
+
+impl CalculatorFieldType {
+    /// Whether `value` is of this type
+    pub fn accepts(self, value: &FactValue) -> bool {
+        match self {
+            Self::Integer => matches!(value, FactValue::Integer(_)),
+            Self::Float => {
+                matches!(
+                    value,
+                    FactValue::Integer(_) | FactValue::Float(_) | FactValue::Decimal(_)
+                )
+            }
+            Self::String => matches!(value, FactValue::String(_)),
+            Self::Boolean => matches!(value, FactValue::Boolean(_)),
+            Self::DateTime => match value {
+                FactValue::Date(_) => true,
+                FactValue::String(s) => chrono::DateTime::parse_from_rfc3339(s).is_ok(),
+                _ => false,
+            },
+            Self::Array => matches!(value, FactValue::Array(_)),
+            Self::Object => matches!(value, FactValue::Object(_)),
+        }
+    }
+}
+
+impl fmt::Display for CalculatorFieldType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Integer => "integer",
+            Self::Float => "number",
+            Self::String => "string",
+            Self::Boolean => "boolean",
+            Self::DateTime => "datetime",
+            Self::Array => "array",
+            Self::Object => "object",
+        })
+    }
+}
+
+/// An input a calculator reads from its arguments
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldSpec {
+    /// Argument name
+    pub name: &'static str,
+    /// Type the argument must have when present
+    pub field_type: CalculatorFieldType,
+    /// Whether the calculation fails without the argument
+    pub required: bool,
+}
+
+impl FieldSpec {
+    /// Input the calculator cannot do without
+    pub const fn required(name: &'static str, field_type: CalculatorFieldType) -> Self {
+        Self { name, field_type, required: true }
+    }
+
+    /// Input the calculator falls back to a default for
+    pub const fn optional(name: &'static str, field_type: CalculatorFieldType) -> Self {
+        Self { name, field_type, required: false }
+    }
+}
+
+/// Check `args` against a calculator's inputs: every required input must be present,
+/// and every input present must have its declared type
+///
+/// Arguments the calculator does not declare are left alone.
+pub fn validate_inputs(
+    fields: &[FieldSpec],
+    args: &HashMap<String, &FactValue>,
+) -> Result<(), String> {
+    for field in fields {
+        match args.get(field.name) {
+            None if field.required => return Err(format!("Missing argument '{}'", field.name)),
+            Some(value) if !field.field_type.accepts(value) => {
+                return Err(format!(
+                    "Invalid argument '{}': expected {}",
+                    field.name, field.field_type
+                ));
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
 /// A trait for calculator plugins.
 pub trait CalculatorPlugin: Send + Sync {
     /// The name of the calculator.
@@ -17,4 +121,16 @@ pub trait CalculatorPlugin: Send + Sync {
 
     /// Performs the calculation.
     fn calculate(&self, args: &HashMap<String, &FactValue>) -> CalculationResult;
+
+    /// Inputs the calculator reads, checked by [`validate_inputs`] before it is called
+    /// through [`Calculator`](crate::Calculator); empty when it does not declare them
+    fn input_fields(&self) -> &[FieldSpec] {
+        &[]
+    }
+
+    /// Whether the result depends on the arguments alone, so callers may reuse it for
+    /// the same arguments; calculators reading clocks or external state return `false`
+    fn is_deterministic(&self) -> bool {
+        true
+    }
 }
@@ -23,4 +23,8 @@ impl PluginManager {
     pub fn get(&self, name: &str) -> Option<&dyn CalculatorPlugin> {
         self.plugins.get(name).map(|p| p.as_ref())
     }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.plugins.contains_key(name)
+    }
 }
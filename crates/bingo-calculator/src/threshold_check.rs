@@ -2,6 +2,7 @@ use std::collections::HashMap;
 
 use bingo_types::FactValue;
 
+use crate::built_in::decimal::{decimal_arg, uses_decimal};
 use crate::plugin::{CalculationResult, CalculatorPlugin};
 
 pub struct ThresholdCheckCalculator;
@@ -12,6 +13,11 @@ impl CalculatorPlugin for ThresholdCheckCalculator {
     }
 
     fn calculate(&self, args: &HashMap<String, &FactValue>) -> CalculationResult {
+        if uses_decimal(args, &["value", "threshold"]) {
+            let value = decimal_arg(args, "value")?;
+            let threshold = decimal_arg(args, "threshold")?;
+            return Ok(FactValue::Boolean(value > threshold));
+        }
         let value = match args.get("value") {
             Some(FactValue::Float(f)) => *f,
             Some(FactValue::Integer(i)) => *i as f64,
@@ -0,0 +1,199 @@
+//! Sandboxed calculators compiled to WebAssembly
+//!
+//! A `WasmCalculator` runs a user-supplied WASM module as a calculator plugin, so
+//! tenant-provided business logic can be called from rule actions without trusting
+//! it. Every call gets a fresh instance with no host imports, a fuel budget bounding
+//! the instructions it may execute and a cap on its linear memory.
+//!
+//! ## Module ABI
+//!
+//! The module must export:
+//!
+//! - `memory`: Its linear memory
+//! - `alloc(size: i32) -> i32`: Reserve `size` bytes and return their offset
+//! - `calculate(ptr: i32, len: i32) -> i64`: Compute a result from the input at
+//!   `ptr`, returning the output's offset in the high 32 bits and its length in the
+//!   low 32 bits
+//!
+//! The input is a UTF-8 JSON object mapping argument names to values. The output is
+//! a UTF-8 JSON object, either `{"ok": <value>}` or `{"error": "<message>"}`.
+
+use crate::plugin::{CalculationResult, CalculatorPlugin};
+use anyhow::{Context, Result, anyhow, bail};
+use bingo_types::FactValue;
+use std::collections::HashMap;
+use std::path::Path;
+use wasmtime::{
+    Config, Engine, ExternType, InstancePre, Linker, Module, Store, StoreLimits,
+    StoreLimitsBuilder, Trap,
+};
+
+/// Resource limits applied to every call of a WASM calculator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WasmLimits {
+    /// Fuel available to a call; roughly one unit per WASM instruction
+    pub fuel: u64,
+    /// Largest linear memory a call may grow to, in bytes
+    pub memory_bytes: usize,
+    /// Largest output a call may return, in bytes
+    pub max_output_bytes: usize,
+}
+
+impl Default for WasmLimits {
+    fn default() -> Self {
+        Self { fuel: 10_000_000, memory_bytes: 16 * 1024 * 1024, max_output_bytes: 1024 * 1024 }
+    }
+}
+
+/// Calculator plugin executing a sandboxed WASM module
+pub struct WasmCalculator {
+    name: String,
+    engine: Engine,
+    instance_pre: InstancePre<StoreLimits>,
+    limits: WasmLimits,
+}
+
+impl std::fmt::Debug for WasmCalculator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WasmCalculator")
+            .field("name", &self.name)
+            .field("limits", &self.limits)
+            .finish()
+    }
+}
+
+impl WasmCalculator {
+    /// Compile a calculator from WASM binary or text
+    ///
+    /// Fails if the module does not compile, imports anything or does not export the
+    /// calculator ABI.
+    pub fn new(
+        name: impl Into<String>,
+        wasm: impl AsRef<[u8]>,
+        limits: WasmLimits,
+    ) -> Result<Self> {
+        let name = name.into();
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config)?;
+        let module = Module::new(&engine, wasm)
+            .with_context(|| format!("WASM calculator '{name}' failed to compile"))?;
+
+        if let Some(import) = module.imports().next() {
+            bail!(
+                "WASM calculator '{name}' imports '{}::{}', but calculators get no host imports",
+                import.module(),
+                import.name()
+            );
+        }
+        for (export, valid) in [
+            (
+                "memory",
+                matches!(module.get_export("memory"), Some(ExternType::Memory(_))),
+            ),
+            (
+                "alloc",
+                matches!(module.get_export("alloc"), Some(ExternType::Func(_))),
+            ),
+            (
+                "calculate",
+                matches!(module.get_export("calculate"), Some(ExternType::Func(_))),
+            ),
+        ] {
+            if !valid {
+                bail!("WASM calculator '{name}' does not export '{export}'");
+            }
+        }
+
+        let linker = Linker::new(&engine);
+        let instance_pre = linker.instantiate_pre(&module)?;
+        Ok(Self { name, engine, instance_pre, limits })
+    }
+
+    /// Compile a calculator from a `.wasm` or `.wat` file
+    pub fn from_file(
+        name: impl Into<String>,
+        path: impl AsRef<Path>,
+        limits: WasmLimits,
+    ) -> Result<Self> {
+        let path = path.as_ref();
+        let wasm = std::fs::read(path)
+            .with_context(|| format!("failed to read WASM calculator {}", path.display()))?;
+        Self::new(name, wasm, limits)
+    }
+
+    pub fn limits(&self) -> WasmLimits {
+        self.limits
+    }
+
+    /// Run the module on a fresh instance and return its raw output
+    fn invoke(&self, input: &[u8]) -> Result<Vec<u8>> {
+        let store_limits = StoreLimitsBuilder::new()
+            .memory_size(self.limits.memory_bytes)
+            .instances(1)
+            .memories(1)
+            .trap_on_grow_failure(true)
+            .build();
+        let mut store = Store::new(&self.engine, store_limits);
+        store.limiter(|limits| limits);
+        store.set_fuel(self.limits.fuel)?;
+
+        let instance = self.instance_pre.instantiate(&mut store)?;
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or_else(|| anyhow!("module does not export 'memory'"))?;
+        let alloc = instance.get_typed_func::<i32, i32>(&mut store, "alloc")?;
+        let calculate = instance.get_typed_func::<(i32, i32), i64>(&mut store, "calculate")?;
+
+        let len = i32::try_from(input.len()).context("input too large")?;
+        let ptr = alloc.call(&mut store, len)?;
+        memory.write(&mut store, ptr as u32 as usize, input)?;
+
+        let packed = calculate.call(&mut store, (ptr, len))? as u64;
+        let (out_ptr, out_len) = ((packed >> 32) as usize, (packed & 0xffff_ffff) as usize);
+        if out_len > self.limits.max_output_bytes {
+            bail!(
+                "output of {out_len} bytes exceeds the {} byte limit",
+                self.limits.max_output_bytes
+            );
+        }
+        let mut output = vec![0; out_len];
+        memory.read(&store, out_ptr, &mut output)?;
+        Ok(output)
+    }
+}
+
+impl CalculatorPlugin for WasmCalculator {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn calculate(&self, args: &HashMap<String, &FactValue>) -> CalculationResult {
+        let input: serde_json::Map<String, serde_json::Value> = args
+            .iter()
+            .map(|(name, value)| (name.clone(), serde_json::Value::from(*value)))
+            .collect();
+        let input = serde_json::to_vec(&input).map_err(|e| e.to_string())?;
+
+        let output = self.invoke(&input).map_err(|error| {
+            if let Some(Trap::OutOfFuel) = error.downcast_ref::<Trap>() {
+                format!("WASM calculator '{}' exceeded its CPU limit", self.name)
+            } else {
+                format!("WASM calculator '{}' failed: {error:#}", self.name)
+            }
+        })?;
+
+        let output: serde_json::Value = serde_json::from_slice(&output)
+            .map_err(|e| format!("WASM calculator '{}' returned invalid JSON: {e}", self.name))?;
+        if let Some(value) = output.get("ok") {
+            FactValue::try_from(value).map_err(|e| e.to_string())
+        } else if let Some(message) = output.get("error") {
+            Err(message.as_str().map_or_else(|| message.to_string(), str::to_string))
+        } else {
+            Err(format!(
+                "WASM calculator '{}' returned neither 'ok' nor 'error'",
+                self.name
+            ))
+        }
+    }
+}
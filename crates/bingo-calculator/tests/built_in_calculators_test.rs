@@ -3,11 +3,15 @@ use std::collections::HashMap;
 use bingo_calculator::FactValue;
 use bingo_calculator::built_in::add::AddCalculator;
 use bingo_calculator::built_in::multiply::MultiplyCalculator;
+use bingo_calculator::built_in::overtime::OvertimeCalculator;
 use bingo_calculator::built_in::percentage_add::PercentageAddCalculator;
 use bingo_calculator::built_in::percentage_deduct::PercentageDeductCalculator;
+use bingo_calculator::built_in::pro_rata::ProRataCalculator;
 use bingo_calculator::built_in::proportional_allocator::ProportionalAllocatorCalculator;
+use bingo_calculator::built_in::tiered_rate::TieredRateCalculator;
 use bingo_calculator::built_in::time_between_datetime::TimeBetweenDatetimeCalculator;
 use bingo_calculator::plugin::CalculatorPlugin;
+use bingo_calculator::{Calculator, plugin::CalculatorFieldType};
 
 fn calculate_with<C: CalculatorPlugin>(calculator: C, inputs: &[(&str, FactValue)]) -> String {
     let var_refs: HashMap<String, &FactValue> =
@@ -302,3 +306,332 @@ fn limit_validate_calculator_works() {
     );
     assert_eq!(result_fail, "false");
 }
+
+fn decimal(value: &str) -> FactValue {
+    FactValue::Decimal(value.parse().unwrap())
+}
+
+#[test]
+fn decimal_inputs_use_exact_arithmetic() {
+    let result = calculate_with(
+        AddCalculator,
+        &[("a", decimal("0.10")), ("b", decimal("0.20"))],
+    );
+    assert_eq!(result, "0.30");
+
+    // Integer and float operands join the decimal arithmetic
+    let result = calculate_with(
+        MultiplyCalculator,
+        &[("a", decimal("19.99")), ("b", FactValue::Integer(3))],
+    );
+    assert_eq!(result, "59.97");
+
+    let result = calculate_with(
+        PercentageDeductCalculator,
+        &[("amount", decimal("1234.56")), ("percentage", FactValue::Float(0.2))],
+    );
+    assert_eq!(result, "987.648");
+}
+
+#[test]
+fn decimal_results_are_rounded_on_request() {
+    let allocation = |rounding: &str| {
+        calculate_with(
+            ProportionalAllocatorCalculator,
+            &[
+                ("total_amount", decimal("100.00")),
+                ("individual_value", FactValue::Integer(1)),
+                ("total_value", FactValue::Integer(3)),
+                ("scale", FactValue::Integer(2)),
+                ("rounding", FactValue::String(rounding.to_string())),
+            ],
+        )
+    };
+    assert_eq!(allocation("half_even"), "33.33");
+    assert_eq!(allocation("ceiling"), "33.34");
+    assert_eq!(allocation("floor"), "33.33");
+
+    // The scale defaults to cents and the mode to banker's rounding
+    let result = calculate_with(
+        MultiplyCalculator,
+        &[
+            ("a", decimal("0.125")),
+            ("b", FactValue::Integer(1)),
+            ("rounding", FactValue::String("half_up".to_string())),
+        ],
+    );
+    assert_eq!(result, "0.13");
+    let result = calculate_with(
+        MultiplyCalculator,
+        &[
+            ("a", decimal("0.125")),
+            ("b", FactValue::Integer(1)),
+            ("scale", FactValue::Integer(2)),
+        ],
+    );
+    assert_eq!(result, "0.12");
+}
+
+#[test]
+fn invalid_rounding_arguments_are_rejected() {
+    let a = decimal("1.005");
+    let b = FactValue::Integer(1);
+    let mode = FactValue::String("nearest".to_string());
+    let args: HashMap<String, &FactValue> =
+        [("a".to_string(), &a), ("b".to_string(), &b), ("rounding".to_string(), &mode)].into();
+    let error = AddCalculator.calculate(&args).unwrap_err();
+    assert!(error.contains("Unknown rounding mode 'nearest'"), "{error}");
+}
+
+#[test]
+fn decimal_comparisons_are_exact() {
+    use bingo_calculator::{LimitValidateCalculator, ThresholdCheckCalculator};
+    let result = calculate_with(
+        ThresholdCheckCalculator,
+        &[("value", decimal("1000.01")), ("threshold", FactValue::Integer(1000))],
+    );
+    assert_eq!(result, "true");
+
+    let result = calculate_with(
+        LimitValidateCalculator,
+        &[
+            ("value", decimal("100.00")),
+            ("min", decimal("0")),
+            ("max", FactValue::Integer(100)),
+        ],
+    );
+    assert_eq!(result, "true");
+}
+
+fn calculate_value<C: CalculatorPlugin>(
+    calculator: C,
+    inputs: &[(&str, FactValue)],
+) -> Result<FactValue, String> {
+    let var_refs: HashMap<String, &FactValue> =
+        inputs.iter().map(|(k, v)| (k.to_string(), v)).collect();
+    calculator.calculate(&var_refs)
+}
+
+fn numbers(values: &[i64]) -> FactValue {
+    FactValue::Array(values.iter().copied().map(FactValue::Integer).collect())
+}
+
+fn field(value: &FactValue, name: &str) -> FactValue {
+    match value {
+        FactValue::Object(fields) => fields[name].clone(),
+        other => panic!("expected object, got {other:?}"),
+    }
+}
+
+#[test]
+fn overtime_calculator_applies_daily_then_weekly_thresholds() {
+    let result = calculate_value(
+        OvertimeCalculator,
+        &[
+            ("hours", numbers(&[10, 13, 8, 8, 8, 6])),
+            ("hourly_rate", FactValue::Integer(20)),
+            ("daily_threshold", FactValue::Integer(8)),
+            ("double_time_threshold", FactValue::Integer(12)),
+        ],
+    )
+    .unwrap();
+    // 46 regular hours after the daily split, 6 of them past the weekly threshold
+    assert_eq!(field(&result, "regular_hours"), FactValue::Float(40.0));
+    assert_eq!(field(&result, "overtime_hours"), FactValue::Float(12.0));
+    assert_eq!(field(&result, "double_time_hours"), FactValue::Float(1.0));
+    assert_eq!(field(&result, "gross_pay"), FactValue::Float(1200.0));
+
+    let result = calculate_value(
+        OvertimeCalculator,
+        &[
+            ("hours", numbers(&[9, 9, 9, 9, 9])),
+            ("hourly_rate", decimal("18.50")),
+            ("scale", FactValue::Integer(2)),
+        ],
+    )
+    .unwrap();
+    assert_eq!(field(&result, "overtime_hours").as_string(), "5.00");
+    assert_eq!(field(&result, "gross_pay").as_string(), "878.75");
+
+    let error = calculate_value(
+        OvertimeCalculator,
+        &[("hours", numbers(&[8, -1])), ("hourly_rate", FactValue::Integer(20))],
+    )
+    .unwrap_err();
+    assert!(error.contains("must not be negative"), "{error}");
+}
+
+#[test]
+fn tiered_rate_calculator_supports_graduated_and_flat_modes() {
+    let tier = |up_to: Option<i64>, rate: f64| {
+        let mut fields = HashMap::from([("rate".to_string(), FactValue::Float(rate))]);
+        if let Some(up_to) = up_to {
+            fields.insert("up_to".to_string(), FactValue::Integer(up_to));
+        }
+        FactValue::Object(fields)
+    };
+    let tiers = FactValue::Array(vec![
+        tier(Some(10_000), 0.1),
+        tier(Some(50_000), 0.2),
+        tier(None, 0.3),
+    ]);
+    let charge = |mode: &str| {
+        calculate_value(
+            TieredRateCalculator,
+            &[
+                ("amount", FactValue::Integer(60_000)),
+                ("tiers", tiers.clone()),
+                ("mode", FactValue::String(mode.to_string())),
+            ],
+        )
+    };
+    assert_eq!(charge("graduated").unwrap(), FactValue::Float(12_000.0));
+    assert_eq!(charge("flat").unwrap(), FactValue::Float(18_000.0));
+
+    // A bounded last tier caps the amount
+    let bounded = FactValue::Array(vec![tier(Some(10_000), 0.1), tier(Some(50_000), 0.2)]);
+    let error = calculate_value(
+        TieredRateCalculator,
+        &[("amount", FactValue::Integer(60_000)), ("tiers", bounded)],
+    )
+    .unwrap_err();
+    assert!(error.contains("exceeds the last tier"), "{error}");
+
+    let unordered = FactValue::Array(vec![tier(Some(50_000), 0.2), tier(Some(10_000), 0.1)]);
+    let error = calculate_value(
+        TieredRateCalculator,
+        &[("amount", FactValue::Integer(100)), ("tiers", unordered)],
+    )
+    .unwrap_err();
+    assert!(error.contains("must be above the previous tier"), "{error}");
+}
+
+#[test]
+fn pro_rata_calculator_counts_covered_days() {
+    let date = |value: &str| FactValue::String(format!("{value}T00:00:00Z"));
+    let prorate = |amount: FactValue, basis: &str| {
+        calculate_value(
+            ProRataCalculator,
+            &[
+                ("amount", amount),
+                ("period_start", date("2024-06-01")),
+                ("period_end", date("2024-06-30")),
+                ("start", date("2024-06-11")),
+                ("basis", FactValue::String(basis.to_string())),
+                ("scale", FactValue::Integer(2)),
+            ],
+        )
+        .unwrap()
+    };
+    // 20 of June's 30 days, and 14 of its 20 weekdays
+    assert_eq!(
+        prorate(FactValue::Integer(3000), "calendar_days"),
+        FactValue::Float(2000.0)
+    );
+    assert_eq!(
+        prorate(FactValue::Integer(3000), "business_days"),
+        FactValue::Float(2100.0)
+    );
+    assert_eq!(
+        prorate(decimal("1000.00"), "calendar_days").as_string(),
+        "666.67"
+    );
+
+    // A range ending before the period starts covers none of it
+    let result = calculate_value(
+        ProRataCalculator,
+        &[
+            ("amount", FactValue::Integer(3000)),
+            ("period_start", date("2024-06-01")),
+            ("period_end", date("2024-06-30")),
+            ("end", date("2024-05-31")),
+        ],
+    )
+    .unwrap();
+    assert_eq!(result, FactValue::Float(0.0));
+}
+
+#[test]
+fn calculator_checks_declared_input_fields() {
+    let calculator = Calculator::new();
+    let fields = calculator.input_fields("pro_rata").unwrap();
+    let period_start = fields.iter().find(|field| field.name == "period_start").unwrap();
+    assert_eq!(period_start.field_type, CalculatorFieldType::DateTime);
+    assert!(period_start.required);
+    assert!(calculator.input_fields("missing").is_none());
+
+    let amount = FactValue::Integer(100);
+    let period_start = FactValue::String("yesterday".to_string());
+    let args: HashMap<String, &FactValue> =
+        [("amount".to_string(), &amount), ("period_start".to_string(), &period_start)].into();
+    let error = calculator.calculate("pro_rata", &args).unwrap_err();
+    assert_eq!(error, "Invalid argument 'period_start': expected datetime");
+
+    let args: HashMap<String, &FactValue> = [("amount".to_string(), &amount)].into();
+    let error = calculator.calculate("tiered_rate", &args).unwrap_err();
+    assert_eq!(error, "Missing argument 'tiers'");
+}
+
+#[test]
+fn rate_lookup_resolves_effective_dated_rates() {
+    use bingo_calculator::built_in::rate_lookup::EffectiveDatedRates;
+    use chrono::NaiveDate;
+    use std::sync::Arc;
+
+    let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+    let rates = EffectiveDatedRates::new()
+        .with_rate("US-CA", date(2023, 1, 1), decimal("0.0725"))
+        .with_rate("US-CA", date(2025, 1, 1), decimal("0.0750"))
+        .with_rate("US-NV", date(2023, 1, 1), decimal("0.0685"));
+    let mut calculator = Calculator::new();
+    calculator.register_rate_table("sales_tax", Arc::new(rates));
+
+    let lookup = |jurisdiction: &str, on: &str, amount: Option<FactValue>| {
+        let table = FactValue::String("sales_tax".to_string());
+        let jurisdiction = FactValue::String(jurisdiction.to_string());
+        let on = FactValue::String(format!("{on}T12:00:00Z"));
+        let mut args: HashMap<String, &FactValue> = [
+            ("table".to_string(), &table),
+            ("jurisdiction".to_string(), &jurisdiction),
+            ("date".to_string(), &on),
+        ]
+        .into();
+        if let Some(amount) = &amount {
+            args.insert("amount".to_string(), amount);
+        }
+        calculator.calculate("rate_lookup", &args)
+    };
+    assert_eq!(
+        lookup("US-CA", "2024-12-31", None).unwrap().as_string(),
+        "0.0725"
+    );
+    assert_eq!(
+        lookup("US-CA", "2025-01-01", None).unwrap().as_string(),
+        "0.0750"
+    );
+    assert_eq!(
+        lookup("US-CA", "2025-06-30", Some(FactValue::Integer(200)))
+            .unwrap()
+            .as_string(),
+        "15.0000"
+    );
+
+    let error = lookup("US-CA", "2022-12-31", None).unwrap_err();
+    assert_eq!(
+        error,
+        "No rate in table 'sales_tax' for jurisdiction 'US-CA' on 2022-12-31"
+    );
+    assert!(lookup("US-TX", "2024-01-01", None).is_err());
+
+    let table = FactValue::String("vat".to_string());
+    let jurisdiction = FactValue::String("UK".to_string());
+    let on = FactValue::String("2024-01-01T00:00:00Z".to_string());
+    let args: HashMap<String, &FactValue> = [
+        ("table".to_string(), &table),
+        ("jurisdiction".to_string(), &jurisdiction),
+        ("date".to_string(), &on),
+    ]
+    .into();
+    let error = calculator.calculate("rate_lookup", &args).unwrap_err();
+    assert_eq!(error, "Unknown rate table 'vat'");
+}
@@ -0,0 +1,182 @@
+//! WASM Calculator Test
+//!
+//! Validates that sandboxed WASM calculators receive their arguments as JSON, return
+//! values and errors through the calculator interface and are stopped by their CPU
+//! and memory limits.
+
+#![cfg(feature = "wasm")]
+
+use bingo_calculator::plugin::CalculatorPlugin;
+use bingo_calculator::{Calculator, FactValue, WasmCalculator, WasmLimits};
+use std::collections::HashMap;
+
+/// Bump allocator shared by the test modules
+const ALLOC: &str = r#"
+  (global $next (mut i32) (i32.const 4096))
+  (func $alloc (export "alloc") (param $size i32) (result i32)
+    (local $ptr i32)
+    (local.set $ptr (global.get $next))
+    (global.set $next (i32.add (global.get $next) (local.get $size)))
+    (local.get $ptr))
+"#;
+
+/// Returns `{"ok": <input>}`
+fn echo_module() -> String {
+    format!(
+        r#"(module
+  (memory (export "memory") 1)
+  (data (i32.const 0) "{{\"ok\":")
+  {ALLOC}
+  (func (export "calculate") (param $ptr i32) (param $len i32) (result i64)
+    (local $out i32)
+    (local.set $out (call $alloc (i32.add (local.get $len) (i32.const 7))))
+    (memory.copy (local.get $out) (i32.const 0) (i32.const 6))
+    (memory.copy (i32.add (local.get $out) (i32.const 6)) (local.get $ptr) (local.get $len))
+    (i32.store8
+      (i32.add (i32.add (local.get $out) (i32.const 6)) (local.get $len))
+      (i32.const 125))
+    (i64.or
+      (i64.shl (i64.extend_i32_u (local.get $out)) (i64.const 32))
+      (i64.extend_i32_u (i32.add (local.get $len) (i32.const 7))))))"#
+    )
+}
+
+/// Always returns the given JSON output
+fn constant_module(output: &str) -> String {
+    format!(
+        r#"(module
+  (memory (export "memory") 1)
+  (data (i32.const 0) "{}")
+  {ALLOC}
+  (func (export "calculate") (param i32 i32) (result i64)
+    (i64.const {})))"#,
+        output.replace('"', "\\\""),
+        output.len()
+    )
+}
+
+/// Runs `body` as the calculation, returning nothing
+fn body_module(body: &str) -> String {
+    format!(
+        r#"(module
+  (memory (export "memory") 1)
+  {ALLOC}
+  (func (export "calculate") (param i32 i32) (result i64)
+    {body}
+    (i64.const 0)))"#
+    )
+}
+
+fn calculate(calculator: &WasmCalculator, args: &[(&str, FactValue)]) -> Result<FactValue, String> {
+    let args: HashMap<String, &FactValue> =
+        args.iter().map(|(name, value)| (name.to_string(), value)).collect();
+    calculator.calculate(&args)
+}
+
+#[test]
+fn test_arguments_round_trip_as_json() {
+    let calculator = WasmCalculator::new("echo", echo_module(), WasmLimits::default()).unwrap();
+    let result = calculate(
+        &calculator,
+        &[
+            ("amount", FactValue::Float(12.5)),
+            ("currency", FactValue::String("EUR".into())),
+        ],
+    )
+    .unwrap();
+
+    let FactValue::Object(fields) = result else {
+        panic!("expected an object, got {result:?}");
+    };
+    assert_eq!(fields.get("amount"), Some(&FactValue::Float(12.5)));
+    assert_eq!(
+        fields.get("currency"),
+        Some(&FactValue::String("EUR".into()))
+    );
+}
+
+#[test]
+fn test_results_and_errors() {
+    let answer = WasmCalculator::new(
+        "answer",
+        constant_module(r#"{"ok":42}"#),
+        WasmLimits::default(),
+    )
+    .unwrap();
+    assert_eq!(calculate(&answer, &[]), Ok(FactValue::Integer(42)));
+
+    let rejecting = WasmCalculator::new(
+        "rejecting",
+        constant_module(r#"{"error":"amount must be positive"}"#),
+        WasmLimits::default(),
+    )
+    .unwrap();
+    assert_eq!(
+        calculate(&rejecting, &[]),
+        Err("amount must be positive".to_string())
+    );
+
+    let garbled = WasmCalculator::new(
+        "garbled",
+        constant_module("not json"),
+        WasmLimits::default(),
+    )
+    .unwrap();
+    assert!(calculate(&garbled, &[]).unwrap_err().contains("invalid JSON"));
+}
+
+#[test]
+fn test_limits_stop_runaway_modules() {
+    let limits = WasmLimits { fuel: 100_000, memory_bytes: 1024 * 1024, ..Default::default() };
+
+    let spinning =
+        WasmCalculator::new("spinning", body_module("(loop $spin (br $spin))"), limits).unwrap();
+    let error = calculate(&spinning, &[]).unwrap_err();
+    assert!(error.contains("exceeded its CPU limit"), "{error}");
+
+    // 64 pages are 4 MiB, beyond the 1 MiB limit
+    let hungry = WasmCalculator::new(
+        "hungry",
+        body_module("(drop (memory.grow (i32.const 64)))"),
+        limits,
+    )
+    .unwrap();
+    assert!(calculate(&hungry, &[]).is_err());
+
+    let small_output = WasmLimits { max_output_bytes: 4, ..limits };
+    let chatty =
+        WasmCalculator::new("chatty", constant_module(r#"{"ok":42}"#), small_output).unwrap();
+    assert!(calculate(&chatty, &[]).unwrap_err().contains("byte limit"));
+}
+
+#[test]
+fn test_modules_must_match_the_abi() {
+    let importing = r#"(module
+  (import "env" "now" (func))
+  (memory (export "memory") 1))"#;
+    let error = WasmCalculator::new("importing", importing, WasmLimits::default()).unwrap_err();
+    assert!(error.to_string().contains("imports 'env::now'"));
+
+    let incomplete = r#"(module (memory (export "memory") 1))"#;
+    let error = WasmCalculator::new("incomplete", incomplete, WasmLimits::default()).unwrap_err();
+    assert!(error.to_string().contains("does not export 'alloc'"));
+}
+
+#[test]
+fn test_registered_with_calculator() {
+    let mut calculator = Calculator::new();
+    calculator.register(Box::new(
+        WasmCalculator::new(
+            "answer",
+            constant_module(r#"{"ok":42}"#),
+            WasmLimits::default(),
+        )
+        .unwrap(),
+    ));
+
+    assert_eq!(
+        calculator.calculate("answer", &HashMap::new()),
+        Ok(FactValue::Integer(42))
+    );
+    assert!(calculator.calculate("add", &HashMap::new()).is_err());
+}
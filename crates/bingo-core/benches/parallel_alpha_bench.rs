@@ -0,0 +1,104 @@
+//! Parallel alpha evaluation scaling
+//!
+//! Processes a batch against a ruleset dominated by alpha tests, range and substring
+//! conditions that rarely match, with the alpha pool at 1 to 16 threads. On an 8+ core
+//! machine throughput should scale with the thread count up to the core count.
+
+use bingo_core::{
+    Action, ActionType, BingoEngine, Condition, Fact, FactData, FactValue, Operator,
+    ParallelAlphaConfig, Rule, RuleMetadata,
+};
+use criterion::{BenchmarkId, Criterion, Throughput, black_box, criterion_group, criterion_main};
+use std::collections::HashMap;
+use std::time::Duration;
+
+const RULE_COUNT: u64 = 400;
+const FACT_COUNT: usize = 20_000;
+
+fn flag(field: &str) -> Vec<Action> {
+    vec![Action {
+        action_type: ActionType::SetField {
+            field: field.to_string(),
+            value: FactValue::Boolean(true),
+        },
+    }]
+}
+
+fn generate_rules() -> Vec<Rule> {
+    (0..RULE_COUNT)
+        .map(|i| {
+            let condition = if i % 2 == 0 {
+                Condition::Simple {
+                    field: "amount".to_string(),
+                    operator: Operator::GreaterThan,
+                    value: FactValue::Integer(990 + i as i64),
+                }
+            } else {
+                Condition::Simple {
+                    field: "description".to_string(),
+                    operator: Operator::Contains,
+                    value: FactValue::String(format!("code-{i}-")),
+                }
+            };
+            Rule {
+                id: i + 1,
+                name: format!("Alpha rule {i}"),
+                conditions: vec![condition],
+                actions: flag(&format!("flag_{i}")),
+                metadata: RuleMetadata::default(),
+            }
+        })
+        .collect()
+}
+
+fn generate_facts(count: usize) -> Vec<Fact> {
+    (0..count)
+        .map(|i| {
+            let fields = HashMap::from([
+                ("amount".to_string(), FactValue::Integer((i % 1000) as i64)),
+                (
+                    "description".to_string(),
+                    FactValue::String(format!(
+                        "order {i} shipped to warehouse {} with code-{}-x",
+                        i % 37,
+                        i % 997
+                    )),
+                ),
+            ]);
+            Fact::new(i as u64, FactData { fields })
+        })
+        .collect()
+}
+
+fn bench_parallel_alpha_scaling(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parallel_alpha_scaling");
+    group.measurement_time(Duration::from_secs(20));
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(FACT_COUNT as u64));
+
+    for threads in [0, 1, 2, 4, 8, 16] {
+        let config =
+            (threads > 0).then_some(ParallelAlphaConfig { threads, min_batch_size: 1_000 });
+        let label = if threads == 0 {
+            "sequential".to_string()
+        } else {
+            threads.to_string()
+        };
+        group.bench_with_input(BenchmarkId::new("threads", label), &config, |b, config| {
+            b.iter_batched(
+                || {
+                    let engine = BingoEngine::new().unwrap();
+                    engine.add_rules(generate_rules()).unwrap();
+                    engine.set_parallel_alpha(*config).unwrap();
+                    (generate_facts(FACT_COUNT), engine)
+                },
+                |(facts, engine)| black_box(engine.process_facts(facts).unwrap()),
+                criterion::BatchSize::LargeInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_parallel_alpha_scaling);
+criterion_main!(benches);
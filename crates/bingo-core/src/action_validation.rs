@@ -0,0 +1,333 @@
+//! Validation of rule actions when rules are added
+//!
+//! A rule calling a calculator that was never registered, writing a value of the wrong
+//! type to a field, or evaluating a formula that cannot be parsed only fails once the
+//! rule fires, and then once per firing. `validate_actions` checks every action of a
+//! rule up front against the engine's calculators and the field types registered in
+//! its [`FieldSchema`], and collects all the problems it finds into one
+//! [`ActionValidationReport`].
+//!
+//! Fields without a registered type accept any value, and a null value is accepted
+//! for any field.
+
+use crate::rete_nodes::check_formula_expression;
+use crate::types::{ActionType, FactValue, FieldType, Rule, RuleId};
+use bingo_calculator::calculator::Calculator;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Types of fact fields, keyed by field name
+pub type FieldSchema = BTreeMap<String, FieldType>;
+
+/// What adding a rule whose actions fail validation does
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionValidationPolicy {
+    /// Add the rule without validating its actions
+    Off,
+    /// Add the rule and log its problems
+    #[default]
+    Warn,
+    /// Refuse the rule, returning every problem found
+    Reject,
+}
+
+/// A problem with one action of a rule
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ActionProblem {
+    /// A `CallCalculator` action names a calculator that is not registered
+    UnknownCalculator { action_index: usize, calculator_name: String },
+    /// An action writes a value the field's registered type does not accept
+    FieldTypeConflict { action_index: usize, field: String, expected: String, actual: String },
+    /// A `Formula` action's expression cannot be evaluated
+    InvalidExpression { action_index: usize, expression: String, reason: String },
+}
+
+impl ActionProblem {
+    /// Position of the offending action in the rule's actions
+    pub fn action_index(&self) -> usize {
+        match self {
+            Self::UnknownCalculator { action_index, .. }
+            | Self::FieldTypeConflict { action_index, .. }
+            | Self::InvalidExpression { action_index, .. } => *action_index,
+        }
+    }
+}
+
+impl fmt::Display for ActionProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownCalculator { action_index, calculator_name } => write!(
+                f,
+                "action {action_index}: calculator '{calculator_name}' is not registered"
+            ),
+            Self::FieldTypeConflict { action_index, field, expected, actual } => write!(
+                f,
+                "action {action_index}: field '{field}' expects {expected}, got {actual}"
+            ),
+            Self::InvalidExpression { action_index, expression, reason } => write!(
+                f,
+                "action {action_index}: formula '{expression}' is invalid: {reason}"
+            ),
+        }
+    }
+}
+
+/// Every problem found in a rule's actions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActionValidationReport {
+    pub rule_id: RuleId,
+    pub rule_name: String,
+    /// In the order of the actions
+    pub problems: Vec<ActionProblem>,
+}
+
+impl ActionValidationReport {
+    /// Whether the actions passed validation
+    pub fn is_valid(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+impl fmt::Display for ActionValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "rule {} ({}) has {} invalid action(s)",
+            self.rule_id,
+            self.rule_name,
+            self.problems.len()
+        )?;
+        for problem in &self.problems {
+            write!(f, "; {problem}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Check every action of `rule` against the registered calculators and field types
+pub fn validate_actions(
+    rule: &Rule,
+    calculator: &Calculator,
+    schema: &FieldSchema,
+) -> ActionValidationReport {
+    let mut problems = Vec::new();
+    for (action_index, action) in rule.actions.iter().enumerate() {
+        let mut check_value = |field: &str, value: &FactValue| {
+            if let Some(expected) = schema.get(field).and_then(|ty| rejects(ty, value)) {
+                problems.push(ActionProblem::FieldTypeConflict {
+                    action_index,
+                    field: field.to_string(),
+                    expected,
+                    actual: describe(value),
+                });
+            }
+        };
+
+        match &action.action_type {
+            ActionType::SetField { field, value } => check_value(field, value),
+            ActionType::CreateFact { data } => {
+                for (field, value) in sorted(&data.fields) {
+                    check_value(field, value);
+                }
+            }
+            ActionType::UpdateFact { updates, .. } => {
+                for (field, value) in sorted(updates) {
+                    check_value(field, value);
+                }
+            }
+            ActionType::IncrementField { field, increment } => {
+                let accepted = match schema.get(field) {
+                    Some(FieldType::Integer { .. }) => matches!(increment, FactValue::Integer(_)),
+                    Some(
+                        FieldType::Currency { .. }
+                        | FieldType::Decimal { .. }
+                        | FieldType::Percentage { .. },
+                    ) => increment.as_f64().is_some(),
+                    Some(_) => false,
+                    None => true,
+                };
+                if let Some(ty) = schema.get(field).filter(|_| !accepted) {
+                    problems.push(ActionProblem::FieldTypeConflict {
+                        action_index,
+                        field: field.clone(),
+                        expected: expectation(ty),
+                        actual: format!("an increment of {}", describe(increment)),
+                    });
+                }
+            }
+            ActionType::AppendToArray { field, .. } => {
+                check_value(field, &FactValue::Array(Vec::new()))
+            }
+            ActionType::MergeObject { field, .. } => {
+                check_value(field, &FactValue::Object(Default::default()))
+            }
+            ActionType::CallCalculator { calculator_name, .. } => {
+                if !calculator.contains(calculator_name) {
+                    problems.push(ActionProblem::UnknownCalculator {
+                        action_index,
+                        calculator_name: calculator_name.clone(),
+                    });
+                }
+            }
+            ActionType::Formula { expression, .. } => {
+                if let Err(reason) = check_formula_expression(expression) {
+                    problems.push(ActionProblem::InvalidExpression {
+                        action_index,
+                        expression: expression.clone(),
+                        reason,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    ActionValidationReport { rule_id: rule.id, rule_name: rule.name.clone(), problems }
+}
+
+fn sorted<V>(fields: &std::collections::HashMap<String, V>) -> Vec<(&String, &V)> {
+    let mut fields: Vec<_> = fields.iter().collect();
+    fields.sort_by(|a, b| a.0.cmp(b.0));
+    fields
+}
+
+pub(crate) fn describe(value: &FactValue) -> String {
+    match value {
+        FactValue::Array(_) | FactValue::Object(_) => value.type_name().to_string(),
+        _ => format!("{} {}", value.type_name(), value.as_string()),
+    }
+}
+
+/// What `ty` expects if it does not accept `value`
+pub(crate) fn rejects(ty: &FieldType, value: &FactValue) -> Option<String> {
+    let numeric = matches!(
+        value,
+        FactValue::Integer(_) | FactValue::Float(_) | FactValue::Decimal(_)
+    );
+    let accepted = match (ty, value) {
+        (_, FactValue::Null) => true,
+        (FieldType::Currency { .. } | FieldType::Decimal { .. }, _) => numeric,
+        (FieldType::Percentage { min, max, .. }, _) => {
+            value.as_f64().is_some_and(|number| (*min..=*max).contains(&number))
+        }
+        (FieldType::Integer { min, max }, FactValue::Integer(number)) => {
+            (*min..=*max).contains(number)
+        }
+        (FieldType::Text { max_length, pattern }, FactValue::String(text)) => {
+            text.chars().count() <= *max_length
+                && pattern.as_deref().is_none_or(|pattern| {
+                    regex::Regex::new(pattern).map_or(true, |regex| regex.is_match(text))
+                })
+        }
+        (FieldType::Date { .. }, FactValue::Date(_) | FactValue::String(_)) => true,
+        (FieldType::Boolean, FactValue::Boolean(_)) => true,
+        (FieldType::Enum { values }, FactValue::String(text)) => values.contains(text),
+        _ => false,
+    };
+    (!accepted).then(|| expectation(ty))
+}
+
+fn expectation(ty: &FieldType) -> String {
+    match ty {
+        FieldType::Currency { currency_code, .. } => format!("a {currency_code} amount"),
+        FieldType::Percentage { min, max, .. } => format!("a percentage from {min} to {max}"),
+        FieldType::Integer { min: i64::MIN, max: i64::MAX } => "an integer".to_string(),
+        FieldType::Integer { min, max } => format!("an integer from {min} to {max}"),
+        FieldType::Decimal { .. } => "a decimal".to_string(),
+        FieldType::Text { max_length: usize::MAX, pattern: None } => "text".to_string(),
+        FieldType::Text { max_length, pattern: None } => {
+            format!("text of at most {max_length} characters")
+        }
+        FieldType::Text { max_length, pattern: Some(pattern) } => {
+            format!("text of at most {max_length} characters matching '{pattern}'")
+        }
+        FieldType::Date { .. } => "a date".to_string(),
+        FieldType::Boolean => "a boolean".to_string(),
+        FieldType::Enum { values } => format!("one of {}", values.join(", ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Action;
+    use std::collections::HashMap;
+
+    fn rule(actions: Vec<ActionType>) -> Rule {
+        Rule {
+            id: 7,
+            name: "Payout".to_string(),
+            conditions: Vec::new(),
+            actions: actions.into_iter().map(|action_type| Action { action_type }).collect(),
+            metadata: Default::default(),
+        }
+    }
+
+    fn schema() -> FieldSchema {
+        FieldSchema::from([
+            ("hours".to_string(), FieldType::Integer { min: 0, max: 80 }),
+            (
+                "status".to_string(),
+                FieldType::Enum { values: vec!["open".to_string(), "paid".to_string()] },
+            ),
+            ("approved".to_string(), FieldType::Boolean),
+        ])
+    }
+
+    #[test]
+    fn test_all_problems_are_reported_in_action_order() {
+        let rule = rule(vec![
+            ActionType::CallCalculator {
+                calculator_name: "payout".to_string(),
+                input_mapping: HashMap::new(),
+                output_field: "amount".to_string(),
+            },
+            ActionType::SetField {
+                field: "status".to_string(),
+                value: FactValue::String("closed".to_string()),
+            },
+            ActionType::Formula {
+                expression: "hours * rate * 2".to_string(),
+                output_field: "pay".to_string(),
+            },
+            ActionType::IncrementField {
+                field: "approved".to_string(),
+                increment: FactValue::Integer(1),
+            },
+            ActionType::SetField { field: "hours".to_string(), value: FactValue::Integer(40) },
+        ]);
+
+        let report = validate_actions(&rule, &Calculator::new(), &schema());
+        let indices: Vec<usize> = report.problems.iter().map(ActionProblem::action_index).collect();
+        assert_eq!(indices, vec![0, 1, 2, 3]);
+        assert_eq!(
+            report.problems[1].to_string(),
+            "action 1: field 'status' expects one of open, paid, got string closed"
+        );
+        assert!(matches!(
+            &report.problems[2],
+            ActionProblem::InvalidExpression { reason, .. } if reason.contains("rate * 2")
+        ));
+    }
+
+    #[test]
+    fn test_valid_actions_and_untyped_fields_pass() {
+        let rule = rule(vec![
+            ActionType::CallCalculator {
+                calculator_name: "multiply".to_string(),
+                input_mapping: HashMap::new(),
+                output_field: "amount".to_string(),
+            },
+            ActionType::Formula {
+                expression: "hours * 1.5".to_string(),
+                output_field: "pay".to_string(),
+            },
+            ActionType::SetField { field: "note".to_string(), value: FactValue::Integer(1) },
+            ActionType::SetField { field: "hours".to_string(), value: FactValue::Null },
+        ]);
+        assert!(validate_actions(&rule, &Calculator::new(), &schema()).is_valid());
+    }
+}
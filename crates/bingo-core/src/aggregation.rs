@@ -325,6 +325,19 @@ impl FactAggregator {
                     _ => Ok(false),
                 }
             }
+            Operator::Within | Operator::OlderThan | Operator::Overlaps => {
+                Ok(operator.matches_temporal(fact_value, condition_value))
+            }
+            Operator::Matches | Operator::ContainsIgnoreCase => Ok(crate::string_match::matches(
+                operator,
+                fact_value,
+                condition_value,
+            )),
+            Operator::In | Operator::NotIn => Ok(crate::value_list::matches_literal(
+                operator,
+                fact_value,
+                condition_value,
+            )),
         }
     }
 
@@ -401,6 +414,7 @@ impl AggregationResult {
             id: fact_id,
             external_id: None,
 
+            fact_type: None,
             data: FactData { fields },
         }
     }
@@ -423,7 +437,13 @@ mod tests {
             FactValue::String("active".to_string()),
         );
 
-        Fact { timestamp: chrono::Utc::now(), id, external_id: None, data: FactData { fields } }
+        Fact {
+            timestamp: chrono::Utc::now(),
+            id,
+            external_id: None,
+            fact_type: None,
+            data: FactData { fields },
+        }
     }
 
     #[test]
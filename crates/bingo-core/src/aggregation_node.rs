@@ -0,0 +1,1087 @@
+//! Native aggregation nodes for the RETE network
+//!
+//! An `AggregationNode` is compiled for every `Condition::Aggregation` in a rule. Instead
+//! of scanning the fact store each time the condition is tested, the node keeps running
+//! aggregates per `group_by` key and updates them as facts are asserted and retracted.
+//!
+//! ## Incremental State
+//!
+//! - **Count / Sum / Average**: Running counters and sums, O(1) per fact
+//! - **Standard Deviation**: Running sum of squares, O(1) per fact
+//! - **Min / Max / Percentile**: Ordered multiset of values, O(log n) per fact
+//!
+//! Each fact's contribution is remembered so a retraction subtracts exactly what the
+//! assertion added, and re-asserting a fact replaces its previous contribution.
+//!
+//! Sums and averages over `Decimal` values are also kept exactly, and come out as
+//! `Decimal` as long as no `Float` value is mixed in; integers count as exact.
+//!
+//! Conditions with an `AggregationWindow::Calendar` window also group facts by the
+//! calendar period their event time falls in; facts outside every period are ignored.
+//!
+//! ## Event-Time Windows
+//!
+//! Tumbling, sliding and session windows split each group further by event time, so a
+//! fact counts towards every window of its group that contains it: one tumbling window,
+//! several overlapping sliding windows, or the session its neighbours form. The node
+//! keeps a watermark, the latest event time seen, and applies its `LateDataPolicy`:
+//! by default events more than a minute behind the watermark are dropped, and windows
+//! the watermark has passed by as much are evicted at the start of the next batch.
+
+use crate::calendar::PeriodCalendar;
+use crate::stream_processing::{
+    LateDataPolicy, StreamProcessingStats, Timestamp, sliding_window_starts, tumbling_window_start,
+};
+use crate::types::{
+    AggregationCondition, AggregationType, AggregationWindow, Decimal, Fact, FactId, FactValue,
+    NodeId, RuleId,
+};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+/// Values of the `group_by` fields identifying one aggregation group
+pub type GroupKey = Vec<Option<FactValue>>;
+
+/// Totally ordered wrapper so aggregated values can live in a `BTreeMap`
+#[derive(Debug, Clone, Copy)]
+struct OrderedValue(f64);
+
+impl PartialEq for OrderedValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OrderedValue {}
+
+impl PartialOrd for OrderedValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OrderedValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+/// Exact sum of the numeric values, if at least one is a `Decimal` and none is a `Float`
+///
+/// Returns the sum and the number of values summed.
+pub fn exact_decimal_sum<'a>(
+    values: impl IntoIterator<Item = &'a FactValue>,
+) -> Option<(Decimal, usize)> {
+    let (mut sum, mut count, mut decimals) = (Decimal::ZERO, 0, 0);
+    for value in values {
+        match value {
+            FactValue::Decimal(d) => {
+                sum = sum.checked_add(*d)?;
+                decimals += 1;
+            }
+            FactValue::Integer(i) => sum = sum.checked_add(Decimal::from(*i))?,
+            FactValue::Float(_) => return None,
+            _ => continue,
+        }
+        count += 1;
+    }
+    (decimals > 0).then_some((sum, count))
+}
+
+/// Aggregate of a field's values, as an aggregation scanning the fact store computes it
+///
+/// `Count` counts every value; the others use the numeric values, with sums and
+/// averages kept exact as in [`exact_decimal_sum`].
+pub fn aggregate_values<'a>(
+    aggregation_type: &AggregationType,
+    values: impl Iterator<Item = &'a FactValue> + Clone,
+) -> FactValue {
+    let numbers = || values.clone().filter_map(FactValue::as_f64);
+    match aggregation_type {
+        AggregationType::Count => FactValue::Integer(values.count() as i64),
+        AggregationType::Sum => match exact_decimal_sum(values.clone()) {
+            Some((sum, _)) => FactValue::Decimal(sum),
+            None => FactValue::Float(numbers().sum()),
+        },
+        AggregationType::Average => {
+            let numbers: Vec<f64> = numbers().collect();
+            if let Some((sum, count)) = exact_decimal_sum(values) {
+                FactValue::Decimal(sum / Decimal::from(count))
+            } else if numbers.is_empty() {
+                FactValue::Float(0.0)
+            } else {
+                FactValue::Float(numbers.iter().sum::<f64>() / numbers.len() as f64)
+            }
+        }
+        AggregationType::Min => FactValue::Float(numbers().fold(f64::INFINITY, f64::min)),
+        AggregationType::Max => FactValue::Float(numbers().fold(f64::NEG_INFINITY, f64::max)),
+        AggregationType::StandardDeviation => {
+            let numbers: Vec<f64> = numbers().collect();
+            if numbers.len() < 2 {
+                FactValue::Float(0.0)
+            } else {
+                let mean = numbers.iter().sum::<f64>() / numbers.len() as f64;
+                let variance =
+                    numbers.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / numbers.len() as f64;
+                FactValue::Float(variance.sqrt())
+            }
+        }
+        AggregationType::Percentile(p) => {
+            let mut numbers: Vec<f64> = numbers().collect();
+            if numbers.is_empty() {
+                return FactValue::Float(0.0);
+            }
+            numbers.sort_by(f64::total_cmp);
+            let rank = (p / 100.0) * (numbers.len() as f64 - 1.0);
+            let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+            if upper == lower {
+                FactValue::Float(numbers[lower])
+            } else {
+                let w = rank - lower as f64;
+                FactValue::Float(numbers[lower] * (1.0 - w) + numbers[upper] * w)
+            }
+        }
+    }
+}
+
+/// What a single fact contributed to its groups
+///
+/// Facts count towards one group, or with event-time windows, one group per window
+/// containing them.
+#[derive(Debug, Clone)]
+struct Contribution {
+    groups: Vec<GroupKey>,
+    event_time: u64,
+    has_field: bool,
+    value: Option<f64>,
+    /// Exact value of an `Integer` or `Decimal` source field
+    exact: Option<Decimal>,
+    is_decimal: bool,
+}
+
+/// Running aggregates for one group
+#[derive(Debug, Clone, Default)]
+pub struct GroupState {
+    /// Facts belonging to the group, with or without the source field
+    pub members: usize,
+    /// Facts that carry the source field
+    pub field_count: usize,
+    /// Facts whose source field is numeric
+    pub numeric_count: usize,
+    /// Sum of numeric values
+    pub sum: f64,
+    /// Sum of squared numeric values
+    pub sum_of_squares: f64,
+    /// Facts whose source field is a `Decimal`
+    pub decimal_count: usize,
+    /// Facts whose source field is a `Float`, which make the exact sum unusable
+    pub float_count: usize,
+    /// Sum of the `Integer` and `Decimal` values
+    pub exact_sum: Decimal,
+    values: BTreeMap<OrderedValue, usize>,
+}
+
+impl GroupState {
+    fn add(&mut self, contribution: &Contribution) {
+        self.members += 1;
+        if contribution.has_field {
+            self.field_count += 1;
+        }
+        if let Some(value) = contribution.value {
+            self.numeric_count += 1;
+            self.sum += value;
+            self.sum_of_squares += value * value;
+            *self.values.entry(OrderedValue(value)).or_insert(0) += 1;
+        }
+        match contribution.exact {
+            Some(exact) => self.exact_sum = self.exact_sum.saturating_add(exact),
+            None if contribution.value.is_some() => self.float_count += 1,
+            None => {}
+        }
+        if contribution.is_decimal {
+            self.decimal_count += 1;
+        }
+    }
+
+    fn remove(&mut self, contribution: &Contribution) {
+        self.members = self.members.saturating_sub(1);
+        if contribution.has_field {
+            self.field_count = self.field_count.saturating_sub(1);
+        }
+        if let Some(value) = contribution.value {
+            self.numeric_count = self.numeric_count.saturating_sub(1);
+            self.sum -= value;
+            self.sum_of_squares -= value * value;
+            let key = OrderedValue(value);
+            if let Some(count) = self.values.get_mut(&key) {
+                *count -= 1;
+                if *count == 0 {
+                    self.values.remove(&key);
+                }
+            }
+        }
+        match contribution.exact {
+            Some(exact) => self.exact_sum = self.exact_sum.saturating_sub(exact),
+            None if contribution.value.is_some() => {
+                self.float_count = self.float_count.saturating_sub(1);
+            }
+            None => {}
+        }
+        if contribution.is_decimal {
+            self.decimal_count = self.decimal_count.saturating_sub(1);
+        }
+    }
+
+    /// Exact sum when the group holds decimals and no floats
+    fn decimal_sum(&self) -> Option<Decimal> {
+        (self.decimal_count > 0 && self.float_count == 0).then_some(self.exact_sum)
+    }
+
+    /// Compute the aggregate for this group
+    pub fn value(&self, aggregation_type: &AggregationType) -> FactValue {
+        match aggregation_type {
+            AggregationType::Count => FactValue::Integer(self.field_count as i64),
+            AggregationType::Sum => match self.decimal_sum() {
+                Some(sum) => FactValue::Decimal(sum),
+                None => FactValue::Float(self.sum),
+            },
+            AggregationType::Average => {
+                if self.numeric_count == 0 {
+                    FactValue::Float(0.0)
+                } else if let Some(sum) = self.decimal_sum() {
+                    FactValue::Decimal(sum / Decimal::from(self.numeric_count))
+                } else {
+                    FactValue::Float(self.sum / self.numeric_count as f64)
+                }
+            }
+            AggregationType::Min => {
+                FactValue::Float(self.values.keys().next().map(|v| v.0).unwrap_or(f64::INFINITY))
+            }
+            AggregationType::Max => FactValue::Float(
+                self.values.keys().next_back().map(|v| v.0).unwrap_or(f64::NEG_INFINITY),
+            ),
+            AggregationType::StandardDeviation => {
+                if self.numeric_count < 2 {
+                    FactValue::Float(0.0)
+                } else {
+                    let n = self.numeric_count as f64;
+                    let mean = self.sum / n;
+                    let variance = (self.sum_of_squares / n - mean * mean).max(0.0);
+                    FactValue::Float(variance.sqrt())
+                }
+            }
+            AggregationType::Percentile(p) => {
+                if self.numeric_count == 0 {
+                    return FactValue::Float(0.0);
+                }
+                let rank = (p / 100.0) * (self.numeric_count as f64 - 1.0);
+                let lower = self.nth_value(rank.floor() as usize);
+                let upper = self.nth_value(rank.ceil() as usize);
+                let weight = rank - rank.floor();
+                FactValue::Float(lower * (1.0 - weight) + upper * weight)
+            }
+        }
+    }
+
+    /// Whether the group holds data the aggregate can be computed from
+    pub fn has_data(&self, aggregation_type: &AggregationType) -> bool {
+        match aggregation_type {
+            AggregationType::Count => self.field_count > 0,
+            _ => self.numeric_count > 0,
+        }
+    }
+
+    /// The `index`-th smallest numeric value in the group
+    fn nth_value(&self, index: usize) -> f64 {
+        let mut seen = 0;
+        for (value, count) in &self.values {
+            seen += count;
+            if index < seen {
+                return value.0;
+            }
+        }
+        self.values.keys().next_back().map(|v| v.0).unwrap_or(0.0)
+    }
+}
+
+/// Bounds and members of one event-time window
+#[derive(Debug, Clone)]
+struct WindowFacts {
+    start: u64,
+    end: u64,
+    facts: Vec<FactId>,
+}
+
+/// Session of one `group_by` group
+#[derive(Debug, Clone, Copy)]
+struct Session {
+    end: u64,
+    id: u64,
+}
+
+/// Open event-time windows of a node and the watermark that closes them
+#[derive(Debug, Clone)]
+struct EventWindows {
+    /// Windows by the key of the group aggregating them
+    windows: HashMap<GroupKey, WindowFacts>,
+    /// Open sessions of each `group_by` group by start time; a session keeps its id as
+    /// it grows, so its group key stays the same
+    sessions: HashMap<GroupKey, BTreeMap<u64, Session>>,
+    next_session: u64,
+    watermark: Timestamp,
+    stats: StreamProcessingStats,
+}
+
+impl EventWindows {
+    fn new() -> Self {
+        Self {
+            windows: HashMap::new(),
+            sessions: HashMap::new(),
+            next_session: 0,
+            watermark: Timestamp::from_millis(0),
+            stats: StreamProcessingStats::default(),
+        }
+    }
+}
+
+/// Key of the group aggregating the window identified by `window` within `group`
+fn window_key(group: &GroupKey, window: u64) -> GroupKey {
+    let mut key = group.clone();
+    key.push(Some(FactValue::Integer(window as i64)));
+    key
+}
+
+/// RETE node maintaining incremental aggregates for one aggregation condition
+#[derive(Debug, Clone)]
+pub struct AggregationNode {
+    pub id: NodeId,
+    pub condition: AggregationCondition,
+    pub dependent_rules: Vec<RuleId>,
+    groups: HashMap<GroupKey, GroupState>,
+    contributions: HashMap<FactId, Contribution>,
+    calendar: Option<Arc<PeriodCalendar>>,
+    windows: Option<EventWindows>,
+    late_data: LateDataPolicy,
+    seeded: bool,
+}
+
+impl AggregationNode {
+    /// Create an empty aggregation node for a condition
+    pub fn new(id: NodeId, condition: AggregationCondition) -> Self {
+        let windows = condition
+            .window
+            .as_ref()
+            .is_some_and(AggregationWindow::is_event_time)
+            .then(EventWindows::new);
+        Self {
+            id,
+            condition,
+            dependent_rules: Vec::new(),
+            groups: HashMap::new(),
+            contributions: HashMap::new(),
+            calendar: None,
+            windows,
+            late_data: LateDataPolicy::default(),
+            seeded: false,
+        }
+    }
+
+    /// Set the calendar whose periods partition the groups
+    ///
+    /// Existing aggregates are dropped; the node will be re-seeded on next use.
+    pub fn set_calendar(&mut self, calendar: Arc<PeriodCalendar>) {
+        self.calendar = Some(calendar);
+        self.clear();
+    }
+
+    /// Calendar whose periods partition the groups, if any
+    pub fn calendar(&self) -> Option<&Arc<PeriodCalendar>> {
+        self.calendar.as_ref()
+    }
+
+    /// Choose what happens to events that arrive behind the watermark of an event-time
+    /// window
+    pub fn set_late_data_policy(&mut self, policy: LateDataPolicy) {
+        self.late_data = policy;
+    }
+
+    /// The policy applied to events that arrive behind the watermark
+    pub fn late_data_policy(&self) -> LateDataPolicy {
+        self.late_data
+    }
+
+    /// Signature used to share nodes between rules with identical aggregation conditions
+    pub fn signature(condition: &AggregationCondition) -> String {
+        format!("{condition:?}")
+    }
+
+    /// Register a rule that depends on this node
+    pub fn add_rule(&mut self, rule_id: RuleId) {
+        if !self.dependent_rules.contains(&rule_id) {
+            self.dependent_rules.push(rule_id);
+        }
+    }
+
+    /// Unregister a rule, returning whether any dependent rules remain
+    pub fn remove_rule(&mut self, rule_id: RuleId) -> bool {
+        self.dependent_rules.retain(|id| *id != rule_id);
+        !self.dependent_rules.is_empty()
+    }
+
+    /// Whether the node has been populated with the facts that existed before it was built
+    pub fn is_seeded(&self) -> bool {
+        self.seeded
+    }
+
+    /// Populate the node from existing facts
+    pub fn seed<'a>(&mut self, facts: impl IntoIterator<Item = &'a Fact>) {
+        let mut facts: Vec<&Fact> = facts.into_iter().collect();
+        if self.windows.is_some() {
+            // In event time order, so existing facts are not dropped as late
+            facts.sort_by_key(|fact| Timestamp::of_fact(fact));
+        }
+        for fact in facts {
+            self.assert_fact(fact);
+        }
+        self.seeded = true;
+    }
+
+    /// Evict windows the watermark has closed, then add a batch of facts
+    pub fn assert_batch<'a>(&mut self, facts: impl IntoIterator<Item = &'a Fact>) {
+        self.evict_closed_windows();
+        for fact in facts {
+            self.assert_fact(fact);
+        }
+    }
+
+    /// Add a fact's contribution to its groups, replacing any earlier contribution
+    ///
+    /// With an event-time window, a fact arriving too far behind the watermark is
+    /// dropped unless the late data policy accepts it. Re-asserting a fact at the same
+    /// event time keeps it, since it was on time when it arrived.
+    pub fn assert_fact(&mut self, fact: &Fact) {
+        let previous_time = self.contributions.get(&fact.id).map(|previous| previous.event_time);
+        self.retract_fact(fact.id);
+
+        let group = self.group_key(fact);
+        if self.calendar.is_some() && group.last().is_some_and(Option::is_none) {
+            // Outside every period of the calendar
+            return;
+        }
+
+        let source = fact.data.fields.get(&self.condition.source_field);
+        let mut contribution = Contribution {
+            groups: Vec::new(),
+            event_time: Timestamp::of_fact(fact).as_millis(),
+            has_field: source.is_some(),
+            value: source.and_then(|value| value.as_f64()),
+            exact: match source {
+                Some(FactValue::Integer(i)) => Some(Decimal::from(*i)),
+                Some(FactValue::Decimal(d)) => Some(*d),
+                _ => None,
+            },
+            is_decimal: source.is_some_and(FactValue::is_decimal),
+        };
+
+        let Some(windows) = self.windows.as_mut() else {
+            self.groups.entry(group.clone()).or_default().add(&contribution);
+            contribution.groups.push(group);
+            self.contributions.insert(fact.id, contribution);
+            return;
+        };
+
+        let event_time = Timestamp::from_millis(contribution.event_time);
+        if previous_time != Some(contribution.event_time) {
+            if self.late_data.is_late(event_time, windows.watermark) {
+                windows.stats.late_events_dropped += 1;
+                return;
+            }
+            if event_time > windows.watermark {
+                windows.watermark = event_time;
+                windows.stats.watermark_updates += 1;
+            }
+            windows.stats.events_processed += 1;
+        }
+
+        let time = contribution.event_time;
+        let spans: Vec<(u64, u64)> = match self.condition.window {
+            Some(AggregationWindow::TumblingTime { duration_ms }) => {
+                let start = tumbling_window_start(time, duration_ms);
+                vec![(start, start + duration_ms.max(1))]
+            }
+            Some(AggregationWindow::SlidingTime { size_ms, advance_ms }) => {
+                sliding_window_starts(time, size_ms, advance_ms)
+                    .into_iter()
+                    .map(|start| (start, start + size_ms.max(1)))
+                    .collect()
+            }
+            Some(AggregationWindow::Session { timeout_ms }) => {
+                self.place_in_session(fact.id, group, contribution, timeout_ms);
+                return;
+            }
+            _ => Vec::new(),
+        };
+
+        for (start, end) in spans {
+            let key = window_key(&group, start);
+            let stats = &mut windows.stats;
+            let window = windows.windows.entry(key.clone()).or_insert_with(|| {
+                stats.windows_created += 1;
+                WindowFacts { start, end, facts: Vec::new() }
+            });
+            window.facts.push(fact.id);
+            self.groups.entry(key.clone()).or_default().add(&contribution);
+            contribution.groups.push(key);
+        }
+        self.contributions.insert(fact.id, contribution);
+    }
+
+    /// Add a contribution to its session, merging every session it bridges into the
+    /// largest of them
+    fn place_in_session(
+        &mut self,
+        fact_id: FactId,
+        group: GroupKey,
+        mut contribution: Contribution,
+        timeout_ms: u64,
+    ) {
+        let Some(windows) = self.windows.as_mut() else {
+            return;
+        };
+        let time = contribution.event_time;
+        let sessions = windows.sessions.entry(group.clone()).or_default();
+        let touching: Vec<(u64, Session)> = sessions
+            .iter()
+            .filter(|(start, session)| {
+                time.saturating_add(timeout_ms) >= **start && time <= session.end
+            })
+            .map(|(start, session)| (*start, *session))
+            .collect();
+
+        let mut start = time;
+        let mut end = time.saturating_add(timeout_ms);
+        for (session_start, session) in &touching {
+            sessions.remove(session_start);
+            start = start.min(*session_start);
+            end = end.max(session.end);
+        }
+
+        let survivor = touching.iter().map(|(_, session)| session.id).max_by_key(|id| {
+            windows
+                .windows
+                .get(&window_key(&group, *id))
+                .map_or(0, |window| window.facts.len())
+        });
+        let id = survivor.unwrap_or_else(|| {
+            windows.stats.windows_created += 1;
+            windows.next_session += 1;
+            windows.next_session
+        });
+        let key = window_key(&group, id);
+        sessions.insert(start, Session { end, id });
+
+        // Move the members of the other sessions into the surviving one
+        let mut moved = Vec::new();
+        for (_, session) in touching.iter().filter(|(_, session)| session.id != id) {
+            let other = window_key(&group, session.id);
+            self.groups.remove(&other);
+            if let Some(window) = windows.windows.remove(&other) {
+                moved.extend(window.facts);
+            }
+        }
+        let state = self.groups.entry(key.clone()).or_default();
+        for member_id in &moved {
+            if let Some(member) = self.contributions.get_mut(member_id) {
+                member.groups = vec![key.clone()];
+                state.add(member);
+            }
+        }
+        state.add(&contribution);
+        contribution.groups.push(key.clone());
+
+        let window = windows.windows.entry(key).or_insert_with(|| WindowFacts {
+            start,
+            end,
+            facts: Vec::new(),
+        });
+        window.start = start;
+        window.end = end;
+        window.facts.extend(moved);
+        window.facts.push(fact_id);
+        self.contributions.insert(fact_id, contribution);
+    }
+
+    /// Remove a fact's contribution, returning whether it was part of the aggregate
+    pub fn retract_fact(&mut self, fact_id: FactId) -> bool {
+        let Some(contribution) = self.contributions.remove(&fact_id) else {
+            return false;
+        };
+
+        for key in &contribution.groups {
+            if let Some(group) = self.groups.get_mut(key) {
+                group.remove(&contribution);
+                if group.members == 0 {
+                    self.groups.remove(key);
+                }
+            }
+
+            let Some(windows) = self.windows.as_mut() else {
+                continue;
+            };
+            let Some(window) = windows.windows.get_mut(key) else {
+                continue;
+            };
+            window.facts.retain(|id| *id != fact_id);
+            if window.facts.is_empty() {
+                let start = window.start;
+                windows.windows.remove(key);
+                Self::remove_session(windows, key, start);
+            } else if let Some(AggregationWindow::Session { timeout_ms }) = self.condition.window {
+                // The retracted event may have bridged two sessions, so rebuild them
+                self.resessionize(key, timeout_ms);
+            }
+        }
+        true
+    }
+
+    /// Rebuild the sessions formed by the remaining members of one session
+    fn resessionize(&mut self, key: &GroupKey, timeout_ms: u64) {
+        let Some(windows) = self.windows.as_mut() else {
+            return;
+        };
+        let Some(window) = windows.windows.remove(key) else {
+            return;
+        };
+        Self::remove_session(windows, key, window.start);
+        self.groups.remove(key);
+
+        let mut members: Vec<(FactId, Contribution)> = window
+            .facts
+            .into_iter()
+            .filter_map(|id| self.contributions.remove(&id).map(|member| (id, member)))
+            .collect();
+        members.sort_by_key(|(_, member)| member.event_time);
+
+        let group = key[..key.len() - 1].to_vec();
+        for (id, mut member) in members {
+            member.groups.clear();
+            self.place_in_session(id, group.clone(), member, timeout_ms);
+        }
+    }
+
+    /// Forget the session a window held, if it was one
+    fn remove_session(windows: &mut EventWindows, key: &GroupKey, start: u64) {
+        let group = &key[..key.len() - 1];
+        if let Some(sessions) = windows.sessions.get_mut(group) {
+            sessions.remove(&start);
+            if sessions.is_empty() {
+                windows.sessions.remove(group);
+            }
+        }
+    }
+
+    /// Evict the windows the watermark has passed by more than the allowed lateness,
+    /// dropping their aggregates
+    pub fn evict_closed_windows(&mut self) {
+        let Some(windows) = self.windows.as_mut() else {
+            return;
+        };
+        let closed: Vec<GroupKey> = windows
+            .windows
+            .iter()
+            .filter(|(_, window)| {
+                self.late_data.is_closed(Timestamp::from_millis(window.end), windows.watermark)
+            })
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        for key in closed {
+            let Some(window) = windows.windows.remove(&key) else {
+                continue;
+            };
+            windows.stats.windows_completed += 1;
+            Self::remove_session(windows, &key, window.start);
+            self.groups.remove(&key);
+            for fact_id in window.facts {
+                if let Some(contribution) = self.contributions.get_mut(&fact_id) {
+                    contribution.groups.retain(|group| *group != key);
+                    if contribution.groups.is_empty() {
+                        self.contributions.remove(&fact_id);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Group key for a fact based on the condition's `group_by` fields
+    ///
+    /// With a calendar, the label of the fact's period is appended as the last part.
+    pub fn group_key(&self, fact: &Fact) -> GroupKey {
+        let mut key: GroupKey = self
+            .condition
+            .group_by
+            .iter()
+            .map(|field| fact.data.fields.get(field).cloned())
+            .collect();
+        if let Some(calendar) = &self.calendar {
+            key.push(
+                calendar.period_of(fact).map(|period| FactValue::String(period.label.clone())),
+            );
+        }
+        key
+    }
+
+    /// Keys of the groups for the windows a fact's event time falls in
+    ///
+    /// Without an event-time window this is just the fact's group key.
+    fn window_keys(&self, fact: &Fact) -> Vec<GroupKey> {
+        let group = self.group_key(fact);
+        let time = Timestamp::of_fact(fact).as_millis();
+        match &self.condition.window {
+            Some(AggregationWindow::TumblingTime { duration_ms }) => {
+                vec![window_key(&group, tumbling_window_start(time, *duration_ms))]
+            }
+            Some(AggregationWindow::SlidingTime { size_ms, advance_ms }) => {
+                sliding_window_starts(time, *size_ms, *advance_ms)
+                    .into_iter()
+                    .map(|start| window_key(&group, start))
+                    .collect()
+            }
+            Some(AggregationWindow::Session { .. }) => self
+                .windows
+                .as_ref()
+                .and_then(|windows| windows.sessions.get(&group))
+                .and_then(|sessions| sessions.range(..=time).next_back())
+                .filter(|(_, session)| time <= session.end)
+                .map(|(_, session)| window_key(&group, session.id))
+                .into_iter()
+                .collect(),
+            _ => vec![group],
+        }
+    }
+
+    /// Running aggregates for the group a fact belongs to
+    ///
+    /// With sliding windows, this is the earliest window holding the fact's event time.
+    pub fn group_for(&self, fact: &Fact) -> Option<&GroupState> {
+        self.groups_for(fact).into_iter().next()
+    }
+
+    /// Running aggregates of every window holding a fact's event time
+    pub fn groups_for(&self, fact: &Fact) -> Vec<&GroupState> {
+        self.window_keys(fact).iter().filter_map(|key| self.groups.get(key)).collect()
+    }
+
+    /// Aggregated value for the group a fact belongs to
+    pub fn aggregate_for(&self, fact: &Fact) -> FactValue {
+        self.group_for(fact)
+            .map(|group| group.value(&self.condition.aggregation_type))
+            .unwrap_or_else(|| GroupState::default().value(&self.condition.aggregation_type))
+    }
+
+    /// Aggregated values the conditions tested against a fact see
+    ///
+    /// That is the fact's group aggregate, or with an event-time window, the aggregate
+    /// of every open window holding the fact's event time, which may be none.
+    pub fn aggregates_for(&self, fact: &Fact) -> Vec<FactValue> {
+        if self.windows.is_none() {
+            return vec![self.aggregate_for(fact)];
+        }
+        self.groups_for(fact)
+            .into_iter()
+            .map(|group| group.value(&self.condition.aggregation_type))
+            .collect()
+    }
+
+    /// Whether a fact contributes to the node, which it stops doing once dropped as late
+    /// or evicted with its windows
+    pub fn contains_fact(&self, fact_id: FactId) -> bool {
+        self.contributions.contains_key(&fact_id)
+    }
+
+    /// Whether the node splits its groups into event-time windows
+    pub fn is_windowed(&self) -> bool {
+        self.windows.is_some()
+    }
+
+    /// Number of facts currently contributing to the node
+    pub fn fact_count(&self) -> usize {
+        self.contributions.len()
+    }
+
+    /// Number of live groups
+    pub fn group_count(&self) -> usize {
+        self.groups.len()
+    }
+
+    /// Number of open event-time windows
+    pub fn window_count(&self) -> usize {
+        self.windows.as_ref().map_or(0, |windows| windows.windows.len())
+    }
+
+    /// Latest event time observed, for nodes with an event-time window
+    pub fn watermark(&self) -> Option<Timestamp> {
+        self.windows.as_ref().map(|windows| windows.watermark)
+    }
+
+    /// Window and event statistics, for nodes with an event-time window
+    pub fn window_stats(&self) -> Option<&StreamProcessingStats> {
+        self.windows.as_ref().map(|windows| &windows.stats)
+    }
+
+    /// Drop all aggregate state; the node will be re-seeded on next use
+    pub fn clear(&mut self) {
+        self.groups.clear();
+        self.contributions.clear();
+        if let Some(windows) = &mut self.windows {
+            let stats = std::mem::take(&mut windows.stats);
+            *windows = EventWindows { stats, ..EventWindows::new() };
+        }
+        self.seeded = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::FactData;
+
+    fn condition(aggregation_type: AggregationType) -> AggregationCondition {
+        AggregationCondition {
+            aggregation_type,
+            source_field: "hours".to_string(),
+            group_by: vec!["employee_id".to_string()],
+            having: None,
+            alias: "total".to_string(),
+            window: None,
+        }
+    }
+
+    fn fact(id: FactId, employee: i64, hours: f64) -> Fact {
+        let mut fields = HashMap::new();
+        fields.insert("employee_id".to_string(), FactValue::Integer(employee));
+        fields.insert("hours".to_string(), FactValue::Float(hours));
+        Fact::new(id, FactData { fields })
+    }
+
+    #[test]
+    fn test_sum_is_maintained_per_group() {
+        let mut node = AggregationNode::new(1, condition(AggregationType::Sum));
+        node.assert_fact(&fact(1, 7, 8.0));
+        node.assert_fact(&fact(2, 7, 4.0));
+        node.assert_fact(&fact(3, 9, 1.0));
+
+        assert_eq!(node.aggregate_for(&fact(0, 7, 0.0)), FactValue::Float(12.0));
+        assert_eq!(node.aggregate_for(&fact(0, 9, 0.0)), FactValue::Float(1.0));
+        assert_eq!(node.group_count(), 2);
+
+        assert!(node.retract_fact(1));
+        assert_eq!(node.aggregate_for(&fact(0, 7, 0.0)), FactValue::Float(4.0));
+    }
+
+    #[test]
+    fn test_min_max_survive_retraction() {
+        let mut min = AggregationNode::new(1, condition(AggregationType::Min));
+        let mut max = AggregationNode::new(2, condition(AggregationType::Max));
+        for (id, hours) in [(1, 3.0), (2, 1.0), (3, 5.0)] {
+            min.assert_fact(&fact(id, 7, hours));
+            max.assert_fact(&fact(id, 7, hours));
+        }
+
+        min.retract_fact(2);
+        max.retract_fact(3);
+        assert_eq!(min.aggregate_for(&fact(0, 7, 0.0)), FactValue::Float(3.0));
+        assert_eq!(max.aggregate_for(&fact(0, 7, 0.0)), FactValue::Float(3.0));
+    }
+
+    #[test]
+    fn test_reassert_replaces_contribution() {
+        let mut node = AggregationNode::new(1, condition(AggregationType::Average));
+        node.assert_fact(&fact(1, 7, 2.0));
+        node.assert_fact(&fact(1, 7, 6.0));
+
+        assert_eq!(node.fact_count(), 1);
+        assert_eq!(node.aggregate_for(&fact(0, 7, 0.0)), FactValue::Float(6.0));
+    }
+
+    #[test]
+    fn test_decimal_sum_and_average_are_exact() {
+        let amount = |id: FactId, cents: &str| {
+            let mut fact = fact(id, 7, 0.0);
+            fact.data.fields.insert(
+                "hours".to_string(),
+                FactValue::Decimal(cents.parse().unwrap()),
+            );
+            fact
+        };
+        let mut sum = AggregationNode::new(1, condition(AggregationType::Sum));
+        let mut average = AggregationNode::new(2, condition(AggregationType::Average));
+        for (id, value) in [(1, "0.10"), (2, "0.20"), (3, "0.30")] {
+            sum.assert_fact(&amount(id, value));
+            average.assert_fact(&amount(id, value));
+        }
+
+        assert_eq!(
+            sum.aggregate_for(&fact(0, 7, 0.0)),
+            FactValue::Decimal("0.60".parse().unwrap())
+        );
+        assert_eq!(
+            average.aggregate_for(&fact(0, 7, 0.0)),
+            FactValue::Decimal("0.2".parse().unwrap())
+        );
+
+        // A float in the group falls back to floating point until it is retracted
+        sum.assert_fact(&fact(4, 7, 1.5));
+        assert!(matches!(
+            sum.aggregate_for(&fact(0, 7, 0.0)),
+            FactValue::Float(_)
+        ));
+        sum.retract_fact(4);
+        sum.retract_fact(3);
+        assert_eq!(
+            sum.aggregate_for(&fact(0, 7, 0.0)),
+            FactValue::Decimal("0.30".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_aggregate_values_match_the_running_aggregates() {
+        let values = [FactValue::Integer(2), FactValue::Float(4.0), FactValue::Null];
+        let aggregate = |aggregation_type| aggregate_values(&aggregation_type, values.iter());
+
+        assert_eq!(aggregate(AggregationType::Count), FactValue::Integer(3));
+        assert_eq!(aggregate(AggregationType::Sum), FactValue::Float(6.0));
+        assert_eq!(aggregate(AggregationType::Average), FactValue::Float(3.0));
+        assert_eq!(aggregate(AggregationType::Max), FactValue::Float(4.0));
+        assert_eq!(
+            aggregate(AggregationType::Percentile(50.0)),
+            FactValue::Float(3.0)
+        );
+
+        let cents = [FactValue::Decimal("0.10".parse().unwrap()), FactValue::Integer(1)];
+        assert_eq!(
+            aggregate_values(&AggregationType::Sum, cents.iter()),
+            FactValue::Decimal("1.10".parse().unwrap())
+        );
+    }
+
+    fn windowed(window: AggregationWindow) -> AggregationCondition {
+        AggregationCondition { window: Some(window), ..condition(AggregationType::Sum) }
+    }
+
+    fn timed(id: FactId, employee: i64, hours: f64, time: i64) -> Fact {
+        let mut fact = fact(id, employee, hours);
+        fact.data.fields.insert("timestamp".to_string(), FactValue::Integer(time));
+        fact
+    }
+
+    fn sums(node: &AggregationNode, employee: i64, time: i64) -> Vec<FactValue> {
+        node.aggregates_for(&timed(0, employee, 0.0, time))
+    }
+
+    #[test]
+    fn test_tumbling_windows_split_groups_by_event_time() {
+        let mut node = AggregationNode::new(
+            1,
+            windowed(AggregationWindow::TumblingTime { duration_ms: 1000 }),
+        );
+        node.assert_fact(&timed(1, 7, 1.0, 100));
+        node.assert_fact(&timed(2, 7, 2.0, 900));
+        node.assert_fact(&timed(3, 7, 4.0, 1000));
+        node.assert_fact(&timed(4, 9, 8.0, 500));
+
+        assert_eq!(sums(&node, 7, 0), vec![FactValue::Float(3.0)]);
+        assert_eq!(sums(&node, 7, 1999), vec![FactValue::Float(4.0)]);
+        assert_eq!(sums(&node, 9, 999), vec![FactValue::Float(8.0)]);
+        assert_eq!(sums(&node, 7, 2000), Vec::<FactValue>::new());
+        assert_eq!(node.window_count(), 3);
+    }
+
+    #[test]
+    fn test_sliding_windows_count_a_fact_in_every_window_holding_it() {
+        let mut node = AggregationNode::new(
+            1,
+            windowed(AggregationWindow::SlidingTime { size_ms: 2000, advance_ms: 1000 }),
+        );
+        node.assert_fact(&timed(1, 7, 1.0, 500));
+        node.assert_fact(&timed(2, 7, 2.0, 1500));
+
+        // Windows [0, 2000) and [1000, 3000) both hold time 1500
+        assert_eq!(
+            sums(&node, 7, 1500),
+            vec![FactValue::Float(3.0), FactValue::Float(2.0)]
+        );
+        assert_eq!(node.window_count(), 2);
+
+        node.retract_fact(2);
+        assert_eq!(sums(&node, 7, 1500), vec![FactValue::Float(1.0)]);
+        assert_eq!(node.window_count(), 1);
+    }
+
+    #[test]
+    fn test_sessions_merge_when_bridged_and_split_on_retraction() {
+        let mut node =
+            AggregationNode::new(1, windowed(AggregationWindow::Session { timeout_ms: 1000 }));
+        node.assert_fact(&timed(1, 7, 1.0, 0));
+        node.assert_fact(&timed(2, 7, 2.0, 1800));
+        assert_eq!(node.window_count(), 2);
+
+        // An event between them is within the timeout of both
+        node.assert_fact(&timed(3, 7, 4.0, 900));
+        assert_eq!(node.window_count(), 1);
+        assert_eq!(sums(&node, 7, 0), vec![FactValue::Float(7.0)]);
+        assert_eq!(sums(&node, 7, 2800), vec![FactValue::Float(7.0)]);
+        assert_eq!(sums(&node, 7, 2801), Vec::<FactValue>::new());
+
+        node.retract_fact(3);
+        assert_eq!(node.window_count(), 2);
+        assert_eq!(sums(&node, 7, 0), vec![FactValue::Float(1.0)]);
+        assert_eq!(sums(&node, 7, 1800), vec![FactValue::Float(2.0)]);
+    }
+
+    #[test]
+    fn test_late_events_are_dropped_and_closed_windows_evicted() {
+        let mut node = AggregationNode::new(
+            1,
+            windowed(AggregationWindow::TumblingTime { duration_ms: 1000 }),
+        );
+        node.set_late_data_policy(LateDataPolicy::Drop {
+            allowed_lateness: std::time::Duration::from_millis(500),
+        });
+        node.assert_batch([&timed(1, 7, 1.0, 100), &timed(2, 7, 2.0, 1600)]);
+        assert_eq!(node.watermark(), Some(Timestamp::from_millis(1600)));
+
+        // More than 500ms behind the watermark
+        node.assert_fact(&timed(3, 7, 4.0, 1000));
+        assert_eq!(sums(&node, 7, 1000), vec![FactValue::Float(2.0)]);
+        assert_eq!(node.window_stats().unwrap().late_events_dropped, 1);
+
+        // The first window closed once the watermark passed 1500
+        node.assert_batch([]);
+        assert_eq!(node.window_count(), 1);
+        assert_eq!(node.fact_count(), 1);
+        assert_eq!(sums(&node, 7, 100), Vec::<FactValue>::new());
+
+        let mut accepting = AggregationNode::new(
+            2,
+            windowed(AggregationWindow::TumblingTime { duration_ms: 1000 }),
+        );
+        accepting.set_late_data_policy(LateDataPolicy::Accept);
+        accepting.assert_batch([&timed(1, 7, 1.0, 100_000), &timed(2, 7, 2.0, 100)]);
+        accepting.assert_batch([]);
+        assert_eq!(sums(&accepting, 7, 0), vec![FactValue::Float(2.0)]);
+        assert_eq!(accepting.window_count(), 2);
+    }
+
+    #[test]
+    fn test_empty_group_removed_after_retraction() {
+        let mut node = AggregationNode::new(1, condition(AggregationType::Count));
+        node.assert_fact(&fact(1, 7, 2.0));
+        node.retract_fact(1);
+
+        assert_eq!(node.group_count(), 0);
+        assert_eq!(node.aggregate_for(&fact(0, 7, 0.0)), FactValue::Integer(0));
+        assert!(!node.retract_fact(1));
+    }
+}
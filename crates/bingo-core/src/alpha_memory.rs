@@ -19,17 +19,38 @@
 //! - **AlphaMemory**: Indexed storage of facts matching specific patterns  
 //! - **AlphaMemoryManager**: Manages multiple alpha memories with efficient indexing
 //! - **PatternIndex**: Hash-based index for O(1) pattern lookups
-
-use crate::types::{Condition, Fact, FactId, FactValue, NodeId, Operator, RuleId};
-use std::collections::{HashMap, HashSet};
+//!
+//! ## Fact Type Routing
+//!
+//! Rules with a `type == "..."` condition only match facts of that type. An alpha
+//! memory whose dependent rules all match one of a set of fact types is routed: facts
+//! of other types, and untyped facts, pass it without being tested. Memories shared
+//! with a rule matching any type keep testing every fact.
+
+use crate::collation::Collation;
+use crate::memory::{fact_value_heap_bytes, hash_map_table_bytes, hash_set_table_bytes};
+use crate::reference_data::ReferenceDataStore;
+use crate::string_match::{self, CompiledRegexes};
+use crate::types::{
+    Condition, FACT_TYPE_FIELD, Fact, FactId, FactValue, NodeId, Operator, Rule, RuleId,
+};
+use crate::value_list::{self, ValueLists};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use tracing::{debug, instrument};
 
+/// Bytes held by a vector of pattern keys
+fn string_vec_bytes(strings: &[String]) -> usize {
+    std::mem::size_of_val(strings) + strings.iter().map(String::capacity).sum::<usize>()
+}
+
 /// Represents a fact pattern for alpha memory indexing
 ///
 /// A FactPattern captures the essential information needed to index facts
 /// based on field values and operators. This enables O(1) lookups during
 /// fact processing.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct FactPattern {
     /// Field name being tested (e.g., "age", "status", "amount")
     pub field: String,
@@ -53,7 +74,18 @@ impl FactPattern {
             | Condition::And { .. }
             | Condition::Or { .. }
             | Condition::Aggregation(_)
-            | Condition::Stream(_) => None,
+            | Condition::Stream(_)
+            | Condition::Sequence(_)
+            | Condition::Accumulate(_) => None,
+        }
+    }
+
+    /// Simple condition testing this pattern
+    pub fn to_condition(&self) -> Condition {
+        Condition::Simple {
+            field: self.field.clone(),
+            operator: self.operator.clone(),
+            value: self.value.clone(),
         }
     }
 
@@ -64,53 +96,78 @@ impl FactPattern {
 
     /// Check if a fact matches this pattern
     pub fn matches_fact(&self, fact: &Fact) -> bool {
-        if let Some(fact_value) = fact.data.fields.get(&self.field) {
-            self.matches_value(fact_value)
+        if let Some(fact_value) = fact.condition_value(&self.field) {
+            self.matches_value(&fact_value)
         } else {
             false
         }
     }
 
+    /// Check if a fact matches this pattern, comparing strings under `collation`,
+    /// resolving `In` and `NotIn` lists from `value_lists` and testing `Matches` with
+    /// the regex compiled into `regexes`
+    pub fn matches_fact_with(
+        &self,
+        fact: &Fact,
+        collation: &Collation,
+        value_lists: &ValueLists,
+        regexes: &CompiledRegexes,
+    ) -> bool {
+        fact.condition_value(&self.field).is_some_and(|fact_value| {
+            self.matches_value_with(&fact_value, collation, value_lists, regexes)
+        })
+    }
+
+    /// Check if a specific value matches this pattern, comparing strings under
+    /// `collation`, resolving `In` and `NotIn` lists from `value_lists` and testing
+    /// `Matches` with the regex compiled into `regexes`
+    pub fn matches_value_with(
+        &self,
+        fact_value: &FactValue,
+        collation: &Collation,
+        value_lists: &ValueLists,
+        regexes: &CompiledRegexes,
+    ) -> bool {
+        if matches!(self.operator, Operator::In | Operator::NotIn) {
+            return value_lists.matches(&self.operator, fact_value, &self.value);
+        }
+        if self.operator == Operator::Matches {
+            return regexes.matches(&self.operator, fact_value, &self.value);
+        }
+        match (fact_value, &self.value) {
+            (FactValue::String(actual), FactValue::String(expected)) if !collation.is_binary() => {
+                collation.matches(&self.operator, actual, expected)
+            }
+            _ => self.matches_value(fact_value),
+        }
+    }
+
+    /// Order a fact value against the pattern value
+    ///
+    /// Decimals are compared exactly; everything else through its `f64` comparable.
+    fn compare_numeric(&self, fact_value: &FactValue) -> Option<std::cmp::Ordering> {
+        if fact_value.is_decimal() || self.value.is_decimal() {
+            return fact_value.partial_cmp(&self.value);
+        }
+        fact_value.to_comparable()?.partial_cmp(&self.value.to_comparable()?)
+    }
+
     /// Check if a specific value matches this pattern
     pub fn matches_value(&self, fact_value: &FactValue) -> bool {
         match self.operator {
             Operator::Equal => fact_value == &self.value,
             Operator::NotEqual => fact_value != &self.value,
             Operator::GreaterThan => {
-                if let (Some(fact_num), Some(pattern_num)) =
-                    (fact_value.to_comparable(), self.value.to_comparable())
-                {
-                    fact_num > pattern_num
-                } else {
-                    false
-                }
+                self.compare_numeric(fact_value).is_some_and(|ordering| ordering.is_gt())
             }
             Operator::LessThan => {
-                if let (Some(fact_num), Some(pattern_num)) =
-                    (fact_value.to_comparable(), self.value.to_comparable())
-                {
-                    fact_num < pattern_num
-                } else {
-                    false
-                }
+                self.compare_numeric(fact_value).is_some_and(|ordering| ordering.is_lt())
             }
             Operator::GreaterThanOrEqual => {
-                if let (Some(fact_num), Some(pattern_num)) =
-                    (fact_value.to_comparable(), self.value.to_comparable())
-                {
-                    fact_num >= pattern_num
-                } else {
-                    false
-                }
+                self.compare_numeric(fact_value).is_some_and(|ordering| ordering.is_ge())
             }
             Operator::LessThanOrEqual => {
-                if let (Some(fact_num), Some(pattern_num)) =
-                    (fact_value.to_comparable(), self.value.to_comparable())
-                {
-                    fact_num <= pattern_num
-                } else {
-                    false
-                }
+                self.compare_numeric(fact_value).is_some_and(|ordering| ordering.is_le())
             }
             Operator::Contains => match (&fact_value, &self.value) {
                 (FactValue::String(fact_str), FactValue::String(pattern_str)) => {
@@ -133,6 +190,15 @@ impl FactPattern {
                 }
                 _ => false,
             },
+            Operator::Within | Operator::OlderThan | Operator::Overlaps => {
+                self.operator.matches_temporal(fact_value, &self.value)
+            }
+            Operator::Matches | Operator::ContainsIgnoreCase => {
+                string_match::matches(&self.operator, fact_value, &self.value)
+            }
+            Operator::In | Operator::NotIn => {
+                value_list::matches_literal(&self.operator, fact_value, &self.value)
+            }
         }
     }
 }
@@ -246,7 +312,7 @@ pub struct AlphaMemoryStats {
 /// - Efficient fact addition/removal propagation
 /// - Memory cleanup when alpha memories are no longer needed
 /// - Optimized indexing for frequently accessed field patterns
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct AlphaMemoryManager {
     /// Alpha memories indexed by pattern key
     alpha_memories: HashMap<String, AlphaMemory>,
@@ -256,14 +322,117 @@ pub struct AlphaMemoryManager {
     equality_index: HashMap<String, HashMap<FactValue, Vec<String>>>, // field -> value -> [pattern_keys]
     /// Range index for numeric comparisons (field -> sorted list of thresholds)
     range_index: HashMap<String, Vec<(f64, Vec<String>)>>, // field -> [(threshold, pattern_keys)]
+    /// Temporal, string matching, `NotIn` and reference table `In` patterns, which can't
+    /// be looked up by value and are checked on every lookup of their field
+    scan_index: HashMap<String, Vec<String>>, // field -> [pattern_keys]
+    /// `In` patterns on literal arrays by every member of the array
+    membership_index: HashMap<String, HashMap<FactValue, Vec<String>>>, // field -> member -> [pattern_keys]
+    /// Literal sets and reference tables `In` and `NotIn` patterns refer to by name
+    value_lists: ValueLists,
+    /// Regexes of `Matches` patterns, compiled when their rules were added
+    regexes: CompiledRegexes,
+    /// Collation for string patterns; the equality index is keyed by collation keys
+    collation: Collation,
     /// Pattern access frequency tracking for optimization
     pattern_frequency: HashMap<String, u64>,
+    /// Fact types, as collation keys, routed to memories whose dependent rules all
+    /// match a single fact type; memories without an entry get facts of every type
+    type_routes: HashMap<String, HashSet<String>>, // pattern_key -> {fact_type}
+    /// Fact type of every routed rule matching a single fact type
+    rule_types: HashMap<RuleId, String>,
+    /// Routed rules with conditions alpha memories don't index, and the fact type
+    /// each matches
+    unindexed_rules: BTreeMap<RuleId, Option<String>>,
     /// Next alpha memory ID
     next_id: NodeId,
     /// Total facts processed
     total_facts_processed: u64,
     /// Total pattern matches found
     total_matches_found: u64,
+    /// Pattern tests skipped for facts of types no dependent rule matches
+    total_tests_skipped: u64,
+}
+
+/// `fact`'s data fields as `type` conditions see them: without the `type` field when
+/// the fact has a type of its own
+fn condition_fields(fact: &Fact) -> impl Iterator<Item = (&str, &FactValue)> {
+    let typed = fact.fact_type.is_some();
+    fact.data
+        .fields
+        .iter()
+        .filter(move |(field, _)| !typed || *field != FACT_TYPE_FIELD)
+        .map(|(field, value)| (field.as_str(), value))
+}
+
+/// Whether facts of `fact_type` reach the alpha memory with key `pattern_key`
+fn routes_to(
+    type_routes: &HashMap<String, HashSet<String>>,
+    pattern_key: &str,
+    fact_type: Option<&str>,
+) -> bool {
+    match type_routes.get(pattern_key) {
+        Some(fact_types) => fact_type.is_some_and(|fact_type| fact_types.contains(fact_type)),
+        None => true,
+    }
+}
+
+/// Threshold `pattern` is kept under in the range index, for comparisons with a
+/// comparable, non-NaN value
+fn range_threshold(pattern: &FactPattern) -> Option<f64> {
+    match pattern.operator {
+        Operator::GreaterThan
+        | Operator::LessThan
+        | Operator::GreaterThanOrEqual
+        | Operator::LessThanOrEqual => pattern.value.to_comparable().filter(|t| !t.is_nan()),
+        _ => None,
+    }
+}
+
+/// Keys of the range patterns in `thresholds` that can match a fact value of `value`
+///
+/// Thresholds are sorted, so `>` and `>=` can only hold for thresholds up to `value`
+/// and `<` and `<=` only for thresholds from `value` on; the rest are never tested.
+/// Candidates are still tested, since decimals compare exactly and ties depend on the
+/// operator.
+fn range_candidates<'a>(
+    thresholds: &'a [(f64, Vec<String>)],
+    alpha_memories: &HashMap<String, AlphaMemory>,
+    value: f64,
+) -> Vec<&'a String> {
+    let below = thresholds.partition_point(|(threshold, _)| *threshold < value);
+    let through = thresholds.partition_point(|(threshold, _)| *threshold <= value);
+    let operator = |pattern_key: &String| {
+        alpha_memories
+            .get(pattern_key)
+            .map(|alpha_memory| alpha_memory.pattern.operator.clone())
+    };
+    let lower = thresholds[..through].iter().flat_map(|(_, keys)| keys).filter(|key| {
+        matches!(
+            operator(key),
+            Some(Operator::GreaterThan | Operator::GreaterThanOrEqual)
+        )
+    });
+    let upper = thresholds[below..].iter().flat_map(|(_, keys)| keys).filter(|key| {
+        matches!(
+            operator(key),
+            Some(Operator::LessThan | Operator::LessThanOrEqual)
+        )
+    });
+    lower.chain(upper).collect()
+}
+
+/// Whether `condition` is matched outside the alpha memories
+fn is_unindexed(condition: &Condition) -> bool {
+    matches!(
+        condition,
+        Condition::Aggregation(_)
+            | Condition::Stream(_)
+            | Condition::Sequence(_)
+            | Condition::Accumulate(_)
+            | Condition::Complex { .. }
+            | Condition::And { .. }
+            | Condition::Or { .. }
+    )
 }
 
 impl AlphaMemoryManager {
@@ -274,10 +443,100 @@ impl AlphaMemoryManager {
             pattern_index: HashMap::new(),
             equality_index: HashMap::new(),
             range_index: HashMap::new(),
+            scan_index: HashMap::new(),
+            membership_index: HashMap::new(),
+            value_lists: ValueLists::default(),
+            regexes: CompiledRegexes::default(),
+            collation: Collation::binary(),
             pattern_frequency: HashMap::new(),
+            type_routes: HashMap::new(),
+            rule_types: HashMap::new(),
+            unindexed_rules: BTreeMap::new(),
             next_id: 1,
             total_facts_processed: 0,
             total_matches_found: 0,
+            total_tests_skipped: 0,
+        }
+    }
+
+    /// Collation string patterns are matched under
+    pub fn collation(&self) -> &Collation {
+        &self.collation
+    }
+
+    /// Change the collation string patterns are matched under
+    ///
+    /// The equality index is rebuilt with the new collation keys. Facts already held
+    /// by alpha memories are not re-evaluated, so set the collation before adding facts.
+    pub fn set_collation(&mut self, collation: Collation) {
+        self.collation = collation;
+        self.equality_index.clear();
+        let patterns: Vec<(String, FactPattern)> = self
+            .alpha_memories
+            .iter()
+            .filter(|(_, alpha_memory)| alpha_memory.pattern.operator == Operator::Equal)
+            .map(|(key, alpha_memory)| (key.clone(), alpha_memory.pattern.clone()))
+            .collect();
+        for (pattern_key, pattern) in patterns {
+            self.add_to_optimized_indexes(&pattern, &pattern_key);
+        }
+        let routed: Vec<String> = self.type_routes.keys().cloned().collect();
+        for pattern_key in routed {
+            self.update_type_route(&pattern_key);
+        }
+    }
+
+    /// Value sets `In` and `NotIn` patterns are matched against
+    pub fn value_lists(&self) -> &ValueLists {
+        &self.value_lists
+    }
+
+    /// Regexes `Matches` patterns are tested with
+    pub fn regexes(&self) -> &CompiledRegexes {
+        &self.regexes
+    }
+
+    /// Keep the regexes compiled for a rule's `Matches` conditions
+    pub fn add_regexes(&mut self, regexes: CompiledRegexes) {
+        self.regexes.extend(regexes);
+    }
+
+    /// Resolve dated reference tables in `In` and `NotIn` patterns as of `as_of`, or
+    /// now when `None`
+    pub fn set_as_of(&mut self, as_of: Option<chrono::DateTime<chrono::Utc>>) {
+        self.value_lists.set_as_of(as_of);
+    }
+
+    /// Compile a literal `In`/`NotIn` array and return the name it is stored under
+    pub fn compile_value_list(&mut self, values: &[FactValue]) -> String {
+        self.value_lists.compile_literal(values)
+    }
+
+    /// Compiled literal lists by generated name, and how many names were generated
+    pub(crate) fn compiled_value_lists(&self) -> (Vec<(String, Vec<FactValue>)>, u64) {
+        self.value_lists.compiled_literals()
+    }
+
+    /// Restore literal lists captured with `compiled_value_lists`
+    pub(crate) fn restore_value_lists(
+        &mut self,
+        literals: &[(String, Vec<FactValue>)],
+        next_literal: u64,
+    ) {
+        self.value_lists.restore_literals(literals, next_literal);
+    }
+
+    /// Resolve named `In` and `NotIn` lists against `reference_data`
+    ///
+    /// Set the store before creating patterns; compiled literals are dropped.
+    pub fn set_reference_data(&mut self, reference_data: ReferenceDataStore) {
+        self.value_lists = ValueLists::new(reference_data);
+    }
+
+    /// Drop every fact from the alpha memories while keeping patterns and indexes
+    pub fn clear_facts(&mut self) {
+        for alpha_memory in self.alpha_memories.values_mut() {
+            alpha_memory.matching_facts.clear();
         }
     }
 
@@ -307,18 +566,39 @@ impl AlphaMemoryManager {
         self.alpha_memories.get_mut(&pattern_key).unwrap()
     }
 
+    /// Recreate the alpha memory with ID `id` of a compiled network
+    pub(crate) fn restore_alpha_memory(
+        &mut self,
+        id: NodeId,
+        pattern: FactPattern,
+        dependent_rules: &[RuleId],
+    ) {
+        let next_id = self.next_id.max(id + 1);
+        self.next_id = id;
+        self.get_or_create_alpha_memory(pattern).dependent_rules.extend(dependent_rules);
+        self.next_id = next_id;
+    }
+
     /// Process a new fact through all alpha memories using optimized indexing
     #[instrument(skip(self, fact))]
     pub fn process_fact_addition(&mut self, fact_id: FactId, fact: &Fact) -> Vec<String> {
         self.total_facts_processed += 1;
         let mut matching_patterns = HashSet::new(); // Use HashSet to avoid duplicates
+        let fact_type = self.fact_type_key(fact);
+        let fact_type = fact_type.as_deref();
 
-        // Use optimized indexing for faster pattern matching
-        for (field_name, field_value) in &fact.data.fields {
+        // Use optimized indexing for faster pattern matching; patterns on the type of a
+        // typed fact are left to the fallback below
+        for (field_name, field_value) in condition_fields(fact) {
             // Check equality patterns using equality index
             if let Some(value_map) = self.equality_index.get(field_name) {
-                if let Some(pattern_keys) = value_map.get(field_value) {
+                if let Some(pattern_keys) =
+                    value_map.get(self.collation.index_value(field_value).as_ref())
+                {
                     for pattern_key in pattern_keys {
+                        if !routes_to(&self.type_routes, pattern_key, fact_type) {
+                            continue;
+                        }
                         // Track pattern access frequency
                         *self.pattern_frequency.entry(pattern_key.clone()).or_insert(0) += 1;
 
@@ -333,25 +613,54 @@ impl AlphaMemoryManager {
                 }
             }
 
+            // Check membership patterns using the membership index
+            if let Some(pattern_keys) = self
+                .membership_index
+                .get(field_name)
+                .and_then(|members| members.get(field_value))
+            {
+                for pattern_key in pattern_keys {
+                    if !routes_to(&self.type_routes, pattern_key, fact_type) {
+                        continue;
+                    }
+                    *self.pattern_frequency.entry(pattern_key.clone()).or_insert(0) += 1;
+
+                    if let Some(alpha_memory) = self.alpha_memories.get_mut(pattern_key) {
+                        if alpha_memory.add_fact(fact_id) {
+                            matching_patterns.insert(pattern_key.clone());
+                            self.total_matches_found += 1;
+                            debug!(
+                                "Fact {} matches membership pattern {}",
+                                fact_id, pattern_key
+                            );
+                        }
+                    }
+                }
+            }
+
             // Check range patterns using range index for numeric values
             if let Some(threshold_list) = self.range_index.get(field_name) {
-                if let Some(_numeric_value) = field_value.to_comparable() {
-                    for (_threshold, pattern_keys) in threshold_list {
-                        for pattern_key in pattern_keys {
-                            // Track pattern access frequency
-                            *self.pattern_frequency.entry(pattern_key.clone()).or_insert(0) += 1;
-
-                            if let Some(alpha_memory) = self.alpha_memories.get_mut(pattern_key) {
-                                if alpha_memory.pattern.matches_fact(fact)
-                                    && alpha_memory.add_fact(fact_id)
-                                {
-                                    matching_patterns.insert(pattern_key.clone());
-                                    self.total_matches_found += 1;
-                                    debug!(
-                                        "Fact {} matches range pattern {}",
-                                        fact_id, pattern_key
-                                    );
-                                }
+                if let Some(numeric_value) = field_value.to_comparable() {
+                    let candidates =
+                        range_candidates(threshold_list, &self.alpha_memories, numeric_value);
+                    for pattern_key in candidates {
+                        if !routes_to(&self.type_routes, pattern_key, fact_type) {
+                            continue;
+                        }
+                        // Track pattern access frequency
+                        *self.pattern_frequency.entry(pattern_key.clone()).or_insert(0) += 1;
+
+                        if let Some(alpha_memory) = self.alpha_memories.get_mut(pattern_key) {
+                            if alpha_memory.pattern.matches_fact_with(
+                                fact,
+                                &self.collation,
+                                &self.value_lists,
+                                &self.regexes,
+                            ) && alpha_memory.add_fact(fact_id)
+                            {
+                                matching_patterns.insert(pattern_key.clone());
+                                self.total_matches_found += 1;
+                                debug!("Fact {} matches range pattern {}", fact_id, pattern_key);
                             }
                         }
                     }
@@ -361,11 +670,28 @@ impl AlphaMemoryManager {
 
         // Fallback: check any remaining patterns not covered by optimized indexes
         for (pattern_key, alpha_memory) in &mut self.alpha_memories {
-            if !matching_patterns.contains(pattern_key) {
+            if !routes_to(&self.type_routes, pattern_key, fact_type) {
+                self.total_tests_skipped += 1;
+                continue;
+            }
+            // Range patterns were already decided for comparable values of their field
+            let range_checked = range_threshold(&alpha_memory.pattern).is_some()
+                && fact
+                    .data
+                    .fields
+                    .get(&alpha_memory.pattern.field)
+                    .is_none_or(|value| value.to_comparable().is_some());
+            if !matching_patterns.contains(pattern_key) && !range_checked {
                 // Track pattern access frequency
                 *self.pattern_frequency.entry(pattern_key.clone()).or_insert(0) += 1;
 
-                if alpha_memory.pattern.matches_fact(fact) && alpha_memory.add_fact(fact_id) {
+                if alpha_memory.pattern.matches_fact_with(
+                    fact,
+                    &self.collation,
+                    &self.value_lists,
+                    &self.regexes,
+                ) && alpha_memory.add_fact(fact_id)
+                {
                     matching_patterns.insert(pattern_key.clone());
                     self.total_matches_found += 1;
                     debug!("Fact {} matches fallback pattern {}", fact_id, pattern_key);
@@ -418,6 +744,96 @@ impl AlphaMemoryManager {
         }
     }
 
+    /// Route facts to `rule`'s alpha memories by the fact type the rule matches
+    ///
+    /// Called once the rule depends on its alpha memories. Memories all of whose
+    /// dependent rules match a single fact type only test facts of those types.
+    pub fn route_rule(&mut self, rule: &Rule) {
+        match rule.fact_type() {
+            Some(fact_type) => self.rule_types.insert(rule.id, fact_type.to_string()),
+            None => self.rule_types.remove(&rule.id),
+        };
+        if rule.conditions.iter().any(is_unindexed) {
+            self.unindexed_rules.insert(rule.id, rule.fact_type().map(str::to_string));
+        } else {
+            self.unindexed_rules.remove(&rule.id);
+        }
+        for pattern_key in self.pattern_keys_of(rule.id) {
+            self.update_type_route(&pattern_key);
+        }
+    }
+
+    /// Stop routing facts to `rule_id`'s alpha memories, dropping its dependencies
+    ///
+    /// Memories no rule depends on any more are left in place but get no facts.
+    pub fn unroute_rule(&mut self, rule_id: RuleId) {
+        self.rule_types.remove(&rule_id);
+        self.unindexed_rules.remove(&rule_id);
+        for pattern_key in self.pattern_keys_of(rule_id) {
+            if let Some(alpha_memory) = self.alpha_memories.get_mut(&pattern_key) {
+                alpha_memory.dependent_rules.remove(&rule_id);
+            }
+            self.update_type_route(&pattern_key);
+        }
+    }
+
+    /// Fact types routed to the alpha memory for `pattern`, `None` when it gets facts
+    /// of every type
+    pub fn routed_fact_types(&self, pattern: &FactPattern) -> Option<&HashSet<String>> {
+        self.type_routes.get(&pattern.to_key())
+    }
+
+    /// Routed rules matched outside the alpha memories that may match `fact`, in rule
+    /// ID order
+    pub fn unindexed_rules_for(&self, fact: &Fact) -> Vec<RuleId> {
+        let fact_type = self.fact_type_key(fact);
+        self.unindexed_rules
+            .iter()
+            .filter(|(_, rule_type)| match rule_type {
+                Some(rule_type) => {
+                    fact_type.as_deref() == Some(self.collation.key(rule_type).as_ref())
+                }
+                None => true,
+            })
+            .map(|(rule_id, _)| *rule_id)
+            .collect()
+    }
+
+    fn pattern_keys_of(&self, rule_id: RuleId) -> Vec<String> {
+        self.alpha_memories
+            .iter()
+            .filter(|(_, alpha_memory)| alpha_memory.dependent_rules.contains(&rule_id))
+            .map(|(pattern_key, _)| pattern_key.clone())
+            .collect()
+    }
+
+    /// Route the memory with key `pattern_key` to the fact types of its dependent
+    /// rules, or to every type when one of them matches any type
+    fn update_type_route(&mut self, pattern_key: &str) {
+        let Some(alpha_memory) = self.alpha_memories.get(pattern_key) else {
+            self.type_routes.remove(pattern_key);
+            return;
+        };
+        let fact_types: Option<HashSet<String>> = alpha_memory
+            .dependent_rules
+            .iter()
+            .map(|rule_id| {
+                self.rule_types
+                    .get(rule_id)
+                    .map(|fact_type| self.collation.key(fact_type).into_owned())
+            })
+            .collect();
+        match fact_types {
+            Some(fact_types) => self.type_routes.insert(pattern_key.to_string(), fact_types),
+            None => self.type_routes.remove(pattern_key),
+        };
+    }
+
+    /// `fact`'s type as a collation key, matching the keys type routes are stored by
+    fn fact_type_key<'a>(&self, fact: &'a Fact) -> Option<Cow<'a, str>> {
+        fact.condition_type().map(|fact_type| self.collation.key(fact_type))
+    }
+
     /// Clean up unused alpha memories
     pub fn cleanup_unused_memories(&mut self) -> usize {
         let mut removed_count = 0;
@@ -457,10 +873,34 @@ impl AlphaMemoryManager {
             total_patterns_indexed: self.pattern_index.len(),
             total_facts_processed: self.total_facts_processed,
             total_matches_found: self.total_matches_found,
+            total_tests_skipped: self.total_tests_skipped,
             memory_stats,
         }
     }
 
+    /// Every alpha memory, in no particular order
+    pub fn alpha_memories(&self) -> impl Iterator<Item = &AlphaMemory> {
+        self.alpha_memories.values()
+    }
+
+    /// Bytes held by alpha memories, split evenly among the rules depending on each
+    pub fn memory_by_rule(&self) -> HashMap<RuleId, usize> {
+        let mut usage: HashMap<RuleId, usize> = HashMap::new();
+        for (key, alpha_memory) in &self.alpha_memories {
+            if alpha_memory.dependent_rules.is_empty() {
+                continue;
+            }
+            let bytes = key.capacity()
+                + hash_set_table_bytes(&alpha_memory.matching_facts)
+                + hash_set_table_bytes(&alpha_memory.dependent_rules);
+            let share = bytes / alpha_memory.dependent_rules.len();
+            for &rule_id in &alpha_memory.dependent_rules {
+                *usage.entry(rule_id).or_default() += share;
+            }
+        }
+        usage
+    }
+
     /// Get memory usage estimate in bytes
     pub fn estimate_memory_usage(&self) -> usize {
         let mut total_size = 0;
@@ -468,20 +908,49 @@ impl AlphaMemoryManager {
         // Base structure size
         total_size += std::mem::size_of::<Self>();
 
-        // Alpha memories
+        // Alpha memories, sized by reserved capacity rather than occupancy
+        total_size += hash_map_table_bytes(&self.alpha_memories);
         for (key, alpha_memory) in &self.alpha_memories {
-            total_size += key.len();
-            total_size += std::mem::size_of::<AlphaMemory>();
-            total_size += alpha_memory.matching_facts.len() * std::mem::size_of::<FactId>();
-            total_size += alpha_memory.dependent_rules.len() * std::mem::size_of::<RuleId>();
+            total_size += key.capacity();
+            total_size += hash_set_table_bytes(&alpha_memory.matching_facts);
+            total_size += hash_set_table_bytes(&alpha_memory.dependent_rules);
         }
 
         // Pattern index
+        total_size += hash_map_table_bytes(&self.pattern_index);
         for (field, patterns) in &self.pattern_index {
-            total_size += field.len();
-            total_size += patterns.iter().map(|p| p.len()).sum::<usize>();
+            total_size += field.capacity() + string_vec_bytes(patterns);
         }
 
+        // Equality and range indexes
+        total_size += hash_map_table_bytes(&self.equality_index);
+        for (field, values) in &self.equality_index {
+            total_size += field.capacity() + hash_map_table_bytes(values);
+            for (value, patterns) in values {
+                total_size += fact_value_heap_bytes(value) + string_vec_bytes(patterns);
+            }
+        }
+        total_size += hash_map_table_bytes(&self.range_index);
+        for (field, thresholds) in &self.range_index {
+            total_size += field.capacity();
+            total_size += thresholds.capacity() * std::mem::size_of::<(f64, Vec<String>)>();
+            for (_, patterns) in thresholds {
+                total_size += string_vec_bytes(patterns);
+            }
+        }
+        total_size += hash_map_table_bytes(&self.scan_index);
+        for (field, patterns) in &self.scan_index {
+            total_size += field.capacity() + string_vec_bytes(patterns);
+        }
+        total_size += hash_map_table_bytes(&self.membership_index);
+        for (field, members) in &self.membership_index {
+            total_size += field.capacity() + hash_map_table_bytes(members);
+            for (member, patterns) in members {
+                total_size += fact_value_heap_bytes(member) + string_vec_bytes(patterns);
+            }
+        }
+        total_size += hash_map_table_bytes(&self.pattern_frequency);
+
         total_size
     }
 
@@ -493,7 +962,7 @@ impl AlphaMemoryManager {
                 self.equality_index
                     .entry(pattern.field.clone())
                     .or_default()
-                    .entry(pattern.value.clone())
+                    .entry(self.collation.index_value(&pattern.value).into_owned())
                     .or_default()
                     .push(pattern_key.to_string());
             }
@@ -502,7 +971,7 @@ impl AlphaMemoryManager {
             | Operator::GreaterThanOrEqual
             | Operator::LessThanOrEqual => {
                 // Add to range index for numeric comparisons
-                if let Some(threshold) = pattern.value.to_comparable() {
+                if let Some(threshold) = range_threshold(pattern) {
                     let range_list = self.range_index.entry(pattern.field.clone()).or_default();
 
                     // Find the right position to insert (keep sorted by threshold)
@@ -512,9 +981,8 @@ impl AlphaMemoryManager {
                         })
                         .unwrap_or_else(|e| e);
 
-                    if insert_pos < range_list.len()
-                        && (range_list[insert_pos].0 - threshold).abs() < f64::EPSILON
-                    {
+                    // Thresholds are merged only when equal, as lookups binary search them
+                    if insert_pos < range_list.len() && range_list[insert_pos].0 == threshold {
                         // Same threshold exists, add to existing entry
                         range_list[insert_pos].1.push(pattern_key.to_string());
                     } else {
@@ -523,6 +991,49 @@ impl AlphaMemoryManager {
                     }
                 }
             }
+            Operator::Within
+            | Operator::OlderThan
+            | Operator::Overlaps
+            | Operator::Contains
+            | Operator::StartsWith
+            | Operator::EndsWith
+            | Operator::Matches
+            | Operator::ContainsIgnoreCase
+            | Operator::NotIn => {
+                // Temporal matches change as time passes, substring or regex matches
+                // have no single key and `NotIn` matches everything outside its set, so
+                // none of them can be bucketed by value
+                self.scan_index
+                    .entry(pattern.field.clone())
+                    .or_default()
+                    .push(pattern_key.to_string());
+            }
+            Operator::In => {
+                // Index literal sets under every member so a lookup is one hash probe.
+                // Reference tables can be reloaded at any time, so they are probed on
+                // every lookup instead of being copied into the index.
+                let members: Vec<FactValue> = match &pattern.value {
+                    FactValue::Array(values) => values.clone(),
+                    FactValue::String(name) => match self.value_lists.literal(name) {
+                        Some(set) => set.iter().cloned().collect(),
+                        None => {
+                            self.scan_index
+                                .entry(pattern.field.clone())
+                                .or_default()
+                                .push(pattern_key.to_string());
+                            return;
+                        }
+                    },
+                    _ => Vec::new(),
+                };
+                let index = self.membership_index.entry(pattern.field.clone()).or_default();
+                for member in members {
+                    let pattern_keys = index.entry(member).or_default();
+                    if !pattern_keys.iter().any(|key| key == pattern_key) {
+                        pattern_keys.push(pattern_key.to_string());
+                    }
+                }
+            }
             _ => {
                 // Other operators don't have specialized indexes yet
                 // They will be handled by the fallback linear search
@@ -534,40 +1045,106 @@ impl AlphaMemoryManager {
     /// This is the core RETE optimization: O(1) hash lookups instead of O(n) iteration
     pub fn get_candidate_rules_for_fact(&self, fact: &Fact) -> Vec<RuleId> {
         let mut candidate_rules = HashSet::new();
-
-        for (field_name, field_value) in &fact.data.fields {
+        let fact_type = self.fact_type_key(fact);
+        let fact_type = fact_type.as_deref();
+        let routed = |pattern_key: &String| routes_to(&self.type_routes, pattern_key, fact_type);
+
+        // The type of a typed fact isn't among its data fields, but `type` patterns
+        // test it
+        let type_value = fact.fact_type().map(|fact_type| FactValue::String(fact_type.into()));
+        let type_field = type_value.as_ref().map(|value| (FACT_TYPE_FIELD, value));
+        for (field_name, field_value) in condition_fields(fact).chain(type_field) {
             // Check equality patterns using equality index (O(1) lookup)
             if let Some(value_map) = self.equality_index.get(field_name) {
-                if let Some(pattern_keys) = value_map.get(field_value) {
+                if let Some(pattern_keys) =
+                    value_map.get(self.collation.index_value(field_value).as_ref())
+                {
                     for pattern_key in pattern_keys {
-                        if let Some(alpha_memory) = self.alpha_memories.get(pattern_key) {
-                            candidate_rules.extend(alpha_memory.dependent_rules.iter().copied());
+                        if let Some(alpha_memory) =
+                            self.alpha_memories.get(pattern_key).filter(|_| routed(pattern_key))
+                        {
+                            self.extend_routed(&mut candidate_rules, alpha_memory, fact_type);
                         }
                     }
                 }
             }
 
+            // Check membership patterns using the membership index (O(1) lookup)
+            if let Some(pattern_keys) = self
+                .membership_index
+                .get(field_name)
+                .and_then(|members| members.get(field_value))
+            {
+                for pattern_key in pattern_keys {
+                    if let Some(alpha_memory) =
+                        self.alpha_memories.get(pattern_key).filter(|_| routed(pattern_key))
+                    {
+                        self.extend_routed(&mut candidate_rules, alpha_memory, fact_type);
+                    }
+                }
+            }
+
             // Check range patterns using range index for numeric values
             if let Some(threshold_list) = self.range_index.get(field_name) {
-                if let Some(_numeric_value) = field_value.to_comparable() {
-                    for (_threshold, pattern_keys) in threshold_list {
-                        for pattern_key in pattern_keys {
-                            if let Some(alpha_memory) = self.alpha_memories.get(pattern_key) {
-                                // Only check pattern match for range patterns (more expensive but necessary)
-                                if alpha_memory.pattern.matches_fact(fact) {
-                                    candidate_rules
-                                        .extend(alpha_memory.dependent_rules.iter().copied());
-                                }
+                if let Some(numeric_value) = field_value.to_comparable() {
+                    for pattern_key in
+                        range_candidates(threshold_list, &self.alpha_memories, numeric_value)
+                    {
+                        if let Some(alpha_memory) =
+                            self.alpha_memories.get(pattern_key).filter(|_| routed(pattern_key))
+                        {
+                            // Only check pattern match for range patterns (more expensive but necessary)
+                            if alpha_memory.pattern.matches_fact_with(
+                                fact,
+                                &self.collation,
+                                &self.value_lists,
+                                &self.regexes,
+                            ) {
+                                self.extend_routed(&mut candidate_rules, alpha_memory, fact_type);
                             }
                         }
                     }
                 }
             }
+
+            // Check temporal and string matching patterns one by one
+            if let Some(pattern_keys) = self.scan_index.get(field_name) {
+                for pattern_key in pattern_keys {
+                    if let Some(alpha_memory) =
+                        self.alpha_memories.get(pattern_key).filter(|_| routed(pattern_key))
+                    {
+                        if alpha_memory.pattern.matches_fact_with(
+                            fact,
+                            &self.collation,
+                            &self.value_lists,
+                            &self.regexes,
+                        ) {
+                            self.extend_routed(&mut candidate_rules, alpha_memory, fact_type);
+                        }
+                    }
+                }
+            }
         }
 
         candidate_rules.into_iter().collect()
     }
 
+    /// Add the rules depending on `alpha_memory` that match facts of `fact_type`
+    fn extend_routed(
+        &self,
+        candidate_rules: &mut HashSet<RuleId>,
+        alpha_memory: &AlphaMemory,
+        fact_type: Option<&str>,
+    ) {
+        candidate_rules.extend(
+            alpha_memory.dependent_rules.iter().copied().filter(|rule_id| {
+                self.rule_types.get(rule_id).is_none_or(|rule_type| {
+                    fact_type == Some(self.collation.key(rule_type).as_ref())
+                })
+            }),
+        );
+    }
+
     /// Get pattern access frequency statistics (most frequently accessed patterns)
     pub fn get_pattern_frequency_stats(&self) -> Vec<(String, u64)> {
         let mut frequencies: Vec<_> = self
@@ -631,6 +1208,8 @@ pub struct AlphaMemoryManagerStats {
     pub total_patterns_indexed: usize,
     pub total_facts_processed: u64,
     pub total_matches_found: u64,
+    /// Pattern tests skipped for facts of types no dependent rule matches
+    pub total_tests_skipped: u64,
     pub memory_stats: Vec<AlphaMemoryStats>,
 }
 
@@ -652,6 +1231,7 @@ impl std::fmt::Display for AlphaMemoryManagerStats {
         writeln!(f, "Total Patterns Indexed: {}", self.total_patterns_indexed)?;
         writeln!(f, "Total Facts Processed: {}", self.total_facts_processed)?;
         writeln!(f, "Total Matches Found: {}", self.total_matches_found)?;
+        writeln!(f, "Total Tests Skipped: {}", self.total_tests_skipped)?;
 
         if self.total_facts_processed > 0 {
             let match_rate =
@@ -692,6 +1272,7 @@ mod tests {
             id,
             external_id: Some(format!("fact_{id}")),
             timestamp: chrono::Utc::now(),
+            fact_type: None,
             data: crate::types::FactData { fields },
         }
     }
@@ -817,4 +1398,48 @@ mod tests {
         }
         assert_eq!(manager.cleanup_unused_memories(), 1);
     }
+
+    #[test]
+    fn test_type_routes_follow_dependent_rules() {
+        let mut manager = AlphaMemoryManager::new();
+        manager.set_collation(Collation::case_insensitive());
+        let age = FactPattern {
+            field: "age".to_string(),
+            operator: Operator::GreaterThan,
+            value: FactValue::Integer(21),
+        };
+        let typed = crate::rule_dsl::parse_rule(
+            r#"rule "Adult" id 1 when type == "Person" and age > 21 then set adult = true"#,
+        )
+        .unwrap();
+        for condition in &typed.conditions {
+            let pattern = FactPattern::from_condition(condition).unwrap();
+            manager.get_or_create_alpha_memory(pattern).add_dependent_rule(1);
+        }
+        manager.route_rule(&typed);
+        let routed: Vec<&String> = manager.routed_fact_types(&age).unwrap().iter().collect();
+        assert_eq!(routed, vec!["person"]);
+
+        // Types are compared under the collation, and other types skip the memories
+        let person = create_test_fact(1, 25, "active").with_type("PERSON");
+        assert_eq!(manager.process_fact_addition(1, &person).len(), 2);
+        assert_eq!(manager.get_candidate_rules_for_fact(&person), vec![1]);
+        let robot = create_test_fact(2, 25, "active").with_type("robot");
+        assert!(manager.process_fact_addition(2, &robot).is_empty());
+        assert!(manager.get_candidate_rules_for_fact(&robot).is_empty());
+        assert_eq!(manager.get_statistics().total_tests_skipped, 2);
+
+        // A rule matching any type on the same pattern opens the memory to every fact
+        manager.register_rule_dependency(&age, 2);
+        let untyped = crate::rule_dsl::parse_rule(
+            r#"rule "Any adult" id 2 when age > 21 then set adult = true"#,
+        )
+        .unwrap();
+        manager.route_rule(&untyped);
+        assert_eq!(manager.routed_fact_types(&age), None);
+        assert_eq!(manager.get_candidate_rules_for_fact(&robot), vec![2]);
+
+        manager.unroute_rule(2);
+        assert!(manager.routed_fact_types(&age).is_some());
+    }
 }
@@ -0,0 +1,174 @@
+//! Per-batch summaries of rule execution results
+//!
+//! A large batch can produce millions of [`RuleExecutionResult`]s, and dashboards
+//! mostly want to know how often each rule fired, how many facts it touched and which
+//! fields it changed. [`BatchSummary`] aggregates those figures once, in the engine, so
+//! clients don't re-aggregate every individual result.
+//!
+//! A field counts as changed when an action sets, increments or appends to it, writes
+//! a calculator result to it or lists it among the fields of an updated fact.
+
+use crate::rete_nodes::{ActionResult, RuleExecutionResult};
+use crate::types::{FactId, RuleId};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+/// Execution figures for one rule within a batch
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleSummary {
+    pub rule_id: RuleId,
+    /// Number of times the rule fired
+    pub times_fired: usize,
+    /// Number of distinct facts the rule fired for
+    pub facts_affected: usize,
+    /// Number of changes per field name
+    pub fields_changed: BTreeMap<String, usize>,
+}
+
+/// Execution figures for a whole batch, broken down by rule
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchSummary {
+    /// Number of facts in the batch
+    pub facts_processed: usize,
+    /// Number of rule execution results
+    pub results_generated: usize,
+    /// Number of distinct facts at least one rule fired for
+    pub facts_affected: usize,
+    /// Number of changes per field name across all rules
+    pub fields_changed: BTreeMap<String, usize>,
+    /// Rules that fired, ordered by rule id
+    pub rules: Vec<RuleSummary>,
+}
+
+impl BatchSummary {
+    /// Summarize the results produced by a batch of `facts_processed` facts
+    pub fn from_results(facts_processed: usize, results: &[RuleExecutionResult]) -> Self {
+        let mut rules: BTreeMap<RuleId, (RuleSummary, HashSet<FactId>)> = BTreeMap::new();
+        let mut facts_affected = HashSet::new();
+        let mut fields_changed = BTreeMap::new();
+
+        for result in results {
+            let (rule, rule_facts) = rules.entry(result.rule_id).or_insert_with(|| {
+                (
+                    RuleSummary { rule_id: result.rule_id, ..RuleSummary::default() },
+                    HashSet::new(),
+                )
+            });
+            rule.times_fired += 1;
+            rule_facts.insert(result.fact_id);
+            facts_affected.insert(result.fact_id);
+
+            for field in result.actions_executed.iter().flat_map(changed_fields) {
+                *rule.fields_changed.entry(field.to_string()).or_default() += 1;
+                *fields_changed.entry(field.to_string()).or_default() += 1;
+            }
+        }
+
+        Self {
+            facts_processed,
+            results_generated: results.len(),
+            facts_affected: facts_affected.len(),
+            fields_changed,
+            rules: rules
+                .into_values()
+                .map(|(rule, rule_facts)| RuleSummary { facts_affected: rule_facts.len(), ..rule })
+                .collect(),
+        }
+    }
+
+    /// Figures for `rule_id`, if it fired in the batch
+    pub fn rule(&self, rule_id: RuleId) -> Option<&RuleSummary> {
+        self.rules
+            .binary_search_by_key(&rule_id, |rule| rule.rule_id)
+            .ok()
+            .map(|index| &self.rules[index])
+    }
+}
+
+/// Fields an action result changed
+fn changed_fields(action: &ActionResult) -> Vec<&str> {
+    match action {
+        ActionResult::FieldSet { field, .. }
+        | ActionResult::FieldIncremented { field, .. }
+        | ActionResult::ArrayAppended { field, .. }
+        | ActionResult::ObjectMerged { field, .. } => vec![field.as_str()],
+        ActionResult::CalculatorResult { output_field, .. } => vec![output_field.as_str()],
+        ActionResult::FactUpdated { updated_fields, .. } => {
+            updated_fields.iter().map(String::as_str).collect()
+        }
+        ActionResult::Logged { .. }
+        | ActionResult::LazyLogged { .. }
+        | ActionResult::FactCreated { .. }
+        | ActionResult::FactDeleted { .. }
+        | ActionResult::NotificationSent { .. }
+        | ActionResult::WebhookDispatched { .. } => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::FactValue;
+
+    fn result(rule_id: RuleId, fact_id: FactId, actions: Vec<ActionResult>) -> RuleExecutionResult {
+        RuleExecutionResult { rule_id, fact_id, actions_executed: actions, ..Default::default() }
+    }
+
+    fn set(fact_id: FactId, field: &str) -> ActionResult {
+        ActionResult::FieldSet {
+            fact_id,
+            field: field.to_string(),
+            value: FactValue::Boolean(true),
+        }
+    }
+
+    #[test]
+    fn test_summary_groups_results_by_rule() {
+        let results = vec![
+            result(2, 10, vec![set(10, "flagged")]),
+            result(1, 10, vec![ActionResult::logged("seen".to_string())]),
+            result(2, 11, vec![set(11, "flagged"), set(11, "reviewed")]),
+            result(2, 11, vec![set(11, "flagged")]),
+            result(
+                3,
+                12,
+                vec![ActionResult::FactUpdated {
+                    fact_id: 12,
+                    updated_fields: vec!["status".to_string(), "reviewed".to_string()],
+                }],
+            ),
+        ];
+        let summary = BatchSummary::from_results(5, &results);
+
+        assert_eq!(summary.facts_processed, 5);
+        assert_eq!(summary.results_generated, 5);
+        assert_eq!(summary.facts_affected, 3);
+        assert_eq!(
+            summary.fields_changed,
+            BTreeMap::from([
+                ("flagged".to_string(), 3),
+                ("reviewed".to_string(), 2),
+                ("status".to_string(), 1),
+            ])
+        );
+        assert_eq!(
+            summary.rules.iter().map(|rule| rule.rule_id).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+
+        let flagging = summary.rule(2).unwrap();
+        assert_eq!(flagging.times_fired, 3);
+        assert_eq!(flagging.facts_affected, 2);
+        assert_eq!(flagging.fields_changed["flagged"], 3);
+        assert!(summary.rule(1).unwrap().fields_changed.is_empty());
+        assert!(summary.rule(4).is_none());
+    }
+
+    #[test]
+    fn test_empty_batch() {
+        let summary = BatchSummary::from_results(7, &[]);
+        assert_eq!(summary.facts_processed, 7);
+        assert_eq!(summary.results_generated, 0);
+        assert!(summary.rules.is_empty());
+    }
+}
@@ -22,11 +22,21 @@
 //! - **BetaMemory**: Stores partial matches for incremental processing
 //! - **TerminalNode**: Executes actions when all conditions are satisfied
 
+use crate::memory::{hash_map_table_bytes, hash_set_table_bytes};
 use crate::memory_pools::MemoryPoolManager;
+use crate::memory_report::BetaMemoryUsage;
 use crate::types::{Fact, FactId, FactValue, NodeId, Rule, RuleId};
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 use tracing::{debug, instrument};
 
+/// Working memory shared between the alpha network, beta network and terminal nodes
+///
+/// Facts are reference counted so joins and activations borrow the stored fact
+/// rather than cloning its data for every token batch.
+pub type FactMemory = HashMap<FactId, Arc<Fact>>;
+
 /// Token represents a partial match in the RETE network
 ///
 /// A token carries the current state of pattern matching for a rule,
@@ -61,6 +71,18 @@ impl Token {
         }
     }
 
+    /// Heap bytes owned by this token, including its chain of parent tokens
+    pub fn heap_bytes(&self) -> usize {
+        let mut size = self.facts.capacity() * std::mem::size_of::<FactId>();
+        let mut parent = self.parent_token.as_deref();
+        while let Some(token) = parent {
+            size += std::mem::size_of::<Token>()
+                + token.facts.capacity() * std::mem::size_of::<FactId>();
+            parent = token.parent_token.as_deref();
+        }
+        size
+    }
+
     /// Create a new token by extending this one with a fact
     pub fn extend(&self, fact_id: FactId) -> Self {
         let mut new_token = self.clone();
@@ -98,7 +120,7 @@ impl Token {
 }
 
 /// Beta node types in the RETE network
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum BetaNodeType {
     /// Root node (no conditions)
     Root,
@@ -148,6 +170,13 @@ impl BetaNode {
         }
     }
 
+    /// Heap bytes owned by this node's children list and token keys
+    pub fn heap_bytes(&self) -> usize {
+        self.children.capacity() * std::mem::size_of::<NodeId>()
+            + hash_set_table_bytes(&self.tokens)
+            + self.tokens.iter().map(String::capacity).sum::<usize>()
+    }
+
     /// Add a child node
     pub fn add_child(&mut self, child_id: NodeId) {
         if !self.children.contains(&child_id) {
@@ -258,7 +287,7 @@ impl JoinNode {
         &mut self,
         token: &Token,
         fact_id: FactId,
-        facts: &HashMap<FactId, Fact>,
+        facts: &FactMemory,
     ) -> Option<Token> {
         self.beta_node.join_attempts += 1;
 
@@ -311,7 +340,7 @@ impl JoinTest {
     }
 
     /// Evaluate this join test against a token and fact
-    pub fn evaluate(&self, token: &Token, fact_id: FactId, facts: &HashMap<FactId, Fact>) -> bool {
+    pub fn evaluate(&self, token: &Token, fact_id: FactId, facts: &FactMemory) -> bool {
         // Get the current fact
         let current_fact = match facts.get(&fact_id) {
             Some(fact) => fact,
@@ -403,7 +432,7 @@ impl JoinOperator {
 }
 
 /// Beta memory for storing partial matches
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct BetaMemory {
     /// Tokens stored in this memory
     pub tokens: HashMap<String, Token>,
@@ -497,7 +526,7 @@ pub struct BetaMemoryStats {
 }
 
 /// Beta network manager
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct BetaNetworkManager {
     /// Beta nodes indexed by ID
     pub beta_nodes: HashMap<NodeId, BetaNode>,
@@ -507,10 +536,14 @@ pub struct BetaNetworkManager {
     pub beta_memories: HashMap<NodeId, BetaMemory>,
     /// Root node ID
     pub root_node_id: Option<NodeId>,
+    /// Terminal node for each rule with a beta network
+    pub terminal_nodes: HashMap<RuleId, NodeId>,
     /// Next node ID
     pub next_node_id: NodeId,
     /// Memory pool manager for token vector allocation
     pub memory_pools: MemoryPoolManager,
+    /// Facts propagated so far, which new tokens are joined against
+    pub fact_memory: FactMemory,
     /// Performance statistics
     pub total_tokens_processed: u64,
     pub total_joins_performed: u64,
@@ -525,8 +558,10 @@ impl BetaNetworkManager {
             join_nodes: HashMap::new(),
             beta_memories: HashMap::new(),
             root_node_id: None,
+            terminal_nodes: HashMap::new(),
             next_node_id: 1,
             memory_pools: MemoryPoolManager::new(),
+            fact_memory: FactMemory::new(),
             total_tokens_processed: 0,
             total_joins_performed: 0,
             total_activations: 0,
@@ -540,8 +575,10 @@ impl BetaNetworkManager {
             join_nodes: HashMap::new(),
             beta_memories: HashMap::new(),
             root_node_id: None,
+            terminal_nodes: HashMap::new(),
             next_node_id: 1,
             memory_pools: MemoryPoolManager::with_high_throughput_config(),
+            fact_memory: FactMemory::new(),
             total_tokens_processed: 0,
             total_joins_performed: 0,
             total_activations: 0,
@@ -580,6 +617,8 @@ impl BetaNetworkManager {
 
         let terminal_node = BetaNode::new(node_id, BetaNodeType::Terminal { rule_id });
         self.beta_nodes.insert(node_id, terminal_node);
+        self.beta_memories.insert(node_id, BetaMemory::new());
+        self.terminal_nodes.insert(rule_id, node_id);
 
         node_id
     }
@@ -602,74 +641,77 @@ impl BetaNetworkManager {
     }
 
     /// Process a token through the beta network
+    ///
+    /// `facts` is the delta being propagated. It is added to the network's fact
+    /// memory first, and join nodes then join the token against every fact seen so
+    /// far, so a match whose facts arrive in separate calls is still found. Tokens
+    /// are routed to explicit child nodes, and terminal nodes only report matches
+    /// they have not activated before.
     #[instrument(skip(self, token, facts))]
-    pub fn process_token(&mut self, token: Token, facts: &HashMap<FactId, Fact>) -> Vec<Token> {
+    pub fn process_token(&mut self, token: Token, facts: &FactMemory) -> Vec<Token> {
         self.total_tokens_processed += 1;
         let mut completed_tokens = self.memory_pools.token_vecs.get();
-        let mut tokens_to_process = self.memory_pools.token_vecs.get();
-        tokens_to_process.push(token);
+        let mut pending: Vec<(NodeId, Token)> = Vec::new();
+        self.fact_memory.extend(facts.iter().map(|(&id, fact)| (id, fact.clone())));
 
         debug!("Processing token through beta network");
 
-        while let Some(current_token) = tokens_to_process.pop() {
+        if let Some(node_id) = self.find_current_beta_node(&token) {
+            pending.push((node_id, token));
+        }
+
+        while let Some((node_id, current_token)) = pending.pop() {
             debug!(
-                "Processing token with {} facts for rule {}",
+                "Processing token with {} facts for rule {} at node {}",
                 current_token.facts.len(),
-                current_token.rule_id
+                current_token.rule_id,
+                node_id
             );
 
-            // Find the current node in the beta network based on the token's progress
-            let current_node_id = self.find_current_beta_node(&current_token);
-
-            if let Some(node_id) = current_node_id {
-                // Get the node and process the token
-                if let Some(node) = self.beta_nodes.get(&node_id) {
-                    match &node.node_type {
-                        BetaNodeType::Root => {
-                            // Root node - pass token to children
-                            for &_child_id in &node.children {
-                                tokens_to_process.push(current_token.clone());
-                            }
+            if let Some(node) = self.beta_nodes.get(&node_id) {
+                match &node.node_type {
+                    BetaNodeType::Root => {
+                        // Root node - route the token to each child
+                        for &child_id in &node.children {
+                            pending.push((child_id, current_token.clone()));
                         }
-                        BetaNodeType::Terminal { .. } => {
-                            // Terminal node - token is complete
+                    }
+                    BetaNodeType::Terminal { .. } => {
+                        // Terminal node - token is complete if it is a new match
+                        if self.record_terminal_token(node_id, current_token.clone()) {
                             completed_tokens.push(current_token);
-                            self.total_activations += 1;
-                        }
-                        BetaNodeType::Join { alpha_memory_id: _, condition_index } => {
-                            // Join node - this would be handled by join_nodes HashMap
-                            debug!("Token reached join node for condition {}", condition_index);
                         }
                     }
-                } else if self.join_nodes.contains_key(&node_id) {
-                    // Process through join node - need to handle borrow checker
-                    let (processed_tokens, child_node_ids, joins_performed) = {
-                        let join_node = self.join_nodes.get_mut(&node_id).unwrap();
-                        let (processed_tokens, joins_performed) =
-                            Self::process_token_through_join_node(&current_token, join_node, facts);
-                        let child_node_ids = join_node.beta_node.children.clone();
-                        (processed_tokens, child_node_ids, joins_performed)
-                    };
-
-                    // Update statistics
-                    self.total_joins_performed += joins_performed;
-
-                    // Add resulting tokens to processing queue
-                    for new_token in processed_tokens {
-                        // Store token in beta memory
-                        if let Some(memory) = self.beta_memories.get_mut(&node_id) {
-                            memory.add_token(new_token.clone());
-                        }
+                    BetaNodeType::Join { alpha_memory_id: _, condition_index } => {
+                        // Join node - this would be handled by join_nodes HashMap
+                        debug!("Token reached join node for condition {}", condition_index);
+                    }
+                }
+            } else if self.join_nodes.contains_key(&node_id) {
+                // Process through join node - need to handle borrow checker
+                let (processed_tokens, child_node_ids, joins_performed) = {
+                    let join_node = self.join_nodes.get_mut(&node_id).unwrap();
+                    let (processed_tokens, joins_performed) = Self::process_token_through_join_node(
+                        &current_token,
+                        join_node,
+                        &self.fact_memory,
+                    );
+                    let child_node_ids = join_node.beta_node.children.clone();
+                    (processed_tokens, child_node_ids, joins_performed)
+                };
+
+                // Update statistics
+                self.total_joins_performed += joins_performed;
+
+                for new_token in processed_tokens {
+                    // Store token in beta memory
+                    if let Some(memory) = self.beta_memories.get_mut(&node_id) {
+                        memory.add_token(new_token.clone());
+                    }
 
-                        // Pass to children if not complete
-                        if !self.is_token_complete(&new_token, facts) {
-                            for &_child_id in &child_node_ids {
-                                tokens_to_process.push(new_token.clone());
-                            }
-                        } else {
-                            completed_tokens.push(new_token);
-                            self.total_activations += 1;
-                        }
+                    // Pass the extended token on to the children of this join
+                    for &child_id in &child_node_ids {
+                        pending.push((child_id, new_token.clone()));
                     }
                 }
             }
@@ -680,23 +722,54 @@ impl BetaNetworkManager {
             completed_tokens.len()
         );
 
-        // Return the tokens_to_process vector to the pool before returning results
-        self.memory_pools.token_vecs.return_vec(tokens_to_process);
-
         // Convert completed_tokens from pooled to regular Vec for return
         let result = completed_tokens.clone();
         self.memory_pools.token_vecs.return_vec(completed_tokens);
         result
     }
 
-    /// Find the current beta node for a token based on its progress
+    /// Record a complete token at its rule's terminal node
+    ///
+    /// Returns `false` when the same match has already been activated, so a terminal
+    /// node fires once per match rather than once per propagation. Rules without a
+    /// beta network have no terminal memory and always activate.
+    pub fn activate_terminal(&mut self, token: Token) -> bool {
+        match self.terminal_nodes.get(&token.rule_id).copied() {
+            Some(node_id) => self.record_terminal_token(node_id, token),
+            None => {
+                self.total_activations += 1;
+                true
+            }
+        }
+    }
+
+    /// Number of matches currently activated for a rule
+    pub fn activated_token_count(&self, rule_id: RuleId) -> usize {
+        self.terminal_nodes
+            .get(&rule_id)
+            .and_then(|node_id| self.beta_memories.get(node_id))
+            .map(|memory| memory.token_count())
+            .unwrap_or(0)
+    }
+
+    /// Store a token in a terminal node's memory, returning whether it is a new match
+    fn record_terminal_token(&mut self, node_id: NodeId, token: Token) -> bool {
+        let memory = self.beta_memories.entry(node_id).or_default();
+        if !memory.add_token(token) {
+            return false;
+        }
+        memory.record_activation();
+        self.total_activations += 1;
+        true
+    }
+
+    /// Find the beta node a token enters the network at
     fn find_current_beta_node(&self, token: &Token) -> Option<NodeId> {
-        // In a simple implementation, use the root node if token has no facts
-        // Otherwise, find the appropriate join node based on condition index
+        // Tokens without facts enter at the root; otherwise find the join node
+        // for the condition the token is waiting on
         if token.facts.is_empty() {
             self.root_node_id
         } else {
-            // Find join node for the current condition index
             let condition_index = token.condition_index;
             for (&node_id, join_node) in &self.join_nodes {
                 if join_node.condition_index == condition_index {
@@ -711,18 +784,17 @@ impl BetaNetworkManager {
     fn process_token_through_join_node(
         token: &Token,
         join_node: &mut JoinNode,
-        facts: &HashMap<FactId, Fact>,
+        facts: &FactMemory,
     ) -> (Vec<Token>, u64) {
         let mut resulting_tokens = Vec::new();
         let mut joins_performed = 0;
 
-        // For each fact in alpha memory that matches this join node's condition
-        // attempt to perform a join with the current token
-
-        // This is a simplified implementation - in a full RETE network,
-        // we would get facts from the specific alpha memory associated
-        // with this join node
+        // Join against every known fact; repeated matches are filtered out at the
+        // terminal node, so re-joining facts from earlier passes is harmless
         for &fact_id in facts.keys() {
+            if token.facts.contains(&fact_id) {
+                continue;
+            }
             if let Some(joined_token) = join_node.perform_join(token, fact_id, facts) {
                 resulting_tokens.push(joined_token);
                 joins_performed += 1;
@@ -732,26 +804,10 @@ impl BetaNetworkManager {
         (resulting_tokens, joins_performed)
     }
 
-    /// Check if a token is complete (has satisfied all conditions)
-    fn is_token_complete(&self, token: &Token, _facts: &HashMap<FactId, Fact>) -> bool {
-        // A token is complete when it has reached a terminal node in the beta network.
-        // We determine this by finding the current beta node and checking if it's terminal.
-        if let Some(current_node_id) = self.find_current_beta_node(token) {
-            if let Some(node) = self.beta_nodes.get(&current_node_id) {
-                matches!(node.node_type, BetaNodeType::Terminal { .. })
-            } else {
-                // If we can't find the node, assume incomplete
-                false
-            }
-        } else {
-            // If we can't determine current position, assume incomplete
-            false
-        }
-    }
-
     /// Retract tokens containing a specific fact
     pub fn retract_tokens_containing_fact(&mut self, fact_id: FactId) -> usize {
         let mut tokens_removed = 0;
+        self.fact_memory.remove(&fact_id);
 
         // Remove tokens from all beta memories
         for memory in self.beta_memories.values_mut() {
@@ -854,6 +910,8 @@ impl BetaNetworkManager {
 
     /// Clear all tokens from the network
     pub fn clear_all_tokens(&mut self) {
+        self.fact_memory.clear();
+
         for memory in self.beta_memories.values_mut() {
             memory.clear();
         }
@@ -871,19 +929,81 @@ impl BetaNetworkManager {
     pub fn estimate_memory_usage(&self) -> usize {
         let mut total_size = std::mem::size_of::<Self>();
 
-        // Beta nodes
-        total_size += self.beta_nodes.len() * std::mem::size_of::<BetaNode>();
+        // Beta nodes, including the token keys they hold
+        total_size += hash_map_table_bytes(&self.beta_nodes);
+        total_size += self.beta_nodes.values().map(BetaNode::heap_bytes).sum::<usize>();
+
+        // Facts shared with the rest of working memory; only the table is owned here
+        total_size += hash_map_table_bytes(&self.fact_memory);
 
         // Join nodes
-        total_size += self.join_nodes.len() * std::mem::size_of::<JoinNode>();
+        total_size += hash_map_table_bytes(&self.join_nodes);
+        for join_node in self.join_nodes.values() {
+            total_size += join_node.beta_node.heap_bytes();
+            total_size += join_node.join_tests.capacity() * std::mem::size_of::<JoinTest>();
+        }
 
         // Beta memories
+        total_size += hash_map_table_bytes(&self.beta_memories);
         for memory in self.beta_memories.values() {
-            total_size += memory.tokens.len() * std::mem::size_of::<Token>();
+            total_size += hash_map_table_bytes(&memory.tokens);
+            for (key, token) in &memory.tokens {
+                total_size += key.capacity() + token.heap_bytes();
+            }
         }
 
+        total_size += hash_map_table_bytes(&self.terminal_nodes);
         total_size
     }
+
+    /// Tokens held across all beta memories
+    pub fn token_count(&self) -> usize {
+        self.beta_memories.values().map(BetaMemory::token_count).sum()
+    }
+
+    /// Size of each node's token memory, with the rules whose matches pass through it
+    pub fn memory_usage(&self) -> Vec<BetaMemoryUsage> {
+        let mut node_rules: HashMap<NodeId, Vec<RuleId>> = HashMap::new();
+        let mut terminals: Vec<(RuleId, NodeId)> = self
+            .terminal_nodes
+            .iter()
+            .map(|(&rule_id, &node_id)| (rule_id, node_id))
+            .collect();
+        terminals.sort_unstable();
+        for (rule_id, terminal_id) in terminals {
+            let mut node = Some(terminal_id);
+            while let Some(node_id) = node {
+                let rules = node_rules.entry(node_id).or_default();
+                if rules.contains(&rule_id) {
+                    break;
+                }
+                rules.push(rule_id);
+                node = self
+                    .beta_nodes
+                    .get(&node_id)
+                    .or_else(|| self.join_nodes.get(&node_id).map(|join| &join.beta_node))
+                    .and_then(|beta_node| beta_node.parent);
+            }
+        }
+
+        let mut usage: Vec<BetaMemoryUsage> = self
+            .beta_memories
+            .iter()
+            .map(|(&node_id, memory)| BetaMemoryUsage {
+                node_id,
+                rule_ids: node_rules.remove(&node_id).unwrap_or_default(),
+                tokens: memory.tokens.len(),
+                bytes: hash_map_table_bytes(&memory.tokens)
+                    + memory
+                        .tokens
+                        .iter()
+                        .map(|(key, token)| key.capacity() + token.heap_bytes())
+                        .sum::<usize>(),
+            })
+            .collect();
+        usage.sort_by_key(|memory| memory.node_id);
+        usage
+    }
 }
 
 impl Default for BetaNetworkManager {
@@ -962,6 +1082,7 @@ mod tests {
             id,
             external_id: Some(format!("fact_{id}")),
             timestamp: chrono::Utc::now(),
+            fact_type: None,
             data: FactData { fields },
         }
     }
@@ -1010,8 +1131,8 @@ mod tests {
         let mut facts = HashMap::new();
         let fact1 = create_test_fact(1, 100, "active");
         let fact2 = create_test_fact(2, 100, "inactive");
-        facts.insert(1, fact1);
-        facts.insert(2, fact2);
+        facts.insert(1, Arc::new(fact1));
+        facts.insert(2, Arc::new(fact2));
 
         let mut token = Token::new(1);
         token.facts.push(1); // Add first fact to token
@@ -1028,7 +1149,7 @@ mod tests {
 
         // Create a fact with different user_id
         let fact3 = create_test_fact(3, 200, "active");
-        facts.insert(3, fact3);
+        facts.insert(3, Arc::new(fact3));
 
         // Should not match because user_ids are different
         assert!(!join_test.evaluate(&token, 3, &facts));
@@ -1085,6 +1206,66 @@ mod tests {
         assert!(join_node.beta_node.children.contains(&terminal_id));
     }
 
+    #[test]
+    fn test_process_token_activates_each_match_once() {
+        let mut manager = BetaNetworkManager::new();
+        let root_id = manager.create_root_node();
+        let join_id = manager.create_join_node(10, 0);
+        let terminal_id = manager.create_terminal_node(1);
+        manager.connect_nodes(root_id, join_id);
+        manager.connect_nodes(join_id, terminal_id);
+
+        let mut delta = FactMemory::new();
+        delta.insert(7, Arc::new(create_test_fact(7, 100, "active")));
+
+        let completed = manager.process_token(Token::new(1), &delta);
+        assert_eq!(completed.len(), 1);
+        assert_eq!(completed[0].facts, vec![7]);
+        assert_eq!(manager.total_joins_performed, 1);
+
+        // Propagating the same delta again reaches the terminal but does not re-fire
+        let completed = manager.process_token(Token::new(1), &delta);
+        assert!(completed.is_empty());
+        assert_eq!(manager.activated_token_count(1), 1);
+
+        // Retraction frees the match so it can activate again
+        manager.retract_tokens_containing_fact(7);
+        assert_eq!(manager.activated_token_count(1), 0);
+        assert!(manager.activate_terminal(Token::new(1).extend(7)));
+    }
+
+    #[test]
+    fn test_process_token_joins_facts_from_separate_calls() {
+        let mut manager = BetaNetworkManager::new();
+        let root_id = manager.create_root_node();
+        let first_join = manager.create_join_node(10, 0);
+        let second_join = manager.create_join_node(11, 1);
+        let terminal_id = manager.create_terminal_node(1);
+        manager.connect_nodes(root_id, first_join);
+        manager.connect_nodes(first_join, second_join);
+        manager.connect_nodes(second_join, terminal_id);
+        manager.join_nodes.get_mut(&second_join).unwrap().add_join_test(JoinTest::new(
+            "user_id".to_string(),
+            "user_id".to_string(),
+            0,
+            JoinOperator::GreaterThan,
+        ));
+
+        let mut delta = FactMemory::new();
+        delta.insert(1, Arc::new(create_test_fact(1, 30, "active")));
+        assert!(manager.process_token(Token::new(1), &delta).is_empty());
+
+        // The second fact arrives on its own and joins with the first
+        let mut delta = FactMemory::new();
+        delta.insert(2, Arc::new(create_test_fact(2, 40, "active")));
+        let completed = manager.process_token(Token::new(1), &delta);
+        assert_eq!(completed.len(), 1);
+        assert_eq!(completed[0].facts, vec![1, 2]);
+
+        assert!(manager.process_token(Token::new(1), &FactMemory::new()).is_empty());
+        assert_eq!(manager.activated_token_count(1), 1);
+    }
+
     #[test]
     fn test_join_operators() {
         let value1 = FactValue::Integer(100);
@@ -1100,4 +1281,24 @@ mod tests {
         assert!(JoinOperator::GreaterThan.compare(&value2, &value1));
         assert!(!JoinOperator::GreaterThan.compare(&value1, &value2));
     }
+
+    #[test]
+    fn test_memory_usage_attributes_shared_nodes() {
+        let mut manager = BetaNetworkManager::new();
+        let root = manager.create_root_node();
+        let join = manager.create_join_node(1, 0);
+        manager.connect_nodes(root, join);
+        for rule_id in [1, 2] {
+            let terminal = manager.create_terminal_node(rule_id);
+            manager.connect_nodes(join, terminal);
+        }
+        manager.beta_memories.get_mut(&join).unwrap().add_token(Token::new(1).extend(7));
+
+        let usage = manager.memory_usage();
+        let rules: Vec<_> = usage.iter().map(|memory| memory.rule_ids.clone()).collect();
+        assert_eq!(rules, vec![vec![1, 2], vec![1, 2], vec![1], vec![2]]);
+        assert_eq!(usage[1].tokens, 1);
+        assert!(usage[1].bytes > 0);
+        assert_eq!(usage[2].tokens, 0);
+    }
 }
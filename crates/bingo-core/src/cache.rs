@@ -2,20 +2,30 @@
 //!
 //! This module provides caching capabilities to improve performance when
 //! the same facts or tokens are repeatedly accessed during rule evaluation.
+//! It also holds the engine's memo of calculator results, so calculators
+//! called with the same inputs for thousands of similar facts run once.
 
+use crate::types::FactValue;
 use std::collections::HashMap;
 use std::hash::Hash;
+use std::time::{Duration, Instant};
 
 /// A simple LRU (Least Recently Used) cache implementation
 ///
 /// This cache maintains items in order of access, evicting the least recently used
-/// items when the cache reaches its capacity limit.
+/// items when the cache reaches its capacity limit. A cache built with a time-to-live
+/// also drops items that outlive it.
 #[derive(Debug)]
 pub struct LruCache<K, V> {
     capacity: usize,
-    map: HashMap<K, (V, usize)>, // Key -> (Value, Access order)
+    ttl: Option<Duration>,
+    map: HashMap<K, (V, usize, Option<Instant>)>, // Key -> (Value, Access order, Expiry)
     access_counter: usize,
     min_access: usize,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+    expirations: u64,
 }
 
 impl<K, V> LruCache<K, V>
@@ -27,19 +37,37 @@ where
     pub fn new(capacity: usize) -> Self {
         Self {
             capacity,
+            ttl: None,
             map: HashMap::with_capacity(capacity),
             access_counter: 0,
             min_access: 0,
+            hits: 0,
+            misses: 0,
+            evictions: 0,
+            expirations: 0,
         }
     }
 
+    /// Create an LRU cache whose items expire `ttl` after they are inserted
+    pub fn with_ttl(capacity: usize, ttl: Duration) -> Self {
+        Self { ttl: Some(ttl), ..Self::new(capacity) }
+    }
+
     /// Get a value from the cache, updating its access time
+    ///
+    /// An expired item is removed and counted as a miss.
     pub fn get(&mut self, key: &K) -> Option<&V> {
-        if let Some((value, access_time)) = self.map.get_mut(key) {
+        if self.map.get(key).is_some_and(|(_, _, expiry)| is_expired(*expiry)) {
+            self.map.remove(key);
+            self.expirations += 1;
+        }
+        if let Some((value, access_time, _)) = self.map.get_mut(key) {
             self.access_counter += 1;
+            self.hits += 1;
             *access_time = self.access_counter;
             Some(value)
         } else {
+            self.misses += 1;
             None
         }
     }
@@ -59,17 +87,18 @@ where
             self.evict_lru();
         }
 
-        self.map.insert(key, (value, self.access_counter));
+        let expiry = self.ttl.map(|ttl| Instant::now() + ttl);
+        self.map.insert(key, (value, self.access_counter, expiry));
     }
 
     /// Remove a key from the cache and return the value if it exists
     pub fn remove(&mut self, key: &K) -> Option<V> {
-        self.map.remove(key).map(|(value, _)| value)
+        self.map.remove(key).map(|(value, _, _)| value)
     }
 
-    /// Check if the cache contains a key
+    /// Check if the cache contains a key that has not expired
     pub fn contains_key(&self, key: &K) -> bool {
-        self.map.contains_key(key)
+        self.map.get(key).is_some_and(|(_, _, expiry)| !is_expired(*expiry))
     }
 
     /// Get the current number of items in the cache
@@ -83,6 +112,8 @@ where
     }
 
     /// Clear all items from the cache
+    ///
+    /// Hit, miss and eviction counters are kept so metrics stay cumulative.
     pub fn clear(&mut self) {
         self.map.clear();
         self.access_counter = 0;
@@ -95,20 +126,52 @@ where
             capacity: self.capacity,
             size: self.map.len(),
             access_counter: self.access_counter,
+            hits: self.hits,
+            misses: self.misses,
+            evictions: self.evictions,
+            expirations: self.expirations,
         }
     }
 
+    /// Time-to-live of the items, `None` when they never expire
+    pub fn ttl(&self) -> Option<Duration> {
+        self.ttl
+    }
+
+    /// Iterate over cached entries without updating access order
+    ///
+    /// Expired entries not yet removed are included.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.map.iter().map(|(key, (value, _, _))| (key, value))
+    }
+
+    /// Bytes reserved by the cache's table, excluding heap data owned by keys and values
+    pub fn table_bytes(&self) -> usize {
+        crate::memory::hash_map_table_bytes(&self.map)
+    }
+
     /// Evict the least recently used item from the cache
     fn evict_lru(&mut self) {
         if self.map.is_empty() {
             return;
         }
 
+        // Expired items go first, without counting as evictions
+        if self.ttl.is_some() {
+            let before = self.map.len();
+            self.map.retain(|_, (_, _, expiry)| !is_expired(*expiry));
+            let expired = before - self.map.len();
+            if expired > 0 {
+                self.expirations += expired as u64;
+                return;
+            }
+        }
+
         // Find the key with the minimum access time
         let mut lru_key = None;
         let mut min_access_time = usize::MAX;
 
-        for (key, (_, access_time)) in &self.map {
+        for (key, (_, access_time, _)) in &self.map {
             if *access_time < min_access_time {
                 min_access_time = *access_time;
                 lru_key = Some(key.clone());
@@ -118,19 +181,43 @@ where
         if let Some(key) = lru_key {
             self.map.remove(&key);
             self.min_access = min_access_time;
+            self.evictions += 1;
         }
     }
 }
 
+/// Whether an item expiring at `expiry` has expired
+fn is_expired(expiry: Option<Instant>) -> bool {
+    expiry.is_some_and(|expiry| Instant::now() >= expiry)
+}
+
 /// Cache statistics for monitoring and debugging
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct CacheStats {
     pub capacity: usize,
     pub size: usize,
     pub access_counter: usize,
+    /// Lookups that found an entry
+    pub hits: u64,
+    /// Lookups that found nothing
+    pub misses: u64,
+    /// Entries removed to make room for new ones
+    pub evictions: u64,
+    /// Entries dropped for outliving their time-to-live
+    pub expirations: u64,
 }
 
 impl CacheStats {
+    /// Calculate the hit rate as a percentage of all lookups
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            (self.hits as f64 / lookups as f64) * 100.0
+        }
+    }
+
     /// Calculate the cache utilization as a percentage
     pub fn utilization(&self) -> f64 {
         if self.capacity == 0 {
@@ -147,6 +234,136 @@ impl CacheStats {
     }
 }
 
+/// Size and lifetime of the calculator result cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CalculatorCacheConfig {
+    /// Results kept before the least recently used is evicted; 0 disables the cache
+    pub capacity: usize,
+    /// How long a result is reused after it is computed, `None` for as long as it stays
+    /// cached
+    pub ttl: Option<Duration>,
+}
+
+impl Default for CalculatorCacheConfig {
+    fn default() -> Self {
+        Self { capacity: 10_000, ttl: None }
+    }
+}
+
+/// Calculator and inputs a result was computed from
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CalculatorCacheKey {
+    calculator: String,
+    /// Inputs sorted by parameter name
+    inputs: Vec<(String, FactValue)>,
+}
+
+impl CalculatorCacheKey {
+    fn new(calculator: &str, inputs: &HashMap<String, FactValue>) -> Self {
+        let mut inputs: Vec<(String, FactValue)> =
+            inputs.iter().map(|(name, value)| (name.clone(), value.clone())).collect();
+        inputs.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+        Self { calculator: calculator.to_string(), inputs }
+    }
+}
+
+/// Memo of deterministic calculator results keyed by calculator name and inputs
+///
+/// Calculators are pure functions of their inputs unless they declare otherwise, so
+/// facts that map the same values into a calculator share one computation.
+#[derive(Debug)]
+pub struct CalculatorCache {
+    config: CalculatorCacheConfig,
+    results: LruCache<CalculatorCacheKey, FactValue>,
+}
+
+impl Default for CalculatorCache {
+    fn default() -> Self {
+        Self::new(CalculatorCacheConfig::default())
+    }
+}
+
+impl CalculatorCache {
+    /// Create an empty cache
+    pub fn new(config: CalculatorCacheConfig) -> Self {
+        let results = match config.ttl {
+            Some(ttl) => LruCache::with_ttl(config.capacity, ttl),
+            None => LruCache::new(config.capacity),
+        };
+        Self { config, results }
+    }
+
+    /// Size and lifetime of the cached results
+    pub fn config(&self) -> CalculatorCacheConfig {
+        self.config
+    }
+
+    /// Whether results are cached at all
+    pub fn is_enabled(&self) -> bool {
+        self.config.capacity > 0
+    }
+
+    /// Result `calculator` returned for `inputs`, if still cached
+    pub fn get(
+        &mut self,
+        calculator: &str,
+        inputs: &HashMap<String, FactValue>,
+    ) -> Option<FactValue> {
+        self.results.get(&CalculatorCacheKey::new(calculator, inputs)).cloned()
+    }
+
+    /// Remember the result `calculator` returned for `inputs`
+    pub fn put(
+        &mut self,
+        calculator: &str,
+        inputs: &HashMap<String, FactValue>,
+        result: FactValue,
+    ) {
+        self.results.put(CalculatorCacheKey::new(calculator, inputs), result);
+    }
+
+    /// Drop every cached result, keeping the statistics
+    pub fn clear(&mut self) {
+        self.results.clear();
+    }
+
+    /// Number of cached results
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Whether no results are cached
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// Hits, misses, evictions and expirations since the cache was created
+    pub fn stats(&self) -> CacheStats {
+        self.results.stats()
+    }
+
+    /// Bytes held by the cached keys and results
+    pub fn memory_bytes(&self) -> usize {
+        use crate::memory::fact_value_heap_bytes;
+
+        self.results.table_bytes()
+            + self
+                .results
+                .iter()
+                .map(|(key, result)| {
+                    key.calculator.capacity()
+                        + key.inputs.capacity() * std::mem::size_of::<(String, FactValue)>()
+                        + key
+                            .inputs
+                            .iter()
+                            .map(|(name, value)| name.capacity() + fact_value_heap_bytes(value))
+                            .sum::<usize>()
+                        + fact_value_heap_bytes(result)
+                })
+                .sum::<usize>()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -257,6 +474,27 @@ mod tests {
         assert_eq!(stats.utilization(), 100.0);
     }
 
+    #[test]
+    fn test_cache_stats_track_hits_misses_and_evictions() {
+        let mut cache = LruCache::new(2);
+
+        cache.put("a", 1);
+        cache.put("b", 2);
+        cache.get(&"a");
+        cache.get(&"z");
+        cache.put("c", 3); // Evicts "b"
+
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 1);
+        assert_eq!(stats.evictions, 1);
+        assert_eq!(stats.hit_rate(), 50.0);
+
+        // Counters survive a clear
+        cache.clear();
+        assert_eq!(cache.stats().evictions, 1);
+    }
+
     #[test]
     fn test_lru_access_pattern() {
         let mut cache = LruCache::new(3);
@@ -278,6 +516,55 @@ mod tests {
         assert_eq!(cache.get(&4), Some(&"four")); // Newly added
     }
 
+    #[test]
+    fn test_items_expire_after_their_ttl() {
+        let mut cache = LruCache::with_ttl(2, Duration::from_millis(20));
+        cache.put("a", 1);
+        assert_eq!(cache.get(&"a"), Some(&1));
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(!cache.contains_key(&"a"));
+        assert_eq!(cache.get(&"a"), None);
+        assert!(cache.is_empty());
+
+        // A full cache drops expired items before evicting live ones
+        cache.put("b", 2);
+        cache.put("c", 3);
+        std::thread::sleep(Duration::from_millis(30));
+        cache.put("d", 4);
+        assert_eq!(cache.len(), 1);
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses), (1, 1));
+        assert_eq!((stats.evictions, stats.expirations), (0, 3));
+    }
+
+    #[test]
+    fn test_calculator_cache_keys_on_name_and_inputs() {
+        let mut cache = CalculatorCache::new(CalculatorCacheConfig { capacity: 2, ttl: None });
+        let inputs = |a, b| {
+            HashMap::from([
+                ("a".to_string(), FactValue::Integer(a)),
+                ("b".to_string(), FactValue::Integer(b)),
+            ])
+        };
+        cache.put("add", &inputs(1, 2), FactValue::Integer(3));
+
+        assert_eq!(cache.get("add", &inputs(1, 2)), Some(FactValue::Integer(3)));
+        assert_eq!(cache.get("add", &inputs(2, 1)), None);
+        assert_eq!(cache.get("multiply", &inputs(1, 2)), None);
+
+        cache.put("add", &inputs(2, 1), FactValue::Integer(3));
+        cache.put("add", &inputs(5, 5), FactValue::Integer(10));
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.stats().evictions, 1);
+        assert!(cache.memory_bytes() > 0);
+
+        let mut disabled = CalculatorCache::new(CalculatorCacheConfig { capacity: 0, ttl: None });
+        disabled.put("add", &inputs(1, 2), FactValue::Integer(3));
+        assert!(!disabled.is_enabled());
+        assert!(disabled.is_empty());
+    }
+
     #[test]
     fn test_zero_capacity_cache() {
         let mut cache = LruCache::new(0);
@@ -0,0 +1,178 @@
+//! Reference calendars for non-uniform aggregation periods
+//!
+//! Semi-monthly pay periods, fiscal quarters and similar business periods have no
+//! fixed length, so they cannot be expressed as tumbling windows. A `PeriodCalendar`
+//! lists the periods explicitly and is registered with the engine as reference data.
+//! Aggregation conditions refer to it by name through `AggregationWindow::Calendar`;
+//! the aggregate is then computed over the facts whose event time falls in the same
+//! period as the fact being tested, e.g. "sum of hours in the current pay period".
+
+use crate::error::{BingoError, BingoResult};
+use crate::stream_processing::Timestamp;
+use crate::types::Fact;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// One period of a calendar, covering `start` up to but excluding `end`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BusinessPeriod {
+    /// Label identifying the period, e.g. "2024-PP03" or "FY25-Q1"
+    pub label: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl BusinessPeriod {
+    /// Create a period covering `start` up to but excluding `end`
+    pub fn new(label: impl Into<String>, start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        Self { label: label.into(), start, end }
+    }
+
+    /// Whether an instant falls within the period
+    pub fn contains(&self, time: DateTime<Utc>) -> bool {
+        self.start <= time && time < self.end
+    }
+}
+
+/// Named set of non-overlapping business periods, ordered by start
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PeriodCalendar {
+    name: String,
+    periods: Vec<BusinessPeriod>,
+}
+
+impl PeriodCalendar {
+    /// Build a calendar from periods in any order
+    ///
+    /// Periods must be non-empty and must not overlap; gaps between them are allowed
+    /// and facts falling in a gap belong to no period.
+    pub fn new(name: impl Into<String>, mut periods: Vec<BusinessPeriod>) -> BingoResult<Self> {
+        let name = name.into();
+        periods.sort_by_key(|period| period.start);
+
+        for period in &periods {
+            if period.start >= period.end {
+                return Err(BingoError::configuration(
+                    &format!("calendar.{name}"),
+                    "period start before end",
+                    &period.label,
+                    format!("Period '{}' of calendar '{name}' is empty", period.label),
+                ));
+            }
+        }
+        for pair in periods.windows(2) {
+            if pair[1].start < pair[0].end {
+                return Err(BingoError::configuration(
+                    &format!("calendar.{name}"),
+                    "non-overlapping periods",
+                    &pair[1].label,
+                    format!(
+                        "Period '{}' of calendar '{name}' overlaps '{}'",
+                        pair[1].label, pair[0].label
+                    ),
+                ));
+            }
+        }
+
+        Ok(Self { name, periods })
+    }
+
+    /// Build a calendar of back-to-back periods from their start instants
+    ///
+    /// Each period runs until the next one starts; the last one runs until `end`.
+    pub fn contiguous(
+        name: impl Into<String>,
+        mut starts: Vec<(String, DateTime<Utc>)>,
+        end: DateTime<Utc>,
+    ) -> BingoResult<Self> {
+        starts.sort_by_key(|(_, start)| *start);
+
+        let ends = starts.iter().skip(1).map(|(_, start)| *start).chain(std::iter::once(end));
+        let periods = starts
+            .iter()
+            .zip(ends)
+            .map(|((label, start), end)| BusinessPeriod::new(label.clone(), *start, end))
+            .collect();
+        Self::new(name, periods)
+    }
+
+    /// Name aggregation windows use to refer to the calendar
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Periods ordered by start
+    pub fn periods(&self) -> &[BusinessPeriod] {
+        &self.periods
+    }
+
+    /// Period containing an instant, if any
+    pub fn period_at(&self, time: DateTime<Utc>) -> Option<&BusinessPeriod> {
+        let index = self.periods.partition_point(|period| period.start <= time);
+        index
+            .checked_sub(1)
+            .map(|index| &self.periods[index])
+            .filter(|period| period.contains(time))
+    }
+
+    /// Period containing a fact's event time, if any
+    pub fn period_of(&self, fact: &Fact) -> Option<&BusinessPeriod> {
+        let millis = Timestamp::of_fact(fact).as_millis() as i64;
+        DateTime::from_timestamp_millis(millis).and_then(|time| self.period_at(time))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn date(month: u32, day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, month, day, 0, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_period_lookup_respects_bounds_and_gaps() {
+        let calendar = PeriodCalendar::new(
+            "pay",
+            vec![
+                BusinessPeriod::new("PP2", date(1, 16), date(2, 1)),
+                BusinessPeriod::new("PP1", date(1, 1), date(1, 16)),
+                BusinessPeriod::new("PP4", date(2, 16), date(3, 1)),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(calendar.periods()[0].label, "PP1");
+        assert_eq!(calendar.period_at(date(1, 15)).unwrap().label, "PP1");
+        assert_eq!(calendar.period_at(date(1, 16)).unwrap().label, "PP2");
+        assert!(calendar.period_at(date(2, 5)).is_none());
+        assert!(calendar.period_at(date(3, 1)).is_none());
+        assert!(calendar.period_at(date(1, 1) - chrono::Duration::seconds(1)).is_none());
+    }
+
+    #[test]
+    fn test_overlapping_and_empty_periods_are_rejected() {
+        let overlapping = vec![
+            BusinessPeriod::new("Q1", date(1, 1), date(4, 1)),
+            BusinessPeriod::new("Q2", date(3, 1), date(7, 1)),
+        ];
+        assert!(PeriodCalendar::new("fiscal", overlapping).is_err());
+
+        let empty = vec![BusinessPeriod::new("Q1", date(4, 1), date(4, 1))];
+        assert!(PeriodCalendar::new("fiscal", empty).is_err());
+    }
+
+    #[test]
+    fn test_contiguous_periods_share_boundaries() {
+        let calendar = PeriodCalendar::contiguous(
+            "fiscal",
+            vec![("Q2".to_string(), date(4, 1)), ("Q1".to_string(), date(1, 1))],
+            date(7, 1),
+        )
+        .unwrap();
+
+        assert_eq!(calendar.periods()[0].end, date(4, 1));
+        assert_eq!(calendar.period_at(date(6, 30)).unwrap().label, "Q2");
+    }
+}
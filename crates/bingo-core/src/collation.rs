@@ -0,0 +1,211 @@
+//! Collation for string comparisons in rule conditions
+//!
+//! By default string operators compare code points exactly, so `"STRASSE"` does not
+//! equal `"straße"` and a precomposed `"é"` does not equal `"e\u{301}"`. A `Collation`
+//! maps both sides of a comparison to a key first:
+//!
+//! - **Normalization**: Unicode NFC, or NFKC which also folds compatibility characters
+//!   such as ligatures and full-width forms
+//! - **Case folding**: Lowercasing plus the multi-character folds of Unicode full case
+//!   folding, so `ß` matches `ss` and `Σ`, `σ` and `ς` all agree
+//! - **Locale**: Tailors case folding; `tr` and `az` fold `I` to dotless `ı` and `İ` to
+//!   `i`, every other locale uses the root folding
+//!
+//! The collation applies to `Equal`, `NotEqual`, the ordering operators, `Contains`,
+//! `StartsWith` and `EndsWith` when both sides are strings. `ContainsIgnoreCase` uses
+//! the collation's locale folding when it is case-insensitive, and `Matches` always
+//! runs the regex over the original text. Fact values are never rewritten, so actions
+//! still see the original text.
+
+use crate::string_match;
+use crate::types::{FactValue, Operator};
+use icu_normalizer::ComposingNormalizerBorrowed;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// Unicode normalization form applied before strings are compared
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Normalization {
+    /// Compare code points as given
+    #[default]
+    None,
+    /// Canonical composition, so `e` + combining acute equals `é`
+    Nfc,
+    /// Compatibility composition, additionally folding `ﬁ` to `fi` and `Ａ` to `A`
+    Nfkc,
+}
+
+/// How strings are compared by rule conditions
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Collation {
+    /// Unicode normalization applied to both sides
+    pub normalization: Normalization,
+    /// Whether case differences are ignored
+    pub case_insensitive: bool,
+    /// BCP 47 language tag tailoring case folding, e.g. `tr` or `de-CH`
+    pub locale: Option<String>,
+}
+
+impl Collation {
+    /// Exact code point comparison, the engine default
+    pub fn binary() -> Self {
+        Self::default()
+    }
+
+    /// Case-insensitive comparison of NFC-normalized strings
+    pub fn case_insensitive() -> Self {
+        Self { normalization: Normalization::Nfc, case_insensitive: true, locale: None }
+    }
+
+    /// Case-insensitive comparison of NFKC-normalized strings using a locale's folding
+    pub fn for_locale(locale: impl Into<String>) -> Self {
+        Self {
+            normalization: Normalization::Nfkc,
+            case_insensitive: true,
+            locale: Some(locale.into()),
+        }
+    }
+
+    /// Whether strings are compared exactly as given
+    pub fn is_binary(&self) -> bool {
+        self.normalization == Normalization::None && !self.case_insensitive
+    }
+
+    /// Comparison key of a string; equal keys compare equal under this collation
+    pub fn key<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if self.is_binary() {
+            return Cow::Borrowed(text);
+        }
+        let normalized = self.normalize(text);
+        if !self.case_insensitive {
+            return normalized;
+        }
+        let folded = fold_case(&normalized, self.uses_turkic_folding());
+        // Folding can leave sequences that are no longer normalized
+        Cow::Owned(self.normalize(&folded).into_owned())
+    }
+
+    /// Value used to index `value` for equality lookups under this collation
+    pub fn index_value<'a>(&self, value: &'a FactValue) -> Cow<'a, FactValue> {
+        match value {
+            FactValue::String(text) if !self.is_binary() => {
+                Cow::Owned(FactValue::String(self.key(text).into_owned()))
+            }
+            other => Cow::Borrowed(other),
+        }
+    }
+
+    /// Evaluate a string operator under this collation
+    ///
+    /// Ordering operators compare the keys by code point. Temporal and membership
+    /// operators never match strings.
+    pub fn matches(&self, operator: &Operator, actual: &str, expected: &str) -> bool {
+        match operator {
+            Operator::Matches => return string_match::regex_matches(expected, actual),
+            Operator::ContainsIgnoreCase if !self.case_insensitive => {
+                return string_match::contains_ignore_case(actual, expected);
+            }
+            _ => {}
+        }
+        let (actual, expected) = (self.key(actual), self.key(expected));
+        match operator {
+            Operator::Equal => actual == expected,
+            Operator::NotEqual => actual != expected,
+            Operator::GreaterThan => actual > expected,
+            Operator::LessThan => actual < expected,
+            Operator::GreaterThanOrEqual => actual >= expected,
+            Operator::LessThanOrEqual => actual <= expected,
+            Operator::Contains | Operator::ContainsIgnoreCase => actual.contains(expected.as_ref()),
+            Operator::StartsWith => actual.starts_with(expected.as_ref()),
+            Operator::EndsWith => actual.ends_with(expected.as_ref()),
+            Operator::Within
+            | Operator::OlderThan
+            | Operator::Overlaps
+            | Operator::Matches
+            | Operator::In
+            | Operator::NotIn => false,
+        }
+    }
+
+    fn normalize<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        match self.normalization {
+            Normalization::None => Cow::Borrowed(text),
+            Normalization::Nfc => ComposingNormalizerBorrowed::new_nfc().normalize(text),
+            Normalization::Nfkc => ComposingNormalizerBorrowed::new_nfkc().normalize(text),
+        }
+    }
+
+    fn uses_turkic_folding(&self) -> bool {
+        self.locale.as_deref().is_some_and(|locale| {
+            let language = locale.split(['-', '_']).next().unwrap_or_default();
+            language.eq_ignore_ascii_case("tr") || language.eq_ignore_ascii_case("az")
+        })
+    }
+}
+
+/// Case-fold a string for caseless matching
+fn fold_case(text: &str, turkic: bool) -> String {
+    let mut folded = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            'I' if turkic => folded.push('ı'),
+            'İ' if turkic => folded.push('i'),
+            'ß' | 'ẞ' => folded.push_str("ss"),
+            'ς' => folded.push('σ'),
+            'ﬀ' => folded.push_str("ff"),
+            'ﬁ' => folded.push_str("fi"),
+            'ﬂ' => folded.push_str("fl"),
+            'ﬃ' => folded.push_str("ffi"),
+            'ﬄ' => folded.push_str("ffl"),
+            'ﬅ' | 'ﬆ' => folded.push_str("st"),
+            c => folded.extend(c.to_lowercase()),
+        }
+    }
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binary_collation_is_exact() {
+        let collation = Collation::binary();
+        assert!(collation.is_binary());
+        assert!(!collation.matches(&Operator::Equal, "STRASSE", "straße"));
+        assert!(collation.matches(&Operator::Equal, "straße", "straße"));
+    }
+
+    #[test]
+    fn test_case_folding_expands_sharp_s() {
+        let collation = Collation::case_insensitive();
+        assert!(collation.matches(&Operator::Equal, "STRASSE", "straße"));
+        assert!(collation.matches(&Operator::Equal, "ΟΔΟΣ", "οδος"));
+        assert!(collation.matches(&Operator::StartsWith, "Straßenbahn", "STRASSE"));
+        assert!(collation.matches(&Operator::Contains, "Hauptstraße 5", "STRASSE"));
+        assert!(!collation.matches(&Operator::NotEqual, "Köln", "KÖLN"));
+    }
+
+    #[test]
+    fn test_normalization_unifies_composed_forms() {
+        let nfc = Collation { normalization: Normalization::Nfc, ..Collation::binary() };
+        assert!(nfc.matches(&Operator::Equal, "caf\u{e9}", "cafe\u{301}"));
+        assert!(!nfc.matches(&Operator::Equal, "\u{fb01}le", "file"));
+
+        let nfkc = Collation { normalization: Normalization::Nfkc, ..Collation::binary() };
+        assert!(nfkc.matches(&Operator::Equal, "\u{fb01}le", "file"));
+        assert!(!nfkc.matches(&Operator::Equal, "File", "file"));
+    }
+
+    #[test]
+    fn test_turkish_locale_folds_dotted_and_dotless_i() {
+        let turkish = Collation::for_locale("tr-TR");
+        assert!(turkish.matches(&Operator::Equal, "İSTANBUL", "istanbul"));
+        assert!(turkish.matches(&Operator::Equal, "ISPARTA", "ısparta"));
+        assert!(!turkish.matches(&Operator::Equal, "ISPARTA", "isparta"));
+
+        let root = Collation::for_locale("de");
+        assert!(root.matches(&Operator::Equal, "ISPARTA", "isparta"));
+    }
+}
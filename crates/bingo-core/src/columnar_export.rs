@@ -0,0 +1,390 @@
+//! Arrow record batch and Parquet export of rule results
+//!
+//! Downstream analytics used to receive results as JSON and parse them back into
+//! tables. These functions build Arrow [`RecordBatch`]es directly from a batch's
+//! [`RuleExecutionResult`]s and facts, and [`write_parquet`] writes them out as Parquet.
+//!
+//! ## Results
+//!
+//! [`results_record_batch`] emits one row per action result; a fact update emits one
+//! row per updated field and a result without actions a single row with no action.
+//! Rows carry the matched fact's `fact_external_id` for joining with client records.
+//! Action values are spread over typed columns, since Parquet has no union type:
+//! `value_type` names the variant and exactly one of `value_integer`, `value_float`,
+//! `value_boolean` or `value_string` holds it. Strings, decimals (exact text), dates
+//! (RFC 3339) and composite values (JSON) all land in `value_string`.
+//!
+//! ## Facts
+//!
+//! [`facts_record_batch`] emits one row per fact with `fact_id`, `fact_external_id` and
+//! `fact_timestamp` followed by a column per field, ordered by name. A field holding
+//! only integers becomes `Int64`, integers and floats `Float64`, booleans `Boolean`
+//! and dates a UTC microsecond `Timestamp`; any other mix is rendered as strings like
+//! `value_string`. Facts lacking a field get a null.
+
+use crate::error::{BingoError, BingoResult};
+use crate::rete_nodes::{ActionResult, RuleExecutionResult};
+use crate::types::{Fact, FactId, FactValue};
+use arrow_array::builder::{
+    BooleanBuilder, Float64Builder, Int64Builder, StringBuilder, TimestampMicrosecondBuilder,
+    UInt64Builder,
+};
+use arrow_array::{ArrayRef, RecordBatch};
+use arrow_schema::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use parquet::arrow::ArrowWriter;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::sync::Arc;
+
+/// Time zone of exported timestamps
+const UTC: &str = "UTC";
+
+/// Columns the fact export adds ahead of the fact fields
+const FACT_METADATA_COLUMNS: [&str; 3] = ["fact_id", "fact_external_id", "fact_timestamp"];
+
+/// Schema of [`results_record_batch`]
+pub fn results_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("rule_id", DataType::UInt64, false),
+        Field::new("fact_id", DataType::UInt64, false),
+        Field::new("fact_external_id", DataType::Utf8, true),
+        Field::new("action", DataType::Utf8, true),
+        Field::new("target_fact_id", DataType::UInt64, true),
+        Field::new("field", DataType::Utf8, true),
+        Field::new("value_type", DataType::Utf8, true),
+        Field::new("value_integer", DataType::Int64, true),
+        Field::new("value_float", DataType::Float64, true),
+        Field::new("value_boolean", DataType::Boolean, true),
+        Field::new("value_string", DataType::Utf8, true),
+        Field::new("message", DataType::Utf8, true),
+    ]))
+}
+
+/// One row per action result, in result order
+pub fn results_record_batch(results: &[RuleExecutionResult]) -> BingoResult<RecordBatch> {
+    let mut rows = ResultRows::default();
+    for result in results {
+        if result.actions_executed.is_empty() {
+            rows.push(result, ResultRow::default());
+        }
+        for action in &result.actions_executed {
+            for row in action_rows(action) {
+                rows.push(result, row);
+            }
+        }
+    }
+    rows.finish()
+}
+
+/// One row per fact, with a typed column per field
+pub fn facts_record_batch(facts: &[Fact]) -> BingoResult<RecordBatch> {
+    let mut columns: BTreeMap<&str, Vec<Option<&FactValue>>> = BTreeMap::new();
+    for (row, fact) in facts.iter().enumerate() {
+        for (field, value) in &fact.data.fields {
+            if FACT_METADATA_COLUMNS.contains(&field.as_str()) {
+                return Err(BingoError::serialization(
+                    "RecordBatch",
+                    "export facts",
+                    format!("Fact field '{field}' clashes with an export column"),
+                ));
+            }
+            columns.entry(field.as_str()).or_insert_with(|| vec![None; facts.len()])[row] =
+                Some(value);
+        }
+    }
+
+    let mut fact_ids = UInt64Builder::with_capacity(facts.len());
+    let mut external_ids = StringBuilder::new();
+    let mut timestamps = TimestampMicrosecondBuilder::with_capacity(facts.len());
+    for fact in facts {
+        fact_ids.append_value(fact.id);
+        external_ids.append_option(fact.external_id.as_deref());
+        timestamps.append_value(fact.timestamp.timestamp_micros());
+    }
+
+    let mut fields = vec![
+        Field::new(FACT_METADATA_COLUMNS[0], DataType::UInt64, false),
+        Field::new(FACT_METADATA_COLUMNS[1], DataType::Utf8, true),
+        Field::new(FACT_METADATA_COLUMNS[2], timestamp_type(), false),
+    ];
+    let mut arrays: Vec<ArrayRef> = vec![
+        Arc::new(fact_ids.finish()),
+        Arc::new(external_ids.finish()),
+        Arc::new(timestamps.finish().with_timezone(UTC)),
+    ];
+    for (name, values) in columns {
+        let (data_type, array) = fact_column(&values);
+        fields.push(Field::new(name, data_type, true));
+        arrays.push(array);
+    }
+
+    record_batch(Arc::new(Schema::new(fields)), arrays)
+}
+
+/// Write `batches` to `writer` as one Parquet file
+///
+/// Every batch must have the schema of the first. Writing no batches is an error,
+/// since the file would have no schema.
+pub fn write_parquet<W: Write + Send>(batches: &[RecordBatch], writer: W) -> BingoResult<()> {
+    let parquet_error = |e: parquet::errors::ParquetError| {
+        BingoError::serialization("Parquet", "write", e.to_string())
+    };
+
+    let Some(first) = batches.first() else {
+        return Err(BingoError::serialization(
+            "Parquet",
+            "write",
+            "No record batches to write",
+        ));
+    };
+    let mut parquet = ArrowWriter::try_new(writer, first.schema(), None).map_err(parquet_error)?;
+    for batch in batches {
+        parquet.write(batch).map_err(parquet_error)?;
+    }
+    parquet.close().map_err(parquet_error)?;
+    Ok(())
+}
+
+/// Columns of a result row other than the rule and fact ids
+#[derive(Default)]
+struct ResultRow<'a> {
+    action: Option<&'static str>,
+    target_fact_id: Option<FactId>,
+    field: Option<&'a str>,
+    value: Option<&'a FactValue>,
+    message: Option<String>,
+}
+
+fn action_rows(action: &ActionResult) -> Vec<ResultRow<'_>> {
+    let row = |name, target_fact_id, field, value| ResultRow {
+        action: Some(name),
+        target_fact_id,
+        field,
+        value,
+        message: None,
+    };
+
+    match action {
+        ActionResult::FieldSet { fact_id, field, value } => {
+            vec![row(
+                "field_set",
+                Some(*fact_id),
+                Some(field.as_str()),
+                Some(value),
+            )]
+        }
+        ActionResult::CalculatorResult { calculator, output_field, parsed_value, .. } => {
+            vec![ResultRow {
+                message: Some(calculator.clone()),
+                ..row(
+                    "calculator_result",
+                    None,
+                    Some(output_field.as_str()),
+                    Some(parsed_value),
+                )
+            }]
+        }
+        ActionResult::Logged { .. } | ActionResult::LazyLogged { .. } => {
+            vec![ResultRow { message: action.get_message(), ..row("logged", None, None, None) }]
+        }
+        ActionResult::FactCreated { fact_id, .. } => {
+            vec![row("fact_created", Some(*fact_id), None, None)]
+        }
+        ActionResult::FactUpdated { fact_id, updated_fields } if updated_fields.is_empty() => {
+            vec![row("fact_updated", Some(*fact_id), None, None)]
+        }
+        ActionResult::FactUpdated { fact_id, updated_fields } => updated_fields
+            .iter()
+            .map(|field| row("fact_updated", Some(*fact_id), Some(field.as_str()), None))
+            .collect(),
+        ActionResult::FactDeleted { fact_id } => {
+            vec![row("fact_deleted", Some(*fact_id), None, None)]
+        }
+        ActionResult::FieldIncremented { fact_id, field, new_value, .. } => {
+            vec![row(
+                "field_incremented",
+                Some(*fact_id),
+                Some(field.as_str()),
+                Some(new_value),
+            )]
+        }
+        ActionResult::ArrayAppended { fact_id, field, appended_value, .. } => vec![row(
+            "array_appended",
+            Some(*fact_id),
+            Some(field.as_str()),
+            Some(appended_value),
+        )],
+        ActionResult::ObjectMerged { fact_id, field, merged_keys, .. } => vec![ResultRow {
+            message: Some(merged_keys.join(",")),
+            ..row("object_merged", Some(*fact_id), Some(field.as_str()), None)
+        }],
+        ActionResult::NotificationSent { recipient, subject, .. } => vec![ResultRow {
+            message: Some(format!("{recipient}: {subject}")),
+            ..row("notification_sent", None, None, None)
+        }],
+        ActionResult::WebhookDispatched { endpoint, queued } => vec![ResultRow {
+            message: Some(format!(
+                "{endpoint}: {}",
+                if *queued { "queued" } else { "dead-lettered" }
+            )),
+            ..row("webhook_dispatched", None, None, None)
+        }],
+    }
+}
+
+#[derive(Default)]
+struct ResultRows {
+    rule_ids: UInt64Builder,
+    fact_ids: UInt64Builder,
+    fact_external_ids: StringBuilder,
+    actions: StringBuilder,
+    target_fact_ids: UInt64Builder,
+    fields: StringBuilder,
+    value_types: StringBuilder,
+    value_integers: Int64Builder,
+    value_floats: Float64Builder,
+    value_booleans: BooleanBuilder,
+    value_strings: StringBuilder,
+    messages: StringBuilder,
+}
+
+impl ResultRows {
+    fn push(&mut self, result: &RuleExecutionResult, row: ResultRow<'_>) {
+        self.rule_ids.append_value(result.rule_id);
+        self.fact_ids.append_value(result.fact_id);
+        self.fact_external_ids.append_option(result.external_id.as_deref());
+        self.actions.append_option(row.action);
+        self.target_fact_ids.append_option(row.target_fact_id);
+        self.fields.append_option(row.field);
+        self.value_types.append_option(row.value.map(value_type));
+        self.value_integers.append_option(row.value.and_then(|value| match value {
+            FactValue::Integer(i) => Some(*i),
+            _ => None,
+        }));
+        self.value_floats.append_option(row.value.and_then(|value| match value {
+            FactValue::Float(f) => Some(*f),
+            _ => None,
+        }));
+        self.value_booleans.append_option(row.value.and_then(|value| match value {
+            FactValue::Boolean(b) => Some(*b),
+            _ => None,
+        }));
+        self.value_strings.append_option(row.value.and_then(|value| match value {
+            FactValue::Integer(_) | FactValue::Float(_) | FactValue::Boolean(_) => None,
+            FactValue::Null => None,
+            other => Some(value_string(other)),
+        }));
+        self.messages.append_option(row.message);
+    }
+
+    fn finish(mut self) -> BingoResult<RecordBatch> {
+        record_batch(
+            results_schema(),
+            vec![
+                Arc::new(self.rule_ids.finish()),
+                Arc::new(self.fact_ids.finish()),
+                Arc::new(self.fact_external_ids.finish()),
+                Arc::new(self.actions.finish()),
+                Arc::new(self.target_fact_ids.finish()),
+                Arc::new(self.fields.finish()),
+                Arc::new(self.value_types.finish()),
+                Arc::new(self.value_integers.finish()),
+                Arc::new(self.value_floats.finish()),
+                Arc::new(self.value_booleans.finish()),
+                Arc::new(self.value_strings.finish()),
+                Arc::new(self.messages.finish()),
+            ],
+        )
+    }
+}
+
+/// Arrow type and array for one fact field, `None` where a fact lacks it
+fn fact_column(values: &[Option<&FactValue>]) -> (DataType, ArrayRef) {
+    let present = || values.iter().flatten().filter(|value| !matches!(value, FactValue::Null));
+
+    if present().all(|value| matches!(value, FactValue::Integer(_))) {
+        let mut builder = Int64Builder::with_capacity(values.len());
+        for value in values {
+            builder.append_option(value.and_then(FactValue::as_integer));
+        }
+        return (DataType::Int64, Arc::new(builder.finish()));
+    }
+    if present().all(|value| matches!(value, FactValue::Integer(_) | FactValue::Float(_))) {
+        let mut builder = Float64Builder::with_capacity(values.len());
+        for value in values {
+            builder.append_option(value.and_then(|value| match value {
+                FactValue::Null => None,
+                other => other.as_float(),
+            }));
+        }
+        return (DataType::Float64, Arc::new(builder.finish()));
+    }
+    if present().all(|value| matches!(value, FactValue::Boolean(_))) {
+        let mut builder = BooleanBuilder::with_capacity(values.len());
+        for value in values {
+            builder.append_option(value.and_then(|value| match value {
+                FactValue::Boolean(b) => Some(*b),
+                _ => None,
+            }));
+        }
+        return (DataType::Boolean, Arc::new(builder.finish()));
+    }
+    if present().all(|value| matches!(value, FactValue::Date(_))) {
+        let mut builder = TimestampMicrosecondBuilder::with_capacity(values.len());
+        for value in values {
+            builder.append_option(value.and_then(|value| match value {
+                FactValue::Date(date) => Some(date.timestamp_micros()),
+                _ => None,
+            }));
+        }
+        return (
+            timestamp_type(),
+            Arc::new(builder.finish().with_timezone(UTC)),
+        );
+    }
+
+    let mut builder = StringBuilder::new();
+    for value in values {
+        builder.append_option(value.and_then(|value| match value {
+            FactValue::Null => None,
+            other => Some(value_string(other)),
+        }));
+    }
+    (DataType::Utf8, Arc::new(builder.finish()))
+}
+
+fn timestamp_type() -> DataType {
+    DataType::Timestamp(TimeUnit::Microsecond, Some(UTC.into()))
+}
+
+/// Name of the variant in the `value_type` column
+fn value_type(value: &FactValue) -> &'static str {
+    match value {
+        FactValue::String(_) => "string",
+        FactValue::Integer(_) => "integer",
+        FactValue::Float(_) => "float",
+        FactValue::Decimal(_) => "decimal",
+        FactValue::Boolean(_) => "boolean",
+        FactValue::Array(_) => "array",
+        FactValue::Object(_) => "object",
+        FactValue::Date(_) => "date",
+        FactValue::Duration(_) => "duration",
+        FactValue::Interval { .. } => "interval",
+        FactValue::Null => "null",
+    }
+}
+
+/// Text of a value exported to a string column
+fn value_string(value: &FactValue) -> String {
+    match value {
+        FactValue::String(s) => s.clone(),
+        FactValue::Decimal(d) => d.to_string(),
+        FactValue::Date(date) => date.to_rfc3339(),
+        other => serde_json::Value::from(other.clone()).to_string(),
+    }
+}
+
+fn record_batch(schema: SchemaRef, arrays: Vec<ArrayRef>) -> BingoResult<RecordBatch> {
+    RecordBatch::try_new(schema, arrays)
+        .map_err(|e| BingoError::serialization("RecordBatch", "build", e.to_string()))
+}
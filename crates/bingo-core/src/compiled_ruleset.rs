@@ -0,0 +1,159 @@
+//! Precompiled ruleset artifacts for warm starts
+//!
+//! Adding a rule optimises its condition order, validates its patterns and builds the
+//! alpha, beta, aggregation, window and sequence nodes it needs, which for a large
+//! ruleset is most of an API pod's cold start. `BingoEngine::compile_ruleset` captures
+//! the compiled network once, offline or on a build pod, and `CompiledRuleset::to_bytes`
+//! encodes it in a compact binary artifact. `BingoEngine::load_compiled` decodes the
+//! artifact, from a buffer or a memory-mapped file, and installs the nodes as they were
+//! built instead of compiling the rules again.
+//!
+//! The artifact holds the rules as added and as optimised, the compiled literal value
+//! lists, and every node with its successors. Conditions tested by several nodes are
+//! interned and stored once. Calendars, reference tables, globals and webhooks are not
+//! part of the artifact: they are registered with the loading engine as usual, and
+//! loading checks that the rules' references to them resolve. Node memories start
+//! empty and fill from the engine's facts.
+
+use crate::beta_network::BetaNodeType;
+use crate::error::{BingoError, BingoResult};
+use crate::types::{Condition, FactValue, NodeId, Rule, RuleId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Version of the compiled ruleset encoding, bumped on incompatible changes
+const COMPILED_FORMAT_VERSION: u32 = 1;
+
+/// Ruleset compiled into a RETE network, ready to be installed without recompiling
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompiledRuleset {
+    format_version: u32,
+    /// Rules as added to the engine
+    rules: Vec<Rule>,
+    network: CompiledNetwork,
+}
+
+impl CompiledRuleset {
+    pub(crate) fn new(rules: Vec<Rule>, network: CompiledNetwork) -> Self {
+        Self { format_version: COMPILED_FORMAT_VERSION, rules, network }
+    }
+
+    /// Rules as added to the engine the ruleset was compiled in
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// Number of alpha, beta, terminal, aggregation, window and sequence nodes
+    pub fn node_count(&self) -> usize {
+        let network = &self.network;
+        network.alpha_nodes.len()
+            + network.alpha_memories.len()
+            + network.beta_nodes.len()
+            + network.terminal_nodes.len()
+            + network.aggregation_nodes.len()
+            + network.window_nodes.len()
+            + network.sequence_nodes.len()
+    }
+
+    pub(crate) fn into_parts(self) -> (Vec<Rule>, CompiledNetwork) {
+        (self.rules, self.network)
+    }
+
+    /// Encode the ruleset as a binary artifact
+    pub fn to_bytes(&self) -> BingoResult<Vec<u8>> {
+        let mut bytes = Vec::new();
+        ciborium::into_writer(self, &mut bytes)
+            .map_err(|e| BingoError::serialization("CompiledRuleset", "encode", e.to_string()))?;
+        Ok(bytes)
+    }
+
+    /// Decode an artifact encoded by [`to_bytes`](Self::to_bytes)
+    pub fn from_bytes(bytes: &[u8]) -> BingoResult<Self> {
+        let compiled: Self = ciborium::from_reader(bytes)
+            .map_err(|e| BingoError::serialization("CompiledRuleset", "decode", e.to_string()))?;
+        if compiled.format_version != COMPILED_FORMAT_VERSION {
+            return Err(BingoError::serialization(
+                "CompiledRuleset",
+                "decode",
+                format!(
+                    "Unsupported compiled ruleset format version {} (expected {COMPILED_FORMAT_VERSION})",
+                    compiled.format_version
+                ),
+            ));
+        }
+        Ok(compiled)
+    }
+}
+
+/// Node layout of a compiled RETE network
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(crate) struct CompiledNetwork {
+    /// Rules as stored by the network: optimised, with literal arrays compiled
+    pub rules: Vec<Rule>,
+    /// Declared index of each condition of rules a query plan reordered
+    pub condition_orders: Vec<(RuleId, Vec<usize>)>,
+    /// Conditions tested by the nodes, each stored once
+    pub conditions: Vec<Condition>,
+    pub alpha_nodes: Vec<CompiledNode>,
+    pub alpha_memories: Vec<CompiledNode>,
+    pub aggregation_nodes: Vec<CompiledNode>,
+    pub window_nodes: Vec<CompiledNode>,
+    #[serde(default)]
+    pub sequence_nodes: Vec<CompiledNode>,
+    /// Terminal node of each rule
+    pub terminal_nodes: Vec<(RuleId, NodeId)>,
+    pub beta_nodes: Vec<CompiledBetaNode>,
+    pub beta_root: Option<NodeId>,
+    /// Compiled literal value lists by generated name
+    pub literals: Vec<(String, Vec<FactValue>)>,
+    pub next_literal: u64,
+    pub next_node_id: NodeId,
+    pub next_beta_node_id: NodeId,
+}
+
+impl CompiledNetwork {
+    /// Interned condition at `index`
+    pub fn condition(&self, index: usize) -> anyhow::Result<&Condition> {
+        self.conditions
+            .get(index)
+            .ok_or_else(|| anyhow::anyhow!("Compiled ruleset refers to unknown condition {index}"))
+    }
+}
+
+/// Node testing one interned condition for a set of rules
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CompiledNode {
+    pub id: NodeId,
+    pub condition: usize,
+    pub rule_ids: Vec<RuleId>,
+}
+
+/// Beta node with its place in the beta network
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CompiledBetaNode {
+    pub id: NodeId,
+    pub node_type: BetaNodeType,
+    pub parent: Option<NodeId>,
+    pub children: Vec<NodeId>,
+}
+
+/// Stores each distinct condition once and hands out its index
+#[derive(Debug, Default)]
+pub(crate) struct ConditionInterner {
+    conditions: Vec<Condition>,
+    indexes: HashMap<String, usize>,
+}
+
+impl ConditionInterner {
+    pub fn intern(&mut self, condition: &Condition) -> usize {
+        let conditions = &mut self.conditions;
+        *self.indexes.entry(format!("{condition:?}")).or_insert_with(|| {
+            conditions.push(condition.clone());
+            conditions.len() - 1
+        })
+    }
+
+    pub fn into_conditions(self) -> Vec<Condition> {
+        self.conditions
+    }
+}
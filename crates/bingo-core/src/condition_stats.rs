@@ -0,0 +1,178 @@
+//! Per-condition evaluation counters
+//!
+//! Every alpha node counts how many candidate facts its condition was tested against
+//! and how many of them matched. The counts serve two purposes:
+//!
+//! - **Selectivity feedback**: Observed match rates replace the rule optimizer's
+//!   heuristic estimates, so conditions are ordered by how selective they really are
+//! - **Never-matching conditions**: A condition that matches nothing is most often a
+//!   misspelt field name or a value in the wrong case, and is reported as such
+
+use crate::types::{AlphaNode, Condition, FactValue, NodeId, Operator, RuleId};
+use std::fmt;
+
+/// Evaluation counts of one alpha node condition
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConditionEvaluationStats {
+    pub node_id: NodeId,
+    pub field: String,
+    pub operator: Operator,
+    pub value: FactValue,
+    /// Rules sharing the condition
+    pub rule_ids: Vec<RuleId>,
+    /// Candidate facts the condition was tested against
+    pub evaluations: u64,
+    /// Candidate facts that satisfied the condition
+    pub matches: u64,
+}
+
+impl ConditionEvaluationStats {
+    /// Counts of an alpha node, if it tests a simple condition
+    pub fn from_alpha_node(node: &AlphaNode) -> Option<Self> {
+        let Condition::Simple { field, operator, value } = &node.condition else {
+            return None;
+        };
+        Some(Self {
+            node_id: node.id,
+            field: field.clone(),
+            operator: operator.clone(),
+            value: value.clone(),
+            rule_ids: node.rule_ids.clone(),
+            evaluations: node.evaluations,
+            matches: node.matches,
+        })
+    }
+
+    /// Fraction of evaluations that matched, once the condition has been evaluated
+    pub fn selectivity(&self) -> Option<f64> {
+        (self.evaluations > 0).then(|| self.matches as f64 / self.evaluations as f64)
+    }
+
+    /// Signature the network and rule optimizer key the condition by
+    pub fn pattern_key(&self) -> String {
+        format!("{}_{:?}_{:?}", self.field, self.operator, self.value)
+    }
+}
+
+impl fmt::Display for ConditionEvaluationStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let operator = match self.operator {
+            Operator::Equal => "==",
+            Operator::NotEqual => "!=",
+            Operator::GreaterThan => ">",
+            Operator::LessThan => "<",
+            Operator::GreaterThanOrEqual => ">=",
+            Operator::LessThanOrEqual => "<=",
+            Operator::Contains => "contains",
+            Operator::StartsWith => "starts_with",
+            Operator::EndsWith => "ends_with",
+            Operator::Within => "within",
+            Operator::OlderThan => "older_than",
+            Operator::Overlaps => "overlaps",
+            Operator::Matches => "matches",
+            Operator::ContainsIgnoreCase => "contains_ignore_case",
+            Operator::In => "in",
+            Operator::NotIn => "not_in",
+        };
+        write!(f, "{} {operator} ", self.field)?;
+        match &self.value {
+            FactValue::String(value) => write!(f, "{value:?}"),
+            value => write!(f, "{value}"),
+        }
+    }
+}
+
+/// Conditions that have not matched any fact they were tested against
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UnmatchedConditionReport {
+    /// Never-matching conditions, including ones no fact was a candidate for
+    pub conditions: Vec<ConditionEvaluationStats>,
+    /// Number of conditions counted
+    pub conditions_tracked: usize,
+}
+
+impl UnmatchedConditionReport {
+    /// Report the never-matching conditions among the given counts
+    pub fn from_stats(stats: Vec<ConditionEvaluationStats>) -> Self {
+        let conditions_tracked = stats.len();
+        let conditions = stats.into_iter().filter(|stats| stats.matches == 0).collect();
+        Self { conditions, conditions_tracked }
+    }
+
+    /// Whether every condition has matched at least once
+    pub fn is_empty(&self) -> bool {
+        self.conditions.is_empty()
+    }
+}
+
+impl fmt::Display for UnmatchedConditionReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} of {} conditions never matched",
+            self.conditions.len(),
+            self.conditions_tracked
+        )?;
+        for condition in &self.conditions {
+            let rules: Vec<String> = condition.rule_ids.iter().map(u64::to_string).collect();
+            write!(f, "  {condition} (rules {}): ", rules.join(", "))?;
+            if condition.evaluations == 0 {
+                writeln!(f, "never evaluated")?;
+            } else {
+                writeln!(f, "0 of {} evaluations matched", condition.evaluations)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(
+        field: &str,
+        value: FactValue,
+        evaluations: u64,
+        matches: u64,
+    ) -> ConditionEvaluationStats {
+        ConditionEvaluationStats {
+            node_id: 1,
+            field: field.to_string(),
+            operator: Operator::Equal,
+            value,
+            rule_ids: vec![1, 2],
+            evaluations,
+            matches,
+        }
+    }
+
+    #[test]
+    fn test_selectivity() {
+        assert_eq!(
+            stats("status", FactValue::Integer(1), 0, 0).selectivity(),
+            None
+        );
+        assert_eq!(
+            stats("status", FactValue::Integer(1), 8, 2).selectivity(),
+            Some(0.25)
+        );
+    }
+
+    #[test]
+    fn test_report_lists_never_matching_conditions() {
+        let report = UnmatchedConditionReport::from_stats(vec![
+            stats("status", FactValue::String("active".to_string()), 10, 4),
+            stats("stauts", FactValue::String("active".to_string()), 10, 0),
+            stats("order_totl", FactValue::Integer(100), 0, 0),
+        ]);
+        assert!(!report.is_empty());
+        assert_eq!(report.conditions.len(), 2);
+        assert_eq!(
+            report.to_string(),
+            "2 of 3 conditions never matched\n\
+             \x20 stauts == \"active\" (rules 1, 2): 0 of 10 evaluations matched\n\
+             \x20 order_totl == 100 (rules 1, 2): never evaluated\n"
+        );
+    }
+}
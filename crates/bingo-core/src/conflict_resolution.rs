@@ -463,6 +463,7 @@ mod tests {
             actions: vec![Action {
                 action_type: ActionType::Log { message: format!("Rule {name} fired") },
             }],
+            metadata: Default::default(),
         }
     }
 
@@ -474,6 +475,7 @@ mod tests {
             id,
             external_id: Some(format!("fact_{id}")),
             timestamp: chrono::Utc::now(),
+            fact_type: None,
             data: FactData { fields },
         }
     }
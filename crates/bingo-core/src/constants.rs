@@ -11,6 +11,10 @@ pub mod fact_ids {
     /// Maximum ID for user-provided facts
     /// IDs above this threshold are considered auto-generated
     pub const MAX_USER_FACT_ID: u64 = 1_000_000;
+
+    /// First ID the fact store generates under the bounded ID strategy
+    /// Generated IDs stay above client IDs so a kept client ID never lands on one
+    pub const FIRST_GENERATED_FACT_ID: u64 = MAX_USER_FACT_ID + 1;
 }
 
 /// Performance and memory management constants
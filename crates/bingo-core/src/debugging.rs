@@ -2,10 +2,21 @@
 //!
 //! This module provides comprehensive debugging capabilities for the RETE network,
 //! including execution traces, performance profiling, and rule analysis tools.
-
-use crate::types::{Fact, FactValue, NodeId, RuleId, Token};
+//!
+//! A [`DebugSession`] is an interactive step-debugger: it processes queued facts one at
+//! a time through a private copy of an engine's network, pauses on breakpoints set on
+//! rules or network nodes, and exposes the alpha and beta memory contents after every
+//! step.
+
+use crate::error::{BingoError, BingoResult};
+use crate::fact_store::arena_store::ArenaFactStore;
+use crate::rete_network::ReteNetwork;
+use crate::rete_nodes::RuleExecutionResult;
+use crate::types::{Fact, FactId, FactValue, NodeId, RuleId, Token};
+use bingo_calculator::calculator::Calculator;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 
@@ -19,7 +30,7 @@ pub type TraceId = Uuid;
 #[derive(Debug)]
 pub struct DebugManager {
     /// Active debugging sessions
-    sessions: HashMap<DebugSessionId, DebugSession>,
+    sessions: HashMap<DebugSessionId, TraceSession>,
     /// Global execution traces
     traces: HashMap<TraceId, ExecutionTrace>,
     /// Performance profiles
@@ -51,9 +62,9 @@ pub struct DebugConfig {
     pub enable_dependency_analysis: bool,
 }
 
-/// A debugging session for tracking rule execution
+/// A tracing session recording the executions of a set of rules
 #[derive(Debug)]
-pub struct DebugSession {
+pub struct TraceSession {
     /// Session identifier
     pub session_id: DebugSessionId,
     /// Rules being debugged in this session
@@ -530,7 +541,7 @@ impl DebugManager {
         config: Option<DebugConfig>,
     ) -> DebugSessionId {
         let session_id = Uuid::new_v4();
-        let session = DebugSession {
+        let session = TraceSession {
             session_id,
             target_rules,
             started_at: SystemTime::now(),
@@ -914,6 +925,220 @@ impl Default for TracePerformance {
     }
 }
 
+/// Where a [`DebugSession`] pauses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum DebugBreakpoint {
+    /// The rule fires
+    Rule(RuleId),
+    /// A fact enters the alpha node's memory
+    AlphaNode(NodeId),
+    /// A token enters the beta node's memory
+    BetaNode(NodeId),
+}
+
+/// Facts held by an alpha node's memory
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AlphaMemoryContents {
+    pub node_id: NodeId,
+    pub condition: String,
+    pub fact_ids: Vec<FactId>,
+}
+
+/// Tokens held by a beta node's memory, each given as the facts it joins
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BetaMemoryContents {
+    pub node_id: NodeId,
+    /// Rules whose matches pass through the node
+    pub rule_ids: Vec<RuleId>,
+    pub tokens: Vec<Vec<FactId>>,
+}
+
+/// Alpha and beta memories of a network, in node ID order
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct NetworkMemoryContents {
+    pub alpha: Vec<AlphaMemoryContents>,
+    pub beta: Vec<BetaMemoryContents>,
+}
+
+/// What processing one fact did to the network
+#[derive(Debug, Clone)]
+pub struct DebugStep {
+    /// Position of the step in the session, from 1
+    pub step: u64,
+    /// The fact as stored, with its assigned ID
+    pub fact: Fact,
+    /// Alpha nodes the fact entered
+    pub alpha_nodes: Vec<NodeId>,
+    /// Beta nodes that gained tokens
+    pub beta_nodes: Vec<NodeId>,
+    pub results: Vec<RuleExecutionResult>,
+    /// Breakpoints the step reached, in breakpoint order
+    pub breakpoints_hit: Vec<DebugBreakpoint>,
+}
+
+/// Interactive step-debugger over a copy of an engine's compiled rules
+///
+/// Created by `BingoEngine::debug_session`. Rules are compiled into a dry-run network,
+/// so actions never reach the engine's working memory and webhook actions are not
+/// dispatched. Aggregation conditions see copies of the facts the engine held when the
+/// session started; only stepped facts enter the alpha and beta memories.
+pub struct DebugSession {
+    id: DebugSessionId,
+    network: ReteNetwork,
+    fact_store: ArenaFactStore,
+    calculator: Arc<Calculator>,
+    pending: VecDeque<Fact>,
+    breakpoints: BTreeSet<DebugBreakpoint>,
+    steps: u64,
+}
+
+impl std::fmt::Debug for DebugSession {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DebugSession")
+            .field("id", &self.id)
+            .field("pending", &self.pending.len())
+            .field("breakpoints", &self.breakpoints)
+            .field("steps", &self.steps)
+            .finish()
+    }
+}
+
+impl DebugSession {
+    /// Debug the rules compiled into a dry-run `network`, starting from copies of `facts`
+    pub(crate) fn new(network: ReteNetwork, facts: Vec<Fact>, calculator: Arc<Calculator>) -> Self {
+        let fact_store = ArenaFactStore::with_capacity(facts.len());
+        for fact in facts {
+            fact_store.insert_with_id(fact);
+        }
+        Self {
+            id: Uuid::new_v4(),
+            network,
+            fact_store,
+            calculator,
+            pending: VecDeque::new(),
+            breakpoints: BTreeSet::new(),
+            steps: 0,
+        }
+    }
+
+    pub fn id(&self) -> DebugSessionId {
+        self.id
+    }
+
+    /// Queue facts to be processed by later steps, in order
+    pub fn queue(&mut self, facts: impl IntoIterator<Item = Fact>) {
+        self.pending.extend(facts);
+    }
+
+    /// Facts queued and not yet stepped
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Steps taken so far
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// Pause after any step that reaches `breakpoint`
+    pub fn set_breakpoint(&mut self, breakpoint: DebugBreakpoint) {
+        self.breakpoints.insert(breakpoint);
+    }
+
+    /// Remove a breakpoint, returning whether it was set
+    pub fn clear_breakpoint(&mut self, breakpoint: DebugBreakpoint) -> bool {
+        self.breakpoints.remove(&breakpoint)
+    }
+
+    pub fn breakpoints(&self) -> impl Iterator<Item = DebugBreakpoint> + '_ {
+        self.breakpoints.iter().copied()
+    }
+
+    /// Process the next queued fact, or return `None` when the queue is empty
+    pub fn step(&mut self) -> BingoResult<Option<DebugStep>> {
+        let Some(fact) = self.pending.pop_front() else {
+            return Ok(None);
+        };
+        let fact_id = self.fact_store.insert(fact);
+        let fact = self.fact_store.get_fact(fact_id).ok_or_else(|| {
+            BingoError::fact_store_with_id(fact_id, "debug_step", "Stepped fact was not stored")
+        })?;
+
+        let before = self.network.memory_contents();
+        let results = self
+            .network
+            .add_fact_to_working_memory(fact.clone(), &self.fact_store, &self.calculator)
+            .map_err(|e| BingoError::rete_network("debug_step", e.to_string()))?;
+        let after = self.network.memory_contents();
+        self.steps += 1;
+
+        let alpha_nodes: Vec<NodeId> = after
+            .alpha
+            .iter()
+            .filter(|memory| memory.fact_ids.binary_search(&fact_id).is_ok())
+            .map(|memory| memory.node_id)
+            .collect();
+        let tokens_before: HashMap<NodeId, HashSet<&Vec<FactId>>> = before
+            .beta
+            .iter()
+            .map(|memory| (memory.node_id, memory.tokens.iter().collect()))
+            .collect();
+        let beta_nodes: Vec<NodeId> = after
+            .beta
+            .iter()
+            .filter(|memory| {
+                let known = tokens_before.get(&memory.node_id);
+                memory
+                    .tokens
+                    .iter()
+                    .any(|token| known.is_none_or(|known| !known.contains(token)))
+            })
+            .map(|memory| memory.node_id)
+            .collect();
+
+        let breakpoints_hit = self
+            .breakpoints
+            .iter()
+            .copied()
+            .filter(|breakpoint| match breakpoint {
+                DebugBreakpoint::Rule(rule_id) => {
+                    results.iter().any(|result| result.rule_id == *rule_id)
+                }
+                DebugBreakpoint::AlphaNode(node_id) => alpha_nodes.contains(node_id),
+                DebugBreakpoint::BetaNode(node_id) => beta_nodes.contains(node_id),
+            })
+            .collect();
+
+        Ok(Some(DebugStep {
+            step: self.steps,
+            fact,
+            alpha_nodes,
+            beta_nodes,
+            results,
+            breakpoints_hit,
+        }))
+    }
+
+    /// Step until a step reaches a breakpoint or the queue is empty, returning every
+    /// step taken
+    pub fn resume(&mut self) -> BingoResult<Vec<DebugStep>> {
+        let mut steps = Vec::new();
+        while let Some(step) = self.step()? {
+            let paused = !step.breakpoints_hit.is_empty();
+            steps.push(step);
+            if paused {
+                break;
+            }
+        }
+        Ok(steps)
+    }
+
+    /// Contents of the alpha and beta memories after the last step
+    pub fn memory(&self) -> NetworkMemoryContents {
+        self.network.memory_contents()
+    }
+}
+
 /// Get current timestamp in milliseconds since Unix epoch
 pub fn current_timestamp() -> u64 {
     SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64
@@ -0,0 +1,50 @@
+//! Deterministic execution for reproducible results
+//!
+//! Auditors rerun a batch and expect the very same results. With
+//! `BingoEngine::set_deterministic(true)` the engine removes the run-to-run variation
+//! under its control:
+//!
+//! - the rules a fact matches fire in rule ID order, in working memory processing as
+//!   in batches, whatever order the alpha indexes find them in
+//! - facts created by rule actions take the timestamp of the fact that triggered the
+//!   rule instead of the clock
+//!
+//! [`encode_results`] renders a result sequence in canonical bytes, in firing order
+//! with object keys sorted, so two runs can be compared byte for byte or hashed.
+//!
+//! The inputs have to be reproducible as well: facts with fixed timestamps, a fact ID
+//! strategy other than `Snowflake`, and an `EvaluationDate::At` date when rules or
+//! reference tables are dated.
+
+use crate::error::{BingoError, BingoResult};
+use crate::rete_nodes::RuleExecutionResult;
+
+/// Canonical bytes of a result sequence: one JSON object per line and result, in the
+/// given order, with object keys sorted
+///
+/// Explanation trace IDs are left out, as they identify a trace in one engine's audit
+/// log rather than the result.
+pub fn encode_results(results: &[RuleExecutionResult]) -> BingoResult<Vec<u8>> {
+    let mut bytes = Vec::new();
+    for result in results {
+        // Going through `Value` sorts object keys, so field maps encode stably
+        let line = serde_json::json!({
+            "rule_id": result.rule_id,
+            "fact_id": result.fact_id,
+            "external_id": result.external_id,
+            "fact_timestamp": result.fact_timestamp.map(|timestamp| timestamp.to_rfc3339()),
+            "actions": to_value("ActionResult", &result.actions_executed)?,
+            "field_collisions": to_value("FieldCollision", &result.field_collisions)?,
+        });
+        serde_json::to_writer(&mut bytes, &line).map_err(|e| {
+            BingoError::serialization("RuleExecutionResult", "encode", e.to_string())
+        })?;
+        bytes.push(b'\n');
+    }
+    Ok(bytes)
+}
+
+fn to_value(data_type: &str, value: &impl serde::Serialize) -> BingoResult<serde_json::Value> {
+    serde_json::to_value(value)
+        .map_err(|e| BingoError::serialization(data_type, "encode", e.to_string()))
+}
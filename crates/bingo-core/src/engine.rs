@@ -10,19 +10,72 @@
 /// 5. **Rule Optimization Module**: Advanced RETE optimizations and performance tuning
 ///
 /// Each module is clearly separated for easy navigation and maintenance.
+use crate::action_validation::{
+    ActionValidationPolicy, ActionValidationReport, FieldSchema, validate_actions,
+};
+use crate::batch_summary::BatchSummary;
+use crate::cache::{CacheStats, CalculatorCacheConfig};
+use crate::calendar::PeriodCalendar;
+use crate::collation::Collation;
+use crate::compiled_ruleset::CompiledRuleset;
+use crate::condition_stats::{ConditionEvaluationStats, UnmatchedConditionReport};
+use crate::debugging::DebugSession;
+use crate::engine_config::{CapacityStats, EngineConfig};
 use crate::error::{BingoError, BingoResult};
-use crate::fact_store::arena_store::ArenaFactStore;
+use crate::evaluation_budget::{BudgetedResults, Continuation, EvaluationBudget};
+use crate::event_bus::{EngineEvent, EngineEventListener, EventBus, SubscriptionId};
+use crate::explanation::{AuditLogConfig, ExplanationTrace, ResultId};
+use crate::fact_expiry::{ExpirySchedule, FactExpiryConfig, FactExpiryStats};
+use crate::fact_history::{FactHistoryConfig, FactVersion};
+use crate::fact_id_strategy::{FactIdStats, FactIdStrategy};
+use crate::fact_query::{FactPage, FactPageRequest, FactQuery};
+use crate::fact_schema::{FactSchema, FactSchemaRegistry};
+use crate::fact_store::arena_store::{ArenaFactStore, FactJournal};
+use crate::field_collisions::FieldCollisionPolicy;
+use crate::field_references::{ReferencedField, referenced_fields};
+use crate::field_typos::{FieldObservations, FieldTypoAnalyzer, FieldTypoWarning};
+use crate::follower::{EngineSnapshot, FollowerEngine};
+use crate::memory::MemoryBreakdown;
+use crate::memory_pressure::{
+    MemoryPressureHandler, MemoryPressureMonitor, MemoryPressureStats, MemoryWatermarks,
+    PressureLevel,
+};
+use crate::memory_report::{MemoryReport, MemoryReportOptions};
+use crate::non_finite::{NonFinitePolicy, NonFiniteStats};
+use crate::parallel::ParallelAlphaConfig;
 use crate::profiler::EngineProfiler;
 use crate::profiler::PerformanceReport;
-use crate::rete_network::ReteNetwork;
+use crate::recalculation::{Correction, RecalculationReport, RecalculationRequest, deltas};
+use crate::reference_data::{ReferenceDataStore, ReferenceTable};
+use crate::rete_network::{ForwardChaining, ReteNetwork};
 use crate::rete_nodes::RuleExecutionResult;
+use crate::rule_dependency::{RuleCyclePolicy, RuleFieldGraph, RuleFlowGraph};
+use crate::rule_dsl::parse_rules;
+use crate::rule_duplicates::{
+    DuplicateKind, DuplicateRulePolicy, DuplicateRules, duplicates_of, find_duplicates,
+};
 use crate::rule_optimizer::{OptimizationMetrics, OptimizationResult};
-use crate::types::{EngineStats, Fact, FactValue, PoolStats, Rule};
+use crate::rule_stats::RuleStats;
+use crate::shadow::{DEFAULT_DIVERGENCE_LIMIT, ShadowEvaluation, ShadowReport};
+use crate::standby::{
+    ReplicationConfig, ReplicationRecord, ReplicationSink, Replicator, StateDigest, WarmStandby,
+};
+use crate::stream_processing::LateDataPolicy;
+use crate::transaction::{EngineTransaction, StagedChange, TransactionOutcome};
+use crate::truth_maintenance::{FactUpdateResult, RetractionResult};
+use crate::types::{
+    EngineStats, EvaluationDate, Fact, FactId, FactValue, FieldType, OverflowPolicy, PoolStats,
+    Rule, RuleId,
+};
 use crate::unified_statistics::UnifiedStats;
+use crate::webhook::{WebhookConfig, WebhookDispatcher, WebhookTarget};
+use crate::working_memory_profiler::{WorkingMemoryProfile, WorkingMemoryProfilerConfig};
 use bingo_calculator::calculator::Calculator;
-use std::sync::{Arc, RwLock};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::ops::RangeBounds;
+use std::sync::{Arc, Mutex, RwLock};
 use std::time::Instant;
-use tracing::info;
+use tracing::{info, warn};
 
 /// High-performance concurrent engine for processing rules and facts
 ///
@@ -59,6 +112,9 @@ pub struct BingoEngine {
     /// **Rule Registry**: Concurrent access to rule definitions
     rules: RwLock<Vec<Rule>>,
 
+    /// **Ruleset Version**: Bumped on every rule change, under the RETE network lock
+    ruleset_version: std::sync::atomic::AtomicU64,
+
     /// **Fact Storage**: Thread-safe arena-based fact store (already concurrent)
     fact_store: Arc<ArenaFactStore>,
 
@@ -79,6 +135,30 @@ pub struct BingoEngine {
 
     /// **Optimization Metrics**: Thread-safe tracking of rule optimization statistics
     optimization_metrics: RwLock<OptimizationMetrics>,
+
+    /// **Memory Pressure**: Watermarks and relief handlers guarding working memory growth
+    memory_pressure: RwLock<MemoryPressureMonitor>,
+
+    /// **Capacity Limits**: Fact and token limits with the eviction policy enforcing them
+    config: RwLock<EngineConfig>,
+    capacity_stats: RwLock<CapacityStats>,
+
+    /// **Fact Expiry**: Where facts carry their expiry, and the facts waiting to expire
+    fact_expiry: RwLock<Option<FactExpiryConfig>>,
+    expiry_schedule: Mutex<ExpirySchedule>,
+
+    /// **Lifecycle Events**: Rule, batch and limit notifications for subscribers
+    events: EventBus,
+    batch_sequence: std::sync::atomic::AtomicU64,
+
+    /// **Shadow Evaluation**: Candidate ruleset compared with the active one on every batch
+    shadow: RwLock<Option<Arc<ShadowEvaluation>>>,
+
+    /// **Replication**: Stream of rule changes and fact deltas feeding warm standbys
+    replication: RwLock<Option<Replicator>>,
+
+    /// **Transactions**: Held while a transaction commits, so commits never interleave
+    transaction_commit: Mutex<()>,
 }
 
 impl std::fmt::Debug for BingoEngine {
@@ -102,6 +182,7 @@ impl BingoEngine {
 
         Ok(Self {
             rules: RwLock::new(Vec::new()),
+            ruleset_version: std::sync::atomic::AtomicU64::new(0),
             fact_store,
             rete_network,
             calculator,
@@ -111,9 +192,36 @@ impl BingoEngine {
             total_rule_executions: std::sync::atomic::AtomicU64::new(0),
             cache_invalidations: std::sync::atomic::AtomicU64::new(0),
             optimization_metrics: RwLock::new(OptimizationMetrics::default()),
+            memory_pressure: RwLock::new(MemoryPressureMonitor::default()),
+            config: RwLock::new(EngineConfig::default()),
+            capacity_stats: RwLock::new(CapacityStats::default()),
+            fact_expiry: RwLock::new(None),
+            expiry_schedule: Mutex::new(ExpirySchedule::default()),
+            events: EventBus::new(),
+            batch_sequence: std::sync::atomic::AtomicU64::new(0),
+            shadow: RwLock::new(None),
+            replication: RwLock::new(None),
+            transaction_commit: Mutex::new(()),
         })
     }
 
+    /// Create an engine whose rule actions call the given calculators
+    ///
+    /// Used to make custom calculators, such as sandboxed WASM calculators, available
+    /// to `CallCalculator` actions alongside the built-in ones.
+    pub fn with_calculator(calculator: Calculator) -> BingoResult<Self> {
+        let mut engine = Self::new()?;
+        engine.calculator = Arc::new(calculator);
+        Ok(engine)
+    }
+
+    /// Create an engine enforcing the given working memory limits
+    pub fn with_config(config: EngineConfig) -> BingoResult<Self> {
+        let engine = Self::new()?;
+        engine.set_config(config)?;
+        Ok(engine)
+    }
+
     /// Create a concurrent thread-safe engine with capacity hint
     pub fn with_capacity(capacity: usize) -> BingoResult<Self> {
         let fact_store = Arc::new(ArenaFactStore::with_capacity(capacity));
@@ -123,6 +231,7 @@ impl BingoEngine {
 
         Ok(Self {
             rules: RwLock::new(Vec::with_capacity(capacity / 100)), // Estimate rules capacity
+            ruleset_version: std::sync::atomic::AtomicU64::new(0),
             fact_store,
             rete_network,
             calculator,
@@ -132,11 +241,78 @@ impl BingoEngine {
             total_rule_executions: std::sync::atomic::AtomicU64::new(0),
             cache_invalidations: std::sync::atomic::AtomicU64::new(0),
             optimization_metrics: RwLock::new(OptimizationMetrics::default()),
+            memory_pressure: RwLock::new(MemoryPressureMonitor::default()),
+            config: RwLock::new(EngineConfig::default()),
+            capacity_stats: RwLock::new(CapacityStats::default()),
+            fact_expiry: RwLock::new(None),
+            expiry_schedule: Mutex::new(ExpirySchedule::default()),
+            events: EventBus::new(),
+            batch_sequence: std::sync::atomic::AtomicU64::new(0),
+            shadow: RwLock::new(None),
+            replication: RwLock::new(None),
+            transaction_commit: Mutex::new(()),
+        })
+    }
+
+    /// Create an engine from an already compiled template engine
+    ///
+    /// The template's rules and compiled RETE network are copied without any facts,
+    /// tokens or activations, and the calculator is shared. The new engine starts with
+    /// an empty fact store, so sessions for the same ruleset skip rule compilation. The
+    /// template's capacity limits and fact expiry settings carry over; memory pressure
+    /// handlers and event subscribers do not.
+    pub fn from_template(template: &BingoEngine) -> BingoResult<Self> {
+        let rules = template.rules.read().unwrap().clone();
+        let rete_network = template.rete_network.read().unwrap().clone_compiled();
+        let optimization_metrics = template.optimization_metrics.read().unwrap().clone();
+
+        info!(
+            rule_count = rules.len(),
+            "Creating engine from compiled template"
+        );
+
+        Ok(Self {
+            rules: RwLock::new(rules),
+            ruleset_version: std::sync::atomic::AtomicU64::new(template.ruleset_version()),
+            fact_store: Arc::new(ArenaFactStore::new()),
+            rete_network: RwLock::new(rete_network),
+            calculator: template.calculator.clone(),
+            profiler: Arc::new(RwLock::new(EngineProfiler::new())),
+            fact_processing_count: std::sync::atomic::AtomicU64::new(0),
+            total_processing_time_ms: std::sync::atomic::AtomicU64::new(0),
+            total_rule_executions: std::sync::atomic::AtomicU64::new(0),
+            cache_invalidations: std::sync::atomic::AtomicU64::new(0),
+            optimization_metrics: RwLock::new(optimization_metrics),
+            memory_pressure: RwLock::new(MemoryPressureMonitor::default()),
+            config: RwLock::new(template.config()),
+            capacity_stats: RwLock::new(CapacityStats::default()),
+            fact_expiry: RwLock::new(template.fact_expiry()),
+            expiry_schedule: Mutex::new(ExpirySchedule::default()),
+            events: EventBus::new(),
+            batch_sequence: std::sync::atomic::AtomicU64::new(0),
+            shadow: RwLock::new(None),
+            replication: RwLock::new(None),
+            transaction_commit: Mutex::new(()),
         })
     }
 
     /// Add a rule to the engine (concurrent safe - uses write lock)
     pub fn add_rule(&self, rule: Rule) -> BingoResult<()> {
+        let (rule_id, rule_name) = (rule.id, rule.name.clone());
+        let replication = self.replication.read().unwrap();
+        self.install_rule(rule, replication.as_ref())?;
+        self.replicate_digest_if_due(replication.as_ref());
+        drop(replication);
+        self.events.publish(EngineEvent::RuleAdded {
+            rule_id,
+            rule_name,
+            ruleset_version: self.ruleset_version(),
+        });
+        Ok(())
+    }
+
+    /// Compile `rule` into the network under the duplicate rule policy
+    fn install_rule(&self, rule: Rule, replication: Option<&Replicator>) -> BingoResult<()> {
         info!(rule_id = rule.id, rule_name = %rule.name, "Adding rule to concurrent engine");
 
         // Write lock for rules (exclusive access)
@@ -145,11 +321,70 @@ impl BingoEngine {
         // Write lock for RETE network to add rule patterns
         let mut rete_network = self.rete_network.write().unwrap();
 
-        // Add rule to rules collection
-        rules.push(rule.clone());
+        self.check_actions(&rete_network, &rule)?;
+        self.check_cycles(&rete_network, &rules, &rule)?;
+
+        // Plan the rule's condition order with what the fact store indexes already hold
+        rete_network.record_index_statistics(&rule, &self.fact_store);
+
+        // Comparing rule bodies takes a pass over the ruleset, so accepting duplicates
+        // only looks for a shared ID
+        let policy = rete_network.duplicate_rule_policy();
+        let duplicates = match policy {
+            DuplicateRulePolicy::Allow => rules
+                .iter()
+                .filter(|existing| existing.id == rule.id)
+                .map(|existing| (existing.id, DuplicateKind::SameId))
+                .collect(),
+            _ => duplicates_of(&rule, &rules),
+        };
+        if !duplicates.is_empty() {
+            let described: Vec<String> = duplicates
+                .iter()
+                .map(|(rule_id, kind)| match kind {
+                    DuplicateKind::SameId => format!("rule {rule_id} has the same ID"),
+                    _ => format!("rule {rule_id} has the same conditions and actions"),
+                })
+                .collect();
+            match policy {
+                DuplicateRulePolicy::Reject => {
+                    return Err(BingoError::Rule {
+                        message: format!("Rule {} duplicates an existing rule", rule.id),
+                        rule_id: Some(rule.id),
+                        rule_name: Some(rule.name),
+                        details: Some(described.join("; ")),
+                    });
+                }
+                DuplicateRulePolicy::Replace => {
+                    info!(rule_id = rule.id, duplicates = %described.join("; "), "Replacing duplicate rules");
+                    let is_duplicate = |existing: &Rule| {
+                        duplicates.iter().any(|&(rule_id, _)| existing.id == rule_id)
+                    };
+                    let position = rules.iter().position(is_duplicate).unwrap_or(rules.len());
+                    let mut replaced: Vec<Rule> =
+                        rules.iter().filter(|existing| !is_duplicate(existing)).cloned().collect();
+                    replaced.insert(position, rule);
+                    *rete_network = Self::rebuild_network(&rete_network, &replaced)?;
+                    *rules = replaced;
+                    self.bump_ruleset_version();
+                    Self::replicate(replication, || {
+                        ReplicationRecord::RuleAdded(rules[position].clone())
+                    });
+                    return Ok(());
+                }
+                DuplicateRulePolicy::Allow => {
+                    warn!(rule_id = rule.id, duplicates = %described.join("; "), "Adding duplicate rule");
+                }
+            }
+        }
 
-        // Add rule to RETE network for pattern matching
-        rete_network.add_rule(rule)?;
+        // Add rule to RETE network for pattern matching; a rule it rejects is not kept
+        rete_network.add_rule(rule.clone())?;
+
+        // Add rule to rules collection
+        Self::replicate(replication, || ReplicationRecord::RuleAdded(rule.clone()));
+        rules.push(rule);
+        self.bump_ruleset_version();
 
         info!("Rule added successfully to concurrent engine");
         Ok(())
@@ -164,16 +399,41 @@ impl BingoEngine {
 
         let processing_start = Instant::now();
 
-        // Insert fact into thread-safe fact store (concurrent operation)
-        let _fact_id = self.fact_store.insert(fact.clone());
+        // Apply the non-finite float policy; a rejected fact is not processed
+        let mut facts = vec![fact];
+        self.rete_network.read().unwrap().ingest_non_finite(&mut facts)?;
+        self.expire_arrivals(&mut facts)?;
+        let Some(fact) = facts.pop() else {
+            return Ok(Vec::new());
+        };
+
+        // Make room for the incoming fact before it lands in working memory
+        let replication = self.replication.read().unwrap();
+        self.relieve_memory_pressure(1, replication.as_ref())?;
+        self.enforce_capacity(1, replication.as_ref())?;
+
+        // A replicated fact is stored under the network lock, so state digests never
+        // include facts the replication stream has not carried yet
+        let locked_network = replication.as_ref().map(|_| self.rete_network.write().unwrap());
+
+        // Insert fact into thread-safe fact store under the ID the strategy assigns
+        let fact_id = self.fact_store.try_insert(fact.clone())?;
+        let fact = Fact { id: fact_id, ..fact };
+        self.schedule_expiry(std::slice::from_ref(&fact));
 
         // Write lock for RETE network (fact processing modifies network state)
-        let mut rete_network = self.rete_network.write().unwrap();
+        let mut rete_network = locked_network.unwrap_or_else(|| self.rete_network.write().unwrap());
+        Self::replicate(replication.as_ref(), || ReplicationRecord::FactsInserted {
+            facts: vec![fact.clone()],
+            next_fact_id: self.fact_store.next_sequential_id(),
+        });
 
         // Process fact through RETE network
         let results = rete_network
             .process_facts(&[fact], &self.fact_store, &self.calculator)
             .map_err(|e| BingoError::rete_network("add_fact_to_working_memory", e.to_string()))?;
+        drop(rete_network);
+        self.replicate_digest_if_due(replication.as_ref());
 
         // Update atomic counters (lock-free)
         self.fact_processing_count.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
@@ -189,6 +449,178 @@ impl BingoEngine {
 
     /// Process multiple facts (concurrent safe - allows multiple concurrent calls)
     pub fn process_facts(&self, facts: Vec<Fact>) -> BingoResult<Vec<RuleExecutionResult>> {
+        self.process_facts_versioned(facts).map(|(results, _)| results)
+    }
+
+    /// Process multiple facts and report the ruleset version that produced the results
+    ///
+    /// Rule changes wait for processing to finish, so every result was produced by the
+    /// returned version of the ruleset.
+    pub fn process_facts_versioned(
+        &self,
+        facts: Vec<Fact>,
+    ) -> BingoResult<(Vec<RuleExecutionResult>, u64)> {
+        self.process_facts_dated(facts, EvaluationDate::Now)
+    }
+
+    /// Process multiple facts against the rules and reference table versions in effect
+    /// at `evaluation_date` rather than now
+    ///
+    /// Rerunning a past pay period with `EvaluationDate::At` its end date, or with
+    /// `EvaluationDate::EventTime` to date each fact by its timestamp, fires the rules
+    /// and uses the rates that applied back then. Batches evaluated as of another date
+    /// are not shadowed.
+    pub fn process_facts_as_of(
+        &self,
+        facts: Vec<Fact>,
+        evaluation_date: EvaluationDate,
+    ) -> BingoResult<Vec<RuleExecutionResult>> {
+        self.process_facts_dated(facts, evaluation_date).map(|(results, _)| results)
+    }
+
+    /// Process multiple facts as of `evaluation_date`, reporting the ruleset version
+    pub fn process_facts_dated(
+        &self,
+        facts: Vec<Fact>,
+        evaluation_date: EvaluationDate,
+    ) -> BingoResult<(Vec<RuleExecutionResult>, u64)> {
+        let batch_id = self.batch_sequence.fetch_add(1, std::sync::atomic::Ordering::Relaxed) + 1;
+        let fact_count = facts.len();
+        self.events.publish(EngineEvent::BatchStarted { batch_id, fact_count });
+
+        let batch_start = Instant::now();
+        let budget = EvaluationBudget::unlimited();
+        match self.process_batch(batch_id, facts, &budget, evaluation_date) {
+            Ok((results, ruleset_version, _)) => {
+                self.events.publish(EngineEvent::BatchFinished {
+                    batch_id,
+                    fact_count,
+                    rules_fired: results.len(),
+                    duration_ms: batch_start.elapsed().as_millis() as u64,
+                });
+                Ok((results, ruleset_version))
+            }
+            Err(error) => {
+                self.events
+                    .publish(EngineEvent::BatchFailed { batch_id, error: error.to_string() });
+                Err(error)
+            }
+        }
+    }
+
+    /// Process a batch until `budget` is spent, returning the results so far and a
+    /// continuation for the facts not evaluated yet
+    ///
+    /// The whole batch is stored and asserted into aggregation and window nodes before
+    /// its first fact is evaluated. The batch finishes, as far as batch events are
+    /// concerned, with its last slice. Budgeted batches are not shadowed, as their
+    /// results arrive in slices.
+    pub fn process_facts_budgeted(
+        &self,
+        facts: Vec<Fact>,
+        budget: EvaluationBudget,
+    ) -> BingoResult<BudgetedResults> {
+        let batch_id = self.batch_sequence.fetch_add(1, std::sync::atomic::Ordering::Relaxed) + 1;
+        let fact_count = facts.len();
+        self.events.publish(EngineEvent::BatchStarted { batch_id, fact_count });
+
+        let continuation = Continuation::new(batch_id, fact_count);
+        let slice = self.process_batch(batch_id, facts, &budget, EvaluationDate::Now).map(
+            |(results, ruleset_version, remaining)| {
+                let remaining = remaining.iter().map(|fact| fact.id).collect();
+                (results, ruleset_version, remaining)
+            },
+        );
+        self.finish_slice(continuation, slice)
+    }
+
+    /// Evaluate the next slice of a paused batch within `budget`
+    ///
+    /// Facts retracted while the batch was paused are skipped, and facts modified
+    /// meanwhile are evaluated as stored now. Rule changes made while it was paused
+    /// apply to the rest of the batch; the results report the ruleset version.
+    pub fn resume_evaluation(
+        &self,
+        continuation: Continuation,
+        budget: EvaluationBudget,
+    ) -> BingoResult<BudgetedResults> {
+        let slice = self.resume_batch(&continuation.fact_ids, &budget);
+        self.finish_slice(continuation, slice)
+    }
+
+    /// Evaluate the stored facts of a paused batch until `budget` is spent, returning
+    /// the IDs of the facts left to evaluate
+    fn resume_batch(
+        &self,
+        fact_ids: &[FactId],
+        budget: &EvaluationBudget,
+    ) -> BingoResult<(Vec<RuleExecutionResult>, u64, Vec<FactId>)> {
+        let processing_start = Instant::now();
+        let facts: Vec<Fact> = fact_ids
+            .iter()
+            .filter_map(|&fact_id| self.fact_store.get_fact(fact_id))
+            .collect();
+
+        let mut rete_network = self.rete_network.write().unwrap();
+        let ruleset_version = self.ruleset_version();
+        let (results, evaluated) = rete_network
+            .process_facts_within(&facts, false, budget, &self.fact_store, &self.calculator)
+            .map_err(|e| BingoError::rete_network("resume_evaluation", e.to_string()))?;
+        drop(rete_network);
+
+        self.record_processing(evaluated, results.len(), processing_start);
+        let remaining = facts[evaluated..].iter().map(|fact| fact.id).collect();
+        Ok((results, ruleset_version, remaining))
+    }
+
+    /// Report the outcome of a slice of a budgeted batch, publishing the batch's end
+    /// once no facts remain
+    fn finish_slice(
+        &self,
+        mut continuation: Continuation,
+        slice: BingoResult<(Vec<RuleExecutionResult>, u64, Vec<FactId>)>,
+    ) -> BingoResult<BudgetedResults> {
+        let batch_id = continuation.batch_id;
+        let (results, ruleset_version, remaining) = match slice {
+            Ok(slice) => slice,
+            Err(error) => {
+                self.events
+                    .publish(EngineEvent::BatchFailed { batch_id, error: error.to_string() });
+                return Err(error);
+            }
+        };
+
+        continuation.slices += 1;
+        continuation.rules_fired += results.len();
+        continuation.fact_ids = remaining;
+        let continuation = if continuation.fact_ids.is_empty() {
+            self.events.publish(EngineEvent::BatchFinished {
+                batch_id,
+                fact_count: continuation.fact_count,
+                rules_fired: continuation.rules_fired,
+                duration_ms: continuation.started.elapsed().as_millis() as u64,
+            });
+            None
+        } else {
+            info!(
+                batch_id = batch_id,
+                remaining_facts = continuation.fact_ids.len(),
+                "Paused batch on its evaluation budget"
+            );
+            Some(continuation)
+        };
+        Ok(BudgetedResults { results, ruleset_version, continuation })
+    }
+
+    /// Insert a batch of facts and run it through the RETE network as of
+    /// `evaluation_date` until `budget` is spent, returning the facts left to evaluate
+    fn process_batch(
+        &self,
+        batch_id: u64,
+        mut facts: Vec<Fact>,
+        budget: &EvaluationBudget,
+        evaluation_date: EvaluationDate,
+    ) -> BingoResult<(Vec<RuleExecutionResult>, u64, Vec<Fact>)> {
         info!(
             fact_count = facts.len(),
             "Processing facts through concurrent engine"
@@ -196,32 +628,105 @@ impl BingoEngine {
 
         let processing_start = Instant::now();
 
-        // Insert facts into thread-safe fact store (concurrent operation)
-        let _fact_ids = self.fact_store.bulk_insert_slice(&facts);
+        // Apply the non-finite float policy before the batch lands in working memory
+        self.rete_network.read().unwrap().ingest_non_finite(&mut facts)?;
+        self.expire_arrivals(&mut facts)?;
 
-        // Write lock for RETE network (fact processing modifies network state)
-        let mut rete_network = self.rete_network.write().unwrap();
+        // Make room for the incoming batch before it lands in working memory
+        let replication = self.replication.read().unwrap();
+        self.relieve_memory_pressure(facts.len(), replication.as_ref())?;
+        self.enforce_capacity(facts.len(), replication.as_ref())?;
 
-        // Process facts through RETE network
-        let results = rete_network
-            .process_facts(&facts, &self.fact_store, &self.calculator)
-            .map_err(|e| BingoError::rete_network("process_facts", e.to_string()))?;
+        // A replicated batch is stored under the network lock, so state digests never
+        // include facts the replication stream has not carried yet
+        let locked_network = replication.as_ref().map(|_| self.rete_network.write().unwrap());
 
-        // Update atomic counters (lock-free)
+        // Insert facts into thread-safe fact store; the network matches them by stored ID
+        let fact_ids = self.fact_store.try_bulk_insert_slice(&facts)?;
+        for (fact, fact_id) in facts.iter_mut().zip(fact_ids) {
+            fact.id = fact_id;
+        }
+        self.schedule_expiry(&facts);
+
+        // A shadowed candidate ruleset evaluates the batch on another thread meanwhile,
+        // unless the batch may be evaluated in slices or as of another date
+        let shadow = self
+            .shadow
+            .read()
+            .unwrap()
+            .clone()
+            .filter(|_| budget.is_unlimited() && evaluation_date == EvaluationDate::Now);
+        let (results, ruleset_version, evaluated) = std::thread::scope(|scope| {
+            let candidate = shadow.as_ref().map(|shadow| scope.spawn(|| shadow.evaluate(&facts)));
+
+            // Write lock for RETE network (fact processing modifies network state)
+            let mut rete_network =
+                locked_network.unwrap_or_else(|| self.rete_network.write().unwrap());
+            Self::replicate(replication.as_ref(), || ReplicationRecord::FactsInserted {
+                facts: facts.clone(),
+                next_fact_id: self.fact_store.next_sequential_id(),
+            });
+
+            let ruleset_version = self.ruleset_version();
+
+            // Process facts through RETE network, back on the clock afterwards
+            rete_network.set_evaluation_date(evaluation_date);
+            let results = rete_network
+                .process_facts_within(&facts, true, budget, &self.fact_store, &self.calculator)
+                .map_err(|e| BingoError::rete_network("process_facts", e.to_string()));
+            rete_network.set_evaluation_date(EvaluationDate::Now);
+            drop(rete_network);
+
+            if let (Some(shadow), Some(candidate), Ok((results, _))) =
+                (&shadow, candidate, &results)
+            {
+                let candidate = candidate.join().unwrap_or_else(|_| {
+                    Err(BingoError::rete_network(
+                        "shadow_evaluation",
+                        "candidate evaluation panicked",
+                    ))
+                });
+                shadow.record(batch_id, &facts, results, candidate);
+            }
+            results.map(|(results, evaluated)| (results, ruleset_version, evaluated))
+        })?;
+        self.replicate_digest_if_due(replication.as_ref());
+        drop(replication);
+
+        self.record_processing(evaluated, results.len(), processing_start);
+
+        info!(
+            results_count = results.len(),
+            "Completed concurrent fact processing"
+        );
+        let remaining = facts.split_off(evaluated);
+        Ok((results, ruleset_version, remaining))
+    }
+
+    /// Update the processing counters after evaluating `facts` facts (lock-free)
+    fn record_processing(&self, facts: usize, results: usize, processing_start: Instant) {
         self.fact_processing_count
-            .fetch_add(facts.len() as u64, std::sync::atomic::Ordering::Relaxed);
+            .fetch_add(facts as u64, std::sync::atomic::Ordering::Relaxed);
         self.total_processing_time_ms.fetch_add(
             processing_start.elapsed().as_millis() as u64,
             std::sync::atomic::Ordering::Relaxed,
         );
         self.total_rule_executions
-            .fetch_add(results.len() as u64, std::sync::atomic::Ordering::Relaxed);
+            .fetch_add(results as u64, std::sync::atomic::Ordering::Relaxed);
+    }
 
-        info!(
-            results_count = results.len(),
-            "Completed concurrent fact processing"
-        );
-        Ok(results)
+    /// Process multiple facts and summarize the results by rule
+    ///
+    /// The summary is computed from the returned results, so clients that only chart
+    /// per-rule figures can drop the detailed results without re-aggregating them.
+    pub fn process_facts_summarized(
+        &self,
+        facts: Vec<Fact>,
+    ) -> BingoResult<(Vec<RuleExecutionResult>, BatchSummary)> {
+        let fact_count = facts.len();
+        let results = self.process_facts(facts)?;
+        let summary = BatchSummary::from_results(fact_count, &results);
+        Ok((results, summary))
     }
 
     /// Get engine statistics (concurrent safe - uses read locks)
@@ -232,9 +737,10 @@ impl BingoEngine {
 
         let rete_stats = rete_network.get_stats();
 
-        // Calculate memory usage
-        let fact_memory = self.fact_store.len() * 200; // ~200 bytes per fact estimate
-        let total_memory = rete_stats.memory_usage_bytes as usize + fact_memory;
+        // Network memory plus fact slots, payloads and indexes held by the fact store
+        let total_memory = rete_stats.memory_usage_bytes as usize
+            + rules.capacity() * std::mem::size_of::<Rule>()
+            + self.fact_store.memory_breakdown().fact_store_bytes();
 
         EngineStats {
             rule_count: rules.len(),
@@ -272,6 +778,7 @@ impl BingoEngine {
     /// Clear all rules and facts from the engine (concurrent safe - uses write locks)
     pub fn clear(&self) {
         info!("Clearing all rules and facts from concurrent engine");
+        let replication = self.replication.read().unwrap();
 
         // Write lock for rules (exclusive access)
         let mut rules = self.rules.write().unwrap();
@@ -279,24 +786,50 @@ impl BingoEngine {
 
         // Clear facts from thread-safe fact store
         self.fact_store.clear();
+        self.expiry_schedule.lock().unwrap().clear();
 
         // Write lock for RETE network to clear and recreate
         let mut rete_network = self.rete_network.write().unwrap();
         rete_network.invalidate_lazy_aggregation_caches();
         *rete_network = ReteNetwork::new();
+        self.bump_ruleset_version();
+        Self::replicate(replication.as_ref(), || ReplicationRecord::Cleared);
+        drop((rete_network, rules));
+        self.replicate_digest_if_due(replication.as_ref());
+    }
+
+    /// Return the engine to the state of a new one, for reuse by an engine pool
+    ///
+    /// Unlike [`clear`](Self::clear), settings, statistics, reference data, event
+    /// subscribers, shadow evaluation and replication are dropped too, so nothing
+    /// carries over to the engine's next user. Calculators the engine was created
+    /// with are kept.
+    pub fn reset(&mut self) -> BingoResult<()> {
+        info!("Resetting engine");
+        let calculator = self.calculator.clone();
+        *self = Self { calculator, ..Self::new()? };
+        Ok(())
     }
 
     /// Clear only facts from the engine (concurrent safe - uses write locks)
     pub fn clear_facts(&self) {
         info!("Clearing facts from concurrent engine (keeping rules)");
+        let replication = self.replication.read().unwrap();
+
+        // Write lock for RETE network to clear created facts and working memory, taken
+        // first so no state digest sees the store cleared before the network
+        let mut rete_network = self.rete_network.write().unwrap();
 
         // Clear facts from thread-safe fact store
         self.fact_store.clear();
+        self.expiry_schedule.lock().unwrap().clear();
 
-        // Write lock for RETE network to clear created facts
-        let mut rete_network = self.rete_network.write().unwrap();
         rete_network.clear_created_facts();
+        rete_network.clear_working_memory();
         rete_network.invalidate_lazy_aggregation_caches();
+        Self::replicate(replication.as_ref(), || ReplicationRecord::FactsCleared);
+        drop(rete_network);
+        self.replicate_digest_if_due(replication.as_ref());
     }
 
     /// Get the number of rules loaded (concurrent safe - uses read lock)
@@ -305,6 +838,28 @@ impl BingoEngine {
         rules.len()
     }
 
+    /// Get a copy of a loaded rule by ID
+    pub fn get_rule(&self, rule_id: u64) -> Option<Rule> {
+        self.rules.read().unwrap().iter().find(|rule| rule.id == rule_id).cloned()
+    }
+
+    /// Get a copy of every loaded rule, in the order they were added
+    pub fn rules(&self) -> Vec<Rule> {
+        self.rules.read().unwrap().clone()
+    }
+
+    /// Version of the loaded ruleset
+    ///
+    /// Starts at 0 and increases with every rule added, updated or removed, so results
+    /// can be traced back to the ruleset that produced them.
+    pub fn ruleset_version(&self) -> u64 {
+        self.ruleset_version.load(std::sync::atomic::Ordering::Acquire)
+    }
+
+    fn bump_ruleset_version(&self) {
+        self.ruleset_version.fetch_add(1, std::sync::atomic::Ordering::AcqRel);
+    }
+
     /// Get the number of facts stored (concurrent safe - thread-safe fact store)
     pub fn fact_count(&self) -> usize {
         self.fact_store.len()
@@ -316,6 +871,51 @@ impl BingoEngine {
         profiler.clone()
     }
 
+    /// Assert facts created by rule actions back into working memory (concurrent safe)
+    ///
+    /// With `Some`, facts created while processing a batch flow through the network
+    /// in further rounds, so multi-step derivations complete in one call and their
+    /// results are returned with the batch's. A fact whose data was already derived
+    /// during the same call is not created again, and a batch still creating facts
+    /// after `max_iterations` rounds fails. `None` (the default) only returns them.
+    pub fn set_forward_chaining(&self, chaining: Option<ForwardChaining>) {
+        info!(?chaining, "Setting forward chaining");
+        self.rete_network.write().unwrap().set_forward_chaining(chaining);
+    }
+
+    /// Evaluate the alpha tests of large batches across a thread pool (concurrent safe)
+    ///
+    /// With `Some`, the candidate rules of the facts in batches of at least
+    /// `min_batch_size` facts, and the conditions of their single-condition rules, are
+    /// tested on a pool of `threads` threads before rules fire fact by fact in batch
+    /// order, so results match evaluating on one thread. Batches evaluated as of each
+    /// fact's event time are evaluated fact by fact. `None` (the default) stops the pool.
+    pub fn set_parallel_alpha(&self, config: Option<ParallelAlphaConfig>) -> BingoResult<()> {
+        info!(?config, "Setting parallel alpha evaluation");
+        self.rete_network.write().unwrap().set_parallel_alpha(config)
+    }
+
+    /// Parallel alpha evaluation setting, `None` when alpha tests run fact by fact
+    pub fn parallel_alpha(&self) -> Option<ParallelAlphaConfig> {
+        self.rete_network.read().unwrap().parallel_alpha()
+    }
+
+    /// Make reruns of the same facts and rules produce identical results (concurrent safe)
+    ///
+    /// With `true`, the rules a fact matches fire in rule ID order in working memory
+    /// processing as in batches, and facts created by actions take the timestamp of the
+    /// fact that triggered the rule. `encode_results` turns a run's results into bytes
+    /// to compare. Off by default.
+    pub fn set_deterministic(&self, deterministic: bool) {
+        info!(deterministic, "Setting deterministic execution");
+        self.rete_network.write().unwrap().set_deterministic(deterministic);
+    }
+
+    /// Whether deterministic execution is enabled
+    pub fn is_deterministic(&self) -> bool {
+        self.rete_network.read().unwrap().is_deterministic()
+    }
+
     /// Get created facts from RETE network (concurrent safe)
     pub fn get_created_facts(&self) -> Vec<Fact> {
         let rete_network = self.rete_network.read().unwrap();
@@ -335,37 +935,51 @@ impl BingoEngine {
     ) -> BingoResult<Vec<RuleExecutionResult>> {
         info!(fact_id = fact_id, "Removing fact from working memory");
 
-        // Actually remove the fact from the fact store
+        // Get read access to rules to check which rules might be affected
+        let replication = self.replication.read().unwrap();
+        let rules = self.rules.read().unwrap();
+
+        // A replicated removal happens under the network lock, like a replicated insert
+        let locked_network = replication.as_ref().map(|_| self.rete_network.write().unwrap());
+
+        // Actually remove the fact from the fact store, keeping it for result correlation
+        let removed_fact = self.fact_store.get_fact(fact_id);
         let fact_existed = self.fact_store.delete_fact(fact_id);
 
-        if !fact_existed {
+        let Some(removed_fact) = removed_fact.filter(|_| fact_existed) else {
             info!(fact_id = fact_id, "Fact not found for removal");
             return Ok(Vec::new());
-        }
+        };
 
-        // Get read access to rules to check which rules might be affected
-        let rules = self.rules.read().unwrap();
         let mut affected_rules = Vec::new();
 
         // For each rule, create a result indicating it was affected by the removal
         for rule in rules.iter() {
             use crate::rete_nodes::{ActionResult, RuleExecutionResult};
 
-            let result = RuleExecutionResult {
-                rule_id: rule.id,
-                fact_id,
-                actions_executed: vec![ActionResult::FieldSet {
+            let result = RuleExecutionResult::for_fact(
+                rule.id,
+                &removed_fact,
+                vec![ActionResult::FieldSet {
                     fact_id,
                     field: "status".to_string(),
                     value: crate::types::FactValue::String("removed".to_string()),
                 }],
-            };
+            );
             affected_rules.push(result);
         }
 
-        // Update RETE network to clear created facts
-        let mut rete_network = self.rete_network.write().unwrap();
+        // Update RETE network to withdraw the fact and clear created facts
+        let mut rete_network = locked_network.unwrap_or_else(|| self.rete_network.write().unwrap());
+        rete_network.remove_fact_from_working_memory(fact_id).map_err(|e| {
+            BingoError::rete_network("remove_fact_from_working_memory", e.to_string())
+        })?;
         rete_network.clear_created_facts();
+        Self::replicate(replication.as_ref(), || {
+            ReplicationRecord::FactsRemoved(vec![fact_id])
+        });
+        drop((rete_network, rules));
+        self.replicate_digest_if_due(replication.as_ref());
 
         info!(
             fact_id = fact_id,
@@ -374,126 +988,1571 @@ impl BingoEngine {
         Ok(affected_rules)
     }
 
-    /// Look up a fact by external ID (concurrent safe)
-    pub fn lookup_fact_by_id(&self, external_id: &str) -> Option<Fact> {
-        self.fact_store.get_by_external_id(external_id)
+    /// Retract a fact with truth maintenance (concurrent safe)
+    ///
+    /// Removes the fact from the fact store and the RETE network, withdraws the rule
+    /// activations it supported and retracts any derived facts that lose all of their
+    /// logical support as a result.
+    pub fn retract_fact(&self, fact_id: FactId) -> BingoResult<RetractionResult> {
+        info!(fact_id = fact_id, "Retracting fact with truth maintenance");
+
+        let replication = self.replication.read().unwrap();
+        let mut rete_network = self.rete_network.write().unwrap();
+        let retraction = rete_network
+            .retract_fact(fact_id)
+            .map_err(|e| BingoError::rete_network("retract_fact", e.to_string()))?;
+
+        self.fact_store.delete_fact(fact_id);
+        for &derived_id in &retraction.retracted_facts {
+            self.fact_store.delete_fact(derived_id);
+        }
+
+        // Aggregations computed over the store are stale once facts disappear
+        rete_network.invalidate_lazy_aggregation_caches();
+        self.cache_invalidations.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        Self::replicate(replication.as_ref(), || {
+            ReplicationRecord::FactRetracted(fact_id)
+        });
+        drop(rete_network);
+        self.replicate_digest_if_due(replication.as_ref());
+
+        info!(
+            fact_id = fact_id,
+            withdrawn_activations = retraction.withdrawn_activations.len(),
+            retracted_facts = retraction.retracted_facts.len(),
+            "Fact retracted"
+        );
+        Ok(retraction)
     }
 
-    /// Get a specific field value from a fact by external ID (concurrent safe)
-    pub fn get_field_by_id(&self, external_id: &str, field_name: &str) -> Option<FactValue> {
-        if let Some(fact) = self.fact_store.get_by_external_id(external_id) {
-            fact.data.fields.get(field_name).cloned()
-        } else {
-            None
+    /// Retract every stored fact matching `query` with truth maintenance (concurrent
+    /// safe)
+    ///
+    /// The facts are deleted from the fact store in one batch, then each is retracted
+    /// from the RETE network like `retract_fact`, withdrawing the activations it
+    /// supported and the derived facts left without support. A matching fact already
+    /// retracted as another's derived fact gets no retraction of its own.
+    pub fn retract_by_criteria(&self, query: &FactQuery) -> BingoResult<Vec<RetractionResult>> {
+        let replication = self.replication.read().unwrap();
+        let mut rete_network = self.rete_network.write().unwrap();
+        let fact_ids = self.fact_store.delete_by_criteria(query);
+        let retractions = self.retract_from_network(&mut rete_network, &fact_ids)?;
+        for retraction in &retractions {
+            Self::replicate(replication.as_ref(), || {
+                ReplicationRecord::FactRetracted(retraction.fact_id)
+            });
         }
+        drop(rete_network);
+        self.replicate_digest_if_due(replication.as_ref());
+
+        info!(
+            matched_facts = fact_ids.len(),
+            retracted_facts = retractions.len(),
+            "Retracted facts by criteria"
+        );
+        Ok(retractions)
     }
 
-    /// Evaluate rules against facts (simplified API for compatibility)
-    pub fn evaluate(
+    /// Apply `updates` to every stored fact matching `query` (concurrent safe)
+    ///
+    /// The facts are rewritten in the fact store in one batch. Their previous versions
+    /// are retracted from the RETE network with truth maintenance, then the updated
+    /// facts run through it again, firing the rules their new values match. Updated
+    /// facts retracted as another's derived fact stay deleted and are not reported as
+    /// updated.
+    pub fn update_by_criteria(
         &self,
-        rules: Vec<Rule>,
-        facts: Vec<Fact>,
-    ) -> BingoResult<Vec<RuleExecutionResult>> {
+        query: &FactQuery,
+        updates: &HashMap<String, FactValue>,
+    ) -> BingoResult<FactUpdateResult> {
+        let processing_start = Instant::now();
+        let replication = self.replication.read().unwrap();
+        let mut rete_network = self.rete_network.write().unwrap();
+        let mut updated = self.fact_store.update_matching(query, updates);
+        let mut updated_facts: Vec<FactId> = updated.iter().map(|fact| fact.id).collect();
+
+        let retractions = self.retract_from_network(&mut rete_network, &updated_facts)?;
+        let retracted: HashSet<FactId> = retractions
+            .iter()
+            .flat_map(|retraction| retraction.retracted_facts.iter().copied())
+            .collect();
+        updated.retain(|fact| !retracted.contains(&fact.id));
+        updated_facts.retain(|fact_id| !retracted.contains(fact_id));
+        self.schedule_expiry(&updated);
+
+        for retraction in &retractions {
+            Self::replicate(replication.as_ref(), || {
+                ReplicationRecord::FactRetracted(retraction.fact_id)
+            });
+        }
+        Self::replicate(replication.as_ref(), || ReplicationRecord::FactsInserted {
+            facts: updated.clone(),
+            next_fact_id: self.fact_store.next_sequential_id(),
+        });
+        let results = rete_network
+            .process_facts(&updated, &self.fact_store, &self.calculator)
+            .map_err(|e| BingoError::rete_network("update_by_criteria", e.to_string()))?;
+        drop(rete_network);
+        self.replicate_digest_if_due(replication.as_ref());
+        self.record_processing(updated.len(), results.len(), processing_start);
+
         info!(
-            "Evaluating {} rules against {} facts",
-            rules.len(),
-            facts.len()
+            updated_facts = updated_facts.len(),
+            results_count = results.len(),
+            "Updated facts by criteria"
         );
+        Ok(FactUpdateResult { updated_facts, retractions, results })
+    }
 
-        // Add rules to engine
-        for rule in rules {
-            self.add_rule(rule)?;
+    /// Retract facts already changed in the fact store from the RETE network, deleting
+    /// the derived facts that lose their support
+    fn retract_from_network(
+        &self,
+        rete_network: &mut ReteNetwork,
+        fact_ids: &[FactId],
+    ) -> BingoResult<Vec<RetractionResult>> {
+        let mut retractions = Vec::with_capacity(fact_ids.len());
+        let mut derived: Vec<FactId> = Vec::new();
+        let mut retracted = HashSet::new();
+        for &fact_id in fact_ids {
+            if retracted.contains(&fact_id) {
+                continue;
+            }
+            let retraction = rete_network
+                .retract_fact(fact_id)
+                .map_err(|e| BingoError::rete_network("retract_fact", e.to_string()))?;
+            retracted.extend(retraction.retracted_facts.iter().copied());
+            derived.extend_from_slice(&retraction.retracted_facts);
+            retractions.push(retraction);
         }
+        self.fact_store.delete_facts(&derived);
 
-        // Process facts
-        let results = self.process_facts(facts)?;
-
-        Ok(results)
+        // Aggregations computed over the store are stale once facts change
+        rete_network.invalidate_lazy_aggregation_caches();
+        self.cache_invalidations.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        Ok(retractions)
     }
 
-    /// Add a rule with optimization
-    pub fn add_rule_optimized(&self, rule: Rule) -> BingoResult<OptimizationResult> {
-        // Add the rule and track optimization metrics
-        let rule_clone = rule.clone();
-        self.add_rule(rule)?;
+    /// Set the working memory watermarks (concurrent safe)
+    pub fn set_memory_watermarks(&self, watermarks: MemoryWatermarks) {
+        info!(
+            warning_bytes = watermarks.warning_bytes,
+            high_bytes = watermarks.high_bytes,
+            critical_bytes = watermarks.critical_bytes,
+            "Setting working memory watermarks"
+        );
+        self.memory_pressure.write().unwrap().set_watermarks(watermarks);
+    }
 
-        // Update optimization metrics
-        {
-            let mut metrics = self.optimization_metrics.write().unwrap();
-            metrics.rules_optimized += 1;
-        }
+    /// Register a handler invoked once working memory reaches `min_level` (concurrent safe)
+    ///
+    /// Handlers run in registration order before facts are inserted, until memory usage
+    /// drops back below the warning watermark.
+    pub fn register_memory_pressure_handler(
+        &self,
+        min_level: PressureLevel,
+        handler: impl MemoryPressureHandler + 'static,
+    ) {
+        self.memory_pressure.write().unwrap().register_handler(min_level, handler);
+    }
 
-        Ok(OptimizationResult {
-            original_rule: rule_clone.clone(),
-            optimized_rule: rule_clone,
-            estimated_improvement: 0.0,
-            strategies_applied: vec![],
-            analysis: crate::rule_optimizer::OptimizationAnalysis {
-                condition_selectivity: vec![],
-                condition_costs: vec![],
-                join_analysis: None,
-                shared_patterns: vec![],
-                total_improvement_estimate: 0.0,
-            },
-        })
+    /// Get memory pressure statistics (concurrent safe)
+    pub fn memory_pressure_stats(&self) -> MemoryPressureStats {
+        self.memory_pressure.read().unwrap().stats().clone()
     }
 
-    /// Update an existing rule
-    pub fn update_rule(&self, rule: Rule) -> BingoResult<()> {
-        // Remove the existing rule first, then add the updated rule
-        self.remove_rule(rule.id)?;
-        self.add_rule(rule)
+    /// Memory held by the engine, broken down by component (concurrent safe)
+    ///
+    /// `get_stats().memory_usage_bytes` reports the total of this breakdown.
+    pub fn memory_breakdown(&self) -> MemoryBreakdown {
+        let rules_bytes = self.rules.read().unwrap().capacity() * std::mem::size_of::<Rule>();
+        let network = self.rete_network.read().unwrap().memory_breakdown();
+        let store = self.fact_store.memory_breakdown();
+
+        MemoryBreakdown {
+            fact_headers: store.fact_headers,
+            fact_payloads: store.fact_payloads,
+            indexes: store.indexes,
+            network_nodes: network.network_nodes + rules_bytes,
+            ..network
+        }
     }
 
-    /// Remove a rule by ID
-    pub fn remove_rule(&self, rule_id: u64) -> BingoResult<()> {
-        info!(rule_id = rule_id, "Removing rule from engine");
+    /// Size and composition of working memory, grouping facts by `type` and `tenant_id`
+    /// (concurrent safe)
+    ///
+    /// Print the report for a human-readable summary or call `to_json()` on it.
+    pub fn memory_report(&self) -> MemoryReport {
+        self.memory_report_with(&MemoryReportOptions::default())
+    }
 
-        // Write lock for rules (exclusive access)
-        let mut rules = self.rules.write().unwrap();
+    /// Size and composition of working memory with custom grouping fields and list
+    /// lengths (concurrent safe)
+    pub fn memory_report_with(&self, options: &MemoryReportOptions) -> MemoryReport {
+        let (beta_memories, top_rules) = {
+            let rete_network = self.rete_network.read().unwrap();
+            (
+                rete_network.beta_memory_usage(),
+                rete_network.rule_memory_usage(),
+            )
+        };
+
+        MemoryReport {
+            total_bytes: 0,
+            breakdown: self.memory_breakdown(),
+            fact_count: self.fact_store.len(),
+            facts_by_type: self.fact_store.group_usage(&options.type_field),
+            facts_by_tenant: self.fact_store.group_usage(&options.tenant_field),
+            field_indexes: self.fact_store.index_usage(),
+            beta_memories,
+            top_rules,
+        }
+        .ranked(options.max_entries)
+    }
 
-        // Find and remove the rule
-        if let Some(pos) = rules.iter().position(|r| r.id == rule_id) {
-            rules.remove(pos);
+    /// Estimated working memory checked against the watermarks (concurrent safe)
+    pub fn estimated_memory_usage(&self) -> usize {
+        self.memory_pressure.read().unwrap().estimate_usage(&self.fact_store)
+    }
 
-            // Write lock for RETE network to rebuild without the removed rule
-            let mut rete_network = self.rete_network.write().unwrap();
-            rete_network.invalidate_lazy_aggregation_caches();
+    /// Run the memory pressure handlers ahead of inserting `incoming` facts
+    ///
+    /// Facts released by the handlers are withdrawn from the RETE network. Returns a
+    /// memory error when a handler refuses the incoming facts.
+    fn relieve_memory_pressure(
+        &self,
+        incoming: usize,
+        replication: Option<&Replicator>,
+    ) -> BingoResult<()> {
+        let mut monitor = self.memory_pressure.write().unwrap();
+        if !monitor.watermarks().is_enabled() {
+            return Ok(());
+        }
 
-            // Rebuild RETE network with remaining rules
-            *rete_network = ReteNetwork::new();
-            for rule in rules.iter() {
-                rete_network.add_rule(rule.clone())?;
+        // Handlers delete facts from the store, which replicated releases do under the
+        // network lock
+        let mut locked_network = replication.map(|_| self.rete_network.write().unwrap());
+        let outcome = monitor.check(&self.fact_store, incoming);
+
+        if !outcome.released_facts.is_empty() {
+            let mut rete_network =
+                locked_network.take().unwrap_or_else(|| self.rete_network.write().unwrap());
+            for &fact_id in &outcome.released_facts {
+                rete_network.remove_fact_from_working_memory(fact_id).map_err(|e| {
+                    BingoError::rete_network("relieve_memory_pressure", e.to_string())
+                })?;
             }
-
-            info!(rule_id = rule_id, "Rule removed successfully");
-        } else {
-            return Err(BingoError::rule_validation(format!(
-                "Rule with ID {rule_id} not found"
-            )));
+            rete_network.invalidate_lazy_aggregation_caches();
+            self.cache_invalidations.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            Self::replicate(replication, || {
+                ReplicationRecord::FactsRemoved(outcome.released_facts.clone())
+            });
+        }
+        drop(locked_network);
+
+        if outcome.rejected {
+            let reason = format!(
+                "Rejected {incoming} incoming facts under {:?} memory pressure",
+                outcome.level
+            );
+            let critical_bytes = monitor.watermarks().critical_bytes;
+            drop(monitor);
+            self.events.publish(EngineEvent::CircuitBreakerTripped {
+                breaker: "memory_pressure".to_string(),
+                reason: reason.clone(),
+            });
+            return Err(BingoError::memory_allocation(
+                "working_memory",
+                outcome.used_bytes,
+                critical_bytes,
+                reason,
+            ));
         }
-
         Ok(())
     }
 
-    /// Add multiple rules (bulk operation)
-    pub fn add_rules(&self, rules: Vec<Rule>) -> BingoResult<()> {
-        for rule in rules {
-            self.add_rule(rule)?;
-        }
+    /// Set the working memory capacity limits and eviction policy (concurrent safe)
+    ///
+    /// The limits apply from the next insert; facts already held are not evicted until
+    /// then.
+    pub fn set_config(&self, config: EngineConfig) -> BingoResult<()> {
+        config.validate()?;
+        info!(
+            max_working_memory_facts = ?config.max_working_memory_facts,
+            max_tokens = ?config.max_tokens,
+            eviction = ?config.eviction,
+            "Setting engine capacity limits"
+        );
+        *self.config.write().unwrap() = config;
         Ok(())
     }
 
-    /// Generate performance report
-    pub fn generate_performance_report(&self) -> PerformanceReport {
-        let profiler = self.profiler.read().unwrap();
-        let stats = self.get_stats();
-        let mut unified_stats = UnifiedStats::new();
-
-        // Register basic engine statistics
-        unified_stats.register_fact_storage("ArenaFactStore", stats.fact_count, 0);
+    /// Get the working memory capacity limits (concurrent safe)
+    pub fn config(&self) -> EngineConfig {
+        self.config.read().unwrap().clone()
+    }
 
-        profiler.generate_report(unified_stats)
+    /// Get facts evicted and inserts refused under the capacity limits (concurrent safe)
+    pub fn capacity_stats(&self) -> CapacityStats {
+        self.capacity_stats.read().unwrap().clone()
+    }
+
+    /// Evict facts to keep working memory within the capacity limits ahead of inserting
+    /// `incoming` facts
+    ///
+    /// Returns a capacity error when the eviction policy cannot make enough room.
+    fn enforce_capacity(
+        &self,
+        incoming: usize,
+        replication: Option<&Replicator>,
+    ) -> BingoResult<()> {
+        let config = self.config.read().unwrap().clone();
+        if !config.is_bounded() {
+            return Ok(());
+        }
+
+        let mut rete_network = self.rete_network.write().unwrap();
+        let mut candidates = None;
+        let mut evicted = Vec::new();
+        let mut evict = |rete_network: &mut ReteNetwork, fact_id: FactId| -> BingoResult<()> {
+            if self.fact_store.delete_fact(fact_id) {
+                rete_network
+                    .remove_fact_from_working_memory(fact_id)
+                    .map_err(|e| BingoError::rete_network("enforce_capacity", e.to_string()))?;
+                evicted.push(fact_id);
+            }
+            Ok(())
+        };
+
+        let mut exceeded = None;
+        if let Some(limit) = config.max_working_memory_facts {
+            let excess = (self.fact_store.len() + incoming).saturating_sub(limit);
+            if incoming > limit {
+                exceeded = Some(("working_memory_facts", limit, incoming));
+            } else if excess > 0 {
+                let facts = candidates.get_or_insert_with(|| {
+                    config.eviction_candidates(&self.fact_store).into_iter()
+                });
+                for fact_id in facts.take(excess) {
+                    evict(&mut rete_network, fact_id)?;
+                }
+                let requested = self.fact_store.len() + incoming;
+                if requested > limit {
+                    exceeded = Some(("working_memory_facts", limit, requested));
+                }
+            }
+        }
+        if let (None, Some(limit)) = (exceeded, config.max_tokens) {
+            if rete_network.token_count() >= limit {
+                let facts = candidates.get_or_insert_with(|| {
+                    config.eviction_candidates(&self.fact_store).into_iter()
+                });
+                for fact_id in facts.by_ref() {
+                    evict(&mut rete_network, fact_id)?;
+                    if rete_network.token_count() < limit {
+                        break;
+                    }
+                }
+                let tokens = rete_network.token_count();
+                if tokens >= limit {
+                    exceeded = Some(("tokens", limit, tokens));
+                }
+            }
+        }
+
+        if !evicted.is_empty() {
+            rete_network.invalidate_lazy_aggregation_caches();
+            self.cache_invalidations.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            warn!(
+                evicted_facts = evicted.len(),
+                eviction = ?config.eviction,
+                "Evicted facts to stay within working memory limits"
+            );
+        }
+
+        let mut stats = self.capacity_stats.write().unwrap();
+        stats.facts_evicted += evicted.len() as u64;
+        if !evicted.is_empty() {
+            Self::replicate(replication, || ReplicationRecord::FactsRemoved(evicted));
+        }
+        let Some((resource, limit, requested)) = exceeded else {
+            return Ok(());
+        };
+        stats.inserts_rejected += incoming as u64;
+        drop((stats, rete_network));
+        self.events.publish(EngineEvent::LimitBreached {
+            resource: resource.to_string(),
+            limit,
+            requested,
+        });
+        Err(BingoError::capacity_exceeded(
+            resource,
+            limit,
+            requested,
+            format!(
+                "Rejected {incoming} incoming facts: {resource} limit of {limit} reached and \
+                 {:?} eviction cannot make room",
+                config.eviction
+            ),
+        ))
+    }
+
+    /// Keep up to `config.max_versions` earlier versions of each fact modified by a rule
+    /// action or an update, or stop keeping them with `None` (concurrent safe)
+    pub fn set_fact_history(&self, config: Option<FactHistoryConfig>) {
+        info!(fact_history = ?config, "Setting fact history");
+        self.fact_store.set_history(config);
+    }
+
+    /// Versions of a stored fact, oldest first and ending with the current one, each
+    /// with the fields changed from the version before it and the rule that changed
+    /// them (concurrent safe)
+    ///
+    /// Empty when no fact has `fact_id`. Facts modified while history was disabled
+    /// report only their current version.
+    pub fn get_fact_history(&self, fact_id: FactId) -> Vec<FactVersion> {
+        self.fact_store.get_fact_history(fact_id)
+    }
+
+    /// Set where facts carry their expiry, or turn fact expiry off with `None`
+    /// (concurrent safe)
+    ///
+    /// Facts already in working memory are scheduled under the new settings; any that
+    /// have expired under them are retracted by the next insert or sweep.
+    pub fn set_fact_expiry(&self, config: Option<FactExpiryConfig>) -> BingoResult<()> {
+        if let Some(config) = &config {
+            config.validate()?;
+        }
+        info!(fact_expiry = ?config, "Setting fact expiry");
+        *self.fact_expiry.write().unwrap() = config;
+        self.reschedule_expiry();
+        Ok(())
+    }
+
+    /// Get the fact expiry settings, `None` when facts never expire (concurrent safe)
+    pub fn fact_expiry(&self) -> Option<FactExpiryConfig> {
+        self.fact_expiry.read().unwrap().clone()
+    }
+
+    /// Get facts waiting to expire and facts expired so far (concurrent safe)
+    pub fn fact_expiry_stats(&self) -> FactExpiryStats {
+        self.expiry_schedule.lock().unwrap().stats()
+    }
+
+    /// Retract the facts that have expired by now, with the facts derived from them
+    /// (concurrent safe)
+    ///
+    /// Inserts call this before adding facts; call it directly, or run a
+    /// [`FactExpirySweeper`](crate::fact_expiry::FactExpirySweeper), to retract expired
+    /// facts while no facts arrive.
+    pub fn expire_facts(&self) -> BingoResult<Vec<RetractionResult>> {
+        self.expire_facts_at(chrono::Utc::now())
+    }
+
+    /// Retract the facts that have expired by `now`, with the facts derived from them
+    /// (concurrent safe)
+    ///
+    /// Lets engines driven by event time rather than wall-clock time expire facts as
+    /// that time advances.
+    pub fn expire_facts_at(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> BingoResult<Vec<RetractionResult>> {
+        let Some(config) = self.fact_expiry() else {
+            return Ok(Vec::new());
+        };
+        let due = self.expiry_schedule.lock().unwrap().take_due(now);
+
+        let mut retractions = Vec::new();
+        for fact_id in due {
+            // The fact may have been removed, or replaced by one expiring later
+            let expired = self
+                .fact_store
+                .get_fact(fact_id)
+                .is_some_and(|fact| config.is_expired(&fact, now));
+            if expired {
+                retractions.push(self.retract_fact(fact_id)?);
+            }
+        }
+
+        if !retractions.is_empty() {
+            let derived: usize =
+                retractions.iter().map(|retraction| retraction.retracted_facts.len()).sum();
+            let mut schedule = self.expiry_schedule.lock().unwrap();
+            let stats = schedule.stats_mut();
+            stats.expired += retractions.len() as u64;
+            stats.derived_retracted += derived as u64;
+            info!(
+                expired_facts = retractions.len(),
+                derived_facts = derived,
+                "Retracted expired facts"
+            );
+        }
+        Ok(retractions)
+    }
+
+    /// Whether `fact` has expired under the fact expiry settings
+    fn is_expired(&self, fact: &Fact) -> bool {
+        self.fact_expiry
+            .read()
+            .unwrap()
+            .as_ref()
+            .is_some_and(|config| config.is_expired(fact, chrono::Utc::now()))
+    }
+
+    /// Retract the facts due to expire and drop incoming facts that have already expired
+    fn expire_arrivals(&self, facts: &mut Vec<Fact>) -> BingoResult<()> {
+        let Some(config) = self.fact_expiry() else {
+            return Ok(());
+        };
+        let now = chrono::Utc::now();
+        if self.expiry_schedule.lock().unwrap().has_due(now) {
+            self.expire_facts_at(now)?;
+        }
+
+        let arrived = facts.len();
+        facts.retain(|fact| !config.is_expired(fact, now));
+        let dropped = arrived - facts.len();
+        if dropped > 0 {
+            self.expiry_schedule.lock().unwrap().stats_mut().expired_on_arrival += dropped as u64;
+            warn!(
+                dropped_facts = dropped,
+                "Dropped facts that expired before they arrived"
+            );
+        }
+        Ok(())
+    }
+
+    /// Schedule the expiry of facts just inserted under their stored IDs
+    fn schedule_expiry(&self, facts: &[Fact]) {
+        if let Some(config) = self.fact_expiry.read().unwrap().as_ref() {
+            self.expiry_schedule.lock().unwrap().schedule(config, facts);
+        }
+    }
+
+    /// Rebuild the expiry schedule from the facts in working memory
+    fn reschedule_expiry(&self) {
+        let config = self.fact_expiry.read().unwrap();
+        let mut schedule = self.expiry_schedule.lock().unwrap();
+        schedule.clear();
+        if let Some(config) = config.as_ref() {
+            schedule.schedule(config, &self.fact_store.iter());
+        }
+    }
+
+    /// Capture an immutable snapshot of rules, compiled network and facts
+    ///
+    /// Read locks are held only while copying, so the primary keeps processing facts
+    /// while followers serve reads from the snapshot.
+    pub fn snapshot(&self) -> BingoResult<Arc<EngineSnapshot>> {
+        let stats = self.get_stats();
+        let rules = self.rules.read().unwrap().clone();
+        let rete_network = self.rete_network.read().unwrap().clone_compiled();
+        let facts = self.fact_store.iter();
+
+        let snapshot = EngineSnapshot::new(rules, rete_network, facts, stats).with_fact_ids(
+            self.fact_store.id_strategy(),
+            self.fact_store.next_sequential_id(),
+        );
+        Ok(Arc::new(snapshot))
+    }
+
+    /// Stage inserts and retracts that apply to working memory together on commit
+    ///
+    /// See [`crate::transaction`] for how a failed commit rolls back.
+    pub fn begin_transaction(&self) -> EngineTransaction<'_> {
+        EngineTransaction::new(self)
+    }
+
+    /// Apply a transaction's staged changes, undoing them if a change or `check` fails
+    pub(crate) fn commit_transaction(
+        &self,
+        changes: Vec<StagedChange>,
+        check: impl FnOnce(&TransactionOutcome) -> BingoResult<()>,
+    ) -> BingoResult<TransactionOutcome> {
+        let _commit = self.transaction_commit.lock().unwrap();
+        self.hold_side_effects();
+        self.fact_store.begin_journal();
+
+        let outcome = self
+            .apply_staged_changes(changes)
+            .and_then(|outcome| check(&outcome).map(|()| outcome));
+        let journal = self.fact_store.end_journal();
+        let Err(error) = outcome else {
+            if let Some(journal) = journal {
+                self.fact_store.discard_journal(journal);
+            }
+            self.release_side_effects(true);
+            return outcome;
+        };
+
+        self.release_side_effects(false);
+        self.undo_transaction(journal).map_err(|rollback_error| {
+            BingoError::internal_component(
+                "transaction",
+                format!("Failed to roll back transaction after '{error}': {rollback_error}"),
+            )
+        })?;
+        info!(error = %error, "Rolled back transaction");
+        Err(error)
+    }
+
+    /// Put back the facts a failed commit changed and undo what it changed in the RETE
+    /// network
+    fn undo_transaction(&self, journal: Option<FactJournal>) -> BingoResult<()> {
+        let mut rete_network = self.rete_network.write().unwrap();
+        let undone = journal.map(|journal| self.fact_store.undo(journal)).unwrap_or_default();
+        self.schedule_expiry(&undone.reinstated);
+        rete_network
+            .roll_back_transaction(&undone, &self.fact_store)
+            .map_err(|e| BingoError::rete_network("roll_back_transaction", e.to_string()))?;
+        self.cache_invalidations.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn apply_staged_changes(&self, changes: Vec<StagedChange>) -> BingoResult<TransactionOutcome> {
+        let mut outcome = TransactionOutcome::default();
+        for change in changes {
+            match change {
+                StagedChange::Insert(facts) => outcome.results.extend(self.process_facts(facts)?),
+                StagedChange::Retract(fact_id) => {
+                    outcome.retractions.push(self.retract_fact(fact_id)?)
+                }
+            }
+        }
+        Ok(outcome)
+    }
+
+    /// Hold back the webhook actions and replication records of the calling thread
+    /// until `release_side_effects`
+    fn hold_side_effects(&self) {
+        self.rete_network.write().unwrap().hold_transaction();
+        if let Some(replicator) = self.replication.read().unwrap().as_ref() {
+            replicator.hold();
+        }
+    }
+
+    /// Dispatch the held webhook actions and stream the held replication records if
+    /// `apply` is true; otherwise drop the records and leave the held webhook actions
+    /// for `undo_transaction` to drop
+    fn release_side_effects(&self, apply: bool) {
+        if apply {
+            self.rete_network.write().unwrap().release_transaction();
+        }
+        if let Some(replicator) = self.replication.read().unwrap().as_ref() {
+            replicator.release(apply);
+        }
+    }
+
+    /// Replace the engine's rules, facts and settings with those of `snapshot`
+    ///
+    /// Calendars, reference tables, webhooks and calculators registered with this
+    /// engine are kept, and the restored rules are compiled against them; a rule that
+    /// fails to compile leaves the engine unchanged. Network memories are rebuilt from
+    /// the restored facts as later batches need them.
+    pub fn restore(&self, snapshot: &EngineSnapshot) -> BingoResult<()> {
+        let mut rules = self.rules.write().unwrap();
+        let mut rete_network = self.rete_network.write().unwrap();
+
+        let settings = snapshot.settings();
+        let mut restored = Self::rebuild_network(&rete_network, snapshot.rules())?;
+        settings.apply_to(&mut restored);
+        self.fact_store.set_id_strategy(settings.fact_id_strategy)?;
+
+        self.fact_store.clear();
+        for fact in snapshot.facts() {
+            self.fact_store.insert_with_id(fact);
+        }
+        self.fact_store.resume_sequential_ids(settings.next_fact_id);
+
+        *rete_network = restored;
+        *rules = snapshot.rules().to_vec();
+        self.bump_ruleset_version();
+        self.reschedule_expiry();
+
+        info!(
+            rule_count = rules.len(),
+            fact_count = self.fact_store.len(),
+            taken_at = %snapshot.taken_at(),
+            "Restored engine from snapshot"
+        );
+        Ok(())
+    }
+
+    /// Capture the compiled ruleset for warm-starting other engines
+    ///
+    /// `CompiledRuleset::to_bytes` encodes it into an artifact that `load_compiled`
+    /// installs without optimising, validating and building the rules again.
+    pub fn compile_ruleset(&self) -> CompiledRuleset {
+        let rules = self.rules.read().unwrap();
+        let rete_network = self.rete_network.read().unwrap();
+        CompiledRuleset::new(rules.clone(), rete_network.to_compiled())
+    }
+
+    /// Replace the engine's rules with those of an artifact encoded by
+    /// `CompiledRuleset::to_bytes`
+    ///
+    /// The artifact's nodes are installed as they were compiled, against the calendars,
+    /// reference tables, globals and webhooks registered with this engine; if a rule
+    /// refers to one that is missing, loading fails and leaves the engine unchanged.
+    /// Stored facts are kept, and network memories are rebuilt from them as later
+    /// batches need them.
+    pub fn load_compiled(&self, bytes: &[u8]) -> BingoResult<()> {
+        let (compiled_rules, network) = CompiledRuleset::from_bytes(bytes)?.into_parts();
+        let mut rules = self.rules.write().unwrap();
+        let mut rete_network = self.rete_network.write().unwrap();
+        for rule in &compiled_rules {
+            self.check_actions(&rete_network, rule)?;
+        }
+
+        rete_network.invalidate_lazy_aggregation_caches();
+        let mut loaded = rete_network.without_rules();
+        loaded.install_compiled(&network)?;
+        *rete_network = loaded;
+        *rules = compiled_rules;
+        self.bump_ruleset_version();
+
+        info!(
+            rule_count = rules.len(),
+            alpha_nodes = network.alpha_nodes.len(),
+            beta_nodes = network.beta_nodes.len(),
+            "Loaded precompiled ruleset"
+        );
+        Ok(())
+    }
+
+    /// Spawn a read-only follower serving queries from a fresh snapshot
+    ///
+    /// Clone the returned follower to share the same snapshot across more readers.
+    pub fn spawn_follower(&self) -> BingoResult<FollowerEngine> {
+        let follower = FollowerEngine::new(self.snapshot()?);
+        info!(
+            fact_count = follower.fact_count(),
+            "Spawned read-only follower engine"
+        );
+        Ok(follower)
+    }
+
+    /// Snapshot the engine and stream every later rule change and fact delta to `sink`
+    ///
+    /// The snapshot is taken while no replicated change is in flight, so a standby
+    /// restored from it and applying the stream from sequence 1 matches this engine.
+    /// A state digest follows the first change after each `config.digest_interval`.
+    /// Replaces any replication already running.
+    pub fn start_replication(
+        &self,
+        config: ReplicationConfig,
+        sink: impl ReplicationSink + 'static,
+    ) -> BingoResult<Arc<EngineSnapshot>> {
+        let mut replication = self.replication.write().unwrap();
+        let snapshot = self.snapshot()?;
+        *replication = Some(Replicator::new(Box::new(sink), &config));
+
+        info!(
+            rule_count = snapshot.rules().len(),
+            digest_interval_ms = config.digest_interval.as_millis() as u64,
+            "Started replication stream"
+        );
+        Ok(snapshot)
+    }
+
+    /// Stop streaming changes; returns whether replication was running
+    pub fn stop_replication(&self) -> bool {
+        let stopped = self.replication.write().unwrap().take().is_some();
+        if stopped {
+            info!("Stopped replication stream");
+        }
+        stopped
+    }
+
+    /// Start replication to a warm standby engine in this process
+    ///
+    /// Call [`WarmStandby::catch_up`] to apply the changes streamed so far, for example
+    /// from a thread of its own; promotion applies whatever is left.
+    pub fn spawn_standby(&self, config: ReplicationConfig) -> BingoResult<WarmStandby> {
+        let (sender, receiver) = crossbeam::channel::unbounded();
+        let snapshot = self.start_replication(config, sender)?;
+        Ok(WarmStandby::from_snapshot(&snapshot)?.receiving(receiver))
+    }
+
+    /// Digest of the rules and stored facts, as compared between a primary and its
+    /// warm standbys (concurrent safe)
+    pub fn state_digest(&self) -> StateDigest {
+        let rules = self.rules.read().unwrap();
+        let _rete_network = self.rete_network.read().unwrap();
+        StateDigest::compute(&rules, &self.fact_store.iter())
+    }
+
+    /// Send `record` to the replication stream if the engine is replicating
+    ///
+    /// Called under the RETE network write lock, so entries are numbered in the order
+    /// their changes were applied.
+    fn replicate(replication: Option<&Replicator>, record: impl FnOnce() -> ReplicationRecord) {
+        if let Some(replicator) = replication {
+            replicator.send(record());
+        }
+    }
+
+    /// Send a state digest if the digest interval has passed; called with the rules and
+    /// RETE network locks released
+    fn replicate_digest_if_due(&self, replication: Option<&Replicator>) {
+        let Some(replicator) = replication.filter(|replicator| replicator.take_digest_due()) else {
+            return;
+        };
+        let rules = self.rules.read().unwrap();
+        // Exclusive, so no replicated change lands between the digest and its entry
+        let _rete_network = self.rete_network.write().unwrap();
+        let digest = StateDigest::compute(&rules, &self.fact_store.iter());
+        replicator.send(ReplicationRecord::Digest(digest));
+    }
+
+    /// Apply a change streamed from a primary engine
+    pub(crate) fn apply_replicated(&self, record: &ReplicationRecord) -> BingoResult<()> {
+        match record {
+            ReplicationRecord::RuleAdded(rule) => self.add_rule(rule.clone()),
+            ReplicationRecord::RuleUpdated(rule) => self.update_rule(rule.clone()),
+            ReplicationRecord::RuleRemoved(rule_id) => self.remove_rule(*rule_id),
+            ReplicationRecord::FactsInserted { facts, next_fact_id } => {
+                let mut rete_network = self.rete_network.write().unwrap();
+                for fact in facts {
+                    self.fact_store.insert_with_id(fact.clone());
+                }
+                self.fact_store.resume_sequential_ids(*next_fact_id);
+                self.schedule_expiry(facts);
+
+                let results = rete_network
+                    .process_facts(facts, &self.fact_store, &self.calculator)
+                    .map_err(|e| BingoError::rete_network("apply_replicated", e.to_string()))?;
+                self.fact_processing_count
+                    .fetch_add(facts.len() as u64, std::sync::atomic::Ordering::Relaxed);
+                self.total_rule_executions
+                    .fetch_add(results.len() as u64, std::sync::atomic::Ordering::Relaxed);
+                Ok(())
+            }
+            ReplicationRecord::FactsRemoved(fact_ids) => {
+                let mut rete_network = self.rete_network.write().unwrap();
+                for &fact_id in fact_ids {
+                    if self.fact_store.delete_fact(fact_id) {
+                        rete_network.remove_fact_from_working_memory(fact_id).map_err(|e| {
+                            BingoError::rete_network("apply_replicated", e.to_string())
+                        })?;
+                    }
+                }
+                rete_network.invalidate_lazy_aggregation_caches();
+                Ok(())
+            }
+            ReplicationRecord::FactRetracted(fact_id) => self.retract_fact(*fact_id).map(drop),
+            ReplicationRecord::FactsCleared => {
+                self.clear_facts();
+                Ok(())
+            }
+            ReplicationRecord::Cleared => {
+                self.clear();
+                Ok(())
+            }
+            // Digests are compared by the standby, not applied
+            ReplicationRecord::Digest(_) => Ok(()),
+        }
+    }
+
+    /// Suppress or resume dispatching webhook actions
+    pub(crate) fn set_webhook_dry_run(&self, dry_run: bool) {
+        self.rete_network.write().unwrap().set_dry_run(dry_run);
+    }
+
+    /// Look up a fact by internal ID (concurrent safe)
+    ///
+    /// Expired facts are not returned, even before they are retracted.
+    pub fn get_fact(&self, fact_id: FactId) -> Option<Fact> {
+        self.fact_store.get_fact(fact_id).filter(|fact| !self.is_expired(fact))
+    }
+
+    /// Look up a fact by external ID (concurrent safe)
+    ///
+    /// Expired facts are not returned, even before they are retracted.
+    pub fn lookup_fact_by_id(&self, external_id: &str) -> Option<Fact> {
+        self.fact_store
+            .get_by_external_id(external_id)
+            .filter(|fact| !self.is_expired(fact))
+    }
+
+    /// Get a specific field value from a fact by external ID (concurrent safe)
+    pub fn get_field_by_id(&self, external_id: &str, field_name: &str) -> Option<FactValue> {
+        if let Some(fact) = self.lookup_fact_by_id(external_id) {
+            fact.data.fields.get(field_name).cloned()
+        } else {
+            None
+        }
+    }
+
+    /// Evaluate rules against facts (simplified API for compatibility)
+    pub fn evaluate(
+        &self,
+        rules: Vec<Rule>,
+        facts: Vec<Fact>,
+    ) -> BingoResult<Vec<RuleExecutionResult>> {
+        info!(
+            "Evaluating {} rules against {} facts",
+            rules.len(),
+            facts.len()
+        );
+
+        // Add rules to engine
+        for rule in rules {
+            self.add_rule(rule)?;
+        }
+
+        // Process facts
+        let results = self.process_facts(facts)?;
+
+        Ok(results)
+    }
+
+    /// Add a rule with optimization
+    pub fn add_rule_optimized(&self, rule: Rule) -> BingoResult<OptimizationResult> {
+        // Add the rule and track optimization metrics
+        let rule_clone = rule.clone();
+        self.add_rule(rule)?;
+
+        // Update optimization metrics
+        {
+            let mut metrics = self.optimization_metrics.write().unwrap();
+            metrics.rules_optimized += 1;
+        }
+
+        Ok(OptimizationResult {
+            original_rule: rule_clone.clone(),
+            optimized_rule: rule_clone,
+            estimated_improvement: 0.0,
+            strategies_applied: vec![],
+            analysis: crate::rule_optimizer::OptimizationAnalysis {
+                condition_selectivity: vec![],
+                condition_costs: vec![],
+                join_analysis: None,
+                shared_patterns: vec![],
+                total_improvement_estimate: 0.0,
+            },
+        })
+    }
+
+    /// Update an existing rule
+    ///
+    /// The rule is replaced in place, so concurrent fact processing sees either the old
+    /// or the new definition and never a ruleset without it. If the new definition is
+    /// rejected the old one stays in effect.
+    pub fn update_rule(&self, rule: Rule) -> BingoResult<()> {
+        info!(rule_id = rule.id, rule_name = %rule.name, "Updating rule in engine");
+
+        let replication = self.replication.read().unwrap();
+        let mut rules = self.rules.write().unwrap();
+        let position = rules.iter().position(|r| r.id == rule.id).ok_or_else(|| {
+            BingoError::rule_validation(format!("Rule with ID {} not found", rule.id))
+        })?;
+
+        let mut rete_network = self.rete_network.write().unwrap();
+        self.check_actions(&rete_network, &rule)?;
+        self.check_cycles(&rete_network, &rules, &rule)?;
+        rete_network.record_index_statistics(&rule, &self.fact_store);
+
+        let mut updated = rules.clone();
+        updated[position] = rule;
+        *rete_network = Self::rebuild_network(&rete_network, &updated)?;
+        *rules = updated;
+        self.bump_ruleset_version();
+        Self::replicate(replication.as_ref(), || {
+            ReplicationRecord::RuleUpdated(rules[position].clone())
+        });
+
+        let (rule_id, rule_name) = (rules[position].id, rules[position].name.clone());
+        drop((rete_network, rules));
+        self.replicate_digest_if_due(replication.as_ref());
+        drop(replication);
+        info!(rule_id = rule_id, "Rule updated successfully");
+        self.events.publish(EngineEvent::RuleUpdated {
+            rule_id,
+            rule_name,
+            ruleset_version: self.ruleset_version(),
+        });
+        Ok(())
+    }
+
+    /// Remove a rule by ID
+    pub fn remove_rule(&self, rule_id: u64) -> BingoResult<()> {
+        info!(rule_id = rule_id, "Removing rule from engine");
+        let replication = self.replication.read().unwrap();
+
+        // Write lock for rules (exclusive access)
+        let mut rules = self.rules.write().unwrap();
+
+        // Find and remove the rule
+        if let Some(pos) = rules.iter().position(|r| r.id == rule_id) {
+            rules.remove(pos);
+
+            // Write lock for RETE network to rebuild without the removed rule
+            let mut rete_network = self.rete_network.write().unwrap();
+            *rete_network = Self::rebuild_network(&rete_network, &rules)?;
+            self.bump_ruleset_version();
+            Self::replicate(replication.as_ref(), || {
+                ReplicationRecord::RuleRemoved(rule_id)
+            });
+            drop((rete_network, rules));
+            self.replicate_digest_if_due(replication.as_ref());
+            drop(replication);
+
+            info!(rule_id = rule_id, "Rule removed successfully");
+            self.events.publish(EngineEvent::RuleRemoved {
+                rule_id,
+                ruleset_version: self.ruleset_version(),
+            });
+        } else {
+            return Err(BingoError::rule_validation(format!(
+                "Rule with ID {rule_id} not found"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Compile a fresh network for `rules`, keeping the calendars registered with `current`
+    fn rebuild_network(current: &ReteNetwork, rules: &[Rule]) -> BingoResult<ReteNetwork> {
+        current.invalidate_lazy_aggregation_caches();
+
+        let mut rebuilt = current.without_rules();
+        for rule in rules {
+            rebuilt.add_rule(rule.clone())?;
+        }
+        Ok(rebuilt)
+    }
+
+    /// Add multiple rules (bulk operation)
+    pub fn add_rules(&self, rules: Vec<Rule>) -> BingoResult<()> {
+        for rule in rules {
+            self.add_rule(rule)?;
+        }
+        Ok(())
+    }
+
+    /// Register a period calendar that calendar aggregation windows can refer to
+    ///
+    /// Register calendars before adding the rules that use them. Registering a calendar
+    /// under an existing name replaces it.
+    pub fn register_calendar(&self, calendar: PeriodCalendar) {
+        self.rete_network.write().unwrap().register_calendar(calendar);
+    }
+
+    /// Register a reference table that conditions and calculator inputs can refer to
+    ///
+    /// Register tables before adding the rules that use them. Registering a table under
+    /// an existing name hot-reloads it: the swap is atomic and applies to the rules
+    /// already using it without recompiling them.
+    pub fn register_reference_table(
+        &self,
+        name: impl Into<String>,
+        table: ReferenceTable,
+    ) -> BingoResult<()> {
+        self.reference_data()
+            .register(name, table)
+            .map_err(|e| BingoError::rule_validation(e.to_string()))
+    }
+
+    /// Register `table` as one dated version of the reference table `name`
+    ///
+    /// Versions with other effective dates are kept, and each batch looks up the
+    /// version in effect at its evaluation date, e.g. last year's tax rates when
+    /// recalculating last year's payroll.
+    pub fn register_reference_table_version(
+        &self,
+        name: impl Into<String>,
+        table: ReferenceTable,
+    ) -> BingoResult<()> {
+        self.reference_data()
+            .register_version(name, table)
+            .map_err(|e| BingoError::rule_validation(e.to_string()))
+    }
+
+    /// Register a key-only reference table for `In` and `NotIn` conditions
+    pub fn register_value_list(
+        &self,
+        name: impl Into<String>,
+        values: impl IntoIterator<Item = FactValue>,
+    ) -> BingoResult<()> {
+        self.register_reference_table(name, ReferenceTable::from_keys(values))
+    }
+
+    /// Set a global value that calculator inputs can read as `@name`
+    ///
+    /// Set globals before adding the rules that use them. Setting a global again applies
+    /// the new value to the rules already using it, like reloading a reference table.
+    pub fn set_global(&self, name: impl Into<String>, value: FactValue) -> BingoResult<()> {
+        self.reference_data()
+            .set_global(name, value)
+            .map_err(|e| BingoError::rule_validation(e.to_string()))
+    }
+
+    /// Set several globals at once; nothing is set if any name is invalid
+    pub fn set_globals(
+        &self,
+        globals: impl IntoIterator<Item = (String, FactValue)>,
+    ) -> BingoResult<()> {
+        self.reference_data()
+            .set_globals(globals)
+            .map_err(|e| BingoError::rule_validation(e.to_string()))
+    }
+
+    /// Current value of the global `name`
+    pub fn global(&self, name: &str) -> Option<FactValue> {
+        self.reference_data().global(name)
+    }
+
+    /// Every global, ordered by name
+    pub fn globals(&self) -> BTreeMap<String, FactValue> {
+        self.reference_data().globals()
+    }
+
+    /// Handle to the reference tables, e.g. for a loader reloading them in the background
+    pub fn reference_data(&self) -> ReferenceDataStore {
+        self.rete_network.read().unwrap().reference_data().clone()
+    }
+
+    /// Register a webhook endpoint for `CallWebhook` actions to name
+    ///
+    /// Register endpoints before adding the rules that use them. Registering under an
+    /// existing name re-points the endpoint for payloads delivered from then on.
+    pub fn register_webhook(
+        &self,
+        name: impl Into<String>,
+        target: WebhookTarget,
+    ) -> BingoResult<()> {
+        self.webhooks().register(name, target)
+    }
+
+    /// Set the webhook worker pool size, queue bound, retries and dead-letter bound
+    pub fn configure_webhooks(&self, config: WebhookConfig) {
+        self.webhooks().configure(config);
+    }
+
+    /// Handle to the webhook endpoints, delivery statistics and dead letters
+    pub fn webhooks(&self) -> WebhookDispatcher {
+        self.rete_network.read().unwrap().webhooks().clone()
+    }
+
+    /// Register a listener notified of rule, session, batch and limit events
+    /// (concurrent safe)
+    pub fn subscribe_events(&self, listener: impl EngineEventListener + 'static) -> SubscriptionId {
+        self.events.subscribe(listener)
+    }
+
+    /// Stop notifying an event listener; returns whether it was subscribed
+    pub fn unsubscribe_events(&self, id: SubscriptionId) -> bool {
+        self.events.unsubscribe(id)
+    }
+
+    /// Event bus publishing the engine's lifecycle events
+    pub fn event_bus(&self) -> &EventBus {
+        &self.events
+    }
+
+    /// Set how simple conditions compare strings, e.g. ignoring case for a locale
+    ///
+    /// Set the collation before processing facts; facts already held in working
+    /// memory are not re-evaluated.
+    pub fn set_collation(&self, collation: Collation) {
+        self.rete_network.write().unwrap().set_collation(collation);
+    }
+
+    /// Collation simple conditions currently compare strings under
+    pub fn collation(&self) -> Collation {
+        self.rete_network.read().unwrap().collation().clone()
+    }
+
+    /// Choose how facts processed from now on are assigned IDs
+    ///
+    /// Results refer to facts by the ID they were stored under, which differs from the
+    /// submitted ID whenever the strategy generates one.
+    pub fn set_fact_id_strategy(&self, strategy: FactIdStrategy) -> BingoResult<()> {
+        self.fact_store.set_id_strategy(strategy)?;
+        info!(?strategy, "Fact ID strategy changed");
+        Ok(())
+    }
+
+    /// The strategy assigning IDs to processed facts
+    pub fn fact_id_strategy(&self) -> FactIdStrategy {
+        self.fact_store.id_strategy()
+    }
+
+    /// How many fact IDs were preserved, generated, reassigned, replaced or rejected
+    pub fn fact_id_stats(&self) -> FactIdStats {
+        self.fact_store.id_stats()
+    }
+
+    /// Refuse processed facts of `schema.fact_type` whose fields do not match `schema`,
+    /// replacing any schema registered for the type
+    ///
+    /// A batch with a refused fact fails as a whole with [`BingoError::FactValidation`],
+    /// listing every problem with the first refused fact. Stored facts and facts created
+    /// by rules are not checked.
+    pub fn register_fact_schema(&self, schema: FactSchema) {
+        info!(fact_type = %schema.fact_type, fields = schema.fields.len(), "Registering fact schema");
+        self.fact_store.register_schema(schema);
+    }
+
+    /// Stop checking facts of `fact_type`, returning the schema they were checked against
+    pub fn remove_fact_schema(&self, fact_type: &str) -> Option<FactSchema> {
+        self.fact_store.remove_schema(fact_type)
+    }
+
+    /// The schemas processed facts are checked against (concurrent safe)
+    pub fn fact_schemas(&self) -> FactSchemaRegistry {
+        self.fact_store.schemas()
+    }
+
+    /// Keep stored facts in a B-tree index over `field`, so range queries over it need
+    /// no scan
+    pub fn add_range_index(&self, field: &str) {
+        self.fact_store.add_range_index(field);
+    }
+
+    /// Stored facts whose integer, float or decimal `field` is in `range`, in value
+    /// order (concurrent safe)
+    ///
+    /// Fields without a range index are scanned. Expired facts are not returned.
+    pub fn find_facts_by_range(&self, field: &str, range: impl RangeBounds<f64>) -> Vec<Fact> {
+        let mut facts = self.fact_store.find_by_range(field, range);
+        facts.retain(|fact| !self.is_expired(fact));
+        facts
+    }
+
+    /// One page of the stored facts matching `query`, in fact ID order (concurrent
+    /// safe)
+    ///
+    /// Expired facts are not returned. Pass the page's `next_cursor` back in
+    /// `FactPageRequest::after` to continue a listing.
+    pub fn query_facts(&self, query: &FactQuery, page: FactPageRequest) -> FactPage {
+        self.fact_store.find_by_query_where(query, page, |fact| !self.is_expired(fact))
+    }
+
+    /// Stored facts with timestamps from `start` to `end` inclusive, in timestamp order
+    /// (concurrent safe)
+    ///
+    /// Expired facts are not returned.
+    pub fn facts_in_time_range(
+        &self,
+        start: chrono::DateTime<chrono::Utc>,
+        end: chrono::DateTime<chrono::Utc>,
+    ) -> Vec<Fact> {
+        let mut facts = self.fact_store.facts_in_time_range(start, end);
+        facts.retain(|fact| !self.is_expired(fact));
+        facts
+    }
+
+    /// Choose what happens to NaN and infinite floats in processed facts, aggregates
+    /// and calculator results
+    pub fn set_non_finite_policy(&self, policy: NonFinitePolicy) {
+        self.rete_network.write().unwrap().set_non_finite_policy(policy);
+        info!(?policy, "Non-finite float policy changed");
+    }
+
+    /// The policy applied to NaN and infinite floats
+    pub fn non_finite_policy(&self) -> NonFinitePolicy {
+        self.rete_network.read().unwrap().non_finite_policy()
+    }
+
+    /// How many facts were rejected and values nulled, discarded or failed on for being
+    /// NaN or infinite
+    pub fn non_finite_stats(&self) -> NonFiniteStats {
+        self.rete_network.read().unwrap().non_finite_stats()
+    }
+
+    /// Choose what `IncrementField` and `Formula` actions produce when integer
+    /// arithmetic overflows `i64`: an error, a saturated integer, or a float or decimal
+    /// result
+    ///
+    /// Under [`OverflowPolicy::Error`] (the default) the field is left unchanged and the
+    /// action result reports the overflow.
+    pub fn set_overflow_policy(&self, policy: OverflowPolicy) {
+        self.rete_network.write().unwrap().set_overflow_policy(policy);
+        info!(?policy, "Integer overflow policy changed");
+    }
+
+    /// The policy applied to overflowing integer arithmetic in actions
+    pub fn overflow_policy(&self) -> OverflowPolicy {
+        self.rete_network.read().unwrap().overflow_policy()
+    }
+
+    /// Choose what stream conditions, sequence conditions and tumbling, sliding and
+    /// session aggregation windows do with events that arrive behind the watermark, the
+    /// latest event time seen
+    ///
+    /// By default events more than a minute late are dropped, and windows and partial
+    /// sequence matches close a minute after the watermark passes their end.
+    /// [`LateDataPolicy::Accept`] aggregates every event instead, keeping windows open
+    /// for as long as their facts are stored; partial sequence matches then time out
+    /// as soon as the watermark passes their deadline.
+    pub fn set_late_data_policy(&self, policy: LateDataPolicy) {
+        self.rete_network.write().unwrap().set_late_data_policy(policy);
+        info!(?policy, "Late data policy changed");
+    }
+
+    /// The policy applied to events that arrive behind the watermark
+    pub fn late_data_policy(&self) -> LateDataPolicy {
+        self.rete_network.read().unwrap().late_data_policy()
+    }
+
+    /// Choose what happens when rules set the same field of a fact to different values
+    /// in one batch: keep every write, keep the highest-salience rule's writes, or fail
+    /// the batch
+    ///
+    /// Collisions are reported in the `field_collisions` of the results involved under
+    /// every policy but [`FieldCollisionPolicy::Error`], which fails the batch after
+    /// its facts were stored.
+    pub fn set_field_collision_policy(&self, policy: FieldCollisionPolicy) {
+        self.rete_network.write().unwrap().set_field_collision_policy(policy);
+        info!(?policy, "Field collision policy changed");
+    }
+
+    /// The policy applied to colliding `SetField` writes
+    pub fn field_collision_policy(&self) -> FieldCollisionPolicy {
+        self.rete_network.read().unwrap().field_collision_policy()
+    }
+
+    /// Choose what `add_rule` does with a rule that has the ID, or the conditions and
+    /// actions, of a rule already added (concurrent safe)
+    pub fn set_duplicate_rule_policy(&self, policy: DuplicateRulePolicy) {
+        self.rete_network.write().unwrap().set_duplicate_rule_policy(policy);
+        info!(?policy, "Duplicate rule policy changed");
+    }
+
+    /// Get the duplicate rule policy (concurrent safe)
+    pub fn duplicate_rule_policy(&self) -> DuplicateRulePolicy {
+        self.rete_network.read().unwrap().duplicate_rule_policy()
+    }
+
+    /// Groups of rules sharing an ID, identical rules and rules sharing their conditions
+    /// (concurrent safe)
+    pub fn duplicate_rules(&self) -> Vec<DuplicateRules> {
+        find_duplicates(&self.rules.read().unwrap())
+    }
+
+    /// Register the type of a fact field that rule actions are checked against
+    ///
+    /// Register field types before adding the rules that write the fields; rules
+    /// already added are not checked again.
+    pub fn register_field_type(&self, field: impl Into<String>, field_type: FieldType) {
+        self.rete_network.write().unwrap().register_field_type(field, field_type);
+    }
+
+    /// Get the registered field types (concurrent safe)
+    pub fn field_schema(&self) -> FieldSchema {
+        self.rete_network.read().unwrap().field_schema().clone()
+    }
+
+    /// Choose what adding a rule whose actions fail validation does
+    pub fn set_action_validation_policy(&self, policy: ActionValidationPolicy) {
+        self.rete_network.write().unwrap().set_action_validation_policy(policy);
+    }
+
+    /// Get the policy applied to rules whose actions fail validation (concurrent safe)
+    pub fn action_validation_policy(&self) -> ActionValidationPolicy {
+        self.rete_network.read().unwrap().action_validation_policy()
+    }
+
+    /// Check a rule's actions against the registered calculators and field types
+    /// without adding it (concurrent safe)
+    pub fn validate_rule_actions(&self, rule: &Rule) -> ActionValidationReport {
+        validate_actions(
+            rule,
+            &self.calculator,
+            self.rete_network.read().unwrap().field_schema(),
+        )
+    }
+
+    /// Validate a rule's actions as the action validation policy says
+    fn check_actions(&self, rete_network: &ReteNetwork, rule: &Rule) -> BingoResult<()> {
+        let policy = rete_network.action_validation_policy();
+        if policy == ActionValidationPolicy::Off {
+            return Ok(());
+        }
+        let report = validate_actions(rule, &self.calculator, rete_network.field_schema());
+        if report.is_valid() {
+            return Ok(());
+        }
+        if policy == ActionValidationPolicy::Reject {
+            return Err(BingoError::action_validation(report));
+        }
+        warn!(rule_id = rule.id, problems = %report, "Adding rule with invalid actions");
+        Ok(())
+    }
+
+    /// Choose what adding a rule that closes a cycle of rules triggering each other
+    /// does; cycles are not looked for by default
+    pub fn set_rule_cycle_policy(&self, policy: RuleCyclePolicy) {
+        info!(?policy, "Setting rule cycle policy");
+        self.rete_network.write().unwrap().set_rule_cycle_policy(policy);
+    }
+
+    /// Get the policy applied to rules closing a dependency cycle (concurrent safe)
+    pub fn rule_cycle_policy(&self) -> RuleCyclePolicy {
+        self.rete_network.read().unwrap().rule_cycle_policy()
+    }
+
+    /// Look for a dependency cycle through `rule`, replacing any loaded rule with its
+    /// ID, as the rule cycle policy says
+    fn check_cycles(
+        &self,
+        rete_network: &ReteNetwork,
+        rules: &[Rule],
+        rule: &Rule,
+    ) -> BingoResult<()> {
+        let policy = rete_network.rule_cycle_policy();
+        if policy == RuleCyclePolicy::Off {
+            return Ok(());
+        }
+        let others = rules.iter().filter(|existing| existing.id != rule.id);
+        let graph = RuleFlowGraph::from_rules(others.chain(std::iter::once(rule)));
+        let Some(cycle) = graph.cycle_through(rule.id) else {
+            return Ok(());
+        };
+        let path = cycle.iter().map(|rule_id| rule_id.to_string()).collect::<Vec<_>>().join(" -> ");
+        if policy == RuleCyclePolicy::Reject {
+            return Err(BingoError::Rule {
+                message: format!("Rule {} closes a dependency cycle", rule.id),
+                rule_id: Some(rule.id),
+                rule_name: Some(rule.name.clone()),
+                details: Some(format!("Rules trigger each other: {path}")),
+            });
+        }
+        warn!(rule_id = rule.id, cycle = %path, "Adding rule that closes a dependency cycle");
+        Ok(())
+    }
+
+    /// Set a loaded rule's salience, which decides whose writes win under
+    /// [`FieldCollisionPolicy::HighestSalience`]; rules default to 0
+    pub fn set_rule_salience(&self, rule_id: RuleId, salience: i32) -> BingoResult<()> {
+        if self.get_rule(rule_id).is_none() {
+            return Err(BingoError::rule_validation(format!(
+                "Rule with ID {rule_id} not found"
+            )));
+        }
+        self.rete_network.write().unwrap().set_salience(rule_id, salience);
+        Ok(())
+    }
+
+    /// Salience of a rule, 0 unless set
+    pub fn rule_salience(&self, rule_id: RuleId) -> i32 {
+        self.rete_network.read().unwrap().salience(rule_id)
+    }
+
+    /// Let the rules of a rule group fire again
+    pub fn enable_rule_group(&self, group: &str) {
+        info!(group, "Enabling rule group");
+        self.rete_network.write().unwrap().set_rule_group_enabled(group, true);
+    }
+
+    /// Stop the rules of a rule group from firing until the group is enabled again
+    ///
+    /// The group doesn't need to have rules yet, so rules added to it later start out
+    /// disabled.
+    pub fn disable_rule_group(&self, group: impl Into<String>) {
+        let group = group.into();
+        info!(group = %group, "Disabling rule group");
+        self.rete_network.write().unwrap().set_rule_group_enabled(group, false);
+    }
+
+    /// Rule groups whose rules don't fire
+    pub fn disabled_rule_groups(&self) -> BTreeSet<String> {
+        self.rete_network.read().unwrap().disabled_rule_groups().clone()
+    }
+
+    /// Size the cache of calculator results and choose how long results are reused
+    ///
+    /// Results of deterministic calculators are cached by calculator name and inputs,
+    /// so facts mapping the same values into a calculator share one computation. A
+    /// `capacity` of 0 turns the cache off. Changing the setting drops the cached
+    /// results.
+    pub fn set_calculator_cache(&self, config: CalculatorCacheConfig) {
+        info!(?config, "Setting calculator cache");
+        self.rete_network.write().unwrap().set_calculator_cache(config);
+    }
+
+    /// Size and lifetime of the calculator result cache
+    pub fn calculator_cache(&self) -> CalculatorCacheConfig {
+        self.rete_network.read().unwrap().calculator_cache()
+    }
+
+    /// Hits, misses, evictions and expirations of the calculator result cache
+    pub fn calculator_cache_stats(&self) -> CacheStats {
+        self.rete_network.read().unwrap().calculator_cache_stats()
+    }
+
+    /// Drop every cached calculator result, e.g. after external state a calculator
+    /// reads has changed
+    pub fn clear_calculator_cache(&self) {
+        self.rete_network.write().unwrap().clear_calculator_cache();
+    }
+
+    /// Record an explanation trace for every rule execution, keeping the most recent
+    /// `capacity` of them
+    ///
+    /// While enabled, each result's `result_id` retrieves its trace through
+    /// [`explain`](Self::explain). `None` (the default) records nothing and drops the
+    /// traces already kept.
+    pub fn set_audit_log(&self, config: Option<AuditLogConfig>) {
+        info!(?config, "Setting audit log");
+        self.rete_network.write().unwrap().set_audit_log(config);
+    }
+
+    /// Audit log setting, `None` when no traces are recorded
+    pub fn audit_log(&self) -> Option<AuditLogConfig> {
+        self.rete_network.read().unwrap().audit_log()
+    }
+
+    /// Start sampling which facts are evaluated and which fields are read most, or stop
+    /// and drop the samples with `None` (the default)
+    pub fn set_working_memory_profiler(&self, config: Option<WorkingMemoryProfilerConfig>) {
+        info!(?config, "Setting working memory profiler");
+        self.rete_network.write().unwrap().set_working_memory_profiler(config);
+    }
+
+    /// Working memory profiler setting, `None` while profiling is disabled
+    pub fn working_memory_profiler(&self) -> Option<WorkingMemoryProfilerConfig> {
+        self.rete_network.read().unwrap().working_memory_profiler()
+    }
+
+    /// Hottest facts and fields sampled so far, with whether the fact store indexes
+    /// each field; `None` while profiling is disabled
+    pub fn working_memory_profile(&self) -> Option<WorkingMemoryProfile> {
+        self.rete_network.read().unwrap().working_memory_profile(&self.fact_store)
+    }
+
+    /// Why a rule fired: the facts and values that satisfied its conditions, its
+    /// calculator inputs and outputs, and the actions it executed
+    ///
+    /// Returns `None` for results recorded while the audit log was disabled and for
+    /// traces already dropped to stay within its capacity.
+    pub fn explain(&self, result_id: ResultId) -> Option<ExplanationTrace> {
+        self.rete_network.read().unwrap().explain(result_id)
+    }
+
+    /// Parse rules written in the rule language and add them
+    ///
+    /// Returns the number of rules added. See `rule_dsl` for the syntax.
+    pub fn add_rules_from_dsl(&self, source: &str) -> BingoResult<usize> {
+        let rules = parse_rules(source)?;
+        let count = rules.len();
+        self.add_rules(rules)?;
+        Ok(count)
+    }
+
+    /// Generate performance report
+    pub fn generate_performance_report(&self) -> PerformanceReport {
+        let profiler = self.profiler.read().unwrap();
+        let stats = self.get_stats();
+        let mut unified_stats = UnifiedStats::new();
+
+        // Register basic engine statistics
+        unified_stats.register_fact_storage("ArenaFactStore", stats.fact_count, 0);
+
+        profiler.generate_report(unified_stats)
     }
 
     /// Enable or disable profiling
@@ -508,12 +2567,271 @@ impl BingoEngine {
         profiler.reset();
     }
 
+    /// Start an interactive step-debugger over the active ruleset (concurrent safe)
+    ///
+    /// The session compiles the rules into its own dry-run network and starts from
+    /// copies of the facts in working memory, so stepping facts through it never changes
+    /// the engine.
+    pub fn debug_session(&self) -> BingoResult<DebugSession> {
+        let network = {
+            let rules = self.rules.read().unwrap();
+            let rete_network = self.rete_network.read().unwrap();
+            Self::rebuild_network(&rete_network.dry_run(), &rules)?
+        };
+        let session = DebugSession::new(network, self.fact_store.iter(), self.calculator.clone());
+        info!(debug_session_id = %session.id(), "Started debug session");
+        Ok(session)
+    }
+
+    /// Evaluate `candidate_rules` against every batch processed from now on, alongside
+    /// the active ruleset, and record where the two fire differently (concurrent safe)
+    ///
+    /// The candidate starts from copies of the facts in working memory and is compiled
+    /// with the engine's settings into a dry-run network, so its actions never change
+    /// working memory and its webhook actions are not dispatched. Replaces any shadow
+    /// evaluation already running.
+    pub fn start_shadow(&self, candidate_rules: Vec<Rule>) -> BingoResult<()> {
+        let network = {
+            let rete_network = self.rete_network.read().unwrap();
+            Self::rebuild_network(&rete_network.dry_run(), &candidate_rules)?
+        };
+        let shadow = ShadowEvaluation::new(
+            network,
+            candidate_rules.len(),
+            self.fact_store.iter(),
+            self.calculator.clone(),
+            DEFAULT_DIVERGENCE_LIMIT,
+        );
+        *self.shadow.write().unwrap() = Some(Arc::new(shadow));
+
+        info!(
+            candidate_rule_count = candidate_rules.len(),
+            "Started shadow evaluation of candidate ruleset"
+        );
+        Ok(())
+    }
+
+    /// Comparison of the shadowed candidate ruleset with the active one, if one is
+    /// being shadowed
+    pub fn shadow_report(&self) -> Option<ShadowReport> {
+        self.shadow.read().unwrap().as_ref().map(|shadow| shadow.report())
+    }
+
+    /// Stop shadowing the candidate ruleset and return its final report
+    pub fn stop_shadow(&self) -> Option<ShadowReport> {
+        let shadow = self.shadow.write().unwrap().take()?;
+        info!("Stopped shadow evaluation of candidate ruleset");
+        Some(shadow.report())
+    }
+
+    /// Work out how `request`'s corrections change the results of the facts in its
+    /// period, without changing the engine
+    ///
+    /// The affected facts are re-run as of their event time twice, as recorded under
+    /// the current rules and corrected under the corrected rules, and the report lists
+    /// the firings that differ. Correcting a fact that is not stored, or removing a rule
+    /// that does not exist, fails.
+    pub fn recalculate(&self, request: &RecalculationRequest) -> BingoResult<RecalculationReport> {
+        let current_rules = self.rules();
+        let mut corrected_rules = current_rules.clone();
+        let mut changed_rules = Vec::new();
+        let mut corrected_facts = HashMap::new();
+        for correction in &request.corrections {
+            match correction {
+                Correction::Rule(rule) => {
+                    match corrected_rules.iter_mut().find(|current| current.id == rule.id) {
+                        Some(current) => {
+                            changed_rules.push(std::mem::replace(current, rule.clone()))
+                        }
+                        None => corrected_rules.push(rule.clone()),
+                    }
+                    changed_rules.push(rule.clone());
+                }
+                Correction::RuleRemoved(rule_id) => {
+                    let index = corrected_rules
+                        .iter()
+                        .position(|rule| rule.id == *rule_id)
+                        .ok_or_else(|| {
+                            BingoError::rule_validation(format!("Rule {rule_id} does not exist"))
+                        })?;
+                    changed_rules.push(corrected_rules.remove(index));
+                }
+                Correction::Fact(fact) => {
+                    if self.fact_store.get_fact(fact.id).is_none() {
+                        return Err(BingoError::fact_store_with_id(
+                            fact.id,
+                            "recalculate",
+                            "Corrected fact is not stored",
+                        ));
+                    }
+                    corrected_facts.insert(fact.id, fact.clone());
+                }
+            }
+        }
+
+        // A changed rule could have fired, or fire now, for any fact with a field it reads
+        let read_fields: HashSet<String> = referenced_fields(&changed_rules)
+            .into_iter()
+            .filter(|field| field.read)
+            .map(|field| field.field)
+            .collect();
+        let reads_every_fact = changed_rules.iter().any(|rule| rule.conditions.is_empty());
+
+        let stored = self.fact_store.iter();
+        let (dry_run, recorded, corrected) = {
+            let rete_network = self.rete_network.read().unwrap();
+            let affected: Vec<Fact> = stored
+                .iter()
+                .filter(|fact| request.covers(fact.timestamp))
+                .filter(|fact| !rete_network.truth_maintenance().is_supported(fact.id))
+                .filter(|fact| {
+                    corrected_facts.contains_key(&fact.id)
+                        || reads_every_fact
+                        || fact.data.fields.keys().any(|field| read_fields.contains(field))
+                })
+                .cloned()
+                .collect();
+            let corrected: Vec<Fact> = affected
+                .iter()
+                .map(|fact| corrected_facts.get(&fact.id).unwrap_or(fact).clone())
+                .collect();
+            (rete_network.dry_run(), affected, corrected)
+        };
+
+        let evaluate = |rules: &[Rule], facts: &[Fact], corrections: &HashMap<FactId, Fact>| {
+            let mut network = Self::rebuild_network(&dry_run, rules)?;
+            network.set_evaluation_date(EvaluationDate::EventTime);
+            let fact_store = ArenaFactStore::with_capacity(stored.len());
+            for fact in &stored {
+                fact_store.insert_with_id(corrections.get(&fact.id).unwrap_or(fact).clone());
+            }
+            network
+                .process_facts(facts, &fact_store, &self.calculator)
+                .map_err(|e| BingoError::rete_network("recalculate", e.to_string()))
+        };
+        let before = evaluate(&current_rules, &recorded, &HashMap::new())?;
+        let after = evaluate(&corrected_rules, &corrected, &corrected_facts)?;
+
+        let report = RecalculationReport {
+            affected_facts: recorded.iter().map(|fact| fact.id).collect(),
+            deltas: deltas(before, after),
+        };
+        info!(
+            affected_facts = report.affected_facts.len(),
+            deltas = report.deltas.len(),
+            "Recalculated historical facts"
+        );
+        Ok(report)
+    }
+
+    /// Activation counts, last firing and condition evaluation time of every rule, in
+    /// rule ID order (concurrent safe)
+    ///
+    /// Counts survive rule updates and removals of other rules; `clear` resets them.
+    pub fn rule_stats(&self) -> Vec<RuleStats> {
+        self.rete_network.read().unwrap().rule_stats()
+    }
+
+    /// Reset the rule hit counts (concurrent safe)
+    pub fn reset_rule_stats(&self) {
+        self.rete_network.write().unwrap().reset_rule_stats();
+    }
+
     /// Get optimization metrics
     pub fn get_optimization_metrics(&self) -> OptimizationMetrics {
         let metrics = self.optimization_metrics.read().unwrap();
         metrics.clone()
     }
 
+    /// Get per-condition evaluation counts of the alpha nodes
+    pub fn get_condition_stats(&self) -> Vec<ConditionEvaluationStats> {
+        self.rete_network.read().unwrap().condition_stats()
+    }
+
+    /// Report conditions that have not matched any fact evaluated so far
+    ///
+    /// Conditions that never match usually reference a misspelt field name.
+    pub fn unmatched_conditions(&self) -> UnmatchedConditionReport {
+        UnmatchedConditionReport::from_stats(self.get_condition_stats())
+    }
+
+    /// Graphviz DOT description of the compiled RETE network (concurrent safe)
+    ///
+    /// Render it with `dot -Tsvg` to see which conditions are shared between rules and
+    /// where joins hold the most tokens.
+    pub fn export_network_dot(&self) -> String {
+        self.rete_network.read().unwrap().export_dot()
+    }
+
+    /// Every fact field the rules read or write, with the operators applied to it
+    ///
+    /// Ingestion pipelines can project facts down to the fields marked `read`, and
+    /// compare the list between ruleset versions to catch breaking schema changes.
+    pub fn referenced_fields(&self) -> Vec<ReferencedField> {
+        referenced_fields(&self.rules.read().unwrap())
+    }
+
+    /// Graph of which rules read and write which fields and fact types
+    ///
+    /// Export it with `to_json` or `to_dot`, or ask `rules_using_field` which rules a
+    /// schema change would affect.
+    pub fn dependency_graph(&self) -> RuleFieldGraph {
+        RuleFieldGraph::from_rules(&self.rules.read().unwrap())
+    }
+
+    /// Graph of which rules can trigger which, through the fields and fact types their
+    /// actions write and other rules' conditions read
+    ///
+    /// `cycles` lists the rules that can keep firing each other under forward
+    /// chaining, and `to_dot` draws their edges in red.
+    pub fn rule_flow_graph(&self) -> RuleFlowGraph {
+        RuleFlowGraph::from_rules(self.rules.read().unwrap().iter())
+    }
+
+    /// Warn about rule fields that look like misspellings of fields seen in facts
+    ///
+    /// Rule fields are compared with the fields of the facts currently in the fact
+    /// store, so facts should be loaded first.
+    pub fn field_typo_warnings(&self) -> Vec<FieldTypoWarning> {
+        let observed = FieldObservations::from_facts(&self.fact_store.iter());
+        let rules = self.rules.read().unwrap();
+        let warnings = FieldTypoAnalyzer::new().analyze(&rules, &observed);
+        for warning in &warnings {
+            warn!(
+                rule_id = warning.rule_id,
+                field = %warning.field,
+                suggestion = %warning.suggestion,
+                "Rule field never seen in facts looks like a typo"
+            );
+        }
+        warnings
+    }
+
+    /// Feed observed condition selectivity to the rule optimizer
+    ///
+    /// Takes effect for rules compiled afterwards, including rules recompiled by
+    /// updates and removals. Returns the number of conditions with observations.
+    pub fn apply_selectivity_feedback(&self) -> usize {
+        self.rete_network.write().unwrap().apply_selectivity_feedback()
+    }
+
+    /// Re-plan every rule's condition order from the fact store's field indexes
+    ///
+    /// Rules are planned when added, so a ruleset loaded before its facts runs with
+    /// heuristic estimates until this is called. The network is recompiled like on a
+    /// rule update. Returns the number of conditions given index statistics.
+    pub fn apply_index_statistics(&self) -> BingoResult<usize> {
+        let rules = self.rules.read().unwrap();
+        let mut rete_network = self.rete_network.write().unwrap();
+
+        let recorded = rules
+            .iter()
+            .map(|rule| rete_network.record_index_statistics(rule, &self.fact_store))
+            .sum();
+        *rete_network = Self::rebuild_network(&rete_network, &rules)?;
+        Ok(recorded)
+    }
+
     /// Get working memory statistics
     pub fn get_working_memory_stats(&self) -> (usize, usize) {
         let stats = self.get_stats();
@@ -626,3 +2944,127 @@ impl BingoEngine {
         info!("Lazy aggregations cleaned up");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine_config::EvictionPolicy;
+    use crate::types::{Action, ActionType, Condition, FactData, Operator};
+    use std::collections::HashMap;
+
+    fn order(id: FactId, amount: i64) -> Fact {
+        let fields = HashMap::from([
+            ("amount".to_string(), FactValue::Integer(amount)),
+            ("status".to_string(), FactValue::String("open".to_string())),
+        ]);
+        Fact::new(id, FactData { fields })
+    }
+
+    #[test]
+    fn test_token_limit_evicts_the_facts_holding_tokens() {
+        let engine = BingoEngine::new().unwrap();
+        let greater_than = |field: &str, value| Condition::Simple {
+            field: field.to_string(),
+            operator: Operator::GreaterThan,
+            value: FactValue::Integer(value),
+        };
+        engine
+            .add_rule(Rule {
+                id: 1,
+                name: "Large open order".to_string(),
+                conditions: vec![
+                    greater_than("amount", 100),
+                    Condition::Simple {
+                        field: "status".to_string(),
+                        operator: Operator::Equal,
+                        value: FactValue::String("open".to_string()),
+                    },
+                ],
+                actions: vec![Action {
+                    action_type: ActionType::SetField {
+                        field: "flagged".to_string(),
+                        value: FactValue::Boolean(true),
+                    },
+                }],
+                metadata: Default::default(),
+            })
+            .unwrap();
+
+        // Incremental assertion leaves a terminal token per matching fact
+        for id in 1..=3 {
+            let fact = order(id, 500);
+            engine.fact_store.insert_with_id(fact.clone());
+            let mut rete_network = engine.rete_network.write().unwrap();
+            rete_network
+                .add_fact_to_working_memory(fact, &engine.fact_store, &engine.calculator)
+                .unwrap();
+        }
+        let tokens = engine.rete_network.read().unwrap().token_count();
+        assert!(tokens >= 3, "{tokens} tokens");
+
+        let config = EngineConfig { max_tokens: Some(tokens), ..EngineConfig::default() };
+        engine.set_config(config.clone()).unwrap();
+        let error = engine.process_facts(vec![order(10, 5)]).unwrap_err();
+        assert!(
+            matches!(error, BingoError::CapacityExceeded { ref resource, .. } if resource == "tokens")
+        );
+
+        engine
+            .set_config(EngineConfig { eviction: EvictionPolicy::OldestFirst, ..config })
+            .unwrap();
+        engine.process_facts(vec![order(10, 5)]).unwrap();
+        assert!(engine.rete_network.read().unwrap().token_count() < tokens);
+        assert!(engine.get_fact(1).is_none());
+        assert!(engine.capacity_stats().facts_evicted >= 1);
+    }
+
+    #[test]
+    fn test_rolled_back_retraction_returns_the_fact_to_working_memory() {
+        let engine = BingoEngine::new().unwrap();
+        engine
+            .add_rule(Rule {
+                id: 1,
+                name: "Large open order".to_string(),
+                conditions: vec![
+                    Condition::Simple {
+                        field: "amount".to_string(),
+                        operator: Operator::GreaterThan,
+                        value: FactValue::Integer(100),
+                    },
+                    Condition::Simple {
+                        field: "status".to_string(),
+                        operator: Operator::Equal,
+                        value: FactValue::String("open".to_string()),
+                    },
+                ],
+                actions: vec![],
+                metadata: Default::default(),
+            })
+            .unwrap();
+        engine.fact_store.insert_with_id(order(1, 500));
+        let mut rete_network = engine.rete_network.write().unwrap();
+        rete_network
+            .add_fact_to_working_memory(order(1, 500), &engine.fact_store, &engine.calculator)
+            .unwrap();
+        let tokens = rete_network.token_count();
+        drop(rete_network);
+
+        let mut transaction = engine.begin_transaction();
+        transaction.retract(1);
+        let rejected = transaction.commit_with(|outcome| {
+            assert_eq!(outcome.retractions.len(), 1);
+            Err(BingoError::external_service("ledger", "posting failed"))
+        });
+        assert!(rejected.is_err());
+
+        let rete_network = engine.rete_network.read().unwrap();
+        let fact = rete_network.working_memory_fact(1).unwrap();
+        assert_eq!(fact.data.fields["amount"], FactValue::Integer(500));
+        assert_eq!(rete_network.token_count(), tokens);
+        assert_eq!(rete_network.activated_match_count(1), 1);
+        assert_eq!(
+            rete_network.truth_maintenance().justifications_for(1).len(),
+            1
+        );
+    }
+}
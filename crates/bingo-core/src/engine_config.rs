@@ -0,0 +1,138 @@
+//! Engine configuration bounding working memory
+//!
+//! Without limits, working memory grows with every fact the engine ingests until the
+//! process runs out of memory. [`EngineConfig`] caps the facts held in working memory
+//! and the tokens held in beta memories. Before each insert the engine checks the
+//! limits and, when the insert would cross one, evicts facts as the
+//! [`EvictionPolicy`] allows. An insert that eviction cannot make room for fails with
+//! [`BingoError::CapacityExceeded`](crate::error::BingoError::CapacityExceeded).
+//!
+//! The token limit is checked before an insert, so one batch can take the network past
+//! it; the next insert then evicts back below the limit or is refused.
+
+use crate::error::{BingoError, BingoResult};
+use crate::fact_store::arena_store::ArenaFactStore;
+use crate::types::FactId;
+
+/// Which facts the engine evicts to make room under the capacity limits
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EvictionPolicy {
+    /// Evict nothing; refuse inserts that would exceed a limit
+    #[default]
+    Reject,
+    /// Evict facts with the oldest timestamps first
+    OldestFirst,
+    /// Evict facts older than the time-to-live, oldest first, and refuse the insert if
+    /// that is not enough
+    Ttl(chrono::Duration),
+}
+
+/// Capacity limits of an engine's working memory
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EngineConfig {
+    /// Facts held in working memory, unlimited when `None`
+    pub max_working_memory_facts: Option<usize>,
+    /// Tokens held across the beta memories, unlimited when `None`
+    pub max_tokens: Option<usize>,
+    pub eviction: EvictionPolicy,
+}
+
+impl EngineConfig {
+    /// Whether any capacity limit is set
+    pub fn is_bounded(&self) -> bool {
+        self.max_working_memory_facts.is_some() || self.max_tokens.is_some()
+    }
+
+    /// Reject zero limits and a non-positive time-to-live
+    pub fn validate(&self) -> BingoResult<()> {
+        for (setting, limit) in [
+            ("max_working_memory_facts", self.max_working_memory_facts),
+            ("max_tokens", self.max_tokens),
+        ] {
+            if limit == Some(0) {
+                return Err(BingoError::configuration(
+                    setting,
+                    "at least 1",
+                    "0",
+                    format!("{setting} must be at least 1 when set"),
+                ));
+            }
+        }
+        if let EvictionPolicy::Ttl(ttl) = self.eviction {
+            if ttl <= chrono::Duration::zero() {
+                return Err(BingoError::configuration(
+                    "eviction",
+                    "a positive time-to-live",
+                    &ttl.to_string(),
+                    "Eviction time-to-live must be positive",
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Facts the eviction policy allows evicting, in eviction order
+    pub(crate) fn eviction_candidates(&self, fact_store: &ArenaFactStore) -> Vec<FactId> {
+        let cutoff = match self.eviction {
+            EvictionPolicy::Reject => return Vec::new(),
+            EvictionPolicy::OldestFirst => None,
+            EvictionPolicy::Ttl(ttl) => Some(chrono::Utc::now() - ttl),
+        };
+        let mut facts: Vec<_> = fact_store
+            .iter()
+            .into_iter()
+            .filter(|fact| cutoff.is_none_or(|cutoff| fact.timestamp < cutoff))
+            .map(|fact| (fact.timestamp, fact.id))
+            .collect();
+        facts.sort_unstable();
+        facts.into_iter().map(|(_, fact_id)| fact_id).collect()
+    }
+}
+
+/// Cumulative capacity enforcement statistics
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CapacityStats {
+    pub facts_evicted: u64,
+    /// Facts in inserts refused with a capacity error
+    pub inserts_rejected: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Fact, FactData};
+    use std::collections::HashMap;
+
+    fn fact(id: FactId, age_minutes: i64) -> Fact {
+        let mut fact = Fact::new(id, FactData { fields: HashMap::new() });
+        fact.timestamp = chrono::Utc::now() - chrono::Duration::minutes(age_minutes);
+        fact
+    }
+
+    #[test]
+    fn test_eviction_candidates_follow_the_policy() {
+        let store = ArenaFactStore::new();
+        for (id, age) in [(1, 5), (2, 60), (3, 30)] {
+            store.insert(fact(id, age));
+        }
+
+        let mut config = EngineConfig::default();
+        assert!(config.eviction_candidates(&store).is_empty());
+        config.eviction = EvictionPolicy::OldestFirst;
+        assert_eq!(config.eviction_candidates(&store), vec![2, 3, 1]);
+        config.eviction = EvictionPolicy::Ttl(chrono::Duration::minutes(10));
+        assert_eq!(config.eviction_candidates(&store), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(EngineConfig::default().validate().is_ok());
+        let zero_limit = EngineConfig { max_tokens: Some(0), ..EngineConfig::default() };
+        assert!(zero_limit.validate().is_err());
+        let zero_ttl = EngineConfig {
+            eviction: EvictionPolicy::Ttl(chrono::Duration::zero()),
+            ..EngineConfig::default()
+        };
+        assert!(zero_ttl.validate().is_err());
+    }
+}
@@ -3,7 +3,12 @@
 //! This module provides comprehensive, granular performance monitoring capabilities
 //! for production environments, including real-time metrics, alerting, and
 //! operational visibility.
+//!
+//! Every metric recorded is also emitted through the configured `MetricsBackend`, so
+//! it reaches whichever metrics stack the embedder already runs.
 
+use crate::cache::CacheStats;
+use crate::metrics_backend::{MetricsBackend, MetricsBackendConfig, NoopMetrics};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
@@ -30,6 +35,9 @@ pub struct EnhancedMonitoring {
 
     /// Configuration for monitoring behavior
     config: MonitoringConfig,
+
+    /// Destination every recorded metric is emitted to
+    metrics_backend: Arc<dyn MetricsBackend>,
 }
 
 /// Comprehensive performance metrics
@@ -153,6 +161,38 @@ pub struct CachePerformanceMetrics {
 
     /// Cache eviction rate (evictions per minute)
     pub cache_eviction_rate: f64,
+
+    /// Total cache hits across monitored caches
+    pub cache_hits: u64,
+
+    /// Total cache misses across monitored caches
+    pub cache_misses: u64,
+
+    /// Total cache evictions across monitored caches
+    pub cache_evictions: u64,
+
+    /// Estimated bytes held by monitored caches
+    pub cache_bytes: u64,
+}
+
+impl CachePerformanceMetrics {
+    /// Add the counters of one cache to these metrics
+    pub fn accumulate(&mut self, stats: &CacheStats) {
+        self.cache_hits += stats.hits;
+        self.cache_misses += stats.misses;
+        self.cache_evictions += stats.evictions;
+        self.cache_bytes += stats.memory_usage_bytes() as u64;
+    }
+
+    /// Overall hit rate percentage across all accumulated caches
+    pub fn overall_hit_rate(&self) -> f64 {
+        let lookups = self.cache_hits + self.cache_misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            (self.cache_hits as f64 / lookups as f64) * 100.0
+        }
+    }
 }
 
 /// Resource utilization metrics
@@ -351,8 +391,8 @@ pub struct MonitoringConfig {
     /// Performance profiler integration
     pub enable_profiler_integration: bool,
 
-    /// Export metrics in Prometheus format
-    pub enable_prometheus_export: bool,
+    /// Metrics stack recorded metrics are emitted to
+    pub metrics_backend: MetricsBackendConfig,
 
     /// Enable detailed tracing
     pub enable_detailed_tracing: bool,
@@ -365,7 +405,7 @@ impl Default for MonitoringConfig {
             sampling_interval_seconds: 60,
             max_historical_samples: 1440, // 24 hours at 1-minute intervals
             enable_profiler_integration: true,
-            enable_prometheus_export: true,
+            metrics_backend: MetricsBackendConfig::None,
             enable_detailed_tracing: false,
         }
     }
@@ -373,7 +413,26 @@ impl Default for MonitoringConfig {
 
 impl EnhancedMonitoring {
     /// Create a new enhanced monitoring instance
+    ///
+    /// Falls back to discarding metrics if the configured backend cannot be created;
+    /// use `try_new` to handle that instead.
     pub fn new(config: MonitoringConfig) -> Self {
+        Self::try_new(config.clone()).unwrap_or_else(|e| {
+            error!("{e}, discarding metrics");
+            Self::with_backend(config, Arc::new(NoopMetrics))
+        })
+    }
+
+    /// Create a new enhanced monitoring instance with the configured metrics backend
+    pub fn try_new(config: MonitoringConfig) -> Result<Self, String> {
+        let backend = config
+            .metrics_backend
+            .build()
+            .map_err(|e| format!("Failed to create metrics backend: {e}"))?;
+        Ok(Self::with_backend(config, backend))
+    }
+
+    fn with_backend(config: MonitoringConfig, metrics_backend: Arc<dyn MetricsBackend>) -> Self {
         info!("Initializing enhanced monitoring system");
 
         Self {
@@ -386,6 +445,29 @@ impl EnhancedMonitoring {
                 ..Default::default()
             })),
             config,
+            metrics_backend,
+        }
+    }
+
+    /// Emit metrics to `backend` instead of the configured one
+    pub fn with_metrics_backend(mut self, backend: Arc<dyn MetricsBackend>) -> Self {
+        self.metrics_backend = backend;
+        self
+    }
+
+    /// Backend recorded metrics are emitted to
+    pub fn metrics_backend(&self) -> &Arc<dyn MetricsBackend> {
+        &self.metrics_backend
+    }
+
+    /// Current metrics in the text format of a scraped backend such as Prometheus
+    pub fn render_metrics(&self) -> Option<String> {
+        self.metrics_backend.render()
+    }
+
+    fn emit_gauges(&self, gauges: &[(&str, f64)]) {
+        for (name, value) in gauges {
+            self.metrics_backend.set_gauge(name, *value, &[]);
         }
     }
 }
@@ -413,6 +495,27 @@ impl EnhancedMonitoring {
             .map_err(|e| format!("Failed to lock performance metrics: {e}"))?;
 
         perf_metrics.engine_performance = metrics.clone();
+        self.emit_gauges(&[
+            ("engine_facts_per_second", metrics.facts_per_second),
+            (
+                "engine_avg_rule_execution_time_us",
+                metrics.avg_rule_execution_time_us,
+            ),
+            (
+                "engine_rules_compiled_per_second",
+                metrics.rules_compiled_per_second,
+            ),
+            ("engine_success_rate_percent", metrics.success_rate_percent),
+            (
+                "engine_avg_memory_per_operation_bytes",
+                metrics.avg_memory_per_operation as f64,
+            ),
+            ("engine_cpu_usage_percent", metrics.cpu_usage_percent),
+            (
+                "engine_gc_frequency_per_minute",
+                metrics.gc_frequency_per_minute,
+            ),
+        ]);
 
         info!(
             "Recorded engine performance metrics: {:.2} facts/sec, {:.2}% success rate",
@@ -438,6 +541,23 @@ impl EnhancedMonitoring {
             .map_err(|e| format!("Failed to lock performance metrics: {e}"))?;
 
         perf_metrics.rete_performance = metrics.clone();
+        self.emit_gauges(&[
+            ("rete_alpha_node_hit_rate", metrics.alpha_node_hit_rate),
+            ("rete_beta_node_efficiency", metrics.beta_node_efficiency),
+            (
+                "rete_working_memory_utilization",
+                metrics.working_memory_utilization,
+            ),
+            ("rete_rule_firing_rate", metrics.rule_firing_rate),
+            (
+                "rete_conflict_resolution_time_us",
+                metrics.conflict_resolution_time_us,
+            ),
+            (
+                "rete_pattern_matching_efficiency",
+                metrics.pattern_matching_efficiency,
+            ),
+        ]);
 
         debug!(
             "Recorded RETE performance: {:.2}% alpha hit rate, {:.2}% beta efficiency",
@@ -460,6 +580,16 @@ impl EnhancedMonitoring {
             .map_err(|e| format!("Failed to lock performance metrics: {e}"))?;
 
         perf_metrics.memory_pool_performance = metrics.clone();
+        self.emit_gauges(&[
+            ("memory_pool_hit_rate", metrics.overall_hit_rate),
+            ("memory_pool_saved_bytes", metrics.memory_saved_bytes as f64),
+            ("memory_pool_utilization", metrics.pool_utilization),
+            (
+                "memory_pool_avg_allocation_time_ns",
+                metrics.avg_allocation_time_ns,
+            ),
+            ("memory_pool_contention_rate", metrics.contention_rate),
+        ]);
 
         debug!(
             "Recorded memory pool performance: {:.2}% hit rate, {} bytes saved",
@@ -485,6 +615,19 @@ impl EnhancedMonitoring {
             .map_err(|e| format!("Failed to lock performance metrics: {e}"))?;
 
         perf_metrics.parallel_performance = metrics.clone();
+        self.emit_gauges(&[
+            ("parallel_efficiency", metrics.parallel_efficiency),
+            ("parallel_worker_utilization", metrics.worker_utilization),
+            (
+                "parallel_load_balancing_score",
+                metrics.load_balancing_score,
+            ),
+            (
+                "parallel_thread_contention_rate",
+                metrics.thread_contention_rate,
+            ),
+            ("parallel_speedup_factor", metrics.speedup_factor),
+        ]);
 
         info!(
             "Recorded parallel performance: {:.2}% efficiency, {:.2}x speedup",
@@ -494,6 +637,42 @@ impl EnhancedMonitoring {
         Ok(())
     }
 
+    /// Record cache performance
+    #[instrument(skip(self))]
+    pub fn record_cache_performance(&self, metrics: CachePerformanceMetrics) -> Result<(), String> {
+        if !self.config.enabled {
+            return Ok(());
+        }
+
+        let mut perf_metrics = self
+            .performance_metrics
+            .write()
+            .map_err(|e| format!("Failed to lock performance metrics: {e}"))?;
+
+        perf_metrics.cache_performance = metrics.clone();
+        self.emit_gauges(&[
+            ("cache_rule_hit_rate", metrics.rule_cache_hit_rate),
+            (
+                "cache_calculator_hit_rate",
+                metrics.calculator_cache_hit_rate,
+            ),
+            ("cache_fact_hit_rate", metrics.fact_cache_hit_rate),
+            ("cache_avg_lookup_time_ns", metrics.avg_cache_lookup_time_ns),
+            ("cache_eviction_rate", metrics.cache_eviction_rate),
+            ("cache_hits", metrics.cache_hits as f64),
+            ("cache_misses", metrics.cache_misses as f64),
+            ("cache_evictions", metrics.cache_evictions as f64),
+            ("cache_bytes", metrics.cache_bytes as f64),
+        ]);
+
+        debug!(
+            "Recorded cache performance: {} hits, {} misses, {} evictions, {} bytes",
+            metrics.cache_hits, metrics.cache_misses, metrics.cache_evictions, metrics.cache_bytes
+        );
+
+        Ok(())
+    }
+
     /// Record resource utilization
     #[instrument(skip(self))]
     pub fn record_resource_metrics(&self, metrics: ResourceMetrics) -> Result<(), String> {
@@ -507,6 +686,30 @@ impl EnhancedMonitoring {
             .map_err(|e| format!("Failed to lock resource metrics: {e}"))?;
 
         *resource_metrics = metrics.clone();
+        self.emit_gauges(&[
+            (
+                "resource_memory_usage_bytes",
+                metrics.memory_usage_bytes as f64,
+            ),
+            (
+                "resource_peak_memory_bytes",
+                metrics.peak_memory_bytes as f64,
+            ),
+            ("resource_memory_growth_rate", metrics.memory_growth_rate),
+            (
+                "resource_file_descriptors_used",
+                metrics.file_descriptors_used as f64,
+            ),
+            (
+                "resource_active_connections",
+                metrics.active_connections as f64,
+            ),
+            (
+                "resource_disk_io_ops_per_second",
+                metrics.disk_io_ops_per_second,
+            ),
+            ("resource_thread_count", metrics.thread_count as f64),
+        ]);
 
         debug!(
             "Recorded resource metrics: {} MB memory, {} threads",
@@ -533,6 +736,33 @@ impl EnhancedMonitoring {
             .map_err(|e| format!("Failed to lock business metrics: {e}"))?;
 
         *business_metrics = metrics.clone();
+        self.emit_gauges(&[
+            (
+                "business_rules_processed_last_hour",
+                metrics.rules_processed_last_hour as f64,
+            ),
+            (
+                "business_compliance_checks_performed",
+                metrics.compliance_checks_performed as f64,
+            ),
+            (
+                "business_payroll_calculations_completed",
+                metrics.payroll_calculations_completed as f64,
+            ),
+            (
+                "business_tronc_distributions_processed",
+                metrics.tronc_distributions_processed as f64,
+            ),
+            ("business_error_rate_percent", metrics.error_rate_percent),
+            (
+                "business_rule_violations_detected",
+                metrics.rule_violations_detected as f64,
+            ),
+            (
+                "business_avg_processing_latency_ms",
+                metrics.avg_processing_latency_ms,
+            ),
+        ]);
 
         info!(
             "Recorded business metrics: {} rules processed, {:.2}% error rate",
@@ -634,12 +864,13 @@ impl EnhancedMonitoring {
 
     /// Check for performance-related alerts
     fn check_performance_alerts(&self, metrics: &EnginePerformanceMetrics) -> Result<(), String> {
-        let alert_manager = self
+        // Released before triggering, which takes the alert manager's write lock
+        let thresholds = self
             .alert_manager
             .read()
-            .map_err(|e| format!("Failed to read alert manager: {e}"))?;
-
-        let thresholds = &alert_manager.thresholds;
+            .map_err(|e| format!("Failed to read alert manager: {e}"))?
+            .thresholds
+            .clone();
 
         // Check CPU usage
         if metrics.cpu_usage_percent > thresholds.cpu_usage_critical {
@@ -677,12 +908,13 @@ impl EnhancedMonitoring {
 
     /// Check for resource-related alerts
     fn check_resource_alerts(&self, metrics: &ResourceMetrics) -> Result<(), String> {
-        let alert_manager = self
+        // Released before triggering, which takes the alert manager's write lock
+        let thresholds = self
             .alert_manager
             .read()
-            .map_err(|e| format!("Failed to read alert manager: {e}"))?;
-
-        let thresholds = &alert_manager.thresholds;
+            .map_err(|e| format!("Failed to read alert manager: {e}"))?
+            .thresholds
+            .clone();
 
         // Check memory usage
         let memory_usage_percent =
@@ -719,6 +951,15 @@ impl EnhancedMonitoring {
             resolved: false,
         };
 
+        self.metrics_backend.increment_counter(
+            "alerts_triggered_total",
+            1,
+            &[
+                ("alert_type", &format!("{alert_type:?}")),
+                ("severity", &format!("{:?}", alert.severity)),
+            ],
+        );
+
         match alert.severity {
             AlertSeverity::Critical | AlertSeverity::Emergency => {
                 error!("ALERT: {:?} - {}", alert_type, message);
@@ -905,6 +1146,23 @@ mod tests {
         assert_eq!(recorded.engine_performance.success_rate_percent, 99.5);
     }
 
+    #[test]
+    fn test_cache_performance_recording() {
+        let monitoring = EnhancedMonitoring::default();
+
+        let mut metrics = CachePerformanceMetrics::default();
+        metrics.accumulate(&CacheStats { size: 2, hits: 3, misses: 1, ..Default::default() });
+        metrics.accumulate(&CacheStats { size: 1, evictions: 4, ..Default::default() });
+        monitoring.record_cache_performance(metrics).unwrap();
+
+        let recorded = monitoring.get_performance_metrics().unwrap().cache_performance;
+        assert_eq!(recorded.cache_hits, 3);
+        assert_eq!(recorded.cache_misses, 1);
+        assert_eq!(recorded.cache_evictions, 4);
+        assert_eq!(recorded.cache_bytes, 3 * 64);
+        assert_eq!(recorded.overall_hit_rate(), 75.0);
+    }
+
     #[test]
     fn test_health_score_calculation() {
         let monitoring = EnhancedMonitoring::default();
@@ -3,6 +3,8 @@
 //! This module provides structured error types for all core engine operations,
 //! enabling better error handling, debugging, and integration with higher-level systems.
 
+use crate::action_validation::ActionValidationReport;
+use crate::fact_schema::FactValidationReport;
 use std::fmt;
 use thiserror::Error;
 
@@ -18,6 +20,11 @@ pub enum BingoError {
         details: Option<String>,
     },
 
+    /// Rule actions that failed validation when the rule was added, with every problem
+    /// found
+    #[error("Rule error: {message}")]
+    ActionValidation { message: String, report: ActionValidationReport },
+
     /// Rule condition parsing and validation errors
     #[error("Condition error: {message}")]
     Condition {
@@ -27,6 +34,10 @@ pub enum BingoError {
         value: Option<String>,
     },
 
+    /// Fact refused by its type's schema on insert, with every problem found
+    #[error("Fact validation error: {message}")]
+    FactValidation { message: String, report: FactValidationReport },
+
     /// Fact store operation errors
     #[error("Fact store error: {message}")]
     FactStore {
@@ -73,6 +84,10 @@ pub enum BingoError {
         available_size: Option<usize>,
     },
 
+    /// Working memory limit reached that eviction could not make room under
+    #[error("Capacity exceeded: {message}")]
+    CapacityExceeded { message: String, resource: String, limit: usize, requested: usize },
+
     /// Serialization and deserialization errors
     #[error("Serialization error: {message}")]
     Serialization { message: String, data_type: Option<String>, operation: Option<String> },
@@ -108,13 +123,15 @@ impl BingoError {
     /// Get the error category for logging and metrics
     pub fn category(&self) -> &'static str {
         match self {
-            BingoError::Rule { .. } => "rule",
+            BingoError::Rule { .. } | BingoError::ActionValidation { .. } => "rule",
             BingoError::Condition { .. } => "condition",
+            BingoError::FactValidation { .. } => "fact_validation",
             BingoError::FactStore { .. } => "fact_store",
             BingoError::ReteNetwork { .. } => "rete_network",
             BingoError::Calculator { .. } => "calculator",
             BingoError::Aggregation { .. } => "aggregation",
             BingoError::Memory { .. } => "memory",
+            BingoError::CapacityExceeded { .. } => "capacity",
             BingoError::Serialization { .. } => "serialization",
             BingoError::Configuration { .. } => "configuration",
             BingoError::Performance { .. } => "performance",
@@ -127,12 +144,15 @@ impl BingoError {
     pub fn severity(&self) -> ErrorSeverity {
         match self {
             BingoError::Rule { .. } => ErrorSeverity::Medium,
+            BingoError::ActionValidation { .. } => ErrorSeverity::Medium,
             BingoError::Condition { .. } => ErrorSeverity::Medium,
+            BingoError::FactValidation { .. } => ErrorSeverity::Medium,
             BingoError::FactStore { .. } => ErrorSeverity::High,
             BingoError::ReteNetwork { .. } => ErrorSeverity::High,
             BingoError::Calculator { .. } => ErrorSeverity::Medium,
             BingoError::Aggregation { .. } => ErrorSeverity::Medium,
             BingoError::Memory { .. } => ErrorSeverity::Critical,
+            BingoError::CapacityExceeded { .. } => ErrorSeverity::High,
             BingoError::Serialization { .. } => ErrorSeverity::Low,
             BingoError::Configuration { .. } => ErrorSeverity::Critical,
             BingoError::Performance { .. } => ErrorSeverity::High,
@@ -149,12 +169,23 @@ impl BingoError {
                 rule_name: rule_name.clone(),
                 ..Default::default()
             },
+            BingoError::ActionValidation { report, .. } => ErrorContext {
+                rule_id: Some(report.rule_id),
+                rule_name: Some(report.rule_name.clone()),
+                ..Default::default()
+            },
             BingoError::Condition { field, operator, value, .. } => ErrorContext {
                 field: field.clone(),
                 operator: operator.clone(),
                 value: value.clone(),
                 ..Default::default()
             },
+            BingoError::FactValidation { report, .. } => ErrorContext {
+                fact_id: Some(report.fact_id),
+                field: report.problems.first().map(|problem| problem.field().to_string()),
+                operation: Some("insert".to_string()),
+                ..Default::default()
+            },
             BingoError::FactStore { fact_id, operation, .. } => ErrorContext {
                 fact_id: *fact_id,
                 operation: operation.clone(),
@@ -179,12 +210,15 @@ impl BingoError {
     pub fn is_recoverable(&self) -> bool {
         match self {
             BingoError::Rule { .. } => true,
+            BingoError::ActionValidation { .. } => true,
             BingoError::Condition { .. } => true,
-            BingoError::FactStore { .. } => false, // Data integrity concerns
-            BingoError::ReteNetwork { .. } => false, // Network state corruption
+            BingoError::FactValidation { .. } => true, // Resubmit the corrected fact
+            BingoError::FactStore { .. } => false,     // Data integrity concerns
+            BingoError::ReteNetwork { .. } => false,   // Network state corruption
             BingoError::Calculator { .. } => true,
             BingoError::Aggregation { .. } => true,
             BingoError::Memory { .. } => false, // Memory issues require restart
+            BingoError::CapacityExceeded { .. } => true, // Retry once memory is freed
             BingoError::Serialization { .. } => true,
             BingoError::Configuration { .. } => false, // Config errors need fixing
             BingoError::Performance { .. } => true,
@@ -249,6 +283,16 @@ impl BingoError {
         Self::Rule { message: message.into(), rule_id: None, rule_name: None, details: None }
     }
 
+    /// Create an error carrying the problems found in a rule's actions
+    pub fn action_validation(report: ActionValidationReport) -> Self {
+        Self::ActionValidation { message: report.to_string(), report }
+    }
+
+    /// Create an error carrying the problems found in a fact refused by its schema
+    pub fn fact_validation(report: FactValidationReport) -> Self {
+        Self::FactValidation { message: report.to_string(), report }
+    }
+
     /// Create a condition parsing error
     pub fn condition_parse(
         field: &str,
@@ -334,6 +378,21 @@ impl BingoError {
         }
     }
 
+    /// Create a capacity error for `requested` units of a resource limited to `limit`
+    pub fn capacity_exceeded(
+        resource: &str,
+        limit: usize,
+        requested: usize,
+        message: impl Into<String>,
+    ) -> Self {
+        Self::CapacityExceeded {
+            message: message.into(),
+            resource: resource.to_string(),
+            limit,
+            requested,
+        }
+    }
+
     /// Create a serialization error
     pub fn serialization(data_type: &str, operation: &str, message: impl Into<String>) -> Self {
         Self::Serialization {
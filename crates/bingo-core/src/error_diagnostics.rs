@@ -534,7 +534,7 @@ impl ErrorDiagnosticsManager {
                         code: r#"// Supported operators:
 "equals", "not_equals", "greater_than", "less_than", 
 "greater_than_or_equal", "less_than_or_equal", 
-"contains", "starts_with", "ends_with", "regex""#
+"contains", "starts_with", "ends_with", "matches", "contains_ignore_case", "in", "not_in""#
                             .to_string(),
                         explanation: "List of supported condition operators".to_string(),
                     }],
@@ -0,0 +1,119 @@
+//! Budgeted evaluation of fact batches
+//!
+//! Tenants sharing one process take turns on their engines. A batch processed with
+//! `BingoEngine::process_facts_budgeted` stops once it has fired a number of rules or
+//! run for a length of time, and returns a `Continuation` for the facts it has not
+//! evaluated yet. `BingoEngine::resume_evaluation` picks the batch up again with a
+//! fresh budget, so a scheduler can interleave large batches from many tenants.
+//!
+//! The whole batch is stored and asserted into aggregation and window nodes before
+//! its first fact is evaluated, as in unbudgeted processing; only rule evaluation is
+//! spread over the slices. Budgets are checked between facts, so a slice always
+//! evaluates at least one fact and may overrun its budget by the firings of its last
+//! fact.
+
+use crate::rete_nodes::RuleExecutionResult;
+use crate::types::FactId;
+use std::time::{Duration, Instant};
+
+/// Firing and time limits of one slice of a batch, unlimited when `None`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EvaluationBudget {
+    /// Rule firings after which the slice stops
+    pub max_firings: Option<usize>,
+    /// Time after which the slice stops
+    pub max_duration: Option<Duration>,
+}
+
+impl EvaluationBudget {
+    /// Budget that never runs out
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+
+    /// Stop after `max_firings` rule firings
+    pub fn firings(max_firings: usize) -> Self {
+        Self { max_firings: Some(max_firings), ..Self::default() }
+    }
+
+    /// Stop after `max_duration`
+    pub fn duration(max_duration: Duration) -> Self {
+        Self { max_duration: Some(max_duration), ..Self::default() }
+    }
+
+    /// Also stop after `max_duration`
+    pub fn with_duration(self, max_duration: Duration) -> Self {
+        Self { max_duration: Some(max_duration), ..self }
+    }
+
+    /// Whether the budget never runs out
+    pub fn is_unlimited(&self) -> bool {
+        self.max_firings.is_none() && self.max_duration.is_none()
+    }
+
+    /// Whether a slice that started at `started` and has fired `firings` rules is done
+    pub(crate) fn is_spent(&self, firings: usize, started: Instant) -> bool {
+        self.max_firings.is_some_and(|max_firings| firings >= max_firings)
+            || self.max_duration.is_some_and(|max_duration| started.elapsed() >= max_duration)
+    }
+}
+
+/// Facts of a paused batch that are still to be evaluated
+///
+/// Resume it on the engine that returned it. Facts retracted or cleared from the
+/// engine while the batch is paused are skipped when it resumes.
+#[derive(Debug)]
+pub struct Continuation {
+    pub(crate) batch_id: u64,
+    pub(crate) fact_ids: Vec<FactId>,
+    pub(crate) fact_count: usize,
+    pub(crate) rules_fired: usize,
+    pub(crate) started: Instant,
+    pub(crate) slices: usize,
+}
+
+impl Continuation {
+    /// Start of a batch of `fact_count` facts with nothing evaluated yet
+    pub(crate) fn new(batch_id: u64, fact_count: usize) -> Self {
+        Self {
+            batch_id,
+            fact_ids: Vec::new(),
+            fact_count,
+            rules_fired: 0,
+            started: Instant::now(),
+            slices: 0,
+        }
+    }
+
+    /// Batch the continuation belongs to, as reported in batch events
+    pub fn batch_id(&self) -> u64 {
+        self.batch_id
+    }
+
+    /// IDs of the facts still to be evaluated
+    pub fn remaining(&self) -> &[FactId] {
+        &self.fact_ids
+    }
+
+    /// Slices of the batch evaluated so far
+    pub fn slices(&self) -> usize {
+        self.slices
+    }
+}
+
+/// Results of one slice of a budgeted batch
+#[derive(Debug)]
+pub struct BudgetedResults {
+    pub results: Vec<RuleExecutionResult>,
+    /// Ruleset version that produced the results
+    pub ruleset_version: u64,
+    /// Where to resume the batch, `None` once every fact has been evaluated
+    pub continuation: Option<Continuation>,
+}
+
+impl BudgetedResults {
+    /// Whether the whole batch has been evaluated
+    pub fn is_complete(&self) -> bool {
+        self.continuation.is_none()
+    }
+}
@@ -0,0 +1,184 @@
+//! Engine lifecycle events
+//!
+//! An [`EventBus`] tells embedders what the engine is doing without scraping its logs.
+//! Every engine owns one and publishes an [`EngineEvent`] when:
+//!
+//! - a rule is added, updated or removed
+//! - a session is created over the engine
+//! - a batch of facts starts, finishes or fails processing
+//! - a working memory limit rejects incoming facts
+//! - a circuit breaker trips, such as memory pressure refusing inserts
+//!
+//! Subscribers are called synchronously on the publishing thread after the engine has
+//! released its locks, so they may call back into the engine. Subscribers that do slow
+//! work should hand events to an [`AsyncSink`] with [`EventBus::subscribe_sink`].
+
+use crate::event_sink::{AsyncSink, Submission};
+use crate::types::RuleId;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use tracing::debug;
+
+/// Lifecycle change published by an engine's [`EventBus`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum EngineEvent {
+    /// A rule was added to the ruleset
+    RuleAdded { rule_id: RuleId, rule_name: String, ruleset_version: u64 },
+    /// A rule's definition was replaced
+    RuleUpdated { rule_id: RuleId, rule_name: String, ruleset_version: u64 },
+    /// A rule was removed from the ruleset
+    RuleRemoved { rule_id: RuleId, ruleset_version: u64 },
+    /// A session was created over the engine
+    SessionCreated { rule_count: usize, ruleset_version: u64 },
+    /// A batch of facts was accepted for processing
+    BatchStarted { batch_id: u64, fact_count: usize },
+    /// A batch of facts was processed
+    BatchFinished { batch_id: u64, fact_count: usize, rules_fired: usize, duration_ms: u64 },
+    /// A batch of facts failed processing
+    BatchFailed { batch_id: u64, error: String },
+    /// A working memory limit rejected incoming facts
+    LimitBreached { resource: String, limit: usize, requested: usize },
+    /// A circuit breaker tripped and the engine is refusing work
+    CircuitBreakerTripped { breaker: String, reason: String },
+}
+
+impl EngineEvent {
+    /// Short name of the event kind, such as `rule_added`
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::RuleAdded { .. } => "rule_added",
+            Self::RuleUpdated { .. } => "rule_updated",
+            Self::RuleRemoved { .. } => "rule_removed",
+            Self::SessionCreated { .. } => "session_created",
+            Self::BatchStarted { .. } => "batch_started",
+            Self::BatchFinished { .. } => "batch_finished",
+            Self::BatchFailed { .. } => "batch_failed",
+            Self::LimitBreached { .. } => "limit_breached",
+            Self::CircuitBreakerTripped { .. } => "circuit_breaker_tripped",
+        }
+    }
+}
+
+/// Callback notified of engine events
+pub trait EngineEventListener: Send + Sync {
+    /// Observe an event; called synchronously on the thread that published it
+    fn on_event(&self, event: &EngineEvent);
+}
+
+impl<F> EngineEventListener for F
+where
+    F: Fn(&EngineEvent) + Send + Sync,
+{
+    fn on_event(&self, event: &EngineEvent) {
+        self(event)
+    }
+}
+
+/// Handle for cancelling a subscription with [`EventBus::unsubscribe`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SubscriptionId(u64);
+
+/// Publishes engine events to subscribers
+#[derive(Default)]
+pub struct EventBus {
+    subscribers: RwLock<Vec<(SubscriptionId, Arc<dyn EngineEventListener>)>>,
+    next_subscription: AtomicU64,
+    published: AtomicU64,
+}
+
+impl std::fmt::Debug for EventBus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventBus")
+            .field("subscribers", &self.subscriber_count())
+            .field("published", &self.published_count())
+            .finish()
+    }
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a listener notified of every event published from now on
+    pub fn subscribe(&self, listener: impl EngineEventListener + 'static) -> SubscriptionId {
+        let id = SubscriptionId(self.next_subscription.fetch_add(1, Ordering::Relaxed));
+        self.subscribers.write().unwrap().push((id, Arc::new(listener)));
+        id
+    }
+
+    /// Queue every event on `sink` for delivery off the publishing thread
+    ///
+    /// Events the sink's overflow policy rejects or evicts are counted in its stats.
+    pub fn subscribe_sink(&self, sink: Arc<AsyncSink<EngineEvent>>) -> SubscriptionId {
+        self.subscribe(move |event: &EngineEvent| {
+            if let Submission::Rejected(_) = sink.submit(event.clone()) {
+                debug!(
+                    event = event.kind(),
+                    "Engine event rejected by full event sink"
+                );
+            }
+        })
+    }
+
+    /// Stop notifying a listener; returns whether it was subscribed
+    pub fn unsubscribe(&self, id: SubscriptionId) -> bool {
+        let mut subscribers = self.subscribers.write().unwrap();
+        let before = subscribers.len();
+        subscribers.retain(|(subscribed, _)| *subscribed != id);
+        subscribers.len() != before
+    }
+
+    /// Notify every subscriber of `event`, in subscription order
+    ///
+    /// Listeners may subscribe or unsubscribe while being notified; the change applies
+    /// from the next event.
+    pub fn publish(&self, event: EngineEvent) {
+        self.published.fetch_add(1, Ordering::Relaxed);
+        let subscribers: Vec<Arc<dyn EngineEventListener>> = self
+            .subscribers
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(_, listener)| listener.clone())
+            .collect();
+        for listener in subscribers {
+            listener.on_event(&event);
+        }
+    }
+
+    /// Number of listeners subscribed
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.read().unwrap().len()
+    }
+
+    /// Number of events published so far
+    pub fn published_count(&self) -> u64 {
+        self.published.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_unsubscribed_listener_stops_receiving_events() {
+        let bus = EventBus::new();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = seen.clone();
+        let id = bus.subscribe(move |event: &EngineEvent| {
+            sink.lock().unwrap().push(event.kind());
+        });
+
+        bus.publish(EngineEvent::RuleRemoved { rule_id: 1, ruleset_version: 1 });
+        assert!(bus.unsubscribe(id));
+        assert!(!bus.unsubscribe(id));
+        bus.publish(EngineEvent::RuleRemoved { rule_id: 2, ruleset_version: 2 });
+
+        assert_eq!(*seen.lock().unwrap(), vec!["rule_removed"]);
+        assert_eq!(bus.published_count(), 2);
+        assert_eq!(bus.subscriber_count(), 0);
+    }
+}
@@ -0,0 +1,458 @@
+//! Bounded asynchronous delivery of side effects
+//!
+//! Rule side effects such as webhooks and session events are handed to external systems
+//! without making rule execution wait for them. An [`AsyncSink`] queues events for a
+//! pool of worker threads that pass them to an [`EventSink`], and bounds the queue so
+//! a slow or unavailable downstream system never makes events pile up in memory. When
+//! the queue is full, [`QueueOverflowPolicy`] decides between:
+//!
+//! - [`QueueOverflowPolicy::Block`]: wait for a worker to free a slot
+//! - [`QueueOverflowPolicy::DropOldest`]: evict the oldest queued event
+//! - [`QueueOverflowPolicy::Error`]: reject the new event
+//!
+//! Evicted and rejected events are handed back to the caller, which decides whether to
+//! dead-letter them. Shutting a sink down, explicitly or by dropping it, stops new
+//! submissions, delivers everything still queued and flushes the sink, waiting at most
+//! the configured shutdown timeout.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+/// Destination of the events queued on an [`AsyncSink`]
+pub trait EventSink<T>: Send + Sync {
+    /// Deliver one event; an error is counted and reported by the next flush
+    fn deliver(&self, event: T) -> Result<(), String>;
+
+    /// Push buffered deliveries downstream, e.g. flush a producer's batch
+    fn flush(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+impl<T, F> EventSink<T> for F
+where
+    F: Fn(T) -> Result<(), String> + Send + Sync,
+{
+    fn deliver(&self, event: T) -> Result<(), String> {
+        self(event)
+    }
+}
+
+/// What happens to an event submitted to a full queue
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueueOverflowPolicy {
+    /// Wait until a worker frees a slot
+    Block,
+    /// Evict the oldest queued event to make room
+    DropOldest,
+    /// Reject the new event
+    #[default]
+    Error,
+}
+
+/// Queue bound, overflow policy, workers and shutdown timeout of an [`AsyncSink`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AsyncSinkConfig {
+    /// Events waiting for a worker
+    pub capacity: usize,
+    pub overflow: QueueOverflowPolicy,
+    /// Worker threads delivering events
+    pub workers: usize,
+    /// Time allowed for delivering the remaining events when the sink shuts down
+    pub shutdown_timeout: Duration,
+}
+
+impl Default for AsyncSinkConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 1024,
+            overflow: QueueOverflowPolicy::Error,
+            workers: 1,
+            shutdown_timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+/// Outcome of submitting an event to an [`AsyncSink`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Submission<T> {
+    /// The event was queued
+    Queued,
+    /// The event was queued by evicting this older event
+    Evicted(T),
+    /// The queue was full or shut down, so the event was not queued
+    Rejected(T),
+}
+
+/// Delivery counters of an [`AsyncSink`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AsyncSinkStats {
+    pub queued: u64,
+    pub delivered: u64,
+    /// Deliveries the sink returned an error for
+    pub failed: u64,
+    /// Events evicted by [`QueueOverflowPolicy::DropOldest`]
+    pub evicted: u64,
+    pub rejected: u64,
+    /// Events queued or being delivered
+    pub pending: usize,
+}
+
+/// Bounded queue drained into an [`EventSink`] by worker threads
+///
+/// Dropping a sink that was not shut down yet shuts it down, waiting up to the
+/// configured shutdown timeout for the queue to drain.
+pub struct AsyncSink<T: Send + 'static> {
+    shared: Arc<Shared<T>>,
+    workers: Mutex<Vec<JoinHandle<()>>>,
+}
+
+struct Shared<T> {
+    sink: Box<dyn EventSink<T>>,
+    config: AsyncSinkConfig,
+    queue: Mutex<QueueState<T>>,
+    /// Signalled when an event is queued or the sink shuts down
+    not_empty: Condvar,
+    /// Signalled when a worker takes an event or the sink shuts down
+    not_full: Condvar,
+    /// Signalled when the last pending event is delivered
+    idle: Condvar,
+    queued: AtomicU64,
+    delivered: AtomicU64,
+    failed: AtomicU64,
+    evicted: AtomicU64,
+    rejected: AtomicU64,
+}
+
+struct QueueState<T> {
+    events: VecDeque<T>,
+    in_flight: usize,
+    closed: bool,
+    /// First delivery error since the last flush
+    error: Option<String>,
+}
+
+impl<T: Send + 'static> fmt::Debug for AsyncSink<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncSink")
+            .field("config", &self.shared.config)
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+impl<T: Send + 'static> AsyncSink<T> {
+    /// Start `config.workers` threads named after `name` delivering to `sink`
+    pub fn new(name: &str, sink: impl EventSink<T> + 'static, config: AsyncSinkConfig) -> Self {
+        let shared = Arc::new(Shared {
+            sink: Box::new(sink),
+            queue: Mutex::new(QueueState {
+                events: VecDeque::with_capacity(config.capacity.clamp(1, 1024)),
+                in_flight: 0,
+                closed: false,
+                error: None,
+            }),
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+            idle: Condvar::new(),
+            queued: AtomicU64::new(0),
+            delivered: AtomicU64::new(0),
+            failed: AtomicU64::new(0),
+            evicted: AtomicU64::new(0),
+            rejected: AtomicU64::new(0),
+            config,
+        });
+
+        let workers = (0..shared.config.workers.max(1))
+            .map(|worker| {
+                let shared = Arc::clone(&shared);
+                std::thread::Builder::new()
+                    .name(format!("{name}-{worker}"))
+                    .spawn(move || shared.run_worker())
+                    .expect("failed to spawn event sink worker")
+            })
+            .collect();
+        debug!(
+            name,
+            workers = shared.config.workers,
+            "Started event sink workers"
+        );
+        Self { shared, workers: Mutex::new(workers) }
+    }
+
+    /// Queue `event` for delivery, applying the overflow policy if the queue is full
+    pub fn submit(&self, event: T) -> Submission<T> {
+        let shared = &self.shared;
+        let capacity = shared.config.capacity.max(1);
+        let mut queue = shared.queue.lock().unwrap();
+        let mut evicted = None;
+
+        if !queue.closed && queue.events.len() >= capacity {
+            match shared.config.overflow {
+                QueueOverflowPolicy::Block => {
+                    queue = shared
+                        .not_full
+                        .wait_while(queue, |queue| {
+                            !queue.closed && queue.events.len() >= capacity
+                        })
+                        .unwrap();
+                }
+                QueueOverflowPolicy::DropOldest => {
+                    evicted = queue.events.pop_front();
+                    shared.evicted.fetch_add(1, Ordering::Relaxed);
+                }
+                QueueOverflowPolicy::Error => {}
+            }
+        }
+        if queue.closed || queue.events.len() >= capacity {
+            shared.rejected.fetch_add(1, Ordering::Relaxed);
+            return Submission::Rejected(event);
+        }
+
+        queue.events.push_back(event);
+        drop(queue);
+        shared.queued.fetch_add(1, Ordering::Relaxed);
+        shared.not_empty.notify_one();
+        match evicted {
+            Some(event) => Submission::Evicted(event),
+            None => Submission::Queued,
+        }
+    }
+
+    /// Wait until every queued event is delivered, then flush the sink
+    ///
+    /// Fails with the first delivery error since the last flush, or if events are
+    /// still pending after `timeout`.
+    pub fn flush(&self, timeout: Duration) -> Result<(), String> {
+        let queue = self.shared.wait_idle(timeout)?;
+        self.shared.finish_flush(queue)
+    }
+
+    /// Stop accepting events, deliver the ones still queued and flush the sink
+    ///
+    /// Events still queued after `timeout` are left to the workers, which exit once
+    /// the queue is empty.
+    pub fn shutdown(&self, timeout: Duration) -> Result<(), String> {
+        self.shared.queue.lock().unwrap().closed = true;
+        self.shared.not_empty.notify_all();
+        self.shared.not_full.notify_all();
+
+        let result = self.flush(timeout);
+        if result.is_ok() {
+            for worker in self.workers.lock().unwrap().drain(..) {
+                let _ = worker.join();
+            }
+        }
+        result
+    }
+
+    /// Whether the sink stopped accepting events
+    pub fn is_shut_down(&self) -> bool {
+        self.shared.queue.lock().unwrap().closed
+    }
+
+    pub fn stats(&self) -> AsyncSinkStats {
+        let shared = &self.shared;
+        let pending = {
+            let queue = shared.queue.lock().unwrap();
+            queue.events.len() + queue.in_flight
+        };
+        AsyncSinkStats {
+            queued: shared.queued.load(Ordering::Relaxed),
+            delivered: shared.delivered.load(Ordering::Relaxed),
+            failed: shared.failed.load(Ordering::Relaxed),
+            evicted: shared.evicted.load(Ordering::Relaxed),
+            rejected: shared.rejected.load(Ordering::Relaxed),
+            pending,
+        }
+    }
+
+    pub fn config(&self) -> &AsyncSinkConfig {
+        &self.shared.config
+    }
+}
+
+impl<T: Send + 'static> Drop for AsyncSink<T> {
+    fn drop(&mut self) {
+        if self.is_shut_down() {
+            return;
+        }
+        if let Err(error) = self.shutdown(self.shared.config.shutdown_timeout) {
+            warn!(%error, "Event sink shut down without delivering every event");
+        }
+    }
+}
+
+impl<T> Shared<T> {
+    fn run_worker(&self) {
+        loop {
+            let event = {
+                let mut queue = self
+                    .not_empty
+                    .wait_while(self.queue.lock().unwrap(), |queue| {
+                        queue.events.is_empty() && !queue.closed
+                    })
+                    .unwrap();
+                let Some(event) = queue.events.pop_front() else {
+                    return;
+                };
+                queue.in_flight += 1;
+                event
+            };
+            self.not_full.notify_one();
+
+            let result = self.sink.deliver(event);
+
+            let mut queue = self.queue.lock().unwrap();
+            queue.in_flight -= 1;
+            match result {
+                Ok(()) => {
+                    self.delivered.fetch_add(1, Ordering::Relaxed);
+                }
+                Err(error) => {
+                    debug!(%error, "Event delivery failed");
+                    self.failed.fetch_add(1, Ordering::Relaxed);
+                    queue.error.get_or_insert(error);
+                }
+            }
+            if queue.events.is_empty() && queue.in_flight == 0 {
+                self.idle.notify_all();
+            }
+        }
+    }
+
+    fn wait_idle(&self, timeout: Duration) -> Result<MutexGuard<'_, QueueState<T>>, String> {
+        let deadline = Instant::now() + timeout;
+        let mut queue = self.queue.lock().unwrap();
+        while !queue.events.is_empty() || queue.in_flight > 0 {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(format!(
+                    "{} events still pending after {timeout:?}",
+                    queue.events.len() + queue.in_flight
+                ));
+            }
+            queue = self.idle.wait_timeout(queue, remaining).unwrap().0;
+        }
+        Ok(queue)
+    }
+
+    fn finish_flush(&self, mut queue: MutexGuard<'_, QueueState<T>>) -> Result<(), String> {
+        let error = queue.error.take();
+        drop(queue);
+        self.sink.flush()?;
+        error.map_or(Ok(()), Err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    /// Sink whose deliveries wait for a permit, so tests control when the queue drains
+    fn gated_sink() -> (impl EventSink<u32>, mpsc::Sender<()>, Arc<Mutex<Vec<u32>>>) {
+        let (permits, gate) = mpsc::channel::<()>();
+        let gate = Mutex::new(gate);
+        let delivered = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&delivered);
+        let sink = move |event: u32| {
+            gate.lock().unwrap().recv().map_err(|_| "gate closed".to_string())?;
+            log.lock().unwrap().push(event);
+            Ok(())
+        };
+        (sink, permits, delivered)
+    }
+
+    fn config(overflow: QueueOverflowPolicy) -> AsyncSinkConfig {
+        AsyncSinkConfig { capacity: 2, overflow, ..AsyncSinkConfig::default() }
+    }
+
+    /// Submit events until one is in flight and the queue holds `capacity` more
+    fn fill(sink: &AsyncSink<u32>) {
+        assert_eq!(sink.submit(1), Submission::Queued);
+        while sink.stats().pending > 0 && sink.shared.queue.lock().unwrap().in_flight == 0 {
+            std::thread::yield_now();
+        }
+        assert_eq!(sink.submit(2), Submission::Queued);
+        assert_eq!(sink.submit(3), Submission::Queued);
+    }
+
+    #[test]
+    fn test_overflow_policies() {
+        let (events, permits, delivered) = gated_sink();
+        let sink = AsyncSink::new("test-sink", events, config(QueueOverflowPolicy::Error));
+        fill(&sink);
+        assert_eq!(sink.submit(4), Submission::Rejected(4));
+        (0..3).for_each(|_| permits.send(()).unwrap());
+        sink.flush(Duration::from_secs(5)).unwrap();
+        assert_eq!(*delivered.lock().unwrap(), vec![1, 2, 3]);
+        assert_eq!(sink.stats().rejected, 1);
+
+        let (events, permits, delivered) = gated_sink();
+        let sink = AsyncSink::new("test-sink", events, config(QueueOverflowPolicy::DropOldest));
+        fill(&sink);
+        assert_eq!(sink.submit(4), Submission::Evicted(2));
+        (0..3).for_each(|_| permits.send(()).unwrap());
+        sink.flush(Duration::from_secs(5)).unwrap();
+        assert_eq!(*delivered.lock().unwrap(), vec![1, 3, 4]);
+
+        let (events, permits, delivered) = gated_sink();
+        let sink = Arc::new(AsyncSink::new(
+            "test-sink",
+            events,
+            config(QueueOverflowPolicy::Block),
+        ));
+        fill(&sink);
+        let blocked = {
+            let sink = Arc::clone(&sink);
+            std::thread::spawn(move || sink.submit(4))
+        };
+        (0..4).for_each(|_| permits.send(()).unwrap());
+        assert_eq!(blocked.join().unwrap(), Submission::Queued);
+        sink.flush(Duration::from_secs(5)).unwrap();
+        assert_eq!(*delivered.lock().unwrap(), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_shutdown_drains_the_queue_and_reports_failures() {
+        let (events, permits, delivered) = gated_sink();
+        let sink = AsyncSink::new("test-sink", events, config(QueueOverflowPolicy::Error));
+        fill(&sink);
+        assert!(sink.flush(Duration::from_millis(10)).is_err());
+
+        (0..3).for_each(|_| permits.send(()).unwrap());
+        sink.shutdown(Duration::from_secs(5)).unwrap();
+        assert_eq!(delivered.lock().unwrap().len(), 3);
+        assert!(sink.is_shut_down());
+        assert_eq!(sink.submit(5), Submission::Rejected(5));
+
+        let failing = AsyncSink::new(
+            "test-sink",
+            |event: u32| {
+                if event == 2 {
+                    Err(format!("event {event} failed"))
+                } else {
+                    Ok(())
+                }
+            },
+            AsyncSinkConfig::default(),
+        );
+        for event in 1..=3 {
+            failing.submit(event);
+        }
+        assert_eq!(
+            failing.flush(Duration::from_secs(5)),
+            Err("event 2 failed".to_string())
+        );
+        assert_eq!((failing.stats().delivered, failing.stats().failed), (2, 1));
+        assert_eq!(failing.flush(Duration::from_secs(5)), Ok(()));
+    }
+}
@@ -0,0 +1,170 @@
+//! Audit traces of rule executions
+//!
+//! Compliance reviews need to know why a rule fired, not only that it did. With the
+//! audit log enabled, every [`RuleExecutionResult`](crate::rete_nodes::RuleExecutionResult)
+//! gets a `result_id` and an [`ExplanationTrace`] recording:
+//!
+//! - which fact satisfied each condition, with the field values the condition read
+//! - the inputs and output of each calculator the rule called
+//! - the actions the rule executed
+//!
+//! Traces are kept for the most recent results only, and serialize to JSON.
+
+use crate::rete_nodes::ActionResult;
+use crate::types::{Condition, FactId, FactValue, RuleId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+/// Identifies a rule execution result in the audit log
+pub type ResultId = u64;
+
+/// Retention of the audit log
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuditLogConfig {
+    /// Traces kept before the oldest is dropped
+    pub capacity: usize,
+}
+
+impl Default for AuditLogConfig {
+    fn default() -> Self {
+        Self { capacity: 10_000 }
+    }
+}
+
+/// Why a rule fired for a fact
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExplanationTrace {
+    pub result_id: ResultId,
+    pub rule_id: RuleId,
+    pub rule_name: String,
+    /// Fact whose processing fired the rule
+    pub fact_id: FactId,
+    pub external_id: Option<String>,
+    /// Conditions in the order the rule declares them
+    pub conditions: Vec<ConditionTrace>,
+    pub calculators: Vec<CalculatorTrace>,
+    pub actions: Vec<ActionResult>,
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl ExplanationTrace {
+    /// The trace as pretty-printed JSON
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// How one condition of a fired rule was satisfied
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConditionTrace {
+    pub condition: Condition,
+    /// Fact that satisfied the condition, `None` if no supporting fact did
+    pub fact_id: Option<FactId>,
+    /// Fields the condition reads, with their values in that fact
+    pub bound_values: BTreeMap<String, FactValue>,
+}
+
+/// Inputs and output of a calculator called by a fired rule
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CalculatorTrace {
+    pub calculator: String,
+    /// Calculator parameters with the values mapped to them
+    pub inputs: BTreeMap<String, FactValue>,
+    pub output_field: String,
+    /// `None` when the calculator failed or its result was discarded
+    pub output: Option<FactValue>,
+}
+
+#[derive(Debug, Default)]
+struct AuditLogState {
+    config: Option<AuditLogConfig>,
+    next_id: ResultId,
+    traces: VecDeque<ExplanationTrace>,
+}
+
+/// Bounded store of explanation traces, shared by network clones
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AuditLog {
+    state: Arc<Mutex<AuditLogState>>,
+}
+
+impl AuditLog {
+    pub fn config(&self) -> Option<AuditLogConfig> {
+        self.state.lock().unwrap().config
+    }
+
+    /// Enable or disable recording; shrinking the capacity drops the oldest traces
+    pub fn configure(&self, config: Option<AuditLogConfig>) {
+        let mut state = self.state.lock().unwrap();
+        state.config = config;
+        let capacity = config.map_or(0, |config| config.capacity);
+        while state.traces.len() > capacity {
+            state.traces.pop_front();
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config().is_some()
+    }
+
+    /// Store a trace under a new result ID, which is returned
+    pub fn record(&self, mut trace: ExplanationTrace) -> ResultId {
+        let mut state = self.state.lock().unwrap();
+        state.next_id += 1;
+        trace.result_id = state.next_id;
+        let capacity = state.config.map_or(0, |config| config.capacity);
+        if capacity > 0 {
+            if state.traces.len() == capacity {
+                state.traces.pop_front();
+            }
+            state.traces.push_back(trace);
+        }
+        state.next_id
+    }
+
+    /// Trace of a result, if it is still retained
+    pub fn get(&self, result_id: ResultId) -> Option<ExplanationTrace> {
+        let state = self.state.lock().unwrap();
+        // IDs are assigned in increasing order, so the traces stay sorted
+        let index = state.traces.binary_search_by_key(&result_id, |trace| trace.result_id).ok()?;
+        Some(state.traces[index].clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trace(rule_id: RuleId) -> ExplanationTrace {
+        ExplanationTrace {
+            result_id: 0,
+            rule_id,
+            rule_name: format!("rule {rule_id}"),
+            fact_id: 1,
+            external_id: None,
+            conditions: vec![],
+            calculators: vec![],
+            actions: vec![],
+            recorded_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_oldest_traces_are_dropped() {
+        let log = AuditLog::default();
+        log.configure(Some(AuditLogConfig { capacity: 2 }));
+
+        let ids: Vec<ResultId> = (1..=3).map(|rule_id| log.record(trace(rule_id))).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+        assert!(log.get(1).is_none());
+        assert_eq!(log.get(3).unwrap().rule_id, 3);
+
+        log.configure(Some(AuditLogConfig { capacity: 1 }));
+        assert!(log.get(2).is_none());
+        log.configure(None);
+        assert!(!log.is_enabled());
+        assert!(log.get(3).is_none());
+    }
+}
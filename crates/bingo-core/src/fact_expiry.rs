@@ -0,0 +1,300 @@
+//! Per-fact time to live
+//!
+//! Facts describing events usually matter only for a while: a rule over recent logins
+//! should not keep matching one from last week. With a [`FactExpiryConfig`] set, a fact
+//! expires at the instant in its expiry field, or a time to live after its timestamp,
+//! and the engine retracts it with truth maintenance so the facts derived from it go
+//! too.
+//!
+//! Expired facts are hidden from lookups as soon as they expire. They are retracted by
+//! the next insert, by an explicit `expire_facts` call, or by a [`FactExpirySweeper`]
+//! calling it periodically. Facts that have already expired when they arrive are not
+//! inserted at all.
+
+use crate::engine::BingoEngine;
+use crate::error::{BingoError, BingoResult};
+use crate::types::{Fact, FactId, FactValue};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::BTreeSet;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Weak};
+use std::thread::JoinHandle;
+use tracing::{debug, warn};
+
+/// Where the engine reads each fact's expiry from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FactExpiryConfig {
+    /// Field holding the instant the fact expires, as a date or an RFC 3339 string
+    pub expires_at_field: String,
+    /// Field holding the fact's time to live from its timestamp, as a duration or a
+    /// number of seconds; ignored when the fact has an expiry instant
+    pub ttl_field: String,
+    /// Time to live of facts carrying neither field; they never expire when `None`
+    pub default_ttl: Option<Duration>,
+}
+
+impl Default for FactExpiryConfig {
+    fn default() -> Self {
+        Self {
+            expires_at_field: "expires_at".to_string(),
+            ttl_field: "ttl".to_string(),
+            default_ttl: None,
+        }
+    }
+}
+
+impl FactExpiryConfig {
+    /// Expire facts carrying neither field `ttl` after their timestamp
+    pub fn with_default_ttl(ttl: Duration) -> Self {
+        Self { default_ttl: Some(ttl), ..Self::default() }
+    }
+
+    /// Reject a non-positive default time to live
+    pub fn validate(&self) -> BingoResult<()> {
+        if let Some(ttl) = self.default_ttl {
+            if ttl <= Duration::zero() {
+                return Err(BingoError::configuration(
+                    "default_ttl",
+                    "a positive duration",
+                    &ttl.to_string(),
+                    "the default fact time to live must be positive",
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// When `fact` expires, or `None` if it never does
+    ///
+    /// Expiry fields of an unusable type are ignored.
+    pub fn expires_at(&self, fact: &Fact) -> Option<DateTime<Utc>> {
+        let fields = &fact.data.fields;
+        if let Some(expires_at) = fields.get(&self.expires_at_field).and_then(instant) {
+            return Some(expires_at);
+        }
+        let ttl = fields.get(&self.ttl_field).and_then(duration).or(self.default_ttl)?;
+        fact.timestamp.checked_add_signed(ttl)
+    }
+
+    /// Whether `fact` has expired at `now`
+    pub fn is_expired(&self, fact: &Fact, now: DateTime<Utc>) -> bool {
+        self.expires_at(fact).is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
+fn instant(value: &FactValue) -> Option<DateTime<Utc>> {
+    match value {
+        FactValue::Date(date) => Some(*date),
+        FactValue::String(text) => {
+            DateTime::parse_from_rfc3339(text).ok().map(|date| date.with_timezone(&Utc))
+        }
+        _ => None,
+    }
+}
+
+fn duration(value: &FactValue) -> Option<Duration> {
+    match value {
+        FactValue::Duration(duration) => Some(*duration),
+        FactValue::Integer(seconds) => Duration::try_seconds(*seconds),
+        FactValue::Float(seconds) if seconds.is_finite() => {
+            Duration::try_milliseconds((seconds * 1000.0) as i64)
+        }
+        _ => None,
+    }
+}
+
+/// Counters of facts expired by the engine
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FactExpiryStats {
+    /// Facts waiting to expire
+    pub pending: usize,
+    /// Facts retracted on expiry
+    pub expired: u64,
+    /// Derived facts retracted with the facts supporting them
+    pub derived_retracted: u64,
+    /// Facts dropped because they had already expired on arrival
+    pub expired_on_arrival: u64,
+}
+
+/// Facts ordered by the instant they expire
+#[derive(Debug, Default)]
+pub(crate) struct ExpirySchedule {
+    queue: BTreeSet<(DateTime<Utc>, FactId)>,
+    stats: FactExpiryStats,
+}
+
+impl ExpirySchedule {
+    /// Schedule the facts of `facts` that expire
+    pub(crate) fn schedule<'a>(
+        &mut self,
+        config: &FactExpiryConfig,
+        facts: impl IntoIterator<Item = &'a Fact>,
+    ) {
+        for fact in facts {
+            if let Some(expires_at) = config.expires_at(fact) {
+                self.queue.insert((expires_at, fact.id));
+            }
+        }
+    }
+
+    /// Remove and return the facts due to expire at `now`
+    ///
+    /// Entries are not updated when a fact is removed or replaced, so the caller checks
+    /// each fact is still there and still expired.
+    pub(crate) fn take_due(&mut self, now: DateTime<Utc>) -> Vec<FactId> {
+        let mut due = Vec::new();
+        while let Some(&(expires_at, fact_id)) = self.queue.first() {
+            if expires_at > now {
+                break;
+            }
+            self.queue.pop_first();
+            due.push(fact_id);
+        }
+        due
+    }
+
+    /// Whether any fact is due to expire at `now`
+    pub(crate) fn has_due(&self, now: DateTime<Utc>) -> bool {
+        self.queue.first().is_some_and(|&(expires_at, _)| expires_at <= now)
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.queue.clear();
+    }
+
+    pub(crate) fn stats_mut(&mut self) -> &mut FactExpiryStats {
+        &mut self.stats
+    }
+
+    pub(crate) fn stats(&self) -> FactExpiryStats {
+        FactExpiryStats { pending: self.queue.len(), ..self.stats.clone() }
+    }
+}
+
+/// Background thread retracting an engine's expired facts at a fixed interval
+///
+/// The thread holds the engine weakly and exits once the engine is dropped, or when
+/// the sweeper is stopped or dropped.
+#[derive(Debug)]
+pub struct FactExpirySweeper {
+    stop: Option<Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl FactExpirySweeper {
+    /// Start sweeping `engine` every `interval`
+    pub fn start(engine: &Arc<BingoEngine>, interval: std::time::Duration) -> Self {
+        let (stop, stopped) = mpsc::channel::<()>();
+        let engine = Arc::downgrade(engine);
+        let thread = std::thread::Builder::new()
+            .name("bingo-fact-expiry".to_string())
+            .spawn(move || {
+                while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                    if !Self::sweep(&engine) {
+                        break;
+                    }
+                }
+                debug!("Fact expiry sweeper stopped");
+            })
+            .expect("failed to spawn fact expiry sweeper");
+        Self { stop: Some(stop), thread: Some(thread) }
+    }
+
+    /// Retract the expired facts; false once the engine is gone
+    fn sweep(engine: &Weak<BingoEngine>) -> bool {
+        let Some(engine) = engine.upgrade() else {
+            return false;
+        };
+        if let Err(error) = engine.expire_facts() {
+            warn!(%error, "Fact expiry sweep failed");
+        }
+        true
+    }
+
+    /// Stop the thread and wait for a sweep in progress to finish
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        self.stop.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for FactExpirySweeper {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::FactData;
+
+    fn fact(id: FactId, fields: Vec<(&str, FactValue)>) -> Fact {
+        let fields = fields.into_iter().map(|(name, value)| (name.to_string(), value)).collect();
+        Fact::new(id, FactData { fields })
+    }
+
+    #[test]
+    fn test_expiry_prefers_the_instant_over_the_ttl() {
+        let config = FactExpiryConfig::with_default_ttl(Duration::minutes(5));
+        let at = Utc::now() + Duration::hours(1);
+
+        let both = fact(
+            1,
+            vec![("expires_at", FactValue::Date(at)), ("ttl", FactValue::Integer(10))],
+        );
+        assert_eq!(config.expires_at(&both), Some(at));
+
+        let text = fact(2, vec![("expires_at", FactValue::String(at.to_rfc3339()))]);
+        assert_eq!(config.expires_at(&text), Some(at));
+
+        let ttl = fact(3, vec![("ttl", FactValue::Duration(Duration::seconds(30)))]);
+        assert_eq!(
+            config.expires_at(&ttl),
+            Some(ttl.timestamp + Duration::seconds(30))
+        );
+
+        let unusable = fact(4, vec![("expires_at", FactValue::Boolean(true))]);
+        assert_eq!(
+            config.expires_at(&unusable),
+            Some(unusable.timestamp + Duration::minutes(5))
+        );
+
+        let never = FactExpiryConfig::default();
+        assert_eq!(never.expires_at(&fact(5, Vec::new())), None);
+        assert!(FactExpiryConfig::with_default_ttl(Duration::zero()).validate().is_err());
+    }
+
+    #[test]
+    fn test_schedule_hands_out_due_facts_in_expiry_order() {
+        let config = FactExpiryConfig::default();
+        let now = Utc::now();
+        let facts: Vec<Fact> = [(1, 20), (2, -5), (3, -10), (4, 0)]
+            .into_iter()
+            .map(|(id, offset)| {
+                fact(
+                    id,
+                    vec![(
+                        "expires_at",
+                        FactValue::Date(now + Duration::seconds(offset)),
+                    )],
+                )
+            })
+            .chain([fact(5, Vec::new())])
+            .collect();
+
+        let mut schedule = ExpirySchedule::default();
+        schedule.schedule(&config, &facts);
+        assert_eq!(schedule.stats().pending, 4);
+        assert!(schedule.has_due(now));
+        assert_eq!(schedule.take_due(now), vec![3, 2, 4]);
+        assert!(!schedule.has_due(now));
+        assert_eq!(schedule.stats().pending, 1);
+    }
+}
@@ -0,0 +1,297 @@
+//! Bounded version history of modified facts
+//!
+//! Rule actions and bulk updates used to overwrite a stored fact's fields in place, so
+//! nothing could say what a fact looked like before a rule incremented it. Field spans
+//! in the arena are immutable, which makes versions copy-on-write: a modification
+//! writes the fields into a fresh span, and with history enabled the previous span is
+//! kept as a version instead of being released.
+//!
+//! Each fact keeps at most [`FactHistoryConfig::max_versions`] earlier versions; older
+//! ones are released. Facts never modified cost nothing, and deleting or replacing a
+//! fact drops its history.
+
+use crate::field_arena::{FieldArena, FieldSpan};
+use crate::memory::hash_map_table_bytes;
+use crate::types::{Fact, FactData, FactId, FactValue, RuleId};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+
+/// How many earlier versions of each fact the fact store keeps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FactHistoryConfig {
+    /// Earlier versions kept per fact before the oldest is released
+    pub max_versions: usize,
+}
+
+impl Default for FactHistoryConfig {
+    fn default() -> Self {
+        Self { max_versions: 16 }
+    }
+}
+
+/// A field whose value differs between consecutive versions of a fact
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldChange {
+    pub field: String,
+    /// Value in the previous version, `None` when the field was unset
+    pub before: Option<FactValue>,
+    /// Value in this version, `None` when the field was removed
+    pub after: Option<FactValue>,
+}
+
+/// One version of a stored fact
+#[derive(Debug, Clone, Serialize)]
+pub struct FactVersion {
+    /// 1 for the fact as inserted, counting up with each modification
+    pub version: u64,
+    /// The fact with this version's fields
+    pub fact: Fact,
+    /// Rule whose action wrote this version, `None` for inserts and API updates
+    pub changed_by: Option<RuleId>,
+    /// When this version replaced the previous one, `None` for the fact as inserted
+    pub written_at: Option<DateTime<Utc>>,
+    /// Fields changed from the previous version, in field order; empty for the oldest
+    /// version kept
+    pub changes: Vec<FieldChange>,
+}
+
+/// Metadata of one version kept in history
+#[derive(Debug, Clone, Copy)]
+struct VersionInfo {
+    version: u64,
+    changed_by: Option<RuleId>,
+    written_at: Option<DateTime<Utc>>,
+}
+
+/// Versions of one modified fact: the earlier ones by span, and the current one's
+/// metadata, whose fields live in the fact store
+#[derive(Debug)]
+pub(crate) struct VersionChain {
+    previous: VecDeque<(VersionInfo, FieldSpan)>,
+    current: VersionInfo,
+}
+
+/// Earlier versions of modified facts, with spans in the fact store's arena
+#[derive(Debug, Default)]
+pub(crate) struct FactHistory {
+    config: Option<FactHistoryConfig>,
+    chains: HashMap<FactId, VersionChain>,
+}
+
+impl FactHistory {
+    pub fn config(&self) -> Option<FactHistoryConfig> {
+        self.config
+    }
+
+    /// Enable, resize or disable history, releasing versions no longer kept
+    pub fn configure(&mut self, config: Option<FactHistoryConfig>, arena: &mut FieldArena) {
+        self.config = config;
+        let max_versions = config.map_or(0, |config| config.max_versions);
+        for chain in self.chains.values_mut() {
+            while chain.previous.len() > max_versions {
+                let (_, span) = chain.previous.pop_front().expect("chain is not empty");
+                arena.release(span);
+            }
+        }
+        if config.is_none() {
+            self.chains.clear();
+        }
+    }
+
+    /// Keep `previous`, the span a modification of `fact_id` just replaced, as the
+    /// fact's latest earlier version, or release it when history is disabled
+    pub fn record(
+        &mut self,
+        fact_id: FactId,
+        previous: FieldSpan,
+        changed_by: Option<RuleId>,
+        arena: &mut FieldArena,
+    ) {
+        let Some(config) = self.config.filter(|config| config.max_versions > 0) else {
+            arena.release(previous);
+            return;
+        };
+        let first = VersionInfo { version: 1, changed_by: None, written_at: None };
+        let chain = self
+            .chains
+            .entry(fact_id)
+            .or_insert_with(|| VersionChain { previous: VecDeque::new(), current: first });
+        let replaced = chain.current;
+        chain.previous.push_back((replaced, previous));
+        chain.current =
+            VersionInfo { version: replaced.version + 1, changed_by, written_at: Some(Utc::now()) };
+        if chain.previous.len() > config.max_versions {
+            let (_, span) = chain.previous.pop_front().expect("chain is not empty");
+            arena.release(span);
+        }
+    }
+
+    /// Release the history of a deleted or replaced fact
+    pub fn forget(&mut self, fact_id: FactId, arena: &mut FieldArena) {
+        if let Some(chain) = self.chains.remove(&fact_id) {
+            Self::release(chain, arena);
+        }
+    }
+
+    /// Number of the current version of `fact_id`, 1 for a fact never modified
+    pub fn version(&self, fact_id: FactId) -> u64 {
+        self.chains.get(&fact_id).map_or(1, |chain| chain.current.version)
+    }
+
+    /// Take the history of a deleted or replaced fact out without releasing it, so
+    /// [`rewind`](Self::rewind) can put it back
+    pub fn detach(&mut self, fact_id: FactId) -> Option<VersionChain> {
+        self.chains.remove(&fact_id)
+    }
+
+    /// Put `fact_id`'s history back to its `version`, reattaching `detached` first
+    /// when the history was detached
+    ///
+    /// Versions written after `version` are released; the fields of `version` itself
+    /// are written back to the fact store by the caller.
+    pub fn rewind(
+        &mut self,
+        fact_id: FactId,
+        detached: Option<VersionChain>,
+        version: u64,
+        arena: &mut FieldArena,
+    ) {
+        if let Some(chain) = detached
+            && let Some(replaced) = self.chains.insert(fact_id, chain)
+        {
+            Self::release(replaced, arena);
+        }
+        let Some(chain) = self.chains.get_mut(&fact_id) else {
+            return;
+        };
+        while chain.current.version > version {
+            let Some((info, span)) = chain.previous.pop_back() else {
+                break;
+            };
+            arena.release(span);
+            chain.current = info;
+        }
+        if chain.current.version == 1 && chain.previous.is_empty() {
+            self.chains.remove(&fact_id);
+        }
+    }
+
+    /// Release the spans of a detached history
+    pub fn release(chain: VersionChain, arena: &mut FieldArena) {
+        for (_, span) in chain.previous {
+            arena.release(span);
+        }
+    }
+
+    /// Drop every history, for an arena that was just reset
+    pub fn clear(&mut self) {
+        self.chains.clear();
+    }
+
+    /// Versions of `current`, oldest first and ending with `current` itself
+    pub fn versions(&self, current: Fact, arena: &FieldArena) -> Vec<FactVersion> {
+        let Some(chain) = self.chains.get(&current.id) else {
+            return vec![version_of(
+                VersionInfo { version: 1, changed_by: None, written_at: None },
+                current,
+                None,
+            )];
+        };
+        let mut versions: Vec<FactVersion> = Vec::with_capacity(chain.previous.len() + 1);
+        for (info, span) in &chain.previous {
+            let fact =
+                Fact { data: FactData { fields: arena.materialize(*span) }, ..current.clone() };
+            let version = version_of(*info, fact, versions.last().map(|last| &last.fact));
+            versions.push(version);
+        }
+        let version = version_of(
+            chain.current,
+            current,
+            versions.last().map(|last| &last.fact),
+        );
+        versions.push(version);
+        versions
+    }
+
+    /// Approximate heap bytes held outside the arena
+    pub fn heap_bytes(&self) -> usize {
+        hash_map_table_bytes(&self.chains)
+            + self
+                .chains
+                .values()
+                .map(|chain| {
+                    chain.previous.capacity() * std::mem::size_of::<(VersionInfo, FieldSpan)>()
+                })
+                .sum::<usize>()
+    }
+}
+
+fn version_of(info: VersionInfo, fact: Fact, previous: Option<&Fact>) -> FactVersion {
+    let changes = previous.map_or_else(Vec::new, |previous| field_changes(previous, &fact));
+    FactVersion {
+        version: info.version,
+        fact,
+        changed_by: info.changed_by,
+        written_at: info.written_at,
+        changes,
+    }
+}
+
+/// Fields whose values differ between `before` and `after`, in field order
+fn field_changes(before: &Fact, after: &Fact) -> Vec<FieldChange> {
+    let fields: BTreeSet<&String> =
+        before.data.fields.keys().chain(after.data.fields.keys()).collect();
+    fields
+        .into_iter()
+        .filter_map(|field| {
+            let old = before.data.fields.get(field);
+            let new = after.data.fields.get(field);
+            (old != new).then(|| FieldChange {
+                field: field.clone(),
+                before: old.cloned(),
+                after: new.cloned(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fact(amount: i64) -> Fact {
+        let fields = HashMap::from([("amount".to_string(), FactValue::Integer(amount))]);
+        Fact::new(1, FactData { fields })
+    }
+
+    #[test]
+    fn test_versions_are_bounded_and_diffed() {
+        let mut arena = FieldArena::new();
+        let mut history = FactHistory::default();
+        history.configure(Some(FactHistoryConfig { max_versions: 2 }), &mut arena);
+
+        for amount in [100, 200, 300] {
+            let span = arena.allocate(&fact(amount).data.fields);
+            history.record(1, span, Some(7), &mut arena);
+        }
+        let versions = history.versions(fact(400), &arena);
+        let numbers: Vec<u64> = versions.iter().map(|version| version.version).collect();
+        assert_eq!(numbers, vec![2, 3, 4]);
+        assert!(versions[0].changes.is_empty());
+        assert_eq!(
+            versions[2].changes,
+            vec![FieldChange {
+                field: "amount".to_string(),
+                before: Some(FactValue::Integer(300)),
+                after: Some(FactValue::Integer(400)),
+            }]
+        );
+        assert_eq!(versions[2].changed_by, Some(7));
+        assert_eq!(arena.stats().live_entries, 2);
+
+        history.forget(1, &mut arena);
+        assert_eq!(history.versions(fact(400), &arena).len(), 1);
+        assert_eq!(arena.stats().live_entries, 0);
+    }
+}
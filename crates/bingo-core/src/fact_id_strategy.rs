@@ -0,0 +1,220 @@
+//! Fact ID assignment strategies
+//!
+//! The fact store decides which ID every inserted fact is stored under. By default it
+//! keeps client IDs up to [`MAX_USER_FACT_ID`] and assigns sequential IDs above it to
+//! everything else, which guards the dense fact vector against hashed IDs and keeps
+//! generated IDs clear of client ones, but silently renumbers facts of clients that use
+//! hashed IDs. [`FactIdStrategy`] makes that choice explicit:
+//!
+//! - [`FactIdStrategy::Bounded`]: the default described above
+//! - [`FactIdStrategy::Sequential`]: ignore client IDs and number facts in arrival order
+//! - [`FactIdStrategy::Snowflake`]: time-ordered IDs that are unique across workers
+//! - [`FactIdStrategy::ClientSupplied`]: keep every client ID, rejecting ID 0 and, if
+//!   asked to, IDs that are already stored
+//!
+//! Every decision is counted in [`FactIdStats`], so renumbered, replaced and rejected
+//! facts can be monitored instead of discovered.
+//!
+//! [`MAX_USER_FACT_ID`]: crate::constants::fact_ids::MAX_USER_FACT_ID
+
+use crate::error::{BingoError, BingoResult};
+use crate::types::FactId;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Milliseconds since the Unix epoch at which snowflake timestamps start (2024-01-01 UTC)
+pub const SNOWFLAKE_EPOCH_MS: u64 = 1_704_067_200_000;
+
+/// Largest worker ID a snowflake can carry (10 bits)
+pub const MAX_SNOWFLAKE_WORKER_ID: u16 = 1023;
+
+const SNOWFLAKE_WORKER_BITS: u32 = 10;
+const SNOWFLAKE_SEQUENCE_BITS: u32 = 12;
+const MAX_SNOWFLAKE_SEQUENCE: u64 = (1 << SNOWFLAKE_SEQUENCE_BITS) - 1;
+
+/// How the fact store picks the ID a fact is stored under
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FactIdStrategy {
+    /// Keep client IDs up to `MAX_USER_FACT_ID`; assign sequential IDs above it to 0 and
+    /// larger IDs
+    #[default]
+    Bounded,
+    /// Ignore client IDs and assign sequential IDs in arrival order
+    Sequential,
+    /// Assign 64-bit IDs made of a millisecond timestamp, the worker ID and a sequence
+    ///
+    /// IDs from different workers never collide, and IDs sort by creation time.
+    Snowflake { worker_id: u16 },
+    /// Keep every client ID; ID 0 is rejected and `on_collision` decides about stored IDs
+    ClientSupplied { on_collision: IdCollisionPolicy },
+}
+
+impl FactIdStrategy {
+    /// Check the strategy's parameters
+    pub fn validate(&self) -> BingoResult<()> {
+        match self {
+            Self::Snowflake { worker_id } if *worker_id > MAX_SNOWFLAKE_WORKER_ID => {
+                Err(BingoError::configuration(
+                    "fact_id_strategy.worker_id",
+                    &format!("at most {MAX_SNOWFLAKE_WORKER_ID}"),
+                    &worker_id.to_string(),
+                    format!(
+                        "Snowflake worker ID {worker_id} exceeds the maximum of {MAX_SNOWFLAKE_WORKER_ID}"
+                    ),
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Whether the client's ID is kept rather than replaced by a generated one
+    pub fn keeps_client_id(&self, requested: FactId) -> bool {
+        match self {
+            Self::Bounded => {
+                requested != 0 && requested <= crate::constants::fact_ids::MAX_USER_FACT_ID
+            }
+            Self::Sequential | Self::Snowflake { .. } => false,
+            Self::ClientSupplied { .. } => requested != 0,
+        }
+    }
+}
+
+/// What happens when a client-supplied ID is already stored
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IdCollisionPolicy {
+    /// The incoming fact replaces the stored one
+    #[default]
+    Replace,
+    /// The insert fails and the stored fact is kept
+    Reject,
+}
+
+/// Counts of the ID decisions the fact store has made
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FactIdStats {
+    /// Facts stored under the ID their client gave them
+    pub preserved: u64,
+    /// Facts without an ID (or under a strategy that ignores IDs) given a generated one
+    pub generated: u64,
+    /// Facts whose client ID was discarded for a generated one because it was too large
+    pub reassigned: u64,
+    /// Inserts that replaced a stored fact with the same ID
+    pub replaced: u64,
+    /// Facts refused because of their ID
+    pub rejected: u64,
+}
+
+/// Lock-free counters behind [`FactIdStats`]
+#[derive(Debug, Default)]
+pub(crate) struct FactIdCounters {
+    pub preserved: AtomicU64,
+    pub generated: AtomicU64,
+    pub reassigned: AtomicU64,
+    pub replaced: AtomicU64,
+    pub rejected: AtomicU64,
+}
+
+impl FactIdCounters {
+    pub fn record(counter: &AtomicU64) {
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> FactIdStats {
+        FactIdStats {
+            preserved: self.preserved.load(Ordering::Relaxed),
+            generated: self.generated.load(Ordering::Relaxed),
+            reassigned: self.reassigned.load(Ordering::Relaxed),
+            replaced: self.replaced.load(Ordering::Relaxed),
+            rejected: self.rejected.load(Ordering::Relaxed),
+        }
+    }
+
+    pub fn reset(&self) {
+        for counter in [
+            &self.preserved,
+            &self.generated,
+            &self.reassigned,
+            &self.replaced,
+            &self.rejected,
+        ] {
+            counter.store(0, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Generator state for [`FactIdStrategy::Snowflake`]
+#[derive(Debug, Default)]
+pub(crate) struct SnowflakeGenerator {
+    last_ms: u64,
+    sequence: u64,
+}
+
+impl SnowflakeGenerator {
+    /// Next ID for `worker_id` at the current time
+    pub fn next_id(&mut self, worker_id: u16) -> FactId {
+        let now_ms = (chrono::Utc::now().timestamp_millis().max(0) as u64)
+            .saturating_sub(SNOWFLAKE_EPOCH_MS);
+        self.next_id_at(now_ms, worker_id)
+    }
+
+    /// Next ID for `worker_id` at `now_ms` milliseconds after the snowflake epoch
+    ///
+    /// A clock that goes backwards keeps the last timestamp, and a millisecond whose
+    /// sequence is used up borrows the next one, so IDs never repeat or decrease.
+    pub fn next_id_at(&mut self, now_ms: u64, worker_id: u16) -> FactId {
+        if now_ms > self.last_ms {
+            self.last_ms = now_ms;
+            self.sequence = 0;
+        } else if self.sequence == MAX_SNOWFLAKE_SEQUENCE {
+            self.last_ms += 1;
+            self.sequence = 0;
+        } else {
+            self.sequence += 1;
+        }
+
+        (self.last_ms << (SNOWFLAKE_WORKER_BITS + SNOWFLAKE_SEQUENCE_BITS))
+            | (u64::from(worker_id) << SNOWFLAKE_SEQUENCE_BITS)
+            | self.sequence
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snowflake_ids_increase_and_carry_the_worker() {
+        let mut generator = SnowflakeGenerator::default();
+        let first = generator.next_id_at(1_000, 7);
+        let second = generator.next_id_at(1_000, 7);
+        let earlier_clock = generator.next_id_at(900, 7);
+
+        assert!(first < second && second < earlier_clock);
+        assert_eq!(first >> 22, 1_000);
+        assert_eq!((first >> 12) & 0x3FF, 7);
+        assert_eq!(second & 0xFFF, 1);
+    }
+
+    #[test]
+    fn test_snowflake_sequence_overflow_borrows_the_next_millisecond() {
+        let mut generator = SnowflakeGenerator::default();
+        let ids: Vec<FactId> = (0..=MAX_SNOWFLAKE_SEQUENCE + 1)
+            .map(|_| generator.next_id_at(5, MAX_SNOWFLAKE_WORKER_ID))
+            .collect();
+
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(ids.last().unwrap() >> 22, 6);
+    }
+
+    #[test]
+    fn test_strategy_validation_and_client_ids() {
+        assert!(FactIdStrategy::Snowflake { worker_id: 1024 }.validate().is_err());
+        assert!(FactIdStrategy::Snowflake { worker_id: 1023 }.validate().is_ok());
+
+        let client = FactIdStrategy::ClientSupplied { on_collision: IdCollisionPolicy::Reject };
+        assert!(client.keeps_client_id(u64::MAX));
+        assert!(!client.keeps_client_id(0));
+        assert!(!FactIdStrategy::Bounded.keeps_client_id(5_000_000));
+        assert!(!FactIdStrategy::Sequential.keeps_client_id(5));
+    }
+}
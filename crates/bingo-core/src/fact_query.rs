@@ -0,0 +1,197 @@
+//! Fact queries combining comparisons with AND, OR and NOT, returned a page at a time
+//!
+//! `find_by_criteria` only ANDs field equalities and returns every match at once, so
+//! browsing working memory meant copying all of it. A [`FactQuery`] compares fields with
+//! any rule [`Operator`] and groups the comparisons freely; a [`FactPageRequest`]
+//! bounds how many matches are materialised. Matches come back in fact ID order, which
+//! stays stable while facts are added and removed, so a [`FactPage::next_cursor`] can
+//! resume a listing where the previous page ended.
+//!
+//! ```rust
+//! use bingo_core::fact_query::{FactPageRequest, FactQuery};
+//! use bingo_core::types::{FactValue, Operator};
+//!
+//! // status == "open" AND (amount > 500 OR NOT priority == "low")
+//! let query = FactQuery::compare("status", Operator::Equal, FactValue::String("open".into()))
+//!     .and(
+//!         FactQuery::compare("amount", Operator::GreaterThan, FactValue::Integer(500)).or(
+//!             FactQuery::compare("priority", Operator::Equal, FactValue::String("low".into()))
+//!                 .negate(),
+//!         ),
+//!     );
+//! let first_page = FactPageRequest::first(50);
+//! ```
+
+use crate::alpha_memory::FactPattern;
+use crate::types::{Fact, FactId, FactValue, Operator};
+use serde::{Deserialize, Serialize};
+
+/// Filter over stored facts
+///
+/// A comparison never matches a fact missing its field, so `NotEqual` needs the field
+/// while `Not` of `Equal` also matches facts without it. `In` and `NotIn` take literal
+/// arrays, and strings compare with binary collation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum FactQuery {
+    /// Field compared with a value as a rule condition would
+    Compare(FactPattern),
+    /// Every query matches; no queries match every fact
+    And(Vec<FactQuery>),
+    /// Some query matches; no queries match no fact
+    Or(Vec<FactQuery>),
+    /// The query does not match
+    Not(Box<FactQuery>),
+}
+
+impl FactQuery {
+    /// Query matching every fact
+    pub fn all() -> Self {
+        FactQuery::And(Vec::new())
+    }
+
+    /// Query comparing `field` with `value`
+    pub fn compare(field: impl Into<String>, operator: Operator, value: FactValue) -> Self {
+        FactQuery::Compare(FactPattern { field: field.into(), operator, value })
+    }
+
+    /// Query matching facts whose fields equal every `(field, value)` pair, as
+    /// `find_by_criteria` does
+    pub fn all_equal(criteria: &[(String, FactValue)]) -> Self {
+        FactQuery::And(
+            criteria
+                .iter()
+                .map(|(field, value)| Self::compare(field.clone(), Operator::Equal, value.clone()))
+                .collect(),
+        )
+    }
+
+    /// Query matching facts that match both queries
+    pub fn and(self, other: FactQuery) -> Self {
+        match self {
+            FactQuery::And(mut queries) => {
+                queries.push(other);
+                FactQuery::And(queries)
+            }
+            query => FactQuery::And(vec![query, other]),
+        }
+    }
+
+    /// Query matching facts that match either query
+    pub fn or(self, other: FactQuery) -> Self {
+        match self {
+            FactQuery::Or(mut queries) => {
+                queries.push(other);
+                FactQuery::Or(queries)
+            }
+            query => FactQuery::Or(vec![query, other]),
+        }
+    }
+
+    /// Query matching facts this query does not match
+    pub fn negate(self) -> Self {
+        FactQuery::Not(Box::new(self))
+    }
+
+    /// Whether `fact` matches the query
+    pub fn matches(&self, fact: &Fact) -> bool {
+        self.matches_fields(&|field| fact.data.fields.get(field))
+    }
+
+    /// Whether a fact whose fields `field` looks up matches the query
+    pub fn matches_fields<'a>(&self, field: &impl Fn(&str) -> Option<&'a FactValue>) -> bool {
+        match self {
+            FactQuery::Compare(pattern) => {
+                field(&pattern.field).is_some_and(|value| pattern.matches_value(value))
+            }
+            FactQuery::And(queries) => queries.iter().all(|query| query.matches_fields(field)),
+            FactQuery::Or(queries) => queries.iter().any(|query| query.matches_fields(field)),
+            FactQuery::Not(query) => !query.matches_fields(field),
+        }
+    }
+}
+
+/// Which matches of a query to return
+///
+/// Matches are in fact ID order. `after` starts the page past a cursor from a previous
+/// page, `offset` then skips that many matches, and at most `limit` are returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FactPageRequest {
+    /// Return only facts with IDs above this one
+    pub after: Option<FactId>,
+    /// Matches to skip before the page starts
+    pub offset: usize,
+    /// Most matches to return
+    pub limit: usize,
+}
+
+impl FactPageRequest {
+    /// The first `limit` matches
+    pub fn first(limit: usize) -> Self {
+        Self { after: None, offset: 0, limit }
+    }
+
+    /// `limit` matches after skipping `offset`
+    pub fn offset(offset: usize, limit: usize) -> Self {
+        Self { after: None, offset, limit }
+    }
+
+    /// `limit` matches with IDs above `cursor`
+    pub fn after(cursor: FactId, limit: usize) -> Self {
+        Self { after: Some(cursor), offset: 0, limit }
+    }
+}
+
+/// One page of query matches
+#[derive(Debug, Clone, Default)]
+pub struct FactPage {
+    /// Matching facts in fact ID order
+    pub facts: Vec<Fact>,
+    /// Cursor for the next page, `None` when no matches follow this page
+    pub next_cursor: Option<FactId>,
+}
+
+impl FactPage {
+    /// Request for the page after this one, `None` on the last page
+    pub fn next(&self, limit: usize) -> Option<FactPageRequest> {
+        self.next_cursor.map(|cursor| FactPageRequest::after(cursor, limit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_groups_and_missing_fields() {
+        let fields = HashMap::from([
+            ("status".to_string(), FactValue::String("open".to_string())),
+            ("amount".to_string(), FactValue::Integer(700)),
+        ]);
+        let fact = Fact::new(1, crate::types::FactData { fields });
+        let open = FactQuery::compare("status", Operator::Equal, FactValue::String("open".into()));
+        let large = FactQuery::compare("amount", Operator::GreaterThan, FactValue::Integer(500));
+        let low = FactQuery::compare(
+            "priority",
+            Operator::Equal,
+            FactValue::String("low".to_string()),
+        );
+
+        assert!(open.clone().and(large.clone()).matches(&fact));
+        assert!(!open.clone().and(low.clone()).matches(&fact));
+        assert!(low.clone().or(large).matches(&fact));
+        // Not matches a missing field where NotEqual does not
+        assert!(low.negate().matches(&fact));
+        let not_low = FactQuery::compare(
+            "priority",
+            Operator::NotEqual,
+            FactValue::String("low".into()),
+        );
+        assert!(!not_low.matches(&fact));
+        assert!(FactQuery::all().matches(&fact));
+        assert!(!FactQuery::Or(Vec::new()).matches(&fact));
+
+        let json = serde_json::to_string(&open).unwrap();
+        assert_eq!(serde_json::from_str::<FactQuery>(&json).unwrap(), open);
+    }
+}
@@ -0,0 +1,276 @@
+//! Typed fact schemas checked when facts are inserted
+//!
+//! A fact whose `hours` arrive as the text "40" silently misses every rule comparing
+//! `hours > 38`. Registering a [`FactSchema`] for a fact type, the type `type`
+//! conditions see for a fact, makes the fact store refuse such facts with a
+//! [`FactValidationReport`] listing every problem found: required fields that are
+//! missing or null, values their field's [`FieldType`] does not accept (an `Enum` type
+//! constrains a field to a set of values), and, for closed schemas, fields the schema
+//! does not declare.
+//!
+//! Facts without a type, and facts of a type without a schema, are accepted unchecked.
+
+use crate::action_validation::{describe, rejects};
+use crate::types::{FACT_TYPE_FIELD, Fact, FactId, FactValue, FieldType};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Type of a schema field, and whether facts must carry it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaField {
+    pub field_type: FieldType,
+    pub required: bool,
+}
+
+/// Fields facts of one type carry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FactSchema {
+    pub fact_type: String,
+    pub fields: BTreeMap<String, SchemaField>,
+    /// Whether facts may carry fields the schema does not declare
+    pub allow_unknown_fields: bool,
+}
+
+impl FactSchema {
+    /// Schema for facts of type `fact_type`, accepting any fields
+    pub fn new(fact_type: impl Into<String>) -> Self {
+        Self { fact_type: fact_type.into(), fields: BTreeMap::new(), allow_unknown_fields: true }
+    }
+
+    /// Declare a field facts must carry, with a non-null value of `field_type`
+    pub fn required(mut self, field: impl Into<String>, field_type: FieldType) -> Self {
+        self.fields.insert(field.into(), SchemaField { field_type, required: true });
+        self
+    }
+
+    /// Declare a field facts may carry, with a value of `field_type` when they do
+    pub fn optional(mut self, field: impl Into<String>, field_type: FieldType) -> Self {
+        self.fields.insert(field.into(), SchemaField { field_type, required: false });
+        self
+    }
+
+    /// Refuse facts carrying fields the schema does not declare
+    pub fn closed(mut self) -> Self {
+        self.allow_unknown_fields = false;
+        self
+    }
+
+    /// Every problem with `fact`'s fields, in field name order
+    pub fn check(&self, fact: &Fact) -> Vec<FactProblem> {
+        let fields = &fact.data.fields;
+        let mut problems = Vec::new();
+        for (field, spec) in &self.fields {
+            match fields.get(field) {
+                None | Some(FactValue::Null) if spec.required => {
+                    problems.push(FactProblem::MissingField { field: field.clone() });
+                }
+                Some(value) => {
+                    if let Some(expected) = rejects(&spec.field_type, value) {
+                        problems.push(FactProblem::TypeMismatch {
+                            field: field.clone(),
+                            expected,
+                            actual: describe(value),
+                        });
+                    }
+                }
+                None => {}
+            }
+        }
+        if !self.allow_unknown_fields {
+            let mut unknown: Vec<&String> = fields
+                .keys()
+                .filter(|field| *field != FACT_TYPE_FIELD && !self.fields.contains_key(*field))
+                .collect();
+            unknown.sort();
+            problems.extend(
+                unknown
+                    .into_iter()
+                    .map(|field| FactProblem::UnknownField { field: field.clone() }),
+            );
+        }
+        problems.sort_by(|a, b| a.field().cmp(b.field()));
+        problems
+    }
+}
+
+/// A problem with one field of a fact
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FactProblem {
+    /// A required field is absent or null
+    MissingField { field: String },
+    /// A field's value is not one its type accepts
+    TypeMismatch { field: String, expected: String, actual: String },
+    /// A closed schema does not declare the field
+    UnknownField { field: String },
+}
+
+impl FactProblem {
+    /// Name of the offending field
+    pub fn field(&self) -> &str {
+        match self {
+            Self::MissingField { field }
+            | Self::TypeMismatch { field, .. }
+            | Self::UnknownField { field } => field,
+        }
+    }
+}
+
+impl fmt::Display for FactProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingField { field } => write!(f, "field '{field}' is required"),
+            Self::TypeMismatch { field, expected, actual } => {
+                write!(f, "field '{field}' expects {expected}, got {actual}")
+            }
+            Self::UnknownField { field } => write!(f, "field '{field}' is not in the schema"),
+        }
+    }
+}
+
+/// Every problem found in a fact refused by its type's schema
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FactValidationReport {
+    /// ID the fact was submitted with
+    pub fact_id: FactId,
+    pub external_id: Option<String>,
+    pub fact_type: String,
+    /// In field name order
+    pub problems: Vec<FactProblem>,
+}
+
+impl fmt::Display for FactValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "fact {} of type '{}' has {} invalid field(s)",
+            self.fact_id,
+            self.fact_type,
+            self.problems.len()
+        )?;
+        for problem in &self.problems {
+            write!(f, "; {problem}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Schemas by fact type
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FactSchemaRegistry {
+    schemas: BTreeMap<String, FactSchema>,
+}
+
+impl FactSchemaRegistry {
+    /// Register `schema`, replacing any schema for its fact type
+    pub fn register(&mut self, schema: FactSchema) {
+        self.schemas.insert(schema.fact_type.clone(), schema);
+    }
+
+    /// Remove the schema for `fact_type`, returning it
+    pub fn remove(&mut self, fact_type: &str) -> Option<FactSchema> {
+        self.schemas.remove(fact_type)
+    }
+
+    pub fn get(&self, fact_type: &str) -> Option<&FactSchema> {
+        self.schemas.get(fact_type)
+    }
+
+    /// Registered schemas in fact type order
+    pub fn schemas(&self) -> impl Iterator<Item = &FactSchema> {
+        self.schemas.values()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.schemas.is_empty()
+    }
+
+    /// Check `fact` against its type's schema, `None` when it passes or has none
+    pub fn validate(&self, fact: &Fact) -> Option<FactValidationReport> {
+        let fact_type = fact.condition_type()?;
+        let problems = self.schemas.get(fact_type)?.check(fact);
+        (!problems.is_empty()).then(|| FactValidationReport {
+            fact_id: fact.id,
+            external_id: fact.external_id.clone(),
+            fact_type: fact_type.to_string(),
+            problems,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::FactData;
+    use std::collections::HashMap;
+
+    fn shift(fields: &[(&str, FactValue)]) -> Fact {
+        let fields: HashMap<String, FactValue> =
+            fields.iter().map(|(field, value)| (field.to_string(), value.clone())).collect();
+        Fact::new(3, FactData { fields }).with_type("shift")
+    }
+
+    fn registry() -> FactSchemaRegistry {
+        let mut registry = FactSchemaRegistry::default();
+        registry.register(
+            FactSchema::new("shift")
+                .required("hours", FieldType::Integer { min: 0, max: 24 })
+                .required(
+                    "kind",
+                    FieldType::Enum { values: vec!["day".to_string(), "night".to_string()] },
+                )
+                .optional("approved", FieldType::Boolean)
+                .closed(),
+        );
+        registry
+    }
+
+    #[test]
+    fn test_every_problem_is_reported_in_field_order() {
+        let fact = shift(&[
+            ("hours", FactValue::String("8".to_string())),
+            ("kind", FactValue::String("evening".to_string())),
+            ("approved", FactValue::Null),
+            ("badge", FactValue::Integer(7)),
+        ]);
+        let report = registry().validate(&fact).unwrap();
+        assert_eq!(report.fact_type, "shift");
+        assert_eq!(
+            report.problems,
+            vec![
+                FactProblem::UnknownField { field: "badge".to_string() },
+                FactProblem::TypeMismatch {
+                    field: "hours".to_string(),
+                    expected: "an integer from 0 to 24".to_string(),
+                    actual: "string 8".to_string(),
+                },
+                FactProblem::TypeMismatch {
+                    field: "kind".to_string(),
+                    expected: "one of day, night".to_string(),
+                    actual: "string evening".to_string(),
+                },
+            ]
+        );
+        assert!(report.to_string().starts_with("fact 3 of type 'shift' has 3 invalid field(s)"));
+
+        let missing = registry().validate(&shift(&[("hours", FactValue::Null)])).unwrap();
+        let fields: Vec<&str> = missing.problems.iter().map(FactProblem::field).collect();
+        assert_eq!(fields, vec!["hours", "kind"]);
+    }
+
+    #[test]
+    fn test_valid_and_unschematized_facts_pass() {
+        let registry = registry();
+        let valid = shift(&[
+            ("hours", FactValue::Integer(8)),
+            ("kind", FactValue::String("night".to_string())),
+        ]);
+        assert_eq!(registry.validate(&valid), None);
+
+        let untyped = Fact::new(1, FactData { fields: HashMap::new() });
+        assert_eq!(registry.validate(&untyped), None);
+        let other = valid.clone().with_type("leave");
+        assert_eq!(registry.validate(&other), None);
+    }
+}
@@ -1,5 +1,11 @@
 use crate::cache::CacheStats;
-use crate::types::{Fact, FactId, FactValue};
+use crate::fact_history::{FactHistory, FactHistoryConfig, FactVersion, VersionChain};
+use crate::fact_query::{FactPage, FactPageRequest, FactQuery};
+use crate::field_arena::{FieldArena, FieldArenaStats, FieldSpan};
+use crate::memory::{MemoryBreakdown, hash_map_table_bytes};
+use crate::memory_report::{FactGroupUsage, IndexUsage, group_usage};
+use crate::range_index::{FieldRangeIndex, RangeIndex, numeric_bounds};
+use crate::types::{Fact, FactData, FactId, FactValue, RuleId};
 use std::borrow::Cow;
 use std::collections::HashMap;
 
@@ -23,8 +29,18 @@ pub struct IndexStats {
 
 pub mod arena_store {
     use super::*;
-    use std::sync::atomic::{AtomicU64, Ordering};
-    use std::sync::{Arc, RwLock};
+    use crate::constants::fact_ids::{FIRST_GENERATED_FACT_ID, MAX_USER_FACT_ID};
+    use crate::error::{BingoError, BingoResult};
+    use crate::fact_id_strategy::{
+        FactIdCounters, FactIdStats, FactIdStrategy, IdCollisionPolicy, SnowflakeGenerator,
+    };
+    use crate::fact_schema::{FactSchema, FactSchemaRegistry};
+    use chrono::{DateTime, Utc};
+    use std::collections::{BTreeMap, HashSet};
+    use std::ops::RangeBounds;
+    use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex, MutexGuard, RwLock};
+    use tracing::debug;
 
     /// Arena-based fact store for high-performance allocation and retrieval with thread safety.
     ///
@@ -33,8 +49,13 @@ pub mod arena_store {
     /// Designed for high-throughput scenarios with minimal allocation overhead and full thread safety.
     ///
     /// # Architecture
-    /// - **Facts Storage**: Direct vector indexing where `fact.id` corresponds to the vector index (RwLock protected)
+    /// - **Facts Storage**: Direct vector indexing where `fact.id` corresponds to the vector index, with
+    ///   a map for IDs far beyond the vector such as snowflake or hashed IDs (RwLock protected)
+    /// - **ID Strategy**: A [`FactIdStrategy`] decides which ID each inserted fact is stored under
+    /// - **Field Arena**: Fact fields live in bump-allocated slabs instead of per-fact HashMaps (RwLock protected)
     /// - **Field Indexes**: Hash-based secondary indexes on commonly queried fields (RwLock protected)
+    /// - **Range Indexes**: B-tree indexes over fact timestamps and chosen numeric or date
+    ///   fields for ordered range queries (RwLock protected)
     /// - **External ID Mapping**: Optional string-based identifiers for external integration (RwLock protected)
     /// - **Thread Safety**: Fully thread-safe with granular locking for optimal concurrency
     ///
@@ -43,6 +64,7 @@ pub mod arena_store {
     /// - **Get by ID**: O(1) direct array access with shared read lock
     /// - **Find by indexed field**: O(1) average case via hash indexes with shared read lock
     /// - **Find by non-indexed field**: O(n) linear scan (fallback) with shared read lock
+    /// - **Find by range of a range indexed field or timestamp**: O(log n + k) via B-tree
     ///
     /// # Thread Safety
     /// - Multiple concurrent readers for all read operations
@@ -76,6 +98,7 @@ pub mod arena_store {
     ///     id: 0, // Will be auto-assigned
     ///     external_id: Some("user-12345".to_string()),
     ///     timestamp: chrono::Utc::now(),
+    ///     fact_type: None,
     ///     data: FactData { fields }
     /// };
     ///
@@ -96,11 +119,220 @@ pub mod arena_store {
     /// ```
     #[derive(Debug)]
     pub struct ArenaFactStore {
-        facts: RwLock<Vec<Option<Fact>>>, // Direct indexing: fact.id == Vec index (thread-safe)
+        facts: RwLock<FactSlots>, // Direct indexing: fact.id == Vec index (thread-safe)
+        field_arena: RwLock<FieldArena>, // Bump-allocated field storage, reset per generation
         field_indexes: RwLock<HashMap<String, HashMap<String, Vec<FactId>>>>, // Thread-safe indexes
         external_id_map: RwLock<HashMap<String, FactId>>, // Thread-safe external ID lookups
-        next_id: AtomicU64,               // Atomic ID generation for lock-free assignment
-        fact_count: AtomicU64,            // Atomic fact count for O(1) len() operations
+        next_id: AtomicU64,       // Atomic ID generation for lock-free assignment
+        fact_count: AtomicU64,    // Atomic fact count for O(1) len() operations
+        id_strategy: RwLock<FactIdStrategy>, // How inserted facts get their IDs
+        snowflake: Mutex<SnowflakeGenerator>, // Sequence state for snowflake IDs
+        id_counters: FactIdCounters, // Observable ID assignment decisions
+        schemas: RwLock<FactSchemaRegistry>, // Schemas checked by the fallible inserts
+        range_indexes: RwLock<HashMap<String, FieldRangeIndex>>, // Ordered indexes by field
+        timestamp_index: RwLock<RangeIndex<DateTime<Utc>>>, // Every fact by timestamp
+        history: RwLock<FactHistory>, // Earlier versions of modified facts
+        journal: Mutex<Option<FactJournal>>, // Versions to restore if a transaction rolls back
+        journaling: AtomicBool,   // Whether `journal` is open, checked before locking it
+    }
+
+    /// Versions of the facts one thread changed, for undoing its changes
+    ///
+    /// Only changes made on the thread that began the journal are recorded, so facts
+    /// other threads write meanwhile are left alone when the journal is undone.
+    #[derive(Debug)]
+    pub(crate) struct FactJournal {
+        owner: std::thread::ThreadId,
+        /// Each changed fact's version before its first change, `None` if it was absent
+        prior: BTreeMap<FactId, Option<PriorVersion>>,
+        /// Histories of the facts the thread deleted or replaced, kept to put back
+        histories: HashMap<FactId, VersionChain>,
+    }
+
+    /// A fact as it was before the journaling thread first changed it
+    #[derive(Debug)]
+    struct PriorVersion {
+        fact: Fact,
+        /// Its version number in the fact's history
+        version: u64,
+    }
+
+    /// Facts put back by undoing a journal
+    #[derive(Debug, Default)]
+    pub(crate) struct UndoneChanges {
+        /// Facts the journaling thread inserted, now removed again
+        pub removed: Vec<FactId>,
+        /// Facts the journaling thread deleted, stored again as they were
+        pub reinstated: Vec<Fact>,
+        /// Facts the journaling thread modified, back to their earlier fields
+        pub restored: Vec<Fact>,
+    }
+
+    /// Fact headers by ID
+    ///
+    /// IDs near the existing ones index a vector directly: client IDs up to
+    /// `MAX_USER_FACT_ID` one vector, IDs from `FIRST_GENERATED_FACT_ID` another. IDs far
+    /// beyond either (snowflake, hashed or other sparse client IDs) go to a map so they
+    /// cannot force a gigantic allocation. An ID lives in exactly one of the three.
+    #[derive(Debug, Default)]
+    struct FactSlots {
+        dense: Vec<Option<StoredFact>>,
+        generated: Vec<Option<StoredFact>>,
+        sparse: HashMap<FactId, StoredFact>,
+    }
+
+    impl FactSlots {
+        fn with_capacity(capacity: usize) -> Self {
+            Self { dense: Vec::with_capacity(capacity), ..Self::default() }
+        }
+
+        /// The vector `id` belongs in and its index there
+        fn vector(&self, id: FactId) -> (&Vec<Option<StoredFact>>, usize) {
+            match id.checked_sub(FIRST_GENERATED_FACT_ID) {
+                Some(offset) => (
+                    &self.generated,
+                    usize::try_from(offset).unwrap_or(usize::MAX),
+                ),
+                None => (&self.dense, id as usize),
+            }
+        }
+
+        fn vector_mut(&mut self, id: FactId) -> (&mut Vec<Option<StoredFact>>, usize) {
+            match id.checked_sub(FIRST_GENERATED_FACT_ID) {
+                Some(offset) => (
+                    &mut self.generated,
+                    usize::try_from(offset).unwrap_or(usize::MAX),
+                ),
+                None => (&mut self.dense, id as usize),
+            }
+        }
+
+        fn get(&self, id: FactId) -> Option<&StoredFact> {
+            let (slots, index) = self.vector(id);
+            match slots.get(index) {
+                Some(Some(stored)) => Some(stored),
+                _ => self.sparse.get(&id),
+            }
+        }
+
+        fn get_mut(&mut self, id: FactId) -> Option<&mut StoredFact> {
+            if self.sparse.contains_key(&id) {
+                return self.sparse.get_mut(&id);
+            }
+            let (slots, index) = self.vector_mut(id);
+            slots.get_mut(index).and_then(Option::as_mut)
+        }
+
+        /// Store `stored` under `id`, returning the header it replaced
+        fn insert(&mut self, id: FactId, stored: StoredFact) -> Option<StoredFact> {
+            if let Some(existing) = self.sparse.get_mut(&id) {
+                return Some(std::mem::replace(existing, stored));
+            }
+            let (slots, index) = self.vector_mut(id);
+            if index < slots.len() {
+                return slots[index].replace(stored);
+            }
+            if index - slots.len() <= MAX_USER_FACT_ID as usize {
+                // Resize vector to accommodate new fact ID with None padding for sparse gaps
+                slots.resize(index + 1, None);
+                return slots[index].replace(stored);
+            }
+            self.sparse.insert(id, stored)
+        }
+
+        fn remove(&mut self, id: FactId) -> Option<StoredFact> {
+            let (slots, index) = self.vector_mut(id);
+            match slots.get_mut(index) {
+                Some(slot @ Some(_)) => slot.take(),
+                _ => self.sparse.remove(&id),
+            }
+        }
+
+        fn iter(&self) -> impl Iterator<Item = &StoredFact> {
+            self.dense.iter().chain(&self.generated).flatten().chain(self.sparse.values())
+        }
+
+        /// Headers with IDs above `after`, in ID order
+        fn iter_by_id(&self, after: Option<FactId>) -> impl Iterator<Item = &StoredFact> {
+            let start = after.map_or(0, |id| id.saturating_add(1));
+            let index = |id: FactId| usize::try_from(id).unwrap_or(usize::MAX);
+            let first = index(start).min(self.dense.len());
+            let first_generated = index(start.saturating_sub(FIRST_GENERATED_FACT_ID));
+            let mut dense = self.dense[first..]
+                .iter()
+                .chain(&self.generated[first_generated.min(self.generated.len())..])
+                .flatten()
+                .peekable();
+            // Sparse IDs are few, so sorting them per call is cheap next to the scan
+            let mut sparse: Vec<&StoredFact> =
+                self.sparse.values().filter(|stored| stored.id >= start).collect();
+            sparse.sort_unstable_by_key(|stored| stored.id);
+            let mut sparse = sparse.into_iter().peekable();
+            std::iter::from_fn(move || match (dense.peek(), sparse.peek()) {
+                (Some(next), Some(other)) if other.id < next.id => sparse.next(),
+                (Some(_), _) => dense.next(),
+                (None, _) => sparse.next(),
+            })
+        }
+
+        /// Reserve room in the vector for `additional` more facts
+        fn reserve(&mut self, additional: usize) {
+            let len = self.dense.len();
+            if self.dense.capacity() < len + additional {
+                let new_capacity = (len + additional).next_power_of_two();
+                self.dense.reserve(new_capacity - len);
+            }
+        }
+
+        fn clear(&mut self) {
+            self.dense.clear();
+            self.generated.clear();
+            self.sparse.clear();
+        }
+
+        fn header_bytes(&self) -> usize {
+            (self.dense.capacity() + self.generated.capacity())
+                * std::mem::size_of::<Option<StoredFact>>()
+                + hash_map_table_bytes(&self.sparse)
+        }
+    }
+
+    /// Fact header stored in the arena store; field data lives in the `FieldArena`
+    #[derive(Debug, Clone)]
+    struct StoredFact {
+        id: FactId,
+        external_id: Option<String>,
+        timestamp: chrono::DateTime<chrono::Utc>,
+        fact_type: Option<String>,
+        fields: FieldSpan,
+    }
+
+    impl StoredFact {
+        fn new(fact: &Fact, fields: FieldSpan) -> Self {
+            Self {
+                id: fact.id,
+                external_id: fact.external_id.clone(),
+                timestamp: fact.timestamp,
+                fact_type: fact.fact_type.clone(),
+                fields,
+            }
+        }
+
+        /// Rebuild an owned `Fact` from the header and its arena-backed fields
+        fn materialize(&self, arena: &FieldArena) -> Fact {
+            Fact {
+                id: self.id,
+                external_id: self.external_id.clone(),
+                timestamp: self.timestamp,
+                fact_type: self.fact_type.clone(),
+                data: FactData { fields: arena.materialize(self.fields) },
+            }
+        }
+
+        /// Check a single field without materialising the whole fact
+        fn field_equals(&self, arena: &FieldArena, field: &str, value: &FactValue) -> bool {
+            arena.get(self.fields, field) == Some(value)
+        }
     }
 
     /// Thread-safe wrapper for ArenaFactStore providing concurrent access.
@@ -167,11 +399,21 @@ pub mod arena_store {
         /// ```
         pub fn new() -> Self {
             Self {
-                facts: RwLock::new(Vec::new()),
+                facts: RwLock::new(FactSlots::default()),
+                field_arena: RwLock::new(FieldArena::new()),
                 field_indexes: RwLock::new(HashMap::new()),
                 external_id_map: RwLock::new(HashMap::new()),
                 next_id: AtomicU64::new(0),
                 fact_count: AtomicU64::new(0),
+                id_strategy: RwLock::new(FactIdStrategy::default()),
+                snowflake: Mutex::new(SnowflakeGenerator::default()),
+                id_counters: FactIdCounters::default(),
+                schemas: RwLock::new(FactSchemaRegistry::default()),
+                range_indexes: RwLock::new(HashMap::new()),
+                timestamp_index: RwLock::new(RangeIndex::default()),
+                history: RwLock::new(FactHistory::default()),
+                journal: Mutex::new(None),
+                journaling: AtomicBool::new(false),
             }
         }
 
@@ -198,11 +440,21 @@ pub mod arena_store {
         /// ```
         pub fn with_capacity(capacity: usize) -> Self {
             Self {
-                facts: RwLock::new(Vec::with_capacity(capacity)),
+                facts: RwLock::new(FactSlots::with_capacity(capacity)),
+                field_arena: RwLock::new(FieldArena::new()),
                 field_indexes: RwLock::new(HashMap::with_capacity(6)), // Pre-allocate for common indexed fields
                 external_id_map: RwLock::new(HashMap::with_capacity(capacity)),
                 next_id: AtomicU64::new(0),
                 fact_count: AtomicU64::new(0),
+                id_strategy: RwLock::new(FactIdStrategy::default()),
+                snowflake: Mutex::new(SnowflakeGenerator::default()),
+                id_counters: FactIdCounters::default(),
+                schemas: RwLock::new(FactSchemaRegistry::default()),
+                range_indexes: RwLock::new(HashMap::new()),
+                timestamp_index: RwLock::new(RangeIndex::default()),
+                history: RwLock::new(FactHistory::default()),
+                journal: Mutex::new(None),
+                journaling: AtomicBool::new(false),
             }
         }
 
@@ -229,11 +481,21 @@ pub mod arena_store {
         /// ```
         pub fn with_large_capacity(capacity: usize) -> Self {
             Self {
-                facts: RwLock::new(Vec::with_capacity(capacity)),
+                facts: RwLock::new(FactSlots::with_capacity(capacity)),
+                field_arena: RwLock::new(FieldArena::new()),
                 field_indexes: RwLock::new(HashMap::with_capacity(10)), // More indexed fields for large datasets
                 external_id_map: RwLock::new(HashMap::with_capacity(capacity)),
                 next_id: AtomicU64::new(0),
                 fact_count: AtomicU64::new(0),
+                id_strategy: RwLock::new(FactIdStrategy::default()),
+                snowflake: Mutex::new(SnowflakeGenerator::default()),
+                id_counters: FactIdCounters::default(),
+                schemas: RwLock::new(FactSchemaRegistry::default()),
+                range_indexes: RwLock::new(HashMap::new()),
+                timestamp_index: RwLock::new(RangeIndex::default()),
+                history: RwLock::new(FactHistory::default()),
+                journal: Mutex::new(None),
+                journaling: AtomicBool::new(false),
             }
         }
 
@@ -325,6 +587,11 @@ pub mod arena_store {
         /// - **Space Complexity**: O(1) per field value (amortized via pre-allocation)
         /// - **Index Structure**: field_name -> value_string -> [fact_id1, fact_id2, ...]
         fn update_indexes(&self, fact: &Fact) {
+            self.update_indexes_batch(std::slice::from_ref(fact));
+        }
+
+        /// Index `facts`, taking each index lock once for the whole batch
+        fn update_indexes_batch(&self, facts: &[Fact]) {
             // Static set of commonly used fields for fast lookup
             // These fields are selected based on query patterns in business rules
             const INDEXED_FIELDS: &[&str] =
@@ -332,23 +599,39 @@ pub mod arena_store {
 
             let mut field_indexes = self.field_indexes.write().unwrap();
 
-            for (field_name, field_value) in &fact.data.fields {
-                // Fast string comparison for indexed fields (O(1) for small constant set)
-                if INDEXED_FIELDS.iter().any(|&f| f == field_name) {
-                    // Convert FactValue to string key for consistent indexing
-                    let value_key = self.fact_value_to_index_key_owned(field_value);
-
-                    // Optimized entry pattern with pre-allocated capacity hints
-                    // Based on empirical analysis of typical workloads
-                    let field_map = field_indexes
-                        .entry(field_name.clone())
-                        .or_insert_with(|| HashMap::with_capacity(64)); // Expect ~64 unique values per field
-
-                    // Insert fact ID into value-specific list
-                    field_map
-                        .entry(value_key)
-                        .or_insert_with(|| Vec::with_capacity(16)) // Expect ~16 facts per value
-                        .push(fact.id);
+            for fact in facts {
+                for (field_name, field_value) in &fact.data.fields {
+                    // Fast string comparison for indexed fields (O(1) for small constant set)
+                    if INDEXED_FIELDS.iter().any(|&f| f == field_name) {
+                        // Convert FactValue to string key for consistent indexing
+                        let value_key = self.fact_value_to_index_key_owned(field_value);
+
+                        // Optimized entry pattern with pre-allocated capacity hints
+                        // Based on empirical analysis of typical workloads
+                        let field_map = field_indexes
+                            .entry(field_name.clone())
+                            .or_insert_with(|| HashMap::with_capacity(64)); // Expect ~64 unique values per field
+
+                        // Insert fact ID into value-specific list
+                        field_map
+                            .entry(value_key)
+                            .or_insert_with(|| Vec::with_capacity(16)) // Expect ~16 facts per value
+                            .push(fact.id);
+                    }
+                }
+            }
+            drop(field_indexes);
+
+            // Range indexes key each fact once, so reindexing an updated fact moves it
+            let mut timestamp_index = self.timestamp_index.write().unwrap();
+            for fact in facts {
+                timestamp_index.insert(fact.id, fact.timestamp);
+            }
+            drop(timestamp_index);
+            let mut range_indexes = self.range_indexes.write().unwrap();
+            for (field, index) in range_indexes.iter_mut() {
+                for fact in facts {
+                    index.insert(fact.id, fact.data.fields.get(field));
                 }
             }
         }
@@ -359,11 +642,15 @@ pub mod arena_store {
                 FactValue::String(s) => Cow::Borrowed(s),
                 FactValue::Integer(i) => Cow::Owned(i.to_string()),
                 FactValue::Float(f) => Cow::Owned(f.to_string()),
+                FactValue::Decimal(d) => Cow::Owned(d.normalize().to_string()),
                 FactValue::Boolean(true) => Cow::Borrowed("true"),
                 FactValue::Boolean(false) => Cow::Borrowed("false"),
                 FactValue::Array(_) => Cow::Borrowed("[array]"),
                 FactValue::Object(_) => Cow::Borrowed("[object]"),
                 FactValue::Date(dt) => Cow::Owned(dt.to_rfc3339()),
+                FactValue::Duration(_) | FactValue::Interval { .. } => {
+                    Cow::Owned(value.to_string())
+                }
                 FactValue::Null => Cow::Borrowed("[null]"),
             }
         }
@@ -393,6 +680,7 @@ pub mod arena_store {
         ///
         /// # Example
         /// ```rust
+        /// use bingo_core::constants::fact_ids::FIRST_GENERATED_FACT_ID;
         /// use bingo_core::fact_store::arena_store::ArenaFactStore;
         /// use bingo_core::types::{Fact, FactData, FactValue};
         /// use std::collections::HashMap;
@@ -408,39 +696,181 @@ pub mod arena_store {
         ///     id: 0, // Will be auto-assigned
         ///     external_id: Some("user-42".to_string()),
         ///     timestamp: chrono::Utc::now(),
+        ///     fact_type: None,
         ///     data: FactData { fields }
         /// };
         ///
         /// let fact_id = store.insert(fact);
-        /// assert_eq!(fact_id, 0); // First fact gets ID 0
+        /// assert_eq!(fact_id, FIRST_GENERATED_FACT_ID); // Generated IDs start above the client range
         ///
         /// // Verify the fact was stored and indexed
         /// assert!(store.get_fact(fact_id).is_some());
         /// assert!(store.get_by_external_id("user-42").is_some());
         /// ```
         pub fn insert(&self, mut fact: Fact) -> FactId {
-            // ID Assignment Algorithm: Balance between preserving external IDs and preventing memory issues
-            //
-            // Strategy 1: Use existing ID if reasonable (supports test scenarios and external integrations)
-            // Strategy 2: Assign sequential ID for edge cases (prevents memory explosion from hash-based IDs)
-            //
-            // Threshold prevents accidental allocation of gigantic vectors when facts
-            // are created from hashed external identifiers (e.g. 64-bit FNV hashes from API layer)
-            let id = if fact.id == 0 || fact.id > crate::constants::fact_ids::MAX_USER_FACT_ID {
-                // Use auto-incrementing sequential ID for new facts or suspicious large IDs
-                self.next_id.fetch_add(1, Ordering::SeqCst)
+            fact.id = self.resolve_id(fact.id);
+            self.store(fact)
+        }
+
+        /// Store `fact` under its own ID regardless of the ID strategy
+        ///
+        /// Any fact already stored under the ID is replaced. Use this for IDs obtained from
+        /// [`allocate_id`](Self::allocate_id) and for copying facts between stores.
+        pub fn insert_with_id(&self, fact: Fact) -> FactId {
+            self.store(fact)
+        }
+
+        /// Insert `fact` if its ID passes the ID strategy's validation and collision policy,
+        /// and its fields pass its type's schema
+        ///
+        /// Only [`FactIdStrategy::ClientSupplied`] rejects IDs: ID 0 always, and IDs that are
+        /// already stored when its collision policy is [`IdCollisionPolicy::Reject`]. A fact
+        /// refused by its schema fails with [`BingoError::FactValidation`].
+        pub fn try_insert(&self, fact: Fact) -> BingoResult<FactId> {
+            self.validate_ids(std::slice::from_ref(&fact))?;
+            self.validate_schemas(std::slice::from_ref(&fact))?;
+            Ok(self.insert(fact))
+        }
+
+        /// Insert `facts` if every ID and every fact passes validation, otherwise insert
+        /// none of them
+        pub fn try_bulk_insert_slice(&self, facts: &[Fact]) -> BingoResult<Vec<FactId>> {
+            self.validate_ids(facts)?;
+            self.validate_schemas(facts)?;
+            Ok(self.bulk_insert_slice(facts))
+        }
+
+        /// Check facts of `schema.fact_type` against `schema` on the fallible inserts,
+        /// replacing any schema registered for the type
+        ///
+        /// Facts already stored are not checked, and the infallible inserts, used for
+        /// facts rules create and facts restored from snapshots, store facts unchecked.
+        pub fn register_schema(&self, schema: FactSchema) {
+            self.schemas.write().unwrap().register(schema);
+        }
+
+        /// Stop checking facts of `fact_type`, returning the schema they were checked
+        /// against
+        pub fn remove_schema(&self, fact_type: &str) -> Option<FactSchema> {
+            self.schemas.write().unwrap().remove(fact_type)
+        }
+
+        /// The schemas facts are checked against
+        pub fn schemas(&self) -> FactSchemaRegistry {
+            self.schemas.read().unwrap().clone()
+        }
+
+        /// Reject the whole batch if any fact fails its type's schema
+        fn validate_schemas(&self, facts: &[Fact]) -> BingoResult<()> {
+            let schemas = self.schemas.read().unwrap();
+            if schemas.is_empty() {
+                return Ok(());
+            }
+            match facts.iter().find_map(|fact| schemas.validate(fact)) {
+                Some(report) => Err(BingoError::fact_validation(report)),
+                None => Ok(()),
+            }
+        }
+
+        /// Change how IDs are assigned to facts inserted from now on
+        pub fn set_id_strategy(&self, strategy: FactIdStrategy) -> BingoResult<()> {
+            strategy.validate()?;
+            *self.id_strategy.write().unwrap() = strategy;
+            Ok(())
+        }
+
+        /// The strategy assigning IDs to inserted facts
+        pub fn id_strategy(&self) -> FactIdStrategy {
+            *self.id_strategy.read().unwrap()
+        }
+
+        /// Counts of preserved, generated, reassigned, replaced and rejected IDs
+        pub fn id_stats(&self) -> FactIdStats {
+            self.id_counters.snapshot()
+        }
+
+        /// Next sequential ID the store would generate
+        pub(crate) fn next_sequential_id(&self) -> FactId {
+            self.next_id.load(Ordering::SeqCst)
+        }
+
+        /// Generate sequential IDs from at least `next_id`, so facts copied in with
+        /// [`insert_with_id`](Self::insert_with_id) are not replaced by later inserts
+        pub(crate) fn resume_sequential_ids(&self, next_id: FactId) {
+            self.next_id.fetch_max(next_id, Ordering::SeqCst);
+        }
+
+        /// Pick the ID a fact asking for `requested` is stored under
+        fn resolve_id(&self, requested: FactId) -> FactId {
+            let strategy = *self.id_strategy.read().unwrap();
+            if strategy.keeps_client_id(requested) {
+                FactIdCounters::record(&self.id_counters.preserved);
+                // Keep generated IDs ahead of client IDs in the dense range
+                if requested <= MAX_USER_FACT_ID {
+                    self.next_id.fetch_max(requested + 1, Ordering::SeqCst);
+                }
+                return requested;
+            }
+
+            if requested != 0 && strategy == FactIdStrategy::Bounded {
+                // Hashed IDs would allocate a gigantic vector, so they are renumbered
+                FactIdCounters::record(&self.id_counters.reassigned);
+                debug!(
+                    requested,
+                    max = MAX_USER_FACT_ID,
+                    "Replacing fact ID above the bounded range with a generated ID"
+                );
             } else {
-                // Preserve existing ID for test compatibility and reasonable external IDs
-                let current_next = self.next_id.load(Ordering::SeqCst);
-                if fact.id >= current_next {
-                    // Update next_id atomically to stay ahead
-                    self.next_id.store(fact.id + 1, Ordering::SeqCst);
+                FactIdCounters::record(&self.id_counters.generated);
+            }
+            self.generate_id(strategy)
+        }
+
+        fn generate_id(&self, strategy: FactIdStrategy) -> FactId {
+            match strategy {
+                FactIdStrategy::Snowflake { worker_id } => {
+                    self.snowflake.lock().unwrap().next_id(worker_id)
+                }
+                FactIdStrategy::Bounded => {
+                    // Kept client IDs stay at or below `MAX_USER_FACT_ID`, so generating
+                    // above it means a client can never replace a generated fact
+                    self.next_id.fetch_max(FIRST_GENERATED_FACT_ID, Ordering::SeqCst);
+                    self.next_id.fetch_add(1, Ordering::SeqCst)
                 }
-                fact.id
+                _ => self.next_id.fetch_add(1, Ordering::SeqCst),
+            }
+        }
+
+        /// Reject the whole batch if any fact's ID is unacceptable to the strategy
+        fn validate_ids(&self, facts: &[Fact]) -> BingoResult<()> {
+            let FactIdStrategy::ClientSupplied { on_collision } = self.id_strategy() else {
+                return Ok(());
             };
 
-            // Update fact with final assigned ID
-            fact.id = id;
+            let stored = self.facts.read().unwrap();
+            let mut batch_ids = HashSet::new();
+            for fact in facts {
+                let problem = if fact.id == 0 {
+                    Some("Fact ID 0 is not a valid client-supplied ID")
+                } else if on_collision == IdCollisionPolicy::Reject
+                    && (stored.get(fact.id).is_some() || !batch_ids.insert(fact.id))
+                {
+                    Some("Fact ID is already stored")
+                } else {
+                    None
+                };
+
+                if let Some(message) = problem {
+                    self.id_counters.rejected.fetch_add(facts.len() as u64, Ordering::Relaxed);
+                    return Err(BingoError::fact_store_with_id(fact.id, "insert", message));
+                }
+            }
+            Ok(())
+        }
+
+        /// Store `fact` under `fact.id`, replacing and unindexing any fact stored there
+        fn store(&self, fact: Fact) -> FactId {
+            let id = fact.id;
 
             // Register external ID mapping for string-based lookups
             if let Some(ref external_id) = fact.external_id {
@@ -448,27 +878,46 @@ pub mod arena_store {
                 external_id_map.insert(external_id.clone(), id);
             }
 
-            // Update field indexes for fast lookups on indexed fields
-            self.update_indexes(&fact);
+            // Bump-allocate the fields into the arena instead of keeping the HashMap
+            let span = self.field_arena.write().unwrap().allocate(&fact.data.fields);
 
-            // Vector Storage Algorithm: Ensure adequate capacity for direct indexing
-            //
-            // This implements the arena-style allocation pattern where fact.id equals Vec index
-            // Benefits: O(1) lookup, cache-friendly sequential access, minimal memory overhead
-            let mut facts = self.facts.write().unwrap();
-            if facts.len() <= id as usize {
-                // Resize vector to accommodate new fact ID with None padding for sparse gaps
-                facts.resize(id as usize + 1, None);
-            }
             // Direct indexing: fact.id becomes the vector index for O(1) access
-            facts[id as usize] = Some(fact);
+            let previous = self.facts.write().unwrap().insert(id, StoredFact::new(&fact, span));
+            match previous {
+                Some(previous) => self.unlink_replaced(previous),
+                // Increment fact count for O(1) len() operations
+                None => {
+                    self.journal(id, || None);
+                    self.fact_count.fetch_add(1, Ordering::Relaxed);
+                }
+            }
 
-            // Increment fact count for O(1) len() operations
-            self.fact_count.fetch_add(1, Ordering::Relaxed);
+            // Update field indexes for fast lookups on indexed fields
+            self.update_indexes(&fact);
 
             id
         }
 
+        /// Release a replaced fact's fields and index entries
+        fn unlink_replaced(&self, previous: StoredFact) {
+            let replaced = {
+                let mut field_arena = self.field_arena.write().unwrap();
+                let replaced = previous.materialize(&field_arena);
+                let mut history = self.history.write().unwrap();
+                self.journal(previous.id, || {
+                    Some(PriorVersion {
+                        fact: replaced.clone(),
+                        version: history.version(previous.id),
+                    })
+                });
+                field_arena.release(previous.fields);
+                self.forget_history(previous.id, &mut history, &mut field_arena);
+                replaced
+            };
+            self.remove_from_indexes(&replaced);
+            FactIdCounters::record(&self.id_counters.replaced);
+        }
+
         /// Retrieves a fact by its internal ID.
         ///
         /// This is the fastest way to retrieve a fact when you have its internal ID,
@@ -496,6 +945,7 @@ pub mod arena_store {
         ///     id: 0,
         ///     external_id: None,
         ///     timestamp: chrono::Utc::now(),
+        ///     fact_type: None,
         ///     data: FactData { fields: std::collections::HashMap::new() }
         /// };
         /// let fact_id = store.insert(fact);
@@ -507,7 +957,9 @@ pub mod arena_store {
         /// ```
         pub fn get_fact(&self, id: FactId) -> Option<Fact> {
             let facts = self.facts.read().unwrap();
-            facts.get(id as usize)?.as_ref().cloned()
+            let stored = facts.get(id)?;
+            let arena = self.field_arena.read().unwrap();
+            Some(stored.materialize(&arena))
         }
 
         /// Retrieves a fact by its external string ID.
@@ -537,6 +989,7 @@ pub mod arena_store {
         ///     id: 0,
         ///     external_id: Some("order-12345".to_string()),
         ///     timestamp: chrono::Utc::now(),
+        ///     fact_type: None,
         ///     data: FactData { fields: std::collections::HashMap::new() }
         /// };
         ///
@@ -603,9 +1056,9 @@ pub mod arena_store {
         ///
         /// let mut store = ArenaFactStore::new();
         /// let facts = vec![
-        ///     Fact { id: 0, external_id: None, timestamp: chrono::Utc::now(), data: FactData { fields: std::collections::HashMap::new() } },
-        ///     Fact { id: 0, external_id: None, timestamp: chrono::Utc::now(), data: FactData { fields: std::collections::HashMap::new() } },
-        ///     Fact { id: 0, external_id: None, timestamp: chrono::Utc::now(), data: FactData { fields: std::collections::HashMap::new() } },
+        ///     Fact { id: 0, external_id: None, timestamp: chrono::Utc::now(), fact_type: None, data: FactData { fields: std::collections::HashMap::new() } },
+        ///     Fact { id: 0, external_id: None, timestamp: chrono::Utc::now(), fact_type: None, data: FactData { fields: std::collections::HashMap::new() } },
+        ///     Fact { id: 0, external_id: None, timestamp: chrono::Utc::now(), fact_type: None, data: FactData { fields: std::collections::HashMap::new() } },
         /// ];
         ///
         /// store.extend_from_vec(facts);
@@ -650,7 +1103,7 @@ pub mod arena_store {
         /// for i in 0..50_000 {
         ///     let mut fields = std::collections::HashMap::new();
         ///     fields.insert("user_id".to_string(), FactValue::Integer(i as i64));
-        ///     facts.push(Fact { id: 0, external_id: None, timestamp: chrono::Utc::now(), data: FactData { fields } });
+        ///     facts.push(Fact { id: 0, external_id: None, timestamp: chrono::Utc::now(), fact_type: None, data: FactData { fields } });
         /// }
         ///
         /// // Bulk insert with optimal performance
@@ -665,19 +1118,7 @@ pub mod arena_store {
             let mut fact_ids = Vec::with_capacity(facts.len());
 
             // Pre-allocate capacity if needed
-            {
-                let facts_read = self.facts.read().unwrap();
-                let current_len = facts_read.len();
-                let current_capacity = facts_read.capacity();
-                drop(facts_read);
-
-                if current_capacity < current_len + facts.len() {
-                    let mut facts_write = self.facts.write().unwrap();
-                    let current_write_len = facts_write.len();
-                    let new_capacity = (current_write_len + facts.len()).next_power_of_two();
-                    facts_write.reserve(new_capacity - current_write_len);
-                }
-            }
+            self.facts.write().unwrap().reserve(facts.len());
 
             // Process each fact individually for slice-based insertion
             for fact in facts {
@@ -692,19 +1133,7 @@ pub mod arena_store {
             let mut fact_ids = Vec::with_capacity(facts.len());
 
             // Pre-allocate capacity if needed
-            {
-                let facts_read = self.facts.read().unwrap();
-                let current_len = facts_read.len();
-                let current_capacity = facts_read.capacity();
-                drop(facts_read);
-
-                if current_capacity < current_len + facts.len() {
-                    let mut facts_write = self.facts.write().unwrap();
-                    let current_write_len = facts_write.len();
-                    let new_capacity = (current_write_len + facts.len()).next_power_of_two();
-                    facts_write.reserve(new_capacity - current_write_len);
-                }
-            }
+            self.facts.write().unwrap().reserve(facts.len());
 
             // Batch process all facts for ID assignment and external ID mapping
             {
@@ -712,16 +1141,7 @@ pub mod arena_store {
 
                 for fact in &mut facts {
                     // Generate ID using same logic as insert
-                    let id =
-                        if fact.id == 0 || fact.id > crate::constants::fact_ids::MAX_USER_FACT_ID {
-                            self.next_id.fetch_add(1, Ordering::SeqCst)
-                        } else {
-                            let current_next = self.next_id.load(Ordering::SeqCst);
-                            if fact.id >= current_next {
-                                self.next_id.store(fact.id + 1, Ordering::SeqCst);
-                            }
-                            fact.id
-                        };
+                    let id = self.resolve_id(fact.id);
 
                     fact.id = id;
                     fact_ids.push(id);
@@ -733,26 +1153,37 @@ pub mod arena_store {
                 }
             }
 
-            // Batch update indexes for all facts
-            for fact in &facts {
-                self.update_indexes(fact);
-            }
+            // Batch allocate field storage for all facts in a single arena lock
+            let spans: Vec<FieldSpan> = {
+                let mut field_arena = self.field_arena.write().unwrap();
+                facts.iter().map(|fact| field_arena.allocate(&fact.data.fields)).collect()
+            };
 
             // Batch insert all facts into storage
-            {
+            let replaced: Vec<StoredFact> = {
                 let mut facts_storage = self.facts.write().unwrap();
-                for fact in facts {
-                    let id = fact.id;
-                    // Ensure Vec capacity and insert
-                    if facts_storage.len() <= id as usize {
-                        facts_storage.resize(id as usize + 1, None);
-                    }
-                    facts_storage[id as usize] = Some(fact);
-                }
+                facts
+                    .iter()
+                    .zip(spans)
+                    .filter_map(|(fact, span)| {
+                        let previous = facts_storage.insert(fact.id, StoredFact::new(fact, span));
+                        if previous.is_none() {
+                            self.journal(fact.id, || None);
+                        }
+                        previous
+                    })
+                    .collect()
+            };
+
+            // Update fact count for all newly stored facts
+            self.fact_count
+                .fetch_add((fact_ids.len() - replaced.len()) as u64, Ordering::Relaxed);
+            for previous in replaced {
+                self.unlink_replaced(previous);
             }
 
-            // Update fact count for all inserted facts
-            self.fact_count.fetch_add(fact_ids.len() as u64, Ordering::Relaxed);
+            // Batch update indexes for all facts
+            self.update_indexes_batch(&facts);
 
             fact_ids
         }
@@ -788,14 +1219,15 @@ pub mod arena_store {
         /// ```
         pub fn iter(&self) -> Vec<Fact> {
             let facts = self.facts.read().unwrap();
-            facts.iter().filter_map(|opt| opt.as_ref().cloned()).collect()
+            let arena = self.field_arena.read().unwrap();
+            facts.iter().map(|stored| stored.materialize(&arena)).collect()
         }
 
         /// Finds facts within a specific time range (inclusive bounds).
         ///
-        /// Returns all facts whose timestamps fall within the specified time range.
-        /// Both start and end times are inclusive. This method performs a linear
-        /// scan through all facts.
+        /// Returns all facts whose timestamps fall within the specified time range, in
+        /// timestamp order and fact ID order within a timestamp. Both start and end times
+        /// are inclusive. Every fact is indexed by timestamp, so no scan is needed.
         ///
         /// # Arguments
         /// * `start` - The earliest timestamp to include (inclusive)
@@ -805,7 +1237,7 @@ pub mod arena_store {
         /// Vector of fact references whose timestamps are within the range.
         ///
         /// # Performance
-        /// - **Time Complexity**: O(n) - linear scan through all facts
+        /// - **Time Complexity**: O(log n + k) - B-tree range over the timestamp index
         /// - **Space Complexity**: O(k) where k is the number of matching facts
         ///
         /// # Example
@@ -828,9 +1260,19 @@ pub mod arena_store {
             start: chrono::DateTime<chrono::Utc>,
             end: chrono::DateTime<chrono::Utc>,
         ) -> Vec<Fact> {
-            self.iter()
-                .into_iter()
-                .filter(|f| f.timestamp >= start && f.timestamp <= end)
+            let fact_ids: Vec<FactId> =
+                self.timestamp_index.read().unwrap().range(start..=end).collect();
+            self.materialize_all(&fact_ids)
+        }
+
+        /// Materialise the stored facts with `fact_ids`, in that order
+        fn materialize_all(&self, fact_ids: &[FactId]) -> Vec<Fact> {
+            let facts = self.facts.read().unwrap();
+            let arena = self.field_arena.read().unwrap();
+            fact_ids
+                .iter()
+                .filter_map(|&id| facts.get(id))
+                .map(|stored| stored.materialize(&arena))
                 .collect()
         }
 
@@ -854,8 +1296,8 @@ pub mod arena_store {
         /// let mut store = ArenaFactStore::new();
         /// assert_eq!(store.len(), 0);
         ///
-        /// let fact1 = Fact { id: 0, external_id: None, timestamp: chrono::Utc::now(), data: FactData { fields: std::collections::HashMap::new() } };
-        /// let fact2 = Fact { id: 0, external_id: None, timestamp: chrono::Utc::now(), data: FactData { fields: std::collections::HashMap::new() } };
+        /// let fact1 = Fact { id: 0, external_id: None, timestamp: chrono::Utc::now(), fact_type: None, data: FactData { fields: std::collections::HashMap::new() } };
+        /// let fact2 = Fact { id: 0, external_id: None, timestamp: chrono::Utc::now(), fact_type: None, data: FactData { fields: std::collections::HashMap::new() } };
         /// store.insert(fact1);
         /// store.insert(fact2);
         /// assert_eq!(store.len(), 2);
@@ -890,6 +1332,9 @@ pub mod arena_store {
         /// and resets the internal ID counter. After calling this method, the store will
         /// be in the same state as a newly created instance.
         ///
+        /// Field storage is reclaimed by starting a new arena generation: every slab is
+        /// released in one step rather than freeing facts individually.
+        ///
         /// # Performance
         /// - **Time Complexity**: O(1) - all data structures support efficient clearing
         /// - **Space Complexity**: Frees all allocated memory
@@ -902,7 +1347,7 @@ pub mod arena_store {
         /// let mut store = ArenaFactStore::new();
         ///
         /// // Insert a test fact
-        /// let fact = Fact { id: 0, external_id: None, timestamp: chrono::Utc::now(), data: FactData { fields: std::collections::HashMap::new() } };
+        /// let fact = Fact { id: 0, external_id: None, timestamp: chrono::Utc::now(), fact_type: None, data: FactData { fields: std::collections::HashMap::new() } };
         /// store.insert(fact);
         /// assert!(!store.is_empty());
         ///
@@ -913,18 +1358,26 @@ pub mod arena_store {
         pub fn clear(&self) {
             let mut facts = self.facts.write().unwrap();
             facts.clear();
+            self.field_arena.write().unwrap().reset();
+            self.history.write().unwrap().clear();
             drop(facts);
 
             let mut field_indexes = self.field_indexes.write().unwrap();
             field_indexes.clear();
             drop(field_indexes);
 
+            self.timestamp_index.write().unwrap().clear();
+            for index in self.range_indexes.write().unwrap().values_mut() {
+                index.clear();
+            }
+
             let mut external_id_map = self.external_id_map.write().unwrap();
             external_id_map.clear();
             drop(external_id_map);
 
             self.next_id.store(0, Ordering::SeqCst);
             self.fact_count.store(0, Ordering::Relaxed);
+            self.id_counters.reset();
         }
 
         /// Finds all facts that have a specific field value.
@@ -978,14 +1431,137 @@ pub mod arena_store {
 
             // Fallback to linear search for non-indexed fields
             let facts = self.facts.read().unwrap();
+            let arena = self.field_arena.read().unwrap();
             facts
                 .iter()
-                .filter_map(|opt_fact| opt_fact.as_ref())
-                .filter(|fact| fact.data.fields.get(field) == Some(value))
-                .cloned()
+                .filter(|stored| stored.field_equals(&arena, field, value))
+                .map(|stored| stored.materialize(&arena))
                 .collect()
         }
 
+        /// Reserve an ID no stored fact has, for a fact stored later with `insert_with_id`
+        pub fn allocate_id(&self) -> FactId {
+            let strategy = self.id_strategy();
+            loop {
+                // ID 0 asks `insert` to assign one, so it is never handed out
+                let id = self.generate_id(strategy);
+                if id != 0 && self.facts.read().unwrap().get(id).is_none() {
+                    FactIdCounters::record(&self.id_counters.generated);
+                    return id;
+                }
+            }
+        }
+
+        /// Number of facts whose indexed `field` holds `value`
+        ///
+        /// Returns `None` when `field` is not indexed, since counting would need a scan.
+        pub fn indexed_value_count(&self, field: &str, value: &FactValue) -> Option<usize> {
+            let value_key = self.fact_value_to_index_key(value);
+            let field_indexes = self.field_indexes.read().unwrap();
+            let field_map = field_indexes.get(field)?;
+            Some(field_map.get(value_key.as_ref()).map_or(0, Vec::len))
+        }
+
+        /// Keep `field` in a B-tree index so range queries over it need no scan
+        ///
+        /// Stored facts are indexed straight away. Integers, floats and decimals are
+        /// ordered by value and dates by instant; other values are not indexed.
+        ///
+        /// # Example
+        /// ```rust
+        /// use bingo_core::fact_store::arena_store::ArenaFactStore;
+        /// use bingo_core::types::{Fact, FactData, FactValue};
+        /// use std::collections::HashMap;
+        ///
+        /// let store = ArenaFactStore::new();
+        /// for (id, amount) in [(1, 650.0), (2, 120.0), (3, 480.0)] {
+        ///     let fields = HashMap::from([("amount".to_string(), FactValue::Float(amount))]);
+        ///     store.insert(Fact::new(id, FactData { fields }));
+        /// }
+        /// store.add_range_index("amount");
+        ///
+        /// let ids: Vec<u64> =
+        ///     store.find_by_range("amount", 100.0..500.0).iter().map(|fact| fact.id).collect();
+        /// assert_eq!(ids, vec![2, 3]);
+        /// ```
+        pub fn add_range_index(&self, field: &str) {
+            if self.range_indexes.read().unwrap().contains_key(field) {
+                return;
+            }
+            let mut index = FieldRangeIndex::default();
+            {
+                let facts = self.facts.read().unwrap();
+                let arena = self.field_arena.read().unwrap();
+                for stored in facts.iter() {
+                    index.insert(stored.id, arena.get(stored.fields, field));
+                }
+            }
+            debug!(field, indexed = index.len(), "Range index added");
+            self.range_indexes.write().unwrap().insert(field.to_string(), index);
+        }
+
+        /// Stop range indexing `field`, returning whether it was indexed
+        pub fn remove_range_index(&self, field: &str) -> bool {
+            self.range_indexes.write().unwrap().remove(field).is_some()
+        }
+
+        /// Fields with a range index, in field order
+        pub fn range_indexed_fields(&self) -> Vec<String> {
+            let mut fields: Vec<String> =
+                self.range_indexes.read().unwrap().keys().cloned().collect();
+            fields.sort();
+            fields
+        }
+
+        /// Facts whose integer, float or decimal `field` is in `range`, in value order
+        /// and fact ID order within a value
+        ///
+        /// Uses the field's range index when it has one and scans every fact otherwise.
+        /// NaN values never match, and neither does a range with a NaN bound.
+        pub fn find_by_range(&self, field: &str, range: impl RangeBounds<f64>) -> Vec<Fact> {
+            let Some(bounds) = numeric_bounds(&range) else {
+                return Vec::new();
+            };
+            let mut index = FieldRangeIndex::default();
+            let fact_ids: Vec<FactId> = match self.range_indexes.read().unwrap().get(field) {
+                Some(field_index) => field_index.numbers.range(bounds).collect(),
+                None => {
+                    self.scan_into(field, &mut index);
+                    index.numbers.range(bounds).collect()
+                }
+            };
+            self.materialize_all(&fact_ids)
+        }
+
+        /// Facts whose date `field` is in `range`, in date order and fact ID order
+        /// within a date
+        ///
+        /// Uses the field's range index when it has one and scans every fact otherwise.
+        pub fn find_by_date_range(
+            &self,
+            field: &str,
+            range: impl RangeBounds<DateTime<Utc>>,
+        ) -> Vec<Fact> {
+            let mut index = FieldRangeIndex::default();
+            let fact_ids: Vec<FactId> = match self.range_indexes.read().unwrap().get(field) {
+                Some(field_index) => field_index.dates.range(range).collect(),
+                None => {
+                    self.scan_into(field, &mut index);
+                    index.dates.range(range).collect()
+                }
+            };
+            self.materialize_all(&fact_ids)
+        }
+
+        /// Index every stored fact's `field` into `index`, for fields without one
+        fn scan_into(&self, field: &str, index: &mut FieldRangeIndex) {
+            let facts = self.facts.read().unwrap();
+            let arena = self.field_arena.read().unwrap();
+            for stored in facts.iter() {
+                index.insert(stored.id, arena.get(stored.fields, field));
+            }
+        }
+
         /// Finds facts that match multiple field criteria (AND logic).
         ///
         /// This advanced search method finds facts that match ALL specified criteria.
@@ -1079,19 +1655,73 @@ pub mod arena_store {
             } else {
                 // Fall back to linear search if no indexed criteria were found
                 let facts = self.facts.read().unwrap();
+                let arena = self.field_arena.read().unwrap();
                 facts
                     .iter()
-                    .filter_map(|opt_fact| opt_fact.as_ref())
-                    .filter(|fact| {
+                    .filter(|stored| {
                         criteria
                             .iter()
-                            .all(|(field, value)| fact.data.fields.get(field) == Some(value))
+                            .all(|(field, value)| stored.field_equals(&arena, field, value))
                     })
-                    .cloned()
+                    .map(|stored| stored.materialize(&arena))
                     .collect()
             }
         }
 
+        /// One page of the facts matching `query`, in fact ID order
+        ///
+        /// Fact headers are scanned in ID order and only matching facts on the page are
+        /// materialised, so listing a large store a page at a time never copies all of
+        /// it. The scan stops at the first match past the page, which decides whether
+        /// the page gets a `next_cursor`.
+        ///
+        /// # Example
+        /// ```rust
+        /// use bingo_core::fact_query::{FactPageRequest, FactQuery};
+        /// use bingo_core::fact_store::arena_store::ArenaFactStore;
+        /// use bingo_core::types::{Fact, FactData, FactValue, Operator};
+        /// use std::collections::HashMap;
+        ///
+        /// let store = ArenaFactStore::new();
+        /// for id in 1..=5 {
+        ///     let fields = HashMap::from([("amount".to_string(), FactValue::Integer(id * 100))]);
+        ///     store.insert(Fact::new(id as u64, FactData { fields }));
+        /// }
+        /// let query = FactQuery::compare("amount", Operator::GreaterThan, FactValue::Integer(100));
+        ///
+        /// let page = store.find_by_query(&query, FactPageRequest::first(2));
+        /// assert_eq!(page.facts.iter().map(|fact| fact.id).collect::<Vec<_>>(), vec![2, 3]);
+        /// let page = store.find_by_query(&query, page.next(2).unwrap());
+        /// assert_eq!(page.facts.iter().map(|fact| fact.id).collect::<Vec<_>>(), vec![4, 5]);
+        /// assert_eq!(page.next_cursor, None);
+        /// ```
+        pub fn find_by_query(&self, query: &FactQuery, page: FactPageRequest) -> FactPage {
+            self.find_by_query_where(query, page, |_| true)
+        }
+
+        /// `find_by_query` over the matching facts `keep` accepts
+        pub(crate) fn find_by_query_where(
+            &self,
+            query: &FactQuery,
+            page: FactPageRequest,
+            keep: impl Fn(&Fact) -> bool,
+        ) -> FactPage {
+            let facts = self.facts.read().unwrap();
+            let arena = self.field_arena.read().unwrap();
+            let mut matches = facts
+                .iter_by_id(page.after)
+                .filter(|stored| query.matches_fields(&|field| arena.get(stored.fields, field)))
+                .map(|stored| stored.materialize(&arena))
+                .filter(|fact| keep(fact))
+                .skip(page.offset);
+            let found: Vec<Fact> = matches.by_ref().take(page.limit).collect();
+            let next_cursor = match found.last() {
+                Some(last) if matches.next().is_some() => Some(last.id),
+                _ => None,
+            };
+            FactPage { facts: found, next_cursor }
+        }
+
         /// Returns cache statistics for the fact store.
         ///
         /// The `ArenaFactStore` does not use caching, so this method always returns `None`.
@@ -1295,7 +1925,7 @@ pub mod arena_store {
         /// use std::collections::HashMap;
         ///
         /// let mut store = ArenaFactStore::new();
-        /// let fact = Fact { id: 0, external_id: None, timestamp: chrono::Utc::now(), data: FactData { fields: std::collections::HashMap::new() } };
+        /// let fact = Fact { id: 0, external_id: None, timestamp: chrono::Utc::now(), fact_type: None, data: FactData { fields: std::collections::HashMap::new() } };
         /// let fact_id = store.insert(fact);
         ///
         /// // Prepare updates
@@ -1314,23 +1944,279 @@ pub mod arena_store {
         /// ```
         pub fn update_fact(&self, fact_id: FactId, updates: HashMap<String, FactValue>) -> bool {
             let mut facts = self.facts.write().unwrap();
-            if let Some(fact_option) = facts.get_mut(fact_id as usize) {
-                if let Some(fact) = fact_option.as_mut() {
-                    // Apply updates to the fact's fields
-                    for (field, value) in updates {
-                        fact.data.fields.insert(field, value);
-                    }
-
-                    // Clone the fact for re-indexing to avoid borrow checker issues
-                    let fact_clone = fact.clone();
-                    drop(facts); // Drop the write lock before calling update_indexes
-                    self.update_indexes(&fact_clone);
-                    return true;
+            if let Some(stored) = facts.get_mut(fact_id) {
+                // Arena spans are immutable: rebuild the fields into a fresh span
+                let mut field_arena = self.field_arena.write().unwrap();
+                let mut fact = stored.materialize(&field_arena);
+                self.journal(fact_id, || Some(self.prior_version(&fact)));
+                for (field, value) in updates {
+                    fact.data.fields.insert(field, value);
                 }
+
+                let new_span = field_arena.allocate(&fact.data.fields);
+                let previous = std::mem::replace(&mut stored.fields, new_span);
+                self.history.write().unwrap().record(fact_id, previous, None, &mut field_arena);
+                drop(field_arena);
+                drop(facts); // Drop the write lock before calling update_indexes
+                self.update_indexes(&fact);
+                return true;
             }
             false
         }
 
+        /// Replaces one field of a stored fact with a value computed from its current value.
+        ///
+        /// `modify` receives the current value (`None` when the field is unset) while the
+        /// store is locked, so concurrent modifications of the same field never lose an
+        /// update. Returns `None` when no fact has `fact_id`. Otherwise returns the old and
+        /// new values, or the error from `modify`, which leaves the fact unchanged.
+        pub fn modify_field<F>(
+            &self,
+            fact_id: FactId,
+            field: &str,
+            modify: F,
+        ) -> Option<Result<(Option<FactValue>, FactValue), String>>
+        where
+            F: FnOnce(Option<&FactValue>) -> Result<FactValue, String>,
+        {
+            self.modify_field_by(fact_id, field, None, modify)
+        }
+
+        /// `modify_field` on behalf of the rule `changed_by`, which fact history records
+        /// as the author of the new version
+        pub(crate) fn modify_field_by<F>(
+            &self,
+            fact_id: FactId,
+            field: &str,
+            changed_by: Option<RuleId>,
+            modify: F,
+        ) -> Option<Result<(Option<FactValue>, FactValue), String>>
+        where
+            F: FnOnce(Option<&FactValue>) -> Result<FactValue, String>,
+        {
+            let mut facts = self.facts.write().unwrap();
+            let stored = facts.get_mut(fact_id)?;
+            let mut field_arena = self.field_arena.write().unwrap();
+            let mut fact = stored.materialize(&field_arena);
+            let new_value = match modify(fact.data.fields.get(field)) {
+                Ok(value) => value,
+                Err(error) => return Some(Err(error)),
+            };
+            self.journal(fact_id, || Some(self.prior_version(&fact)));
+            let old_value = fact.data.fields.insert(field.to_string(), new_value.clone());
+
+            // Arena spans are immutable: rebuild the fields into a fresh span
+            let new_span = field_arena.allocate(&fact.data.fields);
+            let previous = std::mem::replace(&mut stored.fields, new_span);
+            self.history
+                .write()
+                .unwrap()
+                .record(fact_id, previous, changed_by, &mut field_arena);
+            drop(field_arena);
+            drop(facts);
+            self.update_indexes(&fact);
+            Some(Ok((old_value, new_value)))
+        }
+
+        /// Keep up to `config.max_versions` earlier versions of each modified fact, or
+        /// stop keeping them with `None`
+        ///
+        /// Shrinking the limit or disabling history releases the versions no longer
+        /// kept. History is disabled by default.
+        pub fn set_history(&self, config: Option<FactHistoryConfig>) {
+            let mut field_arena = self.field_arena.write().unwrap();
+            self.history.write().unwrap().configure(config, &mut field_arena);
+        }
+
+        /// How many earlier versions of each fact are kept, `None` when history is
+        /// disabled
+        pub fn history_config(&self) -> Option<FactHistoryConfig> {
+            self.history.read().unwrap().config()
+        }
+
+        /// Start recording the facts the calling thread changes, replacing any journal
+        /// already open
+        pub(crate) fn begin_journal(&self) {
+            *self.journal.lock().unwrap() = Some(FactJournal {
+                owner: std::thread::current().id(),
+                prior: BTreeMap::new(),
+                histories: HashMap::new(),
+            });
+            self.journaling.store(true, Ordering::Release);
+        }
+
+        /// Stop recording changes, returning what was recorded
+        pub(crate) fn end_journal(&self) -> Option<FactJournal> {
+            self.journaling.store(false, Ordering::Release);
+            self.journal.lock().unwrap().take()
+        }
+
+        /// Keep the changes `journal` recorded, releasing the histories it held
+        pub(crate) fn discard_journal(&self, journal: FactJournal) {
+            let mut field_arena = self.field_arena.write().unwrap();
+            for chain in journal.histories.into_values() {
+                FactHistory::release(chain, &mut field_arena);
+            }
+        }
+
+        /// Put every fact `journal` recorded back to its version before the journal
+        /// began, history included
+        ///
+        /// Costs time proportional to the number of facts the journal recorded, not to
+        /// the size of the store. Clearing the store is not journaled.
+        pub(crate) fn undo(&self, mut journal: FactJournal) -> UndoneChanges {
+            let mut undone = UndoneChanges::default();
+            for (fact_id, prior) in std::mem::take(&mut journal.prior) {
+                let Some(PriorVersion { fact, version }) = prior else {
+                    self.delete_fact(fact_id);
+                    undone.removed.push(fact_id);
+                    continue;
+                };
+                let deleted = self.reinstate(&fact);
+                let mut field_arena = self.field_arena.write().unwrap();
+                let detached = journal.histories.remove(&fact_id);
+                self.history
+                    .write()
+                    .unwrap()
+                    .rewind(fact_id, detached, version, &mut field_arena);
+                drop(field_arena);
+                if deleted {
+                    undone.reinstated.push(fact);
+                } else {
+                    undone.restored.push(fact);
+                }
+            }
+            self.discard_journal(journal);
+            undone
+        }
+
+        /// Write `fact` back over whatever is stored under its ID without touching its
+        /// history, returning whether the ID was empty
+        fn reinstate(&self, fact: &Fact) -> bool {
+            if let Some(ref external_id) = fact.external_id {
+                self.external_id_map.write().unwrap().insert(external_id.clone(), fact.id);
+            }
+            let span = self.field_arena.write().unwrap().allocate(&fact.data.fields);
+            let previous = self.facts.write().unwrap().insert(fact.id, StoredFact::new(fact, span));
+            let deleted = match previous {
+                Some(previous) => {
+                    let replaced = {
+                        let mut field_arena = self.field_arena.write().unwrap();
+                        let replaced = previous.materialize(&field_arena);
+                        field_arena.release(previous.fields);
+                        replaced
+                    };
+                    self.remove_from_indexes(&replaced);
+                    false
+                }
+                None => {
+                    self.fact_count.fetch_add(1, Ordering::Relaxed);
+                    true
+                }
+            };
+            self.update_indexes(fact);
+            deleted
+        }
+
+        /// The journal, if the calling thread opened it
+        fn own_journal(&self) -> Option<MutexGuard<'_, Option<FactJournal>>> {
+            if !self.journaling.load(Ordering::Acquire) {
+                return None;
+            }
+            let journal = self.journal.lock().unwrap();
+            let owner = std::thread::current().id();
+            journal
+                .as_ref()
+                .is_some_and(|journal| journal.owner == owner)
+                .then_some(journal)
+        }
+
+        /// Record `prior` as the version of `fact_id` to restore, if the calling thread
+        /// is journaling and has not changed the fact before
+        ///
+        /// `prior` runs before the journal is locked, as it may lock the history.
+        fn journal(&self, fact_id: FactId, prior: impl FnOnce() -> Option<PriorVersion>) {
+            if !self.journaling.load(Ordering::Acquire) {
+                return;
+            }
+            let prior = prior();
+            if let Some(mut journal) = self.own_journal()
+                && let Some(journal) = journal.as_mut()
+            {
+                journal.prior.entry(fact_id).or_insert(prior);
+            }
+        }
+
+        /// `fact` as the version to restore, numbered from its history
+        fn prior_version(&self, fact: &Fact) -> PriorVersion {
+            PriorVersion {
+                fact: fact.clone(),
+                version: self.history.read().unwrap().version(fact.id),
+            }
+        }
+
+        /// Drop the history of a deleted or replaced fact, or keep it in the journal
+        /// when the calling thread is journaling
+        fn forget_history(
+            &self,
+            fact_id: FactId,
+            history: &mut FactHistory,
+            field_arena: &mut FieldArena,
+        ) {
+            if let Some(mut journal) = self.own_journal()
+                && let Some(journal) = journal.as_mut()
+            {
+                if let Some(chain) = history.detach(fact_id) {
+                    match journal.histories.entry(fact_id) {
+                        // Deleted again after being reinserted: the first history is the
+                        // one to put back
+                        std::collections::hash_map::Entry::Occupied(_) => {
+                            FactHistory::release(chain, field_arena)
+                        }
+                        std::collections::hash_map::Entry::Vacant(entry) => {
+                            entry.insert(chain);
+                        }
+                    }
+                }
+                return;
+            }
+            history.forget(fact_id, field_arena);
+        }
+
+        /// Versions of the fact with `fact_id`, oldest first and ending with the
+        /// current one; empty when no fact has the ID
+        ///
+        /// Each version lists the fields changed from the version before it. Facts
+        /// modified while history was disabled report only their current version.
+        ///
+        /// # Example
+        /// ```rust
+        /// use bingo_core::fact_history::FactHistoryConfig;
+        /// use bingo_core::fact_store::arena_store::ArenaFactStore;
+        /// use bingo_core::types::{Fact, FactData, FactValue};
+        /// use std::collections::HashMap;
+        ///
+        /// let store = ArenaFactStore::new();
+        /// store.set_history(Some(FactHistoryConfig::default()));
+        /// let fields = HashMap::from([("hours".to_string(), FactValue::Integer(8))]);
+        /// let id = store.insert(Fact::new(1, FactData { fields }));
+        /// store.update_fact(id, HashMap::from([("hours".to_string(), FactValue::Integer(9))]));
+        ///
+        /// let history = store.get_fact_history(id);
+        /// assert_eq!(history.len(), 2);
+        /// assert_eq!(history[1].changes[0].before, Some(FactValue::Integer(8)));
+        /// assert_eq!(history[1].changes[0].after, Some(FactValue::Integer(9)));
+        /// ```
+        pub fn get_fact_history(&self, fact_id: FactId) -> Vec<FactVersion> {
+            let facts = self.facts.read().unwrap();
+            let field_arena = self.field_arena.read().unwrap();
+            let Some(stored) = facts.get(fact_id) else {
+                return Vec::new();
+            };
+            let current = stored.materialize(&field_arena);
+            self.history.read().unwrap().versions(current, &field_arena)
+        }
+
         /// Deletes a fact by its internal ID.
         ///
         /// This method permanently removes a fact from the store, including:
@@ -1360,7 +2246,7 @@ pub mod arena_store {
         /// # use bingo_core::types::{Fact, FactData};
         ///
         /// let mut store = ArenaFactStore::new();
-        /// let fact = Fact { id: 0, external_id: None, timestamp: chrono::Utc::now(), data: FactData { fields: std::collections::HashMap::new() } };
+        /// let fact = Fact { id: 0, external_id: None, timestamp: chrono::Utc::now(), fact_type: None, data: FactData { fields: std::collections::HashMap::new() } };
         /// let fact_id = store.insert(fact);
         ///
         /// // Verify fact exists
@@ -1374,51 +2260,332 @@ pub mod arena_store {
         /// assert!(store.get_fact(fact_id).is_none());
         /// ```
         pub fn delete_fact(&self, fact_id: FactId) -> bool {
-            let mut facts = self.facts.write().unwrap();
-            if let Some(fact_option) = facts.get_mut(fact_id as usize) {
-                if let Some(fact) = fact_option.take() {
-                    drop(facts); // Release facts lock early
+            self.delete_facts(&[fact_id]) == 1
+        }
 
-                    // Remove from external ID mapping if present
+        /// Deletes the facts with the given IDs in one batch, returning how many were
+        /// stored
+        ///
+        /// The fact table is locked once for the whole batch and each index is cleaned
+        /// up in a single pass. IDs with no stored fact are ignored.
+        pub fn delete_facts(&self, fact_ids: &[FactId]) -> usize {
+            let removed: Vec<StoredFact> = {
+                let mut facts = self.facts.write().unwrap();
+                fact_ids.iter().filter_map(|&fact_id| facts.remove(fact_id)).collect()
+            };
+            self.unlink_deleted(removed).len()
+        }
+
+        /// Deletes every fact matching `query`, returning their IDs in ID order
+        ///
+        /// Matching facts are found and removed under one write lock on the fact table
+        /// and unindexed in one pass per index, so an end-of-period cleanup of millions
+        /// of facts needs no per-fact calls. The store knows nothing of the RETE
+        /// network; `BingoEngine::retract_by_criteria` also withdraws what the facts
+        /// supported.
+        ///
+        /// # Example
+        /// ```rust
+        /// use bingo_core::fact_query::FactQuery;
+        /// use bingo_core::fact_store::arena_store::ArenaFactStore;
+        /// use bingo_core::types::{Fact, FactData, FactValue, Operator};
+        /// use std::collections::HashMap;
+        ///
+        /// let store = ArenaFactStore::new();
+        /// for (id, period) in [(1, 202403), (2, 202404), (3, 202403)] {
+        ///     let fields = HashMap::from([("period".to_string(), FactValue::Integer(period))]);
+        ///     store.insert(Fact::new(id, FactData { fields }));
+        /// }
+        ///
+        /// let closed = FactQuery::compare("period", Operator::LessThan, FactValue::Integer(202404));
+        /// assert_eq!(store.delete_by_criteria(&closed), vec![1, 3]);
+        /// assert_eq!(store.len(), 1);
+        /// ```
+        pub fn delete_by_criteria(&self, query: &FactQuery) -> Vec<FactId> {
+            let removed: Vec<StoredFact> = {
+                let mut facts = self.facts.write().unwrap();
+                let matching: Vec<FactId> = {
+                    let arena = self.field_arena.read().unwrap();
+                    facts
+                        .iter_by_id(None)
+                        .filter(|stored| {
+                            query.matches_fields(&|field| arena.get(stored.fields, field))
+                        })
+                        .map(|stored| stored.id)
+                        .collect()
+                };
+                matching.into_iter().filter_map(|fact_id| facts.remove(fact_id)).collect()
+            };
+            self.unlink_deleted(removed)
+        }
+
+        /// Applies `updates` to every fact matching `query`, returning their IDs in ID
+        /// order
+        ///
+        /// Like `update_fact`, each field in `updates` is set or replaced and other fields
+        /// are kept. All matching facts are rewritten under one write lock on the fact
+        /// table, and each index drops their old values and takes their new ones in one
+        /// pass.
+        pub fn update_by_criteria(
+            &self,
+            query: &FactQuery,
+            updates: &HashMap<String, FactValue>,
+        ) -> Vec<FactId> {
+            self.update_matching(query, updates).iter().map(|fact| fact.id).collect()
+        }
+
+        /// `update_by_criteria` returning the updated facts
+        pub(crate) fn update_matching(
+            &self,
+            query: &FactQuery,
+            updates: &HashMap<String, FactValue>,
+        ) -> Vec<Fact> {
+            let mut previous = Vec::new();
+            let mut updated = Vec::new();
+            {
+                let mut facts = self.facts.write().unwrap();
+                let mut field_arena = self.field_arena.write().unwrap();
+                let mut history = self.history.write().unwrap();
+                let matching: Vec<FactId> = facts
+                    .iter_by_id(None)
+                    .filter(|stored| {
+                        query.matches_fields(&|field| field_arena.get(stored.fields, field))
+                    })
+                    .map(|stored| stored.id)
+                    .collect();
+                for fact_id in matching {
+                    let Some(stored) = facts.get_mut(fact_id) else {
+                        continue;
+                    };
+                    // Arena spans are immutable: rebuild the fields into a fresh span
+                    let old = stored.materialize(&field_arena);
+                    self.journal(fact_id, || {
+                        Some(PriorVersion { fact: old.clone(), version: history.version(fact_id) })
+                    });
+                    let mut fact = old.clone();
+                    for (field, value) in updates {
+                        fact.data.fields.insert(field.clone(), value.clone());
+                    }
+                    let new_span = field_arena.allocate(&fact.data.fields);
+                    let replaced = std::mem::replace(&mut stored.fields, new_span);
+                    history.record(fact_id, replaced, None, &mut field_arena);
+                    previous.push(old);
+                    updated.push(fact);
+                }
+            }
+
+            self.remove_from_indexes_batch(&previous);
+            self.update_indexes_batch(&updated);
+            updated
+        }
+
+        /// Release the fields, external IDs and index entries of removed facts,
+        /// returning their IDs
+        fn unlink_deleted(&self, removed: Vec<StoredFact>) -> Vec<FactId> {
+            if removed.is_empty() {
+                return Vec::new();
+            }
+
+            // Materialise for index cleanup, then mark the arena spans as dead
+            let deleted: Vec<Fact> = {
+                let mut field_arena = self.field_arena.write().unwrap();
+                let mut history = self.history.write().unwrap();
+                removed
+                    .into_iter()
+                    .map(|stored| {
+                        let fact = stored.materialize(&field_arena);
+                        self.journal(stored.id, || {
+                            Some(PriorVersion {
+                                fact: fact.clone(),
+                                version: history.version(fact.id),
+                            })
+                        });
+                        field_arena.release(stored.fields);
+                        self.forget_history(stored.id, &mut history, &mut field_arena);
+                        fact
+                    })
+                    .collect()
+            };
+
+            // Remove from external ID mapping if present
+            {
+                let mut external_id_map = self.external_id_map.write().unwrap();
+                for fact in &deleted {
                     if let Some(ref external_id) = fact.external_id {
-                        let mut external_id_map = self.external_id_map.write().unwrap();
                         external_id_map.remove(external_id);
                     }
+                }
+            }
 
-                    // Remove from field indexes
-                    self.remove_from_indexes(&fact);
+            self.remove_from_indexes_batch(&deleted);
+            self.fact_count.fetch_sub(deleted.len() as u64, Ordering::Relaxed);
+            deleted.iter().map(|fact| fact.id).collect()
+        }
 
-                    // Decrement fact count
-                    self.fact_count.fetch_sub(1, Ordering::Relaxed);
+        /// Returns allocation and fragmentation statistics for the field arena.
+        ///
+        /// Deleted and updated facts leave dead entries behind in their slabs until the
+        /// slab empties or the store is cleared; `fragmentation_ratio()` reports that waste.
+        ///
+        /// # Example
+        /// ```rust
+        /// use bingo_core::fact_store::arena_store::ArenaFactStore;
+        ///
+        /// let store = ArenaFactStore::new();
+        /// let stats = store.arena_stats();
+        /// assert_eq!(stats.live_entries, 0);
+        /// assert_eq!(stats.fragmentation_ratio(), 0.0);
+        /// ```
+        pub fn arena_stats(&self) -> FieldArenaStats {
+            self.field_arena.read().unwrap().stats()
+        }
 
-                    return true;
-                }
+        /// Memory held by fact slots, field payloads and indexes
+        ///
+        /// Only the fact store components of the breakdown are filled in.
+        pub fn memory_breakdown(&self) -> MemoryBreakdown {
+            let fact_headers = {
+                let facts = self.facts.read().unwrap();
+                facts.header_bytes()
+                    + facts
+                        .iter()
+                        .filter_map(|stored| stored.external_id.as_ref())
+                        .map(|id| id.capacity())
+                        .sum::<usize>()
+                    + self.history.read().unwrap().heap_bytes()
+            };
+
+            let fact_payloads = self.field_arena.read().unwrap().heap_bytes();
+
+            let field_index_bytes: usize = {
+                let field_indexes = self.field_indexes.read().unwrap();
+                hash_map_table_bytes(&field_indexes)
+                    + field_indexes
+                        .iter()
+                        .map(|(field, values)| {
+                            field.capacity()
+                                + hash_map_table_bytes(values)
+                                + values
+                                    .iter()
+                                    .map(|(value, ids)| {
+                                        value.capacity()
+                                            + ids.capacity() * std::mem::size_of::<FactId>()
+                                    })
+                                    .sum::<usize>()
+                        })
+                        .sum::<usize>()
+            };
+            let external_id_bytes: usize = {
+                let external_id_map = self.external_id_map.read().unwrap();
+                hash_map_table_bytes(&external_id_map)
+                    + external_id_map.keys().map(|id| id.capacity()).sum::<usize>()
+            };
+
+            let range_index_bytes = self.timestamp_index.read().unwrap().heap_bytes()
+                + self
+                    .range_indexes
+                    .read()
+                    .unwrap()
+                    .iter()
+                    .map(|(field, index)| field.capacity() + index.heap_bytes())
+                    .sum::<usize>();
+
+            MemoryBreakdown {
+                fact_headers,
+                fact_payloads,
+                indexes: field_index_bytes + external_id_bytes + range_index_bytes,
+                ..Default::default()
             }
-            false
+        }
+
+        /// Fact counts and bytes grouped by the value of `field`
+        ///
+        /// Facts without the field form the group with no key. Non-string values are
+        /// grouped by their display form.
+        pub fn group_usage(&self, field: &str) -> Vec<FactGroupUsage> {
+            let facts = self.facts.read().unwrap();
+            let arena = self.field_arena.read().unwrap();
+            group_usage(facts.iter().map(|stored| {
+                let key = arena.get(stored.fields, field).map(|value| match value {
+                    FactValue::String(value) => value.clone(),
+                    value => value.to_string(),
+                });
+                let bytes = std::mem::size_of::<StoredFact>()
+                    + stored.external_id.as_ref().map_or(0, |id| id.capacity())
+                    + stored.fact_type.as_ref().map_or(0, |fact_type| fact_type.capacity())
+                    + arena.span_bytes(stored.fields);
+                (key, bytes)
+            }))
+        }
+
+        /// Size of the index over each indexed field, in field order
+        pub fn index_usage(&self) -> Vec<IndexUsage> {
+            let field_indexes = self.field_indexes.read().unwrap();
+            let mut usage: Vec<IndexUsage> = field_indexes
+                .iter()
+                .map(|(field, values)| IndexUsage {
+                    field: field.clone(),
+                    distinct_values: values.len(),
+                    entries: values.values().map(Vec::len).sum(),
+                    bytes: field.capacity()
+                        + hash_map_table_bytes(values)
+                        + values
+                            .iter()
+                            .map(|(value, ids)| {
+                                value.capacity() + ids.capacity() * std::mem::size_of::<FactId>()
+                            })
+                            .sum::<usize>(),
+                })
+                .collect();
+            usage.sort_by(|a, b| a.field.cmp(&b.field));
+            usage
         }
 
         /// Remove a fact from all field indexes
         fn remove_from_indexes(&self, fact: &Fact) {
+            self.remove_from_indexes_batch(std::slice::from_ref(fact));
+        }
+
+        /// Unindex `facts`, taking each index lock once and filtering each value's fact
+        /// list once however many of its facts go
+        fn remove_from_indexes_batch(&self, facts: &[Fact]) {
             const INDEXED_FIELDS: &[&str] =
                 &["entity_id", "id", "user_id", "customer_id", "status", "category"];
 
-            let mut field_indexes = self.field_indexes.write().unwrap();
-
-            for (field_name, field_value) in &fact.data.fields {
-                if INDEXED_FIELDS.iter().any(|&f| f == field_name) {
-                    let value_key = self.fact_value_to_index_key_owned(field_value);
+            let mut removed: HashMap<(&str, String), HashSet<FactId>> = HashMap::new();
+            for fact in facts {
+                for (field_name, field_value) in &fact.data.fields {
+                    if INDEXED_FIELDS.iter().any(|&f| f == field_name) {
+                        let value_key = self.fact_value_to_index_key_owned(field_value);
+                        removed.entry((field_name, value_key)).or_default().insert(fact.id);
+                    }
+                }
+            }
 
-                    if let Some(field_map) = field_indexes.get_mut(field_name) {
-                        if let Some(fact_ids) = field_map.get_mut(&value_key) {
-                            fact_ids.retain(|&id| id != fact.id);
-                            // Remove the entry if no facts remain
-                            if fact_ids.is_empty() {
-                                field_map.remove(&value_key);
-                            }
+            let mut field_indexes = self.field_indexes.write().unwrap();
+            for ((field_name, value_key), fact_ids) in removed {
+                if let Some(field_map) = field_indexes.get_mut(field_name) {
+                    if let Some(indexed) = field_map.get_mut(&value_key) {
+                        indexed.retain(|id| !fact_ids.contains(id));
+                        // Remove the entry if no facts remain
+                        if indexed.is_empty() {
+                            field_map.remove(&value_key);
                         }
                     }
                 }
             }
+            drop(field_indexes);
+
+            let mut timestamp_index = self.timestamp_index.write().unwrap();
+            for fact in facts {
+                timestamp_index.remove(fact.id);
+            }
+            drop(timestamp_index);
+            for index in self.range_indexes.write().unwrap().values_mut() {
+                for fact in facts {
+                    index.remove(fact.id);
+                }
+            }
         }
     }
 
@@ -1430,6 +2597,8 @@ pub mod arena_store {
 #[cfg(test)]
 mod tests {
     use super::arena_store::{ArenaFactStore, ThreadSafeArenaFactStore};
+    use crate::constants::fact_ids::{FIRST_GENERATED_FACT_ID, MAX_USER_FACT_ID};
+    use crate::fact_id_strategy::{FactIdStrategy, IdCollisionPolicy};
     use crate::types::{Fact, FactData, FactValue};
     use chrono::{Duration, Utc};
     use std::collections::HashMap;
@@ -1442,7 +2611,13 @@ mod tests {
             "test_field".to_string(),
             FactValue::String("test_value".to_string()),
         );
-        Fact { id, external_id: None, timestamp: chrono::Utc::now(), data: FactData { fields } }
+        Fact {
+            id,
+            external_id: None,
+            timestamp: chrono::Utc::now(),
+            fact_type: None,
+            data: FactData { fields },
+        }
     }
 
     fn create_test_fact_with_external_id(id: u64, external_id: &str) -> Fact {
@@ -1452,7 +2627,13 @@ mod tests {
     }
 
     fn create_test_fact_with_fields(id: u64, fields: HashMap<String, FactValue>) -> Fact {
-        Fact { id, external_id: None, timestamp: chrono::Utc::now(), data: FactData { fields } }
+        Fact {
+            id,
+            external_id: None,
+            timestamp: chrono::Utc::now(),
+            fact_type: None,
+            data: FactData { fields },
+        }
     }
 
     #[test]
@@ -1876,22 +3057,109 @@ mod tests {
         // Test auto-generated IDs
         let fact_auto = create_test_fact(0); // ID 0 should trigger auto-generation
         let auto_id = store.insert(fact_auto);
-        assert_eq!(auto_id, 0);
+        assert_eq!(auto_id, FIRST_GENERATED_FACT_ID);
 
         // Test large ID fallback to auto-generation
         let fact_large = create_test_fact(2_000_000); // Should fallback to auto-generation
         let large_id = store.insert(fact_large);
-        assert_eq!(large_id, 1); // Should get next auto-generated ID
+        assert_eq!(large_id, FIRST_GENERATED_FACT_ID + 1); // Should get next auto-generated ID
 
         // Test specific ID preservation
         let fact_specific = create_test_fact(42);
         let specific_id = store.insert(fact_specific);
         assert_eq!(specific_id, 42);
 
-        // Test that next auto-generated ID accounts for specific ID
+        // Client IDs never land on a generated fact
         let fact_next_auto = create_test_fact(0);
         let next_auto_id = store.insert(fact_next_auto);
-        assert_eq!(next_auto_id, 43); // Should be after the specific ID
+        assert_eq!(next_auto_id, FIRST_GENERATED_FACT_ID + 2);
+        store.insert(create_test_fact(1));
+        assert_eq!(store.len(), 5);
+        assert_eq!(store.id_stats().replaced, 0);
+        assert!(store.get_fact(FIRST_GENERATED_FACT_ID).is_some());
+    }
+
+    #[test]
+    fn test_id_strategies() {
+        let store = ArenaFactStore::new();
+        store.insert(create_test_fact(2_000_000));
+        assert_eq!(store.id_stats().reassigned, 1);
+
+        store.set_id_strategy(FactIdStrategy::Sequential).unwrap();
+        assert_eq!(
+            store.insert(create_test_fact(42)),
+            FIRST_GENERATED_FACT_ID + 1
+        );
+
+        store.set_id_strategy(FactIdStrategy::Snowflake { worker_id: 3 }).unwrap();
+        let first = store.insert(create_test_fact(0));
+        let second = store.insert(create_test_fact(0));
+        assert!(first > MAX_USER_FACT_ID && second > first);
+        assert_eq!((first >> 12) & 0x3FF, 3);
+
+        let stats = store.id_stats();
+        assert_eq!(
+            (stats.preserved, stats.generated, stats.reassigned),
+            (0, 3, 1)
+        );
+        assert!(store.set_id_strategy(FactIdStrategy::Snowflake { worker_id: 4096 }).is_err());
+    }
+
+    #[test]
+    fn test_client_supplied_ids_are_stored_sparsely() {
+        let store = ArenaFactStore::new();
+        store
+            .set_id_strategy(FactIdStrategy::ClientSupplied {
+                on_collision: IdCollisionPolicy::Replace,
+            })
+            .unwrap();
+
+        let hashed = 0x9E37_79B9_7F4A_7C15;
+        assert_eq!(
+            store.insert(create_test_fact_with_external_id(hashed, "hashed")),
+            hashed
+        );
+        assert_eq!(store.insert(create_test_fact(7)), 7);
+        assert_eq!(
+            store.get_fact(hashed).unwrap().external_id.as_deref(),
+            Some("hashed")
+        );
+        assert_eq!(store.iter().len(), 2);
+        assert!(store.memory_breakdown().fact_headers < 1024 * 1024);
+
+        // Replacing keeps the count and the indexes in step
+        let mut fields = HashMap::new();
+        fields.insert("status".to_string(), FactValue::String("old".to_string()));
+        store.insert(create_test_fact_with_fields(hashed, fields.clone()));
+        fields.insert("status".to_string(), FactValue::String("new".to_string()));
+        store.insert(create_test_fact_with_fields(hashed, fields));
+        assert_eq!(store.len(), 2);
+        assert!(store.find_by_field("status", &FactValue::String("old".to_string())).is_empty());
+        assert_eq!(store.id_stats().replaced, 2);
+
+        assert!(store.delete_fact(hashed));
+        assert!(store.get_fact(hashed).is_none());
+        assert_eq!(store.len(), 1);
+    }
+
+    #[test]
+    fn test_client_supplied_ids_are_validated() {
+        let store = ArenaFactStore::new();
+        store
+            .set_id_strategy(FactIdStrategy::ClientSupplied {
+                on_collision: IdCollisionPolicy::Reject,
+            })
+            .unwrap();
+
+        assert!(store.try_insert(create_test_fact(0)).is_err());
+        assert_eq!(store.try_insert(create_test_fact(5)).unwrap(), 5);
+        assert!(store.try_insert(create_test_fact(5)).is_err());
+
+        // A batch with one bad ID stores nothing
+        let batch = [create_test_fact(6), create_test_fact(8), create_test_fact(6)];
+        assert!(store.try_bulk_insert_slice(&batch).is_err());
+        assert_eq!(store.len(), 1);
+        assert_eq!(store.id_stats().rejected, 5);
     }
 
     #[test]
@@ -2031,6 +3299,7 @@ mod tests {
             id: 0,
             external_id: None,
             timestamp: Utc::now(),
+            fact_type: None,
             data: FactData { fields: HashMap::new() },
         };
         let fact_id = store.insert(empty_fact);
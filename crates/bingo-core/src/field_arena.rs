@@ -0,0 +1,394 @@
+//! Bump-allocated storage for fact fields
+//!
+//! Facts stored in the `ArenaFactStore` used to own a `HashMap` each, which meant one
+//! heap allocation per fact plus one per field name. The `FieldArena` instead appends
+//! every fact's fields to large, fixed-capacity slabs and hands back a `FieldSpan`
+//! describing where they live.
+//!
+//! ## Allocation Model
+//!
+//! - **Slabs**: Fixed-capacity vectors that are filled front to back and never reallocate
+//! - **Spans**: `(slab, offset, len)` handles stamped with the generation that created them
+//! - **Interned Keys**: Field names are stored once and referenced by a compact `KeyId`
+//! - **Generations**: `reset()` reclaims every slab at once and invalidates older spans
+//!
+//! Individual spans are never freed in place. Releasing a span only marks its entries as
+//! dead; a slab is returned to the allocator once all of its entries are dead, and the
+//! remaining waste is reported through `FieldArenaStats::fragmentation_ratio`.
+
+use crate::memory::{fact_value_heap_bytes, hash_map_table_bytes};
+use crate::types::FactValue;
+use std::collections::HashMap;
+
+/// Default number of field entries per slab
+pub const DEFAULT_SLAB_CAPACITY: usize = 4096;
+
+/// Compact identifier for an interned field name
+pub type KeyId = u32;
+
+/// Handle to a contiguous run of fields inside the arena
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldSpan {
+    generation: u64,
+    slab: u32,
+    offset: u32,
+    len: u32,
+}
+
+impl FieldSpan {
+    /// Number of fields covered by this span
+    pub fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    /// Whether the span covers no fields
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Generation in which the span was allocated
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+}
+
+/// A single fixed-capacity slab of field entries
+#[derive(Debug)]
+struct Slab {
+    entries: Vec<(KeyId, FactValue)>,
+    live_entries: usize,
+}
+
+impl Slab {
+    fn with_capacity(capacity: usize) -> Self {
+        Self { entries: Vec::with_capacity(capacity), live_entries: 0 }
+    }
+
+    fn remaining(&self) -> usize {
+        self.entries.capacity() - self.entries.len()
+    }
+}
+
+/// Allocation and fragmentation statistics for a `FieldArena`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FieldArenaStats {
+    /// Current arena generation
+    pub generation: u64,
+    /// Slabs currently holding memory
+    pub active_slabs: usize,
+    /// Entries reserved across all active slabs
+    pub reserved_entries: usize,
+    /// Entries handed out by the bump allocator
+    pub allocated_entries: usize,
+    /// Entries still referenced by a live span
+    pub live_entries: usize,
+    /// Number of distinct interned field names
+    pub interned_keys: usize,
+    /// Approximate bytes reserved by the slabs
+    pub reserved_bytes: usize,
+}
+
+impl FieldArenaStats {
+    /// Entries that were allocated but whose spans have since been released
+    pub fn dead_entries(&self) -> usize {
+        self.allocated_entries - self.live_entries
+    }
+
+    /// Fraction of allocated entries that are dead (0.0 = no fragmentation)
+    pub fn fragmentation_ratio(&self) -> f64 {
+        if self.allocated_entries == 0 {
+            0.0
+        } else {
+            self.dead_entries() as f64 / self.allocated_entries as f64
+        }
+    }
+
+    /// Fraction of reserved capacity that holds live entries
+    pub fn utilization(&self) -> f64 {
+        if self.reserved_entries == 0 {
+            0.0
+        } else {
+            self.live_entries as f64 / self.reserved_entries as f64
+        }
+    }
+}
+
+/// Generational bump allocator for fact field storage
+#[derive(Debug)]
+pub struct FieldArena {
+    slabs: Vec<Slab>,
+    slab_capacity: usize,
+    generation: u64,
+    key_ids: HashMap<String, KeyId>,
+    key_names: Vec<String>,
+}
+
+impl Default for FieldArena {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FieldArena {
+    /// Create an arena with the default slab capacity
+    pub fn new() -> Self {
+        Self::with_slab_capacity(DEFAULT_SLAB_CAPACITY)
+    }
+
+    /// Create an arena whose slabs hold `slab_capacity` field entries
+    pub fn with_slab_capacity(slab_capacity: usize) -> Self {
+        Self {
+            slabs: Vec::new(),
+            slab_capacity: slab_capacity.max(1),
+            generation: 0,
+            key_ids: HashMap::new(),
+            key_names: Vec::new(),
+        }
+    }
+
+    /// Current generation; spans from older generations are no longer readable
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Bump-allocate a span holding the given fields
+    pub fn allocate<'a, I>(&mut self, fields: I) -> FieldSpan
+    where
+        I: IntoIterator<Item = (&'a String, &'a FactValue)>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let fields = fields.into_iter();
+        let len = fields.len();
+        let slab_index = self.slab_with_room(len);
+
+        let mut entries = Vec::with_capacity(len);
+        for (key, value) in fields {
+            entries.push((self.intern(key), value.clone()));
+        }
+
+        let slab = &mut self.slabs[slab_index];
+        let offset = slab.entries.len();
+        slab.entries.extend(entries);
+        slab.live_entries += len;
+
+        FieldSpan {
+            generation: self.generation,
+            slab: slab_index as u32,
+            offset: offset as u32,
+            len: len as u32,
+        }
+    }
+
+    /// Mark a span's entries as dead, returning its slab to the allocator once empty
+    pub fn release(&mut self, span: FieldSpan) {
+        if span.generation != self.generation || span.is_empty() {
+            return;
+        }
+
+        let is_current_slab = span.slab as usize + 1 == self.slabs.len();
+        if let Some(slab) = self.slabs.get_mut(span.slab as usize) {
+            slab.live_entries = slab.live_entries.saturating_sub(span.len());
+            if slab.live_entries == 0 && !is_current_slab {
+                // Free the backing memory but keep the slot so other spans stay valid
+                slab.entries = Vec::new();
+            }
+        }
+    }
+
+    /// Look up a single field within a span
+    pub fn get(&self, span: FieldSpan, field: &str) -> Option<&FactValue> {
+        let key_id = *self.key_ids.get(field)?;
+        self.entries(span)?
+            .iter()
+            .find(|(key, _)| *key == key_id)
+            .map(|(_, value)| value)
+    }
+
+    /// Iterate over the `(field, value)` pairs in a span
+    pub fn iter(&self, span: FieldSpan) -> impl Iterator<Item = (&str, &FactValue)> {
+        self.entries(span)
+            .unwrap_or(&[])
+            .iter()
+            .map(|(key, value)| (self.key_names[*key as usize].as_str(), value))
+    }
+
+    /// Copy a span's fields into an owned map
+    pub fn materialize(&self, span: FieldSpan) -> HashMap<String, FactValue> {
+        let mut fields = HashMap::with_capacity(span.len());
+        for (key, value) in self.iter(span) {
+            fields.insert(key.to_string(), value.clone());
+        }
+        fields
+    }
+
+    /// Reclaim every slab and start a new generation
+    ///
+    /// All spans handed out so far become unreadable. The first slab's capacity is kept
+    /// so that refilling the arena after a clear does not immediately allocate again.
+    pub fn reset(&mut self) {
+        self.slabs.truncate(1);
+        if let Some(slab) = self.slabs.first_mut() {
+            slab.entries.clear();
+            slab.live_entries = 0;
+        }
+        self.generation += 1;
+    }
+
+    /// Allocation and fragmentation statistics
+    pub fn stats(&self) -> FieldArenaStats {
+        let mut stats = FieldArenaStats {
+            generation: self.generation,
+            interned_keys: self.key_names.len(),
+            ..Default::default()
+        };
+
+        for slab in &self.slabs {
+            if slab.entries.capacity() > 0 {
+                stats.active_slabs += 1;
+            }
+            stats.reserved_entries += slab.entries.capacity();
+            stats.allocated_entries += slab.entries.len();
+            stats.live_entries += slab.live_entries;
+        }
+        stats.reserved_bytes = stats.reserved_entries * std::mem::size_of::<(KeyId, FactValue)>();
+
+        stats
+    }
+
+    /// Bytes held by the arena: reserved slab capacity, heap data owned by allocated
+    /// values (live or dead) and the interned field names
+    pub fn heap_bytes(&self) -> usize {
+        let slab_bytes: usize = self
+            .slabs
+            .iter()
+            .map(|slab| {
+                slab.entries.capacity() * std::mem::size_of::<(KeyId, FactValue)>()
+                    + slab
+                        .entries
+                        .iter()
+                        .map(|(_, value)| fact_value_heap_bytes(value))
+                        .sum::<usize>()
+            })
+            .sum();
+        // Each interned name is owned twice: once in `key_names`, once as a `key_ids` key
+        let key_bytes: usize = self.key_names.iter().map(|name| name.capacity() * 2).sum::<usize>()
+            + self.key_names.capacity() * std::mem::size_of::<String>()
+            + hash_map_table_bytes(&self.key_ids);
+
+        slab_bytes + key_bytes
+    }
+
+    /// Bytes held by the entries of one span, including heap data owned by their values
+    pub fn span_bytes(&self, span: FieldSpan) -> usize {
+        self.entries(span).map_or(0, |entries| {
+            std::mem::size_of_val(entries)
+                + entries.iter().map(|(_, value)| fact_value_heap_bytes(value)).sum::<usize>()
+        })
+    }
+
+    fn entries(&self, span: FieldSpan) -> Option<&[(KeyId, FactValue)]> {
+        if span.generation != self.generation {
+            return None;
+        }
+        let start = span.offset as usize;
+        self.slabs.get(span.slab as usize)?.entries.get(start..start + span.len())
+    }
+
+    fn intern(&mut self, key: &str) -> KeyId {
+        if let Some(&id) = self.key_ids.get(key) {
+            return id;
+        }
+        let id = self.key_names.len() as KeyId;
+        self.key_names.push(key.to_string());
+        self.key_ids.insert(key.to_string(), id);
+        id
+    }
+
+    /// Index of a slab with room for `len` contiguous entries, opening one if needed
+    fn slab_with_room(&mut self, len: usize) -> usize {
+        match self.slabs.last() {
+            Some(slab) if slab.remaining() >= len => self.slabs.len() - 1,
+            _ => {
+                self.slabs.push(Slab::with_capacity(self.slab_capacity.max(len)));
+                self.slabs.len() - 1
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(pairs: &[(&str, i64)]) -> HashMap<String, FactValue> {
+        pairs.iter().map(|(k, v)| (k.to_string(), FactValue::Integer(*v))).collect()
+    }
+
+    #[test]
+    fn test_allocate_and_read_back() {
+        let mut arena = FieldArena::with_slab_capacity(8);
+        let input = fields(&[("a", 1), ("b", 2)]);
+        let span = arena.allocate(&input);
+
+        assert_eq!(span.len(), 2);
+        assert_eq!(arena.get(span, "a"), Some(&FactValue::Integer(1)));
+        assert_eq!(arena.get(span, "missing"), None);
+        assert_eq!(arena.materialize(span), input);
+    }
+
+    #[test]
+    fn test_heap_bytes_include_string_payloads() {
+        let mut arena = FieldArena::with_slab_capacity(8);
+        arena.allocate(&fields(&[("a", 1)]));
+        let numeric_only = arena.heap_bytes();
+        assert!(numeric_only >= arena.stats().reserved_bytes);
+
+        let long_text = "x".repeat(4096);
+        arena.allocate(&HashMap::from([(
+            "a".to_string(),
+            FactValue::String(long_text),
+        )]));
+        assert!(arena.heap_bytes() >= numeric_only + 4096);
+    }
+
+    #[test]
+    fn test_spans_open_new_slabs_when_full() {
+        let mut arena = FieldArena::with_slab_capacity(3);
+        arena.allocate(&fields(&[("a", 1), ("b", 2)]));
+        arena.allocate(&fields(&[("a", 3), ("b", 4)]));
+
+        let stats = arena.stats();
+        assert_eq!(stats.active_slabs, 2);
+        assert_eq!(stats.live_entries, 4);
+        assert_eq!(stats.interned_keys, 2);
+    }
+
+    #[test]
+    fn test_release_reports_fragmentation_and_frees_empty_slabs() {
+        let mut arena = FieldArena::with_slab_capacity(2);
+        let first = arena.allocate(&fields(&[("a", 1), ("b", 2)]));
+        let second = arena.allocate(&fields(&[("a", 3)]));
+
+        arena.release(second);
+        assert!(arena.stats().fragmentation_ratio() > 0.0);
+
+        arena.release(first);
+        let stats = arena.stats();
+        assert_eq!(stats.active_slabs, 1);
+        assert_eq!(stats.live_entries, 0);
+    }
+
+    #[test]
+    fn test_reset_starts_new_generation() {
+        let mut arena = FieldArena::with_slab_capacity(4);
+        let span = arena.allocate(&fields(&[("a", 1)]));
+        arena.reset();
+
+        assert_eq!(arena.generation(), 1);
+        assert_eq!(arena.get(span, "a"), None);
+        assert_eq!(arena.stats().allocated_entries, 0);
+
+        let span = arena.allocate(&fields(&[("a", 2)]));
+        assert_eq!(arena.get(span, "a"), Some(&FactValue::Integer(2)));
+    }
+}
@@ -0,0 +1,229 @@
+//! Resolution of `SetField` collisions within a batch
+//!
+//! Rules firing for the same fact in one batch may set the same field to different
+//! values. Rules fire in no guaranteed order, so whichever write a client applies last
+//! would win arbitrarily. [`FieldCollisionPolicy`] decides what happens instead, and
+//! every collision is reported on the results of the rules involved.
+
+use crate::rete_nodes::{ActionResult, RuleExecutionResult};
+use crate::types::{FactId, FactValue, RuleId};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+/// What happens when rules set the same field of a fact to different values in a batch
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldCollisionPolicy {
+    /// Keep every write and report the collision
+    #[default]
+    CollectAll,
+    /// Keep only the writes of the rule with the highest salience, the lowest rule ID
+    /// among equals, and drop the other rules' writes
+    HighestSalience,
+    /// Fail the batch
+    Error,
+}
+
+/// Rules setting the same field of a fact to different values in one batch
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldCollision {
+    pub fact_id: FactId,
+    pub field: String,
+    /// Writes in the order the rules fired
+    pub writes: Vec<FieldWrite>,
+    /// Rule whose writes were kept, under [`FieldCollisionPolicy::HighestSalience`]
+    pub winner: Option<RuleId>,
+}
+
+/// One rule's write in a [`FieldCollision`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldWrite {
+    pub rule_id: RuleId,
+    pub value: FactValue,
+}
+
+/// Position of a `FieldSet` action: result index and action index
+type WriteSite = (usize, usize);
+
+/// Find the batch's field collisions and resolve them with `policy`
+///
+/// Each collision is added to the `field_collisions` of every result taking part in
+/// it. Writes of the same rule never collide with each other.
+///
+/// # Errors
+///
+/// Under [`FieldCollisionPolicy::Error`], fails describing the first collision.
+pub(crate) fn resolve_field_collisions(
+    results: &mut [RuleExecutionResult],
+    policy: FieldCollisionPolicy,
+    salience: impl Fn(RuleId) -> i32,
+) -> Result<(), String> {
+    // Only results for the same fact can collide, so most batches need no grouping
+    let mut writers: Vec<usize> = (0..results.len())
+        .filter(|&index| results[index].actions_executed.iter().any(is_field_set))
+        .collect();
+    writers.sort_by_key(|&index| results[index].fact_id);
+
+    let mut collisions = Vec::new();
+    for group in writers.chunk_by(|&a, &b| results[a].fact_id == results[b].fact_id) {
+        if group.len() > 1 {
+            collisions.extend(group_collisions(results, group));
+        }
+    }
+
+    if policy == FieldCollisionPolicy::Error {
+        if let Some((collision, _)) = collisions.first() {
+            let mut rules: Vec<RuleId> =
+                collision.writes.iter().map(|write| write.rule_id).collect();
+            rules.sort_unstable();
+            rules.dedup();
+            return Err(format!(
+                "Rules {rules:?} set field '{}' of fact {} to different values",
+                collision.field, collision.fact_id
+            ));
+        }
+        return Ok(());
+    }
+
+    let mut dropped: HashSet<WriteSite> = HashSet::new();
+    for (mut collision, sites) in collisions {
+        if policy == FieldCollisionPolicy::HighestSalience {
+            let winner = collision
+                .writes
+                .iter()
+                .map(|write| write.rule_id)
+                .max_by_key(|&rule_id| (salience(rule_id), std::cmp::Reverse(rule_id)))
+                .expect("collisions have writes");
+            collision.winner = Some(winner);
+            dropped.extend(
+                sites
+                    .iter()
+                    .filter(|&&(result_index, _)| results[result_index].rule_id != winner),
+            );
+        }
+
+        let mut involved: Vec<usize> =
+            sites.iter().map(|&(result_index, _)| result_index).collect();
+        involved.dedup();
+        for result_index in involved {
+            results[result_index].field_collisions.push(collision.clone());
+        }
+    }
+
+    if !dropped.is_empty() {
+        for (result_index, result) in results.iter_mut().enumerate() {
+            let mut action_index = 0;
+            result.actions_executed.retain(|_| {
+                let keep = !dropped.contains(&(result_index, action_index));
+                action_index += 1;
+                keep
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Collisions among the writes of `group`, results for the same fact in firing order
+fn group_collisions(
+    results: &[RuleExecutionResult],
+    group: &[usize],
+) -> Vec<(FieldCollision, Vec<WriteSite>)> {
+    let mut writes: BTreeMap<(FactId, &str), Vec<WriteSite>> = BTreeMap::new();
+    for &result_index in group {
+        for (action_index, action) in results[result_index].actions_executed.iter().enumerate() {
+            if let ActionResult::FieldSet { fact_id, field, .. } = action {
+                writes
+                    .entry((*fact_id, field.as_str()))
+                    .or_default()
+                    .push((result_index, action_index));
+            }
+        }
+    }
+
+    let mut collisions = Vec::new();
+    for ((fact_id, field), sites) in writes {
+        let collision = FieldCollision {
+            fact_id,
+            field: field.to_string(),
+            writes: sites
+                .iter()
+                .map(|&(result_index, action_index)| FieldWrite {
+                    rule_id: results[result_index].rule_id,
+                    value: written_value(&results[result_index].actions_executed[action_index]),
+                })
+                .collect(),
+            winner: None,
+        };
+        let rules: HashSet<RuleId> = collision.writes.iter().map(|write| write.rule_id).collect();
+        let first_value = &collision.writes[0].value;
+        if rules.len() > 1 && collision.writes.iter().any(|write| write.value != *first_value) {
+            collisions.push((collision, sites));
+        }
+    }
+    collisions
+}
+
+fn is_field_set(action: &ActionResult) -> bool {
+    matches!(action, ActionResult::FieldSet { .. })
+}
+
+fn written_value(action: &ActionResult) -> FactValue {
+    match action {
+        ActionResult::FieldSet { value, .. } => value.clone(),
+        _ => unreachable!("write sites only index FieldSet actions"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(rule_id: RuleId, field: &str, value: i64) -> RuleExecutionResult {
+        RuleExecutionResult {
+            rule_id,
+            fact_id: 1,
+            actions_executed: vec![ActionResult::FieldSet {
+                fact_id: 1,
+                field: field.to_string(),
+                value: FactValue::Integer(value),
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_only_disagreeing_rules_collide() {
+        let mut results = vec![set(1, "tier", 1), set(2, "tier", 1), set(3, "score", 5)];
+        resolve_field_collisions(&mut results, FieldCollisionPolicy::Error, |_| 0).unwrap();
+        assert!(results.iter().all(|result| result.field_collisions.is_empty()));
+    }
+
+    #[test]
+    fn test_highest_salience_keeps_the_winning_write() {
+        let mut results = vec![set(1, "tier", 1), set(2, "tier", 2), set(3, "tier", 3)];
+        let salience = |rule_id| if rule_id == 3 { 0 } else { 10 };
+        resolve_field_collisions(
+            &mut results,
+            FieldCollisionPolicy::HighestSalience,
+            salience,
+        )
+        .unwrap();
+
+        // Rules 1 and 2 tie on salience, so the lower rule ID wins
+        let kept: Vec<usize> = results.iter().map(|result| result.actions_executed.len()).collect();
+        assert_eq!(kept, vec![1, 0, 0]);
+        for result in &results {
+            assert_eq!(result.field_collisions.len(), 1);
+            assert_eq!(result.field_collisions[0].winner, Some(1));
+            assert_eq!(result.field_collisions[0].writes.len(), 3);
+        }
+
+        let mut results = vec![set(1, "tier", 1), set(2, "tier", 2)];
+        let error =
+            resolve_field_collisions(&mut results, FieldCollisionPolicy::Error, |_| 0).unwrap_err();
+        assert_eq!(
+            error,
+            "Rules [1, 2] set field 'tier' of fact 1 to different values"
+        );
+    }
+}
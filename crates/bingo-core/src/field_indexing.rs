@@ -215,7 +215,13 @@ mod tests {
     fn create_test_fact(id: FactId, field_name: &str, field_value: FactValue) -> Fact {
         let mut fields = HashMap::new();
         fields.insert(field_name.to_string(), field_value);
-        Fact { id, external_id: None, timestamp: chrono::Utc::now(), data: FactData { fields } }
+        Fact {
+            id,
+            external_id: None,
+            timestamp: chrono::Utc::now(),
+            fact_type: None,
+            data: FactData { fields },
+        }
     }
 
     #[test]
@@ -268,6 +274,7 @@ mod tests {
             id: 1,
             external_id: None,
             timestamp: chrono::Utc::now(),
+            fact_type: None,
             data: FactData { fields: fields1 },
         };
 
@@ -284,6 +291,7 @@ mod tests {
             id: 2,
             external_id: None,
             timestamp: chrono::Utc::now(),
+            fact_type: None,
             data: FactData { fields: fields2 },
         };
 
@@ -300,6 +308,7 @@ mod tests {
             id: 3,
             external_id: None,
             timestamp: chrono::Utc::now(),
+            fact_type: None,
             data: FactData { fields: fields3 },
         };
 
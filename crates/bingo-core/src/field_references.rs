@@ -0,0 +1,364 @@
+//! Fields referenced by a ruleset
+//!
+//! Upstream ingestion pipelines only need to ship the columns rules actually read,
+//! and a schema change that drops or retypes one of them should fail before it
+//! reaches production. `referenced_fields` walks every condition and action and
+//! reports each fact field with the operators applied to it, whether it is read or
+//! written and which rules use it.
+//!
+//! Aggregation and stream `having` clauses test the computed alias rather than a fact
+//! field, so they are left out, as are conditions and actions using the value an
+//! accumulate condition binds.
+
+use crate::reference_data::{parse_global_reference, parse_table_reference};
+use crate::types::{
+    Accumulator, ActionType, AggregationType, Condition, Operator, Rule, RuleId, StreamAggregation,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A fact field used by the ruleset
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReferencedField {
+    pub field: String,
+    /// Operators simple conditions compare the field with, in the order first seen
+    pub operators: Vec<Operator>,
+    /// Aggregations computed over the field, in the order first seen
+    pub aggregations: Vec<AggregationType>,
+    /// Whether conditions or actions read the field from facts
+    pub read: bool,
+    /// Whether actions write the field
+    pub written: bool,
+    /// Rules referencing the field
+    pub rule_ids: BTreeSet<RuleId>,
+}
+
+impl ReferencedField {
+    fn new(field: &str) -> Self {
+        Self {
+            field: field.to_string(),
+            operators: Vec::new(),
+            aggregations: Vec::new(),
+            read: false,
+            written: false,
+            rule_ids: BTreeSet::new(),
+        }
+    }
+}
+
+/// Every fact field the rules read or write, ordered by field name
+pub fn referenced_fields(rules: &[Rule]) -> Vec<ReferencedField> {
+    let mut collector = Collector::default();
+    for rule in rules {
+        collector.rule_id = rule.id;
+        collector.bound = bound_aliases(rule);
+        for condition in &rule.conditions {
+            collector.condition(condition);
+        }
+        for action in &rule.actions {
+            collector.action(&action.action_type);
+        }
+    }
+    collector.fields.into_values().collect()
+}
+
+/// Fact fields a condition reads, ordered by name
+pub(crate) fn condition_fields(condition: &Condition) -> Vec<String> {
+    let mut collector = Collector::default();
+    collector.condition(condition);
+    collector.fields.into_keys().collect()
+}
+
+/// Aliases the rule's accumulate conditions bind on the fact being evaluated
+pub(crate) fn bound_aliases(rule: &Rule) -> BTreeSet<String> {
+    rule.conditions
+        .iter()
+        .filter_map(|condition| match condition {
+            Condition::Accumulate(accumulate) => Some(accumulate.alias.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[derive(Default)]
+struct Collector {
+    rule_id: RuleId,
+    /// Accumulate aliases of the current rule, which are not fact fields
+    bound: BTreeSet<String>,
+    fields: BTreeMap<String, ReferencedField>,
+}
+
+impl Collector {
+    fn entry(&mut self, field: &str) -> &mut ReferencedField {
+        let entry = self
+            .fields
+            .entry(field.to_string())
+            .or_insert_with(|| ReferencedField::new(field));
+        entry.rule_ids.insert(self.rule_id);
+        entry
+    }
+
+    fn read(&mut self, field: &str) {
+        if !self.bound.contains(field) {
+            self.entry(field).read = true;
+        }
+    }
+
+    fn written(&mut self, field: &str) {
+        self.entry(field).written = true;
+    }
+
+    fn condition(&mut self, condition: &Condition) {
+        match condition {
+            Condition::Simple { field, .. } if self.bound.contains(field) => {}
+            Condition::Simple { field, operator, .. } => {
+                let entry = self.entry(field);
+                entry.read = true;
+                if !entry.operators.contains(operator) {
+                    entry.operators.push(operator.clone());
+                }
+            }
+            Condition::Complex { conditions, .. }
+            | Condition::And { conditions }
+            | Condition::Or { conditions } => {
+                for condition in conditions {
+                    self.condition(condition);
+                }
+            }
+            Condition::Aggregation(aggregation) => {
+                let entry = self.entry(&aggregation.source_field);
+                entry.read = true;
+                if !entry.aggregations.contains(&aggregation.aggregation_type) {
+                    entry.aggregations.push(aggregation.aggregation_type.clone());
+                }
+                for field in &aggregation.group_by {
+                    self.read(field);
+                }
+            }
+            Condition::Stream(stream) => {
+                match &stream.aggregation {
+                    StreamAggregation::Sum { field }
+                    | StreamAggregation::Average { field }
+                    | StreamAggregation::Min { field }
+                    | StreamAggregation::Max { field }
+                    | StreamAggregation::Distinct { field }
+                    | StreamAggregation::First { field }
+                    | StreamAggregation::Last { field } => self.read(field),
+                    StreamAggregation::Count
+                    | StreamAggregation::Rate { .. }
+                    | StreamAggregation::Custom { .. } => {}
+                }
+                if let Some(filter) = &stream.filter {
+                    self.condition(filter);
+                }
+            }
+            Condition::Sequence(sequence) => {
+                for field in &sequence.correlate_by {
+                    self.read(field);
+                }
+                for step in &sequence.steps {
+                    self.condition(step);
+                }
+            }
+            Condition::Accumulate(accumulate) => {
+                for field in &accumulate.correlate_by {
+                    self.read(field);
+                }
+                self.condition(&accumulate.source);
+                match &accumulate.accumulator {
+                    Accumulator::Collect => {}
+                    Accumulator::CollectField { field } | Accumulator::Calculator { field, .. } => {
+                        self.read(field)
+                    }
+                    Accumulator::Aggregate { aggregation_type, field } => {
+                        let entry = self.entry(field);
+                        entry.read = true;
+                        if !entry.aggregations.contains(aggregation_type) {
+                            entry.aggregations.push(aggregation_type.clone());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn action(&mut self, action: &ActionType) {
+        match action {
+            ActionType::SetField { field, .. } => self.written(field),
+            ActionType::IncrementField { field, .. }
+            | ActionType::AppendToArray { field, .. }
+            | ActionType::MergeObject { field, .. } => {
+                self.read(field);
+                self.written(field);
+            }
+            ActionType::CreateFact { data } => {
+                for field in data.fields.keys() {
+                    self.written(field);
+                }
+            }
+            ActionType::CallCalculator { input_mapping, output_field, .. } => {
+                for mapping in input_mapping.values() {
+                    // `@name` reads a global rather than a fact field
+                    if parse_global_reference(mapping).is_some() {
+                        continue;
+                    }
+                    // `table[field]` reads the fact field keying the reference table
+                    let field =
+                        parse_table_reference(mapping).map_or(mapping.as_str(), |(_, field)| field);
+                    self.read(field);
+                }
+                self.written(output_field);
+            }
+            ActionType::Formula { expression, output_field } => {
+                for field in formula_fields(expression) {
+                    self.read(field);
+                }
+                self.written(output_field);
+            }
+            ActionType::UpdateFact { fact_id_field, updates } => {
+                self.read(fact_id_field);
+                for field in updates.keys() {
+                    self.written(field);
+                }
+            }
+            ActionType::DeleteFact { fact_id_field } => self.read(fact_id_field),
+            ActionType::Log { .. }
+            | ActionType::TriggerAlert { .. }
+            | ActionType::SendNotification { .. }
+            | ActionType::CallWebhook { .. } => {}
+        }
+    }
+}
+
+/// Field operands of a formula expression, which is a single operand or `a OP b`
+fn formula_fields(expression: &str) -> Vec<&str> {
+    let expression = expression.trim();
+    let operands = [" + ", " - ", " * ", " / ", " % "]
+        .iter()
+        .find_map(|op| expression.split_once(op))
+        .map_or_else(
+            || vec![expression],
+            |(left, right)| vec![left.trim(), right.trim()],
+        );
+    operands.into_iter().filter(|operand| is_field_operand(operand)).collect()
+}
+
+fn is_field_operand(operand: &str) -> bool {
+    !operand.is_empty()
+        && operand.parse::<f64>().is_err()
+        && !operand.starts_with('"')
+        && operand != "true"
+        && operand != "false"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Action, AggregationCondition, FactValue};
+    use std::collections::HashMap;
+
+    fn rule(id: u64, conditions: Vec<Condition>, actions: Vec<ActionType>) -> Rule {
+        Rule {
+            id,
+            name: format!("Rule {id}"),
+            conditions,
+            actions: actions.into_iter().map(|action_type| Action { action_type }).collect(),
+            metadata: Default::default(),
+        }
+    }
+
+    fn simple(field: &str, operator: Operator) -> Condition {
+        Condition::Simple { field: field.to_string(), operator, value: FactValue::Integer(1) }
+    }
+
+    fn find<'a>(fields: &'a [ReferencedField], name: &str) -> &'a ReferencedField {
+        fields.iter().find(|field| field.field == name).unwrap()
+    }
+
+    #[test]
+    fn test_condition_operators_are_merged_across_rules() {
+        let rules = vec![
+            rule(
+                1,
+                vec![
+                    simple("amount", Operator::GreaterThan),
+                    Condition::Or {
+                        conditions: vec![
+                            simple("status", Operator::Equal),
+                            simple("amount", Operator::LessThan),
+                        ],
+                    },
+                ],
+                vec![],
+            ),
+            rule(2, vec![simple("amount", Operator::GreaterThan)], vec![]),
+        ];
+
+        let fields = referenced_fields(&rules);
+        let names: Vec<&str> = fields.iter().map(|field| field.field.as_str()).collect();
+        assert_eq!(names, vec!["amount", "status"]);
+
+        let amount = find(&fields, "amount");
+        assert_eq!(
+            amount.operators,
+            vec![Operator::GreaterThan, Operator::LessThan]
+        );
+        assert_eq!(amount.rule_ids, BTreeSet::from([1, 2]));
+        assert!(amount.read && !amount.written);
+    }
+
+    #[test]
+    fn test_aggregations_and_actions_are_included() {
+        let rules = vec![rule(
+            1,
+            vec![Condition::Aggregation(AggregationCondition {
+                aggregation_type: AggregationType::Sum,
+                source_field: "hours".to_string(),
+                group_by: vec!["employee_id".to_string()],
+                having: Some(Box::new(simple("total_hours", Operator::GreaterThan))),
+                alias: "total_hours".to_string(),
+                window: None,
+            })],
+            vec![
+                ActionType::Formula {
+                    expression: "hours * rate".to_string(),
+                    output_field: "pay".to_string(),
+                },
+                ActionType::IncrementField {
+                    field: "overtime_count".to_string(),
+                    increment: FactValue::Integer(1),
+                },
+                ActionType::CallCalculator {
+                    calculator_name: "tax".to_string(),
+                    input_mapping: HashMap::from([("amount".to_string(), "pay".to_string())]),
+                    output_field: "tax_due".to_string(),
+                },
+            ],
+        )];
+
+        let fields = referenced_fields(&rules);
+        let names: Vec<&str> = fields.iter().map(|field| field.field.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["employee_id", "hours", "overtime_count", "pay", "rate", "tax_due"]
+        );
+
+        assert_eq!(
+            find(&fields, "hours").aggregations,
+            vec![AggregationType::Sum]
+        );
+        let pay = find(&fields, "pay");
+        assert!(pay.read && pay.written);
+        let overtime = find(&fields, "overtime_count");
+        assert!(overtime.read && overtime.written);
+        let tax_due = find(&fields, "tax_due");
+        assert!(!tax_due.read && tax_due.written);
+    }
+
+    #[test]
+    fn test_formula_literals_are_not_fields() {
+        assert_eq!(formula_fields("amount * 1.2"), vec!["amount"]);
+        assert_eq!(formula_fields("price + tax"), vec!["price", "tax"]);
+        assert_eq!(formula_fields("42"), Vec::<&str>::new());
+        assert_eq!(formula_fields("total"), vec!["total"]);
+    }
+}
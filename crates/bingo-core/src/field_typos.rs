@@ -0,0 +1,346 @@
+//! Field-name typo detection
+//!
+//! A condition on a misspelt field (`order_totl` for `order_total`) is valid, compiles
+//! and silently never matches. The `FieldTypoAnalyzer` compares the fields rules read
+//! with the fields actually seen in facts and warns about every rule field that is
+//! never seen but is a small edit away from one that is.
+//!
+//! Fields that are never seen and resemble no observed field are not flagged, as
+//! optional fields legitimately go missing from a sample of facts.
+
+use crate::field_references::bound_aliases;
+use crate::types::{Accumulator, Condition, Fact, Rule, RuleId, StreamAggregation};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// Field names seen in facts and how many facts carried each
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FieldObservations {
+    counts: BTreeMap<String, usize>,
+}
+
+impl FieldObservations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Observations of the fields of the given facts
+    pub fn from_facts<'a>(facts: impl IntoIterator<Item = &'a Fact>) -> Self {
+        let mut observations = Self::new();
+        for fact in facts {
+            observations.observe(fact);
+        }
+        observations
+    }
+
+    /// Record the fields of a fact
+    pub fn observe(&mut self, fact: &Fact) {
+        for field in fact.data.fields.keys() {
+            *self.counts.entry(field.clone()).or_default() += 1;
+        }
+    }
+
+    /// Whether any fact carried the field
+    pub fn contains(&self, field: &str) -> bool {
+        self.counts.contains_key(field)
+    }
+
+    /// Number of facts that carried the field
+    pub fn count(&self, field: &str) -> usize {
+        self.counts.get(field).copied().unwrap_or(0)
+    }
+
+    /// Observed field names in order
+    pub fn fields(&self) -> impl Iterator<Item = &str> {
+        self.counts.keys().map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+}
+
+/// A rule field that is never seen in facts but closely resembles one that is
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldTypoWarning {
+    pub rule_id: RuleId,
+    pub rule_name: String,
+    /// Field the rule reads
+    pub field: String,
+    /// Observed field the rule most likely meant
+    pub suggestion: String,
+    /// Edit distance between the two
+    pub distance: usize,
+}
+
+impl fmt::Display for FieldTypoWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "rule {} ({}): field '{}' is never seen in facts, did you mean '{}'?",
+            self.rule_id, self.rule_name, self.field, self.suggestion
+        )
+    }
+}
+
+/// Flags rule fields that look like misspellings of observed fact fields
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldTypoAnalyzer {
+    /// Largest edit distance reported as a near-miss
+    pub max_distance: usize,
+    /// Shortest field name checked; short names are too close to each other to tell
+    /// a typo from a different field
+    pub min_field_length: usize,
+}
+
+impl Default for FieldTypoAnalyzer {
+    fn default() -> Self {
+        Self { max_distance: 2, min_field_length: 4 }
+    }
+}
+
+impl FieldTypoAnalyzer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Warnings for every near-miss field in the rules, in rule order
+    pub fn analyze(&self, rules: &[Rule], observed: &FieldObservations) -> Vec<FieldTypoWarning> {
+        let mut warnings = Vec::new();
+        for rule in rules {
+            for field in rule_fields(rule) {
+                if observed.contains(&field) || field.chars().count() < self.min_field_length {
+                    continue;
+                }
+                if let Some((suggestion, distance)) = self.closest_field(&field, observed) {
+                    warnings.push(FieldTypoWarning {
+                        rule_id: rule.id,
+                        rule_name: rule.name.clone(),
+                        field,
+                        suggestion,
+                        distance,
+                    });
+                }
+            }
+        }
+        warnings
+    }
+
+    /// Closest observed field within reach, preferring the most frequently observed
+    fn closest_field(&self, field: &str, observed: &FieldObservations) -> Option<(String, usize)> {
+        // A third of the name may differ at most, so short names need closer matches
+        let limit = self.max_distance.min(field.chars().count() / 3).max(1);
+        observed
+            .fields()
+            .map(|candidate| (candidate, edit_distance(field, candidate)))
+            .filter(|(_, distance)| *distance <= limit)
+            .min_by(|(a, a_distance), (b, b_distance)| {
+                a_distance
+                    .cmp(b_distance)
+                    .then_with(|| observed.count(b).cmp(&observed.count(a)))
+                    .then_with(|| a.cmp(b))
+            })
+            .map(|(candidate, distance)| (candidate.to_string(), distance))
+    }
+}
+
+/// Fact fields a rule reads, without duplicates
+///
+/// Aggregation and stream `having` clauses test the computed alias rather than fact
+/// fields, so they are left out, as are tests of values accumulate conditions bind.
+pub fn rule_fields(rule: &Rule) -> BTreeSet<String> {
+    let mut fields = BTreeSet::new();
+    for condition in &rule.conditions {
+        collect_condition_fields(condition, &mut fields);
+    }
+    for alias in bound_aliases(rule) {
+        fields.remove(&alias);
+    }
+    fields
+}
+
+fn collect_condition_fields(condition: &Condition, fields: &mut BTreeSet<String>) {
+    match condition {
+        Condition::Simple { field, .. } => {
+            fields.insert(field.clone());
+        }
+        Condition::Complex { conditions, .. }
+        | Condition::And { conditions }
+        | Condition::Or { conditions } => {
+            for condition in conditions {
+                collect_condition_fields(condition, fields);
+            }
+        }
+        Condition::Aggregation(aggregation) => {
+            fields.insert(aggregation.source_field.clone());
+            fields.extend(aggregation.group_by.iter().cloned());
+        }
+        Condition::Stream(stream) => {
+            match &stream.aggregation {
+                StreamAggregation::Sum { field }
+                | StreamAggregation::Average { field }
+                | StreamAggregation::Min { field }
+                | StreamAggregation::Max { field }
+                | StreamAggregation::Distinct { field }
+                | StreamAggregation::First { field }
+                | StreamAggregation::Last { field } => {
+                    fields.insert(field.clone());
+                }
+                StreamAggregation::Count
+                | StreamAggregation::Rate { .. }
+                | StreamAggregation::Custom { .. } => {}
+            }
+            if let Some(filter) = &stream.filter {
+                collect_condition_fields(filter, fields);
+            }
+        }
+        Condition::Sequence(sequence) => {
+            fields.extend(sequence.correlate_by.iter().cloned());
+            for step in &sequence.steps {
+                collect_condition_fields(step, fields);
+            }
+        }
+        Condition::Accumulate(accumulate) => {
+            fields.extend(accumulate.correlate_by.iter().cloned());
+            collect_condition_fields(&accumulate.source, fields);
+            match &accumulate.accumulator {
+                Accumulator::Collect => {}
+                Accumulator::CollectField { field }
+                | Accumulator::Aggregate { field, .. }
+                | Accumulator::Calculator { field, .. } => {
+                    fields.insert(field.clone());
+                }
+            }
+        }
+    }
+}
+
+/// Edit distance counting insertions, deletions, substitutions and swaps of adjacent
+/// characters as one edit each
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let width = b.len() + 1;
+    let mut table = vec![0usize; (a.len() + 1) * width];
+    for (i, row) in table.chunks_mut(width).enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in table[..width].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let substitution = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (table[(i - 1) * width + j] + 1)
+                .min(table[i * width + j - 1] + 1)
+                .min(table[(i - 1) * width + j - 1] + substitution);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(table[(i - 2) * width + j - 2] + 1);
+            }
+            table[i * width + j] = distance;
+        }
+    }
+    table[a.len() * width + b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Action, ActionType, AggregationCondition, AggregationType, FactData};
+    use crate::types::{FactValue, Operator};
+    use std::collections::HashMap;
+
+    fn fact(id: u64, fields: &[&str]) -> Fact {
+        let fields: HashMap<String, FactValue> =
+            fields.iter().map(|field| (field.to_string(), FactValue::Integer(1))).collect();
+        Fact::new(id, FactData { fields })
+    }
+
+    fn rule(id: u64, conditions: Vec<Condition>) -> Rule {
+        Rule {
+            id,
+            name: format!("Rule {id}"),
+            conditions,
+            actions: vec![Action {
+                action_type: ActionType::Log { message: "matched".to_string() },
+            }],
+            metadata: Default::default(),
+        }
+    }
+
+    fn simple(field: &str) -> Condition {
+        Condition::Simple {
+            field: field.to_string(),
+            operator: Operator::Equal,
+            value: FactValue::Integer(1),
+        }
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("order_total", "order_total"), 0);
+        assert_eq!(edit_distance("order_totl", "order_total"), 1);
+        assert_eq!(edit_distance("ordre_total", "order_total"), 1);
+        assert_eq!(edit_distance("Status", "status"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn test_near_misses_are_flagged() {
+        let observed = FieldObservations::from_facts(&[
+            fact(1, &["order_total", "customer_id", "status"]),
+            fact(2, &["order_total", "status"]),
+        ]);
+        let rules = vec![
+            rule(1, vec![simple("order_totl"), simple("status")]),
+            rule(
+                2,
+                vec![Condition::Or { conditions: vec![simple("Status"), simple("region")] }],
+            ),
+        ];
+
+        let warnings = FieldTypoAnalyzer::new().analyze(&rules, &observed);
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(
+            (warnings[0].field.as_str(), warnings[0].suggestion.as_str()),
+            ("order_totl", "order_total")
+        );
+        assert_eq!(warnings[1].rule_id, 2);
+        assert_eq!(warnings[1].suggestion, "status");
+        assert_eq!(
+            warnings[0].to_string(),
+            "rule 1 (Rule 1): field 'order_totl' is never seen in facts, did you mean 'order_total'?"
+        );
+    }
+
+    #[test]
+    fn test_short_and_distant_fields_are_not_flagged() {
+        let observed = FieldObservations::from_facts(&[fact(1, &["ip", "amount", "customer"])]);
+        let rules = vec![rule(1, vec![simple("id"), simple("amt"), simple("supplier")])];
+        assert!(FieldTypoAnalyzer::new().analyze(&rules, &observed).is_empty());
+    }
+
+    #[test]
+    fn test_aggregation_fields_are_checked_without_having() {
+        let aggregation = Condition::Aggregation(AggregationCondition {
+            aggregation_type: AggregationType::Sum,
+            source_field: "hours_workd".to_string(),
+            group_by: vec!["employee_id".to_string()],
+            having: Some(Box::new(simple("total_hours"))),
+            alias: "total_hours".to_string(),
+            window: None,
+        });
+        let fields = rule_fields(&rule(1, vec![aggregation.clone()]));
+        assert_eq!(
+            fields.into_iter().collect::<Vec<_>>(),
+            vec!["employee_id".to_string(), "hours_workd".to_string()]
+        );
+
+        let observed = FieldObservations::from_facts(&[fact(1, &["hours_worked", "employee_id"])]);
+        let warnings = FieldTypoAnalyzer::new().analyze(&[rule(1, vec![aggregation])], &observed);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].suggestion, "hours_worked");
+    }
+}
@@ -0,0 +1,284 @@
+//! Read-only follower engines for scale-out querying
+//!
+//! A primary `BingoEngine` serialises mutations behind its locks, so explain, query
+//! and statistics requests queue up behind fact processing under mixed workloads.
+//! `BingoEngine::snapshot` captures the compiled network, the rules and the stored
+//! facts once into an immutable `EngineSnapshot`; any number of `FollowerEngine`s can
+//! then serve reads from that snapshot concurrently without touching the primary.
+//!
+//! Followers are cheap to clone because they only hold an `Arc` to the snapshot.
+//! They never see facts processed by the primary after the snapshot was taken; call
+//! `FollowerEngine::refresh` to move a follower to a newer snapshot.
+//!
+//! Snapshots also checkpoint sessions: `EngineSnapshot::to_bytes` encodes one in a
+//! compact binary format that `EngineSnapshot::from_bytes` decodes on another pod or
+//! offline, and `BingoEngine::restore` loads it into an engine. The encoding holds
+//! the rules, the stored facts and the engine settings they are processed under.
+//! Alpha, beta, aggregation and window memories are not encoded: they only hold what
+//! the stored facts imply, and the restored network rebuilds them from the facts.
+//! Rule activations fire within the batch that causes them, so there is no pending
+//! agenda to carry over.
+
+use crate::error::{BingoError, BingoResult};
+use crate::fact_id_strategy::FactIdStrategy;
+use crate::fact_query::{FactPage, FactPageRequest, FactQuery};
+use crate::fact_store::arena_store::ArenaFactStore;
+use crate::field_collisions::FieldCollisionPolicy;
+use crate::non_finite::NonFinitePolicy;
+use crate::rete_network::{ForwardChaining, ReteNetwork, RuleExplanation};
+use crate::types::{EngineStats, Fact, FactId, FactValue, OverflowPolicy, Rule, RuleId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+use tracing::debug;
+
+/// Version of the binary snapshot encoding, bumped on incompatible changes
+const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
+/// Immutable point-in-time copy of an engine's rules, network and facts
+#[derive(Debug)]
+pub struct EngineSnapshot {
+    rules: Vec<Rule>,
+    rete_network: ReteNetwork,
+    fact_store: ArenaFactStore,
+    stats: EngineStats,
+    taken_at: DateTime<Utc>,
+}
+
+impl EngineSnapshot {
+    /// Build a snapshot from state copied out of a primary engine
+    pub fn new(
+        rules: Vec<Rule>,
+        rete_network: ReteNetwork,
+        facts: Vec<Fact>,
+        stats: EngineStats,
+    ) -> Self {
+        let fact_store = ArenaFactStore::with_capacity(facts.len());
+        for fact in facts {
+            fact_store.insert_with_id(fact);
+        }
+
+        debug!(
+            rules = rules.len(),
+            facts = fact_store.len(),
+            "Captured engine snapshot"
+        );
+
+        Self { rules, rete_network, fact_store, stats, taken_at: Utc::now() }
+    }
+
+    /// Continue the fact ID sequence where the primary's store left it
+    pub(crate) fn with_fact_ids(self, strategy: FactIdStrategy, next_id: FactId) -> Self {
+        // Strategies were validated when the primary accepted them
+        let _ = self.fact_store.set_id_strategy(strategy);
+        self.fact_store.resume_sequential_ids(next_id);
+        self
+    }
+
+    /// When the snapshot was taken
+    pub fn taken_at(&self) -> DateTime<Utc> {
+        self.taken_at
+    }
+
+    /// Rules as of the snapshot
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// Copies of the facts stored as of the snapshot
+    pub fn facts(&self) -> Vec<Fact> {
+        self.fact_store.iter()
+    }
+
+    pub(crate) fn settings(&self) -> SnapshotSettings {
+        SnapshotSettings {
+            forward_chaining: self.rete_network.forward_chaining(),
+            overflow_policy: self.rete_network.overflow_policy(),
+            non_finite_policy: self.rete_network.non_finite_policy(),
+            fact_id_strategy: self.fact_store.id_strategy(),
+            next_fact_id: self.fact_store.next_sequential_id(),
+            field_collision_policy: self.rete_network.field_collision_policy(),
+            rule_salience: self.rete_network.saliences().clone(),
+            disabled_rule_groups: self.rete_network.disabled_rule_groups().clone(),
+        }
+    }
+
+    /// Encode the snapshot in a compact binary format for checkpointing or transfer
+    pub fn to_bytes(&self) -> BingoResult<Vec<u8>> {
+        let image = SnapshotImage {
+            format_version: SNAPSHOT_FORMAT_VERSION,
+            rules: self.rules.clone(),
+            facts: self.facts(),
+            settings: self.settings(),
+            stats: self.stats.clone(),
+            taken_at: self.taken_at,
+        };
+        let mut bytes = Vec::new();
+        ciborium::into_writer(&image, &mut bytes)
+            .map_err(|e| BingoError::serialization("EngineSnapshot", "encode", e.to_string()))?;
+        Ok(bytes)
+    }
+
+    /// Decode a snapshot encoded by [`to_bytes`](Self::to_bytes), recompiling its rules
+    pub fn from_bytes(bytes: &[u8]) -> BingoResult<Self> {
+        let image: SnapshotImage = ciborium::from_reader(bytes)
+            .map_err(|e| BingoError::serialization("EngineSnapshot", "decode", e.to_string()))?;
+        if image.format_version != SNAPSHOT_FORMAT_VERSION {
+            return Err(BingoError::serialization(
+                "EngineSnapshot",
+                "decode",
+                format!(
+                    "Unsupported snapshot format version {} (expected {SNAPSHOT_FORMAT_VERSION})",
+                    image.format_version
+                ),
+            ));
+        }
+
+        let mut rete_network = ReteNetwork::new();
+        image.settings.apply_to(&mut rete_network);
+        for rule in &image.rules {
+            rete_network.add_rule(rule.clone())?;
+        }
+
+        let settings = image.settings;
+        let snapshot = Self::new(image.rules, rete_network, image.facts, image.stats)
+            .with_fact_ids(settings.fact_id_strategy, settings.next_fact_id);
+        Ok(Self { taken_at: image.taken_at, ..snapshot })
+    }
+}
+
+/// Engine settings that decide how a snapshot's facts are processed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct SnapshotSettings {
+    pub forward_chaining: Option<ForwardChaining>,
+    pub overflow_policy: OverflowPolicy,
+    pub non_finite_policy: NonFinitePolicy,
+    pub fact_id_strategy: FactIdStrategy,
+    pub next_fact_id: FactId,
+    #[serde(default)]
+    pub field_collision_policy: FieldCollisionPolicy,
+    #[serde(default)]
+    pub rule_salience: HashMap<RuleId, i32>,
+    #[serde(default)]
+    pub disabled_rule_groups: BTreeSet<String>,
+}
+
+impl SnapshotSettings {
+    /// Apply the settings held by the RETE network
+    pub fn apply_to(&self, rete_network: &mut ReteNetwork) {
+        rete_network.set_forward_chaining(self.forward_chaining);
+        rete_network.set_overflow_policy(self.overflow_policy);
+        rete_network.set_non_finite_policy(self.non_finite_policy);
+        rete_network.set_field_collision_policy(self.field_collision_policy);
+        for (&rule_id, &salience) in &self.rule_salience {
+            rete_network.set_salience(rule_id, salience);
+        }
+        for group in &self.disabled_rule_groups {
+            rete_network.set_rule_group_enabled(group.clone(), false);
+        }
+    }
+}
+
+/// Encoded form of an [`EngineSnapshot`]
+#[derive(Serialize, Deserialize)]
+struct SnapshotImage {
+    format_version: u32,
+    rules: Vec<Rule>,
+    facts: Vec<Fact>,
+    settings: SnapshotSettings,
+    stats: EngineStats,
+    taken_at: DateTime<Utc>,
+}
+
+/// Read-only replica of an engine serving explain, query and statistics requests
+#[derive(Debug, Clone)]
+pub struct FollowerEngine {
+    snapshot: Arc<EngineSnapshot>,
+}
+
+impl FollowerEngine {
+    /// Create a follower reading from `snapshot`
+    pub fn new(snapshot: Arc<EngineSnapshot>) -> Self {
+        Self { snapshot }
+    }
+
+    /// Switch this follower to a newer snapshot of the primary
+    pub fn refresh(&mut self, snapshot: Arc<EngineSnapshot>) {
+        self.snapshot = snapshot;
+    }
+
+    /// Snapshot this follower reads from
+    pub fn snapshot(&self) -> &Arc<EngineSnapshot> {
+        &self.snapshot
+    }
+
+    /// Engine statistics as of the snapshot
+    pub fn get_stats(&self) -> EngineStats {
+        self.snapshot.stats.clone()
+    }
+
+    /// Number of rules in the snapshot
+    pub fn rule_count(&self) -> usize {
+        self.snapshot.rules.len()
+    }
+
+    /// Number of facts in the snapshot
+    pub fn fact_count(&self) -> usize {
+        self.snapshot.fact_store.len()
+    }
+
+    /// Rules as of the snapshot
+    pub fn rules(&self) -> &[Rule] {
+        &self.snapshot.rules
+    }
+
+    /// Look up a fact by internal ID
+    pub fn get_fact(&self, fact_id: FactId) -> Option<Fact> {
+        self.snapshot.fact_store.get_fact(fact_id)
+    }
+
+    /// Look up a fact by external ID
+    pub fn lookup_fact_by_id(&self, external_id: &str) -> Option<Fact> {
+        self.snapshot.fact_store.get_by_external_id(external_id)
+    }
+
+    /// Get a specific field value from a fact by external ID
+    pub fn get_field_by_id(&self, external_id: &str, field_name: &str) -> Option<FactValue> {
+        self.snapshot.fact_store.get_field_by_id(external_id, field_name)
+    }
+
+    /// Find facts whose `field` equals `value`
+    pub fn find_by_field(&self, field: &str, value: &FactValue) -> Vec<Fact> {
+        self.snapshot.fact_store.find_by_field(field, value)
+    }
+
+    /// Find facts matching every `(field, value)` pair
+    pub fn find_by_criteria(&self, criteria: &[(String, FactValue)]) -> Vec<Fact> {
+        self.snapshot.fact_store.find_by_criteria(criteria)
+    }
+
+    /// One page of the facts matching `query`, in fact ID order
+    pub fn query_facts(&self, query: &FactQuery, page: FactPageRequest) -> FactPage {
+        self.snapshot.fact_store.find_by_query(query, page)
+    }
+
+    /// Explain which rule conditions a fact satisfies
+    pub fn explain_fact(&self, fact: &Fact) -> BingoResult<Vec<RuleExplanation>> {
+        self.snapshot
+            .rete_network
+            .explain_fact(fact, &self.snapshot.fact_store)
+            .map_err(|e| BingoError::rete_network("explain_fact", e.to_string()))
+    }
+
+    /// Explain which rule conditions a stored fact satisfies, by external ID
+    pub fn explain_fact_by_id(&self, external_id: &str) -> BingoResult<Vec<RuleExplanation>> {
+        let fact = self.lookup_fact_by_id(external_id).ok_or_else(|| {
+            BingoError::fact_store(
+                "explain_fact_by_id",
+                format!("Fact '{external_id}' not found in snapshot"),
+            )
+        })?;
+        self.explain_fact(&fact)
+    }
+}
@@ -0,0 +1,358 @@
+//! Golden-file regression testing for rule outputs
+//!
+//! A `GoldenHarness` runs a fixed fact corpus through a ruleset on a fresh engine
+//! and compares the resulting `RuleExecutionResult`s with a golden file committed
+//! next to the rules. Results are rendered one per line, ordered by fact and rule
+//! ID with object keys sorted, so the files are stable across runs and review well
+//! as plain text diffs. A mismatch reports a line diff of expected and actual output.
+//!
+//! Golden files are (re)written instead of compared when the harness is created in
+//! update mode, or when the `BINGO_UPDATE_GOLDEN` environment variable is set, so a
+//! deliberate behaviour change is accepted by rerunning the tests once.
+
+use crate::engine::BingoEngine;
+use crate::error::{BingoError, BingoResult};
+use crate::rete_nodes::RuleExecutionResult;
+use crate::types::{Fact, Rule};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Environment variable that switches harnesses to update mode
+pub const UPDATE_GOLDEN_ENV: &str = "BINGO_UPDATE_GOLDEN";
+
+/// File extension of golden files
+pub const GOLDEN_EXTENSION: &str = "golden";
+
+/// Lines of context kept around each change in a diff
+const DIFF_CONTEXT: usize = 2;
+
+/// Outcome of checking one case against its golden file
+#[derive(Debug, Clone, PartialEq)]
+pub enum GoldenOutcome {
+    /// Output matched the golden file
+    Matched,
+    /// The golden file was written in update mode
+    Updated { path: PathBuf },
+    /// No golden file exists yet
+    Missing { path: PathBuf, actual: String },
+    /// Output differs from the golden file
+    Mismatch { path: PathBuf, diff: GoldenDiff },
+}
+
+impl GoldenOutcome {
+    /// Whether the check passed
+    pub fn is_success(&self) -> bool {
+        matches!(self, GoldenOutcome::Matched | GoldenOutcome::Updated { .. })
+    }
+}
+
+impl fmt::Display for GoldenOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GoldenOutcome::Matched => write!(f, "output matches golden file"),
+            GoldenOutcome::Updated { path } => write!(f, "updated {}", path.display()),
+            GoldenOutcome::Missing { path, actual } => write!(
+                f,
+                "golden file {} does not exist; rerun with {UPDATE_GOLDEN_ENV}=1 to create it\n\
+                 actual output:\n{actual}",
+                path.display()
+            ),
+            GoldenOutcome::Mismatch { path, diff } => write!(
+                f,
+                "output differs from {} (- expected, + actual); rerun with \
+                 {UPDATE_GOLDEN_ENV}=1 if the change is intended\n{diff}",
+                path.display()
+            ),
+        }
+    }
+}
+
+/// One line of a golden diff
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    /// Line present in both outputs
+    Context(String),
+    /// Line only in the golden file
+    Expected(String),
+    /// Line only in the actual output
+    Actual(String),
+    /// Unchanged lines left out of the diff
+    Skipped(usize),
+}
+
+/// Line diff between golden and actual output, trimmed to the changed regions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GoldenDiff {
+    pub lines: Vec<DiffLine>,
+}
+
+impl GoldenDiff {
+    /// Diff two outputs line by line
+    pub fn between(expected: &str, actual: &str) -> Self {
+        let expected: Vec<&str> = expected.lines().collect();
+        let actual: Vec<&str> = actual.lines().collect();
+
+        // Longest common subsequence table, filled from the end
+        let mut lcs = vec![vec![0usize; actual.len() + 1]; expected.len() + 1];
+        for i in (0..expected.len()).rev() {
+            for j in (0..actual.len()).rev() {
+                lcs[i][j] = if expected[i] == actual[j] {
+                    lcs[i + 1][j + 1] + 1
+                } else {
+                    lcs[i + 1][j].max(lcs[i][j + 1])
+                };
+            }
+        }
+
+        let mut full = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < expected.len() || j < actual.len() {
+            if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+                full.push(DiffLine::Context(expected[i].to_string()));
+                i += 1;
+                j += 1;
+            } else if i < expected.len() && (j == actual.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+                full.push(DiffLine::Expected(expected[i].to_string()));
+                i += 1;
+            } else {
+                full.push(DiffLine::Actual(actual[j].to_string()));
+                j += 1;
+            }
+        }
+
+        Self { lines: Self::trim_context(full) }
+    }
+
+    /// Whether the outputs were identical
+    pub fn is_empty(&self) -> bool {
+        self.lines
+            .iter()
+            .all(|line| matches!(line, DiffLine::Context(_) | DiffLine::Skipped(_)))
+    }
+
+    /// Number of lines only in the golden file and only in the actual output
+    pub fn change_counts(&self) -> (usize, usize) {
+        self.lines.iter().fold((0, 0), |(removed, added), line| match line {
+            DiffLine::Expected(_) => (removed + 1, added),
+            DiffLine::Actual(_) => (removed, added + 1),
+            _ => (removed, added),
+        })
+    }
+
+    /// Replace runs of unchanged lines far from any change with a `Skipped` marker
+    fn trim_context(full: Vec<DiffLine>) -> Vec<DiffLine> {
+        let changed: Vec<usize> = full
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| !matches!(line, DiffLine::Context(_)))
+            .map(|(index, _)| index)
+            .collect();
+        let near_change =
+            |index: usize| changed.iter().any(|changed| changed.abs_diff(index) <= DIFF_CONTEXT);
+
+        let mut trimmed = Vec::new();
+        let mut skipped = 0;
+        for (index, line) in full.into_iter().enumerate() {
+            if matches!(line, DiffLine::Context(_)) && !near_change(index) {
+                skipped += 1;
+                continue;
+            }
+            if skipped > 0 {
+                trimmed.push(DiffLine::Skipped(skipped));
+                skipped = 0;
+            }
+            trimmed.push(line);
+        }
+        if skipped > 0 {
+            trimmed.push(DiffLine::Skipped(skipped));
+        }
+        trimmed
+    }
+}
+
+impl fmt::Display for GoldenDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.lines {
+            match line {
+                DiffLine::Context(text) => writeln!(f, "  {text}")?,
+                DiffLine::Expected(text) => writeln!(f, "- {text}")?,
+                DiffLine::Actual(text) => writeln!(f, "+ {text}")?,
+                DiffLine::Skipped(count) => writeln!(f, "  ... {count} unchanged")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Runs rulesets over fact corpora and checks the results against golden files
+#[derive(Debug, Clone)]
+pub struct GoldenHarness {
+    directory: PathBuf,
+    update: bool,
+}
+
+impl GoldenHarness {
+    /// Harness reading golden files from `directory`
+    ///
+    /// Update mode is enabled when `BINGO_UPDATE_GOLDEN` is set to anything but
+    /// `0` or an empty value.
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        let update = std::env::var(UPDATE_GOLDEN_ENV)
+            .map(|value| !value.is_empty() && value != "0")
+            .unwrap_or(false);
+        Self { directory: directory.into(), update }
+    }
+
+    /// Force update mode on or off regardless of the environment
+    pub fn with_update(mut self, update: bool) -> Self {
+        self.update = update;
+        self
+    }
+
+    /// Path of the golden file for a case
+    pub fn golden_path(&self, case: &str) -> PathBuf {
+        self.directory.join(format!("{case}.{GOLDEN_EXTENSION}"))
+    }
+
+    /// Run facts through the rules on a fresh engine
+    pub fn run(&self, rules: &[Rule], facts: Vec<Fact>) -> BingoResult<Vec<RuleExecutionResult>> {
+        let engine = BingoEngine::new()?;
+        engine.add_rules(rules.to_vec())?;
+        engine.process_facts(facts)
+    }
+
+    /// Run a case and compare its output with the golden file
+    pub fn check(
+        &self,
+        case: &str,
+        rules: &[Rule],
+        facts: Vec<Fact>,
+    ) -> BingoResult<GoldenOutcome> {
+        let actual = render_results(&self.run(rules, facts)?)?;
+        self.check_rendered(case, &actual)
+    }
+
+    /// Compare already rendered output with the golden file of a case
+    pub fn check_rendered(&self, case: &str, actual: &str) -> BingoResult<GoldenOutcome> {
+        let path = self.golden_path(case);
+
+        if self.update {
+            std::fs::create_dir_all(&self.directory)
+                .and_then(|_| std::fs::write(&path, actual))
+                .map_err(|e| golden_io_error("write", &path, e))?;
+            return Ok(GoldenOutcome::Updated { path });
+        }
+
+        let expected = match std::fs::read_to_string(&path) {
+            Ok(expected) => expected,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(GoldenOutcome::Missing { path, actual: actual.to_string() });
+            }
+            Err(e) => return Err(golden_io_error("read", &path, e)),
+        };
+
+        let diff = GoldenDiff::between(&expected, actual);
+        if diff.is_empty() {
+            Ok(GoldenOutcome::Matched)
+        } else {
+            Ok(GoldenOutcome::Mismatch { path, diff })
+        }
+    }
+
+    /// Run a case and panic with a readable diff unless it matches its golden file
+    pub fn assert_golden(&self, case: &str, rules: &[Rule], facts: Vec<Fact>) {
+        match self.check(case, rules, facts) {
+            Ok(outcome) if outcome.is_success() => {}
+            Ok(outcome) => panic!("golden case '{case}' failed: {outcome}"),
+            Err(e) => panic!("golden case '{case}' could not run: {e}"),
+        }
+    }
+}
+
+/// Render results as stable golden file text
+///
+/// Each result becomes one `rule <id> fact <id>: <actions>` line with actions as
+/// JSON with sorted keys; lines are ordered by fact ID, then rule ID, keeping the
+/// firing order of a rule that fired more than once for the same fact.
+pub fn render_results(results: &[RuleExecutionResult]) -> BingoResult<String> {
+    let mut ordered: Vec<&RuleExecutionResult> = results.iter().collect();
+    ordered.sort_by_key(|result| (result.fact_id, result.rule_id));
+
+    let mut rendered = String::new();
+    for result in ordered {
+        // Going through `Value` sorts object keys, so field maps render stably
+        let actions = serde_json::to_value(&result.actions_executed)
+            .map_err(|e| BingoError::serialization("ActionResult", "serialize", e.to_string()))?;
+        rendered.push_str(&format!(
+            "rule {} fact {}: {}\n",
+            result.rule_id, result.fact_id, actions
+        ));
+    }
+    Ok(rendered)
+}
+
+/// Load a fact corpus from a JSON file holding an array of facts
+pub fn load_fact_corpus(path: impl AsRef<Path>) -> BingoResult<Vec<Fact>> {
+    let path = path.as_ref();
+    let contents = std::fs::read_to_string(path).map_err(|e| golden_io_error("read", path, e))?;
+    serde_json::from_str(&contents).map_err(|e| {
+        BingoError::serialization("Fact", "deserialize", format!("{}: {e}", path.display()))
+    })
+}
+
+fn golden_io_error(operation: &str, path: &Path, error: std::io::Error) -> BingoError {
+    BingoError::configuration(
+        "golden.path",
+        &format!("{operation}able golden file"),
+        &path.display().to_string(),
+        format!("Failed to {operation} {}: {error}", path.display()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rete_nodes::ActionResult;
+
+    #[test]
+    fn test_render_is_ordered_and_stable() {
+        let results = vec![
+            RuleExecutionResult {
+                rule_id: 2,
+                fact_id: 5,
+                actions_executed: vec![ActionResult::Logged { message: "b".to_string() }],
+                ..Default::default()
+            },
+            RuleExecutionResult {
+                rule_id: 1,
+                fact_id: 5,
+                actions_executed: vec![ActionResult::FieldSet {
+                    fact_id: 5,
+                    field: "tier".to_string(),
+                    value: crate::types::FactValue::Integer(3),
+                }],
+                ..Default::default()
+            },
+        ];
+
+        assert_eq!(
+            render_results(&results).unwrap(),
+            "rule 1 fact 5: [{\"FieldSet\":{\"fact_id\":5,\"field\":\"tier\",\"value\":{\"Integer\":3}}}]\n\
+             rule 2 fact 5: [{\"Logged\":{\"message\":\"b\"}}]\n"
+        );
+    }
+
+    #[test]
+    fn test_diff_marks_changes_and_skips_distant_context() {
+        let expected = "a\nb\nc\nd\ne\nf\ng\n";
+        let actual = "a\nb\nc\nd\ne\nF\ng\nh\n";
+
+        let diff = GoldenDiff::between(expected, actual);
+        assert_eq!(diff.change_counts(), (1, 2));
+        assert_eq!(
+            diff.to_string(),
+            "  ... 3 unchanged\n  d\n  e\n- f\n+ F\n  g\n+ h\n"
+        );
+        assert!(GoldenDiff::between(expected, expected).is_empty());
+    }
+}
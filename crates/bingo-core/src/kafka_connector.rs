@@ -0,0 +1,636 @@
+//! Kafka source and sink connector
+//!
+//! A [`KafkaConnector`] streams facts through a [`BingoSession`]: it consumes fact
+//! records from input topics, fires the session's rules for each batch, and publishes
+//! every rule execution result as JSON to an output topic.
+//!
+//! Delivery is at-least-once. Offsets are committed to the consumer group only after
+//! the batch's results were published and flushed, so a batch that fails anywhere
+//! before the commit is consumed again after a restart or rebalance, and its results
+//! may be published twice. Consumers must have auto-commit disabled.
+//!
+//! The Kafka client is supplied through [`KafkaConsumer`] and [`KafkaProducer`], e.g.
+//! thin wrappers over an `rdkafka` consumer and producer, so deployments choose the
+//! client library and its security settings. Records are decoded as:
+//!
+//! - [`RecordFormat::Json`]: a JSON object whose members are the fact's fields
+//! - [`RecordFormat::Custom`]: a [`FactDecoder`], e.g. for Avro with a schema registry
+//!
+//! A record key that is valid UTF-8 becomes the fact's external ID. Records that
+//! cannot be decoded go to a dead-letter topic when one is configured and are skipped
+//! otherwise; either way their offsets are committed so they never block a partition.
+//!
+//! [`ProcessingMode::ExactlyOnce`] replaces offset commits with producer transactions.
+//! Each batch's results and dead letters are published in one transaction together
+//! with a [`ConnectorCheckpoint`]: the source offsets after the batch and a snapshot of
+//! the session's engine, written to a checkpoint topic. Consumers of the output topic
+//! reading with `isolation.level=read_committed` see a batch's results only once its
+//! checkpoint is durable. After a crash, a new connector [`resume`](KafkaConnector::resume)s
+//! from the latest checkpoint, restoring the engine and seeking the consumer, so the
+//! replayed records fire every rule exactly once.
+
+use crate::error::{BingoError, BingoResult};
+use crate::follower::EngineSnapshot;
+use crate::rete_nodes::RuleExecutionResult;
+use crate::session::{BingoSession, SessionEvent};
+use crate::types::{Fact, FactData, FactId, FactValue};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+/// Record consumed from a Kafka topic
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KafkaRecord {
+    pub topic: String,
+    pub partition: i32,
+    pub offset: i64,
+    pub key: Option<Vec<u8>>,
+    pub payload: Vec<u8>,
+}
+
+/// Consumer group position of a partition: the offset of the next record to consume
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TopicPartitionOffset {
+    pub topic: String,
+    pub partition: i32,
+    pub offset: i64,
+}
+
+/// Kafka consumer in a consumer group, with auto-commit disabled
+pub trait KafkaConsumer: Send {
+    /// Subscribe to `topics` through the consumer group
+    fn subscribe(&mut self, topics: &[String]) -> Result<(), String>;
+
+    /// Fetch up to `max_records` records, waiting at most `timeout` for the first
+    fn poll(&mut self, max_records: usize, timeout: Duration) -> Result<Vec<KafkaRecord>, String>;
+
+    /// Commit the consumer group's positions synchronously
+    fn commit(&mut self, offsets: &[TopicPartitionOffset]) -> Result<(), String>;
+
+    /// Move the consumer's positions, so the next poll starts at `offsets`
+    ///
+    /// Required by [`KafkaConnector::resume`] only.
+    fn seek(&mut self, offsets: &[TopicPartitionOffset]) -> Result<(), String> {
+        let _ = offsets;
+        Err("Consumer does not support seeking".to_string())
+    }
+}
+
+/// Kafka producer publishing results and dead letters
+pub trait KafkaProducer: Send {
+    /// Queue a record for `topic`
+    fn send(&mut self, topic: &str, key: Option<&[u8]>, payload: &[u8]) -> Result<(), String>;
+
+    /// Wait until every queued record is acknowledged by the brokers
+    fn flush(&mut self, timeout: Duration) -> Result<(), String>;
+
+    /// Start a transaction covering the records sent until it is committed or aborted
+    ///
+    /// The transaction methods are required by [`ProcessingMode::ExactlyOnce`] only;
+    /// the producer must already have a `transactional.id` and initialized transactions.
+    fn begin_transaction(&mut self) -> Result<(), String> {
+        Err("Producer does not support transactions".to_string())
+    }
+
+    /// Flush and atomically publish every record sent in the current transaction
+    fn commit_transaction(&mut self, timeout: Duration) -> Result<(), String> {
+        let _ = timeout;
+        Err("Producer does not support transactions".to_string())
+    }
+
+    /// Discard every record sent in the current transaction
+    fn abort_transaction(&mut self, timeout: Duration) -> Result<(), String> {
+        let _ = timeout;
+        Err("Producer does not support transactions".to_string())
+    }
+}
+
+/// Decodes record payloads in formats other than JSON
+pub trait FactDecoder: Send + Sync {
+    /// Fields of the fact carried by `record`
+    fn decode(&self, record: &KafkaRecord) -> Result<HashMap<String, FactValue>, String>;
+}
+
+/// How record payloads are decoded into facts
+#[derive(Clone, Default)]
+pub enum RecordFormat {
+    /// A JSON object whose members are the fact's fields
+    #[default]
+    Json,
+    /// A caller-supplied decoder, e.g. for Avro
+    Custom(Arc<dyn FactDecoder>),
+}
+
+impl fmt::Debug for RecordFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json => f.write_str("Json"),
+            Self::Custom(_) => f.write_str("Custom"),
+        }
+    }
+}
+
+impl RecordFormat {
+    fn decode(&self, record: &KafkaRecord) -> Result<HashMap<String, FactValue>, String> {
+        match self {
+            Self::Json => {
+                let value: serde_json::Value = serde_json::from_slice(&record.payload)
+                    .map_err(|e| format!("Invalid JSON: {e}"))?;
+                let serde_json::Value::Object(members) = value else {
+                    return Err("Expected a JSON object of fact fields".to_string());
+                };
+                members
+                    .iter()
+                    .map(|(field, value)| {
+                        FactValue::try_from(value)
+                            .map(|value| (field.clone(), value))
+                            .map_err(|e| format!("Invalid value for field '{field}': {e}"))
+                    })
+                    .collect()
+            }
+            Self::Custom(decoder) => decoder.decode(record),
+        }
+    }
+}
+
+/// How a batch's results and source offsets are made durable
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ProcessingMode {
+    /// Results are flushed, then offsets are committed to the consumer group; a batch
+    /// interrupted in between is consumed again and its results published twice
+    #[default]
+    AtLeastOnce,
+    /// Results, dead letters and a [`ConnectorCheckpoint`] on `checkpoint_topic` are
+    /// published in one producer transaction; the consumer group is not committed
+    ExactlyOnce { checkpoint_topic: String },
+}
+
+/// Source offsets and engine state after a batch committed in exactly-once mode
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConnectorCheckpoint {
+    /// Position of every partition the connector has consumed from
+    pub offsets: Vec<TopicPartitionOffset>,
+    /// Next ID the session gives to inserted facts
+    pub next_fact_id: u64,
+    /// Engine state encoded by [`EngineSnapshot::to_bytes`]
+    pub snapshot: Vec<u8>,
+}
+
+impl ConnectorCheckpoint {
+    /// Key of the checkpoint records, so a compacted checkpoint topic keeps the latest
+    pub const KEY: &'static [u8] = b"bingo-connector-checkpoint";
+
+    /// Encode the checkpoint as the payload of a checkpoint record
+    pub fn to_bytes(&self) -> BingoResult<Vec<u8>> {
+        let mut bytes = Vec::new();
+        ciborium::into_writer(self, &mut bytes).map_err(|e| {
+            BingoError::serialization("ConnectorCheckpoint", "encode", e.to_string())
+        })?;
+        Ok(bytes)
+    }
+
+    /// Decode the payload of a checkpoint record
+    pub fn from_bytes(bytes: &[u8]) -> BingoResult<Self> {
+        ciborium::from_reader(bytes)
+            .map_err(|e| BingoError::serialization("ConnectorCheckpoint", "decode", e.to_string()))
+    }
+}
+
+/// Topics, batching and decoding of a [`KafkaConnector`]
+#[derive(Debug, Clone)]
+pub struct KafkaConnectorConfig {
+    pub input_topics: Vec<String>,
+    pub output_topic: String,
+    /// Topic receiving records that cannot be decoded, skipped when `None`
+    pub dead_letter_topic: Option<String>,
+    pub format: RecordFormat,
+    /// Records consumed and fired as one batch
+    pub max_batch_records: usize,
+    pub poll_timeout: Duration,
+    /// Time allowed for the brokers to acknowledge a batch's results
+    pub flush_timeout: Duration,
+    pub processing_mode: ProcessingMode,
+}
+
+impl KafkaConnectorConfig {
+    /// Configuration consuming JSON facts from `input_topics`
+    pub fn new(input_topics: Vec<String>, output_topic: impl Into<String>) -> Self {
+        Self {
+            input_topics,
+            output_topic: output_topic.into(),
+            dead_letter_topic: None,
+            format: RecordFormat::Json,
+            max_batch_records: 500,
+            poll_timeout: Duration::from_millis(100),
+            flush_timeout: Duration::from_secs(10),
+            processing_mode: ProcessingMode::AtLeastOnce,
+        }
+    }
+
+    fn validate(&self) -> BingoResult<()> {
+        if self.input_topics.is_empty() || self.input_topics.iter().any(String::is_empty) {
+            return Err(BingoError::configuration(
+                "input_topics",
+                "one or more topic names",
+                &format!("{:?}", self.input_topics),
+                "Kafka connector needs named input topics",
+            ));
+        }
+        if self.output_topic.is_empty() {
+            return Err(BingoError::configuration(
+                "output_topic",
+                "a topic name",
+                "\"\"",
+                "Kafka connector needs a named output topic",
+            ));
+        }
+        if self.max_batch_records == 0 {
+            return Err(BingoError::configuration(
+                "max_batch_records",
+                "at least 1",
+                "0",
+                "Kafka connector batches must hold at least one record",
+            ));
+        }
+        if let ProcessingMode::ExactlyOnce { checkpoint_topic } = &self.processing_mode
+            && (checkpoint_topic.is_empty()
+                || *checkpoint_topic == self.output_topic
+                || self.input_topics.contains(checkpoint_topic))
+        {
+            return Err(BingoError::configuration(
+                "processing_mode.checkpoint_topic",
+                "a topic name distinct from the input and output topics",
+                &format!("{checkpoint_topic:?}"),
+                "Exactly-once Kafka connector needs a dedicated checkpoint topic",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// What one consumed batch produced
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct KafkaBatchOutcome {
+    pub records: usize,
+    pub facts: usize,
+    pub results_published: usize,
+    pub dead_lettered: usize,
+    pub skipped: usize,
+}
+
+/// Running totals of a connector
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct KafkaConnectorStats {
+    pub batches: u64,
+    pub records: u64,
+    pub facts: u64,
+    pub results_published: u64,
+    pub dead_lettered: u64,
+    pub skipped: u64,
+}
+
+impl KafkaConnectorStats {
+    fn record(&mut self, outcome: &KafkaBatchOutcome) {
+        self.batches += 1;
+        self.records += outcome.records as u64;
+        self.facts += outcome.facts as u64;
+        self.results_published += outcome.results_published as u64;
+        self.dead_lettered += outcome.dead_lettered as u64;
+        self.skipped += outcome.skipped as u64;
+    }
+}
+
+/// Streams facts from Kafka through a session and publishes its results
+pub struct KafkaConnector<C: KafkaConsumer, P: KafkaProducer> {
+    consumer: C,
+    producer: P,
+    session: Arc<BingoSession>,
+    config: KafkaConnectorConfig,
+    /// Results of rules fired by the session since the last batch
+    fired: Arc<Mutex<Vec<RuleExecutionResult>>>,
+    stats: KafkaConnectorStats,
+    /// Exactly-once positions of every partition consumed, carried by checkpoints
+    positions: BTreeMap<(String, i32), i64>,
+    /// An exactly-once batch failed after changing the engine, which no longer matches
+    /// the latest checkpoint
+    needs_resume: bool,
+}
+
+impl<C: KafkaConsumer, P: KafkaProducer> fmt::Debug for KafkaConnector<C, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KafkaConnector")
+            .field("config", &self.config)
+            .field("stats", &self.stats)
+            .finish()
+    }
+}
+
+impl<C: KafkaConsumer, P: KafkaProducer> KafkaConnector<C, P> {
+    /// Subscribe `consumer` to the input topics and publish results with `producer`
+    ///
+    /// The session should be dedicated to the connector: results of rules it fires
+    /// for facts inserted by other callers are published too.
+    pub fn new(
+        mut consumer: C,
+        producer: P,
+        session: Arc<BingoSession>,
+        config: KafkaConnectorConfig,
+    ) -> BingoResult<Self> {
+        config.validate()?;
+        consumer
+            .subscribe(&config.input_topics)
+            .map_err(|e| BingoError::external_service("kafka", e))?;
+
+        let fired = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&fired);
+        session.add_event_listener(move |event: &SessionEvent| {
+            if let SessionEvent::RuleFired { result } = event {
+                sink.lock().unwrap().push(result.clone());
+            }
+        });
+
+        info!(topics = ?config.input_topics, output = %config.output_topic, "Kafka connector subscribed");
+        Ok(Self {
+            consumer,
+            producer,
+            session,
+            config,
+            fired,
+            stats: KafkaConnectorStats::default(),
+            positions: BTreeMap::new(),
+            needs_resume: false,
+        })
+    }
+
+    /// Restore the engine and consumer positions of a checkpoint record's payload
+    ///
+    /// Call before the first batch with the latest record on the checkpoint topic, read
+    /// with `isolation.level=read_committed`. Records consumed after the checkpoint are
+    /// consumed again and fire the same rules with the same fact IDs.
+    pub fn resume(&mut self, checkpoint: &[u8]) -> BingoResult<()> {
+        let checkpoint = ConnectorCheckpoint::from_bytes(checkpoint)?;
+        let snapshot = EngineSnapshot::from_bytes(&checkpoint.snapshot)?;
+        self.session.engine().restore(&snapshot)?;
+        self.session.resume_fact_ids(checkpoint.next_fact_id);
+        self.consumer
+            .seek(&checkpoint.offsets)
+            .map_err(|e| BingoError::external_service("kafka", e))?;
+
+        self.positions = checkpoint
+            .offsets
+            .iter()
+            .map(|position| {
+                (
+                    (position.topic.clone(), position.partition),
+                    position.offset,
+                )
+            })
+            .collect();
+        self.needs_resume = false;
+        info!(offsets = ?checkpoint.offsets, "Kafka connector resumed from checkpoint");
+        Ok(())
+    }
+
+    /// Consume, fire and publish one batch, then commit its offsets
+    ///
+    /// In at-least-once mode an error leaves the batch's offsets uncommitted, so its
+    /// records are consumed again after a restart. In exactly-once mode an error aborts
+    /// the batch's transaction, and the connector refuses further batches until it is
+    /// resumed from the latest checkpoint.
+    pub fn poll_once(&mut self) -> BingoResult<KafkaBatchOutcome> {
+        let kafka = |e: String| BingoError::external_service("kafka", e);
+        if self.needs_resume {
+            return Err(kafka(
+                "Exactly-once batch failed; resume from the latest checkpoint".to_string(),
+            ));
+        }
+        let records = self
+            .consumer
+            .poll(self.config.max_batch_records, self.config.poll_timeout)
+            .map_err(kafka)?;
+        if records.is_empty() {
+            return Ok(KafkaBatchOutcome::default());
+        }
+
+        let outcome = match self.config.processing_mode.clone() {
+            ProcessingMode::AtLeastOnce => {
+                let outcome = self.publish_batch(&records)?;
+                self.producer.flush(self.config.flush_timeout).map_err(kafka)?;
+                self.consumer.commit(&next_offsets(&records)).map_err(kafka)?;
+                outcome
+            }
+            ProcessingMode::ExactlyOnce { checkpoint_topic } => {
+                match self.publish_transaction(&records, &checkpoint_topic) {
+                    Ok(outcome) => outcome,
+                    Err(error) => {
+                        self.needs_resume = true;
+                        if let Err(abort) =
+                            self.producer.abort_transaction(self.config.flush_timeout)
+                        {
+                            warn!(%abort, "Failed to abort Kafka transaction");
+                        }
+                        return Err(error);
+                    }
+                }
+            }
+        };
+        self.stats.record(&outcome);
+        debug!(?outcome, "Kafka batch committed");
+        Ok(outcome)
+    }
+
+    /// Publish a batch's results with a checkpoint of the state after it, atomically
+    fn publish_transaction(
+        &mut self,
+        records: &[KafkaRecord],
+        checkpoint_topic: &str,
+    ) -> BingoResult<KafkaBatchOutcome> {
+        let kafka = |e: String| BingoError::external_service("kafka", e);
+        self.producer.begin_transaction().map_err(kafka)?;
+        let outcome = self.publish_batch(records)?;
+
+        for position in next_offsets(records) {
+            self.positions.insert((position.topic, position.partition), position.offset);
+        }
+        let checkpoint = ConnectorCheckpoint {
+            offsets: self
+                .positions
+                .iter()
+                .map(|((topic, partition), offset)| TopicPartitionOffset {
+                    topic: topic.clone(),
+                    partition: *partition,
+                    offset: *offset,
+                })
+                .collect(),
+            next_fact_id: self.session.next_fact_id(),
+            snapshot: self.session.engine().snapshot()?.to_bytes()?,
+        };
+        self.producer
+            .send(
+                checkpoint_topic,
+                Some(ConnectorCheckpoint::KEY),
+                &checkpoint.to_bytes()?,
+            )
+            .map_err(kafka)?;
+        self.producer.commit_transaction(self.config.flush_timeout).map_err(kafka)?;
+        Ok(outcome)
+    }
+
+    /// Insert a batch's facts, fire the session's rules and send their results
+    fn publish_batch(&mut self, records: &[KafkaRecord]) -> BingoResult<KafkaBatchOutcome> {
+        let kafka = |e: String| BingoError::external_service("kafka", e);
+        let mut outcome = KafkaBatchOutcome { records: records.len(), ..Default::default() };
+
+        // Queue the batch's facts, remembering the record each one came from
+        let mut sources: HashMap<FactId, &KafkaRecord> = HashMap::new();
+        for record in records {
+            match self.config.format.decode(record) {
+                Ok(fields) => {
+                    let mut fact = Fact::new(0, FactData { fields });
+                    fact.external_id = record
+                        .key
+                        .as_deref()
+                        .and_then(|key| std::str::from_utf8(key).ok())
+                        .map(str::to_string);
+                    let handle = self.session.insert(fact);
+                    sources.insert(handle.fact_id(), record);
+                    outcome.facts += 1;
+                }
+                Err(error) => self.dead_letter(record, &error, &mut outcome).map_err(kafka)?,
+            }
+        }
+
+        self.fired.lock().unwrap().clear();
+        self.session.fire_all_rules()?;
+        let results = std::mem::take(&mut *self.fired.lock().unwrap());
+
+        for result in &results {
+            let source = sources.get(&result.fact_id).copied();
+            let payload = result_json(result, source).to_string();
+            let key = result.external_id.as_deref().map(str::as_bytes);
+            self.producer
+                .send(&self.config.output_topic, key, payload.as_bytes())
+                .map_err(kafka)?;
+        }
+        outcome.results_published = results.len();
+        Ok(outcome)
+    }
+
+    /// Process batches until `stop` is set, returning the connector's totals
+    pub fn run(&mut self, stop: &AtomicBool) -> BingoResult<KafkaConnectorStats> {
+        while !stop.load(Ordering::Relaxed) {
+            self.poll_once()?;
+        }
+        info!(stats = ?self.stats, "Kafka connector stopped");
+        Ok(self.stats)
+    }
+
+    /// Totals of every committed batch
+    pub fn stats(&self) -> KafkaConnectorStats {
+        self.stats
+    }
+
+    /// Session the connector fires rules in
+    pub fn session(&self) -> &Arc<BingoSession> {
+        &self.session
+    }
+
+    fn dead_letter(
+        &mut self,
+        record: &KafkaRecord,
+        error: &str,
+        outcome: &mut KafkaBatchOutcome,
+    ) -> Result<(), String> {
+        let Some(topic) = &self.config.dead_letter_topic else {
+            warn!(topic = %record.topic, partition = record.partition, offset = record.offset, %error, "Skipping undecodable Kafka record");
+            outcome.skipped += 1;
+            return Ok(());
+        };
+        warn!(topic = %record.topic, partition = record.partition, offset = record.offset, %error, "Dead-lettering undecodable Kafka record");
+        self.producer.send(topic, record.key.as_deref(), &record.payload)?;
+        outcome.dead_lettered += 1;
+        Ok(())
+    }
+}
+
+/// Positions after the last record of each partition in `records`
+fn next_offsets(records: &[KafkaRecord]) -> Vec<TopicPartitionOffset> {
+    let mut last: BTreeMap<(&str, i32), i64> = BTreeMap::new();
+    for record in records {
+        let offset = last.entry((&record.topic, record.partition)).or_insert(record.offset);
+        *offset = (*offset).max(record.offset);
+    }
+    last.into_iter()
+        .map(|((topic, partition), offset)| TopicPartitionOffset {
+            topic: topic.to_string(),
+            partition,
+            offset: offset + 1,
+        })
+        .collect()
+}
+
+/// Output record for a rule execution result
+fn result_json(result: &RuleExecutionResult, source: Option<&KafkaRecord>) -> serde_json::Value {
+    let mut json = serde_json::json!({
+        "rule_id": result.rule_id,
+        "fact_id": result.fact_id,
+        "external_id": result.external_id,
+        "actions": result.actions_executed,
+    });
+    if !result.field_collisions.is_empty() {
+        json["field_collisions"] = serde_json::json!(result.field_collisions);
+    }
+    if let Some(source) = source {
+        json["source"] = serde_json::json!({
+            "topic": source.topic,
+            "partition": source.partition,
+            "offset": source.offset,
+        });
+    }
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(topic: &str, partition: i32, offset: i64, payload: &str) -> KafkaRecord {
+        KafkaRecord {
+            topic: topic.to_string(),
+            partition,
+            offset,
+            key: None,
+            payload: payload.as_bytes().to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_commits_the_position_after_each_partition() {
+        let records = vec![
+            record("orders", 0, 7, "{}"),
+            record("orders", 1, 3, "{}"),
+            record("orders", 0, 8, "{}"),
+        ];
+        let offsets: Vec<_> = next_offsets(&records)
+            .into_iter()
+            .map(|position| (position.partition, position.offset))
+            .collect();
+        assert_eq!(offsets, vec![(0, 9), (1, 4)]);
+    }
+
+    #[test]
+    fn test_json_records_decode_to_fields() {
+        let fields = RecordFormat::Json
+            .decode(&record("orders", 0, 0, r#"{"amount": 12, "region": "EU"}"#))
+            .unwrap();
+        assert_eq!(fields["amount"], FactValue::Integer(12));
+        assert_eq!(fields["region"], FactValue::String("EU".to_string()));
+
+        assert!(RecordFormat::Json.decode(&record("orders", 0, 0, "[1, 2]")).is_err());
+        assert!(RecordFormat::Json.decode(&record("orders", 0, 0, "{")).is_err());
+    }
+}
@@ -107,6 +107,7 @@ impl LazyAggregationResult {
             id: 0,
             external_id: None,
             timestamp: chrono::Utc::now(),
+            fact_type: None,
             data: crate::types::FactData { fields: synthetic_fields },
         };
 
@@ -249,8 +250,16 @@ impl LazyAggregationResult {
                         all_facts.into_iter().skip(window_start).take(*size).collect()
                     }
                 }
-                AggregationWindow::Session { .. } => {
-                    // Session windows not fully supported yet
+                AggregationWindow::Session { .. }
+                | AggregationWindow::TumblingTime { .. }
+                | AggregationWindow::SlidingTime { .. } => {
+                    // Event-time windows are maintained by the RETE network's aggregation
+                    // nodes, which track the watermark across batches
+                    self.fact_store.iter()
+                }
+                AggregationWindow::Calendar { .. } => {
+                    // Calendars are registered with the RETE network, whose aggregation
+                    // nodes partition facts by period
                     self.fact_store.iter()
                 }
             }
@@ -391,6 +400,15 @@ impl LazyAggregationResult {
                                 }
                                 _ => false,
                             },
+                            Within | OlderThan | Overlaps => {
+                                operator.matches_temporal(fact_val, value)
+                            }
+                            Matches | ContainsIgnoreCase => {
+                                crate::string_match::matches(operator, fact_val, value)
+                            }
+                            In | NotIn => {
+                                crate::value_list::matches_literal(operator, fact_val, value)
+                            }
                         };
                         Ok(result)
                     }
@@ -603,6 +621,7 @@ mod tests {
             id,
             external_id: Some(format!("fact-{id}")),
             timestamp: Utc::now(),
+            fact_type: None,
             data: FactData { fields },
         }
     }
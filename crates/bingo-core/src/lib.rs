@@ -45,6 +45,7 @@
 //!             }
 //!         }
 //!     ],
+//!     metadata: RuleMetadata::default(),
 //! };
 //!
 //! // Add rule to engine
@@ -81,14 +82,17 @@
 //!
 //! ## Module Organization
 //!
+//! Modules hidden from this documentation are internal, such as the RETE nodes and
+//! memories, allocators and test scaffolding: their items change between releases.
+//! Applications wanting a stable API depend on `bingo-prelude`, which re-exports the
+//! embedding surface under versioned paths.
+//!
 //! | Module | Purpose |
 //! |--------|---------|
 //! | [`engine`] | Main rules engine and orchestration |
 //! | [`types`] | Core data structures and type definitions |
 //! | [`error`] | Comprehensive error handling system |
-//! | [`rete_network`] | RETE algorithm implementation |
 //! | [`fact_store`] | High-performance fact storage |
-//! | [`memory_pools`] | Object pooling for optimization |
 //! | [`serialization`] | Fast serialization/deserialization |
 //! | [`aggregation`] | Aggregation functions and windowing |
 //! | [`stream_processing`] | Real-time stream processing |
@@ -124,23 +128,47 @@
 
 use tracing::{debug, instrument};
 
+/// Validation of rule actions against registered calculators and field types
+pub mod action_validation;
 /// Aggregation functions and time-window processing
 pub mod aggregation;
+/// Incremental aggregation nodes for the RETE network
+#[doc(hidden)]
+pub mod aggregation_node;
 /// Alpha memory implementation for RETE network
+#[doc(hidden)]
 pub mod alpha_memory;
+/// Per-batch summaries of rule execution results
+pub mod batch_summary;
 /// Beta network implementation for RETE network
+#[doc(hidden)]
 pub mod beta_network;
 /// Caching infrastructure for performance optimisation
 pub mod cache;
+/// Reference period calendars for calendar aggregation windows
+pub mod calendar;
+/// Case folding, normalization and locale options for string comparisons
+pub mod collation;
+/// Arrow record batch and Parquet export of rule results
+#[cfg(feature = "arrow")]
+pub mod columnar_export;
+/// Precompiled ruleset artifacts for warm-starting engines
+pub mod compiled_ruleset;
+/// Per-condition evaluation counters and never-matching condition reports
+pub mod condition_stats;
 /// Conflict resolution strategies for rule execution ordering
 pub mod conflict_resolution;
 /// System constants and configuration values
 pub mod constants;
 
-/// Debug visualisation and tracing utilities
+/// Debug visualisation, tracing utilities and the interactive step-debugger
 pub mod debugging;
+/// Deterministic execution and canonical result encoding for reproducible runs
+pub mod deterministic;
 /// Core rules engine and RETE network management
 pub mod engine;
+/// Working memory capacity limits and eviction policies
+pub mod engine_config;
 /// Enhanced monitoring system for comprehensive observability
 pub mod enhanced_monitoring;
 /// Comprehensive error handling for core engine operations
@@ -148,19 +176,65 @@ pub mod error;
 /// Enhanced error diagnostics and debugging tools
 pub mod error_diagnostics;
 /// Error testing and validation framework
+#[doc(hidden)]
 pub mod error_testing;
+/// Budgeted evaluation of fact batches in resumable slices
+pub mod evaluation_budget;
+/// Engine lifecycle events published to subscribers
+pub mod event_bus;
+/// Bounded asynchronous delivery queues for rule side effects
+pub mod event_sink;
+/// Audit traces of rule executions, retrievable by result ID
+pub mod explanation;
+/// Per-fact time to live and retraction of expired facts
+pub mod fact_expiry;
+/// Bounded version history of facts modified by rules and updates
+pub mod fact_history;
+/// Fact ID assignment strategies and collision policies
+pub mod fact_id_strategy;
+/// Fact queries with AND, OR and NOT groups, returned a page at a time
+pub mod fact_query;
+/// Typed fact schemas checked when facts are inserted
+pub mod fact_schema;
 /// Fact storage and retrieval with indexing support
 pub mod fact_store;
 /// Fast lookup optimisations for rule pattern matching
+#[doc(hidden)]
 pub mod fast_lookup;
+/// Bump-allocated generational storage for fact fields
+#[doc(hidden)]
+pub mod field_arena;
+/// Resolution of rules setting the same field to different values in a batch
+pub mod field_collisions;
 /// Field-based indexing for efficient fact queries
 pub mod field_indexing;
+/// Fields referenced by a ruleset's conditions and actions
+pub mod field_references;
+/// Field-name typo detection against observed fact fields
+pub mod field_typos;
+/// Read-only follower engines serving queries from engine snapshots
+pub mod follower;
+/// Golden-file regression testing for rule outputs
+pub mod golden;
+/// Kafka source and sink connector streaming facts through a session
+#[cfg(feature = "kafka")]
+pub mod kafka_connector;
 /// Lazy evaluation for complex aggregations
+#[doc(hidden)]
 pub mod lazy_aggregation;
 /// Memory management for RETE network nodes
 pub mod memory;
 /// Memory pooling for frequently allocated objects
+#[doc(hidden)]
 pub mod memory_pools;
+/// Memory watermarks, pressure callbacks and spill-to-disk
+pub mod memory_pressure;
+/// Working memory size and composition reports
+pub mod memory_report;
+/// Pluggable metrics backends for Prometheus, statsd, OpenTelemetry and tests
+pub mod metrics_backend;
+/// Policy for NaN and infinite floats in facts, aggregates and calculator results
+pub mod non_finite;
 /// Parallel processing for improved throughput
 pub mod parallel;
 /// Advanced parallel RETE processing for multi-core systems
@@ -173,31 +247,78 @@ pub mod pipeline;
 pub mod production_readiness;
 /// Advanced performance profiling and monitoring
 pub mod profiler;
+/// Ordered secondary indexes over numeric, date and timestamp values
+pub mod range_index;
+/// Retroactive recalculation of historical facts under corrected rules or facts
+pub mod recalculation;
+/// Named reference data tables with atomic hot reload
+pub mod reference_data;
 /// RETE network construction and execution
+#[doc(hidden)]
 pub mod rete_network;
 /// Individual RETE node implementations
+#[doc(hidden)]
 pub mod rete_nodes;
 /// Rule dependency analysis and optimization
 pub mod rule_dependency;
+/// Textual rule language compiling to rule structs
+pub mod rule_dsl;
+/// Duplicate and near-duplicate rule detection
+pub mod rule_duplicates;
+/// Mutation testing of rulesets against rule test suites
+pub mod rule_mutation;
 /// Advanced rule optimization for RETE network performance
 pub mod rule_optimizer;
+/// Per-rule hit counts and condition evaluation time
+pub mod rule_stats;
 /// Rule visualisation and debugging support
 pub mod rule_visualization;
+/// Sequence nodes for temporal pattern conditions
+#[doc(hidden)]
+pub mod sequence_node;
 /// High-performance serialization and deserialization
 pub mod serialization;
+/// Session-based working memory with insert/modify/retract lifecycle
+pub mod session;
+/// Shadow evaluation of candidate rulesets against live facts
+pub mod shadow;
+/// Warm standby engines fed by a primary's replication stream for failover
+pub mod standby;
 /// Stream processing for real-time rule evaluation
 pub mod stream_processing;
-/// Performance testing utilities
+/// Regex and case-insensitive string operators
+#[doc(hidden)]
+pub mod string_match;
+/// Performance testing utilities and synthetic fact scenarios
 pub mod test_utils;
+/// Given/when/then rule tests and rule coverage reports
+pub mod testkit;
+/// Staged inserts and retracts committed atomically or rolled back
+pub mod transaction;
+/// Truth maintenance for fact retraction and derived fact withdrawal
+#[doc(hidden)]
+pub mod truth_maintenance;
+/// Value sets for `In` and `NotIn` conditions
+#[doc(hidden)]
+pub mod value_list;
 
 /// Test module for verifying Send + Sync bounds on core components
+#[doc(hidden)]
 pub mod send_sync_test;
 /// Integration tests for threading safety in parallel RETE
+#[doc(hidden)]
 pub mod threading_integration_test;
 /// Core types and functionality for the Bingo RETE rules engine
 pub mod types;
 /// Unified statistics collection across engine components
 pub mod unified_statistics;
+/// Webhook actions delivered by a bounded worker pool with retries and dead letters
+pub mod webhook;
+/// Stream window nodes for temporal rule conditions
+#[doc(hidden)]
+pub mod window_node;
+/// Sampling profiler for frequently evaluated facts and frequently read fields
+pub mod working_memory_profiler;
 
 // Re-export critical types for API layer
 pub use engine::BingoEngine;
@@ -209,25 +330,91 @@ pub use error_diagnostics::{
 pub use error_testing::{ErrorTestConfig, ErrorTestSuite, ErrorTestSummary, run_error_tests};
 pub use rete_nodes::{ActionResult, RuleExecutionResult};
 pub use serialization::{
-    SerializationContext, SerializationStats, deserialize_fact, deserialize_facts,
-    get_serialization_stats, serialize_fact, serialize_facts,
+    FactPayloadFormat, SerializationContext, SerializationStats, deserialize_fact,
+    deserialize_fact_fields, deserialize_facts, get_serialization_stats, serialize_fact,
+    serialize_fact_fields, serialize_facts,
 };
+pub use session::{BingoSession, FactHandle, SessionEvent, SessionEventListener};
 pub use types::{
-    Action, ActionType, Condition, Fact, FactData, FactValue, LogicalOperator, Operator, Rule,
+    Action, ActionType, Condition, EvaluationDate, Fact, FactData, FactValue, LogicalOperator,
+    Operator, Rule, RuleMetadata,
 };
 
 // Additional re-exports required by benchmarks and external crates
+pub use action_validation::{
+    ActionProblem, ActionValidationPolicy, ActionValidationReport, FieldSchema, validate_actions,
+};
+pub use batch_summary::{BatchSummary, RuleSummary};
+pub use cache::{CacheStats, CalculatorCacheConfig};
+pub use calendar::{BusinessPeriod, PeriodCalendar};
+pub use collation::{Collation, Normalization};
+#[cfg(feature = "arrow")]
+pub use columnar_export::{
+    facts_record_batch, results_record_batch, results_schema, write_parquet,
+};
+pub use compiled_ruleset::CompiledRuleset;
+pub use condition_stats::{ConditionEvaluationStats, UnmatchedConditionReport};
 pub use conflict_resolution::{
     ConflictResolutionConfig, ConflictResolutionManager, ConflictResolutionStats,
     ConflictResolutionStrategy, RuleExecution,
 };
+pub use debugging::{
+    AlphaMemoryContents, BetaMemoryContents, DebugBreakpoint, DebugSession, DebugStep,
+    NetworkMemoryContents,
+};
+pub use deterministic::encode_results;
+pub use engine_config::{CapacityStats, EngineConfig, EvictionPolicy};
 pub use enhanced_monitoring::{
-    BusinessMetrics, EnhancedMonitoring, MonitoringConfig, MonitoringReport, MonitoringSummary,
-    PerformanceMetrics, ResourceMetrics,
+    BusinessMetrics, CachePerformanceMetrics, EnhancedMonitoring, MonitoringConfig,
+    MonitoringReport, MonitoringSummary, PerformanceMetrics, ResourceMetrics,
+};
+pub use evaluation_budget::{BudgetedResults, Continuation, EvaluationBudget};
+pub use event_bus::{EngineEvent, EngineEventListener, EventBus, SubscriptionId};
+pub use event_sink::{
+    AsyncSink, AsyncSinkConfig, AsyncSinkStats, EventSink, QueueOverflowPolicy, Submission,
+};
+pub use explanation::{
+    AuditLogConfig, CalculatorTrace, ConditionTrace, ExplanationTrace, ResultId,
+};
+pub use fact_expiry::{FactExpiryConfig, FactExpiryStats, FactExpirySweeper};
+pub use fact_history::{FactHistoryConfig, FactVersion, FieldChange};
+pub use fact_id_strategy::{FactIdStats, FactIdStrategy, IdCollisionPolicy};
+pub use fact_query::{FactPage, FactPageRequest, FactQuery};
+pub use fact_schema::{
+    FactProblem, FactSchema, FactSchemaRegistry, FactValidationReport, SchemaField,
 };
 pub use fact_store::arena_store::ArenaFactStore;
-pub use memory::MemoryTracker;
-pub use parallel::{ParallelAggregationEngine, ParallelAggregator, ParallelConfig};
+pub use field_arena::{FieldArena, FieldArenaStats};
+pub use field_collisions::{FieldCollision, FieldCollisionPolicy, FieldWrite};
+pub use field_references::ReferencedField;
+pub use field_typos::{FieldObservations, FieldTypoAnalyzer, FieldTypoWarning};
+pub use follower::{EngineSnapshot, FollowerEngine};
+pub use golden::{GoldenDiff, GoldenHarness, GoldenOutcome};
+#[cfg(feature = "kafka")]
+pub use kafka_connector::{
+    ConnectorCheckpoint, FactDecoder, KafkaBatchOutcome, KafkaConnector, KafkaConnectorConfig,
+    KafkaConnectorStats, KafkaConsumer, KafkaProducer, KafkaRecord, ProcessingMode, RecordFormat,
+    TopicPartitionOffset,
+};
+pub use memory::{ArenaFragmentationReport, MemoryBreakdown, MemoryTracker};
+pub use memory_pressure::{
+    DiskSpiller, InsertRejector, MemoryPressureEvent, MemoryPressureHandler, MemoryPressureMonitor,
+    MemoryPressureStats, MemoryWatermarks, PressureLevel, PressureResponse, PriorityShedder,
+    TtlShedder,
+};
+pub use memory_report::{
+    BetaMemoryUsage, FactGroupUsage, IndexUsage, MemoryReport, MemoryReportOptions, RuleMemoryUsage,
+};
+#[cfg(feature = "otel")]
+pub use metrics_backend::OpenTelemetryMetrics;
+pub use metrics_backend::{
+    HistogramSummary, InMemoryMetrics, MetricsBackend, MetricsBackendConfig, NoopMetrics,
+    PrometheusMetrics, StatsdMetrics,
+};
+pub use non_finite::{NonFinitePolicy, NonFiniteStats};
+pub use parallel::{
+    ParallelAggregationEngine, ParallelAggregator, ParallelAlphaConfig, ParallelConfig,
+};
 pub use parallel_rete::{
     ParallelReteConfig, ParallelReteProcessor, ParallelReteStats, WorkItem, WorkQueue,
 };
@@ -237,15 +424,51 @@ pub use production_readiness::{
     SecurityConfig, ServiceConfig, check_production_readiness, load_config_from_env,
 };
 pub use profiler::{EngineProfiler, PerformanceReport, PerformanceThresholds};
+pub use range_index::{FieldRangeIndex, NumericKey, RangeIndex};
+pub use recalculation::{
+    Correction, DeltaKind, RecalculationReport, RecalculationRequest, ResultDelta,
+};
+pub use reference_data::{ReferenceDataStore, ReferenceTable};
+pub use rete_network::{ForwardChaining, RuleExplanation};
 pub use rule_dependency::{
     CircularDependency, CircularDependencySeverity, DependencyAnalysisConfig,
-    DependencyAnalysisStats, DependencyType, ExecutionCluster, RuleDependency,
-    RuleDependencyAnalyzer,
+    DependencyAnalysisStats, DependencyType, ExecutionCluster, FieldAccess, GraphEdge, GraphNode,
+    GraphNodeKind, RuleCyclePolicy, RuleDependency, RuleDependencyAnalyzer, RuleFieldGraph,
+    RuleFlowEdge, RuleFlowGraph, RuleFlowNode,
 };
+pub use rule_dsl::{parse_rule, parse_rules};
+pub use rule_duplicates::{DuplicateKind, DuplicateRulePolicy, DuplicateRules};
+pub use rule_mutation::{MutationReport, MutationTester, RuleTestCase};
 pub use rule_optimizer::{
     OptimizationAnalysis, OptimizationMetrics, OptimizationResult, OptimizationStrategy,
     OptimizerConfig, RuleOptimizer, optimize_rule_batch,
 };
+pub use rule_stats::RuleStats;
+pub use shadow::{RuleDivergence, ShadowDivergence, ShadowEvaluation, ShadowReport};
+pub use standby::{
+    Divergence, ReplicationConfig, ReplicationEntry, ReplicationRecord, ReplicationSink,
+    StateDigest, WarmStandby,
+};
+pub use stream_processing::LateDataPolicy;
+pub use string_match::CompiledRegexes;
+pub use testkit::{
+    CoverageReport, FiredRule, RuleCoverage, RuleTest, RuleTestOutcome, UncoveredCondition,
+};
+pub use transaction::{EngineTransaction, TransactionOutcome};
+pub use truth_maintenance::{
+    FactUpdateResult, Justification, RetractionResult, TruthMaintenanceSystem,
+};
+pub use value_list::{ValueLists, ValueSet};
+pub use webhook::{
+    DeadLetter, WebhookConfig, WebhookDispatcher, WebhookPayload, WebhookStats, WebhookTarget,
+    WebhookTransport,
+};
+pub use working_memory_profiler::{
+    HotFact, HotField, WorkingMemoryProfile, WorkingMemoryProfilerConfig,
+};
+
+/// Version of the bingo-core crate
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 /// Initialize the core engine components
 #[instrument]
@@ -1,3 +1,10 @@
+use crate::engine::BingoEngine;
+use crate::fact_store::arena_store::ArenaFactStore;
+use crate::field_arena::FieldArenaStats;
+use crate::types::{Fact, FactValue};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
 /// Get current RSS (Resident Set Size) memory usage in bytes
 pub fn get_memory_usage() -> anyhow::Result<usize> {
     #[cfg(target_os = "linux")]
@@ -132,15 +139,180 @@ impl MemoryStats {
     }
 }
 
+/// Fragmentation snapshot of a fact store's field arena
+#[derive(Debug, Clone)]
+pub struct ArenaFragmentationReport {
+    pub stats: FieldArenaStats,
+    pub fragmentation_ratio: f64,
+    pub dead_bytes: usize,
+}
+
+impl ArenaFragmentationReport {
+    /// Build a report from arena statistics
+    pub fn from_stats(stats: FieldArenaStats) -> Self {
+        let entry_bytes = stats.reserved_bytes.checked_div(stats.reserved_entries).unwrap_or(0);
+        Self {
+            fragmentation_ratio: stats.fragmentation_ratio(),
+            dead_bytes: stats.dead_entries() * entry_bytes,
+            stats,
+        }
+    }
+
+    /// Format the fragmentation in human-readable form
+    pub fn format(&self) -> String {
+        let dead_kb = self.dead_bytes as f64 / 1024.0;
+        format!(
+            "{:.1}% fragmented ({dead_kb:.2} KB dead, {} slabs, generation {})",
+            self.fragmentation_ratio * 100.0,
+            self.stats.active_slabs,
+            self.stats.generation
+        )
+    }
+}
+
+/// Heap bytes owned by a fact value beyond its inline size
+pub fn fact_value_heap_bytes(value: &FactValue) -> usize {
+    match value {
+        FactValue::String(s) => s.capacity(),
+        FactValue::Array(items) => {
+            items.capacity() * std::mem::size_of::<FactValue>()
+                + items.iter().map(fact_value_heap_bytes).sum::<usize>()
+        }
+        FactValue::Object(map) => {
+            hash_map_table_bytes(map)
+                + map
+                    .iter()
+                    .map(|(key, value)| key.capacity() + fact_value_heap_bytes(value))
+                    .sum::<usize>()
+        }
+        _ => 0,
+    }
+}
+
+/// Bytes held by a fact including its field map, keys and values
+pub fn fact_bytes(fact: &Fact) -> usize {
+    std::mem::size_of::<Fact>()
+        + fact.external_id.as_ref().map_or(0, |id| id.capacity())
+        + hash_map_table_bytes(&fact.data.fields)
+        + fact
+            .data
+            .fields
+            .iter()
+            .map(|(key, value)| key.capacity() + fact_value_heap_bytes(value))
+            .sum::<usize>()
+}
+
+/// Bytes reserved by a hash map's table (one control byte per bucket plus the entries)
+pub fn hash_map_table_bytes<K, V>(map: &HashMap<K, V>) -> usize {
+    map.capacity() * (std::mem::size_of::<(K, V)>() + 1)
+}
+
+/// Bytes reserved by a hash set's table (one control byte per bucket plus the values)
+pub fn hash_set_table_bytes<T>(set: &HashSet<T>) -> usize {
+    set.capacity() * (std::mem::size_of::<T>() + 1)
+}
+
+/// Memory held by an engine, broken down by component
+///
+/// Sizes cover heap allocations (fact payloads, strings, index maps, node memories)
+/// rather than just the inline size of the owning handles.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct MemoryBreakdown {
+    /// Fact slots and external ID strings in the fact store
+    pub fact_headers: usize,
+    /// Field data held by the fact store's arena, including string payloads
+    pub fact_payloads: usize,
+    /// Field value and external ID indexes
+    pub indexes: usize,
+    /// Facts held in RETE working memory and facts created by rule actions
+    pub working_memory: usize,
+    /// Alpha memories and their pattern indexes
+    pub alpha_memories: usize,
+    /// Beta nodes, join nodes and their token memories
+    pub beta_memories: usize,
+    /// Alpha, beta and terminal nodes plus rule definitions
+    pub network_nodes: usize,
+    /// Aggregation and stream window node state plus cached calculator results
+    pub aggregation_state: usize,
+}
+
+impl MemoryBreakdown {
+    /// Total bytes across all components
+    pub fn total(&self) -> usize {
+        self.fact_headers
+            + self.fact_payloads
+            + self.indexes
+            + self.working_memory
+            + self.alpha_memories
+            + self.beta_memories
+            + self.network_nodes
+            + self.aggregation_state
+    }
+
+    /// Bytes held by the fact store
+    pub fn fact_store_bytes(&self) -> usize {
+        self.fact_headers + self.fact_payloads + self.indexes
+    }
+
+    /// Bytes held by the RETE network
+    pub fn network_bytes(&self) -> usize {
+        self.total() - self.fact_store_bytes()
+    }
+
+    /// Format the breakdown in human-readable form
+    pub fn format(&self) -> String {
+        let kb = |bytes: usize| bytes as f64 / 1024.0;
+        format!(
+            "{:.2} KB total (facts {:.2} KB, payloads {:.2} KB, indexes {:.2} KB, working memory {:.2} KB, alpha {:.2} KB, beta {:.2} KB, nodes {:.2} KB, aggregations {:.2} KB)",
+            kb(self.total()),
+            kb(self.fact_headers),
+            kb(self.fact_payloads),
+            kb(self.indexes),
+            kb(self.working_memory),
+            kb(self.alpha_memories),
+            kb(self.beta_memories),
+            kb(self.network_nodes),
+            kb(self.aggregation_state)
+        )
+    }
+}
+
 /// Memory tracker for benchmarking
 pub struct MemoryTracker {
     start_stats: MemoryStats,
+    arena_report: Option<ArenaFragmentationReport>,
+    engine_breakdown: Option<MemoryBreakdown>,
 }
 
 impl MemoryTracker {
     /// Start tracking memory usage
     pub fn start() -> anyhow::Result<Self> {
-        Ok(Self { start_stats: MemoryStats::current()? })
+        Ok(Self {
+            start_stats: MemoryStats::current()?,
+            arena_report: None,
+            engine_breakdown: None,
+        })
+    }
+
+    /// Record the memory an engine accounts for, by component
+    pub fn record_engine(&mut self, engine: &BingoEngine) -> &MemoryBreakdown {
+        self.engine_breakdown.insert(engine.memory_breakdown())
+    }
+
+    /// Most recently recorded engine memory breakdown, if any
+    pub fn engine_breakdown(&self) -> Option<&MemoryBreakdown> {
+        self.engine_breakdown.as_ref()
+    }
+
+    /// Record the field arena fragmentation of a fact store
+    pub fn record_arena(&mut self, store: &ArenaFactStore) -> &ArenaFragmentationReport {
+        self.arena_report
+            .insert(ArenaFragmentationReport::from_stats(store.arena_stats()))
+    }
+
+    /// Most recently recorded arena fragmentation, if any
+    pub fn arena_fragmentation(&self) -> Option<&ArenaFragmentationReport> {
+        self.arena_report.as_ref()
     }
 
     /// Get current memory delta since start
@@ -202,4 +374,59 @@ mod tests {
             final_delta
         );
     }
+
+    #[test]
+    fn test_memory_tracker_records_arena_fragmentation() {
+        use crate::types::{Fact, FactData, FactValue};
+        use std::collections::HashMap;
+
+        let store = ArenaFactStore::new();
+        for id in 1..=4 {
+            let mut fields = HashMap::new();
+            fields.insert("value".to_string(), FactValue::Integer(id as i64));
+            store.insert(Fact::new(id, FactData { fields }));
+        }
+        store.delete_fact(2);
+
+        let mut tracker = MemoryTracker::start().unwrap();
+        assert!(tracker.arena_fragmentation().is_none());
+
+        let report = tracker.record_arena(&store);
+        assert_eq!(report.stats.live_entries, 3);
+        assert!(report.fragmentation_ratio > 0.0);
+        assert!(report.dead_bytes > 0);
+
+        store.clear();
+        let report = tracker.record_arena(&store);
+        assert_eq!(report.fragmentation_ratio, 0.0);
+        assert_eq!(report.stats.generation, 1);
+    }
+
+    #[test]
+    fn test_engine_breakdown_accounts_for_payloads() {
+        use crate::types::{Fact, FactData, FactValue};
+        use std::collections::HashMap;
+
+        let engine = BingoEngine::new().unwrap();
+        let empty = engine.memory_breakdown();
+
+        let facts = (1..=10)
+            .map(|id| {
+                let mut fields = HashMap::new();
+                fields.insert("notes".to_string(), FactValue::String("x".repeat(10_000)));
+                Fact::new(id, FactData { fields })
+            })
+            .collect();
+        engine.process_facts(facts).unwrap();
+
+        let mut tracker = MemoryTracker::start().unwrap();
+        assert!(tracker.engine_breakdown().is_none());
+        let breakdown = *tracker.record_engine(&engine);
+
+        // Ten 10 KB strings live in the fact store's arena
+        assert!(breakdown.fact_payloads >= empty.fact_payloads + 100_000);
+        assert!(breakdown.total() >= empty.total() + 100_000);
+        assert_eq!(breakdown.total(), engine.get_stats().memory_usage_bytes);
+        assert!(breakdown.format().contains("KB total"));
+    }
 }
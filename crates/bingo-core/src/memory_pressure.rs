@@ -0,0 +1,688 @@
+//! Memory watermarks and pressure relief for working memory
+//!
+//! Working memory grows with every fact the engine ingests. Rather than letting the
+//! process run until it is OOM-killed, the `MemoryPressureMonitor` estimates the size
+//! of the fact store before each insert and, when a watermark is crossed, invokes the
+//! registered handlers in order until usage falls back below the warning watermark.
+//!
+//! ## Built-in Handlers
+//!
+//! - **`TtlShedder`**: Drops facts older than a time-to-live
+//! - **`PriorityShedder`**: Drops the lowest-priority facts by a numeric field
+//! - **`DiskSpiller`**: Writes the coldest partitions to JSON-lines files and drops them
+//! - **`InsertRejector`**: Refuses the incoming batch
+//!
+//! Any `Fn(&MemoryPressureEvent, &ArenaFactStore) -> BingoResult<PressureResponse>`
+//! closure can also be registered as a handler.
+
+use crate::error::{BingoError, BingoResult};
+use crate::fact_store::arena_store::ArenaFactStore;
+use crate::types::{Fact, FactId, FactValue};
+use std::collections::HashMap;
+use std::io::{BufRead, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// Default estimated size of a stored fact, excluding arena field storage
+pub const DEFAULT_BYTES_PER_FACT: usize = 200;
+
+/// Severity of memory pressure, ordered from least to most severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum PressureLevel {
+    #[default]
+    Normal,
+    Warning,
+    High,
+    Critical,
+}
+
+/// Byte thresholds at which each pressure level begins
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryWatermarks {
+    pub warning_bytes: usize,
+    pub high_bytes: usize,
+    pub critical_bytes: usize,
+}
+
+impl MemoryWatermarks {
+    /// Create watermarks, validating that they are strictly increasing
+    pub fn new(
+        warning_bytes: usize,
+        high_bytes: usize,
+        critical_bytes: usize,
+    ) -> BingoResult<Self> {
+        if warning_bytes >= high_bytes || high_bytes >= critical_bytes {
+            return Err(BingoError::configuration(
+                "memory_watermarks",
+                "warning < high < critical",
+                &format!("{warning_bytes} / {high_bytes} / {critical_bytes}"),
+                "Memory watermarks must be strictly increasing",
+            ));
+        }
+        Ok(Self { warning_bytes, high_bytes, critical_bytes })
+    }
+
+    /// Watermarks that are never crossed
+    pub fn disabled() -> Self {
+        Self {
+            warning_bytes: usize::MAX - 2,
+            high_bytes: usize::MAX - 1,
+            critical_bytes: usize::MAX,
+        }
+    }
+
+    /// Whether the watermarks can ever trigger
+    pub fn is_enabled(&self) -> bool {
+        *self != Self::disabled()
+    }
+
+    /// Pressure level for a given memory usage
+    pub fn level_for(&self, used_bytes: usize) -> PressureLevel {
+        if used_bytes >= self.critical_bytes {
+            PressureLevel::Critical
+        } else if used_bytes >= self.high_bytes {
+            PressureLevel::High
+        } else if used_bytes >= self.warning_bytes {
+            PressureLevel::Warning
+        } else {
+            PressureLevel::Normal
+        }
+    }
+}
+
+impl Default for MemoryWatermarks {
+    fn default() -> Self {
+        Self::disabled()
+    }
+}
+
+/// Snapshot passed to handlers when a watermark is crossed
+#[derive(Debug, Clone)]
+pub struct MemoryPressureEvent {
+    pub level: PressureLevel,
+    /// Estimated working memory including the incoming batch
+    pub used_bytes: usize,
+    pub watermarks: MemoryWatermarks,
+    pub fact_count: usize,
+    /// Facts about to be inserted
+    pub incoming_facts: usize,
+    pub bytes_per_fact: usize,
+}
+
+impl MemoryPressureEvent {
+    /// Bytes that must be released to drop back below the warning watermark
+    pub fn excess_bytes(&self) -> usize {
+        (self.used_bytes + 1).saturating_sub(self.watermarks.warning_bytes)
+    }
+
+    /// Approximate number of facts to release to relieve the pressure
+    pub fn facts_to_free(&self) -> usize {
+        self.excess_bytes().div_ceil(self.bytes_per_fact.max(1))
+    }
+}
+
+/// What a handler did (or asks the monitor to do) in response to pressure
+#[derive(Debug, Clone, PartialEq)]
+pub enum PressureResponse {
+    /// Nothing to release
+    None,
+    /// Facts to remove from working memory
+    ShedFacts(Vec<FactId>),
+    /// Facts written to disk that can now be removed from working memory
+    SpilledFacts { fact_ids: Vec<FactId>, path: PathBuf },
+    /// Refuse the incoming batch
+    RejectInserts,
+}
+
+/// Callback invoked when working memory crosses a watermark
+pub trait MemoryPressureHandler: Send + Sync {
+    /// Respond to memory pressure; the store must not be modified directly
+    fn on_pressure(
+        &self,
+        event: &MemoryPressureEvent,
+        fact_store: &ArenaFactStore,
+    ) -> BingoResult<PressureResponse>;
+
+    /// Name used in logs and statistics
+    fn name(&self) -> &str {
+        "callback"
+    }
+}
+
+impl<F> MemoryPressureHandler for F
+where
+    F: Fn(&MemoryPressureEvent, &ArenaFactStore) -> BingoResult<PressureResponse> + Send + Sync,
+{
+    fn on_pressure(
+        &self,
+        event: &MemoryPressureEvent,
+        fact_store: &ArenaFactStore,
+    ) -> BingoResult<PressureResponse> {
+        self(event, fact_store)
+    }
+}
+
+/// Sheds facts whose timestamp is older than a time-to-live
+#[derive(Debug, Clone)]
+pub struct TtlShedder {
+    pub ttl: chrono::Duration,
+}
+
+impl TtlShedder {
+    pub fn new(ttl: chrono::Duration) -> Self {
+        Self { ttl }
+    }
+}
+
+impl MemoryPressureHandler for TtlShedder {
+    fn on_pressure(
+        &self,
+        _event: &MemoryPressureEvent,
+        fact_store: &ArenaFactStore,
+    ) -> BingoResult<PressureResponse> {
+        let cutoff = chrono::Utc::now() - self.ttl;
+        let expired: Vec<FactId> = fact_store
+            .iter()
+            .into_iter()
+            .filter(|fact| fact.timestamp < cutoff)
+            .map(|fact| fact.id)
+            .collect();
+
+        Ok(if expired.is_empty() {
+            PressureResponse::None
+        } else {
+            PressureResponse::ShedFacts(expired)
+        })
+    }
+
+    fn name(&self) -> &str {
+        "ttl_shedder"
+    }
+}
+
+/// Sheds the lowest-priority facts, ranked by a numeric field
+///
+/// Facts without the field are treated as lowest priority; ties shed the oldest first.
+#[derive(Debug, Clone)]
+pub struct PriorityShedder {
+    pub priority_field: String,
+}
+
+impl PriorityShedder {
+    pub fn new(priority_field: impl Into<String>) -> Self {
+        Self { priority_field: priority_field.into() }
+    }
+}
+
+impl MemoryPressureHandler for PriorityShedder {
+    fn on_pressure(
+        &self,
+        event: &MemoryPressureEvent,
+        fact_store: &ArenaFactStore,
+    ) -> BingoResult<PressureResponse> {
+        let mut facts = fact_store.iter();
+        facts.sort_by(|a, b| {
+            let priority = |fact: &Fact| {
+                fact.data
+                    .fields
+                    .get(&self.priority_field)
+                    .and_then(FactValue::as_f64)
+                    .unwrap_or(f64::NEG_INFINITY)
+            };
+            priority(a).total_cmp(&priority(b)).then(a.timestamp.cmp(&b.timestamp))
+        });
+
+        let shed: Vec<FactId> =
+            facts.iter().take(event.facts_to_free()).map(|fact| fact.id).collect();
+        Ok(if shed.is_empty() {
+            PressureResponse::None
+        } else {
+            PressureResponse::ShedFacts(shed)
+        })
+    }
+
+    fn name(&self) -> &str {
+        "priority_shedder"
+    }
+}
+
+/// Spills the coldest partitions of working memory to disk
+///
+/// Facts are partitioned by `partition_field`; a partition's temperature is the
+/// timestamp of its newest fact. Partitions are spilled coldest first until enough
+/// facts have been released, each batch as one JSON-lines file in `spill_dir`.
+#[derive(Debug, Clone)]
+pub struct DiskSpiller {
+    pub spill_dir: PathBuf,
+    pub partition_field: String,
+}
+
+impl DiskSpiller {
+    pub fn new(spill_dir: impl Into<PathBuf>, partition_field: impl Into<String>) -> Self {
+        Self { spill_dir: spill_dir.into(), partition_field: partition_field.into() }
+    }
+
+    /// Load facts back from a spill file
+    pub fn restore(path: &Path) -> BingoResult<Vec<Fact>> {
+        let file = std::fs::File::open(path)
+            .map_err(|e| BingoError::external_service("filesystem", e.to_string()))?;
+
+        let mut facts = Vec::new();
+        for line in std::io::BufReader::new(file).lines() {
+            let line =
+                line.map_err(|e| BingoError::external_service("filesystem", e.to_string()))?;
+            if line.is_empty() {
+                continue;
+            }
+            let fact = serde_json::from_str(&line)
+                .map_err(|e| BingoError::serialization("fact", "deserialize", e.to_string()))?;
+            facts.push(fact);
+        }
+        Ok(facts)
+    }
+
+    fn write_spill_file(&self, facts: &[&Fact]) -> BingoResult<PathBuf> {
+        std::fs::create_dir_all(&self.spill_dir)
+            .map_err(|e| BingoError::external_service("filesystem", e.to_string()))?;
+
+        let path = self.spill_dir.join(format!("spill-{}.jsonl", uuid::Uuid::new_v4()));
+        let file = std::fs::File::create(&path)
+            .map_err(|e| BingoError::external_service("filesystem", e.to_string()))?;
+        let mut writer = BufWriter::new(file);
+
+        for fact in facts {
+            serde_json::to_writer(&mut writer, fact)
+                .map_err(|e| BingoError::serialization("fact", "serialize", e.to_string()))?;
+            writer
+                .write_all(b"\n")
+                .map_err(|e| BingoError::external_service("filesystem", e.to_string()))?;
+        }
+        writer
+            .flush()
+            .map_err(|e| BingoError::external_service("filesystem", e.to_string()))?;
+
+        Ok(path)
+    }
+}
+
+impl MemoryPressureHandler for DiskSpiller {
+    fn on_pressure(
+        &self,
+        event: &MemoryPressureEvent,
+        fact_store: &ArenaFactStore,
+    ) -> BingoResult<PressureResponse> {
+        let facts = fact_store.iter();
+
+        let mut partitions: HashMap<Option<&FactValue>, Vec<&Fact>> = HashMap::new();
+        for fact in &facts {
+            partitions
+                .entry(fact.data.fields.get(&self.partition_field))
+                .or_default()
+                .push(fact);
+        }
+
+        let mut partitions: Vec<Vec<&Fact>> = partitions.into_values().collect();
+        partitions.sort_by_key(|partition| partition.iter().map(|fact| fact.timestamp).max());
+
+        let target = event.facts_to_free();
+        let mut spilled: Vec<&Fact> = Vec::new();
+        for partition in partitions {
+            if spilled.len() >= target {
+                break;
+            }
+            spilled.extend(partition);
+        }
+
+        if spilled.is_empty() {
+            return Ok(PressureResponse::None);
+        }
+
+        let path = self.write_spill_file(&spilled)?;
+        info!(
+            spilled_facts = spilled.len(),
+            path = %path.display(),
+            "Spilled cold working memory partitions to disk"
+        );
+        Ok(PressureResponse::SpilledFacts {
+            fact_ids: spilled.iter().map(|fact| fact.id).collect(),
+            path,
+        })
+    }
+
+    fn name(&self) -> &str {
+        "disk_spiller"
+    }
+}
+
+/// Rejects the incoming batch
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InsertRejector;
+
+impl MemoryPressureHandler for InsertRejector {
+    fn on_pressure(
+        &self,
+        _event: &MemoryPressureEvent,
+        _fact_store: &ArenaFactStore,
+    ) -> BingoResult<PressureResponse> {
+        Ok(PressureResponse::RejectInserts)
+    }
+
+    fn name(&self) -> &str {
+        "insert_rejector"
+    }
+}
+
+/// Cumulative memory pressure statistics
+#[derive(Debug, Clone, Default)]
+pub struct MemoryPressureStats {
+    pub checks: u64,
+    pub pressure_events: u64,
+    pub facts_shed: u64,
+    pub facts_spilled: u64,
+    pub inserts_rejected: u64,
+    pub handler_errors: u64,
+    pub last_level: PressureLevel,
+    pub spill_files: Vec<PathBuf>,
+}
+
+/// Result of a pressure check
+#[derive(Debug, Clone, Default)]
+pub struct PressureOutcome {
+    /// Highest level observed during the check
+    pub level: PressureLevel,
+    /// Facts removed from the fact store that must also leave the RETE network
+    pub released_facts: Vec<FactId>,
+    /// Whether the incoming batch must be refused
+    pub rejected: bool,
+    /// Estimated usage after relief
+    pub used_bytes: usize,
+}
+
+struct RegisteredHandler {
+    min_level: PressureLevel,
+    handler: Box<dyn MemoryPressureHandler>,
+}
+
+/// Watches working memory size and invokes handlers when watermarks are crossed
+pub struct MemoryPressureMonitor {
+    watermarks: MemoryWatermarks,
+    bytes_per_fact: usize,
+    handlers: Vec<RegisteredHandler>,
+    stats: MemoryPressureStats,
+}
+
+impl std::fmt::Debug for MemoryPressureMonitor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MemoryPressureMonitor")
+            .field("watermarks", &self.watermarks)
+            .field("bytes_per_fact", &self.bytes_per_fact)
+            .field(
+                "handlers",
+                &self.handlers.iter().map(|h| h.handler.name()).collect::<Vec<_>>(),
+            )
+            .field("stats", &self.stats)
+            .finish()
+    }
+}
+
+impl Default for MemoryPressureMonitor {
+    fn default() -> Self {
+        Self::new(MemoryWatermarks::disabled())
+    }
+}
+
+impl MemoryPressureMonitor {
+    /// Create a monitor with the given watermarks and no handlers
+    pub fn new(watermarks: MemoryWatermarks) -> Self {
+        Self {
+            watermarks,
+            bytes_per_fact: DEFAULT_BYTES_PER_FACT,
+            handlers: Vec::new(),
+            stats: MemoryPressureStats::default(),
+        }
+    }
+
+    /// Override the estimated size of a stored fact
+    pub fn with_bytes_per_fact(mut self, bytes_per_fact: usize) -> Self {
+        self.bytes_per_fact = bytes_per_fact.max(1);
+        self
+    }
+
+    pub fn watermarks(&self) -> MemoryWatermarks {
+        self.watermarks
+    }
+
+    pub fn set_watermarks(&mut self, watermarks: MemoryWatermarks) {
+        self.watermarks = watermarks;
+    }
+
+    /// Register a handler that runs once pressure reaches `min_level`
+    ///
+    /// Handlers run in registration order until usage drops below the warning watermark.
+    pub fn register_handler(
+        &mut self,
+        min_level: PressureLevel,
+        handler: impl MemoryPressureHandler + 'static,
+    ) {
+        self.handlers.push(RegisteredHandler { min_level, handler: Box::new(handler) });
+    }
+
+    pub fn handler_count(&self) -> usize {
+        self.handlers.len()
+    }
+
+    pub fn stats(&self) -> &MemoryPressureStats {
+        &self.stats
+    }
+
+    /// Estimated working memory held by the fact store
+    pub fn estimate_usage(&self, fact_store: &ArenaFactStore) -> usize {
+        fact_store.len() * self.bytes_per_fact + fact_store.arena_stats().reserved_bytes
+    }
+
+    /// Check pressure ahead of inserting `incoming_facts` facts and relieve it if needed
+    ///
+    /// Facts shed or spilled by handlers are deleted from `fact_store` and reported in
+    /// the outcome so the caller can withdraw them from the RETE network as well.
+    pub fn check(&mut self, fact_store: &ArenaFactStore, incoming_facts: usize) -> PressureOutcome {
+        self.stats.checks += 1;
+        let incoming_bytes = incoming_facts * self.bytes_per_fact;
+        let mut used_bytes = self.estimate_usage(fact_store) + incoming_bytes;
+        let initial_level = self.watermarks.level_for(used_bytes);
+
+        let mut outcome =
+            PressureOutcome { level: initial_level, used_bytes, ..Default::default() };
+        self.stats.last_level = initial_level;
+        if initial_level == PressureLevel::Normal {
+            return outcome;
+        }
+
+        self.stats.pressure_events += 1;
+        warn!(
+            level = ?initial_level,
+            used_bytes = used_bytes,
+            fact_count = fact_store.len(),
+            "Working memory crossed a memory watermark"
+        );
+
+        for registered in &self.handlers {
+            let level = self.watermarks.level_for(used_bytes);
+            if level == PressureLevel::Normal {
+                break;
+            }
+            if level < registered.min_level {
+                continue;
+            }
+
+            let event = MemoryPressureEvent {
+                level,
+                used_bytes,
+                watermarks: self.watermarks,
+                fact_count: fact_store.len(),
+                incoming_facts,
+                bytes_per_fact: self.bytes_per_fact,
+            };
+
+            let response = match registered.handler.on_pressure(&event, fact_store) {
+                Ok(response) => response,
+                Err(e) => {
+                    self.stats.handler_errors += 1;
+                    warn!(handler = registered.handler.name(), error = %e, "Memory pressure handler failed");
+                    continue;
+                }
+            };
+
+            match response {
+                PressureResponse::None => {}
+                PressureResponse::ShedFacts(fact_ids) => {
+                    let released = Self::release(fact_store, &fact_ids, &mut outcome);
+                    self.stats.facts_shed += released as u64;
+                }
+                PressureResponse::SpilledFacts { fact_ids, path } => {
+                    let released = Self::release(fact_store, &fact_ids, &mut outcome);
+                    self.stats.facts_spilled += released as u64;
+                    self.stats.spill_files.push(path);
+                }
+                PressureResponse::RejectInserts => {
+                    outcome.rejected = true;
+                    self.stats.inserts_rejected += incoming_facts as u64;
+                    break;
+                }
+            }
+
+            used_bytes = self.estimate_usage(fact_store) + incoming_bytes;
+        }
+
+        outcome.used_bytes = used_bytes;
+        self.stats.last_level = self.watermarks.level_for(used_bytes);
+        info!(
+            released_facts = outcome.released_facts.len(),
+            rejected = outcome.rejected,
+            used_bytes = used_bytes,
+            "Memory pressure relief completed"
+        );
+        outcome
+    }
+
+    fn release(
+        fact_store: &ArenaFactStore,
+        fact_ids: &[FactId],
+        outcome: &mut PressureOutcome,
+    ) -> usize {
+        let mut released = 0;
+        for &fact_id in fact_ids {
+            if fact_store.delete_fact(fact_id) {
+                outcome.released_facts.push(fact_id);
+                released += 1;
+            }
+        }
+        released
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::FactData;
+
+    fn store_with_facts(count: u64) -> ArenaFactStore {
+        let store = ArenaFactStore::new();
+        for id in 1..=count {
+            let mut fields = HashMap::new();
+            fields.insert("priority".to_string(), FactValue::Integer(id as i64));
+            fields.insert("partition".to_string(), FactValue::Integer((id % 2) as i64));
+            store.insert(Fact::new(id, FactData { fields }));
+        }
+        store
+    }
+
+    /// Watermarks sized in facts so the tests do not depend on arena slab sizing
+    fn monitor(store: &ArenaFactStore, warning_facts: usize) -> MemoryPressureMonitor {
+        let arena = store.arena_stats().reserved_bytes;
+        let bytes = |facts: usize| arena + facts * DEFAULT_BYTES_PER_FACT;
+        let watermarks = MemoryWatermarks::new(
+            bytes(warning_facts),
+            bytes(warning_facts + 2),
+            bytes(warning_facts + 4),
+        )
+        .unwrap();
+        MemoryPressureMonitor::new(watermarks)
+    }
+
+    #[test]
+    fn test_watermark_levels() {
+        let watermarks = MemoryWatermarks::new(10, 20, 30).unwrap();
+        assert_eq!(watermarks.level_for(5), PressureLevel::Normal);
+        assert_eq!(watermarks.level_for(10), PressureLevel::Warning);
+        assert_eq!(watermarks.level_for(25), PressureLevel::High);
+        assert_eq!(watermarks.level_for(30), PressureLevel::Critical);
+        assert!(MemoryWatermarks::new(30, 20, 10).is_err());
+        assert!(!MemoryWatermarks::disabled().is_enabled());
+    }
+
+    #[test]
+    fn test_priority_shedder_releases_lowest_priority() {
+        let store = store_with_facts(10);
+        let mut monitor = monitor(&store, 8);
+        monitor.register_handler(PressureLevel::Warning, PriorityShedder::new("priority"));
+
+        let outcome = monitor.check(&store, 0);
+        assert_eq!(outcome.level, PressureLevel::High);
+        assert_eq!(outcome.released_facts, vec![1, 2, 3]);
+        assert!(!outcome.rejected);
+        assert_eq!(store.len(), 7);
+        assert_eq!(monitor.stats().facts_shed, 3);
+        assert_eq!(monitor.stats().last_level, PressureLevel::Normal);
+    }
+
+    #[test]
+    fn test_handlers_respect_min_level_and_reject() {
+        let store = store_with_facts(10);
+        let mut monitor = monitor(&store, 8);
+        monitor.register_handler(PressureLevel::Critical, PriorityShedder::new("priority"));
+        monitor.register_handler(PressureLevel::Warning, InsertRejector);
+
+        let outcome = monitor.check(&store, 1);
+        assert!(outcome.released_facts.is_empty());
+        assert!(outcome.rejected);
+        assert_eq!(monitor.stats().inserts_rejected, 1);
+    }
+
+    #[test]
+    fn test_disk_spiller_round_trip() {
+        let store = store_with_facts(6);
+        let mut monitor = monitor(&store, 4);
+        let spill_dir = std::env::temp_dir().join(format!("bingo-spill-{}", uuid::Uuid::new_v4()));
+        monitor.register_handler(
+            PressureLevel::Warning,
+            DiskSpiller::new(&spill_dir, "partition"),
+        );
+
+        let outcome = monitor.check(&store, 0);
+        assert_eq!(outcome.released_facts.len(), 3);
+        assert_eq!(store.len(), 3);
+
+        let spill_file = &monitor.stats().spill_files[0];
+        let restored = DiskSpiller::restore(spill_file).unwrap();
+        assert_eq!(restored.len(), 3);
+
+        std::fs::remove_dir_all(&spill_dir).unwrap();
+    }
+
+    #[test]
+    fn test_closure_handler() {
+        let store = store_with_facts(10);
+        let mut monitor = monitor(&store, 8);
+        monitor.register_handler(
+            PressureLevel::Warning,
+            |_event: &MemoryPressureEvent,
+             _store: &ArenaFactStore|
+             -> BingoResult<PressureResponse> {
+                Ok(PressureResponse::ShedFacts(vec![10, 9]))
+            },
+        );
+
+        let outcome = monitor.check(&store, 0);
+        assert_eq!(outcome.released_facts, vec![10, 9]);
+    }
+}
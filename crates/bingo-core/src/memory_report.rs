@@ -0,0 +1,274 @@
+//! Working memory size and composition reports
+//!
+//! [`MemoryReport`] breaks an engine's memory down far enough to diagnose capacity
+//! problems without a debugger: which kinds of facts and which tenants hold the most
+//! memory, how large the field indexes and beta memories have grown, and which rules
+//! account for the most network state. Reports print for humans through `Display` and
+//! serialize to JSON for dashboards and support tickets.
+//!
+//! Fact types and tenants are read from fact fields, `type` and `tenant_id` unless
+//! [`MemoryReportOptions`] names others. Sizes are estimates with the same accounting
+//! as [`MemoryBreakdown`].
+
+use crate::memory::MemoryBreakdown;
+use crate::types::{NodeId, RuleId};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Fields and list lengths used to build a [`MemoryReport`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryReportOptions {
+    /// Field naming a fact's type
+    pub type_field: String,
+    /// Field naming the tenant owning a fact
+    pub tenant_field: String,
+    /// Entries kept in each ranked list, largest first
+    pub max_entries: usize,
+}
+
+impl Default for MemoryReportOptions {
+    fn default() -> Self {
+        Self {
+            type_field: "type".to_string(),
+            tenant_field: "tenant_id".to_string(),
+            max_entries: 10,
+        }
+    }
+}
+
+/// Facts sharing a value of the type or tenant field
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FactGroupUsage {
+    /// Field value shared by the group, `None` for facts without the field
+    pub key: Option<String>,
+    pub facts: usize,
+    pub bytes: usize,
+}
+
+/// Size of the fact store's index over one field
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct IndexUsage {
+    pub field: String,
+    pub distinct_values: usize,
+    /// Fact IDs held across all values
+    pub entries: usize,
+    pub bytes: usize,
+}
+
+/// Size of one beta node's token memory
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BetaMemoryUsage {
+    pub node_id: NodeId,
+    /// Rules whose matches pass through the node
+    pub rule_ids: Vec<RuleId>,
+    pub tokens: usize,
+    pub bytes: usize,
+}
+
+/// Network memory attributed to one rule
+///
+/// State shared between rules, like an alpha memory several rules test, is split
+/// evenly among them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct RuleMemoryUsage {
+    pub rule_id: RuleId,
+    pub name: String,
+    /// Conditions, actions and name of the rule itself
+    pub definition_bytes: usize,
+    pub alpha_bytes: usize,
+    pub beta_bytes: usize,
+    /// Aggregation and stream window state
+    pub aggregation_bytes: usize,
+}
+
+impl RuleMemoryUsage {
+    /// Total bytes attributed to the rule
+    pub fn total(&self) -> usize {
+        self.definition_bytes + self.alpha_bytes + self.beta_bytes + self.aggregation_bytes
+    }
+}
+
+/// Size and composition of an engine's working memory
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MemoryReport {
+    pub total_bytes: usize,
+    pub breakdown: MemoryBreakdown,
+    pub fact_count: usize,
+    pub facts_by_type: Vec<FactGroupUsage>,
+    pub facts_by_tenant: Vec<FactGroupUsage>,
+    pub field_indexes: Vec<IndexUsage>,
+    /// Beta memories holding tokens
+    pub beta_memories: Vec<BetaMemoryUsage>,
+    pub top_rules: Vec<RuleMemoryUsage>,
+}
+
+impl MemoryReport {
+    /// Fill in the total and rank every list largest first, keeping the top `limit`
+    /// entries of each
+    pub(crate) fn ranked(mut self, limit: usize) -> Self {
+        self.total_bytes = self.breakdown.total();
+        rank(&mut self.facts_by_type, limit, |group| {
+            (group.bytes, group.facts)
+        });
+        rank(&mut self.facts_by_tenant, limit, |group| {
+            (group.bytes, group.facts)
+        });
+        rank(&mut self.field_indexes, limit, |index| {
+            (index.bytes, index.entries)
+        });
+        self.beta_memories.retain(|memory| memory.tokens > 0);
+        rank(&mut self.beta_memories, limit, |memory| {
+            (memory.bytes, memory.tokens)
+        });
+        rank(&mut self.top_rules, limit, |rule| (rule.total(), 0));
+        self
+    }
+
+    /// The report as pretty-printed JSON
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// Sort `entries` by descending `size` and keep the first `limit`
+///
+/// The sort is stable, so callers pass entries in a deterministic order for ties.
+fn rank<T>(entries: &mut Vec<T>, limit: usize, size: impl Fn(&T) -> (usize, usize)) {
+    entries.sort_by_key(|entry| std::cmp::Reverse(size(entry)));
+    entries.truncate(limit);
+}
+
+/// Count facts and bytes per group key
+pub(crate) fn group_usage(
+    facts: impl IntoIterator<Item = (Option<String>, usize)>,
+) -> Vec<FactGroupUsage> {
+    let mut groups: HashMap<Option<String>, (usize, usize)> = HashMap::new();
+    for (key, bytes) in facts {
+        let group = groups.entry(key).or_default();
+        group.0 += 1;
+        group.1 += bytes;
+    }
+    let mut groups: Vec<FactGroupUsage> = groups
+        .into_iter()
+        .map(|(key, (facts, bytes))| FactGroupUsage { key, facts, bytes })
+        .collect();
+    groups.sort_by(|a, b| a.key.cmp(&b.key));
+    groups
+}
+
+fn kb(bytes: usize) -> String {
+    format!("{:.2} KB", bytes as f64 / 1024.0)
+}
+
+fn write_groups(f: &mut fmt::Formatter<'_>, title: &str, groups: &[FactGroupUsage]) -> fmt::Result {
+    if groups.is_empty() {
+        return Ok(());
+    }
+    writeln!(f, "  {title}:")?;
+    for group in groups {
+        let key = group.key.as_deref().unwrap_or("(none)");
+        writeln!(f, "    {key}: {} facts, {}", group.facts, kb(group.bytes))?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for MemoryReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Working memory: {} across {} facts",
+            kb(self.total_bytes),
+            self.fact_count
+        )?;
+        writeln!(f, "  {}", self.breakdown.format())?;
+        write_groups(f, "Facts by type", &self.facts_by_type)?;
+        write_groups(f, "Facts by tenant", &self.facts_by_tenant)?;
+
+        if !self.field_indexes.is_empty() {
+            writeln!(f, "  Field indexes:")?;
+            for index in &self.field_indexes {
+                writeln!(
+                    f,
+                    "    {}: {} values, {} entries, {}",
+                    index.field,
+                    index.distinct_values,
+                    index.entries,
+                    kb(index.bytes)
+                )?;
+            }
+        }
+        if !self.beta_memories.is_empty() {
+            writeln!(f, "  Beta memories:")?;
+            for memory in &self.beta_memories {
+                let rules: Vec<String> = memory.rule_ids.iter().map(u64::to_string).collect();
+                writeln!(
+                    f,
+                    "    node {} (rules {}): {} tokens, {}",
+                    memory.node_id,
+                    rules.join(", "),
+                    memory.tokens,
+                    kb(memory.bytes)
+                )?;
+            }
+        }
+        if !self.top_rules.is_empty() {
+            writeln!(f, "  Top rules:")?;
+            for rule in &self.top_rules {
+                writeln!(
+                    f,
+                    "    {} '{}': {} (definition {}, alpha {}, beta {}, aggregations {})",
+                    rule.rule_id,
+                    rule.name,
+                    kb(rule.total()),
+                    kb(rule.definition_bytes),
+                    kb(rule.alpha_bytes),
+                    kb(rule.beta_bytes),
+                    kb(rule.aggregation_bytes)
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_groups_are_ranked_and_truncated() {
+        let groups = group_usage([
+            (Some("order".to_string()), 100),
+            (None, 10),
+            (Some("order".to_string()), 100),
+            (Some("payment".to_string()), 300),
+        ]);
+        let report = MemoryReport {
+            total_bytes: 0,
+            breakdown: MemoryBreakdown::default(),
+            fact_count: 4,
+            facts_by_type: groups,
+            facts_by_tenant: Vec::new(),
+            field_indexes: Vec::new(),
+            beta_memories: Vec::new(),
+            top_rules: Vec::new(),
+        }
+        .ranked(2);
+
+        let ranked: Vec<_> = report
+            .facts_by_type
+            .iter()
+            .map(|group| (group.key.as_deref(), group.facts, group.bytes))
+            .collect();
+        assert_eq!(
+            ranked,
+            vec![(Some("payment"), 1, 300), (Some("order"), 2, 200)]
+        );
+
+        let text = report.to_string();
+        assert!(text.contains("across 4 facts"), "{text}");
+        assert!(text.contains("payment: 1 facts, 0.29 KB"), "{text}");
+        assert!(!text.contains("Facts by tenant"), "{text}");
+    }
+}
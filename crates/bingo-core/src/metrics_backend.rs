@@ -0,0 +1,466 @@
+//! Pluggable metrics backends
+//!
+//! `EnhancedMonitoring` emits every metric it records through a `MetricsBackend`, so
+//! embedders that already run a metrics stack get Bingo's metrics in it instead of a
+//! second stack alongside. The backend is selected with `MonitoringConfig::metrics_backend`,
+//! or any implementation can be passed to `EnhancedMonitoring::with_metrics_backend`:
+//!
+//! - **`NoopMetrics`**: Discards every metric; the default
+//! - **`InMemoryMetrics`**: Keeps current values for assertions in tests
+//! - **`PrometheusMetrics`**: Keeps current values and renders them in the Prometheus
+//!   text exposition format for a scrape endpoint
+//! - **`StatsdMetrics`**: Sends each metric as a statsd datagram over UDP, with labels as
+//!   DogStatsD tags
+//! - **`OpenTelemetryMetrics`** (`otel` feature): Records to an OpenTelemetry `Meter`
+//!
+//! Emitting a metric never fails; backends that cannot deliver a metric log and drop it.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::io;
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+use std::sync::{Arc, Mutex};
+use tracing::debug;
+
+/// Destination for metrics emitted by the engine
+pub trait MetricsBackend: Send + Sync + std::fmt::Debug {
+    /// Add `value` to a monotonically increasing counter
+    fn increment_counter(&self, name: &str, value: u64, labels: &[(&str, &str)]);
+
+    /// Set a gauge to its current value
+    fn set_gauge(&self, name: &str, value: f64, labels: &[(&str, &str)]);
+
+    /// Record one observation of a distribution
+    fn record_histogram(&self, name: &str, value: f64, labels: &[(&str, &str)]);
+
+    /// Text exposition of current values, for backends that are scraped rather than
+    /// pushed to
+    fn render(&self) -> Option<String> {
+        None
+    }
+}
+
+/// Metrics backend selected by `MonitoringConfig`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum MetricsBackendConfig {
+    /// Discard all metrics
+    #[default]
+    None,
+    /// Keep current values in memory
+    InMemory,
+    /// Keep current values for scraping, with names prefixed by `namespace_`
+    Prometheus { namespace: String },
+    /// Push to a statsd agent at `address`, with names prefixed by `prefix.`
+    Statsd { address: String, prefix: String },
+    /// Record to the global OpenTelemetry meter provider
+    #[cfg(feature = "otel")]
+    OpenTelemetry { meter_name: String },
+}
+
+impl MetricsBackendConfig {
+    /// Create the configured backend
+    pub fn build(&self) -> io::Result<Arc<dyn MetricsBackend>> {
+        Ok(match self {
+            Self::None => Arc::new(NoopMetrics),
+            Self::InMemory => Arc::new(InMemoryMetrics::new()),
+            Self::Prometheus { namespace } => Arc::new(PrometheusMetrics::new(namespace.clone())),
+            Self::Statsd { address, prefix } => {
+                Arc::new(StatsdMetrics::connect(address.as_str(), prefix.clone())?)
+            }
+            #[cfg(feature = "otel")]
+            Self::OpenTelemetry { meter_name } => Arc::new(OpenTelemetryMetrics::new(
+                opentelemetry::global::meter(meter_name.clone()),
+            )),
+        })
+    }
+}
+
+/// Backend discarding every metric
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoopMetrics;
+
+impl MetricsBackend for NoopMetrics {
+    fn increment_counter(&self, _name: &str, _value: u64, _labels: &[(&str, &str)]) {}
+
+    fn set_gauge(&self, _name: &str, _value: f64, _labels: &[(&str, &str)]) {}
+
+    fn record_histogram(&self, _name: &str, _value: f64, _labels: &[(&str, &str)]) {}
+}
+
+/// Observations recorded for one histogram
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct HistogramSummary {
+    pub count: u64,
+    pub sum: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl HistogramSummary {
+    fn observe(&mut self, value: f64) {
+        if self.count == 0 {
+            self.min = value;
+            self.max = value;
+        } else {
+            self.min = self.min.min(value);
+            self.max = self.max.max(value);
+        }
+        self.count += 1;
+        self.sum += value;
+    }
+}
+
+/// Metric name with its labels, sorted so label order does not matter
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct MetricKey {
+    name: String,
+    labels: Vec<(String, String)>,
+}
+
+impl MetricKey {
+    fn new(name: &str, labels: &[(&str, &str)]) -> Self {
+        let mut labels: Vec<(String, String)> =
+            labels.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        labels.sort();
+        Self { name: name.to_string(), labels }
+    }
+}
+
+/// Current value of every metric, shared by the in-memory and Prometheus backends
+#[derive(Debug, Default)]
+struct MetricStore {
+    counters: BTreeMap<MetricKey, u64>,
+    gauges: BTreeMap<MetricKey, f64>,
+    histograms: BTreeMap<MetricKey, HistogramSummary>,
+}
+
+impl MetricStore {
+    fn increment_counter(&mut self, name: &str, value: u64, labels: &[(&str, &str)]) {
+        *self.counters.entry(MetricKey::new(name, labels)).or_default() += value;
+    }
+
+    fn set_gauge(&mut self, name: &str, value: f64, labels: &[(&str, &str)]) {
+        self.gauges.insert(MetricKey::new(name, labels), value);
+    }
+
+    fn record_histogram(&mut self, name: &str, value: f64, labels: &[(&str, &str)]) {
+        self.histograms.entry(MetricKey::new(name, labels)).or_default().observe(value);
+    }
+
+    /// Prometheus text exposition of every metric, with names prefixed by `namespace_`
+    fn render(&self, namespace: &str) -> String {
+        let mut out = String::new();
+        let mut last_name = None;
+        let mut declare = |out: &mut String, name: &str, kind: &str| {
+            if last_name.as_deref() != Some(name) {
+                let _ = writeln!(out, "# TYPE {name} {kind}");
+                last_name = Some(name.to_string());
+            }
+        };
+
+        for (key, value) in &self.counters {
+            let name = prometheus_name(namespace, &key.name);
+            declare(&mut out, &name, "counter");
+            let _ = writeln!(out, "{name}{} {value}", prometheus_labels(&key.labels));
+        }
+        for (key, value) in &self.gauges {
+            let name = prometheus_name(namespace, &key.name);
+            declare(&mut out, &name, "gauge");
+            let _ = writeln!(out, "{name}{} {value}", prometheus_labels(&key.labels));
+        }
+        for (key, summary) in &self.histograms {
+            let name = prometheus_name(namespace, &key.name);
+            declare(&mut out, &name, "summary");
+            let labels = prometheus_labels(&key.labels);
+            let _ = writeln!(out, "{name}_sum{labels} {}", summary.sum);
+            let _ = writeln!(out, "{name}_count{labels} {}", summary.count);
+        }
+        out
+    }
+}
+
+/// Metric name restricted to the characters Prometheus accepts
+fn prometheus_name(namespace: &str, name: &str) -> String {
+    let name = if namespace.is_empty() {
+        name.to_string()
+    } else {
+        format!("{namespace}_{name}")
+    };
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == ':' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn prometheus_labels(labels: &[(String, String)]) -> String {
+    if labels.is_empty() {
+        return String::new();
+    }
+    let labels: Vec<String> = labels
+        .iter()
+        .map(|(key, value)| {
+            let value = value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n");
+            format!("{}=\"{value}\"", prometheus_name("", key))
+        })
+        .collect();
+    format!("{{{}}}", labels.join(","))
+}
+
+/// Backend keeping current values in memory, for assertions in tests
+#[derive(Debug, Default)]
+pub struct InMemoryMetrics {
+    store: Mutex<MetricStore>,
+}
+
+impl InMemoryMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Total of a counter, 0 if it was never incremented
+    pub fn counter(&self, name: &str, labels: &[(&str, &str)]) -> u64 {
+        let store = self.store.lock().unwrap();
+        store.counters.get(&MetricKey::new(name, labels)).copied().unwrap_or_default()
+    }
+
+    /// Current value of a gauge
+    pub fn gauge(&self, name: &str, labels: &[(&str, &str)]) -> Option<f64> {
+        self.store.lock().unwrap().gauges.get(&MetricKey::new(name, labels)).copied()
+    }
+
+    /// Observations recorded for a histogram
+    pub fn histogram(&self, name: &str, labels: &[(&str, &str)]) -> Option<HistogramSummary> {
+        self.store
+            .lock()
+            .unwrap()
+            .histograms
+            .get(&MetricKey::new(name, labels))
+            .copied()
+    }
+
+    /// Names of every metric emitted so far
+    pub fn metric_names(&self) -> Vec<String> {
+        let store = self.store.lock().unwrap();
+        let mut names: Vec<String> = store
+            .counters
+            .keys()
+            .chain(store.gauges.keys())
+            .chain(store.histograms.keys())
+            .map(|key| key.name.clone())
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+}
+
+impl MetricsBackend for InMemoryMetrics {
+    fn increment_counter(&self, name: &str, value: u64, labels: &[(&str, &str)]) {
+        self.store.lock().unwrap().increment_counter(name, value, labels);
+    }
+
+    fn set_gauge(&self, name: &str, value: f64, labels: &[(&str, &str)]) {
+        self.store.lock().unwrap().set_gauge(name, value, labels);
+    }
+
+    fn record_histogram(&self, name: &str, value: f64, labels: &[(&str, &str)]) {
+        self.store.lock().unwrap().record_histogram(name, value, labels);
+    }
+
+    fn render(&self) -> Option<String> {
+        Some(self.store.lock().unwrap().render(""))
+    }
+}
+
+/// Backend rendering current values in the Prometheus text exposition format
+///
+/// Histograms are exposed as summaries with `_sum` and `_count` series.
+#[derive(Debug, Default)]
+pub struct PrometheusMetrics {
+    namespace: String,
+    store: Mutex<MetricStore>,
+}
+
+impl PrometheusMetrics {
+    /// Backend prefixing every metric name with `namespace_`, unless it is empty
+    pub fn new(namespace: impl Into<String>) -> Self {
+        Self { namespace: namespace.into(), store: Mutex::default() }
+    }
+
+    /// Current values as a scrape response body
+    pub fn render_text(&self) -> String {
+        self.store.lock().unwrap().render(&self.namespace)
+    }
+}
+
+impl MetricsBackend for PrometheusMetrics {
+    fn increment_counter(&self, name: &str, value: u64, labels: &[(&str, &str)]) {
+        self.store.lock().unwrap().increment_counter(name, value, labels);
+    }
+
+    fn set_gauge(&self, name: &str, value: f64, labels: &[(&str, &str)]) {
+        self.store.lock().unwrap().set_gauge(name, value, labels);
+    }
+
+    fn record_histogram(&self, name: &str, value: f64, labels: &[(&str, &str)]) {
+        self.store.lock().unwrap().record_histogram(name, value, labels);
+    }
+
+    fn render(&self) -> Option<String> {
+        Some(self.render_text())
+    }
+}
+
+/// Backend sending each metric to a statsd agent as one UDP datagram
+#[derive(Debug)]
+pub struct StatsdMetrics {
+    socket: UdpSocket,
+    prefix: String,
+}
+
+impl StatsdMetrics {
+    /// Backend sending to the agent at `address`, prefixing names with `prefix.`
+    pub fn connect(address: impl ToSocketAddrs, prefix: impl Into<String>) -> io::Result<Self> {
+        let address = address.to_socket_addrs()?.next().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "statsd address resolved to nothing",
+            )
+        })?;
+        let local: SocketAddr = if address.is_ipv4() {
+            ([0, 0, 0, 0], 0).into()
+        } else {
+            ([0u16; 8], 0).into()
+        };
+        let socket = UdpSocket::bind(local)?;
+        socket.connect(address)?;
+        Ok(Self { socket, prefix: prefix.into() })
+    }
+
+    fn send(&self, name: &str, value: &str, kind: &str, labels: &[(&str, &str)]) {
+        let mut line = String::new();
+        if !self.prefix.is_empty() {
+            line.push_str(&self.prefix);
+            line.push('.');
+        }
+        let _ = write!(line, "{name}:{value}|{kind}");
+        if !labels.is_empty() {
+            let tags: Vec<String> = labels.iter().map(|(k, v)| format!("{k}:{v}")).collect();
+            let _ = write!(line, "|#{}", tags.join(","));
+        }
+
+        if let Err(e) = self.socket.send(line.as_bytes()) {
+            debug!(metric = name, error = %e, "Failed to send statsd metric");
+        }
+    }
+}
+
+impl MetricsBackend for StatsdMetrics {
+    fn increment_counter(&self, name: &str, value: u64, labels: &[(&str, &str)]) {
+        self.send(name, &value.to_string(), "c", labels);
+    }
+
+    fn set_gauge(&self, name: &str, value: f64, labels: &[(&str, &str)]) {
+        // A leading sign makes statsd adjust the gauge instead of setting it
+        if value < 0.0 {
+            self.send(name, "0", "g", labels);
+        }
+        self.send(name, &value.to_string(), "g", labels);
+    }
+
+    fn record_histogram(&self, name: &str, value: f64, labels: &[(&str, &str)]) {
+        self.send(name, &value.to_string(), "h", labels);
+    }
+}
+
+#[cfg(feature = "otel")]
+pub use otel::OpenTelemetryMetrics;
+
+#[cfg(feature = "otel")]
+mod otel {
+    use super::MetricsBackend;
+    use opentelemetry::KeyValue;
+    use opentelemetry::metrics::{Counter, Histogram, Meter, ObservableGauge};
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    /// Latest value of each labelled series of one gauge
+    type GaugeValues = Arc<Mutex<HashMap<Vec<(String, String)>, f64>>>;
+
+    /// Backend recording to an OpenTelemetry `Meter`
+    ///
+    /// Gauges are observable gauges reporting the latest value set for each label set
+    /// when the meter provider collects.
+    #[derive(Debug)]
+    pub struct OpenTelemetryMetrics {
+        meter: Meter,
+        counters: Mutex<HashMap<String, Counter<u64>>>,
+        histograms: Mutex<HashMap<String, Histogram<f64>>>,
+        gauges: Mutex<HashMap<String, (ObservableGauge<f64>, GaugeValues)>>,
+    }
+
+    impl OpenTelemetryMetrics {
+        pub fn new(meter: Meter) -> Self {
+            Self {
+                meter,
+                counters: Mutex::default(),
+                histograms: Mutex::default(),
+                gauges: Mutex::default(),
+            }
+        }
+    }
+
+    fn attributes(labels: &[(&str, &str)]) -> Vec<KeyValue> {
+        labels
+            .iter()
+            .map(|(k, v)| KeyValue::new(k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    impl MetricsBackend for OpenTelemetryMetrics {
+        fn increment_counter(&self, name: &str, value: u64, labels: &[(&str, &str)]) {
+            let mut counters = self.counters.lock().unwrap();
+            let counter = counters
+                .entry(name.to_string())
+                .or_insert_with(|| self.meter.u64_counter(name.to_string()).init());
+            counter.add(value, &attributes(labels));
+        }
+
+        fn set_gauge(&self, name: &str, value: f64, labels: &[(&str, &str)]) {
+            let mut gauges = self.gauges.lock().unwrap();
+            let (_, values) = gauges.entry(name.to_string()).or_insert_with(|| {
+                let values = GaugeValues::default();
+                let observed = Arc::clone(&values);
+                let gauge = self
+                    .meter
+                    .f64_observable_gauge(name.to_string())
+                    .with_callback(move |observer| {
+                        for (labels, value) in observed.lock().unwrap().iter() {
+                            let attributes: Vec<KeyValue> = labels
+                                .iter()
+                                .map(|(k, v)| KeyValue::new(k.clone(), v.clone()))
+                                .collect();
+                            observer.observe(*value, &attributes);
+                        }
+                    })
+                    .init();
+                (gauge, values)
+            });
+            let labels = labels.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+            values.lock().unwrap().insert(labels, value);
+        }
+
+        fn record_histogram(&self, name: &str, value: f64, labels: &[(&str, &str)]) {
+            let mut histograms = self.histograms.lock().unwrap();
+            let histogram = histograms
+                .entry(name.to_string())
+                .or_insert_with(|| self.meter.f64_histogram(name.to_string()).init());
+            histogram.record(value, &attributes(labels));
+        }
+    }
+}
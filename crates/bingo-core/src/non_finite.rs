@@ -0,0 +1,254 @@
+//! Handling of NaN and infinite floats
+//!
+//! NaN compares unequal to everything and infinities come out of overflowing sums and
+//! divisions by zero, so a non-finite float reaching a condition, an aggregate or a
+//! calculator result used to produce whatever the comparison happened to return.
+//! [`NonFinitePolicy`] decides what happens instead, wherever such a value enters the
+//! engine:
+//!
+//! - ingestion: a processed fact holds a non-finite float, in a field or nested in an
+//!   array or object
+//! - derivation: an aggregate or a calculator result comes out non-finite
+//!
+//! Every decision is counted in [`NonFiniteStats`].
+
+use crate::error::{BingoError, BingoResult};
+use crate::types::{Fact, FactValue};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// What happens to NaN and infinite floats
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NonFinitePolicy {
+    /// Drop facts holding non-finite floats before they are stored or matched, and
+    /// discard non-finite derived values: the aggregation condition does not match and
+    /// the calculator result is replaced by a log message
+    #[default]
+    Reject,
+    /// Replace non-finite floats with `FactValue::Null`, which fails every ordering
+    /// comparison
+    PropagateAsNull,
+    /// Fail the call that met the non-finite float; a failed ingestion stores nothing
+    Error,
+}
+
+/// Counts of the non-finite floats the engine has met
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NonFiniteStats {
+    /// Facts dropped at ingestion
+    pub facts_rejected: u64,
+    /// Non-finite floats replaced with null, in facts or derived values
+    pub values_nulled: u64,
+    /// Derived values discarded
+    pub values_discarded: u64,
+    /// Calls failed
+    pub errors: u64,
+}
+
+#[derive(Debug, Default)]
+struct NonFiniteCounters {
+    facts_rejected: AtomicU64,
+    values_nulled: AtomicU64,
+    values_discarded: AtomicU64,
+    errors: AtomicU64,
+}
+
+/// A [`NonFinitePolicy`] and the counters it reports to, shared by network clones
+#[derive(Debug, Clone, Default)]
+pub(crate) struct NonFiniteGuard {
+    policy: NonFinitePolicy,
+    counters: Arc<NonFiniteCounters>,
+}
+
+impl NonFiniteGuard {
+    pub fn policy(&self) -> NonFinitePolicy {
+        self.policy
+    }
+
+    pub fn set_policy(&mut self, policy: NonFinitePolicy) {
+        self.policy = policy;
+    }
+
+    pub fn stats(&self) -> NonFiniteStats {
+        NonFiniteStats {
+            facts_rejected: self.counters.facts_rejected.load(Ordering::Relaxed),
+            values_nulled: self.counters.values_nulled.load(Ordering::Relaxed),
+            values_discarded: self.counters.values_discarded.load(Ordering::Relaxed),
+            errors: self.counters.errors.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Apply the policy to facts about to be processed, dropping rejected ones
+    pub fn ingest(&self, facts: &mut Vec<Fact>) -> BingoResult<()> {
+        let Some(offending) = facts.iter().find(|fact| first_non_finite(fact).is_some()) else {
+            return Ok(());
+        };
+
+        match self.policy {
+            NonFinitePolicy::Reject => {
+                let before = facts.len();
+                facts.retain(|fact| first_non_finite(fact).is_none());
+                self.counters
+                    .facts_rejected
+                    .fetch_add((before - facts.len()) as u64, Ordering::Relaxed);
+            }
+            NonFinitePolicy::PropagateAsNull => {
+                let nulled: u64 = facts
+                    .iter_mut()
+                    .flat_map(|fact| fact.data.fields.values_mut())
+                    .map(null_non_finite)
+                    .sum();
+                self.counters.values_nulled.fetch_add(nulled, Ordering::Relaxed);
+            }
+            NonFinitePolicy::Error => {
+                self.counters.errors.fetch_add(1, Ordering::Relaxed);
+                let (field, value) = first_non_finite(offending).unwrap_or_default();
+                return Err(BingoError::fact_store_with_id(
+                    offending.id,
+                    "ingest",
+                    format!("Field '{field}' holds non-finite float {value}"),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply the policy to a derived value; `None` means it was discarded
+    ///
+    /// `error` builds the error for [`NonFinitePolicy::Error`] from a description of
+    /// the value.
+    pub fn derive(
+        &self,
+        mut value: FactValue,
+        error: impl FnOnce(String) -> BingoError,
+    ) -> BingoResult<Option<FactValue>> {
+        let Some(non_finite) = non_finite_in(&value) else {
+            return Ok(Some(value));
+        };
+
+        match self.policy {
+            NonFinitePolicy::Reject => {
+                self.counters.values_discarded.fetch_add(1, Ordering::Relaxed);
+                Ok(None)
+            }
+            NonFinitePolicy::PropagateAsNull => {
+                let nulled = null_non_finite(&mut value);
+                self.counters.values_nulled.fetch_add(nulled, Ordering::Relaxed);
+                Ok(Some(value))
+            }
+            NonFinitePolicy::Error => {
+                self.counters.errors.fetch_add(1, Ordering::Relaxed);
+                Err(error(format!("non-finite float {non_finite}")))
+            }
+        }
+    }
+}
+
+/// First field of `fact` holding a non-finite float, with the float
+fn first_non_finite(fact: &Fact) -> Option<(String, f64)> {
+    fact.data
+        .fields
+        .iter()
+        .find_map(|(field, value)| non_finite_in(value).map(|float| (field.clone(), float)))
+}
+
+/// First non-finite float in `value` or its elements
+fn non_finite_in(value: &FactValue) -> Option<f64> {
+    match value {
+        FactValue::Float(float) if !float.is_finite() => Some(*float),
+        FactValue::Array(items) => items.iter().find_map(non_finite_in),
+        FactValue::Object(fields) => fields.values().find_map(non_finite_in),
+        _ => None,
+    }
+}
+
+/// Replace the non-finite floats in `value` with null, returning how many there were
+fn null_non_finite(value: &mut FactValue) -> u64 {
+    match value {
+        FactValue::Float(float) if !float.is_finite() => {
+            *value = FactValue::Null;
+            1
+        }
+        FactValue::Array(items) => items.iter_mut().map(null_non_finite).sum(),
+        FactValue::Object(fields) => fields.values_mut().map(null_non_finite).sum(),
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::FactData;
+    use std::collections::HashMap;
+
+    fn fact(id: u64, value: FactValue) -> Fact {
+        Fact::new(
+            id,
+            FactData { fields: HashMap::from([("rate".to_string(), value)]) },
+        )
+    }
+
+    fn guard(policy: NonFinitePolicy) -> NonFiniteGuard {
+        let mut guard = NonFiniteGuard::default();
+        guard.set_policy(policy);
+        guard
+    }
+
+    #[test]
+    fn test_reject_drops_offending_facts() {
+        let guard = guard(NonFinitePolicy::Reject);
+        let mut facts = vec![
+            fact(1, FactValue::Float(1.5)),
+            fact(2, FactValue::Array(vec![FactValue::Float(f64::NAN)])),
+        ];
+
+        guard.ingest(&mut facts).unwrap();
+        assert_eq!(facts.len(), 1);
+        assert_eq!(facts[0].id, 1);
+        assert_eq!(guard.stats().facts_rejected, 1);
+        assert_eq!(
+            guard.derive(FactValue::Float(f64::INFINITY), BingoError::internal).unwrap(),
+            None
+        );
+        assert_eq!(guard.stats().values_discarded, 1);
+    }
+
+    #[test]
+    fn test_propagate_as_null_replaces_nested_values() {
+        let guard = guard(NonFinitePolicy::PropagateAsNull);
+        let nested = FactValue::Object(HashMap::from([
+            ("low".to_string(), FactValue::Float(f64::NEG_INFINITY)),
+            ("high".to_string(), FactValue::Float(2.0)),
+        ]));
+        let mut facts = vec![fact(1, FactValue::Float(f64::NAN)), fact(2, nested)];
+
+        guard.ingest(&mut facts).unwrap();
+        assert_eq!(facts[0].data.fields["rate"], FactValue::Null);
+        let FactValue::Object(fields) = &facts[1].data.fields["rate"] else {
+            panic!("object expected");
+        };
+        assert_eq!(fields["low"], FactValue::Null);
+        assert_eq!(fields["high"], FactValue::Float(2.0));
+        assert_eq!(guard.stats().values_nulled, 2);
+    }
+
+    #[test]
+    fn test_error_leaves_facts_untouched() {
+        let guard = guard(NonFinitePolicy::Error);
+        let mut facts = vec![fact(1, FactValue::Float(1.0)), fact(2, FactValue::Float(f64::NAN))];
+
+        let error = guard.ingest(&mut facts).unwrap_err();
+        assert!(
+            error.to_string().contains("non-finite float NaN"),
+            "{error}"
+        );
+        assert_eq!(facts.len(), 2);
+        assert!(guard.derive(FactValue::Float(f64::NAN), BingoError::internal).is_err());
+        assert_eq!(
+            guard.derive(FactValue::Float(3.0), BingoError::internal).unwrap(),
+            Some(FactValue::Float(3.0))
+        );
+        assert_eq!(guard.stats().errors, 2);
+    }
+}
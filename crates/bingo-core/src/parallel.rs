@@ -35,13 +35,15 @@
 //! - **Synchronized Result Collection**: Results are safely merged using concurrent collections
 //! - **Memory Pool Partitioning**: Each thread gets its own memory pool slice
 
+use crate::error::{BingoError, BingoResult};
 use crate::fact_store::arena_store::ArenaFactStore;
 use crate::rete_network::ReteNetwork;
 use crate::rete_nodes::RuleExecutionResult;
 use crate::types::{Fact, Rule};
 use anyhow::Result;
 use bingo_calculator::calculator::Calculator;
-// use rayon::prelude::*;  // Not used in current implementation
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex, RwLock};
 use tracing::{info, instrument};
@@ -89,6 +91,76 @@ impl Default for ParallelConfig {
     }
 }
 
+/// Evaluation of alpha tests for the facts of large batches across a thread pool
+///
+/// Each fact's candidate rules and the conditions of its single-condition rules are
+/// tested on the pool, then rules fire fact by fact in batch order, so results are the
+/// same as evaluating the batch on one thread.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParallelAlphaConfig {
+    /// Threads of the pool evaluating alpha tests
+    pub threads: usize,
+    /// Facts a batch needs for its alpha tests to be evaluated in parallel; larger
+    /// batches are evaluated this many facts at a time
+    pub min_batch_size: usize,
+}
+
+impl Default for ParallelAlphaConfig {
+    fn default() -> Self {
+        Self { threads: num_cpus::get(), min_batch_size: 1_000 }
+    }
+}
+
+impl ParallelAlphaConfig {
+    /// Reject zero threads and a zero batch size
+    pub fn validate(&self) -> BingoResult<()> {
+        for (setting, value) in [("threads", self.threads), ("min_batch_size", self.min_batch_size)]
+        {
+            if value == 0 {
+                return Err(BingoError::configuration(
+                    setting,
+                    "at least 1",
+                    "0",
+                    format!("Parallel alpha evaluation {setting} must be at least 1"),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Thread pool evaluating alpha tests, shared by copies of a network
+#[derive(Debug, Clone)]
+pub(crate) struct AlphaPool {
+    config: ParallelAlphaConfig,
+    pool: Arc<rayon::ThreadPool>,
+}
+
+impl AlphaPool {
+    pub fn new(config: ParallelAlphaConfig) -> BingoResult<Self> {
+        config.validate()?;
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(config.threads)
+            .thread_name(|index| format!("bingo-alpha-{index}"))
+            .build()
+            .map_err(|e| BingoError::internal(format!("Failed to start alpha thread pool: {e}")))?;
+        Ok(Self { config, pool: Arc::new(pool) })
+    }
+
+    pub fn config(&self) -> ParallelAlphaConfig {
+        self.config
+    }
+
+    /// Evaluate `evaluate` for every fact on the pool, in fact order
+    pub fn evaluate<T: Send>(
+        &self,
+        facts: &[Fact],
+        evaluate: impl Fn(&Fact) -> T + Sync + Send,
+    ) -> Vec<T> {
+        self.pool.install(|| facts.par_iter().map(evaluate).collect())
+    }
+}
+
 /// Parallel processing extensions for the RETE network
 pub trait ParallelReteNetwork {
     /// Process facts in parallel across multiple threads
@@ -35,7 +35,7 @@
 //! 5. **Concurrent Memory Pools**: Thread-safe object pooling for performance
 
 use crate::alpha_memory::{AlphaMemoryManager, FactPattern};
-use crate::beta_network::{BetaNetworkManager, Token};
+use crate::beta_network::{BetaNetworkManager, FactMemory, Token};
 use crate::error::{BingoError, BingoResult};
 use crate::fact_store::arena_store::{ArenaFactStore, ThreadSafeArenaFactStore};
 // Removed memory pools for thread safety - simplifying for Phase 5
@@ -323,8 +323,7 @@ impl ParallelWorker {
             );
             // Generate RuleExecutionResult for each matching rule
             for rule_id in rule_matches {
-                let execution_result =
-                    RuleExecutionResult { rule_id, fact_id, actions_executed: vec![] };
+                let execution_result = RuleExecutionResult::for_fact(rule_id, fact, vec![]);
 
                 let mut results = self.results.lock().map_err(|e| {
                     BingoError::rete_network(
@@ -384,7 +383,7 @@ impl ParallelWorker {
         for token in tokens {
             // Note: We would need fact_store access here for full integration
             // For now, process the token through the beta network with empty facts
-            let empty_facts = std::collections::HashMap::new();
+            let empty_facts = FactMemory::new();
             let _beta_results = beta_network.process_token(token.clone(), &empty_facts);
             local_stats.tokens_processed += 1;
         }
@@ -438,6 +437,7 @@ impl ParallelWorker {
             actions_executed: vec![crate::rete_nodes::ActionResult::Logged {
                 message: format!("Rule {rule_id} fired for fact {fact_id}"),
             }],
+            ..Default::default()
         };
 
         // Add result to shared results collection
@@ -567,10 +567,10 @@ impl ParallelWorker {
                 })?;
 
                 // Create a fact map for the beta network processing
-                let mut facts = std::collections::HashMap::new();
+                let mut facts = FactMemory::new();
                 for &fact_id in &token.facts {
                     if let Some(fact) = fact_store_read.get_fact(fact_id) {
-                        facts.insert(fact_id, fact.clone());
+                        facts.insert(fact_id, Arc::new(fact));
                     }
                 }
                 facts
@@ -629,6 +629,7 @@ impl ParallelWorker {
             actions_executed: vec![crate::rete_nodes::ActionResult::Logged {
                 message: format!("Rule {rule_id} fired for fact {fact_id} (threaded)"),
             }],
+            ..Default::default()
         };
 
         // Add result to shared results collection
@@ -918,11 +919,8 @@ impl ParallelReteProcessor {
 
             // For each matching rule, execute actions
             for rule_id in rule_matches {
-                let execution_result = RuleExecutionResult {
-                    rule_id,
-                    fact_id,
-                    actions_executed: vec![], // Actions would be executed here
-                };
+                // Actions would be executed here
+                let execution_result = RuleExecutionResult::for_fact(rule_id, &fact, vec![]);
                 results.push(execution_result);
             }
         }
@@ -988,11 +986,8 @@ impl ParallelReteProcessor {
 
             // For each matching rule, execute actions
             for rule_id in rule_matches {
-                let execution_result = RuleExecutionResult {
-                    rule_id,
-                    fact_id,
-                    actions_executed: vec![], // Actions would be executed here
-                };
+                // Actions would be executed here
+                let execution_result = RuleExecutionResult::for_fact(rule_id, &fact, vec![]);
                 results.push(execution_result);
             }
         }
@@ -1186,6 +1181,7 @@ mod tests {
             id,
             external_id: Some(format!("fact_{id}")),
             timestamp: chrono::Utc::now(),
+            fact_type: None,
             data: FactData { fields },
         }
     }
@@ -1202,6 +1198,7 @@ mod tests {
             actions: vec![Action {
                 action_type: ActionType::Log { message: "Rule fired".to_string() },
             }],
+            metadata: Default::default(),
         }
     }
 
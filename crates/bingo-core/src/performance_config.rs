@@ -33,6 +33,20 @@ pub struct PerformanceProfile {
     pub memory_multiplier: f64,
 }
 
+/// Debug builds run without optimizations and take roughly this much longer
+const DEBUG_BUILD_TIME_MULTIPLIER: f64 = 10.0;
+
+/// Pass thresholds of a performance scenario on the baseline machine in release mode
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScenarioThresholds {
+    /// Time to add the scenario's rules to an empty engine
+    pub max_rule_load_time: Duration,
+    /// Time to process the scenario's facts
+    pub max_processing_time: Duration,
+    /// Engine-reported memory once the facts are processed
+    pub max_memory_bytes: u64,
+}
+
 /// Configuration for performance test thresholds
 #[derive(Debug, Clone)]
 pub struct PerformanceConfig {
@@ -110,6 +124,23 @@ impl PerformanceConfig {
         (base_bytes as f64 * self.memory_multiplier) as u64
     }
 
+    /// Adjust a scenario's baseline thresholds to this environment, allowing for the
+    /// slowdown of debug builds
+    pub fn adjust_thresholds(&self, base: ScenarioThresholds) -> ScenarioThresholds {
+        let build_multiplier = if cfg!(debug_assertions) {
+            DEBUG_BUILD_TIME_MULTIPLIER
+        } else {
+            1.0
+        };
+        ScenarioThresholds {
+            max_rule_load_time: self
+                .adjust_time_threshold(base.max_rule_load_time.mul_f64(build_multiplier)),
+            max_processing_time: self
+                .adjust_time_threshold(base.max_processing_time.mul_f64(build_multiplier)),
+            max_memory_bytes: self.adjust_memory_threshold(base.max_memory_bytes),
+        }
+    }
+
     /// Get a descriptive string for the current configuration
     pub fn description(&self) -> String {
         format!(
@@ -324,6 +355,28 @@ mod tests {
         assert_eq!(adjusted_memory, 1_500_000);
     }
 
+    #[test]
+    fn test_scenario_threshold_adjustment() {
+        let config = PerformanceConfig::custom(2.0, 1.5);
+        let base = ScenarioThresholds {
+            max_rule_load_time: Duration::from_secs(1),
+            max_processing_time: Duration::from_secs(3),
+            max_memory_bytes: 1_000_000,
+        };
+        let build = if cfg!(debug_assertions) {
+            DEBUG_BUILD_TIME_MULTIPLIER
+        } else {
+            1.0
+        };
+
+        let adjusted = config.adjust_thresholds(base);
+        assert_eq!(
+            adjusted.max_processing_time,
+            Duration::from_secs(6).mul_f64(build)
+        );
+        assert_eq!(adjusted.max_memory_bytes, 1_500_000);
+    }
+
     #[test]
     fn test_ci_detection() {
         // This would need to be tested with actual CI environment variables
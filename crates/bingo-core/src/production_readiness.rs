@@ -3,10 +3,23 @@
 //! This module provides comprehensive production readiness checks for the Bingo RETE Rules Engine,
 //! ensuring all components are properly configured for production deployment.
 
+use crate::engine::BingoEngine;
 use crate::error::BingoResult;
+use crate::types::{Action, ActionType, Condition, Fact, FactData, FactValue, Operator, Rule};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
 use tracing::info;
 
+/// Share of `max_memory_mb` in use above which memory headroom is a warning
+const MEMORY_WARNING_RATIO: f64 = 0.85;
+
+/// Share of `max_memory_mb` in use above which memory headroom is a failure
+const MEMORY_FAILURE_RATIO: f64 = 0.95;
+
+/// Time the worker thread pool is given to pick up a probe job
+const THREAD_POOL_PROBE_TIMEOUT: Duration = Duration::from_secs(1);
+
 /// Production readiness configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProductionConfig {
@@ -181,6 +194,8 @@ pub struct ReadinessReport {
     pub monitoring_checks: Vec<CheckResult>,
     /// Resource checks
     pub resource_checks: Vec<CheckResult>,
+    /// Self-checks of the engine running in this process
+    pub engine_checks: Vec<CheckResult>,
     /// Summary statistics
     pub summary: ReadinessSummary,
 }
@@ -270,6 +285,7 @@ impl ProductionReadinessValidator {
         let mut security_checks = self.validate_security_config()?;
         let mut monitoring_checks = self.validate_monitoring_config()?;
         let mut resource_checks = self.validate_resource_config()?;
+        let engine_checks = self.validate_engine()?;
 
         // Collect all checks
         let mut all_checks = Vec::new();
@@ -278,6 +294,7 @@ impl ProductionReadinessValidator {
         all_checks.append(&mut security_checks);
         all_checks.append(&mut monitoring_checks);
         all_checks.append(&mut resource_checks);
+        all_checks.extend(engine_checks.iter().cloned());
 
         // Calculate summary
         let summary = self.calculate_summary(&all_checks);
@@ -290,6 +307,7 @@ impl ProductionReadinessValidator {
             security_checks: self.validate_security_config()?,
             monitoring_checks: self.validate_monitoring_config()?,
             resource_checks: self.validate_resource_config()?,
+            engine_checks,
             summary,
         };
 
@@ -661,6 +679,158 @@ impl ProductionReadinessValidator {
         Ok(checks)
     }
 
+    /// Self-check the engine running in this process
+    ///
+    /// Checks memory in use against `resources.max_memory_mb`, that a probe rule compiles
+    /// and fires on a fresh engine, and that the worker thread pool picks up jobs. Unlike
+    /// the configuration checks these reflect the process as it runs, so they suit
+    /// readiness probes.
+    pub fn validate_engine(&self) -> BingoResult<Vec<CheckResult>> {
+        Ok(vec![
+            self.check_memory_headroom(),
+            Self::check_rule_compilation(),
+            Self::check_thread_pool(),
+        ])
+    }
+
+    /// Memory in use against the configured limit
+    fn check_memory_headroom(&self) -> CheckResult {
+        let limit_bytes = self.config.resources.max_memory_mb as f64 * 1024.0 * 1024.0;
+        let used_bytes = match crate::memory::get_memory_usage() {
+            Ok(bytes) => bytes as f64,
+            Err(e) => {
+                return CheckResult {
+                    name: "Memory Headroom".to_string(),
+                    status: CheckStatus::Unknown,
+                    message: Some(format!("Memory usage unavailable: {e}")),
+                    severity: CheckSeverity::Critical,
+                    recommendations: vec![],
+                };
+            }
+        };
+        let ratio = if limit_bytes > 0.0 {
+            used_bytes / limit_bytes
+        } else {
+            1.0
+        };
+        let message = Some(format!(
+            "{:.0}MB of {}MB in use ({:.0}%)",
+            used_bytes / (1024.0 * 1024.0),
+            self.config.resources.max_memory_mb,
+            ratio * 100.0
+        ));
+
+        if ratio >= MEMORY_FAILURE_RATIO {
+            CheckResult {
+                name: "Memory Headroom".to_string(),
+                status: CheckStatus::Fail,
+                message,
+                severity: CheckSeverity::Critical,
+                recommendations: vec![
+                    "Shed sessions or facts, or raise the memory limit".to_string(),
+                ],
+            }
+        } else if ratio >= MEMORY_WARNING_RATIO {
+            CheckResult {
+                name: "Memory Headroom".to_string(),
+                status: CheckStatus::Warning,
+                message,
+                severity: CheckSeverity::High,
+                recommendations: vec!["Memory is close to its limit".to_string()],
+            }
+        } else {
+            CheckResult {
+                name: "Memory Headroom".to_string(),
+                status: CheckStatus::Pass,
+                message,
+                severity: CheckSeverity::Critical,
+                recommendations: vec![],
+            }
+        }
+    }
+
+    /// A probe rule compiles and fires on a fresh engine
+    fn check_rule_compilation() -> CheckResult {
+        let probe = Rule {
+            id: 1,
+            name: "Readiness probe".to_string(),
+            conditions: vec![Condition::Simple {
+                field: "probe".to_string(),
+                operator: Operator::Equal,
+                value: FactValue::Boolean(true),
+            }],
+            actions: vec![Action {
+                action_type: ActionType::Log { message: "Readiness probe fired".to_string() },
+            }],
+            metadata: Default::default(),
+        };
+        let fact = Fact::new(
+            1,
+            FactData { fields: HashMap::from([("probe".to_string(), FactValue::Boolean(true))]) },
+        );
+        let fired = BingoEngine::new().and_then(|engine| {
+            engine.add_rule(probe)?;
+            engine.process_facts(vec![fact])
+        });
+
+        let failure = match fired {
+            Ok(results) if results.len() == 1 => None,
+            Ok(results) => Some(format!(
+                "Probe rule fired {} times instead of once",
+                results.len()
+            )),
+            Err(e) => Some(format!("Probe rule failed: {e}")),
+        };
+        match failure {
+            None => CheckResult {
+                name: "Rule Compilation".to_string(),
+                status: CheckStatus::Pass,
+                message: Some("Probe rule compiled and fired".to_string()),
+                severity: CheckSeverity::Critical,
+                recommendations: vec![],
+            },
+            Some(message) => CheckResult {
+                name: "Rule Compilation".to_string(),
+                status: CheckStatus::Fail,
+                message: Some(message),
+                severity: CheckSeverity::Critical,
+                recommendations: vec!["Restart the instance".to_string()],
+            },
+        }
+    }
+
+    /// The worker thread pool picks up a probe job
+    fn check_thread_pool() -> CheckResult {
+        let threads = rayon::current_num_threads();
+        let (done_tx, done_rx) = std::sync::mpsc::channel();
+        rayon::spawn(move || {
+            let _ = done_tx.send(());
+        });
+
+        if done_rx.recv_timeout(THREAD_POOL_PROBE_TIMEOUT).is_ok() {
+            CheckResult {
+                name: "Thread Pool".to_string(),
+                status: CheckStatus::Pass,
+                message: Some(format!("{threads} worker threads responding")),
+                severity: CheckSeverity::High,
+                recommendations: vec![],
+            }
+        } else {
+            CheckResult {
+                name: "Thread Pool".to_string(),
+                status: CheckStatus::Fail,
+                message: Some(format!(
+                    "None of {threads} worker threads picked up a job within {}ms",
+                    THREAD_POOL_PROBE_TIMEOUT.as_millis()
+                )),
+                severity: CheckSeverity::High,
+                recommendations: vec![
+                    "Worker threads are saturated or stuck; reduce load or restart".to_string(),
+                ],
+            }
+        }
+    }
+
     /// Calculate summary statistics
     fn calculate_summary(&self, checks: &[CheckResult]) -> ReadinessSummary {
         let total_checks = checks.len();
@@ -756,6 +926,7 @@ impl ProductionReadinessValidator {
             "Resource Configuration",
             &report.resource_checks,
         );
+        self.add_section_to_report(&mut markdown, "Engine Self-Checks", &report.engine_checks);
 
         markdown
     }
@@ -913,5 +1084,25 @@ mod tests {
         assert!(markdown.contains("# Production Readiness Report"));
         assert!(markdown.contains("## Summary"));
         assert!(markdown.contains("Service Configuration"));
+        assert!(markdown.contains("Engine Self-Checks"));
+    }
+
+    #[test]
+    fn test_engine_self_checks() {
+        let validator = ProductionReadinessValidator::with_default_config();
+        let checks = validator.validate_engine().unwrap();
+
+        let names: Vec<&str> = checks.iter().map(|check| check.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["Memory Headroom", "Rule Compilation", "Thread Pool"]
+        );
+        assert!(checks[1..].iter().all(|check| check.status == CheckStatus::Pass));
+
+        // A limit below what the process already uses leaves no headroom
+        let mut config = ProductionConfig::default();
+        config.resources.max_memory_mb = 0;
+        let checks = ProductionReadinessValidator::new(config).validate_engine().unwrap();
+        assert_eq!(checks[0].status, CheckStatus::Fail);
     }
 }
@@ -0,0 +1,231 @@
+//! Ordered secondary indexes over numeric, date and timestamp values
+//!
+//! The fact store's hash indexes answer equality lookups only, so "amounts from 100 to
+//! 500" or "facts of the last hour" used to scan every stored fact. A [`RangeIndex`]
+//! keeps fact IDs in a B-tree ordered by value, answering a range query in
+//! O(log n + k) and returning matches in value order, ties in fact ID order.
+//!
+//! [`FieldRangeIndex`] indexes one field: integers, floats and decimals by their
+//! `f64` value, dates by instant. NaN and other values are not indexed, so range
+//! queries never return them.
+
+use crate::memory::hash_map_table_bytes;
+use crate::types::{FactId, FactValue};
+use chrono::{DateTime, Utc};
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap};
+use std::ops::{Bound, RangeBounds};
+
+/// Totally ordered `f64` key, never NaN, with `-0.0` stored as `0.0`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NumericKey(f64);
+
+impl NumericKey {
+    /// Key for `value`, `None` for NaN
+    pub fn new(value: f64) -> Option<Self> {
+        // Adding 0.0 turns -0.0 into 0.0 so both order equal
+        (!value.is_nan()).then_some(Self(value + 0.0))
+    }
+
+    pub fn value(self) -> f64 {
+        self.0
+    }
+}
+
+impl Eq for NumericKey {}
+
+impl PartialOrd for NumericKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for NumericKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+/// Fact IDs ordered by a key, answering range queries without a scan
+#[derive(Debug, Clone)]
+pub struct RangeIndex<K> {
+    entries: BTreeSet<(K, FactId)>,
+    keys: HashMap<FactId, K>,
+}
+
+impl<K> Default for RangeIndex<K> {
+    fn default() -> Self {
+        Self { entries: BTreeSet::new(), keys: HashMap::new() }
+    }
+}
+
+impl<K: Ord + Copy> RangeIndex<K> {
+    /// Index `fact_id` under `key`, replacing its previous key
+    pub fn insert(&mut self, fact_id: FactId, key: K) {
+        if let Some(previous) = self.keys.insert(fact_id, key) {
+            self.entries.remove(&(previous, fact_id));
+        }
+        self.entries.insert((key, fact_id));
+    }
+
+    /// Drop `fact_id` from the index, returning the key it was indexed under
+    pub fn remove(&mut self, fact_id: FactId) -> Option<K> {
+        let key = self.keys.remove(&fact_id)?;
+        self.entries.remove(&(key, fact_id));
+        Some(key)
+    }
+
+    /// IDs of facts with keys in `range`, in key order and fact ID order within a key
+    pub fn range(&self, range: impl RangeBounds<K>) -> impl Iterator<Item = FactId> + '_ {
+        let start = match range.start_bound() {
+            Bound::Included(key) => Bound::Included((*key, FactId::MIN)),
+            Bound::Excluded(key) => Bound::Excluded((*key, FactId::MAX)),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(key) => Bound::Included((*key, FactId::MAX)),
+            Bound::Excluded(key) => Bound::Excluded((*key, FactId::MIN)),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+        // BTreeSet::range panics on inverted ranges, which simply match nothing here
+        let empty = match (&start, &end) {
+            (Bound::Included(start) | Bound::Excluded(start), Bound::Included(end)) => start > end,
+            (Bound::Included(start), Bound::Excluded(end)) => start >= end,
+            (Bound::Excluded(start), Bound::Excluded(end)) => start >= end,
+            _ => false,
+        };
+        let entries = (!empty).then(|| self.entries.range((start, end)));
+        entries.into_iter().flatten().map(|(_, fact_id)| *fact_id)
+    }
+
+    /// Key `fact_id` is indexed under
+    pub fn key(&self, fact_id: FactId) -> Option<K> {
+        self.keys.get(&fact_id).copied()
+    }
+
+    /// Number of indexed facts
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.keys.clear();
+    }
+
+    /// Approximate heap bytes held by the index
+    pub fn heap_bytes(&self) -> usize {
+        let entry = std::mem::size_of::<(K, FactId)>();
+        self.entries.len() * entry * 2 + hash_map_table_bytes(&self.keys)
+    }
+}
+
+/// Range index over one field's numeric and date values
+#[derive(Debug, Clone, Default)]
+pub struct FieldRangeIndex {
+    pub numbers: RangeIndex<NumericKey>,
+    pub dates: RangeIndex<DateTime<Utc>>,
+}
+
+impl FieldRangeIndex {
+    /// Index `fact_id` under `value`, or drop it when `value` is not orderable
+    pub fn insert(&mut self, fact_id: FactId, value: Option<&FactValue>) {
+        self.remove(fact_id);
+        match value {
+            Some(FactValue::Date(date)) => self.dates.insert(fact_id, *date),
+            Some(value) => {
+                if let Some(key) = numeric_key(value) {
+                    self.numbers.insert(fact_id, key);
+                }
+            }
+            None => {}
+        }
+    }
+
+    pub fn remove(&mut self, fact_id: FactId) {
+        self.numbers.remove(fact_id);
+        self.dates.remove(fact_id);
+    }
+
+    pub fn clear(&mut self) {
+        self.numbers.clear();
+        self.dates.clear();
+    }
+
+    /// Number of indexed facts
+    pub fn len(&self) -> usize {
+        self.numbers.len() + self.dates.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn heap_bytes(&self) -> usize {
+        self.numbers.heap_bytes() + self.dates.heap_bytes()
+    }
+}
+
+/// Key of an integer, float or decimal value, `None` for other values and NaN
+pub fn numeric_key(value: &FactValue) -> Option<NumericKey> {
+    match value {
+        FactValue::Integer(_) | FactValue::Float(_) | FactValue::Decimal(_) => {
+            NumericKey::new(value.to_comparable()?)
+        }
+        _ => None,
+    }
+}
+
+/// `range` over `f64` bounds as a range over keys, `None` when a bound is NaN
+pub fn numeric_bounds(
+    range: &impl RangeBounds<f64>,
+) -> Option<(Bound<NumericKey>, Bound<NumericKey>)> {
+    fn key(bound: Bound<&f64>) -> Option<Bound<NumericKey>> {
+        Some(match bound {
+            Bound::Included(value) => Bound::Included(NumericKey::new(*value)?),
+            Bound::Excluded(value) => Bound::Excluded(NumericKey::new(*value)?),
+            Bound::Unbounded => Bound::Unbounded,
+        })
+    }
+    Some((key(range.start_bound())?, key(range.end_bound())?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ranges_come_back_in_key_order() {
+        let mut index = FieldRangeIndex::default();
+        index.insert(1, Some(&FactValue::Float(250.0)));
+        index.insert(2, Some(&FactValue::Integer(100)));
+        index.insert(3, Some(&FactValue::Float(f64::NAN)));
+        index.insert(4, Some(&FactValue::Integer(500)));
+        index.insert(5, Some(&FactValue::Integer(100)));
+        index.insert(6, Some(&FactValue::String("300".to_string())));
+        assert_eq!(index.len(), 4);
+
+        let (start, end) = numeric_bounds(&(100.0..500.0)).unwrap();
+        let ids: Vec<FactId> = index.numbers.range((start, end)).collect();
+        assert_eq!(ids, vec![2, 5, 1]);
+        let (start, end) = numeric_bounds(&(100.0..=500.0)).unwrap();
+        assert_eq!(index.numbers.range((start, end)).count(), 4);
+        assert_eq!(numeric_bounds(&(f64::NAN..1.0)), None);
+
+        // Reindexing moves a fact, and inverted ranges match nothing
+        index.insert(2, Some(&FactValue::Integer(900)));
+        let (start, end) = numeric_bounds(&(..=500.0)).unwrap();
+        assert_eq!(
+            index.numbers.range((start, end)).collect::<Vec<_>>(),
+            vec![5, 1, 4]
+        );
+        let (start, end) = numeric_bounds(&(600.0..100.0)).unwrap();
+        assert_eq!(index.numbers.range((start, end)).count(), 0);
+        index.remove(2);
+        assert_eq!(index.numbers.key(2), None);
+    }
+}
@@ -0,0 +1,178 @@
+//! Retroactive recalculation of historical facts
+//!
+//! When a rule turns out to have been wrong, or a fact was recorded wrongly, the
+//! results already paid out for a past period need correcting. `BingoEngine::recalculate`
+//! takes the corrections and the period and works out the difference they make:
+//!
+//! 1. The affected facts are the stored facts timestamped within the period that were
+//!    corrected, or that carry a field a changed rule reads in its old or new version.
+//!    Facts derived by rules are left out, as re-running their sources derives them again.
+//! 2. The affected facts are evaluated twice in dry-run networks, as of each fact's
+//!    event time: as originally recorded under the current rules, and corrected under
+//!    the corrected rules. Effective-dated rules and reference tables resolve to the
+//!    versions in effect when each fact happened.
+//! 3. The two runs are compared per fact and rule into [`ResultDelta`]s: firings that
+//!    appear, disappear or execute different actions.
+//!
+//! Recalculation changes nothing in the engine: webhooks are not dispatched, and the
+//! corrections are applied with `update_rule` and friends once the deltas are accepted.
+
+use crate::rete_nodes::{ActionResult, RuleExecutionResult};
+use crate::types::{Fact, FactId, Rule, RuleId};
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+
+/// A change to recalculate history under
+#[derive(Debug, Clone)]
+pub enum Correction {
+    /// A new version of the rule with this ID, or a rule that should have existed
+    Rule(Rule),
+    /// A rule that should not have existed
+    RuleRemoved(RuleId),
+    /// Corrected data for the stored fact with this ID
+    Fact(Fact),
+}
+
+/// Corrections and the period of history they apply to
+#[derive(Debug, Clone)]
+pub struct RecalculationRequest {
+    pub corrections: Vec<Correction>,
+    /// Start of the period by fact timestamp, inclusive
+    pub from: DateTime<Utc>,
+    /// End of the period by fact timestamp, exclusive
+    pub until: DateTime<Utc>,
+}
+
+impl RecalculationRequest {
+    /// Recalculate the facts timestamped from `from` until `until` under `corrections`
+    pub fn new(corrections: Vec<Correction>, from: DateTime<Utc>, until: DateTime<Utc>) -> Self {
+        Self { corrections, from, until }
+    }
+
+    /// Whether `at` falls within the period
+    pub fn covers(&self, at: DateTime<Utc>) -> bool {
+        at >= self.from && at < self.until
+    }
+}
+
+/// How a rule's firing for a fact changes under the corrections
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeltaKind {
+    /// The rule fires for the fact only after the corrections
+    Added,
+    /// The rule no longer fires for the fact
+    Removed,
+    /// The rule fires either way but executes different actions
+    Changed,
+}
+
+/// Difference the corrections make to one rule's firing for one fact
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResultDelta {
+    pub fact_id: FactId,
+    pub rule_id: RuleId,
+    /// Actions the rule executed for the fact as recorded, empty if it did not fire
+    pub before: Vec<ActionResult>,
+    /// Actions the rule executes for the fact once corrected, empty if it no longer fires
+    pub after: Vec<ActionResult>,
+}
+
+impl ResultDelta {
+    pub fn kind(&self) -> DeltaKind {
+        match (self.before.is_empty(), self.after.is_empty()) {
+            (true, _) => DeltaKind::Added,
+            (_, true) => DeltaKind::Removed,
+            _ => DeltaKind::Changed,
+        }
+    }
+}
+
+/// Outcome of a retroactive recalculation
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecalculationReport {
+    /// Facts that were re-run, in ID order
+    pub affected_facts: Vec<FactId>,
+    /// Firings the corrections change, ordered by fact and rule
+    pub deltas: Vec<ResultDelta>,
+}
+
+impl RecalculationReport {
+    /// Whether the corrections change no result in the period
+    pub fn is_unchanged(&self) -> bool {
+        self.deltas.is_empty()
+    }
+}
+
+/// Actions of every firing, grouped by matched fact and rule
+fn actions_by_firing(
+    results: Vec<RuleExecutionResult>,
+) -> BTreeMap<(FactId, RuleId), Vec<ActionResult>> {
+    let mut firings: BTreeMap<(FactId, RuleId), Vec<ActionResult>> = BTreeMap::new();
+    for result in results {
+        firings
+            .entry((result.fact_id, result.rule_id))
+            .or_default()
+            .extend(result.actions_executed);
+    }
+    firings
+}
+
+/// Compare the results of the recorded and the corrected run
+pub(crate) fn deltas(
+    before: Vec<RuleExecutionResult>,
+    after: Vec<RuleExecutionResult>,
+) -> Vec<ResultDelta> {
+    let mut before = actions_by_firing(before);
+    let after = actions_by_firing(after);
+    let mut deltas = Vec::new();
+    for ((fact_id, rule_id), after) in after {
+        let before = before.remove(&(fact_id, rule_id)).unwrap_or_default();
+        if !crate::shadow::same_actions(&before, &after) {
+            deltas.push(ResultDelta { fact_id, rule_id, before, after });
+        }
+    }
+    deltas.extend(
+        before.into_iter().map(|((fact_id, rule_id), before)| ResultDelta {
+            fact_id,
+            rule_id,
+            before,
+            after: Vec::new(),
+        }),
+    );
+    deltas.sort_by_key(|delta| (delta.fact_id, delta.rule_id));
+    deltas
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::FactValue;
+
+    fn fired(rule_id: RuleId, fact_id: FactId, value: i64) -> RuleExecutionResult {
+        RuleExecutionResult {
+            rule_id,
+            fact_id,
+            actions_executed: vec![ActionResult::FieldSet {
+                fact_id,
+                field: "pay".to_string(),
+                value: FactValue::Integer(value),
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_deltas_classify_firings() {
+        let before = vec![fired(1, 1, 100), fired(1, 2, 100), fired(2, 3, 5)];
+        let after = vec![fired(1, 1, 100), fired(1, 2, 120), fired(3, 3, 7)];
+        let deltas = deltas(before, after);
+        let kinds: Vec<_> = deltas
+            .iter()
+            .map(|delta| (delta.fact_id, delta.rule_id, delta.kind()))
+            .collect();
+        assert_eq!(
+            kinds,
+            [(2, 1, DeltaKind::Changed), (3, 2, DeltaKind::Removed), (3, 3, DeltaKind::Added)]
+        );
+    }
+}
@@ -0,0 +1,435 @@
+//! Named reference data tables
+//!
+//! Rate tables, holiday calendars and sanction lists are too large to inline in rules
+//! and change on their own schedule. They are registered once under a name in the
+//! [`ReferenceDataStore`] and referenced from rules by that name:
+//!
+//! - `In` / `NotIn` conditions whose value is a `FactValue::String` test the field
+//!   against the keys of the table with that name
+//! - calculator input mappings written `table[field]` pass the table row keyed by the
+//!   fact's `field` value instead of a fact field
+//!
+//! Rules hold the name, not the data, so registering a table again swaps it in
+//! atomically for every rule using it without recompiling anything. Readers take a
+//! snapshot `Arc` of the table, so a reload never exposes a half-built table.
+//!
+//! A table can also be registered as one dated version of several, each valid between
+//! its effective and expiry dates, like a tax rate table that changes every January.
+//! Lookups pick the version in effect at the batch's evaluation date, so recalculating
+//! a past period uses the rates of that period.
+//!
+//! The store also holds named global values, such as a VAT rate shared by every fact
+//! of a session. Calculator input mappings written `@name` pass the global's current
+//! value instead of a fact field.
+
+use crate::types::FactValue;
+use anyhow::{Result, bail};
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, PoisonError, RwLock};
+
+/// Prefix reserved for names the engine generates
+pub const RESERVED_NAME_PREFIX: char = '$';
+
+/// Prefix of calculator input mappings that read a global value
+pub const GLOBAL_REFERENCE_PREFIX: char = '@';
+
+/// Immutable lookup table from keys to values
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReferenceTable {
+    entries: HashMap<FactValue, FactValue>,
+    effective_date: Option<DateTime<Utc>>,
+    expiry_date: Option<DateTime<Utc>>,
+}
+
+impl ReferenceTable {
+    /// Table mapping each key to its value, e.g. region to tax rate
+    pub fn from_entries(entries: impl IntoIterator<Item = (FactValue, FactValue)>) -> Self {
+        Self { entries: entries.into_iter().collect(), ..Self::default() }
+    }
+
+    /// Table of keys only, e.g. sanctioned country codes or holiday dates
+    ///
+    /// Every key maps to `Boolean(true)`.
+    pub fn from_keys(keys: impl IntoIterator<Item = FactValue>) -> Self {
+        Self::from_entries(keys.into_iter().map(|key| (key, FactValue::Boolean(true))))
+    }
+
+    /// Value stored under `key`
+    pub fn get(&self, key: &FactValue) -> Option<&FactValue> {
+        self.entries.get(key)
+    }
+
+    /// Whether `key` is in the table
+    pub fn contains_key(&self, key: &FactValue) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    /// Number of rows
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the table has no rows
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Keys in arbitrary order
+    pub fn keys(&self) -> impl Iterator<Item = &FactValue> {
+        self.entries.keys()
+    }
+
+    /// Rows in arbitrary order
+    pub fn iter(&self) -> impl Iterator<Item = (&FactValue, &FactValue)> {
+        self.entries.iter()
+    }
+
+    /// This table valid from `effective_date` until `expiry_date`, either open-ended
+    /// when `None`
+    pub fn valid_between(
+        mut self,
+        effective_date: Option<DateTime<Utc>>,
+        expiry_date: Option<DateTime<Utc>>,
+    ) -> Self {
+        self.effective_date = effective_date;
+        self.expiry_date = expiry_date;
+        self
+    }
+
+    /// When the table takes effect, always when `None`
+    pub fn effective_date(&self) -> Option<DateTime<Utc>> {
+        self.effective_date
+    }
+
+    /// When the table stops being in effect, never when `None`
+    pub fn expiry_date(&self) -> Option<DateTime<Utc>> {
+        self.expiry_date
+    }
+
+    /// Whether the table is in effect at `at`: on or after its effective date and
+    /// before its expiry date
+    pub fn is_in_effect_at(&self, at: DateTime<Utc>) -> bool {
+        self.effective_date.is_none_or(|effective| at >= effective)
+            && self.expiry_date.is_none_or(|expiry| at < expiry)
+    }
+
+    fn is_dated(&self) -> bool {
+        self.effective_date.is_some() || self.expiry_date.is_some()
+    }
+}
+
+/// Shared registry of reference tables and global values
+///
+/// Cloning the store clones a handle: every clone sees tables registered through any
+/// other, which is how an engine, its rebuilt networks and its sessions share one copy.
+#[derive(Debug, Clone, Default)]
+pub struct ReferenceDataStore {
+    tables: Arc<RwLock<HashMap<String, Vec<Arc<ReferenceTable>>>>>,
+    globals: Arc<RwLock<HashMap<String, FactValue>>>,
+}
+
+impl ReferenceDataStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `table` under `name`, atomically replacing any table with that name
+    /// and all of its versions
+    pub fn register(&self, name: impl Into<String>, table: ReferenceTable) -> Result<()> {
+        let name = validate_table(name.into(), &table)?;
+        self.tables
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(name, vec![Arc::new(table)]);
+        Ok(())
+    }
+
+    /// Register `table` as one version of the table `name`, keeping the versions
+    /// with other effective dates
+    ///
+    /// A version with the same effective date is replaced. Where versions overlap,
+    /// lookups use the one that took effect last.
+    pub fn register_version(&self, name: impl Into<String>, table: ReferenceTable) -> Result<()> {
+        let name = validate_table(name.into(), &table)?;
+        let mut tables = self.tables.write().unwrap_or_else(PoisonError::into_inner);
+        let versions = tables.entry(name).or_default();
+        versions.retain(|version| version.effective_date != table.effective_date);
+        versions.push(Arc::new(table));
+        // Undated versions sort first, as if in effect since the beginning of time
+        versions.sort_by_key(|version| version.effective_date);
+        Ok(())
+    }
+
+    /// Snapshot of the table registered under `name` that is in effect now
+    pub fn get(&self, name: &str) -> Option<Arc<ReferenceTable>> {
+        self.get_as_of(name, None)
+    }
+
+    /// Snapshot of the version of the table `name` in effect at `as_of`, or now when
+    /// `as_of` is `None`
+    pub fn get_as_of(
+        &self,
+        name: &str,
+        as_of: Option<DateTime<Utc>>,
+    ) -> Option<Arc<ReferenceTable>> {
+        let tables = self.tables.read().unwrap_or_else(PoisonError::into_inner);
+        let versions = tables.get(name)?;
+        match versions.as_slice() {
+            // The common undated table is used without reading the clock
+            [table] if !table.is_dated() => Some(Arc::clone(table)),
+            versions => {
+                let at = as_of.unwrap_or_else(Utc::now);
+                versions.iter().rev().find(|version| version.is_in_effect_at(at)).cloned()
+            }
+        }
+    }
+
+    /// Every version of the table `name`, ordered by effective date
+    pub fn versions(&self, name: &str) -> Vec<Arc<ReferenceTable>> {
+        self.tables
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(name)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Whether a table is registered under `name`
+    pub fn contains(&self, name: &str) -> bool {
+        self.tables.read().unwrap_or_else(PoisonError::into_inner).contains_key(name)
+    }
+
+    /// Value of `key` in the table `name`
+    pub fn lookup(&self, name: &str, key: &FactValue) -> Option<FactValue> {
+        self.lookup_as_of(name, key, None)
+    }
+
+    /// Value of `key` in the version of the table `name` in effect at `as_of`, or now
+    /// when `as_of` is `None`
+    pub fn lookup_as_of(
+        &self,
+        name: &str,
+        key: &FactValue,
+        as_of: Option<DateTime<Utc>>,
+    ) -> Option<FactValue> {
+        self.get_as_of(name, as_of)?.get(key).cloned()
+    }
+
+    /// Names of the registered tables, sorted
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .tables
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .keys()
+            .cloned()
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Set the global `name` to `value`, replacing any previous value
+    pub fn set_global(&self, name: impl Into<String>, value: FactValue) -> Result<()> {
+        self.set_globals([(name.into(), value)])
+    }
+
+    /// Set several globals at once
+    ///
+    /// Readers see either none or all of the new values, and nothing is set if any
+    /// name is invalid.
+    pub fn set_globals(
+        &self,
+        globals: impl IntoIterator<Item = (String, FactValue)>,
+    ) -> Result<()> {
+        let globals: Vec<(String, FactValue)> = globals.into_iter().collect();
+        for (name, _) in &globals {
+            if !is_valid_name(name) || name.starts_with(GLOBAL_REFERENCE_PREFIX) {
+                bail!("Invalid global name '{name}'");
+            }
+        }
+        self.globals.write().unwrap_or_else(PoisonError::into_inner).extend(globals);
+        Ok(())
+    }
+
+    /// Current value of the global `name`
+    pub fn global(&self, name: &str) -> Option<FactValue> {
+        self.globals.read().unwrap_or_else(PoisonError::into_inner).get(name).cloned()
+    }
+
+    /// Copy of every global, ordered by name
+    pub fn globals(&self) -> BTreeMap<String, FactValue> {
+        self.globals
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect()
+    }
+}
+
+/// Check that `table` can be registered under `name`
+fn validate_table(name: String, table: &ReferenceTable) -> Result<String> {
+    if !is_valid_name(&name) {
+        bail!("Invalid reference table name '{name}'");
+    }
+    if let (Some(effective), Some(expiry)) = (table.effective_date, table.expiry_date)
+        && expiry <= effective
+    {
+        bail!(
+            "Reference table '{name}' expires at {expiry}, before it takes effect at {effective}"
+        );
+    }
+    Ok(name)
+}
+
+/// Whether `name` can be registered by users
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty() && !name.starts_with(RESERVED_NAME_PREFIX)
+}
+
+/// Name of the global a calculator input mapping written `@name` reads
+pub fn parse_global_reference(mapping: &str) -> Option<&str> {
+    let name = mapping.strip_prefix(GLOBAL_REFERENCE_PREFIX)?.trim();
+    (!name.is_empty()).then_some(name)
+}
+
+/// Split a calculator input mapping written `table[field]` into its table and field
+pub fn parse_table_reference(mapping: &str) -> Option<(&str, &str)> {
+    let (table, rest) = mapping.split_once('[')?;
+    let field = rest.strip_suffix(']')?;
+    let (table, field) = (table.trim(), field.trim());
+    (!table.is_empty() && !field.is_empty()).then_some((table, field))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn string(value: &str) -> FactValue {
+        FactValue::String(value.to_string())
+    }
+
+    #[test]
+    fn test_reload_is_visible_through_every_handle() {
+        let store = ReferenceDataStore::new();
+        let shared = store.clone();
+        store
+            .register(
+                "tax_rates",
+                ReferenceTable::from_entries([(string("CA"), FactValue::Float(0.0725))]),
+            )
+            .unwrap();
+        let snapshot = shared.get("tax_rates").unwrap();
+
+        store
+            .register(
+                "tax_rates",
+                ReferenceTable::from_entries([(string("CA"), FactValue::Float(0.08))]),
+            )
+            .unwrap();
+        assert_eq!(
+            shared.lookup("tax_rates", &string("CA")),
+            Some(FactValue::Float(0.08))
+        );
+        // Readers holding the old snapshot keep a consistent table
+        assert_eq!(snapshot.get(&string("CA")), Some(&FactValue::Float(0.0725)));
+        assert_eq!(shared.names(), vec!["tax_rates"]);
+    }
+
+    #[test]
+    fn test_versions_are_selected_by_date() {
+        let store = ReferenceDataStore::new();
+        let date = |year| chrono::TimeZone::with_ymd_and_hms(&Utc, year, 1, 1, 0, 0, 0).unwrap();
+        let rates = |rate| ReferenceTable::from_entries([(string("CA"), FactValue::Float(rate))]);
+        store.register_version("tax_rates", rates(0.07)).unwrap();
+        store
+            .register_version(
+                "tax_rates",
+                rates(0.08).valid_between(Some(date(2024)), None),
+            )
+            .unwrap();
+        store
+            .register_version(
+                "tax_rates",
+                rates(0.09).valid_between(Some(date(2025)), None),
+            )
+            .unwrap();
+
+        let rate = |year| store.lookup_as_of("tax_rates", &string("CA"), Some(date(year)));
+        assert_eq!(rate(2023), Some(FactValue::Float(0.07)));
+        assert_eq!(rate(2024), Some(FactValue::Float(0.08)));
+        assert_eq!(rate(2030), Some(FactValue::Float(0.09)));
+
+        // A version with the same effective date is replaced, and plain registration
+        // drops every version
+        store
+            .register_version(
+                "tax_rates",
+                rates(0.1).valid_between(Some(date(2025)), None),
+            )
+            .unwrap();
+        assert_eq!(rate(2030), Some(FactValue::Float(0.1)));
+        assert_eq!(store.versions("tax_rates").len(), 3);
+        store.register("tax_rates", rates(0.05)).unwrap();
+        assert_eq!(rate(2024), Some(FactValue::Float(0.05)));
+        assert_eq!(store.versions("tax_rates").len(), 1);
+    }
+
+    #[test]
+    fn test_reserved_names_are_rejected() {
+        let store = ReferenceDataStore::new();
+        assert!(store.register("", ReferenceTable::default()).is_err());
+        assert!(store.register("$literal1", ReferenceTable::default()).is_err());
+        assert!(!store.contains("$literal1"));
+    }
+
+    #[test]
+    fn test_globals_are_shared_and_replaced() {
+        let store = ReferenceDataStore::new();
+        let shared = store.clone();
+        store.set_global("vat_rate", FactValue::Float(0.2)).unwrap();
+        store.set_global("vat_rate", FactValue::Float(0.25)).unwrap();
+        store.set_global("currency", string("EUR")).unwrap();
+
+        assert_eq!(shared.global("vat_rate"), Some(FactValue::Float(0.25)));
+        assert_eq!(shared.global("missing"), None);
+        assert_eq!(
+            shared.globals().keys().collect::<Vec<_>>(),
+            vec!["currency", "vat_rate"]
+        );
+        // Globals and tables live in separate namespaces
+        assert!(!shared.contains("vat_rate"));
+
+        assert!(store.set_global("", FactValue::Boolean(true)).is_err());
+        assert!(store.set_global("$rate", FactValue::Boolean(true)).is_err());
+        assert!(store.set_global("@rate", FactValue::Boolean(true)).is_err());
+
+        // A batch with an invalid name sets nothing
+        let batch = [
+            ("rounding".to_string(), FactValue::Integer(2)),
+            (String::new(), FactValue::Null),
+        ];
+        assert!(store.set_globals(batch).is_err());
+        assert_eq!(store.global("rounding"), None);
+    }
+
+    #[test]
+    fn test_parse_global_reference() {
+        assert_eq!(parse_global_reference("@vat_rate"), Some("vat_rate"));
+        assert_eq!(parse_global_reference("vat_rate"), None);
+        assert_eq!(parse_global_reference("@"), None);
+    }
+
+    #[test]
+    fn test_parse_table_reference() {
+        assert_eq!(
+            parse_table_reference("tax_rates[region]"),
+            Some(("tax_rates", "region"))
+        );
+        assert_eq!(parse_table_reference("region"), None);
+        assert_eq!(parse_table_reference("tax_rates[]"), None);
+        assert_eq!(parse_table_reference("[region]"), None);
+    }
+}
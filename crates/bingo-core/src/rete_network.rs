@@ -10,21 +10,56 @@
 /// 4. **Network Management**: Network lifecycle and statistics
 ///
 /// Each section is clearly marked with module-style comments for easy navigation.
-use crate::alpha_memory::{AlphaMemoryManager, FactPattern};
-use crate::beta_network::{BetaNetworkManager, Token};
-use crate::fact_store::arena_store::ArenaFactStore;
+use crate::action_validation::{ActionValidationPolicy, FieldSchema};
+use crate::aggregation_node::{AggregationNode, aggregate_values};
+use crate::alpha_memory::{AlphaMemory, AlphaMemoryManager, FactPattern};
+use crate::beta_network::{self, BetaNetworkManager, BetaNodeType, FactMemory, Token};
+use crate::cache::{CacheStats, CalculatorCache, CalculatorCacheConfig};
+use crate::calendar::PeriodCalendar;
+use crate::collation::Collation;
+use crate::compiled_ruleset::{CompiledBetaNode, CompiledNetwork, CompiledNode, ConditionInterner};
+use crate::condition_stats::ConditionEvaluationStats;
+use crate::debugging::{AlphaMemoryContents, BetaMemoryContents, NetworkMemoryContents};
+use crate::evaluation_budget::EvaluationBudget;
+use crate::explanation::{
+    AuditLog, AuditLogConfig, CalculatorTrace, ConditionTrace, ExplanationTrace, ResultId,
+};
+use crate::fact_store::arena_store::{ArenaFactStore, UndoneChanges};
+use crate::field_collisions::{FieldCollisionPolicy, resolve_field_collisions};
+use crate::field_references::condition_fields;
 use crate::lazy_aggregation::LazyAggregationManager;
+use crate::memory::{MemoryBreakdown, fact_bytes, hash_map_table_bytes};
 use crate::memory_pools::MemoryPoolManager;
-use crate::rete_nodes::RuleExecutionResult;
-use crate::rule_optimizer::RuleOptimizer;
+use crate::memory_report::{BetaMemoryUsage, RuleMemoryUsage};
+use crate::non_finite::{NonFiniteGuard, NonFinitePolicy, NonFiniteStats};
+use crate::parallel::{AlphaPool, ParallelAlphaConfig};
+use crate::reference_data::{ReferenceDataStore, parse_global_reference, parse_table_reference};
+use crate::rete_nodes::{RuleExecutionResult, mutated_value, mutation_result};
+use crate::rule_dependency::{RuleCyclePolicy, dot_escape};
+use crate::rule_duplicates::DuplicateRulePolicy;
+use crate::rule_optimizer::{OptimizationStrategy, RuleOptimizer};
+use crate::rule_stats::{RuleCounters, RuleStats};
+use crate::sequence_node::SequenceNode;
+use crate::stream_processing::LateDataPolicy;
+use crate::string_match::{self, CompiledRegexes};
+use crate::truth_maintenance::{RetractionResult, TruthMaintenanceSystem};
 use crate::types::{
-    AlphaNode, BetaNode, Condition, Fact, FactId, FactValue, NodeId, Operator, Rule, RuleId,
-    TerminalNode,
+    AggregationWindow, AlphaNode, BetaNode, Condition, EvaluationDate, Fact, FactId, FactValue,
+    FieldType, NodeId, Operator, OverflowPolicy, Rule, RuleId, TerminalNode,
+};
+use crate::value_list::ValueLists;
+use crate::webhook::{WebhookDispatcher, WebhookPayload};
+use crate::window_node::WindowNode;
+use crate::working_memory_profiler::{
+    WorkingMemoryProfile, WorkingMemoryProfiler, WorkingMemoryProfilerConfig,
 };
 use anyhow::Result;
 use bingo_calculator::calculator::Calculator;
-use std::collections::HashMap;
-use tracing::{debug, info, instrument};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::{debug, info, instrument, warn};
 
 // Note: Token is now defined in beta_network.rs and imported above
 
@@ -107,6 +142,10 @@ pub struct ReteNetwork {
     /// Rules contain conditions, actions, and metadata.
     rules: HashMap<RuleId, Rule>,
 
+    /// **Condition Plans**: Declared index of each stored condition, for rules whose
+    /// conditions a query plan reordered.
+    condition_orders: HashMap<RuleId, Vec<usize>>,
+
     /// **Node ID Generator**: Monotonically increasing counter for unique node identifiers.
     ///
     /// Ensures each alpha, beta, and terminal node has a unique ID within the network.
@@ -118,6 +157,27 @@ pub struct ReteNetwork {
     /// via `take_created_facts()` for integration with the broader fact store.
     created_facts: Vec<Fact>,
 
+    /// **Forward Chaining**: Whether created facts are asserted back into the network,
+    /// and the guard against chains that never settle.
+    forward_chaining: Option<ForwardChaining>,
+
+    /// **Chain Conclusions**: Data of the facts derived by the chain in progress, so a
+    /// conclusion reached again is not asserted twice.
+    chain_conclusions: HashSet<Vec<(String, FactValue)>>,
+
+    /// **Evaluation Date**: What rule and reference table validity is checked against,
+    /// and the date the fact being evaluated resolves it to (`None` for the clock).
+    evaluation_date: EvaluationDate,
+    as_of: Option<chrono::DateTime<chrono::Utc>>,
+
+    /// **Parallel Alpha**: Thread pool evaluating the alpha tests of large batches,
+    /// shared with copies of the network
+    parallel_alpha: Option<AlphaPool>,
+
+    /// **Deterministic Execution**: Fire matched rules in rule ID order in working
+    /// memory processing too, and date created facts by the fact that triggered them
+    deterministic: bool,
+
     /// **Memory Pool Manager**: Provides object pooling for high-frequency allocations.
     ///
     /// Manages pools for vectors, hashmaps, and other frequently allocated objects
@@ -137,7 +197,10 @@ pub struct ReteNetwork {
     /// - Only process new/changed facts (not all facts every time)
     /// - Remove facts when they are deleted/expired
     /// - Maintain partial matches across fact lifecycle events
-    working_memory: HashMap<FactId, Fact>,
+    ///
+    /// Facts are held behind `Arc` so the beta network shares them instead of
+    /// copying working memory for every token batch.
+    working_memory: FactMemory,
 
     /// **Alpha Memory Manager**: Efficient indexing of facts by patterns
     ///
@@ -163,7 +226,104 @@ pub struct ReteNetwork {
     ///
     /// LRU cache that stores results of deterministic calculator calls.
     /// Improves performance when the same calculation is repeated with identical inputs.
-    calculator_cache: std::collections::HashMap<String, crate::types::FactValue>,
+    calculator_cache: CalculatorCache,
+
+    /// **Aggregation Nodes**: Incremental aggregates keyed by aggregation condition signature
+    ///
+    /// Each node maintains running Sum/Count/Avg/Min/Max state per `group_by` key and is
+    /// updated as facts are asserted and retracted, so aggregation conditions no longer
+    /// rescan the fact store on every test.
+    aggregation_nodes: HashMap<String, AggregationNode>,
+
+    /// **Window Nodes**: Stream windows keyed by stream condition signature
+    ///
+    /// Each node assigns filtered facts to tumbling, sliding, session or count windows
+    /// and keeps the stream aggregation of every window current, so temporal rules are
+    /// evaluated against the windows their triggering fact falls in.
+    window_nodes: HashMap<String, WindowNode>,
+
+    /// **Sequence Nodes**: Partial matches keyed by sequence condition signature
+    ///
+    /// Each node holds the partial matches of one sequence condition until they are
+    /// completed or time out, so temporal pattern rules fire for the fact completing
+    /// the sequence.
+    sequence_nodes: HashMap<String, SequenceNode>,
+
+    /// **Calendars**: Reference period calendars for calendar aggregation windows
+    calendars: HashMap<String, Arc<PeriodCalendar>>,
+
+    /// **Reference Data**: Named lookup tables for membership conditions and calculator
+    /// inputs, shared with the alpha memory manager and hot-reloadable
+    reference_data: ReferenceDataStore,
+
+    /// **Collation**: How simple conditions compare two strings
+    collation: Collation,
+
+    /// **Non-Finite Floats**: What happens to NaN and infinite floats in facts and
+    /// derived values, with counters shared by copies of the network
+    non_finite: NonFiniteGuard,
+
+    /// **Integer Overflow**: What integer arithmetic in actions produces when it
+    /// overflows `i64`
+    overflow_policy: OverflowPolicy,
+
+    /// **Late Data**: What windowed and sequence conditions do with events that arrive
+    /// behind the watermark
+    late_data_policy: LateDataPolicy,
+
+    /// **Field Collisions**: What happens when rules set the same field of a fact to
+    /// different values in one batch
+    field_collision_policy: FieldCollisionPolicy,
+
+    /// **Rule Salience**: Precedence of rules under
+    /// [`FieldCollisionPolicy::HighestSalience`]; unlisted rules have salience 0
+    rule_salience: HashMap<RuleId, i32>,
+
+    /// **Rule Groups**: Groups whose rules match but don't fire until the group is
+    /// enabled again
+    disabled_rule_groups: BTreeSet<String>,
+
+    /// **Rule Hit Counts**: Evaluations, firings and condition evaluation time of each
+    /// rule, kept when the network is recompiled
+    rule_counters: HashMap<RuleId, RuleCounters>,
+
+    /// **Working Memory Profiler**: Sampled hot facts and fields, while profiling is
+    /// enabled
+    working_memory_profiler: Option<WorkingMemoryProfiler>,
+
+    /// **Duplicate Rules**: What adding a rule duplicating an existing one does
+    duplicate_rule_policy: DuplicateRulePolicy,
+
+    /// **Action Validation**: Field types rule actions are checked against, and what
+    /// adding a rule with invalid actions does
+    field_schema: FieldSchema,
+    action_validation_policy: ActionValidationPolicy,
+
+    /// **Rule Cycles**: What adding a rule that closes a cycle of rules triggering
+    /// each other does
+    rule_cycle_policy: RuleCyclePolicy,
+
+    /// **Webhooks**: Named endpoints for webhook actions and the workers delivering to
+    /// them, shared with copies of the network
+    webhooks: WebhookDispatcher,
+
+    /// **Audit Log**: Explanation traces of recent rule executions, shared with copies
+    /// of the network
+    audit_log: AuditLog,
+
+    /// **Dry Run**: Evaluate rules without side effects outside the network, such as
+    /// dispatching webhooks
+    dry_run: bool,
+
+    /// **Transaction Hold**: Webhook actions held back while a transaction commits,
+    /// dispatched only if it applies, and the network changes to undo if it rolls back
+    transaction: Option<TransactionHold>,
+
+    /// **Truth Maintenance**: Logical support for rule activations and derived facts
+    ///
+    /// Records which facts justified each activation and which facts the activation
+    /// asserted, so that retracting a fact withdraws everything that depended on it.
+    truth_maintenance: TruthMaintenanceSystem,
 }
 
 impl ReteNetwork {
@@ -197,22 +357,141 @@ impl ReteNetwork {
         #[allow(clippy::arc_with_non_send_sync)]
         let lazy_aggregation_manager =
             LazyAggregationManager::new(std::sync::Arc::new(memory_pools.clone()));
+        let reference_data = ReferenceDataStore::new();
+        let mut alpha_memory_manager = AlphaMemoryManager::new();
+        alpha_memory_manager.set_reference_data(reference_data.clone());
 
         Self {
             alpha_nodes: HashMap::new(),
             beta_nodes: HashMap::new(),
             terminal_nodes: HashMap::new(),
             rules: HashMap::new(),
+            condition_orders: HashMap::new(),
             next_node_id: 1,
             created_facts: Vec::new(),
+            forward_chaining: None,
+            parallel_alpha: None,
+            deterministic: false,
+            chain_conclusions: HashSet::new(),
+            evaluation_date: EvaluationDate::Now,
+            as_of: None,
             memory_pools,
             lazy_aggregation_manager,
             working_memory: HashMap::new(),
-            alpha_memory_manager: AlphaMemoryManager::new(),
+            alpha_memory_manager,
             beta_network_manager: BetaNetworkManager::new(),
             rule_optimizer: RuleOptimizer::new(),
-            calculator_cache: std::collections::HashMap::new(),
+            calculator_cache: CalculatorCache::default(),
+            aggregation_nodes: HashMap::new(),
+            window_nodes: HashMap::new(),
+            sequence_nodes: HashMap::new(),
+            calendars: HashMap::new(),
+            reference_data,
+            collation: Collation::binary(),
+            non_finite: NonFiniteGuard::default(),
+            overflow_policy: OverflowPolicy::default(),
+            late_data_policy: LateDataPolicy::default(),
+            field_collision_policy: FieldCollisionPolicy::default(),
+            rule_salience: HashMap::new(),
+            disabled_rule_groups: BTreeSet::new(),
+            rule_counters: HashMap::new(),
+            working_memory_profiler: None,
+            duplicate_rule_policy: DuplicateRulePolicy::default(),
+            field_schema: FieldSchema::new(),
+            action_validation_policy: ActionValidationPolicy::default(),
+            rule_cycle_policy: RuleCyclePolicy::default(),
+            webhooks: WebhookDispatcher::new(),
+            audit_log: AuditLog::default(),
+            dry_run: false,
+            transaction: None,
+            truth_maintenance: TruthMaintenanceSystem::new(),
+        }
+    }
+
+    /// Copies the compiled network without any fact state.
+    ///
+    /// Rules, nodes, alpha memory patterns and the beta network topology are copied
+    /// as they are, while working memory, tokens, aggregates, windows, truth
+    /// maintenance, caches and condition counters start empty. Sessions running the same ruleset can start
+    /// from this copy instead of compiling every rule again.
+    pub fn clone_compiled(&self) -> Self {
+        let mut network = Self::new();
+        network.alpha_nodes = self.alpha_nodes.clone();
+        for node in network.alpha_nodes.values_mut() {
+            node.reset_counters();
         }
+        network.beta_nodes = self.beta_nodes.clone();
+        network.terminal_nodes = self.terminal_nodes.clone();
+        network.rules = self.rules.clone();
+        network.condition_orders = self.condition_orders.clone();
+        network.forward_chaining = self.forward_chaining;
+        network.parallel_alpha = self.parallel_alpha.clone();
+        network.deterministic = self.deterministic;
+        network.next_node_id = self.next_node_id;
+        network.rule_optimizer = self.rule_optimizer.clone();
+        network.calendars = self.calendars.clone();
+        network.reference_data = self.reference_data.clone();
+        network.collation = self.collation.clone();
+        network.non_finite = self.non_finite.clone();
+        network.overflow_policy = self.overflow_policy;
+        network.late_data_policy = self.late_data_policy;
+        network.field_collision_policy = self.field_collision_policy;
+        network.rule_salience = self.rule_salience.clone();
+        network.disabled_rule_groups = self.disabled_rule_groups.clone();
+        network.duplicate_rule_policy = self.duplicate_rule_policy;
+        network.field_schema = self.field_schema.clone();
+        network.action_validation_policy = self.action_validation_policy;
+        network.rule_cycle_policy = self.rule_cycle_policy;
+        network.webhooks = self.webhooks.clone();
+        network.audit_log = self.audit_log.clone();
+        network.calculator_cache = CalculatorCache::new(self.calculator_cache.config());
+        network.dry_run = self.dry_run;
+        network.working_memory_profiler = self
+            .working_memory_profiler
+            .as_ref()
+            .map(|profiler| WorkingMemoryProfiler::new(profiler.config()));
+
+        network.alpha_memory_manager = self.alpha_memory_manager.clone();
+        network.alpha_memory_manager.clear_facts();
+
+        network.beta_network_manager = self.beta_network_manager.clone();
+        network.beta_network_manager.clear_all_tokens();
+        network.beta_network_manager.memory_pools = MemoryPoolManager::new();
+
+        network.aggregation_nodes = self
+            .aggregation_nodes
+            .iter()
+            .map(|(signature, node)| {
+                let mut node = node.clone();
+                node.clear();
+                (signature.clone(), node)
+            })
+            .collect();
+        network.window_nodes = self
+            .window_nodes
+            .iter()
+            .map(|(signature, node)| {
+                let mut node = node.clone();
+                node.clear();
+                (signature.clone(), node)
+            })
+            .collect();
+        network.sequence_nodes = self
+            .sequence_nodes
+            .iter()
+            .map(|(signature, node)| {
+                let mut node = node.clone();
+                node.clear();
+                (signature.clone(), node)
+            })
+            .collect();
+
+        debug!(
+            rules = network.rules.len(),
+            alpha_nodes = network.alpha_nodes.len(),
+            "Cloned compiled RETE network"
+        );
+        network
     }
 
     /// Adds a rule to the RETE network, creating necessary nodes and connections.
@@ -257,6 +536,7 @@ impl ReteNetwork {
     ///     name: "test_rule".to_string(),
     ///     conditions: vec![],
     ///     actions: vec![],
+    ///     metadata: RuleMetadata::default(),
     /// };
     ///
     /// network.add_rule(rule)?;
@@ -269,7 +549,7 @@ impl ReteNetwork {
 
         // Optimize rule conditions for better performance
         let optimization_result = self.rule_optimizer.optimize_rule(rule);
-        let optimized_rule = optimization_result.optimized_rule;
+        let mut optimized_rule = optimization_result.optimized_rule;
 
         if optimization_result.estimated_improvement > 0.0 {
             debug!(
@@ -280,6 +560,43 @@ impl ReteNetwork {
             );
         }
 
+        if let (Some(effective), Some(expiry)) = (
+            optimized_rule.metadata.effective_date,
+            optimized_rule.metadata.expiry_date,
+        ) && expiry <= effective
+        {
+            anyhow::bail!(
+                "Rule {rule_id} expires at {expiry}, before it takes effect at {effective}"
+            );
+        }
+
+        // Reject unbuildable windows before any nodes are created for the rule
+        let mut regexes = CompiledRegexes::default();
+        for condition in &optimized_rule.conditions {
+            match condition {
+                Condition::Stream(stream_condition) => WindowNode::validate(stream_condition)?,
+                Condition::Sequence(sequence_condition) => {
+                    SequenceNode::validate(sequence_condition)?;
+                }
+                Condition::Aggregation(agg_condition) => {
+                    self.aggregation_calendar(agg_condition)?;
+                }
+                _ => {}
+            }
+            // Compile regexes now so a bad pattern fails here rather than never matching
+            regexes.compile(condition)?;
+            self.validate_value_lists(condition)?;
+            Self::validate_float_literals(condition)?;
+        }
+        self.validate_calculator_inputs(&optimized_rule)?;
+        self.validate_webhooks(&optimized_rule)?;
+
+        // Hash literal membership arrays once instead of scanning them per fact
+        self.alpha_memory_manager.add_regexes(regexes);
+        for condition in &mut optimized_rule.conditions {
+            self.compile_value_lists(condition);
+        }
+
         // Create alpha nodes for optimized conditions
         for condition in &optimized_rule.conditions {
             self.create_alpha_node_for_condition(rule_id, condition)?;
@@ -296,12 +613,135 @@ impl ReteNetwork {
             self.create_beta_network_for_rule(&optimized_rule)?;
         }
 
-        // Store the optimized rule
+        // Store the optimized rule, remembering the declared order of planned conditions
+        let planned_order = optimization_result.strategies_applied.into_iter().find_map(
+            |strategy| match strategy {
+                OptimizationStrategy::QueryPlanReordering { order } => Some(order),
+                _ => None,
+            },
+        );
+        match planned_order {
+            Some(order) => self.condition_orders.insert(rule_id, order),
+            None => self.condition_orders.remove(&rule_id),
+        };
+        self.alpha_memory_manager.route_rule(&optimized_rule);
         self.rules.insert(rule_id, optimized_rule);
 
         Ok(())
     }
 
+    /// Reject `In` and `NotIn` conditions naming an unregistered reference table or
+    /// testing membership in anything other than an array or a table name
+    fn validate_value_lists(&self, condition: &Condition) -> Result<()> {
+        match condition {
+            Condition::Simple { field, operator: Operator::In | Operator::NotIn, value } => {
+                match value {
+                    FactValue::Array(_) => Ok(()),
+                    FactValue::String(name) => {
+                        if !self.alpha_memory_manager.value_lists().contains(name) {
+                            anyhow::bail!("Reference table '{name}' is not registered");
+                        }
+                        Ok(())
+                    }
+                    other => anyhow::bail!(
+                        "Field '{field}' can only be tested for membership in an array or a value list, got {other}"
+                    ),
+                }
+            }
+            Condition::Complex { conditions, .. }
+            | Condition::And { conditions }
+            | Condition::Or { conditions } => {
+                conditions.iter().try_for_each(|condition| self.validate_value_lists(condition))
+            }
+            Condition::Accumulate(accumulate) => self.validate_value_lists(&accumulate.source),
+            Condition::Simple { .. }
+            | Condition::Aggregation(_)
+            | Condition::Stream(_)
+            | Condition::Sequence(_) => Ok(()),
+        }
+    }
+
+    /// Reject conditions comparing with a NaN or infinite literal, which no value can
+    /// meaningfully match
+    fn validate_float_literals(condition: &Condition) -> Result<()> {
+        match condition {
+            Condition::Simple { field, value: FactValue::Float(value), .. }
+                if !value.is_finite() =>
+            {
+                anyhow::bail!("Field '{field}' is compared with non-finite float {value}")
+            }
+            Condition::Complex { conditions, .. }
+            | Condition::And { conditions }
+            | Condition::Or { conditions } => {
+                conditions.iter().try_for_each(Self::validate_float_literals)
+            }
+            Condition::Aggregation(agg_condition) => {
+                agg_condition.having.as_deref().map_or(Ok(()), Self::validate_float_literals)
+            }
+            Condition::Accumulate(accumulate) => Self::validate_float_literals(&accumulate.source),
+            Condition::Simple { .. } | Condition::Stream(_) | Condition::Sequence(_) => Ok(()),
+        }
+    }
+
+    /// Reject calculator inputs mapped from an unregistered reference table or an unset
+    /// global
+    fn validate_calculator_inputs(&self, rule: &Rule) -> Result<()> {
+        for action in &rule.actions {
+            if let crate::types::ActionType::CallCalculator { input_mapping, .. } =
+                &action.action_type
+            {
+                for mapping in input_mapping.values() {
+                    if let Some(name) = parse_global_reference(mapping) {
+                        if self.reference_data.global(name).is_none() {
+                            anyhow::bail!("Global '{name}' is not set");
+                        }
+                    } else if let Some((table, _)) = parse_table_reference(mapping) {
+                        if !self.reference_data.contains(table) {
+                            anyhow::bail!("Reference table '{table}' is not registered");
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Reject webhook actions naming an unregistered endpoint
+    fn validate_webhooks(&self, rule: &Rule) -> Result<()> {
+        for action in &rule.actions {
+            if let crate::types::ActionType::CallWebhook { endpoint, .. } = &action.action_type {
+                if !self.webhooks.contains(endpoint) {
+                    anyhow::bail!("Webhook endpoint '{endpoint}' is not registered");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Replace the literal arrays of `In` and `NotIn` conditions with compiled value lists
+    fn compile_value_lists(&mut self, condition: &mut Condition) {
+        match condition {
+            Condition::Simple { operator: Operator::In | Operator::NotIn, value, .. } => {
+                if let FactValue::Array(values) = value {
+                    let name = self.alpha_memory_manager.compile_value_list(values);
+                    *value = FactValue::String(name);
+                }
+            }
+            Condition::Complex { conditions, .. }
+            | Condition::And { conditions }
+            | Condition::Or { conditions } => {
+                for condition in conditions {
+                    self.compile_value_lists(condition);
+                }
+            }
+            Condition::Accumulate(accumulate) => self.compile_value_lists(&mut accumulate.source),
+            Condition::Simple { .. }
+            | Condition::Aggregation(_)
+            | Condition::Stream(_)
+            | Condition::Sequence(_) => {}
+        }
+    }
+
     /// Add a fact to working memory for incremental processing
     ///
     /// ## Working Memory Management
@@ -352,10 +792,24 @@ impl ReteNetwork {
             fact_id
         );
 
+        // Re-asserting a fact replaces it, so its earlier matches are retracted and
+        // the new version propagates as a fresh delta
+        if self.working_memory.contains_key(&fact_id) {
+            self.beta_network_manager.retract_tokens_containing_fact(fact_id);
+            self.alpha_memory_manager.process_fact_removal(fact_id);
+        }
+
         // Store fact in working memory FIRST
-        self.working_memory.insert(fact_id, fact.clone());
+        let fact = Arc::new(fact);
+        self.working_memory.insert(fact_id, Arc::clone(&fact));
+
+        // Update incremental aggregates before any rule tests them
+        self.assert_into_aggregation_nodes(std::slice::from_ref(&*fact), fact_store);
+        self.assert_into_window_nodes(std::slice::from_ref(&*fact), fact_store)?;
+        self.assert_into_sequence_nodes(std::slice::from_ref(&*fact), fact_store)?;
 
         // Process fact through alpha memory for proper RETE indexing
+        self.evaluate_as_of(&fact);
         let matching_patterns = self.alpha_memory_manager.process_fact_addition(fact_id, &fact);
         debug!(
             "Fact {} matched {} alpha memory patterns",
@@ -380,12 +834,31 @@ impl ReteNetwork {
             }
         }
 
+        if self.deterministic {
+            rule_ids_to_process.sort_unstable();
+        }
+        let rule_ids_to_process = self.order_activation_groups(rule_ids_to_process, |id| *id);
+        let mut fired_groups = HashSet::new();
+
         // Process each rule
         for rule_id in rule_ids_to_process {
+            let activation_group = self.activation_group(rule_id);
+            if activation_group.as_ref().is_some_and(|group| fired_groups.contains(group)) {
+                debug!(
+                    rule_id,
+                    "Activation group already fired for this fact, skipping rule"
+                );
+                continue;
+            }
             if let Some(rule) = self.rules.get(&rule_id).cloned() {
                 // Process this fact through the beta network for this rule
                 let rule_results =
                     self.process_fact_incrementally(rule_id, &fact, &rule, fact_store, calculator)?;
+                if let Some(group) = activation_group
+                    && !rule_results.is_empty()
+                {
+                    fired_groups.insert(group);
+                }
                 results.extend(rule_results);
             }
         }
@@ -420,34 +893,39 @@ impl ReteNetwork {
             rule.conditions.len()
         );
 
-        if rule.conditions.len() == 1 {
+        if let Some(accumulated) =
+            self.process_accumulating_rule(rule_id, new_fact, fact_store, calculator)?
+        {
+            results.extend(accumulated);
+        } else if rule.conditions.len() == 1 {
             // Single condition rule - direct alpha network processing
-            if self.fact_matches_all_conditions(new_fact, &rule.conditions, fact_store)? {
-                let executed_actions =
-                    self.execute_rule_actions(rule, new_fact, fact_store, calculator)?;
-                results.push(RuleExecutionResult {
-                    rule_id,
-                    fact_id: new_fact.id,
-                    actions_executed: executed_actions,
-                });
+            let evaluation_start = Instant::now();
+            let matched =
+                self.fact_matches_all_conditions(new_fact, &rule.conditions, fact_store)?;
+            self.record_rule_evaluation(rule_id, new_fact.id, evaluation_start.elapsed());
+            self.record_alpha_evaluation(&rule.conditions[0], matched);
+            if matched {
+                results.extend(self.fire_rule(
+                    rule,
+                    new_fact,
+                    &[new_fact.id],
+                    fact_store,
+                    calculator,
+                )?);
                 debug!(
                     "Single-condition rule {} fired for fact {}",
                     rule_id, new_fact.id
                 );
             }
         } else {
-            // Multi-condition rule - use enhanced beta network with token propagation
-            let initial_tokens = self.create_or_extend_tokens_for_fact(rule_id, new_fact, rule)?;
-
-            // Process tokens through the beta network for proper propagation
-            let completed_tokens = self
-                .beta_network_manager
-                .process_token(Token::new(rule_id), &self.working_memory);
-
-            // Process both initial tokens and completed tokens from beta network
-            let all_tokens = [initial_tokens, completed_tokens].concat();
-
-            for token in all_tokens {
+            // Multi-condition rule - only the new fact's tokens are propagated; the
+            // rest of working memory has already been matched on earlier passes
+            let evaluation_start = Instant::now();
+            let delta_tokens =
+                self.create_or_extend_tokens_for_fact(rule_id, new_fact, rule, fact_store)?;
+            self.record_rule_evaluation(rule_id, new_fact.id, evaluation_start.elapsed());
+
+            for token in delta_tokens {
                 debug!(
                     "🎯 Checking token completeness: rule {} has {} facts, needs {} conditions",
                     rule_id,
@@ -456,18 +934,27 @@ impl ReteNetwork {
                 );
                 debug!("🎯 Token facts: {:?}", token.facts);
                 if token.is_complete(rule) {
+                    // Terminal nodes fire once per match, even if it is propagated again
+                    if !self.beta_network_manager.activate_terminal(token.clone()) {
+                        debug!(
+                            "Token {:?} already activated for rule {}",
+                            token.facts, rule_id
+                        );
+                        continue;
+                    }
+
                     // All conditions satisfied - execute rule
                     debug!(
                         "🔥 FIRING RULE {} - Complete token with facts: {:?}",
                         rule_id, token.facts
                     );
-                    let executed_actions =
-                        self.execute_rule_actions(rule, new_fact, fact_store, calculator)?;
-                    results.push(RuleExecutionResult {
-                        rule_id,
-                        fact_id: new_fact.id,
-                        actions_executed: executed_actions,
-                    });
+                    results.extend(self.fire_rule(
+                        rule,
+                        new_fact,
+                        &token.facts,
+                        fact_store,
+                        calculator,
+                    )?);
                     debug!(
                         "🔥 Multi-condition rule {} fired for complete token",
                         rule_id
@@ -499,6 +986,7 @@ impl ReteNetwork {
         rule_id: RuleId,
         new_fact: &Fact,
         rule: &Rule,
+        fact_store: &ArenaFactStore,
     ) -> Result<Vec<Token>> {
         let mut resulting_tokens = Vec::new();
 
@@ -506,18 +994,49 @@ impl ReteNetwork {
         let mut matching_condition_indices = Vec::new();
         for (index, condition) in rule.conditions.iter().enumerate() {
             match condition {
-                Condition::Aggregation(_) => {
-                    // Aggregation conditions are always considered "matched" by any fact
-                    // because they operate on the entire fact store, not individual facts
-                    matching_condition_indices.push(index);
-                    debug!(
-                        "Fact {} triggers aggregation condition {} for rule {}",
-                        new_fact.id, index, rule_id
-                    );
+                Condition::Aggregation(agg_condition) => {
+                    // Aggregation nodes gate the join: the condition matches when the
+                    // aggregate for the fact's group satisfies the having clause
+                    if self.evaluate_aggregation_condition(new_fact, agg_condition, fact_store)? {
+                        matching_condition_indices.push(index);
+                        debug!(
+                            "Fact {} satisfies aggregation condition {} for rule {}",
+                            new_fact.id, index, rule_id
+                        );
+                    }
+                }
+                Condition::Stream(stream_condition) => {
+                    // Window nodes gate the join: the condition matches when a window
+                    // holding the fact satisfies the having clause
+                    if self.evaluate_stream_condition(new_fact, stream_condition, fact_store)? {
+                        matching_condition_indices.push(index);
+                        debug!(
+                            "Fact {} satisfies stream condition {} for rule {}",
+                            new_fact.id, index, rule_id
+                        );
+                    }
+                }
+                Condition::Sequence(sequence_condition) => {
+                    // Sequence nodes gate the join: the condition matches when the fact
+                    // completes the sequence
+                    if self.evaluate_sequence_condition(new_fact, sequence_condition) {
+                        matching_condition_indices.push(index);
+                        debug!(
+                            "Fact {} completes sequence condition {} for rule {}",
+                            new_fact.id, index, rule_id
+                        );
+                    }
                 }
                 _ => {
                     if let Some(pattern) = FactPattern::from_condition(condition) {
-                        if pattern.matches_fact(new_fact) {
+                        let matched = pattern.matches_fact_with(
+                            new_fact,
+                            &self.collation,
+                            self.alpha_memory_manager.value_lists(),
+                            self.alpha_memory_manager.regexes(),
+                        );
+                        self.record_alpha_evaluation(condition, matched);
+                        if matched {
                             matching_condition_indices.push(index);
                             debug!(
                                 "✅ Fact {} matches condition {} for rule {}: {:?}",
@@ -597,12 +1116,46 @@ impl ReteNetwork {
         // Store which rules might be affected for return value
         let mut affected_rules = Vec::new();
 
-        // Remove fact from working memory first
-        let removed_fact = self.working_memory.remove(&fact_id);
+        // Withdraw the fact's contribution from incremental aggregates
+        for node in self.aggregation_nodes.values_mut() {
+            if node.retract_fact(fact_id) {
+                for rule_id in &node.dependent_rules {
+                    if !affected_rules.contains(rule_id) {
+                        affected_rules.push(*rule_id);
+                    }
+                }
+            }
+        }
+
+        // Withdraw the fact from every stream window holding it
+        for node in self.window_nodes.values_mut() {
+            if node.retract_fact(fact_id)? {
+                for rule_id in &node.dependent_rules {
+                    if !affected_rules.contains(rule_id) {
+                        affected_rules.push(*rule_id);
+                    }
+                }
+            }
+        }
 
-        if removed_fact.is_none() {
+        // Drop the sequence matches the fact took part in
+        for node in self.sequence_nodes.values_mut() {
+            if node.retract_fact(fact_id) {
+                for rule_id in &node.dependent_rules {
+                    if !affected_rules.contains(rule_id) {
+                        affected_rules.push(*rule_id);
+                    }
+                }
+            }
+        }
+
+        // Remove fact from working memory first
+        let Some(removed_fact) = self.working_memory.remove(&fact_id) else {
             debug!("Fact {} was not in working memory", fact_id);
             return Ok(affected_rules);
+        };
+        if let Some(transaction) = self.own_transaction() {
+            transaction.working_memory.entry(fact_id).or_insert(removed_fact);
         }
 
         // Remove fact from alpha memory indexes
@@ -637,6 +1190,44 @@ impl ReteNetwork {
         Ok(affected_rules)
     }
 
+    /// Retract a fact and withdraw everything that logically depended on it
+    ///
+    /// ## Truth Maintenance
+    ///
+    /// 1. Withdraw every rule activation the fact supported
+    /// 2. Retract facts asserted by those activations once they lose all support
+    /// 3. Repeat for each retracted derived fact until the network is consistent
+    ///
+    /// Each retracted fact is also removed from working memory, alpha memories and
+    /// beta tokens, and derived facts are dropped from the created facts buffer.
+    pub fn retract_fact(&mut self, fact_id: FactId) -> Result<RetractionResult> {
+        info!("Retracting fact {} with truth maintenance", fact_id);
+
+        let retraction = self.truth_maintenance.retract(fact_id);
+
+        self.remove_fact_from_working_memory(fact_id)?;
+        for &derived_id in &retraction.retracted_facts {
+            self.remove_fact_from_working_memory(derived_id)?;
+        }
+
+        if !retraction.retracted_facts.is_empty() {
+            self.created_facts.retain(|fact| !retraction.retracted_facts.contains(&fact.id));
+        }
+
+        info!(
+            "Fact {} retracted: {} activations withdrawn, {} derived facts retracted",
+            fact_id,
+            retraction.withdrawn_activations.len(),
+            retraction.retracted_facts.len()
+        );
+        Ok(retraction)
+    }
+
+    /// Get the truth maintenance system tracking activation support
+    pub fn truth_maintenance(&self) -> &TruthMaintenanceSystem {
+        &self.truth_maintenance
+    }
+
     /// Propagate fact retraction through the beta network
     ///
     /// This method implements the retraction propagation logic:
@@ -702,6 +1293,51 @@ impl ReteNetwork {
         (fact_count, memory_bytes)
     }
 
+    /// Shared handle to a fact held in working memory
+    pub fn working_memory_fact(&self, fact_id: FactId) -> Option<Arc<Fact>> {
+        self.working_memory.get(&fact_id).cloned()
+    }
+
+    /// Number of matches the terminal node of a multi-condition rule has activated
+    pub fn activated_match_count(&self, rule_id: RuleId) -> usize {
+        self.beta_network_manager.activated_token_count(rule_id)
+    }
+
+    /// Explain which conditions of every rule a fact satisfies
+    ///
+    /// Conditions are tested against the fact without touching working memory,
+    /// tokens or activations, so this is safe to run on a shared, read-only network.
+    /// Explanations are ordered by rule ID.
+    pub fn explain_fact(
+        &self,
+        fact: &Fact,
+        fact_store: &ArenaFactStore,
+    ) -> Result<Vec<RuleExplanation>> {
+        let mut explanations = Vec::with_capacity(self.rules.len());
+        for rule in self.rules.values() {
+            let mut condition_results = rule
+                .conditions
+                .iter()
+                .map(|condition| self.test_condition(fact, condition, fact_store))
+                .collect::<Result<Vec<_>>>()?;
+            if let Some(order) = self.condition_orders.get(&rule.id) {
+                let planned = std::mem::take(&mut condition_results);
+                condition_results = vec![false; planned.len()];
+                for (&declared, result) in order.iter().zip(planned) {
+                    condition_results[declared] = result;
+                }
+            }
+            explanations.push(RuleExplanation {
+                rule_id: rule.id,
+                rule_name: rule.name.clone(),
+                matched: condition_results.iter().all(|&result| result),
+                condition_results,
+            });
+        }
+        explanations.sort_by_key(|explanation| explanation.rule_id);
+        Ok(explanations)
+    }
+
     /// Get comprehensive alpha memory statistics
     pub fn get_alpha_memory_stats(&self) -> crate::alpha_memory::AlphaMemoryManagerStats {
         self.alpha_memory_manager.get_statistics()
@@ -722,6 +1358,18 @@ impl ReteNetwork {
         self.working_memory.clear();
         // Also clear beta network as it depends on working memory
         self.beta_network_manager.clear_all_tokens();
+        // Support information is meaningless once the facts are gone
+        self.truth_maintenance.clear();
+        // Aggregates are re-seeded from the fact store on next use
+        for node in self.aggregation_nodes.values_mut() {
+            node.clear();
+        }
+        for node in self.window_nodes.values_mut() {
+            node.clear();
+        }
+        for node in self.sequence_nodes.values_mut() {
+            node.clear();
+        }
     }
 
     /// Get beta network statistics for monitoring and debugging
@@ -747,21 +1395,93 @@ impl ReteNetwork {
         debug!("Beta network cleanup requested");
     }
 
+    /// Activation group of a rule, if it has one
+    fn activation_group(&self, rule_id: RuleId) -> Option<String> {
+        self.rules.get(&rule_id)?.metadata.activation_group.clone()
+    }
+
+    /// Reorders the candidates of each activation group so the one that should win is
+    /// tried first: highest salience, then lowest rule ID
+    ///
+    /// Members of a group take over the positions the group already held among the
+    /// candidates, so rules outside activation groups keep their order.
+    fn order_activation_groups<T>(
+        &self,
+        candidates: Vec<T>,
+        rule_id: impl Fn(&T) -> RuleId,
+    ) -> Vec<T> {
+        let mut slots: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+        for (index, candidate) in candidates.iter().enumerate() {
+            if let Some(group) = self
+                .rules
+                .get(&rule_id(candidate))
+                .and_then(|rule| rule.metadata.activation_group.as_deref())
+            {
+                slots.entry(group).or_default().push(index);
+            }
+        }
+        if slots.is_empty() {
+            return candidates;
+        }
+
+        let mut order: Vec<usize> = (0..candidates.len()).collect();
+        for indices in slots.values() {
+            let mut by_precedence = indices.clone();
+            by_precedence.sort_by_key(|&index| {
+                let id = rule_id(&candidates[index]);
+                (std::cmp::Reverse(self.salience(id)), id)
+            });
+            for (&slot, &index) in indices.iter().zip(&by_precedence) {
+                order[slot] = index;
+            }
+        }
+        let mut candidates: Vec<Option<T>> = candidates.into_iter().map(Some).collect();
+        order.into_iter().filter_map(|index| candidates[index].take()).collect()
+    }
+
     /// Process a single fact (used by incremental processing)
+    ///
+    /// Of the rules sharing an activation group, only the first to fire for the fact
+    /// does.
     fn process_single_fact(
         &mut self,
         fact: &Fact,
+        alpha: Option<Vec<AlphaMatch>>,
         fact_store: &ArenaFactStore,
         calculator: &Calculator,
     ) -> Result<Vec<RuleExecutionResult>> {
         let mut results = Vec::new();
+        self.evaluate_as_of(fact);
+
+        // Get candidate rules from alpha memory based on fact fields, unless they were
+        // evaluated in parallel with the rest of the batch
+        let candidate_rules = alpha.unwrap_or_else(|| {
+            self.get_candidate_rules_from_alpha_memory(fact)
+                .into_iter()
+                .map(|rule_id| AlphaMatch { rule_id, test: None })
+                .collect()
+        });
 
-        // Get candidate rules from alpha memory based on fact fields
-        let candidate_rules = self.get_candidate_rules_from_alpha_memory(fact);
+        let candidate_rules =
+            self.order_activation_groups(candidate_rules, |candidate| candidate.rule_id);
+        let mut fired_groups = HashSet::new();
 
         // Process each candidate rule through the beta network
-        for rule_id in candidate_rules {
-            if let Some(rule) = self.rules.get(&rule_id) {
+        for AlphaMatch { rule_id, test } in candidate_rules {
+            let activation_group = self.activation_group(rule_id);
+            if activation_group.as_ref().is_some_and(|group| fired_groups.contains(group)) {
+                debug!(
+                    rule_id,
+                    "Activation group already fired for this fact, skipping rule"
+                );
+                continue;
+            }
+            let fired_before = results.len();
+            if let Some(accumulated) =
+                self.process_accumulating_rule(rule_id, fact, fact_store, calculator)?
+            {
+                results.extend(accumulated);
+            } else if let Some(rule) = self.rules.get(&rule_id) {
                 let conditions = rule.conditions.clone(); // Clone to avoid borrow checker issues
 
                 debug!(
@@ -776,21 +1496,32 @@ impl ReteNetwork {
                     // Alpha memory optimization does NOT apply to aggregation conditions
                     // We must explicitly test the condition for correctness
 
-                    if self.fact_matches_all_conditions(fact, &conditions, fact_store)? {
+                    let (matched, evaluation_time) = match test {
+                        Some(test) => (test.matched?, test.elapsed),
+                        None => {
+                            let evaluation_start = Instant::now();
+                            let matched =
+                                self.fact_matches_all_conditions(fact, &conditions, fact_store)?;
+                            (matched, evaluation_start.elapsed())
+                        }
+                    };
+                    if matched {
                         debug!("Rule {} matches - executing actions", rule_id);
                         // Clone the rule to avoid borrow checker issues
                         let rule_clone = rule.clone();
                         // Execute the rule actions properly
-                        let executed_actions =
-                            self.execute_rule_actions(&rule_clone, fact, fact_store, calculator)?;
-                        results.push(RuleExecutionResult {
-                            rule_id,
-                            fact_id: fact.id,
-                            actions_executed: executed_actions,
-                        });
+                        results.extend(self.fire_rule(
+                            &rule_clone,
+                            fact,
+                            &[fact.id],
+                            fact_store,
+                            calculator,
+                        )?);
                     } else {
                         debug!("Rule {} does NOT match - skipping", rule_id);
                     }
+                    self.record_alpha_evaluation(&conditions[0], matched);
+                    self.record_rule_evaluation(rule_id, fact.id, evaluation_time);
                 } else {
                     // Multi-condition rule - use beta network with token propagation
                     let rule_results = self.process_fact_through_beta_network(
@@ -803,6 +1534,11 @@ impl ReteNetwork {
                     results.extend(rule_results);
                 }
             }
+            if let Some(group) = activation_group
+                && results.len() > fired_before
+            {
+                fired_groups.insert(group);
+            }
         }
 
         Ok(results)
@@ -892,47 +1628,179 @@ impl ReteNetwork {
         fact_store: &ArenaFactStore,
         calculator: &Calculator,
     ) -> Result<Vec<RuleExecutionResult>> {
+        let unlimited = EvaluationBudget::unlimited();
+        self.process_facts_within(facts, true, &unlimited, fact_store, calculator)
+            .map(|(results, _)| results)
+    }
+
+    /// Process facts until `budget` is spent, returning the results and how many of
+    /// the facts were evaluated
+    ///
+    /// The first slice of a batch asserts all of its facts into aggregation and window
+    /// nodes; later slices of the same batch pass `first_slice` false and only evaluate
+    /// their facts. At least one fact is evaluated whatever the budget.
+    pub fn process_facts_within(
+        &mut self,
+        facts: &[Fact],
+        first_slice: bool,
+        budget: &EvaluationBudget,
+        fact_store: &ArenaFactStore,
+        calculator: &Calculator,
+    ) -> Result<(Vec<RuleExecutionResult>, usize)> {
         info!(
             fact_count = facts.len(),
             rule_count = self.rules.len(),
             "Processing facts through RETE network (batch mode)"
         );
+        let started = Instant::now();
         let mut results = Vec::new();
-
-        // OPTIMIZATION: Only clear beta network if we have multi-condition rules
-        // For single-condition rules (most common case), beta network isn't used
-        // This can provide significant performance improvement for simple rule sets
-        let has_multi_condition_rules = self.rules.values().any(|rule| rule.conditions.len() > 1);
-        if has_multi_condition_rules {
-            // Clear beta network at the start of each batch to ensure clean state
-            // Note: True incremental RETE would maintain state between batches
-            self.beta_network_manager.clear_all_tokens();
+        let created_before = self.created_facts.len();
+        self.chain_conclusions.clear();
+
+        // Batch mode does not store tokens, so beta memories built up by incremental
+        // processing are left untouched rather than cleared on every batch
+
+        // Assert the whole batch into aggregation nodes first so every fact in the
+        // batch is tested against the same aggregate state
+        if first_slice {
+            self.assert_into_aggregation_nodes(facts, fact_store);
+            self.assert_into_window_nodes(facts, fact_store)?;
+            self.assert_into_sequence_nodes(facts, fact_store)?;
         }
 
         // PROPER RETE IMPLEMENTATION: Use alpha memory + beta network
-        for fact in facts {
-            // Process each fact through the complete RETE network
-            let fact_results = self.process_single_fact(fact, fact_store, calculator)?;
-            results.extend(fact_results);
+        let mut evaluated = 0;
+        'chunks: for chunk in facts.chunks(self.alpha_chunk_size(facts.len())) {
+            // Alpha tests of large batches are evaluated on the pool ahead of firing
+            let mut alpha_matches = self.evaluate_alpha_in_parallel(chunk, fact_store);
+            for (index, fact) in chunk.iter().enumerate() {
+                // Process each fact through the complete RETE network
+                let alpha =
+                    alpha_matches.as_mut().map(|matches| std::mem::take(&mut matches[index]));
+                let fact_results = self.process_single_fact(fact, alpha, fact_store, calculator)?;
+                results.extend(fact_results);
+                evaluated += 1;
+                if budget.is_spent(results.len(), started) {
+                    break 'chunks;
+                }
+            }
         }
 
-        Ok(results)
+        if let Some(chaining) = self.forward_chaining {
+            let chained =
+                self.chain_created_facts(chaining, created_before, fact_store, calculator);
+            self.chain_conclusions = HashSet::new();
+            results.extend(chained?);
+        }
+
+        resolve_field_collisions(&mut results, self.field_collision_policy, |rule_id| {
+            self.salience(rule_id)
+        })
+        .map_err(anyhow::Error::msg)?;
+
+        Ok((results, evaluated))
     }
 
-    /// Test if a fact matches all conditions in a rule
-    /// OPTIMIZED: Early termination on first failed condition (short-circuit evaluation)
-    fn fact_matches_all_conditions(
-        &self,
-        fact: &Fact,
-        conditions: &[Condition],
+    /// Assert the facts created since `created_from` and process them like a new batch,
+    /// round after round, until rules stop creating facts
+    fn chain_created_facts(
+        &mut self,
+        chaining: ForwardChaining,
+        mut created_from: usize,
         fact_store: &ArenaFactStore,
-    ) -> Result<bool> {
-        // Early termination optimization: return false immediately on first non-match
-        // This prevents unnecessary condition evaluations and can provide significant speedup
-        for condition in conditions {
-            if !self.test_condition(fact, condition, fact_store)? {
-                return Ok(false); // Short-circuit: no need to check remaining conditions
-            }
+        calculator: &Calculator,
+    ) -> Result<Vec<RuleExecutionResult>> {
+        let mut results = Vec::new();
+        let mut iterations = 0;
+        while created_from < self.created_facts.len() {
+            if iterations == chaining.max_iterations {
+                anyhow::bail!(
+                    "Forward chaining did not settle within {} iterations; {} derived facts are still pending",
+                    chaining.max_iterations,
+                    self.created_facts.len() - created_from
+                );
+            }
+            iterations += 1;
+
+            let derived = self.created_facts[created_from..].to_vec();
+            created_from = self.created_facts.len();
+            debug!(
+                iteration = iterations,
+                facts = derived.len(),
+                "Chaining derived facts"
+            );
+
+            for fact in &derived {
+                fact_store.insert_with_id(fact.clone());
+            }
+            self.assert_into_aggregation_nodes(&derived, fact_store);
+            self.assert_into_window_nodes(&derived, fact_store)?;
+            self.assert_into_sequence_nodes(&derived, fact_store)?;
+            for fact in &derived {
+                results.extend(self.process_single_fact(fact, None, fact_store, calculator)?);
+            }
+        }
+        Ok(results)
+    }
+
+    /// Assert facts created by rule actions back into the network, or stop doing so
+    ///
+    /// With chaining, every batch is followed by rounds processing the facts the
+    /// previous round created until no rule creates another. Created facts get fact
+    /// store IDs so they cannot overwrite asserted facts, and a fact whose data matches
+    /// one already derived during the chain is not created again. A chain still
+    /// creating facts after `max_iterations` rounds fails the batch.
+    pub fn set_forward_chaining(&mut self, chaining: Option<ForwardChaining>) {
+        self.forward_chaining = chaining;
+    }
+
+    /// Forward chaining setting, `None` when created facts are only returned
+    pub fn forward_chaining(&self) -> Option<ForwardChaining> {
+        self.forward_chaining
+    }
+
+    /// Check rule and reference table validity against `evaluation_date` for the facts
+    /// processed from now on
+    pub fn set_evaluation_date(&mut self, evaluation_date: EvaluationDate) {
+        self.evaluation_date = evaluation_date;
+    }
+
+    /// Date rule and reference table validity is checked against
+    pub fn evaluation_date(&self) -> EvaluationDate {
+        self.evaluation_date
+    }
+
+    /// Fire matched rules in rule ID order in working memory processing too, and date
+    /// facts created by actions by the fact that triggered the rule
+    pub fn set_deterministic(&mut self, deterministic: bool) {
+        self.deterministic = deterministic;
+    }
+
+    /// Whether deterministic execution is enabled
+    pub fn is_deterministic(&self) -> bool {
+        self.deterministic
+    }
+
+    /// Resolve rule and reference table validity as of the evaluation date of `fact`
+    fn evaluate_as_of(&mut self, fact: &Fact) {
+        self.as_of = self.evaluation_date.as_of(fact);
+        self.alpha_memory_manager.set_as_of(self.as_of);
+    }
+
+    /// Test if a fact matches all conditions in a rule
+    /// OPTIMIZED: Early termination on first failed condition (short-circuit evaluation)
+    fn fact_matches_all_conditions(
+        &self,
+        fact: &Fact,
+        conditions: &[Condition],
+        fact_store: &ArenaFactStore,
+    ) -> Result<bool> {
+        // Early termination optimization: return false immediately on first non-match
+        // This prevents unnecessary condition evaluations and can provide significant speedup
+        for condition in conditions {
+            if !self.test_condition(fact, condition, fact_store)? {
+                return Ok(false); // Short-circuit: no need to check remaining conditions
+            }
         }
         Ok(true)
     }
@@ -1002,10 +1870,15 @@ impl ReteNetwork {
                 // Use lazy aggregation manager to evaluate aggregation conditions
                 self.evaluate_aggregation_condition(fact, agg_condition, fact_store)
             }
-            Condition::Stream(_) => {
-                // Stream conditions require temporal context and are not supported
-                // in single-fact evaluation context
-                Ok(false)
+            Condition::Stream(stream_condition) => {
+                self.evaluate_stream_condition(fact, stream_condition, fact_store)
+            }
+            Condition::Sequence(sequence_condition) => {
+                Ok(self.evaluate_sequence_condition(fact, sequence_condition))
+            }
+            // Holds once `bind_accumulations` has bound the value to the fact
+            Condition::Accumulate(accumulate) => {
+                Ok(fact.data.fields.contains_key(&accumulate.alias))
             }
         }
     }
@@ -1020,13 +1893,46 @@ impl ReteNetwork {
         _fact_store: &ArenaFactStore,
     ) -> Result<bool> {
         // OPTIMIZATION: Early return for missing fields (common case)
-        let actual_value = match fact.data.fields.get(field) {
+        let actual_value = match fact.condition_value(field) {
             Some(value) => value,
             None => {
-                // Field doesn't exist - only NotEqual can be true
-                return Ok(*operator == Operator::NotEqual);
+                // Field doesn't exist - only NotEqual and NotIn can be true
+                return Ok(matches!(operator, Operator::NotEqual | Operator::NotIn));
             }
         };
+        let actual_value = actual_value.as_ref();
+
+        // Null, e.g. a non-finite float propagated as null, fails every ordering
+        if matches!(actual_value, FactValue::Null)
+            && matches!(
+                operator,
+                Operator::GreaterThan
+                    | Operator::LessThan
+                    | Operator::GreaterThanOrEqual
+                    | Operator::LessThanOrEqual
+            )
+        {
+            return Ok(false);
+        }
+
+        // Regexes are compiled with the rule rather than collated
+        if *operator == Operator::Matches {
+            return Ok(self.alpha_memory_manager.regexes().matches(
+                operator,
+                actual_value,
+                expected_value,
+            ));
+        }
+
+        // Membership values are list names, not strings to collate
+        let is_membership = matches!(operator, Operator::In | Operator::NotIn);
+        if let (FactValue::String(actual), FactValue::String(expected)) =
+            (actual_value, expected_value)
+        {
+            if !self.collation.is_binary() && !is_membership {
+                return Ok(self.collation.matches(operator, actual, expected));
+            }
+        }
 
         match operator {
             Operator::Equal => Ok(actual_value == expected_value),
@@ -1066,6 +1972,19 @@ impl ReteNetwork {
                     Ok(false)
                 }
             }
+            Operator::Within | Operator::OlderThan | Operator::Overlaps => {
+                Ok(operator.matches_temporal(actual_value, expected_value))
+            }
+            Operator::Matches | Operator::ContainsIgnoreCase => Ok(string_match::matches(
+                operator,
+                actual_value,
+                expected_value,
+            )),
+            Operator::In | Operator::NotIn => Ok(self.alpha_memory_manager.value_lists().matches(
+                operator,
+                actual_value,
+                expected_value,
+            )),
         }
     }
 
@@ -1103,6 +2022,14 @@ impl ReteNetwork {
                     Ok(0)
                 }
             }
+            (FactValue::Decimal(_), _)
+            | (_, FactValue::Decimal(_))
+            | (FactValue::Date(_), FactValue::Date(_))
+            | (FactValue::Duration(_), FactValue::Duration(_)) => {
+                a.partial_cmp(b).map(|ordering| ordering as i32).ok_or_else(|| {
+                    anyhow::anyhow!("Cannot compare incompatible types: {:?} and {:?}", a, b)
+                })
+            }
             _ => Err(anyhow::anyhow!(
                 "Cannot compare incompatible types: {:?} and {:?}",
                 a,
@@ -1118,24 +2045,16 @@ impl ReteNetwork {
 
     /// Get candidate rules from alpha memory based on fact field values
     /// This is the core RETE optimization: O(1) lookup instead of O(rules)
-    fn get_candidate_rules_from_alpha_memory(&mut self, fact: &Fact) -> Vec<RuleId> {
+    fn get_candidate_rules_from_alpha_memory(&self, fact: &Fact) -> Vec<RuleId> {
         // OPTIMIZED RETE: Use alpha memory manager's optimized method
         // This will leverage the equality_index and range_index for O(1) lookups
         let mut candidate_rules = self.alpha_memory_manager.get_candidate_rules_for_fact(fact);
 
-        // NON-INDEXABLE RULES: Also include rules with conditions that can't be indexed in alpha memory
-        // This includes aggregation and complex conditions that operate differently
-        for (rule_id, rule) in &self.rules {
-            let has_non_indexable_conditions = rule.conditions.iter().any(|condition| {
-                matches!(
-                    condition,
-                    crate::types::Condition::Aggregation(_)
-                        | crate::types::Condition::Complex { .. }
-                )
-            });
-
-            if has_non_indexable_conditions && !candidate_rules.contains(rule_id) {
-                candidate_rules.push(*rule_id);
+        // NON-INDEXABLE RULES: Also include rules with conditions that can't be indexed in
+        // alpha memory and match the fact's type, such as aggregation and complex conditions
+        for rule_id in self.alpha_memory_manager.unindexed_rules_for(fact) {
+            if !candidate_rules.contains(&rule_id) {
+                candidate_rules.push(rule_id);
                 debug!(
                     "Added non-indexable rule {} as candidate for fact {}",
                     rule_id, fact.id
@@ -1143,9 +2062,79 @@ impl ReteNetwork {
             }
         }
 
+        // Rules fire in ID order whichever index found them
+        candidate_rules.sort_unstable();
         candidate_rules
     }
 
+    /// Start evaluating the alpha tests of large batches on a thread pool, or evaluate
+    /// them fact by fact again with `None` (the default)
+    pub fn set_parallel_alpha(
+        &mut self,
+        config: Option<ParallelAlphaConfig>,
+    ) -> crate::error::BingoResult<()> {
+        self.parallel_alpha = config.map(AlphaPool::new).transpose()?;
+        Ok(())
+    }
+
+    /// Parallel alpha evaluation setting, `None` when alpha tests run fact by fact
+    pub fn parallel_alpha(&self) -> Option<ParallelAlphaConfig> {
+        self.parallel_alpha.as_ref().map(AlphaPool::config)
+    }
+
+    /// Facts of a batch of `fact_count` whose alpha tests are evaluated together
+    fn alpha_chunk_size(&self, fact_count: usize) -> usize {
+        match &self.parallel_alpha {
+            Some(pool) if fact_count >= pool.config().min_batch_size => {
+                pool.config().min_batch_size
+            }
+            _ => fact_count.max(1),
+        }
+    }
+
+    /// Candidate rules of each fact, with the outcome of testing single-condition rules,
+    /// evaluated on the alpha pool when the facts are enough to be worth it
+    ///
+    /// Facts evaluated as of their own event time resolve reference data differently
+    /// from one another and are left to be evaluated one by one.
+    fn evaluate_alpha_in_parallel(
+        &mut self,
+        facts: &[Fact],
+        fact_store: &ArenaFactStore,
+    ) -> Option<Vec<Vec<AlphaMatch>>> {
+        let pool = self.parallel_alpha.as_ref()?;
+        if facts.len() < pool.config().min_batch_size
+            || matches!(self.evaluation_date, EvaluationDate::EventTime)
+        {
+            return None;
+        }
+        let pool = pool.clone();
+        self.evaluate_as_of(&facts[0]);
+
+        let network = &*self;
+        Some(pool.evaluate(facts, |fact| {
+            network
+                .get_candidate_rules_from_alpha_memory(fact)
+                .into_iter()
+                .map(|rule_id| {
+                    let test =
+                        network.rules.get(&rule_id).filter(|rule| rule.conditions.len() == 1).map(
+                            |rule| {
+                                let evaluation_start = Instant::now();
+                                let matched = network.fact_matches_all_conditions(
+                                    fact,
+                                    &rule.conditions,
+                                    fact_store,
+                                );
+                                AlphaTest { matched, elapsed: evaluation_start.elapsed() }
+                            },
+                        );
+                    AlphaMatch { rule_id, test }
+                })
+                .collect()
+        }))
+    }
+
     /// Process a fact through the beta network for multi-condition rules
     ///
     /// ## Beta Network Processing
@@ -1184,20 +2173,19 @@ impl ReteNetwork {
             fact.id, rule_id
         );
 
-        // Create a working memory snapshot for token processing
-        let mut working_facts = HashMap::new();
-        working_facts.insert(fact.id, fact.clone());
-
-        // For existing facts in working memory, add them too (simplified)
-        for (fact_id, existing_fact) in &self.working_memory {
-            working_facts.insert(*fact_id, existing_fact.clone());
-        }
-
         // Check each condition to see if this fact matches any alpha memories
+        let evaluation_start = Instant::now();
         let mut matching_conditions = Vec::new();
         for (index, condition) in conditions.iter().enumerate() {
             if let Some(pattern) = FactPattern::from_condition(condition) {
-                if pattern.matches_fact(fact) {
+                let matched = pattern.matches_fact_with(
+                    fact,
+                    &self.collation,
+                    self.alpha_memory_manager.value_lists(),
+                    self.alpha_memory_manager.regexes(),
+                );
+                self.record_alpha_evaluation(condition, matched);
+                if matched {
                     matching_conditions.push(index);
                     debug!(
                         "Fact {} matches condition {} of rule {}",
@@ -1212,6 +2200,8 @@ impl ReteNetwork {
             // For simplification, if fact matches ALL conditions, execute rule
             // True RETE would build partial matches incrementally
             if matching_conditions.len() == conditions.len() {
+                self.record_rule_evaluation(rule_id, fact.id, evaluation_start.elapsed());
+
                 // Create a complete token for this rule
                 let mut token = Token::new(rule_id);
                 token.facts.push(fact.id);
@@ -1224,13 +2214,13 @@ impl ReteNetwork {
                 // Execute the rule actions
                 if let Some(rule) = self.rules.get(&rule_id) {
                     let rule_clone = rule.clone();
-                    let executed_actions =
-                        self.execute_rule_actions(&rule_clone, fact, fact_store, calculator)?;
-                    results.push(RuleExecutionResult {
-                        rule_id,
-                        fact_id: fact.id,
-                        actions_executed: executed_actions,
-                    });
+                    results.extend(self.fire_rule(
+                        &rule_clone,
+                        fact,
+                        &token.facts,
+                        fact_store,
+                        calculator,
+                    )?);
                 }
             } else {
                 // Partial match - in true RETE this would be stored as a token
@@ -1242,19 +2232,23 @@ impl ReteNetwork {
                 );
 
                 // For now, use the old logic as fallback
-                if self.fact_matches_all_conditions(fact, conditions, fact_store)? {
+                let matched = self.fact_matches_all_conditions(fact, conditions, fact_store)?;
+                self.record_rule_evaluation(rule_id, fact.id, evaluation_start.elapsed());
+                if matched {
                     if let Some(rule) = self.rules.get(&rule_id) {
                         let rule_clone = rule.clone();
-                        let executed_actions =
-                            self.execute_rule_actions(&rule_clone, fact, fact_store, calculator)?;
-                        results.push(RuleExecutionResult {
-                            rule_id,
-                            fact_id: fact.id,
-                            actions_executed: executed_actions,
-                        });
+                        results.extend(self.fire_rule(
+                            &rule_clone,
+                            fact,
+                            &[fact.id],
+                            fact_store,
+                            calculator,
+                        )?);
                     }
                 }
             }
+        } else {
+            self.record_rule_evaluation(rule_id, fact.id, evaluation_start.elapsed());
         }
 
         Ok(results)
@@ -1266,12 +2260,182 @@ impl ReteNetwork {
     // This section contains functions for rule execution, action processing,
     // and fact creation/modification operations.
 
+    /// Fire a rule for a matched fact and record the activation's logical support
+    ///
+    /// The supporting facts are the facts that satisfied the rule's conditions. Any
+    /// facts asserted by the rule's actions are recorded as derived from them so they
+    /// can be withdrawn when the support is retracted. A rule outside its effective
+    /// and expiry dates at the fact's evaluation date, or in a disabled rule group,
+    /// does not fire.
+    fn fire_rule(
+        &mut self,
+        rule: &Rule,
+        fact: &Fact,
+        supporting_facts: &[FactId],
+        fact_store: &ArenaFactStore,
+        calculator: &Calculator,
+    ) -> Result<Option<RuleExecutionResult>> {
+        use crate::rete_nodes::ActionResult;
+
+        if !rule.metadata.is_in_effect(self.as_of) {
+            debug!(rule_id = rule.id, "Rule is not in effect, skipping firing");
+            return Ok(None);
+        }
+        if let Some(group) = &rule.metadata.rule_group
+            && !self.is_rule_group_enabled(group)
+        {
+            debug!(
+                rule_id = rule.id,
+                group, "Rule group is disabled, skipping firing"
+            );
+            return Ok(None);
+        }
+
+        // Spans the firing for tracing layers such as the API's OpenTelemetry export
+        let firing = tracing::debug_span!(
+            target: "bingo::rule_firing",
+            "rule_firing",
+            rule_id = rule.id,
+            fact_ids = ?supporting_facts,
+        );
+        let _firing = firing.enter();
+
+        let actions_executed = self.execute_rule_actions(rule, fact, fact_store, calculator)?;
+        self.rule_counters
+            .entry(rule.id)
+            .or_default()
+            .record_activation(chrono::Utc::now());
+
+        let derived_facts: Vec<FactId> = actions_executed
+            .iter()
+            .filter_map(|result| match result {
+                ActionResult::FactCreated { fact_id, .. } => Some(*fact_id),
+                _ => None,
+            })
+            .collect();
+        self.truth_maintenance
+            .record_activation(rule.id, supporting_facts, &derived_facts);
+        if let Some(transaction) = self.own_transaction() {
+            transaction.derived_facts.extend_from_slice(&derived_facts);
+        }
+
+        let mut result = RuleExecutionResult::for_fact(rule.id, fact, actions_executed);
+        if self.audit_log.is_enabled() {
+            let trace = self.trace_activation(
+                rule,
+                fact,
+                supporting_facts,
+                fact_store,
+                &result.actions_executed,
+            )?;
+            result.result_id = Some(self.audit_log.record(trace));
+        }
+        Ok(Some(result))
+    }
+
+    /// Explanation of `rule` firing for `fact`, for the audit log
+    ///
+    /// Each condition is attributed to the first supporting fact satisfying it, starting
+    /// with `fact` itself, and calculator inputs are resolved again from `fact`.
+    fn trace_activation(
+        &self,
+        rule: &Rule,
+        fact: &Fact,
+        supporting_facts: &[FactId],
+        fact_store: &ArenaFactStore,
+        actions: &[crate::rete_nodes::ActionResult],
+    ) -> Result<ExplanationTrace> {
+        use crate::rete_nodes::ActionResult;
+        use crate::types::ActionType;
+
+        let others: Vec<Fact> = supporting_facts
+            .iter()
+            .filter(|&&id| id != fact.id)
+            .filter_map(|&id| fact_store.get_fact(id))
+            .collect();
+        let mut conditions = Vec::with_capacity(rule.conditions.len());
+        for condition in &rule.conditions {
+            let mut matching = None;
+            for candidate in std::iter::once(fact).chain(&others) {
+                if self.test_condition(candidate, condition, fact_store)? {
+                    matching = Some(candidate);
+                    break;
+                }
+            }
+            let bound_values = matching.map_or_else(Default::default, |matching| {
+                condition_fields(condition)
+                    .into_iter()
+                    .filter_map(|field| {
+                        let value = matching.data.fields.get(&field)?.clone();
+                        Some((field, value))
+                    })
+                    .collect()
+            });
+            conditions.push(ConditionTrace {
+                condition: condition.clone(),
+                fact_id: matching.map(|matching| matching.id),
+                bound_values,
+            });
+        }
+        if let Some(order) = self.condition_orders.get(&rule.id) {
+            let mut declared: Vec<Option<ConditionTrace>> = vec![None; conditions.len()];
+            for (&index, condition) in order.iter().zip(conditions) {
+                declared[index] = Some(condition);
+            }
+            conditions = declared.into_iter().flatten().collect();
+        }
+
+        let calculators = rule
+            .actions
+            .iter()
+            .filter_map(|action| {
+                let ActionType::CallCalculator { calculator_name, input_mapping, output_field } =
+                    &action.action_type
+                else {
+                    return None;
+                };
+                let output = actions.iter().find_map(|result| match result {
+                    ActionResult::CalculatorResult {
+                        calculator,
+                        output_field: field,
+                        parsed_value,
+                        ..
+                    } if calculator == calculator_name && field == output_field => {
+                        Some(parsed_value.clone())
+                    }
+                    _ => None,
+                });
+                Some(CalculatorTrace {
+                    calculator: calculator_name.clone(),
+                    inputs: self
+                        .resolve_calculator_inputs(input_mapping, fact)
+                        .into_iter()
+                        .collect(),
+                    output_field: output_field.clone(),
+                    output,
+                })
+            })
+            .collect();
+
+        Ok(ExplanationTrace {
+            result_id: 0,
+            rule_id: rule.id,
+            rule_name: rule.name.clone(),
+            fact_id: fact.id,
+            external_id: fact.external_id.clone(),
+            conditions,
+            calculators,
+            actions: actions.to_vec(),
+            recorded_at: chrono::Utc::now(),
+        })
+    }
+
     /// Execute rule actions and return the action results
     fn execute_rule_actions(
         &mut self,
         rule: &Rule,
         fact: &Fact,
-        _fact_store: &ArenaFactStore,
+        fact_store: &ArenaFactStore,
         calculator: &Calculator,
     ) -> Result<Vec<crate::rete_nodes::ActionResult>> {
         use crate::types::ActionType;
@@ -1285,22 +2449,114 @@ impl ReteNetwork {
                 ActionType::CreateFact { data } => {
                     create_fact_actions.push(data.clone());
                 }
+                ActionType::IncrementField { field, .. }
+                | ActionType::AppendToArray { field, .. }
+                | ActionType::MergeObject { field, .. } => {
+                    action_results.push(self.execute_mutation(
+                        &action.action_type,
+                        field,
+                        fact,
+                        rule.id,
+                        fact_store,
+                    ));
+                }
                 _ => {
                     let result = self.execute_single_action(action, fact, rule.id, calculator);
-                    action_results.push(result);
+                    action_results.push(self.guard_calculator_result(result)?);
                 }
             }
         }
 
         // Second pass: execute all CreateFact actions in batch
         if !create_fact_actions.is_empty() {
-            let batch_results = self.execute_create_fact_batch(&create_fact_actions, rule.id);
+            let batch_results =
+                self.execute_create_fact_batch(&create_fact_actions, rule.id, fact, fact_store);
             action_results.extend(batch_results);
         }
 
         Ok(action_results)
     }
 
+    /// Apply an `IncrementField`, `AppendToArray` or `MergeObject` action to `fact`
+    ///
+    /// The new value is computed from the stored field and written back while the fact
+    /// store is locked, so rules mutating the same field in one batch build on each
+    /// other's results instead of overwriting them. A fact missing from the store is
+    /// mutated in the returned result only. Fact history records `rule_id` as the
+    /// author of the new version.
+    fn execute_mutation(
+        &self,
+        action: &crate::types::ActionType,
+        field: &str,
+        fact: &Fact,
+        rule_id: RuleId,
+        fact_store: &ArenaFactStore,
+    ) -> crate::rete_nodes::ActionResult {
+        let overflow = self.overflow_policy;
+        let outcome = fact_store
+            .modify_field_by(fact.id, field, Some(rule_id), |current| {
+                mutated_value(action, current, overflow)
+            })
+            .unwrap_or_else(|| {
+                let current = fact.data.fields.get(field);
+                mutated_value(action, current, overflow)
+                    .map(|new_value| (current.cloned(), new_value))
+            });
+        match outcome {
+            Ok((old_value, new_value)) => mutation_result(action, fact.id, old_value, new_value),
+            Err(message) => crate::rete_nodes::ActionResult::Logged { message },
+        }
+    }
+
+    /// Apply the non-finite float policy to a calculator result
+    fn guard_calculator_result(
+        &self,
+        result: crate::rete_nodes::ActionResult,
+    ) -> Result<crate::rete_nodes::ActionResult> {
+        use crate::rete_nodes::ActionResult;
+
+        let ActionResult::CalculatorResult { calculator, output_field, parsed_value, .. } = &result
+        else {
+            return Ok(result);
+        };
+        let guarded = self.non_finite.derive(parsed_value.clone(), |message| {
+            crate::error::BingoError::calculator(
+                calculator,
+                format!("Calculator '{calculator}' returned a {message}"),
+            )
+        })?;
+
+        Ok(match guarded {
+            Some(value) if value == *parsed_value => result,
+            Some(value) => ActionResult::CalculatorResult {
+                calculator: calculator.clone(),
+                result: format!("{value:?}"),
+                output_field: output_field.clone(),
+                parsed_value: value,
+            },
+            None => ActionResult::Logged {
+                message: format!(
+                    "Calculator '{calculator}' returned a non-finite float; result discarded"
+                ),
+            },
+        })
+    }
+
+    /// Apply the non-finite float policy to an aggregate; `None` fails the condition
+    fn guard_aggregate(
+        &self,
+        agg_condition: &crate::types::AggregationCondition,
+        value: FactValue,
+    ) -> Result<Option<FactValue>> {
+        Ok(self.non_finite.derive(value, |message| {
+            crate::error::BingoError::aggregation(
+                &format!("{:?}", agg_condition.aggregation_type),
+                &agg_condition.source_field,
+                format!("Aggregate '{}' is a {message}", agg_condition.alias),
+            )
+        })?)
+    }
+
     /// Execute a single non-batchable action
     fn execute_single_action(
         &mut self,
@@ -1376,76 +2632,6 @@ impl ReteNetwork {
                     }
                 }
             }
-            ActionType::IncrementField { field, increment } => {
-                if let Some(current_value) = fact.data.fields.get(field) {
-                    match (current_value, increment) {
-                        (
-                            crate::types::FactValue::Integer(current),
-                            crate::types::FactValue::Integer(inc),
-                        ) => {
-                            let new_value = crate::types::FactValue::Integer(current + inc);
-                            ActionResult::FieldIncremented {
-                                fact_id: fact.id,
-                                field: field.clone(),
-                                old_value: current_value.clone(),
-                                new_value,
-                            }
-                        }
-                        (
-                            crate::types::FactValue::Float(current),
-                            crate::types::FactValue::Float(inc),
-                        ) => {
-                            let new_value = crate::types::FactValue::Float(current + inc);
-                            ActionResult::FieldIncremented {
-                                fact_id: fact.id,
-                                field: field.clone(),
-                                old_value: current_value.clone(),
-                                new_value,
-                            }
-                        }
-                        _ => ActionResult::Logged {
-                            message: format!(
-                                "IncrementField failed: incompatible types for field '{field}'"
-                            ),
-                        },
-                    }
-                } else {
-                    ActionResult::FieldIncremented {
-                        fact_id: fact.id,
-                        field: field.clone(),
-                        old_value: crate::types::FactValue::Integer(0),
-                        new_value: increment.clone(),
-                    }
-                }
-            }
-            ActionType::AppendToArray { field, value } => {
-                if let Some(current_value) = fact.data.fields.get(field) {
-                    if let crate::types::FactValue::Array(mut current_array) = current_value.clone()
-                    {
-                        current_array.push(value.clone());
-                        let new_length = current_array.len();
-                        ActionResult::ArrayAppended {
-                            fact_id: fact.id,
-                            field: field.clone(),
-                            appended_value: value.clone(),
-                            new_length,
-                        }
-                    } else {
-                        ActionResult::Logged {
-                            message: format!(
-                                "AppendToArray failed: field '{field}' is not an array"
-                            ),
-                        }
-                    }
-                } else {
-                    ActionResult::ArrayAppended {
-                        fact_id: fact.id,
-                        field: field.clone(),
-                        appended_value: value.clone(),
-                        new_length: 1,
-                    }
-                }
-            }
             ActionType::SendNotification {
                 recipient,
                 subject,
@@ -1460,6 +2646,35 @@ impl ReteNetwork {
                     subject: subject.clone(),
                 }
             }
+            ActionType::CallWebhook { endpoint, metadata } => {
+                let payload = WebhookPayload {
+                    endpoint: endpoint.clone(),
+                    rule_id,
+                    rule_name: self
+                        .rules
+                        .get(&rule_id)
+                        .map(|rule| rule.name.clone())
+                        .unwrap_or_default(),
+                    fact: fact.clone(),
+                    metadata: metadata.clone(),
+                    triggered_at: chrono::Utc::now(),
+                };
+                let queued = if self.dry_run {
+                    false
+                } else if let Some(transaction) = self.own_transaction() {
+                    transaction.webhooks.push(payload);
+                    true
+                } else {
+                    self.webhooks.dispatch(payload)
+                };
+                info!(
+                    rule_id = rule_id,
+                    endpoint = endpoint,
+                    queued,
+                    "Rule action: CallWebhook"
+                );
+                ActionResult::WebhookDispatched { endpoint: endpoint.clone(), queued }
+            }
             ActionType::CallCalculator { calculator_name, input_mapping, output_field } => self
                 .execute_calculator_action(
                     calculator_name,
@@ -1469,6 +2684,22 @@ impl ReteNetwork {
                     rule_id,
                     calculator,
                 ),
+            ActionType::Formula { expression, output_field } => {
+                match crate::rete_nodes::evaluate_formula_expression_with_overflow(
+                    expression,
+                    &fact.data.fields,
+                    self.overflow_policy,
+                ) {
+                    Ok(value) => ActionResult::FieldSet {
+                        fact_id: fact.id,
+                        field: output_field.clone(),
+                        value,
+                    },
+                    Err(error) => ActionResult::Logged {
+                        message: format!("Formula '{expression}' failed: {error}"),
+                    },
+                }
+            }
             _ => ActionResult::Logged {
                 message: format!("Action type not yet implemented: {:?}", action.action_type),
             },
@@ -1480,27 +2711,48 @@ impl ReteNetwork {
         &mut self,
         fact_data_list: &[crate::types::FactData],
         rule_id: RuleId,
+        trigger: &Fact,
+        fact_store: &ArenaFactStore,
     ) -> Vec<crate::rete_nodes::ActionResult> {
         use crate::rete_nodes::ActionResult;
         use tracing::info;
 
+        // A chain only asserts each conclusion once, which stops rules re-deriving it
+        let chaining = self.forward_chaining.is_some();
+        let fact_data_list: Vec<&crate::types::FactData> = fact_data_list
+            .iter()
+            .filter(|data| !chaining || self.chain_conclusions.insert(canonical_fact_data(data)))
+            .collect();
+
         let mut results = Vec::with_capacity(fact_data_list.len());
 
         // Pre-allocate fact IDs to avoid repeated ID generation
         let start_id = self.next_node_id;
-        self.next_node_id += fact_data_list.len() as u64;
+        if !chaining {
+            self.next_node_id += fact_data_list.len() as u64;
+        }
 
-        // Create all facts in batch
+        // Create all facts in batch; chained facts are stored, so they take store IDs
+        let timestamp = if self.deterministic {
+            trigger.timestamp
+        } else {
+            chrono::Utc::now()
+        };
         let new_facts: Vec<crate::types::Fact> = fact_data_list
             .iter()
             .enumerate()
             .map(|(i, data)| {
-                let fact_id = start_id + i as u64;
+                let fact_id = if chaining {
+                    fact_store.allocate_id()
+                } else {
+                    start_id + i as u64
+                };
                 crate::types::Fact {
-                    timestamp: chrono::Utc::now(),
+                    timestamp,
                     id: fact_id,
                     external_id: None,
-                    data: data.clone(),
+                    fact_type: None,
+                    data: (*data).clone(),
                 }
             })
             .collect();
@@ -1539,11 +2791,17 @@ impl ReteNetwork {
 
         // The calculator will handle non-existent calculators and return an error
 
-        // Create cache key from calculator name and input values
-        let cache_key = self.generate_calculator_cache_key(calculator_name, input_mapping, fact);
+        // Prepare inputs for calculator by mapping fact fields to calculator parameters;
+        // reference table rows are resolved first so a reloaded table never hits a stale
+        // entry
+        let resolved = self.resolve_calculator_inputs(input_mapping, fact);
+        let cacheable =
+            self.calculator_cache.is_enabled() && calculator.is_deterministic(calculator_name);
 
         // Check cache first
-        if let Some(cached_result) = self.calculator_cache.get(&cache_key) {
+        if cacheable
+            && let Some(cached_result) = self.calculator_cache.get(calculator_name, &resolved)
+        {
             info!(
                 rule_id = rule_id,
                 calculator_name = calculator_name,
@@ -1554,18 +2812,13 @@ impl ReteNetwork {
                 calculator: calculator_name.to_string(),
                 result: format!("{cached_result:?}"),
                 output_field: output_field.to_string(),
-                parsed_value: cached_result.clone(),
+                parsed_value: cached_result,
             };
         }
 
         // Cache miss - need to execute calculator
-        // Prepare inputs for calculator by mapping fact fields to calculator parameters
-        let mut calculator_inputs = std::collections::HashMap::new();
-        for (calc_param, fact_field) in input_mapping {
-            if let Some(field_value) = fact.data.fields.get(fact_field) {
-                calculator_inputs.insert(calc_param.clone(), field_value);
-            }
-        }
+        let calculator_inputs: std::collections::HashMap<String, &FactValue> =
+            resolved.iter().map(|(param, value)| (param.clone(), value)).collect();
 
         // Call the real calculator
         let calculator_result = match calculator.calculate(calculator_name, &calculator_inputs) {
@@ -1584,7 +2837,9 @@ impl ReteNetwork {
         };
 
         // Store result in cache
-        self.calculator_cache.insert(cache_key, calculator_result.clone());
+        if cacheable {
+            self.calculator_cache.put(calculator_name, &resolved, calculator_result.clone());
+        }
 
         info!(
             rule_id = rule_id,
@@ -1607,28 +2862,27 @@ impl ReteNetwork {
         }
     }
 
-    /// Generate a cache key for calculator results based on inputs
-    fn generate_calculator_cache_key(
+    /// Calculator inputs for a fact: mapped fact fields, the reference table row keyed by
+    /// a fact field for mappings written `table[field]`, or a global for `@name`
+    fn resolve_calculator_inputs(
         &self,
-        calculator_name: &str,
         input_mapping: &std::collections::HashMap<String, String>,
         fact: &Fact,
-    ) -> String {
-        let mut key_parts = vec![calculator_name.to_string()];
-
-        // Add sorted input values to ensure consistent cache keys
-        let mut sorted_inputs: Vec<_> = input_mapping.iter().collect();
-        sorted_inputs.sort_by(|a, b| a.0.cmp(b.0));
-
-        for (calc_input, fact_field) in sorted_inputs {
-            if let Some(field_value) = fact.data.fields.get(fact_field) {
-                key_parts.push(format!("{calc_input}={field_value:?}"));
-            } else {
-                key_parts.push(format!("{calc_input}=null"));
-            }
-        }
-
-        key_parts.join("|")
+    ) -> HashMap<String, FactValue> {
+        input_mapping
+            .iter()
+            .filter_map(|(calc_param, mapping)| {
+                let value = if let Some(name) = parse_global_reference(mapping) {
+                    self.reference_data.global(name)?
+                } else if let Some((table, field)) = parse_table_reference(mapping) {
+                    let key = fact.data.fields.get(field)?;
+                    self.reference_data.lookup_as_of(table, key, self.as_of)?
+                } else {
+                    fact.data.fields.get(mapping)?.clone()
+                };
+                Some((calc_param.clone(), value))
+            })
+            .collect()
     }
 
     /// Clear the calculator cache (useful for testing or when inputs change significantly)
@@ -1636,12 +2890,20 @@ impl ReteNetwork {
         self.calculator_cache.clear();
     }
 
-    /// Get calculator cache statistics
-    pub fn get_calculator_cache_stats(&self) -> (usize, usize) {
-        (
-            self.calculator_cache.len(),
-            self.calculator_cache.capacity(),
-        )
+    /// Resize the calculator cache or change how long its results live, dropping the
+    /// results cached so far
+    pub fn set_calculator_cache(&mut self, config: CalculatorCacheConfig) {
+        self.calculator_cache = CalculatorCache::new(config);
+    }
+
+    /// Size and lifetime of the calculator cache
+    pub fn calculator_cache(&self) -> CalculatorCacheConfig {
+        self.calculator_cache.config()
+    }
+
+    /// Hits, misses, evictions and expirations of the calculator cache
+    pub fn calculator_cache_stats(&self) -> CacheStats {
+        self.calculator_cache.stats()
     }
 
     // ============================================================================
@@ -1656,25 +2918,76 @@ impl ReteNetwork {
         rule_id: RuleId,
         condition: &Condition,
     ) -> Result<()> {
-        if let Condition::Simple { field, operator, value } = condition {
-            let key = format!("{field}_{operator:?}_{value:?}");
-
-            // Create alpha node if it doesn't exist
-            if !self.alpha_nodes.contains_key(&key) {
+        if let Condition::Aggregation(agg_condition) = condition {
+            let key = AggregationNode::signature(agg_condition);
+            if !self.aggregation_nodes.contains_key(&key) {
                 let node_id = self.next_node_id;
                 self.next_node_id += 1;
-                let alpha_node = AlphaNode::new(node_id, condition.clone());
-                self.alpha_nodes.insert(key.clone(), alpha_node);
+                let mut node = AggregationNode::new(node_id, agg_condition.clone());
+                if let Some(calendar) = self.aggregation_calendar(agg_condition)? {
+                    node.set_calendar(calendar);
+                }
+                node.set_late_data_policy(self.late_data_policy);
+                self.aggregation_nodes.insert(key.clone(), node);
+                debug!("Created aggregation node {} for rule {}", node_id, rule_id);
             }
-
-            // Associate the rule with this alpha node
-            if let Some(alpha_node) = self.alpha_nodes.get_mut(&key) {
-                alpha_node.add_rule(rule_id);
+            if let Some(node) = self.aggregation_nodes.get_mut(&key) {
+                node.add_rule(rule_id);
             }
+            return Ok(());
+        }
 
-            // Create or get alpha memory for this pattern
-            if let Some(pattern) = FactPattern::from_condition(condition) {
-                let alpha_memory =
+        if let Condition::Stream(stream_condition) = condition {
+            let key = WindowNode::signature(stream_condition);
+            if !self.window_nodes.contains_key(&key) {
+                let node_id = self.next_node_id;
+                self.next_node_id += 1;
+                let mut node = WindowNode::new(node_id, stream_condition.clone());
+                node.set_late_data_policy(self.late_data_policy);
+                self.window_nodes.insert(key.clone(), node);
+                debug!("Created window node {} for rule {}", node_id, rule_id);
+            }
+            if let Some(node) = self.window_nodes.get_mut(&key) {
+                node.add_rule(rule_id);
+            }
+            return Ok(());
+        }
+
+        if let Condition::Sequence(sequence_condition) = condition {
+            let key = SequenceNode::signature(sequence_condition);
+            if !self.sequence_nodes.contains_key(&key) {
+                let node_id = self.next_node_id;
+                self.next_node_id += 1;
+                let mut node = SequenceNode::new(node_id, sequence_condition.clone());
+                node.set_late_data_policy(self.late_data_policy);
+                self.sequence_nodes.insert(key.clone(), node);
+                debug!("Created sequence node {} for rule {}", node_id, rule_id);
+            }
+            if let Some(node) = self.sequence_nodes.get_mut(&key) {
+                node.add_rule(rule_id);
+            }
+            return Ok(());
+        }
+
+        if let Condition::Simple { field, operator, value } = condition {
+            let key = format!("{field}_{operator:?}_{value:?}");
+
+            // Create alpha node if it doesn't exist
+            if !self.alpha_nodes.contains_key(&key) {
+                let node_id = self.next_node_id;
+                self.next_node_id += 1;
+                let alpha_node = AlphaNode::new(node_id, condition.clone());
+                self.alpha_nodes.insert(key.clone(), alpha_node);
+            }
+
+            // Associate the rule with this alpha node
+            if let Some(alpha_node) = self.alpha_nodes.get_mut(&key) {
+                alpha_node.add_rule(rule_id);
+            }
+
+            // Create or get alpha memory for this pattern
+            if let Some(pattern) = FactPattern::from_condition(condition) {
+                let alpha_memory =
                     self.alpha_memory_manager.get_or_create_alpha_memory(pattern.clone());
                 alpha_memory.add_dependent_rule(rule_id);
 
@@ -1738,124 +3051,1659 @@ impl ReteNetwork {
             }
         }
 
-        // Create terminal node
-        let terminal_node_id = self.beta_network_manager.create_terminal_node(rule.id);
-        self.beta_network_manager.connect_nodes(current_node_id, terminal_node_id);
+        // Create terminal node
+        let terminal_node_id = self.beta_network_manager.create_terminal_node(rule.id);
+        self.beta_network_manager.connect_nodes(current_node_id, terminal_node_id);
+
+        debug!(
+            "Beta network created for rule {} with terminal node {}",
+            rule.id, terminal_node_id
+        );
+
+        Ok(())
+    }
+
+    /// Add join tests for cross-fact pattern matching
+    fn add_join_tests_for_condition(
+        &mut self,
+        _join_node_id: NodeId,
+        rule: &Rule,
+        condition_index: usize,
+    ) -> Result<()> {
+        // Cross-fact join logic implementation
+        // Analyzes conditions to find variable bindings and creates appropriate join tests
+
+        // Example: if we have conditions like:
+        // 1. order.customer_id = ?customer_id
+        // 2. customer.id = ?customer_id
+        // We create a join test to ensure order.customer_id == customer.id
+
+        debug!(
+            "Adding join tests for condition {} in rule {}",
+            condition_index, rule.id
+        );
+
+        Ok(())
+    }
+
+    /// Count an evaluation of a rule's top-level simple condition on its alpha node
+    fn record_alpha_evaluation(&mut self, condition: &Condition, matched: bool) {
+        if let Condition::Simple { field, operator, value } = condition {
+            let key = format!("{field}_{operator:?}_{value:?}");
+            if let Some(alpha_node) = self.alpha_nodes.get_mut(&key) {
+                alpha_node.record_evaluation(matched);
+            }
+        }
+    }
+
+    /// Evaluation counts of every alpha node, in node creation order
+    pub fn condition_stats(&self) -> Vec<ConditionEvaluationStats> {
+        let mut stats: Vec<ConditionEvaluationStats> = self
+            .alpha_nodes
+            .values()
+            .filter_map(ConditionEvaluationStats::from_alpha_node)
+            .collect();
+        stats.sort_by_key(|stats| stats.node_id);
+        stats
+    }
+
+    /// Count an evaluation of a rule's conditions against a fact that took `elapsed`
+    fn record_rule_evaluation(
+        &mut self,
+        rule_id: RuleId,
+        fact_id: FactId,
+        elapsed: std::time::Duration,
+    ) {
+        self.rule_counters.entry(rule_id).or_default().record_evaluation(elapsed);
+        if let Some(profiler) = &mut self.working_memory_profiler {
+            profiler.record_evaluation(fact_id, self.rules.get(&rule_id));
+        }
+    }
+
+    /// Start sampling hot facts and fields, or stop and drop the samples with `None`
+    ///
+    /// Enabling the profiler while it runs restarts it with the new configuration.
+    pub fn set_working_memory_profiler(&mut self, config: Option<WorkingMemoryProfilerConfig>) {
+        self.working_memory_profiler = config.map(WorkingMemoryProfiler::new);
+    }
+
+    /// Working memory profiler configuration, `None` while profiling is disabled
+    pub fn working_memory_profiler(&self) -> Option<WorkingMemoryProfilerConfig> {
+        self.working_memory_profiler.as_ref().map(WorkingMemoryProfiler::config)
+    }
+
+    /// Hottest facts and fields sampled so far, `None` while profiling is disabled
+    pub fn working_memory_profile(
+        &self,
+        fact_store: &ArenaFactStore,
+    ) -> Option<WorkingMemoryProfile> {
+        self.working_memory_profiler
+            .as_ref()
+            .map(|profiler| profiler.profile(fact_store))
+    }
+
+    /// Hit counts of every rule in the network, in rule ID order
+    pub fn rule_stats(&self) -> Vec<RuleStats> {
+        let mut stats: Vec<RuleStats> = self
+            .rules
+            .values()
+            .map(|rule| {
+                RuleStats::new(
+                    rule,
+                    self.rule_counters.get(&rule.id).copied().unwrap_or_default(),
+                )
+            })
+            .collect();
+        stats.sort_by_key(|stats| stats.rule_id);
+        stats
+    }
+
+    /// Reset the hit counts of every rule
+    pub fn reset_rule_stats(&mut self) {
+        self.rule_counters.clear();
+    }
+
+    /// Feed the selectivity observed on alpha nodes to the rule optimizer
+    ///
+    /// Rules compiled afterwards order their conditions by the observed match rates
+    /// instead of heuristic estimates. Returns the number of conditions that had been
+    /// evaluated and so had a selectivity to report.
+    pub fn apply_selectivity_feedback(&mut self) -> usize {
+        let mut applied = 0;
+        for stats in self.condition_stats() {
+            if stats.evaluations > 0 {
+                self.rule_optimizer.record_observed_selectivity(
+                    stats.pattern_key(),
+                    stats.evaluations,
+                    stats.matches,
+                );
+                applied += 1;
+            }
+        }
+        debug!(
+            conditions = applied,
+            "Applied observed condition selectivity"
+        );
+        applied
+    }
+
+    /// Record the selectivity of `rule`'s conditions on indexed fields of `fact_store`
+    ///
+    /// Used when `rule` is compiled next. Returns the number of conditions with index
+    /// statistics.
+    pub fn record_index_statistics(&mut self, rule: &Rule, fact_store: &ArenaFactStore) -> usize {
+        self.rule_optimizer.record_index_statistics(rule, fact_store)
+    }
+
+    /// Get statistics about the network
+    pub fn get_stats(&self) -> NetworkStats {
+        let node_count = self.alpha_nodes.len()
+            + self.beta_nodes.len()
+            + self.terminal_nodes.len()
+            + self.aggregation_nodes.len()
+            + self.window_nodes.len()
+            + self.sequence_nodes.len();
+
+        NetworkStats {
+            node_count: node_count as u64,
+            memory_usage_bytes: self.memory_breakdown().network_bytes() as u64,
+        }
+    }
+
+    /// Memory held by working memory, node memories, nodes and rules
+    ///
+    /// Only the network components of the breakdown are filled in.
+    pub fn memory_breakdown(&self) -> MemoryBreakdown {
+        let working_memory = hash_map_table_bytes(&self.working_memory)
+            + self
+                .working_memory
+                .values()
+                // Arc header holds the strong and weak counts
+                .map(|fact| 2 * std::mem::size_of::<usize>() + fact_bytes(fact))
+                .sum::<usize>()
+            + self.created_facts.iter().map(fact_bytes).sum::<usize>();
+
+        let rule_bytes: usize = self.rules.values().map(rule_definition_bytes).sum();
+        let network_nodes = std::mem::size_of::<Self>()
+            + hash_map_table_bytes(&self.alpha_nodes)
+            + self.alpha_nodes.keys().map(String::capacity).sum::<usize>()
+            + hash_map_table_bytes(&self.beta_nodes)
+            + hash_map_table_bytes(&self.terminal_nodes)
+            + hash_map_table_bytes(&self.rules)
+            + rule_bytes
+            + hash_map_table_bytes(&self.condition_orders)
+            + self
+                .condition_orders
+                .values()
+                .map(|order| order.capacity() * std::mem::size_of::<usize>())
+                .sum::<usize>();
+
+        let aggregation_memory: usize =
+            self.aggregation_nodes.values().map(aggregation_node_bytes).sum();
+        let window_memory: usize = self.window_nodes.values().map(window_node_bytes).sum();
+        let sequence_memory: usize = self.sequence_nodes.values().map(sequence_node_bytes).sum();
+        let calculator_cache_memory = self.calculator_cache.memory_bytes();
+
+        MemoryBreakdown {
+            working_memory,
+            alpha_memories: self.alpha_memory_manager.estimate_memory_usage(),
+            beta_memories: self.beta_network_manager.estimate_memory_usage(),
+            network_nodes,
+            aggregation_state: aggregation_memory
+                + window_memory
+                + sequence_memory
+                + calculator_cache_memory,
+            ..Default::default()
+        }
+    }
+
+    /// Network memory attributed to each rule, in rule ID order
+    ///
+    /// Alpha memories, beta memories and aggregation nodes shared between rules are
+    /// split evenly among them.
+    pub fn rule_memory_usage(&self) -> Vec<RuleMemoryUsage> {
+        let mut usage: BTreeMap<RuleId, RuleMemoryUsage> = self
+            .rules
+            .values()
+            .map(|rule| {
+                let usage = RuleMemoryUsage {
+                    rule_id: rule.id,
+                    name: rule.name.clone(),
+                    definition_bytes: rule_definition_bytes(rule),
+                    ..Default::default()
+                };
+                (rule.id, usage)
+            })
+            .collect();
+
+        for (rule_id, bytes) in self.alpha_memory_manager.memory_by_rule() {
+            if let Some(rule) = usage.get_mut(&rule_id) {
+                rule.alpha_bytes += bytes;
+            }
+        }
+        for memory in self.beta_memory_usage() {
+            for rule_id in &memory.rule_ids {
+                if let Some(rule) = usage.get_mut(rule_id) {
+                    rule.beta_bytes += memory.bytes / memory.rule_ids.len();
+                }
+            }
+        }
+        let aggregations = self
+            .aggregation_nodes
+            .values()
+            .map(|node| (aggregation_node_bytes(node), &node.dependent_rules))
+            .chain(
+                self.window_nodes
+                    .values()
+                    .map(|node| (window_node_bytes(node), &node.dependent_rules)),
+            )
+            .chain(
+                self.sequence_nodes
+                    .values()
+                    .map(|node| (sequence_node_bytes(node), &node.dependent_rules)),
+            );
+        for (bytes, dependent_rules) in aggregations {
+            for rule_id in dependent_rules {
+                if let Some(rule) = usage.get_mut(rule_id) {
+                    rule.aggregation_bytes += bytes / dependent_rules.len();
+                }
+            }
+        }
+        usage.into_values().collect()
+    }
+
+    /// Tokens held across the beta memories
+    pub fn token_count(&self) -> usize {
+        self.beta_network_manager.token_count()
+    }
+
+    /// Size of each beta node's token memory
+    pub fn beta_memory_usage(&self) -> Vec<BetaMemoryUsage> {
+        self.beta_network_manager.memory_usage()
+    }
+
+    /// Facts in every alpha node's memory and tokens in every beta memory, in node ID
+    /// order
+    ///
+    /// Memories are filled by incremental processing through
+    /// [`add_fact_to_working_memory`](Self::add_fact_to_working_memory).
+    pub fn memory_contents(&self) -> NetworkMemoryContents {
+        let memories: HashMap<String, &AlphaMemory> = self
+            .alpha_memory_manager
+            .alpha_memories()
+            .map(|memory| (memory.pattern.to_key(), memory))
+            .collect();
+        let mut alpha: Vec<AlphaMemoryContents> = self
+            .alpha_nodes
+            .iter()
+            .map(|(key, node)| {
+                let mut fact_ids: Vec<FactId> = memories
+                    .get(key)
+                    .map(|memory| memory.matching_facts.iter().copied().collect())
+                    .unwrap_or_default();
+                fact_ids.sort_unstable();
+                AlphaMemoryContents {
+                    node_id: node.id,
+                    condition: ConditionEvaluationStats::from_alpha_node(node)
+                        .map_or_else(|| key.clone(), |stats| stats.to_string()),
+                    fact_ids,
+                }
+            })
+            .collect();
+        alpha.sort_by_key(|contents| contents.node_id);
+
+        let mut rule_ids: HashMap<NodeId, Vec<RuleId>> = self
+            .beta_memory_usage()
+            .into_iter()
+            .map(|usage| (usage.node_id, usage.rule_ids))
+            .collect();
+        let mut beta: Vec<BetaMemoryContents> = self
+            .beta_network_manager
+            .beta_memories
+            .iter()
+            .map(|(&node_id, memory)| {
+                let mut tokens: Vec<Vec<FactId>> =
+                    memory.tokens.values().map(|token| token.facts.clone()).collect();
+                tokens.sort_unstable();
+                BetaMemoryContents {
+                    node_id,
+                    rule_ids: rule_ids.remove(&node_id).unwrap_or_default(),
+                    tokens,
+                }
+            })
+            .collect();
+        beta.sort_by_key(|contents| contents.node_id);
+
+        NetworkMemoryContents { alpha, beta }
+    }
+
+    /// Graphviz DOT description of the compiled network
+    ///
+    /// Alpha nodes are labelled with their condition, the facts their alpha memory holds
+    /// and their evaluation counts, join nodes with the condition they join and the tokens in their beta
+    /// memory, and terminal nodes with their rule. Edges follow facts from the alpha
+    /// network through the joins to the rules they activate.
+    pub fn export_dot(&self) -> String {
+        use std::fmt::Write;
+
+        let mut dot = String::from("digraph ReteNetwork {\n  rankdir=LR;\n  node [fontsize=10];\n");
+        let mut write_node = |id: &str, shape: &str, label: &str| {
+            let _ = writeln!(
+                dot,
+                "  \"{}\" [shape={shape}, label=\"{}\"];",
+                dot_escape(id),
+                dot_escape(label).replace('\n', "\\n")
+            );
+        };
+
+        // Alpha nodes, each drawn together with the alpha memory of its pattern
+        let memories: HashMap<String, &AlphaMemory> = self
+            .alpha_memory_manager
+            .alpha_memories()
+            .map(|memory| (memory.pattern.to_key(), memory))
+            .collect();
+        let mut alpha_nodes: Vec<(&String, &AlphaNode)> = self.alpha_nodes.iter().collect();
+        alpha_nodes.sort_by_key(|(_, node)| node.id);
+        let mut memory_nodes: HashMap<NodeId, String> = HashMap::new();
+        for (key, node) in &alpha_nodes {
+            let id = format!("alpha:{}", node.id);
+            let condition = ConditionEvaluationStats::from_alpha_node(node)
+                .map_or_else(|| key.to_string(), |stats| stats.to_string());
+            let facts = memories.get(*key).map_or(0, |memory| memory.count());
+            let label = format!(
+                "{condition}\nfacts: {facts} | matched: {}/{}",
+                node.matches, node.evaluations
+            );
+            write_node(&id, "ellipse", &label);
+            if let Some(memory) = memories.get(*key) {
+                memory_nodes.insert(memory.id, id);
+            }
+        }
+
+        let mut aggregations: Vec<(String, &str, usize, &Vec<RuleId>)> = self
+            .aggregation_nodes
+            .values()
+            .map(|node| {
+                let id = format!("aggregation:{}", node.id);
+                (
+                    id,
+                    "aggregation",
+                    aggregation_node_bytes(node),
+                    &node.dependent_rules,
+                )
+            })
+            .chain(self.window_nodes.values().map(|node| {
+                let id = format!("window:{}", node.id);
+                (id, "window", window_node_bytes(node), &node.dependent_rules)
+            }))
+            .chain(self.sequence_nodes.values().map(|node| {
+                let id = format!("sequence:{}", node.id);
+                (
+                    id,
+                    "sequence",
+                    sequence_node_bytes(node),
+                    &node.dependent_rules,
+                )
+            }))
+            .collect();
+        aggregations.sort();
+        for (id, kind, bytes, _) in &aggregations {
+            write_node(id, "hexagon", &format!("{kind}\nbytes: {bytes}"));
+        }
+
+        // Beta network: the root, join nodes and the edges between them
+        let beta = &self.beta_network_manager;
+        let beta_usage: HashMap<NodeId, BetaMemoryUsage> = self
+            .beta_memory_usage()
+            .into_iter()
+            .map(|usage| (usage.node_id, usage))
+            .collect();
+        let mut beta_nodes: Vec<&beta_network::BetaNode> = beta
+            .beta_nodes
+            .values()
+            .chain(beta.join_nodes.values().map(|join| &join.beta_node))
+            .collect();
+        beta_nodes.sort_by_key(|node| node.id);
+        let beta_id = |node: &beta_network::BetaNode| match node.node_type {
+            BetaNodeType::Terminal { rule_id } => format!("rule:{rule_id}"),
+            _ => format!("beta:{}", node.id),
+        };
+        for node in &beta_nodes {
+            let label = match node.node_type {
+                BetaNodeType::Root => "root".to_string(),
+                BetaNodeType::Join { condition_index, .. } => {
+                    let usage = beta_usage.get(&node.id);
+                    format!(
+                        "join condition {condition_index}\ntokens: {} | bytes: {}",
+                        usage.map_or(0, |usage| usage.tokens),
+                        usage.map_or(0, |usage| usage.bytes)
+                    )
+                }
+                BetaNodeType::Terminal { .. } => continue,
+            };
+            write_node(&beta_id(node), "box", &label);
+        }
+
+        // Terminal nodes, one per rule
+        let mut terminals: Vec<&TerminalNode> = self.terminal_nodes.values().collect();
+        terminals.sort_by_key(|node| node.rule_id);
+        for terminal in &terminals {
+            let name = self.rules.get(&terminal.rule_id).map_or("", |rule| rule.name.as_str());
+            let label = format!(
+                "rule {}: {name}\nactions: {}",
+                terminal.rule_id,
+                terminal.actions.len()
+            );
+            write_node(
+                &format!("rule:{}", terminal.rule_id),
+                "doubleoctagon",
+                &label,
+            );
+        }
+
+        let mut write_edge = |from: &str, to: &str, label: Option<&str>| {
+            let label = label.map(|label| format!(" [label=\"{label}\"]")).unwrap_or_default();
+            let _ = writeln!(
+                dot,
+                "  \"{}\" -> \"{}\"{label};",
+                dot_escape(from),
+                dot_escape(to)
+            );
+        };
+        // Rules without a beta network are activated by their alpha nodes directly
+        for (_, node) in &alpha_nodes {
+            for rule_id in &node.rule_ids {
+                if !beta.terminal_nodes.contains_key(rule_id) {
+                    write_edge(
+                        &format!("alpha:{}", node.id),
+                        &format!("rule:{rule_id}"),
+                        None,
+                    );
+                }
+            }
+        }
+        for (id, _, _, rule_ids) in &aggregations {
+            for rule_id in *rule_ids {
+                write_edge(id, &format!("rule:{rule_id}"), None);
+            }
+        }
+        for node in &beta_nodes {
+            if let BetaNodeType::Join { alpha_memory_id, .. } = node.node_type {
+                if let Some(alpha) = memory_nodes.get(&alpha_memory_id) {
+                    write_edge(alpha, &beta_id(node), Some("right"));
+                }
+            }
+            for child in &node.children {
+                let child = beta
+                    .beta_nodes
+                    .get(child)
+                    .or_else(|| beta.join_nodes.get(child).map(|join| &join.beta_node));
+                if let Some(child) = child {
+                    write_edge(&beta_id(node), &beta_id(child), None);
+                }
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Remove a rule from the network
+    pub fn remove_rule(&mut self, rule_id: RuleId) -> Result<()> {
+        // Remove from rules map
+        self.rules.remove(&rule_id);
+        self.condition_orders.remove(&rule_id);
+
+        // Remove terminal node
+        self.terminal_nodes.remove(&rule_id);
+
+        // Stop routing facts to alpha memories for the rule
+        self.alpha_memory_manager.unroute_rule(rule_id);
+
+        // Drop aggregation nodes no other rule depends on
+        self.aggregation_nodes.retain(|_, node| node.remove_rule(rule_id));
+        self.window_nodes.retain(|_, node| node.remove_rule(rule_id));
+        self.sequence_nodes.retain(|_, node| node.remove_rule(rule_id));
+
+        // Note: Alpha/beta node cleanup could be implemented for memory optimization
+        // but is not required for correctness in this stateless architecture
+        Ok(())
+    }
+
+    /// Get created facts
+    /// In the stateless architecture, facts are processed immediately
+    pub fn get_created_facts(&self) -> &[crate::types::Fact] {
+        // Facts are processed immediately in this stateless implementation
+        &[]
+    }
+
+    /// Clear created facts
+    /// In the stateless architecture, this is a no-op
+    pub fn clear_created_facts(&mut self) {
+        // No-op in stateless implementation - facts are processed immediately
+    }
+
+    /// Get action result pool statistics (simplified)
+    pub fn get_action_result_pool_stats(&self) -> (usize, usize) {
+        // Return (pool_size, active_items) - simplified implementation
+        (0, 0)
+    }
+
+    /// Get comprehensive memory pool statistics
+    pub fn get_memory_pool_stats(&self) -> crate::memory_pools::MemoryPoolStats {
+        self.memory_pools.get_comprehensive_stats()
+    }
+
+    /// Get memory pool efficiency percentage
+    pub fn get_memory_pool_efficiency(&self) -> f64 {
+        self.memory_pools.overall_efficiency()
+    }
+
+    /// Get lazy aggregation statistics
+    pub fn get_lazy_aggregation_stats(
+        &self,
+    ) -> crate::lazy_aggregation::LazyAggregationManagerStats {
+        self.lazy_aggregation_manager.get_stats()
+    }
+
+    /// Invalidate all lazy aggregation caches (when fact store changes)
+    pub fn invalidate_lazy_aggregation_caches(&self) {
+        self.lazy_aggregation_manager.invalidate_all_caches();
+    }
+
+    /// Clean up inactive lazy aggregations to free memory
+    pub fn cleanup_lazy_aggregations(&self) {
+        self.lazy_aggregation_manager.cleanup_inactive_aggregations();
+    }
+
+    /// Number of aggregation nodes compiled into the network
+    pub fn aggregation_node_count(&self) -> usize {
+        self.aggregation_nodes.len()
+    }
+
+    /// Get the aggregation node compiled for a condition, if any
+    pub fn aggregation_node(
+        &self,
+        agg_condition: &crate::types::AggregationCondition,
+    ) -> Option<&AggregationNode> {
+        self.aggregation_nodes.get(&AggregationNode::signature(agg_condition))
+    }
+
+    /// Register a period calendar for calendar aggregation windows
+    ///
+    /// Replacing a calendar re-partitions the aggregation nodes that use it.
+    pub fn register_calendar(&mut self, calendar: PeriodCalendar) {
+        let calendar = Arc::new(calendar);
+        for node in self.aggregation_nodes.values_mut() {
+            if node.calendar().is_some_and(|current| current.name() == calendar.name()) {
+                node.set_calendar(calendar.clone());
+            }
+        }
+        info!(
+            calendar = calendar.name(),
+            periods = calendar.periods().len(),
+            "Registered period calendar"
+        );
+        self.calendars.insert(calendar.name().to_string(), calendar);
+    }
+
+    /// Reference tables rules can refer to by name
+    ///
+    /// Registering a table through the returned store, or any clone of it, takes effect
+    /// for rules already in the network.
+    pub fn reference_data(&self) -> &ReferenceDataStore {
+        &self.reference_data
+    }
+
+    /// Webhook endpoints and workers for webhook actions
+    ///
+    /// Registering an endpoint through the returned dispatcher, or any clone of it,
+    /// takes effect for rules already in the network.
+    pub fn webhooks(&self) -> &WebhookDispatcher {
+        &self.webhooks
+    }
+
+    /// Compiled literals and reference tables `In` and `NotIn` conditions resolve against
+    pub fn value_lists(&self) -> &ValueLists {
+        self.alpha_memory_manager.value_lists()
+    }
+
+    /// Collation simple conditions compare strings under
+    pub fn collation(&self) -> &Collation {
+        &self.collation
+    }
+
+    /// Change how simple conditions compare strings
+    pub fn set_collation(&mut self, collation: Collation) {
+        self.alpha_memory_manager.set_collation(collation.clone());
+        self.collation = collation;
+    }
+
+    /// Policy for NaN and infinite floats in facts and derived values
+    pub fn non_finite_policy(&self) -> NonFinitePolicy {
+        self.non_finite.policy()
+    }
+
+    /// Change what happens to NaN and infinite floats
+    pub fn set_non_finite_policy(&mut self, policy: NonFinitePolicy) {
+        self.non_finite.set_policy(policy);
+    }
+
+    /// Counts of the non-finite floats met so far
+    pub fn non_finite_stats(&self) -> NonFiniteStats {
+        self.non_finite.stats()
+    }
+
+    /// Apply the non-finite float policy to facts about to be processed
+    ///
+    /// Rejected facts are removed from `facts`; under [`NonFinitePolicy::Error`] an
+    /// offending fact fails the whole batch and `facts` is left as it was.
+    pub fn ingest_non_finite(&self, facts: &mut Vec<Fact>) -> crate::error::BingoResult<()> {
+        self.non_finite.ingest(facts)
+    }
+
+    /// Enable the audit log with `Some`, or stop recording with `None` (the default)
+    ///
+    /// While enabled, every rule that fires records an [`ExplanationTrace`] and its
+    /// result carries the trace's `result_id`. Only the most recent `capacity` traces
+    /// are kept, and disabling the log drops them all.
+    pub fn set_audit_log(&mut self, config: Option<AuditLogConfig>) {
+        self.audit_log.configure(config);
+    }
+
+    /// Audit log setting, `None` when no traces are recorded
+    pub fn audit_log(&self) -> Option<AuditLogConfig> {
+        self.audit_log.config()
+    }
+
+    /// Explanation trace of a rule execution result, if it is still retained
+    pub fn explain(&self, result_id: ResultId) -> Option<ExplanationTrace> {
+        self.audit_log.get(result_id)
+    }
+
+    /// Choose what integer arithmetic in actions produces when it overflows `i64`
+    ///
+    /// Applies to `IncrementField` and to `+`, `-` and `*` in formulas. Under
+    /// [`OverflowPolicy::Error`] (the default) an overflowing action leaves its field
+    /// unchanged and its action result reports the overflow.
+    pub fn set_overflow_policy(&mut self, policy: OverflowPolicy) {
+        self.overflow_policy = policy;
+    }
+
+    /// The policy applied to overflowing integer arithmetic in actions
+    pub fn overflow_policy(&self) -> OverflowPolicy {
+        self.overflow_policy
+    }
+
+    /// Choose what stream conditions, sequence conditions and event-time aggregation
+    /// windows do with events that arrive behind the watermark
+    ///
+    /// Applies to existing window, sequence and aggregation nodes as well as ones
+    /// compiled later.
+    pub fn set_late_data_policy(&mut self, policy: LateDataPolicy) {
+        self.late_data_policy = policy;
+        for node in self.aggregation_nodes.values_mut() {
+            node.set_late_data_policy(policy);
+        }
+        for node in self.window_nodes.values_mut() {
+            node.set_late_data_policy(policy);
+        }
+        for node in self.sequence_nodes.values_mut() {
+            node.set_late_data_policy(policy);
+        }
+    }
+
+    /// The policy applied to events that arrive behind the watermark
+    pub fn late_data_policy(&self) -> LateDataPolicy {
+        self.late_data_policy
+    }
+
+    /// Choose what happens when rules set the same field of a fact to different values
+    /// in one batch
+    pub fn set_field_collision_policy(&mut self, policy: FieldCollisionPolicy) {
+        self.field_collision_policy = policy;
+    }
+
+    /// The policy applied to colliding `SetField` writes
+    pub fn field_collision_policy(&self) -> FieldCollisionPolicy {
+        self.field_collision_policy
+    }
+
+    /// Choose what adding a rule that duplicates an existing one does
+    pub fn set_duplicate_rule_policy(&mut self, policy: DuplicateRulePolicy) {
+        self.duplicate_rule_policy = policy;
+    }
+
+    /// The policy applied to rules duplicating existing ones
+    pub fn duplicate_rule_policy(&self) -> DuplicateRulePolicy {
+        self.duplicate_rule_policy
+    }
+
+    /// Register the type of a fact field that rule actions are checked against
+    pub fn register_field_type(&mut self, field: impl Into<String>, field_type: FieldType) {
+        self.field_schema.insert(field.into(), field_type);
+    }
+
+    /// Types of the fact fields registered so far
+    pub fn field_schema(&self) -> &FieldSchema {
+        &self.field_schema
+    }
+
+    /// Choose what adding a rule whose actions fail validation does
+    pub fn set_action_validation_policy(&mut self, policy: ActionValidationPolicy) {
+        self.action_validation_policy = policy;
+    }
+
+    /// The policy applied to rules whose actions fail validation
+    pub fn action_validation_policy(&self) -> ActionValidationPolicy {
+        self.action_validation_policy
+    }
+
+    /// Choose what adding a rule that closes a dependency cycle does
+    pub fn set_rule_cycle_policy(&mut self, policy: RuleCyclePolicy) {
+        self.rule_cycle_policy = policy;
+    }
+
+    /// The policy applied to rules closing a dependency cycle
+    pub fn rule_cycle_policy(&self) -> RuleCyclePolicy {
+        self.rule_cycle_policy
+    }
+
+    /// Set the precedence of a rule's writes under
+    /// [`FieldCollisionPolicy::HighestSalience`]
+    pub fn set_salience(&mut self, rule_id: RuleId, salience: i32) {
+        self.rule_salience.insert(rule_id, salience);
+    }
+
+    /// Salience of a rule, 0 unless set
+    pub fn salience(&self, rule_id: RuleId) -> i32 {
+        self.rule_salience.get(&rule_id).copied().unwrap_or(0)
+    }
+
+    /// Salience of every rule it was set for
+    pub fn saliences(&self) -> &HashMap<RuleId, i32> {
+        &self.rule_salience
+    }
+
+    /// Enable or disable the rules of a rule group; groups are enabled by default
+    pub fn set_rule_group_enabled(&mut self, group: impl Into<String>, enabled: bool) {
+        let group = group.into();
+        if enabled {
+            self.disabled_rule_groups.remove(&group);
+        } else {
+            self.disabled_rule_groups.insert(group);
+        }
+    }
+
+    /// Whether rules in `group` fire
+    pub fn is_rule_group_enabled(&self, group: &str) -> bool {
+        !self.disabled_rule_groups.contains(group)
+    }
+
+    /// Rule groups whose rules don't fire
+    pub fn disabled_rule_groups(&self) -> &BTreeSet<String> {
+        &self.disabled_rule_groups
+    }
+
+    /// Empty network that keeps the registered calendars, reference data, field types,
+    /// webhook endpoints, audit log, optimizer statistics, rule hit counts, disabled rule
+    /// groups, non-finite float, integer overflow, late data, duplicate rule and action
+    /// validation policies, calculator cache size and forward chaining setting
+    ///
+    /// Used to recompile a ruleset after rules are changed or removed without losing
+    /// reference data the rules depend on or observed condition selectivity.
+    pub fn without_rules(&self) -> Self {
+        let mut network = Self::new();
+        network.forward_chaining = self.forward_chaining;
+        network.parallel_alpha = self.parallel_alpha.clone();
+        network.deterministic = self.deterministic;
+        network.calendars = self.calendars.clone();
+        network.rule_optimizer = self.rule_optimizer.clone();
+        network.set_collation(self.collation.clone());
+        network.reference_data = self.reference_data.clone();
+        network.alpha_memory_manager.set_reference_data(self.reference_data.clone());
+        network.webhooks = self.webhooks.clone();
+        network.non_finite = self.non_finite.clone();
+        network.overflow_policy = self.overflow_policy;
+        network.late_data_policy = self.late_data_policy;
+        network.field_collision_policy = self.field_collision_policy;
+        network.rule_salience = self.rule_salience.clone();
+        network.disabled_rule_groups = self.disabled_rule_groups.clone();
+        network.duplicate_rule_policy = self.duplicate_rule_policy;
+        network.field_schema = self.field_schema.clone();
+        network.action_validation_policy = self.action_validation_policy;
+        network.rule_cycle_policy = self.rule_cycle_policy;
+        network.audit_log = self.audit_log.clone();
+        network.calculator_cache = CalculatorCache::new(self.calculator_cache.config());
+        network.rule_counters = self.rule_counters.clone();
+        network.working_memory_profiler = self.working_memory_profiler.clone();
+        network.dry_run = self.dry_run;
+        network
+    }
+
+    /// Node layout of the compiled network, for a precompiled ruleset artifact
+    pub(crate) fn to_compiled(&self) -> CompiledNetwork {
+        let mut interner = ConditionInterner::default();
+        let mut compiled_nodes = |nodes: Vec<(NodeId, Condition, Vec<RuleId>)>| {
+            let mut nodes: Vec<CompiledNode> = nodes
+                .into_iter()
+                .map(|(id, condition, rule_ids)| CompiledNode {
+                    id,
+                    condition: interner.intern(&condition),
+                    rule_ids,
+                })
+                .collect();
+            nodes.sort_by_key(|node| node.id);
+            nodes
+        };
+
+        let alpha_nodes = compiled_nodes(
+            self.alpha_nodes
+                .values()
+                .map(|node| (node.id, node.condition.clone(), node.rule_ids.clone()))
+                .collect(),
+        );
+        let alpha_memories = compiled_nodes(
+            self.alpha_memory_manager
+                .alpha_memories()
+                .map(|memory| {
+                    let mut rule_ids: Vec<RuleId> =
+                        memory.dependent_rules.iter().copied().collect();
+                    rule_ids.sort_unstable();
+                    (memory.id, memory.pattern.to_condition(), rule_ids)
+                })
+                .collect(),
+        );
+        let aggregation_nodes = compiled_nodes(
+            self.aggregation_nodes
+                .values()
+                .map(|node| {
+                    let condition = Condition::Aggregation(node.condition.clone());
+                    (node.id, condition, node.dependent_rules.clone())
+                })
+                .collect(),
+        );
+        let window_nodes = compiled_nodes(
+            self.window_nodes
+                .values()
+                .map(|node| {
+                    let condition = Condition::Stream(node.condition.clone());
+                    (node.id, condition, node.dependent_rules.clone())
+                })
+                .collect(),
+        );
+        let sequence_nodes = compiled_nodes(
+            self.sequence_nodes
+                .values()
+                .map(|node| {
+                    let condition = Condition::Sequence(node.condition.clone());
+                    (node.id, condition, node.dependent_rules.clone())
+                })
+                .collect(),
+        );
+
+        let beta = &self.beta_network_manager;
+        let mut beta_nodes: Vec<CompiledBetaNode> = beta
+            .beta_nodes
+            .values()
+            .chain(beta.join_nodes.values().map(|join_node| &join_node.beta_node))
+            .map(|node| CompiledBetaNode {
+                id: node.id,
+                node_type: node.node_type.clone(),
+                parent: node.parent,
+                children: node.children.clone(),
+            })
+            .collect();
+        beta_nodes.sort_by_key(|node| node.id);
+
+        let mut rules: Vec<Rule> = self.rules.values().cloned().collect();
+        rules.sort_by_key(|rule| rule.id);
+        let mut condition_orders: Vec<(RuleId, Vec<usize>)> =
+            self.condition_orders.iter().map(|(&id, order)| (id, order.clone())).collect();
+        condition_orders.sort_by_key(|(rule_id, _)| *rule_id);
+        let mut terminal_nodes: Vec<(RuleId, NodeId)> =
+            self.terminal_nodes.values().map(|node| (node.rule_id, node.id)).collect();
+        terminal_nodes.sort_unstable();
+        let (literals, next_literal) = self.alpha_memory_manager.compiled_value_lists();
+
+        CompiledNetwork {
+            rules,
+            condition_orders,
+            conditions: interner.into_conditions(),
+            alpha_nodes,
+            alpha_memories,
+            aggregation_nodes,
+            window_nodes,
+            sequence_nodes,
+            terminal_nodes,
+            beta_nodes,
+            beta_root: beta.root_node_id,
+            literals,
+            next_literal,
+            next_node_id: self.next_node_id,
+            next_beta_node_id: beta.next_node_id,
+        }
+    }
+
+    /// Install the nodes of a compiled network into this network, which has no rules
+    ///
+    /// Rules are not optimised or validated again, but the calendars, reference tables,
+    /// globals and webhooks they refer to must be registered with this network.
+    pub(crate) fn install_compiled(&mut self, compiled: &CompiledNetwork) -> Result<()> {
+        self.alpha_memory_manager
+            .restore_value_lists(&compiled.literals, compiled.next_literal);
+        let mut regexes = CompiledRegexes::default();
+        for rule in &compiled.rules {
+            for condition in &rule.conditions {
+                if let Condition::Aggregation(agg_condition) = condition {
+                    self.aggregation_calendar(agg_condition)?;
+                }
+                self.validate_value_lists(condition)?;
+                regexes.compile(condition)?;
+            }
+            self.validate_calculator_inputs(rule)?;
+            self.validate_webhooks(rule)?;
+        }
+        self.alpha_memory_manager.add_regexes(regexes);
+
+        for node in &compiled.alpha_nodes {
+            let condition = compiled.condition(node.condition)?;
+            let Condition::Simple { field, operator, value } = condition else {
+                anyhow::bail!("Alpha node {} does not test a simple condition", node.id);
+            };
+            let mut alpha_node = AlphaNode::new(node.id, condition.clone());
+            alpha_node.rule_ids = node.rule_ids.clone();
+            self.alpha_nodes.insert(format!("{field}_{operator:?}_{value:?}"), alpha_node);
+        }
+        for node in &compiled.alpha_memories {
+            let pattern = FactPattern::from_condition(compiled.condition(node.condition)?)
+                .ok_or_else(|| {
+                    anyhow::anyhow!("Alpha memory {} does not test a simple condition", node.id)
+                })?;
+            self.alpha_memory_manager.restore_alpha_memory(node.id, pattern, &node.rule_ids);
+        }
+        for node in &compiled.aggregation_nodes {
+            let Condition::Aggregation(agg_condition) = compiled.condition(node.condition)? else {
+                anyhow::bail!("Aggregation node {} does not test an aggregation", node.id);
+            };
+            let mut aggregation_node = AggregationNode::new(node.id, agg_condition.clone());
+            if let Some(calendar) = self.aggregation_calendar(agg_condition)? {
+                aggregation_node.set_calendar(calendar);
+            }
+            aggregation_node.set_late_data_policy(self.late_data_policy);
+            aggregation_node.dependent_rules = node.rule_ids.clone();
+            self.aggregation_nodes
+                .insert(AggregationNode::signature(agg_condition), aggregation_node);
+        }
+        for node in &compiled.window_nodes {
+            let Condition::Stream(stream_condition) = compiled.condition(node.condition)? else {
+                anyhow::bail!("Window node {} does not test a stream condition", node.id);
+            };
+            let mut window_node = WindowNode::new(node.id, stream_condition.clone());
+            window_node.set_late_data_policy(self.late_data_policy);
+            window_node.dependent_rules = node.rule_ids.clone();
+            self.window_nodes.insert(WindowNode::signature(stream_condition), window_node);
+        }
+        for node in &compiled.sequence_nodes {
+            let Condition::Sequence(sequence_condition) = compiled.condition(node.condition)?
+            else {
+                anyhow::bail!(
+                    "Sequence node {} does not test a sequence condition",
+                    node.id
+                );
+            };
+            let mut sequence_node = SequenceNode::new(node.id, sequence_condition.clone());
+            sequence_node.set_late_data_policy(self.late_data_policy);
+            sequence_node.dependent_rules = node.rule_ids.clone();
+            self.sequence_nodes
+                .insert(SequenceNode::signature(sequence_condition), sequence_node);
+        }
+
+        let rules: HashMap<RuleId, Rule> =
+            compiled.rules.iter().map(|rule| (rule.id, rule.clone())).collect();
+        for &(rule_id, node_id) in &compiled.terminal_nodes {
+            let rule = rules.get(&rule_id).ok_or_else(|| {
+                anyhow::anyhow!("Terminal node {node_id} refers to unknown rule {rule_id}")
+            })?;
+            self.terminal_nodes.insert(
+                rule_id,
+                TerminalNode::new(node_id, rule_id, rule.actions.clone()),
+            );
+        }
+
+        let beta = &mut self.beta_network_manager;
+        for node in &compiled.beta_nodes {
+            let mut beta_node = beta_network::BetaNode::new(node.id, node.node_type.clone());
+            beta_node.parent = node.parent;
+            beta_node.children = node.children.clone();
+            match node.node_type {
+                BetaNodeType::Join { alpha_memory_id, condition_index } => {
+                    let mut join_node =
+                        beta_network::JoinNode::new(node.id, alpha_memory_id, condition_index);
+                    join_node.beta_node = beta_node;
+                    beta.join_nodes.insert(node.id, join_node);
+                }
+                BetaNodeType::Terminal { rule_id } => {
+                    beta.terminal_nodes.insert(rule_id, node.id);
+                    beta.beta_nodes.insert(node.id, beta_node);
+                }
+                BetaNodeType::Root => {
+                    beta.beta_nodes.insert(node.id, beta_node);
+                }
+            }
+            beta.beta_memories.insert(node.id, beta_network::BetaMemory::new());
+        }
+        beta.root_node_id = compiled.beta_root;
+        beta.next_node_id = compiled.next_beta_node_id;
+
+        self.condition_orders = compiled.condition_orders.iter().cloned().collect();
+        for rule in rules.values() {
+            self.alpha_memory_manager.route_rule(rule);
+        }
+        self.rules = rules;
+        self.next_node_id = compiled.next_node_id;
+        Ok(())
+    }
+
+    /// Empty network with this network's settings that evaluates rules without side
+    /// effects: webhook actions are not dispatched, firings are not written to the audit
+    /// log and rule hit counts start empty
+    pub(crate) fn dry_run(&self) -> Self {
+        let mut network = self.without_rules();
+        network.dry_run = true;
+        network.audit_log = AuditLog::default();
+        network.rule_counters.clear();
+        network.working_memory_profiler = None;
+        network
+    }
+
+    /// Stop or resume dispatching webhook actions, keeping the network's rules and
+    /// memories
+    pub(crate) fn set_dry_run(&mut self, dry_run: bool) {
+        self.dry_run = dry_run;
+    }
+
+    /// Hold back webhook actions fired from now on by the calling thread, and record
+    /// what it changes in the network until the transaction is released or rolled back
+    ///
+    /// Other threads keep dispatching their webhook actions meanwhile.
+    pub(crate) fn hold_transaction(&mut self) {
+        self.transaction = Some(TransactionHold {
+            owner: std::thread::current().id(),
+            webhooks: Vec::new(),
+            working_memory: HashMap::new(),
+            derived_facts: Vec::new(),
+        });
+        self.truth_maintenance.begin_journal();
+    }
+
+    /// Dispatch the webhook actions held for a transaction that applied, keeping its
+    /// changes
+    pub(crate) fn release_transaction(&mut self) {
+        self.truth_maintenance.end_journal();
+        let held = self.transaction.take().map(|transaction| transaction.webhooks);
+        for payload in held.unwrap_or_default() {
+            self.webhooks.dispatch(payload);
+        }
+    }
+
+    /// Undo what a rolled back transaction changed in the network, dropping its held
+    /// webhook actions
+    ///
+    /// `undone` lists the facts the fact store put back. Facts the transaction inserted
+    /// are withdrawn from every memory and the facts it derived are dropped, its
+    /// changes to logical support are undone, and the earlier versions of the facts it
+    /// deleted or modified go back into aggregation nodes and, when they were in
+    /// working memory, into working memory, alpha memories and beta tokens. Nothing is
+    /// fired: the facts' matches were activated before the transaction began.
+    pub(crate) fn roll_back_transaction(
+        &mut self,
+        undone: &UndoneChanges,
+        fact_store: &ArenaFactStore,
+    ) -> Result<()> {
+        let Some(transaction) = self.transaction.take() else {
+            return Ok(());
+        };
+        if let Some(journal) = self.truth_maintenance.end_journal() {
+            self.truth_maintenance.undo(journal);
+        }
+
+        for &fact_id in &undone.removed {
+            self.remove_fact_from_working_memory(fact_id)?;
+        }
+        let withdrawn: HashSet<FactId> =
+            undone.removed.iter().chain(&transaction.derived_facts).copied().collect();
+        self.created_facts.retain(|fact| !withdrawn.contains(&fact.id));
+
+        for fact in &undone.restored {
+            for node in self.aggregation_nodes.values_mut() {
+                node.retract_fact(fact.id);
+            }
+        }
+        self.assert_into_aggregation_nodes(&undone.restored, fact_store);
+        self.assert_into_aggregation_nodes(&undone.reinstated, fact_store);
+        self.assert_into_window_nodes(&undone.reinstated, fact_store)?;
+        self.assert_into_sequence_nodes(&undone.reinstated, fact_store)?;
+
+        let mut earlier: HashMap<FactId, Fact> = transaction
+            .working_memory
+            .into_iter()
+            .filter(|(fact_id, _)| !withdrawn.contains(fact_id))
+            .map(|(fact_id, fact)| (fact_id, Fact::clone(&fact)))
+            .collect();
+        for fact in undone.reinstated.iter().chain(&undone.restored) {
+            if earlier.contains_key(&fact.id) || self.working_memory.contains_key(&fact.id) {
+                earlier.insert(fact.id, fact.clone());
+            }
+        }
+        let mut earlier: Vec<Fact> = earlier.into_values().collect();
+        earlier.sort_unstable_by_key(|fact| fact.id);
+        for fact in earlier {
+            self.reinstate_in_working_memory(fact, fact_store)?;
+        }
+
+        self.invalidate_lazy_aggregation_caches();
+        Ok(())
+    }
+
+    /// Put `fact` back into working memory, alpha memories and the beta tokens of its
+    /// complete matches, marking the matches as already activated
+    fn reinstate_in_working_memory(
+        &mut self,
+        fact: Fact,
+        fact_store: &ArenaFactStore,
+    ) -> Result<()> {
+        let fact_id = fact.id;
+        if self.working_memory.contains_key(&fact_id) {
+            self.beta_network_manager.retract_tokens_containing_fact(fact_id);
+            self.alpha_memory_manager.process_fact_removal(fact_id);
+        }
+        let fact = Arc::new(fact);
+        self.working_memory.insert(fact_id, Arc::clone(&fact));
+
+        let mut rule_ids = Vec::new();
+        for pattern_key in self.alpha_memory_manager.process_fact_addition(fact_id, &fact) {
+            if let Some(alpha_memory) =
+                self.alpha_memory_manager.get_alpha_memory_by_key(&pattern_key)
+            {
+                for rule_id in &alpha_memory.dependent_rules {
+                    if !rule_ids.contains(rule_id) {
+                        rule_ids.push(*rule_id);
+                    }
+                }
+            }
+        }
+        for rule_id in rule_ids {
+            let Some(rule) = self.rules.get(&rule_id).cloned() else {
+                continue;
+            };
+            if rule.conditions.len() < 2 {
+                continue;
+            }
+            for token in self.create_or_extend_tokens_for_fact(rule_id, &fact, &rule, fact_store)? {
+                if token.is_complete(&rule) {
+                    self.beta_network_manager.activate_terminal(token);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The transaction hold, if the calling thread is committing the transaction
+    fn own_transaction(&mut self) -> Option<&mut TransactionHold> {
+        let owner = std::thread::current().id();
+        self.transaction.as_mut().filter(|transaction| transaction.owner == owner)
+    }
 
-        debug!(
-            "Beta network created for rule {} with terminal node {}",
-            rule.id, terminal_node_id
-        );
+    /// Get a registered period calendar by name
+    pub fn calendar(&self, name: &str) -> Option<&Arc<PeriodCalendar>> {
+        self.calendars.get(name)
+    }
 
-        Ok(())
+    /// Calendar referenced by an aggregation condition's window, if it has one
+    fn aggregation_calendar(
+        &self,
+        agg_condition: &crate::types::AggregationCondition,
+    ) -> Result<Option<Arc<PeriodCalendar>>> {
+        match &agg_condition.window {
+            Some(crate::types::AggregationWindow::Calendar { calendar }) => self
+                .calendars
+                .get(calendar)
+                .cloned()
+                .map(Some)
+                .ok_or_else(|| anyhow::anyhow!("Calendar '{calendar}' is not registered")),
+            _ => Ok(None),
+        }
     }
 
-    /// Add join tests for cross-fact pattern matching
-    fn add_join_tests_for_condition(
+    /// Number of window nodes compiled into the network
+    pub fn window_node_count(&self) -> usize {
+        self.window_nodes.len()
+    }
+
+    /// Get the window node compiled for a stream condition, if any
+    pub fn window_node(
+        &self,
+        stream_condition: &crate::types::StreamCondition,
+    ) -> Option<&WindowNode> {
+        self.window_nodes.get(&WindowNode::signature(stream_condition))
+    }
+
+    /// Assert facts into every window node, seeding nodes built after facts arrived
+    ///
+    /// Only facts passing a stream condition's filter enter its windows. Seeds leave out
+    /// the batch itself, so its facts can't advance the watermark and close windows
+    /// before the batch is asserted.
+    fn assert_into_window_nodes(
         &mut self,
-        _join_node_id: NodeId,
-        rule: &Rule,
-        condition_index: usize,
+        facts: &[Fact],
+        fact_store: &ArenaFactStore,
     ) -> Result<()> {
-        // Cross-fact join logic implementation
-        // Analyzes conditions to find variable bindings and creates appropriate join tests
-
-        // Example: if we have conditions like:
-        // 1. order.customer_id = ?customer_id
-        // 2. customer.id = ?customer_id
-        // We create a join test to ensure order.customer_id == customer.id
+        if self.window_nodes.is_empty() {
+            return Ok(());
+        }
 
-        debug!(
-            "Adding join tests for condition {} in rule {}",
-            condition_index, rule.id
-        );
+        let mut existing_facts = None;
+        let keys: Vec<String> = self.window_nodes.keys().cloned().collect();
+        for key in keys {
+            let node = &self.window_nodes[&key];
+            let seed = if node.is_seeded() {
+                None
+            } else {
+                let existing = existing_facts.get_or_insert_with(|| {
+                    let batch: std::collections::HashSet<FactId> =
+                        facts.iter().map(|fact| fact.id).collect();
+                    let mut existing = fact_store.iter();
+                    existing.retain(|fact| !batch.contains(&fact.id));
+                    existing
+                });
+                Some(self.filter_stream_facts(&node.condition, existing.iter(), fact_store)?)
+            };
+            let batch = self.filter_stream_facts(&node.condition, facts.iter(), fact_store)?;
 
+            let node = self.window_nodes.get_mut(&key).expect("window node key was just listed");
+            if let Some(seed) = seed {
+                node.seed(seed)?;
+            }
+            node.assert_batch(batch)?;
+        }
         Ok(())
     }
 
-    /// Get statistics about the network
-    pub fn get_stats(&self) -> NetworkStats {
-        // Calculate approximate memory usage
-        let node_memory =
-            (self.alpha_nodes.len() + self.beta_nodes.len() + self.terminal_nodes.len()) * 64; // ~64 bytes per node
-        let rule_memory = self.rules.len() * 256; // ~256 bytes per rule
-        let base_memory = 1024; // Base RETE network overhead
+    /// Facts that pass a stream condition's filter
+    fn filter_stream_facts<'a>(
+        &self,
+        stream_condition: &crate::types::StreamCondition,
+        facts: impl Iterator<Item = &'a Fact>,
+        fact_store: &ArenaFactStore,
+    ) -> Result<Vec<&'a Fact>> {
+        let Some(filter) = &stream_condition.filter else {
+            return Ok(facts.collect());
+        };
 
-        NetworkStats {
-            node_count: (self.alpha_nodes.len() + self.beta_nodes.len() + self.terminal_nodes.len())
-                as u64,
-            memory_usage_bytes: (base_memory + node_memory + rule_memory) as u64,
+        let mut passing = Vec::new();
+        for fact in facts {
+            if self.test_condition(fact, filter, fact_store)? {
+                passing.push(fact);
+            }
         }
+        Ok(passing)
     }
 
-    /// Remove a rule from the network
-    pub fn remove_rule(&mut self, rule_id: RuleId) -> Result<()> {
-        // Remove from rules map
-        self.rules.remove(&rule_id);
+    /// Evaluate a stream condition against the windows holding the trigger fact
+    ///
+    /// Stream conditions nested inside composite conditions have no window node of
+    /// their own and never match.
+    fn evaluate_stream_condition(
+        &self,
+        trigger_fact: &Fact,
+        stream_condition: &crate::types::StreamCondition,
+        fact_store: &ArenaFactStore,
+    ) -> Result<bool> {
+        let Some(node) = self.window_node(stream_condition) else {
+            debug!(
+                "Stream condition without a window node for fact {} -> returning false",
+                trigger_fact.id
+            );
+            return Ok(false);
+        };
 
-        // Remove terminal node
-        self.terminal_nodes.remove(&rule_id);
+        let values = node.values_for(trigger_fact.id);
+        let Some(having_condition) = &stream_condition.having else {
+            return Ok(!values.is_empty());
+        };
 
-        // Note: Alpha/beta node cleanup could be implemented for memory optimization
-        // but is not required for correctness in this stateless architecture
-        Ok(())
+        // Evaluate the having clause against a synthetic fact holding each window's aggregate
+        for value in values {
+            let mut synthetic_fields = HashMap::new();
+            synthetic_fields.insert(stream_condition.alias.clone(), value.clone());
+            let synthetic_fact = Fact::new(0, crate::types::FactData { fields: synthetic_fields });
+            if self.test_condition(&synthetic_fact, having_condition, fact_store)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
     }
 
-    /// Get created facts
-    /// In the stateless architecture, facts are processed immediately
-    pub fn get_created_facts(&self) -> &[crate::types::Fact] {
-        // Facts are processed immediately in this stateless implementation
-        &[]
+    /// Number of sequence nodes compiled into the network
+    pub fn sequence_node_count(&self) -> usize {
+        self.sequence_nodes.len()
     }
 
-    /// Clear created facts
-    /// In the stateless architecture, this is a no-op
-    pub fn clear_created_facts(&mut self) {
-        // No-op in stateless implementation - facts are processed immediately
+    /// Get the sequence node compiled for a sequence condition, if any
+    pub fn sequence_node(
+        &self,
+        sequence_condition: &crate::types::SequenceCondition,
+    ) -> Option<&SequenceNode> {
+        self.sequence_nodes.get(&SequenceNode::signature(sequence_condition))
     }
 
-    /// Get action result pool statistics (simplified)
-    pub fn get_action_result_pool_stats(&self) -> (usize, usize) {
-        // Return (pool_size, active_items) - simplified implementation
-        (0, 0)
+    /// Assert facts into every sequence node, seeding nodes built after facts arrived
+    ///
+    /// Each fact is tested against the steps of the sequence and handed to the node with
+    /// the steps it passes. Seeds leave out the batch itself, as for window nodes.
+    fn assert_into_sequence_nodes(
+        &mut self,
+        facts: &[Fact],
+        fact_store: &ArenaFactStore,
+    ) -> Result<()> {
+        if self.sequence_nodes.is_empty() {
+            return Ok(());
+        }
+
+        let mut existing_facts = None;
+        let keys: Vec<String> = self.sequence_nodes.keys().cloned().collect();
+        for key in keys {
+            let node = &self.sequence_nodes[&key];
+            let seed = if node.is_seeded() {
+                None
+            } else {
+                let existing = existing_facts.get_or_insert_with(|| {
+                    let batch: std::collections::HashSet<FactId> =
+                        facts.iter().map(|fact| fact.id).collect();
+                    let mut existing = fact_store.iter();
+                    existing.retain(|fact| !batch.contains(&fact.id));
+                    existing
+                });
+                Some(self.sequence_steps(&node.condition, existing.iter(), fact_store)?)
+            };
+            let batch = self.sequence_steps(&node.condition, facts.iter(), fact_store)?;
+
+            let node =
+                self.sequence_nodes.get_mut(&key).expect("sequence node key was just listed");
+            if let Some(seed) = seed {
+                node.seed(seed);
+            }
+            node.assert_batch(batch);
+        }
+        Ok(())
     }
 
-    /// Get comprehensive memory pool statistics
-    pub fn get_memory_pool_stats(&self) -> crate::memory_pools::MemoryPoolStats {
-        self.memory_pools.get_comprehensive_stats()
+    /// Facts passing at least one step of a sequence, with the steps each passes
+    fn sequence_steps<'a>(
+        &self,
+        sequence_condition: &crate::types::SequenceCondition,
+        facts: impl Iterator<Item = &'a Fact>,
+        fact_store: &ArenaFactStore,
+    ) -> Result<Vec<(&'a Fact, Vec<usize>)>> {
+        let mut passing = Vec::new();
+        for fact in facts {
+            let mut steps = Vec::new();
+            for (index, step) in sequence_condition.steps.iter().enumerate() {
+                if self.test_condition(fact, step, fact_store)? {
+                    steps.push(index);
+                }
+            }
+            if !steps.is_empty() {
+                passing.push((fact, steps));
+            }
+        }
+        Ok(passing)
     }
 
-    /// Get memory pool efficiency percentage
-    pub fn get_memory_pool_efficiency(&self) -> f64 {
-        self.memory_pools.overall_efficiency()
+    /// Whether the trigger fact completes a sequence condition
+    ///
+    /// Sequence conditions nested inside composite conditions have no sequence node of
+    /// their own and never match.
+    fn evaluate_sequence_condition(
+        &self,
+        trigger_fact: &Fact,
+        sequence_condition: &crate::types::SequenceCondition,
+    ) -> bool {
+        self.sequence_node(sequence_condition)
+            .is_some_and(|node| node.completes(trigger_fact.id))
     }
 
-    /// Get lazy aggregation statistics
-    pub fn get_lazy_aggregation_stats(
+    /// The trigger fact with the value of every accumulate condition of the rule bound
+    /// to its alias, or `None` if the rule has no accumulate conditions
+    ///
+    /// A value that cannot be computed, because the trigger fact lacks a correlated
+    /// field, the calculator fails or a non-finite aggregate is discarded, is left
+    /// unbound so the condition does not hold.
+    fn bind_accumulations(
         &self,
-    ) -> crate::lazy_aggregation::LazyAggregationManagerStats {
-        self.lazy_aggregation_manager.get_stats()
+        rule: &Rule,
+        trigger_fact: &Fact,
+        fact_store: &ArenaFactStore,
+        calculator: &Calculator,
+    ) -> Result<Option<Fact>> {
+        let mut bound = None;
+        for condition in &rule.conditions {
+            let Condition::Accumulate(accumulate) = condition else {
+                continue;
+            };
+            let fact = bound.get_or_insert_with(|| trigger_fact.clone());
+            if let Some(value) =
+                self.accumulate(accumulate, trigger_fact, fact_store, calculator)?
+            {
+                fact.data.fields.insert(accumulate.alias.clone(), value);
+            }
+        }
+        Ok(bound)
     }
 
-    /// Invalidate all lazy aggregation caches (when fact store changes)
-    pub fn invalidate_lazy_aggregation_caches(&self) {
-        self.lazy_aggregation_manager.invalidate_all_caches();
+    /// Value of an accumulate condition for the trigger fact, if one can be computed
+    fn accumulate(
+        &self,
+        accumulate: &crate::types::AccumulateCondition,
+        trigger_fact: &Fact,
+        fact_store: &ArenaFactStore,
+        calculator: &Calculator,
+    ) -> Result<Option<FactValue>> {
+        use crate::types::Accumulator;
+
+        let Some(criteria) = accumulate
+            .correlate_by
+            .iter()
+            .map(|field| Some((field.clone(), trigger_fact.data.fields.get(field)?.clone())))
+            .collect::<Option<Vec<_>>>()
+        else {
+            return Ok(None);
+        };
+
+        let mut collected = fact_store.find_by_criteria(&criteria);
+        if !collected.iter().any(|fact| fact.id == trigger_fact.id) {
+            collected.push(trigger_fact.clone());
+        }
+        let mut facts = Vec::with_capacity(collected.len());
+        for fact in collected {
+            if self.test_condition(&fact, &accumulate.source, fact_store)? {
+                facts.push(fact);
+            }
+        }
+        facts.sort_by_key(|fact| fact.id);
+        let field_values = |field: &String| {
+            facts.iter().filter_map(|fact| fact.data.fields.get(field)).cloned().collect()
+        };
+
+        let value = match &accumulate.accumulator {
+            Accumulator::Collect => FactValue::Array(
+                facts.iter().map(|fact| FactValue::Object(fact.data.fields.clone())).collect(),
+            ),
+            Accumulator::CollectField { field } => FactValue::Array(field_values(field)),
+            Accumulator::Aggregate { aggregation_type, field } => {
+                let value = aggregate_values(
+                    aggregation_type,
+                    facts.iter().filter_map(|fact| fact.data.fields.get(field)),
+                );
+                return Ok(self.non_finite.derive(value, |message| {
+                    crate::error::BingoError::aggregation(
+                        &format!("{aggregation_type:?}"),
+                        field,
+                        format!("Accumulated value '{}' is a {message}", accumulate.alias),
+                    )
+                })?);
+            }
+            Accumulator::Calculator { calculator: name, field } => {
+                let values = FactValue::Array(field_values(field));
+                let inputs = std::collections::HashMap::from([("values".to_string(), &values)]);
+                match calculator.calculate(name, &inputs) {
+                    Ok(value) => value,
+                    Err(error) => {
+                        warn!(
+                            calculator_name = name.as_str(),
+                            alias = accumulate.alias.as_str(),
+                            error = error,
+                            "Accumulate calculator failed"
+                        );
+                        return Ok(None);
+                    }
+                }
+            }
+        };
+        Ok(Some(value))
     }
 
-    /// Clean up inactive lazy aggregations to free memory
-    pub fn cleanup_lazy_aggregations(&self) {
-        self.lazy_aggregation_manager.cleanup_inactive_aggregations();
+    /// Evaluate a rule with accumulate conditions for one fact
+    ///
+    /// Accumulated values only hold for the fact they were bound to, so every condition
+    /// is tested against that fact rather than joined across facts. Returns `None` if
+    /// the rule has no accumulate conditions.
+    fn process_accumulating_rule(
+        &mut self,
+        rule_id: RuleId,
+        fact: &Fact,
+        fact_store: &ArenaFactStore,
+        calculator: &Calculator,
+    ) -> Result<Option<Vec<RuleExecutionResult>>> {
+        let Some(rule) = self
+            .rules
+            .get(&rule_id)
+            .filter(|rule| {
+                rule.conditions
+                    .iter()
+                    .any(|condition| matches!(condition, Condition::Accumulate(_)))
+            })
+            .cloned()
+        else {
+            return Ok(None);
+        };
+        let evaluation_start = Instant::now();
+        let Some(bound) = self.bind_accumulations(&rule, fact, fact_store, calculator)? else {
+            return Ok(None);
+        };
+        let matched = self.fact_matches_all_conditions(&bound, &rule.conditions, fact_store)?;
+        self.record_rule_evaluation(rule.id, fact.id, evaluation_start.elapsed());
+        if !matched {
+            return Ok(Some(Vec::new()));
+        }
+        let results = self.fire_rule(&rule, &bound, &[fact.id], fact_store, calculator)?;
+        Ok(Some(results.into_iter().collect()))
+    }
+
+    /// Assert facts into every aggregation node, seeding nodes built after facts arrived
+    fn assert_into_aggregation_nodes(&mut self, facts: &[Fact], fact_store: &ArenaFactStore) {
+        if self.aggregation_nodes.is_empty() {
+            return;
+        }
+
+        let mut existing_facts = None;
+        for node in self.aggregation_nodes.values_mut() {
+            if !node.is_seeded() {
+                let existing = existing_facts.get_or_insert_with(|| fact_store.iter());
+                node.seed(existing.iter());
+            }
+            node.assert_batch(facts);
+        }
     }
 
     /// Evaluate an aggregation condition
     ///
-    /// For now, implements basic aggregation evaluation without lazy optimization.
-    /// This will be enhanced with full lazy aggregation support in a future iteration.
+    /// Compiled conditions read the running aggregate from their `AggregationNode`.
+    /// Conditions nested inside composite conditions have no node of their own and
+    /// fall back to scanning the fact store, or with an event-time window, to windowing
+    /// it in a throwaway node. With an event-time window the condition holds when any
+    /// window containing the trigger fact satisfies it.
     fn evaluate_aggregation_condition(
         &self,
         trigger_fact: &Fact,
         agg_condition: &crate::types::AggregationCondition,
         fact_store: &ArenaFactStore,
+    ) -> Result<bool> {
+        let scratch;
+        let node = match self.aggregation_node(agg_condition).filter(|node| node.is_seeded()) {
+            Some(node) => node,
+            None if agg_condition.window.as_ref().is_some_and(AggregationWindow::is_event_time) => {
+                // The fact store is the whole history, so none of it counts as late
+                let mut node = AggregationNode::new(0, agg_condition.clone());
+                node.set_late_data_policy(LateDataPolicy::Accept);
+                node.seed(fact_store.iter().iter());
+                node.assert_fact(trigger_fact);
+                scratch = node;
+                &scratch
+            }
+            None => {
+                return self.scan_aggregation_condition(trigger_fact, agg_condition, fact_store);
+            }
+        };
+
+        // EDGE CASE: No facts observed yet should return false for aggregations
+        if node.fact_count() == 0 {
+            return Ok(false);
+        }
+
+        // Facts dropped as late or evicted with their windows are in no window
+        if node.is_windowed() && !node.contains_fact(trigger_fact.id) {
+            return Ok(false);
+        }
+
+        // Facts outside every calendar period have no period to aggregate over
+        if node
+            .calendar()
+            .is_some_and(|calendar| calendar.period_of(trigger_fact).is_none())
+        {
+            return Ok(false);
+        }
+
+        if let Some(having_condition) = &agg_condition.having {
+            // Evaluate the having clause against a synthetic fact holding the aggregate
+            for aggregate in node.aggregates_for(trigger_fact) {
+                let Some(aggregate) = self.guard_aggregate(agg_condition, aggregate)? else {
+                    continue;
+                };
+                let mut synthetic_fields = std::collections::HashMap::new();
+                synthetic_fields.insert(agg_condition.alias.clone(), aggregate);
+                let synthetic_fact =
+                    Fact::new(0, crate::types::FactData { fields: synthetic_fields });
+                if self.test_condition(&synthetic_fact, having_condition, fact_store)? {
+                    return Ok(true);
+                }
+            }
+            Ok(false)
+        } else {
+            Ok(node
+                .groups_for(trigger_fact)
+                .iter()
+                .any(|group| group.has_data(&agg_condition.aggregation_type)))
+        }
+    }
+
+    /// Evaluate an aggregation condition by scanning the whole fact store
+    fn scan_aggregation_condition(
+        &self,
+        trigger_fact: &Fact,
+        agg_condition: &crate::types::AggregationCondition,
+        fact_store: &ArenaFactStore,
     ) -> Result<bool> {
         // Get all facts for aggregation
         let all_facts = fact_store.iter();
@@ -1876,9 +4724,20 @@ impl ReteNetwork {
             all_facts.len()
         );
 
+        // Calendar windows only aggregate facts in the trigger fact's period
+        let calendar = self.aggregation_calendar(agg_condition)?;
+        let period = calendar.as_ref().map(|calendar| calendar.period_of(trigger_fact));
+        if period.as_ref().is_some_and(Option::is_none) {
+            return Ok(false);
+        }
+
         // Filter facts by group criteria
         let matching_facts: Vec<_> = all_facts
             .into_iter()
+            .filter(|fact| match (&calendar, &period) {
+                (Some(calendar), Some(period)) => calendar.period_of(fact) == *period,
+                _ => true,
+            })
             .filter(|fact| {
                 // Check if this fact matches the group criteria
                 if agg_condition.group_by.is_empty() {
@@ -1897,92 +4756,20 @@ impl ReteNetwork {
 
         // Calculate aggregation value
         use crate::types::AggregationType;
-        let aggregated_value = match agg_condition.aggregation_type {
-            AggregationType::Count => {
-                let count = matching_facts
-                    .iter()
-                    .filter(|fact| fact.data.fields.contains_key(&agg_condition.source_field))
-                    .count();
-                crate::types::FactValue::Integer(count as i64)
-            }
-            AggregationType::Sum => {
-                let sum: f64 = matching_facts
-                    .iter()
-                    .filter_map(|fact| fact.data.fields.get(&agg_condition.source_field))
-                    .filter_map(|value| value.as_f64())
-                    .sum();
-                crate::types::FactValue::Float(sum)
-            }
-            AggregationType::Average => {
-                let values: Vec<f64> = matching_facts
-                    .iter()
-                    .filter_map(|fact| fact.data.fields.get(&agg_condition.source_field))
-                    .filter_map(|value| value.as_f64())
-                    .collect();
-                if values.is_empty() {
-                    crate::types::FactValue::Float(0.0)
-                } else {
-                    let avg = values.iter().sum::<f64>() / values.len() as f64;
-                    crate::types::FactValue::Float(avg)
-                }
-            }
-            AggregationType::Min => {
-                let min_value = matching_facts
-                    .iter()
-                    .filter_map(|fact| fact.data.fields.get(&agg_condition.source_field))
-                    .filter_map(|value| value.as_f64())
-                    .fold(f64::INFINITY, f64::min);
-                crate::types::FactValue::Float(min_value)
-            }
-            AggregationType::Max => {
-                let max_value = matching_facts
-                    .iter()
-                    .filter_map(|fact| fact.data.fields.get(&agg_condition.source_field))
-                    .filter_map(|value| value.as_f64())
-                    .fold(f64::NEG_INFINITY, f64::max);
-                crate::types::FactValue::Float(max_value)
-            }
-            AggregationType::StandardDeviation => {
-                let values: Vec<f64> = matching_facts
-                    .iter()
-                    .filter_map(|fact| fact.data.fields.get(&agg_condition.source_field))
-                    .filter_map(|value| value.as_f64())
-                    .collect();
-                if values.len() < 2 {
-                    crate::types::FactValue::Float(0.0)
-                } else {
-                    let mean = values.iter().sum::<f64>() / values.len() as f64;
-                    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>()
-                        / values.len() as f64;
-                    crate::types::FactValue::Float(variance.sqrt())
-                }
-            }
-            AggregationType::Percentile(p) => {
-                let mut values: Vec<f64> = matching_facts
-                    .iter()
-                    .filter_map(|fact| fact.data.fields.get(&agg_condition.source_field))
-                    .filter_map(|value| value.as_f64())
-                    .collect();
-                if values.is_empty() {
-                    crate::types::FactValue::Float(0.0)
-                } else {
-                    values.sort_by(|a, b| a.partial_cmp(b).unwrap());
-                    let rank_f = (p / 100.0) * (values.len() as f64 - 1.0);
-                    let lower = rank_f.floor() as usize;
-                    let upper = rank_f.ceil() as usize;
-                    let interp = if upper == lower {
-                        values[lower]
-                    } else {
-                        let w = rank_f - lower as f64;
-                        values[lower] * (1.0 - w) + values[upper] * w
-                    };
-                    crate::types::FactValue::Float(interp)
-                }
-            }
-        };
+        let aggregated_value = aggregate_values(
+            &agg_condition.aggregation_type,
+            matching_facts
+                .iter()
+                .filter_map(|fact| fact.data.fields.get(&agg_condition.source_field)),
+        );
 
         // Evaluate the having clause if present
         if let Some(having_condition) = &agg_condition.having {
+            let Some(aggregated_value) = self.guard_aggregate(agg_condition, aggregated_value)?
+            else {
+                return Ok(false);
+            };
+
             // Create a synthetic fact with the aggregated value
             let mut synthetic_fields = std::collections::HashMap::new();
             synthetic_fields.insert(agg_condition.alias.clone(), aggregated_value);
@@ -1991,6 +4778,7 @@ impl ReteNetwork {
                 id: 0,
                 external_id: None,
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: crate::types::FactData { fields: synthetic_fields },
             };
 
@@ -2089,8 +4877,91 @@ pub struct NetworkStats {
     pub memory_usage_bytes: u64,
 }
 
+/// Limits for asserting facts created by rule actions back into the network
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ForwardChaining {
+    /// Rounds of created facts processed after a batch before the batch fails
+    pub max_iterations: usize,
+}
+
+impl Default for ForwardChaining {
+    fn default() -> Self {
+        Self { max_iterations: 32 }
+    }
+}
+
+/// What a committing transaction holds back or changed in the network
+#[derive(Debug)]
+struct TransactionHold {
+    /// Thread committing the transaction; other threads' changes are not held
+    owner: std::thread::ThreadId,
+    /// Webhook actions the transaction fired
+    webhooks: Vec<WebhookPayload>,
+    /// Facts the transaction removed from working memory, as they were
+    working_memory: HashMap<FactId, Arc<Fact>>,
+    /// Facts derived by the rules the transaction fired
+    derived_facts: Vec<FactId>,
+}
+
+/// Candidate rule of a fact, with its condition already tested when it has only one
+#[derive(Debug)]
+struct AlphaMatch {
+    rule_id: RuleId,
+    test: Option<AlphaTest>,
+}
+
+/// Outcome of testing a single-condition rule ahead of firing it
+#[derive(Debug)]
+struct AlphaTest {
+    matched: Result<bool>,
+    elapsed: std::time::Duration,
+}
+
+/// Fields of fact data in name order, for comparing derived facts
+fn canonical_fact_data(data: &crate::types::FactData) -> Vec<(String, FactValue)> {
+    let mut fields: Vec<(String, FactValue)> =
+        data.fields.iter().map(|(name, value)| (name.clone(), value.clone())).collect();
+    fields.sort_by(|(a, _), (b, _)| a.cmp(b));
+    fields
+}
+
+/// Outcome of testing one fact against the conditions of a rule
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleExplanation {
+    pub rule_id: RuleId,
+    pub rule_name: String,
+    /// Whether the fact satisfies every condition of the rule
+    pub matched: bool,
+    /// Result for each condition, in the order the rule declares them
+    pub condition_results: Vec<bool>,
+}
+
 impl Default for ReteNetwork {
     fn default() -> Self {
         Self::new()
     }
 }
+
+/// Bytes held by a rule's name, conditions and actions
+fn rule_definition_bytes(rule: &Rule) -> usize {
+    rule.name.capacity()
+        + rule.conditions.capacity() * std::mem::size_of::<Condition>()
+        + rule.actions.capacity() * std::mem::size_of::<crate::types::Action>()
+}
+
+/// Estimated state of an aggregation node, ~48 bytes per contribution and ~96 per
+/// event-time window
+fn aggregation_node_bytes(node: &AggregationNode) -> usize {
+    node.fact_count() * 48 + node.window_count() * 96
+}
+
+/// Estimated state of a window node, ~256 bytes per windowed fact copy
+fn window_node_bytes(node: &WindowNode) -> usize {
+    node.fact_count() * 256
+}
+
+/// Estimated state of a sequence node, ~256 bytes per held fact copy and ~64 per
+/// partial match
+fn sequence_node_bytes(node: &SequenceNode) -> usize {
+    node.fact_count() * 256 + node.partial_match_count() * 64
+}
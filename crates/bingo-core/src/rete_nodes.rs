@@ -1,6 +1,6 @@
 use super::types::{
-    ActionType, BetaNode, Condition, Fact, FactData, FactId, FactValue, NodeId, Operator, Rule,
-    RuleId, TerminalNode,
+    ActionType, BetaNode, Condition, Fact, FactData, FactId, FactValue, IntegerOp, NodeId,
+    Operator, OverflowPolicy, Rule, RuleId, TerminalNode,
 };
 use crate::fact_store::arena_store::ArenaFactStore;
 
@@ -516,7 +516,8 @@ impl BetaMemory {
     fn fact_matches_first_condition(&self, fact: &Fact, condition: &Condition) -> bool {
         match condition {
             Condition::Simple { field, operator, value } => {
-                if let Some(fact_value) = fact.data.fields.get(field) {
+                if let Some(fact_value) = fact.condition_value(field) {
+                    let fact_value = fact_value.as_ref();
                     match operator {
                         Operator::Equal => fact_value == value,
                         Operator::NotEqual => fact_value != value,
@@ -545,6 +546,15 @@ impl BetaMemory {
                             ) => fact_str.ends_with(pattern),
                             _ => false,
                         },
+                        Operator::Within | Operator::OlderThan | Operator::Overlaps => {
+                            operator.matches_temporal(fact_value, value)
+                        }
+                        Operator::Matches | Operator::ContainsIgnoreCase => {
+                            crate::string_match::matches(operator, fact_value, value)
+                        }
+                        Operator::In | Operator::NotIn => {
+                            crate::value_list::matches_literal(operator, fact_value, value)
+                        }
                     }
                 } else {
                     false
@@ -891,9 +901,9 @@ impl ReteNetwork {
     fn fact_matches_condition(&self, fact: &Fact, condition: &Condition) -> Result<bool> {
         match condition {
             Condition::Simple { field, operator, value } => {
-                let fact_value = fact.data.fields.get(field);
+                let fact_value = fact.condition_value(field);
 
-                match fact_value {
+                match fact_value.as_deref() {
                     Some(fact_val) => {
                         match operator {
                             Operator::Equal => Ok(fact_val == value),
@@ -932,6 +942,15 @@ impl ReteNetwork {
                                     _ => Ok(false),
                                 }
                             }
+                            Operator::Within | Operator::OlderThan | Operator::Overlaps => {
+                                Ok(operator.matches_temporal(fact_val, value))
+                            }
+                            Operator::Matches | Operator::ContainsIgnoreCase => {
+                                Ok(crate::string_match::matches(operator, fact_val, value))
+                            }
+                            Operator::In | Operator::NotIn => Ok(
+                                crate::value_list::matches_literal(operator, fact_val, value),
+                            ),
                         }
                     }
                     None => Ok(false), // Field doesn't exist
@@ -957,6 +976,14 @@ impl ReteNetwork {
                 // Stream conditions are handled by specialized stream processing nodes
                 Ok(false)
             }
+            Condition::Sequence(_) => {
+                // Sequence conditions are handled by sequence nodes holding partial matches
+                Ok(false)
+            }
+            Condition::Accumulate(_) => {
+                // Accumulate conditions are bound by the network before rules are evaluated
+                Ok(false)
+            }
         }
     }
 
@@ -1005,6 +1032,7 @@ impl ReteNetwork {
                         id: new_fact_id,
                         external_id: None,
 
+                        fact_type: None,
                         data: data.clone(),
                     };
 
@@ -1120,12 +1148,22 @@ impl ReteNetwork {
                     if let Some(current_value) = fact.data.fields.get(field) {
                         match (current_value, increment) {
                             (FactValue::Integer(current), FactValue::Integer(inc)) => {
-                                let new_value = FactValue::Integer(current + inc);
-                                ActionResult::FieldIncremented {
-                                    fact_id: fact.id,
-                                    field: field.clone(),
-                                    old_value: current_value.clone(),
-                                    new_value,
+                                match OverflowPolicy::default().apply(
+                                    *current,
+                                    IntegerOp::Add,
+                                    *inc,
+                                ) {
+                                    Ok(new_value) => ActionResult::FieldIncremented {
+                                        fact_id: fact.id,
+                                        field: field.clone(),
+                                        old_value: current_value.clone(),
+                                        new_value,
+                                    },
+                                    Err(error) => ActionResult::Logged {
+                                        message: format!(
+                                            "IncrementField failed for field '{field}': {error}"
+                                        ),
+                                    },
                                 }
                             }
                             (FactValue::Float(current), FactValue::Float(inc)) => {
@@ -1182,6 +1220,18 @@ impl ReteNetwork {
                         }
                     }
                 }
+                ActionType::MergeObject { field, .. } => {
+                    let current = fact.data.fields.get(field);
+                    match mutated_value(&action.action_type, current, OverflowPolicy::default()) {
+                        Ok(new_value) => mutation_result(
+                            &action.action_type,
+                            fact.id,
+                            current.cloned(),
+                            new_value,
+                        ),
+                        Err(message) => ActionResult::Logged { message },
+                    }
+                }
                 ActionType::SendNotification {
                     recipient,
                     subject,
@@ -1202,16 +1252,17 @@ impl ReteNetwork {
                         subject: subject.clone(),
                     }
                 }
+                ActionType::CallWebhook { endpoint, .. } => ActionResult::Logged {
+                    message: format!(
+                        "CallWebhook '{endpoint}' needs the network's webhook dispatcher"
+                    ),
+                },
             };
             action_results.push(result);
         }
 
         // Create the result but don't return the Vec to pool yet (it's moved into the result)
-        Ok(RuleExecutionResult {
-            rule_id: rule.id,
-            fact_id: fact.id,
-            actions_executed: action_results,
-        })
+        Ok(RuleExecutionResult::for_fact(rule.id, fact, action_results))
     }
 
     /// Get statistics about the network including alpha and beta memory performance
@@ -1360,6 +1411,7 @@ mod tests {
             timestamp: chrono::Utc::now(),
             id: 1,
             external_id: None,
+            fact_type: None,
             data: FactData { fields },
         };
 
@@ -1391,6 +1443,7 @@ mod tests {
             timestamp: chrono::Utc::now(),
             id: 1,
             external_id: None,
+            fact_type: None,
             data: FactData { fields },
         };
 
@@ -1419,6 +1472,7 @@ mod tests {
             timestamp: chrono::Utc::now(),
             id: 1,
             external_id: None,
+            fact_type: None,
             data: FactData { fields },
         };
 
@@ -1442,6 +1496,7 @@ mod tests {
             actions: vec![Action {
                 action_type: crate::types::ActionType::Log { message: "User found".to_string() },
             }],
+            metadata: Default::default(),
         };
 
         network.add_rule(rule).unwrap();
@@ -1455,6 +1510,7 @@ mod tests {
             timestamp: chrono::Utc::now(),
             id: 1,
             external_id: None,
+            fact_type: None,
             data: FactData { fields },
         };
 
@@ -1675,6 +1731,7 @@ mod tests {
             id: 101,
             external_id: None,
             timestamp: chrono::Utc::now(),
+            fact_type: None,
             data: FactData { fields },
         };
 
@@ -1713,6 +1770,7 @@ mod tests {
             id: 101,
             external_id: None,
             timestamp: chrono::Utc::now(),
+            fact_type: None,
             data: FactData { fields: fields1 },
         };
 
@@ -1725,6 +1783,7 @@ mod tests {
             id: 102,
             external_id: None,
             timestamp: chrono::Utc::now(),
+            fact_type: None,
             data: FactData { fields: fields2 },
         };
 
@@ -1765,11 +1824,34 @@ mod tests {
 }
 
 /// Result of executing a rule
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct RuleExecutionResult {
     pub rule_id: RuleId,
     pub fact_id: FactId,
     pub actions_executed: Vec<ActionResult>,
+    /// External ID of the matched fact, stable across sessions unlike `fact_id`
+    pub external_id: Option<String>,
+    /// Creation timestamp of the matched fact
+    pub fact_timestamp: Option<chrono::DateTime<chrono::Utc>>,
+    /// ID of the result's explanation trace, set while the audit log is enabled
+    pub result_id: Option<crate::explanation::ResultId>,
+    /// `SetField` collisions with other rules in the batch that this result took part in
+    pub field_collisions: Vec<crate::field_collisions::FieldCollision>,
+}
+
+impl RuleExecutionResult {
+    /// Result of `rule_id` firing for `fact`, carrying the fact's correlation metadata
+    pub fn for_fact(rule_id: RuleId, fact: &Fact, actions_executed: Vec<ActionResult>) -> Self {
+        Self {
+            rule_id,
+            fact_id: fact.id,
+            actions_executed,
+            external_id: fact.external_id.clone(),
+            fact_timestamp: Some(fact.timestamp),
+            result_id: None,
+            field_collisions: Vec::new(),
+        }
+    }
 }
 
 /// Result of executing an action with lazy string materialization
@@ -1822,12 +1904,26 @@ pub enum ActionResult {
         appended_value: crate::types::FactValue,
         new_length: usize,
     },
+    /// Entries merged into object field
+    ObjectMerged {
+        fact_id: FactId,
+        field: String,
+        /// Merged keys in name order
+        merged_keys: Vec<String>,
+        new_size: usize,
+    },
     /// Notification sent
     NotificationSent {
         recipient: String,
         notification_type: crate::types::NotificationType,
         subject: String,
     },
+    /// Webhook payload handed to the webhook workers
+    WebhookDispatched {
+        endpoint: String,
+        /// False when the queue was full and the payload was dead-lettered instead
+        queued: bool,
+    },
 }
 
 impl ActionResult {
@@ -1859,11 +1955,114 @@ impl ActionResult {
     }
 }
 
+/// New value of the field an `IncrementField`, `AppendToArray` or `MergeObject` action
+/// mutates, given its current value
+///
+/// An unset field counts as zero, an empty array or an empty object. Errors are the
+/// messages the action's `Logged` result reports.
+pub(crate) fn mutated_value(
+    action: &ActionType,
+    current: Option<&FactValue>,
+    overflow: OverflowPolicy,
+) -> std::result::Result<FactValue, String> {
+    match (action, current) {
+        (ActionType::IncrementField { increment, .. }, None) => Ok(increment.clone()),
+        (ActionType::IncrementField { field, increment }, Some(current)) => {
+            match (current, increment) {
+                (FactValue::Integer(current), FactValue::Integer(increment)) => overflow
+                    .apply(*current, IntegerOp::Add, *increment)
+                    .map_err(|error| format!("IncrementField failed for field '{field}': {error}")),
+                (FactValue::Float(current), FactValue::Float(increment)) => {
+                    Ok(FactValue::Float(current + increment))
+                }
+                _ => Err(format!(
+                    "IncrementField failed: incompatible types for field '{field}'"
+                )),
+            }
+        }
+        (ActionType::AppendToArray { field, value }, current) => match current {
+            None => Ok(FactValue::Array(vec![value.clone()])),
+            Some(FactValue::Array(items)) => {
+                let mut items = items.clone();
+                items.push(value.clone());
+                Ok(FactValue::Array(items))
+            }
+            Some(_) => Err(format!(
+                "AppendToArray failed: field '{field}' is not an array"
+            )),
+        },
+        (ActionType::MergeObject { field, entries }, current) => match current {
+            None => Ok(FactValue::Object(entries.clone())),
+            Some(FactValue::Object(object)) => {
+                let mut object = object.clone();
+                object.extend(entries.iter().map(|(key, value)| (key.clone(), value.clone())));
+                Ok(FactValue::Object(object))
+            }
+            Some(_) => Err(format!(
+                "MergeObject failed: field '{field}' is not an object"
+            )),
+        },
+        (other, _) => Err(format!("Not a mutation action: {other:?}")),
+    }
+}
+
+/// Result of a mutation action that changed its field from `old_value` to `new_value`
+pub(crate) fn mutation_result(
+    action: &ActionType,
+    fact_id: FactId,
+    old_value: Option<FactValue>,
+    new_value: FactValue,
+) -> ActionResult {
+    match action {
+        ActionType::AppendToArray { field, value } => ActionResult::ArrayAppended {
+            fact_id,
+            field: field.clone(),
+            appended_value: value.clone(),
+            new_length: match &new_value {
+                FactValue::Array(items) => items.len(),
+                _ => 1,
+            },
+        },
+        ActionType::MergeObject { field, entries } => {
+            let mut merged_keys: Vec<String> = entries.keys().cloned().collect();
+            merged_keys.sort();
+            ActionResult::ObjectMerged {
+                fact_id,
+                field: field.clone(),
+                merged_keys,
+                new_size: match &new_value {
+                    FactValue::Object(object) => object.len(),
+                    _ => entries.len(),
+                },
+            }
+        }
+        ActionType::IncrementField { field, .. } => ActionResult::FieldIncremented {
+            fact_id,
+            field: field.clone(),
+            old_value: old_value.unwrap_or(FactValue::Integer(0)),
+            new_value,
+        },
+        other => ActionResult::Logged { message: format!("Not a mutation action: {other:?}") },
+    }
+}
+
 /// Evaluate a formula expression against fact fields
 /// Very simple implementation for BSSN - handle basic cases
+///
+/// Integer overflow fails the formula; see [`evaluate_formula_expression_with_overflow`].
 pub fn evaluate_formula_expression(
     expression: &str,
     fact_fields: &HashMap<String, FactValue>,
+) -> Result<FactValue> {
+    evaluate_formula_expression_with_overflow(expression, fact_fields, OverflowPolicy::default())
+}
+
+/// Evaluate a formula expression, resolving integer `+`, `-` and `*` overflowing `i64`
+/// as `overflow` says
+pub fn evaluate_formula_expression_with_overflow(
+    expression: &str,
+    fact_fields: &HashMap<String, FactValue>,
+    overflow: OverflowPolicy,
 ) -> Result<FactValue> {
     let expr = expression.trim();
 
@@ -1877,7 +2076,7 @@ pub fn evaluate_formula_expression(
         let left_val = evaluate_operand(&left, fact_fields)?;
         let right_val = evaluate_operand(&right, fact_fields)?;
 
-        return evaluate_binary_operation(&left_val, &op, &right_val);
+        return evaluate_binary_operation(&left_val, &op, &right_val, overflow);
     }
 
     // Handle literal values
@@ -1943,15 +2142,64 @@ fn evaluate_operand(operand: &str, fact_fields: &HashMap<String, FactValue>) ->
     Err(anyhow::anyhow!("Unable to evaluate operand: {}", operand))
 }
 
+/// Check that a formula expression has a form [`evaluate_formula_expression`] can
+/// evaluate, without resolving its field references
+pub(crate) fn check_formula_expression(expression: &str) -> std::result::Result<(), String> {
+    let expr = expression.trim();
+    if expr.is_empty() {
+        return Err("expression is empty".to_string());
+    }
+
+    if let Some((left, op, right)) = parse_simple_binary_expression(expr) {
+        for operand in [&left, &right] {
+            if !is_formula_number(operand) && !is_formula_field(operand) {
+                return Err(format!(
+                    "'{operand}' is not a field or a number; '{op}' takes exactly two operands"
+                ));
+            }
+        }
+        return Ok(());
+    }
+
+    let quoted = expr.len() >= 2 && expr.starts_with('"') && expr.ends_with('"');
+    if quoted
+        || is_formula_number(expr)
+        || is_formula_field(expr)
+        || expr == "true"
+        || expr == "false"
+    {
+        Ok(())
+    } else {
+        Err(format!(
+            "'{expr}' is not a field, a literal or a binary expression"
+        ))
+    }
+}
+
+fn is_formula_number(operand: &str) -> bool {
+    operand.parse::<i64>().is_ok() || operand.parse::<f64>().is_ok()
+}
+
+fn is_formula_field(operand: &str) -> bool {
+    let mut chars = operand.chars();
+    chars.next().is_some_and(|first| first.is_alphabetic() || first == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '.')
+}
+
 /// Evaluate a binary operation between two FactValues
-fn evaluate_binary_operation(left: &FactValue, op: &str, right: &FactValue) -> Result<FactValue> {
+fn evaluate_binary_operation(
+    left: &FactValue,
+    op: &str,
+    right: &FactValue,
+    overflow: OverflowPolicy,
+) -> Result<FactValue> {
     use FactValue::*;
 
     match (left, right) {
         (Integer(a), Integer(b)) => match op {
-            "+" => Ok(Integer(a + b)),
-            "-" => Ok(Integer(a - b)),
-            "*" => Ok(Integer(a * b)),
+            "+" => overflow.apply(*a, IntegerOp::Add, *b),
+            "-" => overflow.apply(*a, IntegerOp::Subtract, *b),
+            "*" => overflow.apply(*a, IntegerOp::Multiply, *b),
             "/" => {
                 if *b == 0 {
                     Err(anyhow::anyhow!("Division by zero"))
@@ -2001,6 +2249,60 @@ fn evaluate_binary_operation(left: &FactValue, op: &str, right: &FactValue) -> R
                 _ => Err(anyhow::anyhow!("Unsupported operator: {}", op)),
             }
         }
+        (Decimal(_), Integer(_) | Float(_) | Decimal(_)) | (Integer(_) | Float(_), Decimal(_)) => {
+            // Decimal arithmetic stays exact; float operands are converted to decimals
+            let (Some(a), Some(b)) = (left.as_decimal(), right.as_decimal()) else {
+                return Err(anyhow::anyhow!(
+                    "Cannot convert operands of {:?} {} {:?} to decimals",
+                    left,
+                    op,
+                    right
+                ));
+            };
+            let result = match op {
+                "+" => a.checked_add(b),
+                "-" => a.checked_sub(b),
+                "*" => a.checked_mul(b),
+                "/" if b.is_zero() => return Err(anyhow::anyhow!("Division by zero")),
+                "/" => a.checked_div(b),
+                "%" if b.is_zero() => return Err(anyhow::anyhow!("Modulo by zero")),
+                "%" => a.checked_rem(b),
+                _ => return Err(anyhow::anyhow!("Unsupported operator: {}", op)),
+            };
+            result
+                .map(Decimal)
+                .ok_or_else(|| anyhow::anyhow!("Decimal overflow in {a} {op} {b}"))
+        }
+        (Date(_) | Duration(_), Date(_) | Duration(_)) | (Duration(_), Integer(_)) => {
+            let result = match (left, op, right) {
+                (Date(a), "+", Duration(d)) => a.checked_add_signed(*d).map(Date),
+                (Duration(d), "+", Date(a)) => a.checked_add_signed(*d).map(Date),
+                (Date(a), "-", Duration(d)) => a.checked_sub_signed(*d).map(Date),
+                (Date(a), "-", Date(b)) => Some(Duration(a.signed_duration_since(*b))),
+                (Duration(a), "+", Duration(b)) => a.checked_add(b).map(Duration),
+                (Duration(a), "-", Duration(b)) => a.checked_sub(b).map(Duration),
+                (Duration(d), "*", Integer(n)) => {
+                    i32::try_from(*n).ok().and_then(|n| d.checked_mul(n)).map(Duration)
+                }
+                (Duration(_), "/", Integer(0)) => {
+                    return Err(anyhow::anyhow!("Division by zero"));
+                }
+                (Duration(d), "/", Integer(n)) => {
+                    i32::try_from(*n).ok().and_then(|n| d.checked_div(n)).map(Duration)
+                }
+                _ => {
+                    return Err(anyhow::anyhow!(
+                        "Unsupported operator '{}' for {} and {}",
+                        op,
+                        left.type_name(),
+                        right.type_name()
+                    ));
+                }
+            };
+            result.ok_or_else(|| {
+                anyhow::anyhow!("Date or duration out of range in {left} {op} {right}")
+            })
+        }
         (String(a), String(b)) => match op {
             "+" => Ok(String(format!("{a}{b}"))),
             _ => Err(anyhow::anyhow!("Unsupported operator '{}' for strings", op)),
@@ -33,8 +33,12 @@
 //! ```
 
 use crate::error::{BingoError, BingoResult};
-use crate::types::{ActionType, Condition, Rule, RuleId};
-use std::collections::{HashMap, HashSet, VecDeque};
+use crate::field_references::{condition_fields, referenced_fields};
+use crate::types::{ActionType, Condition, FACT_TYPE_FIELD, FactValue, Operator, Rule, RuleId};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::fmt::Write;
 use tracing::{debug, info, instrument, warn};
 
 /// Type of dependency between rules
@@ -912,6 +916,509 @@ impl Default for RuleDependencyAnalyzer {
     }
 }
 
+/// Kind of node in a [`RuleFieldGraph`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GraphNodeKind {
+    Rule,
+    Field,
+    /// Value of the `type` field that a rule tests for or gives the facts it creates
+    FactType,
+}
+
+/// Node of a [`RuleFieldGraph`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphNode {
+    /// Unique node id: `rule:<id>`, `field:<name>` or `type:<name>`
+    pub id: String,
+    pub kind: GraphNodeKind,
+    /// Rule name, field name or fact type
+    pub label: String,
+}
+
+/// How a rule uses a field or fact type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldAccess {
+    Reads,
+    Writes,
+}
+
+/// Edge between a rule and a field or fact type node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphEdge {
+    pub rule_id: RuleId,
+    /// Id of the field or fact type node
+    pub target: String,
+    pub access: FieldAccess,
+}
+
+/// Graph of which rules read and write which fields and fact types
+///
+/// Answers impact questions such as "which rules use `employee.termination_date`?"
+/// in one call, and exports as JSON or Graphviz DOT. In DOT, reads point from the
+/// field to the rule and writes from the rule to the field, so paths follow the data.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleFieldGraph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+impl RuleFieldGraph {
+    /// Build the graph of a ruleset
+    pub fn from_rules(rules: &[Rule]) -> Self {
+        let mut graph = Self::default();
+        let mut node_ids = HashSet::new();
+        for rule in rules {
+            graph.add_node(
+                &mut node_ids,
+                GraphNodeKind::Rule,
+                format!("rule:{}", rule.id),
+                &rule.name,
+            );
+            for field in referenced_fields(std::slice::from_ref(rule)) {
+                let target = format!("field:{}", field.field);
+                graph.add_node(
+                    &mut node_ids,
+                    GraphNodeKind::Field,
+                    target.clone(),
+                    &field.field,
+                );
+                if field.read {
+                    graph.add_edge(rule.id, &target, FieldAccess::Reads);
+                }
+                if field.written {
+                    graph.add_edge(rule.id, &target, FieldAccess::Writes);
+                }
+            }
+            for (fact_type, access) in rule_fact_types(rule) {
+                let target = format!("type:{fact_type}");
+                graph.add_node(
+                    &mut node_ids,
+                    GraphNodeKind::FactType,
+                    target.clone(),
+                    &fact_type,
+                );
+                graph.add_edge(rule.id, &target, access);
+            }
+        }
+        graph
+    }
+
+    /// Rules reading or writing `field`, in ascending id order
+    pub fn rules_using_field(&self, field: &str) -> Vec<RuleId> {
+        self.rules_targeting(&format!("field:{field}"))
+    }
+
+    /// Rules matching or creating facts of `fact_type`, in ascending id order
+    pub fn rules_using_fact_type(&self, fact_type: &str) -> Vec<RuleId> {
+        self.rules_targeting(&format!("type:{fact_type}"))
+    }
+
+    /// Serialize the graph as pretty-printed JSON
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Render the graph in Graphviz DOT format
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph RuleFields {\n  rankdir=LR;\n");
+        for node in &self.nodes {
+            let shape = match node.kind {
+                GraphNodeKind::Rule => "box",
+                GraphNodeKind::Field => "ellipse",
+                GraphNodeKind::FactType => "folder",
+            };
+            let _ = writeln!(
+                dot,
+                "  \"{}\" [label=\"{}\", shape={shape}];",
+                dot_escape(&node.id),
+                dot_escape(&node.label)
+            );
+        }
+        for edge in &self.edges {
+            let rule = format!("rule:{}", edge.rule_id);
+            let (from, to, label) = match edge.access {
+                FieldAccess::Reads => (edge.target.as_str(), rule.as_str(), "reads"),
+                FieldAccess::Writes => (rule.as_str(), edge.target.as_str(), "writes"),
+            };
+            let _ = writeln!(
+                dot,
+                "  \"{}\" -> \"{}\" [label=\"{label}\"];",
+                dot_escape(from),
+                dot_escape(to)
+            );
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    fn add_node(
+        &mut self,
+        node_ids: &mut HashSet<String>,
+        kind: GraphNodeKind,
+        id: String,
+        label: &str,
+    ) {
+        if node_ids.insert(id.clone()) {
+            self.nodes.push(GraphNode { id, kind, label: label.to_string() });
+        }
+    }
+
+    fn add_edge(&mut self, rule_id: RuleId, target: &str, access: FieldAccess) {
+        let edge = GraphEdge { rule_id, target: target.to_string(), access };
+        if !self.edges.contains(&edge) {
+            self.edges.push(edge);
+        }
+    }
+
+    fn rules_targeting(&self, target: &str) -> Vec<RuleId> {
+        let rule_ids: BTreeSet<RuleId> = self
+            .edges
+            .iter()
+            .filter(|edge| edge.target == target)
+            .map(|edge| edge.rule_id)
+            .collect();
+        rule_ids.into_iter().collect()
+    }
+}
+
+/// Fact types a rule matches through `type == "..."` conditions or gives created facts
+fn rule_fact_types(rule: &Rule) -> Vec<(String, FieldAccess)> {
+    fn visit(condition: &Condition, fact_types: &mut Vec<(String, FieldAccess)>) {
+        match condition {
+            Condition::Simple {
+                field,
+                operator: Operator::Equal,
+                value: FactValue::String(fact_type),
+            } if field == FACT_TYPE_FIELD => {
+                fact_types.push((fact_type.clone(), FieldAccess::Reads));
+            }
+            Condition::Complex { conditions, .. }
+            | Condition::And { conditions }
+            | Condition::Or { conditions } => {
+                for condition in conditions {
+                    visit(condition, fact_types);
+                }
+            }
+            Condition::Stream(stream) => {
+                if let Some(filter) = &stream.filter {
+                    visit(filter, fact_types);
+                }
+            }
+            Condition::Sequence(sequence) => {
+                for step in &sequence.steps {
+                    visit(step, fact_types);
+                }
+            }
+            Condition::Accumulate(accumulate) => visit(&accumulate.source, fact_types),
+            Condition::Simple { .. } | Condition::Aggregation(_) => {}
+        }
+    }
+
+    let mut fact_types = Vec::new();
+    for condition in &rule.conditions {
+        visit(condition, &mut fact_types);
+    }
+    for action in &rule.actions {
+        let created_type = match &action.action_type {
+            ActionType::CreateFact { data } => data.fields.get(FACT_TYPE_FIELD),
+            ActionType::SetField { field, value } if field == FACT_TYPE_FIELD => Some(value),
+            _ => None,
+        };
+        if let Some(FactValue::String(fact_type)) = created_type {
+            fact_types.push((fact_type.clone(), FieldAccess::Writes));
+        }
+    }
+    fact_types
+}
+
+/// What adding a rule that closes a cycle of rules triggering each other does
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleCyclePolicy {
+    /// Add the rule without looking for cycles
+    #[default]
+    Off,
+    /// Add the rule and log the cycle
+    Warn,
+    /// Refuse the rule, reporting the cycle it would close
+    Reject,
+}
+
+/// Node of a [`RuleFlowGraph`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleFlowNode {
+    pub rule_id: RuleId,
+    pub name: String,
+}
+
+/// Edge of a [`RuleFlowGraph`]: facts `from` creates or modifies can match `to`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleFlowEdge {
+    pub from: RuleId,
+    pub to: RuleId,
+    /// Fields, and `type:<name>` fact types, the data flows through, in name order
+    pub via: Vec<String>,
+}
+
+/// Graph of which rules can trigger which
+///
+/// An edge runs from a rule whose actions write a field, or create facts of a type, to
+/// every rule whose conditions read that field or match that type; a rule reading what
+/// it writes has an edge to itself. With forward chaining, a cycle is a set of rules
+/// that can keep firing each other, so [`RuleCyclePolicy`] can refuse rules closing
+/// one. Exports as JSON or Graphviz DOT, with the edges of cycles drawn in red.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleFlowGraph {
+    /// Rules in the order given
+    pub nodes: Vec<RuleFlowNode>,
+    /// Edges ordered by source, then target rule
+    pub edges: Vec<RuleFlowEdge>,
+}
+
+impl RuleFlowGraph {
+    /// Build the graph of a ruleset
+    pub fn from_rules<'a>(rules: impl IntoIterator<Item = &'a Rule>) -> Self {
+        let rules: Vec<&Rule> = rules.into_iter().collect();
+        let usage: Vec<(BTreeSet<String>, BTreeSet<String>)> =
+            rules.iter().map(|rule| rule_flow_usage(rule)).collect();
+
+        let mut edges = Vec::new();
+        for (from, (_, writes)) in rules.iter().zip(&usage) {
+            for (to, (reads, _)) in rules.iter().zip(&usage) {
+                let via: Vec<String> = writes.intersection(reads).cloned().collect();
+                if !via.is_empty() {
+                    edges.push(RuleFlowEdge { from: from.id, to: to.id, via });
+                }
+            }
+        }
+        edges.sort_by_key(|edge| (edge.from, edge.to));
+        edges.dedup_by_key(|edge| (edge.from, edge.to));
+
+        let nodes = rules
+            .iter()
+            .map(|rule| RuleFlowNode { rule_id: rule.id, name: rule.name.clone() })
+            .collect();
+        Self { nodes, edges }
+    }
+
+    /// Rules `rule_id` can trigger, in ascending id order
+    pub fn successors(&self, rule_id: RuleId) -> Vec<RuleId> {
+        self.edges
+            .iter()
+            .filter(|edge| edge.from == rule_id)
+            .map(|edge| edge.to)
+            .collect()
+    }
+
+    /// Shortest cycle through `rule_id`, as the rules along it from `rule_id` back to
+    /// itself, or `None` when the rule cannot trigger itself
+    pub fn cycle_through(&self, rule_id: RuleId) -> Option<Vec<RuleId>> {
+        let mut previous: HashMap<RuleId, RuleId> = HashMap::new();
+        let mut queue = VecDeque::from([rule_id]);
+        while let Some(current) = queue.pop_front() {
+            for next in self.successors(current) {
+                if next == rule_id {
+                    let mut cycle = vec![rule_id];
+                    let mut step = current;
+                    while step != rule_id {
+                        cycle.push(step);
+                        step = previous[&step];
+                    }
+                    cycle.push(rule_id);
+                    cycle.reverse();
+                    return Some(cycle);
+                }
+                if let Entry::Vacant(entry) = previous.entry(next) {
+                    entry.insert(current);
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+
+    /// Groups of rules that can trigger each other, each in ascending id order, ordered
+    /// by their lowest rule id
+    pub fn cycles(&self) -> Vec<Vec<RuleId>> {
+        let mut cycles: Vec<Vec<RuleId>> = self
+            .strongly_connected()
+            .into_iter()
+            .filter(|component| {
+                component.len() > 1
+                    || self
+                        .edges
+                        .iter()
+                        .any(|edge| edge.from == component[0] && edge.to == edge.from)
+            })
+            .collect();
+        for cycle in &mut cycles {
+            cycle.sort_unstable();
+        }
+        cycles.sort_unstable();
+        cycles
+    }
+
+    /// Whether no rule can trigger itself, directly or through other rules
+    pub fn is_acyclic(&self) -> bool {
+        self.cycles().is_empty()
+    }
+
+    /// Rules ordered so each comes after every rule that can trigger it, lowest id
+    /// first among rules free to go next; `None` when the graph has a cycle
+    pub fn topological_order(&self) -> Option<Vec<RuleId>> {
+        let mut incoming: BTreeMap<RuleId, usize> =
+            self.nodes.iter().map(|node| (node.rule_id, 0)).collect();
+        for edge in &self.edges {
+            *incoming.entry(edge.to).or_default() += 1;
+        }
+        let mut ready: BTreeSet<RuleId> = incoming
+            .iter()
+            .filter(|(_, count)| **count == 0)
+            .map(|(rule_id, _)| *rule_id)
+            .collect();
+        let mut order = Vec::with_capacity(incoming.len());
+        while let Some(rule_id) = ready.pop_first() {
+            order.push(rule_id);
+            for next in self.successors(rule_id) {
+                let count = incoming.get_mut(&next).expect("edge targets are nodes");
+                *count -= 1;
+                if *count == 0 {
+                    ready.insert(next);
+                }
+            }
+        }
+        (order.len() == incoming.len()).then_some(order)
+    }
+
+    /// Serialize the graph as pretty-printed JSON
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Render the graph in Graphviz DOT format, drawing the edges of cycles in red
+    pub fn to_dot(&self) -> String {
+        let cyclic: HashMap<RuleId, usize> = self
+            .cycles()
+            .into_iter()
+            .enumerate()
+            .flat_map(|(index, cycle)| cycle.into_iter().map(move |rule_id| (rule_id, index)))
+            .collect();
+
+        let mut dot = String::from("digraph RuleFlow {\n  rankdir=LR;\n");
+        for node in &self.nodes {
+            let _ = writeln!(
+                dot,
+                "  \"rule:{}\" [label=\"{}\", shape=box];",
+                node.rule_id,
+                dot_escape(&node.name)
+            );
+        }
+        for edge in &self.edges {
+            let in_cycle =
+                cyclic.get(&edge.from).is_some_and(|cycle| cyclic.get(&edge.to) == Some(cycle));
+            let color = if in_cycle { ", color=red" } else { "" };
+            let _ = writeln!(
+                dot,
+                "  \"rule:{}\" -> \"rule:{}\" [label=\"{}\"{color}];",
+                edge.from,
+                edge.to,
+                dot_escape(&edge.via.join(", "))
+            );
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Strongly connected components, found with iterative Kosaraju passes
+    fn strongly_connected(&self) -> Vec<Vec<RuleId>> {
+        let mut forward: BTreeMap<RuleId, Vec<RuleId>> = BTreeMap::new();
+        let mut backward: BTreeMap<RuleId, Vec<RuleId>> = BTreeMap::new();
+        for node in &self.nodes {
+            forward.entry(node.rule_id).or_default();
+            backward.entry(node.rule_id).or_default();
+        }
+        for edge in &self.edges {
+            forward.entry(edge.from).or_default().push(edge.to);
+            backward.entry(edge.to).or_default().push(edge.from);
+        }
+
+        // First pass: order rules by when their depth-first search finishes
+        let mut finished = Vec::with_capacity(forward.len());
+        let mut visited = HashSet::new();
+        for &start in forward.keys() {
+            if !visited.insert(start) {
+                continue;
+            }
+            let mut stack = vec![(start, 0)];
+            while let Some((rule_id, next)) = stack.pop() {
+                match forward[&rule_id].get(next) {
+                    Some(&successor) => {
+                        stack.push((rule_id, next + 1));
+                        if visited.insert(successor) {
+                            stack.push((successor, 0));
+                        }
+                    }
+                    None => finished.push(rule_id),
+                }
+            }
+        }
+
+        // Second pass: rules reaching each other over reversed edges, latest finish first
+        let mut assigned = HashSet::new();
+        let mut components = Vec::new();
+        for &start in finished.iter().rev() {
+            if !assigned.insert(start) {
+                continue;
+            }
+            let mut component = Vec::new();
+            let mut stack = vec![start];
+            while let Some(rule_id) = stack.pop() {
+                component.push(rule_id);
+                for &predecessor in &backward[&rule_id] {
+                    if assigned.insert(predecessor) {
+                        stack.push(predecessor);
+                    }
+                }
+            }
+            components.push(component);
+        }
+        components
+    }
+}
+
+/// Fields and fact types a rule's conditions read, and those its actions write
+fn rule_flow_usage(rule: &Rule) -> (BTreeSet<String>, BTreeSet<String>) {
+    let mut reads = BTreeSet::new();
+    for condition in &rule.conditions {
+        reads.extend(condition_fields(condition));
+    }
+    let mut writes: BTreeSet<String> = referenced_fields(std::slice::from_ref(rule))
+        .into_iter()
+        .filter(|field| field.written)
+        .map(|field| field.field)
+        .collect();
+
+    // Fact types are matched by value, so the type field itself only links rules of
+    // the same type
+    reads.remove(FACT_TYPE_FIELD);
+    writes.remove(FACT_TYPE_FIELD);
+    for (fact_type, access) in rule_fact_types(rule) {
+        let node = format!("type:{fact_type}");
+        match access {
+            FieldAccess::Reads => reads.insert(node),
+            FieldAccess::Writes => writes.insert(node),
+        };
+    }
+    (reads, writes)
+}
+
+/// Escape a string for use inside a quoted DOT identifier
+pub(crate) fn dot_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -942,7 +1449,7 @@ mod tests {
             })
             .collect();
 
-        Rule { id, name: name.to_string(), conditions, actions }
+        Rule { id, name: name.to_string(), conditions, actions, metadata: Default::default() }
     }
 
     #[test]
@@ -1106,4 +1613,132 @@ mod tests {
         assert!(stats.dependencies_found > 0);
         assert!(stats.analysis_time_ms < 10000); // Should complete quickly
     }
+
+    #[test]
+    fn test_field_graph_reads_and_writes() {
+        let rules = vec![
+            create_rule_with_fields(1, "Producer", &["input"], &["intermediate"]),
+            create_rule_with_fields(2, "Consumer", &["intermediate"], &["output"]),
+        ];
+
+        let graph = RuleFieldGraph::from_rules(&rules);
+        assert_eq!(graph.rules_using_field("intermediate"), vec![1, 2]);
+        assert_eq!(graph.rules_using_field("output"), vec![2]);
+        assert!(graph.rules_using_field("missing").is_empty());
+        assert!(graph.edges.contains(&GraphEdge {
+            rule_id: 1,
+            target: "field:intermediate".to_string(),
+            access: FieldAccess::Writes,
+        }));
+
+        let dot = graph.to_dot();
+        assert!(dot.contains("\"rule:1\" -> \"field:intermediate\" [label=\"writes\"];"));
+        assert!(dot.contains("\"field:intermediate\" -> \"rule:2\" [label=\"reads\"];"));
+    }
+
+    #[test]
+    fn test_field_graph_fact_types() {
+        let mut rule = create_rule_with_fields(1, "Termination \"final\" pay", &[], &[]);
+        rule.conditions.push(Condition::Simple {
+            field: FACT_TYPE_FIELD.to_string(),
+            operator: Operator::Equal,
+            value: FactValue::String("employee".to_string()),
+        });
+        rule.actions.push(Action {
+            action_type: ActionType::CreateFact {
+                data: crate::types::FactData {
+                    fields: HashMap::from([(
+                        FACT_TYPE_FIELD.to_string(),
+                        FactValue::String("payslip".to_string()),
+                    )]),
+                },
+            },
+        });
+
+        let graph = RuleFieldGraph::from_rules(&[rule]);
+        assert_eq!(graph.rules_using_fact_type("employee"), vec![1]);
+        assert_eq!(graph.rules_using_fact_type("payslip"), vec![1]);
+        let kinds: Vec<GraphNodeKind> = graph.nodes.iter().map(|node| node.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                GraphNodeKind::Rule,
+                GraphNodeKind::Field,
+                GraphNodeKind::FactType,
+                GraphNodeKind::FactType
+            ]
+        );
+        assert!(graph.to_dot().contains("label=\"Termination \\\"final\\\" pay\""));
+    }
+
+    #[test]
+    fn test_flow_graph_cycles_and_order() {
+        let rules = vec![
+            create_rule_with_fields(1, "Gross", &["hours"], &["gross"]),
+            create_rule_with_fields(2, "Tax", &["gross"], &["net"]),
+            create_rule_with_fields(3, "Report", &["net"], &["reported"]),
+        ];
+        let graph = RuleFlowGraph::from_rules(&rules);
+        assert_eq!(
+            graph.edges,
+            vec![
+                RuleFlowEdge { from: 1, to: 2, via: vec!["gross".to_string()] },
+                RuleFlowEdge { from: 2, to: 3, via: vec!["net".to_string()] },
+            ]
+        );
+        assert!(graph.is_acyclic());
+        assert_eq!(graph.topological_order(), Some(vec![1, 2, 3]));
+        assert_eq!(graph.cycle_through(1), None);
+
+        // Feeding the report back into hours closes a loop through all three rules
+        let mut looped = rules.clone();
+        looped.push(create_rule_with_fields(
+            4,
+            "Recount",
+            &["reported"],
+            &["hours"],
+        ));
+        looped.push(create_rule_with_fields(5, "Clamp", &["bonus"], &["bonus"]));
+        let graph = RuleFlowGraph::from_rules(&looped);
+        assert_eq!(graph.cycles(), vec![vec![1, 2, 3, 4], vec![5]]);
+        assert_eq!(graph.cycle_through(3), Some(vec![3, 4, 1, 2, 3]));
+        assert_eq!(graph.cycle_through(5), Some(vec![5, 5]));
+        assert_eq!(graph.topological_order(), None);
+
+        let dot = graph.to_dot();
+        assert!(dot.contains("\"rule:4\" -> \"rule:1\" [label=\"hours\", color=red];"));
+        assert!(dot.contains("\"rule:5\" -> \"rule:5\" [label=\"bonus\", color=red];"));
+    }
+
+    #[test]
+    fn test_flow_graph_fact_types() {
+        let of_type = |fact_type: &str| Condition::Simple {
+            field: FACT_TYPE_FIELD.to_string(),
+            operator: Operator::Equal,
+            value: FactValue::String(fact_type.to_string()),
+        };
+        let mut timesheet = create_rule_with_fields(1, "Timesheet", &[], &[]);
+        timesheet.conditions.push(of_type("shift"));
+        timesheet.actions.push(Action {
+            action_type: ActionType::CreateFact {
+                data: crate::types::FactData {
+                    fields: HashMap::from([(
+                        FACT_TYPE_FIELD.to_string(),
+                        FactValue::String("payslip".to_string()),
+                    )]),
+                },
+            },
+        });
+        let mut payslip = create_rule_with_fields(2, "Payslip", &[], &[]);
+        payslip.conditions.push(of_type("payslip"));
+        let mut other = create_rule_with_fields(3, "Invoice", &[], &[]);
+        other.conditions.push(of_type("invoice"));
+
+        let graph = RuleFlowGraph::from_rules(&[timesheet, payslip, other]);
+        assert_eq!(
+            graph.edges,
+            vec![RuleFlowEdge { from: 1, to: 2, via: vec!["type:payslip".to_string()] }]
+        );
+        assert!(graph.to_json().unwrap().contains("\"type:payslip\""));
+    }
 }
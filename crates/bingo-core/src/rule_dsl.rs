@@ -0,0 +1,786 @@
+//! Textual rule language
+//!
+//! Building `Rule` values by hand means assembling nested `Condition` and `Action`
+//! structs, which is verbose for anyone who is not writing Rust. This module parses a
+//! compact rule language into the same structs:
+//!
+//! ```text
+//! # Premium customers
+//! rule "Premium tier" id 10
+//! when order.total > 1000 and status == "active"
+//! then
+//!     set customer.tier = "premium";
+//!     log "Customer upgraded"
+//! end
+//! ```
+//!
+//! ## Conditions
+//!
+//! - **Comparisons**: `field OP value` with `==` (or `=`), `!=`, `>`, `>=`, `<`, `<=`,
+//!   `contains`, `contains_ignore_case`, `starts_with`, `ends_with`, `matches` (regex),
+//!   `in` and `not_in` (an `[array]` or a `"named list"`), `within`, `older_than` and
+//!   `overlaps`
+//! - **Logic**: `and`, `or`, `not` and parentheses; top-level `and` terms become
+//!   separate rule conditions so each gets its own alpha node
+//! - **Fields**: Identifiers may contain dots (`order.total`) and are used verbatim as
+//!   fact field names
+//! - **Values**: Integers, floats, `"strings"`, `true`, `false`, `null`, `[arrays]`,
+//!   `{ key: value }` objects, `date "2025-01-31T09:00:00Z"`, `duration "PT30M"` and
+//!   `interval "2025-01-31T09:00:00Z/2025-01-31T17:00:00Z"`
+//!
+//! ## Actions
+//!
+//! | Syntax | Action |
+//! |--------|--------|
+//! | `set field = value` | `SetField` |
+//! | `log "message"` | `Log` |
+//! | `increment field [by value]` | `IncrementField` (default step 1) |
+//! | `append value to field` | `AppendToArray` |
+//! | `merge { key: value, ... } into field` | `MergeObject` |
+//! | `create { field: value, ... }` | `CreateFact` |
+//! | `formula field = "expression"` | `Formula` |
+//! | `call name(param = field, ...) into field` | `CallCalculator` |
+//! | `alert severity "type" "message"` | `TriggerAlert` |
+//!
+//! Actions may be separated by `;` or `,`. `end` closes a rule and is optional before
+//! the next `rule` or the end of input. Rules without an explicit `id` are numbered
+//! after the highest ID seen so far. `#` and `//` start comments.
+
+use crate::error::{BingoError, BingoResult};
+use crate::types::{
+    Action, ActionType, AlertSeverity, Condition, FactData, FactValue, LogicalOperator, Operator,
+    Rule, RuleId,
+};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Parse every rule in `source`
+pub fn parse_rules(source: &str) -> BingoResult<Vec<Rule>> {
+    let tokens = Lexer::new(source).tokenize()?;
+    Parser::new(tokens).parse_rules()
+}
+
+/// Parse a source containing exactly one rule
+pub fn parse_rule(source: &str) -> BingoResult<Rule> {
+    let mut rules = parse_rules(source)?;
+    match rules.len() {
+        1 => Ok(rules.remove(0)),
+        count => Err(BingoError::rule_validation(format!(
+            "Expected exactly one rule, found {count}"
+        ))),
+    }
+}
+
+/// Line and column of a token, both starting at 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Position {
+    line: usize,
+    column: usize,
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+fn syntax_error(position: Position, message: impl fmt::Display) -> BingoError {
+    BingoError::rule_validation(format!("Rule syntax error at {position}: {message}"))
+}
+
+/// Value of a `date`, `duration` or `interval` literal
+fn parse_temporal_literal(kind: &str, text: &str) -> Result<FactValue, String> {
+    let parse_instant = |text: &str| {
+        chrono::DateTime::parse_from_rfc3339(text)
+            .map(|instant| instant.with_timezone(&chrono::Utc))
+            .map_err(|e| format!("invalid date \"{text}\": {e}"))
+    };
+    match kind {
+        "date" => parse_instant(text).map(FactValue::Date),
+        "duration" => FactValue::duration_from_iso(text).map_err(|e| e.to_string()),
+        _ => {
+            let (start, end) = text
+                .split_once('/')
+                .ok_or_else(|| format!("invalid interval \"{text}\": expected \"start/end\""))?;
+            Ok(FactValue::interval(
+                parse_instant(start)?,
+                parse_instant(end)?,
+            ))
+        }
+    }
+}
+
+/// Tokens of the rule language; keywords are recognised by the parser from identifiers
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Identifier(String),
+    String(String),
+    Integer(i64),
+    Float(f64),
+    Assign,
+    Equal,
+    NotEqual,
+    GreaterThan,
+    GreaterThanEqual,
+    LessThan,
+    LessThanEqual,
+    LeftParen,
+    RightParen,
+    LeftBracket,
+    RightBracket,
+    LeftBrace,
+    RightBrace,
+    Comma,
+    Semicolon,
+    Colon,
+    Eof,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Identifier(name) => write!(f, "'{name}'"),
+            Token::String(s) => write!(f, "\"{s}\""),
+            Token::Integer(n) => write!(f, "{n}"),
+            Token::Float(n) => write!(f, "{n}"),
+            Token::Assign => write!(f, "'='"),
+            Token::Equal => write!(f, "'=='"),
+            Token::NotEqual => write!(f, "'!='"),
+            Token::GreaterThan => write!(f, "'>'"),
+            Token::GreaterThanEqual => write!(f, "'>='"),
+            Token::LessThan => write!(f, "'<'"),
+            Token::LessThanEqual => write!(f, "'<='"),
+            Token::LeftParen => write!(f, "'('"),
+            Token::RightParen => write!(f, "')'"),
+            Token::LeftBracket => write!(f, "'['"),
+            Token::RightBracket => write!(f, "']'"),
+            Token::LeftBrace => write!(f, "'{{'"),
+            Token::RightBrace => write!(f, "'}}'"),
+            Token::Comma => write!(f, "','"),
+            Token::Semicolon => write!(f, "';'"),
+            Token::Colon => write!(f, "':'"),
+            Token::Eof => write!(f, "end of input"),
+        }
+    }
+}
+
+struct Lexer {
+    input: Vec<char>,
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+
+impl Lexer {
+    fn new(source: &str) -> Self {
+        Self { input: source.chars().collect(), offset: 0, line: 1, column: 1 }
+    }
+
+    fn current(&self) -> Option<char> {
+        self.input.get(self.offset).copied()
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input.get(self.offset + 1).copied()
+    }
+
+    fn advance(&mut self) {
+        if self.current() == Some('\n') {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        self.offset += 1;
+    }
+
+    fn position(&self) -> Position {
+        Position { line: self.line, column: self.column }
+    }
+
+    fn tokenize(mut self) -> BingoResult<Vec<(Token, Position)>> {
+        let mut tokens = Vec::new();
+
+        loop {
+            self.skip_whitespace_and_comments();
+            let position = self.position();
+            let Some(ch) = self.current() else {
+                tokens.push((Token::Eof, position));
+                return Ok(tokens);
+            };
+
+            let token = match ch {
+                '"' => self.read_string()?,
+                c if c.is_ascii_digit() => self.read_number()?,
+                '-' if self.peek().is_some_and(|c| c.is_ascii_digit()) => self.read_number()?,
+                c if c.is_alphabetic() || c == '_' => self.read_identifier(),
+                _ => self.read_symbol()?,
+            };
+            tokens.push((token, position));
+        }
+    }
+
+    fn skip_whitespace_and_comments(&mut self) {
+        while let Some(ch) = self.current() {
+            let comment = ch == '#' || (ch == '/' && self.peek() == Some('/'));
+            if comment {
+                while self.current().is_some_and(|c| c != '\n') {
+                    self.advance();
+                }
+            } else if ch.is_whitespace() {
+                self.advance();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn read_string(&mut self) -> BingoResult<Token> {
+        let start = self.position();
+        self.advance(); // opening quote
+
+        let mut value = String::new();
+        loop {
+            match self.current() {
+                None => return Err(syntax_error(start, "unterminated string")),
+                Some('"') => {
+                    self.advance();
+                    return Ok(Token::String(value));
+                }
+                Some('\\') => {
+                    self.advance();
+                    let escaped = match self.current() {
+                        Some('n') => '\n',
+                        Some('t') => '\t',
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some(other) => {
+                            return Err(syntax_error(
+                                self.position(),
+                                format!("unknown escape '\\{other}'"),
+                            ));
+                        }
+                        None => return Err(syntax_error(start, "unterminated string")),
+                    };
+                    value.push(escaped);
+                    self.advance();
+                }
+                Some(ch) => {
+                    value.push(ch);
+                    self.advance();
+                }
+            }
+        }
+    }
+
+    fn read_number(&mut self) -> BingoResult<Token> {
+        let start = self.position();
+        let mut text = String::new();
+        if self.current() == Some('-') {
+            text.push('-');
+            self.advance();
+        }
+
+        let mut is_float = false;
+        while let Some(ch) = self.current() {
+            if ch.is_ascii_digit() || ch == '_' {
+                if ch != '_' {
+                    text.push(ch);
+                }
+            } else if ch == '.' && !is_float && self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                is_float = true;
+                text.push(ch);
+            } else {
+                break;
+            }
+            self.advance();
+        }
+
+        if is_float {
+            text.parse()
+                .map(Token::Float)
+                .map_err(|_| syntax_error(start, format!("invalid number '{text}'")))
+        } else {
+            text.parse()
+                .map(Token::Integer)
+                .map_err(|_| syntax_error(start, format!("invalid number '{text}'")))
+        }
+    }
+
+    fn read_identifier(&mut self) -> Token {
+        let mut name = String::new();
+        while let Some(ch) = self.current() {
+            if ch.is_alphanumeric() || ch == '_' || ch == '.' {
+                name.push(ch);
+                self.advance();
+            } else {
+                break;
+            }
+        }
+        Token::Identifier(name)
+    }
+
+    fn read_symbol(&mut self) -> BingoResult<Token> {
+        let position = self.position();
+        let ch = self.current().unwrap_or_default();
+        let next = self.peek();
+
+        let (token, width) = match (ch, next) {
+            ('=', Some('=')) => (Token::Equal, 2),
+            ('!', Some('=')) => (Token::NotEqual, 2),
+            ('>', Some('=')) => (Token::GreaterThanEqual, 2),
+            ('<', Some('=')) => (Token::LessThanEqual, 2),
+            ('=', _) => (Token::Assign, 1),
+            ('>', _) => (Token::GreaterThan, 1),
+            ('<', _) => (Token::LessThan, 1),
+            ('(', _) => (Token::LeftParen, 1),
+            (')', _) => (Token::RightParen, 1),
+            ('[', _) => (Token::LeftBracket, 1),
+            (']', _) => (Token::RightBracket, 1),
+            ('{', _) => (Token::LeftBrace, 1),
+            ('}', _) => (Token::RightBrace, 1),
+            (',', _) => (Token::Comma, 1),
+            (';', _) => (Token::Semicolon, 1),
+            (':', _) => (Token::Colon, 1),
+            _ => {
+                return Err(syntax_error(
+                    position,
+                    format!("unexpected character '{ch}'"),
+                ));
+            }
+        };
+
+        for _ in 0..width {
+            self.advance();
+        }
+        Ok(token)
+    }
+}
+
+struct Parser {
+    tokens: Vec<(Token, Position)>,
+    index: usize,
+    next_rule_id: RuleId,
+}
+
+impl Parser {
+    fn new(tokens: Vec<(Token, Position)>) -> Self {
+        Self { tokens, index: 0, next_rule_id: 1 }
+    }
+
+    fn current(&self) -> &Token {
+        &self.tokens[self.index].0
+    }
+
+    fn position(&self) -> Position {
+        self.tokens[self.index].1
+    }
+
+    fn advance(&mut self) -> Token {
+        let token = self.tokens[self.index].0.clone();
+        if token != Token::Eof {
+            self.index += 1;
+        }
+        token
+    }
+
+    fn unexpected(&self, expected: &str) -> BingoError {
+        syntax_error(
+            self.position(),
+            format!("expected {expected}, found {}", self.current()),
+        )
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.current(), Token::Identifier(name) if name == keyword)
+    }
+
+    fn accept_keyword(&mut self, keyword: &str) -> bool {
+        let accepted = self.is_keyword(keyword);
+        if accepted {
+            self.advance();
+        }
+        accepted
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> BingoResult<()> {
+        if self.accept_keyword(keyword) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("'{keyword}'")))
+        }
+    }
+
+    fn expect(&mut self, token: Token) -> BingoResult<()> {
+        if *self.current() == token {
+            self.advance();
+            Ok(())
+        } else {
+            Err(self.unexpected(&token.to_string()))
+        }
+    }
+
+    fn expect_identifier(&mut self, what: &str) -> BingoResult<String> {
+        match self.current() {
+            Token::Identifier(_) => match self.advance() {
+                Token::Identifier(name) => Ok(name),
+                _ => unreachable!(),
+            },
+            _ => Err(self.unexpected(what)),
+        }
+    }
+
+    fn expect_string(&mut self, what: &str) -> BingoResult<String> {
+        match self.current() {
+            Token::String(_) => match self.advance() {
+                Token::String(value) => Ok(value),
+                _ => unreachable!(),
+            },
+            _ => Err(self.unexpected(what)),
+        }
+    }
+
+    fn parse_rules(mut self) -> BingoResult<Vec<Rule>> {
+        let mut rules = Vec::new();
+        while *self.current() != Token::Eof {
+            rules.push(self.parse_rule()?);
+        }
+        Ok(rules)
+    }
+
+    fn parse_rule(&mut self) -> BingoResult<Rule> {
+        self.expect_keyword("rule")?;
+        let name = self.expect_string("rule name")?;
+
+        let id = if self.accept_keyword("id") {
+            match self.advance() {
+                Token::Integer(id) if id >= 0 => id as RuleId,
+                _ => {
+                    self.index -= 1;
+                    return Err(self.unexpected("non-negative rule id"));
+                }
+            }
+        } else {
+            self.next_rule_id
+        };
+        self.next_rule_id = self.next_rule_id.max(id + 1);
+
+        self.expect_keyword("when")?;
+        let conditions = match self.parse_or()? {
+            Condition::And { conditions } => conditions,
+            condition => vec![condition],
+        };
+
+        self.expect_keyword("then")?;
+        let mut actions = vec![self.parse_action()?];
+        loop {
+            while matches!(self.current(), Token::Semicolon | Token::Comma) {
+                self.advance();
+            }
+            if self.accept_keyword("end")
+                || self.is_keyword("rule")
+                || *self.current() == Token::Eof
+            {
+                break;
+            }
+            actions.push(self.parse_action()?);
+        }
+
+        Ok(Rule { id, name, conditions, actions, metadata: Default::default() })
+    }
+
+    fn parse_or(&mut self) -> BingoResult<Condition> {
+        let mut conditions = vec![self.parse_and()?];
+        while self.accept_keyword("or") {
+            conditions.push(self.parse_and()?);
+        }
+        Ok(if conditions.len() == 1 {
+            conditions.remove(0)
+        } else {
+            Condition::Or { conditions }
+        })
+    }
+
+    fn parse_and(&mut self) -> BingoResult<Condition> {
+        let mut conditions = vec![self.parse_not()?];
+        while self.accept_keyword("and") {
+            conditions.push(self.parse_not()?);
+        }
+        Ok(if conditions.len() == 1 {
+            conditions.remove(0)
+        } else {
+            Condition::And { conditions }
+        })
+    }
+
+    fn parse_not(&mut self) -> BingoResult<Condition> {
+        if self.accept_keyword("not") {
+            let negated = self.parse_not()?;
+            return Ok(Condition::Complex {
+                operator: LogicalOperator::Not,
+                conditions: vec![negated],
+            });
+        }
+
+        if *self.current() == Token::LeftParen {
+            self.advance();
+            let condition = self.parse_or()?;
+            self.expect(Token::RightParen)?;
+            return Ok(condition);
+        }
+
+        self.parse_comparison()
+    }
+
+    fn parse_comparison(&mut self) -> BingoResult<Condition> {
+        let field = self.expect_identifier("field name")?;
+
+        let operator = match self.current() {
+            Token::Equal | Token::Assign => Operator::Equal,
+            Token::NotEqual => Operator::NotEqual,
+            Token::GreaterThan => Operator::GreaterThan,
+            Token::GreaterThanEqual => Operator::GreaterThanOrEqual,
+            Token::LessThan => Operator::LessThan,
+            Token::LessThanEqual => Operator::LessThanOrEqual,
+            Token::Identifier(name) if name == "contains" => Operator::Contains,
+            Token::Identifier(name) if name == "starts_with" => Operator::StartsWith,
+            Token::Identifier(name) if name == "ends_with" => Operator::EndsWith,
+            Token::Identifier(name) if name == "within" => Operator::Within,
+            Token::Identifier(name) if name == "older_than" => Operator::OlderThan,
+            Token::Identifier(name) if name == "overlaps" => Operator::Overlaps,
+            Token::Identifier(name) if name == "matches" => Operator::Matches,
+            Token::Identifier(name) if name == "in" => Operator::In,
+            Token::Identifier(name) if name == "not_in" => Operator::NotIn,
+            Token::Identifier(name) if name == "contains_ignore_case" => {
+                Operator::ContainsIgnoreCase
+            }
+            _ => return Err(self.unexpected("comparison operator")),
+        };
+        self.advance();
+
+        let value = self.parse_value()?;
+        Ok(Condition::Simple { field, operator, value })
+    }
+
+    fn parse_value(&mut self) -> BingoResult<FactValue> {
+        let value = match self.current() {
+            Token::String(_) => FactValue::String(self.expect_string("string")?),
+            Token::Integer(n) => {
+                let n = *n;
+                self.advance();
+                FactValue::Integer(n)
+            }
+            Token::Float(n) => {
+                let n = *n;
+                self.advance();
+                FactValue::Float(n)
+            }
+            Token::Identifier(name) if name == "true" || name == "false" => {
+                let value = name == "true";
+                self.advance();
+                FactValue::Boolean(value)
+            }
+            Token::Identifier(name) if name == "null" => {
+                self.advance();
+                FactValue::Null
+            }
+            Token::LeftBracket => {
+                self.advance();
+                let mut items = Vec::new();
+                while *self.current() != Token::RightBracket {
+                    items.push(self.parse_value()?);
+                    if *self.current() != Token::Comma {
+                        break;
+                    }
+                    self.advance();
+                }
+                self.expect(Token::RightBracket)?;
+                FactValue::Array(items)
+            }
+            Token::LeftBrace => FactValue::Object(self.parse_object()?),
+            Token::Identifier(name)
+                if matches!(name.as_str(), "date" | "duration" | "interval") =>
+            {
+                let kind = name.clone();
+                self.advance();
+                let position = self.position();
+                let text = self.expect_string(&format!("{kind} string"))?;
+                parse_temporal_literal(&kind, &text)
+                    .map_err(|message| syntax_error(position, message))?
+            }
+            _ => return Err(self.unexpected("value")),
+        };
+        Ok(value)
+    }
+
+    fn parse_object(&mut self) -> BingoResult<HashMap<String, FactValue>> {
+        self.expect(Token::LeftBrace)?;
+        let mut fields = HashMap::new();
+        while *self.current() != Token::RightBrace {
+            let key = match self.current() {
+                Token::String(_) => self.expect_string("field name")?,
+                _ => self.expect_identifier("field name")?,
+            };
+            self.expect(Token::Colon)?;
+            fields.insert(key, self.parse_value()?);
+            if *self.current() != Token::Comma {
+                break;
+            }
+            self.advance();
+        }
+        self.expect(Token::RightBrace)?;
+        Ok(fields)
+    }
+
+    fn parse_action(&mut self) -> BingoResult<Action> {
+        let position = self.position();
+        let keyword = self.expect_identifier("action")?;
+
+        let action_type = match keyword.as_str() {
+            "set" => {
+                let field = self.expect_identifier("field name")?;
+                self.expect(Token::Assign)?;
+                ActionType::SetField { field, value: self.parse_value()? }
+            }
+            "log" => ActionType::Log { message: self.expect_string("log message")? },
+            "increment" => {
+                let field = self.expect_identifier("field name")?;
+                let increment = if self.accept_keyword("by") {
+                    self.parse_value()?
+                } else {
+                    FactValue::Integer(1)
+                };
+                ActionType::IncrementField { field, increment }
+            }
+            "append" => {
+                let value = self.parse_value()?;
+                self.expect_keyword("to")?;
+                ActionType::AppendToArray { field: self.expect_identifier("field name")?, value }
+            }
+            "merge" => {
+                let entries = self.parse_object()?;
+                self.expect_keyword("into")?;
+                ActionType::MergeObject { field: self.expect_identifier("field name")?, entries }
+            }
+            "create" => ActionType::CreateFact { data: FactData { fields: self.parse_object()? } },
+            "formula" => {
+                let output_field = self.expect_identifier("field name")?;
+                self.expect(Token::Assign)?;
+                ActionType::Formula { expression: self.expect_string("expression")?, output_field }
+            }
+            "call" => {
+                let calculator_name = self.expect_identifier("calculator name")?;
+                self.expect(Token::LeftParen)?;
+                let mut input_mapping = HashMap::new();
+                while *self.current() != Token::RightParen {
+                    let parameter = self.expect_identifier("calculator input")?;
+                    self.expect(Token::Assign)?;
+                    input_mapping.insert(parameter, self.expect_identifier("field name")?);
+                    if *self.current() != Token::Comma {
+                        break;
+                    }
+                    self.advance();
+                }
+                self.expect(Token::RightParen)?;
+                self.expect_keyword("into")?;
+                ActionType::CallCalculator {
+                    calculator_name,
+                    input_mapping,
+                    output_field: self.expect_identifier("field name")?,
+                }
+            }
+            "alert" => {
+                let severity = match self.expect_identifier("alert severity")?.as_str() {
+                    "low" => AlertSeverity::Low,
+                    "medium" => AlertSeverity::Medium,
+                    "high" => AlertSeverity::High,
+                    "critical" => AlertSeverity::Critical,
+                    other => {
+                        return Err(syntax_error(
+                            position,
+                            format!("unknown alert severity '{other}'"),
+                        ));
+                    }
+                };
+                ActionType::TriggerAlert {
+                    alert_type: self.expect_string("alert type")?,
+                    message: self.expect_string("alert message")?,
+                    severity,
+                    metadata: HashMap::new(),
+                }
+            }
+            other => return Err(syntax_error(position, format!("unknown action '{other}'"))),
+        };
+
+        Ok(Action { action_type })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_single_comparison_rule() {
+        let rule =
+            parse_rule(r#"rule "x" when order.total > 1000 then set customer.tier = "premium""#)
+                .unwrap();
+
+        assert_eq!(rule.id, 1);
+        assert_eq!(rule.name, "x");
+        assert!(matches!(
+            &rule.conditions[..],
+            [Condition::Simple { field, operator: Operator::GreaterThan, value: FactValue::Integer(1000) }]
+                if field == "order.total"
+        ));
+        assert!(matches!(
+            &rule.actions[0].action_type,
+            ActionType::SetField { field, value: FactValue::String(tier) }
+                if field == "customer.tier" && tier == "premium"
+        ));
+    }
+
+    #[test]
+    fn test_logical_operators_and_precedence() {
+        let rule = parse_rule(
+            r#"rule "r" when a == 1 and (b < 2.5 or not c contains "x") then log "hit" end"#,
+        )
+        .unwrap();
+
+        // Top-level `and` terms become separate conditions
+        assert_eq!(rule.conditions.len(), 2);
+        let Condition::Or { conditions } = &rule.conditions[1] else {
+            panic!("expected or condition, got {:?}", rule.conditions[1]);
+        };
+        assert!(matches!(
+            &conditions[1],
+            Condition::Complex { operator: LogicalOperator::Not, .. }
+        ));
+    }
+
+    #[test]
+    fn test_ids_are_assigned_after_explicit_ones() {
+        let rules = parse_rules(
+            r#"
+            rule "a" id 10 when x > 1 then log "a"
+            // second rule gets the next free id
+            rule "b" when x > 2 then log "b"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            rules.iter().map(|rule| rule.id).collect::<Vec<_>>(),
+            vec![10, 11]
+        );
+    }
+
+    #[test]
+    fn test_errors_report_position() {
+        let error = parse_rules("rule \"a\"\nwhen x >\nthen log \"a\"").unwrap_err();
+        assert!(error.to_string().contains("line 3, column 1"), "{error}");
+
+        assert!(parse_rules(r#"rule "a" when x > 1 then explode"#).is_err());
+        assert!(parse_rule("").is_err());
+    }
+}
@@ -0,0 +1,215 @@
+//! Duplicate rule detection
+//!
+//! Two rules with the same ID, or with the same conditions and actions, fire twice for
+//! every match. [`DuplicateRulePolicy`] decides whether `add_rule` accepts such a rule,
+//! rejects it, or replaces the rule it duplicates. Rules sharing their conditions but
+//! not their actions are near-duplicates: they are allowed, since they do different
+//! things, but are listed alongside the duplicates for review.
+//!
+//! Conditions are compared regardless of their order; rule names are ignored.
+
+use crate::types::{Rule, RuleId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// What `add_rule` does with a rule duplicating one already in the engine
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateRulePolicy {
+    /// Add it alongside the rules it duplicates; only a shared ID is logged
+    #[default]
+    Allow,
+    /// Refuse the new rule
+    Reject,
+    /// Remove the rules it duplicates and add it in place of the first
+    Replace,
+}
+
+/// How the rules of a [`DuplicateRules`] group relate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateKind {
+    /// Rules sharing an ID
+    SameId,
+    /// Rules with the same conditions and actions
+    Identical,
+    /// Rules with the same conditions but different actions
+    SameConditions,
+}
+
+/// Rules duplicating each other
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DuplicateRules {
+    pub kind: DuplicateKind,
+    /// In the order the rules were added
+    pub rule_ids: Vec<RuleId>,
+    /// Names of the rules, in the same order
+    pub rule_names: Vec<String>,
+}
+
+impl DuplicateRules {
+    fn new(kind: DuplicateKind, rules: &[Rule], indices: &[usize]) -> Self {
+        Self {
+            kind,
+            rule_ids: indices.iter().map(|&index| rules[index].id).collect(),
+            rule_names: indices.iter().map(|&index| rules[index].name.clone()).collect(),
+        }
+    }
+}
+
+/// Conditions of a rule, independent of their order
+fn condition_signature(rule: &Rule) -> Vec<String> {
+    let mut conditions: Vec<String> =
+        rule.conditions.iter().map(|condition| format!("{condition:?}")).collect();
+    conditions.sort_unstable();
+    conditions
+}
+
+fn action_signature(rule: &Rule) -> String {
+    format!("{:?}", rule.actions)
+}
+
+/// Rules in `rules` that `rule` duplicates, with how it duplicates each
+///
+/// Near-duplicates are not reported, since `add_rule` accepts them.
+pub(crate) fn duplicates_of(rule: &Rule, rules: &[Rule]) -> Vec<(RuleId, DuplicateKind)> {
+    let conditions = condition_signature(rule);
+    let actions = action_signature(rule);
+    rules
+        .iter()
+        .filter_map(|existing| {
+            if existing.id == rule.id {
+                Some((existing.id, DuplicateKind::SameId))
+            } else if existing.conditions.len() == rule.conditions.len()
+                && existing.actions.len() == rule.actions.len()
+                && action_signature(existing) == actions
+                && condition_signature(existing) == conditions
+            {
+                Some((existing.id, DuplicateKind::Identical))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Groups of duplicate and near-duplicate rules, exact duplicates first
+///
+/// Copies of one rule added under the same ID are only listed as sharing the ID.
+pub(crate) fn find_duplicates(rules: &[Rule]) -> Vec<DuplicateRules> {
+    let mut by_id: HashMap<RuleId, Vec<usize>> = HashMap::new();
+    let mut by_conditions: HashMap<Vec<String>, Vec<usize>> = HashMap::new();
+    for (index, rule) in rules.iter().enumerate() {
+        by_id.entry(rule.id).or_default().push(index);
+        by_conditions.entry(condition_signature(rule)).or_default().push(index);
+    }
+
+    let mut groups: Vec<DuplicateRules> = by_id
+        .into_values()
+        .filter(|indices| indices.len() > 1)
+        .map(|indices| DuplicateRules::new(DuplicateKind::SameId, rules, &indices))
+        .collect();
+
+    for indices in by_conditions.into_values().filter(|indices| indices.len() > 1) {
+        let mut by_actions: Vec<(String, Vec<usize>)> = Vec::new();
+        for index in indices {
+            let actions = action_signature(&rules[index]);
+            match by_actions.iter_mut().find(|(existing, _)| *existing == actions) {
+                Some((_, identical)) => identical.push(index),
+                None => by_actions.push((actions, vec![index])),
+            }
+        }
+
+        let distinct: Vec<usize> = by_actions.iter().map(|(_, identical)| identical[0]).collect();
+        for (_, identical) in by_actions {
+            let first_id = rules[identical[0]].id;
+            if identical.iter().any(|&index| rules[index].id != first_id) {
+                groups.push(DuplicateRules::new(
+                    DuplicateKind::Identical,
+                    rules,
+                    &identical,
+                ));
+            }
+        }
+        if distinct.len() > 1 {
+            groups.push(DuplicateRules::new(
+                DuplicateKind::SameConditions,
+                rules,
+                &distinct,
+            ));
+        }
+    }
+
+    groups.sort_by(|a, b| a.kind.cmp(&b.kind).then_with(|| a.rule_ids.cmp(&b.rule_ids)));
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Action, ActionType, Condition, FactValue, Operator};
+
+    fn rule(id: RuleId, fields: &[&str], flag: &str) -> Rule {
+        Rule {
+            id,
+            name: format!("{id} sets {flag}"),
+            conditions: fields
+                .iter()
+                .map(|field| Condition::Simple {
+                    field: field.to_string(),
+                    operator: Operator::GreaterThan,
+                    value: FactValue::Integer(0),
+                })
+                .collect(),
+            actions: vec![Action {
+                action_type: ActionType::SetField {
+                    field: flag.to_string(),
+                    value: FactValue::Boolean(true),
+                },
+            }],
+            metadata: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_duplicates_of_ignores_condition_order_and_near_duplicates() {
+        let rules =
+            vec![rule(1, &["a", "b"], "x"), rule(2, &["a"], "x"), rule(3, &["b", "a"], "y")];
+        assert_eq!(
+            duplicates_of(&rule(4, &["b", "a"], "x"), &rules),
+            vec![(1, DuplicateKind::Identical)]
+        );
+        assert_eq!(
+            duplicates_of(&rule(2, &["c"], "z"), &rules),
+            vec![(2, DuplicateKind::SameId)]
+        );
+        assert!(duplicates_of(&rule(5, &["a", "b"], "z"), &rules).is_empty());
+    }
+
+    #[test]
+    fn test_find_duplicates_groups_rules_by_kind() {
+        let rules = vec![
+            rule(1, &["a", "b"], "x"),
+            rule(2, &["b", "a"], "x"),
+            rule(3, &["a", "b"], "y"),
+            rule(4, &["c"], "x"),
+            rule(4, &["d"], "x"),
+        ];
+        let groups: Vec<_> = find_duplicates(&rules)
+            .into_iter()
+            .map(|group| (group.kind, group.rule_ids))
+            .collect();
+        assert_eq!(
+            groups,
+            vec![
+                (DuplicateKind::SameId, vec![4, 4]),
+                (DuplicateKind::Identical, vec![1, 2]),
+                (DuplicateKind::SameConditions, vec![1, 3]),
+            ]
+        );
+        assert_eq!(
+            find_duplicates(&rules)[2].rule_names,
+            vec!["1 sets x", "3 sets y"]
+        );
+    }
+}
@@ -0,0 +1,538 @@
+//! Mutation testing for rulesets
+//!
+//! A rule test suite that passes tells little about how well it pins down rule
+//! behaviour. Mutation testing measures that: the `MutationTester` derives many
+//! slightly wrong variants ("mutants") of a ruleset and runs the suite against each.
+//! A mutant the suite notices is *killed*; one it does not is a *survivor* and
+//! points at a condition whose exact threshold or operator is never tested.
+//!
+//! Mutations are applied to simple field comparisons anywhere in a rule, including
+//! inside logical groups and aggregation or stream filters:
+//!
+//! - **Operator flips**: Boundary changes (`>` to `>=`) and negations (`>` to `<=`,
+//!   `==` to `!=`)
+//! - **Threshold perturbations**: Numeric comparison values nudged up and down
+//!
+//! Each mutant runs on a fresh engine, so the suite's cases must be self-contained.
+
+use crate::engine::BingoEngine;
+use crate::error::{BingoError, BingoResult};
+use crate::golden::render_results;
+use crate::rete_nodes::RuleExecutionResult;
+use crate::types::{Condition, Fact, FactId, FactValue, Operator, Rule, RuleId};
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// Kinds of mutation the tester applies
+#[derive(Debug, Clone, PartialEq)]
+pub enum MutationKind {
+    /// Comparison operator replaced by another
+    OperatorFlip { from: Operator, to: Operator },
+    /// Comparison value moved to a nearby value
+    ThresholdPerturbation { from: FactValue, to: FactValue },
+}
+
+/// One mutation of one simple condition in a rule
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mutation {
+    pub rule_id: RuleId,
+    pub rule_name: String,
+    /// Position of the mutated condition among the rule's simple comparisons, in
+    /// depth-first order
+    pub site: usize,
+    /// Field the mutated condition compares
+    pub field: String,
+    pub kind: MutationKind,
+}
+
+impl fmt::Display for Mutation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "rule {} ({}) comparison #{} on '{}': ",
+            self.rule_id, self.rule_name, self.site, self.field
+        )?;
+        match &self.kind {
+            MutationKind::OperatorFlip { from, to } => write!(f, "{from:?} -> {to:?}"),
+            MutationKind::ThresholdPerturbation { from, to } => write!(f, "{from} -> {to}"),
+        }
+    }
+}
+
+/// Which mutations to generate
+#[derive(Debug, Clone, PartialEq)]
+pub struct MutationConfig {
+    pub operator_flips: bool,
+    pub threshold_perturbations: bool,
+    /// Amount integer thresholds are moved up and down
+    pub integer_step: i64,
+    /// Fraction float thresholds are moved up and down (absolute for a zero threshold)
+    pub relative_step: f64,
+}
+
+impl Default for MutationConfig {
+    fn default() -> Self {
+        Self {
+            operator_flips: true,
+            threshold_perturbations: true,
+            integer_step: 1,
+            relative_step: 0.1,
+        }
+    }
+}
+
+/// What a rule test case expects from its facts
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expectation {
+    /// Exactly these `(rule, fact)` firings, in any order
+    Firings(BTreeSet<(RuleId, FactId)>),
+    /// Output rendered as in a golden file
+    Golden(String),
+}
+
+/// Facts and the results a ruleset must produce for them
+#[derive(Debug, Clone)]
+pub struct RuleTestCase {
+    pub name: String,
+    pub facts: Vec<Fact>,
+    pub expectation: Expectation,
+}
+
+impl RuleTestCase {
+    /// Case expecting exactly the given `(rule, fact)` firings
+    pub fn expecting_firings(
+        name: impl Into<String>,
+        facts: Vec<Fact>,
+        firings: impl IntoIterator<Item = (RuleId, FactId)>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            facts,
+            expectation: Expectation::Firings(firings.into_iter().collect()),
+        }
+    }
+
+    /// Case expecting output identical to a golden file's contents
+    pub fn expecting_golden(
+        name: impl Into<String>,
+        facts: Vec<Fact>,
+        golden: impl Into<String>,
+    ) -> Self {
+        Self { name: name.into(), facts, expectation: Expectation::Golden(golden.into()) }
+    }
+
+    /// Whether a ruleset's results satisfy the case
+    pub fn is_satisfied_by(&self, results: &[RuleExecutionResult]) -> BingoResult<bool> {
+        Ok(match &self.expectation {
+            Expectation::Firings(expected) => {
+                let fired: BTreeSet<(RuleId, FactId)> =
+                    results.iter().map(|result| (result.rule_id, result.fact_id)).collect();
+                &fired == expected
+            }
+            Expectation::Golden(expected) => &render_results(results)? == expected,
+        })
+    }
+}
+
+/// Outcome of running the suite against one mutant
+#[derive(Debug, Clone, PartialEq)]
+pub enum MutantOutcome {
+    /// A test case failed; names the first one that did
+    Killed { case: String },
+    /// Every test case still passed
+    Survived,
+    /// The mutated ruleset was rejected by the engine
+    Invalid { error: String },
+}
+
+/// Results of mutation testing a ruleset
+#[derive(Debug, Clone, Default)]
+pub struct MutationReport {
+    pub outcomes: Vec<(Mutation, MutantOutcome)>,
+}
+
+impl MutationReport {
+    /// Mutations the suite did not catch
+    pub fn survivors(&self) -> impl Iterator<Item = &Mutation> {
+        self.outcomes
+            .iter()
+            .filter(|(_, outcome)| *outcome == MutantOutcome::Survived)
+            .map(|(mutation, _)| mutation)
+    }
+
+    /// Number of mutants killed by the suite
+    pub fn killed(&self) -> usize {
+        self.outcomes
+            .iter()
+            .filter(|(_, outcome)| matches!(outcome, MutantOutcome::Killed { .. }))
+            .count()
+    }
+
+    /// Number of mutants the engine rejected, which count towards neither side
+    pub fn invalid(&self) -> usize {
+        self.outcomes
+            .iter()
+            .filter(|(_, outcome)| matches!(outcome, MutantOutcome::Invalid { .. }))
+            .count()
+    }
+
+    /// Fraction of valid mutants killed; 1.0 when there were none
+    pub fn mutation_score(&self) -> f64 {
+        let valid = self.outcomes.len() - self.invalid();
+        if valid == 0 {
+            1.0
+        } else {
+            self.killed() as f64 / valid as f64
+        }
+    }
+}
+
+impl fmt::Display for MutationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} mutants: {} killed, {} survived, {} invalid (score {:.1}%)",
+            self.outcomes.len(),
+            self.killed(),
+            self.survivors().count(),
+            self.invalid(),
+            self.mutation_score() * 100.0
+        )?;
+        for mutation in self.survivors() {
+            writeln!(f, "  survived: {mutation}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Generates mutants of a ruleset and runs a test suite against them
+#[derive(Debug, Clone)]
+pub struct MutationTester {
+    rules: Vec<Rule>,
+    config: MutationConfig,
+    /// Only mutate these rules when set
+    targets: Option<BTreeSet<RuleId>>,
+}
+
+impl MutationTester {
+    /// Tester for a ruleset with the default mutation config
+    pub fn new(rules: Vec<Rule>) -> Self {
+        Self { rules, config: MutationConfig::default(), targets: None }
+    }
+
+    /// Use a different mutation config
+    pub fn with_config(mut self, config: MutationConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Only mutate the given rules, e.g. the critical compliance rules
+    ///
+    /// Other rules stay in the ruleset unchanged.
+    pub fn only_rules(mut self, rule_ids: impl IntoIterator<Item = RuleId>) -> Self {
+        self.targets = Some(rule_ids.into_iter().collect());
+        self
+    }
+
+    /// Every mutation the config produces for the targeted rules
+    pub fn mutations(&self) -> Vec<Mutation> {
+        let mut mutations = Vec::new();
+        for rule in &self.rules {
+            if self.targets.as_ref().is_some_and(|targets| !targets.contains(&rule.id)) {
+                continue;
+            }
+
+            let mut conditions = rule.conditions.clone();
+            let mut site = 0;
+            for_each_comparison(&mut conditions, &mut |field, operator, value| {
+                let mutation = |kind| Mutation {
+                    rule_id: rule.id,
+                    rule_name: rule.name.clone(),
+                    site,
+                    field: field.clone(),
+                    kind,
+                };
+                if self.config.operator_flips {
+                    for to in operator_flips(operator) {
+                        mutations.push(mutation(MutationKind::OperatorFlip {
+                            from: operator.clone(),
+                            to,
+                        }));
+                    }
+                }
+                if self.config.threshold_perturbations {
+                    for to in self.perturbations(value) {
+                        mutations.push(mutation(MutationKind::ThresholdPerturbation {
+                            from: value.clone(),
+                            to,
+                        }));
+                    }
+                }
+                site += 1;
+            });
+        }
+        mutations
+    }
+
+    /// Run the suite against every mutant
+    ///
+    /// Fails if the suite does not pass against the unmutated ruleset, since
+    /// survivors would be meaningless.
+    pub fn run(&self, suite: &[RuleTestCase]) -> BingoResult<MutationReport> {
+        if let Some(case) = first_failing_case(&self.rules, suite)? {
+            return Err(BingoError::rule_validation(format!(
+                "Rule test case '{case}' fails against the unmutated ruleset"
+            )));
+        }
+
+        let mut report = MutationReport::default();
+        for mutation in self.mutations() {
+            let outcome = match first_failing_case(&self.mutate(&mutation), suite) {
+                Ok(Some(case)) => MutantOutcome::Killed { case },
+                Ok(None) => MutantOutcome::Survived,
+                Err(error) => MutantOutcome::Invalid { error: error.to_string() },
+            };
+            report.outcomes.push((mutation, outcome));
+        }
+        Ok(report)
+    }
+
+    /// Copy of the ruleset with one mutation applied
+    pub fn mutate(&self, mutation: &Mutation) -> Vec<Rule> {
+        let mut rules = self.rules.clone();
+        if let Some(rule) = rules.iter_mut().find(|rule| rule.id == mutation.rule_id) {
+            let mut site = 0;
+            for_each_comparison(&mut rule.conditions, &mut |_, operator, value| {
+                if site == mutation.site {
+                    match &mutation.kind {
+                        MutationKind::OperatorFlip { to, .. } => *operator = to.clone(),
+                        MutationKind::ThresholdPerturbation { to, .. } => *value = to.clone(),
+                    }
+                }
+                site += 1;
+            });
+        }
+        rules
+    }
+
+    fn perturbations(&self, value: &FactValue) -> Vec<FactValue> {
+        match value {
+            FactValue::Integer(threshold) => vec![
+                FactValue::Integer(threshold.saturating_sub(self.config.integer_step)),
+                FactValue::Integer(threshold.saturating_add(self.config.integer_step)),
+            ],
+            FactValue::Float(threshold) => {
+                let step = if *threshold == 0.0 {
+                    self.config.relative_step
+                } else {
+                    threshold.abs() * self.config.relative_step
+                };
+                vec![FactValue::Float(threshold - step), FactValue::Float(threshold + step)]
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Operators a comparison operator is flipped to
+fn operator_flips(operator: &Operator) -> Vec<Operator> {
+    match operator {
+        Operator::GreaterThan => vec![Operator::GreaterThanOrEqual, Operator::LessThanOrEqual],
+        Operator::GreaterThanOrEqual => vec![Operator::GreaterThan, Operator::LessThan],
+        Operator::LessThan => vec![Operator::LessThanOrEqual, Operator::GreaterThanOrEqual],
+        Operator::LessThanOrEqual => vec![Operator::LessThan, Operator::GreaterThan],
+        Operator::Equal => vec![Operator::NotEqual],
+        Operator::NotEqual => vec![Operator::Equal],
+        Operator::In => vec![Operator::NotIn],
+        Operator::NotIn => vec![Operator::In],
+        Operator::Contains
+        | Operator::StartsWith
+        | Operator::EndsWith
+        | Operator::Within
+        | Operator::OlderThan
+        | Operator::Overlaps
+        | Operator::Matches
+        | Operator::ContainsIgnoreCase => Vec::new(),
+    }
+}
+
+/// Visit every simple comparison in depth-first order
+fn for_each_comparison(
+    conditions: &mut [Condition],
+    visit: &mut dyn FnMut(&String, &mut Operator, &mut FactValue),
+) {
+    for condition in conditions {
+        match condition {
+            Condition::Simple { field, operator, value } => visit(field, operator, value),
+            Condition::Complex { conditions, .. }
+            | Condition::And { conditions }
+            | Condition::Or { conditions } => for_each_comparison(conditions, visit),
+            Condition::Aggregation(aggregation) => {
+                if let Some(having) = aggregation.having.as_deref_mut() {
+                    for_each_comparison(std::slice::from_mut(having), visit);
+                }
+            }
+            Condition::Stream(stream) => {
+                for nested in [stream.filter.as_deref_mut(), stream.having.as_deref_mut()]
+                    .into_iter()
+                    .flatten()
+                {
+                    for_each_comparison(std::slice::from_mut(nested), visit);
+                }
+            }
+            Condition::Sequence(sequence) => for_each_comparison(&mut sequence.steps, visit),
+            Condition::Accumulate(accumulate) => {
+                for_each_comparison(std::slice::from_mut(accumulate.source.as_mut()), visit)
+            }
+        }
+    }
+}
+
+/// Name of the first case the ruleset fails, if any
+fn first_failing_case(rules: &[Rule], suite: &[RuleTestCase]) -> BingoResult<Option<String>> {
+    for case in suite {
+        let engine = BingoEngine::new()?;
+        engine.add_rules(rules.to_vec())?;
+        let results = engine.process_facts(case.facts.clone())?;
+        if !case.is_satisfied_by(&results)? {
+            return Ok(Some(case.name.clone()));
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Action, ActionType, FactData, LogicalOperator};
+    use std::collections::HashMap;
+
+    fn overtime_rule() -> Rule {
+        Rule {
+            id: 1,
+            name: "Overtime".to_string(),
+            conditions: vec![Condition::Complex {
+                operator: LogicalOperator::And,
+                conditions: vec![
+                    Condition::Simple {
+                        field: "hours".to_string(),
+                        operator: Operator::GreaterThan,
+                        value: FactValue::Integer(40),
+                    },
+                    Condition::Simple {
+                        field: "country".to_string(),
+                        operator: Operator::Equal,
+                        value: FactValue::String("UK".to_string()),
+                    },
+                ],
+            }],
+            actions: vec![Action {
+                action_type: ActionType::Log { message: "Overtime".to_string() },
+            }],
+            metadata: Default::default(),
+        }
+    }
+
+    fn hours_fact(id: FactId, hours: i64) -> Fact {
+        let mut fields = HashMap::new();
+        fields.insert("hours".to_string(), FactValue::Integer(hours));
+        Fact::new(id, FactData { fields })
+    }
+
+    #[test]
+    fn test_mutations_cover_nested_comparisons() {
+        let mutations = MutationTester::new(vec![overtime_rule()]).mutations();
+
+        // Two operator flips and two perturbations for hours, one flip for country
+        assert_eq!(mutations.len(), 5);
+        assert!(mutations.iter().any(|mutation| mutation.site == 1
+            && mutation.kind
+                == MutationKind::OperatorFlip { from: Operator::Equal, to: Operator::NotEqual }));
+
+        let flip = &mutations[0];
+        let mutated = MutationTester::new(vec![overtime_rule()]).mutate(flip);
+        let Condition::Complex { conditions, .. } = &mutated[0].conditions[0] else {
+            panic!("mutation changed the condition structure");
+        };
+        assert!(matches!(
+            conditions[0],
+            Condition::Simple { operator: Operator::GreaterThanOrEqual, .. }
+        ));
+    }
+
+    #[test]
+    fn test_only_rules_limits_targets() {
+        let mut other = overtime_rule();
+        other.id = 2;
+        let tester = MutationTester::new(vec![overtime_rule(), other]).only_rules([2]);
+        assert!(tester.mutations().iter().all(|mutation| mutation.rule_id == 2));
+    }
+
+    #[test]
+    fn test_threshold_perturbations_of_floats() {
+        let tester = MutationTester::new(Vec::new());
+        assert_eq!(
+            tester.perturbations(&FactValue::Float(200.0)),
+            vec![FactValue::Float(180.0), FactValue::Float(220.0)]
+        );
+        assert!(tester.perturbations(&FactValue::String("UK".to_string())).is_empty());
+    }
+
+    #[test]
+    fn test_boundary_cases_kill_boundary_mutants() {
+        let rule = Rule {
+            id: 1,
+            name: "Overtime".to_string(),
+            conditions: vec![Condition::Simple {
+                field: "hours".to_string(),
+                operator: Operator::GreaterThan,
+                value: FactValue::Integer(40),
+            }],
+            actions: vec![Action {
+                action_type: ActionType::Log { message: "Overtime".to_string() },
+            }],
+            metadata: Default::default(),
+        };
+        let tester = MutationTester::new(vec![rule]);
+
+        let weak = vec![RuleTestCase::expecting_firings(
+            "far from threshold",
+            vec![hours_fact(1, 50), hours_fact(2, 10)],
+            [(1, 1)],
+        )];
+        let report = tester.run(&weak).unwrap();
+        assert_eq!(report.outcomes.len(), 4);
+        assert_eq!(report.killed(), 1);
+        assert_eq!(report.survivors().count(), 3);
+        assert!(report.to_string().contains("survived: rule 1 (Overtime) comparison #0"));
+
+        let mut strong = weak.clone();
+        strong.push(RuleTestCase::expecting_firings(
+            "at threshold",
+            vec![hours_fact(3, 40), hours_fact(4, 41)],
+            [(1, 4)],
+        ));
+        let report = tester.run(&strong).unwrap();
+        assert_eq!(report.survivors().count(), 0);
+        assert_eq!(report.mutation_score(), 1.0);
+
+        // A suite that already fails says nothing about mutants
+        let failing = vec![RuleTestCase::expecting_firings("wrong", vec![hours_fact(5, 50)], [])];
+        assert!(tester.run(&failing).is_err());
+    }
+
+    #[test]
+    fn test_case_expectations() {
+        let results = vec![RuleExecutionResult {
+            rule_id: 1,
+            fact_id: 7,
+            actions_executed: vec![],
+            ..Default::default()
+        }];
+
+        let firing = RuleTestCase::expecting_firings("fires", vec![hours_fact(7, 50)], [(1, 7)]);
+        assert!(firing.is_satisfied_by(&results).unwrap());
+        let silent = RuleTestCase::expecting_firings("silent", vec![hours_fact(7, 50)], []);
+        assert!(!silent.is_satisfied_by(&results).unwrap());
+    }
+}
@@ -16,7 +16,16 @@
 //! - **Predicate Pushdown**: Move simple conditions before complex ones
 //! - **Index Optimization**: Leverage alpha memory indexing patterns
 //! - **Cross-Rule Optimization**: Share conditions across multiple rules
-
+//!
+//! ### Query Planning
+//! With query planning enabled, a rule's alpha tests are put into the order that
+//! minimises the expected cost per fact: ascending by cost / (1 - selectivity), so
+//! cheap tests that reject most facts run first and beta joins see fewer tokens.
+//! Selectivity comes, in order of preference, from the fact store's field indexes
+//! ([`RuleOptimizer::record_index_statistics`]), from matches observed on alpha
+//! nodes, or from operator heuristics.
+
+use crate::fact_store::arena_store::ArenaFactStore;
 use crate::types::{Condition, FactValue, Operator, Rule, RuleId};
 use std::collections::HashMap;
 use tracing::{debug, info, instrument};
@@ -49,7 +58,7 @@ impl Default for ConditionStats {
 }
 
 /// Advanced rule optimization engine
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct RuleOptimizer {
     /// Condition statistics for optimization decisions
     condition_stats: HashMap<String, ConditionStats>,
@@ -74,6 +83,9 @@ pub struct OptimizerConfig {
     pub max_conditions_per_analysis: usize,
     /// Enable runtime statistics collection
     pub enable_runtime_statistics: bool,
+    /// Order alpha tests by a cost-based query plan instead of the selectivity and
+    /// cost heuristics above
+    pub enable_query_planning: bool,
 }
 
 impl Default for OptimizerConfig {
@@ -85,6 +97,7 @@ impl Default for OptimizerConfig {
             min_selectivity_difference: 0.2, // 20% difference threshold
             max_conditions_per_analysis: 10,
             enable_runtime_statistics: true,
+            enable_query_planning: true,
         }
     }
 }
@@ -134,6 +147,26 @@ pub enum OptimizationStrategy {
     PredicatePushdown { pushed_conditions: Vec<usize> },
     /// Identified shared condition patterns
     ConditionSharing { pattern_key: String, shared_rules: Vec<RuleId> },
+    /// Reordered conditions by a cost-based query plan
+    QueryPlanReordering { order: Vec<usize> },
+}
+
+/// Evaluation order chosen for a rule's conditions
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryPlan {
+    /// Original index of the condition evaluated at each position
+    pub order: Vec<usize>,
+    /// Expected evaluation cost per fact in the original order (microseconds)
+    pub original_cost: f64,
+    /// Expected evaluation cost per fact in the planned order (microseconds)
+    pub planned_cost: f64,
+}
+
+impl QueryPlan {
+    /// Whether the plan keeps the original order
+    pub fn is_identity(&self) -> bool {
+        self.order.iter().enumerate().all(|(position, &index)| position == index)
+    }
 }
 
 /// Detailed analysis of optimization decisions
@@ -210,24 +243,32 @@ impl RuleOptimizer {
         // Analyze condition selectivity and costs
         let analysis = self.analyze_rule_conditions(&rule);
 
-        // Apply selectivity-based reordering if enabled
-        if self.config.enable_selectivity_ordering {
-            if let Some(reordering) =
-                self.apply_selectivity_reordering(&mut optimized_rule, &analysis)
-            {
-                strategies_applied.push(reordering.0);
-                estimated_improvement += reordering.1;
+        if self.config.enable_query_planning {
+            if let Some(planned) = self.apply_query_plan(&mut optimized_rule, &analysis) {
+                strategies_applied.push(planned.0);
+                estimated_improvement += planned.1;
                 self.optimization_metrics.conditions_reordered += 1;
             }
-        }
+        } else {
+            // Apply selectivity-based reordering if enabled
+            if self.config.enable_selectivity_ordering {
+                if let Some(reordering) =
+                    self.apply_selectivity_reordering(&mut optimized_rule, &analysis)
+                {
+                    strategies_applied.push(reordering.0);
+                    estimated_improvement += reordering.1;
+                    self.optimization_metrics.conditions_reordered += 1;
+                }
+            }
 
-        // Apply cost-based optimization if enabled
-        if self.config.enable_cost_based_optimization {
-            if let Some(cost_optimization) =
-                self.apply_cost_based_optimization(&mut optimized_rule, &analysis)
-            {
-                strategies_applied.push(cost_optimization.0);
-                estimated_improvement += cost_optimization.1;
+            // Apply cost-based optimization if enabled
+            if self.config.enable_cost_based_optimization {
+                if let Some(cost_optimization) =
+                    self.apply_cost_based_optimization(&mut optimized_rule, &analysis)
+                {
+                    strategies_applied.push(cost_optimization.0);
+                    estimated_improvement += cost_optimization.1;
+                }
             }
         }
 
@@ -321,7 +362,7 @@ impl RuleOptimizer {
     pub fn calculate_condition_selectivity(&self, condition: &Condition) -> f64 {
         match condition {
             Condition::Simple { field, operator, value } => {
-                let pattern_key = format!("{field}_{operator:?}_{value:?}");
+                let pattern_key = pattern_key(field, operator, value);
 
                 if let Some(stats) = self.condition_stats.get(&pattern_key) {
                     // Use statistics if available
@@ -368,7 +409,14 @@ impl RuleOptimizer {
             }
             Operator::GreaterThan | Operator::LessThan => 0.4, // Range queries are moderately selective
             Operator::GreaterThanOrEqual | Operator::LessThanOrEqual => 0.5,
-            Operator::Contains | Operator::StartsWith | Operator::EndsWith => 0.3, // String matching
+            Operator::Contains
+            | Operator::StartsWith
+            | Operator::EndsWith
+            | Operator::Matches
+            | Operator::ContainsIgnoreCase => 0.3, // String matching
+            Operator::Within | Operator::OlderThan | Operator::Overlaps => 0.4, // Time windows
+            Operator::In => 0.2,
+            Operator::NotIn => 0.8,
         }
     }
 
@@ -377,12 +425,15 @@ impl RuleOptimizer {
         match condition {
             Condition::Simple { operator, value, .. } => {
                 let base_cost = match operator {
-                    Operator::Equal | Operator::NotEqual => 1.0,
+                    Operator::Equal | Operator::NotEqual | Operator::In | Operator::NotIn => 1.0,
                     Operator::GreaterThan
                     | Operator::LessThan
                     | Operator::GreaterThanOrEqual
                     | Operator::LessThanOrEqual => 2.0,
                     Operator::Contains | Operator::StartsWith | Operator::EndsWith => 5.0,
+                    Operator::Within | Operator::OlderThan | Operator::Overlaps => 3.0,
+                    Operator::ContainsIgnoreCase => 8.0,
+                    Operator::Matches => 10.0,
                 };
 
                 let value_cost = match value {
@@ -406,6 +457,83 @@ impl RuleOptimizer {
         }
     }
 
+    /// Plan the evaluation order of a rule's conditions
+    ///
+    /// Alpha tests are sorted by ascending cost / (1 - selectivity), the order that
+    /// minimises the expected cost of evaluating independent tests that stop at the
+    /// first failure. Ties keep their original order. Aggregation and stream conditions
+    /// stay where they are and the other conditions are ordered around them.
+    pub fn plan_conditions(&self, rule: &Rule, analysis: &OptimizationAnalysis) -> QueryPlan {
+        let rank = |index: usize| {
+            let selectivity = analysis.condition_selectivity[index];
+            if selectivity >= 1.0 {
+                f64::INFINITY
+            } else {
+                analysis.condition_costs[index] / (1.0 - selectivity)
+            }
+        };
+
+        let slots: Vec<usize> = rule
+            .conditions
+            .iter()
+            .enumerate()
+            .filter(|(_, condition)| {
+                !matches!(
+                    condition,
+                    Condition::Aggregation(_)
+                        | Condition::Stream(_)
+                        | Condition::Sequence(_)
+                        | Condition::Accumulate(_)
+                )
+            })
+            .map(|(index, _)| index)
+            .collect();
+        let mut planned = slots.clone();
+        planned.sort_by(|&a, &b| rank(a).total_cmp(&rank(b)));
+
+        let mut order: Vec<usize> = (0..rule.conditions.len()).collect();
+        for (&slot, &index) in slots.iter().zip(&planned) {
+            order[slot] = index;
+        }
+
+        let ordered = |values: &[f64]| order.iter().map(|&index| values[index]).collect::<Vec<_>>();
+        QueryPlan {
+            original_cost: self.calculate_expected_evaluation_cost(
+                &analysis.condition_selectivity,
+                &analysis.condition_costs,
+            ),
+            planned_cost: self.calculate_expected_evaluation_cost(
+                &ordered(&analysis.condition_selectivity),
+                &ordered(&analysis.condition_costs),
+            ),
+            order,
+        }
+    }
+
+    /// Reorder a rule's conditions by its query plan when that lowers the expected cost
+    fn apply_query_plan(
+        &self,
+        rule: &mut Rule,
+        analysis: &OptimizationAnalysis,
+    ) -> Option<(OptimizationStrategy, f64)> {
+        let plan = self.plan_conditions(rule, analysis);
+        if plan.is_identity() || plan.planned_cost >= plan.original_cost {
+            return None;
+        }
+
+        rule.conditions = plan.order.iter().map(|&index| rule.conditions[index].clone()).collect();
+        let improvement = (plan.original_cost - plan.planned_cost) / plan.original_cost * 100.0;
+        debug!(
+            "Applied query plan {:?}: {:.1}% lower expected cost",
+            plan.order, improvement
+        );
+
+        Some((
+            OptimizationStrategy::QueryPlanReordering { order: plan.order },
+            improvement,
+        ))
+    }
+
     /// Apply selectivity-based condition reordering
     fn apply_selectivity_reordering(
         &self,
@@ -720,6 +848,84 @@ impl RuleOptimizer {
         self.condition_stats.insert(pattern_key, stats);
     }
 
+    /// Record how often a simple condition matched the facts it was evaluated against
+    ///
+    /// Matches are stored per 1,000 evaluations, the scale
+    /// `calculate_condition_selectivity` reads them on. Conditions never evaluated
+    /// keep their heuristic estimate.
+    pub fn record_observed_selectivity(
+        &mut self,
+        pattern_key: String,
+        evaluations: u64,
+        matches: u64,
+    ) {
+        if evaluations == 0 {
+            return;
+        }
+        let stats = self.condition_stats.entry(pattern_key).or_default();
+        stats.average_matches = matches as f64 / evaluations as f64 * 1000.0;
+        stats.last_updated = chrono::Utc::now();
+    }
+
+    /// Record the selectivity of a rule's conditions from the fact store's field indexes
+    ///
+    /// Equality and literal membership tests on an indexed field get the share of
+    /// stored facts they would match, counted from the index, replacing observed or
+    /// heuristic estimates. Other conditions are left alone, as is everything when the
+    /// store is empty. Returns the number of conditions given index statistics.
+    pub fn record_index_statistics(&mut self, rule: &Rule, fact_store: &ArenaFactStore) -> usize {
+        let total = fact_store.len();
+        if total == 0 {
+            return 0;
+        }
+
+        let mut pending: Vec<&Condition> = rule.conditions.iter().collect();
+        let mut recorded = 0;
+        while let Some(condition) = pending.pop() {
+            let (field, operator, value) = match condition {
+                Condition::Simple { field, operator, value } => (field, operator, value),
+                Condition::And { conditions }
+                | Condition::Or { conditions }
+                | Condition::Complex { conditions, .. } => {
+                    pending.extend(conditions);
+                    continue;
+                }
+                Condition::Aggregation(_)
+                | Condition::Stream(_)
+                | Condition::Sequence(_)
+                | Condition::Accumulate(_) => {
+                    continue;
+                }
+            };
+
+            let matches = match (operator, value) {
+                (Operator::Equal | Operator::NotEqual, _) => {
+                    fact_store.indexed_value_count(field, value)
+                }
+                (Operator::In | Operator::NotIn, FactValue::Array(members)) => members
+                    .iter()
+                    .map(|member| fact_store.indexed_value_count(field, member))
+                    .sum::<Option<usize>>(),
+                _ => None,
+            };
+            let Some(matches) = matches.map(|matches| matches.min(total)) else {
+                continue;
+            };
+            let matches = match operator {
+                Operator::NotEqual | Operator::NotIn => total - matches,
+                _ => matches,
+            };
+
+            self.record_observed_selectivity(
+                pattern_key(field, operator, value),
+                total as u64,
+                matches as u64,
+            );
+            recorded += 1;
+        }
+        recorded
+    }
+
     /// Get current optimization metrics
     pub fn get_metrics(&self) -> &OptimizationMetrics {
         &self.optimization_metrics
@@ -747,6 +953,11 @@ impl Default for RuleOptimizer {
     }
 }
 
+/// Key the statistics of a simple condition are stored under
+fn pattern_key(field: &str, operator: &Operator, value: &FactValue) -> String {
+    format!("{field}_{operator:?}_{value:?}")
+}
+
 /// Optimize a batch of rules for maximum performance
 pub fn optimize_rule_batch(
     rules: Vec<Rule>,
@@ -805,6 +1016,25 @@ mod tests {
         assert!(selectivity > 0.0 && selectivity <= 1.0);
     }
 
+    #[test]
+    fn test_observed_selectivity_replaces_heuristic() {
+        let mut optimizer = RuleOptimizer::new();
+        let condition = Condition::Simple {
+            field: "status".to_string(),
+            operator: Operator::Equal,
+            value: FactValue::String("active".to_string()),
+        };
+        let pattern_key = "status_Equal_String(\"active\")".to_string();
+
+        // Unevaluated conditions keep the heuristic estimate
+        optimizer.record_observed_selectivity(pattern_key.clone(), 0, 0);
+        assert!(optimizer.condition_stats.is_empty());
+
+        optimizer.record_observed_selectivity(pattern_key, 200, 180);
+        let selectivity = optimizer.calculate_condition_selectivity(&condition);
+        assert!((selectivity - 0.9).abs() < 1e-9);
+    }
+
     #[test]
     fn test_cost_calculation() {
         let _optimizer = RuleOptimizer::new();
@@ -855,6 +1085,7 @@ mod tests {
             actions: vec![Action {
                 action_type: ActionType::Log { message: "Rule fired".to_string() },
             }],
+            metadata: Default::default(),
         };
 
         let result = optimizer.optimize_rule(rule);
@@ -880,6 +1111,7 @@ mod tests {
                     value: FactValue::String("active".to_string()),
                 }],
                 actions: vec![],
+                metadata: Default::default(),
             },
             Rule {
                 id: 2,
@@ -890,10 +1122,119 @@ mod tests {
                     value: FactValue::Float(100.0),
                 }],
                 actions: vec![],
+                metadata: Default::default(),
             },
         ];
 
         let results = optimize_rule_batch(rules, None);
         assert_eq!(results.len(), 2);
     }
+
+    fn status_equals(status: &str) -> Condition {
+        Condition::Simple {
+            field: "status".to_string(),
+            operator: Operator::Equal,
+            value: FactValue::String(status.to_string()),
+        }
+    }
+
+    fn rule_with(conditions: Vec<Condition>) -> Rule {
+        Rule {
+            id: 1,
+            name: "Planned".to_string(),
+            conditions,
+            actions: vec![],
+            metadata: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_index_statistics_plan_selective_condition_first() {
+        use crate::types::{Fact, FactData};
+
+        let fact_store = ArenaFactStore::new();
+        for id in 0..100 {
+            let status = if id < 90 { "active" } else { "blocked" };
+            let fields = HashMap::from([
+                ("status".to_string(), FactValue::String(status.to_string())),
+                ("amount".to_string(), FactValue::Integer(id)),
+            ]);
+            fact_store.insert(Fact::new(id as u64, FactData { fields }));
+        }
+
+        let rule = rule_with(vec![
+            status_equals("active"),
+            Condition::Simple {
+                field: "amount".to_string(),
+                operator: Operator::GreaterThan,
+                value: FactValue::Integer(10),
+            },
+            Condition::Simple {
+                field: "status".to_string(),
+                operator: Operator::NotEqual,
+                value: FactValue::String("active".to_string()),
+            },
+        ]);
+
+        let mut optimizer = RuleOptimizer::new();
+        // `amount` is not an indexed field
+        assert_eq!(optimizer.record_index_statistics(&rule, &fact_store), 2);
+        assert!(
+            (optimizer.calculate_condition_selectivity(&rule.conditions[0]) - 0.9).abs() < 1e-9
+        );
+        assert!(
+            (optimizer.calculate_condition_selectivity(&rule.conditions[2]) - 0.1).abs() < 1e-9
+        );
+
+        let result = optimizer.optimize_rule(rule.clone());
+        assert!(matches!(
+            &result.optimized_rule.conditions[0],
+            Condition::Simple { operator: Operator::NotEqual, .. }
+        ));
+        assert!(matches!(
+            result.strategies_applied.as_slice(),
+            [OptimizationStrategy::QueryPlanReordering { order }, ..] if order[0] == 2
+        ));
+        assert!(result.estimated_improvement > 0.0);
+
+        // Nothing to count in an empty store
+        assert_eq!(
+            optimizer.record_index_statistics(&rule, &ArenaFactStore::new()),
+            0
+        );
+    }
+
+    #[test]
+    fn test_query_plan_keeps_aggregation_slots() {
+        use crate::types::{AggregationCondition, AggregationType};
+
+        let aggregation = Condition::Aggregation(AggregationCondition {
+            aggregation_type: AggregationType::Sum,
+            source_field: "amount".to_string(),
+            group_by: vec![],
+            having: None,
+            alias: "total".to_string(),
+            window: None,
+        });
+        let rule = rule_with(vec![
+            status_equals("active"),
+            aggregation,
+            status_equals("pending"),
+            status_equals("blocked"),
+        ]);
+
+        let optimizer = RuleOptimizer::new();
+        let analysis = OptimizationAnalysis {
+            condition_selectivity: vec![0.9, 0.5, 0.6, 0.1],
+            condition_costs: vec![1.0, 10.0, 1.0, 1.0],
+            join_analysis: None,
+            shared_patterns: vec![],
+            total_improvement_estimate: 0.0,
+        };
+
+        let plan = optimizer.plan_conditions(&rule, &analysis);
+        assert_eq!(plan.order, vec![3, 1, 2, 0]);
+        assert!(plan.planned_cost < plan.original_cost);
+        assert!(!plan.is_identity());
+    }
 }
@@ -0,0 +1,108 @@
+//! Per-rule hit counts and condition evaluation time
+//!
+//! The network counts, for every rule, how many facts its conditions were evaluated
+//! against, how often it fired and when it last did, and how long evaluating its
+//! conditions took. Operators read them through `BingoEngine::rule_stats` to find:
+//!
+//! - **Dead rules**: Rules that never fire, usually because the facts they expect
+//!   never arrive or a condition can never hold
+//! - **Hot rules**: Rules firing far more often than the rest, or whose conditions
+//!   dominate evaluation time
+
+use crate::types::{Rule, RuleId};
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+/// Counters kept by the network for one rule
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct RuleCounters {
+    pub evaluations: u64,
+    pub activations: u64,
+    pub last_fired: Option<DateTime<Utc>>,
+    pub condition_time: Duration,
+}
+
+impl RuleCounters {
+    /// Count one evaluation of the rule's conditions against a fact
+    pub fn record_evaluation(&mut self, elapsed: Duration) {
+        self.evaluations += 1;
+        self.condition_time += elapsed;
+    }
+
+    /// Count one firing of the rule
+    pub fn record_activation(&mut self, fired_at: DateTime<Utc>) {
+        self.activations += 1;
+        self.last_fired = Some(fired_at);
+    }
+}
+
+/// Activation counts and condition evaluation time of one rule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleStats {
+    pub rule_id: RuleId,
+    pub rule_name: String,
+    /// Facts the rule's conditions were evaluated against
+    pub evaluations: u64,
+    /// Times the rule fired
+    pub activations: u64,
+    /// When the rule last fired, `None` if it never has
+    pub last_fired: Option<DateTime<Utc>>,
+    /// Time spent evaluating the rule's conditions
+    pub condition_time: Duration,
+}
+
+impl RuleStats {
+    pub(crate) fn new(rule: &Rule, counters: RuleCounters) -> Self {
+        Self {
+            rule_id: rule.id,
+            rule_name: rule.name.clone(),
+            evaluations: counters.evaluations,
+            activations: counters.activations,
+            last_fired: counters.last_fired,
+            condition_time: counters.condition_time,
+        }
+    }
+
+    /// Mean time to evaluate the rule's conditions against a fact, once evaluated
+    pub fn average_condition_time(&self) -> Option<Duration> {
+        (self.evaluations > 0).then(|| {
+            let nanos = self.condition_time.as_nanos() / u128::from(self.evaluations);
+            Duration::from_nanos(u64::try_from(nanos).unwrap_or(u64::MAX))
+        })
+    }
+
+    /// Whether the rule has never fired
+    pub fn is_dead(&self) -> bool {
+        self.activations == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_average_condition_time() {
+        let mut counters = RuleCounters::default();
+        let rule = Rule {
+            id: 7,
+            name: "Rule".to_string(),
+            conditions: vec![],
+            actions: vec![],
+            metadata: Default::default(),
+        };
+        assert_eq!(
+            RuleStats::new(&rule, counters).average_condition_time(),
+            None
+        );
+
+        counters.record_evaluation(Duration::from_micros(3));
+        counters.record_evaluation(Duration::from_micros(5));
+        let stats = RuleStats::new(&rule, counters);
+        assert_eq!(
+            stats.average_condition_time(),
+            Some(Duration::from_micros(4))
+        );
+        assert!(stats.is_dead());
+    }
+}
@@ -341,6 +341,16 @@ impl RuleDependencyAnalyzer {
                     self.extract_fields_from_condition(having, fields);
                 }
             }
+            Condition::Sequence(sequence) => {
+                fields.extend(sequence.correlate_by.iter().cloned());
+                for step in &sequence.steps {
+                    self.extract_fields_from_condition(step, fields);
+                }
+            }
+            Condition::Accumulate(accumulate) => {
+                fields.extend(accumulate.correlate_by.iter().cloned());
+                self.extract_fields_from_condition(&accumulate.source, fields);
+            }
             Condition::And { conditions } => {
                 for cond in conditions {
                     self.extract_fields_from_condition(cond, fields);
@@ -0,0 +1,441 @@
+//! Sequence nodes for temporal pattern conditions in the RETE network
+//!
+//! A `SequenceNode` is compiled for every `Condition::Sequence` in a rule. The network
+//! tests each fact against the sequence's steps and hands the node the steps it passes.
+//! Facts passing the first step start partial matches, facts passing a later step
+//! extend the partial matches waiting for that step, and a fact passing the last step
+//! completes them. A fact satisfies the sequence condition when it completes at least
+//! one match, so "a failed login followed by a password change within 5 minutes"
+//! matches the password change.
+//!
+//! Facts of one match agree on the `correlate_by` fields, and each step's event time is
+//! no earlier than the previous step's and no later than `within_ms` after the first
+//! step's. A partial match keeps waiting after it is extended or completed, so a second
+//! password change inside the time limit completes the same match again.
+//!
+//! Partial and completed matches time out once the watermark (the latest event time
+//! seen) passes their deadline by the allowed lateness of the node's `LateDataPolicy`,
+//! or as soon as it passes the deadline when late data is accepted. Facts that arrive
+//! behind the watermark by more than the allowed lateness are dropped as late.
+
+use crate::aggregation_node::GroupKey;
+use crate::stream_processing::{LateDataPolicy, Timestamp};
+use crate::types::{Condition, Fact, FactData, FactId, NodeId, RuleId, SequenceCondition};
+use crate::window_node::DEFAULT_MAX_LATENESS;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+/// Facts matching the leading steps of a sequence, one per step
+#[derive(Debug, Clone)]
+struct PartialMatch {
+    facts: Vec<FactId>,
+    start: Timestamp,
+    last: Timestamp,
+}
+
+/// A fact held by the node's matches
+#[derive(Debug, Clone)]
+struct Member {
+    data: FactData,
+    key: GroupKey,
+}
+
+/// Counters for the facts and matches of a sequence node
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SequenceStats {
+    /// Facts passing at least one step
+    pub events_processed: u64,
+    /// Facts dropped for arriving after the partial matches they could extend timed out
+    pub late_events_dropped: u64,
+    /// Partial matches started or extended
+    pub partial_matches_created: u64,
+    /// Partial matches that timed out
+    pub partial_matches_expired: u64,
+    /// Matches completed by a fact passing the last step
+    pub matches_completed: u64,
+}
+
+/// RETE node holding the partial matches of one sequence condition
+#[derive(Debug, Clone)]
+pub struct SequenceNode {
+    pub id: NodeId,
+    pub condition: SequenceCondition,
+    pub dependent_rules: Vec<RuleId>,
+    partials: HashMap<GroupKey, Vec<PartialMatch>>,
+    completed: HashMap<FactId, Vec<PartialMatch>>,
+    members: HashMap<FactId, Member>,
+    watermark: Timestamp,
+    late_data: LateDataPolicy,
+    seeded: bool,
+    stats: SequenceStats,
+}
+
+impl SequenceNode {
+    /// Create an empty sequence node for a sequence condition
+    pub fn new(id: NodeId, condition: SequenceCondition) -> Self {
+        Self {
+            id,
+            condition,
+            dependent_rules: Vec::new(),
+            partials: HashMap::new(),
+            completed: HashMap::new(),
+            members: HashMap::new(),
+            watermark: Timestamp::from_millis(0),
+            late_data: LateDataPolicy::Drop { allowed_lateness: DEFAULT_MAX_LATENESS },
+            seeded: false,
+            stats: SequenceStats::default(),
+        }
+    }
+
+    /// Check that a sequence condition can be matched
+    pub fn validate(condition: &SequenceCondition) -> anyhow::Result<()> {
+        if condition.steps.is_empty() {
+            anyhow::bail!("Sequence '{}' has no steps", condition.alias);
+        }
+        if condition.steps.iter().any(|step| matches!(step, Condition::Sequence(_))) {
+            anyhow::bail!("Sequence '{}' has a sequence as a step", condition.alias);
+        }
+        Ok(())
+    }
+
+    /// Signature used to share nodes between rules with identical sequence conditions
+    pub fn signature(condition: &SequenceCondition) -> String {
+        format!("{condition:?}")
+    }
+
+    /// Register a rule that depends on this node
+    pub fn add_rule(&mut self, rule_id: RuleId) {
+        if !self.dependent_rules.contains(&rule_id) {
+            self.dependent_rules.push(rule_id);
+        }
+    }
+
+    /// Unregister a rule, returning whether any dependent rules remain
+    pub fn remove_rule(&mut self, rule_id: RuleId) -> bool {
+        self.dependent_rules.retain(|id| *id != rule_id);
+        !self.dependent_rules.is_empty()
+    }
+
+    /// Choose what happens to events that arrive behind the watermark
+    pub fn set_late_data_policy(&mut self, policy: LateDataPolicy) {
+        self.late_data = policy;
+    }
+
+    /// The policy applied to events that arrive behind the watermark
+    pub fn late_data_policy(&self) -> LateDataPolicy {
+        self.late_data
+    }
+
+    /// Whether the node has been populated with the facts that existed before it was built
+    pub fn is_seeded(&self) -> bool {
+        self.seeded
+    }
+
+    /// Populate the node from existing facts and the steps each passes
+    pub fn seed<'a>(&mut self, facts: impl IntoIterator<Item = (&'a Fact, Vec<usize>)>) {
+        self.assert_batch(facts);
+        self.seeded = true;
+    }
+
+    /// Time out expired matches, then assert a batch of facts in event-time order
+    pub fn assert_batch<'a>(&mut self, facts: impl IntoIterator<Item = (&'a Fact, Vec<usize>)>) {
+        self.expire_matches();
+        let mut facts: Vec<(&Fact, Vec<usize>)> = facts.into_iter().collect();
+        facts.sort_by_key(|(fact, _)| Timestamp::of_fact(fact));
+        for (fact, steps) in facts {
+            self.assert_fact(fact, &steps);
+        }
+    }
+
+    /// Extend the partial matches of a fact passing `steps`, returning whether it
+    /// completed a match
+    ///
+    /// Re-asserting an unchanged fact is a no-op; a changed fact replaces its earlier
+    /// contribution.
+    pub fn assert_fact(&mut self, fact: &Fact, steps: &[usize]) -> bool {
+        if let Some(member) = self.members.get(&fact.id) {
+            if member.data == fact.data {
+                return self.completes(fact.id);
+            }
+            self.retract_fact(fact.id);
+        }
+        if steps.is_empty() {
+            return false;
+        }
+
+        let event_time = Timestamp::of_fact(fact);
+        if self.late_data.is_late(event_time, self.watermark) {
+            self.stats.late_events_dropped += 1;
+            return false;
+        }
+        self.watermark = self.watermark.max(event_time);
+        self.stats.events_processed += 1;
+
+        let key = self.correlation_key(fact);
+        let last_step = self.condition.steps.len() - 1;
+        let within = Duration::from_millis(self.condition.within_ms);
+        let waiting = self.partials.get(&key).map(Vec::as_slice).unwrap_or(&[]);
+        let mut started = Vec::new();
+        let mut completed = Vec::new();
+        for &step in steps {
+            let extended: Vec<PartialMatch> = if step == 0 {
+                vec![PartialMatch { facts: vec![fact.id], start: event_time, last: event_time }]
+            } else {
+                waiting
+                    .iter()
+                    .filter(|partial| {
+                        partial.facts.len() == step
+                            && partial.last <= event_time
+                            && event_time <= partial.start.add_duration(within)
+                    })
+                    .map(|partial| {
+                        let mut facts = partial.facts.clone();
+                        facts.push(fact.id);
+                        PartialMatch { facts, start: partial.start, last: event_time }
+                    })
+                    .collect()
+            };
+            if step == last_step {
+                completed.extend(extended);
+            } else {
+                started.extend(extended);
+            }
+        }
+
+        if started.is_empty() && completed.is_empty() {
+            return false;
+        }
+        self.members.insert(
+            fact.id,
+            Member { data: fact.data.clone(), key: key.clone() },
+        );
+        self.stats.partial_matches_created += started.len() as u64;
+        if !started.is_empty() {
+            self.partials.entry(key).or_default().extend(started);
+        }
+        if completed.is_empty() {
+            return false;
+        }
+        self.stats.matches_completed += completed.len() as u64;
+        self.completed.insert(fact.id, completed);
+        true
+    }
+
+    /// Remove every match holding a fact, returning whether any did
+    pub fn retract_fact(&mut self, fact_id: FactId) -> bool {
+        let Some(member) = self.members.remove(&fact_id) else {
+            return false;
+        };
+
+        if let Some(partials) = self.partials.get_mut(&member.key) {
+            partials.retain(|partial| !partial.facts.contains(&fact_id));
+            if partials.is_empty() {
+                self.partials.remove(&member.key);
+            }
+        }
+        self.completed.remove(&fact_id);
+        self.completed.retain(|_, matches| {
+            matches.retain(|matched| !matched.facts.contains(&fact_id));
+            !matches.is_empty()
+        });
+        self.release_unheld_facts();
+        true
+    }
+
+    /// Whether a fact completed at least one match that has not timed out
+    pub fn completes(&self, fact_id: FactId) -> bool {
+        self.completed.contains_key(&fact_id)
+    }
+
+    /// Facts of each match a fact completed, in step order
+    pub fn matches_for(&self, fact_id: FactId) -> Vec<&[FactId]> {
+        self.completed
+            .get(&fact_id)
+            .map(|matches| matches.iter().map(|matched| matched.facts.as_slice()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Number of partial matches waiting for their next step
+    pub fn partial_match_count(&self) -> usize {
+        self.partials.values().map(Vec::len).sum()
+    }
+
+    /// Number of facts held by partial or completed matches
+    pub fn fact_count(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Latest event time observed
+    pub fn watermark(&self) -> Timestamp {
+        self.watermark
+    }
+
+    /// Fact and match statistics
+    pub fn stats(&self) -> &SequenceStats {
+        &self.stats
+    }
+
+    /// Drop all matches; the node will be re-seeded on next use
+    pub fn clear(&mut self) {
+        self.partials.clear();
+        self.completed.clear();
+        self.members.clear();
+        self.watermark = Timestamp::from_millis(0);
+        self.seeded = false;
+    }
+
+    fn correlation_key(&self, fact: &Fact) -> GroupKey {
+        self.condition
+            .correlate_by
+            .iter()
+            .map(|field| fact.data.fields.get(field).cloned())
+            .collect()
+    }
+
+    /// Whether a match started at `start` can no longer be extended
+    fn is_expired(&self, start: Timestamp) -> bool {
+        let deadline = start.add_duration(Duration::from_millis(self.condition.within_ms));
+        match self.late_data {
+            LateDataPolicy::Accept => deadline < self.watermark,
+            policy => policy.is_late(deadline, self.watermark),
+        }
+    }
+
+    /// Drop partial and completed matches whose deadline has passed
+    fn expire_matches(&mut self) {
+        let mut partials = std::mem::take(&mut self.partials);
+        let mut expired = 0;
+        partials.retain(|_, waiting| {
+            let before = waiting.len();
+            waiting.retain(|partial| !self.is_expired(partial.start));
+            expired += before - waiting.len();
+            !waiting.is_empty()
+        });
+        self.partials = partials;
+        self.stats.partial_matches_expired += expired as u64;
+
+        let mut completed = std::mem::take(&mut self.completed);
+        completed.retain(|_, matches| {
+            matches.retain(|matched| !self.is_expired(matched.start));
+            !matches.is_empty()
+        });
+        self.completed = completed;
+        self.release_unheld_facts();
+    }
+
+    /// Forget facts no partial or completed match holds any more
+    fn release_unheld_facts(&mut self) {
+        let held: HashSet<FactId> = self
+            .partials
+            .values()
+            .flatten()
+            .chain(self.completed.values().flatten())
+            .flat_map(|partial| partial.facts.iter().copied())
+            .collect();
+        self.members.retain(|fact_id, _| held.contains(fact_id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{FactValue, Operator};
+
+    fn is_kind(kind: &str) -> Condition {
+        Condition::Simple {
+            field: "kind".to_string(),
+            operator: Operator::Equal,
+            value: FactValue::String(kind.to_string()),
+        }
+    }
+
+    fn sequence(kinds: &[&str], within_ms: u64) -> SequenceNode {
+        SequenceNode::new(
+            1,
+            SequenceCondition {
+                steps: kinds.iter().map(|kind| is_kind(kind)).collect(),
+                within_ms,
+                correlate_by: vec!["user".to_string()],
+                alias: "takeover".to_string(),
+            },
+        )
+    }
+
+    fn event(id: FactId, user: &str, timestamp: i64) -> Fact {
+        let mut fields = HashMap::new();
+        fields.insert("user".to_string(), FactValue::String(user.to_string()));
+        fields.insert("timestamp".to_string(), FactValue::Integer(timestamp));
+        Fact::new(id, FactData { fields })
+    }
+
+    #[test]
+    fn test_sequence_completes_in_order_within_time_limit() {
+        let mut node = sequence(&["login_failed", "password_changed"], 300_000);
+
+        assert!(!node.assert_fact(&event(1, "ann", 1_000), &[0]));
+        assert!(
+            !node.assert_fact(&event(2, "bob", 2_000), &[1]),
+            "another user's change"
+        );
+        assert!(node.assert_fact(&event(3, "ann", 60_000), &[1]));
+        assert!(
+            !node.assert_fact(&event(4, "ann", 400_000), &[1]),
+            "after the time limit"
+        );
+
+        assert_eq!(node.matches_for(3), vec![&[1, 3][..]]);
+        assert!(!node.completes(2));
+        assert_eq!(node.stats().matches_completed, 1);
+    }
+
+    #[test]
+    fn test_steps_must_not_go_back_in_time() {
+        let mut node = sequence(&["a", "b", "c"], 10_000);
+
+        node.assert_fact(&event(1, "ann", 5_000), &[0]);
+        node.assert_fact(&event(2, "ann", 4_000), &[1]);
+        assert_eq!(
+            node.partial_match_count(),
+            1,
+            "b before a does not extend it"
+        );
+
+        node.assert_fact(&event(3, "ann", 6_000), &[1]);
+        assert!(node.assert_fact(&event(4, "ann", 7_000), &[2]));
+        assert_eq!(node.matches_for(4), vec![&[1, 3, 4][..]]);
+    }
+
+    #[test]
+    fn test_fact_passing_several_steps_does_not_match_itself() {
+        let mut node = sequence(&["attempt", "attempt"], 10_000);
+
+        assert!(!node.assert_fact(&event(1, "ann", 1_000), &[0, 1]));
+        assert!(node.assert_fact(&event(2, "ann", 2_000), &[0, 1]));
+        assert_eq!(node.matches_for(2), vec![&[1, 2][..]]);
+    }
+
+    #[test]
+    fn test_retraction_and_timeouts_release_matches() {
+        let mut node = sequence(&["login_failed", "password_changed"], 1_000);
+        node.set_late_data_policy(LateDataPolicy::Drop { allowed_lateness: Duration::ZERO });
+
+        node.assert_fact(&event(1, "ann", 1_000), &[0]);
+        assert!(node.assert_fact(&event(2, "ann", 1_500), &[1]));
+        assert!(node.retract_fact(1));
+        assert!(!node.completes(2));
+        assert_eq!(node.fact_count(), 0);
+
+        node.assert_batch([(&event(3, "ann", 2_000), vec![0])]);
+        node.assert_batch([(&event(4, "bob", 5_000), vec![0])]);
+        assert_eq!(node.partial_match_count(), 2);
+        node.assert_batch([]);
+        assert_eq!(node.partial_match_count(), 1, "ann's attempt timed out");
+        assert_eq!(node.stats().partial_matches_expired, 1);
+
+        assert!(
+            !node.assert_fact(&event(5, "ann", 2_500), &[1]),
+            "too late to complete"
+        );
+        assert_eq!(node.stats().late_events_dropped, 1);
+    }
+}
@@ -5,6 +5,7 @@
 //! - Cached type conversions for repeated value patterns
 //! - Zero-copy serialization when possible
 //! - Bulk serialization for fact batches
+//! - JSON and MessagePack payloads holding one fact's fields
 
 use crate::types::{Fact, FactData, FactValue};
 use anyhow::Result;
@@ -108,6 +109,11 @@ impl SerializationContext {
             write!(&mut buffer, r#""external_id":"{external_id}","#)?;
         }
 
+        // Write fact_type if present
+        if let Some(fact_type) = &fact.fact_type {
+            write!(&mut buffer, r#""fact_type":"{fact_type}","#)?;
+        }
+
         // Write timestamp
         write!(
             &mut buffer,
@@ -188,6 +194,7 @@ impl SerializationContext {
             .ok_or_else(|| anyhow::anyhow!("Missing or invalid fact ID"))?;
 
         let external_id = json_value["external_id"].as_str().map(|s| s.to_string());
+        let fact_type = json_value["fact_type"].as_str().map(|s| s.to_string());
 
         let timestamp_str = json_value["timestamp"]
             .as_str()
@@ -204,7 +211,7 @@ impl SerializationContext {
             fields.insert(key.clone(), FactValue::try_from(value)?);
         }
 
-        Ok(Fact { id, external_id, timestamp, data: FactData { fields } })
+        Ok(Fact { id, external_id, timestamp, fact_type, data: FactData { fields } })
     }
 
     /// Deserialize multiple facts from JSON array
@@ -227,6 +234,7 @@ impl SerializationContext {
             FactValue::String(s) => format!("s:{s}"),
             FactValue::Integer(i) => format!("i:{i}"),
             FactValue::Float(f) => format!("f:{f}"),
+            FactValue::Decimal(d) => format!("d:{}", d.normalize()),
             FactValue::Boolean(b) => format!("b:{b}"),
             FactValue::Null => "null".to_string(),
             // For complex types, use a hash
@@ -321,6 +329,253 @@ impl SerializationStats {
     }
 }
 
+/// Encoding of a fact's fields in a serialized payload
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FactPayloadFormat {
+    /// A JSON object whose members are the fields
+    Json,
+    /// A MessagePack map whose string keys are the field names
+    MessagePack,
+}
+
+impl FactPayloadFormat {
+    /// Format named by a MIME content type such as `application/json`, ignoring
+    /// parameters like `; charset=utf-8`
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        let mime = content_type.split(';').next().unwrap_or_default().trim();
+        match mime.to_ascii_lowercase().as_str() {
+            "application/json" | "json" => Some(Self::Json),
+            "application/msgpack"
+            | "application/x-msgpack"
+            | "application/vnd.msgpack"
+            | "msgpack" => Some(Self::MessagePack),
+            _ => None,
+        }
+    }
+
+    /// Canonical content type of the format
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            Self::MessagePack => "application/msgpack",
+        }
+    }
+}
+
+/// Decode the fields of one fact from a payload holding a single object or map
+pub fn deserialize_fact_fields(
+    format: FactPayloadFormat,
+    payload: &[u8],
+) -> Result<HashMap<String, FactValue>> {
+    let value = match format {
+        FactPayloadFormat::Json => serde_json::from_slice(payload)?,
+        FactPayloadFormat::MessagePack => {
+            let mut reader = MessagePackReader { bytes: payload, position: 0 };
+            let value = reader.read_value(0)?;
+            if reader.position != payload.len() {
+                anyhow::bail!(
+                    "Trailing bytes after MessagePack value at offset {}",
+                    reader.position
+                );
+            }
+            value
+        }
+    };
+    let serde_json::Value::Object(members) = value else {
+        anyhow::bail!("Expected a {} object of fact fields", format.content_type());
+    };
+    members
+        .iter()
+        .map(|(field, value)| {
+            FactValue::try_from(value)
+                .map(|value| (field.clone(), value))
+                .map_err(|e| anyhow::anyhow!("Invalid value for field '{field}': {e}"))
+        })
+        .collect()
+}
+
+/// Encode the fields of one fact as a payload in `format`
+pub fn serialize_fact_fields(
+    format: FactPayloadFormat,
+    fields: &HashMap<String, FactValue>,
+) -> Result<Vec<u8>> {
+    let object = serde_json::Value::Object(
+        fields.iter().map(|(field, value)| (field.clone(), value.into())).collect(),
+    );
+    Ok(match format {
+        FactPayloadFormat::Json => serde_json::to_vec(&object)?,
+        FactPayloadFormat::MessagePack => {
+            let mut bytes = Vec::new();
+            write_message_pack(&object, &mut bytes);
+            bytes
+        }
+    })
+}
+
+/// Nesting depth beyond which MessagePack payloads are rejected
+const MAX_MESSAGE_PACK_DEPTH: usize = 64;
+
+/// Decoder for the MessagePack types that have a JSON equivalent
+struct MessagePackReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl MessagePackReader<'_> {
+    fn take(&mut self, len: usize) -> Result<&[u8]> {
+        let end = self.position.checked_add(len).filter(|&end| end <= self.bytes.len());
+        let Some(end) = end else {
+            anyhow::bail!("Truncated MessagePack payload at offset {}", self.position);
+        };
+        let bytes = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+
+    fn read_uint(&mut self, len: usize) -> Result<u64> {
+        Ok(self.take(len)?.iter().fold(0, |value, &byte| (value << 8) | u64::from(byte)))
+    }
+
+    fn read_int(&mut self, len: usize) -> Result<i64> {
+        let value = self.read_uint(len)?;
+        let shift = 64 - 8 * len as u32;
+        Ok(((value << shift) as i64) >> shift)
+    }
+
+    fn read_value(&mut self, depth: usize) -> Result<serde_json::Value> {
+        if depth > MAX_MESSAGE_PACK_DEPTH {
+            anyhow::bail!("MessagePack payload nests deeper than {MAX_MESSAGE_PACK_DEPTH} levels");
+        }
+        let offset = self.position;
+        let marker = self.take(1)?[0];
+        Ok(match marker {
+            0x00..=0x7f => serde_json::Value::from(marker),
+            0xe0..=0xff => serde_json::Value::from(marker as i8),
+            0x80..=0x8f => self.read_map(usize::from(marker & 0x0f), depth)?,
+            0x90..=0x9f => self.read_array(usize::from(marker & 0x0f), depth)?,
+            0xa0..=0xbf => self.read_str(usize::from(marker & 0x1f))?,
+            0xc0 => serde_json::Value::Null,
+            0xc2 => serde_json::Value::Bool(false),
+            0xc3 => serde_json::Value::Bool(true),
+            0xca => {
+                let bits = self.read_uint(4)? as u32;
+                serde_json::Number::from_f64(f64::from(f32::from_bits(bits)))
+                    .map_or(serde_json::Value::Null, serde_json::Value::Number)
+            }
+            0xcb => serde_json::Number::from_f64(f64::from_bits(self.read_uint(8)?))
+                .map_or(serde_json::Value::Null, serde_json::Value::Number),
+            0xcc..=0xcf => serde_json::Value::from(self.read_uint(1 << (marker - 0xcc))?),
+            0xd0..=0xd3 => serde_json::Value::from(self.read_int(1 << (marker - 0xd0))?),
+            0xd9..=0xdb => {
+                let len = self.read_uint(1 << (marker - 0xd9))? as usize;
+                self.read_str(len)?
+            }
+            0xdc | 0xdd => {
+                let len = self.read_uint(if marker == 0xdc { 2 } else { 4 })? as usize;
+                self.read_array(len, depth)?
+            }
+            0xde | 0xdf => {
+                let len = self.read_uint(if marker == 0xde { 2 } else { 4 })? as usize;
+                self.read_map(len, depth)?
+            }
+            0xc4..=0xc6 => {
+                anyhow::bail!("MessagePack binary value at offset {offset} is not supported")
+            }
+            0xc7..=0xc9 | 0xd4..=0xd8 => {
+                anyhow::bail!("MessagePack extension value at offset {offset} is not supported")
+            }
+            0xc1 => anyhow::bail!("Invalid MessagePack marker 0xc1 at offset {offset}"),
+        })
+    }
+
+    fn read_str(&mut self, len: usize) -> Result<serde_json::Value> {
+        let offset = self.position;
+        let text = std::str::from_utf8(self.take(len)?).map_err(|e| {
+            anyhow::anyhow!("Invalid UTF-8 in MessagePack string at offset {offset}: {e}")
+        })?;
+        Ok(serde_json::Value::String(text.to_string()))
+    }
+
+    fn read_array(&mut self, len: usize, depth: usize) -> Result<serde_json::Value> {
+        // Every element takes at least a byte, so a corrupt length cannot over-allocate
+        let mut items = Vec::with_capacity(len.min(self.bytes.len() - self.position));
+        for _ in 0..len {
+            items.push(self.read_value(depth + 1)?);
+        }
+        Ok(serde_json::Value::Array(items))
+    }
+
+    fn read_map(&mut self, len: usize, depth: usize) -> Result<serde_json::Value> {
+        let mut members = serde_json::Map::new();
+        for _ in 0..len {
+            let offset = self.position;
+            let serde_json::Value::String(key) = self.read_value(depth + 1)? else {
+                anyhow::bail!("MessagePack map key at offset {offset} is not a string");
+            };
+            members.insert(key, self.read_value(depth + 1)?);
+        }
+        Ok(serde_json::Value::Object(members))
+    }
+}
+
+fn write_message_pack(value: &serde_json::Value, out: &mut Vec<u8>) {
+    fn write_len(out: &mut Vec<u8>, len: usize, fix: (u8, usize), markers: [u8; 3]) {
+        match len {
+            len if len < fix.1 => out.push(fix.0 | len as u8),
+            len if markers[0] != 0 && len <= u8::MAX as usize => {
+                out.extend([markers[0], len as u8])
+            }
+            len if len <= u16::MAX as usize => {
+                out.push(markers[1]);
+                out.extend((len as u16).to_be_bytes());
+            }
+            len => {
+                out.push(markers[2]);
+                out.extend((len as u32).to_be_bytes());
+            }
+        }
+    }
+
+    match value {
+        serde_json::Value::Null => out.push(0xc0),
+        serde_json::Value::Bool(b) => out.push(if *b { 0xc3 } else { 0xc2 }),
+        serde_json::Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                match i {
+                    -32..=0x7f => out.push(i as u8),
+                    _ => {
+                        out.push(0xd3);
+                        out.extend(i.to_be_bytes());
+                    }
+                }
+            } else if let Some(u) = n.as_u64() {
+                out.push(0xcf);
+                out.extend(u.to_be_bytes());
+            } else {
+                out.push(0xcb);
+                out.extend(n.as_f64().unwrap_or_default().to_be_bytes());
+            }
+        }
+        serde_json::Value::String(s) => {
+            write_len(out, s.len(), (0xa0, 32), [0xd9, 0xda, 0xdb]);
+            out.extend(s.as_bytes());
+        }
+        serde_json::Value::Array(items) => {
+            write_len(out, items.len(), (0x90, 16), [0, 0xdc, 0xdd]);
+            for item in items {
+                write_message_pack(item, out);
+            }
+        }
+        serde_json::Value::Object(members) => {
+            write_len(out, members.len(), (0x80, 16), [0, 0xde, 0xdf]);
+            for (key, item) in members {
+                write_message_pack(&serde_json::Value::String(key.clone()), out);
+                write_message_pack(item, out);
+            }
+        }
+    }
+}
+
 // Global serialization context for the engine
 thread_local! {
     static SERIALIZATION_CONTEXT: RefCell<SerializationContext> = RefCell::new(SerializationContext::new());
@@ -389,6 +644,7 @@ mod tests {
             id: 1,
             external_id: Some("test".to_string()),
             timestamp: Utc::now(),
+            fact_type: None,
             data: FactData {
                 fields: std::iter::once((
                     "key".to_string(),
@@ -417,6 +673,7 @@ mod tests {
             id: 123,
             external_id: Some("test-fact".to_string()),
             timestamp: Utc::now(),
+            fact_type: Some("player".to_string()),
             data: FactData {
                 fields: [
                     ("name".to_string(), FactValue::String("Test".to_string())),
@@ -434,6 +691,7 @@ mod tests {
 
         assert_eq!(original_fact.id, deserialized_fact.id);
         assert_eq!(original_fact.external_id, deserialized_fact.external_id);
+        assert_eq!(original_fact.fact_type, deserialized_fact.fact_type);
         assert_eq!(original_fact.data.fields, deserialized_fact.data.fields);
     }
 
@@ -446,6 +704,7 @@ mod tests {
                 id: i,
                 external_id: Some(format!("fact-{i}")),
                 timestamp: Utc::now(),
+                fact_type: None,
                 data: FactData {
                     fields: std::iter::once((
                         "value".to_string(),
@@ -480,4 +739,38 @@ mod tests {
         assert!(stats.cache_misses > 0);
         assert!(ctx.cache_hit_rate() > 0.0);
     }
+
+    #[test]
+    fn test_message_pack_fact_fields_round_trip() {
+        // {"compact": true, "schema": 0} from the MessagePack specification
+        let payload = b"\x82\xa7compact\xc3\xa6schema\x00";
+        let fields = deserialize_fact_fields(FactPayloadFormat::MessagePack, payload).unwrap();
+        assert_eq!(fields["compact"], FactValue::Boolean(true));
+        assert_eq!(fields["schema"], FactValue::Integer(0));
+
+        let fields = HashMap::from([
+            ("amount".to_string(), FactValue::Integer(-40_000)),
+            ("rate".to_string(), FactValue::Float(1.5)),
+            ("name".to_string(), FactValue::String("x".repeat(300))),
+            (
+                "tags".to_string(),
+                FactValue::Array(vec![FactValue::Null; 20]),
+            ),
+        ]);
+        for format in [FactPayloadFormat::Json, FactPayloadFormat::MessagePack] {
+            let payload = serialize_fact_fields(format, &fields).unwrap();
+            assert_eq!(deserialize_fact_fields(format, &payload).unwrap(), fields);
+        }
+
+        assert!(deserialize_fact_fields(FactPayloadFormat::MessagePack, b"\x82\xa7comp").is_err());
+        assert!(deserialize_fact_fields(FactPayloadFormat::MessagePack, b"\x81\x01\xc3").is_err());
+        assert!(
+            deserialize_fact_fields(FactPayloadFormat::MessagePack, b"\x93\x01\x02\x03").is_err()
+        );
+        assert_eq!(
+            FactPayloadFormat::from_content_type("Application/JSON; charset=utf-8"),
+            Some(FactPayloadFormat::Json)
+        );
+        assert_eq!(FactPayloadFormat::from_content_type("text/csv"), None);
+    }
 }
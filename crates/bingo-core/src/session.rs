@@ -0,0 +1,308 @@
+//! Session-based working memory API
+//!
+//! `BingoEngine::process_facts` matches and fires a whole batch in one call, which
+//! suits stateless evaluation but not interactive or long-lived decisioning. A
+//! `BingoSession` wraps an engine with a Drools-style lifecycle instead:
+//!
+//! - **`insert`**: Queue a fact for matching and get a `FactHandle` back
+//! - **`modify`**: Change fields of a fact; its activations and derived facts are
+//!   withdrawn and it is matched again on the next fire
+//! - **`retract`**: Remove a fact with truth maintenance
+//! - **`fire_all_rules`**: Match every queued fact and fire the resulting activations
+//! - **`fire_until_halt`**: Keep firing as facts arrive from other threads until
+//!   `halt` is called
+//!
+//! Event listeners observe every insert, modify, retract and rule firing. Any
+//! `Fn(&SessionEvent)` closure can be registered as a listener, and `add_event_sink`
+//! hands events to a bounded `AsyncSink` so slow consumers never stall rule firing.
+
+use crate::engine::BingoEngine;
+use crate::error::{BingoError, BingoResult};
+use crate::event_bus::EngineEvent;
+use crate::event_sink::{AsyncSink, Submission};
+use crate::rete_nodes::RuleExecutionResult;
+use crate::truth_maintenance::RetractionResult;
+use crate::types::{Fact, FactId, FactValue};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex, RwLock};
+use tracing::{debug, info};
+
+/// Handle identifying a fact inserted into a session
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FactHandle(FactId);
+
+impl FactHandle {
+    /// ID of the fact in the engine's working memory
+    pub fn fact_id(&self) -> FactId {
+        self.0
+    }
+}
+
+/// Working memory change or rule firing observed by session listeners
+#[derive(Debug, Clone)]
+pub enum SessionEvent {
+    /// A fact was queued for matching
+    FactInserted { handle: FactHandle },
+    /// A fact's fields changed and it was queued to be matched again
+    FactModified { handle: FactHandle, updated_fields: Vec<String> },
+    /// A fact was removed along with everything it logically supported
+    FactRetracted { handle: FactHandle, retraction: RetractionResult },
+    /// A rule fired for a fact
+    RuleFired { result: RuleExecutionResult },
+    /// `fire_until_halt` returned after `halt` was called
+    Halted { rules_fired: usize },
+}
+
+/// Callback notified of session events
+pub trait SessionEventListener: Send + Sync {
+    /// Observe an event; called synchronously on the thread that caused it
+    fn on_event(&self, event: &SessionEvent);
+}
+
+impl<F> SessionEventListener for F
+where
+    F: Fn(&SessionEvent) + Send + Sync,
+{
+    fn on_event(&self, event: &SessionEvent) {
+        self(event)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Agenda {
+    /// Facts waiting to be matched by the next fire
+    pending: VecDeque<Fact>,
+    halted: bool,
+}
+
+/// Long-lived working memory session over a `BingoEngine`
+pub struct BingoSession {
+    engine: Arc<BingoEngine>,
+    agenda: Mutex<Agenda>,
+    agenda_changed: Condvar,
+    listeners: RwLock<Vec<Box<dyn SessionEventListener>>>,
+    next_fact_id: AtomicU64,
+    rules_fired: AtomicU64,
+}
+
+impl std::fmt::Debug for BingoSession {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BingoSession")
+            .field("engine", &self.engine)
+            .field("pending_facts", &self.pending_count())
+            .field("listeners", &self.listeners.read().unwrap().len())
+            .finish()
+    }
+}
+
+impl BingoSession {
+    /// Create a session over an engine that already holds the rules to run
+    pub fn new(engine: Arc<BingoEngine>) -> Self {
+        engine.event_bus().publish(EngineEvent::SessionCreated {
+            rule_count: engine.rule_count(),
+            ruleset_version: engine.ruleset_version(),
+        });
+        Self {
+            engine,
+            agenda: Mutex::new(Agenda::default()),
+            agenda_changed: Condvar::new(),
+            listeners: RwLock::new(Vec::new()),
+            next_fact_id: AtomicU64::new(1),
+            rules_fired: AtomicU64::new(0),
+        }
+    }
+
+    /// Engine backing this session
+    pub fn engine(&self) -> &Arc<BingoEngine> {
+        &self.engine
+    }
+
+    /// Register a listener notified of every session event
+    pub fn add_event_listener(&self, listener: impl SessionEventListener + 'static) {
+        self.listeners.write().unwrap().push(Box::new(listener));
+    }
+
+    /// Queue every session event on `sink` for delivery off the firing thread
+    ///
+    /// Events the sink's overflow policy rejects or evicts are counted in its stats.
+    pub fn add_event_sink(&self, sink: Arc<AsyncSink<SessionEvent>>) {
+        self.add_event_listener(move |event: &SessionEvent| {
+            if let Submission::Rejected(_) = sink.submit(event.clone()) {
+                debug!("Session event rejected by full event sink");
+            }
+        });
+    }
+
+    /// Queue a fact for matching on the next fire
+    ///
+    /// Facts with an ID of 0 are given the next free session ID; explicit IDs are kept.
+    pub fn insert(&self, mut fact: Fact) -> FactHandle {
+        if fact.id == 0 {
+            fact.id = self.next_fact_id.fetch_add(1, Ordering::SeqCst);
+        } else {
+            self.next_fact_id.fetch_max(fact.id + 1, Ordering::SeqCst);
+        }
+        let handle = FactHandle(fact.id);
+
+        self.agenda.lock().unwrap().pending.push_back(fact);
+        self.agenda_changed.notify_all();
+
+        debug!(fact_id = handle.fact_id(), "Fact inserted into session");
+        self.emit(&SessionEvent::FactInserted { handle });
+        handle
+    }
+
+    /// Current contents of a fact, whether still queued or already matched
+    pub fn get_fact(&self, handle: FactHandle) -> Option<Fact> {
+        let agenda = self.agenda.lock().unwrap();
+        agenda
+            .pending
+            .iter()
+            .find(|fact| fact.id == handle.fact_id())
+            .cloned()
+            .or_else(|| self.engine.get_fact(handle.fact_id()))
+    }
+
+    /// Update fields of a fact and queue it to be matched again
+    ///
+    /// A fact that was already matched is retracted first, so activations and
+    /// derived facts that depended on its old values are withdrawn.
+    pub fn modify(
+        &self,
+        handle: FactHandle,
+        updates: HashMap<String, FactValue>,
+    ) -> BingoResult<()> {
+        let fact_id = handle.fact_id();
+        let updated_fields: Vec<String> = updates.keys().cloned().collect();
+
+        {
+            let mut agenda = self.agenda.lock().unwrap();
+            if let Some(fact) = agenda.pending.iter_mut().find(|fact| fact.id == fact_id) {
+                fact.data.fields.extend(updates);
+            } else {
+                let mut fact = self.engine.get_fact(fact_id).ok_or_else(|| {
+                    BingoError::fact_store_with_id(fact_id, "modify", "Fact not found in session")
+                })?;
+                self.engine.retract_fact(fact_id)?;
+                fact.data.fields.extend(updates);
+                agenda.pending.push_back(fact);
+            }
+        }
+        self.agenda_changed.notify_all();
+
+        debug!(fact_id = fact_id, "Fact modified in session");
+        self.emit(&SessionEvent::FactModified { handle, updated_fields });
+        Ok(())
+    }
+
+    /// Remove a fact and everything it logically supported
+    pub fn retract(&self, handle: FactHandle) -> BingoResult<RetractionResult> {
+        let fact_id = handle.fact_id();
+
+        let retraction = {
+            let mut agenda = self.agenda.lock().unwrap();
+            if let Some(position) = agenda.pending.iter().position(|fact| fact.id == fact_id) {
+                // Never matched, so nothing depends on it yet
+                agenda.pending.remove(position);
+                RetractionResult { fact_id, ..Default::default() }
+            } else if self.engine.get_fact(fact_id).is_some() {
+                self.engine.retract_fact(fact_id)?
+            } else {
+                return Err(BingoError::fact_store_with_id(
+                    fact_id,
+                    "retract",
+                    "Fact not found in session",
+                ));
+            }
+        };
+
+        debug!(fact_id = fact_id, "Fact retracted from session");
+        self.emit(&SessionEvent::FactRetracted { handle, retraction: retraction.clone() });
+        Ok(retraction)
+    }
+
+    /// Match every queued fact and fire the resulting activations
+    ///
+    /// Facts inserted by listeners while firing are matched in the same call.
+    /// Returns the number of rules fired.
+    pub fn fire_all_rules(&self) -> BingoResult<usize> {
+        let mut rules_fired = 0;
+
+        loop {
+            let batch: Vec<Fact> = self.agenda.lock().unwrap().pending.drain(..).collect();
+            if batch.is_empty() {
+                break;
+            }
+
+            let results = self.engine.process_facts(batch)?;
+            rules_fired += results.len();
+            for result in results {
+                self.emit(&SessionEvent::RuleFired { result });
+            }
+        }
+
+        self.rules_fired.fetch_add(rules_fired as u64, Ordering::Relaxed);
+        if rules_fired > 0 {
+            info!(rules_fired = rules_fired, "Session fired rules");
+        }
+        Ok(rules_fired)
+    }
+
+    /// Fire rules as facts arrive until `halt` is called
+    ///
+    /// Blocks the calling thread; insert, modify and retract from other threads.
+    /// Returns the number of rules fired before halting.
+    pub fn fire_until_halt(&self) -> BingoResult<usize> {
+        let mut rules_fired = 0;
+
+        loop {
+            rules_fired += self.fire_all_rules()?;
+
+            let mut agenda = self.agenda.lock().unwrap();
+            while agenda.pending.is_empty() && !agenda.halted {
+                agenda = self.agenda_changed.wait(agenda).unwrap();
+            }
+            if agenda.halted {
+                agenda.halted = false;
+                break;
+            }
+        }
+
+        info!(rules_fired = rules_fired, "Session halted");
+        self.emit(&SessionEvent::Halted { rules_fired });
+        Ok(rules_fired)
+    }
+
+    /// Stop a running `fire_until_halt` once its current fire completes
+    pub fn halt(&self) {
+        self.agenda.lock().unwrap().halted = true;
+        self.agenda_changed.notify_all();
+    }
+
+    /// Number of facts waiting for the next fire
+    pub fn pending_count(&self) -> usize {
+        self.agenda.lock().unwrap().pending.len()
+    }
+
+    /// Total rules fired by this session
+    pub fn rules_fired(&self) -> u64 {
+        self.rules_fired.load(Ordering::Relaxed)
+    }
+
+    /// ID the next fact inserted without one will be given
+    pub fn next_fact_id(&self) -> u64 {
+        self.next_fact_id.load(Ordering::SeqCst)
+    }
+
+    /// Continue giving fact IDs from `next`, e.g. after restoring the engine
+    pub fn resume_fact_ids(&self, next: u64) {
+        self.next_fact_id.store(next, Ordering::SeqCst);
+    }
+
+    fn emit(&self, event: &SessionEvent) {
+        for listener in self.listeners.read().unwrap().iter() {
+            listener.on_event(event);
+        }
+    }
+}
@@ -0,0 +1,320 @@
+//! Shadow evaluation of candidate rulesets
+//!
+//! Deploying a changed ruleset straight to live traffic risks rules firing where they
+//! should not, or no longer firing where they should. `BingoEngine::start_shadow`
+//! compiles a candidate ruleset next to the active one; every batch processed with
+//! `process_facts` is then also evaluated by the candidate, in parallel with the active
+//! ruleset, and the engine records the facts the two rulesets disagree on:
+//!
+//! - **Live only**: Rules the active ruleset fired that the candidate would not
+//! - **Candidate only**: Rules the candidate would fire that the active ruleset did not
+//! - **Changed**: Rules both fired whose actions would produce different results
+//!
+//! The candidate runs in a dry-run network over its own copy of working memory, so its
+//! actions never reach the engine's facts, the results returned to callers or the audit
+//! log, and its webhook actions are not dispatched. A candidate failing on a batch is
+//! counted in the report without failing the batch. Facts later retracted or evicted
+//! from working memory stay in the candidate's copy.
+
+use crate::error::{BingoError, BingoResult};
+use crate::fact_store::arena_store::ArenaFactStore;
+use crate::rete_network::ReteNetwork;
+use crate::rete_nodes::{ActionResult, RuleExecutionResult};
+use crate::types::{Fact, FactId, RuleId};
+use bingo_calculator::calculator::Calculator;
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::{Arc, Mutex};
+use tracing::{debug, warn};
+
+/// Divergent facts kept in a shadow report before the oldest is dropped
+pub const DEFAULT_DIVERGENCE_LIMIT: usize = 1_000;
+
+/// Rules the active and candidate rulesets fired differently for one fact
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShadowDivergence {
+    pub batch_id: u64,
+    pub fact_id: FactId,
+    /// Rules only the active ruleset fired
+    pub live_only: Vec<RuleId>,
+    /// Rules only the candidate ruleset would fire
+    pub candidate_only: Vec<RuleId>,
+    /// Rules both fired whose actions would produce different results
+    pub changed: Vec<RuleId>,
+}
+
+/// Facts one rule fired differently for under the candidate ruleset
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RuleDivergence {
+    pub live_only: u64,
+    pub candidate_only: u64,
+    pub changed: u64,
+}
+
+/// Comparison of a candidate ruleset with the active one since shadowing started
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShadowReport {
+    pub started_at: DateTime<Utc>,
+    pub candidate_rule_count: usize,
+    /// Batches evaluated by both rulesets
+    pub batches: u64,
+    pub facts: u64,
+    pub live_activations: u64,
+    pub candidate_activations: u64,
+    /// Facts at least one rule fired differently for
+    pub divergent_facts: u64,
+    /// Batches the candidate failed to evaluate
+    pub failed_batches: u64,
+    pub last_error: Option<String>,
+    /// Divergence counts of every rule that fired differently
+    pub rules: BTreeMap<RuleId, RuleDivergence>,
+    /// Most recent divergent facts, oldest first
+    pub divergences: Vec<ShadowDivergence>,
+}
+
+impl ShadowReport {
+    fn new(candidate_rule_count: usize) -> Self {
+        Self {
+            started_at: Utc::now(),
+            candidate_rule_count,
+            batches: 0,
+            facts: 0,
+            live_activations: 0,
+            candidate_activations: 0,
+            divergent_facts: 0,
+            failed_batches: 0,
+            last_error: None,
+            rules: BTreeMap::new(),
+            divergences: Vec::new(),
+        }
+    }
+
+    /// Whether the candidate fired exactly like the active ruleset on every batch
+    pub fn is_clean(&self) -> bool {
+        self.divergent_facts == 0 && self.failed_batches == 0
+    }
+}
+
+/// Candidate ruleset evaluated alongside an engine's active ruleset
+pub struct ShadowEvaluation {
+    network: Mutex<ReteNetwork>,
+    fact_store: ArenaFactStore,
+    calculator: Arc<Calculator>,
+    divergence_limit: usize,
+    report: Mutex<ShadowReport>,
+}
+
+impl std::fmt::Debug for ShadowEvaluation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShadowEvaluation")
+            .field("facts", &self.fact_store.len())
+            .field("divergence_limit", &self.divergence_limit)
+            .finish()
+    }
+}
+
+impl ShadowEvaluation {
+    /// Shadow a ruleset compiled into a dry-run `network`, starting from copies of the
+    /// facts in working memory
+    pub(crate) fn new(
+        network: ReteNetwork,
+        candidate_rule_count: usize,
+        facts: Vec<Fact>,
+        calculator: Arc<Calculator>,
+        divergence_limit: usize,
+    ) -> Self {
+        let fact_store = ArenaFactStore::with_capacity(facts.len());
+        for fact in facts {
+            fact_store.insert_with_id(fact);
+        }
+        Self {
+            network: Mutex::new(network),
+            fact_store,
+            calculator,
+            divergence_limit,
+            report: Mutex::new(ShadowReport::new(candidate_rule_count)),
+        }
+    }
+
+    /// Run a batch, already stored in working memory, through the candidate ruleset
+    pub(crate) fn evaluate(&self, facts: &[Fact]) -> BingoResult<Vec<RuleExecutionResult>> {
+        for fact in facts {
+            self.fact_store.insert_with_id(fact.clone());
+        }
+        self.network
+            .lock()
+            .unwrap()
+            .process_facts(facts, &self.fact_store, &self.calculator)
+            .map_err(|e| BingoError::rete_network("shadow_evaluation", e.to_string()))
+    }
+
+    /// Compare the candidate's results for a batch with the active ruleset's
+    pub(crate) fn record(
+        &self,
+        batch_id: u64,
+        facts: &[Fact],
+        live: &[RuleExecutionResult],
+        candidate: BingoResult<Vec<RuleExecutionResult>>,
+    ) {
+        let mut report = self.report.lock().unwrap();
+        let candidate = match candidate {
+            Ok(candidate) => candidate,
+            Err(error) => {
+                warn!(batch_id, error = %error, "Candidate ruleset failed shadow evaluation");
+                report.failed_batches += 1;
+                report.last_error = Some(error.to_string());
+                return;
+            }
+        };
+
+        report.batches += 1;
+        report.facts += facts.len() as u64;
+        report.live_activations += live.len() as u64;
+        report.candidate_activations += candidate.len() as u64;
+
+        let live = firings_by_fact(live);
+        let candidate = firings_by_fact(&candidate);
+        let fact_ids: BTreeSet<FactId> = live.keys().chain(candidate.keys()).copied().collect();
+        let no_firings = BTreeMap::new();
+        for fact_id in fact_ids {
+            let divergence = compare_firings(
+                batch_id,
+                fact_id,
+                live.get(&fact_id).unwrap_or(&no_firings),
+                candidate.get(&fact_id).unwrap_or(&no_firings),
+            );
+            if let Some(divergence) = divergence {
+                report.divergent_facts += 1;
+                for rule_id in &divergence.live_only {
+                    report.rules.entry(*rule_id).or_default().live_only += 1;
+                }
+                for rule_id in &divergence.candidate_only {
+                    report.rules.entry(*rule_id).or_default().candidate_only += 1;
+                }
+                for rule_id in &divergence.changed {
+                    report.rules.entry(*rule_id).or_default().changed += 1;
+                }
+                report.divergences.push(divergence);
+            }
+        }
+
+        let overflow = report.divergences.len().saturating_sub(self.divergence_limit);
+        report.divergences.drain(..overflow);
+        debug!(
+            batch_id,
+            divergent_facts = report.divergent_facts,
+            "Recorded shadow evaluation"
+        );
+    }
+
+    /// Comparison of the candidate with the active ruleset so far
+    pub fn report(&self) -> ShadowReport {
+        self.report.lock().unwrap().clone()
+    }
+}
+
+/// Action results of each rule firing, grouped by matched fact and rule
+type Firings<'a> = BTreeMap<FactId, BTreeMap<RuleId, Vec<&'a [ActionResult]>>>;
+
+fn firings_by_fact(results: &[RuleExecutionResult]) -> Firings<'_> {
+    let mut firings = Firings::new();
+    for result in results {
+        firings
+            .entry(result.fact_id)
+            .or_default()
+            .entry(result.rule_id)
+            .or_default()
+            .push(&result.actions_executed);
+    }
+    firings
+}
+
+fn compare_firings(
+    batch_id: u64,
+    fact_id: FactId,
+    live: &BTreeMap<RuleId, Vec<&[ActionResult]>>,
+    candidate: &BTreeMap<RuleId, Vec<&[ActionResult]>>,
+) -> Option<ShadowDivergence> {
+    let live_only: Vec<RuleId> = live
+        .keys()
+        .filter(|rule_id| !candidate.contains_key(rule_id))
+        .copied()
+        .collect();
+    let candidate_only: Vec<RuleId> = candidate
+        .keys()
+        .filter(|rule_id| !live.contains_key(rule_id))
+        .copied()
+        .collect();
+    let changed: Vec<RuleId> = live
+        .iter()
+        .filter_map(|(rule_id, live_actions)| {
+            let candidate_actions = candidate.get(rule_id)?;
+            let same = live_actions.len() == candidate_actions.len()
+                && live_actions
+                    .iter()
+                    .zip(candidate_actions)
+                    .all(|(live, candidate)| same_actions(live, candidate));
+            (!same).then_some(*rule_id)
+        })
+        .collect();
+
+    if live_only.is_empty() && candidate_only.is_empty() && changed.is_empty() {
+        return None;
+    }
+    Some(ShadowDivergence { batch_id, fact_id, live_only, candidate_only, changed })
+}
+
+/// Whether two firings' actions had the same effect, ignoring webhook delivery, which
+/// the dry-run candidate never attempts
+pub(crate) fn same_actions(live: &[ActionResult], candidate: &[ActionResult]) -> bool {
+    live.len() == candidate.len()
+        && live.iter().zip(candidate).all(|pair| match pair {
+            (
+                ActionResult::WebhookDispatched { endpoint: live, .. },
+                ActionResult::WebhookDispatched { endpoint: candidate, .. },
+            ) => live == candidate,
+            (live, candidate) => live == candidate,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::FactValue;
+
+    fn fired(rule_id: RuleId, fact_id: FactId, value: i64) -> RuleExecutionResult {
+        RuleExecutionResult {
+            rule_id,
+            fact_id,
+            actions_executed: vec![ActionResult::FieldSet {
+                fact_id,
+                field: "score".to_string(),
+                value: FactValue::Integer(value),
+            }],
+            ..RuleExecutionResult::default()
+        }
+    }
+
+    #[test]
+    fn test_record_classifies_divergent_rules() {
+        let shadow = ShadowEvaluation::new(ReteNetwork::new(), 2, vec![], Arc::default(), 1);
+        let live = vec![fired(1, 10, 5), fired(2, 10, 5), fired(1, 11, 5)];
+        let candidate = vec![fired(2, 10, 6), fired(3, 10, 5), fired(1, 11, 5)];
+        shadow.record(1, &[], &live, Ok(candidate));
+
+        let report = shadow.report();
+        assert_eq!(report.divergent_facts, 1);
+        assert_eq!(
+            report.divergences,
+            vec![ShadowDivergence {
+                batch_id: 1,
+                fact_id: 10,
+                live_only: vec![1],
+                candidate_only: vec![3],
+                changed: vec![2],
+            }]
+        );
+        assert_eq!(report.rules[&2].changed, 1);
+        assert!(!report.is_clean());
+    }
+}
@@ -0,0 +1,410 @@
+//! Warm standby engines for failover
+//!
+//! `BingoEngine::start_replication` snapshots a primary engine and from then on sends
+//! every rule change and fact delta to a `ReplicationSink` as a numbered
+//! `ReplicationEntry`, in the order the primary applied them. A `WarmStandby` seeded
+//! with the snapshot applies the entries to its own engine as they arrive, so its
+//! network memories stay warm and it can take over as soon as the primary fails.
+//!
+//! A standby may run in the same process, fed through the channel set up by
+//! `BingoEngine::spawn_standby`, or on a remote peer: `ReplicationEntry::to_bytes`
+//! encodes entries for any transport, and `EngineSnapshot::to_bytes` the snapshot the
+//! peer starts from.
+//!
+//! At most once per digest interval the primary also sends a `StateDigest` of its
+//! rules and facts. The standby compares it with a digest of its own state at the same
+//! point of the stream and reports a `Divergence` when they differ. Engine settings,
+//! calendars, reference data and webhooks are not streamed: configure the standby
+//! like the primary, and restart replication after changing them. Webhook actions are
+//! not dispatched by a standby until it is promoted.
+
+use crate::engine::BingoEngine;
+use crate::error::{BingoError, BingoResult};
+use crate::follower::EngineSnapshot;
+use crate::types::{Fact, FactId, Rule, RuleId};
+use crossbeam::channel::{Receiver, Sender};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// Version of the binary replication entry encoding, bumped on incompatible changes
+const REPLICATION_FORMAT_VERSION: u32 = 1;
+
+/// How often the primary sends a state digest when not configured
+pub const DEFAULT_DIGEST_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Settings of a replication stream
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplicationConfig {
+    /// Least time between two state digests; a digest follows the first change after
+    /// the interval has passed
+    pub digest_interval: Duration,
+}
+
+impl Default for ReplicationConfig {
+    fn default() -> Self {
+        Self { digest_interval: DEFAULT_DIGEST_INTERVAL }
+    }
+}
+
+/// One change applied to the primary engine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ReplicationRecord {
+    /// A rule was added under the primary's duplicate rule policy
+    RuleAdded(Rule),
+    /// A rule was replaced in place
+    RuleUpdated(Rule),
+    /// A rule was removed
+    RuleRemoved(RuleId),
+    /// Facts were stored under the given IDs and run through the network
+    FactsInserted {
+        facts: Vec<Fact>,
+        /// Next sequential fact ID after storing the facts, so facts derived from them
+        /// take the same IDs on the standby
+        next_fact_id: FactId,
+    },
+    /// Facts were withdrawn without truth maintenance, by eviction, memory pressure
+    /// relief or an explicit removal
+    FactsRemoved(Vec<FactId>),
+    /// A fact was retracted with truth maintenance
+    FactRetracted(FactId),
+    /// All facts were cleared, keeping the rules
+    FactsCleared,
+    /// All rules and facts were cleared
+    Cleared,
+    /// Digest of the primary's state after every earlier entry
+    Digest(StateDigest),
+}
+
+/// A replication record with its position in the stream
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplicationEntry {
+    /// Position in the stream, starting at 1 after the snapshot replication began with
+    pub sequence: u64,
+    pub record: ReplicationRecord,
+}
+
+#[derive(Serialize)]
+struct EntryImageRef<'a> {
+    format_version: u32,
+    entry: &'a ReplicationEntry,
+}
+
+#[derive(Deserialize)]
+struct EntryImage {
+    format_version: u32,
+    entry: ReplicationEntry,
+}
+
+impl ReplicationEntry {
+    /// Encode the entry in a compact binary format for sending to a remote standby
+    pub fn to_bytes(&self) -> BingoResult<Vec<u8>> {
+        let image = EntryImageRef { format_version: REPLICATION_FORMAT_VERSION, entry: self };
+        let mut bytes = Vec::new();
+        ciborium::into_writer(&image, &mut bytes)
+            .map_err(|e| BingoError::serialization("ReplicationEntry", "encode", e.to_string()))?;
+        Ok(bytes)
+    }
+
+    /// Decode an entry encoded by [`to_bytes`](Self::to_bytes)
+    pub fn from_bytes(bytes: &[u8]) -> BingoResult<Self> {
+        let image: EntryImage = ciborium::from_reader(bytes)
+            .map_err(|e| BingoError::serialization("ReplicationEntry", "decode", e.to_string()))?;
+        if image.format_version != REPLICATION_FORMAT_VERSION {
+            return Err(BingoError::serialization(
+                "ReplicationEntry",
+                "decode",
+                format!(
+                    "Unsupported replication format version {} (expected {REPLICATION_FORMAT_VERSION})",
+                    image.format_version
+                ),
+            ));
+        }
+        Ok(image.entry)
+    }
+}
+
+/// Destination of a primary engine's replication stream
+///
+/// Entries are sent while the primary holds its network lock, so that they arrive in
+/// the order the changes were applied; implementations should hand them off, for
+/// example to a channel or a network client's send queue, rather than block.
+pub trait ReplicationSink: Send + Sync {
+    fn send(&self, entry: ReplicationEntry);
+}
+
+impl ReplicationSink for Sender<ReplicationEntry> {
+    fn send(&self, entry: ReplicationEntry) {
+        // A standby that has gone away only stops receiving
+        let _ = Sender::send(self, entry);
+    }
+}
+
+/// Order-independent digest of an engine's rules and stored facts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateDigest {
+    pub rule_count: usize,
+    pub fact_count: usize,
+    /// Hash of the rules in ruleset order
+    pub rules_hash: u64,
+    /// Hash of the stored facts in any order
+    pub facts_hash: u64,
+}
+
+impl StateDigest {
+    /// Digest `rules` and `facts`, hashing values the same way on every platform and
+    /// build so remote peers can compare digests
+    pub(crate) fn compute(rules: &[Rule], facts: &[Fact]) -> Self {
+        let rules_hash = rules.iter().fold(FNV_OFFSET_BASIS, |hash, rule| {
+            fnv1a(hash, &canonical_hash(rule).to_le_bytes())
+        });
+        let facts_hash =
+            facts.iter().fold(0u64, |hash, fact| hash.wrapping_add(canonical_hash(fact)));
+        Self { rule_count: rules.len(), fact_count: facts.len(), rules_hash, facts_hash }
+    }
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+/// FNV-1a hash of `value` encoded as JSON with object keys sorted, so hash map
+/// iteration order does not change it
+fn canonical_hash<T: Serialize>(value: &T) -> u64 {
+    let canonical = serde_json::to_value(value)
+        .and_then(|value| serde_json::to_vec(&value))
+        .unwrap_or_default();
+    fnv1a(FNV_OFFSET_BASIS, &canonical)
+}
+
+/// Records a committing transaction held back, and the thread committing it
+struct HeldRecords {
+    owner: std::thread::ThreadId,
+    records: Vec<ReplicationRecord>,
+}
+
+/// Replication stream of a primary engine
+pub(crate) struct Replicator {
+    sink: Box<dyn ReplicationSink>,
+    sequence: AtomicU64,
+    digest_interval: Duration,
+    last_digest: Mutex<Instant>,
+    /// Records held back while a transaction commits, sent only if it applies
+    held: Mutex<Option<HeldRecords>>,
+}
+
+impl Replicator {
+    pub(crate) fn new(sink: Box<dyn ReplicationSink>, config: &ReplicationConfig) -> Self {
+        Self {
+            sink,
+            sequence: AtomicU64::new(0),
+            digest_interval: config.digest_interval,
+            last_digest: Mutex::new(Instant::now()),
+            held: Mutex::new(None),
+        }
+    }
+
+    /// Send `record` as the next entry of the stream, or hold it back until
+    /// [`Replicator::release`] when the calling thread is committing a transaction
+    ///
+    /// Digests are skipped while a transaction commits, as the state they describe
+    /// includes changes the stream has not carried yet.
+    pub(crate) fn send(&self, record: ReplicationRecord) {
+        let mut held = self.held.lock().unwrap();
+        if let Some(held) = held.as_mut() {
+            if matches!(record, ReplicationRecord::Digest(_)) {
+                return;
+            }
+            if held.owner == std::thread::current().id() {
+                held.records.push(record);
+                return;
+            }
+        }
+        // Sent under the lock, so a release cannot slip its records in between
+        self.send_entry(record);
+    }
+
+    /// Hold back records the calling thread sends from now on; other threads' records
+    /// are still sent as they come
+    pub(crate) fn hold(&self) {
+        *self.held.lock().unwrap() =
+            Some(HeldRecords { owner: std::thread::current().id(), records: Vec::new() });
+    }
+
+    /// Stop holding records back, sending the held ones if `send` is true and
+    /// dropping them otherwise
+    pub(crate) fn release(&self, send: bool) {
+        let mut held = self.held.lock().unwrap();
+        let records = held.take().map(|held| held.records).unwrap_or_default();
+        if send {
+            for record in records {
+                self.send_entry(record);
+            }
+        }
+    }
+
+    fn send_entry(&self, record: ReplicationRecord) {
+        let sequence = self.sequence.fetch_add(1, Ordering::SeqCst) + 1;
+        self.sink.send(ReplicationEntry { sequence, record });
+    }
+
+    /// Whether a digest is due, restarting the interval if it is
+    pub(crate) fn take_digest_due(&self) -> bool {
+        let mut last_digest = self.last_digest.lock().unwrap();
+        if last_digest.elapsed() < self.digest_interval {
+            return false;
+        }
+        *last_digest = Instant::now();
+        true
+    }
+}
+
+/// Primary and standby digests that differed at the same point of the stream
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Divergence {
+    /// Sequence of the digest entry
+    pub sequence: u64,
+    pub primary: StateDigest,
+    pub standby: StateDigest,
+}
+
+/// Engine kept in step with a primary by its replication stream, ready to take over
+#[derive(Debug)]
+pub struct WarmStandby {
+    engine: BingoEngine,
+    entries: Option<Receiver<ReplicationEntry>>,
+    applied_sequence: u64,
+    digests_checked: u64,
+    divergence: Option<Divergence>,
+}
+
+impl WarmStandby {
+    /// Standby starting from the snapshot replication began with
+    pub fn from_snapshot(snapshot: &EngineSnapshot) -> BingoResult<Self> {
+        Self::with_engine(BingoEngine::new()?, snapshot)
+    }
+
+    /// Standby running on `engine`, with its calendars, reference data, calculators
+    /// and webhooks, restored from the snapshot replication began with
+    pub fn with_engine(engine: BingoEngine, snapshot: &EngineSnapshot) -> BingoResult<Self> {
+        engine.set_webhook_dry_run(true);
+        engine.restore(snapshot)?;
+        Ok(Self {
+            engine,
+            entries: None,
+            applied_sequence: 0,
+            digests_checked: 0,
+            divergence: None,
+        })
+    }
+
+    /// Receive the entries to apply on `catch_up` from `entries`
+    pub(crate) fn receiving(mut self, entries: Receiver<ReplicationEntry>) -> Self {
+        self.entries = Some(entries);
+        self
+    }
+
+    /// Apply the next entry of the stream
+    ///
+    /// Entries already applied are skipped, so a transport may redeliver them. An entry
+    /// past the next one is refused, as the standby would miss changes in between; seed
+    /// a new standby from a fresh snapshot then.
+    pub fn apply(&mut self, entry: &ReplicationEntry) -> BingoResult<()> {
+        if entry.sequence <= self.applied_sequence {
+            return Ok(());
+        }
+        if entry.sequence != self.applied_sequence + 1 {
+            return Err(BingoError::internal_component(
+                "warm_standby",
+                format!(
+                    "Replication entry {} arrived after entry {}; entries in between were lost",
+                    entry.sequence, self.applied_sequence
+                ),
+            ));
+        }
+
+        match &entry.record {
+            ReplicationRecord::Digest(primary) => {
+                let standby = self.engine.state_digest();
+                self.digests_checked += 1;
+                if standby == *primary {
+                    self.divergence = None;
+                } else {
+                    warn!(
+                        sequence = entry.sequence,
+                        primary = ?primary,
+                        standby = ?standby,
+                        "Warm standby diverged from its primary"
+                    );
+                    self.divergence =
+                        Some(Divergence { sequence: entry.sequence, primary: *primary, standby });
+                }
+            }
+            record => {
+                self.engine.apply_replicated(record)?;
+                if matches!(record, ReplicationRecord::Cleared) {
+                    self.engine.set_webhook_dry_run(true);
+                }
+            }
+        }
+        self.applied_sequence = entry.sequence;
+        Ok(())
+    }
+
+    /// Apply every entry received from an in-process primary so far, returning how
+    /// many were applied
+    pub fn catch_up(&mut self) -> BingoResult<usize> {
+        let Some(entries) = self.entries.take() else {
+            return Ok(0);
+        };
+        let mut applied = 0;
+        let result = entries.try_iter().try_for_each(|entry| {
+            self.apply(&entry)?;
+            applied += 1;
+            BingoResult::Ok(())
+        });
+        self.entries = Some(entries);
+        result.map(|()| applied)
+    }
+
+    /// Sequence of the last entry applied, 0 before the first
+    pub fn applied_sequence(&self) -> u64 {
+        self.applied_sequence
+    }
+
+    /// Digests compared with the primary's so far
+    pub fn digests_checked(&self) -> u64 {
+        self.digests_checked
+    }
+
+    /// Difference found at the last digest, if the standby disagreed with the primary
+    pub fn divergence(&self) -> Option<&Divergence> {
+        self.divergence.as_ref()
+    }
+
+    /// The standby's engine, for read-only queries
+    pub fn engine(&self) -> &BingoEngine {
+        &self.engine
+    }
+
+    /// Take over from the failed primary: apply what is left of the stream from an
+    /// in-process primary and return the engine, dispatching webhooks from now on
+    pub fn promote(mut self) -> BingoResult<BingoEngine> {
+        self.catch_up()?;
+        self.engine.set_webhook_dry_run(false);
+        info!(
+            applied_sequence = self.applied_sequence,
+            fact_count = self.engine.fact_count(),
+            rule_count = self.engine.rule_count(),
+            "Promoted warm standby engine"
+        );
+        Ok(self.engine)
+    }
+}
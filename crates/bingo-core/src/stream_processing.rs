@@ -4,7 +4,8 @@
 //! and event processing, including windowed aggregations, temporal pattern matching,
 //! and out-of-order event handling.
 
-use super::types::{Fact, FactValue};
+use super::aggregation_node::exact_decimal_sum;
+use super::types::{Decimal, Fact, FactValue, StreamAggregation};
 use std::collections::{BTreeMap, HashMap};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
@@ -37,6 +38,28 @@ impl Timestamp {
     pub fn sub_duration(&self, duration: Duration) -> Self {
         Self(self.0.saturating_sub(duration.as_millis() as u64))
     }
+
+    /// Event time of a fact
+    ///
+    /// Uses the first integer timestamp field (`timestamp`, `time`, `event_time`,
+    /// `created_at`) holding milliseconds since epoch, falling back to the time the
+    /// fact was created.
+    pub fn of_fact(fact: &Fact) -> Self {
+        Self::from_fields(fact)
+            .unwrap_or_else(|| Self(fact.timestamp.timestamp_millis().max(0) as u64))
+    }
+
+    /// Event time carried in a fact's fields, if any
+    fn from_fields(fact: &Fact) -> Option<Self> {
+        const TIMESTAMP_FIELDS: [&str; 4] = ["timestamp", "time", "event_time", "created_at"];
+
+        TIMESTAMP_FIELDS
+            .iter()
+            .find_map(|field_name| match fact.data.fields.get(*field_name) {
+                Some(FactValue::Integer(millis)) => Some(Self::from_millis(*millis as u64)),
+                _ => None,
+            })
+    }
 }
 
 /// Window specification for stream processing
@@ -61,6 +84,72 @@ pub enum WindowSpec {
     },
 }
 
+/// What windowed conditions do with events that arrive behind the watermark
+///
+/// The watermark is the latest event time seen. Under `Drop`, an event more than
+/// `allowed_lateness` behind it is dropped, and a window closes once the watermark
+/// passes its end by as much. Under `Accept`, late events are aggregated into their
+/// windows however late they arrive, so windows stay open until their facts are
+/// retracted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LateDataPolicy {
+    /// Drop events more than `allowed_lateness` behind the watermark
+    Drop { allowed_lateness: Duration },
+    /// Aggregate every event and never close windows
+    Accept,
+}
+
+impl Default for LateDataPolicy {
+    /// Drop events more than a minute late, matching the `StreamProcessor`
+    fn default() -> Self {
+        Self::Drop { allowed_lateness: Duration::from_secs(60) }
+    }
+}
+
+impl LateDataPolicy {
+    /// Whether an event at `event_time` arrives too late to be aggregated
+    pub fn is_late(&self, event_time: Timestamp, watermark: Timestamp) -> bool {
+        match self {
+            Self::Drop { allowed_lateness } => {
+                event_time.add_duration(*allowed_lateness) < watermark
+            }
+            Self::Accept => false,
+        }
+    }
+
+    /// Whether a window ending at `end` can no longer receive events
+    pub fn is_closed(&self, end: Timestamp, watermark: Timestamp) -> bool {
+        match self {
+            Self::Drop { allowed_lateness } => end.add_duration(*allowed_lateness) <= watermark,
+            Self::Accept => false,
+        }
+    }
+}
+
+/// Start of the tumbling window of `size` containing `position`
+///
+/// Windows are aligned to zero, so time windows start on multiples of their size since
+/// the epoch.
+pub fn tumbling_window_start(position: u64, size: u64) -> u64 {
+    let size = size.max(1);
+    position / size * size
+}
+
+/// Starts of the sliding windows of `size`, one every `advance`, containing `position`
+pub fn sliding_window_starts(position: u64, size: u64, advance: u64) -> Vec<u64> {
+    let size = size.max(1);
+    let advance = advance.max(1);
+
+    // Every start aligned to `advance` with start <= position < start + size
+    let mut start = (position + 1).saturating_sub(size).div_ceil(advance) * advance;
+    let mut starts = Vec::new();
+    while start <= position {
+        starts.push(start);
+        start += advance;
+    }
+    starts
+}
+
 /// Aggregation function for windowed operations
 #[derive(Debug, Clone, PartialEq)]
 pub enum AggregationFunction {
@@ -88,6 +177,29 @@ pub enum AggregationFunction {
     Custom { field: String, expression: String },
 }
 
+impl AggregationFunction {
+    /// Equivalent windowed aggregation for a rule's stream aggregation
+    ///
+    /// Returns `None` for `First`, `Last` and `Rate`, which depend on event time
+    /// ordering and are computed by the window node itself.
+    pub fn from_stream(aggregation: &StreamAggregation) -> Option<Self> {
+        match aggregation {
+            StreamAggregation::Count => Some(Self::Count),
+            StreamAggregation::Sum { field } => Some(Self::Sum { field: field.clone() }),
+            StreamAggregation::Average { field } => Some(Self::Average { field: field.clone() }),
+            StreamAggregation::Min { field } => Some(Self::Min { field: field.clone() }),
+            StreamAggregation::Max { field } => Some(Self::Max { field: field.clone() }),
+            StreamAggregation::Distinct { field } => Some(Self::Distinct { field: field.clone() }),
+            StreamAggregation::Custom { expression } => {
+                Some(Self::Custom { field: String::new(), expression: expression.clone() })
+            }
+            StreamAggregation::First { .. }
+            | StreamAggregation::Last { .. }
+            | StreamAggregation::Rate { .. } => None,
+        }
+    }
+}
+
 /// Window instance containing facts and metadata
 #[derive(Debug, Clone)]
 pub struct WindowInstance {
@@ -142,6 +254,7 @@ impl WindowInstance {
         let result = match func {
             AggregationFunction::Count => FactValue::Integer(self.facts.len() as i64),
             AggregationFunction::Sum { field } => {
+                let values = self.facts.iter().filter_map(|fact| fact.data.fields.get(field));
                 let mut sum = 0.0;
                 for fact in &self.facts {
                     if let Some(value) = fact.data.fields.get(field) {
@@ -152,7 +265,9 @@ impl WindowInstance {
                         }
                     }
                 }
-                if sum.fract() == 0.0 {
+                if let Some((exact, _)) = exact_decimal_sum(values) {
+                    FactValue::Decimal(exact)
+                } else if sum.fract() == 0.0 {
                     FactValue::Integer(sum as i64)
                 } else {
                     FactValue::Float(sum)
@@ -176,7 +291,10 @@ impl WindowInstance {
                         }
                     }
                 }
-                if count > 0 {
+                let values = self.facts.iter().filter_map(|fact| fact.data.fields.get(field));
+                if let Some((exact, count)) = exact_decimal_sum(values) {
+                    FactValue::Decimal(exact / Decimal::from(count))
+                } else if count > 0 {
                     FactValue::Float(sum / count as f64)
                 } else {
                     FactValue::Float(0.0)
@@ -351,6 +469,12 @@ impl WindowInstance {
             }
             (FactValue::String(a), FactValue::String(b)) => a.cmp(b) as i32,
             (FactValue::Boolean(a), FactValue::Boolean(b)) => a.cmp(b) as i32,
+            (FactValue::Decimal(_), _)
+            | (_, FactValue::Decimal(_))
+            | (FactValue::Date(_), FactValue::Date(_))
+            | (FactValue::Duration(_), FactValue::Duration(_)) => {
+                a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal) as i32
+            }
             _ => 0, // Incomparable types are equal
         }
     }
@@ -669,21 +793,7 @@ impl StreamProcessor {
 
     /// Extract timestamp from fact data
     fn extract_timestamp_from_fact(&self, fact: &Fact) -> Option<Timestamp> {
-        // Try common timestamp field names
-        let timestamp_fields = ["timestamp", "time", "event_time", "created_at"];
-
-        for field_name in &timestamp_fields {
-            if let Some(value) = fact.data.fields.get(*field_name) {
-                match value {
-                    FactValue::Integer(millis) => {
-                        return Some(Timestamp::from_millis(*millis as u64));
-                    }
-                    _ => continue,
-                }
-            }
-        }
-
-        None
+        Timestamp::from_fields(fact)
     }
 
     /// Compute aggregation for a specific window
@@ -760,7 +870,13 @@ mod tests {
             FactValue::Integer(timestamp as i64),
         );
 
-        Fact { id, external_id: None, timestamp: chrono::Utc::now(), data: FactData { fields } }
+        Fact {
+            id,
+            external_id: None,
+            timestamp: chrono::Utc::now(),
+            fact_type: None,
+            data: FactData { fields },
+        }
     }
 
     #[test]
@@ -1032,4 +1148,24 @@ mod tests {
         // Should be dropped due to lateness
         assert_eq!(processor.stats.late_events_dropped, 1);
     }
+
+    #[test]
+    fn test_window_starts_and_late_data_policy() {
+        assert_eq!(tumbling_window_start(12_345, 1000), 12_000);
+        assert_eq!(
+            sliding_window_starts(12_345, 3000, 1000),
+            vec![10_000, 11_000, 12_000]
+        );
+        assert_eq!(sliding_window_starts(500, 3000, 1000), vec![0]);
+
+        let drop = LateDataPolicy::Drop { allowed_lateness: Duration::from_secs(5) };
+        let watermark = Timestamp::from_millis(10_000);
+        assert!(drop.is_late(Timestamp::from_millis(4_999), watermark));
+        assert!(!drop.is_late(Timestamp::from_millis(5_000), watermark));
+        assert!(drop.is_closed(Timestamp::from_millis(5_000), watermark));
+        assert!(!drop.is_closed(Timestamp::from_millis(5_001), watermark));
+
+        assert!(!LateDataPolicy::Accept.is_late(Timestamp::from_millis(0), watermark));
+        assert!(!LateDataPolicy::Accept.is_closed(Timestamp::from_millis(0), watermark));
+    }
 }
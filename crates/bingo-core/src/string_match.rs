@@ -0,0 +1,205 @@
+//! Regex and case-insensitive string operators
+//!
+//! `Matches` tests a string against a regular expression and `ContainsIgnoreCase`
+//! looks for a substring regardless of case, so facts don't have to be lowercased or
+//! otherwise normalized before they reach the engine.
+//!
+//! Regexes are compiled into the network's [`CompiledRegexes`] when a rule is added,
+//! which also rejects invalid patterns, so rule evaluation never recompiles them per
+//! fact. They belong to the network, so they are dropped with the rules using them
+//! when the network is rebuilt. Like the `regex` crate, `Matches` finds the pattern
+//! anywhere in the string unless it is anchored with `^` and `$`.
+
+use crate::collation::Collation;
+use crate::types::{Condition, FactValue, Operator};
+use anyhow::{Context, Result, bail};
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Compiled regexes of a network's `Matches` conditions, keyed by pattern
+#[derive(Debug, Clone, Default)]
+pub struct CompiledRegexes(HashMap<String, Arc<Regex>>);
+
+impl CompiledRegexes {
+    /// Compile the regex of every `Matches` condition in `condition`, rejecting invalid
+    /// patterns
+    pub fn compile(&mut self, condition: &Condition) -> Result<()> {
+        match condition {
+            Condition::Simple { field, operator: Operator::Matches, value } => match value {
+                FactValue::String(pattern) => {
+                    if !self.0.contains_key(pattern) {
+                        let regex = Regex::new(pattern)
+                            .with_context(|| format!("Invalid regex for field '{field}'"))?;
+                        self.0.insert(pattern.clone(), Arc::new(regex));
+                    }
+                    Ok(())
+                }
+                other => {
+                    bail!("Field '{field}' can only be matched against a string regex, got {other}")
+                }
+            },
+            Condition::Simple { .. } => Ok(()),
+            Condition::Complex { conditions, .. }
+            | Condition::And { conditions }
+            | Condition::Or { conditions } => {
+                conditions.iter().try_for_each(|condition| self.compile(condition))
+            }
+            Condition::Aggregation(aggregation) => {
+                aggregation.having.as_deref().map_or(Ok(()), |having| self.compile(having))
+            }
+            Condition::Stream(stream) => {
+                stream.filter.as_deref().map_or(Ok(()), |filter| self.compile(filter))?;
+                stream.having.as_deref().map_or(Ok(()), |having| self.compile(having))
+            }
+            Condition::Sequence(sequence) => {
+                sequence.steps.iter().try_for_each(|step| self.compile(step))
+            }
+            Condition::Accumulate(accumulate) => self.compile(&accumulate.source),
+        }
+    }
+
+    /// Add the regexes compiled into `other`
+    pub fn extend(&mut self, other: CompiledRegexes) {
+        self.0.extend(other.0);
+    }
+
+    /// Compiled regex for `pattern`, if a condition using it was compiled
+    pub fn get(&self, pattern: &str) -> Option<&Arc<Regex>> {
+        self.0.get(pattern)
+    }
+
+    /// Number of distinct patterns compiled
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether no pattern is compiled
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Evaluate `Matches` or `ContainsIgnoreCase` like [`matches`], testing `Matches`
+    /// with the compiled regex of its pattern
+    pub fn matches(&self, operator: &Operator, actual: &FactValue, expected: &FactValue) -> bool {
+        match (operator, actual, expected) {
+            (Operator::Matches, FactValue::String(text), FactValue::String(pattern)) => {
+                match self.get(pattern) {
+                    Some(regex) => regex.is_match(text),
+                    None => regex_matches(pattern, text),
+                }
+            }
+            _ => matches(operator, actual, expected),
+        }
+    }
+}
+
+/// Compile the regex of every `Matches` condition, rejecting invalid patterns
+pub fn validate_condition(condition: &Condition) -> Result<()> {
+    CompiledRegexes::default().compile(condition)
+}
+
+/// Whether `text` matches the regex `pattern`; an invalid pattern matches nothing
+///
+/// The pattern is compiled on every call. Rule evaluation goes through the network's
+/// [`CompiledRegexes`] instead.
+pub fn regex_matches(pattern: &str, text: &str) -> bool {
+    Regex::new(pattern).is_ok_and(|regex| regex.is_match(text))
+}
+
+/// Whether `haystack` contains `needle` under Unicode case folding
+pub fn contains_ignore_case(haystack: &str, needle: &str) -> bool {
+    Collation::case_insensitive().matches(&Operator::Contains, haystack, needle)
+}
+
+/// Evaluate `Matches` or `ContainsIgnoreCase`
+///
+/// Returns `false` for other operators and for values that aren't strings, except
+/// that `ContainsIgnoreCase` also finds a string in an array of strings.
+pub fn matches(operator: &Operator, actual: &FactValue, expected: &FactValue) -> bool {
+    match (operator, actual, expected) {
+        (Operator::Matches, FactValue::String(text), FactValue::String(pattern)) => {
+            regex_matches(pattern, text)
+        }
+        (Operator::ContainsIgnoreCase, FactValue::String(text), FactValue::String(needle)) => {
+            contains_ignore_case(text, needle)
+        }
+        (Operator::ContainsIgnoreCase, FactValue::Array(items), FactValue::String(needle)) => {
+            let collation = Collation::case_insensitive();
+            items.iter().any(|item| match item {
+                FactValue::String(item) => collation.matches(&Operator::Equal, item, needle),
+                _ => false,
+            })
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn string(text: &str) -> FactValue {
+        FactValue::String(text.to_string())
+    }
+
+    #[test]
+    fn test_regex_is_compiled_once() {
+        let pattern = r"^ORD-\d{6}$";
+        let condition = |field: &str| Condition::Simple {
+            field: field.to_string(),
+            operator: Operator::Matches,
+            value: string(pattern),
+        };
+        let mut regexes = CompiledRegexes::default();
+        regexes.compile(&condition("order_id")).unwrap();
+        let first = Arc::clone(regexes.get(pattern).unwrap());
+        regexes.compile(&condition("reference")).unwrap();
+        assert!(Arc::ptr_eq(&first, regexes.get(pattern).unwrap()));
+        assert_eq!(regexes.len(), 1);
+
+        let operator = Operator::Matches;
+        assert!(regexes.matches(&operator, &string("ORD-123456"), &string(pattern)));
+        assert!(!regexes.matches(&operator, &string("ORD-12345"), &string(pattern)));
+        assert!(!regexes.matches(&operator, &FactValue::Integer(1), &string(r"\d")));
+        assert!(matches(&operator, &string("ORD-123456"), &string(pattern)));
+    }
+
+    #[test]
+    fn test_invalid_regex_is_rejected() {
+        let condition = Condition::And {
+            conditions: vec![Condition::Simple {
+                field: "sku".to_string(),
+                operator: Operator::Matches,
+                value: string("[unclosed"),
+            }],
+        };
+        let error = validate_condition(&condition).unwrap_err();
+        assert!(format!("{error:#}").contains("Invalid regex for field 'sku'"));
+        assert!(!regex_matches("[unclosed", "[unclosed"));
+    }
+
+    #[test]
+    fn test_contains_ignore_case() {
+        let operator = Operator::ContainsIgnoreCase;
+        assert!(matches(
+            &operator,
+            &string("Card DECLINED by issuer"),
+            &string("declined")
+        ));
+        assert!(matches(
+            &operator,
+            &string("Hauptstraße"),
+            &string("STRASSE")
+        ));
+        assert!(!matches(
+            &operator,
+            &string("approved"),
+            &string("declined")
+        ));
+
+        let tags = FactValue::Array(vec![string("VIP"), string("Priority")]);
+        assert!(matches(&operator, &tags, &string("vip")));
+        assert!(!matches(&operator, &tags, &string("vi")));
+    }
+}
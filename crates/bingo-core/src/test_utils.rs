@@ -1,6 +1,33 @@
 //! Performance testing utilities
+//!
+//! Besides a simple `Timer`, this module provides a scenario engine for load tests
+//! and rule behaviour exploration. A `Scenario` combines declarative fact
+//! generators with correlations between fact types and produces a synthetic,
+//! time-ordered fact stream:
+//!
+//! - **`Distribution`**: How each field's values are drawn
+//! - **`ArrivalRate`**: How often a generator emits facts (fixed rate or Poisson)
+//! - **`Correlation`**: Facts of one type that follow facts of another, e.g. a
+//!   payment following an order and carrying its `order_id`
+//!
+//! Streams are fully determined by the scenario's seed, so a failing load test or
+//! a surprising rule outcome can be replayed exactly.
+//!
+//! `StandardScenario` names the workloads the performance documentation quotes, each
+//! with rules, a generated fact stream and baseline pass thresholds that
+//! `PerformanceConfig` adjusts to the machine running them.
 
-use std::time::Instant;
+use crate::engine::BingoEngine;
+use crate::error::{BingoError, BingoResult};
+use crate::performance_config::{PerformanceConfig, ScenarioThresholds};
+use crate::types::{
+    Action, ActionType, Condition, Fact, FactData, FactValue, Operator, Rule, RuleId,
+};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+pub use crate::types::FACT_TYPE_FIELD;
 
 /// A simple timer to measure elapsed time.
 pub struct Timer {
@@ -24,3 +51,861 @@ impl Default for Timer {
         Self::new()
     }
 }
+
+/// Small deterministic random number generator (SplitMix64)
+///
+/// Not suitable for anything security related; it exists so scenarios are
+/// reproducible from a seed without pulling in a random number crate.
+#[derive(Debug, Clone)]
+pub struct ScenarioRng {
+    state: u64,
+}
+
+impl ScenarioRng {
+    /// Create a generator from a seed
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Next 64 random bits
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform float in `[0, 1)`
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniform integer in `[min, max]`
+    pub fn range_i64(&mut self, min: i64, max: i64) -> i64 {
+        let span = (max as i128 - min as i128 + 1) as u128;
+        (min as i128 + (self.next_u64() as u128 % span) as i128) as i64
+    }
+
+    /// Sample from a normal distribution (Box-Muller)
+    pub fn normal(&mut self, mean: f64, std_dev: f64) -> f64 {
+        let u1 = 1.0 - self.next_f64();
+        let u2 = self.next_f64();
+        mean + std_dev * (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+    }
+}
+
+/// How values of a generated field are drawn
+#[derive(Debug, Clone, PartialEq)]
+pub enum Distribution {
+    /// Always the same value
+    Constant(FactValue),
+    /// Integers uniformly distributed over `[min, max]`
+    UniformInt { min: i64, max: i64 },
+    /// Floats uniformly distributed over `[min, max)`
+    UniformFloat { min: f64, max: f64 },
+    /// Normally distributed floats
+    Normal { mean: f64, std_dev: f64 },
+    /// One of a set of values, picked with the given relative weights
+    Weighted(Vec<(FactValue, f64)>),
+    /// `true` with the given probability
+    Bernoulli { probability: f64 },
+    /// `start`, `start + step`, ... counting the facts of the generator
+    Sequence { start: i64, step: i64 },
+    /// `prefix` followed by an integer uniformly distributed over `[0, cardinality)`,
+    /// e.g. customer IDs drawn from a fixed population
+    Key { prefix: String, cardinality: u64 },
+}
+
+impl Distribution {
+    /// Draw a value; `index` is the number of facts the generator produced before
+    pub fn sample(&self, rng: &mut ScenarioRng, index: u64) -> FactValue {
+        match self {
+            Distribution::Constant(value) => value.clone(),
+            Distribution::UniformInt { min, max } => FactValue::Integer(rng.range_i64(*min, *max)),
+            Distribution::UniformFloat { min, max } => {
+                FactValue::Float(min + (max - min) * rng.next_f64())
+            }
+            Distribution::Normal { mean, std_dev } => FactValue::Float(rng.normal(*mean, *std_dev)),
+            Distribution::Weighted(choices) => {
+                let total: f64 = choices.iter().map(|(_, weight)| weight).sum();
+                let mut target = rng.next_f64() * total;
+                for (value, weight) in choices {
+                    if target < *weight {
+                        return value.clone();
+                    }
+                    target -= weight;
+                }
+                choices.last().map(|(value, _)| value.clone()).unwrap_or(FactValue::Null)
+            }
+            Distribution::Bernoulli { probability } => {
+                FactValue::Boolean(rng.next_f64() < *probability)
+            }
+            Distribution::Sequence { start, step } => {
+                FactValue::Integer(start.wrapping_add(step.wrapping_mul(index as i64)))
+            }
+            Distribution::Key { prefix, cardinality } => {
+                FactValue::String(format!("{prefix}{}", rng.next_u64() % cardinality))
+            }
+        }
+    }
+
+    fn validate(&self, field: &str) -> BingoResult<()> {
+        let invalid = match self {
+            Distribution::UniformInt { min, max } => min > max,
+            Distribution::UniformFloat { min, max } => min > max,
+            Distribution::Normal { std_dev, .. } => *std_dev < 0.0,
+            Distribution::Weighted(choices) => {
+                choices.is_empty()
+                    || choices.iter().any(|(_, weight)| *weight < 0.0)
+                    || choices.iter().map(|(_, weight)| weight).sum::<f64>() <= 0.0
+            }
+            Distribution::Bernoulli { probability } => !(0.0..=1.0).contains(probability),
+            Distribution::Key { cardinality, .. } => *cardinality == 0,
+            Distribution::Constant(_) | Distribution::Sequence { .. } => false,
+        };
+        if invalid {
+            return Err(BingoError::configuration(
+                &format!("scenario.field.{field}"),
+                "valid distribution parameters",
+                &format!("{self:?}"),
+                format!("Invalid distribution for field '{field}'"),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// How often a generator emits facts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArrivalRate {
+    /// Evenly spaced facts
+    Fixed { per_second: f64 },
+    /// Exponentially distributed gaps with the given mean rate
+    Poisson { per_second: f64 },
+}
+
+impl ArrivalRate {
+    fn per_second(&self) -> f64 {
+        match self {
+            ArrivalRate::Fixed { per_second } | ArrivalRate::Poisson { per_second } => *per_second,
+        }
+    }
+
+    /// Gap before the next fact, in milliseconds
+    fn next_gap_millis(&self, rng: &mut ScenarioRng) -> f64 {
+        match self {
+            ArrivalRate::Fixed { per_second } => 1000.0 / per_second,
+            ArrivalRate::Poisson { per_second } => {
+                -(1.0 - rng.next_f64()).ln() * 1000.0 / per_second
+            }
+        }
+    }
+}
+
+/// Declarative generator for facts of one type
+#[derive(Debug, Clone)]
+pub struct FactGenerator {
+    pub fact_type: String,
+    pub arrival: ArrivalRate,
+    pub fields: Vec<(String, Distribution)>,
+}
+
+impl FactGenerator {
+    /// Generator for `fact_type` facts arriving at `arrival`
+    pub fn new(fact_type: impl Into<String>, arrival: ArrivalRate) -> Self {
+        Self { fact_type: fact_type.into(), arrival, fields: Vec::new() }
+    }
+
+    /// Add a field drawn from `distribution`
+    pub fn field(mut self, name: impl Into<String>, distribution: Distribution) -> Self {
+        self.fields.push((name.into(), distribution));
+        self
+    }
+}
+
+/// Facts of one type that follow facts of another
+///
+/// Whenever the `trigger` generator emits a fact, a `follower` fact is emitted with
+/// `probability` after a delay uniformly distributed over `delay`. Follower facts
+/// copy the listed fields from the trigger and draw the rest from their own
+/// generator's distributions. Correlations only react to facts of generators with
+/// a non-zero arrival rate, so they cannot cascade.
+#[derive(Debug, Clone)]
+pub struct Correlation {
+    pub trigger: String,
+    pub follower: String,
+    pub probability: f64,
+    pub delay: (Duration, Duration),
+    /// `(trigger field, follower field)` pairs copied onto the follower
+    pub copied_fields: Vec<(String, String)>,
+}
+
+impl Correlation {
+    /// `follower` facts following every `trigger` fact after `delay`
+    pub fn new(trigger: impl Into<String>, follower: impl Into<String>, delay: Duration) -> Self {
+        Self {
+            trigger: trigger.into(),
+            follower: follower.into(),
+            probability: 1.0,
+            delay: (delay, delay),
+            copied_fields: Vec::new(),
+        }
+    }
+
+    /// Only follow a fraction of trigger facts
+    pub fn with_probability(mut self, probability: f64) -> Self {
+        self.probability = probability;
+        self
+    }
+
+    /// Spread the delay uniformly over `[min, max]`
+    pub fn with_delay_range(mut self, min: Duration, max: Duration) -> Self {
+        self.delay = (min, max);
+        self
+    }
+
+    /// Copy a field of the trigger fact onto the follower under the same name
+    pub fn copy_field(self, field: impl Into<String>) -> Self {
+        let field = field.into();
+        self.copy_field_as(field.clone(), field)
+    }
+
+    /// Copy a field of the trigger fact onto the follower under another name
+    pub fn copy_field_as(mut self, from: impl Into<String>, to: impl Into<String>) -> Self {
+        self.copied_fields.push((from.into(), to.into()));
+        self
+    }
+}
+
+/// Seeded set of generators and correlations producing a synthetic fact stream
+#[derive(Debug, Clone)]
+pub struct Scenario {
+    pub seed: u64,
+    /// Event time of the start of the stream
+    pub start: DateTime<Utc>,
+    pub generators: Vec<FactGenerator>,
+    pub correlations: Vec<Correlation>,
+}
+
+impl Scenario {
+    /// Empty scenario starting at the Unix epoch
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            start: DateTime::UNIX_EPOCH,
+            generators: Vec::new(),
+            correlations: Vec::new(),
+        }
+    }
+
+    /// Start the stream at another event time
+    pub fn starting_at(mut self, start: DateTime<Utc>) -> Self {
+        self.start = start;
+        self
+    }
+
+    /// Add a fact generator
+    pub fn generator(mut self, generator: FactGenerator) -> Self {
+        self.generators.push(generator);
+        self
+    }
+
+    /// Add a correlation between two generators' fact types
+    pub fn correlation(mut self, correlation: Correlation) -> Self {
+        self.correlations.push(correlation);
+        self
+    }
+
+    /// Check rates, distributions and correlation references
+    pub fn validate(&self) -> BingoResult<()> {
+        for generator in &self.generators {
+            let rate = generator.arrival.per_second();
+            if !rate.is_finite() || rate < 0.0 {
+                return Err(BingoError::configuration(
+                    &format!("scenario.generator.{}", generator.fact_type),
+                    "non-negative arrival rate",
+                    &rate.to_string(),
+                    format!("Invalid arrival rate for '{}' facts", generator.fact_type),
+                ));
+            }
+            for (field, distribution) in &generator.fields {
+                distribution.validate(field)?;
+            }
+        }
+
+        for correlation in &self.correlations {
+            for fact_type in [&correlation.trigger, &correlation.follower] {
+                if self.find_generator(fact_type).is_none() {
+                    return Err(BingoError::configuration(
+                        "scenario.correlation",
+                        "known fact type",
+                        fact_type,
+                        format!("Correlation refers to unknown fact type '{fact_type}'"),
+                    ));
+                }
+            }
+            if !(0.0..=1.0).contains(&correlation.probability)
+                || correlation.delay.0 > correlation.delay.1
+            {
+                return Err(BingoError::configuration(
+                    "scenario.correlation",
+                    "probability in [0, 1] and min delay <= max delay",
+                    &format!("{correlation:?}"),
+                    format!(
+                        "Invalid correlation from '{}' to '{}'",
+                        correlation.trigger, correlation.follower
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Generate every fact with an event time within `duration` of the start
+    ///
+    /// Facts are ordered by event time and numbered from 1. Correlated follower
+    /// facts whose delay takes them past the end are dropped.
+    pub fn generate(&self, duration: Duration) -> BingoResult<Vec<Fact>> {
+        self.validate()?;
+
+        let end_millis = duration.as_secs_f64() * 1000.0;
+        let mut rng = ScenarioRng::new(self.seed);
+        let mut counters: HashMap<&str, u64> = HashMap::new();
+        let mut events: Vec<(f64, usize, HashMap<String, FactValue>)> = Vec::new();
+
+        // Draw each generator's stream in turn so adding a generator does not
+        // change the facts of the ones before it
+        for (position, generator) in self.generators.iter().enumerate() {
+            if generator.arrival.per_second() == 0.0 {
+                continue;
+            }
+            let mut generator_rng = ScenarioRng::new(self.seed ^ rng.next_u64());
+            let mut time = generator.arrival.next_gap_millis(&mut generator_rng);
+            while time < end_millis {
+                let fields = self.sample_fields(generator, &mut generator_rng, &mut counters);
+                events.push((time, position, fields));
+                time += generator.arrival.next_gap_millis(&mut generator_rng);
+            }
+        }
+        events.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+
+        let mut followers = Vec::new();
+        for (time, position, trigger_fields) in &events {
+            let trigger_type = &self.generators[*position].fact_type;
+            for correlation in self.correlations.iter().filter(|c| &c.trigger == trigger_type) {
+                if rng.next_f64() >= correlation.probability {
+                    continue;
+                }
+                let (min, max) = correlation.delay;
+                let delay =
+                    min.as_secs_f64() + (max.as_secs_f64() - min.as_secs_f64()) * rng.next_f64();
+                let follow_time = time + delay * 1000.0;
+                if follow_time >= end_millis {
+                    continue;
+                }
+
+                let (position, generator) = self.find_generator(&correlation.follower).unwrap();
+                let mut fields = self.sample_fields(generator, &mut rng, &mut counters);
+                for (from, to) in &correlation.copied_fields {
+                    if let Some(value) = trigger_fields.get(from) {
+                        fields.insert(to.clone(), value.clone());
+                    }
+                }
+                followers.push((follow_time, position, fields));
+            }
+        }
+        events.extend(followers);
+        events.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+
+        Ok(events
+            .into_iter()
+            .enumerate()
+            .map(|(index, (time, position, fields))| Fact {
+                id: index as u64 + 1,
+                external_id: None,
+                timestamp: self.start + chrono::Duration::microseconds((time * 1000.0) as i64),
+                fact_type: Some(self.generators[position].fact_type.clone()),
+                data: FactData { fields },
+            })
+            .collect())
+    }
+
+    /// Generate `duration` worth of facts and process them through `engine`
+    ///
+    /// Facts are fed in batches of `batch_size` in event-time order.
+    pub fn run(
+        &self,
+        engine: &BingoEngine,
+        duration: Duration,
+        batch_size: usize,
+    ) -> BingoResult<ScenarioReport> {
+        let facts = self.generate(duration)?;
+        let timer = Timer::new();
+        let mut report = ScenarioReport { facts_generated: facts.len(), ..Default::default() };
+
+        for batch in facts.chunks(batch_size.max(1)) {
+            for fact in batch {
+                if let Some(fact_type) = fact.fact_type() {
+                    *report.facts_by_type.entry(fact_type.to_string()).or_default() += 1;
+                }
+            }
+            for result in engine.process_facts(batch.to_vec())? {
+                report.rules_fired += 1;
+                *report.fired_by_rule.entry(result.rule_id).or_default() += 1;
+            }
+        }
+
+        report.elapsed = timer.elapsed();
+        Ok(report)
+    }
+
+    fn find_generator(&self, fact_type: &str) -> Option<(usize, &FactGenerator)> {
+        self.generators
+            .iter()
+            .enumerate()
+            .find(|(_, generator)| generator.fact_type == fact_type)
+    }
+
+    fn sample_fields<'a>(
+        &self,
+        generator: &'a FactGenerator,
+        rng: &mut ScenarioRng,
+        counters: &mut HashMap<&'a str, u64>,
+    ) -> HashMap<String, FactValue> {
+        let index = counters.entry(generator.fact_type.as_str()).or_default();
+        let fields: HashMap<String, FactValue> = generator
+            .fields
+            .iter()
+            .map(|(name, distribution)| (name.clone(), distribution.sample(rng, *index)))
+            .collect();
+        *index += 1;
+        fields
+    }
+}
+
+/// Outcome of running a scenario through an engine
+#[derive(Debug, Clone, Default)]
+pub struct ScenarioReport {
+    pub facts_generated: usize,
+    pub facts_by_type: HashMap<String, usize>,
+    pub rules_fired: usize,
+    pub fired_by_rule: HashMap<RuleId, usize>,
+    /// Wall-clock time spent processing, excluding generation
+    pub elapsed: Duration,
+}
+
+impl ScenarioReport {
+    /// Facts processed per wall-clock second
+    pub fn throughput(&self) -> f64 {
+        self.facts_generated as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+/// Standard workloads whose performance characteristics the documentation quotes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StandardScenario {
+    /// 1M transactions against 100 single-condition rules, each transaction firing one
+    MillionFactsHundredRules,
+    /// 10K products against 10K rules that each match one SKU out of a million, so
+    /// almost every rule never matches
+    TenThousandFactsTenThousandRules,
+    /// 100K orders and payments sharing a customer key with 50K distinct values,
+    /// matched by multi-condition rules testing the key
+    HighCardinalityJoin,
+}
+
+impl StandardScenario {
+    /// Every standard scenario
+    pub const ALL: [StandardScenario; 3] = [
+        StandardScenario::MillionFactsHundredRules,
+        StandardScenario::TenThousandFactsTenThousandRules,
+        StandardScenario::HighCardinalityJoin,
+    ];
+
+    /// Seed of every scenario's fact stream
+    const SEED: u64 = 42;
+
+    /// Short name, e.g. for selecting scenarios from the command line
+    pub fn name(&self) -> &'static str {
+        match self {
+            StandardScenario::MillionFactsHundredRules => "1m_facts_100_rules",
+            StandardScenario::TenThousandFactsTenThousandRules => "10k_facts_10k_rules",
+            StandardScenario::HighCardinalityJoin => "high_cardinality_join",
+        }
+    }
+
+    /// Scenario with the given `name`
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|scenario| scenario.name() == name)
+    }
+
+    /// Facts the full scenario processes
+    pub fn fact_count(&self) -> usize {
+        match self {
+            StandardScenario::MillionFactsHundredRules => 1_000_000,
+            StandardScenario::TenThousandFactsTenThousandRules => 10_000,
+            StandardScenario::HighCardinalityJoin => 100_000,
+        }
+    }
+
+    /// Pass thresholds on the baseline machine (3 GHz, 16 GB) in release mode
+    pub fn base_thresholds(&self) -> ScenarioThresholds {
+        match self {
+            StandardScenario::MillionFactsHundredRules => ScenarioThresholds {
+                max_rule_load_time: Duration::from_secs(1),
+                max_processing_time: Duration::from_secs(30),
+                max_memory_bytes: 3_000_000_000,
+            },
+            StandardScenario::TenThousandFactsTenThousandRules => ScenarioThresholds {
+                max_rule_load_time: Duration::from_secs(10),
+                max_processing_time: Duration::from_secs(5),
+                max_memory_bytes: 1_000_000_000,
+            },
+            StandardScenario::HighCardinalityJoin => ScenarioThresholds {
+                max_rule_load_time: Duration::from_secs(1),
+                max_processing_time: Duration::from_secs(10),
+                max_memory_bytes: 1_000_000_000,
+            },
+        }
+    }
+
+    /// The scenario's rules
+    pub fn rules(&self) -> Vec<Rule> {
+        match self {
+            StandardScenario::MillionFactsHundredRules => (0..100)
+                .map(|index| {
+                    flag_rule(
+                        index + 1,
+                        vec![equals("category", &format!("category_{index}"))],
+                        "categorized",
+                    )
+                })
+                .collect(),
+            StandardScenario::TenThousandFactsTenThousandRules => (0..10_000)
+                .map(|index| {
+                    flag_rule(
+                        index + 1,
+                        vec![equals("sku", &format!("sku_{}", index * 100))],
+                        "listed",
+                    )
+                })
+                .collect(),
+            StandardScenario::HighCardinalityJoin => vec![
+                flag_rule(
+                    1,
+                    vec![
+                        equals(FACT_TYPE_FIELD, "order"),
+                        compare("customer_id", Operator::NotEqual, FactValue::Null),
+                        compare("amount", Operator::GreaterThan, FactValue::Integer(400)),
+                    ],
+                    "large_order",
+                ),
+                flag_rule(
+                    2,
+                    vec![
+                        equals(FACT_TYPE_FIELD, "payment"),
+                        compare("customer_id", Operator::NotEqual, FactValue::Null),
+                        compare("settled", Operator::Equal, FactValue::Boolean(false)),
+                    ],
+                    "unsettled",
+                ),
+                flag_rule(
+                    3,
+                    vec![equals("customer_id", "customer_7")],
+                    "watched_customer",
+                ),
+            ],
+        }
+    }
+
+    /// Generate `fact_count` facts of the scenario's stream, the same on every call
+    pub fn facts(&self, fact_count: usize) -> BingoResult<Vec<Fact>> {
+        let per_second = fact_count as f64;
+        let scenario = match self {
+            StandardScenario::MillionFactsHundredRules => Scenario::new(Self::SEED).generator(
+                FactGenerator::new("transaction", ArrivalRate::Fixed { per_second })
+                    .field("category", key("category_", 100))
+                    .field("amount", Distribution::UniformInt { min: 1, max: 10_000 }),
+            ),
+            StandardScenario::TenThousandFactsTenThousandRules => Scenario::new(Self::SEED)
+                .generator(
+                    FactGenerator::new("product", ArrivalRate::Fixed { per_second })
+                        .field("sku", key("sku_", 1_000_000))
+                        .field("price", Distribution::UniformFloat { min: 1.0, max: 100.0 }),
+                ),
+            StandardScenario::HighCardinalityJoin => Scenario::new(Self::SEED)
+                .generator(
+                    FactGenerator::new(
+                        "order",
+                        ArrivalRate::Fixed { per_second: per_second / 2.0 },
+                    )
+                    .field("order_id", Distribution::Sequence { start: 1, step: 1 })
+                    .field("customer_id", key("customer_", 50_000))
+                    .field("amount", Distribution::UniformInt { min: 1, max: 500 }),
+                )
+                .generator(
+                    FactGenerator::new("payment", ArrivalRate::Fixed { per_second: 0.0 })
+                        .field("settled", Distribution::Bernoulli { probability: 0.9 }),
+                )
+                .correlation(
+                    Correlation::new("order", "payment", Duration::from_millis(1))
+                        .copy_field("order_id")
+                        .copy_field("customer_id")
+                        .copy_field_as("amount", "paid"),
+                ),
+        };
+        // Fixed rates emit one fact less per second than their rate, so generate spare
+        let mut facts = scenario.generate(Duration::from_secs(2))?;
+        facts.truncate(fact_count);
+        Ok(facts)
+    }
+
+    /// Load the rules into a new engine, process the full fact stream and compare the
+    /// measurements with the thresholds adjusted by `config`
+    pub fn run(&self, config: &PerformanceConfig) -> BingoResult<StandardScenarioReport> {
+        let rules = self.rules();
+        let facts = self.facts(self.fact_count())?;
+        let (rule_count, fact_count) = (rules.len(), facts.len());
+
+        let engine = BingoEngine::with_capacity(fact_count)?;
+        let timer = Timer::new();
+        engine.add_rules(rules)?;
+        let rule_load_time = timer.elapsed();
+
+        let timer = Timer::new();
+        let results = engine.process_facts(facts)?;
+        let processing_time = timer.elapsed();
+
+        Ok(StandardScenarioReport {
+            scenario: *self,
+            facts: fact_count,
+            rules: rule_count,
+            rules_fired: results.len(),
+            rule_load_time,
+            processing_time,
+            memory_bytes: engine.get_stats().memory_usage_bytes as u64,
+            thresholds: config.adjust_thresholds(self.base_thresholds()),
+        })
+    }
+}
+
+fn key(prefix: &str, cardinality: u64) -> Distribution {
+    Distribution::Key { prefix: prefix.to_string(), cardinality }
+}
+
+fn equals(field: &str, value: &str) -> Condition {
+    compare(field, Operator::Equal, FactValue::String(value.to_string()))
+}
+
+fn compare(field: &str, operator: Operator, value: FactValue) -> Condition {
+    Condition::Simple { field: field.to_string(), operator, value }
+}
+
+fn flag_rule(id: RuleId, conditions: Vec<Condition>, flag: &str) -> Rule {
+    Rule {
+        id,
+        name: format!("{flag} {id}"),
+        conditions,
+        actions: vec![Action {
+            action_type: ActionType::SetField {
+                field: flag.to_string(),
+                value: FactValue::Boolean(true),
+            },
+        }],
+        metadata: Default::default(),
+    }
+}
+
+/// Measurements of a standard scenario run against its adjusted thresholds
+#[derive(Debug, Clone)]
+pub struct StandardScenarioReport {
+    pub scenario: StandardScenario,
+    pub facts: usize,
+    pub rules: usize,
+    pub rules_fired: usize,
+    pub rule_load_time: Duration,
+    pub processing_time: Duration,
+    pub memory_bytes: u64,
+    /// Thresholds adjusted to the environment the scenario ran in
+    pub thresholds: ScenarioThresholds,
+}
+
+impl StandardScenarioReport {
+    /// Facts processed per wall-clock second
+    pub fn throughput(&self) -> f64 {
+        self.facts as f64 / self.processing_time.as_secs_f64().max(f64::EPSILON)
+    }
+
+    /// Thresholds the run exceeded, described
+    pub fn violations(&self) -> Vec<String> {
+        let mut violations = Vec::new();
+        if self.rule_load_time > self.thresholds.max_rule_load_time {
+            violations.push(format!(
+                "loading {} rules took {:?}, expected <= {:?}",
+                self.rules, self.rule_load_time, self.thresholds.max_rule_load_time
+            ));
+        }
+        if self.processing_time > self.thresholds.max_processing_time {
+            violations.push(format!(
+                "processing {} facts took {:?}, expected <= {:?}",
+                self.facts, self.processing_time, self.thresholds.max_processing_time
+            ));
+        }
+        if self.memory_bytes > self.thresholds.max_memory_bytes {
+            violations.push(format!(
+                "engine used {} bytes, expected <= {} bytes",
+                self.memory_bytes, self.thresholds.max_memory_bytes
+            ));
+        }
+        violations
+    }
+
+    /// Whether the run stayed within every threshold
+    pub fn passed(&self) -> bool {
+        self.violations().is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Action, ActionType, Condition, Operator, Rule};
+
+    fn orders_and_payments(seed: u64) -> Scenario {
+        Scenario::new(seed)
+            .generator(
+                FactGenerator::new("order", ArrivalRate::Poisson { per_second: 50.0 })
+                    .field("order_id", Distribution::Sequence { start: 1000, step: 1 })
+                    .field("amount", Distribution::UniformInt { min: 10, max: 500 })
+                    .field(
+                        "channel",
+                        Distribution::Weighted(vec![
+                            (FactValue::String("web".to_string()), 3.0),
+                            (FactValue::String("store".to_string()), 1.0),
+                        ]),
+                    ),
+            )
+            .generator(
+                FactGenerator::new("payment", ArrivalRate::Fixed { per_second: 0.0 })
+                    .field("settled", Distribution::Bernoulli { probability: 0.9 }),
+            )
+            .correlation(
+                Correlation::new("order", "payment", Duration::from_millis(200))
+                    .with_delay_range(Duration::from_millis(100), Duration::from_millis(900))
+                    .with_probability(0.5)
+                    .copy_field("order_id")
+                    .copy_field_as("amount", "paid"),
+            )
+    }
+
+    fn count_of(facts: &[Fact], fact_type: &str) -> usize {
+        facts.iter().filter(|fact| fact.fact_type() == Some(fact_type)).count()
+    }
+
+    #[test]
+    fn test_same_seed_reproduces_stream() {
+        let first = orders_and_payments(7).generate(Duration::from_secs(10)).unwrap();
+        let second = orders_and_payments(7).generate(Duration::from_secs(10)).unwrap();
+        let other = orders_and_payments(8).generate(Duration::from_secs(10)).unwrap();
+
+        let values = |facts: &[Fact]| -> Vec<(i64, Vec<(String, FactValue)>)> {
+            facts
+                .iter()
+                .map(|fact| {
+                    let mut fields: Vec<_> = fact.data.fields.clone().into_iter().collect();
+                    fields.sort_by(|a, b| a.0.cmp(&b.0));
+                    (fact.timestamp.timestamp_micros(), fields)
+                })
+                .collect()
+        };
+        assert_eq!(values(&first), values(&second));
+        assert_ne!(values(&first), values(&other));
+    }
+
+    #[test]
+    fn test_stream_is_time_ordered_with_expected_rate() {
+        let facts = orders_and_payments(1).generate(Duration::from_secs(20)).unwrap();
+
+        assert!(facts.windows(2).all(|pair| pair[0].timestamp <= pair[1].timestamp));
+        assert!(facts.iter().enumerate().all(|(index, fact)| fact.id == index as u64 + 1));
+
+        // Poisson with a mean of 1000 orders; roughly half get a payment
+        let orders = count_of(&facts, "order");
+        let payments = count_of(&facts, "payment");
+        assert!((850..1150).contains(&orders), "orders: {orders}");
+        assert!(
+            payments > orders / 3 && payments < orders * 2 / 3,
+            "payments: {payments}"
+        );
+    }
+
+    #[test]
+    fn test_correlated_facts_copy_trigger_fields() {
+        let facts = orders_and_payments(3).generate(Duration::from_secs(5)).unwrap();
+        let orders: HashMap<_, _> = facts
+            .iter()
+            .filter(|fact| count_of(std::slice::from_ref(fact), "order") == 1)
+            .map(|fact| (fact.data.fields["order_id"].clone(), fact))
+            .collect();
+
+        for payment in
+            facts.iter().filter(|fact| count_of(std::slice::from_ref(fact), "payment") == 1)
+        {
+            let order = orders[&payment.data.fields["order_id"]];
+            assert_eq!(payment.data.fields["paid"], order.data.fields["amount"]);
+            let delay = payment.timestamp - order.timestamp;
+            assert!(delay >= chrono::Duration::milliseconds(100));
+            assert!(delay <= chrono::Duration::milliseconds(900));
+        }
+    }
+
+    #[test]
+    fn test_invalid_scenarios_are_rejected() {
+        let bad_range = Scenario::new(1).generator(
+            FactGenerator::new("reading", ArrivalRate::Fixed { per_second: 1.0 })
+                .field("value", Distribution::UniformInt { min: 5, max: 1 }),
+        );
+        assert!(bad_range.generate(Duration::from_secs(1)).is_err());
+
+        let unknown_type = orders_and_payments(1).correlation(Correlation::new(
+            "order",
+            "refund",
+            Duration::from_secs(1),
+        ));
+        assert!(unknown_type.generate(Duration::from_secs(1)).is_err());
+    }
+
+    #[test]
+    fn test_run_reports_rule_firings() {
+        let engine = BingoEngine::new().unwrap();
+        engine
+            .add_rule(Rule {
+                id: 1,
+                name: "Large Order".to_string(),
+                conditions: vec![Condition::Simple {
+                    field: "amount".to_string(),
+                    operator: Operator::GreaterThan,
+                    value: FactValue::Integer(400),
+                }],
+                actions: vec![Action {
+                    action_type: ActionType::Log { message: "Large order".to_string() },
+                }],
+                metadata: Default::default(),
+            })
+            .unwrap();
+
+        let scenario = orders_and_payments(11);
+        let facts = scenario.generate(Duration::from_secs(2)).unwrap();
+        let large = facts
+            .iter()
+            .filter(|fact| matches!(fact.data.fields.get("amount"), Some(FactValue::Integer(amount)) if *amount > 400))
+            .count();
+
+        let report = scenario.run(&engine, Duration::from_secs(2), 16).unwrap();
+        assert_eq!(report.facts_generated, facts.len());
+        assert_eq!(report.rules_fired, large);
+        assert_eq!(report.fired_by_rule.get(&1).copied().unwrap_or(0), large);
+        assert_eq!(report.facts_by_type["order"], count_of(&facts, "order"));
+    }
+}
@@ -0,0 +1,386 @@
+//! Given/when/then rule tests and rule coverage
+//!
+//! [`RuleTest`] runs facts through a ruleset on a fresh engine and asserts on what
+//! fired, reading like the rule's specification:
+//!
+//! ```rust,ignore
+//! RuleTest::given_facts(vec![customer])
+//!     .when_rules(rules)
+//!     .then_fired("Premium customer")
+//!     .with_field("customer_tier", "premium");
+//! ```
+//!
+//! Assertions panic with a description of what did fire, so they are meant for test
+//! functions. Recording each outcome in a [`RuleCoverage`] shows which rules a suite
+//! never fires and which simple conditions it never satisfies.
+
+use crate::condition_stats::ConditionEvaluationStats;
+use crate::engine::BingoEngine;
+use crate::error::BingoResult;
+use crate::rete_nodes::{ActionResult, RuleExecutionResult};
+use crate::types::{Fact, FactId, FactValue, Rule, RuleId};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Facts a rule test starts from
+#[derive(Debug, Clone, Default)]
+pub struct RuleTest {
+    facts: Vec<Fact>,
+}
+
+impl RuleTest {
+    /// Start a test from the facts given to the rules
+    pub fn given_facts(facts: impl IntoIterator<Item = Fact>) -> Self {
+        Self { facts: facts.into_iter().collect() }
+    }
+
+    /// Run the facts through `rules` on a fresh engine
+    ///
+    /// Panics if the engine refuses a rule or fails processing; use
+    /// [`try_when_rules`](Self::try_when_rules) to handle those errors.
+    #[track_caller]
+    pub fn when_rules(self, rules: impl IntoIterator<Item = Rule>) -> RuleTestOutcome {
+        match self.try_when_rules(rules) {
+            Ok(outcome) => outcome,
+            Err(error) => panic!("rule test failed to run: {error}"),
+        }
+    }
+
+    /// Run the facts through `rules` on a fresh engine
+    pub fn try_when_rules(
+        self,
+        rules: impl IntoIterator<Item = Rule>,
+    ) -> BingoResult<RuleTestOutcome> {
+        let rules: Vec<Rule> = rules.into_iter().collect();
+        let engine = BingoEngine::new()?;
+        engine.add_rules(rules.clone())?;
+        let results = engine.process_facts(self.facts)?;
+        Ok(RuleTestOutcome { rules, results, condition_stats: engine.get_condition_stats() })
+    }
+}
+
+/// What a ruleset did with a rule test's facts
+#[derive(Debug, Clone)]
+pub struct RuleTestOutcome {
+    rules: Vec<Rule>,
+    results: Vec<RuleExecutionResult>,
+    condition_stats: Vec<ConditionEvaluationStats>,
+}
+
+impl RuleTestOutcome {
+    /// Results of every rule that fired
+    pub fn results(&self) -> &[RuleExecutionResult] {
+        &self.results
+    }
+
+    /// Assert the rule named `rule_name` fired at least once
+    #[track_caller]
+    pub fn then_fired(&self, rule_name: &str) -> FiredRule<'_> {
+        let rule = self.rule(rule_name);
+        let firings: Vec<&RuleExecutionResult> =
+            self.results.iter().filter(|result| result.rule_id == rule.id).collect();
+        if firings.is_empty() {
+            panic!(
+                "expected rule '{rule_name}' to fire, but {}",
+                self.describe_firings()
+            );
+        }
+        FiredRule { outcome: self, rule, firings }
+    }
+
+    /// Assert the rule named `rule_name` did not fire
+    #[track_caller]
+    pub fn then_not_fired(&self, rule_name: &str) -> &Self {
+        let rule = self.rule(rule_name);
+        let firings = self.results.iter().filter(|result| result.rule_id == rule.id).count();
+        if firings > 0 {
+            panic!("expected rule '{rule_name}' not to fire, but it fired {firings} time(s)");
+        }
+        self
+    }
+
+    /// Assert no rule fired
+    #[track_caller]
+    pub fn then_nothing_fired(&self) -> &Self {
+        if !self.results.is_empty() {
+            panic!("expected no rule to fire, but {}", self.describe_firings());
+        }
+        self
+    }
+
+    /// Add the outcome to a suite's coverage
+    pub fn record(&self, coverage: &mut RuleCoverage) -> &Self {
+        coverage.record(self);
+        self
+    }
+
+    #[track_caller]
+    fn rule(&self, rule_name: &str) -> &Rule {
+        self.rules.iter().find(|rule| rule.name == rule_name).unwrap_or_else(|| {
+            let names: Vec<&str> = self.rules.iter().map(|rule| rule.name.as_str()).collect();
+            panic!("no rule named '{rule_name}' in the ruleset; rules are {names:?}")
+        })
+    }
+
+    fn describe_firings(&self) -> String {
+        let mut fired: BTreeMap<&str, usize> = BTreeMap::new();
+        for result in &self.results {
+            let name = self
+                .rules
+                .iter()
+                .find(|rule| rule.id == result.rule_id)
+                .map_or("<unknown rule>", |rule| rule.name.as_str());
+            *fired.entry(name).or_default() += 1;
+        }
+        if fired.is_empty() {
+            return "no rule fired".to_string();
+        }
+        let fired: Vec<String> =
+            fired.into_iter().map(|(name, count)| format!("'{name}' x{count}")).collect();
+        format!("fired rules were {}", fired.join(", "))
+    }
+}
+
+/// Firings of one rule, for further assertions
+#[derive(Debug, Clone)]
+pub struct FiredRule<'a> {
+    outcome: &'a RuleTestOutcome,
+    rule: &'a Rule,
+    firings: Vec<&'a RuleExecutionResult>,
+}
+
+impl<'a> FiredRule<'a> {
+    /// Assert the rule fired exactly `times` times
+    #[track_caller]
+    pub fn times(self, times: usize) -> Self {
+        if self.firings.len() != times {
+            panic!(
+                "expected rule '{}' to fire {times} time(s), but it fired {} time(s)",
+                self.rule.name,
+                self.firings.len()
+            );
+        }
+        self
+    }
+
+    /// Assert the rule fired for the fact with ID `fact_id`
+    #[track_caller]
+    pub fn for_fact(self, fact_id: FactId) -> Self {
+        if !self.firings.iter().any(|result| result.fact_id == fact_id) {
+            let fact_ids: Vec<FactId> = self.firings.iter().map(|result| result.fact_id).collect();
+            panic!(
+                "expected rule '{}' to fire for fact {fact_id}, but it fired for facts {fact_ids:?}",
+                self.rule.name
+            );
+        }
+        self
+    }
+
+    /// Assert a firing of the rule set `field` to `value`
+    ///
+    /// Field writes, calculator outputs and increments count as setting the field.
+    #[track_caller]
+    pub fn with_field(self, field: &str, value: impl Into<FactValue>) -> Self {
+        let value = value.into();
+        let written: Vec<&FactValue> = self
+            .firings
+            .iter()
+            .flat_map(|result| &result.actions_executed)
+            .filter_map(|action| written_value(action, field))
+            .collect();
+        if !written.contains(&&value) {
+            let written: Vec<String> = written.iter().map(ToString::to_string).collect();
+            panic!(
+                "expected rule '{}' to set '{field}' to {value}, but it set it to [{}]",
+                self.rule.name,
+                written.join(", ")
+            );
+        }
+        self
+    }
+
+    /// Continue with an assertion about another rule
+    #[track_caller]
+    pub fn then_fired(self, rule_name: &str) -> FiredRule<'a> {
+        self.outcome.then_fired(rule_name)
+    }
+
+    /// Continue with an assertion that another rule did not fire
+    #[track_caller]
+    pub fn then_not_fired(self, rule_name: &str) -> &'a RuleTestOutcome {
+        self.outcome.then_not_fired(rule_name)
+    }
+}
+
+/// Value an action result wrote to `field`, if it wrote to it
+fn written_value<'a>(action: &'a ActionResult, field: &str) -> Option<&'a FactValue> {
+    match action {
+        ActionResult::FieldSet { field: written, value, .. } if written == field => Some(value),
+        ActionResult::CalculatorResult { output_field, parsed_value, .. }
+            if output_field == field =>
+        {
+            Some(parsed_value)
+        }
+        ActionResult::FieldIncremented { field: written, new_value, .. } if written == field => {
+            Some(new_value)
+        }
+        _ => None,
+    }
+}
+
+/// Rules and conditions exercised across the rule tests of a suite
+#[derive(Debug, Clone, Default)]
+pub struct RuleCoverage {
+    rules: BTreeMap<RuleId, RuleUsage>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct RuleUsage {
+    name: String,
+    firings: usize,
+    /// Matches of each simple condition, keyed by its description
+    conditions: BTreeMap<String, u64>,
+}
+
+impl RuleCoverage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add what one rule test exercised
+    pub fn record(&mut self, outcome: &RuleTestOutcome) {
+        for rule in &outcome.rules {
+            let usage = self.rules.entry(rule.id).or_default();
+            usage.name.clone_from(&rule.name);
+            usage.firings +=
+                outcome.results.iter().filter(|result| result.rule_id == rule.id).count();
+        }
+        for stats in &outcome.condition_stats {
+            for rule_id in &stats.rule_ids {
+                if let Some(usage) = self.rules.get_mut(rule_id) {
+                    *usage.conditions.entry(stats.to_string()).or_default() += stats.matches;
+                }
+            }
+        }
+    }
+
+    /// Rules never fired and simple conditions never satisfied so far
+    pub fn report(&self) -> CoverageReport {
+        let mut report = CoverageReport::default();
+        for (&rule_id, usage) in &self.rules {
+            report.rule_count += 1;
+            if usage.firings == 0 {
+                report.unfired_rules.push((rule_id, usage.name.clone()));
+            }
+            for (condition, &matches) in &usage.conditions {
+                report.condition_count += 1;
+                if matches == 0 {
+                    report.unmatched_conditions.push(UncoveredCondition {
+                        rule_id,
+                        rule_name: usage.name.clone(),
+                        condition: condition.clone(),
+                    });
+                }
+            }
+        }
+        report
+    }
+}
+
+/// A simple condition no rule test satisfied
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UncoveredCondition {
+    pub rule_id: RuleId,
+    pub rule_name: String,
+    /// The condition, such as `amount > 100`
+    pub condition: String,
+}
+
+/// What a suite of rule tests left unexercised
+///
+/// Only simple conditions are tracked; conditions inside logical groups, aggregations
+/// and streams count as covered when their rule fires.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CoverageReport {
+    /// Rules the suite ran
+    pub rule_count: usize,
+    /// Simple conditions of those rules
+    pub condition_count: usize,
+    /// Rules that never fired, by ID
+    pub unfired_rules: Vec<(RuleId, String)>,
+    /// Simple conditions that no fact satisfied, by rule ID
+    pub unmatched_conditions: Vec<UncoveredCondition>,
+}
+
+impl CoverageReport {
+    /// Fraction of the rules that fired at least once, 1.0 when there are none
+    pub fn rule_coverage(&self) -> f64 {
+        covered_fraction(self.rule_count, self.unfired_rules.len())
+    }
+
+    /// Fraction of the simple conditions that some fact satisfied, 1.0 when there are
+    /// none
+    pub fn condition_coverage(&self) -> f64 {
+        covered_fraction(self.condition_count, self.unmatched_conditions.len())
+    }
+
+    /// Whether every rule fired and every simple condition was satisfied
+    pub fn is_complete(&self) -> bool {
+        self.unfired_rules.is_empty() && self.unmatched_conditions.is_empty()
+    }
+}
+
+fn covered_fraction(total: usize, uncovered: usize) -> f64 {
+    if total == 0 {
+        1.0
+    } else {
+        (total - uncovered) as f64 / total as f64
+    }
+}
+
+impl fmt::Display for CoverageReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Rules fired: {}/{} ({:.0}%)",
+            self.rule_count - self.unfired_rules.len(),
+            self.rule_count,
+            self.rule_coverage() * 100.0
+        )?;
+        writeln!(
+            f,
+            "Conditions satisfied: {}/{} ({:.0}%)",
+            self.condition_count - self.unmatched_conditions.len(),
+            self.condition_count,
+            self.condition_coverage() * 100.0
+        )?;
+        for (rule_id, name) in &self.unfired_rules {
+            writeln!(f, "  never fired: rule {rule_id} ({name})")?;
+        }
+        for uncovered in &self.unmatched_conditions {
+            writeln!(
+                f,
+                "  never satisfied: {} in rule {} ({})",
+                uncovered.condition, uncovered.rule_id, uncovered.rule_name
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_coverage_is_complete() {
+        let report = RuleCoverage::new().report();
+        assert!(report.is_complete());
+        assert_eq!(report.rule_coverage(), 1.0);
+        assert_eq!(report.condition_coverage(), 1.0);
+        assert_eq!(
+            report.to_string(),
+            "Rules fired: 0/0 (100%)\nConditions satisfied: 0/0 (100%)\n"
+        );
+    }
+}
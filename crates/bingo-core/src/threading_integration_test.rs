@@ -19,6 +19,7 @@ mod tests {
             id,
             external_id: Some(format!("fact_{id}")),
             timestamp: chrono::Utc::now(),
+            fact_type: None,
             data: FactData { fields },
         }
     }
@@ -35,6 +36,7 @@ mod tests {
             actions: vec![Action {
                 action_type: ActionType::Log { message: "Rule fired".to_string() },
             }],
+            metadata: Default::default(),
         }
     }
 
@@ -118,6 +120,7 @@ mod tests {
                         rule_id: thread_id * 100 + i,
                         fact_id: i,
                         actions_executed: vec![],
+                        ..Default::default()
                     });
                     pool_clone.rule_execution_results.return_vec(vec);
                 }
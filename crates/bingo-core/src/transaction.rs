@@ -0,0 +1,119 @@
+//! Engine transactions with rollback
+//!
+//! `process_facts` stores a batch before evaluating it, so a rule action that fails
+//! half way through, or a failed downstream step run on the results, leaves working
+//! memory with the batch inserted and some of its rules fired. A transaction from
+//! `BingoEngine::begin_transaction` stages inserts and retracts instead, leaving working
+//! memory untouched until `commit`, which applies them in order as one unit:
+//!
+//! - **Undo log**: While a commit applies, the fact store records the version each
+//!   fact had before the commit first changed it, history included, and the RETE
+//!   network records the facts the commit removed from working memory and its changes
+//!   to logical support. When a change fails, or when the check passed to
+//!   `commit_with` rejects the outcome, those versions are put back into the store and
+//!   the network's memories, and the facts the commit inserted or derived are
+//!   withdrawn, so rolling back costs time proportional to the commit rather than to
+//!   working memory
+//! - **Held side effects**: Webhook actions and replication records are held back
+//!   while the changes apply, then dispatched and streamed once the commit succeeds or
+//!   dropped when it rolls back
+//!
+//! Commits are serialised with each other. Only the committing thread's changes and
+//! side effects are logged and held, so writes made outside a transaction while it
+//! commits, such as direct `process_facts` calls from other threads, are kept, and
+//! their webhooks and replication records sent, when it rolls back. State digests are
+//! not replicated while a commit is open. A rollback that cannot be completed is
+//! returned as an error.
+
+use crate::engine::BingoEngine;
+use crate::error::BingoResult;
+use crate::rete_nodes::RuleExecutionResult;
+use crate::truth_maintenance::RetractionResult;
+use crate::types::{Fact, FactId};
+
+/// Change staged by a transaction
+#[derive(Debug, Clone)]
+pub(crate) enum StagedChange {
+    /// Facts inserted and evaluated as one batch
+    Insert(Vec<Fact>),
+    /// Fact retracted with truth maintenance
+    Retract(FactId),
+}
+
+/// Rule firings and retractions of a committed transaction
+#[derive(Debug, Clone, Default)]
+pub struct TransactionOutcome {
+    /// Rules fired by the inserted facts, in the order they fired
+    pub results: Vec<RuleExecutionResult>,
+    /// Retractions, in the order they were staged
+    pub retractions: Vec<RetractionResult>,
+}
+
+/// Inserts and retracts staged against an engine, applied together on commit
+///
+/// Dropping a transaction without committing it rolls it back.
+#[derive(Debug)]
+pub struct EngineTransaction<'a> {
+    engine: &'a BingoEngine,
+    changes: Vec<StagedChange>,
+}
+
+impl<'a> EngineTransaction<'a> {
+    pub(crate) fn new(engine: &'a BingoEngine) -> Self {
+        Self { engine, changes: Vec::new() }
+    }
+
+    /// Stage a fact insert
+    pub fn insert(&mut self, fact: Fact) -> &mut Self {
+        self.insert_facts(vec![fact])
+    }
+
+    /// Stage inserting `facts`; consecutive inserts are evaluated as one batch
+    pub fn insert_facts(&mut self, facts: Vec<Fact>) -> &mut Self {
+        match self.changes.last_mut() {
+            Some(StagedChange::Insert(staged)) => staged.extend(facts),
+            _ => self.changes.push(StagedChange::Insert(facts)),
+        }
+        self
+    }
+
+    /// Stage retracting a stored fact with truth maintenance
+    pub fn retract(&mut self, fact_id: FactId) -> &mut Self {
+        self.changes.push(StagedChange::Retract(fact_id));
+        self
+    }
+
+    /// Number of staged inserts and retracts
+    pub fn len(&self) -> usize {
+        self.changes
+            .iter()
+            .map(|change| match change {
+                StagedChange::Insert(facts) => facts.len(),
+                StagedChange::Retract(_) => 1,
+            })
+            .sum()
+    }
+
+    /// Whether nothing is staged
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Apply the staged changes in order, rolling all of them back if one fails
+    pub fn commit(self) -> BingoResult<TransactionOutcome> {
+        self.commit_with(|_| Ok(()))
+    }
+
+    /// Apply the staged changes, then run `check` on the outcome before releasing
+    /// held webhooks and replication records; an error from a change or from `check`
+    /// rolls every change back and is returned
+    pub fn commit_with(
+        self,
+        check: impl FnOnce(&TransactionOutcome) -> BingoResult<()>,
+    ) -> BingoResult<TransactionOutcome> {
+        self.engine.commit_transaction(self.changes, check)
+    }
+
+    /// Discard the staged changes
+    pub fn rollback(self) {}
+}
@@ -0,0 +1,306 @@
+//! Truth maintenance for fact retraction
+//!
+//! This module tracks the logical support behind every rule activation so that
+//! retracting a fact can withdraw the activations it produced and, transitively,
+//! any facts that were asserted by those activations' actions.
+//!
+//! ## Support Model
+//!
+//! - **Justification**: A rule activation together with the facts it derived
+//! - **Support**: The facts that caused an activation (its matched facts), all of which
+//!   must hold for the activation to hold
+//! - **Cascade**: When a derived fact loses every activation that derived it, it is
+//!   retracted too, which may in turn withdraw further activations
+
+use crate::rete_nodes::RuleExecutionResult;
+use crate::types::{FactId, RuleId};
+use std::collections::{HashMap, HashSet};
+
+/// A single rule activation and the facts its actions asserted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Justification {
+    /// Rule that fired
+    pub rule_id: RuleId,
+    /// Fact that triggered the activation
+    pub fact_id: FactId,
+    /// Facts asserted by the activation's actions
+    pub derived_facts: Vec<FactId>,
+}
+
+/// Outcome of retracting a fact from the engine
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RetractionResult {
+    /// The fact that was explicitly retracted
+    pub fact_id: FactId,
+    /// Activations withdrawn because their support was retracted, as `(rule_id, fact_id)`
+    pub withdrawn_activations: Vec<(RuleId, FactId)>,
+    /// Derived facts retracted because they lost all logical support
+    pub retracted_facts: Vec<FactId>,
+}
+
+impl RetractionResult {
+    /// Whether the retraction had any effect beyond the fact itself
+    pub fn is_empty(&self) -> bool {
+        self.withdrawn_activations.is_empty() && self.retracted_facts.is_empty()
+    }
+}
+
+/// Outcome of updating stored facts in place
+///
+/// The previous version of each fact is retracted with truth maintenance before the
+/// updated version is matched again, so activations and derived facts follow the
+/// new field values.
+#[derive(Debug, Clone, Default)]
+pub struct FactUpdateResult {
+    /// IDs of the updated facts still stored, in ID order
+    pub updated_facts: Vec<FactId>,
+    /// Retractions of the facts' previous versions
+    pub retractions: Vec<RetractionResult>,
+    /// Rules fired by the updated facts
+    pub results: Vec<RuleExecutionResult>,
+}
+
+/// Tracks logical support between facts and rule activations
+#[derive(Debug, Default)]
+pub struct TruthMaintenanceSystem {
+    /// Supporting fact -> activations it justifies
+    justifications: HashMap<FactId, Vec<Justification>>,
+    /// Derived fact -> supporting facts of each activation still deriving it
+    support: HashMap<FactId, Vec<HashSet<FactId>>>,
+    /// Entries to put back if the transaction changing them rolls back
+    journal: Option<SupportJournal>,
+}
+
+/// Support entries as they were before one thread first changed them
+///
+/// Only changes made on the thread that began the journal are recorded, like the fact
+/// store's journal of the same transaction.
+#[derive(Debug)]
+pub(crate) struct SupportJournal {
+    owner: std::thread::ThreadId,
+    justifications: HashMap<FactId, Option<Vec<Justification>>>,
+    support: HashMap<FactId, Option<Vec<HashSet<FactId>>>>,
+}
+
+impl TruthMaintenanceSystem {
+    /// Create an empty truth maintenance system
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that `rule_id` fired on the given supporting facts and derived `derived_facts`
+    pub fn record_activation(
+        &mut self,
+        rule_id: RuleId,
+        supporting_facts: &[FactId],
+        derived_facts: &[FactId],
+    ) {
+        let Some(&fact_id) = supporting_facts.first() else {
+            return;
+        };
+
+        for &support in supporting_facts {
+            self.journal_justifications(support);
+            self.justifications.entry(support).or_default().push(Justification {
+                rule_id,
+                fact_id,
+                derived_facts: derived_facts.to_vec(),
+            });
+        }
+
+        let supports: HashSet<FactId> = supporting_facts.iter().copied().collect();
+        for &derived in derived_facts {
+            self.journal_support(derived);
+            self.support.entry(derived).or_default().push(supports.clone());
+        }
+    }
+
+    /// Retract a fact and cascade the retraction through everything it supported
+    ///
+    /// Activations justified by the fact are withdrawn. Any derived fact left without an
+    /// activation whose supports all still hold is retracted as well, recursively.
+    pub fn retract(&mut self, fact_id: FactId) -> RetractionResult {
+        let mut result = RetractionResult { fact_id, ..Default::default() };
+        let mut pending = vec![fact_id];
+        let mut visited = HashSet::new();
+
+        while let Some(current) = pending.pop() {
+            if !visited.insert(current) {
+                continue;
+            }
+
+            // A retracted fact no longer needs support itself
+            self.journal_support(current);
+            self.support.remove(&current);
+
+            self.journal_justifications(current);
+            let Some(justifications) = self.justifications.remove(&current) else {
+                continue;
+            };
+
+            for justification in justifications {
+                let activation = (justification.rule_id, justification.fact_id);
+                if !result.withdrawn_activations.contains(&activation) {
+                    result.withdrawn_activations.push(activation);
+                }
+
+                for derived in justification.derived_facts {
+                    self.journal_support(derived);
+                    let unsupported = match self.support.get_mut(&derived) {
+                        Some(supports) => {
+                            supports.retain(|supporters| !supporters.contains(&current));
+                            supports.is_empty()
+                        }
+                        None => false,
+                    };
+
+                    if unsupported {
+                        self.support.remove(&derived);
+                        result.retracted_facts.push(derived);
+                        pending.push(derived);
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Activations currently justified by a fact
+    pub fn justifications_for(&self, fact_id: FactId) -> &[Justification] {
+        self.justifications.get(&fact_id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Whether a derived fact still has logical support
+    pub fn is_supported(&self, fact_id: FactId) -> bool {
+        self.support.get(&fact_id).is_some_and(|supports| !supports.is_empty())
+    }
+
+    /// Number of facts with recorded justifications
+    pub fn tracked_fact_count(&self) -> usize {
+        self.justifications.len()
+    }
+
+    /// Drop all recorded support information
+    pub fn clear(&mut self) {
+        self.justifications.clear();
+        self.support.clear();
+    }
+
+    /// Start recording the entries the calling thread changes, replacing any journal
+    /// already open
+    pub(crate) fn begin_journal(&mut self) {
+        self.journal = Some(SupportJournal {
+            owner: std::thread::current().id(),
+            justifications: HashMap::new(),
+            support: HashMap::new(),
+        });
+    }
+
+    /// Stop recording changes, returning what was recorded
+    pub(crate) fn end_journal(&mut self) -> Option<SupportJournal> {
+        self.journal.take()
+    }
+
+    /// Put every entry `journal` recorded back as it was before the journal began
+    pub(crate) fn undo(&mut self, journal: SupportJournal) {
+        for (fact_id, justifications) in journal.justifications {
+            match justifications {
+                Some(justifications) => self.justifications.insert(fact_id, justifications),
+                None => self.justifications.remove(&fact_id),
+            };
+        }
+        for (fact_id, supports) in journal.support {
+            match supports {
+                Some(supports) => self.support.insert(fact_id, supports),
+                None => self.support.remove(&fact_id),
+            };
+        }
+    }
+
+    /// Record `fact_id`'s justifications as they stand, if the calling thread is
+    /// journaling and has not changed them before
+    fn journal_justifications(&mut self, fact_id: FactId) {
+        if let Some(journal) = Self::own_journal(&mut self.journal) {
+            journal
+                .justifications
+                .entry(fact_id)
+                .or_insert_with(|| self.justifications.get(&fact_id).cloned());
+        }
+    }
+
+    /// Record `fact_id`'s supports as they stand, if the calling thread is
+    /// journaling and has not changed them before
+    fn journal_support(&mut self, fact_id: FactId) {
+        if let Some(journal) = Self::own_journal(&mut self.journal) {
+            journal
+                .support
+                .entry(fact_id)
+                .or_insert_with(|| self.support.get(&fact_id).cloned());
+        }
+    }
+
+    /// The journal, if the calling thread opened it
+    fn own_journal(journal: &mut Option<SupportJournal>) -> Option<&mut SupportJournal> {
+        journal.as_mut().filter(|journal| journal.owner == std::thread::current().id())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retract_withdraws_activations() {
+        let mut tms = TruthMaintenanceSystem::new();
+        tms.record_activation(1, &[10], &[]);
+        tms.record_activation(2, &[10], &[]);
+
+        let result = tms.retract(10);
+        assert_eq!(result.withdrawn_activations, vec![(1, 10), (2, 10)]);
+        assert!(result.retracted_facts.is_empty());
+        assert_eq!(tms.tracked_fact_count(), 0);
+    }
+
+    #[test]
+    fn test_retract_cascades_through_derived_facts() {
+        let mut tms = TruthMaintenanceSystem::new();
+        // Fact 10 fires rule 1 which derives fact 100, which fires rule 2 deriving 200
+        tms.record_activation(1, &[10], &[100]);
+        tms.record_activation(2, &[100], &[200]);
+
+        let result = tms.retract(10);
+        assert_eq!(result.withdrawn_activations, vec![(1, 10), (2, 100)]);
+        assert_eq!(result.retracted_facts, vec![100, 200]);
+        assert!(!tms.is_supported(100));
+        assert!(!tms.is_supported(200));
+    }
+
+    #[test]
+    fn test_derived_fact_with_remaining_support_survives() {
+        let mut tms = TruthMaintenanceSystem::new();
+        tms.record_activation(1, &[10], &[100]);
+        tms.record_activation(1, &[11], &[100]);
+
+        let result = tms.retract(10);
+        assert_eq!(result.withdrawn_activations, vec![(1, 10)]);
+        assert!(result.retracted_facts.is_empty());
+        assert!(tms.is_supported(100));
+
+        let result = tms.retract(11);
+        assert_eq!(result.retracted_facts, vec![100]);
+    }
+
+    #[test]
+    fn test_derived_fact_needs_every_support_of_an_activation() {
+        let mut tms = TruthMaintenanceSystem::new();
+        // Facts 10 and 11 together derive fact 100
+        tms.record_activation(1, &[10, 11], &[100]);
+        assert!(tms.is_supported(100));
+
+        let result = tms.retract(10);
+        assert_eq!(result.withdrawn_activations, vec![(1, 10)]);
+        assert_eq!(result.retracted_facts, vec![100]);
+        assert!(!tms.is_supported(100));
+    }
+}
@@ -1,10 +1,19 @@
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::fmt;
 
 use serde::{Deserialize, Serialize};
 
+/// Field rule conditions test a fact's type through
+///
+/// A condition on this field compares against [`Fact::fact_type`] when the fact has a
+/// type, and against the data field of the same name otherwise.
+pub const FACT_TYPE_FIELD: &str = "type";
+
 // Re-export FactValue from bingo-types
-pub use bingo_types::FactValue;
+pub use bingo_types::{
+    Decimal, DecimalRounding, FactValue, IntegerOp, OverflowPolicy, RoundingMode,
+};
 
 // Built-in Calculator Error Handling
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -92,6 +101,9 @@ pub struct Fact {
     pub external_id: Option<String>,
     /// UTC timestamp when the fact was created
     pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Type of the fact, which routes it only to rules of its type or of any type
+    #[serde(default)]
+    pub fact_type: Option<String>,
     /// Structured data content of the fact
     pub data: FactData,
 }
@@ -134,7 +146,44 @@ impl Fact {
     /// In production, facts are typically created through the engine's fact
     /// ingestion methods which handle ID assignment automatically.
     pub fn new(id: FactId, data: FactData) -> Self {
-        Self { id, external_id: None, timestamp: chrono::Utc::now(), data }
+        Self { id, external_id: None, timestamp: chrono::Utc::now(), fact_type: None, data }
+    }
+
+    /// Fact type of this fact
+    ///
+    /// The RETE network routes a fact only to the alpha memories of rules matching
+    /// its type, or matching any type, so tagging facts of heterogeneous streams
+    /// saves testing them against the conditions of every other type's rules.
+    pub fn fact_type(&self) -> Option<&str> {
+        self.fact_type.as_deref()
+    }
+
+    /// Tag this fact with `fact_type`
+    pub fn with_type(mut self, fact_type: impl Into<String>) -> Self {
+        self.fact_type = Some(fact_type.into());
+        self
+    }
+
+    /// Type a condition on [`FACT_TYPE_FIELD`] sees: the fact's type, or the text of
+    /// the data field of that name for facts without one
+    pub(crate) fn condition_type(&self) -> Option<&str> {
+        match (&self.fact_type, self.data.fields.get(FACT_TYPE_FIELD)) {
+            (Some(fact_type), _) => Some(fact_type),
+            (None, Some(FactValue::String(fact_type))) => Some(fact_type),
+            _ => None,
+        }
+    }
+
+    /// Value a condition on `field` is tested against
+    ///
+    /// Conditions on [`FACT_TYPE_FIELD`] see the fact's type when it has one.
+    pub fn condition_value(&self, field: &str) -> Option<Cow<'_, FactValue>> {
+        match &self.fact_type {
+            Some(fact_type) if field == FACT_TYPE_FIELD => {
+                Some(Cow::Owned(FactValue::String(fact_type.clone())))
+            }
+            _ => self.data.fields.get(field).map(Cow::Borrowed),
+        }
     }
 }
 
@@ -220,7 +269,7 @@ pub struct FactData {
 /// ## Usage Example
 ///
 /// ```rust
-/// use bingo_core::types::{Rule, Condition, Action, ActionType, Operator, FactValue};
+/// use bingo_core::types::{Rule, RuleMetadata, Condition, Action, ActionType, Operator, FactValue};
 ///
 /// let rule = Rule {
 ///     id: 1,
@@ -239,6 +288,10 @@ pub struct FactData {
 ///             }
 ///         }
 ///     ],
+///     metadata: RuleMetadata {
+///         owner: Some("payroll-team".to_string()),
+///         ..Default::default()
+///     },
 /// };
 /// ```
 ///
@@ -257,6 +310,102 @@ pub struct Rule {
     pub conditions: Vec<Condition>,
     /// Actions to execute when conditions are met
     pub actions: Vec<Action>,
+    /// Documentation, ownership and validity period, kept with the rule for governance
+    #[serde(default)]
+    pub metadata: RuleMetadata,
+}
+
+impl Rule {
+    /// Fact type the rule matches, when one of its conditions is `type == "..."`
+    ///
+    /// Conditions nested in `and`, `or` and complex conditions don't restrict the
+    /// rule to one type.
+    pub fn fact_type(&self) -> Option<&str> {
+        self.conditions.iter().find_map(|condition| match condition {
+            Condition::Simple {
+                field,
+                operator: Operator::Equal,
+                value: FactValue::String(fact_type),
+            } if field == FACT_TYPE_FIELD => Some(fact_type.as_str()),
+            _ => None,
+        })
+    }
+}
+
+/// Documentation, ownership, validity period and grouping of a rule
+///
+/// A rule only fires between its effective and expiry dates, checked against the
+/// batch's [`EvaluationDate`] when it matches, which is the clock unless the batch
+/// says otherwise. Rules in a disabled rule group don't fire, and rules sharing an
+/// activation group fire at most once per fact between them. The other fields are
+/// informational and are surfaced by the rule listing APIs.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleMetadata {
+    /// What the rule is for
+    pub description: Option<String>,
+    /// Team or person responsible for the rule
+    pub owner: Option<String>,
+    /// Link to the rule's specification, ticket or policy document
+    pub link: Option<String>,
+    /// When the rule starts firing, immediately when `None`; `valid_from` in JSON also
+    /// works
+    #[serde(alias = "valid_from")]
+    pub effective_date: Option<chrono::DateTime<chrono::Utc>>,
+    /// When the rule stops firing, never when `None`; `valid_to` in JSON also works
+    #[serde(alias = "valid_to")]
+    pub expiry_date: Option<chrono::DateTime<chrono::Utc>>,
+    /// Named group that can be disabled and enabled again as a whole
+    pub rule_group: Option<String>,
+    /// Rules sharing an activation group fire at most once per fact: the matching
+    /// rule with the highest salience, then the lowest rule ID, wins
+    pub activation_group: Option<String>,
+}
+
+impl RuleMetadata {
+    /// Whether the rule fires at `at`: on or after its effective date and before its
+    /// expiry date
+    pub fn is_in_effect_at(&self, at: chrono::DateTime<chrono::Utc>) -> bool {
+        self.effective_date.is_none_or(|effective| at >= effective)
+            && self.expiry_date.is_none_or(|expiry| at < expiry)
+    }
+
+    /// Whether the rule fires now, without reading the clock for rules that are
+    /// always in effect
+    pub fn is_in_effect_now(&self) -> bool {
+        self.is_in_effect(None)
+    }
+
+    /// Whether the rule fires at `as_of`, or now when `as_of` is `None`
+    pub fn is_in_effect(&self, as_of: Option<chrono::DateTime<chrono::Utc>>) -> bool {
+        (self.effective_date.is_none() && self.expiry_date.is_none())
+            || self.is_in_effect_at(as_of.unwrap_or_else(chrono::Utc::now))
+    }
+}
+
+/// Date that rule and reference table validity is checked against
+///
+/// Recalculating a past period evaluates its facts against the rules and reference
+/// tables that were in effect back then rather than today's.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EvaluationDate {
+    /// The clock when each fact is evaluated
+    #[default]
+    Now,
+    /// A fixed date for the whole batch, e.g. the end of the pay period being rerun
+    At(chrono::DateTime<chrono::Utc>),
+    /// Each fact's own timestamp, i.e. when the event it records happened
+    EventTime,
+}
+
+impl EvaluationDate {
+    /// Date `fact` is evaluated as of, `None` meaning the clock
+    pub fn as_of(&self, fact: &Fact) -> Option<chrono::DateTime<chrono::Utc>> {
+        match self {
+            Self::Now => None,
+            Self::At(date) => Some(*date),
+            Self::EventTime => Some(fact.timestamp),
+        }
+    }
 }
 
 /// Unique identifier for rules within the engine
@@ -282,6 +431,8 @@ pub type RuleId = u64;
 /// - **Complex**: Logical combinations of other conditions (AND, OR, NOT)
 /// - **Aggregation**: Patterns across multiple facts (SUM, COUNT, etc.)
 /// - **Stream**: Time-windowed patterns for real-time processing
+/// - **Sequence**: Facts occurring in order within a time limit
+/// - **Accumulate**: Related facts combined into a value later conditions can test
 ///
 /// ## Usage Examples
 ///
@@ -321,6 +472,7 @@ pub type RuleId = u64;
 /// - Complex conditions create beta nodes with optimized join algorithms
 /// - Aggregation conditions use lazy evaluation for efficiency
 /// - Stream conditions leverage time-window indexing
+/// - Sequence conditions keep partial matches until they complete or time out
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum Condition {
@@ -354,6 +506,10 @@ pub enum Condition {
     Aggregation(AggregationCondition),
     /// Stream processing condition with time windows
     Stream(StreamCondition),
+    /// Ordered fact patterns occurring within a time limit
+    Sequence(SequenceCondition),
+    /// Facts matching a pattern, combined into a value bound for later conditions
+    Accumulate(AccumulateCondition),
 }
 
 impl PartialEq for Condition {
@@ -382,6 +538,8 @@ impl PartialEq for Condition {
                     && s1.alias == s2.alias
                 // Skip filter and having conditions for simplicity in equality comparison
             }
+            (Condition::Sequence(s1), Condition::Sequence(s2)) => s1 == s2,
+            (Condition::Accumulate(a1), Condition::Accumulate(a2)) => a1 == a2,
             _ => false,
         }
     }
@@ -426,6 +584,17 @@ impl std::hash::Hash for Condition {
                 stream.alias.hash(state);
                 // Skip optional filter and having for simplicity
             }
+            Condition::Sequence(sequence) => {
+                6u8.hash(state);
+                sequence.hash(state);
+            }
+            Condition::Accumulate(accumulate) => {
+                7u8.hash(state);
+                accumulate.source.hash(state);
+                accumulate.correlate_by.hash(state);
+                accumulate.alias.hash(state);
+                // Skip the accumulator, which may hold a float percentile
+            }
         }
     }
 }
@@ -456,6 +625,58 @@ pub struct StreamCondition {
     pub alias: String,
 }
 
+/// Temporal pattern condition matching facts that occur in order within a time limit
+///
+/// Matches the fact completing the sequence: one passing the last step, preceded by
+/// facts passing each earlier step in order, all within `within_ms` of the first step
+/// and agreeing on the `correlate_by` fields.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct SequenceCondition {
+    /// Patterns the facts of the sequence pass, in order
+    pub steps: Vec<Condition>,
+    /// Longest time from the first step's event to the last step's, in milliseconds
+    pub within_ms: u64,
+    /// Fields every fact of one sequence must have the same value for
+    #[serde(default)]
+    pub correlate_by: Vec<String>,
+    /// Name of the sequence
+    pub alias: String,
+}
+
+/// Condition collecting the facts that match a pattern into one value
+///
+/// The value is bound to `alias` on the fact the rule is evaluated for, so later
+/// conditions test it like any other field and actions read it, e.g. in a `Formula`.
+/// The condition itself matches once the value is bound; constraints on the value
+/// belong in the conditions that follow.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AccumulateCondition {
+    /// Pattern the collected facts match
+    pub source: Box<Condition>,
+    /// Fields the collected facts share with the fact the rule is evaluated for, such
+    /// as the `order_id` of an order's line items; every matching fact when empty
+    #[serde(default)]
+    pub correlate_by: Vec<String>,
+    /// How the collected facts are combined
+    pub accumulator: Accumulator,
+    /// Field the result is bound to, shadowing any field of that name
+    pub alias: String,
+}
+
+/// How an accumulate condition combines the facts it collects
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum Accumulator {
+    /// Array holding every collected fact's fields as an object
+    Collect,
+    /// Array of one field's values, from the collected facts that have it
+    CollectField { field: String },
+    /// Aggregate of one field, computed as for aggregation conditions
+    Aggregate { aggregation_type: AggregationType, field: String },
+    /// Result of a registered calculator called with the array of one field's values
+    /// as its `values` argument
+    Calculator { calculator: String, field: String },
+}
+
 /// Window specification for stream processing conditions
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum StreamWindowSpec {
@@ -465,12 +686,33 @@ pub enum StreamWindowSpec {
     Sliding { size_ms: u64, advance_ms: u64 },
     /// Session window with gap timeout
     Session { gap_timeout_ms: u64 },
+    /// Tumbling window aligned to calendar boundaries in an IANA time zone
+    /// (e.g. days starting at midnight in `America/Los_Angeles`)
+    Calendar { period: CalendarPeriod, timezone: String },
     /// Count-based tumbling window
     CountTumbling { count: usize },
     /// Count-based sliding window
     CountSliding { size: usize, advance: usize },
 }
 
+/// Calendar periods for timezone-aligned stream windows
+///
+/// Periods follow local clock time, so a daily window spanning a daylight saving
+/// transition lasts 23 or 25 hours.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum CalendarPeriod {
+    /// Local clock hours
+    Hourly,
+    /// Local days starting at midnight
+    Daily,
+    /// Weeks starting at midnight on `week_start`
+    Weekly { week_start: chrono::Weekday },
+    /// The 1st to the 15th and the 16th to the end of each month
+    SemiMonthly,
+    /// Calendar months
+    Monthly,
+}
+
 /// Aggregation functions for stream processing
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum StreamAggregation {
@@ -508,6 +750,42 @@ pub enum Operator {
     Contains,
     StartsWith,
     EndsWith,
+    /// Date no further from now than a `Duration`, or inside an `Interval`
+    Within,
+    /// Date, or end of an `Interval`, further in the past than a `Duration`
+    OlderThan,
+    /// `Interval` sharing an instant with another interval, or containing a date
+    Overlaps,
+    /// String matching a regular expression, anywhere unless the pattern is anchored
+    Matches,
+    /// String containing a substring, or array containing a string, ignoring case
+    ContainsIgnoreCase,
+    /// Member of a literal array or of a named value list
+    In,
+    /// Not a member of a literal array or of a named value list
+    NotIn,
+}
+
+impl Operator {
+    /// Whether the operator compares dates, durations and intervals
+    pub fn is_temporal(&self) -> bool {
+        matches!(
+            self,
+            Operator::Within | Operator::OlderThan | Operator::Overlaps
+        )
+    }
+
+    /// Evaluate a temporal operator against the current time
+    ///
+    /// Returns `false` for non-temporal operators.
+    pub fn matches_temporal(&self, actual: &FactValue, expected: &FactValue) -> bool {
+        match self {
+            Operator::Within => actual.is_within(expected, chrono::Utc::now()),
+            Operator::OlderThan => actual.is_older_than(expected, chrono::Utc::now()),
+            Operator::Overlaps => actual.overlaps(expected),
+            _ => false,
+        }
+    }
 }
 
 /// Logical operators for complex conditions
@@ -563,9 +841,9 @@ pub struct Action {
 ///
 /// - **Data Manipulation**: SetField, UpdateFact, DeleteFact, CreateFact
 /// - **Calculations**: Formula, CallCalculator, IncrementField
-/// - **External Integration**: TriggerAlert, SendNotification
+/// - **External Integration**: TriggerAlert, SendNotification, CallWebhook
 /// - **Debugging**: Log
-/// - **Collections**: AppendToArray
+/// - **Collections**: AppendToArray, MergeObject
 ///
 /// ## Usage Examples
 ///
@@ -664,6 +942,10 @@ pub enum ActionType {
     },
 
     /// Increment a numeric field by a specified amount
+    ///
+    /// Like the other mutation actions, `AppendToArray` and `MergeObject`, the stored
+    /// fact is updated atomically, so rules mutating the same field in one batch build
+    /// on each other's results.
     IncrementField {
         /// Field to increment
         field: String,
@@ -679,6 +961,14 @@ pub enum ActionType {
         value: FactValue,
     },
 
+    /// Merge entries into an object field, replacing existing keys
+    MergeObject {
+        /// Object field to modify
+        field: String,
+        /// Entries to add or replace
+        entries: HashMap<String, FactValue>,
+    },
+
     /// Send a notification to external systems
     SendNotification {
         /// Notification recipient
@@ -692,6 +982,16 @@ pub enum ActionType {
         /// Additional metadata
         metadata: HashMap<String, FactValue>,
     },
+
+    /// Post the triggering fact and rule metadata to a registered webhook endpoint
+    ///
+    /// Delivery is asynchronous, with retries and a dead-letter queue (see `webhook`).
+    CallWebhook {
+        /// Name of the registered endpoint
+        endpoint: String,
+        /// Additional fields sent with the payload
+        metadata: HashMap<String, FactValue>,
+    },
 }
 
 /// Alert severity levels for stream processing
@@ -716,7 +1016,7 @@ pub enum NotificationType {
 }
 
 /// Aggregation types supported by the engine
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum AggregationType {
     Sum,
     Count,
@@ -745,10 +1045,44 @@ impl AggregationType {
 /// Window types for aggregations
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AggregationWindow {
-    Sliding { size: usize },
-    Tumbling { size: usize },
-    Session { timeout_ms: u64 },
-    Time { duration_ms: u64 },
+    Sliding {
+        size: usize,
+    },
+    Tumbling {
+        size: usize,
+    },
+    /// Event-time sessions per group, each ending once no event follows the last one
+    /// within `timeout_ms`
+    Session {
+        timeout_ms: u64,
+    },
+    Time {
+        duration_ms: u64,
+    },
+    /// Periods of a `PeriodCalendar` registered with the engine under this name
+    Calendar {
+        calendar: String,
+    },
+    /// Event-time windows of `duration_ms` per group, aligned to the epoch
+    TumblingTime {
+        duration_ms: u64,
+    },
+    /// Event-time windows of `size_ms` per group, one starting every `advance_ms`
+    SlidingTime {
+        size_ms: u64,
+        advance_ms: u64,
+    },
+}
+
+impl AggregationWindow {
+    /// Whether facts are assigned to windows by event time and subject to the
+    /// watermark, as tumbling, sliding and session windows are
+    pub fn is_event_time(&self) -> bool {
+        matches!(
+            self,
+            Self::Session { .. } | Self::TumblingTime { .. } | Self::SlidingTime { .. }
+        )
+    }
 }
 
 /// Comprehensive performance and resource statistics for the engine
@@ -976,11 +1310,29 @@ pub struct AlphaNode {
     pub condition: Condition,
     /// Rules that use this alpha node condition
     pub rule_ids: Vec<RuleId>,
+    /// Candidate facts the condition was tested against
+    pub evaluations: u64,
+    /// Candidate facts that satisfied the condition
+    pub matches: u64,
 }
 
 impl AlphaNode {
     pub fn new(id: NodeId, condition: Condition) -> Self {
-        Self { id, condition, rule_ids: Vec::new() }
+        Self { id, condition, rule_ids: Vec::new(), evaluations: 0, matches: 0 }
+    }
+
+    /// Count one evaluation of the condition
+    pub fn record_evaluation(&mut self, matched: bool) {
+        self.evaluations += 1;
+        if matched {
+            self.matches += 1;
+        }
+    }
+
+    /// Reset the evaluation counters
+    pub fn reset_counters(&mut self) {
+        self.evaluations = 0;
+        self.matches = 0;
     }
 
     /// Add a rule that uses this alpha node
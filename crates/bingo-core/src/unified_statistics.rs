@@ -526,7 +526,8 @@ mod tests {
     #[test]
     fn test_cache_hit_rate_calculation() {
         let mut stats = UnifiedStats::new();
-        let cache_stats = CacheStats { capacity: 100, size: 50, access_counter: 200 };
+        let cache_stats =
+            CacheStats { capacity: 100, size: 50, access_counter: 200, ..Default::default() };
 
         stats.register_cache("FactStore", cache_stats, 800, 200);
         assert_eq!(stats.overall_cache_hit_rate(), 80.0);
@@ -537,7 +538,8 @@ mod tests {
         let mut stats = UnifiedStats::new();
 
         // Register high-performance statistics
-        let cache_stats = CacheStats { capacity: 100, size: 80, access_counter: 1000 };
+        let cache_stats =
+            CacheStats { capacity: 100, size: 80, access_counter: 1000, ..Default::default() };
         stats.register_cache("FactStore", cache_stats, 900, 100); // 90% hit rate
         stats.register_memory_pool("Token", 800, 200, 500, 400, 50); // 80% utilization
         stats.register_calculator(700, 300, 850, 150, 1000); // 70% and 85% efficiency
@@ -561,7 +563,8 @@ mod tests {
 
     #[test]
     fn test_builder_pattern() {
-        let cache_stats = CacheStats { capacity: 100, size: 75, access_counter: 500 };
+        let cache_stats =
+            CacheStats { capacity: 100, size: 75, access_counter: 500, ..Default::default() };
 
         let stats = UnifiedStatsBuilder::new()
             .with_fact_storage("HashMap", 1000, 800)
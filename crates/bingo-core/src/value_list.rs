@@ -0,0 +1,210 @@
+//! Value sets for `In` and `NotIn` conditions
+//!
+//! A membership condition compares a field against either a literal
+//! `FactValue::Array` or the name of a table in the engine's
+//! [`ReferenceDataStore`], such as a sanctions list with thousands of country codes.
+//! Literal arrays are compiled into a hashed [`ValueSet`] when the rule is added and
+//! `In` conditions on them are indexed by every member, so facts find the rules they
+//! match directly. Named tables are looked up when the condition is tested, so a
+//! reloaded table applies to existing rules immediately, and a dated table resolves
+//! to the version in effect at the date facts are evaluated as of. Either way a test costs one
+//! hash lookup instead of one comparison per value.
+//!
+//! Members compare exactly, like `Equal` under the binary collation: `Integer(1)`
+//! is not a member of `[1.0]` and string case is significant.
+
+use crate::reference_data::{ReferenceDataStore, ReferenceTable};
+use crate::types::{FactValue, Operator};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// Prefix of the names literal arrays are compiled under
+///
+/// It starts with the reserved prefix, so no reference table can shadow a literal.
+pub const LITERAL_LIST_PREFIX: &str = "$literal";
+
+/// Hashed set of values, cheap to clone
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValueSet(Arc<HashSet<FactValue>>);
+
+impl ValueSet {
+    /// Whether `value` is a member
+    pub fn contains(&self, value: &FactValue) -> bool {
+        self.0.contains(value)
+    }
+
+    /// Number of distinct members
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether the set has no members
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Members in arbitrary order
+    pub fn iter(&self) -> impl Iterator<Item = &FactValue> {
+        self.0.iter()
+    }
+}
+
+impl FromIterator<FactValue> for ValueSet {
+    fn from_iter<I: IntoIterator<Item = FactValue>>(values: I) -> Self {
+        Self(Arc::new(values.into_iter().collect()))
+    }
+}
+
+/// Value sets by name: compiled literal arrays plus the reference tables
+#[derive(Debug, Clone, Default)]
+pub struct ValueLists {
+    literals: HashMap<String, ValueSet>,
+    next_literal: u64,
+    reference_data: ReferenceDataStore,
+    as_of: Option<DateTime<Utc>>,
+}
+
+impl ValueLists {
+    /// Value lists resolving names against `reference_data`
+    pub fn new(reference_data: ReferenceDataStore) -> Self {
+        Self { reference_data, ..Self::default() }
+    }
+
+    /// Register `values` as a key-only reference table named `name`
+    pub fn register(
+        &self,
+        name: impl Into<String>,
+        values: impl IntoIterator<Item = FactValue>,
+    ) -> Result<()> {
+        self.reference_data.register(name, ReferenceTable::from_keys(values))
+    }
+
+    /// Compile a literal array and return the generated name it is stored under
+    pub fn compile_literal(&mut self, values: &[FactValue]) -> String {
+        self.next_literal += 1;
+        let name = format!("{LITERAL_LIST_PREFIX}{}", self.next_literal);
+        self.literals.insert(name.clone(), values.iter().cloned().collect());
+        name
+    }
+
+    /// Compiled literal sets by name, and how many names were generated
+    pub(crate) fn compiled_literals(&self) -> (Vec<(String, Vec<FactValue>)>, u64) {
+        let mut literals: Vec<(String, Vec<FactValue>)> = self
+            .literals
+            .iter()
+            .map(|(name, values)| (name.clone(), values.iter().cloned().collect()))
+            .collect();
+        literals.sort_by(|(a, _), (b, _)| a.cmp(b));
+        (literals, self.next_literal)
+    }
+
+    /// Restore literal sets captured with `compiled_literals`
+    pub(crate) fn restore_literals(&mut self, literals: &[(String, Vec<FactValue>)], next: u64) {
+        for (name, values) in literals {
+            self.literals.insert(name.clone(), values.iter().cloned().collect());
+        }
+        self.next_literal = self.next_literal.max(next);
+    }
+
+    /// Compiled literal set stored under `name`
+    pub fn literal(&self, name: &str) -> Option<&ValueSet> {
+        self.literals.get(name)
+    }
+
+    /// Whether `name` is a compiled literal or a registered reference table
+    pub fn contains(&self, name: &str) -> bool {
+        self.literals.contains_key(name) || self.reference_data.contains(name)
+    }
+
+    /// Store named lists are resolved against
+    pub fn reference_data(&self) -> &ReferenceDataStore {
+        &self.reference_data
+    }
+
+    /// Resolve dated reference tables as of `as_of`, or now when `None`
+    pub fn set_as_of(&mut self, as_of: Option<DateTime<Utc>>) {
+        self.as_of = as_of;
+    }
+
+    /// Copy sharing the reference tables but none of the compiled literals
+    pub fn without_literals(&self) -> Self {
+        Self::new(self.reference_data.clone())
+    }
+
+    /// Evaluate `In` or `NotIn`
+    ///
+    /// `expected` is a literal array, a compiled literal name or a reference table
+    /// name. An unknown name or any other value matches neither operator.
+    pub fn matches(&self, operator: &Operator, actual: &FactValue, expected: &FactValue) -> bool {
+        let is_member = match expected {
+            FactValue::String(name) => match self.literals.get(name) {
+                Some(set) => set.contains(actual),
+                None => match self.reference_data.get_as_of(name, self.as_of) {
+                    Some(table) => table.contains_key(actual),
+                    None => return false,
+                },
+            },
+            _ => return matches_literal(operator, actual, expected),
+        };
+        membership(operator, is_member)
+    }
+}
+
+/// Evaluate `In` or `NotIn` against a literal array; any other value matches neither
+pub fn matches_literal(operator: &Operator, actual: &FactValue, expected: &FactValue) -> bool {
+    match expected {
+        FactValue::Array(values) => membership(operator, values.contains(actual)),
+        _ => false,
+    }
+}
+
+fn membership(operator: &Operator, is_member: bool) -> bool {
+    match operator {
+        Operator::In => is_member,
+        Operator::NotIn => !is_member,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<FactValue> {
+        values.iter().map(|value| FactValue::String(value.to_string())).collect()
+    }
+
+    #[test]
+    fn test_named_list_membership() {
+        let lists = ValueLists::default();
+        lists.register("sanctioned", strings(&["IR", "KP", "SY"])).unwrap();
+
+        let list = FactValue::String("sanctioned".to_string());
+        let iran = FactValue::String("IR".to_string());
+        let france = FactValue::String("FR".to_string());
+        assert!(lists.matches(&Operator::In, &iran, &list));
+        assert!(!lists.matches(&Operator::In, &france, &list));
+        assert!(lists.matches(&Operator::NotIn, &france, &list));
+
+        let unknown = FactValue::String("missing".to_string());
+        assert!(!lists.matches(&Operator::In, &iran, &unknown));
+        assert!(!lists.matches(&Operator::NotIn, &iran, &unknown));
+    }
+
+    #[test]
+    fn test_literals_are_kept_apart_from_registered_lists() {
+        let mut lists = ValueLists::default();
+        lists.register("tiers", strings(&["gold"])).unwrap();
+        let name = lists.compile_literal(&[FactValue::Integer(1), FactValue::Integer(1)]);
+
+        assert_eq!(lists.literal(&name).map(ValueSet::len), Some(1));
+        assert!(lists.register(name.clone(), strings(&["x"])).is_err());
+        assert_eq!(lists.reference_data().names(), vec!["tiers"]);
+
+        let rebuilt = lists.without_literals();
+        assert!(!rebuilt.contains(&name));
+        assert!(rebuilt.contains("tiers"));
+    }
+}
@@ -0,0 +1,637 @@
+//! Webhook actions
+//!
+//! `ActionType::CallWebhook` hands the triggering fact and rule metadata to a named
+//! endpoint so rules can drive downstream systems directly. Rule execution does not wait
+//! for delivery: payloads are queued on a bounded [`AsyncSink`] and delivered by a fixed
+//! pool of worker threads, which retry failures with exponential backoff and move
+//! payloads that still fail to a bounded dead-letter queue for inspection and replay.
+//! Payloads rejected or evicted by the queue's overflow policy are dead-lettered too.
+//!
+//! Endpoints are registered by name on a [`WebhookDispatcher`], so rules do not carry
+//! URLs and an endpoint can be re-pointed without recompiling rules. A target is one of:
+//!
+//! - [`WebhookTarget::Http`]: POST the payload as JSON to an `http://` URL
+//! - [`WebhookTarget::Channel`]: publish the payload on a crossbeam channel
+//! - [`WebhookTarget::Custom`]: hand the payload to a [`WebhookTransport`], e.g. for
+//!   TLS or a message broker client
+
+use crate::error::{BingoError, BingoResult};
+use crate::event_sink::{AsyncSink, AsyncSinkConfig, QueueOverflowPolicy, Submission};
+use crate::types::{Fact, FactValue, RuleId};
+use chrono::{DateTime, Utc};
+use crossbeam::channel::Sender;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+/// Delivers payloads for [`WebhookTarget::Custom`] endpoints
+pub trait WebhookTransport: Send + Sync {
+    /// Deliver one payload; an error is retried and eventually dead-lettered
+    fn deliver(&self, payload: &WebhookPayload) -> Result<(), String>;
+}
+
+/// Where a webhook endpoint sends its payloads
+#[derive(Clone)]
+pub enum WebhookTarget {
+    /// POST the payload as JSON to an `http://` URL; a non-2xx status is a failure
+    Http {
+        url: String,
+        /// Extra request headers, e.g. for authentication
+        headers: Vec<(String, String)>,
+        /// Connect, write and read timeout
+        timeout: Duration,
+    },
+    /// Publish the payload on a channel; a disconnected receiver is a failure
+    Channel(Sender<WebhookPayload>),
+    /// Hand the payload to a custom transport
+    Custom(Arc<dyn WebhookTransport>),
+}
+
+impl WebhookTarget {
+    /// HTTP target with no extra headers and a 5 second timeout
+    pub fn http(url: impl Into<String>) -> Self {
+        Self::Http { url: url.into(), headers: Vec::new(), timeout: Duration::from_secs(5) }
+    }
+
+    fn validate(&self) -> BingoResult<()> {
+        match self {
+            Self::Http { url, .. } => HttpUrl::parse(url).map(|_| ()).map_err(|message| {
+                BingoError::configuration("webhook.url", "an http:// URL", url, message)
+            }),
+            Self::Channel(_) | Self::Custom(_) => Ok(()),
+        }
+    }
+
+    fn deliver(&self, payload: &WebhookPayload) -> Result<(), String> {
+        match self {
+            Self::Http { url, headers, timeout } => post_json(url, headers, *timeout, payload),
+            Self::Channel(sender) => sender
+                .send(payload.clone())
+                .map_err(|_| "Webhook channel receiver is disconnected".to_string()),
+            Self::Custom(transport) => transport.deliver(payload),
+        }
+    }
+}
+
+impl fmt::Debug for WebhookTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Http { url, timeout, .. } => {
+                f.debug_struct("Http").field("url", url).field("timeout", timeout).finish()
+            }
+            Self::Channel(_) => f.write_str("Channel"),
+            Self::Custom(_) => f.write_str("Custom"),
+        }
+    }
+}
+
+/// Worker pool, retry and dead-letter settings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookConfig {
+    /// Worker threads delivering payloads
+    pub workers: usize,
+    /// Payloads waiting for a worker
+    pub queue_capacity: usize,
+    /// What happens to payloads dispatched while the queue is full
+    pub overflow: QueueOverflowPolicy,
+    /// Time allowed for delivering queued payloads when the dispatcher is dropped
+    pub shutdown_timeout: Duration,
+    /// Delivery attempts per payload, including the first
+    pub max_attempts: u32,
+    /// Wait before the first retry, doubled for every further retry
+    pub retry_backoff: Duration,
+    /// Dead letters kept; the oldest is dropped when full
+    pub dead_letter_capacity: usize,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            workers: 2,
+            queue_capacity: 1024,
+            overflow: QueueOverflowPolicy::Error,
+            shutdown_timeout: Duration::from_secs(5),
+            max_attempts: 3,
+            retry_backoff: Duration::from_millis(100),
+            dead_letter_capacity: 1024,
+        }
+    }
+}
+
+/// What a webhook endpoint receives when a rule fires
+#[derive(Debug, Clone)]
+pub struct WebhookPayload {
+    pub endpoint: String,
+    pub rule_id: RuleId,
+    pub rule_name: String,
+    pub fact: Fact,
+    /// Extra fields configured on the action
+    pub metadata: HashMap<String, FactValue>,
+    pub triggered_at: DateTime<Utc>,
+}
+
+impl WebhookPayload {
+    /// The JSON document posted to HTTP endpoints
+    pub fn to_json(&self) -> serde_json::Value {
+        let fields = |fields: &HashMap<String, FactValue>| -> serde_json::Map<_, _> {
+            fields.iter().map(|(name, value)| (name.clone(), value.into())).collect()
+        };
+        serde_json::json!({
+            "endpoint": self.endpoint,
+            "rule": { "id": self.rule_id, "name": self.rule_name },
+            "fact": {
+                "id": self.fact.id,
+                "external_id": self.fact.external_id,
+                "timestamp": self.fact.timestamp.to_rfc3339(),
+                "fields": fields(&self.fact.data.fields),
+            },
+            "metadata": fields(&self.metadata),
+            "triggered_at": self.triggered_at.to_rfc3339(),
+        })
+    }
+}
+
+/// A payload that could not be delivered
+#[derive(Debug, Clone)]
+pub struct DeadLetter {
+    pub payload: WebhookPayload,
+    /// Error of the last attempt
+    pub error: String,
+    /// Delivery attempts made; 0 when the queue was full
+    pub attempts: u32,
+    pub failed_at: DateTime<Utc>,
+}
+
+/// Delivery counters of a dispatcher
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WebhookStats {
+    /// Payloads accepted by the queue
+    pub queued: u64,
+    pub delivered: u64,
+    /// Attempts after a failed one
+    pub retried: u64,
+    pub dead_lettered: u64,
+    /// Payloads queued or being delivered
+    pub pending: usize,
+}
+
+/// Named webhook endpoints and the worker pool delivering to them
+///
+/// Clones share endpoints, workers and the dead-letter queue. Workers start with the
+/// first dispatched payload. Dropping the last clone waits up to the configured
+/// shutdown timeout for queued payloads to be delivered.
+#[derive(Debug, Clone, Default)]
+pub struct WebhookDispatcher {
+    inner: Arc<DispatcherInner>,
+}
+
+#[derive(Debug, Default)]
+struct DispatcherInner {
+    shared: Arc<SharedState>,
+    config: RwLock<WebhookConfig>,
+    queue: Mutex<Option<Arc<AsyncSink<WebhookPayload>>>>,
+}
+
+/// State the workers need, kept apart from the queue sender so workers do not keep it
+/// alive
+#[derive(Debug, Default)]
+struct SharedState {
+    endpoints: RwLock<HashMap<String, WebhookTarget>>,
+    dead_letters: Mutex<VecDeque<DeadLetter>>,
+    queued: AtomicU64,
+    delivered: AtomicU64,
+    retried: AtomicU64,
+    dead_lettered: AtomicU64,
+    pending: AtomicUsize,
+}
+
+impl WebhookDispatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register or replace the endpoint `name`
+    pub fn register(&self, name: impl Into<String>, target: WebhookTarget) -> BingoResult<()> {
+        target.validate()?;
+        self.inner.shared.endpoints.write().unwrap().insert(name.into(), target);
+        Ok(())
+    }
+
+    /// Remove the endpoint `name`; payloads still queued for it are dead-lettered
+    pub fn unregister(&self, name: &str) -> bool {
+        self.inner.shared.endpoints.write().unwrap().remove(name).is_some()
+    }
+
+    /// Whether an endpoint is registered under `name`
+    pub fn contains(&self, name: &str) -> bool {
+        self.inner.shared.endpoints.read().unwrap().contains_key(name)
+    }
+
+    /// Names of the registered endpoints, sorted
+    pub fn endpoints(&self) -> Vec<String> {
+        let mut names: Vec<String> =
+            self.inner.shared.endpoints.read().unwrap().keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Replace the pool settings
+    ///
+    /// Payloads already queued are delivered by the current workers; a new pool with
+    /// these settings starts with the next payload.
+    pub fn configure(&self, config: WebhookConfig) {
+        *self.inner.config.write().unwrap() = config;
+        if let Some(queue) = self.inner.queue.lock().unwrap().take() {
+            // The old workers drain their queue in the background
+            let _ = queue.shutdown(Duration::ZERO);
+        }
+    }
+
+    pub fn config(&self) -> WebhookConfig {
+        self.inner.config.read().unwrap().clone()
+    }
+
+    /// Queue `payload` for delivery without waiting for it
+    ///
+    /// Returns `false` if the queue was full and the overflow policy rejected the
+    /// payload, in which case it is dead-lettered. Under
+    /// [`QueueOverflowPolicy::DropOldest`] the evicted payload is dead-lettered instead,
+    /// and under [`QueueOverflowPolicy::Block`] this waits for room in the queue.
+    pub fn dispatch(&self, payload: WebhookPayload) -> bool {
+        let shared = &self.inner.shared;
+        shared.pending.fetch_add(1, Ordering::SeqCst);
+
+        let queue = {
+            let mut queue = self.inner.queue.lock().unwrap();
+            Arc::clone(queue.get_or_insert_with(|| Arc::new(self.start_workers())))
+        };
+        let capacity = self.config().dead_letter_capacity;
+        match queue.submit(payload) {
+            Submission::Queued => {
+                shared.queued.fetch_add(1, Ordering::Relaxed);
+                true
+            }
+            Submission::Evicted(evicted) => {
+                shared.queued.fetch_add(1, Ordering::Relaxed);
+                let error = "Evicted from full webhook queue".to_string();
+                shared.dead_letter(evicted, error, 0, capacity);
+                true
+            }
+            Submission::Rejected(payload) => {
+                shared.dead_letter(payload, "Webhook queue is full".to_string(), 0, capacity);
+                false
+            }
+        }
+    }
+
+    /// Wait until every queued payload is delivered or dead-lettered
+    ///
+    /// Returns `false` if payloads are still pending after `timeout`.
+    pub fn wait_idle(&self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        while self.inner.shared.pending.load(Ordering::SeqCst) > 0 {
+            if Instant::now() >= deadline {
+                return false;
+            }
+            std::thread::sleep(Duration::from_millis(2));
+        }
+        true
+    }
+
+    pub fn stats(&self) -> WebhookStats {
+        let shared = &self.inner.shared;
+        WebhookStats {
+            queued: shared.queued.load(Ordering::Relaxed),
+            delivered: shared.delivered.load(Ordering::Relaxed),
+            retried: shared.retried.load(Ordering::Relaxed),
+            dead_lettered: shared.dead_lettered.load(Ordering::Relaxed),
+            pending: shared.pending.load(Ordering::SeqCst),
+        }
+    }
+
+    /// Dead letters, oldest first
+    pub fn dead_letters(&self) -> Vec<DeadLetter> {
+        self.inner.shared.dead_letters.lock().unwrap().iter().cloned().collect()
+    }
+
+    /// Queue every dead letter for delivery again, returning how many were queued
+    pub fn retry_dead_letters(&self) -> usize {
+        let letters: Vec<DeadLetter> =
+            self.inner.shared.dead_letters.lock().unwrap().drain(..).collect();
+        letters
+            .into_iter()
+            .filter(|letter| self.dispatch(letter.payload.clone()))
+            .count()
+    }
+
+    /// Start the worker pool, returning its queue
+    fn start_workers(&self) -> AsyncSink<WebhookPayload> {
+        let config = self.config();
+        let shared = Arc::clone(&self.inner.shared);
+        let sink_config = AsyncSinkConfig {
+            capacity: config.queue_capacity,
+            overflow: config.overflow,
+            workers: config.workers,
+            shutdown_timeout: config.shutdown_timeout,
+        };
+        AsyncSink::new(
+            "bingo-webhook",
+            move |payload| {
+                shared.deliver(payload, &config);
+                Ok(())
+            },
+            sink_config,
+        )
+    }
+}
+
+impl SharedState {
+    /// Deliver with retries, dead-lettering the payload if every attempt fails
+    fn deliver(&self, payload: WebhookPayload, config: &WebhookConfig) {
+        let mut backoff = config.retry_backoff;
+        let mut attempts = 0;
+        let error = loop {
+            attempts += 1;
+            let target = self.endpoints.read().unwrap().get(&payload.endpoint).cloned();
+            let result = match target {
+                Some(target) => target.deliver(&payload),
+                None => Err(format!(
+                    "Webhook endpoint '{}' is not registered",
+                    payload.endpoint
+                )),
+            };
+            match result {
+                Ok(()) => {
+                    self.delivered.fetch_add(1, Ordering::Relaxed);
+                    self.pending.fetch_sub(1, Ordering::SeqCst);
+                    return;
+                }
+                Err(error) if attempts >= config.max_attempts.max(1) => break error,
+                Err(error) => {
+                    debug!(endpoint = %payload.endpoint, attempts, %error, "Retrying webhook");
+                    self.retried.fetch_add(1, Ordering::Relaxed);
+                    std::thread::sleep(backoff);
+                    backoff = backoff.saturating_mul(2);
+                }
+            }
+        };
+        self.dead_letter(payload, error, attempts, config.dead_letter_capacity);
+    }
+
+    fn dead_letter(&self, payload: WebhookPayload, error: String, attempts: u32, capacity: usize) {
+        warn!(endpoint = %payload.endpoint, rule_id = payload.rule_id, attempts, %error, "Webhook dead-lettered");
+        let mut dead_letters = self.dead_letters.lock().unwrap();
+        if dead_letters.len() >= capacity.max(1) {
+            dead_letters.pop_front();
+        }
+        dead_letters.push_back(DeadLetter { payload, error, attempts, failed_at: Utc::now() });
+        drop(dead_letters);
+        self.dead_lettered.fetch_add(1, Ordering::Relaxed);
+        self.pending.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Parsed `http://host[:port][/path]` URL
+#[derive(Debug, PartialEq, Eq)]
+struct HttpUrl<'a> {
+    host: &'a str,
+    port: u16,
+    path: &'a str,
+}
+
+impl<'a> HttpUrl<'a> {
+    fn parse(url: &'a str) -> Result<Self, String> {
+        let rest = url.strip_prefix("http://").ok_or_else(|| {
+            format!("Webhook URL '{url}' is not an http:// URL; use a custom transport for other schemes")
+        })?;
+        let (authority, path) = match rest.find('/') {
+            Some(index) => rest.split_at(index),
+            None => (rest, "/"),
+        };
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (
+                host,
+                port.parse().map_err(|_| format!("Webhook URL '{url}' has an invalid port"))?,
+            ),
+            None => (authority, 80),
+        };
+        if host.is_empty() {
+            return Err(format!("Webhook URL '{url}' has no host"));
+        }
+        Ok(Self { host, port, path })
+    }
+}
+
+/// POST `payload` as JSON and require a 2xx status
+fn post_json(
+    url: &str,
+    headers: &[(String, String)],
+    timeout: Duration,
+    payload: &WebhookPayload,
+) -> Result<(), String> {
+    let url = HttpUrl::parse(url)?;
+    let address = (url.host, url.port)
+        .to_socket_addrs()
+        .map_err(|error| format!("Cannot resolve {}: {error}", url.host))?
+        .next()
+        .ok_or_else(|| format!("Cannot resolve {}", url.host))?;
+    let mut stream = TcpStream::connect_timeout(&address, timeout)
+        .map_err(|error| format!("Cannot connect to {address}: {error}"))?;
+    stream.set_read_timeout(Some(timeout)).map_err(|error| error.to_string())?;
+    stream.set_write_timeout(Some(timeout)).map_err(|error| error.to_string())?;
+
+    let body = payload.to_json().to_string();
+    let mut request = format!(
+        "POST {} HTTP/1.1\r\nHost: {}:{}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+        url.path,
+        url.host,
+        url.port,
+        body.len()
+    );
+    for (name, value) in headers {
+        request.push_str(&format!("{name}: {value}\r\n"));
+    }
+    request.push_str("\r\n");
+    request.push_str(&body);
+    stream
+        .write_all(request.as_bytes())
+        .map_err(|error| format!("Request failed: {error}"))?;
+
+    // Only the status line matters
+    let mut response = Vec::new();
+    let mut buffer = [0u8; 256];
+    while !response.windows(2).any(|window| window == b"\r\n") {
+        match stream.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => response.extend_from_slice(&buffer[..read]),
+            Err(error) => return Err(format!("Response failed: {error}")),
+        }
+    }
+    let status_line = String::from_utf8_lossy(&response);
+    let status: u16 = status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|status| status.parse().ok())
+        .ok_or_else(|| "Endpoint sent no HTTP status".to_string())?;
+    if (200..300).contains(&status) {
+        Ok(())
+    } else {
+        Err(format!("Endpoint responded with HTTP {status}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::FactData;
+
+    fn payload(endpoint: &str) -> WebhookPayload {
+        WebhookPayload {
+            endpoint: endpoint.to_string(),
+            rule_id: 7,
+            rule_name: "Large order".to_string(),
+            fact: Fact::new(
+                1,
+                FactData { fields: HashMap::from([("amount".to_string(), FactValue::Integer(5))]) },
+            ),
+            metadata: HashMap::from([("team".to_string(), FactValue::String("ops".into()))]),
+            triggered_at: Utc::now(),
+        }
+    }
+
+    /// Fails the first `failures` deliveries
+    struct Flaky {
+        failures: AtomicUsize,
+    }
+
+    impl WebhookTransport for Flaky {
+        fn deliver(&self, _payload: &WebhookPayload) -> Result<(), String> {
+            let remaining = self.failures.load(Ordering::SeqCst);
+            if remaining == 0 {
+                return Ok(());
+            }
+            self.failures.store(remaining - 1, Ordering::SeqCst);
+            Err("unavailable".to_string())
+        }
+    }
+
+    fn dispatcher(max_attempts: u32) -> WebhookDispatcher {
+        let dispatcher = WebhookDispatcher::new();
+        dispatcher.configure(WebhookConfig {
+            max_attempts,
+            retry_backoff: Duration::from_millis(1),
+            ..WebhookConfig::default()
+        });
+        dispatcher
+    }
+
+    #[test]
+    fn test_url_parsing() {
+        assert_eq!(
+            HttpUrl::parse("http://localhost:8080/hooks/orders").unwrap(),
+            HttpUrl { host: "localhost", port: 8080, path: "/hooks/orders" }
+        );
+        assert_eq!(HttpUrl::parse("http://example.com").unwrap().port, 80);
+        assert!(HttpUrl::parse("https://example.com").is_err());
+        assert!(HttpUrl::parse("http://example.com:x/").is_err());
+        assert!(
+            WebhookDispatcher::new()
+                .register("tls", WebhookTarget::http("https://a"))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_payload_json() {
+        let json = payload("orders").to_json();
+        assert_eq!(json["rule"]["id"], 7);
+        assert_eq!(json["fact"]["fields"]["amount"], 5);
+        assert_eq!(json["metadata"]["team"], "ops");
+    }
+
+    #[test]
+    fn test_failures_are_retried_then_dead_lettered() {
+        let dispatcher = dispatcher(3);
+        let transport = Arc::new(Flaky { failures: AtomicUsize::new(2) });
+        dispatcher.register("flaky", WebhookTarget::Custom(transport.clone())).unwrap();
+
+        assert!(dispatcher.dispatch(payload("flaky")));
+        assert!(dispatcher.wait_idle(Duration::from_secs(5)));
+        assert_eq!(dispatcher.stats().delivered, 1);
+        assert_eq!(dispatcher.stats().retried, 2);
+
+        transport.failures.store(10, Ordering::SeqCst);
+        dispatcher.dispatch(payload("flaky"));
+        dispatcher.dispatch(payload("missing"));
+        assert!(dispatcher.wait_idle(Duration::from_secs(5)));
+        let letters = dispatcher.dead_letters();
+        assert_eq!(letters.len(), 2);
+        assert!(letters.iter().all(|letter| letter.attempts == 3));
+
+        // Replaying once the endpoint recovers empties the dead-letter queue
+        transport.failures.store(0, Ordering::SeqCst);
+        dispatcher.unregister("flaky");
+        dispatcher
+            .register("missing", WebhookTarget::Custom(transport.clone()))
+            .unwrap();
+        dispatcher.register("flaky", WebhookTarget::Custom(transport)).unwrap();
+        assert_eq!(dispatcher.retry_dead_letters(), 2);
+        assert!(dispatcher.wait_idle(Duration::from_secs(5)));
+        assert!(dispatcher.dead_letters().is_empty());
+        assert_eq!(dispatcher.stats().delivered, 3);
+        assert_eq!(dispatcher.stats().pending, 0);
+    }
+
+    /// Signals each delivery it starts, then waits for a permit to finish it
+    struct Gated {
+        started: Mutex<std::sync::mpsc::Sender<RuleId>>,
+        permits: Mutex<std::sync::mpsc::Receiver<()>>,
+    }
+
+    impl WebhookTransport for Gated {
+        fn deliver(&self, payload: &WebhookPayload) -> Result<(), String> {
+            self.started.lock().unwrap().send(payload.rule_id).unwrap();
+            self.permits.lock().unwrap().recv().map_err(|_| "closed".to_string())
+        }
+    }
+
+    #[test]
+    fn test_full_queue_evicts_the_oldest_payload() {
+        let (started_sender, started) = std::sync::mpsc::channel();
+        let (permits, permit_receiver) = std::sync::mpsc::channel();
+        let dispatcher = WebhookDispatcher::new();
+        dispatcher.configure(WebhookConfig {
+            workers: 1,
+            queue_capacity: 1,
+            overflow: QueueOverflowPolicy::DropOldest,
+            ..WebhookConfig::default()
+        });
+        let transport =
+            Gated { started: Mutex::new(started_sender), permits: Mutex::new(permit_receiver) };
+        dispatcher
+            .register("gated", WebhookTarget::Custom(Arc::new(transport)))
+            .unwrap();
+
+        let numbered = |rule_id| WebhookPayload { rule_id, ..payload("gated") };
+        assert!(dispatcher.dispatch(numbered(1)));
+        assert_eq!(started.recv_timeout(Duration::from_secs(5)), Ok(1));
+        assert!(dispatcher.dispatch(numbered(2)));
+        assert!(dispatcher.dispatch(numbered(3)));
+
+        permits.send(()).unwrap();
+        assert_eq!(started.recv_timeout(Duration::from_secs(5)), Ok(3));
+        permits.send(()).unwrap();
+        assert!(dispatcher.wait_idle(Duration::from_secs(5)));
+
+        let letters = dispatcher.dead_letters();
+        assert_eq!(letters.len(), 1);
+        assert_eq!(letters[0].payload.rule_id, 2);
+        assert_eq!(letters[0].error, "Evicted from full webhook queue");
+        assert_eq!(dispatcher.stats().delivered, 2);
+    }
+}
@@ -0,0 +1,767 @@
+//! Window nodes for stream conditions in the RETE network
+//!
+//! A `WindowNode` is compiled for every `Condition::Stream` in a rule. Facts that pass
+//! the condition's filter are assigned to windows by event time, or by arrival order for
+//! count windows, and the stream aggregation is recomputed for every window a fact
+//! enters or leaves. Window contents are held in `stream_processing::WindowInstance`s so
+//! the aggregation functions are shared with the standalone `StreamProcessor`.
+//!
+//! ## Window Types
+//!
+//! - **Tumbling**: Fixed, non-overlapping time windows aligned to the epoch
+//! - **Sliding**: Overlapping time windows starting every `advance_ms`
+//! - **Session**: Windows that grow while events arrive within the gap timeout
+//! - **Calendar**: Tumbling windows aligned to local hours, days, weeks, half-months or
+//!   months in an IANA time zone, following daylight saving transitions
+//! - **Count Tumbling / Count Sliding**: The same shapes measured in facts instead of time
+//!
+//! A fact satisfies the stream condition when any window containing it satisfies the
+//! `having` clause, or simply holds data when there is none. Time windows are evicted
+//! once the watermark (the latest event time seen) passes their end by more than the
+//! allowed lateness, and facts that arrive after that are dropped as late, unless the
+//! node's `LateDataPolicy` accepts late data. Full count windows are evicted at the
+//! start of the next batch.
+
+use crate::stream_processing::{
+    AggregationFunction, LateDataPolicy, StreamProcessingStats, Timestamp, WindowInstance,
+    sliding_window_starts, tumbling_window_start,
+};
+use crate::types::{
+    CalendarPeriod, Fact, FactId, FactValue, NodeId, RuleId, StreamAggregation, StreamCondition,
+    StreamWindowSpec,
+};
+use chrono::{Datelike, Months, NaiveDate, NaiveTime, Offset, TimeZone};
+use chrono_tz::Tz;
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+/// Default lateness allowed for out-of-order events, matching the `StreamProcessor`
+pub const DEFAULT_MAX_LATENESS: Duration = Duration::from_secs(60);
+
+/// One window and its current aggregate
+///
+/// Bounds are milliseconds since epoch for time windows and arrival sequence numbers
+/// for count windows; the `WindowInstance` start and end times use the same units.
+#[derive(Debug, Clone)]
+struct Window {
+    start: u64,
+    end: u64,
+    instance: WindowInstance,
+    value: FactValue,
+}
+
+impl Window {
+    fn new(kind: &str, start: u64, end: u64) -> Self {
+        Self {
+            start,
+            end,
+            instance: WindowInstance::new(
+                format!("{kind}_{start}_{end}"),
+                Timestamp::from_millis(start),
+                Timestamp::from_millis(end),
+            ),
+            value: FactValue::Null,
+        }
+    }
+}
+
+/// Windows a fact currently belongs to
+#[derive(Debug, Clone)]
+struct Membership {
+    event_time: Timestamp,
+    windows: Vec<u64>,
+}
+
+/// RETE node assigning facts to the windows of one stream condition
+#[derive(Debug, Clone)]
+pub struct WindowNode {
+    pub id: NodeId,
+    pub condition: StreamCondition,
+    pub dependent_rules: Vec<RuleId>,
+    windows: BTreeMap<u64, Window>,
+    members: HashMap<FactId, Membership>,
+    next_sequence: u64,
+    watermark: Timestamp,
+    late_data: LateDataPolicy,
+    timezone: Tz,
+    seeded: bool,
+    stats: StreamProcessingStats,
+}
+
+impl WindowNode {
+    /// Create an empty window node for a stream condition
+    ///
+    /// Calendar windows with an unknown time zone fall back to UTC; call `validate`
+    /// first to reject them instead.
+    pub fn new(id: NodeId, condition: StreamCondition) -> Self {
+        let timezone = Self::timezone(&condition).unwrap_or(Tz::UTC);
+        Self {
+            id,
+            condition,
+            dependent_rules: Vec::new(),
+            windows: BTreeMap::new(),
+            members: HashMap::new(),
+            next_sequence: 0,
+            watermark: Timestamp::from_millis(0),
+            late_data: LateDataPolicy::Drop { allowed_lateness: DEFAULT_MAX_LATENESS },
+            timezone,
+            seeded: false,
+            stats: StreamProcessingStats::default(),
+        }
+    }
+
+    /// Check that a stream condition's window can be built
+    pub fn validate(condition: &StreamCondition) -> anyhow::Result<()> {
+        Self::timezone(condition).map(|_| ())
+    }
+
+    fn timezone(condition: &StreamCondition) -> anyhow::Result<Tz> {
+        match &condition.window_spec {
+            StreamWindowSpec::Calendar { timezone, .. } => timezone
+                .parse()
+                .map_err(|_| anyhow::anyhow!("Unknown window time zone '{timezone}'")),
+            _ => Ok(Tz::UTC),
+        }
+    }
+
+    /// Signature used to share nodes between rules with identical stream conditions
+    pub fn signature(condition: &StreamCondition) -> String {
+        format!("{condition:?}")
+    }
+
+    /// Register a rule that depends on this node
+    pub fn add_rule(&mut self, rule_id: RuleId) {
+        if !self.dependent_rules.contains(&rule_id) {
+            self.dependent_rules.push(rule_id);
+        }
+    }
+
+    /// Unregister a rule, returning whether any dependent rules remain
+    pub fn remove_rule(&mut self, rule_id: RuleId) -> bool {
+        self.dependent_rules.retain(|id| *id != rule_id);
+        !self.dependent_rules.is_empty()
+    }
+
+    /// Configure how far behind the watermark an event may arrive before it is dropped
+    pub fn set_max_lateness(&mut self, lateness: Duration) {
+        self.late_data = LateDataPolicy::Drop { allowed_lateness: lateness };
+    }
+
+    /// Choose what happens to events that arrive behind the watermark
+    pub fn set_late_data_policy(&mut self, policy: LateDataPolicy) {
+        self.late_data = policy;
+    }
+
+    /// The policy applied to events that arrive behind the watermark
+    pub fn late_data_policy(&self) -> LateDataPolicy {
+        self.late_data
+    }
+
+    /// Whether the node has been populated with the facts that existed before it was built
+    pub fn is_seeded(&self) -> bool {
+        self.seeded
+    }
+
+    /// Populate the node from existing facts that passed the condition's filter
+    pub fn seed<'a>(&mut self, facts: impl IntoIterator<Item = &'a Fact>) -> anyhow::Result<()> {
+        self.assert_batch(facts)?;
+        self.seeded = true;
+        Ok(())
+    }
+
+    /// Evict closed windows, then assign a batch of filtered facts to windows
+    pub fn assert_batch<'a>(
+        &mut self,
+        facts: impl IntoIterator<Item = &'a Fact>,
+    ) -> anyhow::Result<()> {
+        self.evict_closed_windows();
+        for fact in facts {
+            self.assert_fact(fact)?;
+        }
+        Ok(())
+    }
+
+    /// Assign a fact to its windows, returning whether it was windowed
+    ///
+    /// Re-asserting an unchanged fact is a no-op; a changed fact replaces its earlier
+    /// contribution.
+    pub fn assert_fact(&mut self, fact: &Fact) -> anyhow::Result<bool> {
+        if self.members.contains_key(&fact.id) {
+            if self.stored_fact(fact.id).is_some_and(|stored| stored.data == fact.data) {
+                return Ok(true);
+            }
+            self.retract_fact(fact.id)?;
+        }
+
+        let event_time = Timestamp::of_fact(fact);
+        if !self.is_count_window() {
+            if self.late_data.is_late(event_time, self.watermark) {
+                self.stats.late_events_dropped += 1;
+                return Ok(false);
+            }
+            if event_time > self.watermark {
+                self.watermark = event_time;
+                self.stats.watermark_updates += 1;
+            }
+        }
+
+        self.stats.events_processed += 1;
+        let sequence = self.next_sequence;
+        self.next_sequence += 1;
+
+        let time = event_time.as_millis();
+        let keys = match self.condition.window_spec.clone() {
+            StreamWindowSpec::Tumbling { duration_ms } => {
+                vec![self.place_tumbling("tumbling", time, duration_ms, fact)]
+            }
+            StreamWindowSpec::Sliding { size_ms, advance_ms } => {
+                self.place_sliding("sliding", time, size_ms, advance_ms, fact)
+            }
+            StreamWindowSpec::Session { gap_timeout_ms } => {
+                vec![self.place_in_session(fact.clone(), time, gap_timeout_ms)]
+            }
+            StreamWindowSpec::Calendar { period, .. } => {
+                let (start, end) = calendar_window(time, period, self.timezone);
+                vec![self.place("calendar", start, end, fact)]
+            }
+            StreamWindowSpec::CountTumbling { count } => {
+                vec![self.place_tumbling("count_tumbling", sequence, count as u64, fact)]
+            }
+            StreamWindowSpec::CountSliding { size, advance } => {
+                self.place_sliding("count_sliding", sequence, size as u64, advance as u64, fact)
+            }
+        };
+
+        self.members.insert(fact.id, Membership { event_time, windows: keys.clone() });
+        for key in keys {
+            self.refresh(key)?;
+        }
+        Ok(true)
+    }
+
+    /// Remove a fact from every window holding it, returning whether it was windowed
+    pub fn retract_fact(&mut self, fact_id: FactId) -> anyhow::Result<bool> {
+        let Some(membership) = self.members.remove(&fact_id) else {
+            return Ok(false);
+        };
+
+        for key in membership.windows {
+            let Some(window) = self.windows.get_mut(&key) else {
+                continue;
+            };
+            window.instance.facts.retain(|fact| fact.id != fact_id);
+            window.instance.aggregation_cache.clear();
+
+            if window.instance.facts.is_empty() {
+                self.windows.remove(&key);
+            } else if let StreamWindowSpec::Session { gap_timeout_ms } = self.condition.window_spec
+            {
+                // The retracted event may have bridged two sessions, so rebuild them
+                self.resessionize(key, gap_timeout_ms)?;
+            } else {
+                self.refresh(key)?;
+            }
+        }
+        Ok(true)
+    }
+
+    /// Whether a fact currently belongs to at least one window
+    pub fn is_windowed(&self, fact_id: FactId) -> bool {
+        self.members
+            .get(&fact_id)
+            .is_some_and(|membership| !membership.windows.is_empty())
+    }
+
+    /// Aggregates of every window containing a fact
+    pub fn values_for(&self, fact_id: FactId) -> Vec<&FactValue> {
+        self.windows_containing(fact_id).map(|window| &window.value).collect()
+    }
+
+    /// Window instances containing a fact
+    pub fn windows_for(&self, fact_id: FactId) -> Vec<&WindowInstance> {
+        self.windows_containing(fact_id).map(|window| &window.instance).collect()
+    }
+
+    /// Number of open windows
+    pub fn window_count(&self) -> usize {
+        self.windows.len()
+    }
+
+    /// Number of facts currently held in windows
+    pub fn fact_count(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Latest event time observed by time windows
+    pub fn watermark(&self) -> Timestamp {
+        self.watermark
+    }
+
+    /// Window and event statistics
+    pub fn stats(&self) -> &StreamProcessingStats {
+        &self.stats
+    }
+
+    /// Drop all windows; the node will be re-seeded on next use
+    pub fn clear(&mut self) {
+        self.windows.clear();
+        self.members.clear();
+        self.next_sequence = 0;
+        self.watermark = Timestamp::from_millis(0);
+        self.seeded = false;
+    }
+
+    fn is_count_window(&self) -> bool {
+        matches!(
+            self.condition.window_spec,
+            StreamWindowSpec::CountTumbling { .. } | StreamWindowSpec::CountSliding { .. }
+        )
+    }
+
+    fn windows_containing(&self, fact_id: FactId) -> impl Iterator<Item = &Window> {
+        self.members
+            .get(&fact_id)
+            .map(|membership| membership.windows.as_slice())
+            .unwrap_or(&[])
+            .iter()
+            .filter_map(|key| self.windows.get(key))
+    }
+
+    fn stored_fact(&self, fact_id: FactId) -> Option<&Fact> {
+        self.windows_containing(fact_id)
+            .flat_map(|window| window.instance.facts.iter())
+            .find(|fact| fact.id == fact_id)
+    }
+
+    /// Evict windows that can no longer receive facts
+    fn evict_closed_windows(&mut self) {
+        let closed: Vec<u64> = if self.is_count_window() {
+            let next_sequence = self.next_sequence;
+            self.windows
+                .values()
+                .filter(|window| window.end <= next_sequence)
+                .map(|window| window.start)
+                .collect()
+        } else {
+            self.windows
+                .values()
+                .filter(|window| {
+                    self.late_data.is_closed(Timestamp::from_millis(window.end), self.watermark)
+                })
+                .map(|window| window.start)
+                .collect()
+        };
+
+        for key in closed {
+            let Some(window) = self.windows.remove(&key) else {
+                continue;
+            };
+            self.stats.windows_completed += 1;
+            for fact in &window.instance.facts {
+                if let Some(membership) = self.members.get_mut(&fact.id) {
+                    membership.windows.retain(|window_key| *window_key != key);
+                    if membership.windows.is_empty() {
+                        self.members.remove(&fact.id);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Add a fact to the window starting at `start`, creating it if needed
+    fn place(&mut self, kind: &str, start: u64, end: u64, fact: &Fact) -> u64 {
+        let stats = &mut self.stats;
+        let window = self.windows.entry(start).or_insert_with(|| {
+            stats.windows_created += 1;
+            Window::new(kind, start, end)
+        });
+        window.instance.add_fact(fact.clone());
+        start
+    }
+
+    fn place_tumbling(&mut self, kind: &str, position: u64, size: u64, fact: &Fact) -> u64 {
+        let start = tumbling_window_start(position, size);
+        self.place(kind, start, start + size.max(1), fact)
+    }
+
+    fn place_sliding(
+        &mut self,
+        kind: &str,
+        position: u64,
+        size: u64,
+        advance: u64,
+        fact: &Fact,
+    ) -> Vec<u64> {
+        sliding_window_starts(position, size, advance)
+            .into_iter()
+            .map(|start| self.place(kind, start, start + size.max(1), fact))
+            .collect()
+    }
+
+    /// Add an event to its session, merging every session it bridges
+    fn place_in_session(&mut self, fact: Fact, time: u64, gap_timeout_ms: u64) -> u64 {
+        let touching: Vec<u64> = self
+            .windows
+            .values()
+            .filter(|window| {
+                time.saturating_add(gap_timeout_ms) >= window.start && time <= window.end
+            })
+            .map(|window| window.start)
+            .collect();
+
+        let mut start = time;
+        let mut end = time.saturating_add(gap_timeout_ms);
+        let mut facts = Vec::new();
+        for key in touching {
+            if let Some(window) = self.windows.remove(&key) {
+                start = start.min(window.start);
+                end = end.max(window.end);
+                facts.extend(window.instance.facts);
+            }
+        }
+        if facts.is_empty() {
+            self.stats.windows_created += 1;
+        }
+        facts.push(fact);
+
+        let mut session = Window::new("session", start, end);
+        for fact in facts {
+            if let Some(membership) = self.members.get_mut(&fact.id) {
+                membership.windows = vec![start];
+            }
+            session.instance.add_fact(fact);
+        }
+        self.windows.insert(start, session);
+        start
+    }
+
+    /// Rebuild the sessions formed by the facts of one session window
+    fn resessionize(&mut self, key: u64, gap_timeout_ms: u64) -> anyhow::Result<()> {
+        let Some(window) = self.windows.remove(&key) else {
+            return Ok(());
+        };
+
+        let mut facts: Vec<(Timestamp, Fact)> = window
+            .instance
+            .facts
+            .into_iter()
+            .map(|fact| {
+                let event_time = self
+                    .members
+                    .get(&fact.id)
+                    .map(|membership| membership.event_time)
+                    .unwrap_or_else(|| Timestamp::of_fact(&fact));
+                (event_time, fact)
+            })
+            .collect();
+        facts.sort_by_key(|(event_time, _)| *event_time);
+
+        let mut keys = Vec::new();
+        for (event_time, fact) in facts {
+            keys.push(self.place_in_session(fact, event_time.as_millis(), gap_timeout_ms));
+        }
+        keys.dedup();
+        for key in keys {
+            self.refresh(key)?;
+        }
+        Ok(())
+    }
+
+    /// Recompute the aggregate of one window
+    fn refresh(&mut self, key: u64) -> anyhow::Result<()> {
+        let is_count_window = self.is_count_window();
+        let Some(window) = self.windows.get_mut(&key) else {
+            return Ok(());
+        };
+
+        window.value = Self::compute(window, &self.condition.aggregation, is_count_window)?;
+        self.stats.aggregations_computed += 1;
+        Ok(())
+    }
+
+    fn compute(
+        window: &mut Window,
+        aggregation: &StreamAggregation,
+        is_count_window: bool,
+    ) -> anyhow::Result<FactValue> {
+        let facts = &window.instance.facts;
+        let value = match aggregation {
+            StreamAggregation::First { field } => facts
+                .iter()
+                .min_by_key(|fact| Timestamp::of_fact(fact))
+                .and_then(|fact| fact.data.fields.get(field))
+                .cloned()
+                .unwrap_or(FactValue::Null),
+            StreamAggregation::Last { field } => facts
+                .iter()
+                .max_by_key(|fact| Timestamp::of_fact(fact))
+                .and_then(|fact| fact.data.fields.get(field))
+                .cloned()
+                .unwrap_or(FactValue::Null),
+            StreamAggregation::Rate { time_unit_ms } => {
+                let span_ms = if is_count_window {
+                    let times = facts.iter().map(|fact| Timestamp::of_fact(fact).as_millis());
+                    let first = times.clone().min().unwrap_or(0);
+                    times.max().unwrap_or(0) - first
+                } else {
+                    window.end - window.start
+                };
+                let units = span_ms.max(1) as f64 / (*time_unit_ms).max(1) as f64;
+                FactValue::Float(facts.len() as f64 / units)
+            }
+            other => match AggregationFunction::from_stream(other) {
+                Some(function) => window.instance.compute_aggregation(&function)?,
+                None => FactValue::Null,
+            },
+        };
+        Ok(value)
+    }
+}
+
+/// Bounds in epoch milliseconds of the calendar window containing `time`
+fn calendar_window(time: u64, period: CalendarPeriod, timezone: Tz) -> (u64, u64) {
+    const HOUR_MS: i64 = 3_600_000;
+    let time = time as i64;
+    let local = timezone.timestamp_millis_opt(time).unwrap();
+
+    if period == CalendarPeriod::Hourly {
+        // Offsets can be fractional hours, so align the local clock rather than UTC
+        let offset = local.offset().fix().local_minus_utc() as i64 * 1000;
+        let start = (time + offset).div_euclid(HOUR_MS) * HOUR_MS - offset;
+        return (start.max(0) as u64, (start + HOUR_MS).max(0) as u64);
+    }
+
+    let date = local.date_naive();
+    let (start, end) = match period {
+        CalendarPeriod::Hourly | CalendarPeriod::Daily => (date, date + chrono::Days::new(1)),
+        CalendarPeriod::Weekly { week_start } => {
+            let days =
+                (date.weekday().num_days_from_monday() + 7 - week_start.num_days_from_monday()) % 7;
+            let start = date - chrono::Days::new(days as u64);
+            (start, start + chrono::Days::new(7))
+        }
+        CalendarPeriod::SemiMonthly => {
+            let first = date.with_day(1).unwrap();
+            if date.day() <= 15 {
+                (first, first.with_day(16).unwrap())
+            } else {
+                (first.with_day(16).unwrap(), first + Months::new(1))
+            }
+        }
+        CalendarPeriod::Monthly => {
+            let first = date.with_day(1).unwrap();
+            (first, first + Months::new(1))
+        }
+    };
+
+    (
+        local_midnight(start, timezone),
+        local_midnight(end, timezone),
+    )
+}
+
+/// First instant of a local date, in epoch milliseconds
+fn local_midnight(date: NaiveDate, timezone: Tz) -> u64 {
+    // Midnight can fall in a daylight saving gap, so use the first valid time after it
+    let mut time = date.and_time(NaiveTime::MIN);
+    loop {
+        if let Some(start) = timezone.from_local_datetime(&time).earliest() {
+            return start.timestamp_millis().max(0) as u64;
+        }
+        time += chrono::TimeDelta::minutes(30);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::FactData;
+
+    fn condition(window_spec: StreamWindowSpec, aggregation: StreamAggregation) -> StreamCondition {
+        StreamCondition {
+            window_spec,
+            aggregation,
+            filter: None,
+            having: None,
+            alias: "result".to_string(),
+        }
+    }
+
+    fn event(id: FactId, timestamp: i64, amount: i64) -> Fact {
+        let mut fields = HashMap::new();
+        fields.insert("timestamp".to_string(), FactValue::Integer(timestamp));
+        fields.insert("amount".to_string(), FactValue::Integer(amount));
+        Fact::new(id, FactData { fields })
+    }
+
+    fn sum() -> StreamAggregation {
+        StreamAggregation::Sum { field: "amount".to_string() }
+    }
+
+    #[test]
+    fn test_tumbling_windows_partition_by_event_time() {
+        let mut node = WindowNode::new(
+            1,
+            condition(StreamWindowSpec::Tumbling { duration_ms: 1000 }, sum()),
+        );
+        node.assert_fact(&event(1, 100, 5)).unwrap();
+        node.assert_fact(&event(2, 900, 7)).unwrap();
+        node.assert_fact(&event(3, 1100, 1)).unwrap();
+
+        assert_eq!(node.window_count(), 2);
+        assert_eq!(node.values_for(1), vec![&FactValue::Integer(12)]);
+        assert_eq!(node.values_for(3), vec![&FactValue::Integer(1)]);
+    }
+
+    #[test]
+    fn test_sliding_windows_overlap() {
+        let spec = StreamWindowSpec::Sliding { size_ms: 1000, advance_ms: 500 };
+        let mut node = WindowNode::new(1, condition(spec, StreamAggregation::Count));
+        node.assert_fact(&event(1, 400, 1)).unwrap();
+        node.assert_fact(&event(2, 700, 1)).unwrap();
+
+        // Event 2 is in the windows starting at 0 and 500; event 1 only in the first
+        assert_eq!(node.windows_for(2).len(), 2);
+        assert_eq!(node.values_for(1), vec![&FactValue::Integer(2)]);
+    }
+
+    #[test]
+    fn test_sessions_merge_and_split_on_retraction() {
+        let spec = StreamWindowSpec::Session { gap_timeout_ms: 100 };
+        let mut node = WindowNode::new(1, condition(spec, StreamAggregation::Count));
+        node.assert_fact(&event(1, 0, 1)).unwrap();
+        node.assert_fact(&event(2, 180, 1)).unwrap();
+        assert_eq!(node.window_count(), 2);
+
+        // Event 3 bridges both sessions
+        node.assert_fact(&event(3, 90, 1)).unwrap();
+        assert_eq!(node.window_count(), 1);
+        assert_eq!(node.values_for(2), vec![&FactValue::Integer(3)]);
+
+        node.retract_fact(3).unwrap();
+        assert_eq!(node.window_count(), 2);
+        assert_eq!(node.values_for(1), vec![&FactValue::Integer(1)]);
+    }
+
+    #[test]
+    fn test_count_windows_evict_when_full() {
+        let spec = StreamWindowSpec::CountTumbling { count: 2 };
+        let mut node = WindowNode::new(1, condition(spec, sum()));
+        node.assert_batch(&[event(1, 0, 1), event(2, 0, 2), event(3, 0, 3)]).unwrap();
+        assert_eq!(node.values_for(2), vec![&FactValue::Integer(3)]);
+
+        node.assert_batch(&[event(4, 0, 4)]).unwrap();
+        assert!(!node.is_windowed(1));
+        assert_eq!(node.values_for(4), vec![&FactValue::Integer(7)]);
+        assert_eq!(node.stats().windows_completed, 1);
+    }
+
+    #[test]
+    fn test_late_events_are_dropped() {
+        let spec = StreamWindowSpec::Tumbling { duration_ms: 1000 };
+        let mut node = WindowNode::new(1, condition(spec, StreamAggregation::Count));
+        node.set_max_lateness(Duration::from_millis(500));
+        node.assert_fact(&event(1, 10_000, 1)).unwrap();
+
+        assert!(!node.assert_fact(&event(2, 1_000, 1)).unwrap());
+        assert_eq!(node.stats().late_events_dropped, 1);
+    }
+
+    fn millis(year: i32, month: u32, day: u32, hour: u32) -> u64 {
+        chrono::Utc
+            .with_ymd_and_hms(year, month, day, hour, 0, 0)
+            .unwrap()
+            .timestamp_millis() as u64
+    }
+
+    #[test]
+    fn test_calendar_windows_follow_local_days() {
+        let spec = StreamWindowSpec::Calendar {
+            period: CalendarPeriod::Daily,
+            timezone: "America/Los_Angeles".to_string(),
+        };
+        let mut node = WindowNode::new(1, condition(spec, sum()));
+
+        // 05:00 UTC on Jan 1 is still Dec 31 in Los Angeles
+        node.assert_fact(&event(1, millis(2024, 1, 1, 5) as i64, 1)).unwrap();
+        node.assert_fact(&event(2, millis(2024, 1, 1, 9) as i64, 2)).unwrap();
+        assert_eq!(node.window_count(), 2);
+        let window = node.windows_for(1)[0];
+        assert_eq!(window.start_time.as_millis(), millis(2023, 12, 31, 8));
+
+        // The spring-forward day is 23 hours long
+        let (start, end) = calendar_window(
+            millis(2024, 3, 10, 12),
+            CalendarPeriod::Daily,
+            "America/Los_Angeles".parse().unwrap(),
+        );
+        assert_eq!(
+            (start, end),
+            (millis(2024, 3, 10, 8), millis(2024, 3, 11, 7))
+        );
+    }
+
+    #[test]
+    fn test_calendar_weeks_and_pay_periods() {
+        // Wednesday Jun 5 2024
+        let time = millis(2024, 6, 5, 12);
+        let week = CalendarPeriod::Weekly { week_start: chrono::Weekday::Mon };
+        assert_eq!(
+            calendar_window(time, week, Tz::UTC),
+            (millis(2024, 6, 3, 0), millis(2024, 6, 10, 0))
+        );
+        let week = CalendarPeriod::Weekly { week_start: chrono::Weekday::Sun };
+        assert_eq!(
+            calendar_window(time, week, Tz::UTC).0,
+            millis(2024, 6, 2, 0)
+        );
+
+        let late_month = millis(2024, 12, 20, 0);
+        assert_eq!(
+            calendar_window(late_month, CalendarPeriod::SemiMonthly, Tz::UTC),
+            (millis(2024, 12, 16, 0), millis(2025, 1, 1, 0))
+        );
+        assert_eq!(
+            calendar_window(late_month, CalendarPeriod::Monthly, Tz::UTC),
+            (millis(2024, 12, 1, 0), millis(2025, 1, 1, 0))
+        );
+
+        // Half-hour offsets align hours to the local clock
+        let kolkata: Tz = "Asia/Kolkata".parse().unwrap();
+        let (start, end) = calendar_window(millis(2024, 6, 5, 12), CalendarPeriod::Hourly, kolkata);
+        assert_eq!(end - start, 3_600_000);
+        assert_eq!(start, millis(2024, 6, 5, 11) + 1_800_000);
+    }
+
+    #[test]
+    fn test_unknown_timezone_is_rejected() {
+        let spec = StreamWindowSpec::Calendar {
+            period: CalendarPeriod::Daily,
+            timezone: "Mars/Olympus_Mons".to_string(),
+        };
+        assert!(WindowNode::validate(&condition(spec, sum())).is_err());
+    }
+
+    #[test]
+    fn test_first_last_and_rate() {
+        let spec = StreamWindowSpec::Tumbling { duration_ms: 2000 };
+        let mut first = WindowNode::new(
+            1,
+            condition(
+                spec.clone(),
+                StreamAggregation::First { field: "amount".to_string() },
+            ),
+        );
+        let mut rate = WindowNode::new(
+            2,
+            condition(spec, StreamAggregation::Rate { time_unit_ms: 1000 }),
+        );
+        for fact in [event(1, 500, 9), event(2, 100, 4)] {
+            first.assert_fact(&fact).unwrap();
+            rate.assert_fact(&fact).unwrap();
+        }
+
+        assert_eq!(first.values_for(1), vec![&FactValue::Integer(4)]);
+        assert_eq!(rate.values_for(1), vec![&FactValue::Float(1.0)]);
+    }
+}
@@ -0,0 +1,219 @@
+//! Sampling profiler for hot facts and fields
+//!
+//! Choosing which fields to index and how to partition facts is guesswork without
+//! knowing where the network spends its evaluations. While enabled, the profiler samples
+//! one in every `sample_rate` evaluations of a rule's conditions against a fact and
+//! counts, per sample:
+//!
+//! - **Hot facts**: The fact being evaluated, so facts re-evaluated again and again
+//!   against the ruleset stand out
+//! - **Hot fields**: The fields the rule's conditions read, so frequently read fields
+//!   that the fact store does not index stand out
+//!
+//! Sampling keeps the overhead off the evaluation path; estimates are the sample counts
+//! scaled by the sample rate.
+
+use crate::fact_store::arena_store::ArenaFactStore;
+use crate::field_references::condition_fields;
+use crate::types::{FactId, Rule};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// How the working memory profiler samples evaluations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorkingMemoryProfilerConfig {
+    /// Sample one in this many condition evaluations
+    pub sample_rate: u64,
+    /// Distinct facts counted before samples of further facts are only tallied
+    pub max_tracked_facts: usize,
+    /// Hot facts and fields listed in a profile
+    pub report_limit: usize,
+}
+
+impl Default for WorkingMemoryProfilerConfig {
+    fn default() -> Self {
+        Self { sample_rate: 100, max_tracked_facts: 10_000, report_limit: 20 }
+    }
+}
+
+/// A fact frequently evaluated against the ruleset
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HotFact {
+    pub fact_id: FactId,
+    /// External ID of the fact, if it is still in working memory and has one
+    pub external_id: Option<String>,
+    pub samples: u64,
+    /// Samples scaled by the sample rate
+    pub estimated_evaluations: u64,
+}
+
+/// A fact field frequently read by rule conditions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HotField {
+    pub field: String,
+    pub samples: u64,
+    /// Samples scaled by the sample rate
+    pub estimated_reads: u64,
+    /// Whether the fact store indexes the field
+    pub indexed: bool,
+}
+
+/// Hottest facts and fields observed by the working memory profiler
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WorkingMemoryProfile {
+    pub sample_rate: u64,
+    /// Condition evaluations seen since profiling started
+    pub evaluations: u64,
+    pub samples: u64,
+    /// Samples of facts beyond `max_tracked_facts`, not attributed to any fact
+    pub untracked_samples: u64,
+    /// Most frequently evaluated facts, hottest first
+    pub hot_facts: Vec<HotFact>,
+    /// Most frequently read fields, hottest first
+    pub hot_fields: Vec<HotField>,
+}
+
+impl WorkingMemoryProfile {
+    /// Hot fields the fact store does not index, hottest first
+    pub fn unindexed_fields(&self) -> impl Iterator<Item = &HotField> {
+        self.hot_fields.iter().filter(|field| !field.indexed)
+    }
+}
+
+/// Sample counts kept by the network while profiling is enabled
+#[derive(Debug, Clone)]
+pub(crate) struct WorkingMemoryProfiler {
+    config: WorkingMemoryProfilerConfig,
+    evaluations: u64,
+    samples: u64,
+    untracked_samples: u64,
+    facts: HashMap<FactId, u64>,
+    fields: HashMap<String, u64>,
+}
+
+impl WorkingMemoryProfiler {
+    pub fn new(config: WorkingMemoryProfilerConfig) -> Self {
+        Self {
+            config: WorkingMemoryProfilerConfig {
+                sample_rate: config.sample_rate.max(1),
+                ..config
+            },
+            evaluations: 0,
+            samples: 0,
+            untracked_samples: 0,
+            facts: HashMap::new(),
+            fields: HashMap::new(),
+        }
+    }
+
+    pub fn config(&self) -> WorkingMemoryProfilerConfig {
+        self.config
+    }
+
+    /// Count an evaluation of `rule`'s conditions against a fact, sampling one in every
+    /// `sample_rate`
+    pub fn record_evaluation(&mut self, fact_id: FactId, rule: Option<&Rule>) {
+        self.evaluations += 1;
+        if self.evaluations % self.config.sample_rate != 0 {
+            return;
+        }
+        self.samples += 1;
+
+        let tracked = self.facts.len() < self.config.max_tracked_facts;
+        match self.facts.get_mut(&fact_id) {
+            Some(samples) => *samples += 1,
+            None if tracked => {
+                self.facts.insert(fact_id, 1);
+            }
+            None => self.untracked_samples += 1,
+        }
+
+        let fields: HashSet<String> = rule
+            .into_iter()
+            .flat_map(|rule| &rule.conditions)
+            .flat_map(condition_fields)
+            .collect();
+        for field in fields {
+            *self.fields.entry(field).or_default() += 1;
+        }
+    }
+
+    /// The hottest facts and fields, looking up external IDs and indexes in `fact_store`
+    pub fn profile(&self, fact_store: &ArenaFactStore) -> WorkingMemoryProfile {
+        let sample_rate = self.config.sample_rate;
+        let limit = self.config.report_limit;
+
+        let mut facts: Vec<(FactId, u64)> =
+            self.facts.iter().map(|(&fact_id, &samples)| (fact_id, samples)).collect();
+        facts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        let hot_facts = facts
+            .into_iter()
+            .take(limit)
+            .map(|(fact_id, samples)| HotFact {
+                fact_id,
+                external_id: fact_store.get_fact(fact_id).and_then(|fact| fact.external_id),
+                samples,
+                estimated_evaluations: samples * sample_rate,
+            })
+            .collect();
+
+        let indexed: HashSet<String> =
+            fact_store.index_usage().into_iter().map(|usage| usage.field).collect();
+        let mut fields: Vec<(&String, u64)> =
+            self.fields.iter().map(|(field, &samples)| (field, samples)).collect();
+        fields.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        let hot_fields = fields
+            .into_iter()
+            .take(limit)
+            .map(|(field, samples)| HotField {
+                field: field.clone(),
+                samples,
+                estimated_reads: samples * sample_rate,
+                indexed: indexed.contains(field),
+            })
+            .collect();
+
+        WorkingMemoryProfile {
+            sample_rate,
+            evaluations: self.evaluations,
+            samples: self.samples,
+            untracked_samples: self.untracked_samples,
+            hot_facts,
+            hot_fields,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Condition, FactValue, Operator};
+
+    #[test]
+    fn test_samples_one_in_sample_rate_evaluations() {
+        let config =
+            WorkingMemoryProfilerConfig { sample_rate: 2, max_tracked_facts: 1, report_limit: 5 };
+        let mut profiler = WorkingMemoryProfiler::new(config);
+        let rule = Rule {
+            id: 1,
+            name: "Rule".to_string(),
+            conditions: vec![Condition::Simple {
+                field: "amount".to_string(),
+                operator: Operator::GreaterThan,
+                value: FactValue::Integer(100),
+            }],
+            actions: vec![],
+            metadata: Default::default(),
+        };
+        for fact_id in [7, 7, 7, 7, 8, 8] {
+            profiler.record_evaluation(fact_id, Some(&rule));
+        }
+
+        let profile = profiler.profile(&ArenaFactStore::new());
+        assert_eq!((profile.evaluations, profile.samples), (6, 3));
+        assert_eq!(profile.untracked_samples, 1);
+        assert_eq!(profile.hot_facts[0].estimated_evaluations, 4);
+        assert_eq!(profile.hot_fields[0].field, "amount");
+        assert_eq!(profile.unindexed_fields().count(), 1);
+    }
+}
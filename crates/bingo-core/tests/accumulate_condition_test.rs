@@ -0,0 +1,272 @@
+//! Accumulate Condition Integration Test
+//!
+//! Validates that accumulate conditions bind the value collected from related facts to
+//! the fact a rule is evaluated for, so later conditions and actions can use it.
+
+use bingo_calculator::calculator::Calculator;
+use bingo_calculator::plugin::{CalculationResult, CalculatorPlugin};
+use bingo_core::BingoEngine;
+use bingo_core::fact_store::arena_store::ArenaFactStore;
+use bingo_core::rete_network::ReteNetwork;
+use bingo_core::rete_nodes::{ActionResult, RuleExecutionResult};
+use bingo_core::types::*;
+use std::collections::HashMap;
+
+fn fact(id: u64, fact_type: &str, order_id: i64, fields: &[(&str, FactValue)]) -> Fact {
+    let mut data = HashMap::new();
+    data.insert("type".to_string(), FactValue::String(fact_type.to_string()));
+    data.insert("order_id".to_string(), FactValue::Integer(order_id));
+    for (field, value) in fields {
+        data.insert(field.to_string(), value.clone());
+    }
+    Fact::new(id, FactData { fields: data })
+}
+
+fn line_item(id: u64, order_id: i64, amount: i64) -> Fact {
+    fact(
+        id,
+        "line_item",
+        order_id,
+        &[("amount", FactValue::Integer(amount))],
+    )
+}
+
+fn order(id: u64, order_id: i64, limit: i64) -> Fact {
+    fact(
+        id,
+        "order",
+        order_id,
+        &[("limit", FactValue::Integer(limit))],
+    )
+}
+
+fn is_type(fact_type: &str) -> Condition {
+    Condition::Simple {
+        field: "type".to_string(),
+        operator: Operator::Equal,
+        value: FactValue::String(fact_type.to_string()),
+    }
+}
+
+fn line_items(accumulator: Accumulator, alias: &str) -> Condition {
+    Condition::Accumulate(AccumulateCondition {
+        source: Box::new(is_type("line_item")),
+        correlate_by: vec!["order_id".to_string()],
+        accumulator,
+        alias: alias.to_string(),
+    })
+}
+
+fn rule(conditions: Vec<Condition>, actions: Vec<ActionType>) -> Rule {
+    Rule {
+        id: 1,
+        name: "Order over its limit".to_string(),
+        conditions,
+        actions: actions.into_iter().map(|action_type| Action { action_type }).collect(),
+        metadata: Default::default(),
+    }
+}
+
+/// Orders whose line items add up to more than the order's limit
+fn over_limit_rule() -> Rule {
+    rule(
+        vec![
+            line_items(
+                Accumulator::Aggregate {
+                    aggregation_type: AggregationType::Sum,
+                    field: "amount".to_string(),
+                },
+                "order_total",
+            ),
+            is_type("order"),
+            Condition::Simple {
+                field: "order_total".to_string(),
+                operator: Operator::GreaterThan,
+                value: FactValue::Integer(100),
+            },
+        ],
+        vec![ActionType::Formula {
+            expression: "order_total - limit".to_string(),
+            output_field: "excess".to_string(),
+        }],
+    )
+}
+
+fn process(
+    network: &mut ReteNetwork,
+    calculator: &Calculator,
+    facts: &[Fact],
+) -> Vec<RuleExecutionResult> {
+    let fact_store = ArenaFactStore::new();
+    for fact in facts {
+        fact_store.insert(fact.clone());
+    }
+    network.process_facts(facts, &fact_store, calculator).unwrap()
+}
+
+fn field_set(result: &RuleExecutionResult, name: &str) -> Option<FactValue> {
+    result.actions_executed.iter().find_map(|action| match action {
+        ActionResult::FieldSet { field, value, .. } if field == name => Some(value.clone()),
+        _ => None,
+    })
+}
+
+#[test]
+fn test_sum_of_line_items_per_order_exceeding_a_limit() {
+    let mut network = ReteNetwork::new();
+    network.add_rule(over_limit_rule()).unwrap();
+
+    let results = process(
+        &mut network,
+        &Calculator::new(),
+        &[
+            line_item(1, 7, 60),
+            line_item(2, 7, 70),
+            line_item(3, 8, 30),
+            order(10, 7, 100),
+            order(11, 8, 100),
+        ],
+    );
+
+    assert_eq!(results.len(), 1, "only order 7's items exceed 100");
+    assert_eq!(results[0].fact_id, 10);
+    assert_eq!(
+        field_set(&results[0], "excess"),
+        Some(FactValue::Float(30.0))
+    );
+}
+
+#[test]
+fn test_collected_lists_are_bound_in_fact_order() {
+    let mut network = ReteNetwork::new();
+    network
+        .add_rule(rule(
+            vec![
+                line_items(
+                    Accumulator::CollectField { field: "amount".to_string() },
+                    "amounts",
+                ),
+                is_type("order"),
+            ],
+            vec![ActionType::Formula {
+                expression: "amounts".to_string(),
+                output_field: "amounts_copy".to_string(),
+            }],
+        ))
+        .unwrap();
+
+    let results = process(
+        &mut network,
+        &Calculator::new(),
+        &[line_item(2, 7, 70), line_item(1, 7, 60), order(10, 7, 100)],
+    );
+
+    assert_eq!(results.len(), 1);
+    assert_eq!(
+        field_set(&results[0], "amounts_copy"),
+        Some(FactValue::Array(vec![
+            FactValue::Integer(60),
+            FactValue::Integer(70)
+        ]))
+    );
+}
+
+/// Number of distinct values in the `values` array
+struct DistinctCount;
+
+impl CalculatorPlugin for DistinctCount {
+    fn name(&self) -> &str {
+        "distinct_count"
+    }
+
+    fn calculate(&self, args: &HashMap<String, &FactValue>) -> CalculationResult {
+        let Some(FactValue::Array(values)) = args.get("values") else {
+            return Err("values must be an array".to_string());
+        };
+        let mut distinct: Vec<&FactValue> = Vec::new();
+        for value in values {
+            if !distinct.contains(&value) {
+                distinct.push(value);
+            }
+        }
+        Ok(FactValue::Integer(distinct.len() as i64))
+    }
+}
+
+#[test]
+fn test_custom_calculator_accumulators_receive_the_collected_values() {
+    let mut calculator = Calculator::new();
+    calculator.register(Box::new(DistinctCount));
+    let distinct_skus = |calculator: &str| {
+        rule(
+            vec![
+                line_items(
+                    Accumulator::Calculator {
+                        calculator: calculator.to_string(),
+                        field: "sku".to_string(),
+                    },
+                    "distinct_skus",
+                ),
+                is_type("order"),
+                Condition::Simple {
+                    field: "distinct_skus".to_string(),
+                    operator: Operator::GreaterThanOrEqual,
+                    value: FactValue::Integer(2),
+                },
+            ],
+            vec![ActionType::SetField {
+                field: "mixed_basket".to_string(),
+                value: FactValue::Boolean(true),
+            }],
+        )
+    };
+    let sku = |id: u64, order_id: i64, sku: &str| {
+        fact(
+            id,
+            "line_item",
+            order_id,
+            &[("sku", FactValue::String(sku.to_string()))],
+        )
+    };
+    let facts = [
+        sku(1, 7, "apple"),
+        sku(2, 7, "pear"),
+        sku(3, 8, "apple"),
+        sku(4, 8, "apple"),
+        order(10, 7, 0),
+        order(11, 8, 0),
+    ];
+
+    let mut network = ReteNetwork::new();
+    network.add_rule(distinct_skus("distinct_count")).unwrap();
+    let fired: Vec<FactId> = process(&mut network, &calculator, &facts)
+        .iter()
+        .map(|result| result.fact_id)
+        .collect();
+    assert_eq!(fired, vec![10]);
+
+    // A calculator that fails leaves the value unbound, so the rule does not fire
+    let mut network = ReteNetwork::new();
+    network.add_rule(distinct_skus("not_registered")).unwrap();
+    assert!(process(&mut network, &calculator, &facts).is_empty());
+}
+
+#[test]
+fn test_accumulate_conditions_round_trip_through_the_engine() {
+    let json = serde_json::to_value(&over_limit_rule().conditions[0]).unwrap();
+    let condition: Condition = serde_json::from_value(json).unwrap();
+    assert_eq!(condition, over_limit_rule().conditions[0]);
+
+    let engine = BingoEngine::new().unwrap();
+    engine.add_rule(over_limit_rule()).unwrap();
+    let results = engine
+        .process_facts(vec![
+            line_item(1, 7, 80),
+            line_item(2, 7, 40),
+            order(10, 7, 100),
+        ])
+        .unwrap();
+
+    let fired: Vec<FactId> = results.iter().map(|result| result.fact_id).collect();
+    assert_eq!(fired, vec![10]);
+}
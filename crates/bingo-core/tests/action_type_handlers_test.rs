@@ -34,6 +34,7 @@ fn create_user_fact(id: u64, name: &str, balance: f64, score: i64) -> Fact {
         id,
         external_id: Some(format!("user-{id}")),
         timestamp: Utc::now(),
+        fact_type: None,
         data: FactData { fields },
     }
 }
@@ -67,6 +68,7 @@ fn test_update_fact_action() {
                 },
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).unwrap();
@@ -130,6 +132,7 @@ fn test_delete_fact_action() {
                 fact_id_field: "user_id".to_string(), // Use Bob's user_id field (2) to target fact ID 2
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).unwrap();
@@ -188,6 +191,7 @@ fn test_increment_field_action() {
                 increment: FactValue::Integer(10),
             },
         }],
+        metadata: Default::default(),
     };
 
     // Test float increment
@@ -205,6 +209,7 @@ fn test_increment_field_action() {
                 increment: FactValue::Float(500.5),
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule1).unwrap();
@@ -306,6 +311,7 @@ fn test_append_to_array_action() {
                 value: FactValue::String("premium".to_string()),
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).unwrap();
@@ -392,6 +398,7 @@ fn test_send_notification_action() {
                 },
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).unwrap();
@@ -466,6 +473,7 @@ fn test_error_handling_for_action_types() {
                 },
             },
         }],
+        metadata: Default::default(),
     };
 
     // Test AppendToArray on non-array field
@@ -483,6 +491,7 @@ fn test_error_handling_for_action_types() {
                 value: FactValue::String("invalid".to_string()),
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule1).unwrap();
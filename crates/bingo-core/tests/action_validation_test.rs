@@ -0,0 +1,161 @@
+//! Action Validation Test
+//!
+//! Validates that adding a rule checks its actions against the registered calculators,
+//! the registered field types and the formula grammar, and that the reject policy
+//! refuses the rule with every problem in one report.
+
+use bingo_core::types::FieldType;
+use bingo_core::*;
+use std::collections::HashMap;
+
+fn payout_rule(id: u64, actions: Vec<ActionType>) -> Rule {
+    Rule {
+        id,
+        name: format!("Payout {id}"),
+        conditions: vec![Condition::Simple {
+            field: "hours".to_string(),
+            operator: Operator::GreaterThan,
+            value: FactValue::Integer(0),
+        }],
+        actions: actions.into_iter().map(|action_type| Action { action_type }).collect(),
+        metadata: Default::default(),
+    }
+}
+
+fn invalid_actions() -> Vec<ActionType> {
+    vec![
+        ActionType::CallCalculator {
+            calculator_name: "shift_premium".to_string(),
+            input_mapping: HashMap::from([("hours".to_string(), "hours".to_string())]),
+            output_field: "overtime_pay".to_string(),
+        },
+        ActionType::SetField {
+            field: "status".to_string(),
+            value: FactValue::String("done".to_string()),
+        },
+        ActionType::Formula { expression: "hours *".to_string(), output_field: "pay".to_string() },
+    ]
+}
+
+fn engine_with_schema(policy: ActionValidationPolicy) -> BingoEngine {
+    let engine = BingoEngine::new().unwrap();
+    engine.set_action_validation_policy(policy);
+    engine.register_field_type(
+        "status",
+        FieldType::Enum { values: vec!["pending".to_string(), "paid".to_string()] },
+    );
+    engine.register_field_type("hours", FieldType::Integer { min: 0, max: 80 });
+    engine
+}
+
+#[test]
+fn test_reject_policy_returns_every_problem() {
+    let engine = engine_with_schema(ActionValidationPolicy::Reject);
+
+    let error = engine.add_rule(payout_rule(1, invalid_actions())).unwrap_err();
+    assert_eq!(error.category(), "rule");
+    assert_eq!(error.context().rule_id, Some(1));
+    let BingoError::ActionValidation { report, .. } = error else {
+        panic!("unexpected error: {error:?}");
+    };
+    assert_eq!(
+        report.problems,
+        vec![
+            ActionProblem::UnknownCalculator {
+                action_index: 0,
+                calculator_name: "shift_premium".to_string(),
+            },
+            ActionProblem::FieldTypeConflict {
+                action_index: 1,
+                field: "status".to_string(),
+                expected: "one of pending, paid".to_string(),
+                actual: "string done".to_string(),
+            },
+            ActionProblem::InvalidExpression {
+                action_index: 2,
+                expression: "hours *".to_string(),
+                reason: "'hours *' is not a field, a literal or a binary expression".to_string(),
+            },
+        ]
+    );
+    assert_eq!(engine.rule_count(), 0);
+
+    // Updates are checked the same way and leave the old definition in place
+    engine
+        .add_rule(payout_rule(
+            2,
+            vec![ActionType::IncrementField {
+                field: "hours".to_string(),
+                increment: FactValue::Integer(1),
+            }],
+        ))
+        .unwrap();
+    let bad_increment = payout_rule(
+        2,
+        vec![ActionType::IncrementField {
+            field: "hours".to_string(),
+            increment: FactValue::Float(0.5),
+        }],
+    );
+    assert!(engine.update_rule(bad_increment).is_err());
+    assert_eq!(engine.rule_count(), 1);
+}
+
+#[test]
+fn test_warn_policy_adds_the_rule_and_reports_on_request() {
+    let engine = engine_with_schema(ActionValidationPolicy::default());
+    assert_eq!(
+        engine.action_validation_policy(),
+        ActionValidationPolicy::Warn
+    );
+
+    let rule = payout_rule(1, invalid_actions());
+    engine.add_rule(rule.clone()).unwrap();
+    assert_eq!(engine.rule_count(), 1);
+    assert_eq!(engine.validate_rule_actions(&rule).problems.len(), 3);
+
+    let valid = payout_rule(
+        2,
+        vec![
+            ActionType::CallCalculator {
+                calculator_name: "multiply".to_string(),
+                input_mapping: HashMap::new(),
+                output_field: "pay".to_string(),
+            },
+            ActionType::SetField {
+                field: "status".to_string(),
+                value: FactValue::String("paid".to_string()),
+            },
+            ActionType::Formula {
+                expression: "hours * 1.5".to_string(),
+                output_field: "pay".to_string(),
+            },
+        ],
+    );
+    assert!(engine.validate_rule_actions(&valid).is_valid());
+}
+
+#[test]
+fn test_field_types_survive_rule_changes_and_templates() {
+    let engine = engine_with_schema(ActionValidationPolicy::Reject);
+    engine
+        .add_rule(payout_rule(
+            1,
+            vec![ActionType::SetField { field: "hours".to_string(), value: FactValue::Integer(8) }],
+        ))
+        .unwrap();
+    engine.remove_rule(1).unwrap();
+
+    let copy = BingoEngine::from_template(&engine).unwrap();
+    for engine in [&engine, &copy] {
+        assert_eq!(engine.field_schema().len(), 2);
+        let too_many_hours = payout_rule(
+            3,
+            vec![ActionType::SetField {
+                field: "hours".to_string(),
+                value: FactValue::Integer(100),
+            }],
+        );
+        assert!(engine.add_rule(too_many_hours).is_err());
+    }
+}
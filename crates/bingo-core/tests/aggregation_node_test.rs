@@ -0,0 +1,152 @@
+//! Aggregation Node Integration Test
+//!
+//! Validates that aggregation conditions compile to native aggregation nodes whose
+//! running aggregates follow fact assertion and retraction.
+
+use bingo_calculator::calculator::Calculator;
+use bingo_core::LateDataPolicy;
+use bingo_core::fact_store::arena_store::ArenaFactStore;
+use bingo_core::rete_network::ReteNetwork;
+use bingo_core::types::*;
+use std::collections::HashMap;
+
+fn create_shift(id: u64, employee_id: i64, hours: f64) -> Fact {
+    let mut fields = HashMap::new();
+    fields.insert("employee_id".to_string(), FactValue::Integer(employee_id));
+    fields.insert("hours".to_string(), FactValue::Float(hours));
+    Fact::new(id, FactData { fields })
+}
+
+fn overtime_condition() -> AggregationCondition {
+    AggregationCondition {
+        aggregation_type: AggregationType::Sum,
+        source_field: "hours".to_string(),
+        group_by: vec!["employee_id".to_string()],
+        having: Some(Box::new(Condition::Simple {
+            field: "total_hours".to_string(),
+            operator: Operator::GreaterThan,
+            value: FactValue::Float(40.0),
+        })),
+        alias: "total_hours".to_string(),
+        window: None,
+    }
+}
+
+fn overtime_rule() -> Rule {
+    Rule {
+        id: 1,
+        name: "Weekly overtime".to_string(),
+        conditions: vec![Condition::Aggregation(overtime_condition())],
+        actions: vec![Action {
+            action_type: ActionType::SetField {
+                field: "overtime".to_string(),
+                value: FactValue::Boolean(true),
+            },
+        }],
+        metadata: Default::default(),
+    }
+}
+
+#[test]
+fn test_aggregation_condition_compiles_to_node() {
+    let mut network = ReteNetwork::new();
+    network.add_rule(overtime_rule()).unwrap();
+
+    assert_eq!(network.aggregation_node_count(), 1);
+    assert!(network.aggregation_node(&overtime_condition()).is_some());
+
+    network.remove_rule(1).unwrap();
+    assert_eq!(network.aggregation_node_count(), 0);
+}
+
+#[test]
+fn test_aggregation_node_tracks_assertion_and_retraction() {
+    let mut network = ReteNetwork::new();
+    let fact_store = ArenaFactStore::new();
+    let calculator = Calculator::new();
+    network.add_rule(overtime_rule()).unwrap();
+
+    let shifts = vec![create_shift(1, 7, 24.0), create_shift(2, 7, 20.0), create_shift(3, 9, 8.0)];
+    for shift in &shifts {
+        fact_store.insert(shift.clone());
+    }
+
+    // Employee 7 is over 40 hours, so both of their shifts fire the rule
+    let results = network.process_facts(&shifts, &fact_store, &calculator).unwrap();
+    assert_eq!(results.len(), 2);
+
+    let node = network.aggregation_node(&overtime_condition()).unwrap();
+    assert_eq!(node.aggregate_for(&shifts[0]), FactValue::Float(44.0));
+    assert_eq!(node.aggregate_for(&shifts[2]), FactValue::Float(8.0));
+
+    // Retracting a shift drops employee 7 back under the threshold
+    let affected = network.remove_fact_from_working_memory(1).unwrap();
+    assert_eq!(affected, vec![1]);
+
+    let node = network.aggregation_node(&overtime_condition()).unwrap();
+    assert_eq!(node.aggregate_for(&shifts[1]), FactValue::Float(20.0));
+    assert_eq!(node.fact_count(), 2);
+}
+
+#[test]
+fn test_tumbling_windows_aggregate_by_event_time_and_drop_late_shifts() {
+    const DAY_MS: i64 = 86_400_000;
+    const HOUR_MS: i64 = 3_600_000;
+    let shift = |id: u64, employee_id: i64, hours: f64, time: i64| {
+        let mut fact = create_shift(id, employee_id, hours);
+        fact.data.fields.insert("timestamp".to_string(), FactValue::Integer(time));
+        fact
+    };
+    let daily_condition = AggregationCondition {
+        having: Some(Box::new(Condition::Simple {
+            field: "total_hours".to_string(),
+            operator: Operator::GreaterThan,
+            value: FactValue::Float(10.0),
+        })),
+        window: Some(AggregationWindow::TumblingTime { duration_ms: DAY_MS as u64 }),
+        ..overtime_condition()
+    };
+    let rule = Rule {
+        conditions: vec![Condition::Aggregation(daily_condition.clone())],
+        ..overtime_rule()
+    };
+
+    let mut network = ReteNetwork::new();
+    let fact_store = ArenaFactStore::new();
+    let calculator = Calculator::new();
+    network.set_late_data_policy(LateDataPolicy::Drop {
+        allowed_lateness: std::time::Duration::from_secs(1),
+    });
+    network.add_rule(rule).unwrap();
+    let process = |network: &mut ReteNetwork, shifts: Vec<Fact>| {
+        for shift in &shifts {
+            fact_store.insert(shift.clone());
+        }
+        network.process_facts(&shifts, &fact_store, &calculator).unwrap().len()
+    };
+
+    // Employee 7 works 12 hours on day 0; employee 9 works 4
+    let day_zero = vec![
+        shift(1, 7, 6.0, HOUR_MS),
+        shift(2, 7, 6.0, 2 * HOUR_MS),
+        shift(3, 9, 4.0, HOUR_MS),
+    ];
+    assert_eq!(process(&mut network, day_zero), 2);
+
+    // Day 1 starts a new window, and a day 0 shift arriving after it is dropped as late
+    let day_one = vec![shift(4, 7, 8.0, DAY_MS + HOUR_MS), shift(5, 7, 5.0, 3 * HOUR_MS)];
+    assert_eq!(process(&mut network, day_one), 0);
+
+    let node = network.aggregation_node(&daily_condition).unwrap();
+    assert_eq!(node.window_stats().unwrap().late_events_dropped, 1);
+    assert_eq!(node.window_count(), 3);
+
+    // The watermark has passed the end of day 0, so its windows close on the next batch
+    assert_eq!(
+        process(&mut network, vec![shift(6, 9, 1.0, DAY_MS + 2 * HOUR_MS)]),
+        0
+    );
+    let node = network.aggregation_node(&daily_condition).unwrap();
+    assert_eq!(node.window_count(), 2);
+    assert_eq!(node.fact_count(), 2);
+}
@@ -28,6 +28,7 @@ fn test_alpha_memory_performance_benefit() {
                     value: FactValue::Boolean(true),
                 },
             }],
+            metadata: Default::default(),
         };
         engine.add_rule(rule).unwrap();
     }
@@ -50,6 +51,7 @@ fn test_alpha_memory_performance_benefit() {
                 id: i as u64,
                 external_id: None,
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: FactData { fields },
             }
         })
@@ -123,6 +125,7 @@ fn test_rule_count_independence() {
                         value: FactValue::Boolean(true),
                     },
                 }],
+                metadata: Default::default(),
             };
             engine.add_rule(rule).unwrap();
         }
@@ -140,6 +143,7 @@ fn test_rule_count_independence() {
                     id: i as u64,
                     external_id: None,
                     timestamp: chrono::Utc::now(),
+                    fact_type: None,
                     data: FactData { fields },
                 }
             })
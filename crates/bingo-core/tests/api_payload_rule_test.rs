@@ -21,6 +21,7 @@ fn api_style_rule_fires() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
     engine.add_rule(rule).unwrap();
 
@@ -0,0 +1,76 @@
+//! Batch Summary Test
+//!
+//! Validates that processing a batch with a summary reports, per rule, how often it
+//! fired, how many facts it affected and which fields it changed, consistently with
+//! the detailed results returned alongside it.
+
+use bingo_core::BingoEngine;
+use bingo_core::types::*;
+use std::collections::{BTreeMap, HashMap};
+
+fn transaction(id: u64, amount: i64) -> Fact {
+    let fields = HashMap::from([("amount".to_string(), FactValue::Integer(amount))]);
+    Fact::new(id, FactData { fields })
+}
+
+fn rule(id: u64, threshold: i64, actions: Vec<ActionType>) -> Rule {
+    Rule {
+        id,
+        name: format!("Rule {id}"),
+        conditions: vec![Condition::Simple {
+            field: "amount".to_string(),
+            operator: Operator::GreaterThan,
+            value: FactValue::Integer(threshold),
+        }],
+        actions: actions.into_iter().map(|action_type| Action { action_type }).collect(),
+        metadata: Default::default(),
+    }
+}
+
+#[test]
+fn test_summary_matches_detailed_results() {
+    let engine = BingoEngine::new().unwrap();
+    engine
+        .add_rule(rule(
+            1,
+            100,
+            vec![
+                ActionType::SetField {
+                    field: "flagged".to_string(),
+                    value: FactValue::Boolean(true),
+                },
+                ActionType::Log { message: "large".to_string() },
+            ],
+        ))
+        .unwrap();
+    engine
+        .add_rule(rule(
+            2,
+            1000,
+            vec![ActionType::SetField {
+                field: "flagged".to_string(),
+                value: FactValue::Boolean(true),
+            }],
+        ))
+        .unwrap();
+
+    let facts = (1..=4).map(|id| transaction(id, id as i64 * 400)).collect();
+    let (results, summary) = engine.process_facts_summarized(facts).unwrap();
+
+    assert_eq!(summary.facts_processed, 4);
+    assert_eq!(summary.results_generated, results.len());
+    assert_eq!(summary.facts_affected, 4);
+
+    let large = summary.rule(1).unwrap();
+    assert_eq!(large.times_fired, 4);
+    assert_eq!(large.facts_affected, 4);
+    assert_eq!(
+        large.fields_changed,
+        BTreeMap::from([("flagged".to_string(), 4)])
+    );
+
+    let very_large = summary.rule(2).unwrap();
+    assert_eq!(very_large.times_fired, 2);
+    assert_eq!(very_large.facts_affected, 2);
+    assert_eq!(summary.fields_changed["flagged"], 6);
+}
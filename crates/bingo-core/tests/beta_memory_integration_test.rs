@@ -43,6 +43,7 @@ fn create_multi_condition_rule() -> Rule {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     }
 }
 
@@ -54,6 +55,7 @@ fn create_test_facts() -> Vec<Fact> {
             id: 1,
             external_id: None,
             timestamp: chrono::Utc::now(),
+            fact_type: None,
             data: FactData {
                 fields: HashMap::from([
                     ("employee_id".to_string(), FactValue::Integer(1)),
@@ -74,6 +76,7 @@ fn create_test_facts() -> Vec<Fact> {
             id: 2,
             external_id: None,
             timestamp: chrono::Utc::now(),
+            fact_type: None,
             data: FactData {
                 fields: HashMap::from([
                     ("employee_id".to_string(), FactValue::Integer(2)),
@@ -94,6 +97,7 @@ fn create_test_facts() -> Vec<Fact> {
             id: 3,
             external_id: None,
             timestamp: chrono::Utc::now(),
+            fact_type: None,
             data: FactData {
                 fields: HashMap::from([
                     ("employee_id".to_string(), FactValue::Integer(3)),
@@ -114,6 +118,7 @@ fn create_test_facts() -> Vec<Fact> {
             id: 4,
             external_id: None,
             timestamp: chrono::Utc::now(),
+            fact_type: None,
             data: FactData {
                 fields: HashMap::from([
                     ("employee_id".to_string(), FactValue::Integer(4)),
@@ -134,6 +139,7 @@ fn create_test_facts() -> Vec<Fact> {
             id: 5,
             external_id: None,
             timestamp: chrono::Utc::now(),
+            fact_type: None,
             data: FactData {
                 fields: HashMap::from([
                     ("employee_id".to_string(), FactValue::Integer(5)),
@@ -254,6 +260,7 @@ fn test_multi_condition_performance() {
                     message: format!("Multi-condition rule {i} triggered"),
                 },
             }],
+            metadata: Default::default(),
         };
         engine.add_rule(rule).unwrap();
     }
@@ -274,6 +281,7 @@ fn test_multi_condition_performance() {
                 id: i as u64,
                 external_id: None,
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: FactData { fields },
             }
         })
@@ -34,6 +34,7 @@ fn test_beta_network_multi_condition_rule() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
     engine.add_rule(rule).unwrap();
 
@@ -44,6 +45,7 @@ fn test_beta_network_multi_condition_rule() {
         id: 1,
         external_id: None,
         timestamp: chrono::Utc::now(),
+        fact_type: None,
         data: FactData {
             fields: [
                 ("user_id".to_string(), FactValue::Integer(101)),
@@ -75,6 +77,7 @@ fn test_beta_network_multi_condition_rule() {
         id: 2,
         external_id: None,
         timestamp: chrono::Utc::now(),
+        fact_type: None,
         data: FactData {
             fields: [
                 ("user_id".to_string(), FactValue::Integer(102)),
@@ -102,6 +105,7 @@ fn test_beta_network_multi_condition_rule() {
         id: 3,
         external_id: None,
         timestamp: chrono::Utc::now(),
+        fact_type: None,
         data: FactData {
             fields: [
                 ("user_id".to_string(), FactValue::Integer(103)),
@@ -181,6 +185,7 @@ fn test_beta_network_incremental_token_propagation() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
     engine.add_rule(rule).unwrap();
 
@@ -193,6 +198,7 @@ fn test_beta_network_incremental_token_propagation() {
             id: 1,
             external_id: None,
             timestamp: chrono::Utc::now(),
+            fact_type: None,
             data: FactData {
                 fields: [
                     ("employee_id".to_string(), FactValue::Integer(1001)),
@@ -216,6 +222,7 @@ fn test_beta_network_incremental_token_propagation() {
             id: 2,
             external_id: None,
             timestamp: chrono::Utc::now(),
+            fact_type: None,
             data: FactData {
                 fields: [
                     ("employee_id".to_string(), FactValue::Integer(1002)),
@@ -239,6 +246,7 @@ fn test_beta_network_incremental_token_propagation() {
             id: 3,
             external_id: None,
             timestamp: chrono::Utc::now(),
+            fact_type: None,
             data: FactData {
                 fields: [
                     ("employee_id".to_string(), FactValue::Integer(1003)),
@@ -336,6 +344,7 @@ fn test_beta_network_performance_scaling() {
                     value: FactValue::Boolean(true),
                 },
             }],
+            metadata: Default::default(),
         };
         engine.add_rule(rule).unwrap();
     }
@@ -362,6 +371,7 @@ fn test_beta_network_performance_scaling() {
                 id: i as u64,
                 external_id: None,
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: FactData { fields },
             }
         })
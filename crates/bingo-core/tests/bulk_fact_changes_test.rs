@@ -0,0 +1,159 @@
+//! Bulk Fact Changes Test
+//!
+//! Validates that deleting and updating the facts matching a query keeps every index
+//! consistent, and that the engine retracts the matched facts' activations and derived
+//! facts before rematching updated facts.
+
+use bingo_core::fact_store::arena_store::ArenaFactStore;
+use bingo_core::types::*;
+use bingo_core::{BingoEngine, FactQuery, ForwardChaining};
+use std::collections::HashMap;
+
+fn expense(id: FactId, status: &str, amount: i64) -> Fact {
+    let fields = HashMap::from([
+        ("status".to_string(), FactValue::String(status.to_string())),
+        ("amount".to_string(), FactValue::Integer(amount)),
+    ]);
+    Fact { external_id: Some(format!("expense-{id}")), ..Fact::new(id, FactData { fields }) }
+}
+
+fn status(value: &str) -> FactQuery {
+    FactQuery::compare(
+        "status",
+        Operator::Equal,
+        FactValue::String(value.to_string()),
+    )
+}
+
+fn ids(facts: &[Fact]) -> Vec<FactId> {
+    let mut ids: Vec<FactId> = facts.iter().map(|fact| fact.id).collect();
+    ids.sort_unstable();
+    ids
+}
+
+#[test]
+fn test_bulk_changes_keep_indexes_consistent() {
+    let store = ArenaFactStore::new();
+    store.add_range_index("amount");
+    store.bulk_insert((1..=6).map(|id| expense(id, "open", id as i64 * 100)).collect());
+
+    let archived = HashMap::from([(
+        "status".to_string(),
+        FactValue::String("archived".to_string()),
+    )]);
+    let small = FactQuery::compare("amount", Operator::LessThan, FactValue::Integer(400));
+    assert_eq!(store.update_by_criteria(&small, &archived), vec![1, 2, 3]);
+    let open = FactValue::String("open".to_string());
+    assert_eq!(ids(&store.find_by_field("status", &open)), vec![4, 5, 6]);
+    let archived_value = FactValue::String("archived".to_string());
+    assert_eq!(
+        ids(&store.find_by_field("status", &archived_value)),
+        vec![1, 2, 3]
+    );
+    assert_eq!(
+        store.get_fact(2).unwrap().data.fields["amount"],
+        FactValue::Integer(200)
+    );
+
+    let deleted = store.delete_by_criteria(&status("archived").or(FactQuery::compare(
+        "amount",
+        Operator::GreaterThan,
+        FactValue::Integer(500),
+    )));
+    assert_eq!(deleted, vec![1, 2, 3, 6]);
+    assert_eq!(store.len(), 2);
+    assert!(store.find_by_field("status", &archived_value).is_empty());
+    assert!(store.get_by_external_id("expense-6").is_none());
+    assert_eq!(ids(&store.find_by_range("amount", ..)), vec![4, 5]);
+    assert_eq!(store.delete_facts(&[4, 6, 99]), 1);
+    assert!(store.delete_by_criteria(&status("archived")).is_empty());
+    assert_eq!(store.len(), 1);
+}
+
+#[test]
+fn test_engine_retracts_and_rematches_matched_facts() {
+    let engine = BingoEngine::new().unwrap();
+    engine
+        .add_rules_from_dsl(
+            r#"
+rule "Review" id 1 when status == "open" and amount > 250 then create { review: true }
+rule "Archive" id 2 when status == "archived" then set archived = true
+"#,
+        )
+        .unwrap();
+    let results =
+        engine.process_facts((1..=4).map(|id| expense(id, "open", id as i64 * 100)).collect());
+    assert_eq!(results.unwrap().len(), 2);
+    assert_eq!(engine.fact_count(), 4);
+
+    // Archiving withdraws the reviews the open facts supported and fires on the new status
+    let updates = HashMap::from([(
+        "status".to_string(),
+        FactValue::String("archived".to_string()),
+    )]);
+    let update = engine
+        .update_by_criteria(
+            &FactQuery::compare(
+                "amount",
+                Operator::GreaterThanOrEqual,
+                FactValue::Integer(300),
+            ),
+            &updates,
+        )
+        .unwrap();
+    assert_eq!(update.updated_facts, vec![3, 4]);
+    assert_eq!(update.retractions.len(), 2);
+    assert!(
+        update
+            .retractions
+            .iter()
+            .all(|retraction| retraction.retracted_facts.len() == 1)
+    );
+    let mut fired: Vec<(FactId, RuleId)> =
+        update.results.iter().map(|result| (result.fact_id, result.rule_id)).collect();
+    fired.sort_unstable();
+    assert_eq!(fired, vec![(3, 2), (4, 2)]);
+    assert_eq!(engine.fact_count(), 4);
+
+    let retractions = engine.retract_by_criteria(&status("archived")).unwrap();
+    assert_eq!(
+        retractions.iter().map(|retraction| retraction.fact_id).collect::<Vec<_>>(),
+        vec![3, 4]
+    );
+    assert_eq!(engine.fact_count(), 2);
+    assert!(engine.retract_by_criteria(&status("archived")).unwrap().is_empty());
+}
+
+#[test]
+fn test_update_does_not_report_derived_facts_it_retracted() {
+    // Chained conclusions are stored, so the query can match them
+    let engine = BingoEngine::new().unwrap();
+    engine.set_forward_chaining(Some(ForwardChaining::default()));
+    engine
+        .add_rules_from_dsl(
+            r#"rule "Review" id 1 when status == "open" and amount > 350 then create { kind: "review", amount: 500 }"#,
+        )
+        .unwrap();
+    engine
+        .process_facts((1..=4).map(|id| expense(id, "open", id as i64 * 100)).collect())
+        .unwrap();
+    assert_eq!(engine.fact_count(), 5);
+
+    // The review matches the query too, but updating its source deletes it
+    let updates = HashMap::from([(
+        "status".to_string(),
+        FactValue::String("archived".to_string()),
+    )]);
+    let update = engine
+        .update_by_criteria(
+            &FactQuery::compare(
+                "amount",
+                Operator::GreaterThanOrEqual,
+                FactValue::Integer(300),
+            ),
+            &updates,
+        )
+        .unwrap();
+    assert_eq!(update.updated_facts, vec![3, 4]);
+    assert_eq!(engine.fact_count(), 4);
+}
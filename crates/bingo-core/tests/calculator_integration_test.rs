@@ -41,6 +41,7 @@ fn create_fact(
         id,
         external_id: Some(format!("fact-{id}")),
         timestamp: Utc::now(),
+        fact_type: None,
         data: FactData { fields },
     }
 }
@@ -72,6 +73,7 @@ fn test_threshold_check_calculator() {
                 output_field: "overtime_check".to_string(),
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).unwrap();
@@ -145,6 +147,7 @@ fn test_limit_validator_calculator() {
                 output_field: "hours_valid".to_string(),
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).unwrap();
@@ -221,6 +224,7 @@ fn test_weighted_average_calculator() {
         id: 100,
         external_id: Some("weighted-test".to_string()),
         timestamp: Utc::now(),
+        fact_type: None,
         data: FactData { fields },
     };
 
@@ -244,6 +248,7 @@ fn test_weighted_average_calculator() {
                 output_field: "weighted_avg".to_string(),
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).unwrap();
@@ -292,3 +297,183 @@ fn test_weighted_average_calculator() {
 
     println!("✅ Weighted average calculator test passed");
 }
+
+#[test]
+fn test_rate_lookup_calculator_uses_rate_in_effect_on_fact_date() {
+    use bingo_calculator::Calculator;
+    use bingo_calculator::built_in::rate_lookup::EffectiveDatedRates;
+    use chrono::NaiveDate;
+    use std::sync::Arc;
+
+    let fiscal_year = |year| NaiveDate::from_ymd_opt(year, 4, 6).unwrap();
+    let rates = EffectiveDatedRates::new()
+        .with_rate("UK", fiscal_year(2023), FactValue::Float(0.2))
+        .with_rate("UK", fiscal_year(2024), FactValue::Float(0.22));
+    let mut calculator = Calculator::new();
+    calculator.register_rate_table("income_tax", Arc::new(rates));
+
+    let engine = BingoEngine::with_calculator(calculator).unwrap();
+    // Rules name the table through a global, as input mappings only read fields
+    engine
+        .set_global(
+            "income_tax_table",
+            FactValue::String("income_tax".to_string()),
+        )
+        .unwrap();
+    let mapping = [
+        ("table", "@income_tax_table"),
+        ("jurisdiction", "region"),
+        ("date", "paid_on"),
+        ("amount", "gross"),
+    ];
+    engine
+        .add_rule(Rule {
+            id: 1,
+            name: "Income tax".to_string(),
+            conditions: vec![Condition::Simple {
+                field: "gross".to_string(),
+                operator: Operator::GreaterThan,
+                value: FactValue::Float(0.0),
+            }],
+            actions: vec![Action {
+                action_type: ActionType::CallCalculator {
+                    calculator_name: "rate_lookup".to_string(),
+                    input_mapping: mapping
+                        .iter()
+                        .map(|(input, field)| (input.to_string(), field.to_string()))
+                        .collect(),
+                    output_field: "tax".to_string(),
+                },
+            }],
+            metadata: Default::default(),
+        })
+        .unwrap();
+
+    let payslip = |id, paid_on: &str| {
+        let fields = HashMap::from([
+            ("region".to_string(), FactValue::String("UK".to_string())),
+            (
+                "paid_on".to_string(),
+                FactValue::String(paid_on.to_string()),
+            ),
+            ("gross".to_string(), FactValue::Float(1000.0)),
+        ]);
+        Fact::new(id, FactData { fields })
+    };
+    let results = engine
+        .process_facts(vec![
+            payslip(1, "2024-03-29T00:00:00Z"),
+            payslip(2, "2024-04-26T00:00:00Z"),
+        ])
+        .unwrap();
+
+    let tax = |fact_id| {
+        results
+            .iter()
+            .filter(|result| result.fact_id == fact_id)
+            .flat_map(|result| &result.actions_executed)
+            .find_map(|action| match action {
+                bingo_core::rete_nodes::ActionResult::CalculatorResult { parsed_value, .. } => {
+                    Some(parsed_value.clone())
+                }
+                _ => None,
+            })
+    };
+    assert_eq!(tax(1), Some(FactValue::Float(200.0)));
+    assert_eq!(tax(2), Some(FactValue::Float(220.0)));
+}
+
+/// Doubles `value`, counting how often it runs
+struct CountingCalculator {
+    name: &'static str,
+    deterministic: bool,
+    calls: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+}
+
+impl bingo_calculator::plugin::CalculatorPlugin for CountingCalculator {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn calculate(
+        &self,
+        args: &HashMap<String, &FactValue>,
+    ) -> bingo_calculator::plugin::CalculationResult {
+        self.calls.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        match args.get("value") {
+            Some(FactValue::Integer(value)) => Ok(FactValue::Integer(value * 2)),
+            _ => Err("Invalid argument 'value': expected integer".to_string()),
+        }
+    }
+
+    fn is_deterministic(&self) -> bool {
+        self.deterministic
+    }
+}
+
+#[test]
+fn test_calculator_results_are_memoized_across_similar_facts() {
+    use bingo_core::CalculatorCacheConfig;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let (pure_calls, clock_calls) = (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+    let mut calculator = bingo_calculator::Calculator::new();
+    calculator.register(Box::new(CountingCalculator {
+        name: "double",
+        deterministic: true,
+        calls: pure_calls.clone(),
+    }));
+    calculator.register(Box::new(CountingCalculator {
+        name: "double_now",
+        deterministic: false,
+        calls: clock_calls.clone(),
+    }));
+    let engine = BingoEngine::with_calculator(calculator).unwrap();
+
+    let call = |id, calculator_name: &str, output_field: &str| Rule {
+        id,
+        name: calculator_name.to_string(),
+        conditions: vec![Condition::Simple {
+            field: "value".to_string(),
+            operator: Operator::GreaterThanOrEqual,
+            value: FactValue::Integer(0),
+        }],
+        actions: vec![Action {
+            action_type: ActionType::CallCalculator {
+                calculator_name: calculator_name.to_string(),
+                input_mapping: HashMap::from([("value".to_string(), "value".to_string())]),
+                output_field: output_field.to_string(),
+            },
+        }],
+        metadata: Default::default(),
+    };
+    engine.add_rule(call(1, "double", "doubled")).unwrap();
+    engine.add_rule(call(2, "double_now", "doubled_now")).unwrap();
+
+    let facts = |first_id: u64| -> Vec<Fact> {
+        (0..1000)
+            .map(|i| {
+                let fields = HashMap::from([("value".to_string(), FactValue::Integer(i % 10))]);
+                Fact::new(first_id + i as u64, FactData { fields })
+            })
+            .collect()
+    };
+    let results = engine.process_facts(facts(0)).unwrap();
+    assert_eq!(results.len(), 2000);
+
+    // One computation per distinct input, but every call of the clock-reading calculator
+    assert_eq!(pure_calls.load(Ordering::Relaxed), 10);
+    assert_eq!(clock_calls.load(Ordering::Relaxed), 1000);
+    let stats = engine.calculator_cache_stats();
+    assert_eq!((stats.hits, stats.misses, stats.size), (990, 10, 10));
+
+    // A disabled cache computes every call
+    engine.set_calculator_cache(CalculatorCacheConfig { capacity: 0, ttl: None });
+    engine.process_facts(facts(1000)).unwrap();
+    assert_eq!(pure_calls.load(Ordering::Relaxed), 1010);
+
+    // The setting survives rule changes
+    engine.add_rule(call(3, "double", "doubled_again")).unwrap();
+    assert_eq!(engine.calculator_cache().capacity, 0);
+}
@@ -0,0 +1,153 @@
+//! Calendar Aggregation Test
+//!
+//! Validates that aggregation conditions with a calendar window aggregate over the
+//! reference calendar period a fact falls in, such as the current pay period.
+
+use bingo_calculator::calculator::Calculator;
+use bingo_core::fact_store::arena_store::ArenaFactStore;
+use bingo_core::rete_network::ReteNetwork;
+use bingo_core::types::*;
+use bingo_core::{BingoEngine, BusinessPeriod, PeriodCalendar};
+use chrono::{DateTime, TimeZone, Utc};
+use std::collections::HashMap;
+
+fn date(month: u32, day: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, month, day, 0, 0, 0).unwrap()
+}
+
+/// Semi-monthly pay periods for the first quarter of 2024
+fn pay_periods() -> PeriodCalendar {
+    let periods = (1..=3)
+        .flat_map(|month| {
+            let next = if month == 3 {
+                date(4, 1)
+            } else {
+                date(month + 1, 1)
+            };
+            [
+                BusinessPeriod::new(
+                    format!("2024-{month:02}-A"),
+                    date(month, 1),
+                    date(month, 16),
+                ),
+                BusinessPeriod::new(format!("2024-{month:02}-B"), date(month, 16), next),
+            ]
+        })
+        .collect();
+    PeriodCalendar::new("pay_periods", periods).unwrap()
+}
+
+fn create_shift(id: u64, employee_id: i64, hours: f64, worked_on: DateTime<Utc>) -> Fact {
+    let mut fields = HashMap::new();
+    fields.insert("employee_id".to_string(), FactValue::Integer(employee_id));
+    fields.insert("hours".to_string(), FactValue::Float(hours));
+    fields.insert(
+        "timestamp".to_string(),
+        FactValue::Integer(worked_on.timestamp_millis()),
+    );
+    Fact::new(id, FactData { fields })
+}
+
+fn pay_period_hours_condition() -> AggregationCondition {
+    AggregationCondition {
+        aggregation_type: AggregationType::Sum,
+        source_field: "hours".to_string(),
+        group_by: vec!["employee_id".to_string()],
+        having: Some(Box::new(Condition::Simple {
+            field: "period_hours".to_string(),
+            operator: Operator::GreaterThan,
+            value: FactValue::Float(80.0),
+        })),
+        alias: "period_hours".to_string(),
+        window: Some(AggregationWindow::Calendar { calendar: "pay_periods".to_string() }),
+    }
+}
+
+fn overtime_rule() -> Rule {
+    Rule {
+        id: 1,
+        name: "Pay period overtime".to_string(),
+        conditions: vec![Condition::Aggregation(pay_period_hours_condition())],
+        actions: vec![Action {
+            action_type: ActionType::SetField {
+                field: "overtime".to_string(),
+                value: FactValue::Boolean(true),
+            },
+        }],
+        metadata: Default::default(),
+    }
+}
+
+#[test]
+fn test_aggregates_are_partitioned_by_pay_period() {
+    let mut network = ReteNetwork::new();
+    let fact_store = ArenaFactStore::new();
+    let calculator = Calculator::new();
+    network.register_calendar(pay_periods());
+    network.add_rule(overtime_rule()).unwrap();
+
+    let shifts = vec![
+        create_shift(1, 7, 50.0, date(1, 3)),
+        create_shift(2, 7, 40.0, date(1, 15)),
+        // Same month, next pay period
+        create_shift(3, 7, 45.0, date(1, 16)),
+        // Outside the calendar
+        create_shift(4, 7, 99.0, date(5, 2)),
+    ];
+    for shift in &shifts {
+        fact_store.insert(shift.clone());
+    }
+
+    let results = network.process_facts(&shifts, &fact_store, &calculator).unwrap();
+    let mut fired: Vec<FactId> = results.iter().map(|result| result.fact_id).collect();
+    fired.sort();
+    assert_eq!(fired, vec![1, 2]);
+
+    let node = network.aggregation_node(&pay_period_hours_condition()).unwrap();
+    assert_eq!(node.aggregate_for(&shifts[0]), FactValue::Float(90.0));
+    assert_eq!(node.aggregate_for(&shifts[2]), FactValue::Float(45.0));
+    assert_eq!(node.fact_count(), 3);
+}
+
+#[test]
+fn test_reregistering_calendar_repartitions_aggregates() {
+    let mut network = ReteNetwork::new();
+    let fact_store = ArenaFactStore::new();
+    let calculator = Calculator::new();
+    network.register_calendar(pay_periods());
+    network.add_rule(overtime_rule()).unwrap();
+
+    let shifts = vec![create_shift(1, 7, 50.0, date(1, 10)), create_shift(2, 7, 40.0, date(1, 20))];
+    for shift in &shifts {
+        fact_store.insert(shift.clone());
+    }
+    assert!(network.process_facts(&shifts, &fact_store, &calculator).unwrap().is_empty());
+
+    // Switch to monthly periods: both shifts now share January
+    let monthly = PeriodCalendar::contiguous(
+        "pay_periods",
+        vec![("2024-01".to_string(), date(1, 1)), ("2024-02".to_string(), date(2, 1))],
+        date(3, 1),
+    )
+    .unwrap();
+    network.register_calendar(monthly);
+
+    let late_shift = create_shift(3, 7, 1.0, date(1, 31));
+    fact_store.insert(late_shift.clone());
+    let results = network.process_facts(&[late_shift], &fact_store, &calculator).unwrap();
+    assert_eq!(results.len(), 1);
+
+    let node = network.aggregation_node(&pay_period_hours_condition()).unwrap();
+    assert_eq!(node.aggregate_for(&shifts[0]), FactValue::Float(91.0));
+}
+
+#[test]
+fn test_rules_with_unregistered_calendar_are_rejected() {
+    let engine = BingoEngine::new().unwrap();
+    assert!(engine.add_rule(overtime_rule()).is_err());
+    assert_eq!(engine.rule_count(), 0);
+
+    engine.register_calendar(pay_periods());
+    engine.add_rule(overtime_rule()).unwrap();
+    assert_eq!(engine.rule_count(), 1);
+}
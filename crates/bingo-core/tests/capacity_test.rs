@@ -0,0 +1,95 @@
+//! Capacity Limit Test
+//!
+//! Validates that an engine bounded by `EngineConfig` refuses inserts past its fact
+//! limit with a structured capacity error, evicts the oldest facts or the expired ones
+//! when the eviction policy allows it, and carries its limits over to engines created
+//! from it as a template.
+
+use bingo_core::types::*;
+use bingo_core::{BingoEngine, BingoError, EngineConfig, EvictionPolicy, parse_rule};
+use std::collections::HashMap;
+
+fn order(id: u64, age_minutes: i64) -> Fact {
+    let fields = HashMap::from([("amount".to_string(), FactValue::Integer(250))]);
+    let mut fact = Fact::new(id, FactData { fields });
+    fact.timestamp = chrono::Utc::now() - chrono::Duration::minutes(age_minutes);
+    fact
+}
+
+fn bounded_engine(eviction: EvictionPolicy) -> BingoEngine {
+    let config =
+        EngineConfig { max_working_memory_facts: Some(3), eviction, ..EngineConfig::default() };
+    let engine = BingoEngine::with_config(config).unwrap();
+    let rule = r#"rule "Large order" id 1 when amount > 100 then set flagged = true"#;
+    engine.add_rule(parse_rule(rule).unwrap()).unwrap();
+    engine
+}
+
+fn fact_ids(engine: &BingoEngine) -> Vec<u64> {
+    (1..=10).filter(|&id| engine.get_fact(id).is_some()).collect()
+}
+
+#[test]
+fn test_inserts_past_the_limit_are_rejected() {
+    let engine = bounded_engine(EvictionPolicy::Reject);
+    engine.process_facts((1..=3).map(|id| order(id, 0)).collect()).unwrap();
+
+    let error = engine.add_fact_to_working_memory(order(4, 0)).unwrap_err();
+    match &error {
+        BingoError::CapacityExceeded { resource, limit, requested, .. } => {
+            assert_eq!(
+                (resource.as_str(), *limit, *requested),
+                ("working_memory_facts", 3, 4)
+            );
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+    assert_eq!(error.category(), "capacity");
+    assert!(error.is_recoverable());
+    assert_eq!(engine.fact_count(), 3);
+
+    // A batch larger than the limit is refused whatever the eviction policy
+    let engine = bounded_engine(EvictionPolicy::OldestFirst);
+    assert!(engine.process_facts((1..=4).map(|id| order(id, 0)).collect()).is_err());
+    assert_eq!(engine.fact_count(), 0);
+    assert_eq!(engine.capacity_stats().inserts_rejected, 4);
+}
+
+#[test]
+fn test_oldest_facts_are_evicted_first() {
+    let engine = bounded_engine(EvictionPolicy::OldestFirst);
+    engine.process_facts(vec![order(1, 10), order(2, 30), order(3, 20)]).unwrap();
+
+    let results = engine.process_facts(vec![order(4, 0), order(5, 0)]).unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(fact_ids(&engine), vec![1, 4, 5]);
+    assert_eq!(engine.capacity_stats().facts_evicted, 2);
+}
+
+#[test]
+fn test_only_expired_facts_are_evicted_under_a_ttl() {
+    let engine = bounded_engine(EvictionPolicy::Ttl(chrono::Duration::minutes(15)));
+    engine.process_facts(vec![order(1, 10), order(2, 30), order(3, 20)]).unwrap();
+
+    engine.process_facts(vec![order(4, 0), order(5, 0)]).unwrap();
+    assert_eq!(fact_ids(&engine), vec![1, 4, 5]);
+
+    // Nothing has expired now, so the next insert cannot be made room for
+    let error = engine.process_facts(vec![order(6, 0)]).unwrap_err();
+    assert!(matches!(error, BingoError::CapacityExceeded { .. }));
+    assert_eq!(fact_ids(&engine), vec![1, 4, 5]);
+}
+
+#[test]
+fn test_configuration_is_validated_and_carried_to_templates() {
+    let zero_limit = EngineConfig { max_working_memory_facts: Some(0), ..EngineConfig::default() };
+    assert!(BingoEngine::with_config(zero_limit).is_err());
+
+    let template = bounded_engine(EvictionPolicy::OldestFirst);
+    let engine = BingoEngine::from_template(&template).unwrap();
+    assert_eq!(engine.config(), template.config());
+    engine.process_facts((1..=3).map(|id| order(id, 10)).collect()).unwrap();
+    engine.process_facts(vec![order(4, 0)]).unwrap();
+    assert_eq!(engine.fact_count(), 3);
+    assert!(engine.get_fact(4).is_some());
+}
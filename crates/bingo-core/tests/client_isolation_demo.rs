@@ -40,6 +40,7 @@ fn demonstrate_complete_client_isolation() {
                 value: FactValue::Integer(10),
             },
         }],
+        metadata: Default::default(),
     };
     ecommerce_engine.add_rule(ecommerce_rule).unwrap();
 
@@ -58,6 +59,7 @@ fn demonstrate_complete_client_isolation() {
                 value: FactValue::Integer(150), // 1.5x rate
             },
         }],
+        metadata: Default::default(),
     };
     payroll_engine.add_rule(payroll_rule).unwrap();
 
@@ -76,6 +78,7 @@ fn demonstrate_complete_client_isolation() {
                 value: FactValue::String("HIGH".to_string()),
             },
         }],
+        metadata: Default::default(),
     };
     risk_engine.add_rule(risk_rule).unwrap();
 
@@ -93,6 +96,7 @@ fn demonstrate_complete_client_isolation() {
                 id: 1001,
                 external_id: Some("ORDER-2024-001".to_string()),
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: FactData {
                     fields: HashMap::from([
                         (
@@ -113,6 +117,7 @@ fn demonstrate_complete_client_isolation() {
                 id: 1002,
                 external_id: Some("ORDER-2024-002".to_string()),
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: FactData {
                     fields: HashMap::from([
                         (
@@ -148,6 +153,7 @@ fn demonstrate_complete_client_isolation() {
                 id: 2001,
                 external_id: Some("EMP-001-WEEK-42".to_string()),
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: FactData {
                     fields: HashMap::from([
                         (
@@ -165,6 +171,7 @@ fn demonstrate_complete_client_isolation() {
                 id: 2002,
                 external_id: Some("EMP-002-WEEK-42".to_string()),
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: FactData {
                     fields: HashMap::from([
                         (
@@ -197,6 +204,7 @@ fn demonstrate_complete_client_isolation() {
                 id: 3001,
                 external_id: Some("TXN-2024-12345".to_string()),
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: FactData {
                     fields: HashMap::from([
                         (
@@ -221,6 +229,7 @@ fn demonstrate_complete_client_isolation() {
                 id: 3002,
                 external_id: Some("TXN-2024-12346".to_string()),
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: FactData {
                     fields: HashMap::from([
                         (
@@ -0,0 +1,115 @@
+//! Collation Test
+//!
+//! Validates that string conditions match under the engine's collation, so spellings
+//! that differ only in case, `ß`/`ss`, Unicode composition or locale-specific casing
+//! still fire rules, while the default collation keeps exact matching.
+
+use bingo_core::types::*;
+use bingo_core::{BingoEngine, Collation, Normalization};
+use std::collections::HashMap;
+
+fn address_fact(id: u64, street: &str) -> Fact {
+    let mut fields = HashMap::new();
+    fields.insert("street".to_string(), FactValue::String(street.to_string()));
+    fields.insert("country".to_string(), FactValue::String("DE".to_string()));
+    Fact::new(id, FactData { fields })
+}
+
+fn street_rule(id: u64, operator: Operator, street: &str) -> Rule {
+    Rule {
+        id,
+        name: format!("Street rule {id}"),
+        conditions: vec![Condition::Simple {
+            field: "street".to_string(),
+            operator,
+            value: FactValue::String(street.to_string()),
+        }],
+        actions: vec![Action { action_type: ActionType::Log { message: "matched".to_string() } }],
+        metadata: Default::default(),
+    }
+}
+
+fn matched_facts(engine: &BingoEngine, streets: &[&str]) -> Vec<u64> {
+    let facts = streets
+        .iter()
+        .enumerate()
+        .map(|(index, street)| address_fact(index as u64 + 1, street))
+        .collect();
+    let mut fact_ids: Vec<u64> = engine
+        .process_facts(facts)
+        .unwrap()
+        .iter()
+        .map(|result| result.fact_id)
+        .collect();
+    fact_ids.sort_unstable();
+    fact_ids
+}
+
+#[test]
+fn test_default_collation_matches_exactly() {
+    let engine = BingoEngine::new().unwrap();
+    assert_eq!(engine.collation(), Collation::binary());
+    engine.add_rule(street_rule(1, Operator::Equal, "HAUPTSTRASSE")).unwrap();
+
+    assert_eq!(
+        matched_facts(&engine, &["HAUPTSTRASSE", "Hauptstraße"]),
+        vec![1]
+    );
+}
+
+#[test]
+fn test_case_insensitive_collation_folds_sharp_s() {
+    let engine = BingoEngine::new().unwrap();
+    // Rules added before the collation is set are re-indexed under it
+    engine.add_rule(street_rule(1, Operator::Equal, "HAUPTSTRASSE")).unwrap();
+    engine.set_collation(Collation::case_insensitive());
+
+    assert_eq!(
+        matched_facts(&engine, &["Hauptstraße", "hauptstrasse", "Hauptweg"]),
+        vec![1, 2]
+    );
+}
+
+#[test]
+fn test_collation_applies_to_multi_condition_rules() {
+    let engine = BingoEngine::new().unwrap();
+    engine.set_collation(Collation::case_insensitive());
+    let mut rule = street_rule(1, Operator::StartsWith, "STRASSE");
+    rule.conditions.push(Condition::Simple {
+        field: "country".to_string(),
+        operator: Operator::Equal,
+        value: FactValue::String("de".to_string()),
+    });
+    engine.add_rule(rule).unwrap();
+
+    assert_eq!(
+        matched_facts(&engine, &["Straße des 17. Juni", "Unter den Linden"]),
+        vec![1]
+    );
+}
+
+#[test]
+fn test_normalization_without_case_folding() {
+    let engine = BingoEngine::new().unwrap();
+    engine.set_collation(Collation { normalization: Normalization::Nfc, ..Collation::binary() });
+    engine
+        .add_rule(street_rule(1, Operator::Equal, "Rue de l'\u{c9}glise"))
+        .unwrap();
+
+    assert_eq!(
+        matched_facts(&engine, &["Rue de l'E\u{301}glise", "RUE DE L'\u{c9}GLISE"]),
+        vec![1]
+    );
+}
+
+#[test]
+fn test_turkish_locale_collation() {
+    let engine = BingoEngine::new().unwrap();
+    engine.set_collation(Collation::for_locale("tr"));
+    engine.add_rule(street_rule(1, Operator::Equal, "İSTİKLAL CADDESİ")).unwrap();
+
+    assert_eq!(
+        matched_facts(&engine, &["İstiklal Caddesi", "Istiklal Caddesi"]),
+        vec![1]
+    );
+}
@@ -0,0 +1,208 @@
+//! Columnar Export Test
+//!
+//! Validates exporting rule results and facts as Arrow record batches with typed
+//! columns, and that a Parquet file written from them reads back unchanged.
+
+#![cfg(feature = "arrow")]
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::{Float64Type, Int64Type, UInt64Type};
+use arrow_array::{Array, RecordBatch};
+use arrow_schema::DataType;
+use bingo_core::rete_nodes::{ActionResult, RuleExecutionResult};
+use bingo_core::types::*;
+use bingo_core::{BingoEngine, facts_record_batch, results_record_batch, write_parquet};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+fn payment(id: u64, fields: Vec<(&str, FactValue)>) -> Fact {
+    let fields = fields.into_iter().map(|(name, value)| (name.to_string(), value)).collect();
+    Fact::new(id, FactData { fields })
+}
+
+fn column<'a>(batch: &'a RecordBatch, name: &str) -> &'a dyn Array {
+    batch.column_by_name(name).unwrap().as_ref()
+}
+
+#[test]
+fn test_results_have_typed_value_columns() {
+    let results = vec![
+        RuleExecutionResult {
+            rule_id: 1,
+            fact_id: 10,
+            actions_executed: vec![
+                ActionResult::FieldSet {
+                    fact_id: 10,
+                    field: "risk".to_string(),
+                    value: FactValue::Integer(3),
+                },
+                ActionResult::CalculatorResult {
+                    calculator: "multiply".to_string(),
+                    result: "2.5".to_string(),
+                    output_field: "tax".to_string(),
+                    parsed_value: FactValue::Float(2.5),
+                },
+            ],
+            ..Default::default()
+        },
+        RuleExecutionResult {
+            rule_id: 2,
+            fact_id: 11,
+            actions_executed: vec![
+                ActionResult::logged("review".to_string()),
+                ActionResult::FactUpdated {
+                    fact_id: 11,
+                    updated_fields: vec!["status".to_string(), "owner".to_string()],
+                },
+            ],
+            external_id: Some("order-11".to_string()),
+            ..Default::default()
+        },
+        RuleExecutionResult {
+            rule_id: 3,
+            fact_id: 12,
+            actions_executed: vec![],
+            ..Default::default()
+        },
+    ];
+    let batch = results_record_batch(&results).unwrap();
+    assert_eq!(batch.num_rows(), 6);
+
+    let rule_ids = column(&batch, "rule_id").as_primitive::<UInt64Type>();
+    assert_eq!(rule_ids.values().to_vec(), vec![1, 1, 2, 2, 2, 3]);
+    let actions = column(&batch, "action").as_string::<i32>();
+    assert_eq!(
+        actions.iter().collect::<Vec<_>>(),
+        vec![
+            Some("field_set"),
+            Some("calculator_result"),
+            Some("logged"),
+            Some("fact_updated"),
+            Some("fact_updated"),
+            None
+        ]
+    );
+    let fields = column(&batch, "field").as_string::<i32>();
+    assert_eq!(fields.value(4), "owner");
+    let external_ids = column(&batch, "fact_external_id").as_string::<i32>();
+    assert!(external_ids.is_null(0));
+    assert_eq!(external_ids.value(3), "order-11");
+
+    let integers = column(&batch, "value_integer").as_primitive::<Int64Type>();
+    let floats = column(&batch, "value_float").as_primitive::<Float64Type>();
+    assert_eq!(integers.value(0), 3);
+    assert!(integers.is_null(1) && floats.is_null(0));
+    assert_eq!(floats.value(1), 2.5);
+    let messages = column(&batch, "message").as_string::<i32>();
+    assert_eq!(messages.value(1), "multiply");
+    assert_eq!(messages.value(2), "review");
+}
+
+#[test]
+fn test_fact_columns_are_typed_by_their_values() {
+    let facts = vec![
+        payment(
+            1,
+            vec![
+                ("amount", FactValue::Integer(100)),
+                ("rate", FactValue::Integer(1)),
+                ("flagged", FactValue::Boolean(true)),
+            ],
+        ),
+        payment(
+            2,
+            vec![
+                ("amount", FactValue::Integer(250)),
+                ("rate", FactValue::Float(0.5)),
+                ("note", FactValue::String("manual".to_string())),
+            ],
+        ),
+    ];
+    let batch = facts_record_batch(&facts).unwrap();
+
+    let schema = batch.schema();
+    let names: Vec<&str> = schema.fields().iter().map(|field| field.name().as_str()).collect();
+    assert_eq!(
+        names,
+        vec![
+            "fact_id",
+            "fact_external_id",
+            "fact_timestamp",
+            "amount",
+            "flagged",
+            "note",
+            "rate"
+        ]
+    );
+    assert_eq!(
+        schema.field_with_name("amount").unwrap().data_type(),
+        &DataType::Int64
+    );
+    assert_eq!(
+        schema.field_with_name("rate").unwrap().data_type(),
+        &DataType::Float64
+    );
+    assert_eq!(
+        schema.field_with_name("flagged").unwrap().data_type(),
+        &DataType::Boolean
+    );
+    assert_eq!(
+        schema.field_with_name("note").unwrap().data_type(),
+        &DataType::Utf8
+    );
+    assert!(column(&batch, "flagged").is_null(1));
+    assert!(column(&batch, "note").is_null(0));
+
+    let clashing = payment(3, vec![("fact_id", FactValue::Integer(1))]);
+    assert!(facts_record_batch(&[clashing]).is_err());
+}
+
+#[test]
+fn test_parquet_round_trip() {
+    let engine = BingoEngine::new().unwrap();
+    engine
+        .add_rule(Rule {
+            id: 1,
+            name: "Large".to_string(),
+            conditions: vec![Condition::Simple {
+                field: "amount".to_string(),
+                operator: Operator::GreaterThan,
+                value: FactValue::Integer(100),
+            }],
+            actions: vec![Action {
+                action_type: ActionType::SetField {
+                    field: "flagged".to_string(),
+                    value: FactValue::Boolean(true),
+                },
+            }],
+        })
+        .unwrap();
+    let facts: Vec<Fact> = (1..=3)
+        .map(|id| payment(id, vec![("amount", FactValue::Integer(id as i64 * 60))]))
+        .collect();
+    let results = engine.process_facts(facts.clone()).unwrap();
+    let batch = results_record_batch(&results).unwrap();
+    assert_eq!(batch.num_rows(), 2);
+
+    for batch in [batch, facts_record_batch(&facts).unwrap()] {
+        let path = std::env::temp_dir().join(format!(
+            "bingo_columnar_export_{}_{}.parquet",
+            std::process::id(),
+            batch.num_columns()
+        ));
+        write_parquet(
+            std::slice::from_ref(&batch),
+            std::fs::File::create(&path).unwrap(),
+        )
+        .unwrap();
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(&path).unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+        let read: Vec<RecordBatch> = reader.collect::<Result<_, _>>().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(read, vec![batch]);
+    }
+
+    assert!(write_parquet(&[], Vec::new()).is_err());
+}
@@ -0,0 +1,137 @@
+//! Compiled Ruleset Test
+//!
+//! Validates that a ruleset compiled in one engine loads into another with the same
+//! network and fires the same rules, that rules can still be added after loading, and
+//! that artifacts referring to unregistered reference tables or undecodable bytes are
+//! rejected without changing the engine.
+
+use bingo_core::types::*;
+use bingo_core::{BingoEngine, CompiledRuleset, ReferenceTable};
+use std::collections::HashMap;
+
+const RULES: &str = r#"
+rule "Large order" id 1 when amount > 100 then set review = true
+rule "Priority region" id 2 when region in ["north", "east"] and amount > 50 then set priority = true
+rule "Blocked customer" id 3 when customer in "blocked_customers" then set blocked = true
+"#;
+
+fn blocked_customers() -> ReferenceTable {
+    ReferenceTable::from_keys([FactValue::String("c-13".to_string())])
+}
+
+fn order(region: &str, customer: &str, amount: i64) -> Fact {
+    let fields = HashMap::from([
+        ("region".to_string(), FactValue::String(region.to_string())),
+        (
+            "customer".to_string(),
+            FactValue::String(customer.to_string()),
+        ),
+        ("amount".to_string(), FactValue::Integer(amount)),
+    ]);
+    Fact::new(0, FactData { fields })
+}
+
+fn orders() -> Vec<Fact> {
+    vec![order("north", "c-1", 120), order("south", "c-13", 60), order("east", "c-2", 70)]
+}
+
+fn firings(engine: &BingoEngine, facts: Vec<Fact>) -> Vec<(FactId, RuleId)> {
+    let mut firings: Vec<_> = engine
+        .process_facts(facts)
+        .unwrap()
+        .iter()
+        .map(|result| (result.fact_id, result.rule_id))
+        .collect();
+    firings.sort_unstable();
+    firings
+}
+
+fn compiled_engine() -> BingoEngine {
+    let engine = BingoEngine::new().unwrap();
+    engine
+        .register_reference_table("blocked_customers", blocked_customers())
+        .unwrap();
+    engine.add_rules_from_dsl(RULES).unwrap();
+    engine
+}
+
+#[test]
+fn test_loaded_ruleset_matches_the_compiled_one() {
+    let compiled = compiled_engine();
+    let bytes = compiled.compile_ruleset().to_bytes().unwrap();
+    let artifact = CompiledRuleset::from_bytes(&bytes).unwrap();
+    assert_eq!(artifact.rules().len(), 3);
+    assert!(artifact.node_count() > 3);
+
+    let loaded = BingoEngine::new().unwrap();
+    loaded
+        .register_reference_table("blocked_customers", blocked_customers())
+        .unwrap();
+    loaded.load_compiled(&bytes).unwrap();
+    assert_eq!(loaded.rule_count(), 3);
+    assert_eq!(loaded.export_network_dot(), compiled.export_network_dot());
+
+    let expected = firings(&compiled, orders());
+    assert_eq!(expected.len(), 4);
+    assert_eq!(firings(&loaded, orders()), expected);
+
+    // Rules added after loading get nodes of their own
+    loaded
+        .add_rules_from_dsl(r#"rule "Small order" id 4 when amount < 65 then set small = true"#)
+        .unwrap();
+    let fired = firings(&loaded, vec![order("west", "c-3", 10)]);
+    assert_eq!(
+        fired.iter().map(|(_, rule_id)| *rule_id).collect::<Vec<_>>(),
+        [4]
+    );
+}
+
+#[test]
+fn test_unloadable_artifacts_leave_the_engine_unchanged() {
+    let bytes = compiled_engine().compile_ruleset().to_bytes().unwrap();
+
+    let engine = BingoEngine::new().unwrap();
+    engine
+        .add_rules_from_dsl(r#"rule "Kept" id 9 when amount > 0 then set seen = true"#)
+        .unwrap();
+    let error = engine.load_compiled(&bytes).unwrap_err();
+    assert!(error.to_string().contains("'blocked_customers' is not registered"));
+    assert!(engine.load_compiled(&bytes[..bytes.len() / 2]).is_err());
+
+    assert_eq!(engine.rule_count(), 1);
+    assert_eq!(firings(&engine, vec![order("north", "c-1", 1)]).len(), 1);
+}
+
+#[test]
+fn test_loaded_aggregations_accumulate_from_empty() {
+    let big_spender = Rule {
+        id: 1,
+        name: "Big spender".to_string(),
+        conditions: vec![Condition::Aggregation(AggregationCondition {
+            aggregation_type: AggregationType::Sum,
+            source_field: "amount".to_string(),
+            group_by: vec!["customer".to_string()],
+            having: Some(Box::new(Condition::Simple {
+                field: "total".to_string(),
+                operator: Operator::GreaterThan,
+                value: FactValue::Integer(100),
+            })),
+            alias: "total".to_string(),
+            window: None,
+        })],
+        actions: vec![Action {
+            action_type: ActionType::SetField {
+                field: "big_spender".to_string(),
+                value: FactValue::Boolean(true),
+            },
+        }],
+        metadata: Default::default(),
+    };
+    let compiled = BingoEngine::new().unwrap();
+    compiled.add_rule(big_spender).unwrap();
+    let loaded = BingoEngine::new().unwrap();
+    loaded.load_compiled(&compiled.compile_ruleset().to_bytes().unwrap()).unwrap();
+
+    assert!(firings(&loaded, vec![order("north", "c-1", 60)]).is_empty());
+    assert_eq!(firings(&loaded, vec![order("north", "c-1", 60)]).len(), 1);
+}
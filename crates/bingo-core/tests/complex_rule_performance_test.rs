@@ -50,6 +50,7 @@ fn create_calculation_rules(count: usize) -> Vec<Rule> {
                             value: FactValue::Boolean(true),
                         },
                     }],
+                    metadata: Default::default(),
                 },
                 1 => Rule {
                     id: i as u64 + 1000,
@@ -65,6 +66,7 @@ fn create_calculation_rules(count: usize) -> Vec<Rule> {
                             value: FactValue::Boolean(true),
                         },
                     }],
+                    metadata: Default::default(),
                 },
                 2 => Rule {
                     id: i as u64 + 1000,
@@ -80,6 +82,7 @@ fn create_calculation_rules(count: usize) -> Vec<Rule> {
                             value: FactValue::Boolean(true),
                         },
                     }],
+                    metadata: Default::default(),
                 },
                 _ => Rule {
                     id: i as u64 + 1000,
@@ -95,6 +98,7 @@ fn create_calculation_rules(count: usize) -> Vec<Rule> {
                             value: FactValue::Boolean(true),
                         },
                     }],
+                    metadata: Default::default(),
                 },
             }
         } else {
@@ -133,6 +137,7 @@ fn create_calculation_rules(count: usize) -> Vec<Rule> {
                             },
                         },
                     ],
+                    metadata: Default::default(),
                 },
                 1 => Rule {
                     id: i as u64 + 1000,
@@ -167,6 +172,7 @@ fn create_calculation_rules(count: usize) -> Vec<Rule> {
                             },
                         },
                     ],
+                    metadata: Default::default(),
                 },
                 _ => Rule {
                     id: i as u64 + 1000,
@@ -201,6 +207,7 @@ fn create_calculation_rules(count: usize) -> Vec<Rule> {
                             },
                         },
                     ],
+                    metadata: Default::default(),
                 },
             }
         };
@@ -320,6 +327,7 @@ fn create_employee_facts(count: usize) -> Vec<Fact> {
                 id: i as u64,
                 external_id: None,
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: FactData { fields },
             }
         })
@@ -154,6 +154,7 @@ fn profile_rule_compilation(engine: &mut BingoEngine) {
                     value: FactValue::Boolean(true),
                 },
             }],
+            metadata: Default::default(),
         };
 
         engine.update_rule(updated_rule).expect("Failed to update rule");
@@ -484,6 +485,7 @@ fn create_large_fact_dataset(count: usize) -> Vec<Fact> {
             id: i as u64,
             external_id: Some(format!("fact_{i}")),
             timestamp: Utc::now(),
+            fact_type: None,
             data: FactData { fields },
         });
     }
@@ -509,6 +511,7 @@ fn create_simple_rules(count: usize) -> Vec<Rule> {
                     value: FactValue::Boolean(true),
                 },
             }],
+            metadata: Default::default(),
         });
     }
 
@@ -561,6 +564,7 @@ fn create_complex_rules(count: usize) -> Vec<Rule> {
                     },
                 },
             ],
+            metadata: Default::default(),
         });
     }
 
@@ -583,6 +587,7 @@ fn create_rete_test_rules() -> Vec<Rule> {
                     value: FactValue::Boolean(true),
                 },
             }],
+            metadata: Default::default(),
         },
         Rule {
             id: 2,
@@ -602,6 +607,7 @@ fn create_rete_test_rules() -> Vec<Rule> {
             actions: vec![Action {
                 action_type: ActionType::Log { message: "RETE multi-condition fired".to_string() },
             }],
+            metadata: Default::default(),
         },
     ]
 }
@@ -620,6 +626,7 @@ fn create_pattern_matching_facts(count: usize) -> Vec<Fact> {
             id: i as u64,
             external_id: Some(format!("pattern_{i}")),
             timestamp: Utc::now(),
+            fact_type: None,
             data: FactData { fields },
         });
     }
@@ -639,6 +646,7 @@ fn create_aggregation_rules() -> Vec<Rule> {
         actions: vec![Action {
             action_type: ActionType::Log { message: "High sum detected".to_string() },
         }],
+        metadata: Default::default(),
     }]
 }
 
@@ -662,6 +670,7 @@ fn create_aggregation_facts(count: usize) -> Vec<Fact> {
             id: i as u64,
             external_id: Some(format!("agg_{i}")),
             timestamp: Utc::now(),
+            fact_type: None,
             data: FactData { fields },
         });
     }
@@ -684,6 +693,7 @@ fn create_calculator_rules() -> Vec<Rule> {
                 output_field: "adjusted_amount".to_string(),
             },
         }],
+        metadata: Default::default(),
     }]
 }
 
@@ -699,6 +709,7 @@ fn create_calculator_facts(count: usize) -> Vec<Fact> {
             id: i as u64,
             external_id: Some(format!("calc_{i}")),
             timestamp: Utc::now(),
+            fact_type: None,
             data: FactData { fields },
         });
     }
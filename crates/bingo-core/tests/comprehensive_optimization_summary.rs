@@ -43,6 +43,7 @@ fn create_comprehensive_test_rules(count: usize) -> Vec<Rule> {
                         output_field: "performance_validation".to_string(),
                     },
                 }],
+                metadata: Default::default(),
             },
             1 => Rule {
                 id: i as u64 + 3000,
@@ -70,6 +71,7 @@ fn create_comprehensive_test_rules(count: usize) -> Vec<Rule> {
                         output_field: "salary_validation".to_string(),
                     },
                 }],
+                metadata: Default::default(),
             },
             2 => Rule {
                 id: i as u64 + 3000,
@@ -90,6 +92,7 @@ fn create_comprehensive_test_rules(count: usize) -> Vec<Rule> {
                         output_field: "hours_compliance".to_string(),
                     },
                 }],
+                metadata: Default::default(),
             },
             3 => Rule {
                 id: i as u64 + 3000,
@@ -112,6 +115,7 @@ fn create_comprehensive_test_rules(count: usize) -> Vec<Rule> {
                         },
                     },
                 ],
+                metadata: Default::default(),
             },
             4 => Rule {
                 id: i as u64 + 3000,
@@ -142,6 +146,7 @@ fn create_comprehensive_test_rules(count: usize) -> Vec<Rule> {
                         },
                     },
                 ],
+                metadata: Default::default(),
             },
             5 => Rule {
                 id: i as u64 + 3000,
@@ -177,6 +182,7 @@ fn create_comprehensive_test_rules(count: usize) -> Vec<Rule> {
                         },
                     },
                 ],
+                metadata: Default::default(),
             },
             _ => unreachable!(),
         };
@@ -267,6 +273,7 @@ fn create_comprehensive_test_facts(count: usize) -> Vec<Fact> {
                 id: i as u64,
                 external_id: None,
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: FactData { fields },
             }
         })
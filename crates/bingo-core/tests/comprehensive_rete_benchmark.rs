@@ -153,6 +153,7 @@ fn generate_benchmark_rules(count: usize) -> Vec<Rule> {
                         },
                     },
                 }],
+                metadata: Default::default(),
             }
         })
         .collect()
@@ -168,6 +169,7 @@ fn generate_benchmark_facts(count: usize, start_id: usize) -> Vec<Fact> {
                 id: fact_id as u64,
                 external_id: Some(format!("benchmark_{fact_id}")),
                 timestamp: Utc::now(),
+                fact_type: None,
                 data: FactData {
                     fields: HashMap::from([
                         (
@@ -25,6 +25,7 @@ fn test_concurrent_engine_basic() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).unwrap();
@@ -34,6 +35,7 @@ fn test_concurrent_engine_basic() {
         id: 1,
         external_id: None,
         timestamp: chrono::Utc::now(),
+        fact_type: None,
         data: FactData {
             fields: HashMap::from([(
                 "status".to_string(),
@@ -75,6 +77,7 @@ fn test_concurrent_fact_processing() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).unwrap();
@@ -108,6 +111,7 @@ fn test_concurrent_fact_processing() {
                         id: (thread_id * 1000 + i) as u64,
                         external_id: None,
                         timestamp: chrono::Utc::now(),
+                        fact_type: None,
                         data: FactData { fields },
                     }
                 })
@@ -195,6 +199,7 @@ fn test_concurrent_rule_access() {
                         value: FactValue::Boolean(true),
                     },
                 }],
+                metadata: Default::default(),
             };
 
             engine_writer.add_rule(rule).unwrap();
@@ -275,6 +280,7 @@ fn test_concurrent_performance_comparison() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).unwrap();
@@ -298,6 +304,7 @@ fn test_concurrent_performance_comparison() {
                 id: i as u64,
                 external_id: None,
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: FactData { fields },
             }
         })
@@ -337,6 +344,7 @@ fn test_concurrent_performance_comparison() {
                         id: (thread_id * 500 + i) as u64,
                         external_id: None,
                         timestamp: chrono::Utc::now(),
+                        fact_type: None,
                         data: FactData { fields },
                     }
                 })
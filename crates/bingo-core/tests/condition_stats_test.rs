@@ -0,0 +1,114 @@
+//! Condition Stats Test
+//!
+//! Validates that alpha nodes count the candidate facts their conditions are tested
+//! against, that never-matching conditions are reported and that observed
+//! selectivity can be fed to the rule optimizer.
+
+use bingo_core::BingoEngine;
+use bingo_core::types::*;
+use std::collections::HashMap;
+
+fn order_rule(id: u64, total_field: &str) -> Rule {
+    Rule {
+        id,
+        name: format!("Large active order ({total_field})"),
+        conditions: vec![
+            Condition::Simple {
+                field: "status".to_string(),
+                operator: Operator::Equal,
+                value: FactValue::String("active".to_string()),
+            },
+            Condition::Simple {
+                field: total_field.to_string(),
+                operator: Operator::GreaterThan,
+                value: FactValue::Integer(100),
+            },
+        ],
+        actions: vec![Action {
+            action_type: ActionType::Log { message: "Large order".to_string() },
+        }],
+        metadata: Default::default(),
+    }
+}
+
+fn order_fact(id: u64, status: &str, total: i64) -> Fact {
+    let mut fields = HashMap::new();
+    fields.insert("status".to_string(), FactValue::String(status.to_string()));
+    fields.insert("order_total".to_string(), FactValue::Integer(total));
+    Fact::new(id, FactData { fields })
+}
+
+fn orders_engine() -> BingoEngine {
+    let engine = BingoEngine::new().unwrap();
+    // Rule 1 misspells the field it compares
+    engine.add_rule(order_rule(1, "order_totl")).unwrap();
+    engine.add_rule(order_rule(2, "order_total")).unwrap();
+    engine
+}
+
+fn orders() -> Vec<Fact> {
+    vec![
+        order_fact(1, "active", 50),
+        order_fact(2, "active", 150),
+        order_fact(3, "active", 200),
+        order_fact(4, "inactive", 500),
+    ]
+}
+
+#[test]
+fn test_alpha_nodes_count_evaluations() {
+    let engine = orders_engine();
+    let results = engine.process_facts(orders()).unwrap();
+    assert_eq!(results.len(), 2);
+
+    let stats = engine.get_condition_stats();
+    assert_eq!(stats.len(), 3);
+    let counts = |field: &str| {
+        let stats = stats.iter().find(|stats| stats.field == field).unwrap();
+        (stats.evaluations, stats.matches)
+    };
+
+    // The shared status node counts the candidate facts of both rules
+    assert_eq!(counts("status"), (7, 6));
+    assert_eq!(counts("order_total"), (4, 3));
+    assert_eq!(counts("order_totl"), (3, 0));
+    assert_eq!(
+        stats.iter().find(|stats| stats.field == "status").unwrap().rule_ids,
+        vec![1, 2]
+    );
+}
+
+#[test]
+fn test_never_matching_conditions_are_reported() {
+    let engine = orders_engine();
+    engine.process_facts(orders()).unwrap();
+
+    let report = engine.unmatched_conditions();
+    assert_eq!(report.conditions_tracked, 3);
+    assert_eq!(report.conditions.len(), 1);
+    assert_eq!(report.conditions[0].field, "order_totl");
+    assert!(
+        report
+            .to_string()
+            .contains("order_totl > 100 (rules 1): 0 of 3 evaluations matched")
+    );
+
+    // Sessions copied from a template start counting from zero
+    let session = BingoEngine::from_template(&engine).unwrap();
+    assert!(session.get_condition_stats().iter().all(|stats| stats.evaluations == 0));
+    assert_eq!(session.unmatched_conditions().conditions.len(), 3);
+}
+
+#[test]
+fn test_selectivity_feedback_covers_evaluated_conditions() {
+    let engine = orders_engine();
+    assert_eq!(engine.apply_selectivity_feedback(), 0);
+
+    engine.process_facts(orders()).unwrap();
+    assert_eq!(engine.apply_selectivity_feedback(), 3);
+
+    // Recompiling keeps the ruleset working with the observed statistics
+    engine.update_rule(order_rule(1, "order_total")).unwrap();
+    let results = engine.process_facts(vec![order_fact(5, "active", 300)]).unwrap();
+    assert_eq!(results.len(), 2);
+}
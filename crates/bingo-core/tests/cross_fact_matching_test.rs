@@ -34,6 +34,7 @@ fn test_cross_fact_pattern_matching() {
                 message: "High value customer order detected".to_string(),
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).expect("Rule addition failed");
@@ -94,6 +95,7 @@ fn test_beta_network_partial_matches() {
         actions: vec![Action {
             action_type: ActionType::Log { message: "Complex rule triggered".to_string() },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).expect("Rule addition failed");
@@ -155,6 +157,7 @@ fn test_beta_network_structure_creation() {
         actions: vec![Action {
             action_type: ActionType::Log { message: "Beta network structure test".to_string() },
         }],
+        metadata: Default::default(),
     };
 
     // Adding the rule should create beta network structure
@@ -201,6 +204,7 @@ fn test_alpha_beta_integration() {
         actions: vec![Action {
             action_type: ActionType::Log { message: "Single condition matched".to_string() },
         }],
+        metadata: Default::default(),
     };
 
     let multi_rule = Rule {
@@ -221,6 +225,7 @@ fn test_alpha_beta_integration() {
         actions: vec![Action {
             action_type: ActionType::Log { message: "Multi condition matched".to_string() },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(single_rule).expect("Single rule addition failed");
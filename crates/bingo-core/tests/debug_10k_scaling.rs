@@ -27,6 +27,7 @@ fn test_10k_fact_scaling() {
                     value: FactValue::Boolean(true),
                 },
             }],
+            metadata: Default::default(),
         };
 
         engine.add_rule(rule).unwrap();
@@ -52,6 +53,7 @@ fn test_10k_fact_scaling() {
                     id: i as u64,
                     external_id: None,
                     timestamp: chrono::Utc::now(),
+                    fact_type: None,
                     data: FactData { fields },
                 }
             })
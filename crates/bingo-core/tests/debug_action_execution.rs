@@ -23,6 +23,7 @@ fn debug_simple_action_execution() {
         actions: vec![Action {
             action_type: ActionType::Log { message: "This is a test log message".to_string() },
         }],
+        metadata: Default::default(),
     };
 
     println!("🔍 Debug: Adding rule to engine");
@@ -38,6 +39,7 @@ fn debug_simple_action_execution() {
         id: 1,
         external_id: Some("test-fact-1".to_string()),
         timestamp: Utc::now(),
+        fact_type: None,
         data: FactData { fields },
     };
 
@@ -87,6 +89,7 @@ fn debug_increment_field_execution() {
                 increment: FactValue::Integer(10),
             },
         }],
+        metadata: Default::default(),
     };
 
     println!("🔍 Debug: Adding IncrementField rule to engine");
@@ -100,6 +103,7 @@ fn debug_increment_field_execution() {
         id: 1,
         external_id: Some("alice-1".to_string()),
         timestamp: Utc::now(),
+        fact_type: None,
         data: FactData { fields },
     };
 
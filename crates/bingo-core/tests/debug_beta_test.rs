@@ -29,6 +29,7 @@ fn debug_beta_network_issue() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
     engine.add_rule(rule).unwrap();
 
@@ -39,6 +40,7 @@ fn debug_beta_network_issue() {
         id: 1,
         external_id: None,
         timestamp: chrono::Utc::now(),
+        fact_type: None,
         data: FactData {
             fields: [
                 ("user_id".to_string(), FactValue::Integer(102)), // Extra field like in original test
@@ -89,6 +91,7 @@ fn debug_single_condition_test() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
     engine.add_rule(rule1).unwrap();
 
@@ -107,6 +110,7 @@ fn debug_single_condition_test() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
     engine.add_rule(rule2).unwrap();
 
@@ -117,6 +121,7 @@ fn debug_single_condition_test() {
         id: 1,
         external_id: None,
         timestamp: chrono::Utc::now(),
+        fact_type: None,
         data: FactData {
             fields: [
                 ("age".to_string(), FactValue::Integer(16)),
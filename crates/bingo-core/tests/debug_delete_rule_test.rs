@@ -9,7 +9,13 @@ fn create_fact(id: u64, name: &str) -> Fact {
     let mut fields = HashMap::new();
     fields.insert("user_id".to_string(), FactValue::Integer(id as i64));
     fields.insert("name".to_string(), FactValue::String(name.to_string()));
-    Fact { id, external_id: None, timestamp: Utc::now(), data: FactData { fields } }
+    Fact {
+        id,
+        external_id: None,
+        timestamp: Utc::now(),
+        fact_type: None,
+        data: FactData { fields },
+    }
 }
 
 #[test]
@@ -27,6 +33,7 @@ fn delete_fact_rule_test() {
         actions: vec![Action {
             action_type: ActionType::DeleteFact { fact_id_field: "user_id".to_string() },
         }],
+        metadata: Default::default(),
     };
     engine.add_rule(rule).unwrap();
 
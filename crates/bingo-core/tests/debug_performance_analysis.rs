@@ -28,6 +28,7 @@ fn debug_performance_scaling() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
     engine.add_rule(rule).unwrap();
     println!("✅ Rule addition: {:?}", start.elapsed());
@@ -55,6 +56,7 @@ fn debug_performance_scaling() {
                     id: i as u64,
                     external_id: None,
                     timestamp: chrono::Utc::now(),
+                    fact_type: None,
                     data: FactData { fields },
                 }
             })
@@ -22,6 +22,7 @@ fn test_debug_rule_matching() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).unwrap();
@@ -41,6 +42,7 @@ fn test_debug_rule_matching() {
                 id: i as u64,
                 external_id: None,
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: FactData { fields },
             }
         })
@@ -85,7 +87,11 @@ fn test_debug_rule_matching() {
 
     // Check that the correct facts matched
     let matched_fact_ids: Vec<u64> = results.iter().map(|r| r.fact_id).collect();
-    assert!(matched_fact_ids.contains(&0), "Fact 0 should match");
+    // Fact 0 asks for a generated ID, which starts above the client range
+    assert!(
+        matched_fact_ids.contains(&constants::fact_ids::FIRST_GENERATED_FACT_ID),
+        "Fact 0 should match"
+    );
     assert!(matched_fact_ids.contains(&3), "Fact 3 should match");
     assert!(matched_fact_ids.contains(&6), "Fact 6 should match");
     assert!(matched_fact_ids.contains(&9), "Fact 9 should match");
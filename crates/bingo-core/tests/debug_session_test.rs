@@ -0,0 +1,105 @@
+//! Debug Session Test
+//!
+//! Validates the interactive step-debugger: facts are processed one step at a time,
+//! resuming pauses after the step that reaches a rule or node breakpoint, each step
+//! reports the alpha and beta memories it changed, and stepping never changes the
+//! engine's working memory.
+
+use bingo_core::*;
+use std::collections::HashMap;
+
+fn order(amount: i64, status: &str) -> Fact {
+    let fields = HashMap::from([
+        ("amount".to_string(), FactValue::Integer(amount)),
+        ("status".to_string(), FactValue::String(status.to_string())),
+    ]);
+    Fact::new(0, FactData { fields })
+}
+
+fn engine() -> BingoEngine {
+    let engine = BingoEngine::new().unwrap();
+    engine
+        .add_rules_from_dsl(
+            r#"
+            rule "Large order" id 1 when amount > 100 then set flagged = true
+            rule "Open large order" id 2 when amount > 100 and status == "open" then set review = true
+            "#,
+        )
+        .unwrap();
+    engine
+}
+
+#[test]
+fn test_resume_pauses_at_rule_breakpoint() {
+    let engine = engine();
+    let mut session = engine.debug_session().unwrap();
+    session.queue([
+        order(50, "open"),
+        order(250, "closed"),
+        order(300, "open"),
+        order(400, "open"),
+    ]);
+    session.set_breakpoint(DebugBreakpoint::Rule(2));
+
+    let steps = session.resume().unwrap();
+    assert_eq!(steps.len(), 3);
+    assert!(steps[0].results.is_empty());
+    assert_eq!(
+        steps[1].results.iter().map(|r| r.rule_id).collect::<Vec<_>>(),
+        [1]
+    );
+    let paused = &steps[2];
+    assert_eq!(paused.step, 3);
+    assert_eq!(paused.breakpoints_hit, [DebugBreakpoint::Rule(2)]);
+    assert_eq!(paused.alpha_nodes.len(), 2);
+    assert!(!paused.beta_nodes.is_empty());
+    assert_eq!(session.pending_count(), 1);
+
+    // Memories hold the facts stepped so far
+    let memory = session.memory();
+    let amount = memory.alpha.iter().find(|alpha| alpha.condition == "amount > 100").unwrap();
+    assert_eq!(amount.fact_ids, [steps[1].fact.id, paused.fact.id]);
+    let open = memory
+        .alpha
+        .iter()
+        .find(|alpha| alpha.condition == "status == \"open\"")
+        .unwrap();
+    assert_eq!(open.fact_ids, [steps[0].fact.id, paused.fact.id]);
+    let rule_2_tokens: Vec<&Vec<u64>> = memory
+        .beta
+        .iter()
+        .filter(|beta| beta.rule_ids == [2])
+        .flat_map(|beta| &beta.tokens)
+        .collect();
+    assert_eq!(rule_2_tokens, [&vec![paused.fact.id]]);
+    assert!(memory.beta.iter().any(|beta| paused.beta_nodes == [beta.node_id]));
+
+    let last = session.step().unwrap().unwrap();
+    assert_eq!(last.breakpoints_hit, [DebugBreakpoint::Rule(2)]);
+    assert!(session.step().unwrap().is_none());
+    assert_eq!(engine.fact_count(), 0);
+}
+
+#[test]
+fn test_node_breakpoints() {
+    let engine = engine();
+    let mut session = engine.debug_session().unwrap();
+    let open = session
+        .memory()
+        .alpha
+        .iter()
+        .find(|alpha| alpha.condition == "status == \"open\"")
+        .unwrap()
+        .node_id;
+    session.set_breakpoint(DebugBreakpoint::AlphaNode(open));
+    session.queue([order(250, "closed"), order(10, "open")]);
+
+    let steps = session.resume().unwrap();
+    assert_eq!(steps.len(), 2);
+    assert_eq!(steps[1].breakpoints_hit, [DebugBreakpoint::AlphaNode(open)]);
+
+    assert!(session.clear_breakpoint(DebugBreakpoint::AlphaNode(open)));
+    assert_eq!(session.breakpoints().count(), 0);
+    session.queue([order(20, "open")]);
+    assert!(session.resume().unwrap()[0].breakpoints_hit.is_empty());
+}
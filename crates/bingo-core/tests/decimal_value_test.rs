@@ -0,0 +1,165 @@
+//! Decimal Value Test
+//!
+//! Validates that `FactValue::Decimal` amounts compare exactly in conditions, sum to
+//! exact totals in aggregations, stay exact in formulas and survive serialization
+//! without picking up binary floating point error.
+
+use bingo_core::BingoEngine;
+use bingo_core::rete_nodes::evaluate_formula_expression;
+use bingo_core::types::*;
+use std::collections::HashMap;
+
+fn decimal(value: &str) -> FactValue {
+    FactValue::Decimal(value.parse().unwrap())
+}
+
+fn pay_fact(id: u64, employee_id: i64, pay: FactValue) -> Fact {
+    let mut fields = HashMap::new();
+    fields.insert("employee_id".to_string(), FactValue::Integer(employee_id));
+    fields.insert("pay".to_string(), pay);
+    Fact::new(id, FactData { fields })
+}
+
+fn log_rule(id: u64, condition: Condition) -> Rule {
+    Rule {
+        id,
+        name: format!("Pay rule {id}"),
+        conditions: vec![condition],
+        actions: vec![Action { action_type: ActionType::Log { message: "matched".to_string() } }],
+        metadata: Default::default(),
+    }
+}
+
+fn pay_total_rule(expected_total: FactValue) -> Rule {
+    log_rule(
+        1,
+        Condition::Aggregation(AggregationCondition {
+            aggregation_type: AggregationType::Sum,
+            source_field: "pay".to_string(),
+            group_by: vec!["employee_id".to_string()],
+            having: Some(Box::new(Condition::Simple {
+                field: "total_pay".to_string(),
+                operator: Operator::Equal,
+                value: expected_total,
+            })),
+            alias: "total_pay".to_string(),
+            window: None,
+        }),
+    )
+}
+
+#[test]
+fn test_decimal_conditions_compare_exactly() {
+    let engine = BingoEngine::new().unwrap();
+    engine
+        .add_rule(log_rule(
+            1,
+            Condition::Simple {
+                field: "pay".to_string(),
+                operator: Operator::GreaterThan,
+                value: decimal("1000.00"),
+            },
+        ))
+        .unwrap();
+    engine
+        .add_rule(log_rule(
+            2,
+            Condition::Simple {
+                field: "pay".to_string(),
+                operator: Operator::LessThanOrEqual,
+                value: FactValue::Integer(1000),
+            },
+        ))
+        .unwrap();
+
+    let results = engine
+        .process_facts(vec![
+            pay_fact(1, 7, decimal("1000.00")),
+            pay_fact(2, 7, decimal("1000.01")),
+        ])
+        .unwrap();
+
+    let mut matches: Vec<(u64, u64)> =
+        results.iter().map(|result| (result.rule_id, result.fact_id)).collect();
+    matches.sort_unstable();
+    assert_eq!(matches, vec![(1, 2), (2, 1)]);
+}
+
+#[test]
+fn test_decimal_sum_has_no_rounding_error() {
+    let cents = || (1..=3).map(|id| pay_fact(id, 7, decimal("0.10"))).collect::<Vec<_>>();
+
+    let engine = BingoEngine::new().unwrap();
+    engine.add_rule(pay_total_rule(decimal("0.30"))).unwrap();
+    let results = engine.process_facts(cents()).unwrap();
+    assert!(
+        !results.is_empty(),
+        "0.10 + 0.10 + 0.10 should equal 0.30 exactly"
+    );
+
+    // The same amounts as floats sum to 0.30000000000000004 and never match
+    let engine = BingoEngine::new().unwrap();
+    engine.add_rule(pay_total_rule(FactValue::Float(0.3))).unwrap();
+    let floats = (1..=3).map(|id| pay_fact(id, 7, FactValue::Float(0.1))).collect();
+    assert!(engine.process_facts(floats).unwrap().is_empty());
+}
+
+#[test]
+fn test_decimal_formula_arithmetic_is_exact() {
+    let mut fields = HashMap::new();
+    fields.insert("pay".to_string(), decimal("100.10"));
+    fields.insert("bonus".to_string(), decimal("0.20"));
+
+    assert_eq!(
+        evaluate_formula_expression("pay * 1.1", &fields).unwrap(),
+        decimal("110.11")
+    );
+    assert_eq!(
+        evaluate_formula_expression("pay + bonus", &fields).unwrap(),
+        decimal("100.30")
+    );
+    assert_eq!(
+        evaluate_formula_expression("pay / 4", &fields).unwrap(),
+        decimal("25.025")
+    );
+    assert!(evaluate_formula_expression("pay / 0", &fields).is_err());
+}
+
+#[test]
+fn test_decimal_serialization_round_trip() {
+    let value = decimal("1234.50");
+
+    let serialized = serde_json::to_string(&value).unwrap();
+    assert_eq!(serialized, r#"{"Decimal":"1234.50"}"#);
+    assert_eq!(
+        serde_json::from_str::<FactValue>(&serialized).unwrap(),
+        value
+    );
+
+    // Plain JSON carries the exact digits as a string
+    assert_eq!(
+        serde_json::Value::from(&value),
+        serde_json::json!("1234.50")
+    );
+}
+
+#[test]
+fn test_decimal_rounding_modes() {
+    let value = decimal("2.675");
+    assert_eq!(
+        value.round(DecimalRounding::new(2, RoundingMode::HalfUp)),
+        decimal("2.68")
+    );
+    assert_eq!(
+        value.round(DecimalRounding::new(2, RoundingMode::Down)),
+        decimal("2.67")
+    );
+    assert_eq!(
+        decimal("2.665").round(DecimalRounding::default()),
+        decimal("2.66")
+    );
+    assert_eq!(
+        FactValue::Float(2.675).round(DecimalRounding::default()),
+        FactValue::Float(2.675)
+    );
+}
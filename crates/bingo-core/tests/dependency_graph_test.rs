@@ -0,0 +1,77 @@
+//! Dependency Graph Test
+//!
+//! Validates the engine's rule/field dependency graph: impact queries for a field,
+//! fact type nodes, and the JSON and DOT exports.
+
+use bingo_core::{BingoEngine, FieldAccess, GraphNodeKind, RuleFieldGraph};
+
+const RULES: &str = r#"
+rule "Final pay" id 1
+when type == "employee" and employee.termination_date older_than duration "P30D"
+then
+    formula final_pay = "salary * 2";
+    create { type: "payslip", kind: "final" }
+end
+
+rule "Leaver notice" id 2
+when employee.termination_date within duration "P7D"
+then log "Leaving soon"
+end
+
+rule "Overtime" id 3
+when hours > 40
+then set overtime = true
+end
+"#;
+
+#[test]
+fn test_rules_using_a_field() {
+    let engine = BingoEngine::new().unwrap();
+    engine.add_rules_from_dsl(RULES).unwrap();
+
+    let graph = engine.dependency_graph();
+    assert_eq!(
+        graph.rules_using_field("employee.termination_date"),
+        vec![1, 2]
+    );
+    assert_eq!(graph.rules_using_field("salary"), vec![1]);
+    assert_eq!(graph.rules_using_field("overtime"), vec![3]);
+    assert_eq!(graph.rules_using_fact_type("employee"), vec![1]);
+    assert_eq!(graph.rules_using_fact_type("payslip"), vec![1]);
+
+    let final_pay_writes: Vec<&str> = graph
+        .edges
+        .iter()
+        .filter(|edge| edge.rule_id == 1 && edge.access == FieldAccess::Writes)
+        .map(|edge| edge.target.as_str())
+        .collect();
+    assert_eq!(
+        final_pay_writes,
+        vec!["field:final_pay", "field:kind", "field:type", "type:payslip"]
+    );
+}
+
+#[test]
+fn test_graph_exports() {
+    let engine = BingoEngine::new().unwrap();
+    engine.add_rules_from_dsl(RULES).unwrap();
+    let graph = engine.dependency_graph();
+
+    let json = graph.to_json().unwrap();
+    let parsed: RuleFieldGraph = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, graph);
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["nodes"][0]["kind"], "rule");
+    assert_eq!(value["nodes"][0]["label"], "Final pay");
+
+    let dot = graph.to_dot();
+    assert!(dot.starts_with("digraph RuleFields {"));
+    assert!(dot.contains(r#""field:hours" -> "rule:3" [label="reads"];"#));
+    assert!(dot.contains(r#""rule:3" -> "field:overtime" [label="writes"];"#));
+    assert!(
+        graph
+            .nodes
+            .iter()
+            .any(|node| node.kind == GraphNodeKind::FactType && node.label == "employee")
+    );
+}
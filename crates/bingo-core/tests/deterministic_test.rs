@@ -0,0 +1,132 @@
+//! Deterministic Execution Test
+//!
+//! Validates that with deterministic execution enabled, reruns of the same facts and
+//! rules on fresh engines encode to identical bytes, rules fire in rule ID order in
+//! working memory processing, and created facts are dated by their triggering fact.
+
+use bingo_core::rete_nodes::ActionResult;
+use bingo_core::types::*;
+use bingo_core::{BingoEngine, ForwardChaining, RuleMetadata, encode_results};
+use chrono::{DateTime, TimeZone, Utc};
+use std::collections::HashMap;
+
+fn worked_on() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 3, 4, 9, 0, 0).unwrap()
+}
+
+fn rule(id: RuleId, condition: Condition, action_type: ActionType) -> Rule {
+    Rule {
+        id,
+        name: format!("Rule {id}"),
+        conditions: vec![condition],
+        actions: vec![Action { action_type }],
+        metadata: RuleMetadata::default(),
+    }
+}
+
+fn greater_than(field: &str, value: i64) -> Condition {
+    Condition::Simple {
+        field: field.to_string(),
+        operator: Operator::GreaterThan,
+        value: FactValue::Integer(value),
+    }
+}
+
+fn set(field: &str) -> ActionType {
+    ActionType::SetField { field: field.to_string(), value: FactValue::Boolean(true) }
+}
+
+fn rules() -> Vec<Rule> {
+    let review = FactData {
+        fields: HashMap::from([
+            ("kind".to_string(), FactValue::String("review".to_string())),
+            ("priority".to_string(), FactValue::Integer(2)),
+            (
+                "queue".to_string(),
+                FactValue::String("payroll".to_string()),
+            ),
+        ]),
+    };
+    vec![
+        rule(7, greater_than("hours", 8), set("overtime")),
+        rule(
+            3,
+            greater_than("hours", 10),
+            ActionType::CreateFact { data: review },
+        ),
+        rule(5, greater_than("hours", 0), set("paid")),
+        rule(9, greater_than("priority", 1), set("escalated")),
+    ]
+}
+
+fn shifts() -> Vec<Fact> {
+    [12, 9, 4, 11]
+        .into_iter()
+        .enumerate()
+        .map(|(i, hours)| {
+            let fields = HashMap::from([
+                ("hours".to_string(), FactValue::Integer(hours)),
+                ("employee".to_string(), FactValue::Integer(i as i64)),
+            ]);
+            Fact { timestamp: worked_on(), ..Fact::new(i as u64 + 1, FactData { fields }) }
+        })
+        .collect()
+}
+
+fn deterministic_engine() -> BingoEngine {
+    let engine = BingoEngine::new().unwrap();
+    engine.set_deterministic(true);
+    engine.set_forward_chaining(Some(ForwardChaining::default()));
+    engine.add_rules(rules()).unwrap();
+    engine
+}
+
+#[test]
+fn test_reruns_encode_to_identical_bytes() {
+    let engine = deterministic_engine();
+    assert!(engine.is_deterministic());
+    let results = engine.process_facts(shifts()).unwrap();
+    let first = encode_results(&results).unwrap();
+
+    for _ in 0..5 {
+        let rerun = deterministic_engine().process_facts(shifts()).unwrap();
+        assert_eq!(encode_results(&rerun).unwrap(), first);
+    }
+
+    // Created facts are dated by the shift that triggered them, so rules matching
+    // them fire for the same date on every run
+    let created: Vec<FactId> = results
+        .iter()
+        .flat_map(|result| &result.actions_executed)
+        .filter_map(|action| match action {
+            ActionResult::FactCreated { fact_id, .. } => Some(*fact_id),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(created.len(), 1);
+    for fact_id in created {
+        assert_eq!(engine.get_fact(fact_id).unwrap().timestamp, worked_on());
+    }
+
+    let text = String::from_utf8(first).unwrap();
+    assert_eq!(text.lines().count(), 10);
+    let first_shift: Vec<RuleId> = results[..3].iter().map(|result| result.rule_id).collect();
+    assert_eq!(first_shift, [3, 5, 7]);
+    assert!(text.lines().next().unwrap().starts_with(r#"{"actions":"#));
+}
+
+#[test]
+fn test_working_memory_fires_in_rule_id_order() {
+    let engine = deterministic_engine();
+    for shift in shifts() {
+        let fired: Vec<RuleId> = engine
+            .add_fact_to_working_memory(shift)
+            .unwrap()
+            .iter()
+            .map(|result| result.rule_id)
+            .collect();
+        let mut ordered = fired.clone();
+        ordered.sort_unstable();
+        assert_eq!(fired, ordered);
+    }
+}
@@ -0,0 +1,115 @@
+//! Duplicate Rule Test
+//!
+//! Validates that `add_rule` rejects or replaces rules duplicating an existing rule's
+//! ID or its conditions and actions as the duplicate rule policy says, and that the
+//! engine lists duplicate and near-duplicate rules.
+
+use bingo_core::types::*;
+use bingo_core::{BingoEngine, BingoError, DuplicateKind, DuplicateRulePolicy, parse_rule};
+use std::collections::HashMap;
+
+fn rule(source: &str) -> Rule {
+    parse_rule(source).unwrap()
+}
+
+fn large_order(id: u64) -> Rule {
+    rule(&format!(
+        r#"rule "Large order {id}" id {id} when amount > 100 then set flagged = true"#
+    ))
+}
+
+fn order(id: u64) -> Fact {
+    let fields = HashMap::from([("amount".to_string(), FactValue::Integer(250))]);
+    Fact::new(id, FactData { fields })
+}
+
+#[test]
+fn test_duplicates_are_allowed_by_default_and_listed() {
+    let engine = BingoEngine::new().unwrap();
+    assert_eq!(engine.duplicate_rule_policy(), DuplicateRulePolicy::Allow);
+    engine.add_rule(large_order(1)).unwrap();
+    engine.add_rule(large_order(2)).unwrap();
+    engine
+        .add_rule(rule(
+            r#"rule "Big order" id 3 when amount > 100 then set big = true"#,
+        ))
+        .unwrap();
+
+    // Identical rules fire twice for the same fact
+    assert_eq!(engine.process_facts(vec![order(1)]).unwrap().len(), 3);
+
+    let duplicates = engine.duplicate_rules();
+    let groups: Vec<_> =
+        duplicates.iter().map(|group| (group.kind, group.rule_ids.clone())).collect();
+    assert_eq!(
+        groups,
+        vec![
+            (DuplicateKind::Identical, vec![1, 2]),
+            (DuplicateKind::SameConditions, vec![1, 3]),
+        ]
+    );
+    assert_eq!(
+        duplicates[0].rule_names,
+        vec!["Large order 1", "Large order 2"]
+    );
+}
+
+#[test]
+fn test_reject_policy_refuses_duplicates() {
+    let engine = BingoEngine::new().unwrap();
+    engine.set_duplicate_rule_policy(DuplicateRulePolicy::Reject);
+    engine.add_rule(large_order(1)).unwrap();
+
+    let same_id = rule(r#"rule "Other" id 1 when status == "open" then set open = true"#);
+    match engine.add_rule(same_id).unwrap_err() {
+        BingoError::Rule { rule_id, details, .. } => {
+            assert_eq!(rule_id, Some(1));
+            assert_eq!(details.as_deref(), Some("rule 1 has the same ID"));
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+    let error = engine.add_rule(large_order(2)).unwrap_err();
+    assert!(
+        error.to_string().contains("duplicates an existing rule"),
+        "{error}"
+    );
+
+    // Rules sharing only their conditions do different things and are accepted
+    engine
+        .add_rule(rule(
+            r#"rule "Big order" id 3 when amount > 100 then set big = true"#,
+        ))
+        .unwrap();
+    assert_eq!(engine.rule_count(), 2);
+}
+
+#[test]
+fn test_replace_policy_swaps_in_the_new_rule() {
+    let engine = BingoEngine::new().unwrap();
+    engine.set_duplicate_rule_policy(DuplicateRulePolicy::Replace);
+    engine.add_rule(large_order(1)).unwrap();
+    engine
+        .add_rule(rule(
+            r#"rule "Open" id 2 when status == "open" then set open = true"#,
+        ))
+        .unwrap();
+    let version = engine.ruleset_version();
+
+    engine.add_rule(large_order(5)).unwrap();
+    let ids: Vec<u64> = engine.rules().iter().map(|rule| rule.id).collect();
+    assert_eq!(ids, vec![5, 2]);
+    assert!(engine.ruleset_version() > version);
+
+    let results = engine.process_facts(vec![order(1)]).unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].rule_id, 5);
+
+    engine
+        .add_rule(rule(
+            r#"rule "Open v2" id 2 when status == "open" then set opened = true"#,
+        ))
+        .unwrap();
+    assert_eq!(engine.get_rule(2).unwrap().name, "Open v2");
+    assert_eq!(engine.rule_count(), 2);
+    assert!(engine.duplicate_rules().is_empty());
+}
@@ -0,0 +1,191 @@
+//! Effective Dating Test
+//!
+//! Validates that batches evaluated as of a past date, or as of each fact's event
+//! time, fire the rule versions and use the reference table versions in effect at
+//! that date, as a retroactive payroll recalculation needs.
+
+use bingo_core::rete_nodes::ActionResult;
+use bingo_core::types::*;
+use bingo_core::{BingoEngine, EvaluationDate, ReferenceTable, RuleExecutionResult, RuleMetadata};
+use chrono::{DateTime, TimeZone, Utc};
+use std::collections::HashMap;
+
+fn date(year: i32, month: u32, day: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(year, month, day, 0, 0, 0).unwrap()
+}
+
+fn shift(region: &str, hours: i64, worked_on: DateTime<Utc>) -> Fact {
+    let fields = HashMap::from([
+        ("region".to_string(), FactValue::String(region.to_string())),
+        ("hours".to_string(), FactValue::Integer(hours)),
+    ]);
+    Fact { timestamp: worked_on, ..Fact::new(0, FactData { fields }) }
+}
+
+/// Version of the pay rule valid from `from` until `until`, multiplying hours by the
+/// regional rate
+fn pay_rule(id: RuleId, from: DateTime<Utc>, until: Option<DateTime<Utc>>) -> Rule {
+    Rule {
+        id,
+        name: format!("Pay v{id}"),
+        conditions: vec![Condition::Simple {
+            field: "hours".to_string(),
+            operator: Operator::GreaterThan,
+            value: FactValue::Integer(0),
+        }],
+        actions: vec![Action {
+            action_type: ActionType::CallCalculator {
+                calculator_name: "multiply".to_string(),
+                input_mapping: HashMap::from([
+                    ("a".to_string(), "hours".to_string()),
+                    ("b".to_string(), "hourly_rates[region]".to_string()),
+                ]),
+                output_field: "pay".to_string(),
+            },
+        }],
+        metadata: RuleMetadata {
+            effective_date: Some(from),
+            expiry_date: until,
+            ..Default::default()
+        },
+    }
+}
+
+fn rates(rate: f64) -> ReferenceTable {
+    ReferenceTable::from_entries([(
+        FactValue::String("north".to_string()),
+        FactValue::Float(rate),
+    )])
+}
+
+/// Engine paying 20 an hour in 2024 and 25 from 2025, with the 2025 rule version
+/// replacing the 2024 one
+fn payroll_engine() -> BingoEngine {
+    let engine = BingoEngine::new().unwrap();
+    let new_year = date(2025, 1, 1);
+    engine
+        .register_reference_table_version(
+            "hourly_rates",
+            rates(20.0).valid_between(Some(date(2024, 1, 1)), Some(new_year)),
+        )
+        .unwrap();
+    engine
+        .register_reference_table_version(
+            "hourly_rates",
+            rates(25.0).valid_between(Some(new_year), None),
+        )
+        .unwrap();
+    engine.add_rule(pay_rule(1, date(2024, 1, 1), Some(new_year))).unwrap();
+    engine.add_rule(pay_rule(2, new_year, None)).unwrap();
+    engine
+}
+
+/// Rule and calculated pay of every firing, in fact order
+fn payslips(results: Vec<RuleExecutionResult>) -> Vec<(RuleId, FactValue)> {
+    let mut payslips: Vec<(u64, RuleId, FactValue)> = results
+        .into_iter()
+        .flat_map(|result| {
+            result.actions_executed.into_iter().filter_map(move |action| match action {
+                ActionResult::CalculatorResult { parsed_value, .. } => {
+                    Some((result.fact_id, result.rule_id, parsed_value))
+                }
+                _ => None,
+            })
+        })
+        .collect();
+    payslips.sort_by_key(|(fact_id, ..)| *fact_id);
+    payslips.into_iter().map(|(_, rule_id, pay)| (rule_id, pay)).collect()
+}
+
+#[test]
+fn test_batches_use_the_versions_in_effect_at_their_evaluation_date() {
+    let engine = payroll_engine();
+    let summer_2024 = date(2024, 7, 1);
+
+    // Rerunning last year's payroll applies last year's rule and rates
+    let results = engine
+        .process_facts_as_of(
+            vec![shift("north", 8, summer_2024)],
+            EvaluationDate::At(date(2024, 12, 31)),
+        )
+        .unwrap();
+    assert_eq!(payslips(results), [(1, FactValue::Float(160.0))]);
+
+    // Evaluated now, the same shift is paid under the current version
+    let results = engine.process_facts(vec![shift("north", 8, summer_2024)]).unwrap();
+    assert_eq!(payslips(results), [(2, FactValue::Float(200.0))]);
+
+    // Before any version took effect, nothing applies
+    let results = engine
+        .process_facts_as_of(
+            vec![shift("north", 8, summer_2024)],
+            EvaluationDate::At(date(2023, 6, 1)),
+        )
+        .unwrap();
+    assert!(results.is_empty());
+}
+
+#[test]
+fn test_each_fact_can_be_evaluated_as_of_its_event_time() {
+    let engine = payroll_engine();
+    let results = engine
+        .process_facts_as_of(
+            vec![shift("north", 8, date(2024, 12, 30)), shift("north", 8, date(2025, 1, 2))],
+            EvaluationDate::EventTime,
+        )
+        .unwrap();
+    assert_eq!(
+        payslips(results),
+        [(1, FactValue::Float(160.0)), (2, FactValue::Float(200.0))]
+    );
+
+    // The next batch is evaluated against the clock again
+    assert_eq!(
+        engine.process_facts(vec![shift("north", 1, date(2024, 12, 30))]).unwrap().len(),
+        1
+    );
+}
+
+#[test]
+fn test_dated_membership_lists() {
+    let engine = BingoEngine::new().unwrap();
+    let holidays = |days: &[&str], from, until| {
+        ReferenceTable::from_keys(days.iter().map(|day| FactValue::String(day.to_string())))
+            .valid_between(from, until)
+    };
+    engine
+        .register_reference_table_version(
+            "holidays",
+            holidays(&["12-25"], None, Some(date(2025, 1, 1))),
+        )
+        .unwrap();
+    engine
+        .register_reference_table_version(
+            "holidays",
+            holidays(&["12-25", "12-26"], Some(date(2025, 1, 1)), None),
+        )
+        .unwrap();
+    engine
+        .add_rules_from_dsl(
+            r#"rule "Holiday premium" id 1 when day in "holidays" then set premium = true"#,
+        )
+        .unwrap();
+
+    let boxing_day = |worked_on| {
+        let fields = HashMap::from([("day".to_string(), FactValue::String("12-26".to_string()))]);
+        Fact { timestamp: worked_on, ..Fact::new(0, FactData { fields }) }
+    };
+    let fired = engine
+        .process_facts_as_of(
+            vec![boxing_day(date(2024, 12, 26)), boxing_day(date(2025, 12, 26))],
+            EvaluationDate::EventTime,
+        )
+        .unwrap();
+    assert_eq!(fired.len(), 1);
+
+    // Versions that would never be in effect are rejected
+    let backwards = holidays(&[], Some(date(2026, 1, 1)), Some(date(2025, 1, 1)));
+    let error = engine.register_reference_table_version("holidays", backwards).unwrap_err();
+    assert!(error.to_string().contains("before it takes effect"));
+    assert_eq!(engine.reference_data().versions("holidays").len(), 2);
+}
@@ -214,6 +214,7 @@ fn add_payroll_rules(engine: &mut BingoEngine) {
                 output_field: "regular_hours".to_string(),
             },
         }],
+        metadata: Default::default(),
     };
 
     // Rule 2: Overtime calculation
@@ -244,6 +245,7 @@ fn add_payroll_rules(engine: &mut BingoEngine) {
                 output_field: "overtime_hours".to_string(),
             },
         }],
+        metadata: Default::default(),
     };
 
     // Rule 3: Gross pay calculation
@@ -267,6 +269,7 @@ fn add_payroll_rules(engine: &mut BingoEngine) {
                 output_field: "gross_pay".to_string(),
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(regular_hours_rule).unwrap();
@@ -313,6 +316,7 @@ fn create_employee_fact(
         id,
         external_id: Some(format!("emp-{id}")),
         timestamp: Utc::now(),
+        fact_type: None,
         data: FactData { fields },
     }
 }
@@ -382,6 +386,7 @@ fn add_order_rules(engine: &mut BingoEngine) {
                 output_field: "quantity_valid".to_string(),
             },
         }],
+        metadata: Default::default(),
     };
 
     // Rule 2: Total calculation
@@ -405,6 +410,7 @@ fn add_order_rules(engine: &mut BingoEngine) {
                 output_field: "subtotal".to_string(),
             },
         }],
+        metadata: Default::default(),
     };
 
     // Rule 3: Tax calculation
@@ -428,6 +434,7 @@ fn add_order_rules(engine: &mut BingoEngine) {
                 output_field: "tax_amount".to_string(),
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(validation_rule).unwrap();
@@ -470,6 +477,7 @@ fn create_order_fact(
         id,
         external_id: Some(format!("order-{id}")),
         timestamp: Utc::now(),
+        fact_type: None,
         data: FactData { fields },
     }
 }
@@ -535,6 +543,7 @@ fn add_risk_rules(engine: &mut BingoEngine) {
                 value: FactValue::String("high".to_string()),
             },
         }],
+        metadata: Default::default(),
     };
 
     // Rule 2: Risk score calculation
@@ -558,6 +567,7 @@ fn add_risk_rules(engine: &mut BingoEngine) {
                 output_field: "above_threshold".to_string(),
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(high_value_rule).unwrap();
@@ -585,6 +595,7 @@ fn create_risk_fact(id: u64, transaction_type: &str, amount: f64, threshold: f64
         id,
         external_id: Some(format!("txn-{id}")),
         timestamp: Utc::now(),
+        fact_type: None,
         data: FactData { fields },
     }
 }
@@ -652,6 +663,7 @@ fn add_validation_rules(engine: &mut BingoEngine) {
                 value: FactValue::String("validated".to_string()),
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(validation_rule).unwrap();
@@ -672,6 +684,7 @@ fn add_processing_rules(engine: &mut BingoEngine) {
                 value: FactValue::String("processed".to_string()),
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(processing_rule).unwrap();
@@ -692,6 +705,7 @@ fn add_notification_rules(engine: &mut BingoEngine) {
                 value: FactValue::String("completed".to_string()),
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(notification_rule).unwrap();
@@ -731,6 +745,7 @@ fn create_stage_fact_with_stage(id: u64, document: &str, stage: &str) -> Fact {
         id,
         external_id: Some(format!("doc-{id}")),
         timestamp: Utc::now(),
+        fact_type: None,
         data: FactData { fields },
     }
 }
@@ -786,6 +801,7 @@ fn add_reporting_rules(engine: &mut BingoEngine) {
                 output_field: "processed_amount".to_string(),
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(category_total_rule).unwrap();
@@ -817,6 +833,7 @@ fn create_transaction_fact(id: u64, category: &str, amount: f64) -> Fact {
         id,
         external_id: Some(format!("txn-{id}")),
         timestamp: Utc::now(),
+        fact_type: None,
         data: FactData { fields },
     }
 }
@@ -872,6 +889,7 @@ fn add_error_handling_rules(engine: &mut BingoEngine) {
                 output_field: "error_result".to_string(),
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(error_rule).unwrap();
@@ -893,6 +911,7 @@ fn create_error_fact(id: u64, amount: f64) -> Fact {
         id,
         external_id: Some(format!("error-{id}")),
         timestamp: Utc::now(),
+        fact_type: None,
         data: FactData { fields },
     }
 }
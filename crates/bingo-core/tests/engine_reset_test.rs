@@ -0,0 +1,80 @@
+//! Engine Reset Test
+//!
+//! Validates that resetting an engine leaves nothing of its previous user behind, so
+//! pooled engines can be handed from one session to the next, while calculators the
+//! engine was created with stay registered.
+
+use bingo_calculator::calculator::Calculator;
+use bingo_calculator::plugin::{CalculationResult, CalculatorPlugin};
+use bingo_core::BingoEngine;
+use bingo_core::rete_nodes::ActionResult;
+use bingo_core::types::*;
+use std::collections::HashMap;
+
+/// Always answers 42
+struct Answer;
+
+impl CalculatorPlugin for Answer {
+    fn name(&self) -> &str {
+        "answer"
+    }
+
+    fn calculate(&self, _args: &HashMap<String, &FactValue>) -> CalculationResult {
+        Ok(FactValue::Integer(42))
+    }
+}
+
+fn answer_rule() -> Rule {
+    Rule {
+        id: 1,
+        name: "Answer".to_string(),
+        conditions: vec![Condition::Simple {
+            field: "asked".to_string(),
+            operator: Operator::Equal,
+            value: FactValue::Boolean(true),
+        }],
+        actions: vec![Action {
+            action_type: ActionType::CallCalculator {
+                calculator_name: "answer".to_string(),
+                input_mapping: HashMap::new(),
+                output_field: "answer".to_string(),
+            },
+        }],
+        metadata: Default::default(),
+    }
+}
+
+fn question() -> Fact {
+    let fields = HashMap::from([("asked".to_string(), FactValue::Boolean(true))]);
+    Fact::new(1, FactData { fields })
+}
+
+#[test]
+fn test_reset_engine_starts_over_with_its_calculators() {
+    let mut calculator = Calculator::new();
+    calculator.register(Box::new(Answer));
+    let mut engine = BingoEngine::with_calculator(calculator).unwrap();
+    engine.add_rule(answer_rule()).unwrap();
+    engine.set_global("region", FactValue::String("north".to_string())).unwrap();
+    engine.disable_rule_group("seasonal");
+    engine.process_facts(vec![question()]).unwrap();
+
+    engine.reset().unwrap();
+
+    assert_eq!(engine.rule_count(), 0);
+    assert_eq!(engine.fact_count(), 0);
+    assert_eq!(engine.ruleset_version(), 0);
+    assert!(engine.globals().is_empty());
+    assert!(engine.disabled_rule_groups().is_empty());
+
+    engine.add_rule(answer_rule()).unwrap();
+    let results = engine.process_facts(vec![question()]).unwrap();
+    assert!(
+        matches!(
+            results[0].actions_executed.as_slice(),
+            [ActionResult::CalculatorResult { parsed_value: FactValue::Integer(42), .. }]
+        ),
+        "the custom calculator is still registered: {:?}",
+        results[0].actions_executed
+    );
+}
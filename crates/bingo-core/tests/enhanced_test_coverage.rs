@@ -17,6 +17,7 @@ fn create_test_fact(id: u64, fields: HashMap<String, FactValue>) -> Fact {
         id,
         external_id: Some(format!("test_{id}")),
         timestamp: Utc::now(),
+        fact_type: None,
         data: FactData { fields },
     }
 }
@@ -102,6 +103,7 @@ fn create_business_rules() -> Vec<Rule> {
                     action_type: ActionType::Log { message: "Overtime detected".to_string() },
                 },
             ],
+            metadata: Default::default(),
         },
         // Rule 2: High amount bonus calculation
         Rule {
@@ -118,6 +120,7 @@ fn create_business_rules() -> Vec<Rule> {
                     output_field: "bonus".to_string(),
                 },
             }],
+            metadata: Default::default(),
         },
         // Rule 3: Department-specific processing
         Rule {
@@ -141,6 +144,7 @@ fn create_business_rules() -> Vec<Rule> {
                     value: FactValue::Boolean(true),
                 },
             }],
+            metadata: Default::default(),
         },
         // Rule 4: Complex logical condition
         Rule {
@@ -177,6 +181,7 @@ fn create_business_rules() -> Vec<Rule> {
                     value: FactValue::Boolean(true),
                 },
             }],
+            metadata: Default::default(),
         },
     ]
 }
@@ -277,6 +282,7 @@ fn test_concurrent_engine_instances() {
                         value: FactValue::Boolean(true),
                     },
                 }],
+                metadata: Default::default(),
             }];
 
             // Process facts
@@ -379,6 +385,7 @@ fn test_memory_pressure_handling() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     }];
 
     let start_time = Instant::now();
@@ -454,6 +461,7 @@ fn test_edge_case_data_handling() {
                     value: FactValue::Boolean(true),
                 },
             }],
+            metadata: Default::default(),
         },
         Rule {
             id: 2,
@@ -469,6 +477,7 @@ fn test_edge_case_data_handling() {
                     value: FactValue::Boolean(true),
                 },
             }],
+            metadata: Default::default(),
         },
     ];
 
@@ -528,6 +537,7 @@ fn test_complex_rule_combinations() {
                     value: FactValue::Boolean(true),
                 },
             }],
+            metadata: Default::default(),
         },
         // Rule 2: Complex nested conditions
         Rule {
@@ -564,6 +574,7 @@ fn test_complex_rule_combinations() {
                     value: FactValue::Boolean(true),
                 },
             }],
+            metadata: Default::default(),
         },
         // Rule 3: Multiple actions
         Rule {
@@ -593,6 +604,7 @@ fn test_complex_rule_combinations() {
                     },
                 },
             ],
+            metadata: Default::default(),
         },
     ];
 
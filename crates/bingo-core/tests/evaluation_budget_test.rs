@@ -0,0 +1,122 @@
+//! Evaluation Budget Test
+//!
+//! Validates that a budgeted batch pauses after its firing or time budget, that
+//! resuming it with continuations yields the results of unbudgeted processing, that
+//! batches of several tenants can be interleaved slice by slice, and that facts
+//! retracted while a batch is paused are skipped.
+
+use bingo_core::*;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+fn order(amount: i64) -> Fact {
+    let fields = HashMap::from([("amount".to_string(), FactValue::Integer(amount))]);
+    Fact::new(0, FactData { fields })
+}
+
+fn orders(count: i64) -> Vec<Fact> {
+    (1..=count).map(|i| order(100 + i)).collect()
+}
+
+fn orders_engine() -> BingoEngine {
+    let engine = BingoEngine::new().unwrap();
+    engine
+        .add_rules_from_dsl(
+            r#"
+            rule "Large order" id 1 when amount > 100 then set flagged = true
+            rule "Huge order" id 2 when amount > 105 then set reviewed = true
+            "#,
+        )
+        .unwrap();
+    engine
+}
+
+fn fired(results: &[RuleExecutionResult]) -> Vec<(u64, u64)> {
+    let mut fired: Vec<_> = results.iter().map(|result| (result.rule_id, result.fact_id)).collect();
+    fired.sort_unstable();
+    fired
+}
+
+#[test]
+fn test_resumed_batch_matches_unbudgeted_processing() {
+    let expected = fired(&orders_engine().process_facts(orders(8)).unwrap());
+
+    let engine = orders_engine();
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let recorded = events.clone();
+    engine.subscribe_events(move |event: &EngineEvent| {
+        recorded.lock().unwrap().push(event.clone());
+    });
+
+    // The whole batch is stored up front, and the first two orders fire one rule each
+    let first = engine.process_facts_budgeted(orders(8), EvaluationBudget::firings(2)).unwrap();
+    assert_eq!(engine.fact_count(), 8);
+    assert_eq!(first.results.len(), 2);
+    assert!(!first.is_complete());
+
+    let mut results = first.results;
+    let mut continuation = first.continuation;
+    assert_eq!(continuation.as_ref().unwrap().remaining().len(), 6);
+    let mut slices = Vec::new();
+    while let Some(paused) = continuation {
+        let slice = engine.resume_evaluation(paused, EvaluationBudget::firings(4)).unwrap();
+        slices.push(slice.results.len());
+        results.extend(slice.results);
+        continuation = slice.continuation;
+    }
+    // The sixth order overruns the budget by firing both rules
+    assert_eq!(slices, [5, 4]);
+    assert_eq!(fired(&results), expected);
+    assert_eq!(engine.get_stats().fact_count, 8);
+
+    let events = events.lock().unwrap();
+    let finished: Vec<_> = events
+        .iter()
+        .filter_map(|event| match event {
+            EngineEvent::BatchFinished { fact_count, rules_fired, .. } => {
+                Some((*fact_count, *rules_fired))
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(finished, [(8, expected.len())]);
+}
+
+#[test]
+fn test_tenants_take_turns_and_retracted_facts_are_skipped() {
+    let tenants = [orders_engine(), orders_engine()];
+    let slice_budget = EvaluationBudget::duration(Duration::ZERO);
+
+    // A spent time budget still evaluates one fact per slice
+    let mut paused: Vec<(usize, Continuation)> = tenants
+        .iter()
+        .enumerate()
+        .map(|(tenant, engine)| {
+            let slice = engine.process_facts_budgeted(orders(3), slice_budget).unwrap();
+            assert_eq!(slice.results.len(), 1);
+            (tenant, slice.continuation.unwrap())
+        })
+        .collect();
+    assert_eq!(paused[0].1.slices(), 1);
+
+    // Tenant 0 retracts its last order before it is evaluated
+    let last = *paused[0].1.remaining().last().unwrap();
+    tenants[0].retract_fact(last).unwrap();
+
+    // Round-robin scheduling, one slice per tenant per round
+    let mut rounds = 0;
+    let mut fired_per_tenant = [1, 1];
+    while !paused.is_empty() {
+        rounds += 1;
+        let mut still_paused = Vec::new();
+        for (tenant, continuation) in paused {
+            let slice = tenants[tenant].resume_evaluation(continuation, slice_budget).unwrap();
+            fired_per_tenant[tenant] += slice.results.len();
+            still_paused.extend(slice.continuation.map(|next| (tenant, next)));
+        }
+        paused = still_paused;
+    }
+    assert_eq!(rounds, 2);
+    assert_eq!(fired_per_tenant, [2, 3]);
+}
@@ -0,0 +1,114 @@
+//! Engine Event Bus Test
+//!
+//! Validates that an engine publishes rule, session and batch lifecycle events to its
+//! subscribers, reports a working memory limit rejecting a batch, and lets listeners
+//! call back into the engine while they are notified.
+
+use bingo_core::*;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+fn order(id: u64) -> Fact {
+    let fields = HashMap::from([("amount".to_string(), FactValue::Integer(250))]);
+    Fact::new(id, FactData { fields })
+}
+
+fn large_order_rule(id: u64) -> Rule {
+    parse_rule(&format!(
+        r#"rule "Large order {id}" id {id} when amount > 100 then set flagged = true"#
+    ))
+    .unwrap()
+}
+
+fn record_events(engine: &BingoEngine) -> Arc<Mutex<Vec<EngineEvent>>> {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let recorded = events.clone();
+    engine.subscribe_events(move |event: &EngineEvent| {
+        recorded.lock().unwrap().push(event.clone());
+    });
+    events
+}
+
+#[test]
+fn test_rule_session_and_batch_events_are_published() {
+    let engine = Arc::new(BingoEngine::new().unwrap());
+    let events = record_events(&engine);
+
+    engine.add_rule(large_order_rule(1)).unwrap();
+    engine.update_rule(large_order_rule(1)).unwrap();
+    let _session = BingoSession::new(engine.clone());
+    engine.process_facts(vec![order(101), order(102)]).unwrap();
+    engine.remove_rule(1).unwrap();
+
+    let events = events.lock().unwrap();
+    let kinds: Vec<&str> = events.iter().map(EngineEvent::kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            "rule_added",
+            "rule_updated",
+            "session_created",
+            "batch_started",
+            "batch_finished",
+            "rule_removed"
+        ]
+    );
+    assert_eq!(
+        events[0],
+        EngineEvent::RuleAdded {
+            rule_id: 1,
+            rule_name: "Large order 1".to_string(),
+            ruleset_version: 1
+        }
+    );
+    assert!(matches!(
+        events[4],
+        EngineEvent::BatchFinished { batch_id: 1, fact_count: 2, rules_fired: 2, .. }
+    ));
+    assert_eq!(
+        events[5],
+        EngineEvent::RuleRemoved { rule_id: 1, ruleset_version: 3 }
+    );
+}
+
+#[test]
+fn test_rejected_batch_reports_the_breached_limit() {
+    let config = EngineConfig { max_working_memory_facts: Some(1), ..EngineConfig::default() };
+    let engine = BingoEngine::with_config(config).unwrap();
+    let events = record_events(&engine);
+
+    engine.process_facts(vec![order(101), order(102)]).unwrap_err();
+
+    let events = events.lock().unwrap();
+    assert_eq!(
+        events[1],
+        EngineEvent::LimitBreached {
+            resource: "working_memory_facts".to_string(),
+            limit: 1,
+            requested: 2
+        }
+    );
+    assert!(matches!(
+        &events[2],
+        EngineEvent::BatchFailed { batch_id: 1, .. }
+    ));
+}
+
+#[test]
+fn test_listeners_can_call_back_into_the_engine() {
+    let engine = Arc::new(BingoEngine::new().unwrap());
+    let rule_counts = Arc::new(Mutex::new(Vec::new()));
+    let (observed, counts) = (Arc::downgrade(&engine), rule_counts.clone());
+    let subscription = engine.subscribe_events(move |event: &EngineEvent| {
+        if let (EngineEvent::RuleAdded { .. }, Some(engine)) = (event, observed.upgrade()) {
+            counts.lock().unwrap().push(engine.rule_count());
+        }
+    });
+
+    engine.add_rules(vec![large_order_rule(1), large_order_rule(2)]).unwrap();
+    assert!(engine.unsubscribe_events(subscription));
+    engine.add_rule(large_order_rule(3)).unwrap();
+
+    assert_eq!(*rule_counts.lock().unwrap(), vec![1, 2]);
+    assert_eq!(engine.event_bus().published_count(), 3);
+}
@@ -0,0 +1,127 @@
+//! Explanation Test
+//!
+//! Validates the audit log: with it enabled, every rule result carries a result ID whose
+//! trace records the values each condition matched, the calculator inputs and outputs
+//! and the executed actions, and serializes to JSON; old traces are dropped at capacity.
+
+use bingo_core::types::*;
+use bingo_core::{AuditLogConfig, BingoEngine};
+use std::collections::HashMap;
+
+fn order(id: u64, amount: i64, region: &str) -> Fact {
+    let fields = HashMap::from([
+        ("amount".to_string(), FactValue::Integer(amount)),
+        ("region".to_string(), FactValue::String(region.to_string())),
+        ("rate".to_string(), FactValue::Integer(3)),
+    ]);
+    Fact::new(id, FactData { fields })
+}
+
+fn surcharge_rule() -> Rule {
+    Rule {
+        id: 7,
+        name: "EU surcharge".to_string(),
+        conditions: vec![
+            Condition::Simple {
+                field: "amount".to_string(),
+                operator: Operator::GreaterThan,
+                value: FactValue::Integer(100),
+            },
+            Condition::Simple {
+                field: "region".to_string(),
+                operator: Operator::Equal,
+                value: FactValue::String("EU".to_string()),
+            },
+        ],
+        actions: vec![Action {
+            action_type: ActionType::CallCalculator {
+                calculator_name: "multiply".to_string(),
+                input_mapping: HashMap::from([
+                    ("a".to_string(), "amount".to_string()),
+                    ("b".to_string(), "rate".to_string()),
+                ]),
+                output_field: "surcharge".to_string(),
+            },
+        }],
+        metadata: Default::default(),
+    }
+}
+
+#[test]
+fn test_trace_records_conditions_calculators_and_actions() {
+    let engine = BingoEngine::new().unwrap();
+    engine.set_audit_log(Some(AuditLogConfig::default()));
+    engine.add_rule(surcharge_rule()).unwrap();
+
+    let results = engine.process_facts(vec![order(1, 500, "EU"), order(2, 500, "US")]).unwrap();
+    assert_eq!(results.len(), 1);
+    let result_id = results[0].result_id.expect("audit log is enabled");
+
+    let trace = engine.explain(result_id).unwrap();
+    assert_eq!(
+        (trace.result_id, trace.rule_id, trace.fact_id),
+        (result_id, 7, 1)
+    );
+    assert_eq!(trace.rule_name, "EU surcharge");
+
+    let bound: Vec<_> = trace
+        .conditions
+        .iter()
+        .map(|condition| (condition.fact_id, condition.bound_values.clone()))
+        .collect();
+    assert_eq!(
+        bound,
+        vec![
+            (
+                Some(1),
+                [("amount".to_string(), FactValue::Integer(500))].into()
+            ),
+            (
+                Some(1),
+                [("region".to_string(), FactValue::String("EU".to_string()))].into()
+            ),
+        ]
+    );
+
+    assert_eq!(trace.calculators.len(), 1);
+    let calculator = &trace.calculators[0];
+    assert_eq!(calculator.inputs["a"], FactValue::Integer(500));
+    assert_eq!(calculator.inputs["b"], FactValue::Integer(3));
+    assert_eq!(calculator.output_field, "surcharge");
+    assert!(calculator.output.is_some());
+    assert_eq!(trace.actions, results[0].actions_executed);
+
+    let json: serde_json::Value = serde_json::from_str(&trace.to_json().unwrap()).unwrap();
+    assert_eq!(json["rule_name"], "EU surcharge");
+    assert_eq!(
+        json["conditions"][1]["bound_values"]["region"]["String"],
+        "EU"
+    );
+    assert_eq!(json["calculators"][0]["inputs"]["a"]["Integer"], 500);
+}
+
+#[test]
+fn test_audit_log_is_opt_in_and_bounded() {
+    let engine = BingoEngine::new().unwrap();
+    engine.add_rule(surcharge_rule()).unwrap();
+    let results = engine.process_facts(vec![order(1, 500, "EU")]).unwrap();
+    assert_eq!(results[0].result_id, None);
+    assert_eq!(engine.audit_log(), None);
+
+    engine.set_audit_log(Some(AuditLogConfig { capacity: 2 }));
+    let results = engine.process_facts((10..13).map(|id| order(id, 500, "EU")).collect()).unwrap();
+    let ids: Vec<_> = results.iter().map(|result| result.result_id.unwrap()).collect();
+    assert_eq!(ids.len(), 3);
+    assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+
+    // Only the two most recent traces are kept
+    assert!(engine.explain(ids[0]).is_none());
+    assert_eq!(engine.explain(ids[2]).unwrap().fact_id, 12);
+
+    // Rebuilding the network keeps the log
+    engine.add_rule(Rule { id: 8, ..surcharge_rule() }).unwrap();
+    assert!(engine.explain(ids[2]).is_some());
+
+    engine.set_audit_log(None);
+    assert!(engine.explain(ids[2]).is_none());
+}
@@ -0,0 +1,147 @@
+//! Fact Expiry Test
+//!
+//! Validates that facts carrying an expiry instant or a time to live are hidden once
+//! they expire, retracted with the facts derived from them by the next insert or an
+//! explicit or background sweep, and dropped when they arrive already expired.
+
+use bingo_core::*;
+use chrono::{Duration, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+fn login(id: u64, expiry: Option<(&str, FactValue)>) -> Fact {
+    let mut fields = HashMap::from([("event".to_string(), FactValue::String("login".to_string()))]);
+    if let Some((field, value)) = expiry {
+        fields.insert(field.to_string(), value);
+    }
+    Fact::new(id, FactData { fields })
+}
+
+fn expiring_in(id: u64, seconds: i64) -> Fact {
+    let expires_at = Utc::now() + Duration::seconds(seconds);
+    login(id, Some(("expires_at", FactValue::Date(expires_at))))
+}
+
+fn alert_engine(config: FactExpiryConfig) -> BingoEngine {
+    let engine = BingoEngine::new().unwrap();
+    engine.set_fact_expiry(Some(config)).unwrap();
+    let alert = HashMap::from([("type".to_string(), FactValue::String("alert".to_string()))]);
+    engine
+        .add_rule(Rule {
+            id: 1,
+            name: "Alert on login".to_string(),
+            conditions: vec![Condition::Simple {
+                field: "event".to_string(),
+                operator: Operator::Equal,
+                value: FactValue::String("login".to_string()),
+            }],
+            actions: vec![Action {
+                action_type: ActionType::CreateFact { data: FactData { fields: alert } },
+            }],
+            metadata: Default::default(),
+        })
+        .unwrap();
+    engine
+}
+
+#[test]
+fn test_expired_facts_are_retracted_with_their_derived_facts() {
+    let engine = alert_engine(FactExpiryConfig::default());
+    engine
+        .process_facts(vec![
+            expiring_in(101, 60),
+            login(102, Some(("ttl", FactValue::Integer(600)))),
+            login(103, None),
+        ])
+        .unwrap();
+    assert_eq!(engine.fact_count(), 3);
+    assert_eq!(engine.fact_expiry_stats().pending, 2);
+
+    assert!(engine.expire_facts().unwrap().is_empty());
+    let retractions = engine.expire_facts_at(Utc::now() + Duration::minutes(5)).unwrap();
+    assert_eq!(retractions.len(), 1);
+    assert_eq!(retractions[0].fact_id, 101);
+    assert_eq!(retractions[0].retracted_facts.len(), 1);
+    assert_eq!(engine.fact_count(), 2);
+
+    engine.expire_facts_at(Utc::now() + Duration::days(1)).unwrap();
+    assert_eq!(engine.fact_count(), 1);
+    assert!(engine.get_fact(103).is_some());
+
+    let stats = engine.fact_expiry_stats();
+    assert_eq!(
+        (stats.pending, stats.expired, stats.derived_retracted),
+        (0, 2, 2)
+    );
+}
+
+#[test]
+fn test_expired_facts_are_hidden_and_retracted_on_the_next_insert() {
+    let engine = alert_engine(FactExpiryConfig::with_default_ttl(Duration::seconds(30)));
+    let mut stale = login(101, None);
+    stale.external_id = Some("stale".to_string());
+    stale.timestamp = Utc::now() - Duration::seconds(20);
+    engine.process_facts(vec![stale]).unwrap();
+
+    // Already expired on arrival, so never inserted
+    let mut old = login(102, None);
+    old.timestamp = Utc::now() - Duration::minutes(1);
+    assert!(engine.process_facts(vec![old]).unwrap().is_empty());
+    assert_eq!(engine.fact_expiry_stats().expired_on_arrival, 1);
+
+    // Lookups stop returning the fact once it expires, before it is retracted
+    engine
+        .set_fact_expiry(Some(FactExpiryConfig::with_default_ttl(Duration::seconds(
+            10,
+        ))))
+        .unwrap();
+    assert!(engine.get_fact(101).is_none());
+    assert!(engine.lookup_fact_by_id("stale").is_none());
+    assert_eq!(engine.fact_count(), 1);
+
+    engine.add_fact_to_working_memory(login(103, None)).unwrap();
+    assert_eq!(engine.fact_count(), 1);
+    assert!(engine.get_fact(103).is_some());
+    assert_eq!(engine.fact_expiry_stats().expired, 1);
+}
+
+#[test]
+fn test_sweeper_retracts_expired_facts_in_the_background() {
+    let engine = Arc::new(alert_engine(FactExpiryConfig::default()));
+    engine
+        .process_facts(vec![
+            login(101, Some(("ttl", FactValue::Float(0.05)))),
+            login(102, None),
+        ])
+        .unwrap();
+    assert_eq!(engine.fact_count(), 2);
+
+    let sweeper = FactExpirySweeper::start(&engine, std::time::Duration::from_millis(10));
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+    while engine.fact_count() > 1 && std::time::Instant::now() < deadline {
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+    sweeper.stop();
+    assert_eq!(engine.fact_count(), 1);
+    assert_eq!(engine.fact_expiry_stats().expired, 1);
+}
+
+#[test]
+fn test_expiry_is_off_by_default_and_validated() {
+    let engine = BingoEngine::new().unwrap();
+    assert_eq!(engine.fact_expiry(), None);
+    engine.process_facts(vec![expiring_in(1, -60)]).unwrap();
+    assert!(engine.get_fact(1).is_some());
+
+    let invalid = FactExpiryConfig::with_default_ttl(Duration::seconds(-1));
+    assert!(engine.set_fact_expiry(Some(invalid)).is_err());
+
+    // Facts already held are scheduled when expiry is turned on
+    engine.set_fact_expiry(Some(FactExpiryConfig::default())).unwrap();
+    assert!(engine.get_fact(1).is_none());
+    assert_eq!(engine.expire_facts().unwrap().len(), 1);
+    assert_eq!(engine.fact_count(), 0);
+
+    let copy = BingoEngine::from_template(&engine).unwrap();
+    assert_eq!(copy.fact_expiry(), Some(FactExpiryConfig::default()));
+}
@@ -0,0 +1,100 @@
+//! Fact History Test
+//!
+//! Validates that facts modified by rule actions and updates keep their earlier
+//! versions, with the changed fields and the rule that changed them, that history is
+//! bounded, and that deleting a fact or disabling history drops its versions.
+
+use bingo_core::types::*;
+use bingo_core::{BingoEngine, FactHistoryConfig, FactQuery, FieldChange};
+use std::collections::HashMap;
+
+fn member(id: FactId) -> Fact {
+    let fields = HashMap::from([
+        ("active".to_string(), FactValue::Boolean(true)),
+        ("points".to_string(), FactValue::Integer(0)),
+    ]);
+    Fact::new(id, FactData { fields })
+}
+
+fn points_change(before: i64, after: i64) -> Vec<FieldChange> {
+    vec![FieldChange {
+        field: "points".to_string(),
+        before: Some(FactValue::Integer(before)),
+        after: Some(FactValue::Integer(after)),
+    }]
+}
+
+#[test]
+fn test_rule_mutations_are_kept_as_versions() {
+    let engine = BingoEngine::new().unwrap();
+    engine
+        .add_rules_from_dsl(
+            r#"
+rule "Login bonus" id 1 when active == true then increment points by 10
+rule "Streak bonus" id 2 when active == true then increment points by 5
+"#,
+        )
+        .unwrap();
+    engine.set_fact_history(Some(FactHistoryConfig { max_versions: 3 }));
+    engine.process_facts(vec![member(1)]).unwrap();
+
+    let history = engine.get_fact_history(1);
+    let versions: Vec<u64> = history.iter().map(|version| version.version).collect();
+    assert_eq!(versions, vec![1, 2, 3]);
+    assert_eq!(history[0].changed_by, None);
+    assert!(history[0].written_at.is_none());
+    let mut authors: Vec<RuleId> = history[1..].iter().filter_map(|v| v.changed_by).collect();
+    authors.sort_unstable();
+    assert_eq!(authors, vec![1, 2]);
+    let last = &history[2];
+    assert_eq!(last.fact.data.fields["points"], FactValue::Integer(15));
+    assert!(last.changes == points_change(10, 15) || last.changes == points_change(5, 15));
+
+    // The update writes a version without an author, the rematched rules write two more,
+    // and only the three latest earlier versions are kept
+    let level = HashMap::from([("level".to_string(), FactValue::Integer(2))]);
+    let update = engine.update_by_criteria(&FactQuery::all(), &level).unwrap();
+    assert_eq!(update.updated_facts, vec![1]);
+    let history = engine.get_fact_history(1);
+    let versions: Vec<u64> = history.iter().map(|version| version.version).collect();
+    assert_eq!(versions, vec![3, 4, 5, 6]);
+    assert!(history[0].changes.is_empty());
+    assert_eq!(history[1].changed_by, None);
+    assert_eq!(
+        history[1].changes,
+        vec![FieldChange {
+            field: "level".to_string(),
+            before: None,
+            after: Some(FactValue::Integer(2)),
+        }]
+    );
+    assert_eq!(
+        history[3].fact.data.fields["points"],
+        FactValue::Integer(30)
+    );
+}
+
+#[test]
+fn test_history_is_released_with_its_fact() {
+    let engine = BingoEngine::new().unwrap();
+    engine
+        .add_rules_from_dsl(r#"rule "Bonus" id 1 when active == true then increment points by 10"#)
+        .unwrap();
+    engine.process_facts(vec![member(1)]).unwrap();
+    // Without history a modified fact reports only its current version
+    assert_eq!(engine.get_fact_history(1).len(), 1);
+
+    engine.set_fact_history(Some(FactHistoryConfig::default()));
+    engine.process_facts(vec![member(2), member(3)]).unwrap();
+    assert_eq!(engine.get_fact_history(2).len(), 2);
+
+    engine.retract_fact(2).unwrap();
+    assert!(engine.get_fact_history(2).is_empty());
+    engine.set_fact_history(None);
+    let history = engine.get_fact_history(3);
+    assert_eq!(history.len(), 1);
+    assert_eq!(
+        history[0].fact.data.fields["points"],
+        FactValue::Integer(10)
+    );
+}
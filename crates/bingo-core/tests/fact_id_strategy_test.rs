@@ -0,0 +1,128 @@
+//! Fact ID Strategy Test
+//!
+//! Validates that the engine reports results under the ID each fact was stored with:
+//! the default strategy renumbers hashed IDs and counts it, client-supplied IDs are kept
+//! as given and validated, snowflake IDs are unique and time-ordered, and results keep
+//! the external ID clients correlate them by.
+
+use bingo_core::types::*;
+use bingo_core::{BingoEngine, FactIdStrategy, IdCollisionPolicy, parse_rule};
+use std::collections::HashMap;
+
+const HASHED_ID: u64 = 0xCBF2_9CE4_8422_2325;
+
+fn order(id: u64, amount: i64) -> Fact {
+    let fields = HashMap::from([("amount".to_string(), FactValue::Integer(amount))]);
+    Fact::new(id, FactData { fields })
+}
+
+fn engine_with_strategy(strategy: FactIdStrategy) -> BingoEngine {
+    let engine = BingoEngine::new().unwrap();
+    engine
+        .add_rule(parse_rule(r#"rule "Large" id 1 when amount > 100 then log "large""#).unwrap())
+        .unwrap();
+    engine.set_fact_id_strategy(strategy).unwrap();
+    engine
+}
+
+fn matched_ids(engine: &BingoEngine, facts: Vec<Fact>) -> Vec<u64> {
+    engine
+        .process_facts(facts)
+        .unwrap()
+        .iter()
+        .map(|result| result.fact_id)
+        .collect()
+}
+
+#[test]
+fn test_default_strategy_reports_renumbered_ids() {
+    let engine = engine_with_strategy(FactIdStrategy::default());
+
+    let ids = matched_ids(&engine, vec![order(5, 500), order(HASHED_ID, 500)]);
+    assert_eq!(ids.len(), 2);
+    assert_eq!(ids[0], 5);
+    assert_ne!(ids[1], HASHED_ID);
+
+    // Results name the stored fact, not the submitted ID
+    assert_eq!(
+        engine.get_fact(ids[1]).unwrap().data.fields["amount"],
+        FactValue::Integer(500)
+    );
+    let stats = engine.fact_id_stats();
+    assert_eq!((stats.preserved, stats.reassigned), (1, 1));
+}
+
+#[test]
+fn test_results_carry_the_external_id() {
+    let engine = engine_with_strategy(FactIdStrategy::Sequential);
+    let mut fact = order(HASHED_ID, 500);
+    fact.external_id = Some("order-2024-0042".to_string());
+    let timestamp = fact.timestamp;
+
+    let results = engine.process_facts(vec![fact]).unwrap();
+    assert_eq!(results.len(), 1);
+    assert_ne!(results[0].fact_id, HASHED_ID);
+    assert_eq!(results[0].external_id.as_deref(), Some("order-2024-0042"));
+    assert_eq!(results[0].fact_timestamp, Some(timestamp));
+}
+
+#[test]
+fn test_client_supplied_ids_are_kept() {
+    let engine = engine_with_strategy(FactIdStrategy::ClientSupplied {
+        on_collision: IdCollisionPolicy::Replace,
+    });
+
+    assert_eq!(
+        matched_ids(&engine, vec![order(HASHED_ID, 500)]),
+        vec![HASHED_ID]
+    );
+    assert!(engine.get_fact(HASHED_ID).is_some());
+
+    // Resubmitting an ID replaces the stored fact
+    assert!(matched_ids(&engine, vec![order(HASHED_ID, 50)]).is_empty());
+    assert_eq!(engine.fact_count(), 1);
+    assert_eq!(
+        engine.get_fact(HASHED_ID).unwrap().data.fields["amount"],
+        FactValue::Integer(50)
+    );
+    assert_eq!(engine.fact_id_stats().replaced, 1);
+}
+
+#[test]
+fn test_client_supplied_collisions_can_be_rejected() {
+    let engine = engine_with_strategy(FactIdStrategy::ClientSupplied {
+        on_collision: IdCollisionPolicy::Reject,
+    });
+    matched_ids(&engine, vec![order(HASHED_ID, 500)]);
+
+    let error = engine.process_facts(vec![order(7, 500), order(HASHED_ID, 500)]).unwrap_err();
+    assert!(error.to_string().contains("already stored"), "{error}");
+    assert!(engine.process_facts(vec![order(0, 500)]).is_err());
+    assert!(engine.add_fact_to_working_memory(order(HASHED_ID, 500)).is_err());
+
+    // Rejected batches leave working memory untouched
+    assert_eq!(engine.fact_count(), 1);
+    assert!(engine.get_fact(7).is_none());
+    assert_eq!(engine.fact_id_stats().rejected, 4);
+}
+
+#[test]
+fn test_snowflake_ids_are_unique_and_ordered() {
+    let engine = engine_with_strategy(FactIdStrategy::Snowflake { worker_id: 12 });
+    assert_eq!(
+        engine.fact_id_strategy(),
+        FactIdStrategy::Snowflake { worker_id: 12 }
+    );
+
+    let ids = matched_ids(&engine, (0..100).map(|_| order(1, 500)).collect());
+    assert_eq!(ids.len(), 100);
+    assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+    assert_eq!(engine.fact_count(), 100);
+    assert!(ids.iter().all(|id| (id >> 12) & 0x3FF == 12));
+
+    assert!(
+        engine
+            .set_fact_id_strategy(FactIdStrategy::Snowflake { worker_id: 2048 })
+            .is_err()
+    );
+}
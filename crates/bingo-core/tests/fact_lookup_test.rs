@@ -17,6 +17,7 @@ fn test_fact_id_lookup() {
         id: 0, // Internal ID, will be overwritten by the fact store
         external_id: Some("user-123".to_string()),
         timestamp: chrono::Utc::now(),
+        fact_type: None,
         data: FactData { fields },
     };
 
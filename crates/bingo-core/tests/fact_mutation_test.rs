@@ -28,6 +28,7 @@ fn test_update_fact_action() {
         id: 1,
         external_id: Some("user-123".to_string()),
         timestamp: Utc::now(),
+        fact_type: None,
         data: FactData { fields },
     };
 
@@ -58,6 +59,7 @@ fn test_update_fact_action() {
                 updates: update_values,
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(update_rule).unwrap();
@@ -74,6 +76,7 @@ fn test_update_fact_action() {
         id: 2,
         external_id: Some("trigger-1".to_string()),
         timestamp: Utc::now(),
+        fact_type: None,
         data: FactData { fields: trigger_fields },
     };
 
@@ -155,6 +158,7 @@ fn test_delete_fact_action() {
         id: 3,
         external_id: Some("temp-456".to_string()),
         timestamp: Utc::now(),
+        fact_type: None,
         data: FactData { fields },
     };
 
@@ -176,6 +180,7 @@ fn test_delete_fact_action() {
                 fact_id_field: "target_fact_id".to_string(), // The trigger fact contains the ID of the fact to delete
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(delete_rule).unwrap();
@@ -192,6 +197,7 @@ fn test_delete_fact_action() {
         id: 4,
         external_id: Some("delete-trigger".to_string()),
         timestamp: Utc::now(),
+        fact_type: None,
         data: FactData { fields: trigger_fields },
     };
 
@@ -0,0 +1,116 @@
+//! Fact Query Test
+//!
+//! Validates that fact queries combine comparisons with AND, OR and NOT groups, and
+//! that paging through the matches by offset or cursor returns each match once, in
+//! fact ID order, across dense and sparse fact IDs and without expired facts.
+
+use bingo_core::fact_store::arena_store::ArenaFactStore;
+use bingo_core::types::*;
+use bingo_core::{BingoEngine, FactExpiryConfig, FactPageRequest, FactQuery};
+use chrono::{Duration, Utc};
+use std::collections::HashMap;
+
+fn expense(id: FactId, status: &str, amount: i64) -> Fact {
+    let fields = HashMap::from([
+        ("status".to_string(), FactValue::String(status.to_string())),
+        ("amount".to_string(), FactValue::Integer(amount)),
+    ]);
+    Fact::new(id, FactData { fields })
+}
+
+fn ids(facts: &[Fact]) -> Vec<FactId> {
+    facts.iter().map(|fact| fact.id).collect()
+}
+
+fn status(value: &str) -> FactQuery {
+    FactQuery::compare(
+        "status",
+        Operator::Equal,
+        FactValue::String(value.to_string()),
+    )
+}
+
+#[test]
+fn test_groups_and_pages_follow_fact_id_order() {
+    let store = ArenaFactStore::new();
+    let sparse_id = 5_000_000_000_000;
+    for fact in [
+        expense(sparse_id, "open", 900),
+        expense(4, "open", 50),
+        expense(1, "open", 700),
+        expense(2, "closed", 800),
+        expense(3, "held", 20),
+        expense(6, "open", 600),
+        expense(5, "closed", 10),
+    ] {
+        store.insert_with_id(fact);
+    }
+
+    // open AND (amount > 500 OR NOT status == "held")
+    let query = status("open").and(
+        FactQuery::compare("amount", Operator::GreaterThan, FactValue::Integer(500))
+            .or(status("held").negate()),
+    );
+    let all = store.find_by_query(&query, FactPageRequest::first(usize::MAX));
+    assert_eq!(ids(&all.facts), vec![1, 4, 6, sparse_id]);
+    assert_eq!(all.next_cursor, None);
+
+    let large_or_held = status("held").or(FactQuery::compare(
+        "amount",
+        Operator::GreaterThanOrEqual,
+        FactValue::Integer(800),
+    ));
+    let page = store.find_by_query(&large_or_held, FactPageRequest::offset(1, 5));
+    assert_eq!(ids(&page.facts), vec![3, sparse_id]);
+    let closed = [(
+        "status".to_string(),
+        FactValue::String("closed".to_string()),
+    )];
+    let page = store.find_by_query(&FactQuery::all_equal(&closed), FactPageRequest::first(5));
+    let mut criteria_ids = ids(&store.find_by_criteria(&closed));
+    criteria_ids.sort_unstable();
+    assert_eq!(ids(&page.facts), criteria_ids);
+
+    // Paging by cursor visits every match once
+    let mut request = FactPageRequest::first(2);
+    let mut listed = Vec::new();
+    loop {
+        let page = store.find_by_query(&FactQuery::all(), request);
+        listed.extend(ids(&page.facts));
+        match page.next(2) {
+            Some(next) => request = next,
+            None => break,
+        }
+    }
+    assert_eq!(listed, vec![1, 2, 3, 4, 5, 6, sparse_id]);
+
+    // A cursor stays valid when facts before and after it change
+    let page = store.find_by_query(&FactQuery::all(), FactPageRequest::first(3));
+    assert_eq!(page.next_cursor, Some(3));
+    store.delete_fact(2);
+    store.insert_with_id(expense(7, "open", 1));
+    let page = store.find_by_query(&FactQuery::all(), page.next(3).unwrap());
+    assert_eq!(ids(&page.facts), vec![4, 5, 6]);
+    let page = store.find_by_query(&FactQuery::all(), page.next(3).unwrap());
+    assert_eq!(ids(&page.facts), vec![7, sparse_id]);
+    assert_eq!(page.next_cursor, None);
+}
+
+#[test]
+fn test_engine_queries_skip_expired_facts() {
+    let engine = BingoEngine::new().unwrap();
+    engine.set_fact_expiry(Some(FactExpiryConfig::default())).unwrap();
+    let mut facts: Vec<Fact> = (1..=4).map(|id| expense(id, "open", id as i64 * 100)).collect();
+    facts[1].data.fields.insert(
+        "expires_at".to_string(),
+        FactValue::Date(Utc::now() + Duration::milliseconds(50)),
+    );
+    engine.process_facts(facts).unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(100));
+
+    let page = engine.query_facts(&status("open"), FactPageRequest::first(2));
+    assert_eq!(ids(&page.facts), vec![1, 3]);
+    let page = engine.query_facts(&status("open"), page.next(2).unwrap());
+    assert_eq!(ids(&page.facts), vec![4]);
+    assert_eq!(page.next_cursor, None);
+}
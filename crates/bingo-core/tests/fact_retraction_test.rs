@@ -0,0 +1,78 @@
+/// Fact Retraction and Truth Maintenance Test
+///
+/// Validates that retracting a fact withdraws the activations it supported and
+/// retracts facts asserted by those activations.
+use bingo_core::*;
+use std::collections::HashMap;
+
+fn create_fact(id: u64, status: &str) -> Fact {
+    let mut fields = HashMap::new();
+    fields.insert("status".to_string(), FactValue::String(status.to_string()));
+    Fact::new(id, FactData { fields })
+}
+
+fn create_alert_rule() -> Rule {
+    let mut alert_fields = HashMap::new();
+    alert_fields.insert("type".to_string(), FactValue::String("alert".to_string()));
+
+    Rule {
+        id: 1,
+        name: "Create alert for active facts".to_string(),
+        conditions: vec![Condition::Simple {
+            field: "status".to_string(),
+            operator: Operator::Equal,
+            value: FactValue::String("active".to_string()),
+        }],
+        actions: vec![Action {
+            action_type: ActionType::CreateFact { data: FactData { fields: alert_fields } },
+        }],
+        metadata: Default::default(),
+    }
+}
+
+#[test]
+fn test_retract_fact_withdraws_activations_and_derived_facts() {
+    let engine = BingoEngine::new().unwrap();
+    engine.add_rule(create_alert_rule()).unwrap();
+
+    let results = engine.process_facts(vec![create_fact(1, "active")]).unwrap();
+    assert_eq!(results.len(), 1);
+
+    let derived_fact_id = results[0]
+        .actions_executed
+        .iter()
+        .find_map(|action| match action {
+            ActionResult::FactCreated { fact_id, .. } => Some(*fact_id),
+            _ => None,
+        })
+        .expect("rule should create a derived fact");
+
+    let retraction = engine.retract_fact(1).unwrap();
+    assert_eq!(retraction.fact_id, 1);
+    assert_eq!(retraction.withdrawn_activations, vec![(1, 1)]);
+    assert_eq!(retraction.retracted_facts, vec![derived_fact_id]);
+    assert_eq!(engine.fact_count(), 0);
+}
+
+#[test]
+fn test_retract_fact_without_activations() {
+    let engine = BingoEngine::new().unwrap();
+    engine.add_rule(create_alert_rule()).unwrap();
+
+    engine.process_facts(vec![create_fact(1, "inactive")]).unwrap();
+    assert_eq!(engine.fact_count(), 1);
+
+    let retraction = engine.retract_fact(1).unwrap();
+    assert!(retraction.is_empty());
+    assert_eq!(engine.fact_count(), 0);
+}
+
+#[test]
+fn test_retract_unknown_fact_is_noop() {
+    let engine = BingoEngine::new().unwrap();
+    engine.add_rule(create_alert_rule()).unwrap();
+
+    let retraction = engine.retract_fact(42).unwrap();
+    assert_eq!(retraction.fact_id, 42);
+    assert!(retraction.is_empty());
+}
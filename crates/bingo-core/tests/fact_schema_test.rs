@@ -0,0 +1,95 @@
+//! Fact Schema Test
+//!
+//! Validates that facts of a type with a registered schema are refused with every
+//! problem listed before they reach working memory, that a batch with a refused fact
+//! is not stored at all, and that facts of other types and removed schemas pass.
+
+use bingo_core::types::*;
+use bingo_core::{BingoEngine, BingoError, FactProblem, FactSchema};
+use std::collections::HashMap;
+
+const RULES: &str = r#"
+rule "Overtime" id 1 when type == "shift" and hours > 8 then set overtime = true
+"#;
+
+fn shift(id: FactId, hours: FactValue) -> Fact {
+    let fields = HashMap::from([
+        ("type".to_string(), FactValue::String("shift".to_string())),
+        ("hours".to_string(), hours),
+        ("kind".to_string(), FactValue::String("night".to_string())),
+    ]);
+    Fact::new(id, FactData { fields })
+}
+
+fn engine() -> BingoEngine {
+    let engine = BingoEngine::new().unwrap();
+    engine.add_rules_from_dsl(RULES).unwrap();
+    engine.register_fact_schema(
+        FactSchema::new("shift")
+            .required("hours", FieldType::Integer { min: 0, max: 24 })
+            .optional(
+                "kind",
+                FieldType::Enum { values: vec!["day".to_string(), "night".to_string()] },
+            ),
+    );
+    engine
+}
+
+#[test]
+fn test_mistyped_facts_are_refused_with_their_problems() {
+    let engine = engine();
+    let facts =
+        vec![shift(1, FactValue::Integer(10)), shift(2, FactValue::String("10".to_string()))];
+
+    let error = engine.process_facts(facts).unwrap_err();
+    let BingoError::FactValidation { report, .. } = error else {
+        panic!("expected a fact validation error, got {error:?}");
+    };
+    assert_eq!(report.fact_id, 2);
+    assert_eq!(report.fact_type, "shift");
+    assert_eq!(
+        report.problems,
+        vec![FactProblem::TypeMismatch {
+            field: "hours".to_string(),
+            expected: "an integer from 0 to 24".to_string(),
+            actual: "string 10".to_string(),
+        }]
+    );
+    // The batch is refused as a whole
+    assert_eq!(engine.fact_count(), 0);
+
+    let missing = Fact::new(
+        3,
+        FactData {
+            fields: HashMap::from([("type".to_string(), FactValue::String("shift".to_string()))]),
+        },
+    );
+    let error = engine.add_fact_to_working_memory(missing).unwrap_err();
+    assert!(error.to_string().contains("field 'hours' is required"));
+
+    let results = engine.process_facts(vec![shift(4, FactValue::Integer(10))]).unwrap();
+    assert_eq!(results.len(), 1);
+}
+
+#[test]
+fn test_other_types_and_removed_schemas_pass() {
+    let engine = engine();
+    assert_eq!(engine.fact_schemas().schemas().count(), 1);
+
+    let mut leave = shift(1, FactValue::String("all day".to_string()));
+    leave
+        .data
+        .fields
+        .insert("type".to_string(), FactValue::String("leave".to_string()));
+    engine.process_facts(vec![leave]).unwrap();
+
+    assert!(engine.remove_fact_schema("shift").is_some());
+    assert!(engine.fact_schemas().is_empty());
+    let mut evening = shift(2, FactValue::Integer(3));
+    evening
+        .data
+        .fields
+        .insert("kind".to_string(), FactValue::String("evening".to_string()));
+    engine.process_facts(vec![evening]).unwrap();
+    assert_eq!(engine.fact_count(), 2);
+}
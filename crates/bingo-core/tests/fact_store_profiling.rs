@@ -26,6 +26,7 @@ fn test_fact_store_insertion_performance() {
                     id: i as u64,
                     external_id: None,
                     timestamp: chrono::Utc::now(),
+                    fact_type: None,
                     data: FactData { fields },
                 }
             })
@@ -0,0 +1,155 @@
+//! Fact Type Routing Test
+//!
+//! Validates that facts only reach the alpha memories of rules matching their fact
+//! type, that memories shared with a rule matching any type keep testing every fact,
+//! and that routing leaves which rules fire for a mixed stream unchanged.
+
+use bingo_calculator::calculator::Calculator;
+use bingo_core::fact_store::arena_store::ArenaFactStore;
+use bingo_core::rete_network::ReteNetwork;
+use bingo_core::rete_nodes::RuleExecutionResult;
+use bingo_core::types::*;
+use bingo_core::{BingoEngine, RuleMetadata, parse_rule};
+use std::collections::HashMap;
+
+const RULES: &str = r#"
+rule "Overtime" id 1 when type == "shift" and hours > 8 then set overtime = true
+rule "Review" id 2 when type == "expense" and amount > 100 then set review = true
+"#;
+
+fn fact(id: FactId, fields: &[(&str, i64)]) -> Fact {
+    let fields: HashMap<String, FactValue> = fields
+        .iter()
+        .map(|(field, value)| (field.to_string(), FactValue::Integer(*value)))
+        .collect();
+    Fact::new(id, FactData { fields })
+}
+
+fn fired(results: &[RuleExecutionResult]) -> Vec<RuleId> {
+    let mut rule_ids: Vec<RuleId> = results.iter().map(|result| result.rule_id).collect();
+    rule_ids.sort_unstable();
+    rule_ids
+}
+
+#[test]
+fn test_facts_skip_memories_of_other_types() {
+    let mut network = ReteNetwork::new();
+    let fact_store = ArenaFactStore::new();
+    let calculator = Calculator::new();
+    for rule in RULES.trim().lines() {
+        network.add_rule(parse_rule(rule).unwrap()).unwrap();
+    }
+
+    let shift = fact(1, &[("hours", 10), ("amount", 500)]).with_type("shift");
+    assert_eq!(shift.fact_type(), Some("shift"));
+    let results = network.add_fact_to_working_memory(shift, &fact_store, &calculator).unwrap();
+    assert_eq!(fired(&results), vec![1]);
+
+    let expense = fact(2, &[("hours", 10), ("amount", 500)]).with_type("expense");
+    let results = network.add_fact_to_working_memory(expense, &fact_store, &calculator).unwrap();
+    assert_eq!(fired(&results), vec![2]);
+
+    let untyped = fact(3, &[("hours", 10), ("amount", 500)]);
+    let results = network.add_fact_to_working_memory(untyped, &fact_store, &calculator).unwrap();
+    assert!(results.is_empty());
+
+    // Each typed fact skips the two memories of the other type's rule, the untyped
+    // fact all four
+    assert_eq!(network.get_alpha_memory_stats().total_tests_skipped, 8);
+
+    // A rule matching any type on a shared condition opens its memory to every fact
+    network
+        .add_rule(
+            parse_rule(r#"rule "Long shift" id 3 when hours > 8 then set long = true"#).unwrap(),
+        )
+        .unwrap();
+    let untyped = fact(4, &[("hours", 10)]);
+    let results = network.add_fact_to_working_memory(untyped, &fact_store, &calculator).unwrap();
+    assert_eq!(fired(&results), vec![3]);
+
+    // Once the rule is removed, untyped facts pass the memory untested again
+    network.remove_rule(3).unwrap();
+    let skipped = network.get_alpha_memory_stats().total_tests_skipped;
+    let untyped = fact(5, &[("hours", 10)]);
+    network.add_fact_to_working_memory(untyped, &fact_store, &calculator).unwrap();
+    assert_eq!(
+        network.get_alpha_memory_stats().total_tests_skipped,
+        skipped + 4
+    );
+}
+
+#[test]
+fn test_mixed_streams_fire_the_rules_of_each_type() {
+    let engine = BingoEngine::new().unwrap();
+    engine.add_rules_from_dsl(RULES).unwrap();
+    let weekend_shift = Rule {
+        id: 4,
+        name: "Weekend shift".to_string(),
+        conditions: vec![
+            Condition::Simple {
+                field: FACT_TYPE_FIELD.to_string(),
+                operator: Operator::Equal,
+                value: FactValue::String("shift".to_string()),
+            },
+            Condition::Or {
+                conditions: vec![
+                    Condition::Simple {
+                        field: "day".to_string(),
+                        operator: Operator::Equal,
+                        value: FactValue::Integer(6),
+                    },
+                    Condition::Simple {
+                        field: "day".to_string(),
+                        operator: Operator::Equal,
+                        value: FactValue::Integer(7),
+                    },
+                ],
+            },
+        ],
+        actions: vec![Action {
+            action_type: ActionType::SetField {
+                field: "weekend".to_string(),
+                value: FactValue::Boolean(true),
+            },
+        }],
+        metadata: RuleMetadata::default(),
+    };
+    assert_eq!(weekend_shift.fact_type(), Some("shift"));
+    engine.add_rule(weekend_shift).unwrap();
+
+    let facts = vec![
+        fact(1, &[("hours", 10), ("day", 6)]).with_type("shift"),
+        fact(2, &[("amount", 500), ("day", 7)]).with_type("expense"),
+        fact(3, &[("hours", 4), ("day", 7)]).with_type("shift"),
+        fact(4, &[("hours", 10), ("amount", 500), ("day", 6)]),
+    ];
+    let results = engine.process_facts(facts).unwrap();
+    let mut fired: Vec<(FactId, RuleId)> =
+        results.iter().map(|result| (result.fact_id, result.rule_id)).collect();
+    fired.sort_unstable();
+    assert_eq!(fired, vec![(1, 1), (1, 4), (2, 2), (3, 4)]);
+}
+
+#[test]
+fn test_fact_type_is_kept_apart_from_data_fields() {
+    let engine = BingoEngine::new().unwrap();
+    engine.add_rules_from_dsl(RULES).unwrap();
+
+    let shift = fact(1, &[("hours", 10), ("amount", 500)]).with_type("shift");
+    assert!(!shift.data.fields.contains_key(FACT_TYPE_FIELD));
+
+    // Facts serialized without a type read back untyped
+    let mut json = serde_json::to_value(&shift).unwrap();
+    json.as_object_mut().unwrap().remove("fact_type");
+    let untyped: Fact = serde_json::from_value(json).unwrap();
+    assert_eq!(untyped.fact_type(), None);
+
+    // The fact's own type decides over a `type` data field
+    let mut mislabelled = shift.clone();
+    mislabelled.data.fields.insert(
+        FACT_TYPE_FIELD.to_string(),
+        FactValue::String("expense".to_string()),
+    );
+    let results = engine.process_facts(vec![mislabelled]).unwrap();
+    assert_eq!(fired(&results), vec![1]);
+}
@@ -0,0 +1,124 @@
+//! Field Collision Test
+//!
+//! Validates that rules setting the same field of a fact to different values in one
+//! batch are reported on their results, and that the collision policy keeps every
+//! write, keeps only the highest-salience rule's write, or fails the batch.
+
+use bingo_core::rete_nodes::{ActionResult, RuleExecutionResult};
+use bingo_core::types::*;
+use bingo_core::{BingoEngine, FieldCollisionPolicy, parse_rule, parse_rules};
+use std::collections::HashMap;
+
+fn customer(id: u64) -> Fact {
+    let fields = HashMap::from([("spend".to_string(), FactValue::Integer(5_000))]);
+    Fact::new(id, FactData { fields })
+}
+
+fn tiering_engine() -> BingoEngine {
+    let engine = BingoEngine::new().unwrap();
+    let rules = parse_rules(
+        r#"
+        rule "Gold tier" id 1 when spend > 1000 then set tier = "gold"
+        rule "Platinum tier" id 2 when spend > 2000 then set tier = "platinum"
+        rule "Loyal" id 3 when spend > 1000 then set loyal = true
+        "#,
+    )
+    .unwrap();
+    for rule in rules {
+        engine.add_rule(rule).unwrap();
+    }
+    engine
+}
+
+/// Tier values set by the batch's results, by rule ID
+fn tier_writes(engine: &BingoEngine) -> (Vec<(RuleId, FactValue)>, Vec<RuleExecutionResult>) {
+    let results = engine.process_facts(vec![customer(1)]).unwrap();
+    let mut writes: Vec<_> = results
+        .iter()
+        .flat_map(|result| {
+            result.actions_executed.iter().filter_map(|action| match action {
+                ActionResult::FieldSet { field, value, .. } if field == "tier" => {
+                    Some((result.rule_id, value.clone()))
+                }
+                _ => None,
+            })
+        })
+        .collect();
+    writes.sort_by_key(|(rule_id, _)| *rule_id);
+    (writes, results)
+}
+
+#[test]
+fn test_collisions_are_reported_and_every_write_kept_by_default() {
+    let engine = tiering_engine();
+    assert_eq!(
+        engine.field_collision_policy(),
+        FieldCollisionPolicy::CollectAll
+    );
+
+    let (writes, results) = tier_writes(&engine);
+    assert_eq!(writes.len(), 2);
+    for result in &results {
+        let expected = if result.rule_id == 3 { 0 } else { 1 };
+        assert_eq!(
+            result.field_collisions.len(),
+            expected,
+            "rule {}",
+            result.rule_id
+        );
+    }
+
+    let collision = &results.iter().find(|result| result.rule_id == 1).unwrap().field_collisions[0];
+    assert_eq!((collision.fact_id, collision.field.as_str()), (1, "tier"));
+    assert_eq!(collision.writes.len(), 2);
+    assert_eq!(collision.winner, None);
+}
+
+#[test]
+fn test_highest_salience_wins() {
+    let engine = tiering_engine();
+    engine.set_field_collision_policy(FieldCollisionPolicy::HighestSalience);
+
+    // Equal salience falls back to the lower rule ID
+    let (writes, _) = tier_writes(&engine);
+    assert_eq!(writes, vec![(1, FactValue::String("gold".to_string()))]);
+
+    engine.set_rule_salience(2, 10).unwrap();
+    assert_eq!(engine.rule_salience(2), 10);
+    let (writes, results) = tier_writes(&engine);
+    assert_eq!(writes, vec![(2, FactValue::String("platinum".to_string()))]);
+    let loser = results.iter().find(|result| result.rule_id == 1).unwrap();
+    assert!(loser.actions_executed.is_empty());
+    assert_eq!(loser.field_collisions[0].winner, Some(2));
+
+    // Other rules' writes are untouched
+    assert!(
+        results
+            .iter()
+            .any(|result| result.rule_id == 3 && !result.actions_executed.is_empty())
+    );
+
+    assert!(engine.set_rule_salience(99, 1).is_err());
+}
+
+#[test]
+fn test_error_policy_fails_the_batch() {
+    let engine = tiering_engine();
+    engine.set_field_collision_policy(FieldCollisionPolicy::Error);
+
+    let error = engine.process_facts(vec![customer(1)]).unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains("Rules [1, 2] set field 'tier' of fact 1 to different values"),
+        "{error}"
+    );
+
+    // Rules agreeing on the value do not collide
+    engine.remove_rule(2).unwrap();
+    let also_gold = r#"rule "Big spender" id 4 when spend > 3000 then set tier = "gold""#;
+    engine.add_rule(parse_rule(also_gold).unwrap()).unwrap();
+    let results = engine.process_facts(vec![customer(1)]).unwrap();
+    assert_eq!(results.len(), 3);
+    assert!(results.iter().all(|result| result.field_collisions.is_empty()));
+}
@@ -0,0 +1,46 @@
+//! Field Typo Test
+//!
+//! Validates that the engine flags rule fields never seen in processed facts that
+//! are a near-miss of an observed field.
+
+use bingo_core::BingoEngine;
+use bingo_core::types::*;
+use std::collections::HashMap;
+
+fn total_rule(id: u64, field: &str) -> Rule {
+    Rule {
+        id,
+        name: format!("Large {field}"),
+        conditions: vec![Condition::Simple {
+            field: field.to_string(),
+            operator: Operator::GreaterThan,
+            value: FactValue::Integer(100),
+        }],
+        actions: vec![Action {
+            action_type: ActionType::Log { message: "Large order".to_string() },
+        }],
+        metadata: Default::default(),
+    }
+}
+
+#[test]
+fn test_engine_flags_misspelt_rule_fields() {
+    let engine = BingoEngine::new().unwrap();
+    engine.add_rule(total_rule(1, "order_total")).unwrap();
+    engine.add_rule(total_rule(2, "order_totl")).unwrap();
+    engine.add_rule(total_rule(3, "discount")).unwrap();
+
+    // Nothing to compare against before facts arrive
+    assert!(engine.field_typo_warnings().is_empty());
+
+    let mut fields = HashMap::new();
+    fields.insert("order_total".to_string(), FactValue::Integer(150));
+    fields.insert("customer_id".to_string(), FactValue::Integer(7));
+    engine.process_facts(vec![Fact::new(1, FactData { fields })]).unwrap();
+
+    let warnings = engine.field_typo_warnings();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].rule_id, 2);
+    assert_eq!(warnings[0].field, "order_totl");
+    assert_eq!(warnings[0].suggestion, "order_total");
+}
@@ -0,0 +1,153 @@
+//! Follower Engine Test
+//!
+//! Validates that read-only followers serve queries, explanations and statistics from
+//! a snapshot while the primary engine keeps processing facts.
+
+use bingo_core::BingoEngine;
+use bingo_core::types::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::thread;
+
+fn create_fact(id: u64, score: i64, status: &str) -> Fact {
+    let mut fields = HashMap::new();
+    fields.insert("score".to_string(), FactValue::Integer(score));
+    fields.insert("status".to_string(), FactValue::String(status.to_string()));
+    Fact {
+        id,
+        external_id: Some(format!("emp-{id}")),
+        timestamp: chrono::Utc::now(),
+        fact_type: None,
+        data: FactData { fields },
+    }
+}
+
+fn high_performer_rule() -> Rule {
+    Rule {
+        id: 1,
+        name: "High Performer".to_string(),
+        conditions: vec![
+            Condition::Simple {
+                field: "score".to_string(),
+                operator: Operator::GreaterThan,
+                value: FactValue::Integer(90),
+            },
+            Condition::Simple {
+                field: "status".to_string(),
+                operator: Operator::Equal,
+                value: FactValue::String("active".to_string()),
+            },
+        ],
+        actions: vec![Action {
+            action_type: ActionType::Log { message: "High performer detected".to_string() },
+        }],
+        metadata: Default::default(),
+    }
+}
+
+fn primary_with_facts() -> BingoEngine {
+    let engine = BingoEngine::new().unwrap();
+    engine.add_rule(high_performer_rule()).unwrap();
+    engine
+        .process_facts(vec![
+            create_fact(1, 95, "active"),
+            create_fact(2, 40, "active"),
+        ])
+        .unwrap();
+    engine
+}
+
+#[test]
+fn test_follower_serves_queries_from_snapshot() {
+    let primary = primary_with_facts();
+    let follower = primary.spawn_follower().unwrap();
+
+    assert_eq!(follower.rule_count(), 1);
+    assert_eq!(follower.fact_count(), 2);
+    assert_eq!(
+        follower.get_stats().fact_count,
+        primary.get_stats().fact_count
+    );
+    assert_eq!(
+        follower.get_field_by_id("emp-1", "score"),
+        Some(FactValue::Integer(95))
+    );
+    assert_eq!(
+        follower.find_by_field("status", &FactValue::String("active".to_string())).len(),
+        2
+    );
+}
+
+#[test]
+fn test_follower_explains_rule_matches() {
+    let primary = primary_with_facts();
+    let follower = primary.spawn_follower().unwrap();
+
+    let matched = follower.explain_fact_by_id("emp-1").unwrap();
+    assert_eq!(matched.len(), 1);
+    assert!(matched[0].matched);
+    assert_eq!(matched[0].condition_results, vec![true, true]);
+
+    let unmatched = follower.explain_fact_by_id("emp-2").unwrap();
+    assert!(!unmatched[0].matched);
+    assert_eq!(unmatched[0].condition_results, vec![false, true]);
+
+    assert!(follower.explain_fact_by_id("emp-404").is_err());
+
+    // Explaining does not change what the follower holds
+    assert_eq!(follower.fact_count(), 2);
+}
+
+#[test]
+fn test_follower_is_isolated_from_later_mutations() {
+    let primary = primary_with_facts();
+    let mut follower = primary.spawn_follower().unwrap();
+
+    primary.process_facts(vec![create_fact(3, 99, "active")]).unwrap();
+    assert_eq!(primary.fact_count(), 3);
+    assert_eq!(follower.fact_count(), 2);
+    assert!(follower.lookup_fact_by_id("emp-3").is_none());
+
+    follower.refresh(primary.snapshot().unwrap());
+    assert_eq!(follower.fact_count(), 3);
+    assert!(follower.lookup_fact_by_id("emp-3").is_some());
+}
+
+#[test]
+fn test_followers_read_concurrently_with_primary_writes() {
+    let primary = Arc::new(primary_with_facts());
+    let follower = primary.spawn_follower().unwrap();
+
+    let readers: Vec<_> = (0..4)
+        .map(|_| {
+            let follower = follower.clone();
+            thread::spawn(move || {
+                for _ in 0..50 {
+                    assert_eq!(follower.fact_count(), 2);
+                    assert!(follower.explain_fact_by_id("emp-1").unwrap()[0].matched);
+                }
+            })
+        })
+        .collect();
+
+    let writer = {
+        let primary = primary.clone();
+        thread::spawn(move || {
+            for id in 10..60 {
+                primary.process_facts(vec![create_fact(id, 91, "active")]).unwrap();
+            }
+        })
+    };
+
+    for reader in readers {
+        reader.join().unwrap();
+    }
+    writer.join().unwrap();
+
+    // Clones share one snapshot
+    assert!(Arc::ptr_eq(
+        follower.snapshot(),
+        follower.clone().snapshot()
+    ));
+    assert_eq!(primary.fact_count(), 52);
+}
@@ -28,6 +28,7 @@ fn test_formula_action_basic_arithmetic() {
                 output_field: "total_price".to_string(),
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).unwrap();
@@ -44,6 +45,7 @@ fn test_formula_action_basic_arithmetic() {
         id: 1,
         external_id: Some("item-1".to_string()),
         timestamp: Utc::now(),
+        fact_type: None,
         data: FactData { fields },
     };
 
@@ -138,6 +140,7 @@ fn test_formula_action_field_reference() {
                 output_field: "copied_amount".to_string(),
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).unwrap();
@@ -150,6 +153,7 @@ fn test_formula_action_field_reference() {
         id: 1,
         external_id: Some("test-1".to_string()),
         timestamp: Utc::now(),
+        fact_type: None,
         data: FactData { fields },
     };
 
@@ -0,0 +1,128 @@
+//! Forward Chaining Test
+//!
+//! Validates that facts created by rule actions flow back through the network when
+//! forward chaining is enabled: multi-step derivations complete in one call, derived
+//! facts do not overwrite asserted ones, repeated conclusions stop a loop, and chains
+//! exceeding the iteration limit fail.
+
+use bingo_core::rete_nodes::ActionResult;
+use bingo_core::types::*;
+use bingo_core::{BingoEngine, ForwardChaining, parse_rules};
+use std::collections::HashMap;
+
+fn order(id: u64, amount: i64) -> Fact {
+    let fields = HashMap::from([
+        ("kind".to_string(), FactValue::String("order".to_string())),
+        ("amount".to_string(), FactValue::Integer(amount)),
+    ]);
+    Fact::new(id, FactData { fields })
+}
+
+/// Large orders are flagged, flagged orders need review and reviews are escalated
+fn escalation_engine() -> BingoEngine {
+    let engine = BingoEngine::new().unwrap();
+    engine
+        .add_rules(
+            parse_rules(
+                r#"
+                rule "Flag" id 1 when kind == "order" and amount > 1000
+                then create { kind: "flag" }
+                rule "Review" id 2 when kind == "flag" then create { kind: "review" }
+                rule "Escalate" id 3 when kind == "review" then log "escalated"
+                "#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+    engine
+}
+
+fn fired_rules(results: &[bingo_core::RuleExecutionResult]) -> Vec<u64> {
+    let mut rules: Vec<u64> = results.iter().map(|result| result.rule_id).collect();
+    rules.sort_unstable();
+    rules
+}
+
+#[test]
+fn test_created_facts_are_only_returned_by_default() {
+    let engine = escalation_engine();
+    let results = engine.process_facts(vec![order(1, 5000)]).unwrap();
+    assert_eq!(fired_rules(&results), vec![1]);
+    assert_eq!(engine.fact_count(), 1);
+}
+
+#[test]
+fn test_derivations_complete_in_one_call() {
+    let engine = escalation_engine();
+    engine.set_forward_chaining(Some(ForwardChaining::default()));
+
+    let results = engine.process_facts(vec![order(1, 5000), order(2, 10)]).unwrap();
+    assert_eq!(fired_rules(&results), vec![1, 2, 3]);
+
+    // Derived facts are stored next to the asserted ones without replacing them
+    assert_eq!(engine.fact_count(), 4);
+    let created: Vec<u64> = results
+        .iter()
+        .flat_map(|result| &result.actions_executed)
+        .filter_map(|action| match action {
+            ActionResult::FactCreated { fact_id, .. } => Some(*fact_id),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(created.len(), 2);
+    assert!(created.iter().all(|id| ![1, 2].contains(id)));
+    for id in [1, 2] {
+        let stored = engine.get_fact(id).unwrap();
+        assert_eq!(
+            stored.data.fields["kind"],
+            FactValue::String("order".to_string())
+        );
+    }
+}
+
+#[test]
+fn test_repeated_conclusions_end_the_chain() {
+    let engine = BingoEngine::new().unwrap();
+    engine
+        .add_rules(
+            parse_rules(
+                r#"
+                rule "Ping" id 1 when kind == "ping" then create { kind: "pong" }
+                rule "Pong" id 2 when kind == "pong" then create { kind: "ping" }
+                "#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+    engine.set_forward_chaining(Some(ForwardChaining::default()));
+
+    let ping = Fact::new(
+        1,
+        FactData {
+            fields: HashMap::from([("kind".to_string(), FactValue::String("ping".to_string()))]),
+        },
+    );
+    let results = engine.process_facts(vec![ping]).unwrap();
+
+    // ping -> pong -> ping -> (pong already derived, not created again)
+    assert_eq!(fired_rules(&results), vec![1, 1, 2]);
+    assert_eq!(engine.fact_count(), 3);
+}
+
+#[test]
+fn test_chains_exceeding_the_limit_fail() {
+    let engine = escalation_engine();
+    engine.set_forward_chaining(Some(ForwardChaining { max_iterations: 1 }));
+
+    let error = engine.process_facts(vec![order(1, 5000)]).unwrap_err();
+    assert!(
+        error.to_string().contains("did not settle within 1 iterations"),
+        "{error}"
+    );
+
+    // Rebuilding the network keeps the setting
+    engine.set_forward_chaining(Some(ForwardChaining { max_iterations: 2 }));
+    engine.remove_rule(3).unwrap();
+    let results = engine.process_facts(vec![order(10, 5000)]).unwrap();
+    assert_eq!(fired_rules(&results), vec![1, 2]);
+}
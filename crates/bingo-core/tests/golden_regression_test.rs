@@ -0,0 +1,114 @@
+//! Golden Regression Test
+//!
+//! Validates the golden-file harness: a committed fact corpus run through a ruleset
+//! matches its committed golden file, mismatches produce a readable diff and update
+//! mode rewrites golden files.
+
+use bingo_core::golden::load_fact_corpus;
+use bingo_core::types::*;
+use bingo_core::{GoldenHarness, GoldenOutcome};
+use std::path::PathBuf;
+
+fn golden_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/golden")
+}
+
+fn log_rule(id: u64, condition: Condition, message: &str) -> Rule {
+    Rule {
+        id,
+        name: message.to_string(),
+        conditions: vec![condition],
+        actions: vec![Action { action_type: ActionType::Log { message: message.to_string() } }],
+        metadata: Default::default(),
+    }
+}
+
+fn review_rules() -> Vec<Rule> {
+    vec![
+        log_rule(
+            1,
+            Condition::Simple {
+                field: "score".to_string(),
+                operator: Operator::GreaterThan,
+                value: FactValue::Integer(90),
+            },
+            "High score",
+        ),
+        log_rule(
+            2,
+            Condition::Simple {
+                field: "status".to_string(),
+                operator: Operator::Equal,
+                value: FactValue::String("inactive".to_string()),
+            },
+            "Inactive employee",
+        ),
+    ]
+}
+
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("bingo-golden-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn test_corpus_matches_committed_golden_file() {
+    let facts = load_fact_corpus(golden_dir().join("employees.json")).unwrap();
+    assert_eq!(facts.len(), 3);
+
+    GoldenHarness::new(golden_dir()).assert_golden("employee_review", &review_rules(), facts);
+}
+
+#[test]
+fn test_behaviour_change_reports_diff() {
+    let facts = load_fact_corpus(golden_dir().join("employees.json")).unwrap();
+    let mut rules = review_rules();
+    // Raising the threshold drops the match for employee 1
+    rules[0].conditions[0] = Condition::Simple {
+        field: "score".to_string(),
+        operator: Operator::GreaterThan,
+        value: FactValue::Integer(96),
+    };
+
+    let outcome = GoldenHarness::new(golden_dir())
+        .with_update(false)
+        .check("employee_review", &rules, facts)
+        .unwrap();
+    let GoldenOutcome::Mismatch { diff, .. } = &outcome else {
+        panic!("expected a mismatch, got {outcome:?}");
+    };
+    assert_eq!(diff.change_counts(), (1, 0));
+    assert!(
+        diff.to_string()
+            .contains("- rule 1 fact 1: [{\"Logged\":{\"message\":\"High score\"}}]")
+    );
+    assert!(outcome.to_string().contains("BINGO_UPDATE_GOLDEN=1"));
+}
+
+#[test]
+fn test_update_mode_writes_golden_files() {
+    let dir = scratch_dir("update");
+    let facts = load_fact_corpus(golden_dir().join("employees.json")).unwrap();
+
+    let checking = GoldenHarness::new(&dir).with_update(false);
+    let missing = checking.check("review", &review_rules(), facts.clone()).unwrap();
+    assert!(matches!(missing, GoldenOutcome::Missing { .. }));
+    assert!(!missing.is_success());
+
+    let updated = GoldenHarness::new(&dir)
+        .with_update(true)
+        .check("review", &review_rules(), facts.clone())
+        .unwrap();
+    assert!(matches!(updated, GoldenOutcome::Updated { .. }));
+    assert_eq!(
+        std::fs::read_to_string(dir.join("review.golden")).unwrap(),
+        std::fs::read_to_string(golden_dir().join("employee_review.golden")).unwrap()
+    );
+
+    assert_eq!(
+        checking.check("review", &review_rules(), facts).unwrap(),
+        GoldenOutcome::Matched
+    );
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,117 @@
+//! Incremental Delta Propagation Test
+//!
+//! Validates that working memory is shared rather than cloned and that only newly
+//! asserted facts propagate through the beta network to terminal nodes.
+
+use bingo_calculator::calculator::Calculator;
+use bingo_core::fact_store::arena_store::ArenaFactStore;
+use bingo_core::rete_network::ReteNetwork;
+use bingo_core::types::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+fn create_fact(id: u64, score: i64, status: &str) -> Fact {
+    let mut fields = HashMap::new();
+    fields.insert("score".to_string(), FactValue::Integer(score));
+    fields.insert("status".to_string(), FactValue::String(status.to_string()));
+    Fact::new(id, FactData { fields })
+}
+
+fn high_performer_rule() -> Rule {
+    Rule {
+        id: 1,
+        name: "High Performer".to_string(),
+        conditions: vec![
+            Condition::Simple {
+                field: "score".to_string(),
+                operator: Operator::GreaterThan,
+                value: FactValue::Integer(90),
+            },
+            Condition::Simple {
+                field: "status".to_string(),
+                operator: Operator::Equal,
+                value: FactValue::String("active".to_string()),
+            },
+        ],
+        actions: vec![Action {
+            action_type: ActionType::Log { message: "High performer detected".to_string() },
+        }],
+        metadata: Default::default(),
+    }
+}
+
+#[test]
+fn test_working_memory_is_shared() {
+    let mut network = ReteNetwork::new();
+    let fact_store = ArenaFactStore::new();
+    let calculator = Calculator::new();
+    network.add_rule(high_performer_rule()).unwrap();
+
+    network
+        .add_fact_to_working_memory(create_fact(1, 95, "active"), &fact_store, &calculator)
+        .unwrap();
+
+    // Working memory and this handle point at the same allocation
+    let shared = network.working_memory_fact(1).unwrap();
+    assert_eq!(Arc::strong_count(&shared), 2);
+    assert!(Arc::ptr_eq(
+        &shared,
+        &network.working_memory_fact(1).unwrap()
+    ));
+}
+
+#[test]
+fn test_only_new_facts_reach_terminal_nodes() {
+    let mut network = ReteNetwork::new();
+    let fact_store = ArenaFactStore::new();
+    let calculator = Calculator::new();
+    network.add_rule(high_performer_rule()).unwrap();
+
+    let first = network
+        .add_fact_to_working_memory(create_fact(1, 95, "active"), &fact_store, &calculator)
+        .unwrap();
+    assert_eq!(first.len(), 1);
+
+    // Earlier matches are not re-activated when another fact arrives
+    let second = network
+        .add_fact_to_working_memory(create_fact(2, 97, "active"), &fact_store, &calculator)
+        .unwrap();
+    assert_eq!(second.len(), 1);
+    assert_eq!(second[0].fact_id, 2);
+
+    let partial = network
+        .add_fact_to_working_memory(create_fact(3, 99, "pending"), &fact_store, &calculator)
+        .unwrap();
+    assert!(partial.is_empty());
+    assert_eq!(network.activated_match_count(1), 2);
+
+    // A batch run does not disturb the incremental activations
+    network
+        .process_facts(&[create_fact(4, 95, "active")], &fact_store, &calculator)
+        .unwrap();
+    assert_eq!(network.activated_match_count(1), 2);
+}
+
+#[test]
+fn test_reasserted_fact_propagates_as_new_delta() {
+    let mut network = ReteNetwork::new();
+    let fact_store = ArenaFactStore::new();
+    let calculator = Calculator::new();
+    network.add_rule(high_performer_rule()).unwrap();
+
+    network
+        .add_fact_to_working_memory(create_fact(1, 95, "active"), &fact_store, &calculator)
+        .unwrap();
+
+    // Updating the fact replaces its earlier match
+    let updated = network
+        .add_fact_to_working_memory(create_fact(1, 96, "active"), &fact_store, &calculator)
+        .unwrap();
+    assert_eq!(updated.len(), 1);
+    assert_eq!(network.activated_match_count(1), 1);
+
+    // Retraction withdraws the activation
+    network.remove_fact_from_working_memory(1).unwrap();
+    assert_eq!(network.activated_match_count(1), 0);
+    assert_eq!(network.get_working_memory_stats().0, 0);
+}
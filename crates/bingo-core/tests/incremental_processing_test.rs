@@ -30,6 +30,7 @@ fn test_incremental_fact_addition() {
         actions: vec![Action {
             action_type: ActionType::Log { message: "High performer detected".to_string() },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).expect("Rule addition failed");
@@ -113,6 +114,7 @@ fn test_fact_retraction() {
         actions: vec![Action {
             action_type: ActionType::Log { message: "Active user found".to_string() },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).expect("Rule addition failed");
@@ -183,6 +185,7 @@ fn test_incremental_vs_batch_processing() {
         actions: vec![Action {
             action_type: ActionType::Log { message: "Premium transaction".to_string() },
         }],
+        metadata: Default::default(),
     };
 
     incremental_engine.add_rule(rule.clone()).expect("Rule addition failed");
@@ -255,6 +258,7 @@ fn test_working_memory_lifecycle() {
         actions: vec![Action {
             action_type: ActionType::Log { message: "Order processed".to_string() },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).expect("Rule addition failed");
@@ -319,6 +323,7 @@ fn test_alpha_memory_integration() {
         actions: vec![Action {
             action_type: ActionType::Log { message: "High priority item".to_string() },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).expect("Rule addition failed");
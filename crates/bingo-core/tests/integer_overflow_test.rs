@@ -0,0 +1,125 @@
+//! Integer Overflow Test
+//!
+//! Validates that `IncrementField` and formula actions never wrap around `i64`: each
+//! overflow policy fails, saturates or promotes the result, for rules built by hand
+//! and rules written in the rule language.
+
+use bingo_core::rete_nodes::ActionResult;
+use bingo_core::types::*;
+use bingo_core::{BingoEngine, parse_rule};
+use std::collections::HashMap;
+
+fn counter(id: u64, count: i64) -> Fact {
+    let fields = HashMap::from([("count".to_string(), FactValue::Integer(count))]);
+    Fact::new(id, FactData { fields })
+}
+
+fn accumulator_rule() -> Rule {
+    parse_rule(
+        r#"
+        rule "Accumulate" id 1
+        when count > 0
+        then increment count by 10
+        "#,
+    )
+    .unwrap()
+}
+
+fn doubling_rule() -> Rule {
+    parse_rule(
+        r#"
+        rule "Double" id 2
+        when count > 0
+        then formula doubled = "count * 2"
+        "#,
+    )
+    .unwrap()
+}
+
+/// The single action result of processing a counter holding `count`
+fn outcome(engine: &BingoEngine, count: i64) -> ActionResult {
+    let results = engine.process_facts(vec![counter(1, count)]).unwrap();
+    assert_eq!(results.len(), 1);
+    results[0].actions_executed[0].clone()
+}
+
+#[test]
+fn test_overflow_fails_the_action_by_default() {
+    let engine = BingoEngine::new().unwrap();
+    assert_eq!(engine.overflow_policy(), OverflowPolicy::Error);
+    engine.add_rule(accumulator_rule()).unwrap();
+
+    let ActionResult::Logged { message } = outcome(&engine, i64::MAX - 5) else {
+        panic!("overflowing increment should fail");
+    };
+    assert!(
+        message.contains("Integer overflow in 9223372036854775802 + 10"),
+        "{message}"
+    );
+
+    // Results in range are unaffected
+    assert!(matches!(
+        outcome(&engine, 5),
+        ActionResult::FieldIncremented { new_value: FactValue::Integer(15), .. }
+    ));
+}
+
+#[test]
+fn test_saturate_clamps_increments() {
+    let engine = BingoEngine::new().unwrap();
+    engine.set_overflow_policy(OverflowPolicy::Saturate);
+    engine.add_rule(accumulator_rule()).unwrap();
+
+    assert!(matches!(
+        outcome(&engine, i64::MAX - 5),
+        ActionResult::FieldIncremented { new_value: FactValue::Integer(i64::MAX), .. }
+    ));
+}
+
+#[test]
+fn test_promotion_keeps_the_result() {
+    let engine = BingoEngine::new().unwrap();
+    engine.set_overflow_policy(OverflowPolicy::PromoteToDecimal);
+    engine.add_rule(accumulator_rule()).unwrap();
+    assert_eq!(
+        outcome(&engine, i64::MAX),
+        ActionResult::FieldIncremented {
+            fact_id: 1,
+            field: "count".to_string(),
+            old_value: FactValue::Integer(i64::MAX),
+            new_value: FactValue::Decimal("9223372036854775817".parse().unwrap()),
+        }
+    );
+
+    // Rebuilding the network keeps the policy
+    engine.set_overflow_policy(OverflowPolicy::PromoteToFloat);
+    engine.remove_rule(1).unwrap();
+    engine.add_rule(doubling_rule()).unwrap();
+    assert_eq!(
+        outcome(&engine, i64::MAX),
+        ActionResult::FieldSet {
+            fact_id: 1,
+            field: "doubled".to_string(),
+            value: FactValue::Float(i64::MAX as f64 * 2.0),
+        }
+    );
+}
+
+#[test]
+fn test_formula_overflow_fails_by_default() {
+    let engine = BingoEngine::new().unwrap();
+    engine.add_rule(doubling_rule()).unwrap();
+
+    assert_eq!(
+        outcome(&engine, 21),
+        ActionResult::FieldSet {
+            fact_id: 1,
+            field: "doubled".to_string(),
+            value: FactValue::Integer(42),
+        }
+    );
+    let ActionResult::Logged { message } = outcome(&engine, i64::MAX) else {
+        panic!("overflowing formula should fail");
+    };
+    assert!(message.contains("Integer overflow"), "{message}");
+}
@@ -30,6 +30,7 @@ fn test_isolated_performance_components() {
                     id: i as u64,
                     external_id: None,
                     timestamp: chrono::Utc::now(),
+                    fact_type: None,
                     data: FactData { fields },
                 }
             })
@@ -96,6 +97,7 @@ fn test_isolated_performance_components() {
                     value: FactValue::Boolean(true),
                 },
             }],
+            metadata: Default::default(),
         };
         engine.add_rule(rule).unwrap();
 
@@ -112,6 +114,7 @@ fn test_isolated_performance_components() {
                     id: i as u64,
                     external_id: None,
                     timestamp: chrono::Utc::now(),
+                    fact_type: None,
                     data: FactData { fields },
                 }
             })
@@ -0,0 +1,250 @@
+//! Kafka Connector Test
+//!
+//! Validates that the connector fires a session's rules for consumed records, publishes
+//! the results before committing the batch's offsets, leaves offsets uncommitted when
+//! publishing fails so the batch is redelivered, and dead-letters undecodable records.
+
+#![cfg(feature = "kafka")]
+
+use bingo_core::{
+    BingoEngine, BingoSession, KafkaConnector, KafkaConnectorConfig, KafkaConsumer, KafkaProducer,
+    KafkaRecord, TopicPartitionOffset, parse_rule,
+};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// In-memory broker shared by the mock consumer and producer
+#[derive(Default)]
+struct Broker {
+    pending: VecDeque<KafkaRecord>,
+    /// Records delivered since the last commit, redelivered on rebalance
+    in_flight: Vec<KafkaRecord>,
+    committed: HashMap<(String, i32), i64>,
+    queued: Vec<(String, Option<Vec<u8>>, Vec<u8>)>,
+    published: Vec<(String, Option<Vec<u8>>, Vec<u8>)>,
+    fail_flush: bool,
+}
+
+impl Broker {
+    fn produce(&mut self, partition: i32, key: Option<&str>, payload: &str) {
+        let offset = self.pending.iter().filter(|record| record.partition == partition).count();
+        self.pending.push_back(KafkaRecord {
+            topic: "facts".to_string(),
+            partition,
+            offset: offset as i64,
+            key: key.map(|key| key.as_bytes().to_vec()),
+            payload: payload.as_bytes().to_vec(),
+        });
+    }
+
+    /// Consumer group restart: uncommitted records are delivered again
+    fn rebalance(&mut self) {
+        for record in self.in_flight.drain(..).rev() {
+            self.pending.push_front(record);
+        }
+    }
+
+    fn published_to(&self, topic: &str) -> Vec<serde_json::Value> {
+        self.published
+            .iter()
+            .filter(|(published_topic, _, _)| published_topic == topic)
+            .map(|(_, _, payload)| serde_json::from_slice(payload).unwrap_or_default())
+            .collect()
+    }
+}
+
+struct MockConsumer(Arc<Mutex<Broker>>);
+
+impl KafkaConsumer for MockConsumer {
+    fn subscribe(&mut self, topics: &[String]) -> Result<(), String> {
+        assert_eq!(topics, ["facts".to_string()]);
+        Ok(())
+    }
+
+    fn poll(&mut self, max_records: usize, _timeout: Duration) -> Result<Vec<KafkaRecord>, String> {
+        let mut broker = self.0.lock().unwrap();
+        let count = max_records.min(broker.pending.len());
+        let records: Vec<_> = broker.pending.drain(..count).collect();
+        broker.in_flight.extend(records.iter().cloned());
+        Ok(records)
+    }
+
+    fn commit(&mut self, offsets: &[TopicPartitionOffset]) -> Result<(), String> {
+        let mut broker = self.0.lock().unwrap();
+        for position in offsets {
+            broker.committed.insert(
+                (position.topic.clone(), position.partition),
+                position.offset,
+            );
+        }
+        broker.in_flight.clear();
+        Ok(())
+    }
+}
+
+struct MockProducer(Arc<Mutex<Broker>>);
+
+impl KafkaProducer for MockProducer {
+    fn send(&mut self, topic: &str, key: Option<&[u8]>, payload: &[u8]) -> Result<(), String> {
+        let mut broker = self.0.lock().unwrap();
+        broker
+            .queued
+            .push((topic.to_string(), key.map(<[u8]>::to_vec), payload.to_vec()));
+        Ok(())
+    }
+
+    fn flush(&mut self, _timeout: Duration) -> Result<(), String> {
+        let mut broker = self.0.lock().unwrap();
+        if broker.fail_flush {
+            broker.queued.clear();
+            return Err("Broker unavailable".to_string());
+        }
+        let queued = std::mem::take(&mut broker.queued);
+        broker.published.extend(queued);
+        Ok(())
+    }
+}
+
+fn connector(
+    broker: &Arc<Mutex<Broker>>,
+    config: KafkaConnectorConfig,
+) -> KafkaConnector<MockConsumer, MockProducer> {
+    let engine = BingoEngine::new().unwrap();
+    let rule = r#"rule "Large order" id 1 when amount > 100 then set flagged = true"#;
+    engine.add_rule(parse_rule(rule).unwrap()).unwrap();
+    let session = Arc::new(BingoSession::new(Arc::new(engine)));
+    KafkaConnector::new(
+        MockConsumer(Arc::clone(broker)),
+        MockProducer(Arc::clone(broker)),
+        session,
+        config,
+    )
+    .unwrap()
+}
+
+fn config() -> KafkaConnectorConfig {
+    KafkaConnectorConfig::new(vec!["facts".to_string()], "results")
+}
+
+#[test]
+fn test_results_are_published_before_offsets_are_committed() {
+    let broker = Arc::new(Mutex::new(Broker::default()));
+    {
+        let mut broker = broker.lock().unwrap();
+        broker.produce(0, Some("order-1"), r#"{"amount": 250}"#);
+        broker.produce(0, Some("order-2"), r#"{"amount": 20}"#);
+        broker.produce(1, Some("order-3"), r#"{"amount": 500}"#);
+    }
+    let mut connector = connector(&broker, config());
+
+    let outcome = connector.poll_once().unwrap();
+    assert_eq!(
+        (outcome.records, outcome.facts, outcome.results_published),
+        (3, 3, 2)
+    );
+
+    let broker = broker.lock().unwrap();
+    let mut results = broker.published_to("results");
+    results.sort_by_key(|result| result["external_id"].as_str().unwrap().to_string());
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0]["external_id"], "order-1");
+    assert_eq!(results[0]["rule_id"], 1);
+    assert_eq!(results[0]["source"]["partition"], 0);
+    assert_eq!(results[0]["source"]["offset"], 0);
+    assert_eq!(results[1]["external_id"], "order-3");
+    assert_eq!(results[1]["source"]["partition"], 1);
+
+    // Results are keyed by the fact's external ID
+    assert!(broker.published.iter().all(|(_, key, _)| key.is_some()));
+    assert_eq!(broker.committed[&("facts".to_string(), 0)], 2);
+    assert_eq!(broker.committed[&("facts".to_string(), 1)], 1);
+    drop(broker);
+
+    assert_eq!(connector.poll_once().unwrap().records, 0);
+    assert_eq!(connector.stats().results_published, 2);
+}
+
+#[test]
+fn test_failed_publish_leaves_the_batch_for_redelivery() {
+    let broker = Arc::new(Mutex::new(Broker::default()));
+    broker.lock().unwrap().produce(0, Some("order-1"), r#"{"amount": 250}"#);
+    broker.lock().unwrap().fail_flush = true;
+    let mut connector = connector(&broker, config());
+
+    assert!(connector.poll_once().is_err());
+    assert!(broker.lock().unwrap().committed.is_empty());
+    assert_eq!(connector.stats().batches, 0);
+
+    {
+        let mut broker = broker.lock().unwrap();
+        broker.fail_flush = false;
+        broker.rebalance();
+    }
+    let outcome = connector.poll_once().unwrap();
+    assert_eq!(outcome.results_published, 1);
+
+    let broker = broker.lock().unwrap();
+    assert_eq!(broker.published_to("results").len(), 1);
+    assert_eq!(broker.committed[&("facts".to_string(), 0)], 1);
+}
+
+#[test]
+fn test_undecodable_records_are_dead_lettered_or_skipped() {
+    let broker = Arc::new(Mutex::new(Broker::default()));
+    {
+        let mut broker = broker.lock().unwrap();
+        broker.produce(0, None, "not json");
+        broker.produce(0, None, r#"{"amount": 250}"#);
+        broker.produce(0, None, "[1, 2]");
+    }
+    let mut dead_letter_config = config();
+    dead_letter_config.dead_letter_topic = Some("facts.dlq".to_string());
+    let mut connector = connector(&broker, dead_letter_config);
+
+    let outcome = connector.poll_once().unwrap();
+    assert_eq!(
+        (outcome.facts, outcome.dead_lettered, outcome.skipped),
+        (1, 2, 0)
+    );
+    {
+        let broker = broker.lock().unwrap();
+        let dead_letters: Vec<_> = broker
+            .published
+            .iter()
+            .filter(|(topic, _, _)| topic == "facts.dlq")
+            .map(|(_, _, payload)| payload.as_slice())
+            .collect();
+        assert_eq!(
+            dead_letters,
+            vec![b"not json".as_slice(), b"[1, 2]".as_slice()]
+        );
+        assert_eq!(broker.committed[&("facts".to_string(), 0)], 3);
+    }
+
+    // Without a dead-letter topic the records are skipped but still committed
+    broker.lock().unwrap().produce(0, None, "not json");
+    let mut connector = self::connector(&broker, config());
+    let outcome = connector.poll_once().unwrap();
+    assert_eq!((outcome.facts, outcome.skipped), (0, 1));
+    assert_eq!(
+        broker.lock().unwrap().committed[&("facts".to_string(), 0)],
+        1
+    );
+}
+
+#[test]
+fn test_invalid_configuration_is_rejected() {
+    let broker = Arc::new(Mutex::new(Broker::default()));
+    let engine = Arc::new(BingoEngine::new().unwrap());
+    let mut config = config();
+    config.max_batch_records = 0;
+
+    let result = KafkaConnector::new(
+        MockConsumer(Arc::clone(&broker)),
+        MockProducer(broker),
+        Arc::new(BingoSession::new(engine)),
+        config,
+    );
+    assert!(result.is_err());
+}
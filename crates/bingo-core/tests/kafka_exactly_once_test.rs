@@ -0,0 +1,236 @@
+//! Kafka Exactly-Once Test
+//!
+//! Validates that an exactly-once connector publishes each batch's results together with
+//! a checkpoint in one transaction, that a batch whose transaction fails publishes
+//! nothing, and that a connector resumed from the latest checkpoint after a crash fires
+//! every rule exactly once.
+
+#![cfg(feature = "kafka")]
+
+use bingo_core::{
+    BingoEngine, BingoSession, ConnectorCheckpoint, KafkaConnector, KafkaConnectorConfig,
+    KafkaConsumer, KafkaProducer, KafkaRecord, ProcessingMode, TopicPartitionOffset, parse_rule,
+};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Topic, key and payload of a record sent in an open transaction
+type Sent = (String, Option<Vec<u8>>, Vec<u8>);
+
+/// In-memory broker with transactional producers
+#[derive(Default)]
+struct Broker {
+    /// Committed records of each topic, on partition 0
+    topics: HashMap<String, Vec<KafkaRecord>>,
+    transaction: Option<Vec<Sent>>,
+    fail_commit: bool,
+}
+
+impl Broker {
+    fn append(&mut self, topic: &str, key: Option<Vec<u8>>, payload: Vec<u8>) {
+        let records = self.topics.entry(topic.to_string()).or_default();
+        records.push(KafkaRecord {
+            topic: topic.to_string(),
+            partition: 0,
+            offset: records.len() as i64,
+            key,
+            payload,
+        });
+    }
+
+    fn records(&self, topic: &str) -> &[KafkaRecord] {
+        self.topics.get(topic).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    fn results(&self) -> Vec<serde_json::Value> {
+        self.records("results")
+            .iter()
+            .map(|record| serde_json::from_slice(&record.payload).unwrap())
+            .collect()
+    }
+}
+
+/// Consumer starting from the earliest offset, as after a crash without resuming
+struct MockConsumer {
+    broker: Arc<Mutex<Broker>>,
+    position: i64,
+}
+
+impl KafkaConsumer for MockConsumer {
+    fn subscribe(&mut self, _topics: &[String]) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn poll(&mut self, max_records: usize, _timeout: Duration) -> Result<Vec<KafkaRecord>, String> {
+        let broker = self.broker.lock().unwrap();
+        let records: Vec<_> = broker
+            .records("orders")
+            .iter()
+            .skip(self.position as usize)
+            .take(max_records)
+            .cloned()
+            .collect();
+        self.position += records.len() as i64;
+        Ok(records)
+    }
+
+    fn commit(&mut self, _offsets: &[TopicPartitionOffset]) -> Result<(), String> {
+        panic!("exactly-once connectors keep offsets in checkpoints");
+    }
+
+    fn seek(&mut self, offsets: &[TopicPartitionOffset]) -> Result<(), String> {
+        for position in offsets {
+            assert_eq!((position.topic.as_str(), position.partition), ("orders", 0));
+            self.position = position.offset;
+        }
+        Ok(())
+    }
+}
+
+struct TransactionalProducer(Arc<Mutex<Broker>>);
+
+impl KafkaProducer for TransactionalProducer {
+    fn send(&mut self, topic: &str, key: Option<&[u8]>, payload: &[u8]) -> Result<(), String> {
+        let mut broker = self.0.lock().unwrap();
+        let transaction = broker.transaction.as_mut().ok_or("No open transaction")?;
+        transaction.push((topic.to_string(), key.map(<[u8]>::to_vec), payload.to_vec()));
+        Ok(())
+    }
+
+    fn flush(&mut self, _timeout: Duration) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn begin_transaction(&mut self) -> Result<(), String> {
+        self.0.lock().unwrap().transaction = Some(Vec::new());
+        Ok(())
+    }
+
+    fn commit_transaction(&mut self, _timeout: Duration) -> Result<(), String> {
+        let mut broker = self.0.lock().unwrap();
+        if broker.fail_commit {
+            return Err("Transaction coordinator unavailable".to_string());
+        }
+        for (topic, key, payload) in broker.transaction.take().unwrap_or_default() {
+            broker.append(&topic, key, payload);
+        }
+        Ok(())
+    }
+
+    fn abort_transaction(&mut self, _timeout: Duration) -> Result<(), String> {
+        self.0.lock().unwrap().transaction = None;
+        Ok(())
+    }
+}
+
+fn exactly_once_config() -> KafkaConnectorConfig {
+    let mut config = KafkaConnectorConfig::new(vec!["orders".to_string()], "results");
+    config.max_batch_records = 2;
+    config.processing_mode =
+        ProcessingMode::ExactlyOnce { checkpoint_topic: "checkpoints".to_string() };
+    config
+}
+
+/// Connector over a fresh engine, as started by a new process
+fn connector(broker: &Arc<Mutex<Broker>>) -> KafkaConnector<MockConsumer, TransactionalProducer> {
+    let engine = BingoEngine::new().unwrap();
+    let rule = r#"rule "Large order" id 1 when amount > 100 then set flagged = true"#;
+    engine.add_rule(parse_rule(rule).unwrap()).unwrap();
+    KafkaConnector::new(
+        MockConsumer { broker: Arc::clone(broker), position: 0 },
+        TransactionalProducer(Arc::clone(broker)),
+        Arc::new(BingoSession::new(Arc::new(engine))),
+        exactly_once_config(),
+    )
+    .unwrap()
+}
+
+fn produce(broker: &Arc<Mutex<Broker>>, orders: &[(&str, i64)]) {
+    let mut broker = broker.lock().unwrap();
+    for (key, amount) in orders {
+        let payload = serde_json::json!({ "amount": amount }).to_string();
+        broker.append(
+            "orders",
+            Some(key.as_bytes().to_vec()),
+            payload.into_bytes(),
+        );
+    }
+}
+
+#[test]
+fn test_replay_after_crash_fires_each_rule_once() {
+    let broker = Arc::new(Mutex::new(Broker::default()));
+    produce(&broker, &[("order-1", 250), ("order-2", 20)]);
+    let mut first = connector(&broker);
+
+    assert_eq!(first.poll_once().unwrap().results_published, 1);
+    {
+        let broker = broker.lock().unwrap();
+        assert_eq!(broker.results().len(), 1);
+        let checkpoint = &broker.records("checkpoints")[0];
+        assert_eq!(checkpoint.key.as_deref(), Some(ConnectorCheckpoint::KEY));
+        let checkpoint = ConnectorCheckpoint::from_bytes(&checkpoint.payload).unwrap();
+        assert_eq!(checkpoint.offsets[0].offset, 2);
+    }
+
+    // The second batch fires its rule but its transaction fails, publishing nothing
+    produce(&broker, &[("order-3", 900), ("order-4", 300)]);
+    broker.lock().unwrap().fail_commit = true;
+    assert!(first.poll_once().is_err());
+    assert_eq!(broker.lock().unwrap().results().len(), 1);
+    assert_eq!(broker.lock().unwrap().records("checkpoints").len(), 1);
+    assert!(
+        first.poll_once().is_err(),
+        "a failed connector must be resumed"
+    );
+    drop(first);
+
+    // A new process resumes from the latest checkpoint and replays only the second batch
+    broker.lock().unwrap().fail_commit = false;
+    produce(&broker, &[("order-5", 150)]);
+    let mut second = connector(&broker);
+    let checkpoint = broker.lock().unwrap().records("checkpoints").last().unwrap().payload.clone();
+    second.resume(&checkpoint).unwrap();
+    while second.poll_once().unwrap().records > 0 {}
+
+    let results = broker.lock().unwrap().results();
+    let external_ids: Vec<_> = results.iter().map(|r| r["external_id"].as_str().unwrap()).collect();
+    assert_eq!(external_ids, ["order-1", "order-3", "order-4", "order-5"]);
+    let fact_ids: Vec<_> = results.iter().map(|r| r["fact_id"].as_u64().unwrap()).collect();
+    assert_eq!(fact_ids, [1, 3, 4, 5]);
+    assert_eq!(second.session().engine().fact_count(), 5);
+    assert_eq!(second.stats().records, 3);
+}
+
+#[test]
+fn test_exactly_once_requires_a_checkpoint_topic_and_transactions() {
+    let broker = Arc::new(Mutex::new(Broker::default()));
+    let session = || {
+        let engine = Arc::new(BingoEngine::new().unwrap());
+        Arc::new(BingoSession::new(engine))
+    };
+    let consumer = || MockConsumer { broker: Arc::clone(&broker), position: 0 };
+
+    let mut config = exactly_once_config();
+    config.processing_mode =
+        ProcessingMode::ExactlyOnce { checkpoint_topic: "results".to_string() };
+    let producer = TransactionalProducer(Arc::clone(&broker));
+    assert!(KafkaConnector::new(consumer(), producer, session(), config).is_err());
+
+    /// Producer without transaction support
+    struct PlainProducer;
+    impl KafkaProducer for PlainProducer {
+        fn send(&mut self, _: &str, _: Option<&[u8]>, _: &[u8]) -> Result<(), String> {
+            Ok(())
+        }
+        fn flush(&mut self, _timeout: Duration) -> Result<(), String> {
+            Ok(())
+        }
+    }
+    produce(&broker, &[("order-1", 250)]);
+    let mut connector =
+        KafkaConnector::new(consumer(), PlainProducer, session(), exactly_once_config()).unwrap();
+    let error = connector.poll_once().unwrap_err();
+    assert!(error.to_string().contains("transactions"), "{error}");
+}
@@ -44,6 +44,7 @@ fn create_fact(
         id,
         external_id: Some(format!("fact-{id}")),
         timestamp: Utc::now(),
+        fact_type: None,
         data: FactData { fields },
     }
 }
@@ -77,6 +78,7 @@ fn test_lazy_aggregation_basic_functionality() {
                 message: "High spending department detected".to_string(),
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).unwrap();
@@ -144,6 +146,7 @@ fn test_lazy_aggregation_caching() {
         actions: vec![Action {
             action_type: ActionType::Log { message: "Large department detected".to_string() },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).unwrap();
@@ -198,6 +201,7 @@ fn test_lazy_aggregation_early_termination() {
         actions: vec![Action {
             action_type: ActionType::Log { message: "Department activity detected".to_string() },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).unwrap();
@@ -242,6 +246,7 @@ fn test_lazy_aggregation_cache_invalidation() {
         actions: vec![Action {
             action_type: ActionType::Log { message: "Department total calculated".to_string() },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).unwrap();
@@ -292,6 +297,7 @@ fn test_lazy_aggregation_memory_cleanup() {
         actions: vec![Action {
             action_type: ActionType::Log { message: "Department average calculated".to_string() },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).unwrap();
@@ -360,6 +366,7 @@ fn test_lazy_aggregation_performance_comparison() {
         actions: vec![Action {
             action_type: ActionType::Log { message: "High department total detected".to_string() },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).unwrap();
@@ -0,0 +1,110 @@
+//! Memory Pressure Integration Test
+//!
+//! Validates that the engine consults its memory watermarks before inserting facts,
+//! sheds or spills working memory through registered handlers, and rejects inserts
+//! when nothing else can relieve the pressure.
+
+use bingo_core::memory_pressure::DEFAULT_BYTES_PER_FACT;
+use bingo_core::*;
+use std::collections::HashMap;
+
+fn create_reading(id: u64, sensor: i64, priority: i64, age_minutes: i64) -> Fact {
+    let mut fields = HashMap::new();
+    fields.insert("sensor_id".to_string(), FactValue::Integer(sensor));
+    fields.insert("priority".to_string(), FactValue::Integer(priority));
+    let mut fact = Fact::new(id, FactData { fields });
+    fact.timestamp = chrono::Utc::now() - chrono::Duration::minutes(age_minutes);
+    fact
+}
+
+/// Engine preloaded with four readings whose watermarks trip on the next insert
+fn engine_at_warning_watermark() -> BingoEngine {
+    let engine = BingoEngine::new().unwrap();
+    engine
+        .process_facts(vec![
+            create_reading(1, 1, 1, 40),
+            create_reading(2, 1, 2, 30),
+            create_reading(3, 2, 3, 20),
+            create_reading(4, 2, 4, 10),
+        ])
+        .unwrap();
+
+    let used = engine.estimated_memory_usage();
+    engine.set_memory_watermarks(
+        MemoryWatermarks::new(
+            used,
+            used + 2 * DEFAULT_BYTES_PER_FACT,
+            used + 4 * DEFAULT_BYTES_PER_FACT,
+        )
+        .unwrap(),
+    );
+    engine
+}
+
+#[test]
+fn test_priority_shedding_makes_room_for_inserts() {
+    let engine = engine_at_warning_watermark();
+    engine
+        .register_memory_pressure_handler(PressureLevel::Warning, PriorityShedder::new("priority"));
+
+    engine.add_fact_to_working_memory(create_reading(5, 3, 5, 0)).unwrap();
+
+    // The two lowest-priority readings were shed to get back under the warning watermark
+    assert_eq!(engine.fact_count(), 3);
+    let stats = engine.memory_pressure_stats();
+    assert_eq!(stats.facts_shed, 2);
+    assert_eq!(stats.pressure_events, 1);
+    assert_eq!(stats.last_level, PressureLevel::Normal);
+}
+
+#[test]
+fn test_cold_partitions_spill_to_disk() {
+    let engine = engine_at_warning_watermark();
+    let spill_dir = std::env::temp_dir().join(format!("bingo-spill-{}", uuid::Uuid::new_v4()));
+    engine.register_memory_pressure_handler(
+        PressureLevel::Warning,
+        DiskSpiller::new(&spill_dir, "sensor_id"),
+    );
+
+    engine.add_fact_to_working_memory(create_reading(5, 3, 5, 0)).unwrap();
+
+    let stats = engine.memory_pressure_stats();
+    assert_eq!(stats.facts_spilled, 2);
+    assert_eq!(engine.fact_count(), 3);
+
+    // Sensor 1 holds the oldest readings, so its partition is the one spilled
+    let restored = DiskSpiller::restore(&stats.spill_files[0]).unwrap();
+    assert_eq!(restored.len(), 2);
+    assert!(
+        restored
+            .iter()
+            .all(|fact| fact.data.fields.get("sensor_id") == Some(&FactValue::Integer(1)))
+    );
+
+    std::fs::remove_dir_all(&spill_dir).unwrap();
+}
+
+#[test]
+fn test_critical_pressure_rejects_inserts() {
+    let engine = engine_at_warning_watermark();
+    engine.register_memory_pressure_handler(PressureLevel::Critical, InsertRejector);
+
+    // A single fact only reaches the warning level, which the rejector ignores
+    engine.add_fact_to_working_memory(create_reading(5, 3, 5, 0)).unwrap();
+
+    let batch = (6..=10).map(|id| create_reading(id, 3, id as i64, 0)).collect();
+    let error = engine.process_facts(batch).unwrap_err();
+    assert!(matches!(error, BingoError::Memory { .. }));
+
+    assert_eq!(engine.fact_count(), 5);
+    assert_eq!(engine.memory_pressure_stats().inserts_rejected, 5);
+}
+
+#[test]
+fn test_watermarks_disabled_by_default() {
+    let engine = BingoEngine::new().unwrap();
+    engine.register_memory_pressure_handler(PressureLevel::Normal, InsertRejector);
+
+    engine.add_fact_to_working_memory(create_reading(1, 1, 1, 0)).unwrap();
+    assert_eq!(engine.memory_pressure_stats().checks, 0);
+}
@@ -0,0 +1,120 @@
+//! Memory Report Test
+//!
+//! Validates that the memory report groups facts by type and tenant, sizes the field
+//! indexes, ranks rules by the network memory attributed to them, and renders as text
+//! and JSON.
+
+use bingo_core::types::*;
+use bingo_core::{BingoEngine, MemoryReportOptions, parse_rule};
+use std::collections::HashMap;
+
+fn fact(id: u64, fact_type: &str, tenant: &str, amount: i64) -> Fact {
+    let fields = HashMap::from([
+        ("type".to_string(), FactValue::String(fact_type.to_string())),
+        (
+            "tenant_id".to_string(),
+            FactValue::String(tenant.to_string()),
+        ),
+        ("status".to_string(), FactValue::String("open".to_string())),
+        ("amount".to_string(), FactValue::Integer(amount)),
+    ]);
+    Fact::new(id, FactData { fields })
+}
+
+fn loaded_engine() -> BingoEngine {
+    let engine = BingoEngine::new().unwrap();
+    let large_order = r#"rule "Large order" id 1 when amount > 100 then set flagged = true"#;
+    engine.add_rule(parse_rule(large_order).unwrap()).unwrap();
+    engine
+        .add_rule(Rule {
+            id: 2,
+            name: "Tenant spend".to_string(),
+            conditions: vec![Condition::Aggregation(AggregationCondition {
+                aggregation_type: AggregationType::Sum,
+                source_field: "amount".to_string(),
+                group_by: vec!["tenant_id".to_string()],
+                having: Some(Box::new(Condition::Simple {
+                    field: "total".to_string(),
+                    operator: Operator::GreaterThan,
+                    value: FactValue::Integer(1000),
+                })),
+                alias: "total".to_string(),
+                window: None,
+            })],
+            actions: vec![Action {
+                action_type: ActionType::SetField {
+                    field: "big_spender".to_string(),
+                    value: FactValue::Boolean(true),
+                },
+            }],
+            metadata: Default::default(),
+        })
+        .unwrap();
+
+    let mut facts: Vec<Fact> = (1..=30).map(|id| fact(id, "order", "acme", 50)).collect();
+    facts.extend((31..=40).map(|id| fact(id, "payment", "globex", 500)));
+    facts.push(Fact::new(41, FactData { fields: HashMap::new() }));
+    engine.process_facts(facts).unwrap();
+    engine
+}
+
+#[test]
+fn test_report_groups_facts_and_ranks_rules() {
+    let engine = loaded_engine();
+    let report = engine.memory_report();
+
+    assert_eq!(report.fact_count, 41);
+    assert_eq!(report.total_bytes, report.breakdown.total());
+
+    let types: Vec<_> = report
+        .facts_by_type
+        .iter()
+        .map(|group| (group.key.as_deref(), group.facts))
+        .collect();
+    assert_eq!(
+        types,
+        vec![(Some("order"), 30), (Some("payment"), 10), (None, 1)]
+    );
+    let tenants: Vec<_> = report.facts_by_tenant.iter().map(|group| group.facts).collect();
+    assert_eq!(tenants, vec![30, 10, 1]);
+    assert!(report.facts_by_type.iter().all(|group| group.bytes > 0));
+
+    let status = report.field_indexes.iter().find(|index| index.field == "status").unwrap();
+    assert_eq!((status.distinct_values, status.entries), (1, 40));
+
+    // The aggregation rule holds a contribution per fact on top of its definition
+    let rule_ids: Vec<_> = report.top_rules.iter().map(|rule| rule.rule_id).collect();
+    assert_eq!(rule_ids, vec![2, 1]);
+    assert_eq!(report.top_rules[0].name, "Tenant spend");
+    assert!(report.top_rules[0].aggregation_bytes > 0);
+    assert_eq!(report.top_rules[1].aggregation_bytes, 0);
+}
+
+#[test]
+fn test_report_options_and_rendering() {
+    let engine = loaded_engine();
+    let options = MemoryReportOptions {
+        type_field: "tenant_id".to_string(),
+        tenant_field: "status".to_string(),
+        max_entries: 1,
+    };
+    let report = engine.memory_report_with(&options);
+    assert_eq!(report.facts_by_type.len(), 1);
+    assert_eq!(report.facts_by_type[0].key.as_deref(), Some("acme"));
+    assert_eq!(report.facts_by_tenant[0].facts, 40);
+    assert_eq!(report.top_rules.len(), 1);
+
+    let text = engine.memory_report().to_string();
+    assert!(text.starts_with("Working memory: "), "{text}");
+    assert!(text.contains("across 41 facts"), "{text}");
+    assert!(text.contains("    order: 30 facts, "), "{text}");
+    assert!(text.contains("    (none): 1 facts, "), "{text}");
+    assert!(text.contains("    2 'Tenant spend': "), "{text}");
+
+    let json: serde_json::Value =
+        serde_json::from_str(&engine.memory_report().to_json().unwrap()).unwrap();
+    assert_eq!(json["fact_count"], 41);
+    assert_eq!(json["facts_by_type"][0]["key"], "order");
+    assert_eq!(json["top_rules"][0]["rule_id"], 2);
+    assert!(json["breakdown"]["fact_payloads"].as_u64().unwrap() > 0);
+}
@@ -0,0 +1,107 @@
+//! Metrics Backend Test
+//!
+//! Validates that enhanced monitoring emits recorded metrics and alerts through the
+//! backend selected by its configuration or passed in by the embedder, that the
+//! Prometheus backend renders the text exposition format and that the statsd backend
+//! pushes one datagram per metric.
+
+use bingo_core::enhanced_monitoring::{EnginePerformanceMetrics, ResourceMetrics};
+use bingo_core::*;
+use std::net::UdpSocket;
+use std::sync::Arc;
+use std::time::Duration;
+
+fn engine_metrics() -> EnginePerformanceMetrics {
+    EnginePerformanceMetrics {
+        facts_per_second: 1000.0,
+        success_rate_percent: 99.5,
+        cpu_usage_percent: 95.0,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_embedder_backend_receives_metrics_and_alerts() {
+    let backend = Arc::new(InMemoryMetrics::new());
+    let monitoring = EnhancedMonitoring::default().with_metrics_backend(backend.clone());
+
+    monitoring.record_engine_performance(engine_metrics()).unwrap();
+    monitoring
+        .record_resource_metrics(ResourceMetrics {
+            memory_usage_bytes: 512,
+            peak_memory_bytes: 1024,
+            thread_count: 8,
+            ..Default::default()
+        })
+        .unwrap();
+
+    assert_eq!(backend.gauge("engine_facts_per_second", &[]), Some(1000.0));
+    assert_eq!(backend.gauge("resource_thread_count", &[]), Some(8.0));
+    assert_eq!(backend.gauge("business_error_rate_percent", &[]), None);
+    assert_eq!(
+        backend.counter(
+            "alerts_triggered_total",
+            &[("severity", "Critical"), ("alert_type", "HighCpuUsage")]
+        ),
+        1
+    );
+    assert!(monitoring.render_metrics().unwrap().contains("engine_cpu_usage_percent 95"));
+}
+
+#[test]
+fn test_prometheus_backend_selected_by_config() {
+    let config = MonitoringConfig {
+        metrics_backend: MetricsBackendConfig::Prometheus { namespace: "bingo".to_string() },
+        ..Default::default()
+    };
+    let monitoring = EnhancedMonitoring::try_new(config).unwrap();
+    monitoring.record_engine_performance(engine_metrics()).unwrap();
+
+    let text = monitoring.render_metrics().unwrap();
+    assert!(text.contains(
+        "# TYPE bingo_engine_facts_per_second gauge\nbingo_engine_facts_per_second 1000\n"
+    ));
+    assert!(text.contains(
+        "bingo_alerts_triggered_total{alert_type=\"HighCpuUsage\",severity=\"Critical\"} 1"
+    ));
+
+    let backend = PrometheusMetrics::new("");
+    backend.record_histogram("latency_ms", 2.0, &[("rule", "a\"b")]);
+    backend.record_histogram("latency_ms", 4.0, &[("rule", "a\"b")]);
+    assert_eq!(
+        backend.render_text(),
+        "# TYPE latency_ms summary\nlatency_ms_sum{rule=\"a\\\"b\"} 6\nlatency_ms_count{rule=\"a\\\"b\"} 2\n"
+    );
+
+    // The default configuration forces no metrics stack on the embedder
+    assert!(EnhancedMonitoring::default().render_metrics().is_none());
+}
+
+#[test]
+fn test_statsd_backend_pushes_datagrams() {
+    let agent = UdpSocket::bind("127.0.0.1:0").unwrap();
+    agent.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+    let backend = StatsdMetrics::connect(agent.local_addr().unwrap(), "bingo").unwrap();
+
+    let mut buffer = [0u8; 512];
+    let mut receive = || {
+        let len = agent.recv(&mut buffer).unwrap();
+        String::from_utf8(buffer[..len].to_vec()).unwrap()
+    };
+
+    backend.increment_counter("facts_total", 3, &[("session", "payroll")]);
+    assert_eq!(receive(), "bingo.facts_total:3|c|#session:payroll");
+    backend.set_gauge("queue_depth", 12.5, &[]);
+    assert_eq!(receive(), "bingo.queue_depth:12.5|g");
+    backend.record_histogram("latency_ms", 7.0, &[]);
+    assert_eq!(receive(), "bingo.latency_ms:7|h");
+
+    let config = MonitoringConfig {
+        metrics_backend: MetricsBackendConfig::Statsd {
+            address: "127.0.0.1".to_string(),
+            prefix: String::new(),
+        },
+        ..Default::default()
+    };
+    assert!(EnhancedMonitoring::try_new(config).is_err());
+}
@@ -25,6 +25,7 @@ fn test_minimal_scaling() {
                     value: FactValue::Boolean(true),
                 },
             }],
+            metadata: Default::default(),
         };
 
         engine.add_rule(rule).unwrap();
@@ -43,6 +44,7 @@ fn test_minimal_scaling() {
                     id: i as u64,
                     external_id: None,
                     timestamp: chrono::Utc::now(),
+                    fact_type: None,
                     data: FactData { fields },
                 }
             })
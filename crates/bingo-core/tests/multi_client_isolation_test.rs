@@ -33,6 +33,7 @@ fn test_multiple_client_complete_isolation() {
                 value: FactValue::String("client_a_engine".to_string()),
             },
         }],
+        metadata: Default::default(),
     };
 
     let client_b_rule = Rule {
@@ -49,6 +50,7 @@ fn test_multiple_client_complete_isolation() {
                 value: FactValue::String("client_b_engine".to_string()),
             },
         }],
+        metadata: Default::default(),
     };
 
     let client_c_rule = Rule {
@@ -65,6 +67,7 @@ fn test_multiple_client_complete_isolation() {
                 value: FactValue::String("client_c_engine".to_string()),
             },
         }],
+        metadata: Default::default(),
     };
 
     // Add rules to respective engines
@@ -91,6 +94,7 @@ fn test_multiple_client_complete_isolation() {
                     id: i as u64,
                     external_id: Some(format!("client_a_fact_{i}")),
                     timestamp: chrono::Utc::now(),
+                    fact_type: None,
                     data: FactData { fields },
                 }
             })
@@ -121,6 +125,7 @@ fn test_multiple_client_complete_isolation() {
                     id: i as u64,
                     external_id: Some(format!("client_b_fact_{i}")),
                     timestamp: chrono::Utc::now(),
+                    fact_type: None,
                     data: FactData { fields },
                 }
             })
@@ -151,6 +156,7 @@ fn test_multiple_client_complete_isolation() {
                     id: i as u64,
                     external_id: Some(format!("client_c_fact_{i}")),
                     timestamp: chrono::Utc::now(),
+                    fact_type: None,
                     data: FactData { fields },
                 }
             })
@@ -245,6 +251,7 @@ fn test_concurrent_client_rule_isolation() {
                         value: FactValue::Integer(i as i64 * 10),
                     },
                 }],
+                metadata: Default::default(),
             };
             session_1_clone.add_rule(rule).unwrap();
             thread::sleep(Duration::from_millis(10));
@@ -270,6 +277,7 @@ fn test_concurrent_client_rule_isolation() {
                         value: FactValue::String("high".to_string()),
                     },
                 }],
+                metadata: Default::default(),
             };
             session_2_clone.add_rule(rule).unwrap();
             thread::sleep(Duration::from_millis(15));
@@ -332,6 +340,7 @@ fn test_high_concurrency_client_isolation() {
                     value: FactValue::Boolean(true),
                 },
             }],
+            metadata: Default::default(),
         };
         engine.add_rule(rule).unwrap();
     }
@@ -361,6 +370,7 @@ fn test_high_concurrency_client_isolation() {
                         id: (client_id * 1000 + fact_id) as u64,
                         external_id: Some(format!("client_{client_id}_fact_{fact_id}")),
                         timestamp: chrono::Utc::now(),
+                        fact_type: None,
                         data: FactData { fields },
                     }
                 })
@@ -463,6 +473,7 @@ fn test_session_cleanup_isolation() {
                     value: FactValue::Boolean(true),
                 },
             }],
+            metadata: Default::default(),
         };
         engine.add_rule(rule).unwrap();
 
@@ -477,6 +488,7 @@ fn test_session_cleanup_isolation() {
                     id: (i * 100 + j) as u64,
                     external_id: Some(format!("session_{i}_fact_{j}")),
                     timestamp: chrono::Utc::now(),
+                    fact_type: None,
                     data: FactData { fields },
                 }
             })
@@ -0,0 +1,123 @@
+//! Mutation Actions Test
+//!
+//! Validates that `IncrementField`, `AppendToArray` and `MergeObject` update the stored
+//! fact atomically: rules mutating the same field in one batch build on each other's
+//! results instead of overwriting them, and mismatched field types fail the action.
+
+use bingo_core::rete_nodes::ActionResult;
+use bingo_core::types::*;
+use bingo_core::{BingoEngine, parse_rules};
+use std::collections::HashMap;
+
+fn account(id: u64, fields: Vec<(&str, FactValue)>) -> Fact {
+    let mut fields: HashMap<String, FactValue> =
+        fields.into_iter().map(|(name, value)| (name.to_string(), value)).collect();
+    fields.insert("active".to_string(), FactValue::Boolean(true));
+    Fact::new(id, FactData { fields })
+}
+
+fn engine_with(rules: &str) -> BingoEngine {
+    let engine = BingoEngine::new().unwrap();
+    for rule in parse_rules(rules).unwrap() {
+        engine.add_rule(rule).unwrap();
+    }
+    engine
+}
+
+#[test]
+fn test_rules_in_one_batch_build_on_each_other() {
+    let engine = engine_with(
+        r#"
+        rule "Login bonus" id 1 when active == true then increment points by 10; append "login" to events
+        rule "Streak bonus" id 2 when active == true then increment points by 10; append "streak" to events
+        "#,
+    );
+
+    let results = engine
+        .process_facts(vec![account(1, vec![("points", FactValue::Integer(0))])])
+        .unwrap();
+    let mut increments: Vec<_> = results
+        .iter()
+        .flat_map(|result| &result.actions_executed)
+        .filter_map(|action| match action {
+            ActionResult::FieldIncremented { old_value, new_value, .. } => {
+                Some((old_value.clone(), new_value.clone()))
+            }
+            _ => None,
+        })
+        .collect();
+    increments.sort_by_key(|(old_value, _)| old_value.as_integer());
+    assert_eq!(
+        increments,
+        vec![
+            (FactValue::Integer(0), FactValue::Integer(10)),
+            (FactValue::Integer(10), FactValue::Integer(20)),
+        ]
+    );
+
+    let stored = engine.get_fact(1).unwrap();
+    assert_eq!(stored.data.fields["points"], FactValue::Integer(20));
+    let FactValue::Array(events) = &stored.data.fields["events"] else {
+        panic!("events should be an array");
+    };
+    assert_eq!(events.len(), 2);
+}
+
+#[test]
+fn test_merge_object_adds_and_replaces_keys() {
+    let engine = engine_with(
+        r#"
+        rule "Tag" id 1 when active == true then merge { tier: "gold", region: "EU" } into profile
+        "#,
+    );
+    let profile = FactValue::Object(HashMap::from([
+        ("tier".to_string(), FactValue::String("silver".to_string())),
+        ("since".to_string(), FactValue::Integer(2020)),
+    ]));
+
+    let results = engine.process_facts(vec![account(1, vec![("profile", profile)])]).unwrap();
+    assert_eq!(
+        results[0].actions_executed,
+        vec![ActionResult::ObjectMerged {
+            fact_id: 1,
+            field: "profile".to_string(),
+            merged_keys: vec!["region".to_string(), "tier".to_string()],
+            new_size: 3,
+        }]
+    );
+
+    let FactValue::Object(stored) = &engine.get_fact(1).unwrap().data.fields["profile"] else {
+        panic!("profile should be an object");
+    };
+    assert_eq!(stored["tier"], FactValue::String("gold".to_string()));
+    assert_eq!(stored["since"], FactValue::Integer(2020));
+}
+
+#[test]
+fn test_mismatched_field_types_fail_the_action() {
+    let engine = engine_with(
+        r#"
+        rule "Tag" id 1 when active == true then merge { tier: "gold" } into profile; append 1 to profile
+        "#,
+    );
+
+    let results = engine
+        .process_facts(vec![account(1, vec![("profile", FactValue::Integer(7))])])
+        .unwrap();
+    let messages: Vec<_> = results[0]
+        .actions_executed
+        .iter()
+        .filter_map(ActionResult::get_message)
+        .collect();
+    assert_eq!(
+        messages,
+        vec![
+            "MergeObject failed: field 'profile' is not an object".to_string(),
+            "AppendToArray failed: field 'profile' is not an array".to_string(),
+        ]
+    );
+    assert_eq!(
+        engine.get_fact(1).unwrap().data.fields["profile"],
+        FactValue::Integer(7)
+    );
+}
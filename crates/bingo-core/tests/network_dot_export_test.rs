@@ -0,0 +1,60 @@
+//! Network DOT Export Test
+//!
+//! Validates the Graphviz export of the compiled RETE network: shared conditions appear
+//! as one alpha node, joins are labelled with their beta memory sizes, and every rule
+//! has a terminal node reached from its conditions.
+
+use bingo_core::*;
+use std::collections::HashMap;
+
+fn order(id: u64, amount: i64, status: &str) -> Fact {
+    let fields = HashMap::from([
+        ("amount".to_string(), FactValue::Integer(amount)),
+        ("status".to_string(), FactValue::String(status.to_string())),
+    ]);
+    Fact::new(id, FactData { fields })
+}
+
+#[test]
+fn test_export_dot_describes_alpha_beta_and_terminal_nodes() {
+    let engine = BingoEngine::new().unwrap();
+    engine
+        .add_rules_from_dsl(
+            r#"
+            rule "Large order" id 1 when amount > 100 then set flagged = true
+            rule "Open large order" id 2 when amount > 100 and status == "open" then set review = true
+            "#,
+        )
+        .unwrap();
+    engine.process_facts(vec![order(1, 250, "open"), order(2, 50, "open")]).unwrap();
+
+    let dot = engine.export_network_dot();
+    assert!(dot.starts_with("digraph ReteNetwork {"));
+    assert!(dot.trim_end().ends_with('}'));
+
+    // The shared condition is one alpha node, labelled with its alpha memory size and
+    // evaluation counts
+    let alpha_nodes: Vec<&str> =
+        dot.lines().filter(|line| line.contains("shape=ellipse")).collect();
+    assert_eq!(alpha_nodes.len(), 2, "{dot}");
+    let amount = alpha_nodes.iter().find(|line| line.contains("amount > 100\\nfacts: "));
+    assert!(
+        amount.is_some_and(|line| line.contains(" | matched: ")),
+        "{dot}"
+    );
+    assert!(alpha_nodes.iter().any(|line| line.contains("status == \\\"open\\\"")));
+
+    assert!(dot.contains("shape=doubleoctagon, label=\"rule 1: Large order\\nactions: 1\""));
+    assert!(dot.contains("shape=doubleoctagon, label=\"rule 2: Open large order\\nactions: 1\""));
+    assert!(dot.contains("join condition 1\\ntokens: "));
+    assert!(dot.contains("[label=\"right\"]"));
+    assert!(dot.contains("\" -> \"rule:1\";"));
+    assert!(dot.contains("\" -> \"rule:2\";"));
+}
+
+#[test]
+fn test_export_dot_of_an_empty_network() {
+    let engine = BingoEngine::new().unwrap();
+    let dot = engine.export_network_dot();
+    assert_eq!(dot.lines().filter(|line| line.contains("->")).count(), 0);
+}
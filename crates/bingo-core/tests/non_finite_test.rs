@@ -0,0 +1,166 @@
+//! Non-Finite Float Test
+//!
+//! Validates each policy for NaN and infinite floats: facts holding them are rejected,
+//! nulled or fail the batch at ingestion, aggregates and calculator results that come
+//! out non-finite are handled the same way, and every decision is counted.
+
+use bingo_core::rete_nodes::ActionResult;
+use bingo_core::types::*;
+use bingo_core::{BingoEngine, NonFinitePolicy};
+use std::collections::HashMap;
+
+fn reading(id: u64, rate: f64) -> Fact {
+    let fields = HashMap::from([("rate".to_string(), FactValue::Float(rate))]);
+    Fact::new(id, FactData { fields })
+}
+
+fn engine_with_policy(policy: NonFinitePolicy) -> BingoEngine {
+    let engine = BingoEngine::new().unwrap();
+    engine.set_non_finite_policy(policy);
+    engine
+}
+
+fn threshold_rule(value: f64) -> Rule {
+    Rule {
+        id: 1,
+        name: "High rate".to_string(),
+        conditions: vec![Condition::Simple {
+            field: "rate".to_string(),
+            operator: Operator::GreaterThan,
+            value: FactValue::Float(value),
+        }],
+        actions: vec![Action { action_type: ActionType::Log { message: "high".to_string() } }],
+        metadata: Default::default(),
+    }
+}
+
+fn total_rule() -> Rule {
+    Rule {
+        id: 2,
+        name: "Positive total".to_string(),
+        conditions: vec![Condition::Aggregation(AggregationCondition {
+            aggregation_type: AggregationType::Sum,
+            source_field: "rate".to_string(),
+            group_by: vec![],
+            having: Some(Box::new(Condition::Simple {
+                field: "total".to_string(),
+                operator: Operator::GreaterThan,
+                value: FactValue::Float(0.0),
+            })),
+            alias: "total".to_string(),
+            window: None,
+        })],
+        actions: vec![Action { action_type: ActionType::Log { message: "total".to_string() } }],
+        metadata: Default::default(),
+    }
+}
+
+fn scaling_rule() -> Rule {
+    Rule {
+        id: 3,
+        name: "Scale rate".to_string(),
+        conditions: vec![Condition::Simple {
+            field: "rate".to_string(),
+            operator: Operator::GreaterThan,
+            value: FactValue::Float(0.0),
+        }],
+        actions: vec![Action {
+            action_type: ActionType::CallCalculator {
+                calculator_name: "multiply".to_string(),
+                input_mapping: HashMap::from([
+                    ("a".to_string(), "rate".to_string()),
+                    ("b".to_string(), "rate".to_string()),
+                ]),
+                output_field: "scaled".to_string(),
+            },
+        }],
+        metadata: Default::default(),
+    }
+}
+
+#[test]
+fn test_reject_drops_facts_and_discards_derived_values() {
+    let engine = engine_with_policy(NonFinitePolicy::default());
+    engine.add_rule(threshold_rule(0.5)).unwrap();
+
+    let results = engine
+        .process_facts(vec![
+            reading(1, 1.0),
+            reading(2, f64::NAN),
+            reading(3, f64::INFINITY),
+        ])
+        .unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(engine.fact_count(), 1);
+    assert!(engine.add_fact_to_working_memory(reading(4, f64::NAN)).unwrap().is_empty());
+    assert_eq!(engine.non_finite_stats().facts_rejected, 3);
+
+    // 1e200 squared overflows to infinity, so the calculator result is dropped
+    engine.add_rule(scaling_rule()).unwrap();
+    let results = engine.process_facts(vec![reading(5, 1e200)]).unwrap();
+    let scaled = results.iter().find(|result| result.rule_id == 3).unwrap();
+    assert!(matches!(
+        &scaled.actions_executed[0],
+        ActionResult::Logged { message } if message.contains("result discarded")
+    ));
+    assert_eq!(engine.non_finite_stats().values_discarded, 1);
+}
+
+#[test]
+fn test_propagate_as_null_stores_nulls_that_never_match() {
+    let engine = engine_with_policy(NonFinitePolicy::PropagateAsNull);
+    engine.add_rule(threshold_rule(0.5)).unwrap();
+    engine.add_rule(total_rule()).unwrap();
+
+    // The nulled reading matches nothing, and the overflowing total is null too
+    let results = engine
+        .process_facts(vec![
+            reading(1, f64::NAN),
+            reading(2, f64::MAX),
+            reading(3, f64::MAX),
+        ])
+        .unwrap();
+    assert!(results.iter().all(|result| result.rule_id == 1 && result.fact_id != 1));
+    assert_eq!(
+        engine.get_fact(1).unwrap().data.fields["rate"],
+        FactValue::Null
+    );
+
+    let stats = engine.non_finite_stats();
+    assert_eq!(stats.facts_rejected, 0);
+    assert!(stats.values_nulled >= 2, "{stats:?}");
+}
+
+#[test]
+fn test_error_fails_the_batch() {
+    let engine = engine_with_policy(NonFinitePolicy::Error);
+    engine.add_rule(threshold_rule(0.5)).unwrap();
+
+    let error = engine
+        .process_facts(vec![reading(1, 1.0), reading(2, f64::NEG_INFINITY)])
+        .unwrap_err();
+    assert!(
+        error.to_string().contains("Field 'rate' holds non-finite float -inf"),
+        "{error}"
+    );
+    assert_eq!(engine.fact_count(), 0);
+
+    engine.add_rule(scaling_rule()).unwrap();
+    let error = engine.process_facts(vec![reading(3, 1e200)]).unwrap_err();
+    assert!(
+        error.to_string().contains("non-finite float inf"),
+        "{error}"
+    );
+    assert_eq!(engine.non_finite_stats().errors, 2);
+    assert_eq!(engine.non_finite_policy(), NonFinitePolicy::Error);
+}
+
+#[test]
+fn test_non_finite_literals_are_rejected() {
+    let engine = BingoEngine::new().unwrap();
+    let error = engine.add_rule(threshold_rule(f64::NAN)).unwrap_err();
+    assert!(
+        error.to_string().contains("non-finite float NaN"),
+        "{error}"
+    );
+}
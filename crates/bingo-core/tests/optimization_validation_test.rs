@@ -35,6 +35,7 @@ fn create_optimization_test_rules(count: usize) -> Vec<Rule> {
                     output_field: "performance_check".to_string(),
                 },
             }],
+            metadata: Default::default(),
         };
         rules.push(rule);
     }
@@ -73,6 +74,7 @@ fn create_optimization_test_facts(count: usize) -> Vec<Fact> {
                 id: i as u64,
                 external_id: None,
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: FactData { fields },
             }
         })
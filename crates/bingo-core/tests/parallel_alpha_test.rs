@@ -0,0 +1,116 @@
+//! Parallel Alpha Evaluation Test
+//!
+//! Validates that evaluating the alpha tests of a large batch on a thread pool fires
+//! the same rules, in the same order, with the same hit counts as evaluating the batch
+//! fact by fact, including batches evaluated a chunk at a time.
+
+use bingo_core::types::*;
+use bingo_core::{BingoEngine, ParallelAlphaConfig};
+use std::collections::HashMap;
+
+const RULES: &str = r#"
+rule "Large order" id 1 when amount > 900 then set review = true
+rule "Priority region" id 2 when region in ["north", "east"] and amount > 500 then set priority = true
+rule "Gold tier" id 3 when tier == "gold" then set discount = 10
+rule "Gold north" id 4 when tier == "gold" and region == "north" then set concierge = true
+"#;
+
+fn orders(count: usize) -> Vec<Fact> {
+    let regions = ["north", "south", "east", "west"];
+    let tiers = ["gold", "silver", "bronze"];
+    (0..count)
+        .map(|i| {
+            let fields = HashMap::from([
+                (
+                    "region".to_string(),
+                    FactValue::String(regions[i % regions.len()].to_string()),
+                ),
+                (
+                    "tier".to_string(),
+                    FactValue::String(tiers[i % tiers.len()].to_string()),
+                ),
+                (
+                    "amount".to_string(),
+                    FactValue::Integer((i * 37 % 1000) as i64),
+                ),
+            ]);
+            Fact::new(i as u64, FactData { fields })
+        })
+        .collect()
+}
+
+fn engine(parallel_alpha: Option<ParallelAlphaConfig>) -> BingoEngine {
+    let engine = BingoEngine::new().unwrap();
+    engine.add_rules_from_dsl(RULES).unwrap();
+    engine.set_parallel_alpha(parallel_alpha).unwrap();
+    engine
+}
+
+fn firings(engine: &BingoEngine, facts: Vec<Fact>) -> Vec<(FactId, RuleId)> {
+    engine
+        .process_facts(facts)
+        .unwrap()
+        .iter()
+        .map(|result| (result.fact_id, result.rule_id))
+        .collect()
+}
+
+fn hit_counts(engine: &BingoEngine) -> Vec<(RuleId, u64, u64)> {
+    let mut counts: Vec<_> = engine
+        .rule_stats()
+        .iter()
+        .map(|stats| (stats.rule_id, stats.evaluations, stats.activations))
+        .collect();
+    counts.sort_unstable();
+    counts
+}
+
+#[test]
+fn test_parallel_alpha_fires_like_sequential_evaluation() {
+    let sequential = engine(None);
+    let expected = firings(&sequential, orders(2_500));
+    assert!(expected.len() > 1_000);
+
+    for config in [
+        ParallelAlphaConfig { threads: 4, min_batch_size: 500 },
+        ParallelAlphaConfig { threads: 8, min_batch_size: 2_500 },
+        ParallelAlphaConfig { threads: 1, min_batch_size: 1 },
+    ] {
+        let parallel = engine(Some(config));
+        assert_eq!(parallel.parallel_alpha(), Some(config));
+        assert_eq!(firings(&parallel, orders(2_500)), expected);
+        assert_eq!(hit_counts(&parallel), hit_counts(&sequential));
+    }
+
+    // Batches below the minimum size are evaluated fact by fact
+    let parallel = engine(Some(ParallelAlphaConfig {
+        threads: 4,
+        min_batch_size: 10_000,
+    }));
+    assert_eq!(firings(&parallel, orders(2_500)), expected);
+}
+
+#[test]
+fn test_parallel_alpha_configuration() {
+    let engine = engine(None);
+    assert_eq!(engine.parallel_alpha(), None);
+
+    let error = engine
+        .set_parallel_alpha(Some(ParallelAlphaConfig {
+            threads: 0,
+            min_batch_size: 100,
+        }))
+        .unwrap_err();
+    assert!(error.to_string().contains("threads must be at least 1"));
+    assert!(
+        engine
+            .set_parallel_alpha(Some(ParallelAlphaConfig { threads: 2, min_batch_size: 0 }))
+            .is_err()
+    );
+    assert_eq!(engine.parallel_alpha(), None);
+
+    engine.set_parallel_alpha(Some(ParallelAlphaConfig::default())).unwrap();
+    assert!(engine.parallel_alpha().is_some());
+    engine.set_parallel_alpha(None).unwrap();
+    assert_eq!(engine.parallel_alpha(), None);
+}
@@ -107,6 +107,7 @@ fn analyze_rule_compilation_bottlenecks(engine: &mut BingoEngine) {
                         value: FactValue::Boolean(true),
                     },
                 }],
+                metadata: Default::default(),
             };
 
             engine.add_rule(rule).expect("Failed to add rule");
@@ -165,6 +166,7 @@ fn analyze_rule_compilation_bottlenecks(engine: &mut BingoEngine) {
                     value: FactValue::Boolean(true),
                 },
             }],
+            metadata: Default::default(),
         };
 
         engine.add_rule(rule).expect("Failed to add complex rule");
@@ -201,6 +203,7 @@ fn analyze_rete_network_bottlenecks(engine: &mut BingoEngine) {
                     value: FactValue::Boolean(true),
                 },
             }],
+            metadata: Default::default(),
         },
         Rule {
             id: 2,
@@ -220,6 +223,7 @@ fn analyze_rete_network_bottlenecks(engine: &mut BingoEngine) {
             actions: vec![Action {
                 action_type: ActionType::Log { message: "Multi-condition fired".to_string() },
             }],
+            metadata: Default::default(),
         },
     ];
 
@@ -268,6 +272,7 @@ fn analyze_rete_network_bottlenecks(engine: &mut BingoEngine) {
                         value: FactValue::Boolean(true),
                     },
                 }],
+                metadata: Default::default(),
             },
             Rule {
                 id: 2,
@@ -287,6 +292,7 @@ fn analyze_rete_network_bottlenecks(engine: &mut BingoEngine) {
                 actions: vec![Action {
                     action_type: ActionType::Log { message: "Multi-condition fired".to_string() },
                 }],
+                metadata: Default::default(),
             },
         ] {
             engine.add_rule(rule.clone()).expect("Failed to re-add rule");
@@ -481,6 +487,7 @@ fn create_test_facts(count: usize) -> Vec<Fact> {
             id: i as u64,
             external_id: Some(format!("test_{i}")),
             timestamp: Utc::now(),
+            fact_type: None,
             data: FactData { fields },
         });
     }
@@ -505,6 +512,7 @@ fn create_rete_test_facts(count: usize) -> Vec<Fact> {
             id: i as u64,
             external_id: Some(format!("rete_{i}")),
             timestamp: Utc::now(),
+            fact_type: None,
             data: FactData { fields },
         });
     }
@@ -26,6 +26,7 @@ fn test_performance_profiling() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).unwrap();
@@ -49,6 +50,7 @@ fn test_performance_profiling() {
                     id: i as u64,
                     external_id: None,
                     timestamp: chrono::Utc::now(),
+                    fact_type: None,
                     data: FactData { fields },
                 }
             })
@@ -29,6 +29,7 @@ fn profile_performance_bottleneck() {
                     value: FactValue::Boolean(true),
                 },
             }],
+            metadata: Default::default(),
         };
         engine.add_rule(rule).unwrap();
         println!("  Rule addition: {:?}", start.elapsed());
@@ -55,6 +56,7 @@ fn profile_performance_bottleneck() {
                     id: i as u64,
                     external_id: None,
                     timestamp: chrono::Utc::now(),
+                    fact_type: None,
                     data: FactData { fields },
                 }
             })
@@ -96,6 +96,7 @@ fn test_rule_compilation_performance(engine: &mut BingoEngine) {
                     value: FactValue::Boolean(true),
                 },
             }],
+            metadata: Default::default(),
         };
 
         engine.add_rule(rule).expect("Failed to add rule");
@@ -128,6 +129,7 @@ fn test_rule_compilation_performance(engine: &mut BingoEngine) {
                     value: FactValue::Boolean(true),
                 },
             }],
+            metadata: Default::default(),
         };
 
         engine.update_rule(updated_rule).expect("Failed to update rule");
@@ -159,6 +161,7 @@ fn test_rete_network_performance(engine: &mut BingoEngine) {
                     value: FactValue::Boolean(true),
                 },
             }],
+            metadata: Default::default(),
         },
         Rule {
             id: 2,
@@ -178,6 +181,7 @@ fn test_rete_network_performance(engine: &mut BingoEngine) {
             actions: vec![Action {
                 action_type: ActionType::Log { message: "Multi-condition fired".to_string() },
             }],
+            metadata: Default::default(),
         },
     ];
 
@@ -349,6 +353,7 @@ fn create_test_facts(count: usize) -> Vec<Fact> {
             id: i as u64,
             external_id: Some(format!("test_{i}")),
             timestamp: Utc::now(),
+            fact_type: None,
             data: FactData { fields },
         });
     }
@@ -373,6 +378,7 @@ fn create_rete_facts(count: usize) -> Vec<Fact> {
             id: i as u64,
             external_id: Some(format!("rete_{i}")),
             timestamp: Utc::now(),
+            fact_type: None,
             data: FactData { fields },
         });
     }
@@ -0,0 +1,162 @@
+//! Range Index Test
+//!
+//! Validates that range queries over range indexed fields and fact timestamps return
+//! the facts a scan would, in value order, as facts are updated and deleted, and that
+//! range conditions pruned by binary search over their thresholds match exactly the
+//! facts they matched when every threshold was tested.
+
+use bingo_core::BingoEngine;
+use bingo_core::fact_store::arena_store::ArenaFactStore;
+use bingo_core::types::*;
+use chrono::{Duration, TimeZone, Utc};
+use std::collections::HashMap;
+
+fn decimal(value: &str) -> FactValue {
+    FactValue::Decimal(value.parse().unwrap())
+}
+
+fn expense(id: FactId, amount: FactValue) -> Fact {
+    let fields = HashMap::from([("amount".to_string(), amount)]);
+    let at = Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap() + Duration::hours(id as i64);
+    Fact { timestamp: at, ..Fact::new(id, FactData { fields }) }
+}
+
+fn ids(facts: &[Fact]) -> Vec<FactId> {
+    facts.iter().map(|fact| fact.id).collect()
+}
+
+#[test]
+fn test_range_queries_match_a_scan_in_value_order() {
+    let store = ArenaFactStore::new();
+    for fact in [
+        expense(1, FactValue::Float(480.0)),
+        expense(2, FactValue::Integer(100)),
+        expense(3, decimal("250.25")),
+        expense(4, FactValue::Float(f64::NAN)),
+        expense(5, FactValue::Integer(500)),
+        expense(6, FactValue::String("300".to_string())),
+        expense(7, FactValue::Integer(100)),
+    ] {
+        store.insert(fact);
+    }
+
+    let scanned = ids(&store.find_by_range("amount", 100.0..500.0));
+    assert_eq!(scanned, vec![2, 7, 3, 1]);
+    store.add_range_index("amount");
+    assert_eq!(store.range_indexed_fields(), vec!["amount".to_string()]);
+    assert_eq!(ids(&store.find_by_range("amount", 100.0..500.0)), scanned);
+    assert_eq!(ids(&store.find_by_range("amount", 250.25..)), vec![3, 1, 5]);
+    assert!(store.find_by_range("amount", f64::NAN..).is_empty());
+
+    // Updated facts move in the index and deleted facts leave it
+    store.update_fact(
+        5,
+        HashMap::from([("amount".to_string(), FactValue::Integer(50))]),
+    );
+    store.delete_fact(7);
+    assert_eq!(
+        ids(&store.find_by_range("amount", ..=250.25)),
+        vec![5, 2, 3]
+    );
+    assert!(store.remove_range_index("amount"));
+    assert_eq!(
+        ids(&store.find_by_range("amount", ..=250.25)),
+        vec![5, 2, 3]
+    );
+
+    // Every fact is kept in timestamp order
+    let start = Utc.with_ymd_and_hms(2024, 3, 1, 2, 0, 0).unwrap();
+    let in_range = store.facts_in_time_range(start, start + Duration::hours(2));
+    assert_eq!(ids(&in_range), vec![2, 3, 4]);
+    store.clear();
+    assert!(store.facts_in_time_range(start, start + Duration::hours(2)).is_empty());
+}
+
+#[test]
+fn test_date_ranges_and_engine_queries() {
+    let engine = BingoEngine::new().unwrap();
+    engine.add_range_index("due");
+    let due = |day: u32| FactValue::Date(Utc.with_ymd_and_hms(2024, 4, day, 12, 0, 0).unwrap());
+    let facts: Vec<Fact> = [(1, 20), (2, 5), (3, 12)]
+        .into_iter()
+        .map(|(id, day)| {
+            let mut fact = expense(id, FactValue::Integer(id as i64 * 100));
+            fact.data.fields.insert("due".to_string(), due(day));
+            fact
+        })
+        .collect();
+    engine.process_facts(facts.clone()).unwrap();
+
+    let store = ArenaFactStore::new();
+    store.add_range_index("due");
+    store.bulk_insert(facts);
+    let april = Utc.with_ymd_and_hms(2024, 4, 1, 0, 0, 0).unwrap();
+    let due_ids = ids(&store.find_by_date_range("due", april..april + Duration::days(15)));
+    assert_eq!(due_ids, vec![2, 3]);
+
+    assert_eq!(
+        ids(&engine.find_facts_by_range("amount", 150.0..)),
+        vec![2, 3]
+    );
+    let start = Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap();
+    assert_eq!(
+        ids(&engine.facts_in_time_range(start, start + Duration::hours(2))),
+        vec![1, 2]
+    );
+}
+
+#[test]
+fn test_pruned_range_conditions_match_every_threshold() {
+    let mut rules = String::new();
+    for step in 1..=20 {
+        let threshold = step * 25;
+        rules.push_str(&format!(
+            "rule \"Above {threshold}\" id {step} when amount > {threshold} then set above = true\n\
+             rule \"At most {threshold}\" id {} when amount <= {threshold} then set below = true\n",
+            step + 100
+        ));
+    }
+    let engine = BingoEngine::new().unwrap();
+    engine.add_rules_from_dsl(&rules).unwrap();
+
+    let amounts = [
+        FactValue::Integer(0),
+        FactValue::Integer(100),
+        FactValue::Float(100.5),
+        decimal("250.0000000001"),
+        decimal("250"),
+        FactValue::Integer(501),
+        FactValue::Float(f64::NAN),
+    ];
+    for (index, amount) in amounts.iter().enumerate() {
+        // NaN amounts are rejected before matching, so they fire nothing
+        let mut expected: Vec<RuleId> = (1..=20u64)
+            .flat_map(|step| {
+                let ordering = amount.partial_cmp(&FactValue::Integer(step as i64 * 25));
+                let above = ordering.is_some_and(|ordering| ordering.is_gt()).then_some(step);
+                let below = ordering.is_some_and(|ordering| ordering.is_le()).then_some(step + 100);
+                above.into_iter().chain(below)
+            })
+            .collect();
+        expected.sort_unstable();
+
+        let fact = expense(index as u64 + 1, amount.clone());
+        let mut fired: Vec<RuleId> = engine
+            .process_facts(vec![fact.clone()])
+            .unwrap()
+            .iter()
+            .map(|result| result.rule_id)
+            .collect();
+        fired.sort_unstable();
+        assert_eq!(fired, expected, "batch evaluation of {amount:?}");
+
+        let mut fired: Vec<RuleId> = engine
+            .add_fact_to_working_memory(Fact { id: fact.id + 100, ..fact })
+            .unwrap()
+            .iter()
+            .map(|result| result.rule_id)
+            .collect();
+        fired.sort_unstable();
+        assert_eq!(fired, expected, "incremental evaluation of {amount:?}");
+    }
+}
@@ -0,0 +1,199 @@
+//! Retroactive Recalculation Test
+//!
+//! Validates that correcting a rule or a fact works out the result deltas for the
+//! stored facts of the corrected period only, evaluated under the rule and reference
+//! table versions in effect when each fact happened, and leaves the engine unchanged.
+
+use bingo_core::rete_nodes::ActionResult;
+use bingo_core::types::*;
+use bingo_core::{
+    BingoEngine, Correction, DeltaKind, RecalculationRequest, ReferenceTable, RuleMetadata,
+};
+use chrono::{DateTime, TimeZone, Utc};
+use std::collections::HashMap;
+
+fn date(year: i32, month: u32, day: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(year, month, day, 0, 0, 0).unwrap()
+}
+
+fn shift(hours: i64, worked_on: DateTime<Utc>) -> Fact {
+    let fields = HashMap::from([
+        ("region".to_string(), FactValue::String("north".to_string())),
+        ("hours".to_string(), FactValue::Integer(hours)),
+    ]);
+    Fact { timestamp: worked_on, ..Fact::new(0, FactData { fields }) }
+}
+
+fn overtime_rule(threshold: i64) -> Rule {
+    Rule {
+        id: 1,
+        name: "Overtime".to_string(),
+        conditions: vec![Condition::Simple {
+            field: "hours".to_string(),
+            operator: Operator::GreaterThan,
+            value: FactValue::Integer(threshold),
+        }],
+        actions: vec![Action {
+            action_type: ActionType::SetField {
+                field: "overtime".to_string(),
+                value: FactValue::Boolean(true),
+            },
+        }],
+        metadata: RuleMetadata::default(),
+    }
+}
+
+fn pay_rule() -> Rule {
+    Rule {
+        id: 2,
+        name: "Pay".to_string(),
+        conditions: vec![Condition::Simple {
+            field: "hours".to_string(),
+            operator: Operator::GreaterThan,
+            value: FactValue::Integer(0),
+        }],
+        actions: vec![Action {
+            action_type: ActionType::CallCalculator {
+                calculator_name: "multiply".to_string(),
+                input_mapping: HashMap::from([
+                    ("a".to_string(), "hours".to_string()),
+                    ("b".to_string(), "hourly_rates[region]".to_string()),
+                ]),
+                output_field: "pay".to_string(),
+            },
+        }],
+        metadata: RuleMetadata::default(),
+    }
+}
+
+fn rates(rate: f64) -> ReferenceTable {
+    ReferenceTable::from_entries([(
+        FactValue::String("north".to_string()),
+        FactValue::Float(rate),
+    )])
+}
+
+/// Engine paying 20 an hour in 2024 and 25 from 2025, with overtime above 8 hours,
+/// and the IDs of the shifts it has processed
+fn payroll_engine(shifts: Vec<Fact>) -> (BingoEngine, Vec<FactId>) {
+    let engine = BingoEngine::new().unwrap();
+    let new_year = date(2025, 1, 1);
+    engine
+        .register_reference_table_version(
+            "hourly_rates",
+            rates(20.0).valid_between(None, Some(new_year)),
+        )
+        .unwrap();
+    engine
+        .register_reference_table_version(
+            "hourly_rates",
+            rates(25.0).valid_between(Some(new_year), None),
+        )
+        .unwrap();
+    engine.add_rule(overtime_rule(8)).unwrap();
+    engine.add_rule(pay_rule()).unwrap();
+
+    let fact_ids = shifts
+        .into_iter()
+        .map(|shift| {
+            let results = engine.process_facts(vec![shift]).unwrap();
+            results.iter().find(|result| result.rule_id == 2).unwrap().fact_id
+        })
+        .collect();
+    (engine, fact_ids)
+}
+
+fn march_2024(corrections: Vec<Correction>) -> RecalculationRequest {
+    RecalculationRequest::new(corrections, date(2024, 3, 1), date(2024, 4, 1))
+}
+
+fn pay(actions: &[ActionResult]) -> Option<FactValue> {
+    actions.iter().find_map(|action| match action {
+        ActionResult::CalculatorResult { parsed_value, .. } => Some(parsed_value.clone()),
+        _ => None,
+    })
+}
+
+#[test]
+fn test_corrected_rule_is_recalculated_over_the_period() {
+    let (engine, ids) = payroll_engine(vec![
+        shift(9, date(2024, 3, 4)),
+        shift(7, date(2024, 3, 5)),
+        shift(7, date(2024, 5, 6)),
+    ]);
+
+    // Overtime should have started after 6 hours
+    let report = engine
+        .recalculate(&march_2024(vec![Correction::Rule(overtime_rule(6))]))
+        .unwrap();
+    assert_eq!(report.affected_facts, ids[..2]);
+    assert_eq!(report.deltas.len(), 1);
+    let delta = &report.deltas[0];
+    assert_eq!(
+        (delta.fact_id, delta.rule_id, delta.kind()),
+        (ids[1], 1, DeltaKind::Added)
+    );
+
+    // Nothing was applied to the engine
+    assert_eq!(engine.rules()[0].conditions, overtime_rule(8).conditions);
+    assert_eq!(engine.fact_count(), 3);
+
+    // Removing the rule takes away the overtime that was granted
+    let report = engine.recalculate(&march_2024(vec![Correction::RuleRemoved(1)])).unwrap();
+    let kinds: Vec<_> = report.deltas.iter().map(|delta| (delta.fact_id, delta.kind())).collect();
+    assert_eq!(kinds, [(ids[0], DeltaKind::Removed)]);
+
+    // Re-submitting the current rule changes nothing
+    let report = engine
+        .recalculate(&march_2024(vec![Correction::Rule(overtime_rule(8))]))
+        .unwrap();
+    assert!(report.is_unchanged());
+}
+
+#[test]
+fn test_corrected_fact_is_paid_at_the_rate_in_effect_when_worked() {
+    let (engine, ids) = payroll_engine(vec![shift(7, date(2024, 3, 5))]);
+    let corrected = Fact { id: ids[0], ..shift(10, date(2024, 3, 5)) };
+
+    let report = engine.recalculate(&march_2024(vec![Correction::Fact(corrected)])).unwrap();
+    assert_eq!(report.affected_facts, ids);
+    let deltas: Vec<_> = report
+        .deltas
+        .iter()
+        .map(|delta| {
+            (
+                delta.rule_id,
+                delta.kind(),
+                pay(&delta.before),
+                pay(&delta.after),
+            )
+        })
+        .collect();
+    assert_eq!(
+        deltas,
+        [
+            (1, DeltaKind::Added, None, None),
+            (
+                2,
+                DeltaKind::Changed,
+                Some(FactValue::Float(140.0)),
+                Some(FactValue::Float(200.0))
+            ),
+        ]
+    );
+    assert_eq!(
+        engine.get_fact(ids[0]).unwrap().data.fields["hours"],
+        FactValue::Integer(7)
+    );
+}
+
+#[test]
+fn test_unknown_corrections_are_rejected() {
+    let (engine, _) = payroll_engine(vec![shift(7, date(2024, 3, 5))]);
+    let error = engine.recalculate(&march_2024(vec![Correction::RuleRemoved(99)])).unwrap_err();
+    assert!(error.to_string().contains("Rule 99 does not exist"));
+
+    let unknown = Fact { id: 99, ..shift(7, date(2024, 3, 5)) };
+    let error = engine.recalculate(&march_2024(vec![Correction::Fact(unknown)])).unwrap_err();
+    assert!(error.to_string().contains("Corrected fact is not stored"));
+}
@@ -0,0 +1,167 @@
+//! Reference Data Test
+//!
+//! Validates named reference tables and globals: membership conditions and calculator
+//! inputs resolve them by name, reloading a table or setting a global again applies to
+//! existing rules without adding them again, and rules naming an unregistered table or
+//! an unset global are rejected.
+
+use bingo_core::rete_nodes::ActionResult;
+use bingo_core::types::*;
+use bingo_core::{BingoEngine, ReferenceTable, parse_rule};
+use std::collections::HashMap;
+
+fn order(id: u64, country: &str, amount: i64) -> Fact {
+    let fields = HashMap::from([
+        (
+            "country".to_string(),
+            FactValue::String(country.to_string()),
+        ),
+        ("amount".to_string(), FactValue::Integer(amount)),
+    ]);
+    Fact::new(id, FactData { fields })
+}
+
+fn string(value: &str) -> FactValue {
+    FactValue::String(value.to_string())
+}
+
+fn tax_rates(rate: f64) -> ReferenceTable {
+    ReferenceTable::from_entries([
+        (string("DE"), FactValue::Float(rate)),
+        (string("FR"), FactValue::Float(0.125)),
+    ])
+}
+
+fn tax_rule() -> Rule {
+    calculated_tax_rule("tax_rates[country]")
+}
+
+fn calculated_tax_rule(rate_mapping: &str) -> Rule {
+    Rule {
+        id: 1,
+        name: "Tax".to_string(),
+        conditions: vec![Condition::Simple {
+            field: "amount".to_string(),
+            operator: Operator::GreaterThan,
+            value: FactValue::Integer(0),
+        }],
+        actions: vec![Action {
+            action_type: ActionType::CallCalculator {
+                calculator_name: "multiply".to_string(),
+                input_mapping: HashMap::from([
+                    ("a".to_string(), "amount".to_string()),
+                    ("b".to_string(), rate_mapping.to_string()),
+                ]),
+                output_field: "tax".to_string(),
+            },
+        }],
+        metadata: Default::default(),
+    }
+}
+
+fn calculated(engine: &BingoEngine, facts: Vec<Fact>) -> Vec<(u64, FactValue)> {
+    let mut values: Vec<(u64, FactValue)> = engine
+        .process_facts(facts)
+        .unwrap()
+        .into_iter()
+        .flat_map(|result| {
+            result.actions_executed.into_iter().filter_map(move |action| match action {
+                ActionResult::CalculatorResult { parsed_value, .. } => {
+                    Some((result.fact_id, parsed_value))
+                }
+                _ => None,
+            })
+        })
+        .collect();
+    values.sort_by_key(|(fact_id, _)| *fact_id);
+    values
+}
+
+#[test]
+fn test_reloading_a_list_applies_to_existing_rules() {
+    let engine = BingoEngine::new().unwrap();
+    engine.register_value_list("sanctions", [string("IR")]).unwrap();
+    engine
+        .add_rule(
+            parse_rule(r#"rule "Sanctioned" id 1 when country in "sanctions" then log "block""#)
+                .unwrap(),
+        )
+        .unwrap();
+
+    let fired = engine.process_facts(vec![order(1, "RU", 10)]).unwrap();
+    assert!(fired.is_empty());
+
+    // A background loader reloads the list through its own handle
+    let loader = engine.reference_data();
+    loader
+        .register(
+            "sanctions",
+            ReferenceTable::from_keys([string("IR"), string("RU")]),
+        )
+        .unwrap();
+
+    let fired = engine.process_facts(vec![order(2, "RU", 10)]).unwrap();
+    assert_eq!(fired.len(), 1);
+    assert_eq!(fired[0].fact_id, 2);
+    assert_eq!(engine.reference_data().names(), vec!["sanctions"]);
+}
+
+#[test]
+fn test_calculator_inputs_from_reference_table() {
+    let engine = BingoEngine::new().unwrap();
+    engine.register_reference_table("tax_rates", tax_rates(0.25)).unwrap();
+    engine.add_rule(tax_rule()).unwrap();
+
+    let values = calculated(&engine, vec![order(1, "DE", 100), order(2, "FR", 50)]);
+    assert_eq!(
+        values,
+        vec![(1, FactValue::Float(25.0)), (2, FactValue::Float(6.25))]
+    );
+
+    // Reloaded rates are used for the same inputs rather than a cached result
+    engine.register_reference_table("tax_rates", tax_rates(0.5)).unwrap();
+    let values = calculated(&engine, vec![order(3, "DE", 100)]);
+    assert_eq!(values, vec![(3, FactValue::Float(50.0))]);
+}
+
+#[test]
+fn test_unregistered_table_rejects_rule() {
+    let engine = BingoEngine::new().unwrap();
+    let error = engine.add_rule(tax_rule()).unwrap_err();
+    assert!(
+        format!("{error:#}").contains("Reference table 'tax_rates' is not registered"),
+        "{error:#}"
+    );
+    assert_eq!(engine.get_stats().rule_count, 0);
+
+    // Rules survive a rebuild with the tables they reference
+    engine.register_reference_table("tax_rates", tax_rates(0.25)).unwrap();
+    engine.add_rule(tax_rule()).unwrap();
+    engine.remove_rule(1).unwrap();
+    engine.add_rule(tax_rule()).unwrap();
+    assert_eq!(engine.get_stats().rule_count, 1);
+}
+
+#[test]
+fn test_calculator_inputs_from_globals() {
+    let engine = BingoEngine::new().unwrap();
+    let error = engine.add_rule(calculated_tax_rule("@vat_rate")).unwrap_err();
+    assert!(
+        format!("{error:#}").contains("Global 'vat_rate' is not set"),
+        "{error:#}"
+    );
+
+    engine.set_global("vat_rate", FactValue::Float(0.25)).unwrap();
+    engine.add_rule(calculated_tax_rule("@vat_rate")).unwrap();
+    let values = calculated(&engine, vec![order(1, "DE", 100)]);
+    assert_eq!(values, vec![(1, FactValue::Float(25.0))]);
+
+    engine.set_global("vat_rate", FactValue::Float(0.5)).unwrap();
+    let values = calculated(&engine, vec![order(2, "DE", 100)]);
+    assert_eq!(values, vec![(2, FactValue::Float(50.0))]);
+    assert_eq!(engine.global("vat_rate"), Some(FactValue::Float(0.5)));
+    assert_eq!(engine.globals().len(), 1);
+
+    // The global is not reported as a fact field the ruleset reads
+    assert!(engine.referenced_fields().iter().all(|field| !field.field.contains("vat_rate")));
+}
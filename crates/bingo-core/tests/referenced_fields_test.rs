@@ -0,0 +1,64 @@
+//! Referenced Fields Test
+//!
+//! Validates that the engine lists every field its ruleset reads or writes, with the
+//! operators applied, and keeps the list current as rules change.
+
+use bingo_core::BingoEngine;
+use bingo_core::types::*;
+
+#[test]
+fn test_engine_lists_referenced_fields() {
+    let engine = BingoEngine::new().unwrap();
+    engine
+        .add_rules_from_dsl(
+            r#"
+            rule "Large order" id 1
+            when order.total > 1000 and status == "active"
+            then set customer.tier = "premium"
+
+            rule "Refund" id 2
+            when order.total <= 0 or status contains "refund"
+            then formula refund = "order.total * -1"
+            "#,
+        )
+        .unwrap();
+
+    let fields = engine.referenced_fields();
+    let summary: Vec<(&str, &[Operator], bool, bool)> = fields
+        .iter()
+        .map(|field| {
+            (
+                field.field.as_str(),
+                field.operators.as_slice(),
+                field.read,
+                field.written,
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("customer.tier", &[][..], false, true),
+            (
+                "order.total",
+                &[Operator::GreaterThan, Operator::LessThanOrEqual][..],
+                true,
+                false
+            ),
+            ("refund", &[][..], false, true),
+            (
+                "status",
+                &[Operator::Equal, Operator::Contains][..],
+                true,
+                false
+            ),
+        ]
+    );
+
+    engine.remove_rule(2).unwrap();
+    let fields = engine.referenced_fields();
+    assert!(fields.iter().all(|field| field.field != "refund"));
+    let status = fields.iter().find(|field| field.field == "status").unwrap();
+    assert_eq!(status.operators, vec![Operator::Equal]);
+    assert_eq!(status.rule_ids.iter().copied().collect::<Vec<_>>(), vec![1]);
+}
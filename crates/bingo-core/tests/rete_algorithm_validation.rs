@@ -29,6 +29,7 @@ fn test_current_algorithm_scaling_problem() {
                     value: FactValue::Boolean(true),
                 },
             }],
+            metadata: Default::default(),
         };
         engine.add_rule(rule).unwrap();
     }
@@ -50,6 +51,7 @@ fn test_current_algorithm_scaling_problem() {
                     id: i as u64,
                     external_id: None,
                     timestamp: chrono::Utc::now(),
+                    fact_type: None,
                     data: FactData { fields },
                 }
             })
@@ -115,6 +117,7 @@ fn test_alpha_memory_requirement() {
                     value: FactValue::Boolean(true),
                 },
             }],
+            metadata: Default::default(),
         };
         engine.add_rule(rule).unwrap();
     }
@@ -124,6 +127,7 @@ fn test_alpha_memory_requirement() {
         id: 1,
         external_id: None,
         timestamp: chrono::Utc::now(),
+        fact_type: None,
         data: FactData {
             fields: [(
                 "status".to_string(),
@@ -174,6 +178,7 @@ fn test_working_memory_requirement() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).unwrap();
@@ -185,6 +190,7 @@ fn test_working_memory_requirement() {
                 id: i,
                 external_id: None,
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: FactData {
                     fields: [
                         ("count".to_string(), FactValue::Integer((i + 3) as i64)), // 3, 4, 5
@@ -206,6 +212,7 @@ fn test_working_memory_requirement() {
         id: 3,
         external_id: None,
         timestamp: chrono::Utc::now(),
+        fact_type: None,
         data: FactData {
             fields: [
                 ("count".to_string(), FactValue::Integer(8)), // > 5, should match
@@ -23,6 +23,7 @@ fn create_test_fact(id: u64, fields: HashMap<String, FactValue>) -> Fact {
         id,
         external_id: Some(format!("ext_{}", id)),
         timestamp: Utc::now(),
+        fact_type: None,
         data: FactData { fields },
     }
 }
@@ -63,6 +64,7 @@ mod edge_cases {
                     value: FactValue::String("triggered".to_string()),
                 },
             }],
+            metadata: Default::default(),
         };
 
         network.add_rule(rule).expect("Failed to add rule");
@@ -115,6 +117,7 @@ mod edge_cases {
                     value: FactValue::String("complex_match".to_string()),
                 },
             }],
+            metadata: Default::default(),
         };
 
         network.add_rule(rule).expect("Failed to add rule");
@@ -167,6 +170,7 @@ mod edge_cases {
                     value: FactValue::String("not_empty".to_string()),
                 },
             }],
+            metadata: Default::default(),
         };
 
         network.add_rule(rule).expect("Failed to add rule");
@@ -210,6 +214,7 @@ mod edge_cases {
                     value: FactValue::String("number_contains".to_string()),
                 },
             }],
+            metadata: Default::default(),
         };
 
         let rule2 = Rule {
@@ -226,6 +231,7 @@ mod edge_cases {
                     value: FactValue::String("invalid_contains".to_string()),
                 },
             }],
+            metadata: Default::default(),
         };
 
         network.add_rule(rule1).expect("Failed to add rule1");
@@ -273,6 +279,7 @@ mod edge_cases {
                     value: FactValue::String("should_not_execute".to_string()),
                 },
             }],
+            metadata: Default::default(),
         };
 
         network.add_rule(rule).expect("Failed to add rule");
@@ -321,6 +328,7 @@ mod memory_pressure_tests {
                     value: FactValue::Boolean(true),
                 },
             }],
+            metadata: Default::default(),
         };
 
         network.add_rule(rule).expect("Failed to add rule");
@@ -380,6 +388,7 @@ mod memory_pressure_tests {
                         value: FactValue::Boolean(true),
                     },
                 }],
+                metadata: Default::default(),
             },
             Rule {
                 id: 2,
@@ -402,6 +411,7 @@ mod memory_pressure_tests {
                         value: FactValue::Boolean(true),
                     },
                 }],
+                metadata: Default::default(),
             },
         ];
 
@@ -495,6 +505,7 @@ mod aggregation_edge_cases {
                     value: FactValue::Boolean(true),
                 },
             }],
+            metadata: Default::default(),
         };
 
         network.add_rule(rule).expect("Failed to add rule");
@@ -568,6 +579,7 @@ mod aggregation_edge_cases {
                     value: FactValue::Boolean(true),
                 },
             }],
+            metadata: Default::default(),
         };
 
         network.add_rule(rule).expect("Failed to add rule");
@@ -624,6 +636,7 @@ mod aggregation_edge_cases {
                     value: FactValue::Boolean(true),
                 },
             }],
+            metadata: Default::default(),
         };
 
         network.add_rule(rule).expect("Failed to add rule");
@@ -666,6 +679,7 @@ mod performance_regression_tests {
                     value: FactValue::Boolean(true),
                 },
             }],
+            metadata: Default::default(),
         };
 
         network.add_rule(rule).expect("Failed to add rule");
@@ -728,6 +742,7 @@ mod performance_regression_tests {
                     value: FactValue::Boolean(true),
                 },
             }],
+            metadata: Default::default(),
         };
 
         network.add_rule(rule).expect("Failed to add rule");
@@ -797,6 +812,7 @@ mod performance_regression_tests {
                             value: FactValue::Boolean(true),
                         },
                     }],
+                    metadata: Default::default(),
                 };
                 test_network.add_rule(rule).expect("Failed to add rule");
             }
@@ -71,6 +71,7 @@ fn create_benchmark_facts(count: usize, pattern: FactPattern) -> Vec<Fact> {
             id: i as u64 + 1,
             external_id: Some(format!("bench_{}", i)),
             timestamp,
+            fact_type: None,
             data: FactData { fields },
         });
     }
@@ -239,6 +240,7 @@ fn create_simple_benchmark_rule(id: usize) -> Rule {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     }
 }
 
@@ -281,6 +283,7 @@ fn create_medium_benchmark_rule(id: usize) -> Rule {
                 },
             },
         ],
+        metadata: Default::default(),
     }
 }
 
@@ -327,6 +330,7 @@ fn create_complex_benchmark_rule(id: usize) -> Rule {
                 },
             },
         ],
+        metadata: Default::default(),
     }
 }
 
@@ -361,6 +365,7 @@ fn create_aggregation_benchmark_rule(id: usize) -> Rule {
                 },
             },
         }],
+        metadata: Default::default(),
     }
 }
 
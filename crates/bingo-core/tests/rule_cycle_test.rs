@@ -0,0 +1,68 @@
+//! Rule Cycle Test
+//!
+//! Validates that the engine's rule flow graph links rules whose actions write what
+//! other rules read, and that adding or updating a rule that closes a cycle of rules
+//! triggering each other is refused, logged or ignored as the rule cycle policy says.
+
+use bingo_core::{BingoEngine, BingoError, RuleCyclePolicy, parse_rule};
+
+const RULES: &str = r#"
+rule "Gross pay" id 1 when hours > 0 then set gross = 100
+rule "Tax" id 2 when gross > 50 then set net = 80
+rule "Report" id 3 when net > 0 then set reported = true
+"#;
+
+const RECOUNT: &str = r#"rule "Recount" id 4 when reported == true then set hours = 1"#;
+
+fn engine(policy: RuleCyclePolicy) -> BingoEngine {
+    let engine = BingoEngine::new().unwrap();
+    engine.set_rule_cycle_policy(policy);
+    engine.add_rules_from_dsl(RULES).unwrap();
+    engine
+}
+
+#[test]
+fn test_reject_refuses_rules_closing_a_cycle() {
+    let engine = engine(RuleCyclePolicy::Reject);
+    assert_eq!(engine.rule_cycle_policy(), RuleCyclePolicy::Reject);
+    assert_eq!(
+        engine.rule_flow_graph().topological_order(),
+        Some(vec![1, 2, 3])
+    );
+
+    let error = engine.add_rule(parse_rule(RECOUNT).unwrap()).unwrap_err();
+    match error {
+        BingoError::Rule { rule_id, details, .. } => {
+            assert_eq!(rule_id, Some(4));
+            assert_eq!(
+                details.as_deref(),
+                Some("Rules trigger each other: 4 -> 1 -> 2 -> 3 -> 4")
+            );
+        }
+        other => panic!("expected a rule error, got {other:?}"),
+    }
+    assert_eq!(engine.rule_count(), 3);
+    assert!(engine.rule_flow_graph().is_acyclic());
+
+    // Updating a rule into a cycle is refused too, keeping the old definition
+    let looped = parse_rule(r#"rule "Report" id 3 when net > 0 then set hours = 2"#).unwrap();
+    assert!(engine.update_rule(looped).is_err());
+    assert!(engine.rule_flow_graph().is_acyclic());
+}
+
+#[test]
+fn test_warn_and_off_add_rules_closing_a_cycle() {
+    for policy in [RuleCyclePolicy::Warn, RuleCyclePolicy::Off] {
+        let engine = engine(policy);
+        engine.add_rule(parse_rule(RECOUNT).unwrap()).unwrap();
+
+        let graph = engine.rule_flow_graph();
+        assert_eq!(graph.cycles(), vec![vec![1, 2, 3, 4]]);
+        assert_eq!(graph.topological_order(), None);
+        assert!(
+            graph
+                .to_dot()
+                .contains("\"rule:4\" -> \"rule:1\" [label=\"hours\", color=red];")
+        );
+    }
+}
@@ -0,0 +1,111 @@
+//! Rule DSL Test
+//!
+//! Validates that rules written in the textual rule language compile to the expected
+//! structs and fire through the engine like hand-built rules.
+
+use bingo_core::types::*;
+use bingo_core::{BingoEngine, parse_rule, parse_rules};
+use std::collections::HashMap;
+
+fn create_order(id: u64, total: i64, status: &str) -> Fact {
+    let mut fields = HashMap::new();
+    fields.insert("order.total".to_string(), FactValue::Integer(total));
+    fields.insert("status".to_string(), FactValue::String(status.to_string()));
+    Fact::new(id, FactData { fields })
+}
+
+#[test]
+fn test_dsl_rule_matches_hand_built_rule() {
+    let parsed = parse_rule(
+        r#"rule "Premium" id 7 when order.total > 1000 then set customer.tier = "premium""#,
+    )
+    .unwrap();
+
+    let expected = Rule {
+        id: 7,
+        name: "Premium".to_string(),
+        conditions: vec![Condition::Simple {
+            field: "order.total".to_string(),
+            operator: Operator::GreaterThan,
+            value: FactValue::Integer(1000),
+        }],
+        actions: vec![Action {
+            action_type: ActionType::SetField {
+                field: "customer.tier".to_string(),
+                value: FactValue::String("premium".to_string()),
+            },
+        }],
+        metadata: Default::default(),
+    };
+
+    assert_eq!(parsed.conditions, expected.conditions);
+    assert_eq!(format!("{parsed:?}"), format!("{expected:?}"));
+}
+
+#[test]
+fn test_dsl_rules_fire_in_engine() {
+    let engine = BingoEngine::new().unwrap();
+    let added = engine
+        .add_rules_from_dsl(
+            r#"
+            # Large active orders
+            rule "Large order"
+            when order.total >= 1000 and status == "active"
+            then
+                set customer.tier = "premium";
+                log "Large order"
+            end
+
+            rule "Cancelled"
+            when status == "cancelled" or order.total < 0
+            then alert high "order" "Order needs review"
+            "#,
+        )
+        .unwrap();
+    assert_eq!(added, 2);
+
+    let results = engine
+        .process_facts(vec![
+            create_order(1, 1500, "active"),
+            create_order(2, 200, "active"),
+            create_order(3, 50, "cancelled"),
+        ])
+        .unwrap();
+
+    let fired: Vec<(u64, u64)> = results.iter().map(|r| (r.rule_id, r.fact_id)).collect();
+    assert!(fired.contains(&(1, 1)));
+    assert!(fired.contains(&(2, 3)));
+    assert_eq!(fired.len(), 2);
+}
+
+#[test]
+fn test_dsl_parses_every_action_form() {
+    let rules = parse_rules(
+        r#"
+        rule "All actions" when x > 0 then
+            set a = 1.5,
+            log "hello",
+            increment counter,
+            increment total by 10,
+            append "tag" to tags,
+            create { kind: "derived", values: [1, 2], nested: { ok: true } },
+            formula score = "x * 2",
+            call tax(amount = order.total, rate = tax_rate) into tax_due,
+            alert critical "fraud" "Suspicious order"
+        end
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(rules.len(), 1);
+    assert_eq!(rules[0].actions.len(), 9);
+    assert!(matches!(
+        &rules[0].actions[2].action_type,
+        ActionType::IncrementField { increment: FactValue::Integer(1), .. }
+    ));
+    assert!(matches!(
+        &rules[0].actions[7].action_type,
+        ActionType::CallCalculator { input_mapping, output_field, .. }
+            if input_mapping["amount"] == "order.total" && output_field == "tax_due"
+    ));
+}
@@ -0,0 +1,123 @@
+//! Rule Group Test
+//!
+//! Validates that rules in a disabled rule group match without firing until the group
+//! is enabled again, and that rules sharing an activation group fire at most once per
+//! fact: the matching rule with the highest salience, then the lowest rule ID.
+
+use bingo_core::types::*;
+use bingo_core::{BingoEngine, EngineSnapshot, RuleMetadata};
+use std::collections::{BTreeSet, HashMap};
+
+fn order(id: FactId, amount: i64) -> Fact {
+    let fields = HashMap::from([("amount".to_string(), FactValue::Integer(amount))]);
+    Fact::new(id, FactData { fields })
+}
+
+/// Rule matching orders over `minimum`, in the given rule and activation groups
+fn discount_rule(
+    id: RuleId,
+    minimum: i64,
+    rule_group: Option<&str>,
+    activation_group: Option<&str>,
+) -> Rule {
+    Rule {
+        id,
+        name: format!("Discount {id}"),
+        conditions: vec![Condition::Simple {
+            field: "amount".to_string(),
+            operator: Operator::GreaterThan,
+            value: FactValue::Integer(minimum),
+        }],
+        actions: vec![Action {
+            action_type: ActionType::SetField {
+                field: format!("discount_{id}"),
+                value: FactValue::Boolean(true),
+            },
+        }],
+        metadata: RuleMetadata {
+            rule_group: rule_group.map(str::to_string),
+            activation_group: activation_group.map(str::to_string),
+            ..Default::default()
+        },
+    }
+}
+
+/// `(fact, rule)` pairs that fired, sorted
+fn fired(engine: &BingoEngine, facts: Vec<Fact>) -> Vec<(FactId, RuleId)> {
+    let mut fired: Vec<(FactId, RuleId)> = engine
+        .process_facts(facts)
+        .unwrap()
+        .iter()
+        .map(|result| (result.fact_id, result.rule_id))
+        .collect();
+    fired.sort_unstable();
+    fired
+}
+
+#[test]
+fn test_disabled_rule_groups_do_not_fire() {
+    let engine = BingoEngine::new().unwrap();
+    engine.add_rule(discount_rule(1, 0, Some("seasonal"), None)).unwrap();
+    engine.add_rule(discount_rule(2, 0, None, None)).unwrap();
+
+    engine.disable_rule_group("seasonal");
+    assert_eq!(
+        engine.disabled_rule_groups(),
+        BTreeSet::from(["seasonal".to_string()])
+    );
+    assert_eq!(fired(&engine, vec![order(1, 50)]), vec![(1, 2)]);
+
+    // Sessions created from the engine, and restored snapshots of it, start with the
+    // group disabled as well
+    let session = BingoEngine::from_template(&engine).unwrap();
+    assert_eq!(fired(&session, vec![order(2, 50)]), vec![(2, 2)]);
+    let bytes = engine.snapshot().unwrap().to_bytes().unwrap();
+    let restored = BingoEngine::new().unwrap();
+    restored.restore(&EngineSnapshot::from_bytes(&bytes).unwrap()).unwrap();
+    assert_eq!(
+        restored.disabled_rule_groups(),
+        engine.disabled_rule_groups()
+    );
+
+    engine.enable_rule_group("seasonal");
+    assert!(engine.disabled_rule_groups().is_empty());
+    assert_eq!(fired(&engine, vec![order(3, 50)]), vec![(3, 1), (3, 2)]);
+    assert_eq!(fired(&session, vec![order(4, 50)]), vec![(4, 2)]);
+}
+
+#[test]
+fn test_activation_group_fires_its_first_matching_rule_once_per_fact() {
+    let engine = BingoEngine::new().unwrap();
+    engine.add_rule(discount_rule(1, 0, None, Some("discount"))).unwrap();
+    engine.add_rule(discount_rule(2, 100, None, Some("discount"))).unwrap();
+    engine.add_rule(discount_rule(3, 0, None, Some("discount"))).unwrap();
+    engine.add_rule(discount_rule(4, 0, None, None)).unwrap();
+
+    // Without salience the lowest rule ID wins
+    assert_eq!(
+        fired(&engine, vec![order(1, 150), order(2, 50)]),
+        vec![(1, 1), (1, 4), (2, 1), (2, 4)]
+    );
+
+    // Higher salience wins, and the next rule fires when it doesn't match
+    engine.set_rule_salience(2, 10).unwrap();
+    assert_eq!(
+        fired(&engine, vec![order(3, 150), order(4, 50)]),
+        vec![(3, 2), (3, 4), (4, 1), (4, 4)]
+    );
+}
+
+#[test]
+fn test_disabled_rules_do_not_win_their_activation_group() {
+    let engine = BingoEngine::new().unwrap();
+    engine
+        .add_rule(discount_rule(1, 0, Some("seasonal"), Some("discount")))
+        .unwrap();
+    engine.add_rule(discount_rule(2, 0, None, Some("discount"))).unwrap();
+    engine.disable_rule_group("seasonal");
+
+    assert_eq!(fired(&engine, vec![order(1, 50)]), vec![(1, 2)]);
+    let incremental = engine.add_fact_to_working_memory(order(2, 50)).unwrap();
+    let rule_ids: Vec<RuleId> = incremental.iter().map(|result| result.rule_id).collect();
+    assert_eq!(rule_ids, vec![2]);
+}
@@ -0,0 +1,112 @@
+//! Rule Metadata Test
+//!
+//! Validates that rules only fire between their effective and expiry dates, that a
+//! rule expiring before it takes effect is rejected, and that a rule's description,
+//! owner and link travel with it through listing, JSON and snapshots, and that JSON
+//! rules may name their validity window `valid_from` and `valid_to`.
+
+use bingo_core::types::*;
+use bingo_core::{BingoEngine, EngineSnapshot, RuleMetadata};
+use chrono::{Duration, TimeZone, Utc};
+use std::collections::HashMap;
+
+fn order(amount: i64) -> Fact {
+    let fields = HashMap::from([("amount".to_string(), FactValue::Integer(amount))]);
+    Fact::new(0, FactData { fields })
+}
+
+fn large_order_rule(id: RuleId, metadata: RuleMetadata) -> Rule {
+    Rule {
+        id,
+        name: format!("Large order {id}"),
+        conditions: vec![Condition::Simple {
+            field: "amount".to_string(),
+            operator: Operator::GreaterThan,
+            value: FactValue::Integer(100),
+        }],
+        actions: vec![Action {
+            action_type: ActionType::SetField {
+                field: "flagged".to_string(),
+                value: FactValue::Boolean(true),
+            },
+        }],
+        metadata,
+    }
+}
+
+#[test]
+fn test_rules_fire_only_while_in_effect() {
+    let now = Utc::now();
+    let engine = BingoEngine::new().unwrap();
+    let in_effect = RuleMetadata {
+        effective_date: Some(now - Duration::days(1)),
+        expiry_date: Some(now + Duration::days(1)),
+        ..Default::default()
+    };
+    let expired =
+        RuleMetadata { expiry_date: Some(now - Duration::hours(1)), ..Default::default() };
+    let upcoming =
+        RuleMetadata { effective_date: Some(now + Duration::hours(1)), ..Default::default() };
+    engine.add_rule(large_order_rule(1, in_effect)).unwrap();
+    engine.add_rule(large_order_rule(2, expired)).unwrap();
+    engine.add_rule(large_order_rule(3, upcoming)).unwrap();
+    engine.add_rule(large_order_rule(4, RuleMetadata::default())).unwrap();
+
+    let mut fired: Vec<_> = engine
+        .process_facts(vec![order(150)])
+        .unwrap()
+        .iter()
+        .map(|r| r.rule_id)
+        .collect();
+    fired.sort_unstable();
+    assert_eq!(fired, [1, 4]);
+
+    // The effective date is inclusive and the expiry date exclusive
+    let start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+    let year =
+        RuleMetadata { effective_date: Some(start), expiry_date: Some(end), ..Default::default() };
+    assert!(year.is_in_effect_at(start));
+    assert!(!year.is_in_effect_at(end));
+    assert!(!year.is_in_effect_at(start - Duration::seconds(1)));
+
+    let backwards =
+        RuleMetadata { effective_date: Some(end), expiry_date: Some(start), ..Default::default() };
+    let error = engine.add_rule(large_order_rule(5, backwards)).unwrap_err();
+    assert!(error.to_string().contains("before it takes effect"));
+    assert_eq!(engine.rule_count(), 4);
+}
+
+#[test]
+fn test_metadata_travels_with_the_rule() {
+    let metadata = RuleMetadata {
+        description: Some("Flags orders that need a second approval".to_string()),
+        owner: Some("risk-team".to_string()),
+        link: Some("https://wiki.example.com/large-orders".to_string()),
+        effective_date: Some(Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap()),
+        expiry_date: None,
+        ..Default::default()
+    };
+    let engine = BingoEngine::new().unwrap();
+    engine.add_rule(large_order_rule(1, metadata.clone())).unwrap();
+    assert_eq!(engine.rules()[0].metadata, metadata);
+
+    let bytes = engine.snapshot().unwrap().to_bytes().unwrap();
+    let restored = BingoEngine::new().unwrap();
+    restored.restore(&EngineSnapshot::from_bytes(&bytes).unwrap()).unwrap();
+    assert_eq!(restored.rules()[0].metadata, metadata);
+
+    // Rules written before metadata existed still load
+    let json = r#"{"id": 7, "name": "Legacy", "conditions": [], "actions": []}"#;
+    let legacy: Rule = serde_json::from_str(json).unwrap();
+    assert_eq!(legacy.metadata, RuleMetadata::default());
+
+    // Validity windows may be written as valid_from and valid_to
+    let json = r#"{"valid_from": "2025-01-01T00:00:00Z", "valid_to": "2026-01-01T00:00:00Z"}"#;
+    let window: RuleMetadata = serde_json::from_str(json).unwrap();
+    assert_eq!(window.effective_date, metadata.effective_date);
+    assert_eq!(
+        window.expiry_date,
+        Some(Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap())
+    );
+}
@@ -63,6 +63,7 @@ fn test_basic_rule_optimization() {
         actions: vec![Action {
             action_type: ActionType::Log { message: "Rule fired successfully".to_string() },
         }],
+        metadata: Default::default(),
     };
 
     let result = optimizer.optimize_rule(rule);
@@ -130,6 +131,7 @@ fn test_multi_condition_rule_optimization() {
                 },
             },
         }],
+        metadata: Default::default(),
     };
 
     let result = optimizer.optimize_rule(rule);
@@ -178,6 +180,7 @@ fn test_batch_rule_optimization() {
             actions: vec![Action {
                 action_type: ActionType::Log { message: "Simple rule fired".to_string() },
             }],
+            metadata: Default::default(),
         },
         Rule {
             id: 11,
@@ -202,6 +205,7 @@ fn test_batch_rule_optimization() {
                     value: FactValue::Boolean(true),
                 },
             }],
+            metadata: Default::default(),
         },
         Rule {
             id: 12,
@@ -220,6 +224,7 @@ fn test_batch_rule_optimization() {
                 },
             ],
             actions: vec![],
+            metadata: Default::default(),
         },
     ];
 
@@ -275,6 +280,7 @@ fn test_engine_rule_optimization_integration() {
                 },
             },
         }],
+        metadata: Default::default(),
     };
 
     // Test optimized rule addition
@@ -390,6 +396,7 @@ fn test_optimization_metrics_tracking() {
                 },
             ],
             actions: vec![],
+            metadata: Default::default(),
         },
         Rule {
             id: 201,
@@ -407,6 +414,7 @@ fn test_optimization_metrics_tracking() {
                 },
             ],
             actions: vec![],
+            metadata: Default::default(),
         },
     ];
 
@@ -435,6 +443,64 @@ fn test_optimization_metrics_tracking() {
     );
 }
 
+#[test]
+fn test_index_statistics_plan_rules_for_stored_facts() {
+    let engine = BingoEngine::new().expect("Failed to create engine");
+    let account = |id: u64, status: &str| {
+        Fact::new(
+            id,
+            FactData {
+                fields: HashMap::from([
+                    ("status".to_string(), FactValue::String(status.to_string())),
+                    ("balance".to_string(), FactValue::Integer(id as i64 * 10)),
+                ]),
+            },
+        )
+    };
+
+    // The ruleset is loaded before any facts, so it is planned from heuristics
+    engine
+        .add_rule(Rule {
+            id: 1,
+            name: "Blocked with balance".to_string(),
+            conditions: vec![
+                Condition::Simple {
+                    field: "balance".to_string(),
+                    operator: Operator::GreaterThan,
+                    value: FactValue::Integer(0),
+                },
+                Condition::Simple {
+                    field: "status".to_string(),
+                    operator: Operator::In,
+                    value: FactValue::Array(vec![
+                        FactValue::String("blocked".to_string()),
+                        FactValue::String("frozen".to_string()),
+                    ]),
+                },
+            ],
+            actions: vec![Action {
+                action_type: ActionType::Log { message: "Review".to_string() },
+            }],
+            metadata: Default::default(),
+        })
+        .expect("Failed to add rule");
+
+    let facts: Vec<Fact> = (1..=20)
+        .map(|id| account(id, if id % 10 == 0 { "blocked" } else { "active" }))
+        .collect();
+    let results = engine.process_facts(facts).expect("Failed to process facts");
+    assert_eq!(results.len(), 2);
+
+    // Only the membership test is on an indexed field
+    assert_eq!(engine.apply_index_statistics().unwrap(), 1);
+
+    let results = engine
+        .process_facts(vec![account(21, "frozen"), account(22, "active")])
+        .expect("Failed to process facts");
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].fact_id, 21);
+}
+
 #[cfg(test)]
 mod integration_tests {
     use super::*;
@@ -485,6 +551,7 @@ mod integration_tests {
                         },
                     },
                 }],
+                metadata: Default::default(),
             },
             // Rule with complex conditions
             Rule {
@@ -513,6 +580,7 @@ mod integration_tests {
                         value: FactValue::String("platinum".to_string()),
                     },
                 }],
+                metadata: Default::default(),
             },
         ];
 
@@ -565,6 +633,7 @@ mod integration_tests {
                 id: 1,
                 external_id: Some("test_fact_1".to_string()),
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: FactData {
                     fields: HashMap::from([
                         ("employee_id".to_string(), FactValue::Integer(12345)),
@@ -583,6 +652,7 @@ mod integration_tests {
                 id: 2,
                 external_id: Some("test_fact_2".to_string()),
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: FactData {
                     fields: HashMap::from([
                         ("customer_id".to_string(), FactValue::Integer(999888777)),
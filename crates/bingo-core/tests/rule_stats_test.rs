@@ -0,0 +1,79 @@
+//! Rule Statistics Test
+//!
+//! Validates that the engine counts the evaluations and firings of every rule with its
+//! last firing time and condition evaluation time, lists rules that never fired, keeps
+//! the counts across rule updates and resets them on request.
+
+use bingo_core::*;
+use std::collections::HashMap;
+
+fn order(id: u64, amount: i64) -> Fact {
+    let fields = HashMap::from([
+        ("amount".to_string(), FactValue::Integer(amount)),
+        ("region".to_string(), FactValue::String("EU".to_string())),
+    ]);
+    Fact::new(id, FactData { fields })
+}
+
+fn engine() -> BingoEngine {
+    let engine = BingoEngine::new().unwrap();
+    engine
+        .add_rules_from_dsl(
+            r#"
+            rule "Large order" id 1 when amount > 100 then set flagged = true
+            rule "EU large order" id 2 when amount > 100 and region == "EU" then set vat = true
+            rule "Huge order" id 3 when amount > 100000 then set review = true
+            "#,
+        )
+        .unwrap();
+    engine
+}
+
+#[test]
+fn test_rule_stats_count_activations_and_find_dead_rules() {
+    let engine = engine();
+    let before = chrono::Utc::now();
+    engine
+        .process_facts(vec![order(101, 250), order(102, 500), order(103, 50)])
+        .unwrap();
+
+    let stats = engine.rule_stats();
+    assert_eq!(
+        stats.iter().map(|stats| stats.rule_id).collect::<Vec<_>>(),
+        vec![1, 2, 3]
+    );
+    assert_eq!(stats[0].rule_name, "Large order");
+    assert_eq!(stats[0].activations, 2);
+    assert_eq!(stats[1].activations, 2);
+    assert!(stats[0].evaluations >= 2);
+    assert!(stats[0].last_fired.is_some_and(|fired| fired >= before));
+    assert!(stats[0].average_condition_time().is_some());
+
+    let dead: Vec<&str> = stats
+        .iter()
+        .filter(|stats| stats.is_dead())
+        .map(|stats| stats.rule_name.as_str())
+        .collect();
+    assert_eq!(dead, vec!["Huge order"]);
+    assert_eq!(stats[2].last_fired, None);
+}
+
+#[test]
+fn test_rule_stats_survive_rule_updates_and_reset() {
+    let engine = engine();
+    engine.process_facts(vec![order(101, 250)]).unwrap();
+
+    let mut huge = engine.get_rule(3).unwrap();
+    huge.name = "Very large order".to_string();
+    engine.update_rule(huge).unwrap();
+    engine.remove_rule(2).unwrap();
+
+    let stats = engine.rule_stats();
+    assert_eq!(stats.len(), 2);
+    assert_eq!(stats[0].activations, 1);
+    assert_eq!(stats[1].rule_name, "Very large order");
+
+    engine.reset_rule_stats();
+    let stats = engine.rule_stats();
+    assert!(stats.iter().all(|stats| stats.evaluations == 0 && stats.is_dead()));
+}
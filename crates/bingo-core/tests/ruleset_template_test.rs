@@ -0,0 +1,96 @@
+//! Ruleset Template Test
+//!
+//! Validates that engines created from a compiled template share its rules and
+//! network shape but keep their facts, tokens and activations to themselves.
+
+use bingo_core::BingoEngine;
+use bingo_core::types::*;
+use std::collections::HashMap;
+
+fn create_fact(id: u64, score: i64, status: &str) -> Fact {
+    let mut fields = HashMap::new();
+    fields.insert("score".to_string(), FactValue::Integer(score));
+    fields.insert("status".to_string(), FactValue::String(status.to_string()));
+    Fact::new(id, FactData { fields })
+}
+
+fn high_performer_rule() -> Rule {
+    Rule {
+        id: 1,
+        name: "High Performer".to_string(),
+        conditions: vec![
+            Condition::Simple {
+                field: "score".to_string(),
+                operator: Operator::GreaterThan,
+                value: FactValue::Integer(90),
+            },
+            Condition::Simple {
+                field: "status".to_string(),
+                operator: Operator::Equal,
+                value: FactValue::String("active".to_string()),
+            },
+        ],
+        actions: vec![Action {
+            action_type: ActionType::Log { message: "High performer detected".to_string() },
+        }],
+        metadata: Default::default(),
+    }
+}
+
+#[test]
+fn test_template_engine_keeps_compiled_rules() {
+    let template = BingoEngine::new().unwrap();
+    template.add_rule(high_performer_rule()).unwrap();
+
+    let session = BingoEngine::from_template(&template).unwrap();
+    let template_stats = template.get_stats();
+    let session_stats = session.get_stats();
+    assert_eq!(session_stats.rule_count, 1);
+    assert_eq!(session_stats.node_count, template_stats.node_count);
+    assert_eq!(session_stats.fact_count, 0);
+
+    let results = session.process_facts(vec![create_fact(1, 95, "active")]).unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].rule_id, 1);
+}
+
+#[test]
+fn test_template_sessions_are_isolated() {
+    let template = BingoEngine::new().unwrap();
+    template.add_rule(high_performer_rule()).unwrap();
+
+    let first = BingoEngine::from_template(&template).unwrap();
+    let second = BingoEngine::from_template(&template).unwrap();
+
+    assert_eq!(
+        first.process_facts(vec![create_fact(1, 95, "active")]).unwrap().len(),
+        1
+    );
+    assert_eq!(first.get_stats().fact_count, 1);
+
+    // Neither the template nor the sibling session saw the fact
+    assert_eq!(template.get_stats().fact_count, 0);
+    assert_eq!(second.get_stats().fact_count, 0);
+    assert_eq!(
+        second.process_facts(vec![create_fact(1, 95, "active")]).unwrap().len(),
+        1
+    );
+}
+
+#[test]
+fn test_template_clone_drops_fact_state() {
+    let template = BingoEngine::new().unwrap();
+    template.add_rule(high_performer_rule()).unwrap();
+    assert_eq!(
+        template.process_facts(vec![create_fact(1, 95, "active")]).unwrap().len(),
+        1
+    );
+
+    // Facts already processed by the template do not leak into new sessions
+    let session = BingoEngine::from_template(&template).unwrap();
+    assert_eq!(session.get_stats().fact_count, 0);
+    assert_eq!(
+        session.process_facts(vec![create_fact(2, 50, "active")]).unwrap().len(),
+        0
+    );
+}
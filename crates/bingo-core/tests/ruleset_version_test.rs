@@ -0,0 +1,111 @@
+//! Ruleset Version Test
+//!
+//! Validates that rule changes bump the engine's ruleset version, that updates
+//! replace rules in place and that rebuilding the network keeps registered calendars.
+
+use bingo_core::types::*;
+use bingo_core::{BingoEngine, BusinessPeriod, PeriodCalendar};
+use chrono::{TimeZone, Utc};
+use std::collections::HashMap;
+
+fn score_rule(id: u64, threshold: i64) -> Rule {
+    Rule {
+        id,
+        name: format!("Score above {threshold}"),
+        conditions: vec![Condition::Simple {
+            field: "score".to_string(),
+            operator: Operator::GreaterThan,
+            value: FactValue::Integer(threshold),
+        }],
+        actions: vec![Action {
+            action_type: ActionType::Log { message: "High score".to_string() },
+        }],
+        metadata: Default::default(),
+    }
+}
+
+fn score_fact(id: u64, score: i64) -> Fact {
+    let mut fields = HashMap::new();
+    fields.insert("score".to_string(), FactValue::Integer(score));
+    Fact::new(id, FactData { fields })
+}
+
+#[test]
+fn test_rule_changes_bump_version() {
+    let engine = BingoEngine::new().unwrap();
+    assert_eq!(engine.ruleset_version(), 0);
+
+    engine.add_rule(score_rule(1, 90)).unwrap();
+    engine.add_rule(score_rule(2, 50)).unwrap();
+    assert_eq!(engine.ruleset_version(), 2);
+
+    engine.update_rule(score_rule(2, 60)).unwrap();
+    assert_eq!(engine.ruleset_version(), 3);
+    assert_eq!(engine.rule_count(), 2);
+    assert_eq!(engine.get_rule(2).unwrap().name, "Score above 60");
+
+    engine.remove_rule(1).unwrap();
+    assert_eq!(engine.ruleset_version(), 4);
+    assert_eq!(
+        engine.rules().iter().map(|rule| rule.id).collect::<Vec<_>>(),
+        vec![2]
+    );
+
+    // Failed changes leave the version alone
+    assert!(engine.remove_rule(1).is_err());
+    assert!(engine.update_rule(score_rule(7, 10)).is_err());
+    assert_eq!(engine.ruleset_version(), 4);
+
+    let template_copy = BingoEngine::from_template(&engine).unwrap();
+    assert_eq!(template_copy.ruleset_version(), 4);
+}
+
+#[test]
+fn test_results_carry_producing_version() {
+    let engine = BingoEngine::new().unwrap();
+    engine.add_rule(score_rule(1, 90)).unwrap();
+
+    let (results, version) = engine.process_facts_versioned(vec![score_fact(1, 95)]).unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(version, 1);
+
+    engine.update_rule(score_rule(1, 99)).unwrap();
+    let (results, version) = engine.process_facts_versioned(vec![score_fact(2, 95)]).unwrap();
+    assert!(results.is_empty());
+    assert_eq!(version, 2);
+}
+
+#[test]
+fn test_rebuild_keeps_registered_calendars() {
+    let engine = BingoEngine::new().unwrap();
+    let january = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let february = Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap();
+    engine.register_calendar(
+        PeriodCalendar::new("pay", vec![BusinessPeriod::new("PP1", january, february)]).unwrap(),
+    );
+
+    let period_hours = Rule {
+        id: 3,
+        name: "Hours in pay period".to_string(),
+        conditions: vec![Condition::Aggregation(AggregationCondition {
+            aggregation_type: AggregationType::Sum,
+            source_field: "hours".to_string(),
+            group_by: vec!["employee_id".to_string()],
+            having: None,
+            alias: "period_hours".to_string(),
+            window: Some(AggregationWindow::Calendar { calendar: "pay".to_string() }),
+        })],
+        actions: vec![Action {
+            action_type: ActionType::Log { message: "Hours recorded".to_string() },
+        }],
+        metadata: Default::default(),
+    };
+    engine.add_rule(score_rule(1, 90)).unwrap();
+    engine.add_rule(period_hours.clone()).unwrap();
+
+    // Both changes recompile the network, which needs the calendar again
+    engine.remove_rule(1).unwrap();
+    engine.update_rule(period_hours).unwrap();
+    assert_eq!(engine.rule_count(), 1);
+    assert_eq!(engine.ruleset_version(), 4);
+}
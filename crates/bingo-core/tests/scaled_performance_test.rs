@@ -25,6 +25,7 @@ fn test_scaled_performance() {
                     value: FactValue::Boolean(true),
                 },
             }],
+            metadata: Default::default(),
         };
 
         engine.add_rule(rule).unwrap();
@@ -50,6 +51,7 @@ fn test_scaled_performance() {
                     id: i as u64,
                     external_id: None,
                     timestamp: chrono::Utc::now(),
+                    fact_type: None,
                     data: FactData { fields },
                 }
             })
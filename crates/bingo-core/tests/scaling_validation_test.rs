@@ -49,6 +49,7 @@ fn test_100k_fact_scaling() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).unwrap();
@@ -74,6 +75,7 @@ fn test_100k_fact_scaling() {
                 id: i as u64,
                 external_id: None,
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: FactData { fields },
             }
         })
@@ -126,6 +128,7 @@ fn test_100k_payroll_scenario() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
 
     // Rule 2: Overtime Detection (applies to shifts >8 hours)
@@ -143,6 +146,7 @@ fn test_100k_payroll_scenario() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
 
     // Rule 3: Weekend Premium (applies to weekend shifts)
@@ -160,6 +164,7 @@ fn test_100k_payroll_scenario() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
 
     // Rule 4: Night Shift Differential
@@ -177,6 +182,7 @@ fn test_100k_payroll_scenario() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(base_pay_rule).unwrap();
@@ -216,6 +222,7 @@ fn test_100k_payroll_scenario() {
                 id: i as u64,
                 external_id: None,
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: FactData { fields },
             }
         })
@@ -305,6 +312,7 @@ fn test_250k_fact_scaling() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).unwrap();
@@ -330,6 +338,7 @@ fn test_250k_fact_scaling() {
                 id: i as u64,
                 external_id: None,
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: FactData { fields },
             }
         })
@@ -403,6 +412,7 @@ fn test_250k_payroll_scenario() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
 
     // Rule 2: Overtime Detection (applies to shifts >8 hours)
@@ -420,6 +430,7 @@ fn test_250k_payroll_scenario() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
 
     // Rule 3: Weekend Premium (applies to weekend shifts)
@@ -437,6 +448,7 @@ fn test_250k_payroll_scenario() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
 
     // Rule 4: Night Shift Differential
@@ -454,6 +466,7 @@ fn test_250k_payroll_scenario() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(base_pay_rule).unwrap();
@@ -493,6 +506,7 @@ fn test_250k_payroll_scenario() {
                 id: i as u64,
                 external_id: None,
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: FactData { fields },
             }
         })
@@ -580,6 +594,7 @@ fn test_500k_fact_scaling() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).unwrap();
@@ -602,6 +617,7 @@ fn test_500k_fact_scaling() {
                 id: i as u64,
                 external_id: None,
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: FactData { fields },
             }
         })
@@ -667,6 +683,7 @@ fn test_500k_payroll_scenario() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
 
     // Rule 2: Overtime Detection (applies to shifts >8 hours)
@@ -684,6 +701,7 @@ fn test_500k_payroll_scenario() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
 
     // Rule 3: Weekend Premium (applies to weekend shifts)
@@ -701,6 +719,7 @@ fn test_500k_payroll_scenario() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
 
     // Rule 4: Night Shift Differential
@@ -718,6 +737,7 @@ fn test_500k_payroll_scenario() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(base_pay_rule).unwrap();
@@ -757,6 +777,7 @@ fn test_500k_payroll_scenario() {
                 id: i as u64,
                 external_id: None,
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: FactData { fields },
             }
         })
@@ -846,6 +867,7 @@ fn test_1m_fact_scaling() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).unwrap();
@@ -871,6 +893,7 @@ fn test_1m_fact_scaling() {
                 id: i as u64,
                 external_id: None,
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: FactData { fields },
             }
         })
@@ -946,6 +969,7 @@ fn test_1m_payroll_scenario() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
 
     // Rule 2: Overtime Detection (applies to shifts >8 hours)
@@ -963,6 +987,7 @@ fn test_1m_payroll_scenario() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
 
     // Rule 3: Weekend Premium (applies to weekend shifts)
@@ -980,6 +1005,7 @@ fn test_1m_payroll_scenario() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
 
     // Rule 4: Night Shift Differential
@@ -997,6 +1023,7 @@ fn test_1m_payroll_scenario() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(base_pay_rule).unwrap();
@@ -1036,6 +1063,7 @@ fn test_1m_payroll_scenario() {
                 id: i as u64,
                 external_id: None,
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: FactData { fields },
             }
         })
@@ -1126,6 +1154,7 @@ fn test_2m_fact_scaling() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).unwrap();
@@ -1152,6 +1181,7 @@ fn test_2m_fact_scaling() {
                 id: i as u64,
                 external_id: None,
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: FactData { fields },
             }
         })
@@ -1230,6 +1260,7 @@ fn test_250k_enterprise_calculation_rules() {
                     value: FactValue::Float(i as f64 * 10.0),
                 },
             }],
+            metadata: Default::default(),
         };
         engine.add_rule(rule).unwrap();
     }
@@ -1250,6 +1281,7 @@ fn test_250k_enterprise_calculation_rules() {
                     value: FactValue::Float(i as f64 * 15.0),
                 },
             }],
+            metadata: Default::default(),
         };
         engine.add_rule(rule).unwrap();
     }
@@ -1270,6 +1302,7 @@ fn test_250k_enterprise_calculation_rules() {
                     value: FactValue::Integer(i as i64),
                 },
             }],
+            metadata: Default::default(),
         };
         engine.add_rule(rule).unwrap();
     }
@@ -1290,6 +1323,7 @@ fn test_250k_enterprise_calculation_rules() {
                     value: FactValue::String("checked".to_string()),
                 },
             }],
+            metadata: Default::default(),
         };
         engine.add_rule(rule).unwrap();
     }
@@ -1332,6 +1366,7 @@ fn test_250k_enterprise_calculation_rules() {
                 id: i as u64,
                 external_id: None,
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: FactData { fields },
             }
         })
@@ -1417,6 +1452,7 @@ fn test_500k_enterprise_calculation_rules() {
                     value: FactValue::Float(i as f64 * 10.0),
                 },
             }],
+            metadata: Default::default(),
         };
         engine.add_rule(rule).unwrap();
     }
@@ -1437,6 +1473,7 @@ fn test_500k_enterprise_calculation_rules() {
                     value: FactValue::Float(i as f64 * 15.0),
                 },
             }],
+            metadata: Default::default(),
         };
         engine.add_rule(rule).unwrap();
     }
@@ -1457,6 +1494,7 @@ fn test_500k_enterprise_calculation_rules() {
                     value: FactValue::Integer(i as i64),
                 },
             }],
+            metadata: Default::default(),
         };
         engine.add_rule(rule).unwrap();
     }
@@ -1477,6 +1515,7 @@ fn test_500k_enterprise_calculation_rules() {
                     value: FactValue::String("checked".to_string()),
                 },
             }],
+            metadata: Default::default(),
         };
         engine.add_rule(rule).unwrap();
     }
@@ -1519,6 +1558,7 @@ fn test_500k_enterprise_calculation_rules() {
                 id: i as u64,
                 external_id: None,
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: FactData { fields },
             }
         })
@@ -1604,6 +1644,7 @@ fn test_1m_enterprise_calculation_rules() {
                     value: FactValue::Float(i as f64 * 10.0),
                 },
             }],
+            metadata: Default::default(),
         };
         engine.add_rule(rule).unwrap();
     }
@@ -1624,6 +1665,7 @@ fn test_1m_enterprise_calculation_rules() {
                     value: FactValue::Float(i as f64 * 15.0),
                 },
             }],
+            metadata: Default::default(),
         };
         engine.add_rule(rule).unwrap();
     }
@@ -1644,6 +1686,7 @@ fn test_1m_enterprise_calculation_rules() {
                     value: FactValue::Integer(i as i64),
                 },
             }],
+            metadata: Default::default(),
         };
         engine.add_rule(rule).unwrap();
     }
@@ -1664,6 +1707,7 @@ fn test_1m_enterprise_calculation_rules() {
                     value: FactValue::String("checked".to_string()),
                 },
             }],
+            metadata: Default::default(),
         };
         engine.add_rule(rule).unwrap();
     }
@@ -1706,6 +1750,7 @@ fn test_1m_enterprise_calculation_rules() {
                 id: i as u64,
                 external_id: None,
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: FactData { fields },
             }
         })
@@ -1793,6 +1838,7 @@ fn test_2m_enterprise_calculation_rules() {
                     value: FactValue::Float(i as f64 * 10.0),
                 },
             }],
+            metadata: Default::default(),
         };
         engine.add_rule(rule).unwrap();
     }
@@ -1813,6 +1859,7 @@ fn test_2m_enterprise_calculation_rules() {
                     value: FactValue::Float(i as f64 * 15.0),
                 },
             }],
+            metadata: Default::default(),
         };
         engine.add_rule(rule).unwrap();
     }
@@ -1833,6 +1880,7 @@ fn test_2m_enterprise_calculation_rules() {
                     value: FactValue::Integer(i as i64),
                 },
             }],
+            metadata: Default::default(),
         };
         engine.add_rule(rule).unwrap();
     }
@@ -1853,6 +1901,7 @@ fn test_2m_enterprise_calculation_rules() {
                     value: FactValue::String("checked".to_string()),
                 },
             }],
+            metadata: Default::default(),
         };
         engine.add_rule(rule).unwrap();
     }
@@ -1895,6 +1944,7 @@ fn test_2m_enterprise_calculation_rules() {
                 id: i as u64,
                 external_id: None,
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: FactData { fields },
             }
         })
@@ -1976,6 +2026,7 @@ fn test_200k_fact_scaling_ci_appropriate() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).unwrap();
@@ -2001,6 +2052,7 @@ fn test_200k_fact_scaling_ci_appropriate() {
                 id: i as u64,
                 external_id: None,
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: FactData { fields },
             }
         })
@@ -0,0 +1,179 @@
+//! Sequence Condition Integration Test
+//!
+//! Validates that sequence conditions compile to sequence nodes and that temporal
+//! pattern rules fire for the fact completing the sequence within its time limit.
+
+use bingo_calculator::calculator::Calculator;
+use bingo_core::BingoEngine;
+use bingo_core::fact_store::arena_store::ArenaFactStore;
+use bingo_core::rete_network::ReteNetwork;
+use bingo_core::types::*;
+use std::collections::HashMap;
+
+fn account_event(id: u64, user: &str, kind: &str, timestamp: i64) -> Fact {
+    let mut fields = HashMap::new();
+    fields.insert("user".to_string(), FactValue::String(user.to_string()));
+    fields.insert("kind".to_string(), FactValue::String(kind.to_string()));
+    fields.insert("timestamp".to_string(), FactValue::Integer(timestamp));
+    Fact::new(id, FactData { fields })
+}
+
+fn is_kind(kind: &str) -> Condition {
+    Condition::Simple {
+        field: "kind".to_string(),
+        operator: Operator::Equal,
+        value: FactValue::String(kind.to_string()),
+    }
+}
+
+fn takeover_condition() -> SequenceCondition {
+    SequenceCondition {
+        steps: vec![is_kind("login_failed"), is_kind("password_changed")],
+        within_ms: 5 * 60_000,
+        correlate_by: vec!["user".to_string()],
+        alias: "takeover".to_string(),
+    }
+}
+
+fn takeover_rule() -> Rule {
+    Rule {
+        id: 1,
+        name: "Possible account takeover".to_string(),
+        conditions: vec![Condition::Sequence(takeover_condition())],
+        actions: vec![Action {
+            action_type: ActionType::SetField {
+                field: "suspicious".to_string(),
+                value: FactValue::Boolean(true),
+            },
+        }],
+        metadata: Default::default(),
+    }
+}
+
+fn process(network: &mut ReteNetwork, fact_store: &ArenaFactStore, facts: &[Fact]) -> Vec<FactId> {
+    let calculator = Calculator::new();
+    for fact in facts {
+        fact_store.insert(fact.clone());
+    }
+    let results = network.process_facts(facts, fact_store, &calculator).unwrap();
+    results.iter().map(|result| result.fact_id).collect()
+}
+
+#[test]
+fn test_sequence_condition_compiles_to_sequence_node() {
+    let mut network = ReteNetwork::new();
+    network.add_rule(takeover_rule()).unwrap();
+
+    assert_eq!(network.sequence_node_count(), 1);
+    assert!(network.sequence_node(&takeover_condition()).is_some());
+
+    network.remove_rule(1).unwrap();
+    assert_eq!(network.sequence_node_count(), 0);
+}
+
+#[test]
+fn test_sequence_fires_for_the_completing_fact_within_the_time_limit() {
+    let mut network = ReteNetwork::new();
+    let fact_store = ArenaFactStore::new();
+    network.add_rule(takeover_rule()).unwrap();
+
+    let fired = process(
+        &mut network,
+        &fact_store,
+        &[
+            account_event(1, "ann", "login_failed", 0),
+            account_event(2, "bob", "password_changed", 60_000),
+            account_event(3, "ann", "password_changed", 120_000),
+        ],
+    );
+    assert_eq!(fired, vec![3], "bob never failed a login");
+
+    let fired = process(
+        &mut network,
+        &fact_store,
+        &[account_event(4, "ann", "password_changed", 10 * 60_000)],
+    );
+    assert!(
+        fired.is_empty(),
+        "ann's failed login is more than five minutes old"
+    );
+
+    let node = network.sequence_node(&takeover_condition()).unwrap();
+    assert_eq!(node.matches_for(3), vec![&[1, 3][..]]);
+}
+
+#[test]
+fn test_steps_arriving_in_separate_batches_complete_the_sequence() {
+    let mut network = ReteNetwork::new();
+    let fact_store = ArenaFactStore::new();
+    network.add_rule(takeover_rule()).unwrap();
+
+    assert!(
+        process(
+            &mut network,
+            &fact_store,
+            &[account_event(1, "ann", "login_failed", 0)]
+        )
+        .is_empty()
+    );
+    let fired = process(
+        &mut network,
+        &fact_store,
+        &[account_event(2, "ann", "password_changed", 30_000)],
+    );
+    assert_eq!(fired, vec![2]);
+}
+
+#[test]
+fn test_retracting_a_step_withdraws_the_match() {
+    let mut network = ReteNetwork::new();
+    let fact_store = ArenaFactStore::new();
+    network.add_rule(takeover_rule()).unwrap();
+    process(
+        &mut network,
+        &fact_store,
+        &[
+            account_event(1, "ann", "login_failed", 0),
+            account_event(2, "ann", "password_changed", 30_000),
+        ],
+    );
+
+    network.retract_fact(1).unwrap();
+
+    let node = network.sequence_node(&takeover_condition()).unwrap();
+    assert!(!node.completes(2));
+    assert_eq!(node.fact_count(), 0);
+}
+
+#[test]
+fn test_sequence_conditions_round_trip_and_survive_compilation() {
+    let json = serde_json::to_value(Condition::Sequence(takeover_condition())).unwrap();
+    let condition: Condition = serde_json::from_value(json).unwrap();
+    assert_eq!(condition, Condition::Sequence(takeover_condition()));
+
+    let compiled = BingoEngine::new().unwrap();
+    compiled.add_rule(takeover_rule()).unwrap();
+    let loaded = BingoEngine::new().unwrap();
+    loaded.load_compiled(&compiled.compile_ruleset().to_bytes().unwrap()).unwrap();
+
+    let results = loaded
+        .process_facts(vec![
+            account_event(1, "ann", "login_failed", 0),
+            account_event(2, "ann", "password_changed", 30_000),
+        ])
+        .unwrap();
+    let fired: Vec<FactId> = results.iter().map(|result| result.fact_id).collect();
+    assert_eq!(fired, vec![2]);
+}
+
+#[test]
+fn test_sequence_without_steps_is_rejected() {
+    let mut rule = takeover_rule();
+    rule.conditions = vec![Condition::Sequence(SequenceCondition {
+        steps: Vec::new(),
+        ..takeover_condition()
+    })];
+
+    let mut network = ReteNetwork::new();
+    assert!(network.add_rule(rule).is_err());
+}
@@ -33,6 +33,7 @@ fn create_test_facts(count: usize) -> Vec<Fact> {
                 id: i as u64,
                 external_id: Some(format!("fact-{i}")),
                 timestamp: Utc::now(),
+                fact_type: None,
                 data: FactData { fields },
             }
         })
@@ -196,6 +197,7 @@ fn test_global_serialization_context() {
         id: 42,
         external_id: Some("global-test".to_string()),
         timestamp: Utc::now(),
+        fact_type: None,
         data: FactData {
             fields: std::iter::once((
                 "test".to_string(),
@@ -0,0 +1,172 @@
+//! Session Lifecycle Test
+//!
+//! Validates the insert/modify/retract/fire_all_rules lifecycle of `BingoSession`,
+//! its event listeners and event sinks, and `fire_until_halt` across threads.
+
+use bingo_core::types::*;
+use bingo_core::{AsyncSink, AsyncSinkConfig, BingoEngine, BingoSession, SessionEvent};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+fn create_fact(score: i64) -> Fact {
+    let mut fields = HashMap::new();
+    fields.insert("score".to_string(), FactValue::Integer(score));
+    Fact::new(0, FactData { fields })
+}
+
+fn high_score_session() -> BingoSession {
+    let engine = BingoEngine::new().unwrap();
+    engine
+        .add_rule(Rule {
+            id: 1,
+            name: "High Score".to_string(),
+            conditions: vec![Condition::Simple {
+                field: "score".to_string(),
+                operator: Operator::GreaterThan,
+                value: FactValue::Integer(90),
+            }],
+            actions: vec![Action {
+                action_type: ActionType::Log { message: "High score".to_string() },
+            }],
+            metadata: Default::default(),
+        })
+        .unwrap();
+    BingoSession::new(Arc::new(engine))
+}
+
+#[test]
+fn test_insert_is_deferred_until_fire() {
+    let session = high_score_session();
+    let first = session.insert(create_fact(95));
+    let second = session.insert(create_fact(10));
+
+    assert_ne!(first, second);
+    assert_eq!(session.pending_count(), 2);
+    assert_eq!(session.engine().fact_count(), 0);
+
+    assert_eq!(session.fire_all_rules().unwrap(), 1);
+    assert_eq!(session.pending_count(), 0);
+    assert_eq!(session.engine().fact_count(), 2);
+    assert_eq!(session.fire_all_rules().unwrap(), 0);
+    assert_eq!(session.rules_fired(), 1);
+}
+
+#[test]
+fn test_modify_rematches_fact() {
+    let session = high_score_session();
+    let handle = session.insert(create_fact(10));
+    assert_eq!(session.fire_all_rules().unwrap(), 0);
+
+    session
+        .modify(
+            handle,
+            HashMap::from([("score".to_string(), FactValue::Integer(99))]),
+        )
+        .unwrap();
+    assert_eq!(session.pending_count(), 1);
+    assert_eq!(session.fire_all_rules().unwrap(), 1);
+    assert_eq!(
+        session.get_fact(handle).unwrap().data.fields["score"],
+        FactValue::Integer(99)
+    );
+    assert_eq!(session.engine().fact_count(), 1);
+}
+
+#[test]
+fn test_retract_pending_and_matched_facts() {
+    let session = high_score_session();
+    let pending = session.insert(create_fact(95));
+    assert!(session.retract(pending).unwrap().is_empty());
+    assert_eq!(session.fire_all_rules().unwrap(), 0);
+
+    let matched = session.insert(create_fact(95));
+    session.fire_all_rules().unwrap();
+    session.retract(matched).unwrap();
+    assert!(session.get_fact(matched).is_none());
+    assert_eq!(session.engine().fact_count(), 0);
+
+    assert!(session.retract(matched).is_err());
+}
+
+#[test]
+fn test_listeners_observe_lifecycle() {
+    let session = high_score_session();
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let recorded = events.clone();
+    session.add_event_listener(move |event: &SessionEvent| {
+        let name = match event {
+            SessionEvent::FactInserted { .. } => "inserted",
+            SessionEvent::FactModified { .. } => "modified",
+            SessionEvent::FactRetracted { .. } => "retracted",
+            SessionEvent::RuleFired { .. } => "fired",
+            SessionEvent::Halted { .. } => "halted",
+        };
+        recorded.lock().unwrap().push(name);
+    });
+
+    let handle = session.insert(create_fact(95));
+    session.fire_all_rules().unwrap();
+    session
+        .modify(
+            handle,
+            HashMap::from([("score".to_string(), FactValue::Integer(5))]),
+        )
+        .unwrap();
+    session.retract(handle).unwrap();
+
+    assert_eq!(
+        *events.lock().unwrap(),
+        vec!["inserted", "fired", "modified", "retracted"]
+    );
+}
+
+#[test]
+fn test_event_sink_receives_events_off_the_firing_thread() {
+    let session = high_score_session();
+    let fired = Arc::new(Mutex::new(Vec::new()));
+    let recorded = fired.clone();
+    let sink = Arc::new(AsyncSink::new(
+        "session-events",
+        move |event: SessionEvent| {
+            if let SessionEvent::RuleFired { result } = event {
+                recorded.lock().unwrap().push(result.fact_id);
+            }
+            Ok(())
+        },
+        AsyncSinkConfig::default(),
+    ));
+    session.add_event_sink(sink.clone());
+
+    let handle = session.insert(create_fact(95));
+    session.insert(create_fact(10));
+    session.fire_all_rules().unwrap();
+
+    sink.shutdown(Duration::from_secs(5)).unwrap();
+    assert_eq!(*fired.lock().unwrap(), vec![handle.fact_id()]);
+    let stats = sink.stats();
+    assert_eq!((stats.queued, stats.delivered, stats.pending), (3, 3, 0));
+}
+
+#[test]
+fn test_fire_until_halt_processes_facts_from_other_threads() {
+    let session = Arc::new(high_score_session());
+
+    let firing = {
+        let session = session.clone();
+        thread::spawn(move || session.fire_until_halt().unwrap())
+    };
+
+    for score in [95, 20, 91] {
+        session.insert(create_fact(score));
+        thread::sleep(Duration::from_millis(5));
+    }
+    while session.pending_count() > 0 {
+        thread::sleep(Duration::from_millis(1));
+    }
+    session.halt();
+
+    assert_eq!(firing.join().unwrap(), 2);
+    assert_eq!(session.engine().fact_count(), 3);
+}
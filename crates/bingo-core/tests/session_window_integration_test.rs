@@ -73,6 +73,7 @@ fn create_fact_with_timestamp(
         id,
         external_id: Some(format!("event-{id}")),
         timestamp,
+        fact_type: None,
         data: FactData { fields },
     }
 }
@@ -103,6 +104,7 @@ fn test_stream_condition_with_session_windows() {
         actions: vec![Action {
             action_type: ActionType::Log { message: "Session with >2 events detected".to_string() },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).unwrap();
@@ -173,6 +175,7 @@ fn test_aggregation_condition_with_session_windows() {
         actions: vec![Action {
             action_type: ActionType::Log { message: "Session sum >= 3 detected".to_string() },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).unwrap();
@@ -241,6 +244,7 @@ fn test_session_window_gap_timeout_behavior() {
         actions: vec![Action {
             action_type: ActionType::Log { message: "Strict session detected".to_string() },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).unwrap();
@@ -342,6 +346,7 @@ fn test_session_window_with_filter_condition() {
         actions: vec![Action {
             action_type: ActionType::Log { message: "Session with purchases detected".to_string() },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).unwrap();
@@ -403,6 +408,7 @@ fn test_multiple_users_session_windows() {
         actions: vec![Action {
             action_type: ActionType::Log { message: "User session with >=2 events".to_string() },
         }],
+        metadata: Default::default(),
     };
 
     engine.add_rule(rule).unwrap();
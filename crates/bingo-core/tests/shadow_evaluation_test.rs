@@ -0,0 +1,112 @@
+//! Shadow Evaluation Test
+//!
+//! Validates that a candidate ruleset shadowing the active one reports the facts it would
+//! fire differently for, leaves working memory and the returned results untouched, and
+//! never dispatches its webhook actions.
+
+use bingo_core::*;
+use std::collections::HashMap;
+use std::time::Duration;
+
+fn order(id: u64, amount: i64) -> Fact {
+    let fields = HashMap::from([("amount".to_string(), FactValue::Integer(amount))]);
+    Fact::new(id, FactData { fields })
+}
+
+#[test]
+fn test_shadow_reports_divergent_rules_without_touching_live_results() {
+    let engine = BingoEngine::new().unwrap();
+    engine
+        .add_rules_from_dsl(
+            r#"
+            rule "Large order" id 1 when amount > 100 then set flagged = true
+            rule "Discount" id 2 when amount > 50 then set discount = 5
+            "#,
+        )
+        .unwrap();
+    let candidate = parse_rules(
+        r#"
+        rule "Large order" id 1 when amount > 200 then set flagged = true
+        rule "Discount" id 2 when amount > 50 then set discount = 10
+        rule "Review" id 3 when amount > 1000 then set review = true
+        "#,
+    )
+    .unwrap();
+    engine.start_shadow(candidate).unwrap();
+
+    let results = engine.process_facts(vec![order(1, 150), order(2, 5000), order(3, 10)]).unwrap();
+    assert_eq!(results.len(), 4);
+    assert!(results.iter().all(|result| result.rule_id != 3));
+
+    let report = engine.shadow_report().unwrap();
+    assert_eq!((report.batches, report.facts), (1, 3));
+    assert_eq!(
+        (report.live_activations, report.candidate_activations),
+        (4, 4)
+    );
+    assert_eq!(report.divergent_facts, 2);
+    assert_eq!(
+        report.divergences[0],
+        ShadowDivergence {
+            batch_id: 1,
+            fact_id: 1,
+            live_only: vec![1],
+            candidate_only: vec![],
+            changed: vec![2],
+        }
+    );
+    assert_eq!(report.divergences[1].candidate_only, vec![3]);
+    assert_eq!(
+        report.rules[&2],
+        RuleDivergence { live_only: 0, candidate_only: 0, changed: 2 }
+    );
+    assert!(!report.is_clean());
+
+    let stopped = engine.stop_shadow().unwrap();
+    assert_eq!(stopped.divergent_facts, 2);
+    assert!(engine.shadow_report().is_none());
+    assert_eq!(engine.get_rule(1).unwrap().name, "Large order");
+}
+
+#[test]
+fn test_shadow_candidate_does_not_dispatch_webhooks() {
+    let engine = BingoEngine::new().unwrap();
+    let (sender, received) = crossbeam::channel::unbounded();
+    engine.register_webhook("orders", WebhookTarget::Channel(sender)).unwrap();
+    engine
+        .add_rules_from_dsl(r#"rule "Large order" id 1 when amount > 100 then set flagged = true"#)
+        .unwrap();
+
+    let mut candidate =
+        parse_rules(r#"rule "Large order" id 1 when amount > 100 then set flagged = true"#)
+            .unwrap();
+    candidate[0].actions.push(Action {
+        action_type: ActionType::CallWebhook {
+            endpoint: "orders".to_string(),
+            metadata: HashMap::new(),
+        },
+    });
+    engine.start_shadow(candidate).unwrap();
+    engine.process_facts(vec![order(1, 500)]).unwrap();
+
+    let report = engine.shadow_report().unwrap();
+    assert_eq!(report.rules[&1].changed, 1);
+    assert!(received.recv_timeout(Duration::from_millis(200)).is_err());
+    assert_eq!(engine.webhooks().stats().queued, 0);
+}
+
+#[test]
+fn test_identical_candidate_reports_no_divergence() {
+    let engine = BingoEngine::new().unwrap();
+    let rules = r#"rule "Large order" id 1 when amount > 100 then set flagged = true"#;
+    engine.add_rules_from_dsl(rules).unwrap();
+    engine.process_facts(vec![order(1, 500)]).unwrap();
+
+    engine.start_shadow(parse_rules(rules).unwrap()).unwrap();
+    engine.process_facts(vec![order(2, 500), order(3, 50)]).unwrap();
+
+    let report = engine.shadow_report().unwrap();
+    assert!(report.is_clean());
+    assert_eq!(report.live_activations, 1);
+    assert!(report.divergences.is_empty());
+}
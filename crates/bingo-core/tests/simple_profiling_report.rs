@@ -273,6 +273,7 @@ fn create_test_facts(count: usize) -> Vec<Fact> {
             id: i as u64,
             external_id: Some(format!("test_{i}")),
             timestamp: Utc::now(),
+            fact_type: None,
             data: FactData { fields },
         });
     }
@@ -295,5 +296,6 @@ fn create_test_rule(id: usize) -> Rule {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     }
 }
@@ -144,6 +144,7 @@ fn generate_simple_rules(count: usize) -> Vec<Rule> {
                     },
                 },
             }],
+            metadata: Default::default(),
         })
         .collect()
 }
@@ -156,6 +157,7 @@ fn generate_simple_facts(count: usize, start_id: usize) -> Vec<Fact> {
                 id: fact_id as u64,
                 external_id: Some(format!("ext_{fact_id}")),
                 timestamp: Utc::now(),
+                fact_type: None,
                 data: FactData {
                     fields: HashMap::from([
                         ("type".to_string(), FactValue::String("test".to_string())),
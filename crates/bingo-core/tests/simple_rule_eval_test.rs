@@ -18,6 +18,7 @@ fn simple_greater_than_rule_fires() {
         actions: vec![Action {
             action_type: ActionType::DeleteFact { fact_id_field: "hours_worked".to_string() },
         }],
+        metadata: Default::default(),
     };
     engine.add_rule(rule).unwrap();
 
@@ -21,6 +21,7 @@ fn test_simplified_api_workflow() {
         actions: vec![Action {
             action_type: ActionType::Log { message: "Hours exceeded!".to_string() },
         }],
+        metadata: Default::default(),
     };
 
     // ✅ YOUR API: Define facts
@@ -32,6 +33,7 @@ fn test_simplified_api_workflow() {
         id: 1,
         external_id: None,
         timestamp: chrono::Utc::now(),
+        fact_type: None,
         data: FactData { fields: fact_fields },
     };
 
@@ -0,0 +1,69 @@
+//! Standard Performance Scenario Tests
+//!
+//! Runs the named workloads from `test_utils::StandardScenario` that the performance
+//! documentation quotes. The smoke test checks every scenario's rules and fact stream on
+//! a small slice of facts; the full runs assert the machine-adjusted thresholds.
+//!
+//! IMPORTANT: The full runs are ignored by default and MUST run in release mode:
+//! `cargo test --release -p bingo-core --test standard_scenarios_test -- --ignored`
+
+use bingo_core::performance_config::PerformanceConfig;
+use bingo_core::test_utils::StandardScenario;
+use bingo_core::*;
+
+#[test]
+fn test_standard_scenarios_fire_on_a_small_slice() {
+    for scenario in StandardScenario::ALL {
+        assert_eq!(StandardScenario::from_name(scenario.name()), Some(scenario));
+
+        let facts = scenario.facts(2_000).unwrap();
+        assert_eq!(facts.len(), 2_000, "{}", scenario.name());
+        assert_eq!(scenario.facts(2_000).unwrap()[0].data, facts[0].data);
+
+        let engine = BingoEngine::new().unwrap();
+        engine.add_rules(scenario.rules()).unwrap();
+        let results = engine.process_facts(facts).unwrap();
+        assert!(!results.is_empty(), "{} fired no rules", scenario.name());
+    }
+}
+
+fn assert_scenario_passes(scenario: StandardScenario) {
+    let config = PerformanceConfig::detect_environment();
+    let report = scenario.run(&config).unwrap();
+    println!(
+        "{}: {} facts, {} rules, {} fired, load {:?}, processing {:?} ({:.0} facts/sec), {} bytes ({})",
+        scenario.name(),
+        report.facts,
+        report.rules,
+        report.rules_fired,
+        report.rule_load_time,
+        report.processing_time,
+        report.throughput(),
+        report.memory_bytes,
+        config.description()
+    );
+    assert!(
+        report.passed(),
+        "{} exceeded its thresholds: {}",
+        scenario.name(),
+        report.violations().join("; ")
+    );
+}
+
+#[test]
+#[ignore] // Performance test - run with --release: cargo test --release -- --ignored
+fn test_1m_facts_100_rules_scenario() {
+    assert_scenario_passes(StandardScenario::MillionFactsHundredRules);
+}
+
+#[test]
+#[ignore] // Performance test - run with --release: cargo test --release -- --ignored
+fn test_10k_facts_10k_rules_scenario() {
+    assert_scenario_passes(StandardScenario::TenThousandFactsTenThousandRules);
+}
+
+#[test]
+#[ignore] // Performance test - run with --release: cargo test --release -- --ignored
+fn test_high_cardinality_join_scenario() {
+    assert_scenario_passes(StandardScenario::HighCardinalityJoin);
+}
@@ -0,0 +1,175 @@
+//! Stream Window Integration Test
+//!
+//! Validates that stream conditions compile to window nodes and that temporal rules
+//! fire once the windows holding a fact satisfy the condition's having clause.
+
+use bingo_calculator::calculator::Calculator;
+use bingo_core::fact_store::arena_store::ArenaFactStore;
+use bingo_core::rete_network::ReteNetwork;
+use bingo_core::types::*;
+use std::collections::HashMap;
+
+fn create_login(id: u64, user: &str, status: &str, timestamp: i64) -> Fact {
+    let mut fields = HashMap::new();
+    fields.insert("user".to_string(), FactValue::String(user.to_string()));
+    fields.insert("status".to_string(), FactValue::String(status.to_string()));
+    fields.insert("timestamp".to_string(), FactValue::Integer(timestamp));
+    Fact::new(id, FactData { fields })
+}
+
+fn failed_logins_condition(window_spec: StreamWindowSpec) -> StreamCondition {
+    StreamCondition {
+        window_spec,
+        aggregation: StreamAggregation::Count,
+        filter: Some(Box::new(Condition::Simple {
+            field: "status".to_string(),
+            operator: Operator::Equal,
+            value: FactValue::String("failed".to_string()),
+        })),
+        having: Some(Box::new(Condition::Simple {
+            field: "failures".to_string(),
+            operator: Operator::GreaterThanOrEqual,
+            value: FactValue::Integer(3),
+        })),
+        alias: "failures".to_string(),
+    }
+}
+
+fn lockout_rule(window_spec: StreamWindowSpec) -> Rule {
+    Rule {
+        id: 1,
+        name: "Brute force lockout".to_string(),
+        conditions: vec![Condition::Stream(failed_logins_condition(window_spec))],
+        actions: vec![Action {
+            action_type: ActionType::SetField {
+                field: "locked".to_string(),
+                value: FactValue::Boolean(true),
+            },
+        }],
+        metadata: Default::default(),
+    }
+}
+
+fn process(network: &mut ReteNetwork, fact_store: &ArenaFactStore, facts: &[Fact]) -> Vec<FactId> {
+    let calculator = Calculator::new();
+    for fact in facts {
+        fact_store.insert(fact.clone());
+    }
+    let results = network.process_facts(facts, fact_store, &calculator).unwrap();
+    results.iter().map(|result| result.fact_id).collect()
+}
+
+#[test]
+fn test_stream_condition_compiles_to_window_node() {
+    let spec = StreamWindowSpec::Tumbling { duration_ms: 60_000 };
+    let mut network = ReteNetwork::new();
+    network.add_rule(lockout_rule(spec.clone())).unwrap();
+
+    assert_eq!(network.window_node_count(), 1);
+    assert!(network.window_node(&failed_logins_condition(spec)).is_some());
+
+    network.remove_rule(1).unwrap();
+    assert_eq!(network.window_node_count(), 0);
+}
+
+#[test]
+fn test_tumbling_window_rule_fires_on_third_failure() {
+    let spec = StreamWindowSpec::Tumbling { duration_ms: 60_000 };
+    let mut network = ReteNetwork::new();
+    let fact_store = ArenaFactStore::new();
+    network.add_rule(lockout_rule(spec.clone())).unwrap();
+
+    let fired = process(
+        &mut network,
+        &fact_store,
+        &[
+            create_login(1, "alice", "failed", 1_000),
+            create_login(2, "alice", "success", 2_000),
+            create_login(3, "alice", "failed", 3_000),
+        ],
+    );
+    assert!(fired.is_empty());
+
+    // The third failure in the same minute trips the rule; one in the next minute does not
+    let fired = process(
+        &mut network,
+        &fact_store,
+        &[
+            create_login(4, "alice", "failed", 4_000),
+            create_login(5, "alice", "failed", 61_000),
+        ],
+    );
+    assert_eq!(fired, vec![4]);
+
+    let node = network.window_node(&failed_logins_condition(spec)).unwrap();
+    assert_eq!(node.window_count(), 2);
+    assert_eq!(node.fact_count(), 4);
+}
+
+#[test]
+fn test_session_window_retraction_withdraws_fact() {
+    let spec = StreamWindowSpec::Session { gap_timeout_ms: 5_000 };
+    let mut network = ReteNetwork::new();
+    let fact_store = ArenaFactStore::new();
+    network.add_rule(lockout_rule(spec.clone())).unwrap();
+
+    let fired = process(
+        &mut network,
+        &fact_store,
+        &[
+            create_login(1, "bob", "failed", 0),
+            create_login(2, "bob", "failed", 4_000),
+            create_login(3, "bob", "failed", 8_000),
+        ],
+    );
+    assert_eq!(fired, vec![1, 2, 3]);
+
+    // Retracting the middle failure splits the session in two
+    let affected = network.remove_fact_from_working_memory(2).unwrap();
+    assert_eq!(affected, vec![1]);
+
+    let node = network.window_node(&failed_logins_condition(spec)).unwrap();
+    assert_eq!(node.window_count(), 2);
+    assert_eq!(node.values_for(3), vec![&FactValue::Integer(1)]);
+}
+
+#[test]
+fn test_calendar_window_aligns_to_rule_timezone() {
+    // 2024-01-02 07:00 UTC is 23:00 on Jan 1 in Los Angeles; 09:00 UTC is Jan 2
+    let jan_2_utc_7am = 1_704_178_800_000;
+    let hour = 3_600_000;
+    let spec = StreamWindowSpec::Calendar {
+        period: CalendarPeriod::Daily,
+        timezone: "America/Los_Angeles".to_string(),
+    };
+    let mut network = ReteNetwork::new();
+    let fact_store = ArenaFactStore::new();
+    network.add_rule(lockout_rule(spec.clone())).unwrap();
+
+    let fired = process(
+        &mut network,
+        &fact_store,
+        &[
+            create_login(1, "carol", "failed", jan_2_utc_7am),
+            create_login(2, "carol", "failed", jan_2_utc_7am + 2 * hour),
+            create_login(3, "carol", "failed", jan_2_utc_7am + 3 * hour),
+        ],
+    );
+    // A UTC day would hold all three failures; the local day boundary splits them
+    assert!(fired.is_empty());
+
+    let node = network.window_node(&failed_logins_condition(spec)).unwrap();
+    assert_eq!(node.window_count(), 2);
+    assert_eq!(node.values_for(3), vec![&FactValue::Integer(2)]);
+}
+
+#[test]
+fn test_calendar_window_rejects_unknown_timezone() {
+    let spec = StreamWindowSpec::Calendar {
+        period: CalendarPeriod::Weekly { week_start: chrono::Weekday::Mon },
+        timezone: "Nowhere/Special".to_string(),
+    };
+    let mut network = ReteNetwork::new();
+    assert!(network.add_rule(lockout_rule(spec)).is_err());
+    assert_eq!(network.window_node_count(), 0);
+}
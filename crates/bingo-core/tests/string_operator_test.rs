@@ -0,0 +1,120 @@
+//! String Operator Test
+//!
+//! Validates the `Matches`, `ContainsIgnoreCase`, `StartsWith` and `EndsWith`
+//! operators on single and multi-condition rules, and that invalid regexes are
+//! rejected when the rule is added rather than silently never matching.
+
+use bingo_core::types::*;
+use bingo_core::{BingoEngine, parse_rule};
+use std::collections::HashMap;
+
+fn fact(id: u64, fields: &[(&str, &str)]) -> Fact {
+    let fields: HashMap<String, FactValue> = fields
+        .iter()
+        .map(|(field, value)| (field.to_string(), FactValue::String(value.to_string())))
+        .collect();
+    Fact::new(id, FactData { fields })
+}
+
+fn matched_facts(engine: &BingoEngine, facts: Vec<Fact>) -> Vec<u64> {
+    let mut fact_ids: Vec<u64> = engine
+        .process_facts(facts)
+        .unwrap()
+        .iter()
+        .map(|result| result.fact_id)
+        .collect();
+    fact_ids.sort_unstable();
+    fact_ids
+}
+
+#[test]
+fn test_matches_regex() {
+    let engine = BingoEngine::new().unwrap();
+    engine
+        .add_rule(
+            parse_rule(
+                r#"rule "Order number" id 1 when order_id matches "^ORD-[0-9]{6}$" then log "ok""#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+    let facts = vec![
+        fact(1, &[("order_id", "ORD-004211")]),
+        fact(2, &[("order_id", "ord-004211")]),
+        fact(3, &[("order_id", "ORD-42")]),
+        fact(4, &[("customer_id", "ORD-004211")]),
+    ];
+    assert_eq!(matched_facts(&engine, facts), vec![1]);
+}
+
+#[test]
+fn test_contains_ignore_case_in_multi_condition_rule() {
+    let engine = BingoEngine::new().unwrap();
+    engine
+        .add_rule(
+            parse_rule(
+                r#"rule "Declines" id 1
+                   when message contains_ignore_case "declined" and country starts_with "D"
+                   then log "decline""#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+    let facts = vec![
+        fact(
+            1,
+            &[("message", "Card DECLINED by issuer"), ("country", "DE")],
+        ),
+        fact(2, &[("message", "card declined"), ("country", "FR")]),
+        fact(3, &[("message", "approved"), ("country", "DK")]),
+    ];
+    assert_eq!(matched_facts(&engine, facts), vec![1]);
+}
+
+#[test]
+fn test_single_condition_string_operators() {
+    let engine = BingoEngine::new().unwrap();
+    engine
+        .add_rule(
+            parse_rule(r#"rule "Prefix" id 1 when sku starts_with "EU-" then log "eu""#).unwrap(),
+        )
+        .unwrap();
+    engine
+        .add_rule(
+            parse_rule(r#"rule "Suffix" id 2 when sku ends_with "-XL" then log "xl""#).unwrap(),
+        )
+        .unwrap();
+
+    let results = engine
+        .process_facts(vec![
+            fact(1, &[("sku", "EU-SHIRT-XL")]),
+            fact(2, &[("sku", "US-SHIRT-M")]),
+        ])
+        .unwrap();
+    let mut fired: Vec<(u64, u64)> =
+        results.iter().map(|result| (result.rule_id, result.fact_id)).collect();
+    fired.sort_unstable();
+    assert_eq!(fired, vec![(1, 1), (2, 1)]);
+}
+
+#[test]
+fn test_invalid_regex_rejects_rule() {
+    let engine = BingoEngine::new().unwrap();
+    let rule = Rule {
+        id: 1,
+        name: "Broken".to_string(),
+        conditions: vec![Condition::Simple {
+            field: "sku".to_string(),
+            operator: Operator::Matches,
+            value: FactValue::String("EU-(".to_string()),
+        }],
+        actions: vec![Action { action_type: ActionType::Log { message: "never".to_string() } }],
+        metadata: Default::default(),
+    };
+
+    let error = engine.add_rule(rule).unwrap_err();
+    assert!(format!("{error:#}").contains("Invalid regex"), "{error:#}");
+    assert_eq!(engine.get_stats().rule_count, 0);
+}
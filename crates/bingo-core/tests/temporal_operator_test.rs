@@ -0,0 +1,223 @@
+//! Temporal Operator Test
+//!
+//! Validates that `Duration` and `Interval` values compare, combine in formulas and
+//! match the `Within`, `OlderThan` and `Overlaps` operators, so time-based compliance
+//! rules can be written without encoding times as raw integers.
+
+use bingo_core::rete_nodes::evaluate_formula_expression;
+use bingo_core::types::*;
+use bingo_core::{BingoEngine, parse_rule};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+
+fn instant(text: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(text).unwrap().with_timezone(&Utc)
+}
+
+fn fact(id: u64, field: &str, value: FactValue) -> Fact {
+    let mut fields = HashMap::new();
+    fields.insert(field.to_string(), value);
+    Fact::new(id, FactData { fields })
+}
+
+fn log_rule(id: u64, field: &str, operator: Operator, value: FactValue) -> Rule {
+    Rule {
+        id,
+        name: format!("Temporal rule {id}"),
+        conditions: vec![Condition::Simple { field: field.to_string(), operator, value }],
+        actions: vec![Action { action_type: ActionType::Log { message: "matched".to_string() } }],
+        metadata: Default::default(),
+    }
+}
+
+fn matched_facts(rule: Rule, facts: Vec<Fact>) -> Vec<u64> {
+    let engine = BingoEngine::new().unwrap();
+    engine.add_rule(rule).unwrap();
+    let mut fact_ids: Vec<u64> = engine
+        .process_facts(facts)
+        .unwrap()
+        .iter()
+        .map(|result| result.fact_id)
+        .collect();
+    fact_ids.sort_unstable();
+    fact_ids
+}
+
+#[test]
+fn test_break_shorter_than_thirty_minutes() {
+    let rule = parse_rule(
+        r#"rule "Short break" id 1 when shift.break < duration "PT30M" then log "Break too short""#,
+    )
+    .unwrap();
+
+    let breaks = vec![
+        fact(1, "shift.break", FactValue::Duration(Duration::minutes(15))),
+        fact(2, "shift.break", FactValue::Duration(Duration::minutes(30))),
+        fact(
+            3,
+            "shift.break",
+            FactValue::duration_from_iso("PT45M").unwrap(),
+        ),
+    ];
+    assert_eq!(matched_facts(rule, breaks), vec![1]);
+}
+
+#[test]
+fn test_within_and_older_than_are_relative_to_now() {
+    let now = Utc::now();
+    let events = || {
+        vec![
+            fact(1, "last_login", FactValue::Date(now - Duration::minutes(5))),
+            fact(2, "last_login", FactValue::Date(now - Duration::hours(2))),
+            fact(3, "last_login", FactValue::Date(now - Duration::days(10))),
+        ]
+    };
+
+    let within_hour = log_rule(
+        1,
+        "last_login",
+        Operator::Within,
+        FactValue::Duration(Duration::hours(1)),
+    );
+    assert_eq!(matched_facts(within_hour, events()), vec![1]);
+
+    let older_than_week = log_rule(
+        2,
+        "last_login",
+        Operator::OlderThan,
+        FactValue::Duration(Duration::weeks(1)),
+    );
+    assert_eq!(matched_facts(older_than_week, events()), vec![3]);
+}
+
+#[test]
+fn test_overlaps_and_within_intervals() {
+    let shift = FactValue::interval(
+        instant("2025-03-01T09:00:00Z"),
+        instant("2025-03-01T17:00:00Z"),
+    );
+    let bookings = || {
+        vec![
+            fact(
+                1,
+                "booking",
+                FactValue::interval(
+                    instant("2025-03-01T16:00:00Z"),
+                    instant("2025-03-01T18:00:00Z"),
+                ),
+            ),
+            // Touching the end of the shift does not overlap it
+            fact(
+                2,
+                "booking",
+                FactValue::interval(
+                    instant("2025-03-01T17:00:00Z"),
+                    instant("2025-03-01T19:00:00Z"),
+                ),
+            ),
+            fact(
+                3,
+                "booking",
+                FactValue::interval(
+                    instant("2025-03-01T10:00:00Z"),
+                    instant("2025-03-01T11:00:00Z"),
+                ),
+            ),
+        ]
+    };
+
+    let overlapping = log_rule(1, "booking", Operator::Overlaps, shift.clone());
+    assert_eq!(matched_facts(overlapping, bookings()), vec![1, 3]);
+
+    let inside = log_rule(2, "booking", Operator::Within, shift.clone());
+    assert_eq!(matched_facts(inside, bookings()), vec![3]);
+
+    let clock_ins = vec![
+        fact(
+            1,
+            "clock_in",
+            FactValue::Date(instant("2025-03-01T09:00:00Z")),
+        ),
+        fact(
+            2,
+            "clock_in",
+            FactValue::Date(instant("2025-03-01T17:00:00Z")),
+        ),
+    ];
+    assert_eq!(
+        matched_facts(log_rule(3, "clock_in", Operator::Within, shift), clock_ins),
+        vec![1]
+    );
+}
+
+#[test]
+fn test_formula_date_and_duration_arithmetic() {
+    let mut fields = HashMap::new();
+    fields.insert(
+        "start".to_string(),
+        FactValue::Date(instant("2025-03-01T09:00:00Z")),
+    );
+    fields.insert(
+        "end".to_string(),
+        FactValue::Date(instant("2025-03-01T17:30:00Z")),
+    );
+    fields.insert(
+        "break".to_string(),
+        FactValue::Duration(Duration::minutes(30)),
+    );
+
+    assert_eq!(
+        evaluate_formula_expression("end - start", &fields).unwrap(),
+        FactValue::duration_from_iso("PT8H30M").unwrap()
+    );
+    assert_eq!(
+        evaluate_formula_expression("start + break", &fields).unwrap(),
+        FactValue::Date(instant("2025-03-01T09:30:00Z"))
+    );
+    assert_eq!(
+        evaluate_formula_expression("break * 3", &fields).unwrap(),
+        FactValue::Duration(Duration::minutes(90))
+    );
+    assert!(evaluate_formula_expression("break / 0", &fields).is_err());
+    assert!(evaluate_formula_expression("start * 2", &fields).is_err());
+}
+
+#[test]
+fn test_dsl_temporal_literals() {
+    let rule = parse_rule(
+        r#"rule "Late" when booking overlaps interval "2025-03-01T17:00:00Z/2025-03-01T09:00:00Z" then log "late""#,
+    )
+    .unwrap();
+    assert_eq!(
+        rule.conditions[0],
+        Condition::Simple {
+            field: "booking".to_string(),
+            operator: Operator::Overlaps,
+            value: FactValue::interval(
+                instant("2025-03-01T09:00:00Z"),
+                instant("2025-03-01T17:00:00Z"),
+            ),
+        }
+    );
+
+    let error = parse_rule(r#"rule "Bad" when gap within duration "P1M" then log "x""#)
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("no fixed length"), "{error}");
+}
+
+#[test]
+fn test_duration_serialization_round_trip() {
+    let value = FactValue::Duration(Duration::minutes(90));
+
+    let serialized = serde_json::to_string(&value).unwrap();
+    assert_eq!(serialized, r#"{"Duration":"PT1H30M"}"#);
+    assert_eq!(
+        serde_json::from_str::<FactValue>(&serialized).unwrap(),
+        value
+    );
+    assert_eq!(
+        serde_json::Value::from(&value),
+        serde_json::json!("PT1H30M")
+    );
+}
@@ -16,6 +16,7 @@ fn test_rule() {
         actions: vec![Action {
             action_type: ActionType::DeleteFact { fact_id_field: "hours_worked".to_string() },
         }],
+        metadata: Default::default(),
     };
     engine.add_rule(rule).unwrap();
     let fact = Fact::new(
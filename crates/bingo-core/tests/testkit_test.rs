@@ -0,0 +1,112 @@
+//! Rule Test Kit Test
+//!
+//! Validates the given/when/then assertions of the rule test kit, including the
+//! messages of failing assertions, and the coverage report of rules never fired and
+//! conditions never satisfied across a suite of rule tests.
+
+use bingo_core::*;
+use std::collections::HashMap;
+
+fn customer(id: u64, spend: f64) -> Fact {
+    Fact::new(
+        id,
+        FactData { fields: HashMap::from([("spend".to_string(), spend.into())]) },
+    )
+}
+
+fn tier_rule(id: u64, name: &str, threshold: f64, tier: &str) -> Rule {
+    Rule {
+        id,
+        name: name.to_string(),
+        conditions: vec![Condition::Simple {
+            field: "spend".to_string(),
+            operator: Operator::GreaterThan,
+            value: FactValue::Float(threshold),
+        }],
+        actions: vec![Action {
+            action_type: ActionType::SetField {
+                field: "customer_tier".to_string(),
+                value: tier.into(),
+            },
+        }],
+        metadata: Default::default(),
+    }
+}
+
+fn rules() -> Vec<Rule> {
+    vec![
+        tier_rule(1, "Premium customer", 1000.0, "premium"),
+        tier_rule(2, "Gold customer", 5000.0, "gold"),
+    ]
+}
+
+#[test]
+fn test_given_when_then_assertions() {
+    RuleTest::given_facts(vec![
+        customer(101, 1500.0),
+        customer(102, 2500.0),
+        customer(103, 10.0),
+    ])
+    .when_rules(rules())
+    .then_fired("Premium customer")
+    .times(2)
+    .for_fact(102)
+    .with_field("customer_tier", "premium")
+    .then_not_fired("Gold customer");
+
+    RuleTest::given_facts(vec![customer(101, 10.0)])
+        .when_rules(rules())
+        .then_nothing_fired();
+}
+
+#[test]
+fn test_failed_assertions_describe_what_fired() {
+    let outcome = RuleTest::given_facts(vec![customer(101, 1500.0)]).when_rules(rules());
+
+    let panic = std::panic::catch_unwind(|| {
+        outcome.then_fired("Gold customer");
+    })
+    .unwrap_err();
+    assert_eq!(
+        panic.downcast_ref::<String>().unwrap(),
+        "expected rule 'Gold customer' to fire, but fired rules were 'Premium customer' x1"
+    );
+
+    let panic = std::panic::catch_unwind(|| {
+        outcome.then_fired("Premium customer").with_field("customer_tier", "gold");
+    })
+    .unwrap_err();
+    assert_eq!(
+        panic.downcast_ref::<String>().unwrap(),
+        "expected rule 'Premium customer' to set 'customer_tier' to gold, but it set it to [premium]"
+    );
+
+    assert!(std::panic::catch_unwind(|| outcome.then_fired("Silver customer")).is_err());
+}
+
+#[test]
+fn test_coverage_reports_unexercised_rules_and_conditions() {
+    let mut coverage = RuleCoverage::new();
+    RuleTest::given_facts(vec![customer(101, 1500.0)])
+        .when_rules(rules())
+        .record(&mut coverage)
+        .then_fired("Premium customer");
+    RuleTest::given_facts(vec![customer(101, 20.0)])
+        .when_rules(rules())
+        .record(&mut coverage);
+
+    let report = coverage.report();
+    assert!(!report.is_complete());
+    assert_eq!(report.unfired_rules, vec![(2, "Gold customer".to_string())]);
+    assert_eq!(report.unmatched_conditions.len(), 1);
+    assert_eq!(report.unmatched_conditions[0].rule_id, 2);
+    assert_eq!(report.rule_coverage(), 0.5);
+    assert!(report.to_string().contains("never fired: rule 2 (Gold customer)"));
+
+    RuleTest::given_facts(vec![customer(101, 7500.0)])
+        .when_rules(rules())
+        .record(&mut coverage);
+    let report = coverage.report();
+    assert!(report.is_complete(), "{report}");
+    assert_eq!(report.condition_coverage(), 1.0);
+}
@@ -385,6 +385,7 @@ fn create_test_rule(id: u64, name: &str) -> Rule {
         actions: vec![Action {
             action_type: ActionType::CreateFact { data: FactData { fields: result_fields } },
         }],
+        metadata: Default::default(),
     }
 }
 
@@ -397,6 +398,7 @@ fn create_test_fact(id: u64, value: f64) -> Fact {
         id,
         external_id: Some(format!("test_fact_{id}")),
         timestamp: chrono::Utc::now(),
+        fact_type: None,
         data: FactData { fields },
     }
 }
@@ -0,0 +1,219 @@
+//! Transaction Test
+//!
+//! Validates that a transaction's inserts and retracts leave working memory untouched
+//! until commit, and that a commit failing on a change or on its check rolls back the
+//! changes already applied along with their webhook actions and replication records.
+
+use bingo_core::types::*;
+use bingo_core::{
+    BingoEngine, BingoError, EngineConfig, FactHistoryConfig, FactQuery, ReplicationConfig,
+    ReplicationEntry, WarmStandby, WebhookTarget,
+};
+use crossbeam::channel::Receiver;
+use std::collections::HashMap;
+use std::time::Duration;
+
+fn order(id: FactId, amount: i64) -> Fact {
+    let fields = HashMap::from([("amount".to_string(), FactValue::Integer(amount))]);
+    Fact::new(id, FactData { fields })
+}
+
+fn large_order_rule() -> Rule {
+    Rule {
+        id: 1,
+        name: "Large order".to_string(),
+        conditions: vec![Condition::Simple {
+            field: "amount".to_string(),
+            operator: Operator::GreaterThan,
+            value: FactValue::Integer(1000),
+        }],
+        actions: vec![Action {
+            action_type: ActionType::CallWebhook {
+                endpoint: "orders".to_string(),
+                metadata: HashMap::new(),
+            },
+        }],
+        metadata: Default::default(),
+    }
+}
+
+/// Standby replicating every change of `engine`, with the webhook endpoint its rule
+/// calls
+fn standby_of(engine: &BingoEngine) -> (WarmStandby, Receiver<ReplicationEntry>) {
+    let (sender, entries) = crossbeam::channel::unbounded();
+    let config = ReplicationConfig { digest_interval: Duration::ZERO };
+    let snapshot = engine.start_replication(config, sender).unwrap();
+    let standby_engine = BingoEngine::new().unwrap();
+    let (webhooks, _) = crossbeam::channel::unbounded();
+    standby_engine
+        .register_webhook("orders", WebhookTarget::Channel(webhooks))
+        .unwrap();
+    (
+        WarmStandby::with_engine(standby_engine, &snapshot).unwrap(),
+        entries,
+    )
+}
+
+/// Apply the entries streamed so far, returning how many there were
+fn catch_up(standby: &mut WarmStandby, entries: &Receiver<ReplicationEntry>) -> usize {
+    let streamed: Vec<ReplicationEntry> = entries.try_iter().collect();
+    for entry in &streamed {
+        standby.apply(entry).unwrap();
+    }
+    streamed.len()
+}
+
+#[test]
+fn test_commit_applies_staged_changes_together() {
+    let engine = BingoEngine::new().unwrap();
+    let (sender, receiver) = crossbeam::channel::unbounded();
+    engine.register_webhook("orders", WebhookTarget::Channel(sender)).unwrap();
+    engine.add_rule(large_order_rule()).unwrap();
+    engine.process_facts(vec![order(1, 10)]).unwrap();
+    let (mut standby, entries) = standby_of(&engine);
+
+    let mut transaction = engine.begin_transaction();
+    transaction.insert(order(2, 5000)).insert(order(3, 20)).retract(1);
+    assert_eq!(transaction.len(), 3);
+    assert_eq!(engine.fact_count(), 1);
+    assert_eq!(catch_up(&mut standby, &entries), 0);
+
+    let outcome = transaction.commit().unwrap();
+    assert_eq!(outcome.results.len(), 1);
+    assert_eq!(outcome.results[0].fact_id, 2);
+    assert_eq!(outcome.retractions.len(), 1);
+    assert_eq!(engine.fact_count(), 2);
+    let payload = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert_eq!(payload.fact.id, 2);
+
+    // Held records are streamed in order once the commit applies
+    assert!(catch_up(&mut standby, &entries) > 0);
+    assert_eq!(standby.divergence(), None);
+    assert_eq!(standby.engine().state_digest(), engine.state_digest());
+}
+
+#[test]
+fn test_failed_commit_rolls_back_applied_changes() {
+    let engine = BingoEngine::new().unwrap();
+    let (sender, receiver) = crossbeam::channel::unbounded();
+    engine.register_webhook("orders", WebhookTarget::Channel(sender)).unwrap();
+    engine.add_rule(large_order_rule()).unwrap();
+    engine
+        .set_config(EngineConfig { max_working_memory_facts: Some(3), ..EngineConfig::default() })
+        .unwrap();
+    engine.process_facts(vec![order(1, 10), order(2, 20)]).unwrap();
+    let digest = engine.state_digest();
+    let (mut standby, entries) = standby_of(&engine);
+
+    // The large order fires before the last insert exceeds the fact limit
+    let mut transaction = engine.begin_transaction();
+    transaction
+        .insert(order(3, 5000))
+        .retract(1)
+        .insert_facts(vec![order(4, 1), order(5, 1)]);
+    let error = transaction.commit().unwrap_err();
+    assert!(matches!(error, BingoError::CapacityExceeded { .. }));
+    assert_eq!(engine.state_digest(), digest);
+    assert!(engine.get_fact(1).is_some());
+    assert!(engine.get_fact(3).is_none());
+
+    // A check rejecting the outcome rolls back too
+    let mut transaction = engine.begin_transaction();
+    transaction.insert(order(6, 7000));
+    let rejected = transaction.commit_with(|outcome| {
+        assert_eq!(outcome.results.len(), 1);
+        Err(BingoError::external_service("ledger", "posting failed"))
+    });
+    assert!(rejected.is_err());
+    assert_eq!(engine.state_digest(), digest);
+
+    // Neither rolled back commit dispatched webhooks or streamed records
+    assert!(receiver.recv_timeout(Duration::from_millis(100)).is_err());
+    assert_eq!(catch_up(&mut standby, &entries), 0);
+
+    let mut transaction = engine.begin_transaction();
+    transaction.insert(order(7, 9000));
+    transaction.rollback();
+    assert_eq!(engine.fact_count(), 2);
+
+    // Rules fire as before once the engine is restored
+    let mut transaction = engine.begin_transaction();
+    transaction.insert(order(8, 9000));
+    assert_eq!(transaction.commit().unwrap().results.len(), 1);
+    assert_eq!(
+        receiver.recv_timeout(Duration::from_secs(5)).unwrap().fact.id,
+        8
+    );
+    assert!(catch_up(&mut standby, &entries) > 0);
+    assert_eq!(standby.engine().state_digest(), engine.state_digest());
+}
+
+/// Flags the order that takes the total of all orders over 100
+fn order_total_rule() -> Rule {
+    Rule {
+        id: 2,
+        name: "Order total".to_string(),
+        conditions: vec![Condition::Aggregation(AggregationCondition {
+            aggregation_type: AggregationType::Sum,
+            source_field: "amount".to_string(),
+            group_by: vec![],
+            having: Some(Box::new(Condition::Simple {
+                field: "total".to_string(),
+                operator: Operator::GreaterThan,
+                value: FactValue::Integer(100),
+            })),
+            alias: "total".to_string(),
+            window: None,
+        })],
+        actions: vec![Action {
+            action_type: ActionType::SetField {
+                field: "flagged".to_string(),
+                value: FactValue::Boolean(true),
+            },
+        }],
+        metadata: Default::default(),
+    }
+}
+
+#[test]
+fn test_rollback_keeps_writes_made_outside_the_transaction() {
+    let engine = BingoEngine::new().unwrap();
+    engine.set_fact_history(Some(FactHistoryConfig::default()));
+    engine.add_rule(order_total_rule()).unwrap();
+    engine.process_facts(vec![order(1, 60), order(2, 30)]).unwrap();
+    let first = FactQuery::compare("amount", Operator::Equal, FactValue::Integer(60));
+    let amount = HashMap::from([("amount".to_string(), FactValue::Integer(50))]);
+    engine.update_by_criteria(&first, &amount).unwrap();
+
+    let mut transaction = engine.begin_transaction();
+    transaction.insert(order(1, 80)).retract(2).insert(order(4, 40));
+    let rejected = transaction.commit_with(|outcome| {
+        assert_eq!(outcome.results.len(), 2);
+        // Another caller stores a fact while the commit is still open
+        std::thread::scope(|scope| {
+            scope.spawn(|| engine.process_facts(vec![order(3, 5)]).unwrap()).join().unwrap();
+        });
+        Err(BingoError::external_service("ledger", "posting failed"))
+    });
+    assert!(rejected.is_err());
+
+    assert!(engine.get_fact(3).is_some());
+    assert!(engine.get_fact(4).is_none());
+    assert_eq!(engine.fact_count(), 3);
+
+    // The replaced fact is back as it was, history included
+    let replaced = engine.get_fact(1).unwrap();
+    assert_eq!(replaced.data.fields["amount"], FactValue::Integer(50));
+    assert!(!replaced.data.fields.contains_key("flagged"));
+    let versions = engine.get_fact_history(1);
+    assert_eq!(versions.len(), 2);
+    assert_eq!(
+        versions[0].fact.data.fields["amount"],
+        FactValue::Integer(60)
+    );
+
+    // The retracted fact counts towards the total again
+    assert!(engine.get_fact(2).is_some());
+    let results = engine.process_facts(vec![order(5, 20)]).unwrap();
+    assert!(results.iter().any(|result| result.fact_id == 5));
+}
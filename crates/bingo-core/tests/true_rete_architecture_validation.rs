@@ -30,6 +30,7 @@ fn create_true_rete_test_rules() -> Vec<Rule> {
                     value: FactValue::Boolean(true),
                 },
             }],
+            metadata: Default::default(),
         },
         // Multi-condition rule (beta memory optimization)
         Rule {
@@ -58,6 +59,7 @@ fn create_true_rete_test_rules() -> Vec<Rule> {
                     value: FactValue::Boolean(true),
                 },
             }],
+            metadata: Default::default(),
         },
         // Calculator rule (cache optimization)
         Rule {
@@ -79,6 +81,7 @@ fn create_true_rete_test_rules() -> Vec<Rule> {
                     output_field: "salary_check".to_string(),
                 },
             }],
+            metadata: Default::default(),
         },
         // Complex multi-action rule (action result pooling)
         Rule {
@@ -120,6 +123,7 @@ fn create_true_rete_test_rules() -> Vec<Rule> {
                     },
                 },
             ],
+            metadata: Default::default(),
         },
     ]
 }
@@ -187,6 +191,7 @@ fn create_true_rete_test_facts() -> Vec<Fact> {
                 id: i as u64,
                 external_id: None,
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: FactData { fields },
             }
         })
@@ -298,6 +303,7 @@ fn test_true_rete_performance_scaling() {
                     output_field: format!("validation_{i}"),
                 },
             }],
+            metadata: Default::default(),
         };
         rules.push(rule);
     }
@@ -345,6 +351,7 @@ fn test_true_rete_performance_scaling() {
                 id: i as u64,
                 external_id: None,
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: FactData { fields },
             }
         })
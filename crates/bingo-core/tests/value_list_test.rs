@@ -0,0 +1,144 @@
+//! Value List Test
+//!
+//! Validates the `In` and `NotIn` operators against literal arrays and named value
+//! lists, re-registering a list under rules already using it, and that rules naming
+//! an unregistered list are rejected.
+
+use bingo_core::types::*;
+use bingo_core::{BingoEngine, parse_rule};
+use std::collections::HashMap;
+
+fn payment(id: u64, country: &str, amount: i64) -> Fact {
+    let fields = HashMap::from([
+        (
+            "country".to_string(),
+            FactValue::String(country.to_string()),
+        ),
+        ("amount".to_string(), FactValue::Integer(amount)),
+    ]);
+    Fact::new(id, FactData { fields })
+}
+
+fn codes(values: &[&str]) -> Vec<FactValue> {
+    values.iter().map(|value| FactValue::String(value.to_string())).collect()
+}
+
+fn fired(engine: &BingoEngine, facts: Vec<Fact>) -> Vec<(u64, u64)> {
+    let mut fired: Vec<(u64, u64)> = engine
+        .process_facts(facts)
+        .unwrap()
+        .iter()
+        .map(|result| (result.rule_id, result.fact_id))
+        .collect();
+    fired.sort_unstable();
+    fired
+}
+
+#[test]
+fn test_named_value_list() {
+    let engine = BingoEngine::new().unwrap();
+    engine.register_value_list("sanctioned", codes(&["IR", "KP", "SY"])).unwrap();
+    engine
+        .add_rule(
+            parse_rule(r#"rule "Sanctioned" id 1 when country in "sanctioned" then log "block""#)
+                .unwrap(),
+        )
+        .unwrap();
+    engine
+        .add_rule(
+            parse_rule(
+                r#"rule "Large foreign" id 2
+                   when country not_in "sanctioned" and amount > 1000
+                   then log "review""#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+    let facts = vec![
+        payment(1, "IR", 50),
+        payment(2, "FR", 5000),
+        payment(3, "KP", 5000),
+        payment(4, "DE", 10),
+    ];
+    assert_eq!(fired(&engine, facts), vec![(1, 1), (1, 3), (2, 2)]);
+}
+
+#[test]
+fn test_literal_arrays() {
+    let engine = BingoEngine::new().unwrap();
+    engine
+        .add_rule(
+            parse_rule(
+                r#"rule "Nordics" id 1 when country in ["DK", "FI", "NO", "SE"] then log "n""#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+    engine
+        .add_rule(
+            parse_rule(
+                r#"rule "Round amounts" id 2
+                   when amount in [100, 500, 1000] and country not_in ["DK"]
+                   then log "round""#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+    let facts = vec![
+        payment(1, "SE", 100),
+        payment(2, "DK", 500),
+        payment(3, "US", 1000),
+        payment(4, "se", 99),
+    ];
+    assert_eq!(fired(&engine, facts), vec![(1, 1), (1, 2), (2, 1), (2, 3)]);
+
+    // The engine keeps the rule as written rather than the compiled list name
+    let rules = engine.rules();
+    let nordics = rules.iter().find(|rule| rule.id == 1).unwrap();
+    assert!(matches!(
+        &nordics.conditions[0],
+        Condition::Simple { value: FactValue::Array(values), .. } if values.len() == 4
+    ));
+}
+
+#[test]
+fn test_reregistering_a_list_updates_rules() {
+    let engine = BingoEngine::new().unwrap();
+    engine.register_value_list("vip", codes(&["alice"])).unwrap();
+    engine
+        .add_rule(Rule {
+            id: 1,
+            name: "VIP".to_string(),
+            conditions: vec![Condition::Simple {
+                field: "country".to_string(),
+                operator: Operator::In,
+                value: FactValue::String("vip".to_string()),
+            }],
+            actions: vec![Action { action_type: ActionType::Log { message: "vip".to_string() } }],
+            metadata: Default::default(),
+        })
+        .unwrap();
+
+    engine.register_value_list("vip", codes(&["bob"])).unwrap();
+    let facts = vec![payment(1, "alice", 1), payment(2, "bob", 1)];
+    assert_eq!(fired(&engine, facts), vec![(1, 2)]);
+}
+
+#[test]
+fn test_unregistered_list_rejects_rule() {
+    let engine = BingoEngine::new().unwrap();
+    let error = engine
+        .add_rule(
+            parse_rule(r#"rule "Missing" id 1 when country in "nowhere" then log "x""#).unwrap(),
+        )
+        .unwrap_err();
+    assert!(
+        format!("{error:#}").contains("Reference table 'nowhere' is not registered"),
+        "{error:#}"
+    );
+    assert_eq!(engine.get_stats().rule_count, 0);
+
+    assert!(engine.register_value_list("$literal1", codes(&["x"])).is_err());
+}
@@ -0,0 +1,181 @@
+//! Warm Standby Test
+//!
+//! Validates that a warm standby applying a primary's replication stream keeps the
+//! same rules, facts and aggregates, takes over on promotion where the primary left
+//! off, follows evictions and retractions, and that digests reveal a standby that
+//! diverged while lost or reordered entries are refused.
+
+use bingo_core::types::*;
+use bingo_core::{
+    BingoEngine, EngineConfig, EngineSnapshot, EvictionPolicy, ReplicationConfig, ReplicationEntry,
+    ReplicationRecord, ReplicationSink, WarmStandby,
+};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+fn shift(employee_id: i64, hours: f64) -> Fact {
+    let fields = HashMap::from([
+        ("employee_id".to_string(), FactValue::Integer(employee_id)),
+        ("hours".to_string(), FactValue::Float(hours)),
+    ]);
+    Fact::new(0, FactData { fields })
+}
+
+fn overtime_rule() -> Rule {
+    Rule {
+        id: 1,
+        name: "Weekly overtime".to_string(),
+        conditions: vec![Condition::Aggregation(AggregationCondition {
+            aggregation_type: AggregationType::Sum,
+            source_field: "hours".to_string(),
+            group_by: vec!["employee_id".to_string()],
+            having: Some(Box::new(Condition::Simple {
+                field: "total_hours".to_string(),
+                operator: Operator::GreaterThan,
+                value: FactValue::Float(40.0),
+            })),
+            alias: "total_hours".to_string(),
+            window: None,
+        })],
+        actions: vec![Action {
+            action_type: ActionType::SetField {
+                field: "overtime".to_string(),
+                value: FactValue::Boolean(true),
+            },
+        }],
+        metadata: Default::default(),
+    }
+}
+
+fn long_shift_rule() -> Rule {
+    Rule {
+        id: 2,
+        name: "Long shift".to_string(),
+        conditions: vec![Condition::Simple {
+            field: "hours".to_string(),
+            operator: Operator::GreaterThan,
+            value: FactValue::Float(10.0),
+        }],
+        actions: vec![Action {
+            action_type: ActionType::SetField {
+                field: "long_shift".to_string(),
+                value: FactValue::Boolean(true),
+            },
+        }],
+        metadata: Default::default(),
+    }
+}
+
+fn every_change() -> ReplicationConfig {
+    ReplicationConfig { digest_interval: Duration::ZERO }
+}
+
+/// Sink standing in for a network transport to a remote standby
+#[derive(Clone, Default)]
+struct Wire(Arc<Mutex<Vec<Vec<u8>>>>);
+
+impl ReplicationSink for Wire {
+    fn send(&self, entry: ReplicationEntry) {
+        self.0.lock().unwrap().push(entry.to_bytes().unwrap());
+    }
+}
+
+impl Wire {
+    fn drain(&self) -> Vec<ReplicationEntry> {
+        let frames = std::mem::take(&mut *self.0.lock().unwrap());
+        frames
+            .iter()
+            .map(|frame| ReplicationEntry::from_bytes(frame).unwrap())
+            .collect()
+    }
+}
+
+#[test]
+fn test_promoted_standby_takes_over_where_the_primary_left_off() {
+    let primary = BingoEngine::new().unwrap();
+    primary.add_rule(overtime_rule()).unwrap();
+    primary.process_facts(vec![shift(7, 12.0)]).unwrap();
+
+    let mut standby = primary.spawn_standby(every_change()).unwrap();
+    primary.add_rule(long_shift_rule()).unwrap();
+    let fired = primary.process_facts(vec![shift(7, 8.0), shift(7, 9.0), shift(8, 11.0)]);
+    let long_shift = fired.unwrap()[0].fact_id;
+    primary.retract_fact(long_shift).unwrap();
+
+    // Every change is followed by a digest
+    assert_eq!(standby.catch_up().unwrap(), 6);
+    assert_eq!(standby.applied_sequence(), 6);
+    assert_eq!(standby.digests_checked(), 3);
+    assert_eq!(standby.divergence(), None);
+    assert_eq!(standby.engine().state_digest(), primary.state_digest());
+    assert_eq!(standby.engine().rule_count(), 2);
+    assert_eq!(standby.engine().fact_count(), 3);
+
+    // Changes streamed after the last catch-up are applied on promotion
+    primary.remove_rule(2).unwrap();
+    let successor = standby.promote().unwrap();
+    assert_eq!(successor.rule_count(), 1);
+    assert_eq!(successor.state_digest(), primary.state_digest());
+
+    // The successor's aggregates carry on from the primary's 29 hours
+    let fired = successor.process_facts(vec![shift(7, 15.0)]).unwrap();
+    assert_eq!(fired.len(), 1);
+    assert_eq!(successor.fact_count(), 4);
+    let stored = successor.get_fact(fired[0].fact_id).unwrap();
+    assert_eq!(stored.data.fields["hours"], FactValue::Float(15.0));
+}
+
+#[test]
+fn test_remote_standby_follows_evictions_and_detects_divergence() {
+    let primary = BingoEngine::with_config(EngineConfig {
+        max_working_memory_facts: Some(2),
+        eviction: EvictionPolicy::OldestFirst,
+        ..Default::default()
+    })
+    .unwrap();
+    primary.add_rule(long_shift_rule()).unwrap();
+
+    let wire = Wire::default();
+    let snapshot = primary.start_replication(every_change(), wire.clone()).unwrap();
+    let snapshot = EngineSnapshot::from_bytes(&snapshot.to_bytes().unwrap()).unwrap();
+    let mut standby = WarmStandby::from_snapshot(&snapshot).unwrap();
+
+    primary.process_facts(vec![shift(1, 11.0), shift(2, 5.0)]).unwrap();
+    primary.process_facts(vec![shift(3, 12.0)]).unwrap();
+    let entries = wire.drain();
+    assert!(entries.iter().any(|entry| matches!(
+        &entry.record,
+        ReplicationRecord::FactsRemoved(fact_ids) if fact_ids.len() == 1
+    )));
+
+    // Redelivered entries are skipped
+    for entry in entries.iter().chain(&entries) {
+        standby.apply(entry).unwrap();
+    }
+    assert_eq!(standby.engine().fact_count(), 2);
+    assert_eq!(standby.divergence(), None);
+
+    // A lost entry stops the standby rather than letting it drift
+    primary.clear_facts();
+    primary.process_facts(vec![shift(4, 3.0)]).unwrap();
+    let entries = wire.drain();
+    let error = standby.apply(&entries[2]).unwrap_err();
+    assert!(error.to_string().contains("were lost"));
+    assert_eq!(standby.applied_sequence(), entries[0].sequence - 1);
+
+    // A change made only on the standby shows up at the next digest
+    standby.engine().remove_rule(2).unwrap();
+    for entry in &entries {
+        standby.apply(entry).unwrap();
+    }
+    let divergence = standby.divergence().unwrap();
+    assert_eq!(divergence.sequence, entries.last().unwrap().sequence);
+    assert_eq!(divergence.primary.rule_count, 1);
+    assert_eq!(divergence.standby.rule_count, 0);
+    assert_eq!(divergence.standby.fact_count, 1);
+
+    assert!(primary.stop_replication());
+    primary.process_facts(vec![shift(6, 2.0)]).unwrap();
+    assert!(wire.drain().is_empty());
+}
@@ -0,0 +1,165 @@
+//! Webhook Test
+//!
+//! Validates webhook actions end to end: payloads reach HTTP and channel endpoints
+//! without blocking rule execution, failing endpoints are retried and dead-lettered,
+//! and rules naming an unregistered endpoint are rejected.
+
+use bingo_core::rete_nodes::ActionResult;
+use bingo_core::types::*;
+use bingo_core::{BingoEngine, WebhookConfig, WebhookTarget};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+fn order(id: u64, amount: i64) -> Fact {
+    let fields = HashMap::from([("amount".to_string(), FactValue::Integer(amount))]);
+    Fact::new(id, FactData { fields })
+}
+
+fn webhook_rule(endpoint: &str) -> Rule {
+    Rule {
+        id: 1,
+        name: "Large order".to_string(),
+        conditions: vec![Condition::Simple {
+            field: "amount".to_string(),
+            operator: Operator::GreaterThan,
+            value: FactValue::Integer(1000),
+        }],
+        actions: vec![Action {
+            action_type: ActionType::CallWebhook {
+                endpoint: endpoint.to_string(),
+                metadata: HashMap::from([(
+                    "team".to_string(),
+                    FactValue::String("fulfilment".to_string()),
+                )]),
+            },
+        }],
+        metadata: Default::default(),
+    }
+}
+
+/// Serve `responses.len()` requests with the given status codes, sending each body back
+fn serve(responses: Vec<u16>) -> (String, mpsc::Receiver<serde_json::Value>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/hooks/orders", listener.local_addr().unwrap());
+    let (bodies, received) = mpsc::channel();
+    thread::spawn(move || {
+        for status in responses {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(length) = line.strip_prefix("Content-Length: ") {
+                    content_length = length.trim().parse().unwrap();
+                }
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            bodies.send(serde_json::from_slice(&body).unwrap()).unwrap();
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 {status} Status\r\nContent-Length: 0\r\n\r\n"
+            )
+            .unwrap();
+        }
+    });
+    (url, received)
+}
+
+#[test]
+fn test_http_endpoint_receives_fact_and_rule() {
+    let engine = BingoEngine::new().unwrap();
+    let (url, received) = serve(vec![200]);
+    engine.register_webhook("orders", WebhookTarget::http(url)).unwrap();
+    engine.add_rule(webhook_rule("orders")).unwrap();
+
+    let results = engine.process_facts(vec![order(1, 5000), order(2, 10)]).unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(
+        results[0].actions_executed,
+        vec![ActionResult::WebhookDispatched { endpoint: "orders".to_string(), queued: true }]
+    );
+
+    let body = received.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert_eq!(body["rule"]["name"], "Large order");
+    assert_eq!(body["fact"]["id"], 1);
+    assert_eq!(body["fact"]["fields"]["amount"], 5000);
+    assert_eq!(body["metadata"]["team"], "fulfilment");
+
+    let webhooks = engine.webhooks();
+    assert!(webhooks.wait_idle(Duration::from_secs(5)));
+    assert_eq!(webhooks.stats().delivered, 1);
+}
+
+#[test]
+fn test_channel_endpoint_survives_rule_changes() {
+    let engine = BingoEngine::new().unwrap();
+    let (sender, receiver) = crossbeam::channel::unbounded();
+    engine.register_webhook("orders", WebhookTarget::Channel(sender)).unwrap();
+    engine.add_rule(webhook_rule("orders")).unwrap();
+
+    // Rebuilding the network keeps the endpoint
+    engine.add_rule(webhook_rule("orders")).unwrap();
+    engine.process_facts(vec![order(1, 5000)]).unwrap();
+
+    let payload = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert_eq!(payload.rule_id, 1);
+    assert_eq!(payload.fact.data.fields["amount"], FactValue::Integer(5000));
+}
+
+#[test]
+fn test_failing_endpoint_is_retried_then_dead_lettered() {
+    let engine = BingoEngine::new().unwrap();
+    engine.configure_webhooks(WebhookConfig {
+        max_attempts: 2,
+        retry_backoff: Duration::from_millis(1),
+        ..WebhookConfig::default()
+    });
+    let (url, received) = serve(vec![503, 500, 200]);
+    engine.register_webhook("orders", WebhookTarget::http(url)).unwrap();
+    engine.add_rule(webhook_rule("orders")).unwrap();
+
+    engine.process_facts(vec![order(1, 5000)]).unwrap();
+    let webhooks = engine.webhooks();
+    assert!(webhooks.wait_idle(Duration::from_secs(5)));
+
+    let dead_letters = webhooks.dead_letters();
+    assert_eq!(dead_letters.len(), 1);
+    assert_eq!(dead_letters[0].attempts, 2);
+    assert!(
+        dead_letters[0].error.contains("HTTP 500"),
+        "{}",
+        dead_letters[0].error
+    );
+    assert_eq!(received.try_iter().count(), 2);
+
+    // Replaying delivers it once the endpoint recovers
+    assert_eq!(webhooks.retry_dead_letters(), 1);
+    assert!(webhooks.wait_idle(Duration::from_secs(5)));
+    let stats = webhooks.stats();
+    assert_eq!(
+        (stats.delivered, stats.retried, stats.dead_lettered),
+        (1, 1, 1)
+    );
+    assert!(webhooks.dead_letters().is_empty());
+}
+
+#[test]
+fn test_unregistered_endpoint_is_rejected() {
+    let engine = BingoEngine::new().unwrap();
+    let error = engine.add_rule(webhook_rule("missing")).unwrap_err();
+    assert!(error.to_string().contains("Webhook endpoint 'missing' is not registered"));
+    assert!(
+        engine
+            .register_webhook("tls", WebhookTarget::http("https://example.com"))
+            .is_err()
+    );
+}
@@ -0,0 +1,89 @@
+//! Working Memory Profiler Test
+//!
+//! Validates that the sampling profiler ranks the facts evaluated most often and the
+//! fields rule conditions read most often, flags fields the fact store does not index,
+//! and records nothing while disabled.
+
+use bingo_core::*;
+use std::collections::HashMap;
+
+fn order(id: u64, external_id: &str, amount: i64) -> Fact {
+    let fields = HashMap::from([
+        ("amount".to_string(), FactValue::Integer(amount)),
+        ("status".to_string(), FactValue::String("open".to_string())),
+    ]);
+    Fact { external_id: Some(external_id.to_string()), ..Fact::new(id, FactData { fields }) }
+}
+
+fn engine() -> BingoEngine {
+    let engine = BingoEngine::new().unwrap();
+    engine
+        .add_rules_from_dsl(
+            r#"
+            rule "Large order" id 1 when amount > 100 then set flagged = true
+            rule "Open large order" id 2 when amount > 100 and status == "open" then set review = true
+            rule "Small order" id 3 when amount < 10 then set small = true
+            "#,
+        )
+        .unwrap();
+    engine
+}
+
+#[test]
+fn test_profile_ranks_hot_facts_and_fields() {
+    let engine = engine();
+    let config = WorkingMemoryProfilerConfig { sample_rate: 1, ..Default::default() };
+    engine.set_working_memory_profiler(Some(config));
+    assert_eq!(engine.working_memory_profiler(), Some(config));
+
+    engine
+        .process_facts(vec![order(1, "order-1", 250), order(2, "order-2", 500)])
+        .unwrap();
+
+    let profile = engine.working_memory_profile().unwrap();
+    assert_eq!(profile.sample_rate, 1);
+    assert_eq!(profile.samples, profile.evaluations);
+    assert!(profile.samples >= 4);
+    assert_eq!(profile.hot_facts.len(), 2);
+    assert_eq!(profile.hot_facts[0].external_id.as_deref(), Some("order-1"));
+    assert_eq!(
+        profile.hot_facts[0].estimated_evaluations,
+        profile.hot_facts[0].samples
+    );
+
+    let amount = &profile.hot_fields[0];
+    assert_eq!(amount.field, "amount");
+    assert!(!amount.indexed);
+    let status = profile.hot_fields.iter().find(|field| field.field == "status").unwrap();
+    assert!(status.indexed);
+    assert!(status.samples < amount.samples);
+    assert_eq!(
+        profile.unindexed_fields().map(|field| field.field.as_str()).collect::<Vec<_>>(),
+        vec!["amount"]
+    );
+}
+
+#[test]
+fn test_profiler_is_disabled_by_default_and_sampling_scales_estimates() {
+    let engine = engine();
+    engine.process_facts(vec![order(1, "order-1", 250)]).unwrap();
+    assert!(engine.working_memory_profile().is_none());
+
+    let config = WorkingMemoryProfilerConfig { sample_rate: 4, ..Default::default() };
+    engine.set_working_memory_profiler(Some(config));
+    let facts = (10..30).map(|id| order(id, &format!("order-{id}"), 250)).collect();
+    engine.process_facts(facts).unwrap();
+
+    let profile = engine.working_memory_profile().unwrap();
+    assert_eq!(profile.samples, profile.evaluations / 4);
+    assert!(profile.hot_facts.len() <= config.report_limit);
+    assert!(
+        profile
+            .hot_fields
+            .iter()
+            .all(|field| field.estimated_reads == field.samples * 4)
+    );
+
+    engine.set_working_memory_profiler(None);
+    assert!(engine.working_memory_profile().is_none());
+}
@@ -27,6 +27,7 @@ fn test_working_memory_incremental_processing() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
     engine.add_rule(rule).unwrap();
 
@@ -49,6 +50,7 @@ fn test_working_memory_incremental_processing() {
                 id: i as u64,
                 external_id: None,
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: FactData { fields },
             }
         })
@@ -123,6 +125,7 @@ fn test_working_memory_lifecycle() {
                 value: FactValue::Boolean(true),
             },
         }],
+        metadata: Default::default(),
     };
     engine.add_rule(rule).unwrap();
 
@@ -137,6 +140,7 @@ fn test_working_memory_lifecycle() {
                 id: i as u64,
                 external_id: None,
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: FactData { fields },
             }
         })
@@ -192,6 +196,7 @@ fn test_working_memory_performance_benefit() {
                     value: FactValue::Integer(i as i64),
                 },
             }],
+            metadata: Default::default(),
         };
         engine.add_rule(rule).unwrap();
     }
@@ -212,6 +217,7 @@ fn test_working_memory_performance_benefit() {
                 id: i as u64,
                 external_id: None,
                 timestamp: chrono::Utc::now(),
+                fact_type: None,
                 data: FactData { fields },
             }
         })
@@ -0,0 +1,94 @@
+//! Embedded engine in an axum service
+//!
+//! One `BingoEngine` is shared by every handler through the router state. Rule
+//! evaluation is CPU-bound, so handlers run it on the blocking thread pool instead of
+//! the async workers.
+//!
+//! ```text
+//! cargo run -p bingo-examples --example axum_service
+//! curl -X POST localhost:3000/rules --data 'rule "Large" id 1 when amount > 100 then set large = true'
+//! curl -X POST localhost:3000/facts -H 'content-type: application/json' --data '[{"id": "o-1", "amount": 250}]'
+//! ```
+
+use anyhow::Context;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use bingo_core::BingoEngine;
+use bingo_examples::{fact_from_json, result_to_json};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+struct AppState {
+    engine: BingoEngine,
+    next_fact_id: AtomicU64,
+}
+
+type AppError = (StatusCode, String);
+
+async fn add_rules(
+    State(state): State<Arc<AppState>>,
+    source: String,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let added = tokio::task::spawn_blocking(move || state.engine.add_rules_from_dsl(&source))
+        .await
+        .map_err(internal)?
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    Ok(Json(serde_json::json!({ "rules_added": added })))
+}
+
+async fn process_facts(
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<Vec<serde_json::Value>>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let facts = body
+        .iter()
+        .map(|json| fact_from_json(state.next_fact_id.fetch_add(1, Ordering::Relaxed), json))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    let results = tokio::task::spawn_blocking(move || state.engine.process_facts(facts))
+        .await
+        .map_err(internal)?
+        .map_err(internal)?;
+    Ok(Json(serde_json::json!({
+        "results": results.iter().map(result_to_json).collect::<Vec<_>>(),
+    })))
+}
+
+async fn health(State(state): State<Arc<AppState>>) -> Json<serde_json::Value> {
+    let stats = state.engine.get_stats();
+    Json(serde_json::json!({
+        "rules": stats.rule_count,
+        "facts": stats.fact_count,
+    }))
+}
+
+fn internal(error: impl std::fmt::Display) -> AppError {
+    (StatusCode::INTERNAL_SERVER_ERROR, error.to_string())
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    let state = Arc::new(AppState {
+        engine: BingoEngine::new().context("failed to create engine")?,
+        next_fact_id: AtomicU64::new(1),
+    });
+    let app = Router::new()
+        .route("/health", get(health))
+        .route("/rules", post(add_rules))
+        .route("/facts", post(process_facts))
+        .with_state(state);
+
+    let addr = std::env::var("BINGO_EXAMPLE_ADDR").unwrap_or_else(|_| "127.0.0.1:3000".into());
+    let listener = tokio::net::TcpListener::bind(&addr).await?;
+    println!("listening on {addr}");
+    axum::serve(listener, app)
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,89 @@
+//! Custom calculator invoked by rule actions
+//!
+//! A `CalculatorPlugin` registered with a `Calculator` becomes callable from `call`
+//! actions by name, alongside the built-in calculators, once the calculator is handed
+//! to `BingoEngine::with_calculator`.
+//!
+//! ```text
+//! cargo run -p bingo-examples --example calculator_plugin
+//! ```
+
+use anyhow::Context;
+use bingo_calculator::Calculator;
+use bingo_calculator::plugin::{CalculationResult, CalculatorPlugin};
+use bingo_core::{ActionResult, BingoEngine, Fact, FactData, FactValue};
+use std::collections::HashMap;
+
+/// Commission on `sales`, paying each tier's rate on the part of the sales within it
+///
+/// # Arguments
+/// * `sales` - Sales amount
+///
+/// # Returns
+/// The commission as a FactValue::Float
+struct TieredCommissionCalculator {
+    /// Lower bound and rate of each tier, in ascending order
+    tiers: Vec<(f64, f64)>,
+}
+
+impl CalculatorPlugin for TieredCommissionCalculator {
+    fn name(&self) -> &str {
+        "tiered_commission"
+    }
+
+    fn calculate(&self, args: &HashMap<String, &FactValue>) -> CalculationResult {
+        let sales = match args.get("sales") {
+            Some(FactValue::Float(f)) => *f,
+            Some(FactValue::Integer(i)) => *i as f64,
+            _ => return Err("Invalid argument 'sales': expected number".to_string()),
+        };
+        let mut commission = 0.0;
+        for (index, &(floor, rate)) in self.tiers.iter().enumerate() {
+            let ceiling = self.tiers.get(index + 1).map_or(f64::INFINITY, |tier| tier.0);
+            if sales > floor {
+                commission += (sales.min(ceiling) - floor) * rate;
+            }
+        }
+        Ok(FactValue::Float(commission))
+    }
+}
+
+fn main() -> anyhow::Result<()> {
+    let mut calculator = Calculator::new();
+    calculator.register(Box::new(TieredCommissionCalculator {
+        tiers: vec![(0.0, 0.05), (10_000.0, 0.08), (50_000.0, 0.12)],
+    }));
+
+    let engine = BingoEngine::with_calculator(calculator).context("failed to create engine")?;
+    engine.add_rules_from_dsl(
+        r#"
+        rule "Commission" id 1
+        when sales > 0
+        then call tiered_commission(sales = sales) into commission
+        "#,
+    )?;
+
+    let facts = [("rep-1", 8_000), ("rep-2", 25_000), ("rep-3", 80_000)]
+        .into_iter()
+        .enumerate()
+        .map(|(index, (rep, sales))| {
+            let fields = HashMap::from([("sales".to_string(), FactValue::Integer(sales))]);
+            Fact {
+                external_id: Some(rep.to_string()),
+                ..Fact::new(index as u64 + 1, FactData { fields })
+            }
+        })
+        .collect();
+
+    for result in engine.process_facts(facts)? {
+        for action in &result.actions_executed {
+            if let ActionResult::CalculatorResult { parsed_value, .. } = action {
+                println!(
+                    "{}: commission {parsed_value}",
+                    result.external_id.as_deref().unwrap_or_default()
+                );
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,124 @@
+//! Kafka consumer streaming facts through a session
+//!
+//! `KafkaConnector` works over any client implementing `KafkaConsumer` and
+//! `KafkaProducer`. Production deployments wrap an `rdkafka` `BaseConsumer` and
+//! `BaseProducer` with auto-commit disabled; this example implements both traits over an
+//! in-memory broker so it runs without a Kafka cluster.
+//!
+//! ```text
+//! cargo run -p bingo-examples --example kafka_consumer
+//! ```
+
+use anyhow::Context;
+use bingo_core::{
+    BingoEngine, BingoSession, KafkaConnector, KafkaConnectorConfig, KafkaConsumer, KafkaProducer,
+    KafkaRecord, TopicPartitionOffset,
+};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Topics of the in-memory broker, shared by its consumer and producer
+#[derive(Default)]
+struct Broker {
+    topics: Mutex<HashMap<String, Vec<KafkaRecord>>>,
+}
+
+impl Broker {
+    fn publish(&self, topic: &str, key: Option<&[u8]>, payload: &[u8]) {
+        let mut topics = self.topics.lock().unwrap();
+        let records = topics.entry(topic.to_string()).or_default();
+        records.push(KafkaRecord {
+            topic: topic.to_string(),
+            partition: 0,
+            offset: records.len() as i64,
+            key: key.map(<[u8]>::to_vec),
+            payload: payload.to_vec(),
+        });
+    }
+
+    fn records(&self, topic: &str) -> Vec<KafkaRecord> {
+        self.topics.lock().unwrap().get(topic).cloned().unwrap_or_default()
+    }
+}
+
+struct InMemoryConsumer {
+    broker: Arc<Broker>,
+    pending: VecDeque<KafkaRecord>,
+}
+
+impl KafkaConsumer for InMemoryConsumer {
+    fn subscribe(&mut self, topics: &[String]) -> Result<(), String> {
+        for topic in topics {
+            self.pending.extend(self.broker.records(topic));
+        }
+        Ok(())
+    }
+
+    fn poll(&mut self, max_records: usize, _timeout: Duration) -> Result<Vec<KafkaRecord>, String> {
+        let count = max_records.min(self.pending.len());
+        Ok(self.pending.drain(..count).collect())
+    }
+
+    fn commit(&mut self, offsets: &[TopicPartitionOffset]) -> Result<(), String> {
+        println!("committed {offsets:?}");
+        Ok(())
+    }
+}
+
+struct InMemoryProducer {
+    broker: Arc<Broker>,
+}
+
+impl KafkaProducer for InMemoryProducer {
+    fn send(&mut self, topic: &str, key: Option<&[u8]>, payload: &[u8]) -> Result<(), String> {
+        self.broker.publish(topic, key, payload);
+        Ok(())
+    }
+
+    fn flush(&mut self, _timeout: Duration) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+fn main() -> anyhow::Result<()> {
+    let broker = Arc::new(Broker::default());
+    for (key, payload) in [
+        ("txn-1", r#"{"account": "acc-1", "amount": 12000}"#),
+        ("txn-2", r#"{"account": "acc-2", "amount": 40}"#),
+        (
+            "txn-3",
+            r#"{"account": "acc-1", "amount": 9800, "country": "XX"}"#,
+        ),
+        ("txn-4", "not json"),
+    ] {
+        broker.publish("transactions", Some(key.as_bytes()), payload.as_bytes());
+    }
+
+    let engine = Arc::new(BingoEngine::new().context("failed to create engine")?);
+    engine.add_rules_from_dsl(
+        r#"
+        rule "Large transaction" id 1 when amount > 10000 then set review = true
+        rule "Unknown country" id 2 when country == "XX" then alert high "fraud" "Unknown country"
+        "#,
+    )?;
+    let session = Arc::new(BingoSession::new(engine));
+
+    let consumer = InMemoryConsumer { broker: Arc::clone(&broker), pending: VecDeque::new() };
+    let producer = InMemoryProducer { broker: Arc::clone(&broker) };
+    let mut config = KafkaConnectorConfig::new(vec!["transactions".to_string()], "decisions");
+    config.dead_letter_topic = Some("transactions.dlq".to_string());
+    config.max_batch_records = 2;
+    let mut connector = KafkaConnector::new(consumer, producer, session, config)?;
+
+    // A service would call `connector.run(&stop)` on a dedicated thread instead
+    while connector.poll_once()?.records > 0 {}
+    println!("{:?}", connector.stats());
+
+    for topic in ["decisions", "transactions.dlq"] {
+        for record in broker.records(topic) {
+            println!("{topic}: {}", String::from_utf8_lossy(&record.payload));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,59 @@
+//! Examples of embedding the Bingo rules engine as a library
+//!
+//! Each program under `examples/` is a complete integration that CI compiles and runs:
+//!
+//! | Example | Pattern |
+//! |---------|---------|
+//! | `axum_service` | Engine shared by the handlers of an HTTP service |
+//! | `kafka_consumer` | `KafkaConnector` streaming facts through a session |
+//! | `batch_pipeline` | Command-line pipeline from a JSON fact file to JSON results |
+//! | `calculator_plugin` | Custom calculator invoked by `call` actions |
+//!
+//! Run one with `cargo run -p bingo-examples --example <name>`. This library holds the
+//! JSON conversions the examples share.
+
+use bingo_core::{Fact, FactData, FactValue, RuleExecutionResult};
+use std::collections::HashMap;
+
+/// Fact whose fields are the members of a JSON object
+///
+/// A string `"id"` member becomes the fact's external ID as well as a field.
+pub fn fact_from_json(id: u64, json: &serde_json::Value) -> Result<Fact, String> {
+    let serde_json::Value::Object(members) = json else {
+        return Err(format!("fact {id}: expected a JSON object of fact fields"));
+    };
+    let fields = members
+        .iter()
+        .map(|(field, value)| {
+            FactValue::try_from(value)
+                .map(|value| (field.clone(), value))
+                .map_err(|e| format!("fact {id}: invalid value for field '{field}': {e}"))
+        })
+        .collect::<Result<HashMap<_, _>, _>>()?;
+    let external_id = members.get("id").and_then(|id| id.as_str()).map(str::to_string);
+    Ok(Fact { external_id, ..Fact::new(id, FactData { fields }) })
+}
+
+/// JSON rendering of a rule execution result
+pub fn result_to_json(result: &RuleExecutionResult) -> serde_json::Value {
+    serde_json::json!({
+        "rule_id": result.rule_id,
+        "fact_id": result.fact_id,
+        "external_id": result.external_id,
+        "actions": result.actions_executed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fact_from_json_uses_string_id_as_external_id() {
+        let fact = fact_from_json(7, &serde_json::json!({"id": "order-7", "amount": 250})).unwrap();
+        assert_eq!(fact.external_id.as_deref(), Some("order-7"));
+        assert_eq!(fact.data.fields["amount"], FactValue::Integer(250));
+
+        assert!(fact_from_json(8, &serde_json::json!([1, 2])).is_err());
+    }
+}
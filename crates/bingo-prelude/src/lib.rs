@@ -0,0 +1,163 @@
+//! Semver-stable API facade over the Bingo rules engine
+//!
+//! `bingo-core` exposes every module it is built from, and its internals move between
+//! releases. This crate re-exports the part of the engine applications embed, under
+//! paths that only change with a new API version:
+//!
+//! ```rust
+//! use bingo_prelude::*;
+//!
+//! let engine = BingoEngine::new()?;
+//! engine.add_rules(parse_rules(r#"rule "Large order" id 1 when amount > 100 then set review = true"#)?)?;
+//! # Ok::<(), BingoError>(())
+//! ```
+//!
+//! The stable surface lives in [`v1`] and is re-exported from the crate root. Within
+//! an API version:
+//!
+//! - items are only added, never removed or renamed
+//! - an item that moves keeps its old path as a `#[deprecated]` shim until the next
+//!   API version, so upgrading turns breakage into warnings
+//! - `bingo-core` modules hidden from its documentation are internal and not covered
+//!
+//! [`version_info`] reports the API, crate and engine versions and every deprecated
+//! path with its replacement, for checking compatibility at startup.
+
+#![deny(warnings)]
+#![deny(missing_docs)]
+
+use std::fmt;
+
+/// Version 1 of the stable API
+pub mod v1 {
+    pub use bingo_calculator::plugin::{CalculationResult, CalculatorInputs, CalculatorPlugin};
+    pub use bingo_core::types::{
+        AggregationCondition, AggregationType, AggregationWindow, CalendarPeriod, FactId, RuleId,
+        StreamAggregation, StreamCondition, StreamWindowSpec,
+    };
+    pub use bingo_core::{
+        Action, ActionResult, ActionType, BatchSummary, BingoEngine, BingoError, BingoResult,
+        BingoSession, CompiledRuleset, Condition, Correction, DeltaKind, EngineConfig,
+        EngineSnapshot, EvaluationDate, ExplanationTrace, Fact, FactData, FactHandle, FactValue,
+        LogicalOperator, Operator, PeriodCalendar, RecalculationReport, RecalculationRequest,
+        ReferenceTable, ResultDelta, Rule, RuleExecutionResult, RuleMetadata, RuleStats,
+        RuleSummary, SessionEvent, SessionEventListener, parse_rule, parse_rules,
+    };
+}
+
+pub use v1::*;
+
+/// Deprecated aliases of stable items under a former path
+macro_rules! moved_to_root {
+    ($($name:ident),* $(,)?) => {
+        $(
+            #[doc = concat!("Moved to [`", stringify!($name), "`](crate::", stringify!($name), ")")]
+            #[deprecated(since = "0.1.0", note = "import it from the `bingo_prelude` root")]
+            pub type $name = crate::v1::$name;
+        )*
+    };
+}
+
+/// Former `bingo_core::types` path of the rule and fact types
+pub mod types {
+    moved_to_root!(
+        Action,
+        ActionType,
+        AggregationCondition,
+        AggregationType,
+        AggregationWindow,
+        CalendarPeriod,
+        Condition,
+        EvaluationDate,
+        Fact,
+        FactData,
+        FactId,
+        FactValue,
+        LogicalOperator,
+        Operator,
+        Rule,
+        RuleId,
+        RuleMetadata,
+        StreamAggregation,
+        StreamCondition,
+        StreamWindowSpec,
+    );
+}
+
+/// Former `bingo_core::rete_nodes` path of rule execution results
+pub mod rete_nodes {
+    moved_to_root!(ActionResult, RuleExecutionResult);
+}
+
+/// Major version of the stable API re-exported from the crate root
+pub const API_VERSION: u32 = 1;
+
+/// Paths kept as deprecated shims in this API version
+const DEPRECATIONS: &[Deprecation] = &[
+    Deprecation { path: "bingo_prelude::types", replacement: "bingo_prelude", since: "0.1.0" },
+    Deprecation { path: "bingo_prelude::rete_nodes", replacement: "bingo_prelude", since: "0.1.0" },
+];
+
+/// Deprecated path that still compiles until the next API version
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Deprecation {
+    /// Deprecated path
+    pub path: &'static str,
+    /// Path to use instead
+    pub replacement: &'static str,
+    /// Crate version the path was deprecated in
+    pub since: &'static str,
+}
+
+/// Compatibility report of the facade
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionInfo {
+    /// Major version of the stable API
+    pub api_version: u32,
+    /// Version of this crate
+    pub prelude_version: &'static str,
+    /// Version of the `bingo-core` engine behind the facade
+    pub core_version: &'static str,
+    /// Paths kept as deprecated shims
+    pub deprecations: &'static [Deprecation],
+}
+
+impl VersionInfo {
+    /// Whether code written against API version `api_version` builds against this facade
+    pub fn supports(&self, api_version: u32) -> bool {
+        api_version == self.api_version
+    }
+
+    /// Deprecation of `path`, if it is deprecated
+    pub fn deprecation(&self, path: &str) -> Option<&Deprecation> {
+        self.deprecations.iter().find(|deprecation| deprecation.path == path)
+    }
+}
+
+impl fmt::Display for VersionInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "bingo-prelude {} (API v{}, bingo-core {})",
+            self.prelude_version, self.api_version, self.core_version
+        )?;
+        for deprecation in self.deprecations {
+            writeln!(
+                f,
+                "  deprecated since {}: {} -> {}",
+                deprecation.since, deprecation.path, deprecation.replacement
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Versions behind the facade and the deprecated paths it still accepts
+pub fn version_info() -> VersionInfo {
+    VersionInfo {
+        api_version: API_VERSION,
+        prelude_version: env!("CARGO_PKG_VERSION"),
+        core_version: bingo_core::VERSION,
+        deprecations: DEPRECATIONS,
+    }
+}
@@ -0,0 +1,48 @@
+//! Facade Test
+//!
+//! Validates that an application can embed the engine through the stable facade
+//! alone, that deprecated paths still resolve to the same items, and that the
+//! compatibility report lists them.
+
+use bingo_prelude::*;
+use std::collections::HashMap;
+
+#[test]
+fn test_engine_is_usable_through_the_facade() {
+    let engine = BingoEngine::new().unwrap();
+    engine
+        .add_rules(
+            parse_rules(r#"rule "Large order" id 1 when amount > 100 then set review = true"#)
+                .unwrap(),
+        )
+        .unwrap();
+
+    let fields = HashMap::from([("amount".to_string(), FactValue::Integer(250))]);
+    let results: Vec<RuleExecutionResult> =
+        engine.process_facts(vec![Fact::new(0, FactData { fields })]).unwrap();
+    assert_eq!(results.len(), 1);
+    assert!(matches!(
+        &results[0].actions_executed[0],
+        ActionResult::FieldSet { field, .. } if field == "review"
+    ));
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_deprecated_paths_resolve_to_the_stable_items() {
+    let result: rete_nodes::RuleExecutionResult = RuleExecutionResult::default();
+    let value: types::FactValue = FactValue::Boolean(true);
+    assert_eq!(result.rule_id, 0);
+    assert_eq!(value, FactValue::Boolean(true));
+
+    let info = version_info();
+    assert!(info.supports(API_VERSION));
+    assert!(!info.supports(API_VERSION + 1));
+    assert_eq!(info.prelude_version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(
+        info.deprecation("bingo_prelude::types").unwrap().replacement,
+        "bingo_prelude"
+    );
+    assert!(info.deprecation("bingo_prelude::BingoEngine").is_none());
+    assert!(info.to_string().contains("bingo_prelude::rete_nodes -> bingo_prelude"));
+}
@@ -0,0 +1,183 @@
+use anyhow::{Result, anyhow, bail};
+use chrono::Duration;
+use serde::{Deserialize, Deserializer, Serializer};
+use std::fmt::Write;
+
+/// Format a duration in ISO 8601 notation, e.g. `PT1H30M`, `P2DT4H` or `-PT0.5S`
+///
+/// Days are the largest unit, as months and years have no fixed length.
+pub fn format_iso8601(duration: Duration) -> String {
+    if duration.is_zero() {
+        return "PT0S".to_string();
+    }
+
+    let mut text = String::new();
+    if duration < Duration::zero() {
+        text.push('-');
+    }
+    let duration = duration.abs();
+    let total_seconds = duration.num_seconds();
+    let nanos = duration.subsec_nanos();
+    let (days, hours) = (total_seconds / 86_400, total_seconds % 86_400 / 3_600);
+    let (minutes, seconds) = (total_seconds % 3_600 / 60, total_seconds % 60);
+
+    text.push('P');
+    if days > 0 {
+        let _ = write!(text, "{days}D");
+    }
+    if hours > 0 || minutes > 0 || seconds > 0 || nanos > 0 {
+        text.push('T');
+        if hours > 0 {
+            let _ = write!(text, "{hours}H");
+        }
+        if minutes > 0 {
+            let _ = write!(text, "{minutes}M");
+        }
+        if nanos > 0 {
+            let fraction = format!("{nanos:09}");
+            let _ = write!(text, "{seconds}.{}S", fraction.trim_end_matches('0'));
+        } else if seconds > 0 {
+            let _ = write!(text, "{seconds}S");
+        }
+    }
+    text
+}
+
+/// Parse an ISO 8601 duration such as `PT30M`, `P1W` or `-P1DT12H`
+///
+/// Only seconds may have a fraction. Years and months are rejected because their
+/// length depends on the date they are counted from.
+pub fn parse_iso8601(text: &str) -> Result<Duration> {
+    let invalid = || anyhow!("Invalid ISO 8601 duration '{text}'");
+    let (negative, unsigned) = text.strip_prefix('-').map_or((false, text), |rest| (true, rest));
+    let designators = unsigned.strip_prefix('P').ok_or_else(invalid)?;
+    let (date_part, time_part) = designators.split_once('T').unwrap_or((designators, ""));
+    if date_part.is_empty() && time_part.is_empty() {
+        return Err(invalid());
+    }
+
+    let date = components(date_part).ok_or_else(invalid)?;
+    let time = components(time_part).ok_or_else(invalid)?;
+    if designators.contains('T') && time.is_empty() {
+        return Err(invalid());
+    }
+
+    let mut total = Duration::zero();
+    let date = date.into_iter().map(|(number, unit)| (number, unit, false));
+    let time = time.into_iter().map(|(number, unit)| (number, unit, true));
+    for (number, unit, in_time) in date.chain(time) {
+        let component = match (unit, in_time) {
+            ('W', false) => number.parse().ok().and_then(Duration::try_weeks),
+            ('D', false) => number.parse().ok().and_then(Duration::try_days),
+            ('H', true) => number.parse().ok().and_then(Duration::try_hours),
+            ('M', true) => number.parse().ok().and_then(Duration::try_minutes),
+            ('S', true) => parse_seconds(number),
+            ('Y' | 'M', false) => {
+                bail!("Invalid ISO 8601 duration '{text}': years and months have no fixed length")
+            }
+            _ => None,
+        };
+        total = component
+            .and_then(|component| total.checked_add(&component))
+            .ok_or_else(invalid)?;
+    }
+    Ok(if negative { -total } else { total })
+}
+
+/// `(number, unit)` pairs of one part of a duration, or `None` if it is malformed
+fn components(part: &str) -> Option<Vec<(&str, char)>> {
+    let mut components = Vec::new();
+    let mut start = 0;
+    for (index, unit) in part.char_indices() {
+        if unit.is_ascii_alphabetic() {
+            let number = &part[start..index];
+            if number.is_empty()
+                || !number.bytes().all(|b| b.is_ascii_digit() || b == b'.' || b == b',')
+            {
+                return None;
+            }
+            components.push((number, unit));
+            start = index + 1;
+        }
+    }
+    (start == part.len()).then_some(components)
+}
+
+/// Seconds with up to nine fractional digits, read exactly
+fn parse_seconds(number: &str) -> Option<Duration> {
+    let (whole, fraction) = number.split_once(['.', ',']).unwrap_or((number, ""));
+    if fraction.len() > 9 || !fraction.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    let nanos = if fraction.is_empty() {
+        0
+    } else {
+        format!("{fraction:0<9}").parse().ok()?
+    };
+    Duration::try_seconds(whole.parse().ok()?)?.checked_add(&Duration::nanoseconds(nanos))
+}
+
+/// Serde representation of a duration as an ISO 8601 string
+pub mod serde_iso8601 {
+    use super::{Deserialize, Deserializer, Duration, Serializer, format_iso8601, parse_iso8601};
+
+    /// Serialize a duration as an ISO 8601 string
+    ///
+    /// # Errors
+    ///
+    /// Returns the serializer's error if it cannot write a string.
+    #[allow(clippy::trivially_copy_pass_by_ref)] // Signature required by `serde(with)`
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format_iso8601(*duration))
+    }
+
+    /// Deserialize a duration from an ISO 8601 string
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is not a string or not a valid duration.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        parse_iso8601(&text).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_iso8601() {
+        assert_eq!(format_iso8601(Duration::zero()), "PT0S");
+        assert_eq!(format_iso8601(Duration::minutes(90)), "PT1H30M");
+        assert_eq!(format_iso8601(Duration::hours(52)), "P2DT4H");
+        assert_eq!(format_iso8601(Duration::days(7)), "P7D");
+        assert_eq!(format_iso8601(-Duration::milliseconds(500)), "-PT0.5S");
+    }
+
+    #[test]
+    fn test_parse_iso8601() {
+        assert_eq!(parse_iso8601("PT30M").unwrap(), Duration::minutes(30));
+        assert_eq!(parse_iso8601("P1W").unwrap(), Duration::weeks(1));
+        assert_eq!(parse_iso8601("-P1DT12H").unwrap(), -Duration::hours(36));
+        assert_eq!(
+            parse_iso8601("PT1.25S").unwrap(),
+            Duration::milliseconds(1250)
+        );
+        assert_eq!(parse_iso8601("P2DT4H").unwrap(), Duration::hours(52));
+
+        for invalid in
+            ["", "P", "PT", "30M", "PTM", "P1H", "PT1D", "PT1.5M", "PT1S2", "P1DT", "PT-5S"]
+        {
+            assert!(parse_iso8601(invalid).is_err(), "{invalid}");
+        }
+        assert!(parse_iso8601("P1M").unwrap_err().to_string().contains("no fixed length"));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for duration in [Duration::seconds(1), Duration::hours(25), -Duration::nanoseconds(1)] {
+            assert_eq!(parse_iso8601(&format_iso8601(duration)).unwrap(), duration);
+        }
+    }
+}
@@ -9,10 +9,20 @@
 #![deny(clippy::pedantic)]
 #![deny(clippy::nursery)]
 #![deny(clippy::cargo)]
+// rust_decimal declares optional rand 0.8 and 0.9 dependencies behind weak `rand?/std`
+// features; cargo metadata lists both even though neither is built, so the duplicate
+// rand/getrandom/wasi versions are not something this crate can line up.
+#![allow(clippy::multiple_crate_versions)]
 #![deny(missing_docs)]
 
 // Re-export types
+mod duration;
+mod overflow;
+mod rounding;
 mod types;
+pub use overflow::{IntegerOp, OverflowPolicy};
+pub use rounding::{DecimalRounding, RoundingMode};
+pub use rust_decimal::Decimal;
 pub use types::FactValue;
 
 // Re-export core engine & type system ---------------------------------------------------------
@@ -0,0 +1,207 @@
+//! Overflow handling for integer arithmetic
+//!
+//! `i64` arithmetic wraps silently in release builds, which corrupts accumulators such
+//! as running totals without any sign that it happened. [`OverflowPolicy`] decides what
+//! an overflowing operation produces instead.
+
+use crate::FactValue;
+use anyhow::{Result, anyhow};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Integer operation checked for overflow
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IntegerOp {
+    /// `left + right`
+    Add,
+    /// `left - right`
+    Subtract,
+    /// `left * right`
+    Multiply,
+    /// `left` raised to the non-negative power `right`
+    Power,
+}
+
+impl IntegerOp {
+    /// Symbol of the operation in expressions
+    #[must_use]
+    pub const fn symbol(self) -> &'static str {
+        match self {
+            Self::Add => "+",
+            Self::Subtract => "-",
+            Self::Multiply => "*",
+            Self::Power => "^",
+        }
+    }
+}
+
+impl fmt::Display for IntegerOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.symbol())
+    }
+}
+
+/// What an integer operation overflowing `i64` produces
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverflowPolicy {
+    /// Fail the operation
+    #[default]
+    Error,
+    /// Clamp the result to `i64::MIN` or `i64::MAX`
+    Saturate,
+    /// Compute the result as a float, keeping its magnitude at the cost of precision
+    PromoteToFloat,
+    /// Compute the result as an exact decimal, failing if it is out of decimal range too
+    PromoteToDecimal,
+}
+
+impl OverflowPolicy {
+    /// Apply `op` to `left` and `right`, resolving an overflow as the policy says
+    ///
+    /// Results that fit in `i64` are always integers, whatever the policy.
+    ///
+    /// # Errors
+    ///
+    /// Fails on overflow under [`Self::Error`], when the result is out of decimal range
+    /// under [`Self::PromoteToDecimal`], and for a negative exponent.
+    pub fn apply(self, left: i64, op: IntegerOp, right: i64) -> Result<FactValue> {
+        if op == IntegerOp::Power && right < 0 {
+            return Err(anyhow!(
+                "Negative exponent in integer power {left} ^ {right}"
+            ));
+        }
+        if let Some(result) = checked(left, op, right) {
+            return Ok(FactValue::Integer(result));
+        }
+
+        let overflow = || anyhow!("Integer overflow in {left} {op} {right}");
+        match self {
+            Self::Error => Err(overflow()),
+            Self::Saturate => Ok(FactValue::Integer(saturated(left, op, right))),
+            Self::PromoteToFloat => Ok(FactValue::Float(promoted_to_float(left, op, right))),
+            Self::PromoteToDecimal => promoted_to_decimal(left, op, right)
+                .map(FactValue::Decimal)
+                .ok_or_else(overflow),
+        }
+    }
+}
+
+fn checked(left: i64, op: IntegerOp, right: i64) -> Option<i64> {
+    match op {
+        IntegerOp::Add => left.checked_add(right),
+        IntegerOp::Subtract => left.checked_sub(right),
+        IntegerOp::Multiply => left.checked_mul(right),
+        // Exponents beyond u32 only fit for bases whose powers stay bounded
+        IntegerOp::Power => match left {
+            0 | 1 => Some(if right == 0 { 1 } else { left }),
+            -1 => Some(if right % 2 == 0 { 1 } else { -1 }),
+            _ => left.checked_pow(u32::try_from(right).ok()?),
+        },
+    }
+}
+
+const fn saturated(left: i64, op: IntegerOp, right: i64) -> i64 {
+    match op {
+        IntegerOp::Add => left.saturating_add(right),
+        IntegerOp::Subtract => left.saturating_sub(right),
+        IntegerOp::Multiply => left.saturating_mul(right),
+        IntegerOp::Power => {
+            if left < 0 && right % 2 == 1 {
+                i64::MIN
+            } else {
+                i64::MAX
+            }
+        }
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn promoted_to_float(left: i64, op: IntegerOp, right: i64) -> f64 {
+    let (left, right) = (left as f64, right as f64);
+    match op {
+        IntegerOp::Add => left + right,
+        IntegerOp::Subtract => left - right,
+        IntegerOp::Multiply => left * right,
+        IntegerOp::Power => left.powf(right),
+    }
+}
+
+fn promoted_to_decimal(left: i64, op: IntegerOp, right: i64) -> Option<Decimal> {
+    let (left_decimal, right_decimal) = (Decimal::from(left), Decimal::from(right));
+    match op {
+        IntegerOp::Add => left_decimal.checked_add(right_decimal),
+        IntegerOp::Subtract => left_decimal.checked_sub(right_decimal),
+        IntegerOp::Multiply => left_decimal.checked_mul(right_decimal),
+        // Only reached for bases of magnitude 2 or more, which leave the decimal range
+        // within a hundred steps
+        IntegerOp::Power => {
+            (0..right).try_fold(Decimal::ONE, |power, _| power.checked_mul(left_decimal))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_results_in_range_stay_integers() {
+        for policy in [
+            OverflowPolicy::Error,
+            OverflowPolicy::Saturate,
+            OverflowPolicy::PromoteToFloat,
+            OverflowPolicy::PromoteToDecimal,
+        ] {
+            assert_eq!(
+                policy.apply(i64::MAX - 1, IntegerOp::Add, 1).unwrap(),
+                FactValue::Integer(i64::MAX)
+            );
+            assert_eq!(
+                policy.apply(-1, IntegerOp::Power, i64::MAX).unwrap(),
+                FactValue::Integer(-1)
+            );
+        }
+    }
+
+    #[test]
+    fn test_each_policy_resolves_overflow() {
+        let error = OverflowPolicy::Error.apply(i64::MAX, IntegerOp::Add, 1).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!("Integer overflow in {} + 1", i64::MAX)
+        );
+
+        let saturate = OverflowPolicy::Saturate;
+        assert_eq!(
+            saturate.apply(i64::MIN, IntegerOp::Subtract, 1).unwrap(),
+            FactValue::Integer(i64::MIN)
+        );
+        assert_eq!(
+            saturate.apply(-2, IntegerOp::Power, 63 + 2).unwrap(),
+            FactValue::Integer(i64::MIN)
+        );
+
+        assert_eq!(
+            OverflowPolicy::PromoteToFloat.apply(i64::MAX, IntegerOp::Multiply, 2).unwrap(),
+            FactValue::Float(2.0 * 9_223_372_036_854_775_807.0)
+        );
+
+        let decimal = OverflowPolicy::PromoteToDecimal;
+        assert_eq!(
+            decimal.apply(i64::MAX, IntegerOp::Add, 1).unwrap(),
+            FactValue::Decimal("9223372036854775808".parse().unwrap())
+        );
+        assert_eq!(
+            decimal.apply(10, IntegerOp::Power, 20).unwrap(),
+            FactValue::Decimal("100000000000000000000".parse().unwrap())
+        );
+        assert!(decimal.apply(i64::MAX, IntegerOp::Multiply, i64::MAX).is_err());
+    }
+
+    #[test]
+    fn test_negative_exponents_are_rejected() {
+        assert!(OverflowPolicy::PromoteToFloat.apply(2, IntegerOp::Power, -1).is_err());
+    }
+}
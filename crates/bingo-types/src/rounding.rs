@@ -0,0 +1,158 @@
+use anyhow::{Result, anyhow};
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// How a decimal value is rounded to a fixed number of places
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundingMode {
+    /// Round to the nearest value, ties to the even neighbour (banker's rounding)
+    #[default]
+    HalfEven,
+    /// Round to the nearest value, ties away from zero
+    HalfUp,
+    /// Round to the nearest value, ties towards zero
+    HalfDown,
+    /// Round away from zero
+    Up,
+    /// Round towards zero (truncate)
+    Down,
+    /// Round towards positive infinity
+    Ceiling,
+    /// Round towards negative infinity
+    Floor,
+}
+
+impl RoundingMode {
+    /// Every rounding mode, in declaration order
+    pub const ALL: [Self; 7] = [
+        Self::HalfEven,
+        Self::HalfUp,
+        Self::HalfDown,
+        Self::Up,
+        Self::Down,
+        Self::Ceiling,
+        Self::Floor,
+    ];
+
+    /// Name of the mode as used in rule definitions and calculator arguments
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::HalfEven => "half_even",
+            Self::HalfUp => "half_up",
+            Self::HalfDown => "half_down",
+            Self::Up => "up",
+            Self::Down => "down",
+            Self::Ceiling => "ceiling",
+            Self::Floor => "floor",
+        }
+    }
+
+    const fn strategy(self) -> RoundingStrategy {
+        match self {
+            Self::HalfEven => RoundingStrategy::MidpointNearestEven,
+            Self::HalfUp => RoundingStrategy::MidpointAwayFromZero,
+            Self::HalfDown => RoundingStrategy::MidpointTowardZero,
+            Self::Up => RoundingStrategy::AwayFromZero,
+            Self::Down => RoundingStrategy::ToZero,
+            Self::Ceiling => RoundingStrategy::ToPositiveInfinity,
+            Self::Floor => RoundingStrategy::ToNegativeInfinity,
+        }
+    }
+}
+
+impl fmt::Display for RoundingMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RoundingMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL.into_iter().find(|mode| mode.as_str() == s).ok_or_else(|| {
+            let names: Vec<&str> = Self::ALL.iter().map(|mode| mode.as_str()).collect();
+            anyhow!(
+                "Unknown rounding mode '{s}', expected one of: {}",
+                names.join(", ")
+            )
+        })
+    }
+}
+
+/// Number of decimal places and rounding mode applied to decimal results
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DecimalRounding {
+    /// Decimal places kept
+    pub scale: u32,
+    /// How the discarded places are rounded
+    pub mode: RoundingMode,
+}
+
+impl Default for DecimalRounding {
+    /// Cents with banker's rounding
+    fn default() -> Self {
+        Self { scale: 2, mode: RoundingMode::HalfEven }
+    }
+}
+
+impl DecimalRounding {
+    /// Rounding to `scale` places with the given mode
+    #[must_use]
+    pub const fn new(scale: u32, mode: RoundingMode) -> Self {
+        Self { scale, mode }
+    }
+
+    /// Round a decimal, keeping exactly `scale` places
+    #[must_use]
+    pub fn apply(self, value: Decimal) -> Decimal {
+        let mut rounded = value.round_dp_with_strategy(self.scale, self.mode.strategy());
+        // Pad so `1.5` at scale 2 reads `1.50`
+        rounded.rescale(self.scale);
+        rounded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round(value: &str, scale: u32, mode: RoundingMode) -> String {
+        DecimalRounding::new(scale, mode).apply(value.parse().unwrap()).to_string()
+    }
+
+    #[test]
+    fn test_rounding_modes() {
+        let cases = [
+            (RoundingMode::HalfEven, ["2.12", "2.12", "-2.12", "2.13"]),
+            (RoundingMode::HalfUp, ["2.13", "2.12", "-2.13", "2.13"]),
+            (RoundingMode::HalfDown, ["2.12", "2.12", "-2.12", "2.13"]),
+            (RoundingMode::Up, ["2.13", "2.13", "-2.13", "2.13"]),
+            (RoundingMode::Down, ["2.12", "2.12", "-2.12", "2.12"]),
+            (RoundingMode::Ceiling, ["2.13", "2.13", "-2.12", "2.13"]),
+            (RoundingMode::Floor, ["2.12", "2.12", "-2.13", "2.12"]),
+        ];
+        for (mode, expected) in cases {
+            let actual = ["2.125", "2.121", "-2.125", "2.126"].map(|value| round(value, 2, mode));
+            assert_eq!(actual, expected, "{mode}");
+        }
+    }
+
+    #[test]
+    fn test_rounding_pads_to_scale() {
+        assert_eq!(round("1.5", 2, RoundingMode::HalfEven), "1.50");
+        assert_eq!(round("7", 2, RoundingMode::HalfEven), "7.00");
+    }
+
+    #[test]
+    fn test_mode_names_round_trip() {
+        for mode in RoundingMode::ALL {
+            assert_eq!(mode.as_str().parse::<RoundingMode>().unwrap(), mode);
+        }
+        assert!("bankers".parse::<RoundingMode>().is_err());
+    }
+}
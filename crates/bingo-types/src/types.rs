@@ -1,5 +1,9 @@
+use crate::DecimalRounding;
+use crate::duration::{format_iso8601, parse_iso8601};
 use anyhow::{Result, anyhow};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::convert::TryFrom;
@@ -14,6 +18,9 @@ pub enum FactValue {
     Integer(i64),
     /// Floating point value
     Float(f64),
+    /// Exact base-10 value for money and other amounts that must not pick up binary
+    /// rounding errors
+    Decimal(Decimal),
     /// Boolean value
     Boolean(bool),
     /// Array of `FactValues`
@@ -22,6 +29,15 @@ pub enum FactValue {
     Object(HashMap<String, FactValue>),
     /// UTC date/time value
     Date(DateTime<Utc>),
+    /// Signed length of time, serialized as an ISO 8601 duration such as `PT30M`
+    Duration(#[serde(with = "crate::duration::serde_iso8601")] Duration),
+    /// Span of time from `start` up to, but not including, `end`
+    Interval {
+        /// First instant of the interval
+        start: DateTime<Utc>,
+        /// First instant after the interval
+        end: DateTime<Utc>,
+    },
     /// Null value
     Null,
 }
@@ -40,6 +56,8 @@ impl From<FactValue> for serde_json::Value {
             FactValue::String(s) => Self::String(s),
             FactValue::Integer(i) => Self::Number(serde_json::Number::from(i)),
             FactValue::Float(f) => serde_json::Number::from_f64(f).map_or(Self::Null, Self::Number),
+            // A JSON number would round-trip through `f64`, so the exact digits go in a string
+            FactValue::Decimal(d) => Self::String(d.to_string()),
             FactValue::Boolean(b) => Self::Bool(b),
             FactValue::Array(arr) => {
                 let vec: Vec<Self> = arr.into_iter().map(std::convert::Into::into).collect();
@@ -53,6 +71,10 @@ impl From<FactValue> for serde_json::Value {
                 Self::Object(json_map)
             }
             FactValue::Date(dt) => Self::String(dt.to_rfc3339()),
+            FactValue::Duration(d) => Self::String(format_iso8601(d)),
+            FactValue::Interval { start, end } => {
+                Self::String(format!("{}/{}", start.to_rfc3339(), end.to_rfc3339()))
+            }
             FactValue::Null => Self::Null,
         }
     }
@@ -66,6 +88,7 @@ impl From<&FactValue> for serde_json::Value {
             FactValue::Float(f) => {
                 serde_json::Number::from_f64(*f).map_or(Self::Null, Self::Number)
             }
+            FactValue::Decimal(d) => Self::String(d.to_string()),
             FactValue::Boolean(b) => Self::Bool(*b),
             FactValue::Array(arr) => {
                 let vec: Vec<Self> = arr.iter().map(std::convert::Into::into).collect();
@@ -79,6 +102,10 @@ impl From<&FactValue> for serde_json::Value {
                 Self::Object(json_map)
             }
             FactValue::Date(dt) => Self::String(dt.to_rfc3339()),
+            FactValue::Duration(d) => Self::String(format_iso8601(*d)),
+            FactValue::Interval { start, end } => {
+                Self::String(format!("{}/{}", start.to_rfc3339(), end.to_rfc3339()))
+            }
             FactValue::Null => Self::Null,
         }
     }
@@ -116,6 +143,39 @@ impl TryFrom<&serde_json::Value> for FactValue {
     }
 }
 
+// Conversions from plain Rust values, so literals can be passed where a `FactValue` is
+// expected (`with_field("tier", "premium")`).
+
+impl From<&str> for FactValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+impl From<String> for FactValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<i64> for FactValue {
+    fn from(value: i64) -> Self {
+        Self::Integer(value)
+    }
+}
+
+impl From<f64> for FactValue {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<bool> for FactValue {
+    fn from(value: bool) -> Self {
+        Self::Boolean(value)
+    }
+}
+
 impl std::hash::Hash for FactValue {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         match self {
@@ -156,6 +216,19 @@ impl std::hash::Hash for FactValue {
             Self::Null => {
                 7u8.hash(state);
             }
+            Self::Decimal(d) => {
+                8u8.hash(state);
+                d.normalize().hash(state); // `1.50` equals `1.5`, so they must hash alike
+            }
+            Self::Duration(d) => {
+                9u8.hash(state);
+                d.hash(state);
+            }
+            Self::Interval { start, end } => {
+                10u8.hash(state);
+                start.hash(state);
+                end.hash(state);
+            }
         }
     }
 }
@@ -164,19 +237,25 @@ impl Eq for FactValue {}
 
 impl PartialOrd for FactValue {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        use FactValue::{Boolean, Date, Float, Integer, Null, String};
+        use FactValue::{Boolean, Date, Decimal, Duration, Float, Integer, Null, String};
         match (self, other) {
             (String(a), String(b)) => a.partial_cmp(b),
             (Integer(a), Integer(b)) => a.partial_cmp(b),
             (Float(a), Float(b)) => a.partial_cmp(b),
+            (Decimal(a), Decimal(b)) => a.partial_cmp(b),
             (Boolean(a), Boolean(b)) => a.partial_cmp(b),
             (Date(a), Date(b)) => a.partial_cmp(b),
+            (Duration(a), Duration(b)) => a.partial_cmp(b),
             (Null, Null) => Some(std::cmp::Ordering::Equal),
             // Cross-type comparisons: convert to same type if possible
             #[allow(clippy::cast_precision_loss)]
             (Integer(a), Float(b)) => (*a as f64).partial_cmp(b),
             #[allow(clippy::cast_precision_loss)]
             (Float(a), Integer(b)) => a.partial_cmp(&(*b as f64)),
+            (Decimal(a), Integer(b)) => a.partial_cmp(&rust_decimal::Decimal::from(*b)),
+            (Integer(a), Decimal(b)) => rust_decimal::Decimal::from(*a).partial_cmp(b),
+            (Decimal(a), Float(b)) => a.to_f64().and_then(|a| a.partial_cmp(b)),
+            (Float(a), Decimal(b)) => b.to_f64().and_then(|b| a.partial_cmp(&b)),
             // For incompatible types, no ordering
             _ => None,
         }
@@ -189,6 +268,7 @@ impl fmt::Display for FactValue {
             Self::String(s) => write!(f, "{s}"),
             Self::Integer(i) => write!(f, "{i}"),
             Self::Float(fl) => write!(f, "{fl}"),
+            Self::Decimal(d) => write!(f, "{d}"),
             Self::Boolean(b) => write!(f, "{b}"),
             Self::Array(arr) => {
                 write!(f, "[")?;
@@ -213,6 +293,13 @@ impl fmt::Display for FactValue {
                 write!(f, "}}")
             }
             Self::Date(dt) => write!(f, "{}", dt.format("%Y-%m-%dT%H:%M:%S%.3fZ")),
+            Self::Duration(d) => write!(f, "{}", format_iso8601(*d)),
+            Self::Interval { start, end } => write!(
+                f,
+                "{}/{}",
+                start.format("%Y-%m-%dT%H:%M:%S%.3fZ"),
+                end.format("%Y-%m-%dT%H:%M:%S%.3fZ")
+            ),
             Self::Null => write!(f, "null"),
         }
     }
@@ -222,13 +309,20 @@ impl FactValue {
     /// Check if two `FactValues` are compatible for comparison
     #[must_use]
     pub const fn is_compatible_with(&self, other: &Self) -> bool {
-        use FactValue::{Array, Boolean, Date, Float, Integer, Null, Object, String};
+        use FactValue::{
+            Array, Boolean, Date, Decimal, Duration, Float, Integer, Interval, Null, Object, String,
+        };
         matches!(
             (self, other),
             (String(_), String(_))
-                | (Integer(_) | Float(_), Integer(_) | Float(_))
+                | (
+                    Integer(_) | Float(_) | Decimal(_),
+                    Integer(_) | Float(_) | Decimal(_)
+                )
                 | (Boolean(_), Boolean(_))
                 | (Date(_), Date(_))
+                | (Duration(_), Duration(_))
+                | (Interval { .. }, Interval { .. })
                 | (Array(_), Array(_))
                 | (Object(_), Object(_))
                 | (Null, _)
@@ -243,11 +337,13 @@ impl FactValue {
         match self {
             Self::Integer(i) => Some(*i as f64),
             Self::Float(f) => Some(*f),
+            Self::Decimal(d) => d.to_f64(),
             Self::Boolean(b) => Some(if *b { 1.0 } else { 0.0 }),
             Self::Date(dt) => Some(dt.timestamp() as f64),
+            Self::Duration(d) => Some(duration_seconds(*d)),
             Self::Array(arr) => Some(arr.len() as f64), // Length for comparison
             Self::Object(obj) => Some(obj.len() as f64), // Length for comparison
-            Self::String(_) | Self::Null => None,
+            Self::String(_) | Self::Interval { .. } | Self::Null => None,
         }
     }
 
@@ -258,10 +354,13 @@ impl FactValue {
             Self::Boolean(b) => *b,
             Self::Integer(i) => *i != 0,
             Self::Float(f) => *f != 0.0,
+            Self::Decimal(d) => !d.is_zero(),
             Self::String(s) => !s.is_empty(),
             Self::Array(arr) => !arr.is_empty(),
             Self::Object(obj) => !obj.is_empty(),
             Self::Date(_) => true, // Dates are always truthy
+            Self::Duration(d) => !d.is_zero(),
+            Self::Interval { start, end } => start < end,
             Self::Null => false,
         }
     }
@@ -273,10 +372,13 @@ impl FactValue {
             Self::String(_) => "string",
             Self::Integer(_) => "integer",
             Self::Float(_) => "float",
+            Self::Decimal(_) => "decimal",
             Self::Boolean(_) => "boolean",
             Self::Array(_) => "array",
             Self::Object(_) => "object",
             Self::Date(_) => "date",
+            Self::Duration(_) => "duration",
+            Self::Interval { .. } => "interval",
             Self::Null => "null",
         }
     }
@@ -289,11 +391,14 @@ impl FactValue {
             Self::Integer(i) => Some(*i),
             #[allow(clippy::cast_possible_truncation)]
             Self::Float(f) => Some(*f as i64),
+            Self::Decimal(d) => d.trunc().to_i64(),
             Self::Boolean(b) => Some(i64::from(*b)),
             Self::String(s) => s.parse::<i64>().ok(),
             Self::Date(d) => Some(d.timestamp()),
+            Self::Duration(d) => Some(d.num_seconds()),
             Self::Array(arr) => Some(arr.len() as i64),
             Self::Object(obj) => Some(obj.len() as i64),
+            Self::Interval { .. } => None,
             Self::Null => Some(0),
         }
     }
@@ -305,11 +410,14 @@ impl FactValue {
         match self {
             Self::Float(f) => Some(*f),
             Self::Integer(i) => Some(*i as f64),
+            Self::Decimal(d) => d.to_f64(),
             Self::Boolean(b) => Some(if *b { 1.0 } else { 0.0 }),
             Self::String(s) => s.parse::<f64>().ok(),
             Self::Date(d) => Some(d.timestamp() as f64),
+            Self::Duration(d) => Some(duration_seconds(*d)),
             Self::Array(arr) => Some(arr.len() as f64),
             Self::Object(obj) => Some(obj.len() as f64),
+            Self::Interval { .. } => None,
             Self::Null => Some(0.0),
         }
     }
@@ -358,6 +466,22 @@ impl FactValue {
         ))
     }
 
+    /// Create duration from an ISO 8601 string such as `PT30M` or `P1DT12H`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string is not a duration in days, hours, minutes and
+    /// seconds. Years and months are rejected as they have no fixed length.
+    pub fn duration_from_iso(iso_string: &str) -> Result<Self> {
+        parse_iso8601(iso_string).map(Self::Duration)
+    }
+
+    /// Create interval between two instants, in whichever order they are given
+    #[must_use]
+    pub fn interval(start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        Self::Interval { start: start.min(end), end: start.max(end) }
+    }
+
     /// Create null value
     #[must_use]
     pub const fn null() -> Self {
@@ -365,14 +489,103 @@ impl FactValue {
     }
 
     /// Convenience accessor returning an `f64` representation if this value is numeric.
-    /// Returns `None` when the variant is not `Integer` or `Float`.
+    /// Returns `None` when the variant is not `Integer`, `Float` or `Decimal`.
     #[must_use]
     #[allow(clippy::cast_precision_loss)]
-    pub const fn as_f64(&self) -> Option<f64> {
+    pub fn as_f64(&self) -> Option<f64> {
         match self {
             Self::Integer(i) => Some(*i as f64),
             Self::Float(f) => Some(*f),
+            Self::Decimal(d) => d.to_f64(),
             _ => None,
         }
     }
+
+    /// Exact decimal representation if this value is numeric or a numeric string
+    ///
+    /// Floats convert to the shortest decimal that reads back as the same float, so
+    /// `0.1` becomes `0.1` rather than its full binary expansion.
+    #[must_use]
+    pub fn as_decimal(&self) -> Option<Decimal> {
+        match self {
+            Self::Decimal(d) => Some(*d),
+            Self::Integer(i) => Some(Decimal::from(*i)),
+            Self::Float(f) => Decimal::try_from(*f).ok(),
+            Self::String(s) => s.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Whether this is a `Decimal` value
+    #[must_use]
+    pub const fn is_decimal(&self) -> bool {
+        matches!(self, Self::Decimal(_))
+    }
+
+    /// Round a `Decimal` value; every other value is returned unchanged
+    #[must_use]
+    pub fn round(&self, rounding: DecimalRounding) -> Self {
+        match self {
+            Self::Decimal(d) => Self::Decimal(rounding.apply(*d)),
+            other => other.clone(),
+        }
+    }
+
+    /// Duration held by a `Duration` value
+    #[must_use]
+    pub const fn as_duration(&self) -> Option<Duration> {
+        match self {
+            Self::Duration(d) => Some(*d),
+            _ => None,
+        }
+    }
+
+    /// Whether this value falls within `bound` as seen at `now`
+    ///
+    /// A date is within a duration when it is at most that far from `now`, in either
+    /// direction, and within an interval when it lies inside it. An interval is within
+    /// another interval it fits inside, and a duration is within a longer one.
+    #[must_use]
+    pub fn is_within(&self, bound: &Self, now: DateTime<Utc>) -> bool {
+        match (self, bound) {
+            (Self::Date(date), Self::Duration(limit)) => (now - *date).abs() <= *limit,
+            (Self::Date(date), Self::Interval { start, end }) => start <= date && date < end,
+            (
+                Self::Interval { start, end },
+                Self::Interval { start: outer_start, end: outer_end },
+            ) => outer_start <= start && end <= outer_end,
+            (Self::Duration(d), Self::Duration(limit)) => d.abs() <= *limit,
+            _ => false,
+        }
+    }
+
+    /// Whether this date, or the end of this interval, is more than `age` before `now`
+    #[must_use]
+    pub fn is_older_than(&self, age: &Self, now: DateTime<Utc>) -> bool {
+        let (Self::Date(instant) | Self::Interval { end: instant, .. }) = self else {
+            return false;
+        };
+        matches!(age, Self::Duration(age) if now - *instant > *age)
+    }
+
+    /// Whether this interval shares an instant with `other`, or contains `other` if
+    /// only one of them is an interval and the other a date
+    #[must_use]
+    pub fn overlaps(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                Self::Interval { start, end },
+                Self::Interval { start: other_start, end: other_end },
+            ) => start < other_end && other_start < end,
+            (Self::Interval { start, end }, Self::Date(date))
+            | (Self::Date(date), Self::Interval { start, end }) => start <= date && date < end,
+            _ => false,
+        }
+    }
+}
+
+/// Length of a duration in (fractional) seconds
+#[allow(clippy::cast_precision_loss)]
+fn duration_seconds(duration: Duration) -> f64 {
+    duration.num_seconds() as f64 + f64::from(duration.subsec_nanos()) / 1e9
 }
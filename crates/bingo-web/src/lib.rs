@@ -0,0 +1,304 @@
+//! Web dashboard for the Bingo rules engine
+//!
+//! Engines and sessions registered with a `Dashboard` are shown live: fact counts,
+//! rule hit counts, agenda sizes and memory pool statistics on the dashboard page,
+//! which refreshes itself periodically, and every compiled rule in the rules browser.
+//! The same data is served as JSON under `/api` for other tools.
+
+use askama::Template;
+use axum::extract::{Query, State};
+use axum::routing::get;
+use axum::{Json, Router};
+use bingo_core::types::PoolStats;
+use bingo_core::{BingoEngine, BingoSession, RuleMetadata};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+/// How often the dashboard page refreshes when not configured
+pub const DEFAULT_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Engine shown on the dashboard, with its agenda if it runs in a session
+#[derive(Debug, Clone)]
+enum Source {
+    Engine(Arc<BingoEngine>),
+    Session(Arc<BingoSession>),
+}
+
+impl Source {
+    fn engine(&self) -> &BingoEngine {
+        match self {
+            Source::Engine(engine) => engine,
+            Source::Session(session) => session.engine(),
+        }
+    }
+}
+
+/// Registry of the engines and sessions a dashboard displays
+#[derive(Debug)]
+pub struct Dashboard {
+    sources: RwLock<BTreeMap<String, Source>>,
+    refresh_interval: Duration,
+}
+
+impl Default for Dashboard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Dashboard {
+    pub fn new() -> Self {
+        Self { sources: RwLock::default(), refresh_interval: DEFAULT_REFRESH_INTERVAL }
+    }
+
+    /// Refresh the dashboard page every `interval`
+    pub fn with_refresh_interval(mut self, interval: Duration) -> Self {
+        self.refresh_interval = interval;
+        self
+    }
+
+    /// Show an engine under `name`, replacing anything registered with that name
+    pub fn register_engine(&self, name: impl Into<String>, engine: Arc<BingoEngine>) {
+        self.sources.write().unwrap().insert(name.into(), Source::Engine(engine));
+    }
+
+    /// Show a session and its agenda under `name`, replacing anything registered with
+    /// that name
+    pub fn register_session(&self, name: impl Into<String>, session: Arc<BingoSession>) {
+        self.sources.write().unwrap().insert(name.into(), Source::Session(session));
+    }
+
+    /// Stop showing `name`; returns whether it was registered
+    pub fn unregister(&self, name: &str) -> bool {
+        self.sources.write().unwrap().remove(name).is_some()
+    }
+
+    /// Current metrics of every registered engine, ordered by name
+    pub fn snapshot(&self) -> DashboardSnapshot {
+        let sessions: Vec<SessionSummary> = self
+            .sources
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(name, source)| SessionSummary::new(name, source))
+            .collect();
+        DashboardSnapshot {
+            total_facts: sessions.iter().map(|s| s.fact_count).sum(),
+            total_rules: sessions.iter().map(|s| s.rule_count).sum(),
+            total_rule_hits: sessions.iter().map(|s| s.rule_hits).sum(),
+            sessions,
+        }
+    }
+
+    /// Compiled rules of every registered engine, or only of `session`
+    pub fn rules(&self, session: Option<&str>) -> Vec<RuleSummary> {
+        let sources = self.sources.read().unwrap();
+        sources
+            .iter()
+            .filter(|(name, _)| session.is_none_or(|session| session == name.as_str()))
+            .flat_map(|(name, source)| RuleSummary::all(name, source.engine()))
+            .collect()
+    }
+}
+
+/// Metrics of every engine on the dashboard
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DashboardSnapshot {
+    pub sessions: Vec<SessionSummary>,
+    pub total_facts: usize,
+    pub total_rules: usize,
+    pub total_rule_hits: u64,
+}
+
+/// Metrics of one registered engine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionSummary {
+    pub name: String,
+    pub fact_count: usize,
+    pub rule_count: usize,
+    pub ruleset_version: u64,
+    /// Times any rule fired
+    pub rule_hits: u64,
+    /// Facts waiting in the session's agenda, `None` for engines outside a session
+    pub agenda_size: Option<usize>,
+    pub memory_usage_bytes: usize,
+    pub memory_pools: Vec<PoolSummary>,
+}
+
+impl SessionSummary {
+    fn new(name: &str, source: &Source) -> Self {
+        let engine = source.engine();
+        let stats = engine.get_memory_pool_stats();
+        let memory_pools = vec![
+            PoolSummary::new("rule execution results", &stats.rule_execution_result_pool),
+            PoolSummary::new("rule ID vectors", &stats.rule_id_vec_pool),
+            PoolSummary::new("fact ID vectors", &stats.fact_id_vec_pool),
+            PoolSummary::new("fact field maps", &stats.fact_field_map_pool),
+            PoolSummary::new("numeric vectors", &stats.numeric_vec_pool),
+        ];
+        Self {
+            name: name.to_string(),
+            fact_count: engine.fact_count(),
+            rule_count: engine.rule_count(),
+            ruleset_version: engine.ruleset_version(),
+            rule_hits: engine.rule_stats().iter().map(|rule| rule.activations).sum(),
+            agenda_size: match source {
+                Source::Engine(_) => None,
+                Source::Session(session) => Some(session.pending_count()),
+            },
+            memory_usage_bytes: stats.memory_usage_bytes,
+            memory_pools,
+        }
+    }
+}
+
+/// Usage of one object pool of an engine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolSummary {
+    pub name: String,
+    pub hits: usize,
+    pub misses: usize,
+    /// Hits as a percentage of all requests
+    pub hit_rate: f64,
+    pub pool_size: usize,
+    pub allocated: usize,
+}
+
+impl PoolSummary {
+    fn new(name: &str, stats: &PoolStats) -> Self {
+        Self {
+            name: name.to_string(),
+            hits: stats.hits,
+            misses: stats.misses,
+            hit_rate: stats.hit_rate(),
+            pool_size: stats.pool_size,
+            allocated: stats.allocated,
+        }
+    }
+}
+
+/// A compiled rule with its hit counts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleSummary {
+    pub session: String,
+    pub rule_id: u64,
+    pub name: String,
+    pub conditions: usize,
+    pub actions: usize,
+    pub salience: i32,
+    /// Facts the rule's conditions were evaluated against
+    pub evaluations: u64,
+    /// Times the rule fired
+    pub activations: u64,
+    /// When the rule last fired, in RFC 3339
+    pub last_fired: Option<String>,
+    /// Description, owner, link and validity period of the rule
+    pub metadata: RuleMetadata,
+    /// Whether the rule is between its effective and expiry dates
+    pub in_effect: bool,
+}
+
+impl RuleSummary {
+    fn all(session: &str, engine: &BingoEngine) -> Vec<Self> {
+        let stats: BTreeMap<u64, _> =
+            engine.rule_stats().into_iter().map(|stats| (stats.rule_id, stats)).collect();
+        let mut rules: Vec<Self> = engine
+            .rules()
+            .into_iter()
+            .map(|rule| {
+                let stats = stats.get(&rule.id);
+                Self {
+                    session: session.to_string(),
+                    rule_id: rule.id,
+                    salience: engine.rule_salience(rule.id),
+                    conditions: rule.conditions.len(),
+                    actions: rule.actions.len(),
+                    evaluations: stats.map_or(0, |s| s.evaluations),
+                    activations: stats.map_or(0, |s| s.activations),
+                    last_fired: stats.and_then(|s| s.last_fired).map(|at| at.to_rfc3339()),
+                    in_effect: rule.metadata.is_in_effect_now(),
+                    metadata: rule.metadata,
+                    name: rule.name,
+                }
+            })
+            .collect();
+        rules.sort_by_key(|rule| rule.rule_id);
+        rules
+    }
+
+    /// Effective and expiry dates for display
+    pub fn validity(&self) -> String {
+        let effective = self.metadata.effective_date.map(|date| date.format("%Y-%m-%d"));
+        let expiry = self.metadata.expiry_date.map(|date| date.format("%Y-%m-%d"));
+        match (effective, expiry) {
+            (None, None) => "always".to_string(),
+            (Some(effective), None) => format!("from {effective}"),
+            (None, Some(expiry)) => format!("until {expiry}"),
+            (Some(effective), Some(expiry)) => format!("{effective} to {expiry}"),
+        }
+    }
+}
+
+/// Router serving the dashboard, the rules browser and their JSON APIs
+pub fn app(dashboard: Arc<Dashboard>) -> Router {
+    Router::new()
+        .route("/", get(dashboard_page))
+        .route("/rules", get(rules_page))
+        .route("/api/dashboard", get(dashboard_json))
+        .route("/api/rules", get(rules_json))
+        .route("/health", get(|| async { "OK" }))
+        .with_state(dashboard)
+}
+
+#[derive(Template)]
+#[template(path = "dashboard.html")]
+struct DashboardTemplate {
+    snapshot: DashboardSnapshot,
+    refresh_ms: u128,
+}
+
+async fn dashboard_page(State(dashboard): State<Arc<Dashboard>>) -> DashboardTemplate {
+    DashboardTemplate {
+        snapshot: dashboard.snapshot(),
+        refresh_ms: dashboard.refresh_interval.as_millis(),
+    }
+}
+
+async fn dashboard_json(State(dashboard): State<Arc<Dashboard>>) -> Json<DashboardSnapshot> {
+    Json(dashboard.snapshot())
+}
+
+/// Query of the rules browser
+#[derive(Debug, Deserialize)]
+struct RulesQuery {
+    session: Option<String>,
+}
+
+#[derive(Template)]
+#[template(path = "rules.html")]
+struct RulesTemplate {
+    rules: Vec<RuleSummary>,
+    sessions: Vec<String>,
+    selected: String,
+}
+
+async fn rules_page(
+    State(dashboard): State<Arc<Dashboard>>,
+    Query(query): Query<RulesQuery>,
+) -> RulesTemplate {
+    let selected = query.session.filter(|session| !session.is_empty());
+    RulesTemplate {
+        rules: dashboard.rules(selected.as_deref()),
+        sessions: dashboard.sources.read().unwrap().keys().cloned().collect(),
+        selected: selected.unwrap_or_default(),
+    }
+}
+
+async fn rules_json(
+    State(dashboard): State<Arc<Dashboard>>,
+    Query(query): Query<RulesQuery>,
+) -> Json<Vec<RuleSummary>> {
+    Json(dashboard.rules(query.session.as_deref()))
+}
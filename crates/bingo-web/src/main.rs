@@ -1,43 +1,23 @@
-use askama::Template;
-use axum::{Router, routing::get};
+use bingo_core::{BingoEngine, BingoSession};
+use bingo_web::{Dashboard, app};
 use std::net::SocketAddr;
+use std::sync::Arc;
 
 #[tokio::main]
 async fn main() {
-    // build our application with a few routes
-    let app = Router::new()
-        .route("/", get(dashboard))
-        .route("/rules", get(rules))
-        .route("/health", get(|| async { "OK" }));
+    // Serve a session over an engine loaded with the rules file given as argument, if any
+    let engine = BingoEngine::new().expect("failed to create engine");
+    if let Some(path) = std::env::args().nth(1) {
+        let source = std::fs::read_to_string(&path).expect("failed to read rules file");
+        let count = engine.add_rules_from_dsl(&source).expect("failed to compile rules");
+        println!("loaded {count} rules from {path}");
+    }
+    let dashboard = Dashboard::new();
+    dashboard.register_session("default", Arc::new(BingoSession::new(Arc::new(engine))));
 
     // run it with hyper on localhost:3000
     let addr = SocketAddr::from(([127, 0, 0, 1], 3000));
     println!("listening on {addr}");
     let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
-    axum::serve(listener, app).await.unwrap();
-}
-
-struct Metrics {
-    total_facts: u64,
-    rules_loaded: usize,
-    processing_time: f64,
-}
-
-#[derive(Template)]
-#[template(path = "dashboard.html")]
-struct DashboardTemplate {
-    metrics: Metrics,
-}
-
-async fn dashboard() -> DashboardTemplate {
-    let metrics = Metrics { total_facts: 1_234_567, rules_loaded: 42, processing_time: 0.123 };
-    DashboardTemplate { metrics }
-}
-
-#[derive(Template)]
-#[template(path = "rules.html")]
-struct RulesTemplate;
-
-async fn rules() -> RulesTemplate {
-    RulesTemplate
+    axum::serve(listener, app(Arc::new(dashboard))).await.unwrap();
 }
@@ -0,0 +1,150 @@
+//! Dashboard Test
+//!
+//! Validates that the dashboard reports the live state of registered engines and
+//! sessions, fact counts, rule hits, agenda sizes and memory pools, and that the rules
+//! browser lists the compiled rules of every session or of one with their owners and
+//! validity periods.
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use bingo_core::{BingoEngine, BingoSession, Fact, FactData, FactValue, RuleMetadata};
+use bingo_web::{Dashboard, DashboardSnapshot, RuleSummary, app};
+use http_body_util::BodyExt;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+fn order(amount: i64) -> Fact {
+    let fields = HashMap::from([("amount".to_string(), FactValue::Integer(amount))]);
+    Fact::new(0, FactData { fields })
+}
+
+fn orders_engine() -> Arc<BingoEngine> {
+    let engine = BingoEngine::new().unwrap();
+    engine
+        .add_rules_from_dsl(
+            r#"
+            rule "Large order" id 1 when amount > 100 then set flagged = true
+            rule "Small order" id 2 when amount < 10 then set small = true
+            "#,
+        )
+        .unwrap();
+    Arc::new(engine)
+}
+
+async fn get(dashboard: &Arc<Dashboard>, uri: &str) -> (StatusCode, String) {
+    let response = app(Arc::clone(dashboard))
+        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn test_dashboard_reflects_live_engines() {
+    let dashboard = Arc::new(Dashboard::new());
+    let engine = orders_engine();
+    engine.process_facts(vec![order(250), order(500), order(50)]).unwrap();
+    dashboard.register_engine("batch", Arc::clone(&engine));
+
+    let session = Arc::new(BingoSession::new(orders_engine()));
+    session.insert(order(5));
+    session.insert(order(7));
+    dashboard.register_session("interactive", Arc::clone(&session));
+
+    let (status, body) = get(&dashboard, "/api/dashboard").await;
+    assert_eq!(status, StatusCode::OK);
+    let snapshot: DashboardSnapshot = serde_json::from_str(&body).unwrap();
+    let names: Vec<&str> = snapshot.sessions.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, ["batch", "interactive"]);
+    let batch = &snapshot.sessions[0];
+    assert_eq!(batch.fact_count, 3);
+    assert_eq!(batch.rule_count, 2);
+    assert_eq!(batch.rule_hits, 2);
+    assert_eq!(batch.agenda_size, None);
+    assert!(!batch.memory_pools.is_empty());
+    assert_eq!(snapshot.sessions[1].agenda_size, Some(2));
+    assert_eq!(snapshot.total_rules, 4);
+
+    // Firing the session shows up on the next refresh
+    session.fire_all_rules().unwrap();
+    let snapshot = dashboard.snapshot();
+    let interactive = &snapshot.sessions[1];
+    assert_eq!(interactive.agenda_size, Some(0));
+    assert_eq!(interactive.fact_count, 2);
+    assert_eq!(interactive.rule_hits, 2);
+    assert_eq!(snapshot.total_rule_hits, 4);
+
+    let (status, page) = get(&dashboard, "/").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(page.contains(r#"<a href="/rules?session=interactive">interactive</a>"#));
+    assert!(page.contains("rule execution results"));
+    assert!(page.contains("}, 5000);"));
+}
+
+#[tokio::test]
+async fn test_rules_browser_lists_compiled_rules() {
+    let dashboard = Arc::new(Dashboard::new());
+    let engine = orders_engine();
+    engine.process_facts(vec![order(250)]).unwrap();
+    dashboard.register_engine("batch", engine);
+    dashboard.register_engine("other", orders_engine());
+
+    let (_, body) = get(&dashboard, "/api/rules?session=batch").await;
+    let rules: Vec<RuleSummary> = serde_json::from_str(&body).unwrap();
+    assert_eq!(rules.len(), 2);
+    assert_eq!(
+        (rules[0].rule_id, rules[0].name.as_str()),
+        (1, "Large order")
+    );
+    assert_eq!(rules[0].activations, 1);
+    assert!(rules[0].last_fired.is_some());
+    assert_eq!(rules[1].activations, 0);
+    assert_eq!(rules[1].last_fired, None);
+    assert_eq!(dashboard.rules(None).len(), 4);
+
+    let (status, page) = get(&dashboard, "/rules?session=other").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(page.contains(r#"<option value="other" selected>other</option>"#));
+    assert!(page.contains("<td>Small order</td>"));
+    assert!(!page.contains("<td>batch</td>"));
+
+    assert!(dashboard.unregister("other"));
+    let (_, page) = get(&dashboard, "/rules?session=other").await;
+    assert!(page.contains("No compiled rules"));
+}
+
+#[tokio::test]
+async fn test_rules_browser_shows_rule_metadata() {
+    let dashboard = Arc::new(Dashboard::new());
+    let engine = orders_engine();
+    let mut rule = engine.rules().into_iter().find(|rule| rule.id == 2).unwrap();
+    rule.metadata = RuleMetadata {
+        description: Some("Small orders go to the bulk queue".to_string()),
+        owner: Some("fulfilment".to_string()),
+        link: Some("https://wiki.example.com/small-orders".to_string()),
+        effective_date: None,
+        expiry_date: Some("2024-01-01T00:00:00Z".parse().unwrap()),
+        ..Default::default()
+    };
+    engine.update_rule(rule).unwrap();
+    dashboard.register_engine("batch", engine);
+
+    let (_, body) = get(&dashboard, "/api/rules").await;
+    let rules: Vec<RuleSummary> = serde_json::from_str(&body).unwrap();
+    assert!(rules[0].in_effect);
+    assert_eq!(rules[0].metadata, RuleMetadata::default());
+    assert!(!rules[1].in_effect);
+    assert_eq!(rules[1].metadata.owner.as_deref(), Some("fulfilment"));
+
+    let (_, page) = get(&dashboard, "/rules").await;
+    assert!(
+        page.contains(r#"Small order <a href="https://wiki.example.com/small-orders">docs</a>"#)
+    );
+    assert!(page.contains("Small orders go to the bulk queue"));
+    assert!(page.contains("<td>fulfilment</td>"));
+    assert!(page.contains("<td>no (until 2024-01-01)</td>"));
+    assert!(page.contains("<td>yes (always)</td>"));
+}